@@ -1,7 +1,12 @@
 // アラート管理コマンド
 
 use crate::error::AppError;
-use crate::services::alerts::{get_alert_engine, Alert};
+use crate::events::{emit_event, TauriEvent};
+use crate::services::alerts::{get_alert_engine, suppress_alerts, Alert, MAX_MANUAL_SUPPRESSION_SECS};
+use crate::commands::utils::history_store;
+use crate::commands::validation::ValidationErrors;
+use crate::storage::metrics_history::{AlertCount, AlertHistoryEntry};
+use tauri::{AppHandle, Manager};
 
 /// アクティブなアラート一覧を取得
 #[tauri::command]
@@ -31,3 +36,101 @@ pub async fn clear_all_alerts() -> Result<(), AppError> {
         "アラートエンジンが初期化されていません",
     ))
 }
+
+/// 指定した秒数の間、メトリクスのアラートを手動で抑制
+///
+/// 負荷の急上昇が分かっている操作（重いシーンの読み込みなど）の前に使う。
+/// 発火済みのアラートはそのまま残り、抑制中は新たに発火・解消しない
+///
+/// # Arguments
+/// * `duration_secs` - 抑制する秒数（1〜3600）
+#[tauri::command]
+pub async fn manually_suppress_alerts(duration_secs: u64) -> Result<(), AppError> {
+    let mut errors = ValidationErrors::new();
+    if !(1..=MAX_MANUAL_SUPPRESSION_SECS).contains(&duration_secs) {
+        errors.add(
+            "durationSecs",
+            &format!("抑制する秒数は1〜{MAX_MANUAL_SUPPRESSION_SECS}秒の範囲で指定してください"),
+        );
+    }
+    errors.into_result()?;
+
+    if suppress_alerts(duration_secs).await {
+        Ok(())
+    } else {
+        Err(AppError::new(
+            "ALERT_ENGINE_NOT_INITIALIZED",
+            "アラートエンジンが初期化されていません",
+        ))
+    }
+}
+
+/// アラートを確認済みにする
+///
+/// # Arguments
+/// * `id` - 履歴ID、またはアラートID（未解消の発生をすべて確認済みにする）
+#[tauri::command]
+pub async fn acknowledge_alert(id: String) -> Result<(), AppError> {
+    let store = history_store().await?;
+    if store.acknowledge_alert(&id).await? {
+        Ok(())
+    } else {
+        Err(AppError::alert_not_found(&id))
+    }
+}
+
+/// 指定期間のアラート履歴を取得（新しい順）
+///
+/// # Arguments
+/// * `from` - 開始時刻（UNIX epoch秒）
+/// * `to` - 終了時刻（UNIX epoch秒）
+#[tauri::command]
+pub async fn get_alert_history(from: i64, to: i64) -> Result<Vec<AlertHistoryEntry>, AppError> {
+    if from > to {
+        return Err(AppError::validation_failed("開始時刻は終了時刻以前を指定してください"));
+    }
+    history_store().await?.get_alert_history(from, to).await
+}
+
+/// セッション中に発生したアラートの集計を取得
+///
+/// セッションレポートの「配信中にクリティカルなCPUアラート 3件」のような表示に使用する
+///
+/// # Arguments
+/// * `session_id` - セッションID
+#[tauri::command]
+pub async fn get_session_alert_counts(session_id: String) -> Result<Vec<AlertCount>, AppError> {
+    history_store().await?.count_session_alerts(&session_id).await
+}
+
+/// 通知アクションからメインウィンドウを前面に表示し、指定画面へ遷移
+///
+/// # Arguments
+/// * `route` - 遷移先の画面（例: "problems"）
+#[tauri::command]
+pub async fn focus_alert_route(app_handle: AppHandle, route: String) -> Result<(), AppError> {
+    let window = app_handle
+        .get_webview_window("main")
+        .ok_or_else(|| AppError::window_error("メインウィンドウが見つかりません"))?;
+
+    window.show()
+        .map_err(|e| AppError::window_error(&format!("ウィンドウの表示に失敗: {e}")))?;
+    window.set_focus()
+        .map_err(|e| AppError::window_error(&format!("ウィンドウのフォーカスに失敗: {e}")))?;
+
+    emit_event(&app_handle, TauriEvent::AppNavigate(route))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manually_suppress_alerts_rejects_out_of_range() {
+        for duration_secs in [0, MAX_MANUAL_SUPPRESSION_SECS + 1] {
+            let err = manually_suppress_alerts(duration_secs).await.unwrap_err();
+            assert_eq!(err.code(), "VALIDATION_FAILED");
+        }
+    }
+}
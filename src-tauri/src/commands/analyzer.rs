@@ -3,18 +3,45 @@
 // システムメトリクスとOBS統計を分析して問題を検出するTauriコマンド
 
 use crate::error::AppError;
-use crate::services::analyzer::{ProblemAnalyzer, ProblemReport};
+use crate::monitor::network::get_network_adapter_type;
+use crate::services::alerts::AlertSeverity;
+use crate::services::analyzer::{
+    headroom_samples, HeadroomEstimate, HeadroomThresholds, ProblemAnalyzer, ProblemReport,
+};
+use crate::services::performance_stats::measure_command;
+use crate::services::request_context::{in_request_span, RequestContext};
+use crate::services::obs_log::{analyze_obs_log_file, latest_log_file, obs_logs_dir, ObsLogAnalysis};
 use crate::services::system::system_monitor_service;
-use crate::services::optimizer::RecommendationEngine;
+use crate::services::optimizer::{GopViolation, HardwareInfo, RecommendationEngine, RecommendedMultitrack, ScoreBreakdown};
 use crate::services::gpu_detection::{MemoryTier, EffectiveTier, determine_cpu_tier, detect_gpu_generation, detect_gpu_grade, calculate_effective_tier};
 use crate::services::system_capability::SystemCapability;
 use crate::services::static_settings::StaticSettings;
 use crate::storage::metrics_history::SystemMetricsSnapshot;
 use crate::monitor::get_memory_info;
-use crate::obs::get_obs_settings;
-use crate::storage::config::{load_config, StreamingPlatform, StreamingStyle};
+use crate::monitor::audio_device::AudioDeviceLatencyWarning;
+use crate::monitor::display::DisplayInfo;
+use crate::monitor::gpu::get_gpu_info;
+use crate::monitor::power::{get_power_status, PowerSource};
+use crate::services::obs::{get_stream_destination, obs_service};
+use crate::obs::{get_obs_client, get_obs_settings, get_output_mode, recent_output_bitrates, ObsOutputMode, ObsSettings, OutputSettings};
+use crate::services::aspect_ratio::AspectHandling;
+use crate::services::audio_audit::{collect_audio_audit_inputs, find_audio_issues};
+use crate::services::audio_devices::collect_microphone_device_names;
+use crate::services::audio_monitor::{collect_audio_inputs, AudioInputStatus};
+use crate::services::dual_pc::{collect_capture_sources, is_likely_dual_pc_streaming};
+use crate::services::monitor_capture::{advise_monitor_capture, MonitorCaptureAdvice};
+use crate::services::monitoring_offset::{get_monitoring_device, needs_offset_calibration, read_monitoring_offset};
+use crate::services::source_frame_rate::{collect_video_sources, detect_frame_rate_mismatch, dominant_role, FrameRateMismatch, VideoSourceInfo, VideoSourceRole};
+use crate::services::scene_checklist::{analyze_scene_checklist, collect_scene_composition, find_checklist_rule};
+use crate::services::game_detector::get_detected_games;
+use crate::storage::config::{load_config, save_config, AppConfig, AudioMonitoringConfig, SetupType, StreamingPlatform, StreamingProtocol, StreamingStyle};
+use crate::commands::config::CONFIG_SAVE_LOCK;
 use crate::commands::utils::get_hardware_info;
+use crate::commands::validation::validate_recommendation_input;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::PoisonError;
 
 /// 問題分析リクエスト
 #[derive(Debug, Clone, Deserialize)]
@@ -34,14 +61,16 @@ pub struct AnalyzeProblemsResponse {
     pub problems: Vec<ProblemReport>,
     /// 総合評価スコア（0-100）
     pub overall_score: f64,
+    /// 直近のサンプルから推定したヘッドルーム
+    pub headroom: HeadroomEstimate,
 }
 
 /// OBS設定分析結果（analyze_settings用）
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AnalysisResult {
-    /// 品質スコア（0-100）
-    pub quality_score: u8,
+    /// 品質スコア（項目別の内訳と合計0-100）
+    pub quality_score: ScoreBreakdown,
     /// 検出された問題の数
     pub issue_count: usize,
     /// 推奨設定変更リスト
@@ -58,6 +87,11 @@ pub struct AnalysisResult {
     /// スペック非依存の静的設定
     #[serde(skip_serializing_if = "Option::is_none")]
     pub static_settings: Option<StaticSettings>,
+    /// マルチトラック配信の推奨（対応プラットフォームのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multitrack: Option<RecommendedMultitrack>,
+    /// リクエストID（ログとの照合用。不具合報告に添付する）
+    pub request_id: Option<uuid::Uuid>,
 }
 
 /// 分析サマリー（初心者向け）
@@ -122,10 +156,18 @@ pub struct SystemInfo {
     pub cpu_model: String,
     /// GPUモデル名
     pub gpu_model: Option<String>,
+    /// GPUドライバーバージョン
+    pub gpu_driver_version: Option<String>,
     /// 総メモリ容量（MB）
     pub total_memory_mb: u64,
     /// 利用可能メモリ（MB）
     pub available_memory_mb: u64,
+    /// 電源の種類
+    pub power_source: PowerSource,
+    /// バッテリー残量（%）
+    pub battery_percent: Option<u8>,
+    /// 接続されているモニター（検出できない場合は空）
+    pub monitors: Vec<DisplayInfo>,
 }
 
 /// 現在の問題を分析
@@ -139,18 +181,35 @@ pub struct SystemInfo {
 /// 検出された問題のリスト
 #[tauri::command]
 pub async fn analyze_problems(request: AnalyzeProblemsRequest) -> Result<AnalyzeProblemsResponse, AppError> {
-    let analyzer = ProblemAnalyzer::new();
+    let ctx = RequestContext::new();
+    in_request_span(
+        crate::request_span!("analyze_problems", ctx),
+        measure_command("analyze_problems", run_analyze_problems(request)),
+    )
+    .await
+}
+
+/// 問題分析の本体
+async fn run_analyze_problems(request: AnalyzeProblemsRequest) -> Result<AnalyzeProblemsResponse, AppError> {
     let service = system_monitor_service();
 
     // 現在のシステムメトリクスを取得
     let cpu_usage = service.get_cpu_usage()?;
+    let cpu_frequency_mhz = service.get_cpu_frequency_mhz()?;
     let (memory_used, memory_total) = service.get_memory_info()?;
     let gpu_metrics = service.get_gpu_metrics()?;
     let network_metrics = service.get_network_metrics()?;
 
+    // Wi-Fi接続かどうか、配信専用PCかどうかで対処方法を出し分ける
+    let setup_type = load_config().map(|config| config.streaming_mode.setup_type).unwrap_or_default();
+    let analyzer = ProblemAnalyzer::new()
+        .with_network_adapter(get_network_adapter_type().await)
+        .with_setup_type(setup_type);
+
     // スナップショットを作成
     let current_snapshot = SystemMetricsSnapshot::from_metrics(
         cpu_usage,
+        cpu_frequency_mhz,
         memory_used,
         memory_total,
         gpu_metrics.as_ref(),
@@ -160,26 +219,167 @@ pub async fn analyze_problems(request: AnalyzeProblemsRequest) -> Result<Analyze
     // 履歴データ（現在は単一スナップショット）
     let metrics_history = vec![current_snapshot];
 
-    // ビットレート履歴（ダミーデータ - 将来的には実データを使用）
-    let bitrate_history: Vec<u64> = vec![request.target_bitrate];
+    // ビットレート履歴（配信中はOBSから取得した実測値、それ以外は目標ビットレート）
+    let bitrate_history = bitrate_history_or_target(recent_output_bitrates().await, request.target_bitrate);
 
     // 総合分析を実行
-    let problems = analyzer.analyze_comprehensive(
+    let mut problems = analyzer.analyze_comprehensive(
         &metrics_history,
         &bitrate_history,
         request.target_bitrate,
         &request.encoder_type,
     );
 
+    // 配信前チェック: OBSの配信先（未接続の場合はスキップ）
+    if let (Ok(destination), Ok(config)) = (get_stream_destination().await, load_config()) {
+        for problem in analyzer.analyze_stream_destination(&destination, config.streaming_mode.platform) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
+    // 配信前チェック: 配信開始時に表示が必要なシーンアイテム（取得できないシーンはスキップ）
+    let required_scene_items = load_config().map(|config| config.required_scene_items).unwrap_or_default();
+    if !required_scene_items.is_empty() {
+        let mut scene_items = HashMap::new();
+        for required in &required_scene_items {
+            if scene_items.contains_key(&required.scene_name) {
+                continue;
+            }
+            if let Ok(items) = obs_service().list_scene_items(&required.scene_name).await {
+                scene_items.insert(required.scene_name.clone(), items);
+            }
+        }
+        for problem in analyzer.analyze_required_scene_items(&required_scene_items, &scene_items) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
+    // 配信前チェック: 配信スタイルに必要なソース（未接続の場合はスキップ）
+    if let (Ok(composition), Ok(config)) = (collect_scene_composition(&get_obs_client()).await, load_config()) {
+        let style = config.streaming_mode.style;
+        for problem in analyze_scene_checklist(style, &composition, &config.dismissed_checklist_rules) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
+    // 配信前チェック: マイク・デスクトップ音声が配信に乗るか（未接続の場合はスキップ）
+    if let Ok((inputs, scene_sources)) = collect_audio_audit_inputs(&get_obs_client()).await {
+        for problem in analyzer.analyze_audio_issues(&find_audio_issues(&inputs, &scene_sources)) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
+    // 配信前チェック: ビデオ通話（仮想カメラ）中のモニタリング遅延補正
+    if let (Ok(video_call_active), Ok(config)) = (obs_service().get_virtual_cam_status().await, load_config()) {
+        let monitoring = AudioMonitoringConfig {
+            monitoring_offset_ms: read_monitoring_offset(&get_obs_client())
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(config.audio_monitoring.monitoring_offset_ms),
+            ..config.audio_monitoring
+        };
+        for problem in analyzer.analyze_monitoring_offset(&monitoring, video_call_active) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
+    // 配信前チェック: バッテリー残量
+    for problem in analyzer.analyze_power_source(&get_power_status().await) {
+        insert_by_severity(&mut problems, problem);
+    }
+
+    // 配信前チェック: ゲーム配信時のOBSプロセス優先度
+    let is_gaming = load_config().is_ok_and(|config| config.streaming_mode.style == StreamingStyle::Gaming);
+    if is_gaming {
+        let priority = service.get_obs_process_info().ok().and_then(|info| info.priority);
+        // 重要度順を維持するため、Info/Tipsより前に挿入
+        for problem in analyzer.analyze_process_priority(priority, is_gaming) {
+            let position = problems
+                .iter()
+                .position(|p| matches!(p.severity, AlertSeverity::Info | AlertSeverity::Tips))
+                .unwrap_or(problems.len());
+            problems.insert(position, problem);
+        }
+    }
+
+    // 配信前チェック: GPUドライバーのバージョン（Info扱いのためTipsより前に挿入）
+    if let Some(gpu) = get_gpu_info().await {
+        for problem in analyzer.analyze_gpu_driver(&gpu.name, gpu.driver_version.as_deref()) {
+            let position = problems
+                .iter()
+                .position(|p| matches!(p.severity, AlertSeverity::Tips))
+                .unwrap_or(problems.len());
+            problems.insert(position, problem);
+        }
+    }
+
+    // 配信前チェック: オーバーレイ・フックを行う競合ソフトウェア
+    if let Ok(conflicts) = service.get_software_conflicts() {
+        for problem in analyzer.analyze_software_conflicts(&conflicts) {
+            insert_by_severity(&mut problems, problem);
+        }
+    }
+
     // スコアを計算（問題の数と重要度から）
     let overall_score = calculate_overall_score(&problems);
 
+    // ヘッドルームに基づく品質向上の提案（問題ではないためスコアには含めない）
+    let headroom = analyzer.estimate_headroom(&headroom_samples(), &load_headroom_thresholds());
+    problems.extend(analyzer.analyze_headroom(&headroom, &request.encoder_type));
+
     Ok(AnalyzeProblemsResponse {
         problems,
         overall_score,
+        headroom,
     })
 }
 
+/// シーン構成チェックのルールを非表示・再表示
+///
+/// 非表示にしたルールIDは設定ファイルに保存され、以降の `analyze_problems` で報告されなくなる
+///
+/// # Arguments
+/// * `rule_id` - ルールID（シーン構成チェックの問題の `id`）
+/// * `dismissed` - trueで非表示、falseで再表示
+///
+/// # Returns
+/// 保存後の設定
+#[tauri::command]
+pub async fn set_checklist_rule_dismissed(rule_id: String, dismissed: bool) -> Result<AppConfig, AppError> {
+    if find_checklist_rule(&rule_id).is_none() {
+        return Err(AppError::validation_failed(&format!("不明なチェックルールです: {rule_id}")));
+    }
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.dismissed_checklist_rules.retain(|id| *id != rule_id);
+    if dismissed {
+        config.dismissed_checklist_rules.push(rule_id);
+    }
+    save_config(&config)?;
+    Ok(config)
+}
+
+/// システムがあとどれだけ負荷に耐えられるかを推定
+///
+/// 直近5分間の監視サンプルの95パーセンタイルを、アラート設定の閾値
+/// （アップロードは回線速度の80%）から引いた値を返す
+///
+/// # Returns
+/// リソースごとのヘッドルーム（サンプル不足・取得できないリソースは `null`）
+#[tauri::command]
+pub async fn get_headroom() -> Result<HeadroomEstimate, AppError> {
+    let analyzer = ProblemAnalyzer::new();
+    Ok(analyzer.estimate_headroom(&headroom_samples(), &load_headroom_thresholds()))
+}
+
+/// 設定ファイルからヘッドルームの上限を決定（読み込めない場合は既定値）
+fn load_headroom_thresholds() -> HeadroomThresholds {
+    let config = load_config().unwrap_or_default();
+    HeadroomThresholds::from_config(&config.alerts, config.streaming_mode.network_speed_mbps)
+}
+
 /// OBS設定を分析して推奨事項を返す
 ///
 /// # Arguments
@@ -191,6 +391,19 @@ pub async fn analyze_problems(request: AnalyzeProblemsRequest) -> Result<Analyze
 pub async fn analyze_settings(
     request: Option<AnalyzeSettingsRequest>,
 ) -> Result<AnalysisResult, AppError> {
+    let ctx = RequestContext::new();
+    let mut result = in_request_span(crate::request_span!("analyze_settings", ctx), run_analyze_settings(request)).await?;
+    result.request_id = Some(ctx.request_id);
+    Ok(result)
+}
+
+/// 設定分析の本体
+async fn run_analyze_settings(request: Option<AnalyzeSettingsRequest>) -> Result<AnalysisResult, AppError> {
+    // リクエストで指定された値を検証
+    if let Some(network_speed) = request.as_ref().and_then(|r| r.network_speed_mbps) {
+        validate_recommendation_input(network_speed, None)?;
+    }
+
     // 現在のOBS設定を取得
     let obs_settings = get_obs_settings().await?;
 
@@ -210,15 +423,154 @@ pub async fn analyze_settings(
     let network_speed = request.as_ref()
         .and_then(|r| r.network_speed_mbps)
         .unwrap_or(app_config.streaming_mode.network_speed_mbps);
+    tracing::info!(target: "analyzer", ?platform, ?style, network_speed, "分析条件を決定");
+
+    // 音声モニタリングの推奨（歌・演奏配信のみ、取得できない場合は省略）
+    let audio_inputs = if RecommendationEngine::recommended_audio_monitor_type(style).is_some() {
+        collect_audio_inputs(&get_obs_client()).await.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // マイクのデバイス名（Bluetooth・USB接続の警告用、取得できない場合は省略）
+    let microphone_devices = collect_microphone_device_names(&get_obs_client())
+        .await
+        .unwrap_or_default();
+
+    // 音声モニタリングの遅延補正（OBSのプロファイルに保存済みの値を優先、取得できない場合は省略）
+    let audio_monitoring = AudioMonitoringConfig {
+        monitoring_offset_ms: read_monitoring_offset(&get_obs_client())
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(app_config.audio_monitoring.monitoring_offset_ms),
+        ..app_config.audio_monitoring
+    };
+    let monitoring_device = if audio_monitoring.enabled {
+        get_monitoring_device(&get_obs_client()).await.ok().flatten()
+    } else {
+        None
+    };
+
+    let (memory_used_bytes, memory_total_bytes) = get_memory_info().unwrap_or((0, 8_000_000_000));
+
+    // 出力モード（取得できない場合は推奨を省略）
+    let current_output_mode = get_output_mode(&get_obs_client()).await.ok();
+
+    // 2台構成の検出（1台構成の設定時のみ、ソース・プロセスを取得できない場合は判定しない）
+    let setup_type = app_config.streaming_mode.setup_type;
+    let dual_pc_detected = setup_type == SetupType::SinglePc
+        && match (collect_capture_sources(&get_obs_client()).await, get_detected_games()) {
+            (Ok(capture_sources), Ok(running_games)) => {
+                is_likely_dual_pc_streaming(&capture_sources, &running_games)
+            }
+            _ => false,
+        };
+
+    // 映像ソースのFPS・解像度（カメラが主要な映像ソースとなる配信スタイルのみ、取得できない場合は省略）
+    let video_sources = if dominant_role(style) == Some(VideoSourceRole::Camera) {
+        collect_video_sources(&get_obs_client()).await.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    Ok(build_analysis_result(&AnalysisInput {
+        obs_settings,
+        hardware_info,
+        platform,
+        style,
+        network_speed_mbps: network_speed,
+        current_protocol: app_config.streaming_mode.protocol,
+        current_output_mode,
+        audio_inputs,
+        microphone_devices,
+        audio_monitoring,
+        monitoring_device,
+        setup_type,
+        dual_pc_detected,
+        video_sources,
+        memory_used_bytes,
+        memory_total_bytes,
+        analyzed_at: chrono::Utc::now().timestamp(),
+    }))
+}
+
+/// 設定分析の入力（OBS・システムから取得済みの値）
+#[derive(Debug, Clone)]
+pub struct AnalysisInput {
+    /// 現在のOBS設定
+    pub obs_settings: ObsSettings,
+    /// ハードウェア情報
+    pub hardware_info: HardwareInfo,
+    /// 配信プラットフォーム
+    pub platform: StreamingPlatform,
+    /// 配信スタイル
+    pub style: StreamingStyle,
+    /// ネットワーク速度（Mbps）
+    pub network_speed_mbps: f64,
+    /// 現在の配信プロトコル
+    pub current_protocol: StreamingProtocol,
+    /// 現在のOBS出力モード（取得していない場合は`None`）
+    pub current_output_mode: Option<ObsOutputMode>,
+    /// 音声入力の状態（取得していない場合は空）
+    pub audio_inputs: Vec<AudioInputStatus>,
+    /// マイク入力で使用しているデバイスの表示名（取得していない場合は空）
+    pub microphone_devices: Vec<String>,
+    /// 音声モニタリング設定
+    pub audio_monitoring: AudioMonitoringConfig,
+    /// OBSのモニタリングデバイス名（取得していない場合は`None`）
+    pub monitoring_device: Option<String>,
+    /// 配信環境の構成
+    pub setup_type: SetupType,
+    /// 2台構成の配信用PCと推定されるか（キャプチャーボード・NDIの映像ソースがあり、ゲームが動作していない）
+    pub dual_pc_detected: bool,
+    /// 映像ソースのFPS・解像度（取得していない場合は空）
+    pub video_sources: Vec<VideoSourceInfo>,
+    /// 使用中メモリ（バイト）
+    pub memory_used_bytes: u64,
+    /// 総メモリ（バイト）
+    pub memory_total_bytes: u64,
+    /// 分析日時（Unixタイムスタンプ）
+    pub analyzed_at: i64,
+}
+
+/// 取得済みの値から設定分析結果を構築
+///
+/// OBS・システムへのアクセスは行わないため、同じ入力に対して常に同じ結果を返す
+pub fn build_analysis_result(input: &AnalysisInput) -> AnalysisResult {
+    let AnalysisInput {
+        obs_settings,
+        hardware_info,
+        platform,
+        style,
+        network_speed_mbps: network_speed,
+        current_protocol,
+        current_output_mode,
+        audio_inputs,
+        microphone_devices,
+        audio_monitoring,
+        monitoring_device,
+        setup_type,
+        dual_pc_detected,
+        video_sources,
+        memory_used_bytes: memory_used,
+        memory_total_bytes: memory_total,
+        analyzed_at,
+    } = input;
+    let (platform, style, network_speed) = (*platform, *style, *network_speed);
 
     // 推奨設定を計算
-    let recommendations = RecommendationEngine::calculate_recommendations(
-        &hardware_info,
-        &obs_settings,
+    let mut recommendations = RecommendationEngine::calculate_recommendations(
+        hardware_info,
+        Some(obs_settings),
         platform,
         style,
         network_speed,
     );
+    let mut encoder_context =
+        RecommendationEngine::encoder_selection_context(hardware_info, platform, style, network_speed);
+    encoder_context.setup_type = *setup_type;
+    RecommendationEngine::apply_setup_type(&mut recommendations, &encoder_context);
 
     // 推奨事項リストを構築
     let mut recommendation_list = Vec::new();
@@ -244,6 +596,52 @@ pub async fn analyze_settings(
         });
     }
 
+    // キャンバス（基本）解像度の推奨（現在の基本解像度より高い場合のみ）
+    if let (Some(canvas_width), Some(canvas_height), Some(display)) = (
+        recommendations.video.canvas_width,
+        recommendations.video.canvas_height,
+        hardware_info.primary_display(),
+    ) {
+        let current_pixels =
+            u64::from(obs_settings.video.base_width) * u64::from(obs_settings.video.base_height);
+        if u64::from(canvas_width) * u64::from(canvas_height) > current_pixels {
+            recommendation_list.push(ObsSetting {
+                key: "video.baseResolution".to_string(),
+                display_name: "基本（キャンバス）解像度".to_string(),
+                current_value: serde_json::json!(format!(
+                    "{}x{}",
+                    obs_settings.video.base_width,
+                    obs_settings.video.base_height
+                )),
+                recommended_value: serde_json::json!(format!("{canvas_width}x{canvas_height}")),
+                reason: format!(
+                    "メインモニター（{}x{}・{}Hz）に対してキャンバスが小さく、画面キャプチャが縮小されて文字がぼやけます",
+                    display.width, display.height, display.refresh_rate_hz
+                ),
+                priority: "recommended".to_string(),
+            });
+        }
+    }
+
+    // キャンバスが16:9でない場合のアスペクト比の扱い
+    if let Some(aspect) = &recommendations.video.aspect {
+        recommendation_list.push(ObsSetting {
+            key: "video.aspectRatio".to_string(),
+            display_name: "アスペクト比".to_string(),
+            current_value: serde_json::json!(format!("{}x{}", aspect.canvas_width, aspect.canvas_height)),
+            recommended_value: serde_json::json!(aspect.handling),
+            reason: aspect.description(),
+            priority: if aspect.handling == AspectHandling::Native { "optional" } else { "recommended" }.to_string(),
+        });
+    }
+
+    // マルチモニター環境の画面キャプチャの推奨
+    recommendation_list.extend(monitor_capture_recommendations(
+        obs_settings,
+        &hardware_info.displays,
+        &recommendation_list,
+    ));
+
     // FPSの推奨
     let current_fps = obs_settings.video.fps() as u32;
     if current_fps != recommendations.video.fps {
@@ -257,6 +655,16 @@ pub async fn analyze_settings(
         });
     }
 
+    // 主要な映像ソース（カメラ）が出力の半分のFPSで動作している場合の推奨
+    if let Some(mismatch) = detect_frame_rate_mismatch(
+        style,
+        obs_settings.video.fps(),
+        (obs_settings.video.output_width, obs_settings.video.output_height),
+        video_sources,
+    ) {
+        add_source_frame_rate_recommendation(&mut recommendation_list, current_fps, &mismatch);
+    }
+
     // ビットレートの推奨
     let bitrate_diff = (obs_settings.output.bitrate_kbps as i32
         - recommendations.output.bitrate_kbps as i32).abs();
@@ -292,23 +700,140 @@ pub async fn analyze_settings(
         });
     }
 
+    // 出力モードの推奨（基本モードでは推奨エンコーダー設定を適用できないため必須）
+    if *current_output_mode == Some(ObsOutputMode::Simple)
+        && recommendations.output.output_mode == ObsOutputMode::Advanced
+    {
+        recommendation_list.push(ObsSetting {
+            key: "output.mode".to_string(),
+            display_name: "出力モード".to_string(),
+            current_value: serde_json::json!(ObsOutputMode::Simple),
+            recommended_value: serde_json::json!(ObsOutputMode::Advanced),
+            reason: "推奨エンコーダー設定（Bフレーム・マルチパス・Look-ahead）は基本モードでは設定できないため、詳細モードに切り替えてください"
+                .to_string(),
+            priority: "critical".to_string(),
+        });
+    }
+
+    // キーフレーム間隔・Bフレーム数の推奨（配信先の要件と異なる場合）
+    recommendation_list.extend(gop_recommendations(platform, &obs_settings.output));
+
+    // 配信プロトコルの推奨（対応サーバーが必要なため任意）
+    if *current_protocol != recommendations.output.protocol {
+        recommendation_list.push(ObsSetting {
+            key: "output.protocol".to_string(),
+            display_name: "配信プロトコル".to_string(),
+            current_value: serde_json::json!(current_protocol),
+            recommended_value: serde_json::json!(recommendations.output.protocol),
+            reason: if recommendations.output.protocol == StreamingProtocol::Srt {
+                "配信先サーバーが対応している場合、SRTで超低遅延配信が可能です"
+            } else {
+                "この配信先ではTLS暗号化されたRTMPSが推奨されます"
+            }
+            .to_string(),
+            priority: "optional".to_string(),
+        });
+    }
+
+    // 録画フォーマットの推奨（MP4・MOVはクラッシュ時に録画全体が失われるため必須）
+    if obs_settings.output.has_crash_unsafe_recording_format() {
+        recommendation_list.push(ObsSetting {
+            key: "output.recordingFormat".to_string(),
+            display_name: "録画フォーマット".to_string(),
+            current_value: serde_json::json!(obs_settings.output.recording_format),
+            recommended_value: serde_json::json!("mkv"),
+            reason: "MP4・MOVで録画するとOBSやPCが異常終了した際に録画ファイル全体が再生できなくなります。MKVで録画し、「録画後に自動でMP4にリマックスする」を有効にしてください"
+                .to_string(),
+            priority: "critical".to_string(),
+        });
+    }
+
+    // 音声モニタリングの推奨（歌・演奏配信のみ）
+    for (input, recommended) in RecommendationEngine::audio_monitor_changes(style, audio_inputs) {
+        recommendation_list.push(ObsSetting {
+            key: format!("audio.monitorType.{}", input.input_name),
+            display_name: format!("音声モニタリング（{}）", input.input_name),
+            current_value: serde_json::json!(input.monitor_type),
+            recommended_value: serde_json::json!(recommended),
+            reason: "歌・演奏配信では「モニターと出力」にすると、ヘッドホンで配信のミックスを確認できます"
+                .to_string(),
+            priority: "optional".to_string(),
+        });
+    }
+
+    // マイクの接続方式による遅延・サンプルレートの警告
+    for device in microphone_devices {
+        match AudioDeviceLatencyWarning::from_device_name(device) {
+            Some(AudioDeviceLatencyWarning::Bluetooth) => recommendation_list.push(ObsSetting {
+                key: format!("audio.deviceLatency.{device}"),
+                display_name: format!("マイクの接続方式（{device}）"),
+                current_value: serde_json::json!(device),
+                recommended_value: serde_json::json!("有線マイク（USB・3.5mm）"),
+                reason: "Bluetoothマイクは100〜300ms程度の遅延があり、配信で声と映像がずれる原因になります。有線のUSBマイクまたは3.5mm接続のマイクを使用してください"
+                    .to_string(),
+                priority: "critical".to_string(),
+            }),
+            Some(AudioDeviceLatencyWarning::UsbSampleRate) => recommendation_list.push(ObsSetting {
+                key: format!("audio.sampleRate.{device}"),
+                display_name: format!("マイクのサンプルレート（{device}）"),
+                current_value: serde_json::json!(device),
+                recommended_value: serde_json::json!(48000),
+                reason: "USBオーディオ機器が44100Hzで動作していると、OBS既定の48000Hzとの変換でノイズや音ずれが出ることがあります。OSのサウンド設定でデバイスを48000Hzに揃えてください"
+                    .to_string(),
+                priority: "recommended".to_string(),
+            }),
+            None => {}
+        }
+    }
+
+    // 音声モニタリングの遅延補正（モニタリング使用中で未調整の場合のみ）
+    if needs_offset_calibration(audio_monitoring) {
+        let device = monitoring_device.as_deref().unwrap_or("既定のデバイス");
+        recommendation_list.push(ObsSetting {
+            key: "audio.monitoringOffset".to_string(),
+            display_name: "オーディオモニタリングの遅延補正".to_string(),
+            current_value: serde_json::json!(audio_monitoring.monitoring_offset_ms),
+            recommended_value: serde_json::json!(format!("{device}に合わせて調整")),
+            reason: format!(
+                "オーディオモニタリングの遅延補正を確認してください。モニタリングデバイス（{device}）ごとに遅延が異なるため、0msのままでは映像と音がずれて聞こえることがあります"
+            ),
+            priority: "optional".to_string(),
+        });
+    }
+
+    // 配信環境の構成の推奨（1台構成の設定で、配信用PCと推定される場合のみ）
+    if *dual_pc_detected && *setup_type == SetupType::SinglePc {
+        recommendation_list.push(ObsSetting {
+            key: "streamingMode.setupType".to_string(),
+            display_name: "配信環境の構成".to_string(),
+            current_value: serde_json::json!(SetupType::SinglePc),
+            recommended_value: serde_json::json!(SetupType::DualPcStreaming),
+            reason: "キャプチャーボード・NDIの映像ソースがあり、ゲームが動作していないため、2台構成の配信用PCと推定されます。設定すると配信専用PC向けの推奨に切り替わります"
+                .to_string(),
+            priority: "optional".to_string(),
+        });
+    }
+
     // システム情報を構築
-    let (memory_used, memory_total) = get_memory_info().unwrap_or((0, 8_000_000_000));
     let system_info = SystemInfo {
         cpu_model: hardware_info.cpu_name.clone(),
         gpu_model: hardware_info.gpu.as_ref().map(|g| g.name.clone()),
+        gpu_driver_version: hardware_info.gpu_driver_version().map(str::to_string),
         total_memory_mb: memory_total / 1_048_576,
         available_memory_mb: (memory_total - memory_used) / 1_048_576,
+        power_source: hardware_info.power.source,
+        battery_percent: hardware_info.power.battery_percent,
+        monitors: hardware_info.displays.clone(),
     };
 
     // 品質スコアを取得
-    let quality_score = recommendations.overall_score;
+    let quality_score = recommendations.score_breakdown;
 
     // 初心者向けサマリーを生成
     let summary = generate_analysis_summary(
-        &hardware_info,
+        hardware_info,
         &recommendations,
-        quality_score,
+        quality_score.total,
     );
 
     // システム能力評価を計算
@@ -325,7 +850,7 @@ pub async fn analyze_settings(
             EffectiveTier::TierE
         };
 
-        let cpu_tier = determine_cpu_tier(hardware_info.cpu_cores);
+        let cpu_tier = determine_cpu_tier(hardware_info.cpu_cores, hardware_info.benchmark_result);
         let memory_gb = hardware_info.total_memory_gb;
         let memory_tier = MemoryTier::from_gb(memory_gb);
 
@@ -342,16 +867,22 @@ pub async fn analyze_settings(
     // 静的設定（配信向けデフォルト）
     let static_settings = Some(StaticSettings::for_streaming());
 
-    Ok(AnalysisResult {
+    // マルチトラック配信の推奨（Twitchの拡張ブロードキャスト）
+    let multitrack = RecommendationEngine::recommend_multitrack(hardware_info, platform, network_speed);
+
+    AnalysisResult {
         quality_score,
         issue_count: recommendation_list.len(),
         recommendations: recommendation_list,
         system_info,
-        analyzed_at: chrono::Utc::now().timestamp(),
+        analyzed_at: *analyzed_at,
         summary,
         system_capability,
         static_settings,
-    })
+        multitrack,
+        // 入力に対して決定的な結果とするため、リクエストIDはコマンドで設定する
+        request_id: None,
+    }
 }
 
 /// 問題履歴を取得
@@ -371,6 +902,25 @@ pub async fn get_problem_history(limit: usize) -> Result<Vec<ProblemReport>, App
     Ok(Vec::new())
 }
 
+/// OBSのログファイルを解析して問題を検出
+///
+/// アプリを起動していなかった配信についても、OBS自身のログに残った
+/// エンコード過負荷・フレームドロップ・出力停止コードから問題を検出する
+///
+/// # Arguments
+/// * `path` - ログファイルのパス（省略時はOBSのログディレクトリ内の最新ファイル）
+///
+/// # Returns
+/// 解析したログファイルと検出された問題（各問題の検出日時はログの時刻）
+#[tauri::command]
+pub async fn analyze_obs_log(path: Option<String>) -> Result<ObsLogAnalysis, AppError> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => latest_log_file(&obs_logs_dir()?)?,
+    };
+    analyze_obs_log_file(&path)
+}
+
 /// スコアを計算
 ///
 /// 問題の数と重要度から総合スコアを算出
@@ -394,6 +944,158 @@ fn calculate_overall_score(problems: &[ProblemReport]) -> f64 {
     score.clamp(0.0, 100.0)
 }
 
+/// 分析に使うビットレート履歴（実測値がない場合は目標ビットレートのみ）
+fn bitrate_history_or_target(measured: Vec<u64>, target_bitrate: u64) -> Vec<u64> {
+    if measured.is_empty() {
+        vec![target_bitrate]
+    } else {
+        measured
+    }
+}
+
+/// 重要度順を維持したまま問題を挿入（同じ重要度の問題の後ろに入る）
+fn insert_by_severity(problems: &mut Vec<ProblemReport>, problem: ProblemReport) {
+    let rank = |severity: AlertSeverity| match severity {
+        AlertSeverity::Critical => 0,
+        AlertSeverity::Warning => 1,
+        AlertSeverity::Info => 2,
+        AlertSeverity::Tips => 3,
+    };
+    let position = problems
+        .iter()
+        .position(|p| rank(p.severity) > rank(problem.severity))
+        .unwrap_or(problems.len());
+    problems.insert(position, problem);
+}
+
+/// マルチモニター環境の画面キャプチャの推奨を生成
+///
+/// キャンバス解像度の推奨が既にある場合、キャンバスをモニターに合わせる推奨は重複するため省略する
+fn monitor_capture_recommendations(
+    obs_settings: &ObsSettings,
+    monitors: &[DisplayInfo],
+    existing: &[ObsSetting],
+) -> Vec<ObsSetting> {
+    let video = &obs_settings.video;
+    let canvas = (video.base_width, video.base_height);
+    let current_canvas = serde_json::json!(format!("{}x{}", video.base_width, video.base_height));
+
+    match advise_monitor_capture(canvas, (video.output_width, video.output_height), monitors) {
+        Some(MonitorCaptureAdvice::CaptureOtherMonitor { captured, target }) => vec![
+            ObsSetting {
+                key: "video.captureMonitor".to_string(),
+                display_name: "キャプチャするモニター".to_string(),
+                current_value: serde_json::json!(captured.display_label()),
+                recommended_value: serde_json::json!(target.display_label()),
+                reason: format!(
+                    "{}x{}の画面を{}x{}に縮小すると非整数倍の縮小になり、文字がぼやけます。出力解像度と同じ{}x{}のモニターに配信する画面を表示し、直接キャプチャすると縮小せずに配信できます",
+                    captured.width, captured.height, video.output_width, video.output_height,
+                    target.width, target.height
+                ),
+                priority: "optional".to_string(),
+            },
+            ObsSetting {
+                key: "video.baseResolution".to_string(),
+                display_name: "基本（キャンバス）解像度".to_string(),
+                current_value: current_canvas,
+                recommended_value: serde_json::json!(format!("{}x{}", target.width, target.height)),
+                reason: format!(
+                    "キャプチャするモニター（{}）にキャンバスを合わせます",
+                    target.display_label()
+                ),
+                priority: "optional".to_string(),
+            },
+        ],
+        Some(MonitorCaptureAdvice::MatchCanvas { monitor })
+            if !existing.iter().any(|setting| setting.key == "video.baseResolution") =>
+        {
+            vec![ObsSetting {
+                key: "video.baseResolution".to_string(),
+                display_name: "基本（キャンバス）解像度".to_string(),
+                current_value: current_canvas,
+                recommended_value: serde_json::json!(format!("{}x{}", monitor.width, monitor.height)),
+                reason: format!(
+                    "キャンバス（{}x{}）がどのモニターの解像度とも一致しないため、画面キャプチャが拡大・縮小されます。キャプチャするモニター（{}）に合わせてください",
+                    video.base_width, video.base_height, monitor.display_label()
+                ),
+                priority: "recommended".to_string(),
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// 主要な映像ソースとのFPS不一致の推奨を追加
+///
+/// 同じFPSを推奨済みの場合は重複させず、既存の推奨に理由を追記する
+fn add_source_frame_rate_recommendation(
+    recommendation_list: &mut Vec<ObsSetting>,
+    current_fps: u32,
+    mismatch: &FrameRateMismatch,
+) {
+    let mut reason = format!(
+        "画面の大部分を占める「{}」が{:.0}fpsで動作しているため、{}fpsで出力しても同じフレームを2回エンコードするだけで見た目は変わりません",
+        mismatch.source_name, mismatch.source_fps, current_fps
+    );
+    if let Some((width, height)) = mismatch.lower_resolution {
+        reason.push_str(&format!("（カメラの解像度も{width}x{height}のため、出力では拡大されています）"));
+    }
+
+    let recommended_value = serde_json::json!(mismatch.recommended_fps);
+    if let Some(existing) = recommendation_list
+        .iter_mut()
+        .find(|setting| setting.key == "video.fps" && setting.recommended_value == recommended_value)
+    {
+        existing.reason = format!("{}。{reason}", existing.reason);
+        return;
+    }
+
+    recommendation_list.push(ObsSetting {
+        key: "video.sourceFps".to_string(),
+        display_name: "FPS（カメラに合わせる）".to_string(),
+        current_value: serde_json::json!(current_fps),
+        recommended_value,
+        reason: format!("{reason}。{}fpsに下げるとエンコード負荷を抑えられます", mismatch.recommended_fps),
+        priority: "optional".to_string(),
+    });
+}
+
+/// キーフレーム間隔・Bフレーム数の推奨を生成
+///
+/// キーフレーム間隔が配信先の要件と異なると取り込みサーバーが警告し、視聴開始やアーカイブに影響するため必須とする。
+/// 推奨設定の適用時に推奨値（`keyframeIntervalSecs`）が書き込まれる
+fn gop_recommendations(platform: StreamingPlatform, output: &OutputSettings) -> Vec<ObsSetting> {
+    RecommendationEngine::gop_violations(platform, output)
+        .into_iter()
+        .map(|violation| match violation {
+            GopViolation::KeyframeInterval { current, required } => ObsSetting {
+                key: "output.keyframeInterval".to_string(),
+                display_name: "キーフレーム間隔".to_string(),
+                current_value: serde_json::json!(current),
+                recommended_value: serde_json::json!(required),
+                reason: if current == 0 {
+                    format!(
+                        "キーフレーム間隔が自動（0）になっています。このエンコーダー・配信先では間隔が一定にならないため、{required}秒を指定してください"
+                    )
+                } else {
+                    format!("配信先はキーフレーム間隔{required}秒を要求しています（現在{current}秒）。異なる場合は取り込みサーバーが警告し、視聴開始が遅くなります")
+                },
+                priority: "critical".to_string(),
+            },
+            GopViolation::BFrames { current, max } => ObsSetting {
+                key: "output.bFrames".to_string(),
+                display_name: "Bフレーム".to_string(),
+                current_value: serde_json::json!(current),
+                recommended_value: serde_json::json!(max),
+                reason: format!(
+                    "Bフレームが{current}に設定されています。配信では{max}以下にしないと、プレイヤーによっては映像がカクつくことがあります"
+                ),
+                priority: "recommended".to_string(),
+            },
+        })
+        .collect()
+}
+
 /// エンコーダーIDからユーザー向け表示ラベルを取得
 ///
 /// OBSで使用される様々なエンコーダーIDを判定して、
@@ -429,10 +1131,14 @@ fn get_encoder_display_label(encoder_id: &str) -> String {
         if encoder_lower.contains("amf") || encoder_lower.contains("amd") {
             return "AMD AMF (AV1)".to_string();
         }
-        // Intel AV1（Arc/Meteor Lake以降）
+        // Intel AV1（Arc A/Bシリーズ・Meteor Lake以降）
         if encoder_lower.contains("qsv") {
             return "Intel QSV (AV1)".to_string();
         }
+        // AOM AV1（OBS 29以降のソフトウェアエンコード）
+        if encoder_lower.contains("aom") {
+            return "CPU (AOM AV1)".to_string();
+        }
         // その他のAV1（ソフトウェアエンコード等）
         return "AV1".to_string();
     }
@@ -609,6 +1315,12 @@ mod tests {
         assert_eq!(score, 100.0);
     }
 
+    #[test]
+    fn test_bitrate_history_prefers_measured_bitrates() {
+        assert_eq!(bitrate_history_or_target(vec![], 6000), vec![6000]);
+        assert_eq!(bitrate_history_or_target(vec![5800, 3200, 2900], 6000), vec![5800, 3200, 2900]);
+    }
+
     #[test]
     fn test_calculate_overall_score_with_problems() {
         use crate::services::alerts::{AlertSeverity, MetricType};
@@ -726,7 +1438,7 @@ mod tests {
 
     #[test]
     fn test_get_encoder_label_intel_av1() {
-        // Intel AV1エンコーダー（Arc/Meteor Lake以降）
+        // Intel AV1エンコーダー（Arc A/Bシリーズ・Meteor Lake以降）
         let test_cases = vec![
             ("obs_qsv11_av1", "Intel QSV (AV1)"),
             ("qsv_av1", "Intel QSV (AV1)"),
@@ -737,6 +1449,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_encoder_label_aom_av1() {
+        // AOM AV1エンコーダー（OBS 29以降のソフトウェアエンコード）
+        let test_cases = vec![
+            ("aom_av1", "CPU (AOM AV1)"),
+            ("ffmpeg_aom_av1", "CPU (AOM AV1)"),
+        ];
+        for (encoder_id, expected) in test_cases {
+            let label = get_encoder_display_label(encoder_id);
+            assert_eq!(label, expected, "Encoder ID: {}", encoder_id);
+        }
+    }
+
     #[test]
     fn test_get_encoder_label_intel_hevc() {
         // Intel HEVCエンコーダー（Skylake以降）
@@ -872,6 +1597,8 @@ mod tests {
                 bitrate_kbps: 6000,
                 preset: "p5".to_string(),
                 rate_control: "CBR".to_string(),
+                recording_format: None,
+                recording_path: None,
             },
             overall_score: 85,
         };
@@ -916,6 +1643,8 @@ mod tests {
                 bitrate_kbps: 6000,
                 preset: "p5".to_string(),
                 rate_control: "CBR".to_string(),
+                recording_format: None,
+                recording_path: None,
             },
             overall_score: 90,
         };
@@ -940,4 +1669,478 @@ mod tests {
             "AV1 encoder message should warn about Enhanced RTMP requirement"
         );
     }
+
+    fn analysis_with_display(width: u32, height: u32) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().primary_display(width, height, 165).build(),
+            platform: StreamingPlatform::Twitch,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_canvas_recommended_when_display_exceeds_base_resolution() {
+        let result = analysis_with_display(2560, 1440);
+        let canvas = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "video.baseResolution")
+            .expect("canvas recommendation should exist");
+
+        assert_eq!(canvas.current_value, serde_json::json!("1920x1080"));
+        assert_eq!(canvas.recommended_value, serde_json::json!("2560x1440"));
+        assert!(canvas.reason.contains("165Hz"));
+    }
+
+    #[test]
+    fn test_canvas_not_recommended_when_display_matches_base_resolution() {
+        let result = analysis_with_display(1920, 1080);
+        assert!(result.recommendations.iter().all(|r| r.key != "video.baseResolution"));
+    }
+
+    #[test]
+    fn test_aspect_ratio_recommended_for_ultrawide_display() {
+        let result = analysis_with_display(3440, 1440);
+        let aspect = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "video.aspectRatio")
+            .expect("aspect recommendation should exist");
+
+        assert_eq!(aspect.current_value, serde_json::json!("3440x1440"));
+        assert_eq!(aspect.recommended_value, serde_json::json!("crop"));
+        assert!(aspect.reason.contains("2560x1440"));
+
+        let result = analysis_with_display(2560, 1440);
+        assert!(result.recommendations.iter().all(|r| r.key != "video.aspectRatio"));
+    }
+
+    fn analysis_with_monitors(
+        base: (u32, u32),
+        output: (u32, u32),
+        secondary: (u32, u32),
+    ) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        let mut obs_settings = standard_obs_settings();
+        (obs_settings.video.base_width, obs_settings.video.base_height) = base;
+        (obs_settings.video.output_width, obs_settings.video.output_height) = output;
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings,
+            hardware_info: HardwareInfoBuilder::new()
+                .primary_display(2560, 1440, 165)
+                .secondary_display(secondary.0, secondary.1, 60)
+                .build(),
+            platform: StreamingPlatform::Twitch,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_downscaled_qhd_capture_recommends_fhd_secondary() {
+        let result = analysis_with_monitors((2560, 1440), (1920, 1080), (1920, 1080));
+
+        let monitor = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "video.captureMonitor")
+            .expect("capture monitor recommendation should exist");
+        assert_eq!(monitor.current_value, serde_json::json!("2560x1440・165Hz（メイン）"));
+        assert_eq!(monitor.recommended_value, serde_json::json!("1920x1080・60Hz"));
+
+        let canvas = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "video.baseResolution")
+            .expect("canvas recommendation should exist");
+        assert_eq!(canvas.recommended_value, serde_json::json!("1920x1080"));
+
+        assert_eq!(result.system_info.monitors.len(), 2);
+    }
+
+    #[test]
+    fn test_unmatched_canvas_recommendation_is_not_duplicated() {
+        let result = analysis_with_monitors((1600, 900), (1280, 720), (1920, 1080));
+
+        let canvas: Vec<_> = result
+            .recommendations
+            .iter()
+            .filter(|r| r.key == "video.baseResolution")
+            .collect();
+        assert_eq!(canvas.len(), 1);
+        assert_eq!(canvas[0].recommended_value, serde_json::json!("2560x1440"));
+        assert!(result.recommendations.iter().all(|r| r.key != "video.captureMonitor"));
+    }
+
+    #[test]
+    fn test_integer_downscale_has_no_capture_monitor_recommendation() {
+        let result = analysis_with_monitors((2560, 1440), (1280, 720), (1920, 1080));
+        assert!(result.recommendations.iter().all(|r| r.key != "video.captureMonitor"));
+    }
+
+    fn analysis_with_output_mode(current_output_mode: Option<ObsOutputMode>) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        // RTX 40シリーズはBフレーム・Look-aheadを使うNVENCが推奨される
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().gpu("NVIDIA GeForce RTX 4070").build(),
+            platform: StreamingPlatform::Twitch,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_simple_output_mode_recommends_advanced() {
+        let result = analysis_with_output_mode(Some(ObsOutputMode::Simple));
+        let mode = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "output.mode")
+            .expect("output mode recommendation should exist");
+
+        assert_eq!(mode.priority, "critical");
+        assert_eq!(mode.current_value, serde_json::json!("simple"));
+        assert_eq!(mode.recommended_value, serde_json::json!("advanced"));
+    }
+
+    #[test]
+    fn test_output_mode_not_recommended_when_advanced_or_unknown() {
+        for current_output_mode in [Some(ObsOutputMode::Advanced), None] {
+            let result = analysis_with_output_mode(current_output_mode);
+            assert!(result.recommendations.iter().all(|r| r.key != "output.mode"));
+        }
+    }
+
+    fn analysis_with_setup(setup_type: SetupType, dual_pc_detected: bool) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().cores(16).gpu("NVIDIA GeForce RTX 4070").build(),
+            platform: StreamingPlatform::Twitch,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type,
+            dual_pc_detected,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_detected_dual_pc_suggests_setup_type() {
+        let result = analysis_with_setup(SetupType::SinglePc, true);
+        let setup = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "streamingMode.setupType")
+            .expect("setup type recommendation should exist");
+
+        assert_eq!(setup.priority, "optional");
+        assert_eq!(setup.current_value, serde_json::json!("singlePc"));
+        assert_eq!(setup.recommended_value, serde_json::json!("dualPcStreaming"));
+
+        let result = analysis_with_setup(SetupType::SinglePc, false);
+        assert!(result.recommendations.iter().all(|r| r.key != "streamingMode.setupType"));
+    }
+
+    #[test]
+    fn test_dual_pc_streaming_recommends_x264_on_high_end_cpu() {
+        let single = analysis_with_setup(SetupType::SinglePc, false);
+        let encoder = |result: &AnalysisResult| {
+            result
+                .recommendations
+                .iter()
+                .find(|r| r.key == "output.encoder")
+                .map(|r| r.recommended_value.clone())
+        };
+        // 現在の設定（NVENC）のまま
+        assert_eq!(encoder(&single), None);
+
+        let dedicated = analysis_with_setup(SetupType::DualPcStreaming, true);
+        assert_eq!(encoder(&dedicated), Some(serde_json::json!("obs_x264")));
+        // 設定済みの場合は構成の変更を提案しない
+        assert!(dedicated.recommendations.iter().all(|r| r.key != "streamingMode.setupType"));
+    }
+
+    fn analysis_with_microphones(devices: &[&str]) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().build(),
+            platform: StreamingPlatform::Twitch,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: devices.iter().map(ToString::to_string).collect(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_microphone_device_latency_warnings() {
+        let bluetooth = "ヘッドセット (WH-1000XM4 Hands-Free AG Audio)";
+        let usb = "マイク (Yeti Stereo Microphone USB)";
+        let result = analysis_with_microphones(&[bluetooth, usb, "マイク (Realtek(R) Audio)"]);
+
+        let latency: Vec<_> = result
+            .recommendations
+            .iter()
+            .filter(|r| r.key.starts_with("audio.deviceLatency."))
+            .collect();
+        assert_eq!(latency.len(), 1);
+        assert_eq!(latency[0].priority, "critical");
+        assert_eq!(latency[0].current_value, serde_json::json!(bluetooth));
+
+        let sample_rate: Vec<_> = result
+            .recommendations
+            .iter()
+            .filter(|r| r.key.starts_with("audio.sampleRate."))
+            .collect();
+        assert_eq!(sample_rate.len(), 1);
+        assert_eq!(sample_rate[0].priority, "recommended");
+        assert_eq!(sample_rate[0].recommended_value, serde_json::json!(48000));
+
+        let result = analysis_with_microphones(&[]);
+        assert!(result.recommendations.iter().all(|r| !r.key.starts_with("audio.deviceLatency.")));
+    }
+
+    fn analysis_with_monitoring(enabled: bool, monitoring_offset_ms: i32) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().build(),
+            platform: StreamingPlatform::YouTube,
+            style: StreamingStyle::Music,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig { enabled, monitoring_offset_ms },
+            monitoring_device: Some("Focusrite USB".to_string()),
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_uncalibrated_monitoring_offset_is_recommended() {
+        let result = analysis_with_monitoring(true, 0);
+        let offset = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "audio.monitoringOffset")
+            .expect("monitoring offset recommendation should exist");
+        assert_eq!(offset.priority, "optional");
+        assert!(offset.reason.starts_with("オーディオモニタリングの遅延補正を確認してください"));
+        assert!(offset.reason.contains("Focusrite USB"));
+
+        // 調整済み・モニタリング未使用の場合は推奨しない
+        for result in [analysis_with_monitoring(true, 80), analysis_with_monitoring(false, 0)] {
+            assert!(result.recommendations.iter().all(|r| r.key != "audio.monitoringOffset"));
+        }
+    }
+
+    fn analysis_with_recording_format(format: Option<&str>) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        let mut obs_settings = standard_obs_settings();
+        obs_settings.output.recording_format = format.map(str::to_string);
+        build_analysis_result(&AnalysisInput {
+            obs_settings,
+            hardware_info: HardwareInfoBuilder::new().build(),
+            platform: StreamingPlatform::YouTube,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: Vec::new(),
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_mp4_recording_recommends_mkv() {
+        let result = analysis_with_recording_format(Some("mp4"));
+        let format = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "output.recordingFormat")
+            .expect("recording format recommendation should exist");
+        assert_eq!(format.priority, "critical");
+        assert_eq!(format.current_value, serde_json::json!("mp4"));
+        assert_eq!(format.recommended_value, serde_json::json!("mkv"));
+
+        // MKV・フラグメント化MP4・未取得の場合は推奨しない
+        for format in [Some("mkv"), Some("fragmented_mp4"), None] {
+            let result = analysis_with_recording_format(format);
+            assert!(result.recommendations.iter().all(|r| r.key != "output.recordingFormat"));
+        }
+    }
+
+    #[test]
+    fn test_keyframe_interval_violation_is_critical() {
+        use crate::testing::standard_obs_settings;
+
+        let mut output = standard_obs_settings().output;
+        output.keyframe_interval_secs = 0;
+        output.b_frames = Some(4);
+
+        let recommendations = gop_recommendations(StreamingPlatform::Twitch, &output);
+        let keyframe = recommendations.iter().find(|r| r.key == "output.keyframeInterval")
+            .expect("keyframe interval recommendation should exist");
+        assert_eq!(keyframe.priority, "critical");
+        assert_eq!(keyframe.current_value, serde_json::json!(0));
+        assert_eq!(keyframe.recommended_value, serde_json::json!(2));
+        assert!(keyframe.reason.contains("自動"));
+        let b_frames = recommendations.iter().find(|r| r.key == "output.bFrames")
+            .expect("b-frames recommendation should exist");
+        assert_eq!(b_frames.priority, "recommended");
+
+        output.keyframe_interval_secs = 2;
+        output.b_frames = Some(2);
+        assert!(gop_recommendations(StreamingPlatform::Twitch, &output).is_empty());
+    }
+
+    fn analysis_with_camera(style: StreamingStyle, camera_fps: f64) -> AnalysisResult {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+
+        build_analysis_result(&AnalysisInput {
+            obs_settings: standard_obs_settings(),
+            hardware_info: HardwareInfoBuilder::new().cores(16).gpu("NVIDIA GeForce RTX 4070").build(),
+            platform: StreamingPlatform::YouTube,
+            style,
+            network_speed_mbps: 20.0,
+            current_protocol: StreamingProtocol::default(),
+            current_output_mode: None,
+            audio_inputs: Vec::new(),
+            microphone_devices: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            monitoring_device: None,
+            setup_type: SetupType::SinglePc,
+            dual_pc_detected: false,
+            video_sources: vec![VideoSourceInfo {
+                name: "Webカメラ".to_string(),
+                role: VideoSourceRole::Camera,
+                fps: Some(camera_fps),
+                resolution: Some((1280, 720)),
+            }],
+            memory_used_bytes: 8_000_000_000,
+            memory_total_bytes: 16_000_000_000,
+            analyzed_at: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_half_rate_camera_suggests_30fps_output() {
+        // 歌配信はスタイルとしては60fpsを推奨するため、カメラに合わせる推奨を別に追加する
+        let result = analysis_with_camera(StreamingStyle::Music, 30.0);
+        let source_fps = result
+            .recommendations
+            .iter()
+            .find(|r| r.key == "video.sourceFps")
+            .expect("source fps recommendation should exist");
+        assert_eq!(source_fps.priority, "optional");
+        assert_eq!(source_fps.current_value, serde_json::json!(60));
+        assert_eq!(source_fps.recommended_value, serde_json::json!(30));
+        assert!(source_fps.reason.contains("Webカメラ"));
+        assert!(source_fps.reason.contains("1280x720"));
+
+        // 雑談配信は30fpsを推奨済みのため、同じ値の推奨を重複させない
+        let result = analysis_with_camera(StreamingStyle::Talk, 30.0);
+        let thirty_fps: Vec<_> = result
+            .recommendations
+            .iter()
+            .filter(|r| r.key.starts_with("video.") && r.recommended_value == serde_json::json!(30))
+            .collect();
+        assert_eq!(thirty_fps.len(), 1);
+        assert!(thirty_fps[0].reason.contains("Webカメラ"));
+    }
+
+    #[test]
+    fn test_camera_frame_rate_rule_skips_gaming_and_matching_rate() {
+        for result in [
+            analysis_with_camera(StreamingStyle::Gaming, 30.0),
+            analysis_with_camera(StreamingStyle::Music, 60.0),
+        ] {
+            assert!(result.recommendations.iter().all(|r| r.key != "video.sourceFps"));
+            assert!(result.recommendations.iter().all(|r| !r.reason.contains("Webカメラ")));
+        }
+    }
 }
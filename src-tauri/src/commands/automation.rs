@@ -0,0 +1,89 @@
+// 自動化ルール管理コマンド
+
+use crate::commands::config::CONFIG_SAVE_LOCK;
+use crate::error::AppError;
+use crate::services::automation::{get_execution_log, AutomationExecution};
+use crate::storage::config::AutomationRule;
+use crate::storage::{load_config, save_config};
+use std::sync::PoisonError;
+
+/// ルールを検証し、エラーの場合は `VALIDATION_FAILED`（`details.errors` に項目ごとのメッセージ）
+fn validate_rule(rule: &AutomationRule) -> Result<(), AppError> {
+    rule.validate().map_err(|errors| {
+        AppError::validation_failed(&format!("自動化ルールが不正です: {}", errors.join(", ")))
+            .with_details(serde_json::json!({ "errors": errors }))
+    })
+}
+
+/// 自動化ルールが見つからない場合のエラー
+fn rule_not_found(id: &str) -> AppError {
+    AppError::validation_failed(&format!("自動化ルールが見つかりません: {id}"))
+}
+
+/// 自動化ルールの一覧を取得
+#[tauri::command]
+pub async fn get_automation_rules() -> Result<Vec<AutomationRule>, AppError> {
+    Ok(load_config()?.automation_rules)
+}
+
+/// 自動化ルールを追加
+///
+/// IDは新しく採番する（指定した値は無視する）
+///
+/// # Returns
+/// 追加したルール
+#[tauri::command]
+pub async fn create_automation_rule(mut rule: AutomationRule) -> Result<AutomationRule, AppError> {
+    rule.id = uuid::Uuid::new_v4().to_string();
+    validate_rule(&rule)?;
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.automation_rules.push(rule.clone());
+    save_config(&config)?;
+    Ok(rule)
+}
+
+/// 自動化ルールを更新
+///
+/// 同じIDのルールを置き換える。クールダウン中のルールは更新後もクールダウンを引き継ぐ
+///
+/// # Returns
+/// 更新したルール
+#[tauri::command]
+pub async fn update_automation_rule(rule: AutomationRule) -> Result<AutomationRule, AppError> {
+    validate_rule(&rule)?;
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    let existing = config
+        .automation_rules
+        .iter_mut()
+        .find(|existing| existing.id == rule.id)
+        .ok_or_else(|| rule_not_found(&rule.id))?;
+    existing.clone_from(&rule);
+    save_config(&config)?;
+    Ok(rule)
+}
+
+/// 自動化ルールを削除
+///
+/// # Arguments
+/// * `id` - ルールID
+#[tauri::command]
+pub async fn delete_automation_rule(id: String) -> Result<(), AppError> {
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    let before = config.automation_rules.len();
+    config.automation_rules.retain(|rule| rule.id != id);
+    if config.automation_rules.len() == before {
+        return Err(rule_not_found(&id));
+    }
+    save_config(&config)
+}
+
+/// 自動化ルールの実行履歴を取得（新しい順、アプリの起動中に実行した直近100件）
+#[tauri::command]
+pub async fn get_automation_log() -> Result<Vec<AutomationExecution>, AppError> {
+    Ok(get_execution_log())
+}
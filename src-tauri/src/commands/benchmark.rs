@@ -0,0 +1,381 @@
+// エンコーダーベンチマークコマンド
+//
+// 推奨設定で短時間のローカル録画を行い、実機の余力を計測する
+
+use crate::commands::utils::get_hardware_info;
+use crate::commands::validation::validate_encoder_benchmark_input;
+use crate::error::AppError;
+use crate::obs::{get_obs_client, get_obs_settings, get_output_mode, ObsClient, VideoApplyResult};
+use crate::services::benchmark::{
+    begin_encoder_benchmark, encoder_benchmark_parameters, evaluate_benchmark, interpret_encoder_benchmark,
+    last_benchmark_result, sleep_or_cancelled, store_benchmark_result, BenchmarkResult, BenchmarkSample,
+    EncoderBenchmarkResult, FrameCounters, DEFAULT_BENCHMARK_DURATION_SECS, MAX_BENCHMARK_DURATION_SECS,
+    MIN_BENCHMARK_DURATION_SECS,
+};
+use crate::services::{get_streaming_mode_service, system_monitor_service, RecommendationEngine};
+use crate::storage::config::load_config;
+use obws::requests::config::SetVideoSettings;
+use obws::responses::config::VideoSettings;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// サンプリング間隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// エンコーダーベンチマークを実行
+///
+/// 推奨設定（解像度・FPS・エンコーダー・ビットレート）で短時間のローカル録画を行い、
+/// CPU/GPU/エンコーダー使用率とレンダリング時間を計測する。
+/// 計測後は録画を停止してファイルを削除し、ビデオ設定とエンコーダー設定を元に戻す。
+///
+/// 配信・録画・仮想カメラのいずれかが動作中の場合は実行できない。
+///
+/// # Arguments
+/// * `duration_secs` - 計測時間（秒、省略時は30秒。10〜120秒に制限）
+#[tauri::command]
+pub async fn run_encoder_benchmark(duration_secs: Option<u64>) -> Result<BenchmarkResult, AppError> {
+    let duration_secs = duration_secs
+        .unwrap_or(DEFAULT_BENCHMARK_DURATION_SECS)
+        .clamp(MIN_BENCHMARK_DURATION_SECS, MAX_BENCHMARK_DURATION_SECS);
+
+    let streaming_service = get_streaming_mode_service();
+
+    // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
+    let result = streaming_service
+        .execute_if_not_streaming(|| async move {
+            let client = get_obs_client();
+            if !client.is_connected().await {
+                return Err(AppError::obs_not_connected());
+            }
+
+            ensure_outputs_idle(&client).await?;
+
+            // 推奨設定を算出
+            let config = load_config()?;
+            let current_settings = get_obs_settings().await?;
+            let hardware = get_hardware_info().await;
+            let recommendations = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current_settings),
+                config.streaming_mode.platform,
+                config.streaming_mode.style,
+                config.streaming_mode.network_speed_mbps,
+            );
+
+            // 推奨ビデオ設定とエンコーダー設定を一時的に適用
+            // （現在のエンコーダーのまま計測すると、推奨エンコーダーの余力を判定できない）
+            let original_video = client.get_video_settings().await?;
+            let parameters = encoder_benchmark_parameters(
+                get_output_mode(&client).await?,
+                &recommendations.output.encoder,
+                recommendations.output.bitrate_kbps,
+            );
+            let original_parameters = read_profile_parameters(&client, &parameters).await?;
+
+            let measured = async {
+                let video = crate::obs::settings::apply_video_settings(
+                    recommendations.video.output_width,
+                    recommendations.video.output_height,
+                    recommendations.video.fps,
+                    1,
+                )
+                .await?;
+                warn_if_unverified(&video);
+                write_profile_parameters(&client, &parameters).await?;
+                record_and_sample(&client, duration_secs).await
+            }
+            .await;
+
+            // 設定を元に戻す（計測の成否に関わらず実行）
+            let restore = restore_video_settings(&client, &original_video).await;
+            restore_profile_parameters(&client, &original_parameters).await;
+
+            let (samples, frames) = measured?;
+            restore?;
+
+            Ok(evaluate_benchmark(
+                &recommendations.output.encoder,
+                recommendations.output.preset.as_deref(),
+                recommendations.video.fps,
+                duration_secs,
+                &samples,
+                frames,
+            ))
+        })
+        .await?;
+
+    tracing::info!(
+        target: "benchmark",
+        verdict = ?result.verdict,
+        headroom = result.headroom_percent,
+        "エンコーダーベンチマーク完了"
+    );
+
+    store_benchmark_result(result.clone()).await;
+    Ok(result)
+}
+
+/// 直近のベンチマーク結果を取得
+#[tauri::command]
+pub async fn get_last_benchmark_result() -> Result<Option<BenchmarkResult>, AppError> {
+    Ok(last_benchmark_result().await)
+}
+
+/// 指定したエンコーダー・解像度・FPS・ビットレートで実際のエンコード性能を計測
+///
+/// 一時的に設定を差し替えてローカル録画を行い、実測のFPSと取りこぼしたフレーム数、
+/// 1フレームあたりの処理時間を計測する。平均FPSが目標の98%以上なら `sustained` がtrueになる。
+/// 計測後は録画を停止してファイルを削除し、ビデオ設定とエンコーダー設定を元に戻す。
+///
+/// 配信・録画・仮想カメラのいずれかが動作中の場合は `OBS_OUTPUT_ACTIVE` エラー。
+/// `cancel_encoder_benchmark` で中断した場合は設定を元に戻してから `BENCHMARK_CANCELLED` エラーを返す。
+///
+/// # Arguments
+/// * `encoder_id` - 計測するエンコーダーID（例: "`obs_x264`", "`ffmpeg_nvenc`"）
+/// * `width` / `height` - 出力解像度
+/// * `fps` - 目標FPS
+/// * `bitrate_kbps` - ビットレート（kbps）
+/// * `duration_secs` - 計測時間（秒、5〜60秒）
+#[tauri::command]
+pub async fn benchmark_encoder(
+    encoder_id: String,
+    width: u32,
+    height: u32,
+    fps: u32,
+    bitrate_kbps: u32,
+    duration_secs: u8,
+) -> Result<EncoderBenchmarkResult, AppError> {
+    validate_encoder_benchmark_input(&encoder_id, width, height, fps, bitrate_kbps, duration_secs)?;
+
+    // 設定ロックの待機中に中断された場合も検知できるよう、先に登録する
+    let mut cancel = begin_encoder_benchmark();
+    let streaming_service = get_streaming_mode_service();
+
+    // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
+    let result = streaming_service
+        .execute_if_not_streaming(|| async move {
+            let client = get_obs_client();
+            if !client.is_connected().await {
+                return Err(AppError::obs_not_connected());
+            }
+            ensure_outputs_idle(&client).await?;
+
+            // 計測対象のビデオ設定とエンコーダー設定を一時的に適用
+            let original_video = client.get_video_settings().await?;
+            let parameters = encoder_benchmark_parameters(get_output_mode(&client).await?, &encoder_id, bitrate_kbps);
+            let original_parameters = read_profile_parameters(&client, &parameters).await?;
+
+            let measured = async {
+                let video = crate::obs::settings::apply_video_settings(width, height, fps, 1).await?;
+                warn_if_unverified(&video);
+                write_profile_parameters(&client, &parameters).await?;
+                record_and_measure(&client, duration_secs, &mut cancel).await
+            }
+            .await;
+
+            // 設定を元に戻す（計測の成否・中断に関わらず実行）
+            let restore = restore_video_settings(&client, &original_video).await;
+            restore_profile_parameters(&client, &original_parameters).await;
+
+            let (elapsed_secs, frames, frame_times_ms) = measured?;
+            restore?;
+
+            Ok(interpret_encoder_benchmark(fps, elapsed_secs, frames, &frame_times_ms))
+        })
+        .await?;
+
+    tracing::info!(
+        target: "benchmark",
+        avg_fps = result.avg_fps,
+        dropped_frames = result.dropped_frames,
+        sustained = result.sustained,
+        "エンコーダー性能の計測完了"
+    );
+    Ok(result)
+}
+
+/// 計測用のビデオ設定がOBSで丸められた場合に警告を記録
+///
+/// 計測は丸められた設定のまま続行する
+fn warn_if_unverified(video: &VideoApplyResult) {
+    if let Some(warning) = video.warning() {
+        tracing::warn!(target: "benchmark", "{warning}");
+    }
+}
+
+/// 実行中のエンコーダー性能の計測を中断
+///
+/// 計測ダイアログを閉じた場合に呼び出す。
+///
+/// # Returns
+/// 実行中の計測があったか
+#[tauri::command]
+pub async fn cancel_encoder_benchmark() -> Result<bool, AppError> {
+    Ok(crate::services::benchmark::cancel_encoder_benchmark())
+}
+
+/// 配信・録画・仮想カメラのいずれも動作していないことを確認
+async fn ensure_outputs_idle(client: &ObsClient) -> Result<(), AppError> {
+    let status = client.get_status().await?;
+    if status.streaming || status.recording || status.virtual_cam_active {
+        return Err(AppError::obs_output_active(
+            "配信・録画・仮想カメラのいずれかが動作中のため、ベンチマークを実行できません",
+        ));
+    }
+    Ok(())
+}
+
+/// 計測で差し替えるプロファイルパラメータの現在値を取得
+async fn read_profile_parameters(
+    client: &ObsClient,
+    parameters: &[(&'static str, &'static str, String)],
+) -> Result<Vec<(&'static str, &'static str, Option<String>)>, AppError> {
+    let mut original = Vec::with_capacity(parameters.len());
+    for (section, name, _) in parameters {
+        original.push((*section, *name, client.get_profile_parameter(section, name).await?));
+    }
+    Ok(original)
+}
+
+/// 計測用のプロファイルパラメータ（エンコーダー・ビットレート等）を書き込む
+async fn write_profile_parameters(
+    client: &ObsClient,
+    parameters: &[(&'static str, &'static str, String)],
+) -> Result<(), AppError> {
+    for (section, name, value) in parameters {
+        client.set_profile_parameter(section, name, Some(value)).await?;
+    }
+    Ok(())
+}
+
+/// ベンチマーク前のプロファイルパラメータに戻す（失敗は警告のみ）
+async fn restore_profile_parameters(client: &ObsClient, original: &[(&'static str, &'static str, Option<String>)]) {
+    for (section, name, value) in original {
+        if let Err(e) = client.set_profile_parameter(section, name, value.as_deref()).await {
+            tracing::warn!(target: "benchmark", error = %e, section, name, "エンコーダー設定の復元に失敗");
+        }
+    }
+}
+
+/// ベンチマーク前のビデオ設定に戻す
+async fn restore_video_settings(client: &ObsClient, original: &VideoSettings) -> Result<(), AppError> {
+    let restore = client
+        .set_video_settings(SetVideoSettings {
+            fps_numerator: Some(original.fps_numerator),
+            fps_denominator: Some(original.fps_denominator),
+            base_width: Some(original.base_width),
+            base_height: Some(original.base_height),
+            output_width: Some(original.output_width),
+            output_height: Some(original.output_height),
+        })
+        .await;
+    if let Err(e) = &restore {
+        tracing::warn!(target: "benchmark", error = %e, "ビデオ設定の復元に失敗");
+    }
+    restore
+}
+
+/// 録画を開始してフレームの処理時間を計測し、必ず録画停止とファイル削除を行う
+///
+/// # Returns
+/// （実際の計測時間（秒）, フレームカウンター差分, 1秒ごとのフレームの処理時間）
+async fn record_and_measure(
+    client: &ObsClient,
+    duration_secs: u8,
+    cancel: &mut watch::Receiver<bool>,
+) -> Result<(f64, FrameCounters, Vec<f64>), AppError> {
+    let start_frames = frame_counters(client).await?;
+    let started_at = Instant::now();
+    client.start_recording().await?;
+
+    let sampled = async {
+        let mut frame_times_ms = Vec::with_capacity(usize::from(duration_secs));
+        for _ in 0..duration_secs {
+            sleep_or_cancelled(SAMPLE_INTERVAL, cancel).await?;
+            frame_times_ms.push(client.get_stats().await?.average_frame_render_time);
+        }
+        Ok::<_, AppError>((frame_times_ms, frame_counters(client).await?))
+    }
+    .await;
+    let elapsed_secs = started_at.elapsed().as_secs_f64();
+
+    let stopped = stop_and_discard_recording(client).await;
+
+    let (frame_times_ms, end_frames) = sampled?;
+    stopped?;
+
+    Ok((elapsed_secs, end_frames.since(&start_frames), frame_times_ms))
+}
+
+/// 録画を開始して計測し、必ず録画停止とファイル削除を行う
+async fn record_and_sample(
+    client: &ObsClient,
+    duration_secs: u64,
+) -> Result<(Vec<BenchmarkSample>, FrameCounters), AppError> {
+    let start_frames = frame_counters(client).await?;
+    client.start_recording().await?;
+
+    let sampled = sample_metrics(client, duration_secs).await;
+
+    // 録画停止と一時ファイル削除（計測の成否に関わらず実行）
+    let stopped = stop_and_discard_recording(client).await;
+
+    let (samples, end_frames) = sampled?;
+    stopped?;
+
+    Ok((samples, end_frames.since(&start_frames)))
+}
+
+/// 録画を停止し、録画ファイルを削除する
+async fn stop_and_discard_recording(client: &ObsClient) -> Result<(), AppError> {
+    match client.stop_recording().await {
+        Ok(path) => {
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                tracing::warn!(target: "benchmark", error = %e, path = %path, "ベンチマーク録画の削除に失敗");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!(target: "benchmark", error = %e, "ベンチマーク録画の停止に失敗");
+            Err(e)
+        }
+    }
+}
+
+/// 計測時間の間、1秒ごとにメトリクスを取得
+async fn sample_metrics(
+    client: &ObsClient,
+    duration_secs: u64,
+) -> Result<(Vec<BenchmarkSample>, FrameCounters), AppError> {
+    let service = system_monitor_service();
+    let mut samples = Vec::with_capacity(duration_secs as usize);
+
+    // 初回のCPU使用率は0になることがあるため、先に1回更新しておく
+    let _ = service.get_cpu_usage();
+
+    for _ in 0..duration_secs {
+        tokio::time::sleep(SAMPLE_INTERVAL).await;
+
+        let gpu = service.get_gpu_metrics().ok().flatten();
+        let stats = client.get_stats().await?;
+        samples.push(BenchmarkSample {
+            cpu_usage: service.get_cpu_usage()?,
+            gpu_usage: gpu.as_ref().map(|g| g.usage_percent),
+            encoder_usage: gpu.as_ref().and_then(|g| g.encoder_usage),
+            average_frame_render_time_ms: stats.average_frame_render_time,
+        });
+    }
+
+    Ok((samples, frame_counters(client).await?))
+}
+
+/// 現在のフレームカウンターを取得
+async fn frame_counters(client: &ObsClient) -> Result<FrameCounters, AppError> {
+    let stats = client.get_stats().await?;
+    Ok(FrameCounters {
+        render_skipped: stats.render_skipped_frames,
+        render_total: stats.render_total_frames,
+        output_skipped: stats.output_skipped_frames,
+        output_total: stats.output_total_frames,
+    })
+}
@@ -1,8 +1,17 @@
 // 設定管理コマンド
 
 use crate::error::AppError;
-use crate::storage::config::AppConfig;
+use crate::services::detected_hardware::clear_detected_hardware_cache;
+use crate::services::schema::app_config_schema;
+use crate::storage::config::{AppConfig, Language};
 use crate::storage::{load_config, save_config};
+use once_cell::sync::Lazy;
+use std::sync::{Mutex, PoisonError};
+
+/// 設定の読み込み・マージ・保存を直列化するロック
+///
+/// 複数の画面から同時に保存された場合も、互いの変更を上書きしないようにする
+pub(crate) static CONFIG_SAVE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 /// 設定を取得
 #[tauri::command]
@@ -11,7 +20,56 @@ pub async fn get_config() -> Result<AppConfig, AppError> {
 }
 
 /// 設定を保存
+///
+/// 渡された設定ですべてのフィールドを置き換える。一部のフィールドだけを変更する場合は `patch_app_config` を使う
 #[tauri::command]
 pub async fn save_app_config(config: AppConfig) -> Result<(), AppError> {
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
     save_config(&config)
 }
+
+/// 設定を部分更新して保存
+///
+/// `patch` はJSON Merge Patch（RFC 7396）として保存済みの設定に適用する。
+/// 指定したフィールドだけを変更し、nullを指定したフィールドは初期値に戻す。
+/// 検証エラーの場合は `VALIDATION_FAILED`（`details.errors` に項目ごとのメッセージ）。
+///
+/// # Returns
+/// 保存後の設定
+#[tauri::command]
+pub async fn patch_app_config(patch: serde_json::Value) -> Result<AppConfig, AppError> {
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let merged = load_config()?.merge(patch).map_err(|errors| {
+        AppError::validation_failed(&format!("設定が不正です: {}", errors.join(", ")))
+            .with_details(serde_json::json!({ "errors": errors }))
+    })?;
+    save_config(&merged)?;
+    Ok(merged)
+}
+
+/// 表示言語を変更
+///
+/// 推奨理由を新しい言語で作り直せるよう、検出済みハードウェア情報のキャッシュも破棄する
+///
+/// # Returns
+/// 保存後の設定
+#[tauri::command]
+pub async fn set_language(language: Language) -> Result<AppConfig, AppError> {
+    let config = {
+        let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut config = load_config()?;
+        config.display.language = language;
+        save_config(&config)?;
+        config
+    };
+
+    clear_detected_hardware_cache().await;
+    Ok(config)
+}
+
+/// 設定（`AppConfig`）のJSON Schemaを取得
+#[tauri::command]
+pub async fn get_config_schema() -> Result<serde_json::Value, AppError> {
+    Ok(app_config_schema())
+}
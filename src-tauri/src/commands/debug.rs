@@ -0,0 +1,66 @@
+// デバッグ記録コマンド
+//
+// 不定期な問題の調査用に、デバッグ記録の切り替えとバンドル出力を提供する
+
+use crate::commands::export::build_diagnostic_report;
+use crate::error::AppError;
+use crate::services::debug_recorder::{self, configure_debug_recording, DebugRecordingStatus};
+use crate::services::performance_stats::{self, AppPerformanceStats};
+use crate::storage::config::{load_config, save_config};
+
+/// デバッグ記録の有効・無効を切り替え
+///
+/// 設定は保存され、次回起動時も引き継がれる
+///
+/// # Arguments
+/// * `enabled` - 記録を有効にするか
+/// * `max_file_size_kb` - 1ファイルあたりの最大サイズ（KB、省略時は現在の設定）
+/// * `max_files` - 保持するファイル数（省略時は現在の設定）
+#[tauri::command]
+pub async fn set_debug_recording(
+    enabled: bool,
+    max_file_size_kb: Option<u64>,
+    max_files: Option<usize>,
+) -> Result<DebugRecordingStatus, AppError> {
+    let mut config = load_config()?;
+    config.debug_recording.enabled = enabled;
+    if let Some(max_file_size_kb) = max_file_size_kb {
+        config.debug_recording.max_file_size_kb = max_file_size_kb;
+    }
+    if let Some(max_files) = max_files {
+        config.debug_recording.max_files = max_files;
+    }
+
+    configure_debug_recording(&config.debug_recording)?;
+    save_config(&config)?;
+
+    tracing::info!(target: "debug_recorder", enabled, "デバッグ記録を切り替えました");
+    debug_recorder::get_debug_recording_status(&config.debug_recording)
+}
+
+/// デバッグ記録の状態を取得
+#[tauri::command]
+pub async fn get_debug_recording_status() -> Result<DebugRecordingStatus, AppError> {
+    let config = load_config()?;
+    debug_recorder::get_debug_recording_status(&config.debug_recording)
+}
+
+/// アプリ自身のパフォーマンス統計を取得
+///
+/// コマンドと監視処理の段階ごとの所要時間・成功/失敗回数（起動からの集計）
+#[tauri::command]
+pub async fn get_app_performance_stats() -> Result<AppPerformanceStats, AppError> {
+    Ok(performance_stats::get_app_performance_stats())
+}
+
+/// 直近の記録ファイル・診断レポート・パフォーマンス統計をZIPにまとめる
+///
+/// # Returns
+/// 作成したZIPファイルのパス
+#[tauri::command]
+pub async fn export_debug_bundle() -> Result<String, AppError> {
+    let config = load_config()?;
+    let report = build_diagnostic_report().await?;
+    let path = debug_recorder::export_debug_bundle(&config.debug_recording, &report)?;
+    Ok(path.display().to_string())
+}
@@ -2,9 +2,16 @@
 //
 // セッションデータと診断レポートをエクスポートするTauriコマンド
 
+use crate::commands::optimizer::calculate_recommendations;
+use crate::commands::utils::history_store;
 use crate::error::AppError;
-use crate::services::exporter::{ReportExporter, DiagnosticReport};
+use crate::monitor::power::get_power_status;
+use crate::obs::get_obs_settings;
+use crate::services::exporter::{
+    month_bounds, week_bounds, DiagnosticReport, MonthlyReport, ReportExporter, ReportFormat, WeeklyReport,
+};
 use crate::services::analyzer::ProblemAnalyzer;
+use crate::services::schema::write_schemas;
 use crate::storage::metrics_history::{SessionSummary, HistoricalMetrics};
 use serde::Deserialize;
 
@@ -36,6 +43,18 @@ pub struct ExportCsvResponse {
     pub filename: String,
 }
 
+/// 診断レポートのレスポンス
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticReportResponse {
+    /// 診断レポート
+    pub report: DiagnosticReport,
+    /// 指定形式に変換したデータ
+    pub data: String,
+    /// ファイル名
+    pub filename: String,
+}
+
 /// セッションをJSON形式でエクスポート
 ///
 /// # Arguments
@@ -47,9 +66,9 @@ pub struct ExportCsvResponse {
 pub async fn export_session_json(request: ExportSessionRequest) -> Result<ExportJsonResponse, AppError> {
     let exporter = ReportExporter::new();
 
-    // TODO: 実際のデータベースから取得
-    // 現在はダミーデータを使用
-    let session_summary = create_dummy_session_summary(&request.session_id);
+    // セッション情報（タイトル・タグ・メモを含む）は履歴データベースから取得
+    // TODO: メトリクス履歴の永続化後、メトリクスもデータベースから取得
+    let session_summary = load_session_summary(&request.session_id).await;
     let metrics_history = create_dummy_metrics_history(&request.session_id);
 
     let json_data = exporter.export_session_json(&session_summary, &metrics_history)?;
@@ -93,6 +112,51 @@ pub async fn export_session_csv(request: ExportSessionRequest) -> Result<ExportC
 /// 診断レポート
 #[tauri::command]
 pub async fn generate_diagnostic_report() -> Result<DiagnosticReport, AppError> {
+    build_diagnostic_report().await
+}
+
+/// 診断レポートを指定形式でエクスポート
+///
+/// # Arguments
+/// * `format` - 出力形式（JSON / CSV / Markdown）
+///
+/// # Returns
+/// 診断レポートと指定形式に変換したデータ
+#[tauri::command]
+pub async fn export_diagnostic_report(format: ReportFormat) -> Result<DiagnosticReportResponse, AppError> {
+    let exporter = ReportExporter::new();
+    let report = build_diagnostic_report().await?;
+
+    let data = exporter.export_diagnostic_report(&report, format)?;
+    let filename = format!("obs_diagnostic_{}.{}", report.generated_at, format.extension());
+
+    Ok(DiagnosticReportResponse {
+        report,
+        data,
+        filename,
+    })
+}
+
+/// エクスポート形式のJSON Schemaをディレクトリに書き出す（開発者向け）
+///
+/// 診断レポート・セッションエクスポート・履歴メトリクス・設定分析結果のスキーマを
+/// `<name>.v<version>.schema.json` として書き出す。
+///
+/// # Arguments
+/// * `dir` - 出力先ディレクトリ（存在しない場合は作成する）
+///
+/// # Returns
+/// 書き出したファイルのパス
+#[tauri::command]
+pub async fn export_schemas(dir: String) -> Result<Vec<String>, AppError> {
+    let paths = write_schemas(std::path::Path::new(&dir))?;
+    Ok(paths.iter().map(|path| path.display().to_string()).collect())
+}
+
+/// 診断レポートを構築
+///
+/// OBSに接続されている場合は現在の設定と推奨設定も含める
+pub async fn build_diagnostic_report() -> Result<DiagnosticReport, AppError> {
     let exporter = ReportExporter::new();
     let analyzer = ProblemAnalyzer::new();
 
@@ -106,11 +170,98 @@ pub async fn generate_diagnostic_report() -> Result<DiagnosticReport, AppError>
         .map(|m| m.system.clone())
         .collect::<Vec<_>>());
 
-    let report = exporter.generate_diagnostic_report(&session_summary, &problems)?;
+    let mut report = exporter.generate_diagnostic_report(&session_summary, &problems)?;
+    let power = get_power_status().await;
+    report.system_info.power_source = power.source;
+    report.system_info.battery_percent = power.battery_percent;
+
+    // OBS未接続の場合は設定なしでレポートを作成
+    report.obs_settings = get_obs_settings().await.ok();
+    if report.obs_settings.is_some() {
+        report.recommended_settings = calculate_recommendations().await.ok();
+    }
 
     Ok(report)
 }
 
+/// 月次配信レポートを取得
+///
+/// 月の区切りはローカルタイムゾーンで判定する
+///
+/// # Arguments
+/// * `year` - 年
+/// * `month` - 月（1-12）
+#[tauri::command]
+pub async fn get_monthly_streaming_report(year: u32, month: u8) -> Result<MonthlyReport, AppError> {
+    let offset = *chrono::Local::now().offset();
+    let (from, to) = month_bounds(year, month, offset)?;
+
+    let sessions = history_store().await?.list_sessions_overlapping(from, to).await?;
+    Ok(ReportExporter::new().aggregate_monthly_report(year, month, from, to, &sessions))
+}
+
+/// 週次レポートのレスポンス
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReportResponse {
+    /// 週次レポート
+    pub report: WeeklyReport,
+    /// 指定形式に変換したデータ
+    pub data: String,
+    /// ファイル名
+    pub filename: String,
+}
+
+/// 週次配信レポートを生成
+///
+/// 週の区切り（月曜0時）はローカルタイムゾーンで判定する
+///
+/// # Arguments
+/// * `week_offset` - 何週前か（0 = 今週）
+/// * `format` - 出力形式（JSON / CSV / Markdown）
+#[tauri::command]
+pub async fn generate_weekly_report(week_offset: u32, format: ReportFormat) -> Result<WeeklyReportResponse, AppError> {
+    let now = chrono::Local::now();
+    let (week_start, week_end) = week_bounds(week_offset, now.timestamp(), *now.offset());
+
+    let store = history_store().await?;
+    // 前週との比較用に前週の開始から取得する
+    let sessions = store
+        .list_sessions_overlapping(week_start - (week_end - week_start), week_end)
+        .await?;
+    let alert_counts = store.count_alerts_in_range(week_start, week_end).await?;
+
+    let exporter = ReportExporter::new();
+    let report = exporter.aggregate_weekly_report(week_start, week_end, &sessions, &alert_counts);
+    let data = exporter.export_weekly_report(&report, format)?;
+    let filename = format!("obs_weekly_{}.{}", report.week_start, format.extension());
+
+    Ok(WeeklyReportResponse {
+        report,
+        data,
+        filename,
+    })
+}
+
+/// 履歴データベースからセッションを取得
+///
+/// 記録されていないセッションの場合はダミーデータを返す
+async fn load_session_summary(session_id: &str) -> SessionSummary {
+    let stored = match history_store().await {
+        Ok(store) => store.get_session(session_id).await,
+        Err(e) => Err(e),
+    };
+
+    match stored {
+        Ok(Some(summary)) => summary,
+        Ok(None) => create_dummy_session_summary(session_id),
+        Err(e) => {
+            tracing::warn!(target: "export", error = %e, "セッション情報の取得に失敗");
+            create_dummy_session_summary(session_id)
+        }
+    }
+}
+
 // ============================================================
 // ダミーデータ生成（テスト用）
 // ============================================================
@@ -126,6 +277,12 @@ fn create_dummy_session_summary(session_id: &str) -> SessionSummary {
         total_dropped_frames: 25,
         peak_bitrate: 6000,
         quality_score: 80.0,
+        title: None,
+        tags: Vec::new(),
+        notes: None,
+        platform: None,
+        avg_bitrate: 0,
+        bytes_uploaded: 0,
     }
 }
 
@@ -140,6 +297,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
             session_id: session_id.to_string(),
             system: SystemMetricsSnapshot {
                 cpu_usage: 50.0,
+                cpu_frequency_mhz: None,
                 memory_used: 8_000_000_000,
                 memory_total: 16_000_000_000,
                 gpu_usage: Some(60.0),
@@ -154,6 +312,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
                 render_dropped_frames: Some(10),
                 output_dropped_frames: Some(5),
                 stream_bitrate: Some(6000),
+                output_stats: None,
             },
         },
         HistoricalMetrics {
@@ -161,6 +320,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
             session_id: session_id.to_string(),
             system: SystemMetricsSnapshot {
                 cpu_usage: 55.0,
+                cpu_frequency_mhz: None,
                 memory_used: 8_500_000_000,
                 memory_total: 16_000_000_000,
                 gpu_usage: Some(65.0),
@@ -175,6 +335,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
                 render_dropped_frames: Some(15),
                 output_dropped_frames: Some(8),
                 stream_bitrate: Some(5800),
+                output_stats: None,
             },
         },
         HistoricalMetrics {
@@ -182,6 +343,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
             session_id: session_id.to_string(),
             system: SystemMetricsSnapshot {
                 cpu_usage: 60.0,
+                cpu_frequency_mhz: None,
                 memory_used: 9_000_000_000,
                 memory_total: 16_000_000_000,
                 gpu_usage: Some(70.0),
@@ -196,6 +358,7 @@ fn create_dummy_metrics_history(session_id: &str) -> Vec<HistoricalMetrics> {
                 render_dropped_frames: Some(20),
                 output_dropped_frames: Some(12),
                 stream_bitrate: Some(6100),
+                output_stats: None,
             },
         },
     ]
@@ -244,4 +407,20 @@ mod tests {
         assert!(report.performance.overall_score >= 0.0);
         assert!(report.performance.overall_score <= 100.0);
     }
+
+    #[tokio::test]
+    async fn test_export_diagnostic_report_json() {
+        let response = export_diagnostic_report(ReportFormat::Json).await.unwrap();
+        assert_eq!(response.report.session.session_id, "current");
+        assert!(response.data.contains("\"sessionId\""));
+        assert!(response.filename.ends_with(".json"));
+    }
+
+    #[tokio::test]
+    async fn test_export_diagnostic_report_markdown() {
+        let response = export_diagnostic_report(ReportFormat::Markdown).await.unwrap();
+        assert!(response.data.starts_with("# OBS配信 診断レポート"));
+        assert!(response.data.contains("## ハードウェア情報"));
+        assert!(response.filename.ends_with(".md"));
+    }
 }
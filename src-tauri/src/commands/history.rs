@@ -2,9 +2,27 @@
 //
 // メトリクス履歴とセッション情報を管理するTauriコマンド
 
+use crate::commands::config::CONFIG_SAVE_LOCK;
+use crate::commands::utils::history_store;
 use crate::error::AppError;
-use crate::storage::metrics_history::{HistoricalMetrics, SessionSummary};
+use crate::events::{emit_event, TauriEvent};
+use crate::services::alerts::update_data_quota_and_notify;
+use crate::services::analyzer::ProblemAnalyzer;
+use crate::services::data_usage::{load_current_month_usage, load_data_usage_summary, DataUsageSummary};
+use crate::services::streaming_overhead::{
+    load_overhead_deltas, load_session_overhead, OverheadDelta, StreamingOverheadSample,
+    DEFAULT_OVERHEAD_DELTA_LIMIT, MAX_OVERHEAD_DELTA_LIMIT,
+};
+use crate::services::post_stream_advisor::{
+    build_post_stream_report, PostStreamReport, PostStreamReportReady, QualityTimeline,
+};
+use crate::storage::config::{load_config, save_config};
+use crate::storage::metrics_history::{
+    HistoricalMetrics, MetricsResolution, SessionFilter, SessionSummary, DEFAULT_METRICS_MAX_POINTS,
+};
 use serde::Deserialize;
+use std::sync::PoisonError;
+use tauri::AppHandle;
 
 /// メトリクス取得リクエスト
 #[derive(Debug, Clone, Deserialize)]
@@ -16,82 +34,211 @@ pub struct GetMetricsRangeRequest {
     pub from: i64,
     /// 終了時刻（Unixタイムスタンプ）
     pub to: i64,
+    /// 時間解像度（省略時は点数が上限に収まる最も細かい解像度）
+    #[serde(default)]
+    pub resolution: Option<MetricsResolution>,
+    /// 解像度を省略した場合の点数の上限（省略時は1000）
+    #[serde(default)]
+    pub max_points: Option<u32>,
 }
 
 /// セッション一覧を取得
 ///
+/// # Arguments
+/// * `filter` - 絞り込み条件（タグ・開始日時の範囲・最小の長さ。省略時は全件）
+///
+/// # Returns
+/// セッションサマリーのリスト（開始時刻の新しい順）
+#[tauri::command]
+pub async fn get_sessions(filter: Option<SessionFilter>) -> Result<Vec<SessionSummary>, AppError> {
+    history_store()
+        .await?
+        .list_sessions(&filter.unwrap_or_default())
+        .await
+}
+
+/// セッションのメタデータ（タイトル・タグ・メモ）を更新
+///
+/// # Arguments
+/// * `session_id` - セッションID
+/// * `title` - タイトル（空の場合は未設定）
+/// * `tags` - タグ
+/// * `notes` - メモ（空の場合は未設定）
+///
 /// # Returns
-/// セッションサマリーのリスト
+/// 更新後のセッションサマリー
 #[tauri::command]
-pub async fn get_sessions() -> Result<Vec<SessionSummary>, AppError> {
-    // TODO: 実際のデータベースから取得
-    // 現在はダミーデータを返す
-    let now = chrono::Utc::now().timestamp();
-
-    Ok(vec![
-        SessionSummary {
-            session_id: "demo-session-1".to_string(),
-            start_time: now - 7200, // 2時間前
-            end_time: now - 3600,   // 1時間前
-            avg_cpu: 45.5,
-            avg_gpu: 62.3,
-            total_dropped_frames: 15,
-            peak_bitrate: 6200,
-            quality_score: 85.5,
-        },
-        SessionSummary {
-            session_id: "demo-session-2".to_string(),
-            start_time: now - 14400, // 4時間前
-            end_time: now - 10800,   // 3時間前
-            avg_cpu: 52.1,
-            avg_gpu: 68.7,
-            total_dropped_frames: 42,
-            peak_bitrate: 6500,
-            quality_score: 78.2,
-        },
-    ])
+pub async fn update_session_metadata(
+    session_id: String,
+    title: Option<String>,
+    tags: Vec<String>,
+    notes: Option<String>,
+) -> Result<SessionSummary, AppError> {
+    history_store()
+        .await?
+        .update_session_metadata(&session_id, title, tags, notes)
+        .await?
+        .ok_or_else(|| AppError::session_not_found(&session_id))
 }
 
 /// 指定期間のメトリクスを取得
 ///
+/// 長時間のセッションでは分・時間単位のロールアップ（区間ごとの平均値）を返し、
+/// グラフに渡す点数を抑える
+///
 /// # Arguments
-/// * `request` - セッションIDと期間の指定
+/// * `request` - セッションID・期間・時間解像度の指定
 ///
 /// # Returns
-/// 履歴メトリクスのリスト
+/// 履歴メトリクスのリスト（古い順）
 #[tauri::command]
 pub async fn get_metrics_range(
     request: GetMetricsRangeRequest,
 ) -> Result<Vec<HistoricalMetrics>, AppError> {
-    // TODO: 実際のデータベースから取得
-    // 現在は空のリストを返す
+    let GetMetricsRangeRequest { session_id, from, to, resolution, max_points } = request;
+    let store = history_store().await?;
+
+    let resolution = match resolution {
+        Some(resolution) => resolution,
+        None => {
+            let max_points = max_points.unwrap_or(DEFAULT_METRICS_MAX_POINTS);
+            store.select_metrics_resolution(&session_id, from, to, max_points).await?
+        }
+    };
+
+    store.get_metrics_range(&session_id, from, to, resolution).await
+}
+
+/// 配信後レポートを生成
+///
+/// セッション中のメトリクスから品質の推移と問題を算出し、次回の配信に向けた改善提案をまとめる。
+/// 生成後に `post-stream-report:ready` イベントを発行する
+///
+/// # Arguments
+/// * `session_id` - セッションID
+#[tauri::command]
+pub async fn generate_post_stream_report(
+    app_handle: AppHandle,
+    session_id: String,
+) -> Result<PostStreamReport, AppError> {
+    let store = history_store().await?;
+    let summary = store
+        .get_session(&session_id)
+        .await?
+        .ok_or_else(|| AppError::session_not_found(&session_id))?;
+
+    let resolution = store
+        .select_metrics_resolution(&session_id, summary.start_time, summary.end_time, DEFAULT_METRICS_MAX_POINTS)
+        .await?;
+    let metrics = store
+        .get_metrics_range(&session_id, summary.start_time, summary.end_time, resolution)
+        .await?;
+
+    let setup_type = load_config().map(|config| config.streaming_mode.setup_type).unwrap_or_default();
+    let analyzer = ProblemAnalyzer::new().with_setup_type(setup_type);
+    let snapshots: Vec<_> = metrics.iter().map(|metric| metric.system.clone()).collect();
+    let bitrates: Vec<u64> = metrics.iter().filter_map(|metric| metric.obs.stream_bitrate).collect();
+    let mut problems = analyzer.analyze_frame_drops(&snapshots);
+    problems.extend(analyzer.analyze_bitrate_issues(&bitrates, summary.effective_bitrate()));
+
+    let report = build_post_stream_report(&summary, &QualityTimeline::from_metrics(&metrics), &problems);
 
-    // パラメータを使用して警告を回避
-    let _ = request.session_id;
-    let _ = request.from;
-    let _ = request.to;
+    let payload = PostStreamReportReady { session_id };
+    if let Err(e) = emit_event(&app_handle, TauriEvent::PostStreamReportReady(payload)) {
+        tracing::warn!(target: "history", error = %e, "配信後レポート生成イベントの発行に失敗");
+    }
+
+    Ok(report)
+}
 
-    Ok(Vec::new())
+/// 月間のデータ使用量（アップロード量）を取得
+///
+/// 月の区切りはローカルタイムゾーンで判定し、月をまたぐセッションは開始した月に計上する
+///
+/// # Arguments
+/// * `year` - 年
+/// * `month` - 月（1〜12）
+#[tauri::command]
+pub async fn get_data_usage_summary(year: u32, month: u8) -> Result<DataUsageSummary, AppError> {
+    load_data_usage_summary(year, month).await
+}
+
+/// 月間の上限の集計を手動でリセット
+///
+/// 通信事業者の請求の締め日が月初でない場合に使う。
+/// リセット以降に始まったセッションだけを上限の集計対象にし、データ通信量アラートを更新する
+///
+/// # Returns
+/// リセット後の今月のデータ使用量
+#[tauri::command]
+pub async fn reset_monthly_quota(app_handle: AppHandle) -> Result<DataUsageSummary, AppError> {
+    {
+        let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut config = load_config()?;
+        config.quota_reset_at = Some(chrono::Utc::now().timestamp());
+        save_config(&config)?;
+    }
+
+    let summary = load_current_month_usage().await?;
+    update_data_quota_and_notify(&app_handle, summary.quota_remaining_gb).await?;
+    Ok(summary)
+}
+
+/// セッション中のOBSの負荷の履歴を取得
+///
+/// # Arguments
+/// * `session_id` - セッションID
+///
+/// # Returns
+/// OBSの負荷のサンプル（計測時刻の古い順）
+#[tauri::command]
+pub async fn get_session_overhead(session_id: String) -> Result<Vec<StreamingOverheadSample>, AppError> {
+    load_session_overhead(&session_id).await
+}
+
+/// 推奨設定の適用前後のOBSの負荷の変化を取得
+///
+/// 適用後の値は負荷が落ち着くまで待ってから計測するため、
+/// 適用直後は `after` と `summary` がnullになる
+///
+/// # Arguments
+/// * `limit` - 取得する件数（省略時は10件、最大100件）
+///
+/// # Returns
+/// 適用前後の負荷の変化（適用日時の新しい順）
+#[tauri::command]
+pub async fn get_overhead_deltas(limit: Option<u32>) -> Result<Vec<OverheadDelta>, AppError> {
+    load_overhead_deltas(limit.unwrap_or(DEFAULT_OVERHEAD_DELTA_LIMIT).min(MAX_OVERHEAD_DELTA_LIMIT)).await
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
     #[tokio::test]
-    async fn test_get_sessions() {
-        let result = get_sessions().await;
+    async fn test_get_sessions_applies_filter() {
+        let filter = SessionFilter {
+            min_duration_secs: Some(3_600),
+            ..SessionFilter::default()
+        };
+        let result = get_sessions(Some(filter)).await;
         assert!(result.is_ok());
 
         let sessions = result.expect("Failed to get sessions in test");
-        assert!(!sessions.is_empty());
-
-        // 最初のセッションの検証
-        let first = &sessions[0];
-        assert_eq!(first.session_id, "demo-session-1");
-        assert!(first.avg_cpu > 0.0);
-        assert!(first.quality_score > 0.0);
-        assert!(first.quality_score <= 100.0);
+        assert!(sessions.iter().all(|s| s.duration_secs() >= 3_600));
+    }
+
+    #[tokio::test]
+    async fn test_update_unknown_session_metadata() {
+        let result = update_session_metadata(
+            "unknown-session".to_string(),
+            Some("タイトル".to_string()),
+            Vec::new(),
+            None,
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), crate::error::ERROR_CODE_SESSION_NOT_FOUND);
     }
 
     #[tokio::test]
@@ -100,13 +247,15 @@ mod tests {
             session_id: "test-session".to_string(),
             from: 1000000,
             to: 2000000,
+            resolution: None,
+            max_points: None,
         };
 
         let result = get_metrics_range(request).await;
         assert!(result.is_ok());
 
         let metrics = result.expect("Failed to get metrics range in test");
-        // 現在は空のリストを返す実装
+        // 記録のないセッションは空
         assert!(metrics.is_empty());
     }
 }
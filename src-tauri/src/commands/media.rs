@@ -0,0 +1,33 @@
+// メディアソースの自動アクション管理コマンド
+
+use crate::commands::config::CONFIG_SAVE_LOCK;
+use crate::error::AppError;
+use crate::storage::config::MediaAutoAction;
+use crate::storage::{load_config, save_config};
+use std::sync::PoisonError;
+
+/// メディアソースの再生終了時の自動アクションを設定
+///
+/// 同じメディアソースの設定は置き換える。`MediaAutoAction::None` を指定すると設定を削除する。
+/// メディアソース名・切り替え先のシーン名が空の場合は `VALIDATION_FAILED`
+///
+/// # Arguments
+/// * `source_name` - OBSのメディアソース名
+/// * `action` - 再生終了時に実行するアクション
+#[tauri::command]
+pub async fn set_media_autoaction(source_name: String, action: MediaAutoAction) -> Result<(), AppError> {
+    if source_name.trim().is_empty() {
+        return Err(AppError::validation_failed("メディアソース名を指定してください"));
+    }
+    if matches!(&action, MediaAutoAction::SwitchScene(scene_name) if scene_name.trim().is_empty()) {
+        return Err(AppError::validation_failed("切り替え先のシーン名を指定してください"));
+    }
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.media_autoactions.retain(|(name, _)| *name != source_name);
+    if action != MediaAutoAction::None {
+        config.media_autoactions.push((source_name, action));
+    }
+    save_config(&config)
+}
@@ -10,6 +10,17 @@ pub mod analyzer;
 pub mod export;
 pub mod history;
 pub mod utils;
+pub mod validation;
+pub mod benchmark;
+pub mod startup;
+pub mod debug;
+pub mod telemetry;
+pub mod automation;
+pub mod shortcuts;
+pub mod platform;
+pub mod obs_files;
+pub mod observer;
+pub mod media;
 
 pub use system::*;
 pub use obs::*;
@@ -22,3 +33,13 @@ pub use streaming_mode::*;
 pub use analyzer::*;
 pub use export::*;
 pub use history::*;
+pub use benchmark::*;
+pub use startup::*;
+pub use debug::*;
+pub use telemetry::*;
+pub use automation::*;
+pub use shortcuts::*;
+pub use platform::*;
+pub use obs_files::*;
+pub use observer::*;
+pub use media::*;
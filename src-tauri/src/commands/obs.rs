@@ -2,16 +2,25 @@
 //
 // フロントエンドから呼び出されるOBS操作コマンド
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::Deserialize;
 use tauri::AppHandle;
 
 use crate::error::AppError;
 use crate::obs::{
-    ConnectionConfig, ConnectionState, ObsEventEmitter, ObsStatus,
-    ConnectionChangedPayload,
+    get_reconnect_manager, start_media_state_polling, stop_media_state_polling, AudioMonitorType, ConnectionConfig, ConnectionState, ObsEventEmitter,
+    ObsStatus, ConnectionChangedPayload, HealthCheckCallback, OutputStats, PingResult, SceneItem,
+};
+use crate::services::{get_streaming_mode_service, obs_service};
+use crate::services::alerts::{
+    emit_alert_notifications, low_battery_notification, suppress_alerts_for_trigger, SuppressionTrigger,
 };
-use crate::services::obs_service;
-use crate::storage::config::{load_config, save_config};
+use crate::services::debug_recorder::traced_command;
+use crate::services::audio_audit::AudioAuditResult;
+use crate::services::audio_monitor::AudioStatus;
+use crate::storage::config::{load_config, save_config, MonitoringConfig, StreamingProtocol};
 use crate::storage::credentials::{save_obs_password, get_obs_password, delete_obs_password};
 
 /// OBS接続パラメータ (フロントエンドからの入力)
@@ -60,10 +69,12 @@ pub async fn connect_obs(
     let previous_state = service.connection_state().await;
 
     // 接続実行（サービス層経由）
-    service.connect(config.clone()).await?;
+    traced_command("connect_obs", service.connect(config.clone())).await?;
 
     // 接続成功: 設定を保存
+    let mut health_check_interval_secs = MonitoringConfig::default().health_check_interval_secs;
     if let Ok(mut app_config) = load_config() {
+        health_check_interval_secs = app_config.monitoring.health_check_interval_secs;
         app_config.connection.last_host = config.host.clone();
         app_config.connection.last_port = config.port;
         app_config.connection.save_password = save_password;
@@ -95,6 +106,12 @@ pub async fn connect_obs(
         }
     }
 
+    // 定期ヘルスチェックを開始
+    start_health_check(app_handle.clone(), health_check_interval_secs).await;
+
+    // 自動アクションを設定したメディアソースの再生終了の検出を開始
+    start_media_state_polling(app_handle.clone()).await;
+
     // 接続成功イベントを発行
     let emitter = ObsEventEmitter::new(app_handle);
     if let Err(e) = emitter.emit_connection_changed(ConnectionChangedPayload {
@@ -109,6 +126,26 @@ pub async fn connect_obs(
     Ok(())
 }
 
+/// 定期ヘルスチェックを開始する
+///
+/// pingの結果で接続状態が変化した場合（Degraded・復帰・接続断）は`ConnectionChangedPayload`イベントを発行する
+async fn start_health_check(app_handle: AppHandle, interval_secs: u64) {
+    let on_state_changed: HealthCheckCallback = Arc::new(move |payload| {
+        let emitter = ObsEventEmitter::new(app_handle.clone());
+        if let Err(e) = emitter.emit_connection_changed(payload) {
+            tracing::warn!(target: "obs_client", error = %e, "Failed to emit connection_changed event");
+        }
+    });
+
+    get_reconnect_manager()
+        .start_health_check(
+            obs_service().client().clone(),
+            Duration::from_secs(interval_secs.max(1)),
+            on_state_changed,
+        )
+        .await;
+}
+
 /// OBS `WebSocketサーバーから切断`
 ///
 /// # Arguments
@@ -124,8 +161,13 @@ pub async fn disconnect_obs(app_handle: AppHandle) -> Result<(), AppError> {
     let previous_state = service.connection_state().await;
 
     // 切断実行（サービス層経由）
+    get_reconnect_manager().stop_health_check().await;
+    stop_media_state_polling().await;
     service.disconnect().await?;
 
+    // 切断後は仮想カメラの状態を追跡できないため、設定変更をブロックしない
+    get_streaming_mode_service().set_virtual_cam_active(false).await;
+
     // 切断イベントを発行
     let emitter = ObsEventEmitter::new(app_handle);
     if let Err(e) = emitter.emit_connection_changed(ConnectionChangedPayload {
@@ -154,6 +196,51 @@ pub async fn get_obs_status() -> Result<ObsStatus, AppError> {
     service.get_status().await
 }
 
+/// 配信出力の統計（送出・ドロップフレーム数、実測ビットレート、輻輳）を取得
+///
+/// 配信中モードの間は1秒ごとに自動取得され `obs:stats-update` イベントで通知されるが、
+/// このコマンドはその場でOBSに問い合わせる
+///
+/// # Returns
+/// 配信出力の統計。OBSが配信中でない場合は `VALIDATION_FAILED` エラー
+#[tauri::command]
+pub async fn get_live_output_stats() -> Result<OutputStats, AppError> {
+    crate::obs::poll_output_stats().await
+}
+
+/// OBS接続のヘルスチェック（ping）を実行
+///
+/// `GetVersion`リクエストの往復時間を計測する。
+/// 失敗した場合は接続状態をDisconnected（応答がないだけの場合は連続回数に応じてDegraded）に更新し、
+/// 接続状態が変化した場合は接続状態変化イベントを発行する
+///
+/// # Returns
+/// 往復時間とOBS/WebSocketのバージョン
+#[tauri::command]
+pub async fn ping_obs_connection(app_handle: AppHandle) -> Result<PingResult, AppError> {
+    let service = obs_service();
+    let previous_state = service.connection_state().await;
+
+    let result = service.ping().await;
+
+    let current_state = service.connection_state().await;
+    if current_state != previous_state {
+        if current_state == ConnectionState::Disconnected {
+            get_reconnect_manager().stop_health_check().await;
+        }
+        let emitter = ObsEventEmitter::new(app_handle);
+        if let Err(emit_err) = emitter.emit_connection_changed(ConnectionChangedPayload {
+            previous_state,
+            current_state,
+            host: None,
+            port: None,
+        }) {
+            tracing::warn!(target: "obs_client", error = %emit_err, "Failed to emit connection_changed event");
+        }
+    }
+    result
+}
+
 /// シーンリストを取得
 ///
 /// # Returns
@@ -166,12 +253,89 @@ pub async fn get_scene_list() -> Result<Vec<String>, AppError> {
 
 /// 現在のシーンを変更
 ///
+/// 切り替えの開始を `obs:scene-transition-started` で通知し、
+/// 切り替え中の一時的な負荷でアラートが発火しないよう抑制する
+///
 /// # Arguments
 /// * `scene_name` - 切り替え先のシーン名
 #[tauri::command]
-pub async fn set_current_scene(scene_name: String) -> Result<(), AppError> {
+pub async fn set_current_scene(app_handle: AppHandle, scene_name: String) -> Result<(), AppError> {
+    let service = obs_service();
+    service.set_current_scene(&scene_name).await?;
+
+    let emitter = ObsEventEmitter::new(app_handle);
+    if let Err(e) = emitter.emit_scene_transition_started(scene_name).await {
+        tracing::warn!(target: "obs_client", error = %e, "Failed to emit scene_transition_started event");
+    }
+
+    Ok(())
+}
+
+/// シーン内のアイテム一覧を取得
+///
+/// # Arguments
+/// * `scene_name` - 対象のシーン名
+#[tauri::command]
+pub async fn list_scene_items(scene_name: String) -> Result<Vec<SceneItem>, AppError> {
     let service = obs_service();
-    service.set_current_scene(&scene_name).await
+    service.list_scene_items(&scene_name).await
+}
+
+/// シーンアイテムの表示状態を取得
+///
+/// # Arguments
+/// * `scene_name` - 対象のシーン名
+/// * `item_id` - シーンアイテムID
+#[tauri::command]
+pub async fn get_scene_item_enabled(scene_name: String, item_id: i64) -> Result<bool, AppError> {
+    let service = obs_service();
+    service.get_scene_item_enabled(&scene_name, item_id).await
+}
+
+/// シーンアイテムの表示・非表示を切り替え
+///
+/// # Arguments
+/// * `scene_name` - 対象のシーン名
+/// * `item_id` - シーンアイテムID
+/// * `enabled` - 表示する場合は `true`
+#[tauri::command]
+pub async fn set_scene_item_enabled(
+    scene_name: String,
+    item_id: i64,
+    enabled: bool,
+) -> Result<(), AppError> {
+    let service = obs_service();
+    service.set_scene_item_enabled(&scene_name, item_id, enabled).await
+}
+
+/// ブラウザソースのURLを取得
+///
+/// # Arguments
+/// * `source_name` - ブラウザソースの入力名
+#[tauri::command]
+pub async fn get_browser_source_url(source_name: String) -> Result<String, AppError> {
+    let service = obs_service();
+    service.get_browser_source_url(&source_name).await
+}
+
+/// ブラウザソースのURLを変更
+///
+/// チャンネル変更時などにチャット・アラートのオーバーレイURLを差し替える
+///
+/// # Arguments
+/// * `source_name` - ブラウザソースの入力名
+/// * `url` - 新しいURL（http:// / https:// / file://）
+/// * `refresh_cache` - 変更後にキャッシュを無視して再読み込みする場合は `true`（省略時は `false`）
+#[tauri::command]
+pub async fn update_browser_source_url(
+    source_name: String,
+    url: String,
+    refresh_cache: Option<bool>,
+) -> Result<(), AppError> {
+    let service = obs_service();
+    service
+        .set_browser_source_url(&source_name, &url, refresh_cache.unwrap_or(false))
+        .await
 }
 
 /// 配信を開始
@@ -180,6 +344,19 @@ pub async fn start_streaming(app_handle: AppHandle) -> Result<(), AppError> {
     let service = obs_service();
     service.start_streaming().await?;
 
+    // バッテリー残量が少ない場合はアプリ内に通知（問題一覧にはanalyze_problemsで表示される）
+    let power = crate::monitor::power::get_power_status().await;
+    if power.is_low_battery() {
+        tracing::warn!(
+            target: "obs_client",
+            battery_percent = ?power.battery_percent,
+            "バッテリー残量が少ない状態で配信を開始しました"
+        );
+        if let Err(e) = emit_alert_notifications(&app_handle, &[low_battery_notification(power.battery_percent)]) {
+            tracing::warn!(target: "obs_client", error = %e, "Failed to emit low battery notification");
+        }
+    }
+
     // 配信開始イベントを発行
     let emitter = ObsEventEmitter::new(app_handle);
     if let Err(e) = emitter.emit_streaming_changed(crate::obs::StreamingChangedPayload {
@@ -211,10 +388,13 @@ pub async fn stop_streaming(app_handle: AppHandle) -> Result<(), AppError> {
 }
 
 /// 録画を開始
+///
+/// 録画の開始直後はエンコーダーの初期化で負荷が上がるため、抑制ルールに従ってアラートを抑制する
 #[tauri::command]
 pub async fn start_recording(app_handle: AppHandle) -> Result<(), AppError> {
     let service = obs_service();
     service.start_recording().await?;
+    suppress_alerts_for_trigger(SuppressionTrigger::RecordingStart).await;
 
     // 録画開始イベントを発行
     let emitter = ObsEventEmitter::new(app_handle);
@@ -249,6 +429,37 @@ pub async fn stop_recording(app_handle: AppHandle) -> Result<String, AppError> {
     Ok(path)
 }
 
+/// 仮想カメラを開始
+///
+/// 仮想カメラの動作中は映像設定を変更できないため、設定適用の可否判定にも反映する
+#[tauri::command]
+pub async fn start_virtual_cam() -> Result<(), AppError> {
+    let service = obs_service();
+    service.start_virtual_cam().await?;
+    get_streaming_mode_service().set_virtual_cam_active(true).await;
+    Ok(())
+}
+
+/// 仮想カメラを停止
+#[tauri::command]
+pub async fn stop_virtual_cam() -> Result<(), AppError> {
+    let service = obs_service();
+    service.stop_virtual_cam().await?;
+    get_streaming_mode_service().set_virtual_cam_active(false).await;
+    Ok(())
+}
+
+/// 仮想カメラが有効かを取得
+///
+/// OBS側で直接切り替えられた場合に備え、取得した状態で設定適用の可否判定も更新する
+#[tauri::command]
+pub async fn get_virtual_cam_status() -> Result<bool, AppError> {
+    let service = obs_service();
+    let active = service.get_virtual_cam_status().await?;
+    get_streaming_mode_service().set_virtual_cam_active(active).await;
+    Ok(active)
+}
+
 /// 保存された接続情報を取得
 ///
 /// # Returns
@@ -307,7 +518,7 @@ pub async fn get_obs_profile_parameter(
 
     let client = get_obs_client();
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     client.get_profile_parameter(&category, &name).await
@@ -329,10 +540,12 @@ pub async fn set_obs_profile_parameter(
 
     let client = get_obs_client();
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
-    client.set_profile_parameter(&category, &name, Some(&value)).await
+    client.set_profile_parameter(&category, &name, Some(&value)).await?;
+    crate::obs::settings_cache::invalidate_obs_settings_cache().await;
+    Ok(())
 }
 
 /// 現在のOBSプロファイル名を取得
@@ -342,7 +555,7 @@ pub async fn get_current_obs_profile() -> Result<String, AppError> {
 
     let client = get_obs_client();
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     client.get_current_profile().await
@@ -355,8 +568,145 @@ pub async fn get_obs_profile_list() -> Result<Vec<String>, AppError> {
 
     let client = get_obs_client();
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     client.get_profile_list().await
 }
+
+/// 接続中のOBSが対応する配信プロトコルを取得
+///
+/// OBS 29以降はSRT/RISTに対応する。バージョンを取得できない場合はRTMP/RTMPSのみを返す
+#[tauri::command]
+pub async fn get_supported_protocols() -> Result<Vec<StreamingProtocol>, AppError> {
+    use crate::obs::get_obs_client;
+
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    let status = client.get_status().await?;
+    let obs_version = status.obs_version.unwrap_or_default();
+    Ok(StreamingProtocol::supported_by_obs(&obs_version))
+}
+
+/// 音声ソースの状態を取得
+///
+/// 各音声入力のミュート状態と音量、監視対象のマイクが無音かを返す。
+/// マイクは設定の `microphoneInputName` を優先し、未設定時は自動検出する
+#[tauri::command]
+pub async fn get_audio_status() -> Result<AudioStatus, AppError> {
+    use crate::obs::get_obs_client;
+
+    if !get_obs_client().is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    crate::services::audio_monitor::get_audio_status().await
+}
+
+/// 音声ソースを監査
+///
+/// マイク・デスクトップ音声の入力ごとに、ミュート状態・音量・現在のシーンに含まれるかを返す。
+/// 現在のシーンに有効なマイクがない、デスクトップ音声が全てミュート、
+/// シーンの音声ソースが「モニターのみ」の場合は `warnings` に含める
+#[tauri::command]
+pub async fn audit_audio_sources() -> Result<AudioAuditResult, AppError> {
+    use crate::obs::get_obs_client;
+
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    crate::services::audio_audit::audit_audio_sources(&client).await
+}
+
+/// 音声ソースのモニタリングの種類を設定
+///
+/// 配信中でも変更できる（歌・演奏配信で自分の音が聞こえない場合にすぐ直せるように）
+///
+/// # Arguments
+/// * `source_name` - 音声入力名
+/// * `monitor_type` - モニタリングの種類
+#[tauri::command]
+pub async fn set_audio_monitor_type(source_name: String, monitor_type: AudioMonitorType) -> Result<(), AppError> {
+    use crate::obs::get_obs_client;
+
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    client.set_audio_monitor_type(&source_name, monitor_type).await
+}
+
+/// 全ての音声入力のモニタリングを一括で切り替える（プライバシーモード）
+///
+/// `enabled` がfalseの場合、モニタリング中の音声入力を全てモニターオフにし、
+/// 元の種類を保存する。trueの場合は保存した種類に戻す
+///
+/// # Arguments
+/// * `enabled` - モニタリングを有効にするか
+///
+/// # Returns
+/// モニタリングの種類を変更した入力名
+#[tauri::command]
+pub async fn set_all_audio_monitoring(enabled: bool) -> Result<Vec<String>, AppError> {
+    use crate::obs::get_obs_client;
+    use crate::services::audio_privacy::{disable_all_monitoring, restore_monitoring};
+
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    if enabled {
+        restore_monitoring(&client).await
+    } else {
+        disable_all_monitoring(&client).await
+    }
+}
+
+/// プライバシーモードで無効にした音声モニタリングを元に戻す
+///
+/// # Returns
+/// モニタリングを元に戻した入力名
+#[tauri::command]
+pub async fn restore_audio_monitoring_state() -> Result<Vec<String>, AppError> {
+    set_all_audio_monitoring(true).await
+}
+
+/// 音声モニタリングの遅延補正を設定
+///
+/// 補正値は現在のOBSプロファイルに保存し、アプリの設定（`audioMonitoring.monitoringOffsetMs`）にも反映する
+///
+/// # Arguments
+/// * `offset_ms` - 遅延補正（ミリ秒、-950〜20000）
+#[tauri::command]
+pub async fn set_audio_monitoring_offset(offset_ms: i32) -> Result<(), AppError> {
+    use crate::commands::config::CONFIG_SAVE_LOCK;
+    use crate::obs::get_obs_client;
+    use crate::services::monitoring_offset::{write_monitoring_offset, MONITORING_OFFSET_RANGE_MS};
+    use std::sync::PoisonError;
+
+    if !MONITORING_OFFSET_RANGE_MS.contains(&offset_ms) {
+        return Err(AppError::validation_failed(&format!(
+            "遅延補正は{}〜{}msの範囲で指定してください",
+            MONITORING_OFFSET_RANGE_MS.start(),
+            MONITORING_OFFSET_RANGE_MS.end()
+        )));
+    }
+
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+    write_monitoring_offset(&client, offset_ms).await?;
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.audio_monitoring.monitoring_offset_ms = offset_ms;
+    save_config(&config)
+}
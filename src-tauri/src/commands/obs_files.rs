@@ -0,0 +1,58 @@
+// OBSのプロファイル・シーンコレクションのファイルのバックアップのコマンド
+//
+// 設定バックアップと同じIDで保存されたOBSのファイルの確認と書き戻しを提供する
+
+use crate::error::AppError;
+use crate::monitor::process::get_obs_process_metrics;
+use crate::obs::get_obs_client;
+use crate::services::debug_recorder::traced_command;
+use crate::services::obs_file_backup::{self, obs_file_backups_dir, ObsFileBackup, ObsFileRestoreResult};
+use crate::services::obs_log::obs_config_dir;
+use crate::services::{check_restore_safety, get_operation_lock, OperationKind};
+
+/// 設定バックアップに含まれるOBSのファイルを取得
+///
+/// # Returns
+/// バックアップしたファイルの一覧（OBSのファイルをバックアップしていない場合はNone）
+#[tauri::command]
+pub async fn get_obs_file_backup(backup_id: String) -> Result<Option<ObsFileBackup>, AppError> {
+    obs_file_backup::load_obs_file_backup(&obs_file_backups_dir()?, &backup_id)
+}
+
+/// バックアップしたOBSのプロファイル・シーンコレクションのファイルを書き戻す
+///
+/// 配信・録画・仮想カメラの出力中は `OBS_OUTPUT_ACTIVE` エラー。
+/// OBSの起動中に書き戻した場合は結果の `restart_required` がtrueになり、
+/// OBSを再起動するまで反映されない。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
+///
+/// # Arguments
+/// * `backup_id` - 設定バックアップのID
+#[tauri::command]
+pub async fn restore_obs_files(backup_id: String) -> Result<ObsFileRestoreResult, AppError> {
+    let _operation = get_operation_lock().try_acquire(OperationKind::RestoreBackup)?;
+
+    let restore = async {
+        let client = get_obs_client();
+        let status = if client.is_connected().await {
+            Some(client.get_status().await?)
+        } else {
+            None
+        };
+        // プロセスを確認できない場合は起動中とみなし、再起動を促す
+        let obs_running = get_obs_process_metrics().map_or(true, |metrics| metrics.main_process.is_some());
+        let restart_required = check_restore_safety(obs_running, status.as_ref())?;
+
+        let result =
+            obs_file_backup::restore_obs_files(&obs_config_dir()?, &obs_file_backups_dir()?, &backup_id, restart_required)?;
+        tracing::info!(
+            target: "optimization",
+            backup_id = %backup_id,
+            restart_required,
+            "OBSのファイルを復元しました"
+        );
+        Ok(result)
+    };
+
+    traced_command("restore_obs_files", restore).await
+}
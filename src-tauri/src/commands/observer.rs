@@ -0,0 +1,44 @@
+// オブザーバーモードのコマンド
+//
+// モデレーター・ヘルパー向けの読み取り専用HTTPサーバーの起動・停止を提供する。
+// サーバーはこれらのコマンドで起動した場合のみ待ち受ける
+
+use crate::error::AppError;
+use crate::services::observer_server::{observer_status, start_observer, stop_observer, ObserverServerInfo};
+use crate::storage::config::load_config;
+
+/// オブザーバーモードを開始
+///
+/// 起動中の場合は停止してから起動し直す。アクセストークンはアプリの起動ごとに変わり、
+/// 停止・再開しても同じ起動中は変わらない。
+/// 待ち受けアドレスが不正な場合は `VALIDATION_FAILED`、
+/// 使用中などで待ち受けできない場合は `OBSERVER_SERVER` エラー。
+///
+/// # Arguments
+/// * `bind_address` - 待ち受けアドレス（省略時は設定の `observer.bindAddress`）
+#[tauri::command]
+pub async fn start_observer_server(bind_address: Option<String>) -> Result<ObserverServerInfo, AppError> {
+    let bind_address = match bind_address {
+        Some(address) => address,
+        None => load_config()?.observer.bind_address,
+    };
+    start_observer(&bind_address).await
+}
+
+/// オブザーバーモードを停止
+///
+/// # Returns
+/// 起動していたサーバーを停止した場合はtrue
+#[tauri::command]
+pub async fn stop_observer_server() -> Result<bool, AppError> {
+    Ok(stop_observer().await)
+}
+
+/// オブザーバーモードの状態を取得
+///
+/// # Returns
+/// 起動中のサーバーの情報（停止中の場合はNone）
+#[tauri::command]
+pub async fn get_observer_server_status() -> Result<Option<ObserverServerInfo>, AppError> {
+    Ok(observer_status().await)
+}
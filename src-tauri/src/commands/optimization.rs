@@ -3,13 +3,35 @@
 // 推奨設定をOBSに一括適用する機能
 
 use crate::commands::utils::get_hardware_info;
+use crate::commands::validation::validate_recommendation_input;
 use crate::error::AppError;
-use crate::obs::{get_obs_client, get_obs_settings};
-use crate::services::{get_streaming_mode_service, RecommendationEngine};
-use crate::storage::config::{load_config, StreamingPlatform, StreamingStyle};
+use crate::obs::{
+    get_obs_client, get_obs_settings, get_output_mode, invalidate_obs_settings_cache, set_output_mode,
+    ObsOutputMode, ObsSettings, SettingsValidationWarning, VideoApplyResult,
+};
+use crate::services::debug_recorder::traced_command;
+use crate::services::request_context::{in_request_span, RequestContext};
+use crate::services::obs::validate_stream_destination;
+use crate::services::obs_log::reported_video_encoders;
+use crate::services::optimizer::{DownscaleFilter, RecordingContainer};
+use crate::services::record_encoder::{
+    simple_recording_quality, write_current_record_encoder_bitrate, write_current_record_encoder_quality,
+    SIMPLE_SHARED_RECORDING_QUALITY,
+};
+use crate::services::encoder_fallback::{apply_with_fallback, EncoderFallback};
+use crate::services::streaming_overhead::{capture_overhead_baseline, save_overhead_baseline, StreamingOverhead};
+use crate::obs::ObsStatus;
+use crate::services::{
+    ensure_no_blocking_outputs, get_force_token_store, get_operation_lock, get_streaming_mode_service,
+    restrict_to_live_safe, CurrentOperation, EncoderSelectionContext, ForceApplyGrant, ForceToken,
+    OperationKind, OutputTarget, RecommendationEngine, RecommendedSettings, apply_audio_inputs,
+    backup_current_obs_files, capture_audio_inputs,
+};
+use crate::storage::config::{load_config, StreamingPlatform, StreamingProtocol, StreamingStyle};
+use crate::storage::profiles::AudioInputSettings;
 use crate::storage::{
-    get_profile, get_profiles, save_profile as storage_save_profile, ProfileSettings,
-    SettingsProfile,
+    get_profile, get_profiles, save_profile as storage_save_profile, BackupTrigger,
+    ProfileSettings, SettingsProfile,
 };
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +47,8 @@ pub struct BackupInfo {
     pub description: String,
     /// バックアップした設定
     pub settings: ProfileSettings,
+    /// 作成契機
+    pub trigger: BackupTrigger,
 }
 
 /// 最適化結果（TypeScriptのOptimizationResultに対応）
@@ -37,107 +61,341 @@ pub struct OptimizationResult {
     pub failed_count: usize,
     /// エラーメッセージ（失敗時）
     pub errors: Vec<String>,
+    /// 推奨エンコーダーを適用できずフォールバックした場合の記録
+    pub encoder_fallback: Option<EncoderFallback>,
+    /// 強制適用トークンを使用して適用したか
+    pub forced: bool,
+    /// 配信中の強制適用で適用を見送った項目の説明
+    pub skipped: Vec<String>,
+    /// 配信中の強制適用でプロファイルに保存したが、出力の再起動（配信の停止・再開）まで反映されない項目の説明
+    ///
+    /// これらの項目は `applied_count` に含めない
+    pub pending_restart: Vec<String>,
+    /// リクエストID（ログとの照合用。不具合報告に添付する）
+    pub request_id: Option<uuid::Uuid>,
+    /// 適用前のOBSの負荷の基準値ID（`get_overhead_deltas` で適用前後の変化を確認できる。
+    /// 適用前の負荷を計測していなかった場合はNone）
+    pub overhead_baseline_id: Option<String>,
+    /// ビデオ設定の読み戻しによる項目ごとの検証結果（ビデオ設定を適用しなかった場合はNone）
+    pub video_verification: Option<VideoApplyResult>,
+    /// 確認を促す警告（配信先とプラットフォーム設定の食い違い等。適用自体は行われている）
+    pub warnings: Vec<String>,
+}
+
+/// 適用時の配信中チェックの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApplyGuard {
+    /// 配信中・仮想カメラ有効中は適用しない
+    Normal,
+    /// 強制適用（`output_active` は実行開始時点で出力が有効だったか）
+    Forced { output_active: bool },
+}
+
+impl ApplyGuard {
+    /// OBSの出力状態を確認し、出力を止めない項目だけに絞り込む必要があるかを返す
+    ///
+    /// 通常の適用では出力が有効な場合に `OBS_OUTPUT_ACTIVE` エラー
+    fn check_outputs(self, status: &ObsStatus) -> Result<bool, AppError> {
+        match self {
+            Self::Normal => {
+                ensure_no_blocking_outputs(status)?;
+                Ok(false)
+            }
+            Self::Forced { output_active } => {
+                Ok(output_active || status.streaming || status.virtual_cam_active)
+            }
+        }
+    }
+
+    /// 強制適用か
+    const fn is_forced(self) -> bool {
+        matches!(self, Self::Forced { .. })
+    }
+}
+
+/// 強制適用トークンの有無に応じて、配信中チェック付きまたは強制で操作を実行
+///
+/// トークンは操作の前に検証・消費する（無効・失効時は `VALIDATION_FAILED` エラー）
+async fn execute_guarded<F, Fut>(
+    force: Option<ForceToken>,
+    operation: F,
+) -> Result<OptimizationResult, AppError>
+where
+    F: FnOnce(ApplyGuard) -> Fut,
+    Fut: std::future::Future<Output = Result<OptimizationResult, AppError>>,
+{
+    let streaming_service = get_streaming_mode_service();
+    let Some(token) = force else {
+        // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
+        return streaming_service
+            .execute_if_not_streaming(|| operation(ApplyGuard::Normal))
+            .await;
+    };
+
+    get_force_token_store().consume(&token, chrono::Utc::now().timestamp())?;
+    tracing::warn!(target: "optimization", "強制適用トークンを使用して配信中チェックを省略します");
+    streaming_service
+        .execute_forced(|output_active| operation(ApplyGuard::Forced { output_active }))
+        .await
+}
+
+/// 配信中の強制適用トークンを発行
+///
+/// 配信中に設定を適用するための2段階確認の1段階目。
+/// 返されたトークンを `apply_recommended_settings` / `apply_custom_settings` の `force` に渡すと、
+/// 配信中でも出力を止めない項目（ビットレート等）だけを適用する。
+/// トークンは60秒で失効し、1回の適用で消費される。
+#[tauri::command]
+pub async fn request_force_apply() -> Result<ForceApplyGrant, AppError> {
+    let grant = get_force_token_store().issue(chrono::Utc::now().timestamp());
+    tracing::warn!(
+        target: "optimization",
+        expires_at = grant.expires_at,
+        "配信中の強制適用が要求されました"
+    );
+    Ok(grant)
 }
 
 /// 推奨設定を一括適用
 ///
 /// 配信中は適用不可。TOCTOU競合条件を防ぐためロックを使用。
+/// OBSの配信先（サーバーURL・ストリームキー）が未設定の場合は `STREAM_DESTINATION_INVALID` エラー。
+/// 推奨エンコーダーを適用できなかった場合は利用可能なエンコーダーにフォールバックし、
+/// 結果の `encoder_fallback` に記録する。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
+/// `force` に `request_force_apply` のトークンを指定すると配信中でも適用するが、
+/// 出力の再起動が必要な項目（解像度・FPS・縮小フィルタ・エンコーダー・出力モード）は見送る。
+/// 適用結果の詳細が必要な場合は `apply_recommended_settings_with_result` を使用する。
 #[tauri::command]
-pub async fn apply_recommended_settings() -> Result<(), AppError> {
-    let streaming_service = get_streaming_mode_service();
+pub async fn apply_recommended_settings(force: Option<ForceToken>) -> Result<(), AppError> {
+    apply_recommended_settings_with_result(force).await.map(|_| ())
+}
 
-    // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
-    streaming_service
-        .execute_if_not_streaming(|| async {
-            // OBS接続確認
-            let client = get_obs_client();
-            if !client.is_connected().await {
-                return Err(AppError::obs_state("OBSに接続されていません"));
-            }
+/// 推奨設定を一括適用し、適用結果を返す
+///
+/// 動作は `apply_recommended_settings` と同じ。
+///
+/// # Returns
+/// 適用結果（適用数・エラー・エンコーダーのフォールバック・警告など）
+#[tauri::command]
+pub async fn apply_recommended_settings_with_result(
+    force: Option<ForceToken>,
+) -> Result<OptimizationResult, AppError> {
+    let ctx = RequestContext::new();
+    let mut result = in_request_span(
+        crate::request_span!("apply_recommended_settings", ctx),
+        run_apply_recommended_settings(force),
+    )
+    .await?;
+    result.request_id = Some(ctx.request_id);
+    Ok(result)
+}
 
-            // 現在の設定をバックアップ
-            backup_current_settings_internal().await?;
+/// 推奨設定の一括適用の本体
+async fn run_apply_recommended_settings(force: Option<ForceToken>) -> Result<OptimizationResult, AppError> {
+    let _operation = get_operation_lock().try_acquire(OperationKind::ApplyRecommendedSettings)?;
 
-            // 推奨設定を計算
-            let config = load_config()?;
-            let current_settings = get_obs_settings().await?;
-            let hardware = get_hardware_info().await;
+    let apply = execute_guarded(force, |guard| async move {
+        // OBS接続確認
+        let client = get_obs_client();
+        if !client.is_connected().await {
+            return Err(AppError::obs_not_connected());
+        }
 
-            // 推奨設定を計算
-            let recommendations = RecommendationEngine::calculate_recommendations(
-                &hardware,
-                &current_settings,
-                config.streaming_mode.platform,
-                config.streaming_mode.style,
-                config.streaming_mode.network_speed_mbps,
-            );
+        // OBS側で直接開始された配信・仮想カメラも検出する
+        let live = guard.check_outputs(&client.get_status().await?)?;
 
-            // 推奨設定をOBSに適用
-            crate::obs::settings::apply_video_settings(
-                recommendations.video.output_width,
-                recommendations.video.output_height,
-                recommendations.video.fps,
-            )
-            .await?;
+        // 配信先が未設定のまま適用すると、配信開始後に送信先がない状態になる
+        let destination_warnings = validate_stream_destination().await?;
 
-            // プロファイルパラメータでビットレート・プリセットを適用
-            apply_output_settings_via_profile(&client, &recommendations.output).await?;
+        // 推奨設定を計算
+        let config = load_config()?;
+        let current_settings = get_obs_settings().await?;
+        let hardware = get_hardware_info().await;
 
-            Ok(())
-        })
-        .await
+        // 推奨設定を計算
+        let mut recommendations = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current_settings),
+            config.streaming_mode.platform,
+            config.streaming_mode.style,
+            config.streaming_mode.network_speed_mbps,
+        );
+        RecommendationEngine::apply_stream_latency(&mut recommendations, &hardware, config.streaming_mode.latency);
+        let mut context = RecommendationEngine::encoder_selection_context(
+            &hardware,
+            config.streaming_mode.platform,
+            config.streaming_mode.style,
+            config.streaming_mode.network_speed_mbps,
+        );
+        // フォールバック先のエンコーダーにも遅延モード・配信環境の構成・HDRモードを反映する
+        context.latency = config.streaming_mode.latency;
+        context.setup_type = config.streaming_mode.setup_type;
+        context.hdr_mode = config.streaming_mode.hdr_mode;
+        RecommendationEngine::apply_setup_type(&mut recommendations, &context);
+        RecommendationEngine::apply_hdr_mode(&mut recommendations, &context);
+        let skipped = if live {
+            restrict_to_live_safe(&mut recommendations, &current_settings)
+        } else {
+            Vec::new()
+        };
+        tracing::info!(
+            target: "optimization",
+            encoder = %recommendations.output.encoder,
+            live,
+            skipped = skipped.len(),
+            "適用する推奨設定を決定"
+        );
+
+        // 適用後の設定に致命的な不整合がある場合は、OBSに書き込む前に中止する
+        ensure_no_critical_warnings(&settings_after_apply(&current_settings, &recommendations))?;
+
+        // 現在の設定をバックアップ
+        create_settings_backup(BackupTrigger::PreOptimization).await?;
+
+        let mut result = apply_recommendations(&client, recommendations, &context, live).await?;
+        result.forced = guard.is_forced();
+        result.skipped.splice(0..0, skipped);
+        result.warnings.extend(destination_warnings);
+        Ok(result)
+    });
+
+    let result = traced_command("apply_recommended_settings", apply).await?;
+    invalidate_obs_settings_cache().await;
+    Ok(result)
+}
+
+/// 推奨設定を適用した後のOBS設定を組み立てる
+///
+/// 基本解像度・音声設定は推奨設定の適用では変わらないため現在の値を維持する
+fn settings_after_apply(current: &ObsSettings, recommendations: &RecommendedSettings) -> ObsSettings {
+    let mut settings = current.clone();
+    settings.video.output_width = recommendations.video.output_width;
+    settings.video.output_height = recommendations.video.output_height;
+    settings.video.fps_numerator = recommendations.video.fps;
+    settings.video.fps_denominator = 1;
+    settings.output.encoder.clone_from(&recommendations.output.encoder);
+    settings.output.bitrate_kbps = recommendations.output.bitrate_kbps;
+    settings.output.keyframe_interval_secs = recommendations.output.keyframe_interval_secs;
+    settings
+}
+
+/// 設定にクリティカルな不整合があれば `VALIDATION_FAILED` エラー
+///
+/// `details` は `{ "warnings": SettingsValidationWarning[] }` の形式（クリティカルなもののみ）
+fn ensure_no_critical_warnings(settings: &ObsSettings) -> Result<(), AppError> {
+    let critical: Vec<SettingsValidationWarning> = settings
+        .validate()
+        .into_iter()
+        .filter(SettingsValidationWarning::is_critical)
+        .collect();
+    if critical.is_empty() {
+        return Ok(());
+    }
+
+    let fields: Vec<&str> = critical.iter().map(|w| w.field.as_str()).collect();
+    Err(AppError::validation_failed(&format!(
+        "適用後の設定に不整合があるため中止しました: {}",
+        fields.join(", ")
+    ))
+    .with_details(serde_json::json!({ "warnings": critical })))
 }
 
 /// カスタム推奨設定を適用
 ///
 /// TOCTOU競合条件を防ぐためロックを使用。
+/// `target` に `Recording` を指定した場合は録画用（品質固定）の推奨設定を適用する。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
+/// `force` の扱いは `apply_recommended_settings` と同じ。
+/// 適用結果の詳細が必要な場合は `apply_custom_settings_with_result` を使用する。
 #[tauri::command]
 pub async fn apply_custom_settings(
     platform: StreamingPlatform,
     style: StreamingStyle,
     network_speed_mbps: f64,
+    target: Option<OutputTarget>,
+    force: Option<ForceToken>,
 ) -> Result<(), AppError> {
-    let streaming_service = get_streaming_mode_service();
+    apply_custom_settings_with_result(platform, style, network_speed_mbps, target, force)
+        .await
+        .map(|_| ())
+}
 
-    // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
-    streaming_service
-        .execute_if_not_streaming(|| async {
-            // OBS接続確認
-            let client = get_obs_client();
-            if !client.is_connected().await {
-                return Err(AppError::obs_state("OBSに接続されていません"));
-            }
+/// カスタム推奨設定を適用し、適用結果を返す
+///
+/// 動作は `apply_custom_settings` と同じ。
+///
+/// # Returns
+/// 適用結果（適用数・エラー・エンコーダーのフォールバック・警告など）
+#[tauri::command]
+pub async fn apply_custom_settings_with_result(
+    platform: StreamingPlatform,
+    style: StreamingStyle,
+    network_speed_mbps: f64,
+    target: Option<OutputTarget>,
+    force: Option<ForceToken>,
+) -> Result<OptimizationResult, AppError> {
+    // 入力値の検証（ロック取得前に行う）
+    validate_recommendation_input(network_speed_mbps, None)?;
 
-            // 現在の設定をバックアップ
-            backup_current_settings_internal().await?;
+    let _operation = get_operation_lock().try_acquire(OperationKind::ApplyCustomSettings)?;
 
-            // 推奨設定を計算
-            let current_settings = get_obs_settings().await?;
-            let hardware = get_hardware_info().await;
+    let apply = execute_guarded(force, |guard| async move {
+        // OBS接続確認
+        let client = get_obs_client();
+        if !client.is_connected().await {
+            return Err(AppError::obs_not_connected());
+        }
 
-            // 推奨設定を計算
-            let recommendations = RecommendationEngine::calculate_recommendations(
+        // OBS側で直接開始された配信・仮想カメラも検出する
+        let live = guard.check_outputs(&client.get_status().await?)?;
+
+        // 現在の設定をバックアップ
+        create_settings_backup(BackupTrigger::PreOptimization).await?;
+
+        // 推奨設定を計算
+        let current_settings = get_obs_settings().await?;
+        let hardware = get_hardware_info().await;
+
+        // 推奨設定を計算
+        let mut recommendations = match target.unwrap_or_default() {
+            OutputTarget::Streaming => RecommendationEngine::calculate_recommendations(
                 &hardware,
-                &current_settings,
+                Some(&current_settings),
                 platform,
                 style,
                 network_speed_mbps,
-            );
-
-            // 推奨設定をOBSに適用
-            crate::obs::settings::apply_video_settings(
-                recommendations.video.output_width,
-                recommendations.video.output_height,
-                recommendations.video.fps,
-            )
-            .await?;
+            ),
+            OutputTarget::Recording => RecommendationEngine::calculate_recording_recommendations(
+                &hardware,
+                &current_settings,
+                style,
+            ),
+        };
+        // 録画はプラットフォーム・ネットワークの制約を受けない
+        let context = match target.unwrap_or_default() {
+            OutputTarget::Streaming => {
+                RecommendationEngine::encoder_selection_context(&hardware, platform, style, network_speed_mbps)
+            }
+            OutputTarget::Recording => {
+                RecommendationEngine::encoder_selection_context(&hardware, StreamingPlatform::Other, style, 0.0)
+            }
+        };
+        let skipped = if live {
+            restrict_to_live_safe(&mut recommendations, &current_settings)
+        } else {
+            Vec::new()
+        };
 
-            // プロファイルパラメータでビットレート・プリセットを適用
-            apply_output_settings_via_profile(&client, &recommendations.output).await?;
+        let mut result = apply_recommendations(&client, recommendations, &context, live).await?;
+        result.forced = guard.is_forced();
+        result.skipped.splice(0..0, skipped);
+        Ok(result)
+    });
 
-            Ok(())
-        })
-        .await
+    let result = traced_command("apply_custom_settings", apply).await?;
+    invalidate_obs_settings_cache().await;
+    Ok(result)
 }
 
 /// プリセットに基づいて最適化を適用
@@ -150,6 +408,7 @@ pub async fn apply_custom_settings(
 /// 最適化結果（適用成功数、失敗数、エラーメッセージ）
 ///
 /// TOCTOU競合条件を防ぐためロックを使用。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
 #[tauri::command]
 pub async fn apply_optimization(
     preset: String,
@@ -158,12 +417,13 @@ pub async fn apply_optimization(
     // プリセットの検証（ロック取得前に行う）
     let valid_presets = ["low", "medium", "high", "ultra", "custom"];
     if !valid_presets.contains(&preset.as_str()) {
-        return Err(AppError::config_error(&format!(
+        return Err(AppError::validation_failed(&format!(
             "無効なプリセット: {}。有効な値は low, medium, high, ultra, custom です",
             preset
         )));
     }
 
+    let _operation = get_operation_lock().try_acquire(OperationKind::ApplyOptimization)?;
     let streaming_service = get_streaming_mode_service();
 
     // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
@@ -172,11 +432,11 @@ pub async fn apply_optimization(
             // OBS接続確認
             let client = get_obs_client();
             if !client.is_connected().await {
-                return Err(AppError::obs_state("OBSに接続されていません"));
+                return Err(AppError::obs_not_connected());
             }
 
             // 現在の設定をバックアップ
-            backup_current_settings_internal().await?;
+            create_settings_backup(BackupTrigger::PreOptimization).await?;
 
             // TODO: Phase 2bでOBS設定適用APIを実装予定
             // 現在はダミーのレスポンスを返す
@@ -187,6 +447,14 @@ pub async fn apply_optimization(
                 applied_count: 0,
                 failed_count: 0,
                 errors: vec![],
+                encoder_fallback: None,
+                forced: false,
+                skipped: Vec::new(),
+                pending_restart: Vec::new(),
+                request_id: None,
+                overhead_baseline_id: None,
+                video_verification: None,
+                warnings: Vec::new(),
             })
         })
         .await
@@ -201,10 +469,10 @@ pub async fn get_backups() -> Result<Vec<BackupInfo>, AppError> {
     // プロファイル一覧を取得
     let profiles = get_profiles()?;
 
-    // "バックアップ"で始まるプロファイルのみをフィルタリング
+    // バックアッププロファイルのみをフィルタリング
     let backups: Vec<BackupInfo> = profiles
         .into_iter()
-        .filter(|p| p.name.starts_with("バックアップ"))
+        .filter(|p| p.is_backup)
         .map(|summary| {
             // 完全なプロファイルを読み込み
             match get_profile(&summary.id) {
@@ -213,6 +481,8 @@ pub async fn get_backups() -> Result<Vec<BackupInfo>, AppError> {
                     created_at: profile.created_at,
                     description: profile.description,
                     settings: profile.settings,
+                    // 旧形式のバックアップは最適化適用時にのみ作成されていた
+                    trigger: profile.backup_trigger.unwrap_or(BackupTrigger::PreOptimization),
                 }),
                 Err(e) => {
                     tracing::warn!(target: "optimization", error = %e, "バックアップの読み込みに失敗");
@@ -226,32 +496,45 @@ pub async fn get_backups() -> Result<Vec<BackupInfo>, AppError> {
     Ok(backups)
 }
 
-/// 現在の設定をバックアップ（内部関数）
+/// 現在の設定をバックアップ
+///
+/// 現在のOBS設定を `is_backup` 付きのプロファイルとして保存する。
+/// 設定を変更するコマンドはロック取得後、変更前にこの関数を呼び出す。
+/// 取得の途中でOBSとの接続が切れた場合は、不完全なバックアップを保存せずに中断する。
+///
+/// # Arguments
+/// * `trigger` - バックアップの作成契機
 ///
-/// TOCTOU対策済みの関数から呼び出される内部実装
-async fn backup_current_settings_internal() -> Result<String, AppError> {
+/// # Returns
+/// 作成したバックアップのID
+pub async fn create_settings_backup(trigger: BackupTrigger) -> Result<String, AppError> {
+    let client = get_obs_client();
+    let cancellation = client.cancellation_token();
+
     // 現在のOBS設定を取得
     let current_settings = get_obs_settings().await?;
+    cancellation.check()?;
+    let audio_inputs = capture_audio_inputs_or_empty(&client).await;
+    cancellation.check()?;
 
     // バックアップIDを生成
     let backup_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
 
-    // Unixタイムスタンプを取得
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|e| AppError::config_error(&format!("時刻の取得に失敗: {e}")))?
-        .as_secs() as i64;
+    let description = match trigger {
+        BackupTrigger::Manual => "手動バックアップ",
+        BackupTrigger::PreOptimization => "最適化適用前の自動バックアップ",
+        BackupTrigger::PreProfileApply => "プロファイル適用前の自動バックアップ",
+    };
 
     // バックアップをプロファイルとして保存
     let backup_profile = SettingsProfile {
         id: backup_id.clone(),
         name: format!(
-            "バックアップ {}",
-            chrono::DateTime::from_timestamp(now, 0)
-                .unwrap_or(chrono::DateTime::UNIX_EPOCH)
-                .format("%Y-%m-%d %H:%M:%S")
+            "Auto-backup {}",
+            now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
         ),
-        description: "自動バックアップ".to_string(),
+        description: description.to_string(),
         platform: StreamingPlatform::Other,
         style: StreamingStyle::Other,
         settings: ProfileSettings {
@@ -264,6 +547,7 @@ async fn backup_current_settings_internal() -> Result<String, AppError> {
             audio: crate::storage::profiles::AudioSettings {
                 sample_rate: current_settings.audio.sample_rate,
                 bitrate_kbps: 160,
+                inputs: audio_inputs,
             },
             output: crate::storage::profiles::OutputSettings {
                 encoder: current_settings.output.encoder,
@@ -276,78 +560,323 @@ async fn backup_current_settings_internal() -> Result<String, AppError> {
                     .unwrap_or_else(|| "CBR".to_string()),
             },
         },
-        created_at: now,
-        updated_at: now,
+        created_at: now.timestamp(),
+        updated_at: now.timestamp(),
+        tags: Vec::new(),
+        is_favorite: false,
+        last_applied_at: None,
+        is_backup: true,
+        backup_trigger: Some(trigger),
     };
 
     storage_save_profile(&backup_profile)?;
 
+    // OBSのプロファイル・シーンコレクションのファイルのバックアップ（設定で有効な場合）
+    // 失敗しても設定のバックアップは作成済みのため、適用は続行する
+    if load_config().is_ok_and(|config| config.backup_obs_files) {
+        if let Err(e) = backup_current_obs_files(&client, &backup_id).await {
+            tracing::warn!(target: "optimization", backup_id = %backup_id, error = %e, "OBSのファイルのバックアップに失敗");
+        }
+    }
+
+    tracing::info!(
+        target: "optimization",
+        backup_id = %backup_id,
+        trigger = ?trigger,
+        "現在の設定をバックアップしました"
+    );
+
     Ok(backup_id)
 }
 
 /// 現在の設定をバックアップ（Tauriコマンド）
+///
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
 #[tauri::command]
 pub async fn backup_current_settings() -> Result<String, AppError> {
-    backup_current_settings_internal().await
+    let _operation = get_operation_lock().try_acquire(OperationKind::BackupSettings)?;
+    create_settings_backup(BackupTrigger::Manual).await
 }
 
 /// バックアップから復元
 ///
+/// バックアッププロファイルの設定をOBSに適用する。
 /// TOCTOU競合条件を防ぐためロックを使用。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
+///
+/// # Arguments
+/// * `backup_id` - 復元するバックアップのID
 #[tauri::command]
-pub async fn restore_backup(_backup_id: String) -> Result<(), AppError> {
+pub async fn restore_backup(backup_id: String) -> Result<(), AppError> {
+    // バックアップを読み込み（ロック取得前に存在を確認）
+    let backup = get_profile(&backup_id)?;
+    if !backup.is_backup_profile() {
+        return Err(AppError::validation_failed(&format!(
+            "バックアップではないプロファイルです: {backup_id}"
+        )));
+    }
+
+    let _operation = get_operation_lock().try_acquire(OperationKind::RestoreBackup)?;
     let streaming_service = get_streaming_mode_service();
 
     // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
-    streaming_service
+    let apply = streaming_service
         .execute_if_not_streaming(|| async {
             // OBS接続確認
             let client = get_obs_client();
             if !client.is_connected().await {
-                return Err(AppError::obs_state("OBSに接続されていません"));
+                return Err(AppError::obs_not_connected());
             }
 
-            // TODO: Phase 2bでOBS設定適用APIを実装予定
-            // _backup_idからプロファイルを読み込み、設定を復元
+            // OBS側で直接開始された配信・仮想カメラも検出する
+            ensure_no_blocking_outputs(&client.get_status().await?)?;
 
+            for warning in apply_profile_settings(&client, &backup.settings).await? {
+                tracing::warn!(target: "optimization", backup_id = %backup.id, "{warning}");
+            }
+
+            tracing::info!(target: "optimization", backup_id = %backup.id, "バックアップから設定を復元しました");
             Ok(())
-        })
-        .await
+        });
+
+    traced_command("restore_backup", apply).await
+}
+
+/// 実行中の設定操作を取得
+///
+/// 推奨設定の適用・プロファイル適用・バックアップ・復元のいずれかが実行中の場合に
+/// その種類と開始日時を返す。実行中の操作がない場合はNone。
+#[tauri::command]
+pub async fn get_current_operation() -> Result<Option<CurrentOperation>, AppError> {
+    Ok(get_operation_lock().current())
+}
+
+/// 現在の音声入力（デスクトップ音声・マイク）の設定を取得
+///
+/// 取得に失敗しても保存自体は続行できるよう、警告を出して空の一覧を返す
+pub async fn capture_audio_inputs_or_empty(client: &crate::obs::ObsClient) -> Vec<AudioInputSettings> {
+    capture_audio_inputs(client).await.unwrap_or_else(|e| {
+        tracing::warn!(target: "optimization", error = %e, "音声入力の設定の取得に失敗");
+        Vec::new()
+    })
+}
+
+/// プロファイルの設定内容をOBSに適用
+///
+/// ビデオ設定（解像度・FPS）、出力設定（エンコーダー・ビットレート等）、
+/// 音声入力のデバイス・ミュート状態を適用する。
+/// 見つからない音声入力・デバイスはスキップし、項目ごとの警告を返す。
+/// 途中でOBSとの接続が切れた場合は残りの項目を適用せずに中断する。
+/// 呼び出し側でロックの取得とOBS接続の確認を行うこと。
+pub async fn apply_profile_settings(
+    client: &crate::obs::ObsClient,
+    settings: &ProfileSettings,
+) -> Result<Vec<String>, AppError> {
+    let cancellation = client.cancellation_token();
+
+    let video = crate::obs::settings::apply_video_settings(
+        settings.video.output_width,
+        settings.video.output_height,
+        settings.video.fps,
+        1,
+    )
+    .await?;
+    cancellation.check()?;
+    let mut warnings: Vec<String> = video.warning().into_iter().collect();
+
+    let output = crate::services::RecommendedOutputSettings {
+        encoder: settings.output.encoder.clone(),
+        bitrate_kbps: settings.output.bitrate_kbps,
+        keyframe_interval_secs: settings.output.keyframe_interval_secs,
+        preset: settings.output.preset.clone(),
+        rate_control: settings.output.rate_control.clone(),
+        quality_value: None,
+        recording_format: None,
+        protocol: StreamingProtocol::default(),
+        pre_analysis: false,
+        pa_look_ahead: None,
+        pa_scene_change_detect: false,
+        output_mode: ObsOutputMode::default(),
+        nvenc_tuning: None,
+        hevc_profile: None,
+    };
+    apply_output_settings_via_profile(client, &output, true).await?;
+    cancellation.check()?;
+
+    // 音声入力の適用失敗はビデオ・出力設定の適用結果に影響させない
+    warnings.extend(
+        apply_audio_inputs(client, &settings.audio.inputs)
+            .await
+            .unwrap_or_else(|e| vec![format!("音声入力の設定に失敗しました: {}", e.message())]),
+    );
+    cancellation.check()?;
+    Ok(warnings)
+}
+
+/// 推奨設定（ビデオ・縮小フィルタ・出力）をOBSに適用
+///
+/// 出力設定はエンコーダーのフォールバック付きで適用する。
+/// `live` の場合（配信中の強制適用）は出力を止めない出力設定だけを適用し、
+/// ビデオ・縮小フィルタ・出力モードの切り替え・フォールバックは行わない。
+/// 途中でOBSとの接続が切れた場合は残りの項目を適用せずに中断する。
+/// 呼び出し側でロックの取得とOBS接続の確認を行うこと。
+async fn apply_recommendations(
+    client: &crate::obs::ObsClient,
+    recommendations: RecommendedSettings,
+    context: &EncoderSelectionContext,
+    live: bool,
+) -> Result<OptimizationResult, AppError> {
+    // 適用前のOBSの負荷を、適用に成功した場合だけ基準値として保存する
+    let baseline = capture_overhead_baseline();
+
+    if live {
+        // プロファイルパラメータへの書き込みは実行中のエンコーダーに反映されないため、適用済みとして数えない
+        apply_output_settings_via_profile(client, &recommendations.output, false).await?;
+        return Ok(OptimizationResult {
+            applied_count: 0,
+            failed_count: 0,
+            errors: Vec::new(),
+            encoder_fallback: None,
+            forced: true,
+            skipped: vec!["縮小フィルタ・出力モードは配信中に変更できないため適用を見送りました".to_string()],
+            pending_restart: vec![
+                "出力設定（ビットレート・キーフレーム間隔・プリセット等）はプロファイルに保存しました。配信を停止して再開すると反映されます"
+                    .to_string(),
+            ],
+            request_id: None,
+            overhead_baseline_id: save_baseline(baseline).await,
+            video_verification: None,
+            warnings: Vec::new(),
+        });
+    }
+
+    let cancellation = client.cancellation_token();
+
+    let video_verification = crate::obs::settings::apply_video_settings(
+        recommendations.video.output_width,
+        recommendations.video.output_height,
+        recommendations.video.fps,
+        1,
+    )
+    .await?;
+    cancellation.check()?;
+    // OBSに値を丸められた場合は適用失敗として警告を返す
+    let mut applied_count = 0;
+    let mut errors = Vec::new();
+    match video_verification.warning() {
+        None => applied_count += 1,
+        Some(warning) => errors.push(warning),
+    }
+
+    // 縮小フィルタを適用
+    match apply_downscale_filter(client, recommendations.video.downscale_filter).await {
+        Ok(()) => applied_count += 1,
+        Err(e) => errors.push(e.message().to_string()),
+    }
+    cancellation.check()?;
+
+    // プロファイルパラメータでビットレート・プリセットを適用
+    let mut output = recommendations.output;
+    let reported = reported_obs_encoders().await;
+    let encoder_fallback = apply_with_fallback(&mut output, context, reported.as_deref(), |output| async move {
+        apply_output_settings_via_profile(client, &output, true).await
+    })
+    .await?;
+    applied_count += 1;
+
+    Ok(OptimizationResult {
+        applied_count,
+        failed_count: errors.len(),
+        errors,
+        encoder_fallback,
+        forced: false,
+        skipped: Vec::new(),
+        pending_restart: Vec::new(),
+        request_id: None,
+        overhead_baseline_id: save_baseline(baseline).await,
+        video_verification: Some(video_verification),
+        warnings: Vec::new(),
+    })
+}
+
+/// OBSがログに報告した利用可能な映像エンコーダーを取得
+///
+/// ログが読めない・一覧が記録されていない場合はNone（フォールバックはGPUからの推定で行う）
+async fn reported_obs_encoders() -> Option<Vec<String>> {
+    let reported = tokio::task::spawn_blocking(reported_video_encoders).await.ok()?;
+    match reported {
+        Ok(encoders) if !encoders.is_empty() => Some(encoders),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::debug!(target: "optimization", error = %e, "OBSが報告したエンコーダーを取得できませんでした");
+            None
+        }
+    }
+}
+
+/// 適用前のOBSの負荷を基準値として保存（計測していなかった場合はNone）
+async fn save_baseline(baseline: Option<StreamingOverhead>) -> Option<String> {
+    match baseline {
+        Some(before) => save_overhead_baseline(before).await,
+        None => None,
+    }
+}
+
+/// 縮小フィルタをプロファイルパラメータ（`Video` / `ScaleType`）で適用
+///
+/// 縮小フィルタは画質への影響が小さいため、失敗しても警告のみで続行する
+async fn apply_downscale_filter(client: &crate::obs::ObsClient, filter: DownscaleFilter) -> Result<(), AppError> {
+    let result = client
+        .set_profile_parameter("Video", "ScaleType", Some(filter.obs_scale_type()))
+        .await;
+    if let Err(e) = &result {
+        tracing::warn!(
+            target: "optimization",
+            error = %e,
+            filter = filter.display_name(),
+            "縮小フィルタの適用に失敗"
+        );
+    }
+    result
 }
 
 /// プロファイルパラメータを使用して出力設定を適用
 ///
 /// OBS WebSocket の SetProfileParameter を使用して
 /// エンコーダ、ビットレート、プリセット等を設定する。
-/// 基本モードの場合は詳細モードに切り替えてから設定を適用。
+/// 基本モードの場合は詳細モードに切り替えてから設定を適用
+/// （`allow_mode_switch` がfalseの場合は基本モードのまま適用）。
 async fn apply_output_settings_via_profile(
     client: &crate::obs::ObsClient,
     output: &crate::services::RecommendedOutputSettings,
+    allow_mode_switch: bool,
 ) -> Result<(), AppError> {
     // 出力モードを取得（Simple or Advanced）
-    let output_mode = client
-        .get_profile_parameter("Output", "Mode")
-        .await
-        .ok()
-        .flatten()
-        .unwrap_or_else(|| "Simple".to_string());
+    let output_mode = get_output_mode(client).await.unwrap_or_default();
 
     tracing::info!(
         target: "optimization",
-        mode = %output_mode,
+        mode = output_mode.profile_value(),
         "OBS出力モードを検出"
     );
 
+    // 録画の推奨設定は録画出力として適用
+    if output.is_recording() {
+        return apply_recording_output_settings(client, output, output_mode).await;
+    }
+
+    // 配信中は出力モードを切り替えると出力が停止するため、現在のモードで適用する
+    if output_mode != ObsOutputMode::Advanced && !allow_mode_switch {
+        return apply_simple_output_settings(client, output).await;
+    }
+
     // 基本モードの場合は詳細モードに切り替え
-    if output_mode != "Advanced" {
+    if output_mode != ObsOutputMode::Advanced {
         tracing::info!(
             target: "optimization",
             "基本モードから詳細モードに切り替えます"
         );
-        if let Err(e) = client
-            .set_profile_parameter("Output", "Mode", Some("Advanced"))
-            .await
-        {
+        if let Err(e) = set_output_mode(client, ObsOutputMode::Advanced).await {
             tracing::warn!(
                 target: "optimization",
                 error = %e,
@@ -362,6 +891,126 @@ async fn apply_output_settings_via_profile(
     apply_advanced_output_settings(client, output).await
 }
 
+/// 録画用の出力設定を適用
+///
+/// 録画の推奨設定を録画エンコーダーのプロファイルパラメータに割り当てる。
+/// 品質値は基本モードでは近い録画品質（`RecQuality`）、詳細モードでは録画エンコーダーの設定ファイル
+/// （`recordEncoder.json`）に書き込む。CBRの推奨では、詳細モードはビットレートを `recordEncoder.json` に書き込み、
+/// 基本モードは録画のビットレートを指定できないため配信と同じエンコーダーを共有する。
+/// 配信用の設定（ビットレート等）は変更しない。
+async fn apply_recording_output_settings(
+    client: &crate::obs::ObsClient,
+    output: &crate::services::RecommendedOutputSettings,
+    output_mode: ObsOutputMode,
+) -> Result<(), AppError> {
+    tracing::info!(
+        target: "optimization",
+        mode = output_mode.profile_value(),
+        rate_control = %output.rate_control,
+        "録画出力の設定を適用中..."
+    );
+
+    let section = if output_mode == ObsOutputMode::Advanced { "AdvOut" } else { "SimpleOutput" };
+    let recording_format = output.recording_format.as_deref().unwrap_or("mkv");
+
+    let mut params: Vec<(&str, String)> = vec![
+        ("RecEncoder", output.encoder.clone()),
+        ("RecFormat2", recording_format.to_string()),
+    ];
+    if section == "SimpleOutput" {
+        // 基本モードでは品質値を直接指定できないため、品質値に近い録画品質を使用
+        let quality = if output.is_constant_quality() {
+            output.quality_value.map_or("HQ", simple_recording_quality)
+        } else {
+            SIMPLE_SHARED_RECORDING_QUALITY
+        };
+        params.push(("RecQuality", quality.to_string()));
+    } else {
+        // 詳細モードでは配信と別のエンコーダーを使用する
+        params.push(("RecType", "Standard".to_string()));
+    }
+
+    for (name, value) in &params {
+        if let Err(e) = client
+            .set_profile_parameter(section, name, Some(value))
+            .await
+        {
+            tracing::warn!(
+                target: "optimization",
+                error = %e,
+                parameter = %name,
+                value = %value,
+                "録画設定の適用に失敗"
+            );
+            // エンコーダーの失敗は呼び出し側でフォールバックする
+            if *name == "RecEncoder" {
+                return Err(e);
+            }
+        } else {
+            tracing::info!(
+                target: "optimization",
+                parameter = %name,
+                value = %value,
+                "録画設定を適用しました"
+            );
+        }
+    }
+
+    // MKVで録画する場合は、編集ソフトで扱えるよう録画終了後にMP4へ自動リマックスする
+    if recording_format == RecordingContainer::Mkv.as_obs_value() {
+        match client.set_profile_parameter("Video", "AutoRemux", Some("true")).await {
+            Ok(()) => tracing::info!(target: "optimization", "録画後の自動リマックスを有効にしました"),
+            Err(e) => tracing::warn!(
+                target: "optimization",
+                error = %e,
+                "録画後の自動リマックスの設定に失敗"
+            ),
+        }
+    }
+
+    apply_amf_pre_analysis(client, section, "Rec", output).await;
+    apply_nvenc_tuning(client, section, "Rec", output).await;
+    apply_hevc_profile(client, section, "Rec", output).await;
+
+    // 詳細モードのCQ/CRF値はエンコーダー固有設定（recordEncoder.json）に保存されるため、ファイルに書き込む
+    if let (Some(quality), ObsOutputMode::Advanced) = (output.quality_value, output_mode) {
+        match write_current_record_encoder_quality(client, &output.encoder, &output.rate_control, quality).await {
+            Ok(()) => tracing::info!(
+                target: "optimization",
+                rate_control = %output.rate_control,
+                quality = quality,
+                "録画エンコーダーの品質値を設定しました（出力の再設定後に反映）"
+            ),
+            Err(e) => tracing::warn!(
+                target: "optimization",
+                error = %e,
+                rate_control = %output.rate_control,
+                quality = quality,
+                "録画エンコーダーの品質値の設定に失敗"
+            ),
+        }
+    }
+
+    // 詳細モードのCBRのビットレートも同じファイルに書き込む
+    if !output.is_constant_quality() && output_mode == ObsOutputMode::Advanced {
+        match write_current_record_encoder_bitrate(client, output.bitrate_kbps).await {
+            Ok(()) => tracing::info!(
+                target: "optimization",
+                bitrate_kbps = output.bitrate_kbps,
+                "録画エンコーダーのビットレートを設定しました（出力の再設定後に反映）"
+            ),
+            Err(e) => tracing::warn!(
+                target: "optimization",
+                error = %e,
+                bitrate_kbps = output.bitrate_kbps,
+                "録画エンコーダーのビットレートの設定に失敗"
+            ),
+        }
+    }
+
+    Ok(())
+}
+
 /// 基本（Simple）出力モードの設定を適用
 async fn apply_simple_output_settings(
     client: &crate::obs::ObsClient,
@@ -369,7 +1018,7 @@ async fn apply_simple_output_settings(
 ) -> Result<(), AppError> {
     tracing::info!(target: "optimization", "基本出力モードの設定を適用中...");
 
-    // エンコーダを設定
+    // エンコーダを設定（失敗時は呼び出し側でフォールバックする）
     if let Err(e) = client
         .set_profile_parameter("SimpleOutput", "StreamEncoder", Some(&output.encoder))
         .await
@@ -380,13 +1029,13 @@ async fn apply_simple_output_settings(
             encoder = %output.encoder,
             "エンコーダの設定に失敗"
         );
-    } else {
-        tracing::info!(
-            target: "optimization",
-            encoder = %output.encoder,
-            "エンコーダを設定しました"
-        );
+        return Err(e);
     }
+    tracing::info!(
+        target: "optimization",
+        encoder = %output.encoder,
+        "エンコーダを設定しました"
+    );
 
     // ビットレートを設定
     if let Err(e) = client
@@ -451,6 +1100,10 @@ async fn apply_simple_output_settings(
         );
     }
 
+    apply_amf_pre_analysis(client, "SimpleOutput", "", output).await;
+    apply_nvenc_tuning(client, "SimpleOutput", "", output).await;
+    apply_hevc_profile(client, "SimpleOutput", "", output).await;
+
     Ok(())
 }
 
@@ -461,7 +1114,7 @@ async fn apply_advanced_output_settings(
 ) -> Result<(), AppError> {
     tracing::info!(target: "optimization", "詳細出力モードの設定を適用中...");
 
-    // 詳細モードではストリーミングエンコーダを設定
+    // 詳細モードではストリーミングエンコーダを設定（失敗時は呼び出し側でフォールバックする）
     if let Err(e) = client
         .set_profile_parameter("AdvOut", "Encoder", Some(&output.encoder))
         .await
@@ -472,13 +1125,13 @@ async fn apply_advanced_output_settings(
             encoder = %output.encoder,
             "エンコーダの設定に失敗"
         );
-    } else {
-        tracing::info!(
-            target: "optimization",
-            encoder = %output.encoder,
-            "エンコーダを設定しました"
-        );
+        return Err(e);
     }
+    tracing::info!(
+        target: "optimization",
+        encoder = %output.encoder,
+        "エンコーダを設定しました"
+    );
 
     // ビットレートを設定（詳細モードではTrackXBitrateを使用）
     // Track1が通常のストリーミングオーディオ
@@ -533,14 +1186,206 @@ async fn apply_advanced_output_settings(
         );
     }
 
+    apply_amf_pre_analysis(client, "AdvOut", "", output).await;
+    apply_nvenc_tuning(client, "AdvOut", "", output).await;
+    apply_hevc_profile(client, "AdvOut", "", output).await;
+
     Ok(())
 }
 
+/// AMFエンコーダーのプリアナリシス設定をプロファイルパラメータに適用
+///
+/// AMF以外のエンコーダーでは何もしない。配信ではプリアナリシスの遅延を避けるため
+/// 無効（false）を明示的に書き込む。失敗しても警告のみで続行する
+///
+/// # Arguments
+/// * `section` - プロファイルのセクション（"AdvOut" / "SimpleOutput"）
+/// * `prefix` - パラメータ名の接頭辞（録画エンコーダーの場合は "Rec"）
+async fn apply_amf_pre_analysis(
+    client: &crate::obs::ObsClient,
+    section: &str,
+    prefix: &str,
+    output: &crate::services::RecommendedOutputSettings,
+) {
+    if !output.encoder.contains("amf") {
+        return;
+    }
+
+    let mut params = vec![
+        ("AMFPreAnalysis", output.pre_analysis.to_string()),
+        ("AMFPASceneChangeDetect", output.pa_scene_change_detect.to_string()),
+    ];
+    if let Some(look_ahead) = output.pa_look_ahead {
+        params.push(("AMFPALookAhead", look_ahead.to_string()));
+    }
+
+    for (name, value) in &params {
+        let name = format!("{prefix}{name}");
+        if let Err(e) = client
+            .set_profile_parameter(section, &name, Some(value))
+            .await
+        {
+            tracing::warn!(
+                target: "optimization",
+                error = %e,
+                parameter = %name,
+                value = %value,
+                "AMFプリアナリシス設定の適用に失敗"
+            );
+        } else {
+            tracing::info!(
+                target: "optimization",
+                parameter = %name,
+                value = %value,
+                "AMFプリアナリシス設定を適用しました"
+            );
+        }
+    }
+}
+
+/// NVENCのチューニング（hq / ll / ull）をプロファイルパラメータに適用
+///
+/// NVENC以外のエンコーダー、またはチューニングが未指定の場合は何もしない。
+/// 失敗しても警告のみで続行する
+///
+/// # Arguments
+/// * `section` - プロファイルのセクション（"AdvOut" / "SimpleOutput"）
+/// * `prefix` - パラメータ名の接頭辞（録画エンコーダーの場合は "Rec"）
+async fn apply_nvenc_tuning(
+    client: &crate::obs::ObsClient,
+    section: &str,
+    prefix: &str,
+    output: &crate::services::RecommendedOutputSettings,
+) {
+    let Some(tuning) = output.nvenc_tuning else {
+        return;
+    };
+    if !output.encoder.contains("nvenc") {
+        return;
+    }
+
+    let name = format!("{prefix}NVENCTuning");
+    let value = tuning.obs_value();
+    if let Err(e) = client
+        .set_profile_parameter(section, &name, Some(value))
+        .await
+    {
+        tracing::warn!(
+            target: "optimization",
+            error = %e,
+            parameter = %name,
+            value = %value,
+            "NVENCチューニングの適用に失敗"
+        );
+    } else {
+        tracing::info!(
+            target: "optimization",
+            parameter = %name,
+            value = %value,
+            "NVENCチューニングを適用しました"
+        );
+    }
+}
+
+/// NVENC HEVCのプロファイル（main / main10）をプロファイルパラメータに適用
+///
+/// NVENC以外のエンコーダー、またはHEVC以外の場合は何もしない。
+/// 失敗しても警告のみで続行する
+///
+/// # Arguments
+/// * `section` - プロファイルのセクション（"AdvOut" / "SimpleOutput"）
+/// * `prefix` - パラメータ名の接頭辞（録画エンコーダーの場合は "Rec"）
+async fn apply_hevc_profile(
+    client: &crate::obs::ObsClient,
+    section: &str,
+    prefix: &str,
+    output: &crate::services::RecommendedOutputSettings,
+) {
+    let Some(hevc_profile) = output.hevc_profile else {
+        return;
+    };
+    if !output.encoder.contains("nvenc") {
+        return;
+    }
+
+    let name = format!("{prefix}NVENCProfile");
+    let value = hevc_profile.obs_value();
+    if let Err(e) = client
+        .set_profile_parameter(section, &name, Some(value))
+        .await
+    {
+        tracing::warn!(
+            target: "optimization",
+            error = %e,
+            parameter = %name,
+            value = %value,
+            "HEVCプロファイルの適用に失敗"
+        );
+    } else {
+        tracing::info!(
+            target: "optimization",
+            parameter = %name,
+            value = %value,
+            "HEVCプロファイルを適用しました"
+        );
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
+    fn recommendations_for(settings: &ObsSettings) -> RecommendedSettings {
+        RecommendationEngine::calculate_recommendations(
+            &crate::testing::fixtures::high_end_hardware(),
+            Some(settings),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            20.0,
+        )
+    }
+
+    #[test]
+    fn test_settings_after_apply_uses_recommended_values() {
+        let current = crate::testing::fixtures::standard_obs_settings();
+        let recommendations = recommendations_for(&current);
+
+        let settings = settings_after_apply(&current, &recommendations);
+        assert_eq!(settings.video.base_width, current.video.base_width);
+        assert_eq!(settings.video.output_width, recommendations.video.output_width);
+        assert_eq!(settings.video.fps_numerator, recommendations.video.fps);
+        assert_eq!(settings.video.fps_denominator, 1);
+        assert_eq!(settings.output.encoder, recommendations.output.encoder);
+        assert_eq!(settings.output.bitrate_kbps, recommendations.output.bitrate_kbps);
+        assert!(ensure_no_critical_warnings(&settings).is_ok());
+    }
+
+    #[test]
+    fn test_settings_after_apply_fixes_zero_fps_denominator() {
+        let mut current = crate::testing::fixtures::standard_obs_settings();
+        current.video.fps_denominator = 0;
+        assert!(ensure_no_critical_warnings(&current).is_err());
+
+        let recommendations = recommendations_for(&current);
+        assert!(ensure_no_critical_warnings(&settings_after_apply(&current, &recommendations)).is_ok());
+    }
+
+    #[test]
+    fn test_critical_warnings_abort_with_details() {
+        let mut settings = crate::testing::fixtures::standard_obs_settings();
+        settings.output.encoder = String::new();
+        settings.output.bitrate_kbps = 0;
+        // 警告のみ（クリティカルでない）の項目はエラーに含めない
+        settings.audio.channels = 6;
+
+        let error = ensure_no_critical_warnings(&settings).unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED);
+        let warnings = error.details().unwrap()["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w["severity"] == "critical"));
+    }
+
     /// BackupInfoのシリアライゼーション/デシリアライゼーションをテスト
     #[test]
     fn test_backup_info_serialization() {
@@ -558,6 +1403,7 @@ mod tests {
                 audio: crate::storage::profiles::AudioSettings {
                     sample_rate: 48000,
                     bitrate_kbps: 160,
+                    inputs: Vec::new(),
                 },
                 output: crate::storage::profiles::OutputSettings {
                     encoder: "ffmpeg_nvenc".to_string(),
@@ -567,6 +1413,7 @@ mod tests {
                     rate_control: "CBR".to_string(),
                 },
             },
+            trigger: BackupTrigger::Manual,
         };
 
         // JSONにシリアライズ
@@ -604,6 +1451,7 @@ mod tests {
                 audio: crate::storage::profiles::AudioSettings {
                     sample_rate: 44100,
                     bitrate_kbps: 128,
+                    inputs: Vec::new(),
                 },
                 output: crate::storage::profiles::OutputSettings {
                     encoder: "obs_x264".to_string(),
@@ -613,6 +1461,7 @@ mod tests {
                     rate_control: "VBR".to_string(),
                 },
             },
+            trigger: BackupTrigger::PreProfileApply,
         };
 
         let json = serde_json::to_value(&backup).unwrap();
@@ -622,11 +1471,28 @@ mod tests {
         assert!(json.get("createdAt").is_some());
         assert!(json.get("description").is_some());
         assert!(json.get("settings").is_some());
+        assert_eq!(json["trigger"], "preProfileApply");
 
         // snake_caseのキーが存在しないことを確認
         assert!(json.get("created_at").is_none());
     }
 
+    /// 強制適用では出力が有効でもエラーにせず、出力を止めない項目への絞り込みを要求することをテスト
+    #[test]
+    fn test_apply_guard_check_outputs() {
+        let streaming = ObsStatus { streaming: true, ..ObsStatus::default() };
+        let idle = ObsStatus::default();
+
+        assert!(ApplyGuard::Normal.check_outputs(&streaming).is_err());
+        assert!(!ApplyGuard::Normal.check_outputs(&idle).unwrap());
+
+        let forced = ApplyGuard::Forced { output_active: false };
+        assert!(forced.check_outputs(&streaming).unwrap());
+        assert!(!forced.check_outputs(&idle).unwrap());
+        // サービスのフラグで配信中と判定された場合も絞り込む
+        assert!(ApplyGuard::Forced { output_active: true }.check_outputs(&idle).unwrap());
+    }
+
     /// OptimizationResultのシリアライゼーションをテスト
     #[test]
     fn test_optimization_result_serialization() {
@@ -637,6 +1503,14 @@ mod tests {
                 "エラー1: 設定の適用に失敗".to_string(),
                 "エラー2: 無効な値".to_string(),
             ],
+            encoder_fallback: None,
+            forced: false,
+            skipped: Vec::new(),
+            pending_restart: Vec::new(),
+            request_id: None,
+            overhead_baseline_id: None,
+            video_verification: None,
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -646,6 +1520,7 @@ mod tests {
         assert_eq!(value["appliedCount"], 10);
         assert_eq!(value["failedCount"], 2);
         assert_eq!(value["errors"].as_array().unwrap().len(), 2);
+        assert!(value["encoderFallback"].is_null());
     }
 
     /// OptimizationResultの成功ケースをテスト
@@ -655,6 +1530,14 @@ mod tests {
             applied_count: 15,
             failed_count: 0,
             errors: vec![],
+            encoder_fallback: None,
+            forced: false,
+            skipped: Vec::new(),
+            pending_restart: Vec::new(),
+            request_id: None,
+            overhead_baseline_id: None,
+            video_verification: None,
+            warnings: Vec::new(),
         };
 
         assert_eq!(result.applied_count, 15);
@@ -673,6 +1556,14 @@ mod tests {
                 "設定B: 無効な値".to_string(),
                 "設定C: OBS接続エラー".to_string(),
             ],
+            encoder_fallback: None,
+            forced: false,
+            skipped: Vec::new(),
+            pending_restart: Vec::new(),
+            request_id: None,
+            overhead_baseline_id: None,
+            video_verification: None,
+            warnings: Vec::new(),
         };
 
         assert_eq!(result.applied_count, 8);
@@ -694,9 +1585,9 @@ mod tests {
         // OBS未接続エラーまたは配信中エラーが返る（プリセット検証はパスする）
         match result {
             Err(e) => {
-                // プリセット検証を通過していればOBS_STATEエラーになるはず
-                // CONFIG_ERRORの場合はプリセット検証に失敗している
-                assert_eq!(e.code(), "OBS_STATE", "プリセット検証に失敗した可能性");
+                // プリセット検証を通過していればOBS_NOT_CONNECTEDエラーになるはず
+                // VALIDATION_FAILEDの場合はプリセット検証に失敗している
+                assert_eq!(e.code(), "OBS_NOT_CONNECTED", "プリセット検証に失敗した可能性");
             },
             Ok(_) => {
                 // OBS接続済みの場合は成功する可能性がある（テスト環境依存）
@@ -711,7 +1602,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "OBS_STATE", "プリセット検証に失敗した可能性");
+                assert_eq!(e.code(), "OBS_NOT_CONNECTED", "プリセット検証に失敗した可能性");
             },
             Ok(_) => {},
         }
@@ -724,7 +1615,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "OBS_STATE", "プリセット検証に失敗した可能性");
+                assert_eq!(e.code(), "OBS_NOT_CONNECTED", "プリセット検証に失敗した可能性");
             },
             Ok(_) => {},
         }
@@ -737,7 +1628,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "OBS_STATE", "プリセット検証に失敗した可能性");
+                assert_eq!(e.code(), "OBS_NOT_CONNECTED", "プリセット検証に失敗した可能性");
             },
             Ok(_) => {},
         }
@@ -750,7 +1641,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "OBS_STATE", "プリセット検証に失敗した可能性");
+                assert_eq!(e.code(), "OBS_NOT_CONNECTED", "プリセット検証に失敗した可能性");
             },
             Ok(_) => {},
         }
@@ -763,7 +1654,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "CONFIG_ERROR");
+                assert_eq!(e.code(), "VALIDATION_FAILED");
                 assert!(e.message().contains("無効なプリセット"));
                 assert!(e.message().contains("invalid"));
             },
@@ -780,7 +1671,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "CONFIG_ERROR");
+                assert_eq!(e.code(), "VALIDATION_FAILED");
                 assert!(e.message().contains("無効なプリセット"));
             },
             Ok(_) => {
@@ -796,7 +1687,7 @@ mod tests {
 
         match result {
             Err(e) => {
-                assert_eq!(e.code(), "CONFIG_ERROR");
+                assert_eq!(e.code(), "VALIDATION_FAILED");
                 assert!(e.message().contains("無効なプリセット"));
             },
             Ok(_) => {
@@ -1,21 +1,130 @@
 // 最適化エンジンコマンド
 
+use crate::commands::validation::validate_recommendation_input;
 use crate::error::AppError;
-use crate::obs::get_obs_settings;
-use crate::monitor::{get_cpu_core_count, get_cpu_name, get_memory_info};
-use crate::monitor::gpu::get_gpu_info;
-use crate::services::optimizer::{HardwareInfo, RecommendationEngine, RecommendedSettings};
-use crate::storage::config::{load_config, StreamingPlatform, StreamingStyle};
+use crate::obs::{
+    get_obs_client, get_obs_settings, get_recording_output, is_crash_unsafe_recording_format, set_output_mode,
+    ObsOutputMode,
+};
+use crate::obs::settings_cache;
+use crate::monitor::disk::get_available_space;
+use crate::services::benchmark::last_benchmark_result;
+use crate::services::performance_stats::measure_command;
+use crate::services::encoder_selector::{EncoderSelector, RecommendedEncoder};
+use crate::commands::utils::get_hardware_info;
+use crate::services::optimizer::{
+    OutputTarget, RecommendationEngine, RecommendedSettings, RecordingRecommendations,
+};
+use crate::services::reason_formatter::formatter_for;
+use crate::services::recommendation_history::{
+    diff_recommendations, find_entry, record_recommendation, RecommendationDiff,
+};
+use crate::services::upgrade_advisor::{advise_upgrades, UpgradeAdvice};
+use crate::storage::config::{load_config, save_config, StreamingPlatform, StreamingStyle};
+use crate::storage::recommendation_history::{load_recommendation_history, RecommendationHistoryEntry};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::PoisonError;
 
 /// OBS設定を取得
+///
+/// 取得から5秒間はキャッシュを返す。OBSの設定を変更するコマンドが成功するとキャッシュは破棄される
 #[tauri::command]
 pub async fn get_obs_settings_command() -> Result<crate::obs::ObsSettings, AppError> {
-    get_obs_settings().await
+    settings_cache::get_cached_obs_settings().await
+}
+
+/// OBS設定のキャッシュを破棄
+///
+/// OBS側で直接設定を変更した場合など、次回の `get_obs_settings_command` で最新の設定を取得させる
+#[tauri::command]
+pub async fn invalidate_obs_settings_cache() -> Result<(), AppError> {
+    settings_cache::invalidate_obs_settings_cache().await;
+    Ok(())
+}
+
+/// 録画の保存先・フォーマット・空き容量
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingOutputInfo {
+    /// 保存先フォルダ（取得できない場合はNone）
+    pub path: Option<String>,
+    /// 録画フォーマット（取得できない場合はNone）
+    pub format: Option<String>,
+    /// 保存先ボリュームの空き容量（バイト、取得できない場合はNone）
+    pub free_space_bytes: Option<u64>,
+    /// 録画終了後に自動でMP4に再多重化（リマックス）するか（取得できない場合はNone）
+    pub auto_remux: Option<bool>,
+    /// クラッシュ時にファイル全体を失うフォーマット（MP4・MOV）か
+    pub crash_unsafe: bool,
+}
+
+/// 録画の保存先・フォーマット・空き容量を取得
+#[tauri::command]
+pub async fn get_recording_output_info() -> Result<RecordingOutputInfo, AppError> {
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    let (path, format) = get_recording_output(&client).await?;
+    let auto_remux = client
+        .get_profile_parameter("Video", "AutoRemux")
+        .await
+        .ok()
+        .flatten()
+        .map(|value| value.eq_ignore_ascii_case("true") || value == "1");
+    let free_space_bytes = path.as_deref().and_then(|path| get_available_space(Path::new(path)));
+    let crash_unsafe = format.as_deref().is_some_and(is_crash_unsafe_recording_format);
+
+    Ok(RecordingOutputInfo {
+        path,
+        format,
+        free_space_bytes,
+        auto_remux,
+        crash_unsafe,
+    })
+}
+
+/// 現在のOBS設定の整合性を検証
+///
+/// 出力解像度が基本解像度を超えている等の不整合を項目ごとに返す。問題がない場合は空
+#[tauri::command]
+pub async fn validate_obs_settings() -> Result<Vec<crate::obs::SettingsValidationWarning>, AppError> {
+    Ok(get_obs_settings().await?.validate())
+}
+
+/// OBSの出力モード（基本/詳細）を切り替え
+///
+/// 推奨エンコーダー設定（Bフレーム・マルチパス・Look-ahead）の適用には詳細モードが必要
+#[tauri::command]
+pub async fn set_obs_output_mode(mode: ObsOutputMode) -> Result<(), AppError> {
+    set_output_mode(&get_obs_client(), mode).await?;
+    settings_cache::invalidate_obs_settings_cache().await;
+    Ok(())
+}
+
+/// HDR配信モードを切り替え
+///
+/// 有効にすると、推奨設定でNVENC HEVC（Main 10プロファイル）を選択する
+#[tauri::command]
+pub async fn set_hdr_mode(enabled: bool) -> Result<(), AppError> {
+    let _lock = crate::commands::config::CONFIG_SAVE_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.streaming_mode.hdr_mode = enabled;
+    save_config(&config)
 }
 
 /// 推奨設定を計算
 #[tauri::command]
 pub async fn calculate_recommendations() -> Result<RecommendedSettings, AppError> {
+    measure_command("calculate_recommendations", run_calculate_recommendations()).await
+}
+
+/// 推奨設定の計算の本体
+async fn run_calculate_recommendations() -> Result<RecommendedSettings, AppError> {
     // 設定を読み込み
     let config = load_config()?;
 
@@ -23,63 +132,245 @@ pub async fn calculate_recommendations() -> Result<RecommendedSettings, AppError
     let current_settings = get_obs_settings().await?;
 
     // ハードウェア情報を収集
-    let cpu_name = get_cpu_name().unwrap_or_else(|_| "Unknown CPU".to_string());
-    let cpu_cores = get_cpu_core_count().unwrap_or(4);
-    let (_, total_memory) = get_memory_info().unwrap_or((0, 8_000_000_000)); // デフォルト8GB
-    let total_memory_gb = total_memory as f64 / 1_000_000_000.0;
-    let gpu_info = get_gpu_info().await;
-
-    let hardware = HardwareInfo {
-        cpu_name,
-        cpu_cores,
-        total_memory_gb,
-        gpu: gpu_info,
-    };
+    let hardware = get_hardware_info().await;
 
     // 推奨設定を算出
-    let recommendations = RecommendationEngine::calculate_recommendations(
+    let mut recommendations = RecommendationEngine::calculate_recommendations_with_formatter(
+        &hardware,
+        Some(&current_settings),
+        config.streaming_mode.platform,
+        config.streaming_mode.style,
+        config.streaming_mode.network_speed_mbps,
+        formatter_for(config.display.language).as_ref(),
+    );
+    RecommendationEngine::apply_stream_latency(&mut recommendations, &hardware, config.streaming_mode.latency);
+    let mut context = RecommendationEngine::encoder_selection_context(
+        &hardware,
+        config.streaming_mode.platform,
+        config.streaming_mode.style,
+        config.streaming_mode.network_speed_mbps,
+    );
+    context.latency = config.streaming_mode.latency;
+    context.setup_type = config.streaming_mode.setup_type;
+    context.hdr_mode = config.streaming_mode.hdr_mode;
+    RecommendationEngine::apply_setup_type(&mut recommendations, &context);
+    RecommendationEngine::apply_hdr_mode(&mut recommendations, &context);
+
+    // 実機ベンチマーク結果があればプリセットを補正
+    if let Some(benchmark) = last_benchmark_result().await {
+        RecommendationEngine::apply_benchmark(&mut recommendations, &benchmark);
+    }
+
+    record_recommendation(
         &hardware,
-        &current_settings,
         config.streaming_mode.platform,
         config.streaming_mode.style,
         config.streaming_mode.network_speed_mbps,
+        OutputTarget::Streaming,
+        &recommendations,
     );
 
     Ok(recommendations)
 }
 
+/// 緊急用の安全な設定を生成
+///
+/// OBSに接続できない・設定が壊れて配信できない場合に手入力するための控えめな設定
+/// （720p30・2,500〜3,500kbps）を、検出したハードウェアのみから算出する。
+/// 推奨理由にOBSのメニューでの入力手順を含む
+///
+/// # Arguments
+/// * `platform` - 配信プラットフォーム
+#[tauri::command]
+pub async fn generate_safe_defaults(platform: StreamingPlatform) -> Result<RecommendedSettings, AppError> {
+    let hardware = get_hardware_info().await;
+    Ok(RecommendationEngine::calculate_safe_defaults(&hardware, platform))
+}
+
 /// 推奨設定をカスタムパラメーターで計算
+///
+/// `target` に `Recording` を指定した場合は録画用（品質固定）の推奨設定を返す。
+/// 省略時は配信用。
 #[tauri::command]
 pub async fn calculate_custom_recommendations(
     platform: StreamingPlatform,
     style: StreamingStyle,
     network_speed_mbps: f64,
+    target: Option<OutputTarget>,
 ) -> Result<RecommendedSettings, AppError> {
+    validate_recommendation_input(network_speed_mbps, None)?;
+    let language = load_config().map(|config| config.display.language).unwrap_or_default();
+
     // 現在のOBS設定を取得
     let current_settings = get_obs_settings().await?;
 
     // ハードウェア情報を収集
-    let cpu_name = get_cpu_name().unwrap_or_else(|_| "Unknown CPU".to_string());
-    let cpu_cores = get_cpu_core_count().unwrap_or(4);
-    let (_, total_memory) = get_memory_info().unwrap_or((0, 8_000_000_000)); // デフォルト8GB
-    let total_memory_gb = total_memory as f64 / 1_000_000_000.0;
-    let gpu_info = get_gpu_info().await;
-
-    let hardware = HardwareInfo {
-        cpu_name,
-        cpu_cores,
-        total_memory_gb,
-        gpu: gpu_info,
-    };
+    let hardware = get_hardware_info().await;
 
     // 推奨設定を算出
-    let recommendations = RecommendationEngine::calculate_recommendations(
+    let target = target.unwrap_or_default();
+    let recommendations = match target {
+        OutputTarget::Streaming => RecommendationEngine::calculate_recommendations_with_formatter(
+            &hardware,
+            Some(&current_settings),
+            platform,
+            style,
+            network_speed_mbps,
+            formatter_for(language).as_ref(),
+        ),
+        OutputTarget::Recording => RecommendationEngine::calculate_recording_recommendations(
+            &hardware,
+            &current_settings,
+            style,
+        ),
+    };
+
+    record_recommendation(&hardware, platform, style, network_speed_mbps, target, &recommendations);
+
+    Ok(recommendations)
+}
+
+/// 録画用の推奨設定を計算
+///
+/// ネットワーク速度を考慮せず、品質固定（CQP/CRF）のレート制御を推奨する
+///
+/// # Arguments
+/// * `style` - 配信スタイル（省略時は設定ファイルの値）
+#[tauri::command]
+pub async fn calculate_recording_recommendations(
+    style: Option<StreamingStyle>,
+) -> Result<RecommendedSettings, AppError> {
+    let style = match style {
+        Some(style) => style,
+        None => load_config()?.streaming_mode.style,
+    };
+
+    // 現在のOBS設定を取得
+    let current_settings = get_obs_settings().await?;
+
+    // ハードウェア情報を収集
+    let hardware = get_hardware_info().await;
+
+    Ok(RecommendationEngine::calculate_recording_recommendations(
         &hardware,
         &current_settings,
-        platform,
         style,
-        network_speed_mbps,
+    ))
+}
+
+/// ハードウェアティア別の録画専用推奨設定を計算
+///
+/// `calculate_recording_recommendations` と異なりOBSの現在の設定を参照せず、
+/// GPUの実効ティアのみからエンコーダー・レート制御・コンテナを決定する
+#[tauri::command]
+pub async fn calculate_tiered_recording_recommendations() -> Result<RecordingRecommendations, AppError> {
+    let hardware = get_hardware_info().await;
+    Ok(RecommendationEngine::calculate_for_recording(&hardware))
+}
+
+/// ハードウェア・回線のボトルネックとアップグレード効果を取得
+///
+/// 目標の配信条件に対して推奨設定を制限しているコンポーネントを、改善効果の大きい順に返す。
+/// 省略したパラメーターは設定ファイルの値を使用する
+///
+/// # Arguments
+/// * `platform` - 目標の配信プラットフォーム
+/// * `style` - 目標の配信スタイル
+/// * `network_speed_mbps` - 上り回線速度（Mbps）
+#[tauri::command]
+pub async fn get_upgrade_advice(
+    platform: Option<StreamingPlatform>,
+    style: Option<StreamingStyle>,
+    network_speed_mbps: Option<f64>,
+) -> Result<Vec<UpgradeAdvice>, AppError> {
+    if let Some(network_speed) = network_speed_mbps {
+        validate_recommendation_input(network_speed, None)?;
+    }
+
+    let streaming_mode = load_config()?.streaming_mode;
+    let hardware = get_hardware_info().await;
+
+    Ok(advise_upgrades(
+        &hardware,
+        platform.unwrap_or(streaming_mode.platform),
+        style.unwrap_or(streaming_mode.style),
+        network_speed_mbps.unwrap_or(streaming_mode.network_speed_mbps),
+    ))
+}
+
+/// 推奨順のエンコーダー選択肢を取得
+///
+/// 先頭は `calculate_recommendations` と同じ推奨エンコーダー。
+/// ハードウェアAV1エンコーダーがないハイエンドCPU環境でのYouTube配信では、
+/// 高画質な代替としてAOM AV1（ソフトウェア）を含める
+#[tauri::command]
+pub async fn get_encoder_options() -> Result<Vec<RecommendedEncoder>, AppError> {
+    let streaming_mode = load_config()?.streaming_mode;
+    let hardware = get_hardware_info().await;
+
+    let mut context = RecommendationEngine::encoder_selection_context(
+        &hardware,
+        streaming_mode.platform,
+        streaming_mode.style,
+        streaming_mode.network_speed_mbps,
     );
+    context.latency = streaming_mode.latency;
+    context.setup_type = streaming_mode.setup_type;
+    context.hdr_mode = streaming_mode.hdr_mode;
 
-    Ok(recommendations)
+    Ok(EncoderSelector::rank_encoders(&context))
+}
+
+/// 推奨設定の履歴を取得
+///
+/// `calculate_recommendations` / `calculate_custom_recommendations` の計算結果を
+/// 入力条件とともに新しい順に返す（最大50件）
+///
+/// # Arguments
+/// * `limit` - 取得する件数（省略時は全件）
+#[tauri::command]
+pub async fn get_recommendation_history(
+    limit: Option<usize>,
+) -> Result<Vec<RecommendationHistoryEntry>, AppError> {
+    let mut entries = load_recommendation_history()?;
+    entries.reverse();
+    if let Some(limit) = limit {
+        entries.truncate(limit);
+    }
+    Ok(entries)
+}
+
+/// 推奨設定の履歴2件の差分を取得
+///
+/// 変化した入力条件と推奨設定、および変化の説明
+/// （例: "回線速度 10→4Mbps → ビットレート 6000→3500kbps"）を返す
+///
+/// # Arguments
+/// * `from_id` - 比較元の履歴ID
+/// * `to_id` - 比較先の履歴ID
+#[tauri::command]
+pub async fn diff_recommendation_history(
+    from_id: String,
+    to_id: String,
+) -> Result<RecommendationDiff, AppError> {
+    let entries = load_recommendation_history()?;
+    Ok(diff_recommendations(find_entry(&entries, &from_id)?, find_entry(&entries, &to_id)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_recommendation_history_respects_limit() {
+        let result = get_recommendation_history(Some(1)).await;
+        assert!(result.is_ok());
+        assert!(result.expect("Failed to get recommendation history in test").len() <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_diff_recommendation_history_unknown_id() {
+        let result = diff_recommendation_history("missing-from".to_string(), "missing-to".to_string()).await;
+        assert!(result.is_err());
+    }
 }
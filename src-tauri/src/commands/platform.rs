@@ -0,0 +1,51 @@
+// 配信プラットフォームのAPI連携のコマンド
+//
+// APIトークンの保存・確認と、配信タイトル・カテゴリーの更新を提供する。
+// トークンはキーリングにのみ保存し、フロントエンドへは返さない
+
+use crate::error::AppError;
+use crate::services::platform_api::{self, PlatformAccount, StreamInfo};
+use crate::storage::config::StreamingPlatform;
+
+/// 配信プラットフォームのAPIトークンを保存
+///
+/// # Arguments
+/// * `platform` - 配信プラットフォーム（現在はTwitchのみ対応）
+/// * `token` - ユーザーが発行したOAuthトークン（`oauth:` 接頭辞は取り除く）
+#[tauri::command]
+pub async fn save_platform_token(platform: StreamingPlatform, token: String) -> Result<(), AppError> {
+    platform_api::save_token(platform, &token)
+}
+
+/// 配信プラットフォームのAPIトークンを削除
+#[tauri::command]
+pub async fn delete_platform_token(platform: StreamingPlatform) -> Result<(), AppError> {
+    platform_api::delete_token(platform)
+}
+
+/// 保存されたAPIトークンを確認
+///
+/// # Returns
+/// トークンのアカウントと、配信情報を変更する権限の有無
+#[tauri::command]
+pub async fn test_platform_token(platform: StreamingPlatform) -> Result<PlatformAccount, AppError> {
+    platform_api::verify_token(platform).await
+}
+
+/// 配信タイトル・カテゴリーを更新
+///
+/// 失敗した場合は `PLATFORM_AUTH` / `PLATFORM_API` / `PLATFORM_NOT_SUPPORTED`
+/// （`details.kind` に原因の種類）を返す
+///
+/// # Arguments
+/// * `platform` - 配信プラットフォーム
+/// * `title` - 配信タイトル
+/// * `category` - カテゴリー名（Noneの場合は変更しない）
+#[tauri::command]
+pub async fn update_stream_info(
+    platform: StreamingPlatform,
+    title: String,
+    category: Option<String>,
+) -> Result<(), AppError> {
+    platform_api::update_stream_info(platform, &StreamInfo { title, category }).await
+}
@@ -1,20 +1,59 @@
 // プロファイル管理コマンド
 
 use crate::error::AppError;
+use crate::commands::optimization::{
+    apply_profile_settings, capture_audio_inputs_or_empty, create_settings_backup,
+};
 use crate::storage::{
-    SettingsProfile, ProfileSettings, ProfileSummary,
-    get_profiles as storage_get_profiles,
+    SettingsProfile, ProfileSettings, ProfileSummary, ProfileFilter, BackupTrigger,
+    get_profiles_filtered as storage_get_profiles_filtered,
     get_profile as storage_get_profile,
     save_profile as storage_save_profile,
     delete_profile as storage_delete_profile,
+    update_profile_metadata as storage_update_profile_metadata,
+    mark_profile_applied as storage_mark_profile_applied,
 };
-use crate::obs::{get_obs_client, get_obs_settings};
-use crate::services::get_streaming_mode_service;
+use crate::storage::profiles::normalize_tags;
+use crate::obs::{get_obs_client, get_obs_settings, invalidate_obs_settings_cache};
+use crate::services::{ensure_no_blocking_outputs, get_operation_lock, get_streaming_mode_service, OperationKind};
+use crate::services::profile_diff::{compute_profile_diff, ProfileDiff};
+use crate::services::debug_recorder::traced_command;
+use crate::services::obs_file_backup::{delete_obs_file_backup, obs_file_backups_dir};
+use serde::{Deserialize, Serialize};
+
+/// プロファイル適用結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileApplyResult {
+    /// スキップした項目ごとの警告（削除された音声入力・接続されていないデバイス等）
+    pub warnings: Vec<String>,
+}
 
 /// プロファイル一覧を取得
+///
+/// # Arguments
+/// * `filter` - 絞り込み条件（プラットフォーム・スタイル・お気に入り。省略時は全件）
 #[tauri::command]
-pub async fn get_profiles() -> Result<Vec<ProfileSummary>, AppError> {
-    storage_get_profiles()
+pub async fn get_profiles(filter: Option<ProfileFilter>) -> Result<Vec<ProfileSummary>, AppError> {
+    storage_get_profiles_filtered(&filter.unwrap_or_default())
+}
+
+/// お気に入りのプロファイル一覧を取得
+#[tauri::command]
+pub async fn get_favorite_profiles() -> Result<Vec<ProfileSummary>, AppError> {
+    storage_get_profiles_filtered(&ProfileFilter::favorites())
+}
+
+/// プロファイルのメタデータ（説明・タグ・お気に入り）を更新
+#[tauri::command]
+pub async fn update_profile_metadata(
+    id: String,
+    description: String,
+    tags: Vec<String>,
+    is_favorite: bool,
+) -> Result<(), AppError> {
+    storage_update_profile_metadata(&id, description, tags, is_favorite)?;
+    Ok(())
 }
 
 /// プロファイルを取得
@@ -30,38 +69,67 @@ pub async fn save_profile(profile: SettingsProfile) -> Result<(), AppError> {
 }
 
 /// プロファイルを削除
+///
+/// バックアップの場合は、一緒に保存したOBSのファイルも削除する
 #[tauri::command]
 pub async fn delete_profile(profile_id: String) -> Result<(), AppError> {
-    storage_delete_profile(&profile_id)
+    storage_delete_profile(&profile_id)?;
+    if let Err(e) = obs_file_backups_dir().and_then(|dir| delete_obs_file_backup(&dir, &profile_id)) {
+        tracing::warn!(target: "profiles", profile_id = %profile_id, error = %e, "OBSのファイルのバックアップの削除に失敗");
+    }
+    Ok(())
 }
 
 /// プロファイルをOBSに適用
 ///
-/// OBSに接続していない場合はエラーを返す
+/// 適用前に現在の設定を自動バックアップする。
+/// OBSに接続していない場合や配信中の場合はエラーを返す。
+/// TOCTOU競合条件を防ぐためロックを使用。
+/// 他の設定操作が実行中の場合は `OPERATION_IN_PROGRESS` エラー。
+/// 見つからない音声入力・デバイスはスキップし、結果の `warnings` に含める。
 #[tauri::command]
-pub async fn apply_profile(profile_id: String) -> Result<(), AppError> {
-    // 配信中の場合は適用を拒否
+pub async fn apply_profile(profile_id: String) -> Result<ProfileApplyResult, AppError> {
+    // プロファイルを読み込み（ロック取得前に存在を確認）
+    let profile = storage_get_profile(&profile_id)?;
+
+    let _operation = get_operation_lock().try_acquire(OperationKind::ApplyProfile)?;
     let streaming_service = get_streaming_mode_service();
-    if streaming_service.is_streaming_mode().await {
-        return Err(AppError::obs_state(
-            "配信中のため設定を変更できません。配信を停止してから再度お試しください。"
-        ));
-    }
 
-    // プロファイルを読み込み（将来のOBS設定適用で使用予定）
-    let _profile = storage_get_profile(&profile_id)?;
+    // TOCTOU対策: ロックを取得し、配信中でないことを確認してから操作を実行
+    let apply = streaming_service
+        .execute_if_not_streaming(|| async {
+            // OBS接続確認
+            let client = get_obs_client();
+            if !client.is_connected().await {
+                return Err(AppError::obs_not_connected());
+            }
 
-    // OBS接続確認
-    let client = get_obs_client();
-    if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
-    }
+            // OBS側で直接開始された配信・仮想カメラも検出する
+            ensure_no_blocking_outputs(&client.get_status().await?)?;
 
-    // TODO: Phase 2bでOBS設定適用APIを実装予定
-    // 現時点ではプロファイル読み込みのみ実装
-    // 将来的にobwsを使用して設定を適用
+            // 現在の設定をバックアップ
+            create_settings_backup(BackupTrigger::PreProfileApply).await?;
 
-    Ok(())
+            apply_profile_settings(&client, &profile.settings).await
+        });
+    let warnings = traced_command("apply_profile", apply).await?;
+    invalidate_obs_settings_cache().await;
+
+    // 適用日時を記録
+    storage_mark_profile_applied(&profile_id)?;
+
+    Ok(ProfileApplyResult { warnings })
+}
+
+/// プロファイルを適用した場合の変更内容をプレビュー（ドライラン）
+///
+/// 現在のOBS設定とプロファイルの設定を項目ごとに比較する。
+/// OBSの設定を変更するAPIは一切呼び出さない。
+#[tauri::command]
+pub async fn apply_profile_dry_run(profile_id: String) -> Result<ProfileDiff, AppError> {
+    let profile = storage_get_profile(&profile_id)?;
+    let current_settings = get_obs_settings().await?;
+    Ok(compute_profile_diff(&current_settings, &profile.settings))
 }
 
 /// 現在のOBS設定をプロファイルとして保存
@@ -71,9 +139,12 @@ pub async fn save_current_settings_as_profile(
     description: String,
     platform: crate::storage::config::StreamingPlatform,
     style: crate::storage::config::StreamingStyle,
+    tags: Option<Vec<String>>,
+    is_favorite: Option<bool>,
 ) -> Result<String, AppError> {
     // 現在のOBS設定を取得
     let current_settings = get_obs_settings().await?;
+    let audio_inputs = capture_audio_inputs_or_empty(&get_obs_client()).await;
 
     // プロファイルIDを生成（UUID）
     let profile_id = uuid::Uuid::new_v4().to_string();
@@ -101,6 +172,7 @@ pub async fn save_current_settings_as_profile(
             audio: crate::storage::profiles::AudioSettings {
                 sample_rate: current_settings.audio.sample_rate,
                 bitrate_kbps: 160, // デフォルト値
+                inputs: audio_inputs,
             },
             output: crate::storage::profiles::OutputSettings {
                 encoder: current_settings.output.encoder,
@@ -112,6 +184,11 @@ pub async fn save_current_settings_as_profile(
         },
         created_at: now,
         updated_at: now,
+        tags: normalize_tags(tags.unwrap_or_default()),
+        is_favorite: is_favorite.unwrap_or(false),
+        last_applied_at: None,
+        is_backup: false,
+        backup_trigger: None,
     };
 
     // プロファイルを保存
@@ -0,0 +1,85 @@
+// グローバルショートカットのコマンド
+//
+// ショートカットの割り当てと、OBS・他のアプリのホットキーとの競合の確認を提供する
+
+use crate::commands::config::CONFIG_SAVE_LOCK;
+use crate::error::AppError;
+use crate::obs::get_obs_client;
+use crate::services::shortcuts::{
+    find_shortcut_conflicts, read_obs_hotkeys, Accelerator, ObsHotkeyBinding, ShortcutAction, ShortcutConflict,
+};
+use crate::storage::config::{load_config, save_config, ShortcutBindings};
+use std::sync::PoisonError;
+
+/// OBSに割り当てられたホットキーを取得（未接続・取得失敗の場合は空）
+async fn load_obs_hotkeys() -> Vec<ObsHotkeyBinding> {
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Vec::new();
+    }
+    read_obs_hotkeys(&client).await.unwrap_or_else(|e| {
+        tracing::warn!(target: "shortcuts", error = %e, "OBSのホットキーの取得に失敗");
+        Vec::new()
+    })
+}
+
+/// 割り当て済みのショートカットの競合を取得
+///
+/// OBSに接続していない場合は、既知のアプリのショートカットとのみ照合する
+#[tauri::command]
+pub async fn get_shortcut_conflicts() -> Result<Vec<ShortcutConflict>, AppError> {
+    let bindings = load_config()?.display.shortcuts.parsed();
+    if bindings.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(find_shortcut_conflicts(&bindings, &load_obs_hotkeys().await))
+}
+
+/// 操作にショートカットを割り当てる
+///
+/// OBSや他のアプリのショートカットと競合する場合は `VALIDATION_FAILED`
+/// （`details.conflicts` に競合の一覧）を返し、保存しない。
+/// `force` を指定した場合は競合があっても保存する
+///
+/// # Arguments
+/// * `action` - 割り当てる操作
+/// * `accelerator` - ショートカット（例: "Ctrl+Shift+F9"、Noneで割り当てを解除）
+/// * `force` - 競合があっても割り当てるか
+///
+/// # Returns
+/// 保存後の割り当て
+#[tauri::command]
+pub async fn set_shortcut_binding(
+    action: ShortcutAction,
+    accelerator: Option<String>,
+    force: bool,
+) -> Result<ShortcutBindings, AppError> {
+    let accelerator = accelerator.as_deref().map(Accelerator::parse).transpose()?;
+
+    if let Some(accelerator) = &accelerator {
+        let conflicts = find_shortcut_conflicts(&[(action, accelerator.clone())], &load_obs_hotkeys().await);
+        if !conflicts.is_empty() && !force {
+            let names: Vec<&str> = conflicts.iter().map(|c| c.conflicting_with.as_str()).collect();
+            return Err(AppError::validation_failed(&format!(
+                "ショートカット「{accelerator}」は次のショートカットと競合しています: {}",
+                names.join(", ")
+            ))
+            .with_details(serde_json::json!({ "conflicts": conflicts })));
+        }
+    }
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config
+        .display
+        .shortcuts
+        .set_binding(action, accelerator.as_ref().map(ToString::to_string));
+    config.display.shortcuts.validate().map_err(|errors| {
+        AppError::validation_failed(&format!("ショートカットが不正です: {}", errors.join(", ")))
+            .with_details(serde_json::json!({ "errors": errors }))
+    })?;
+    save_config(&config)?;
+
+    tracing::info!(target: "shortcuts", action = action.field_name(), force, "ショートカットを割り当てました");
+    Ok(config.display.shortcuts)
+}
@@ -0,0 +1,16 @@
+// 起動時ダイジェストコマンド
+//
+// 前回終了時から実行環境がどう変わったかをフロントエンドに返す
+
+use crate::error::AppError;
+use crate::services::startup_digest::{self, StartupDigest};
+use tauri::AppHandle;
+
+/// 前回セッションからの環境変化を取得
+///
+/// OBS・GPUドライバーの更新、設定の変更、モニター構成の変化を
+/// 重要度付きで返す。初回起動時は変化なしとなる。
+#[tauri::command]
+pub async fn get_startup_digest(app_handle: AppHandle) -> Result<StartupDigest, AppError> {
+    Ok(startup_digest::get_startup_digest(&app_handle).await)
+}
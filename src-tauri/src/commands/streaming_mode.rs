@@ -1,13 +1,32 @@
 // 配信中モード管理コマンド
 
+use tauri::AppHandle;
+
 use crate::error::AppError;
+use crate::obs::{start_output_stats_polling, stop_output_stats_polling};
 use crate::services::get_streaming_mode_service;
+use crate::services::stream_session::{begin_stream_session, finish_stream_session};
 
 /// 配信中モードを設定
+///
+/// 配信中モードの間は、OBSの配信出力の統計を1秒ごとに取得して `obs:stats-update` イベントを発行する。
+/// 有効化で履歴DBの配信セッションを開始し、無効化で終了する
 #[tauri::command]
-pub async fn set_streaming_mode(enabled: bool) -> Result<(), AppError> {
+pub async fn set_streaming_mode(app_handle: AppHandle, enabled: bool) -> Result<(), AppError> {
     let service = get_streaming_mode_service();
     service.set_streaming_mode(enabled).await;
+
+    if enabled {
+        start_output_stats_polling(app_handle).await;
+    } else {
+        stop_output_stats_polling().await;
+    }
+
+    // セッションの記録に失敗しても配信中モードの切り替えは成功として扱う
+    let session = if enabled { begin_stream_session().await } else { finish_stream_session().await };
+    if let Err(e) = session {
+        tracing::warn!(target: "stream_session", error = %e, enabled, "配信セッションの記録に失敗");
+    }
     Ok(())
 }
 
@@ -1,7 +1,30 @@
 use serde::Serialize;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use crate::commands::config::CONFIG_SAVE_LOCK;
 use crate::error::AppError;
-use crate::monitor::{GpuMetrics, NetworkMetrics, ObsProcessMetrics};
+use crate::monitor::conflicts::SoftwareConflict;
+use crate::monitor::network::NetworkInterfaceInfo;
+use crate::monitor::hardware_cache::{calculate_throttle_percent, is_cpu_throttling};
+use crate::monitor::{GpuMetrics, NetworkMetrics, ObsProcessMetrics, ProcessPriority};
+use crate::services::alerts::{update_metric_and_notify, MetricType};
+use crate::services::analyzer::{record_headroom_sample, HeadroomSample};
+use crate::services::data_usage::record_upload_sample;
+use crate::services::debug_recorder::record_monitor_tick;
+use crate::services::observer_server::{record_observer_metrics, ObserverMetrics};
+use crate::services::stream_session::record_session_metrics;
+use crate::services::streaming_overhead::{
+    compute_streaming_overhead, latest_streaming_overhead, record_streaming_overhead, StreamingOverhead,
+};
+use crate::services::detected_hardware::{get_or_detect_hardware, DetectedHardware};
+use crate::services::performance_stats::{
+    measure_stage, record_monitor_tick_duration, record_performance, PerformanceKind,
+};
+use crate::commands::utils::get_hardware_info;
 use crate::services::system_monitor_service;
+use crate::storage::config::{cached_config, load_config, save_config};
+use crate::storage::metrics_history::SystemMetricsSnapshot;
 
 // ========================================
 // 型定義（contracts/api.md に準拠）
@@ -47,6 +70,14 @@ pub struct SystemMetrics {
     pub gpu: Option<GpuMetrics>,
     /// ネットワーク情報
     pub network: NetworkMetrics,
+    /// 現在のCPUクロック周波数（MHz、取得できない場合はnull）
+    pub cpu_frequency_mhz: Option<f64>,
+    /// CPUのベースクロック周波数（MHz、最初の計測値。未計測の場合はnull）
+    pub cpu_base_frequency_mhz: Option<f64>,
+    /// ベースクロックからの低下率（0.0-1.0、算出できない場合はnull）
+    pub cpu_throttle_percent: Option<f32>,
+    /// 低下率が25%を超えているか（サーマルスロットリングの可能性）
+    pub cpu_is_throttling: bool,
 }
 
 /// レガシー形式のシステムメトリクス（後方互換性用）
@@ -64,18 +95,48 @@ pub struct LegacySystemMetrics {
 
 /// システムメトリクスを取得（契約準拠の完全版）
 ///
-/// CPU、メモリ、GPU、ネットワークの詳細情報を返す
+/// CPU、メモリ、GPU、ネットワークの詳細情報を返す。
+/// アラートの通知は発行しない（定期的な監視には `poll_system_metrics` を使う）
 #[tauri::command]
 pub async fn get_system_metrics() -> Result<SystemMetrics, AppError> {
+    collect_system_metrics(None).await
+}
+
+/// 監視ティックとしてシステムメトリクスを取得
+///
+/// `get_system_metrics` と同じメトリクスを返す。
+/// 配信セッション中はメトリクスを履歴DBに保存し、アップロード速度はセッションのアップロード量として積算し、
+/// CPU・GPU・メモリ使用率でアラートと自動化ルールを評価して通知する。
+/// 所要時間は監視ティックとして記録し、更新間隔を超えた場合は警告を出力する
+#[tauri::command]
+pub async fn poll_system_metrics(app_handle: AppHandle) -> Result<SystemMetrics, AppError> {
+    let started = Instant::now();
+    let result = collect_system_metrics(Some(&app_handle)).await;
+
+    // 設定ファイルが保存されたときだけ読み直した設定から更新間隔を取得する
+    let interval_ms = cached_config()
+        .map(|config| config.monitoring.update_interval_ms)
+        .unwrap_or_else(|_| crate::storage::config::MonitoringConfig::default().update_interval_ms);
+    record_monitor_tick_duration(started.elapsed(), Duration::from_millis(interval_ms));
+    result
+}
+
+/// システムメトリクスを取得し、監視の段階ごとの所要時間を記録する
+///
+/// `app_handle` がない場合はアラートの通知を発行しない
+async fn collect_system_metrics(app_handle: Option<&AppHandle>) -> Result<SystemMetrics, AppError> {
     // サービス層経由で各メトリクスを取得し、コマンド用の型に変換
     let service = system_monitor_service();
 
-    let cpu_usage = service.get_cpu_usage()?;
+    let cpu_usage = measure_stage("cpu_usage", || service.get_cpu_usage())?;
     let core_count = service.get_cpu_core_count()?;
     let per_core_usage = service.get_per_core_cpu_usage()?;
     let cpu_name = service.get_cpu_name()?;
+    let cpu_frequency_mhz = service.get_cpu_frequency_mhz()?;
+    let cpu_base_frequency_mhz = service.get_cpu_base_frequency_mhz();
+    let cpu_throttle_percent = calculate_throttle_percent(cpu_frequency_mhz, cpu_base_frequency_mhz);
 
-    let (memory_used, memory_total) = service.get_memory_info()?;
+    let (memory_used, memory_total) = measure_stage("memory_info", || service.get_memory_info())?;
     let memory_available = service.get_available_memory()?;
     let memory_usage_percent = if memory_total > 0 {
         (memory_used as f64 / memory_total as f64 * 100.0) as f32
@@ -83,8 +144,66 @@ pub async fn get_system_metrics() -> Result<SystemMetrics, AppError> {
         0.0
     };
 
-    let gpu = service.get_gpu_metrics()?;
-    let network = service.get_network_metrics()?;
+    let gpu = measure_stage("gpu_metrics", || service.get_gpu_metrics())?;
+    let network = measure_stage("network_metrics", || service.get_network_metrics())?;
+
+    let upload_kbps = network.upload_bytes_per_sec as f64 * 8.0 / 1000.0;
+    record_headroom_sample(HeadroomSample {
+        cpu_usage,
+        gpu_usage: gpu.as_ref().map(|gpu| gpu.usage_percent),
+        encoder_usage: gpu.as_ref().and_then(|gpu| gpu.encoder_usage),
+        upload_kbps,
+    });
+    record_upload_sample(app_handle, network.upload_bytes_per_sec).await;
+    record_session_metrics(SystemMetricsSnapshot::from_metrics(
+        cpu_usage,
+        cpu_frequency_mhz,
+        memory_used,
+        memory_total,
+        gpu.as_ref(),
+        &network,
+    ))
+    .await;
+
+    // OBSのプロセスを取得できない場合も他のメトリクスは返す
+    let overhead = measure_stage("obs_process_metrics", || service.get_obs_process_metrics())
+        .ok()
+        .and_then(|process| compute_streaming_overhead(&process, gpu.as_ref(), core_count));
+    record_streaming_overhead(overhead.clone()).await;
+    record_observer_metrics(ObserverMetrics {
+        timestamp: chrono::Utc::now().timestamp(),
+        cpu_usage,
+        memory_usage_percent,
+        gpu_usage: gpu.as_ref().map(|gpu| gpu.usage_percent),
+        encoder_usage: gpu.as_ref().and_then(|gpu| gpu.encoder_usage),
+        upload_kbps,
+        overhead,
+    });
+
+    if let Some(app_handle) = app_handle {
+        let alerts_started = Instant::now();
+        for (metric, value) in [
+            (MetricType::CpuUsage, Some(cpu_usage)),
+            (MetricType::GpuUsage, gpu.as_ref().map(|gpu| gpu.usage_percent)),
+            (MetricType::MemoryUsage, Some(memory_usage_percent)),
+        ] {
+            let Some(value) = value else { continue };
+            if let Err(e) = update_metric_and_notify(app_handle, metric, f64::from(value)).await {
+                tracing::warn!(target: "alerts", error = %e, ?metric, "アラート通知の発行に失敗しました");
+            }
+        }
+        record_performance(PerformanceKind::MonitoringStage, "alert_rules", alerts_started.elapsed(), true);
+    }
+
+    record_monitor_tick(
+        "system_metrics",
+        serde_json::json!({
+            "cpuUsage": cpu_usage,
+            "cpuFrequencyMhz": cpu_frequency_mhz,
+            "memoryUsagePercent": memory_usage_percent,
+            "gpuUsage": gpu.as_ref().map(|gpu| gpu.usage_percent),
+        }),
+    );
 
     Ok(SystemMetrics {
         cpu: CpuMetrics {
@@ -101,6 +220,10 @@ pub async fn get_system_metrics() -> Result<SystemMetrics, AppError> {
         },
         gpu,
         network,
+        cpu_frequency_mhz,
+        cpu_base_frequency_mhz,
+        cpu_throttle_percent,
+        cpu_is_throttling: is_cpu_throttling(cpu_throttle_percent),
     })
 }
 
@@ -111,6 +234,103 @@ pub async fn get_process_metrics() -> Result<ObsProcessMetrics, AppError> {
     service.get_obs_process_metrics()
 }
 
+/// OBSの負荷（OBSプロセスのCPU・メモリ使用量とGPU・エンコーダー使用率）を取得
+///
+/// 値は `get_system_metrics` の監視ティックごとに更新される
+///
+/// # Returns
+/// 最新のOBSの負荷（監視を開始していない・OBSが起動していない場合はNone）
+#[tauri::command]
+pub async fn get_streaming_overhead() -> Result<Option<StreamingOverhead>, AppError> {
+    Ok(latest_streaming_overhead())
+}
+
+/// OBSプロセスの情報をCPU優先度付きで取得
+///
+/// 優先度の取得はOSコマンドの実行を伴う場合があるため、
+/// 定期ポーリングには `get_process_metrics` を使用すること
+#[tauri::command]
+pub async fn get_obs_process_info() -> Result<ObsProcessMetrics, AppError> {
+    let service = system_monitor_service();
+    service.get_obs_process_info()
+}
+
+/// OBSプロセスのCPU優先度を変更し、設定に保存
+///
+/// # Arguments
+/// * `priority` - 設定する優先度
+#[tauri::command]
+pub async fn set_obs_priority(priority: ProcessPriority) -> Result<(), AppError> {
+    let service = system_monitor_service();
+    service.set_obs_process_priority(priority)?;
+
+    let mut config = load_config()?;
+    config.obs_process_priority = priority;
+    save_config(&config)
+}
+
+/// 実行中の既知ゲームを取得
+///
+/// 設定の `knownGameExecutables` に一致するプロセス名を返す
+#[tauri::command]
+pub async fn get_detected_games() -> Result<Vec<String>, AppError> {
+    crate::services::game_detector::get_detected_games()
+}
+
+/// 検出したハードウェア情報と分類結果を取得
+///
+/// 検出結果はキャッシュされる。ドライバー更新やハードウェア変更後は
+/// `refresh` に true を指定して再検出する
+///
+/// # Arguments
+/// * `refresh` - キャッシュを破棄して再検出するか（省略時はfalse）
+#[tauri::command]
+pub async fn get_detected_hardware(refresh: Option<bool>) -> Result<DetectedHardware, AppError> {
+    Ok(get_or_detect_hardware(refresh.unwrap_or(false), get_hardware_info).await)
+}
+
+/// ネットワークインターフェースの一覧を取得
+///
+/// 速度は前回の計測からの差分で算出するため、初回は0になる場合がある
+///
+/// # Returns
+/// インターフェース名と現在の送受信速度（ビット/秒、ループバックを除く）
+#[tauri::command]
+pub async fn get_network_interfaces() -> Result<Vec<NetworkInterfaceInfo>, AppError> {
+    system_monitor_service().get_network_interfaces()
+}
+
+/// 帯域を計測するネットワークインターフェースを設定
+///
+/// # Arguments
+/// * `name` - インターフェース名。Noneまたは空文字の場合はアップロード速度が最大のものを自動で選ぶ
+#[tauri::command]
+pub async fn set_preferred_network_interface(name: Option<String>) -> Result<(), AppError> {
+    let name = name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+    if let Some(name) = &name {
+        let interfaces = system_monitor_service().get_network_interfaces()?;
+        if !interfaces.iter().any(|info| &info.name == name) {
+            return Err(AppError::validation_failed(&format!(
+                "ネットワークインターフェースが見つかりません: {name}"
+            )));
+        }
+    }
+
+    let _lock = CONFIG_SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut config = load_config()?;
+    config.monitoring.preferred_network_interface = name;
+    save_config(&config)
+}
+
+/// 実行中の競合ソフトウェアを取得
+///
+/// OBSのキャプチャー・エンコードを妨げることが多いオーバーレイ・フックを行う常駐ソフトを、
+/// 影響と対処方法とともに返す
+#[tauri::command]
+pub async fn get_software_conflicts() -> Result<Vec<SoftwareConflict>, AppError> {
+    system_monitor_service().get_software_conflicts()
+}
+
 /// レガシー形式のシステムメトリクスを取得（後方互換性用）
 ///
 /// 既存のフロントエンドコードとの互換性を維持するために提供
@@ -0,0 +1,15 @@
+// 匿名ハードウェア統計コマンド
+//
+// 送信が有効になる前に、送信される内容をそのまま確認できるようにする
+
+use crate::error::AppError;
+use crate::services::telemetry::{preview_telemetry, TelemetryPreview};
+
+/// 送信される匿名ハードウェア統計のプレビューを取得
+///
+/// 送信の有効・無効に関わらず、現在の環境で送信されるデータを返す。
+/// 送信の有効化・送信先の設定は `save_app_config` で行う。
+#[tauri::command]
+pub async fn preview_telemetry_payload() -> Result<TelemetryPreview, AppError> {
+    Ok(preview_telemetry().await)
+}
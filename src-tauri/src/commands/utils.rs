@@ -2,28 +2,25 @@
 //
 // 複数のコマンドで共有する関数を提供
 
-use crate::monitor::{get_cpu_core_count, get_memory_info};
+use crate::monitor::{get_cpu_core_count, get_cpu_name, get_memory_info};
+use crate::monitor::display::get_display_info;
 use crate::monitor::gpu::get_gpu_info;
+use crate::monitor::power::get_power_status;
 use crate::services::optimizer::HardwareInfo;
-use sysinfo::System;
+
+pub use crate::storage::metrics_history::history_store;
 
 /// CPUモデル名を取得
 ///
 /// # Returns
 /// CPUブランド名（取得できない場合は"Unknown CPU"）
 fn get_cpu_model_name() -> String {
-    let mut sys = System::new();
-    sys.refresh_all();
-
-    let cpus = sys.cpus();
-    if let Some(cpu) = cpus.first() {
-        let brand = cpu.brand().trim();
-        if !brand.is_empty() {
-            return brand.to_string();
-        }
-    }
-
-    "Unknown CPU".to_string()
+    // 共有のシステム情報を使い、呼び出しごとに全プロセスを列挙しない
+    get_cpu_name()
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "Unknown CPU".to_string())
 }
 
 /// ハードウェア情報を取得（共通関数）
@@ -43,5 +40,19 @@ pub async fn get_hardware_info() -> HardwareInfo {
         cpu_cores,
         total_memory_gb,
         gpu: gpu_info,
+        power: get_power_status().await,
+        displays: get_display_info().await,
+        benchmark_result: get_cpu_benchmark_result().await,
     }
 }
+
+/// CPUエンコードベンチマークの結果を取得（共通関数）
+///
+/// 初回のみ計測し（数秒かかる）、以降はキャッシュを返す。
+/// ffmpegがないなど計測できない場合はNone（理由は `cpu_benchmark_error` で取得できる）
+pub async fn get_cpu_benchmark_result() -> Option<u32> {
+    tokio::task::spawn_blocking(crate::monitor::cpu_benchmark::cached_cpu_encode_speed)
+        .await
+        .ok()
+        .flatten()
+}
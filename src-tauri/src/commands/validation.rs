@@ -0,0 +1,249 @@
+// コマンド入力値の検証
+//
+// 推奨設定の算出・適用系コマンドで共通の範囲チェックを行い、
+// フィールド単位のエラーをまとめてフロントエンドに返す。
+// プラットフォーム・スタイルは列挙型のため、未知の値は引数のデシリアライズ時に拒否される。
+
+use crate::error::AppError;
+use crate::services::benchmark::{MAX_ENCODER_BENCHMARK_DURATION_SECS, MIN_ENCODER_BENCHMARK_DURATION_SECS};
+use crate::services::optimizer::HardwareInfo;
+use std::collections::BTreeMap;
+
+/// ネットワーク速度の下限（Mbps）
+pub const MIN_NETWORK_SPEED_MBPS: f64 = 0.1;
+
+/// ネットワーク速度の上限（Mbps）
+pub const MAX_NETWORK_SPEED_MBPS: f64 = 10_000.0;
+
+/// フィールド単位の検証エラー
+///
+/// キーはフロントエンドの引数名（camelCase）、値は表示用メッセージ
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    fields: BTreeMap<String, String>,
+}
+
+impl ValidationErrors {
+    /// 空のエラー一覧を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// フィールドのエラーを追加（同じフィールドは最初のエラーを優先）
+    pub fn add(&mut self, field: &str, message: &str) {
+        self.fields
+            .entry(field.to_string())
+            .or_insert_with(|| message.to_string());
+    }
+
+    /// エラーがないか
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// フィールドごとのエラー
+    pub const fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// エラーがあれば `VALIDATION_FAILED` に変換
+    ///
+    /// `details` は `{ "fields": { "<field>": "<message>" } }` の形式
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+
+        let message = format!(
+            "入力値が不正です: {}",
+            self.fields.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+        Err(AppError::validation_failed(&message)
+            .with_details(serde_json::json!({ "fields": self.fields })))
+    }
+}
+
+/// 出力解像度の上限（幅・高さ、8K）
+pub const MAX_OUTPUT_DIMENSION: u32 = 7680;
+
+/// FPSの上限
+pub const MAX_FPS: u32 = 240;
+
+/// ビットレートの上限（kbps）
+pub const MAX_BITRATE_KBPS: u32 = 200_000;
+
+/// ネットワーク速度を検証
+///
+/// # Arguments
+/// * `errors` - エラーの追加先
+/// * `field` - フィールド名
+/// * `value` - ネットワーク速度（Mbps）
+pub fn check_network_speed(errors: &mut ValidationErrors, field: &str, value: f64) {
+    if !value.is_finite() || !(MIN_NETWORK_SPEED_MBPS..=MAX_NETWORK_SPEED_MBPS).contains(&value) {
+        errors.add(
+            field,
+            &format!("ネットワーク速度は{MIN_NETWORK_SPEED_MBPS}〜{MAX_NETWORK_SPEED_MBPS}Mbpsの範囲で指定してください"),
+        );
+    }
+}
+
+/// ハードウェア情報を検証
+///
+/// CPUコア数とメモリ容量は0より大きい必要がある
+pub fn check_hardware(errors: &mut ValidationErrors, hardware: &HardwareInfo) {
+    if hardware.cpu_cores == 0 {
+        errors.add("hardware.cpuCores", "CPUコア数は1以上を指定してください");
+    }
+    if !hardware.total_memory_gb.is_finite() || hardware.total_memory_gb <= 0.0 {
+        errors.add("hardware.totalMemoryGb", "メモリ容量は0より大きい値を指定してください");
+    }
+}
+
+/// 推奨設定の算出・適用コマンドの入力を検証
+///
+/// # Arguments
+/// * `network_speed_mbps` - ネットワーク速度（Mbps）
+/// * `hardware` - 指定されたハードウェア情報（自動検出の場合はNone）
+pub fn validate_recommendation_input(
+    network_speed_mbps: f64,
+    hardware: Option<&HardwareInfo>,
+) -> Result<(), AppError> {
+    let mut errors = ValidationErrors::new();
+    check_network_speed(&mut errors, "networkSpeedMbps", network_speed_mbps);
+    if let Some(hardware) = hardware {
+        check_hardware(&mut errors, hardware);
+    }
+    errors.into_result()
+}
+
+/// エンコーダーベンチマークの入力を検証
+///
+/// # Arguments
+/// * `encoder_id` - エンコーダーID
+/// * `width` / `height` - 出力解像度
+/// * `fps` - 目標FPS
+/// * `bitrate_kbps` - ビットレート（kbps）
+/// * `duration_secs` - 計測時間（秒）
+pub fn validate_encoder_benchmark_input(
+    encoder_id: &str,
+    width: u32,
+    height: u32,
+    fps: u32,
+    bitrate_kbps: u32,
+    duration_secs: u8,
+) -> Result<(), AppError> {
+    let mut errors = ValidationErrors::new();
+    if encoder_id.trim().is_empty() {
+        errors.add("encoderId", "エンコーダーを指定してください");
+    }
+    for (field, value) in [("width", width), ("height", height)] {
+        // エンコーダーは奇数の解像度を扱えない
+        if value == 0 || value > MAX_OUTPUT_DIMENSION || value % 2 != 0 {
+            errors.add(field, &format!("解像度は{MAX_OUTPUT_DIMENSION}以下の偶数で指定してください"));
+        }
+    }
+    if !(1..=MAX_FPS).contains(&fps) {
+        errors.add("fps", &format!("FPSは1〜{MAX_FPS}の範囲で指定してください"));
+    }
+    if !(1..=MAX_BITRATE_KBPS).contains(&bitrate_kbps) {
+        errors.add("bitrateKbps", &format!("ビットレートは1〜{MAX_BITRATE_KBPS}kbpsの範囲で指定してください"));
+    }
+    if !(MIN_ENCODER_BENCHMARK_DURATION_SECS..=MAX_ENCODER_BENCHMARK_DURATION_SECS).contains(&duration_secs) {
+        errors.add(
+            "durationSecs",
+            &format!(
+                "計測時間は{MIN_ENCODER_BENCHMARK_DURATION_SECS}〜{MAX_ENCODER_BENCHMARK_DURATION_SECS}秒の範囲で指定してください"
+            ),
+        );
+    }
+    errors.into_result()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::error::ERROR_CODE_VALIDATION_FAILED;
+    use crate::monitor::power::PowerStatus;
+
+    fn create_hardware(cpu_cores: usize, total_memory_gb: f64) -> HardwareInfo {
+        HardwareInfo {
+            cpu_name: "Test CPU".to_string(),
+            cpu_cores,
+            total_memory_gb,
+            gpu: None,
+            power: PowerStatus::default(),
+            displays: Vec::new(),
+            benchmark_result: None,
+        }
+    }
+
+    fn field_errors(result: Result<(), AppError>) -> serde_json::Value {
+        let error = result.unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_VALIDATION_FAILED);
+        error.details().unwrap()["fields"].clone()
+    }
+
+    #[test]
+    fn test_network_speed_boundaries() {
+        assert!(validate_recommendation_input(MIN_NETWORK_SPEED_MBPS, None).is_ok());
+        assert!(validate_recommendation_input(MAX_NETWORK_SPEED_MBPS, None).is_ok());
+        assert!(validate_recommendation_input(10.0, None).is_ok());
+
+        for invalid in [0.099, 0.0, -5.0, 10_000.1, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let fields = field_errors(validate_recommendation_input(invalid, None));
+            assert!(fields.get("networkSpeedMbps").is_some(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_hardware_boundaries() {
+        assert!(validate_recommendation_input(10.0, Some(&create_hardware(1, 0.5))).is_ok());
+
+        let fields = field_errors(validate_recommendation_input(10.0, Some(&create_hardware(0, 16.0))));
+        assert!(fields.get("hardware.cpuCores").is_some());
+        assert!(fields.get("hardware.totalMemoryGb").is_none());
+
+        for invalid in [0.0, -1.0, f64::NAN] {
+            let fields = field_errors(validate_recommendation_input(10.0, Some(&create_hardware(8, invalid))));
+            assert!(fields.get("hardware.totalMemoryGb").is_some(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_multiple_field_errors_are_collected() {
+        let fields = field_errors(validate_recommendation_input(-1.0, Some(&create_hardware(0, 0.0))));
+        let map = fields.as_object().unwrap();
+        assert_eq!(map.len(), 3);
+        assert!(map.contains_key("networkSpeedMbps"));
+        assert!(map.contains_key("hardware.cpuCores"));
+        assert!(map.contains_key("hardware.totalMemoryGb"));
+    }
+
+    #[test]
+    fn test_first_error_per_field_wins() {
+        let mut errors = ValidationErrors::new();
+        errors.add("networkSpeedMbps", "最初のエラー");
+        errors.add("networkSpeedMbps", "2番目のエラー");
+        assert_eq!(errors.fields().get("networkSpeedMbps").map(String::as_str), Some("最初のエラー"));
+    }
+
+    #[test]
+    fn test_empty_errors_is_ok() {
+        let errors = ValidationErrors::new();
+        assert!(errors.is_empty());
+        assert!(errors.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_encoder_benchmark_input() {
+        assert!(validate_encoder_benchmark_input("obs_x264", 1920, 1080, 60, 6000, 10).is_ok());
+
+        let fields = field_errors(validate_encoder_benchmark_input(" ", 1921, 0, 0, 0, 0));
+        let map = fields.as_object().unwrap();
+        for field in ["encoderId", "width", "height", "fps", "bitrateKbps", "durationSecs"] {
+            assert!(map.contains_key(field), "{field}");
+        }
+        assert!(validate_encoder_benchmark_input("obs_x264", 1920, 1080, 60, 6000, 61).is_err());
+    }
+}
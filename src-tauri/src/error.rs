@@ -15,15 +15,54 @@ pub const ERROR_CODE_EXPORT: &str = "EXPORT_ERROR";
 pub const ERROR_CODE_ANALYZER: &str = "ANALYZER_ERROR";
 #[allow(dead_code)]
 pub const ERROR_CODE_KEYRING: &str = "KEYRING_ERROR";
+/// OBSに未接続の状態で操作しようとした
+pub const ERROR_CODE_OBS_NOT_CONNECTED: &str = "OBS_NOT_CONNECTED";
+/// 配信・録画などの出力がアクティブなため操作できない
+pub const ERROR_CODE_OBS_OUTPUT_ACTIVE: &str = "OBS_OUTPUT_ACTIVE";
+/// 設定ファイル・設定ディレクトリの入出力エラー
+pub const ERROR_CODE_CONFIG_IO: &str = "CONFIG_IO";
+/// OSのキーリングが利用できない
+pub const ERROR_CODE_KEYRING_UNAVAILABLE: &str = "KEYRING_UNAVAILABLE";
+/// 入力値の検証に失敗した
+pub const ERROR_CODE_VALIDATION_FAILED: &str = "VALIDATION_FAILED";
+/// OBSプロセスが起動していない
+pub const ERROR_CODE_OBS_PROCESS_NOT_FOUND: &str = "OBS_PROCESS_NOT_FOUND";
+/// プロセス優先度の変更に失敗した（権限不足を含む）
+pub const ERROR_CODE_PROCESS_PRIORITY: &str = "PROCESS_PRIORITY";
+/// 指定されたアラート履歴が存在しない
+pub const ERROR_CODE_ALERT_NOT_FOUND: &str = "ALERT_NOT_FOUND";
+/// 指定されたセッションが存在しない
+pub const ERROR_CODE_SESSION_NOT_FOUND: &str = "SESSION_NOT_FOUND";
+/// OBSの配信先（サーバーURL・ストリームキー）が未設定または不正
+pub const ERROR_CODE_STREAM_DESTINATION_INVALID: &str = "STREAM_DESTINATION_INVALID";
+/// 別の設定操作（適用・バックアップ・復元）が実行中
+pub const ERROR_CODE_OPERATION_IN_PROGRESS: &str = "OPERATION_IN_PROGRESS";
+/// フロントエンドへのイベント発行に失敗した
+pub const ERROR_CODE_EVENT_EMIT: &str = "EVENT_EMIT_ERROR";
+/// 配信プラットフォームのAPI（タイトル・カテゴリーの更新）に未対応
+pub const ERROR_CODE_PLATFORM_NOT_SUPPORTED: &str = "PLATFORM_NOT_SUPPORTED";
+/// 配信プラットフォームのAPIトークンが未設定・無効・権限不足
+pub const ERROR_CODE_PLATFORM_AUTH: &str = "PLATFORM_AUTH";
+/// 配信プラットフォームのAPI呼び出しに失敗した
+pub const ERROR_CODE_PLATFORM_API: &str = "PLATFORM_API";
+/// エンコーダーベンチマークが中断された
+pub const ERROR_CODE_BENCHMARK_CANCELLED: &str = "BENCHMARK_CANCELLED";
+/// オブザーバーモードのHTTPサーバーを起動できない（待ち受けアドレスの使用中など）
+pub const ERROR_CODE_OBSERVER_SERVER: &str = "OBSERVER_SERVER";
 
 /// アプリケーション全体で使用するエラー型
 ///
 /// Tauri コマンドからフロントエンドに返されるエラーは
 /// この型にシリアライズされる
+///
+/// シリアライズ形式: `{ "code": string, "message": string, "details": any | null }`
+/// フロントエンドは `code` で分岐し、`message` は表示用に使用する
 #[derive(Debug, Serialize)]
 pub struct AppError {
     code: String,
     message: String,
+    /// 追加情報（検証エラーのフィールド名など）
+    details: Option<serde_json::Value>,
 }
 
 impl AppError {
@@ -36,9 +75,20 @@ impl AppError {
         Self {
             code: code.to_string(),
             message: message.to_string(),
+            details: None,
         }
     }
 
+    /// 追加情報を付与
+    ///
+    /// # Arguments
+    /// * `details` - フロントエンドに渡す追加情報
+    #[must_use]
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
     /// エラーコードを取得
     pub fn code(&self) -> &str {
         &self.code
@@ -49,6 +99,11 @@ impl AppError {
         &self.message
     }
 
+    /// 追加情報を取得
+    pub const fn details(&self) -> Option<&serde_json::Value> {
+        self.details.as_ref()
+    }
+
     /// OBS接続関連のエラーを作成
     pub fn obs_connection(msg: &str) -> Self {
         Self::new(ERROR_CODE_OBS_CONNECTION, msg)
@@ -71,6 +126,11 @@ impl AppError {
         Self::new(ERROR_CODE_WINDOW, msg)
     }
 
+    /// イベント発行関連のエラーを作成
+    pub fn event_emit(msg: &str) -> Self {
+        Self::new(ERROR_CODE_EVENT_EMIT, msg)
+    }
+
     /// 設定関連のエラーを作成
     pub fn config_error(msg: &str) -> Self {
         Self::new(ERROR_CODE_CONFIG, msg)
@@ -96,6 +156,86 @@ impl AppError {
     pub fn keyring_error(msg: &str) -> Self {
         Self::new(ERROR_CODE_KEYRING, msg)
     }
+
+    /// OBS未接続エラーを作成
+    pub fn obs_not_connected() -> Self {
+        Self::new(ERROR_CODE_OBS_NOT_CONNECTED, "OBSに接続されていません")
+    }
+
+    /// 出力（配信・録画）アクティブ中エラーを作成
+    pub fn obs_output_active(msg: &str) -> Self {
+        Self::new(ERROR_CODE_OBS_OUTPUT_ACTIVE, msg)
+    }
+
+    /// 設定ファイル入出力エラーを作成
+    pub fn config_io(msg: &str) -> Self {
+        Self::new(ERROR_CODE_CONFIG_IO, msg)
+    }
+
+    /// キーリング利用不可エラーを作成
+    pub fn keyring_unavailable(msg: &str) -> Self {
+        Self::new(ERROR_CODE_KEYRING_UNAVAILABLE, msg)
+    }
+
+    /// 入力値検証エラーを作成
+    pub fn validation_failed(msg: &str) -> Self {
+        Self::new(ERROR_CODE_VALIDATION_FAILED, msg)
+    }
+
+    /// ベンチマーク中断エラーを作成
+    pub fn benchmark_cancelled() -> Self {
+        Self::new(ERROR_CODE_BENCHMARK_CANCELLED, "ベンチマークが中断されました")
+    }
+
+    /// オブザーバーモードのサーバーエラーを作成
+    pub fn observer_server(msg: &str) -> Self {
+        Self::new(ERROR_CODE_OBSERVER_SERVER, msg)
+    }
+
+    /// OBSプロセス未起動エラーを作成
+    pub fn obs_process_not_found() -> Self {
+        Self::new(ERROR_CODE_OBS_PROCESS_NOT_FOUND, "OBSプロセスが見つかりません")
+    }
+
+    /// プロセス優先度変更エラーを作成
+    pub fn process_priority(msg: &str) -> Self {
+        Self::new(ERROR_CODE_PROCESS_PRIORITY, msg)
+    }
+
+    /// アラート履歴未検出エラーを作成
+    pub fn alert_not_found(id: &str) -> Self {
+        Self::new(ERROR_CODE_ALERT_NOT_FOUND, &format!("アラートが見つかりません: {id}"))
+    }
+
+    /// セッション未検出エラーを作成
+    pub fn session_not_found(session_id: &str) -> Self {
+        Self::new(ERROR_CODE_SESSION_NOT_FOUND, &format!("セッションが見つかりません: {session_id}"))
+    }
+
+    /// 配信先設定エラーを作成
+    pub fn stream_destination_invalid(msg: &str) -> Self {
+        Self::new(ERROR_CODE_STREAM_DESTINATION_INVALID, msg)
+    }
+
+    /// 操作実行中エラーを作成
+    ///
+    /// # Arguments
+    /// * `operation` - 実行中の操作の表示名
+    /// * `started_at` - 実行中の操作の開始日時（Unixタイムスタンプ）
+    pub fn operation_in_progress(operation: &str, started_at: i64) -> Self {
+        let started = chrono::DateTime::from_timestamp(started_at, 0).map_or_else(
+            || started_at.to_string(),
+            |time| time.with_timezone(&chrono::Local).format("%H:%M:%S").to_string(),
+        );
+        Self::new(
+            ERROR_CODE_OPERATION_IN_PROGRESS,
+            &format!("別の操作が実行中です: {operation}（{started}から）"),
+        )
+        .with_details(serde_json::json!({
+            "operation": operation,
+            "startedAt": started_at,
+        }))
+    }
 }
 
 impl std::fmt::Display for AppError {
@@ -305,4 +445,62 @@ mod tests {
         // std::error::Error traitを実装していることを確認
         let _: &dyn std::error::Error = &error;
     }
+
+    #[test]
+    fn test_error_serialized_shape() {
+        let error = AppError::config_error("設定エラー");
+        let json = serde_json::to_value(&error).expect("serialization failed");
+
+        // フロントエンド契約: code / message / details の3フィールド
+        let obj = json.as_object().expect("object");
+        assert_eq!(obj.len(), 3);
+        assert_eq!(json["code"], "CONFIG_ERROR");
+        assert_eq!(json["message"], "設定エラー");
+        assert!(json["details"].is_null());
+    }
+
+    #[test]
+    fn test_error_with_details_serialization() {
+        let error = AppError::validation_failed("入力値が不正です")
+            .with_details(serde_json::json!({ "field": "port" }));
+        let json = serde_json::to_value(&error).expect("serialization failed");
+
+        assert_eq!(json["code"], "VALIDATION_FAILED");
+        assert_eq!(json["details"]["field"], "port");
+        assert_eq!(error.details().and_then(|d| d.get("field")), Some(&serde_json::json!("port")));
+    }
+
+    #[test]
+    fn test_structured_error_variants_serialization() {
+        let cases = [
+            (AppError::obs_not_connected(), ERROR_CODE_OBS_NOT_CONNECTED),
+            (AppError::obs_output_active("配信中"), ERROR_CODE_OBS_OUTPUT_ACTIVE),
+            (AppError::config_io("読み込み失敗"), ERROR_CODE_CONFIG_IO),
+            (AppError::keyring_unavailable("利用不可"), ERROR_CODE_KEYRING_UNAVAILABLE),
+            (AppError::validation_failed("不正"), ERROR_CODE_VALIDATION_FAILED),
+        ];
+
+        for (error, expected_code) in cases {
+            let json = serde_json::to_value(&error).expect("serialization failed");
+            assert_eq!(json["code"], expected_code);
+            assert!(json["message"].is_string());
+            assert!(json.get("details").is_some(), "{expected_code}: detailsフィールドが必要");
+        }
+    }
+
+    #[test]
+    fn test_structured_error_code_constants() {
+        assert_eq!(ERROR_CODE_OBS_NOT_CONNECTED, "OBS_NOT_CONNECTED");
+        assert_eq!(ERROR_CODE_OBS_OUTPUT_ACTIVE, "OBS_OUTPUT_ACTIVE");
+        assert_eq!(ERROR_CODE_CONFIG_IO, "CONFIG_IO");
+        assert_eq!(ERROR_CODE_KEYRING_UNAVAILABLE, "KEYRING_UNAVAILABLE");
+        assert_eq!(ERROR_CODE_VALIDATION_FAILED, "VALIDATION_FAILED");
+    }
+
+    #[test]
+    fn test_display_unchanged_with_details() {
+        let error = AppError::validation_failed("不正な値")
+            .with_details(serde_json::json!({ "field": "host" }));
+        assert_eq!(format!("{error}"), "[VALIDATION_FAILED] 不正な値");
+    }
 }
@@ -0,0 +1,209 @@
+// Tauriイベントの一覧
+//
+// バックエンドからフロントエンドに発行するイベントを1か所で定義する。
+// イベント名はフロントエンドとの契約のため、発行は必ず `emit_event` を通し、
+// 文字列のイベント名を直接 `emit` に渡さない
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::error::AppError;
+use crate::obs::events::{
+    event_names, ConnectionChangedPayload, ErrorPayload, MediaEndedPayload, ObsStatsUpdate,
+    RecordingChangedPayload, SceneChangedPayload, SceneTransitionStartedPayload, StreamingChangedPayload,
+};
+use crate::obs::ObsStatus;
+use crate::services::alerts::{AlertNotification, ALERT_NOTIFICATION_EVENT};
+use crate::services::automation::{AutomationExecution, AUTOMATION_EXECUTED_EVENT};
+use crate::services::game_detector::{GameDetected, GAME_DETECTED_EVENT};
+use crate::services::post_stream_advisor::{PostStreamReportReady, POST_STREAM_REPORT_READY_EVENT};
+
+/// 画面遷移イベント名（通知クリック時に該当画面を開く）
+pub const APP_NAVIGATE_EVENT: &str = "app:navigate";
+
+/// 発行する全イベント名
+pub const EVENT_NAMES: &[&str] = &[
+    event_names::OBS_CONNECTION_CHANGED,
+    event_names::OBS_STREAMING_CHANGED,
+    event_names::OBS_RECORDING_CHANGED,
+    event_names::OBS_STATS_UPDATE,
+    event_names::OBS_STATUS_UPDATE,
+    event_names::OBS_SCENE_CHANGED,
+    event_names::OBS_SCENE_TRANSITION_STARTED,
+    event_names::OBS_MEDIA_ENDED,
+    event_names::OBS_ERROR,
+    ALERT_NOTIFICATION_EVENT,
+    AUTOMATION_EXECUTED_EVENT,
+    GAME_DETECTED_EVENT,
+    POST_STREAM_REPORT_READY_EVENT,
+    APP_NAVIGATE_EVENT,
+];
+
+/// 文字列が等しいか（const文脈で使用）
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// 全ての名前が異なるか（const文脈で使用）
+const fn all_unique(names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            if str_eq(names[i], names[j]) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+// イベント名の重複はコンパイルエラーにする
+const _: () = assert!(all_unique(EVENT_NAMES), "Tauriイベント名が重複しています");
+
+/// フロントエンドに発行するイベント
+#[derive(Debug, Clone)]
+pub enum TauriEvent {
+    /// OBSの接続状態変化
+    ConnectionChanged(ConnectionChangedPayload),
+    /// 配信状態変化
+    StreamingChanged(StreamingChangedPayload),
+    /// 録画状態変化
+    RecordingChanged(RecordingChangedPayload),
+    /// 配信出力の統計更新（配信中に1秒ごと）
+    ObsStatsUpdate(ObsStatsUpdate),
+    /// OBSのステータス更新（将来使用予定）
+    #[allow(dead_code)]
+    ObsStatusUpdate(ObsStatus),
+    /// シーン変更（将来使用予定）
+    #[allow(dead_code)]
+    SceneChanged(SceneChangedPayload),
+    /// シーン切り替えの開始
+    SceneTransitionStarted(SceneTransitionStartedPayload),
+    /// メディアソースの再生終了
+    MediaEnded(MediaEndedPayload),
+    /// OBSのエラー（将来使用予定）
+    #[allow(dead_code)]
+    ObsError(ErrorPayload),
+    /// アラートのデスクトップ通知
+    AlertNotification(AlertNotification),
+    /// 自動化ルールの実行
+    AutomationExecuted(AutomationExecution),
+    /// 既知のゲームの起動を検出
+    GameDetected(GameDetected),
+    /// 配信後レポートの生成完了
+    PostStreamReportReady(PostStreamReportReady),
+    /// 画面遷移（遷移先の画面名）
+    AppNavigate(String),
+}
+
+impl TauriEvent {
+    /// イベント名
+    pub const fn event_name(&self) -> &'static str {
+        match self {
+            Self::ConnectionChanged(_) => event_names::OBS_CONNECTION_CHANGED,
+            Self::StreamingChanged(_) => event_names::OBS_STREAMING_CHANGED,
+            Self::RecordingChanged(_) => event_names::OBS_RECORDING_CHANGED,
+            Self::ObsStatsUpdate(_) => event_names::OBS_STATS_UPDATE,
+            Self::ObsStatusUpdate(_) => event_names::OBS_STATUS_UPDATE,
+            Self::SceneChanged(_) => event_names::OBS_SCENE_CHANGED,
+            Self::SceneTransitionStarted(_) => event_names::OBS_SCENE_TRANSITION_STARTED,
+            Self::MediaEnded(_) => event_names::OBS_MEDIA_ENDED,
+            Self::ObsError(_) => event_names::OBS_ERROR,
+            Self::AlertNotification(_) => ALERT_NOTIFICATION_EVENT,
+            Self::AutomationExecuted(_) => AUTOMATION_EXECUTED_EVENT,
+            Self::GameDetected(_) => GAME_DETECTED_EVENT,
+            Self::PostStreamReportReady(_) => POST_STREAM_REPORT_READY_EVENT,
+            Self::AppNavigate(_) => APP_NAVIGATE_EVENT,
+        }
+    }
+
+    /// イベントのペイロード（JSON）
+    pub fn payload(&self) -> serde_json::Value {
+        fn to_value<T: Serialize>(payload: &T) -> serde_json::Value {
+            serde_json::to_value(payload).unwrap_or_default()
+        }
+
+        match self {
+            Self::ConnectionChanged(payload) => to_value(payload),
+            Self::StreamingChanged(payload) => to_value(payload),
+            Self::RecordingChanged(payload) => to_value(payload),
+            Self::ObsStatsUpdate(payload) => to_value(payload),
+            Self::ObsStatusUpdate(payload) => to_value(payload),
+            Self::SceneChanged(payload) => to_value(payload),
+            Self::SceneTransitionStarted(payload) => to_value(payload),
+            Self::MediaEnded(payload) => to_value(payload),
+            Self::ObsError(payload) => to_value(payload),
+            Self::AlertNotification(payload) => to_value(payload),
+            Self::AutomationExecuted(payload) => to_value(payload),
+            Self::GameDetected(payload) => to_value(payload),
+            Self::PostStreamReportReady(payload) => to_value(payload),
+            Self::AppNavigate(route) => to_value(route),
+        }
+    }
+}
+
+/// イベントをフロントエンドに発行
+///
+/// # Arguments
+/// * `app_handle` - `TauriのAppHandle`
+/// * `event` - 発行するイベント
+pub fn emit_event<R: Runtime>(app_handle: &AppHandle<R>, event: TauriEvent) -> Result<(), AppError> {
+    let name = event.event_name();
+    app_handle
+        .emit(name, event.payload())
+        .map_err(|e| AppError::event_emit(&format!("イベント {name} の発行に失敗: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obs::OutputStats;
+
+    #[test]
+    fn test_event_names_are_unique() {
+        assert!(all_unique(EVENT_NAMES));
+        assert!(!all_unique(&["obs:error", "game:detected", "obs:error"]));
+        assert!(str_eq("app:navigate", APP_NAVIGATE_EVENT));
+        assert!(!str_eq("app:navigate", "app:navigat"));
+    }
+
+    #[test]
+    fn test_event_name_and_payload() {
+        let events = [
+            TauriEvent::StreamingChanged(StreamingChangedPayload { is_streaming: true, started_at: Some(1) }),
+            TauriEvent::ObsStatsUpdate(ObsStatsUpdate {
+                stats: OutputStats {
+                    frames_sent: 600,
+                    frames_dropped: 3,
+                    current_bitrate_kbps: 6000.0,
+                    congestion: 0.0,
+                },
+            }),
+            TauriEvent::GameDetected(GameDetected { process_name: "cs2.exe".to_string() }),
+            TauriEvent::AppNavigate("problems".to_string()),
+        ];
+
+        for event in &events {
+            assert!(EVENT_NAMES.contains(&event.event_name()), "{}", event.event_name());
+        }
+        assert_eq!(events[0].event_name(), "obs:streaming-changed");
+        assert_eq!(events[0].payload()["isStreaming"], true);
+        assert_eq!(events[1].payload()["stats"]["framesDropped"], 3);
+        assert_eq!(events[2].payload()["processName"], "cs2.exe");
+        assert_eq!(events[3].payload(), "problems");
+    }
+}
@@ -20,6 +20,7 @@
 
 mod error;
 mod commands;
+mod events;
 mod obs;
 mod monitor;
 mod services;
@@ -70,48 +71,122 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // システム監視コマンド
             commands::get_system_metrics,
+            commands::poll_system_metrics,
             commands::get_process_metrics,
+            commands::get_streaming_overhead,
+            commands::get_obs_process_info,
+            commands::set_obs_priority,
+            commands::get_detected_games,
+            commands::get_detected_hardware,
+            commands::get_network_interfaces,
+            commands::set_preferred_network_interface,
+            commands::get_software_conflicts,
             commands::get_legacy_system_metrics,
             // OBS接続コマンド
             commands::connect_obs,
             commands::disconnect_obs,
             commands::get_obs_status,
+            commands::get_live_output_stats,
+            commands::ping_obs_connection,
             commands::get_saved_connection,
             // OBSシーン操作コマンド
             commands::get_scene_list,
             commands::set_current_scene,
+            commands::list_scene_items,
+            commands::get_scene_item_enabled,
+            commands::set_scene_item_enabled,
+            commands::get_browser_source_url,
+            commands::update_browser_source_url,
             // OBS配信・録画コマンド
             commands::start_streaming,
             commands::stop_streaming,
             commands::start_recording,
             commands::stop_recording,
+            commands::start_virtual_cam,
+            commands::stop_virtual_cam,
+            commands::get_virtual_cam_status,
             // OBSプロファイルパラメータ操作（テスト用）
             commands::get_obs_profile_parameter,
             commands::set_obs_profile_parameter,
             commands::get_current_obs_profile,
             commands::get_obs_profile_list,
+            commands::get_supported_protocols,
+            commands::get_audio_status,
+            commands::audit_audio_sources,
+            commands::set_audio_monitor_type,
+            commands::set_audio_monitoring_offset,
+            commands::set_all_audio_monitoring,
+            commands::restore_audio_monitoring_state,
             // 設定管理コマンド
             commands::get_config,
             commands::save_app_config,
+            commands::patch_app_config,
+            commands::get_config_schema,
+            commands::set_language,
+            commands::get_shortcut_conflicts,
+            commands::set_shortcut_binding,
+            commands::save_platform_token,
+            commands::delete_platform_token,
+            commands::test_platform_token,
+            commands::update_stream_info,
             // 最適化エンジンコマンド
             commands::get_obs_settings_command,
+            commands::invalidate_obs_settings_cache,
+            commands::get_recording_output_info,
+            commands::validate_obs_settings,
+            commands::set_obs_output_mode,
+            commands::set_hdr_mode,
             commands::calculate_recommendations,
             commands::calculate_custom_recommendations,
+            commands::generate_safe_defaults,
+            commands::calculate_recording_recommendations,
+            commands::calculate_tiered_recording_recommendations,
+            commands::get_upgrade_advice,
+            commands::get_encoder_options,
+            commands::get_recommendation_history,
+            commands::diff_recommendation_history,
+            // ベンチマークコマンド
+            commands::run_encoder_benchmark,
+            commands::get_last_benchmark_result,
+            commands::benchmark_encoder,
+            commands::cancel_encoder_benchmark,
             // アラート管理コマンド
             commands::get_active_alerts,
             commands::clear_all_alerts,
+            commands::manually_suppress_alerts,
+            commands::focus_alert_route,
+            commands::acknowledge_alert,
+            commands::get_alert_history,
+            commands::get_session_alert_counts,
+            // 自動化ルール管理コマンド
+            commands::get_automation_rules,
+            commands::create_automation_rule,
+            commands::update_automation_rule,
+            commands::delete_automation_rule,
+            commands::get_automation_log,
+            // メディアソースの自動アクション
+            commands::set_media_autoaction,
             // Phase 2a: プロファイル管理コマンド
             commands::get_profiles,
             commands::get_profile,
             commands::save_profile,
             commands::delete_profile,
             commands::apply_profile,
+            commands::apply_profile_dry_run,
             commands::save_current_settings_as_profile,
+            commands::get_favorite_profiles,
+            commands::update_profile_metadata,
             // Phase 2a: 最適化適用コマンド
             commands::apply_recommended_settings,
+            commands::apply_recommended_settings_with_result,
             commands::apply_custom_settings,
+            commands::apply_custom_settings_with_result,
+            commands::request_force_apply,
             commands::backup_current_settings,
             commands::restore_backup,
+            commands::get_obs_file_backup,
+            commands::restore_obs_files,
+            commands::get_current_operation,
             commands::get_backups,
             commands::apply_optimization,
             // Phase 2a: 配信中モード管理コマンド
@@ -121,13 +196,39 @@ pub fn run() {
             commands::analyze_problems,
             commands::analyze_settings,
             commands::get_problem_history,
+            commands::get_headroom,
+            commands::set_checklist_rule_dismissed,
+            commands::analyze_obs_log,
             // Phase 2b: エクスポートコマンド
             commands::export_session_json,
             commands::export_session_csv,
             commands::generate_diagnostic_report,
+            commands::export_diagnostic_report,
+            commands::get_monthly_streaming_report,
+            commands::generate_weekly_report,
+            commands::export_schemas,
             // Phase 2b: セッション履歴コマンド
             commands::get_sessions,
+            commands::update_session_metadata,
             commands::get_metrics_range,
+            commands::generate_post_stream_report,
+            commands::get_data_usage_summary,
+            commands::reset_monthly_quota,
+            commands::get_session_overhead,
+            commands::get_overhead_deltas,
+            // 起動時ダイジェスト
+            commands::get_startup_digest,
+            // デバッグ記録
+            commands::set_debug_recording,
+            commands::get_debug_recording_status,
+            commands::export_debug_bundle,
+            commands::get_app_performance_stats,
+            // 匿名ハードウェア統計
+            commands::preview_telemetry_payload,
+            // オブザーバーモード（読み取り専用HTTPサーバー）
+            commands::start_observer_server,
+            commands::stop_observer_server,
+            commands::get_observer_server_status,
         ])
         .setup(|app| {
             // システムトレイのセットアップ
@@ -135,9 +236,38 @@ pub fn run() {
                 tracing::warn!(target: "tray", "システムトレイの初期化に失敗: {e}");
                 // トレイの初期化失敗は致命的ではないため、アプリケーションは継続
             }
+
+            // ゲーム検出（5秒ごとにポーリングし、起動を検出したらイベント発行）
+            services::game_detector::start_game_detector(app.handle().clone());
+
+            // アラートエンジン（設定の閾値でルールを構築）
+            tauri::async_runtime::spawn(async {
+                let config = storage::config::load_config().unwrap_or_default();
+                services::initialize_alert_engine(&config.alerts).await;
+            });
+
+            // デバッグ記録（ユーザーが有効にしている場合のみ）
+            let config = storage::config::load_config().unwrap_or_default();
+            if let Err(e) = services::debug_recorder::configure_debug_recording(&config.debug_recording) {
+                tracing::warn!(target: "debug_recorder", error = %e, "デバッグ記録の開始に失敗");
+            }
+
+            // 音声監視（2秒ごとにマイクの状態を確認し、配信中の無音を検知）
+            services::audio_monitor::start_audio_monitor(app.handle().clone());
+
+            // 匿名ハードウェア統計（オプトイン時のみ、週1回まで送信）
+            services::telemetry::start_telemetry_sender();
+
+            // 初回起動時は比較の基準となる環境スナップショットを保存
+            if matches!(storage::environment::load_environment_snapshot(), Ok(None)) {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    services::startup_digest::save_current_environment(&handle).await;
+                });
+            }
             Ok(())
         })
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .unwrap_or_else(|e| {
             // エラー詳細をログ出力してから終了
             tracing::error!(target: "app", "Failed to run Tauri application");
@@ -145,5 +275,11 @@ pub fn run() {
             tracing::error!(target: "app", "Error type: {}", std::any::type_name_of_val(&e));
             tracing::error!(target: "app", "Terminating process with exit code 1");
             std::process::exit(1);
+        })
+        .run(|app_handle, event| {
+            // 終了時に環境スナップショットを保存し、次回起動時の比較に使用する
+            if let tauri::RunEvent::Exit = event {
+                tauri::async_runtime::block_on(services::startup_digest::save_current_environment(app_handle));
+            }
         });
 }
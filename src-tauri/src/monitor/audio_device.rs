@@ -0,0 +1,117 @@
+// 音声デバイス名の判定
+//
+// マイクのデバイス名からBluetooth・USB接続を推定する。
+// Bluetoothマイクはコーデック処理で100〜300ms程度の遅延が加わり映像とずれやすく、
+// USBオーディオは44100Hz固定の製品があり、OBS既定の48000Hzとの変換でノイズが出ることがある。
+
+/// Bluetoothデバイスとみなすデバイス名のキーワード（小文字）
+///
+/// Windowsのハンズフリー（HFP）エンドポイントは "Headset (WH-1000XM4 Hands-Free AG Audio)"、
+/// PulseAudio/PipeWireのソース名は "bluez_input.XX_XX_..." のような名前になる
+const BLUETOOTH_KEYWORDS: &[&str] = &[
+    "bluetooth",
+    "bluez",
+    "hands-free",
+    "handsfree",
+    "ag audio",
+    "a2dp",
+    "airpods",
+    "galaxy buds",
+];
+
+/// 単語として含まれる場合のみBluetoothとみなす略称（小文字）
+///
+/// "btn" や "subtitle" などへの誤判定を避けるため、部分一致では判定しない
+const BLUETOOTH_ABBREVIATIONS: &[&str] = &["bt", "hfp", "hsp"];
+
+/// USBオーディオとみなすデバイス名のキーワード（小文字）
+const USB_KEYWORDS: &[&str] = &["usb"];
+
+/// デバイス名から推定される音声の遅延・品質リスク
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDeviceLatencyWarning {
+    /// Bluetooth接続（音声の遅延・映像とのずれ）
+    Bluetooth,
+    /// USB接続（サンプルレートの不一致によるノイズ）
+    UsbSampleRate,
+}
+
+impl AudioDeviceLatencyWarning {
+    /// デバイス名から警告を判定（該当しない場合はNone）
+    ///
+    /// Bluetooth接続のUSBドングルもあるため、Bluetoothの判定を優先する
+    pub fn from_device_name(name: &str) -> Option<Self> {
+        if is_bluetooth_device(name) {
+            Some(Self::Bluetooth)
+        } else if is_usb_audio_device(name) {
+            Some(Self::UsbSampleRate)
+        } else {
+            None
+        }
+    }
+}
+
+/// デバイス名を英数字以外で区切った単語（小文字）の一覧
+fn words(name: &str) -> impl Iterator<Item = String> + '_ {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+/// デバイス名からBluetoothの音声デバイスかを推定
+pub fn is_bluetooth_device(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    BLUETOOTH_KEYWORDS.iter().any(|keyword| lower.contains(keyword))
+        || words(name).any(|word| BLUETOOTH_ABBREVIATIONS.contains(&word.as_str()))
+}
+
+/// デバイス名からUSBの音声デバイスかを推定
+pub fn is_usb_audio_device(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    USB_KEYWORDS.iter().any(|keyword| lower.contains(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bluetooth_device_detection() {
+        assert!(is_bluetooth_device("Headset (WH-1000XM4 Hands-Free AG Audio)"));
+        assert!(is_bluetooth_device("ヘッドセット (AirPods Pro)"));
+        assert!(is_bluetooth_device("bluez_input.AA_BB_CC_DD_EE_FF.0"));
+        assert!(is_bluetooth_device("Bluetooth Headset"));
+        assert!(is_bluetooth_device("Jabra BT Headset"));
+        assert!(is_bluetooth_device("Headphones (HFP)"));
+
+        // 略称は単語として含まれる場合のみ
+        assert!(!is_bluetooth_device("Subtle Mic"));
+        assert!(!is_bluetooth_device("マイク (Realtek(R) Audio)"));
+        assert!(!is_bluetooth_device("Shure MV7"));
+    }
+
+    #[test]
+    fn test_usb_audio_device_detection() {
+        assert!(is_usb_audio_device("マイク (Yeti Stereo Microphone USB)"));
+        assert!(is_usb_audio_device("alsa_input.usb-Blue_Microphones_Yeti-00.analog-stereo"));
+        assert!(!is_usb_audio_device("マイク (Realtek(R) Audio)"));
+    }
+
+    #[test]
+    fn test_latency_warning_from_device_name() {
+        assert_eq!(
+            AudioDeviceLatencyWarning::from_device_name("Headset (WH-1000XM4 Hands-Free AG Audio)"),
+            Some(AudioDeviceLatencyWarning::Bluetooth)
+        );
+        // BluetoothのUSBドングルはBluetoothとして扱う
+        assert_eq!(
+            AudioDeviceLatencyWarning::from_device_name("USB Bluetooth Audio Adapter"),
+            Some(AudioDeviceLatencyWarning::Bluetooth)
+        );
+        assert_eq!(
+            AudioDeviceLatencyWarning::from_device_name("Microphone (USB Audio Device)"),
+            Some(AudioDeviceLatencyWarning::UsbSampleRate)
+        );
+        assert_eq!(AudioDeviceLatencyWarning::from_device_name("Shure MV7 XLR"), None);
+    }
+}
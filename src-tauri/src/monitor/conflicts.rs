@@ -0,0 +1,208 @@
+// 競合ソフトウェアの検出
+//
+// 「OBSが重い」という報告の多くは、オーバーレイやフックを行う常駐ソフトが原因となる。
+// 実行中のプロセス一覧を既知の競合ソフトの表と照合し、原因と対処方法を返す。
+// 新しいソフトを追加する場合は `KNOWN_CONFLICTS` に1行追加する。
+
+use crate::error::AppError;
+use crate::monitor::process::running_process_names;
+use serde::{Deserialize, Serialize};
+
+/// 競合の重要度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictSeverity {
+    /// OBSのキャプチャー・エンコードに影響することが多い
+    Info,
+    /// 環境によっては影響する（設定の見直しを提案）
+    Tips,
+}
+
+/// 既知の競合ソフトウェア
+#[derive(Debug, Clone, Copy)]
+pub struct ConflictRule {
+    /// ルールID
+    pub id: &'static str,
+    /// ソフトウェア名
+    pub name: &'static str,
+    /// 実行ファイル名（大文字小文字・拡張子`.exe`を区別しない）
+    pub process_names: &'static [&'static str],
+    /// 重要度
+    pub severity: ConflictSeverity,
+    /// OBSへの影響
+    pub explanation: &'static str,
+    /// 対処方法
+    pub mitigation: &'static str,
+}
+
+/// 既知の競合ソフトウェアの一覧
+pub const KNOWN_CONFLICTS: &[ConflictRule] = &[
+    ConflictRule {
+        id: "nvidia_overlay",
+        name: "GeForce Experience / NVIDIAアプリのオーバーレイ",
+        process_names: &["NVIDIA Share.exe", "NVIDIA Overlay.exe", "nvsphelper64.exe"],
+        severity: ConflictSeverity::Info,
+        explanation: "ゲーム内オーバーレイとインスタントリプレイがNVENCとGPUメモリを使用するため、OBSのエンコードと競合します。",
+        mitigation: "ゲーム内オーバーレイとインスタントリプレイを無効にする",
+    },
+    ConflictRule {
+        id: "discord",
+        name: "Discord",
+        process_names: &["Discord.exe", "Discord"],
+        severity: ConflictSeverity::Tips,
+        explanation: "ハードウェアアクセラレーションが有効な場合、Discordの画面共有・動画再生がGPUのエンコーダーを使用し、OBSのエンコードが遅れることがあります。",
+        mitigation: "Discordの設定で「ハードウェアアクセラレーション」を無効にし、配信中は「配信モード」を有効にする",
+    },
+    ConflictRule {
+        id: "rtss",
+        name: "MSI Afterburner / RivaTuner Statistics Server",
+        process_names: &["RTSS.exe", "MSIAfterburner.exe"],
+        severity: ConflictSeverity::Info,
+        explanation: "RTSSのオーバーレイはOBSのプロセスにもフックするため、プレビューのカクつきやゲームキャプチャーの失敗の原因になります。",
+        mitigation: "RTSSでobs64.exeを追加し、「Application detection level」を「None」に設定する",
+    },
+    ConflictRule {
+        id: "antivirus",
+        name: "ウイルス対策ソフト（リアルタイムスキャン）",
+        process_names: &["avp.exe", "AvastSvc.exe", "AVGSvc.exe", "bdservicehost.exe", "mcshield.exe", "NortonSecurity.exe"],
+        severity: ConflictSeverity::Tips,
+        explanation: "リアルタイムスキャンが録画ファイルの書き込みやOBSのプラグイン読み込みを検査するため、ディスクI/Oとフレームの書き出しが遅れることがあります。",
+        mitigation: "OBSのインストールフォルダーと録画の保存先をスキャン対象から除外する",
+    },
+];
+
+/// 検出された競合ソフトウェア
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftwareConflict {
+    /// ルールID
+    pub id: String,
+    /// ソフトウェア名
+    pub name: String,
+    /// 検出されたプロセス名
+    pub process_name: String,
+    /// 重要度
+    pub severity: ConflictSeverity,
+    /// OBSへの影響
+    pub explanation: String,
+    /// 対処方法
+    pub mitigation: String,
+}
+
+/// 実行中のプロセス名の取得元
+pub trait ProcessListProvider {
+    /// 実行中のプロセス名の一覧を取得
+    fn process_names(&self) -> Result<Vec<String>, AppError>;
+}
+
+/// sysinfoによるプロセス一覧の取得
+#[derive(Debug, Default)]
+pub struct SystemProcessListProvider;
+
+impl ProcessListProvider for SystemProcessListProvider {
+    fn process_names(&self) -> Result<Vec<String>, AppError> {
+        running_process_names()
+    }
+}
+
+/// 比較用にプロセス名を正規化（小文字化し、拡張子`.exe`を除く）
+fn normalize(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    lower.strip_suffix(".exe").map(ToString::to_string).unwrap_or(lower)
+}
+
+/// プロセス名一覧と競合ソフトの表を照合
+///
+/// 1つのルールに複数のプロセスが一致しても1件にまとめ、表の順で返す
+pub fn match_conflicts<S: AsRef<str>>(process_names: &[S], rules: &[ConflictRule]) -> Vec<SoftwareConflict> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let process_name = process_names.iter().map(AsRef::as_ref).find(|name| {
+                rule.process_names.iter().any(|known| normalize(known) == normalize(name))
+            })?;
+            Some(SoftwareConflict {
+                id: rule.id.to_string(),
+                name: rule.name.to_string(),
+                process_name: process_name.to_string(),
+                severity: rule.severity,
+                explanation: rule.explanation.to_string(),
+                mitigation: rule.mitigation.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 実行中の競合ソフトウェアを検出
+///
+/// # Arguments
+/// * `provider` - プロセス一覧の取得元
+pub fn detect_conflicts(provider: &dyn ProcessListProvider) -> Result<Vec<SoftwareConflict>, AppError> {
+    Ok(match_conflicts(&provider.process_names()?, KNOWN_CONFLICTS))
+}
+
+/// 実行中の競合ソフトウェアを検出（システムのプロセス一覧を使用）
+pub fn get_software_conflicts() -> Result<Vec<SoftwareConflict>, AppError> {
+    detect_conflicts(&SystemProcessListProvider)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    struct FakeProcessList(Vec<&'static str>);
+
+    impl ProcessListProvider for FakeProcessList {
+        fn process_names(&self) -> Result<Vec<String>, AppError> {
+            Ok(self.0.iter().map(ToString::to_string).collect())
+        }
+    }
+
+    #[test]
+    fn test_detect_known_conflicts() {
+        let provider = FakeProcessList(vec!["explorer.exe", "obs64.exe", "rtss.exe", "Discord.exe", "Discord.exe", "avp.exe"]);
+        let conflicts = detect_conflicts(&provider).unwrap();
+
+        let ids: Vec<&str> = conflicts.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["discord", "rtss", "antivirus"]);
+        assert_eq!(conflicts[1].process_name, "rtss.exe");
+        assert_eq!(conflicts[1].severity, ConflictSeverity::Info);
+        assert!(!conflicts[2].mitigation.is_empty());
+    }
+
+    #[test]
+    fn test_no_conflicts() {
+        let provider = FakeProcessList(vec!["explorer.exe", "obs64.exe", "nvcontainer.exe"]);
+        assert!(detect_conflicts(&provider).unwrap().is_empty());
+        assert!(detect_conflicts(&FakeProcessList(Vec::new())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_match_conflicts_custom_table() {
+        const RULES: &[ConflictRule] = &[ConflictRule {
+            id: "test",
+            name: "テスト",
+            process_names: &["Overlay.exe"],
+            severity: ConflictSeverity::Tips,
+            explanation: "",
+            mitigation: "",
+        }];
+        // Linux/macOS向けに拡張子なしの名前にも一致する
+        let conflicts = match_conflicts(&["overlay"], RULES);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].process_name, "overlay");
+    }
+
+    #[test]
+    fn test_known_conflicts_are_well_formed() {
+        for rule in KNOWN_CONFLICTS {
+            assert!(!rule.process_names.is_empty(), "{}", rule.id);
+            assert!(!rule.explanation.is_empty() && !rule.mitigation.is_empty(), "{}", rule.id);
+        }
+        let mut ids: Vec<&str> = KNOWN_CONFLICTS.iter().map(|rule| rule.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), KNOWN_CONFLICTS.len());
+    }
+}
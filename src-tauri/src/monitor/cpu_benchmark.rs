@@ -0,0 +1,114 @@
+// CPUエンコード性能の簡易ベンチマーク
+//
+// コア数だけではCPUの世代差（8コアのRyzen 9 5900XとCore i7-4770など）を区別できないため、
+// 320x240・100フレームの合成映像をx264でエンコードし、1秒あたりのフレーム数を計測する。
+// エンコードはPATH上のffmpeg（libx264）をサブプロセスとして実行する。
+// ffmpegがない環境では計測せず、CPUティアはコア数から判定する。
+
+use crate::error::AppError;
+use once_cell::sync::OnceCell;
+
+/// エンコードに使用する実行ファイル
+const FFMPEG_PROGRAM: &str = "ffmpeg";
+
+/// エンコードするフレーム数
+const BENCHMARK_FRAMES: u32 = 100;
+
+/// 合成映像の解像度
+const BENCHMARK_RESOLUTION: &str = "320x240";
+
+/// 計測結果のキャッシュ（計測に失敗した場合も理由を記録し、再実行しない）
+static CACHED_RESULT: OnceCell<Result<u32, String>> = OnceCell::new();
+
+/// x264のエンコード速度を計測
+///
+/// ffmpegの `-benchmark` が出力する実時間（`rtime`）から、プロセス起動を除いたエンコード速度を求める
+///
+/// # Returns
+/// 1秒あたりのエンコードフレーム数
+pub fn benchmark_cpu_encode_speed() -> Result<u32, AppError> {
+    let source = format!("testsrc=size={BENCHMARK_RESOLUTION}:rate=30");
+    let frames = BENCHMARK_FRAMES.to_string();
+    let output = std::process::Command::new(FFMPEG_PROGRAM)
+        .args(["-hide_banner", "-nostats", "-benchmark", "-f", "lavfi", "-i", &source])
+        .args(["-frames:v", &frames, "-c:v", "libx264", "-preset", "medium", "-f", "null", "-"])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AppError::system_monitor("ffmpeg not found on PATH; install ffmpeg to measure CPU encode speed")
+            } else {
+                AppError::system_monitor(&format!("Failed to run ffmpeg: {e}"))
+            }
+        })?;
+    if !output.status.success() {
+        return Err(AppError::system_monitor("ffmpeg exited with an error"));
+    }
+
+    let rtime = parse_benchmark_rtime(&String::from_utf8_lossy(&output.stderr))
+        .ok_or_else(|| AppError::system_monitor("Failed to parse ffmpeg benchmark output"))?;
+    Ok(frames_per_second(BENCHMARK_FRAMES, rtime))
+}
+
+/// 計測結果を取得（初回のみ計測し、以降はキャッシュを返す）
+///
+/// ffmpegがない環境などで計測できない場合はNone
+pub fn cached_cpu_encode_speed() -> Option<u32> {
+    CACHED_RESULT
+        .get_or_init(|| {
+            benchmark_cpu_encode_speed().map_err(|e| {
+                tracing::info!(target: "cpu_benchmark", error = %e, "CPUベンチマークを実行できませんでした");
+                e.message().to_string()
+            })
+        })
+        .as_ref()
+        .ok()
+        .copied()
+}
+
+/// 計測できなかった理由（未計測・計測済みの場合はNone）
+pub fn cpu_benchmark_error() -> Option<String> {
+    CACHED_RESULT.get().and_then(|result| result.as_ref().err().cloned())
+}
+
+/// ffmpegの `-benchmark` の出力から実時間（秒）を取得
+///
+/// 出力例: `bench: utime=0.812s stime=0.031s rtime=0.245s`
+fn parse_benchmark_rtime(stderr: &str) -> Option<f64> {
+    stderr
+        .lines()
+        .filter(|line| line.starts_with("bench:"))
+        .flat_map(str::split_whitespace)
+        .find_map(|field| field.strip_prefix("rtime="))
+        .and_then(|value| value.trim_end_matches('s').parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+}
+
+/// フレーム数と所要時間から1秒あたりのフレーム数を算出
+fn frames_per_second(frames: u32, seconds: f64) -> u32 {
+    (f64::from(frames) / seconds).round().min(f64::from(u32::MAX)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_benchmark_rtime() {
+        let stderr = "Input #0, lavfi, from 'testsrc=size=320x240:rate=30':\n\
+                      frame=  100 fps=0.0 q=-1.0 Lsize=N/A time=00:00:03.33 bitrate=N/A speed=13.6x\n\
+                      bench: utime=0.812s stime=0.031s rtime=0.245s\n\
+                      bench: maxrss=52340KiB\n";
+        assert_eq!(parse_benchmark_rtime(stderr), Some(0.245));
+
+        assert_eq!(parse_benchmark_rtime("bench: maxrss=52340KiB"), None);
+        assert_eq!(parse_benchmark_rtime("bench: utime=0.0s stime=0.0s rtime=0.000s"), None);
+        assert_eq!(parse_benchmark_rtime(""), None);
+    }
+
+    #[test]
+    fn test_frames_per_second() {
+        assert_eq!(frames_per_second(100, 0.25), 400);
+        assert_eq!(frames_per_second(100, 2.0), 50);
+        assert_eq!(frames_per_second(100, 0.3), 333);
+    }
+}
@@ -0,0 +1,45 @@
+// ディスクの空き容量
+//
+// 録画の保存先ボリュームの空き容量を確認するために使用する。
+
+use std::path::Path;
+use sysinfo::Disks;
+
+/// 指定したパスを含むボリュームの空き容量（バイト）を取得
+///
+/// マウントポイントが最も長く一致するボリュームを選ぶ。見つからない場合はNone
+pub fn get_available_space(path: &Path) -> Option<u64> {
+    let disks = Disks::new_with_refreshed_list();
+    let mounts: Vec<(&Path, u64)> = disks
+        .iter()
+        .map(|disk| (disk.mount_point(), disk.available_space()))
+        .collect();
+    find_volume_space(&mounts, path)
+}
+
+/// マウントポイントと空き容量の一覧から、パスを含むボリュームの空き容量を選ぶ
+fn find_volume_space(mounts: &[(&Path, u64)], path: &Path) -> Option<u64> {
+    mounts
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .map(|(_, available)| *available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_volume_space_prefers_longest_mount_point() {
+        let mounts = [
+            (Path::new("/"), 10),
+            (Path::new("/home"), 20),
+            (Path::new("/home/user/videos"), 30),
+        ];
+        assert_eq!(find_volume_space(&mounts, Path::new("/home/user/videos/obs")), Some(30));
+        assert_eq!(find_volume_space(&mounts, Path::new("/home/user")), Some(20));
+        assert_eq!(find_volume_space(&mounts, Path::new("/var/tmp")), Some(10));
+        assert_eq!(find_volume_space(&mounts[1..], Path::new("/var/tmp")), None);
+    }
+}
@@ -0,0 +1,379 @@
+// ディスプレイ情報の検出
+//
+// 接続されているモニターの解像度・リフレッシュレートを検出する。
+// OBSの基本（キャンバス）解像度はメインモニターの解像度に合わせるのが基本のため、
+// キャンバス解像度の推奨に使用する。
+//
+// 検出には外部コマンドを使うため、結果を一定時間キャッシュし、ブロッキング処理用のスレッドで実行する。
+
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// ディスプレイ情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    /// 解像度（幅）
+    pub width: u32,
+    /// 解像度（高さ）
+    pub height: u32,
+    /// リフレッシュレート（Hz、取得できない場合は0）
+    pub refresh_rate_hz: u32,
+    /// メインモニターか
+    pub is_primary: bool,
+}
+
+impl DisplayInfo {
+    /// 4K（2160p）以上か
+    pub const fn is_4k_or_higher(&self) -> bool {
+        self.height >= 2160
+    }
+
+    /// 総画素数
+    pub const fn pixel_count(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    /// 解像度が一致するか
+    pub const fn has_resolution(&self, width: u32, height: u32) -> bool {
+        self.width == width && self.height == height
+    }
+
+    /// 表示用ラベル（例: "2560x1440・165Hz（メイン）"）
+    pub fn display_label(&self) -> String {
+        let mut label = format!("{}x{}", self.width, self.height);
+        if self.refresh_rate_hz > 0 {
+            label.push_str(&format!("・{}Hz", self.refresh_rate_hz));
+        }
+        if self.is_primary {
+            label.push_str("（メイン）");
+        }
+        label
+    }
+}
+
+/// ディスプレイ一覧からメインモニターを取得
+///
+/// メインモニターが判定できない場合は先頭のディスプレイを返す
+pub fn primary_display(displays: &[DisplayInfo]) -> Option<&DisplayInfo> {
+    displays.iter().find(|d| d.is_primary).or_else(|| displays.first())
+}
+
+/// "2560x1440" 形式の解像度を解析
+fn parse_resolution(text: &str) -> Option<(u32, u32)> {
+    let (width, height) = text.split_once('x')?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+/// リフレッシュレート（"164.96" 等）を整数Hzに丸める
+fn parse_refresh_rate(text: &str) -> Option<u32> {
+    let rate: f64 = text.trim().parse().ok()?;
+    (rate.is_finite() && rate > 0.0).then(|| rate.round() as u32)
+}
+
+/// `xrandr --query` の出力を解析（Linux）
+///
+/// 接続済みで有効な出力のみを対象とし、リフレッシュレートは `*` の付いた現在のモードから取得する
+pub fn parse_xrandr_output(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    // 現在解析中の出力（無効な出力の場合はモード行を無視する）
+    let mut in_active_output = false;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            in_active_output = false;
+            let mut tokens = line.split_whitespace();
+            if tokens.nth(1) != Some("connected") {
+                continue;
+            }
+
+            let tokens: Vec<&str> = tokens.collect();
+            // "2560x1440+0+0" 形式のジオメトリ（無効な出力にはない）
+            let geometry = tokens
+                .iter()
+                .find_map(|token| token.split_once('+').and_then(|(size, _)| parse_resolution(size)));
+            if let Some((width, height)) = geometry {
+                displays.push(DisplayInfo {
+                    width,
+                    height,
+                    refresh_rate_hz: 0,
+                    is_primary: tokens.contains(&"primary"),
+                });
+                in_active_output = true;
+            }
+        } else if in_active_output {
+            let current = line
+                .split_whitespace()
+                .skip(1)
+                .find(|token| token.contains('*'))
+                .and_then(|token| parse_refresh_rate(token.trim_end_matches(['*', '+'])));
+            if let (Some(rate), Some(display)) = (current, displays.last_mut()) {
+                display.refresh_rate_hz = rate;
+            }
+        }
+    }
+
+    displays
+}
+
+/// PowerShellの `幅,高さ,リフレッシュレート,メインか` 形式の出力を解析（Windows）
+pub fn parse_windows_displays(output: &str) -> Vec<DisplayInfo> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [width, height, refresh_rate, primary] = fields.as_slice() else {
+                return None;
+            };
+            Some(DisplayInfo {
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+                refresh_rate_hz: parse_refresh_rate(refresh_rate).unwrap_or(0),
+                is_primary: primary.eq_ignore_ascii_case("true"),
+            })
+        })
+        .collect()
+}
+
+/// `system_profiler SPDisplaysDataType` の出力を解析（macOS）
+///
+/// "Resolution:" 行ごとに1つのディスプレイとして扱う。
+/// リフレッシュレートは "@ 120.00Hz" の表記から取得する
+pub fn parse_system_profiler_displays(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+
+    for line in output.lines().map(str::trim) {
+        if let Some(resolution) = line.strip_prefix("Resolution:") {
+            let tokens: Vec<&str> = resolution.split_whitespace().collect();
+            // "2560 x 1440 @ 144.00Hz" 形式
+            let (Some(width), Some(height)) = (
+                tokens.first().and_then(|t| t.parse().ok()),
+                tokens.get(2).and_then(|t| t.parse().ok()),
+            ) else {
+                continue;
+            };
+            displays.push(DisplayInfo {
+                width,
+                height,
+                refresh_rate_hz: 0,
+                is_primary: false,
+            });
+        }
+
+        let Some(display) = displays.last_mut() else {
+            continue;
+        };
+        if let Some((_, rate)) = line.split_once('@') {
+            if let Some(rate) = parse_refresh_rate(rate.trim().trim_end_matches("Hz")) {
+                display.refresh_rate_hz = rate;
+            }
+        }
+        if line == "Main Display: Yes" {
+            display.is_primary = true;
+        }
+    }
+
+    displays
+}
+
+/// 接続されているディスプレイの情報を検出
+#[cfg(target_os = "linux")]
+pub fn detect_display_info() -> Result<Vec<DisplayInfo>, AppError> {
+    let output = std::process::Command::new("xrandr")
+        .arg("--query")
+        .output()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to run xrandr: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::system_monitor("xrandr exited with an error"));
+    }
+    Ok(parse_xrandr_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 接続されているディスプレイの情報を検出
+///
+/// DPIスケーリングの影響を受けない物理解像度を取得するため、PowerShellのプロセスをモニターごとのDPI対応にしてから
+/// `Screen.Bounds` を参照する（対応していないOSではシステムのDPI対応にする）。
+/// リフレッシュレートは `Win32_VideoController` の現在値を全ディスプレイに使用する
+#[cfg(target_os = "windows")]
+pub fn detect_display_info() -> Result<Vec<DisplayInfo>, AppError> {
+    let script = "Add-Type -TypeDefinition 'using System; using System.Runtime.InteropServices; \
+                  public static class Dpi { \
+                  [DllImport(\"user32.dll\")] public static extern bool SetProcessDpiAwarenessContext(IntPtr value); \
+                  [DllImport(\"user32.dll\")] public static extern bool SetProcessDPIAware(); }'; \
+                  if (-not [Dpi]::SetProcessDpiAwarenessContext([IntPtr]-4)) { [void][Dpi]::SetProcessDPIAware() }; \
+                  Add-Type -AssemblyName System.Windows.Forms; \
+                  $hz = (Get-CimInstance Win32_VideoController | Select-Object -First 1).CurrentRefreshRate; \
+                  [System.Windows.Forms.Screen]::AllScreens | ForEach-Object { \
+                  \"$($_.Bounds.Width),$($_.Bounds.Height),$hz,$($_.Primary)\" }";
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to run powershell: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::system_monitor("Display query exited with an error"));
+    }
+    Ok(parse_windows_displays(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 接続されているディスプレイの情報を検出
+#[cfg(target_os = "macos")]
+pub fn detect_display_info() -> Result<Vec<DisplayInfo>, AppError> {
+    let output = std::process::Command::new("system_profiler")
+        .arg("SPDisplaysDataType")
+        .output()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to run system_profiler: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::system_monitor("system_profiler exited with an error"));
+    }
+    Ok(parse_system_profiler_displays(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 接続されているディスプレイの情報を検出（未対応OS）
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+pub fn detect_display_info() -> Result<Vec<DisplayInfo>, AppError> {
+    Ok(Vec::new())
+}
+
+/// ディスプレイ情報の再検出間隔
+const DISPLAY_INFO_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// 検出済みのディスプレイ情報と検出時刻
+static DISPLAY_INFO_CACHE: Lazy<Mutex<Option<(Instant, Vec<DisplayInfo>)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 直近に検出したディスプレイ情報（未検出の場合は空）
+///
+/// 外部コマンドを実行しないため、同期処理から参照する場合に使う
+pub fn cached_display_info() -> Vec<DisplayInfo> {
+    DISPLAY_INFO_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .map(|(_, displays)| displays.clone())
+        .unwrap_or_default()
+}
+
+/// 接続されているディスプレイの情報を取得（一定時間キャッシュする）
+///
+/// 外部コマンドの実行はブロッキング処理用のスレッドで行い、非同期ランタイムを止めない。
+/// 検出できない場合は空（失敗も同じ間隔でキャッシュし、再実行を繰り返さない）
+pub async fn get_display_info() -> Vec<DisplayInfo> {
+    if let Some((detected_at, displays)) = DISPLAY_INFO_CACHE.lock().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        if detected_at.elapsed() < DISPLAY_INFO_CACHE_TTL {
+            return displays.clone();
+        }
+    }
+
+    let displays = tokio::task::spawn_blocking(detect_display_info)
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_default();
+    *DISPLAY_INFO_CACHE.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), displays.clone()));
+    displays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xrandr_dual_monitor() {
+        let output = "\
+Screen 0: minimum 320 x 200, current 4480 x 1440, maximum 16384 x 16384
+DP-1 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm
+   2560x1440    164.96*+ 143.97   59.95
+   1920x1080     60.00    59.94
+HDMI-1 connected 1920x1080+2560+0 (normal left inverted right x axis y axis) 527mm x 296mm
+   1920x1080     60.00*+  50.00    59.94
+HDMI-2 disconnected (normal left inverted right x axis y axis)
+";
+        let displays = parse_xrandr_output(output);
+
+        assert_eq!(
+            displays,
+            vec![
+                DisplayInfo { width: 2560, height: 1440, refresh_rate_hz: 165, is_primary: true },
+                DisplayInfo { width: 1920, height: 1080, refresh_rate_hz: 60, is_primary: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_xrandr_skips_inactive_output() {
+        let output = "\
+eDP-1 connected (normal left inverted right x axis y axis)
+   3840x2160     60.00 +
+DP-2 connected primary 3840x2160+0+0 (normal left inverted right x axis y axis) 600mm x 340mm
+   3840x2160     59.997*+
+";
+        let displays = parse_xrandr_output(output);
+
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].refresh_rate_hz, 60);
+        assert!(displays[0].is_4k_or_higher());
+    }
+
+    #[test]
+    fn test_parse_windows_displays() {
+        let output = "2560,1440,165,True\r\n1920,1080,165,False\r\n\r\n";
+        let displays = parse_windows_displays(output);
+
+        assert_eq!(displays.len(), 2);
+        assert_eq!(
+            displays[0],
+            DisplayInfo { width: 2560, height: 1440, refresh_rate_hz: 165, is_primary: true }
+        );
+        assert!(!displays[1].is_primary);
+    }
+
+    #[test]
+    fn test_parse_windows_displays_ignores_malformed_lines() {
+        let displays = parse_windows_displays("invalid\n1920,1080,,True\n");
+
+        assert_eq!(displays.len(), 1);
+        assert_eq!(displays[0].refresh_rate_hz, 0);
+    }
+
+    #[test]
+    fn test_parse_system_profiler_displays() {
+        let output = "\
+Graphics/Displays:
+
+    Apple M2 Pro:
+
+      Displays:
+        Color LCD:
+          Display Type: Built-in Liquid Retina XDR Display
+          Resolution: 3456 x 2234 Retina
+          UI Looks like: 1728 x 1117 @ 120.00Hz
+          Main Display: Yes
+        LG UltraFine:
+          Resolution: 3840 x 2160 (2160p/4K UHD 1 - Ultra High Definition)
+          UI Looks like: 1920 x 1080 @ 60.00Hz
+";
+        let displays = parse_system_profiler_displays(output);
+
+        assert_eq!(
+            displays,
+            vec![
+                DisplayInfo { width: 3456, height: 2234, refresh_rate_hz: 120, is_primary: true },
+                DisplayInfo { width: 3840, height: 2160, refresh_rate_hz: 60, is_primary: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primary_display_falls_back_to_first() {
+        let secondary = DisplayInfo { width: 1920, height: 1080, refresh_rate_hz: 60, is_primary: false };
+        let primary = DisplayInfo { width: 2560, height: 1440, refresh_rate_hz: 144, is_primary: true };
+
+        assert_eq!(primary_display(&[secondary, primary]), Some(&primary));
+        assert_eq!(primary_display(&[secondary]), Some(&secondary));
+        assert_eq!(primary_display(&[]), None);
+    }
+}
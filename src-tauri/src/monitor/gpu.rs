@@ -1,9 +1,11 @@
 // GPU監視モジュール
 //
 // NVIDIA GPUの監視にnvml-wrapperクレートを使用
+// AMD・Intel GPUは `gpu_provider` のベンダー別の取得元を使用する
 
 use serde::Serialize;
 use crate::error::AppError;
+use crate::monitor::gpu_provider::{select_platform_provider, GpuMetricsProvider, GpuVendor};
 use nvml_wrapper::Nvml;
 use nvml_wrapper::error::NvmlError;
 use std::sync::Mutex;
@@ -56,36 +58,54 @@ fn is_nvml_available() -> bool {
 }
 
 
+/// 起動時に選択したGPUメトリクスの取得元（利用可能なものがない場合はNone）
+static GPU_PROVIDER: Lazy<Option<Box<dyn GpuMetricsProvider>>> = Lazy::new(select_platform_provider);
+
+/// NVIDIA GPUの取得元（NVML）
+pub struct NvmlProvider;
+
+impl GpuMetricsProvider for NvmlProvider {
+    fn vendor(&self) -> GpuVendor {
+        GpuVendor::Nvidia
+    }
+
+    fn is_available(&self) -> bool {
+        is_nvml_available()
+    }
+
+    fn metrics(&self) -> Result<Option<GpuMetrics>, AppError> {
+        // NVML初期化
+        let Ok(nvml) = Nvml::init() else {
+            return Ok(None); // 初期化失敗時はNoneを返す
+        };
+
+        // デバイス数を確認
+        let Ok(device_count) = nvml.device_count() else {
+            return Ok(None);
+        };
+
+        if device_count == 0 {
+            return Ok(None);
+        }
+
+        // 最初のGPUを取得
+        get_gpu_metrics_by_index(&nvml, 0)
+    }
+}
+
 /// GPU情報を取得（プライマリGPU）
 ///
-/// システムの最初のGPU（インデックス0）の情報を取得します。
+/// 搭載GPUのベンダーに合わせて選択した取得元（NVIDIA: NVML、AMD/Intel: sysfs・パフォーマンスカウンター）から
+/// 最初のGPUの情報を取得します。
 ///
 /// # Returns
 /// - `Ok(Some(GpuMetrics))` - GPU情報が取得できた場合
-/// - `Ok(None)` - GPUが検出されない、またはNVIDIAドライバがない場合
+/// - `Ok(None)` - GPUが検出されない、または対応する取得元がない場合
 /// - `Err(AppError)` - エラーが発生した場合
 pub fn get_gpu_metrics() -> Result<Option<GpuMetrics>, AppError> {
-    // NVMLが利用可能かチェック
-    if !is_nvml_available() {
-        return Ok(None);
-    }
-
-    // NVML初期化
-    let Ok(nvml) = Nvml::init() else {
-        return Ok(None); // 初期化失敗時はNoneを返す
-    };
-
-    // デバイス数を確認
-    let Ok(device_count) = nvml.device_count() else {
-        return Ok(None);
-    };
-
-    if device_count == 0 {
-        return Ok(None);
-    }
-
-    // 最初のGPUを取得
-    get_gpu_metrics_by_index(&nvml, 0)
+    GPU_PROVIDER
+        .as_ref()
+        .map_or(Ok(None), |provider| provider.metrics())
 }
 
 /// 指定インデックスのGPU情報を取得
@@ -144,6 +164,8 @@ fn get_gpu_metrics_by_index(nvml: &Nvml, index: u32) -> Result<Option<GpuMetrics
 pub struct GpuInfo {
     /// GPU名称
     pub name: String,
+    /// ドライバーバージョン（例: "551.86"、取得できない場合はNone）
+    pub driver_version: Option<String>,
 }
 
 /// GPU情報を非同期で取得（推奨設定計算用）
@@ -156,12 +178,38 @@ pub async fn get_gpu_info() -> Option<GpuInfo> {
     let metrics = get_gpu_metrics().ok()??;
     Some(GpuInfo {
         name: metrics.name,
+        driver_version: get_driver_version(),
     })
 }
 
+/// Linuxでドライバーバージョンを公開しているカーネルモジュール
+const SYSFS_DRIVER_VERSION_PATHS: &[&str] = &["/sys/module/nvidia/version"];
+
+/// GPUドライバーのバージョンを取得
+///
+/// NVMLが利用可能な場合はNVMLから取得し、取得できない場合は
+/// sysfs（Linux）のモジュール情報にフォールバックする
+///
+/// # Returns
+/// - `Some(String)` - ドライバーバージョン（例: "551.86"）
+/// - `None` - 取得できない場合
+pub fn get_driver_version() -> Option<String> {
+    if is_nvml_available() {
+        if let Some(version) = Nvml::init().ok().and_then(|nvml| nvml.sys_driver_version().ok()) {
+            return Some(version);
+        }
+    }
+
+    SYSFS_DRIVER_VERSION_PATHS
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|version| version.trim().to_string())
+        .find(|version| !version.is_empty())
+}
+
 /// 全GPUのリストを取得（マルチGPU対応）（将来使用予定）
 ///
-/// システム内の全NVIDIA GPUの情報を取得します。
+/// システム内の全NVIDIA GPUの情報を取得します（AMD・Intel GPUは含まない）。
 ///
 /// # Returns
 /// - `Ok(Vec<GpuMetrics>)` - 検出されたGPUのリスト（空の場合あり）
@@ -225,6 +273,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_driver_version_no_panic() {
+        // ドライバー未インストール環境ではNone、取得できた場合は空でない
+        if let Some(version) = get_driver_version() {
+            assert!(!version.is_empty());
+        }
+    }
+
     #[test]
     fn test_get_all_gpu_metrics_returns_vec() {
         // 全GPU取得でパニックしないことを確認
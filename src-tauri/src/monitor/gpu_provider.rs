@@ -0,0 +1,693 @@
+// GPUメトリクスの取得元（ベンダー別）
+//
+// NVIDIAはNVML、AMDはLinuxのsysfs（amdgpu）、AMD・IntelはWindowsのパフォーマンスカウンター
+// （WDDMのGPU Engine / GPU Adapter Memory）から使用率を取得する。
+// パフォーマンスカウンターは対象GPUのアダプターLUIDのインスタンスのみを集計する。
+// 起動時に搭載GPUのベンダーを検出し、利用可能な取得元を1つ選択する。
+// どの取得元も使えない場合（Linux/macOSのIntel GPU等）はGPUメトリクスなしとして扱う。
+
+use crate::error::AppError;
+use crate::monitor::gpu::GpuMetrics;
+use serde::Serialize;
+
+/// GPUベンダー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GpuVendor {
+    /// NVIDIA
+    Nvidia,
+    /// AMD
+    Amd,
+    /// Intel
+    Intel,
+    /// 判定できない
+    Unknown,
+}
+
+impl GpuVendor {
+    /// PCIベンダーIDから判定
+    pub const fn from_pci_vendor_id(vendor_id: u16) -> Self {
+        match vendor_id {
+            0x10de => Self::Nvidia,
+            0x1002 | 0x1022 => Self::Amd,
+            0x8086 => Self::Intel,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// 取得元を選択する優先順位（小さいほど優先）
+    ///
+    /// 内蔵GPUと外付けGPUが混在する場合に外付けGPUを優先する
+    const fn priority(self) -> u8 {
+        match self {
+            Self::Nvidia => 0,
+            Self::Amd => 1,
+            Self::Intel => 2,
+            Self::Unknown => 3,
+        }
+    }
+}
+
+/// GPUメトリクスの取得元
+pub trait GpuMetricsProvider: Send + Sync {
+    /// 対応するGPUベンダー
+    fn vendor(&self) -> GpuVendor;
+
+    /// この環境で利用可能か
+    fn is_available(&self) -> bool;
+
+    /// プライマリGPUのメトリクスを取得
+    ///
+    /// # Returns
+    /// - `Ok(Some(GpuMetrics))` - 取得できた場合
+    /// - `Ok(None)` - GPUが見つからない場合
+    fn metrics(&self) -> Result<Option<GpuMetrics>, AppError>;
+}
+
+/// 搭載GPUのベンダーに合う取得元を選択
+///
+/// ベンダーを優先順位（NVIDIA → AMD → Intel）に並べ、対応する取得元のうち最初に利用可能なものを返す
+///
+/// # Arguments
+/// * `vendors` - 検出されたGPUのベンダー
+/// * `providers` - 取得元の候補
+pub fn select_provider(
+    vendors: &[GpuVendor],
+    mut providers: Vec<Box<dyn GpuMetricsProvider>>,
+) -> Option<Box<dyn GpuMetricsProvider>> {
+    let mut vendors: Vec<GpuVendor> = vendors.iter().copied().filter(|v| *v != GpuVendor::Unknown).collect();
+    vendors.sort_by_key(|vendor| vendor.priority());
+    vendors.dedup();
+
+    for vendor in vendors {
+        if let Some(index) = providers
+            .iter()
+            .position(|provider| provider.vendor() == vendor && provider.is_available())
+        {
+            return Some(providers.swap_remove(index));
+        }
+    }
+    None
+}
+
+/// `lspci -mm` の1行からGPUの製品名を取り出す
+///
+/// デバイス名の角括弧内（例: "Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]"）を優先し、
+/// ベンダー名を前に付ける（例: "AMD Radeon RX 6800/6800 XT / 6900 XT"）
+pub fn parse_lspci_device_name(line: &str, vendor: GpuVendor) -> Option<String> {
+    // 書式: スロット "クラス" "ベンダー" "デバイス" ...
+    let device = line.split('"').nth(5)?.trim();
+    if device.is_empty() {
+        return None;
+    }
+
+    let model = device
+        .find('[')
+        .and_then(|start| device[start + 1..].find(']').map(|end| &device[start + 1..start + 1 + end]))
+        .unwrap_or(device);
+    let prefix = match vendor {
+        GpuVendor::Amd => "AMD ",
+        GpuVendor::Intel => "Intel ",
+        GpuVendor::Nvidia => "NVIDIA ",
+        GpuVendor::Unknown => "",
+    };
+    Some(format!("{prefix}{}", model.trim()))
+}
+
+/// Windowsのパフォーマンスカウンターの集計結果（"3D使用率,エンコード使用率,専用メモリ使用量"）を解析
+///
+/// 複数のエンジン・プロセスの合計のため、使用率は100%で頭打ちにする
+pub fn parse_wddm_counters(output: &str) -> Option<(f32, Option<f32>, u64)> {
+    let line = output.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let mut fields = line.split(',').map(str::trim);
+
+    let usage = fields.next()?.parse::<f32>().ok()?.clamp(0.0, 100.0);
+    let encoder = fields
+        .next()
+        .and_then(|value| value.parse::<f32>().ok())
+        .map(|value| value.clamp(0.0, 100.0));
+    let memory_used = fields
+        .next()
+        .and_then(|value| value.parse::<f64>().ok())
+        .map_or(0, |value| value.max(0.0) as u64);
+    Some((usage, encoder, memory_used))
+}
+
+/// アダプターLUIDからパフォーマンスカウンターのインスタンス名の接頭辞（例: "luid_0x00000000_0x0000d1a5"）を作る
+pub fn wddm_luid_instance(adapter_luid: u64) -> String {
+    format!("luid_0x{:08x}_0x{:08x}", adapter_luid >> 32, adapter_luid & 0xFFFF_FFFF)
+}
+
+/// DirectXのアダプター一覧（"VendorId|AdapterLuid|Description"、いずれも10進数）を解析（Windows）
+///
+/// `HKLM\SOFTWARE\Microsoft\DirectX` の各アダプターのキーから取得した値を対象とする。
+/// ソフトウェアアダプター（Microsoft Basic Render Driver等）はベンダーが判定できないため除く
+///
+/// # Returns
+/// ベンダー・カウンターのインスタンス名の接頭辞・アダプター名のリスト
+pub fn parse_directx_adapters(output: &str) -> Vec<(GpuVendor, String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(3, '|');
+            let vendor_id = fields.next()?.trim().parse::<u32>().ok()?;
+            let luid = fields.next()?.trim().parse::<i64>().ok()?;
+            let description = fields.next().unwrap_or_default().trim().to_string();
+            let vendor = GpuVendor::from_pci_vendor_id(u16::try_from(vendor_id).ok()?);
+            (vendor != GpuVendor::Unknown).then(|| (vendor, wddm_luid_instance(luid as u64), description))
+        })
+        .collect()
+}
+
+/// 対象GPUのアダプターLUIDのインスタンス名の接頭辞を選ぶ
+///
+/// 同じベンダーのアダプターが複数ある場合は、ビデオコントローラーの名前と一致するものを優先する
+pub fn select_adapter_luid(adapters: &[(GpuVendor, String, String)], vendor: GpuVendor, name: &str) -> Option<String> {
+    let candidates = || adapters.iter().filter(|(v, _, _)| *v == vendor);
+    candidates()
+        .find(|(_, _, description)| description.eq_ignore_ascii_case(name))
+        .or_else(|| candidates().next())
+        .map(|(_, luid, _)| luid.clone())
+}
+
+/// Windowsのビデオコントローラー一覧（"PNPDeviceID|名前|AdapterRAM"）を解析
+///
+/// # Returns
+/// ベンダー・名前・VRAM容量（バイト）のリスト
+pub fn parse_windows_video_controllers(output: &str) -> Vec<(GpuVendor, String, u64)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().split('|');
+            let pnp_id = fields.next()?.to_uppercase();
+            let name = fields.next()?.trim().to_string();
+            let memory_total = fields.next().and_then(|v| v.trim().parse::<u64>().ok()).unwrap_or(0);
+
+            let vendor_id = pnp_id
+                .split("VEN_")
+                .nth(1)
+                .and_then(|rest| u16::from_str_radix(rest.get(..4)?, 16).ok())?;
+            Some((GpuVendor::from_pci_vendor_id(vendor_id), name, memory_total))
+        })
+        .collect()
+}
+
+/// AMD GPUの取得元（Linux: amdgpuドライバーのsysfs）
+#[cfg(target_os = "linux")]
+pub struct AmdSysfsProvider {
+    /// `/sys/class/drm/cardN/device`
+    device_dir: Option<std::path::PathBuf>,
+    /// 製品名
+    name: String,
+}
+
+#[cfg(target_os = "linux")]
+impl AmdSysfsProvider {
+    /// 最初に見つかったAMD GPUを対象にする
+    pub fn new() -> Self {
+        let device_dir = linux::find_drm_devices()
+            .into_iter()
+            .find(|(vendor, dir)| *vendor == GpuVendor::Amd && dir.join("gpu_busy_percent").exists())
+            .map(|(_, dir)| dir);
+        let name = device_dir
+            .as_deref()
+            .and_then(|dir| linux::lspci_device_name(dir, GpuVendor::Amd))
+            .unwrap_or_else(|| "AMD Radeon".to_string());
+        Self { device_dir, name }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Default for AmdSysfsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl GpuMetricsProvider for AmdSysfsProvider {
+    fn vendor(&self) -> GpuVendor {
+        GpuVendor::Amd
+    }
+
+    fn is_available(&self) -> bool {
+        self.device_dir.is_some()
+    }
+
+    fn metrics(&self) -> Result<Option<GpuMetrics>, AppError> {
+        let Some(dir) = &self.device_dir else {
+            return Ok(None);
+        };
+        let read = |name: &str| linux::read_trimmed(&dir.join(name));
+
+        let Some(usage) = read("gpu_busy_percent").and_then(|v| v.parse::<f32>().ok()) else {
+            return Ok(None);
+        };
+        let temperature = std::fs::read_dir(dir.join("hwmon"))
+            .ok()
+            .and_then(|mut entries| entries.next())
+            .and_then(|entry| entry.ok())
+            .and_then(|entry| linux::read_trimmed(&entry.path().join("temp1_input")))
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|millidegrees| millidegrees / 1000);
+
+        Ok(Some(GpuMetrics {
+            name: self.name.clone(),
+            index: 0,
+            usage_percent: usage.clamp(0.0, 100.0),
+            memory_used_bytes: read("mem_info_vram_used").and_then(|v| v.parse().ok()).unwrap_or(0),
+            memory_total_bytes: read("mem_info_vram_total").and_then(|v| v.parse().ok()).unwrap_or(0),
+            temperature,
+            // amdgpuはVCN（エンコーダー）の使用率をsysfsで公開していない
+            encoder_usage: None,
+        }))
+    }
+}
+
+/// 直近の取得結果（取得時刻とメトリクス）
+#[cfg(target_os = "windows")]
+type WddmSample = std::sync::Arc<std::sync::Mutex<Option<(std::time::Instant, GpuMetrics)>>>;
+
+/// AMD・Intel GPUの取得元（Windows: WDDMのパフォーマンスカウンター）
+///
+/// PowerShellの起動が重いため、取得結果を `SAMPLE_INTERVAL` の間再利用する。
+/// 取得結果が古い場合はブロッキング処理用のスレッドで再取得し、メトリクスの取得経路では直近の結果を返す
+#[cfg(target_os = "windows")]
+pub struct WddmCounterProvider {
+    /// 対象ベンダー
+    vendor: GpuVendor,
+    /// 製品名とVRAM容量（対象ベンダーのGPUが見つからない場合はNone）
+    adapter: Option<(String, u64)>,
+    /// 対象GPUのカウンターのインスタンス名の接頭辞（LUIDが取得できない場合は全アダプターを集計する）
+    luid_instance: Option<String>,
+    /// 直近の取得結果
+    last_sample: WddmSample,
+    /// 再取得が実行中か
+    refreshing: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(target_os = "windows")]
+impl WddmCounterProvider {
+    /// 取得結果を再利用する間隔
+    const SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+    /// 指定ベンダーのGPUを対象にする
+    ///
+    /// # Arguments
+    /// * `controllers` - ビデオコントローラーの一覧
+    /// * `adapters` - DirectXのアダプター一覧（LUIDの特定に使う）
+    pub fn new(
+        vendor: GpuVendor,
+        controllers: &[(GpuVendor, String, u64)],
+        adapters: &[(GpuVendor, String, String)],
+    ) -> Self {
+        let adapter = controllers
+            .iter()
+            .find(|(v, _, _)| *v == vendor)
+            .map(|(_, name, memory)| (name.clone(), *memory));
+        let luid_instance = adapter
+            .as_ref()
+            .and_then(|(name, _)| select_adapter_luid(adapters, vendor, name));
+        if adapter.is_some() && luid_instance.is_none() {
+            tracing::debug!(target: "gpu", vendor = ?vendor, "アダプターLUIDを特定できないため全アダプターを集計します");
+        }
+        Self {
+            vendor,
+            adapter,
+            luid_instance,
+            last_sample: WddmSample::default(),
+            refreshing: std::sync::Arc::default(),
+        }
+    }
+
+    /// パフォーマンスカウンターを取得して直近の取得結果を更新
+    fn refresh(sample: &WddmSample, name: &str, memory_total: u64, luid_instance: Option<&str>) {
+        let metrics = windows::query_wddm_counters(luid_instance).map(|(usage, encoder_usage, memory_used)| GpuMetrics {
+            name: name.to_string(),
+            index: 0,
+            usage_percent: usage,
+            memory_used_bytes: memory_used,
+            memory_total_bytes: memory_total.max(memory_used),
+            temperature: None,
+            encoder_usage,
+        });
+        if let Some(metrics) = metrics {
+            *sample.lock().unwrap_or_else(std::sync::PoisonError::into_inner) =
+                Some((std::time::Instant::now(), metrics));
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl GpuMetricsProvider for WddmCounterProvider {
+    fn vendor(&self) -> GpuVendor {
+        self.vendor
+    }
+
+    fn is_available(&self) -> bool {
+        self.adapter.is_some()
+    }
+
+    fn metrics(&self) -> Result<Option<GpuMetrics>, AppError> {
+        use std::sync::atomic::Ordering;
+
+        let Some((name, memory_total)) = &self.adapter else {
+            return Ok(None);
+        };
+
+        let cached = self
+            .last_sample
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        if cached
+            .as_ref()
+            .is_some_and(|(taken_at, _)| taken_at.elapsed() < Self::SAMPLE_INTERVAL)
+        {
+            return Ok(cached.map(|(_, metrics)| metrics));
+        }
+
+        match tokio::runtime::Handle::try_current() {
+            // 非同期ランタイム上では再取得を待たず、直近の結果を返す（初回は取得前のためNone）
+            Ok(runtime) => {
+                if !self.refreshing.swap(true, Ordering::AcqRel) {
+                    let (sample, refreshing) = (self.last_sample.clone(), self.refreshing.clone());
+                    let (name, memory_total, luid_instance) = (name.clone(), *memory_total, self.luid_instance.clone());
+                    runtime.spawn_blocking(move || {
+                        Self::refresh(&sample, &name, memory_total, luid_instance.as_deref());
+                        refreshing.store(false, Ordering::Release);
+                    });
+                }
+                Ok(cached.map(|(_, metrics)| metrics))
+            }
+            Err(_) => {
+                Self::refresh(&self.last_sample, name, *memory_total, self.luid_instance.as_deref());
+                Ok(self
+                    .last_sample
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .as_ref()
+                    .map(|(_, metrics)| metrics.clone()))
+            }
+        }
+    }
+}
+
+/// 搭載GPUのベンダーを検出し、プラットフォームで使える取得元を選択
+pub fn select_platform_provider() -> Option<Box<dyn GpuMetricsProvider>> {
+    let nvml: Box<dyn GpuMetricsProvider> = Box::new(crate::monitor::gpu::NvmlProvider);
+
+    #[cfg(target_os = "linux")]
+    let (vendors, providers): (Vec<GpuVendor>, Vec<Box<dyn GpuMetricsProvider>>) = (
+        linux::find_drm_devices().into_iter().map(|(vendor, _)| vendor).collect(),
+        vec![nvml, Box::new(AmdSysfsProvider::new())],
+    );
+
+    #[cfg(target_os = "windows")]
+    let (vendors, providers): (Vec<GpuVendor>, Vec<Box<dyn GpuMetricsProvider>>) = {
+        let controllers = windows::video_controllers();
+        let adapters = windows::directx_adapters();
+        (
+            controllers.iter().map(|(vendor, _, _)| *vendor).collect(),
+            vec![
+                nvml,
+                Box::new(WddmCounterProvider::new(GpuVendor::Amd, &controllers, &adapters)),
+                Box::new(WddmCounterProvider::new(GpuVendor::Intel, &controllers, &adapters)),
+            ],
+        )
+    };
+
+    // ベンダーを列挙できない環境ではNVMLのみ試す
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    let (vendors, providers): (Vec<GpuVendor>, Vec<Box<dyn GpuMetricsProvider>>) =
+        (vec![GpuVendor::Nvidia], vec![nvml]);
+
+    let provider = select_provider(&vendors, providers);
+    match &provider {
+        Some(provider) => {
+            tracing::info!(target: "gpu", vendor = ?provider.vendor(), "GPUメトリクスの取得元を選択");
+        }
+        None => {
+            tracing::info!(target: "gpu", vendors = ?vendors, "GPUメトリクスを取得できる取得元がありません");
+        }
+    }
+    provider
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{parse_lspci_device_name, GpuVendor};
+    use std::path::{Path, PathBuf};
+
+    /// ファイルを読み込み前後の空白を除く
+    pub fn read_trimmed(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+    }
+
+    /// `/sys/class/drm/cardN/device` とベンダーの一覧（cardN-DP-1等のコネクターは除く）
+    pub fn find_drm_devices() -> Vec<(GpuVendor, PathBuf)> {
+        let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+            return Vec::new();
+        };
+
+        let mut devices: Vec<(GpuVendor, PathBuf)> = entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.strip_prefix("card"))
+                    .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+            })
+            .filter_map(|entry| {
+                let device_dir = entry.path().join("device");
+                let vendor_id = read_trimmed(&device_dir.join("vendor"))?;
+                let vendor_id = u16::from_str_radix(vendor_id.trim_start_matches("0x"), 16).ok()?;
+                Some((GpuVendor::from_pci_vendor_id(vendor_id), device_dir))
+            })
+            .collect();
+        devices.sort_by(|a, b| a.1.cmp(&b.1));
+        devices
+    }
+
+    /// `lspci -mm` でPCIスロットの製品名を取得
+    pub fn lspci_device_name(device_dir: &Path, vendor: GpuVendor) -> Option<String> {
+        let slot = std::fs::canonicalize(device_dir).ok()?.file_name()?.to_str()?.to_string();
+        let output = std::process::Command::new("lspci")
+            .args(["-mm", "-s", &slot])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| parse_lspci_device_name(line, vendor))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::{parse_directx_adapters, parse_wddm_counters, parse_windows_video_controllers, GpuVendor};
+
+    /// PowerShellのスクリプトを実行し、標準出力を返す
+    fn run_powershell(script: &str) -> Option<String> {
+        std::process::Command::new("powershell")
+            .args(["-NoProfile", "-Command", script])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// ビデオコントローラーの一覧（ベンダー・名前・VRAM容量）
+    pub fn video_controllers() -> Vec<(GpuVendor, String, u64)> {
+        let script = "Get-CimInstance Win32_VideoController | \
+                      ForEach-Object { \"$($_.PNPDeviceID)|$($_.Name)|$($_.AdapterRAM)\" }";
+        run_powershell(script).map_or_else(Vec::new, |out| parse_windows_video_controllers(&out))
+    }
+
+    /// DirectXのアダプター一覧（ベンダー・カウンターのインスタンス名の接頭辞・アダプター名）
+    pub fn directx_adapters() -> Vec<(GpuVendor, String, String)> {
+        let script = "Get-ChildItem HKLM:\\SOFTWARE\\Microsoft\\DirectX -ErrorAction SilentlyContinue | \
+                      ForEach-Object { $p = Get-ItemProperty $_.PSPath; \
+                      if ($null -ne $p.AdapterLuid) { \"$($p.VendorId)|$($p.AdapterLuid)|$($p.Description)\" } }";
+        run_powershell(script).map_or_else(Vec::new, |out| parse_directx_adapters(&out))
+    }
+
+    /// GPUの3D・エンコード使用率と専用メモリ使用量を取得
+    ///
+    /// # Arguments
+    /// * `luid_instance` - 対象GPUのインスタンス名の接頭辞（Noneの場合は全アダプターを集計する）
+    pub fn query_wddm_counters(luid_instance: Option<&str>) -> Option<(f32, Option<f32>, u64)> {
+        let luid = luid_instance.unwrap_or_default();
+        let script = format!(
+            "$s = (Get-Counter '\\GPU Engine(*{luid}*engtype_3D)\\Utilization Percentage', \
+             '\\GPU Engine(*{luid}*engtype_VideoEncode)\\Utilization Percentage', \
+             '\\GPU Adapter Memory({luid}*)\\Dedicated Usage').CounterSamples; \
+             $d = ($s | Where-Object Path -like '*engtype_3d*' | Measure-Object CookedValue -Sum).Sum; \
+             $e = ($s | Where-Object Path -like '*engtype_videoencode*' | Measure-Object CookedValue -Sum).Sum; \
+             $m = ($s | Where-Object Path -like '*dedicated usage' | Measure-Object CookedValue -Sum).Sum; \
+             \"$d,$e,$m\""
+        );
+        run_powershell(&script).and_then(|out| parse_wddm_counters(&out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockProvider {
+        vendor: GpuVendor,
+        available: bool,
+    }
+
+    impl GpuMetricsProvider for MockProvider {
+        fn vendor(&self) -> GpuVendor {
+            self.vendor
+        }
+
+        fn is_available(&self) -> bool {
+            self.available
+        }
+
+        fn metrics(&self) -> Result<Option<GpuMetrics>, AppError> {
+            Ok(Some(GpuMetrics {
+                name: format!("{:?}", self.vendor),
+                index: 0,
+                usage_percent: 50.0,
+                memory_used_bytes: 0,
+                memory_total_bytes: 0,
+                temperature: None,
+                encoder_usage: None,
+            }))
+        }
+    }
+
+    fn mocks(entries: &[(GpuVendor, bool)]) -> Vec<Box<dyn GpuMetricsProvider>> {
+        entries
+            .iter()
+            .map(|&(vendor, available)| Box::new(MockProvider { vendor, available }) as Box<dyn GpuMetricsProvider>)
+            .collect()
+    }
+
+    fn selected_vendor(vendors: &[GpuVendor], providers: &[(GpuVendor, bool)]) -> Option<GpuVendor> {
+        select_provider(vendors, mocks(providers)).map(|provider| provider.vendor())
+    }
+
+    #[test]
+    fn test_select_provider_by_vendor() {
+        let all = [(GpuVendor::Nvidia, true), (GpuVendor::Amd, true), (GpuVendor::Intel, true)];
+
+        assert_eq!(selected_vendor(&[GpuVendor::Amd], &all), Some(GpuVendor::Amd));
+        assert_eq!(selected_vendor(&[GpuVendor::Intel], &all), Some(GpuVendor::Intel));
+        assert_eq!(selected_vendor(&[GpuVendor::Nvidia], &all), Some(GpuVendor::Nvidia));
+    }
+
+    #[test]
+    fn test_select_provider_prefers_discrete_gpu() {
+        let all = [(GpuVendor::Nvidia, true), (GpuVendor::Amd, true), (GpuVendor::Intel, true)];
+
+        // 内蔵GPU（Intel）と外付けGPUが混在する場合は外付けGPUを優先
+        assert_eq!(selected_vendor(&[GpuVendor::Intel, GpuVendor::Amd], &all), Some(GpuVendor::Amd));
+        assert_eq!(selected_vendor(&[GpuVendor::Intel, GpuVendor::Nvidia], &all), Some(GpuVendor::Nvidia));
+    }
+
+    #[test]
+    fn test_select_provider_falls_back_when_unavailable() {
+        // NVIDIAのドライバーがない場合は内蔵GPUの取得元を使う
+        let providers = [(GpuVendor::Nvidia, false), (GpuVendor::Intel, true)];
+        assert_eq!(selected_vendor(&[GpuVendor::Nvidia, GpuVendor::Intel], &providers), Some(GpuVendor::Intel));
+
+        // 対応する取得元がない場合はNone
+        assert_eq!(selected_vendor(&[GpuVendor::Intel], &[(GpuVendor::Amd, true)]), None);
+        assert_eq!(selected_vendor(&[GpuVendor::Unknown], &[(GpuVendor::Nvidia, true)]), None);
+        assert_eq!(selected_vendor(&[], &[(GpuVendor::Nvidia, true)]), None);
+    }
+
+    #[test]
+    fn test_selected_provider_returns_metrics() {
+        let provider = select_provider(&[GpuVendor::Amd], mocks(&[(GpuVendor::Amd, true)])).unwrap();
+        let metrics = provider.metrics().unwrap().unwrap();
+        assert_eq!(metrics.name, "Amd");
+    }
+
+    #[test]
+    fn test_vendor_from_pci_id() {
+        assert_eq!(GpuVendor::from_pci_vendor_id(0x10de), GpuVendor::Nvidia);
+        assert_eq!(GpuVendor::from_pci_vendor_id(0x1002), GpuVendor::Amd);
+        assert_eq!(GpuVendor::from_pci_vendor_id(0x8086), GpuVendor::Intel);
+        assert_eq!(GpuVendor::from_pci_vendor_id(0x1234), GpuVendor::Unknown);
+    }
+
+    #[test]
+    fn test_parse_lspci_device_name() {
+        let line = r#"03:00.0 "VGA compatible controller" "Advanced Micro Devices, Inc. [AMD/ATI]" "Navi 31 [Radeon RX 7900 XT/7900 XTX]" -rc8 "Sapphire" "Device 471e""#;
+        assert_eq!(
+            parse_lspci_device_name(line, GpuVendor::Amd).as_deref(),
+            Some("AMD Radeon RX 7900 XT/7900 XTX")
+        );
+
+        let line = r#"00:02.0 "VGA compatible controller" "Intel Corporation" "Arc A770" "" """#;
+        assert_eq!(parse_lspci_device_name(line, GpuVendor::Intel).as_deref(), Some("Intel Arc A770"));
+        assert_eq!(parse_lspci_device_name("garbage", GpuVendor::Amd), None);
+    }
+
+    #[test]
+    fn test_parse_wddm_counters() {
+        assert_eq!(parse_wddm_counters("37.5,12,2147483648\r\n"), Some((37.5, Some(12.0), 2_147_483_648)));
+        // 複数エンジンの合計が100%を超えても頭打ち
+        assert_eq!(parse_wddm_counters("130.2,,0"), Some((100.0, None, 0)));
+        assert_eq!(parse_wddm_counters(""), None);
+        assert_eq!(parse_wddm_counters(",,"), None);
+    }
+
+    #[test]
+    fn test_parse_directx_adapters() {
+        // AdapterLuid 0x0000_0000_0000_D1A5 と 0x0000_0001_0000_0F2C
+        let output = "4098|53669|AMD Radeon RX 7900 XTX\r\n\
+                      32902|4294971180|Intel(R) UHD Graphics 770\r\n\
+                      5140|55555|Microsoft Basic Render Driver\r\n\
+                      invalid\r\n";
+        let adapters = parse_directx_adapters(output);
+
+        assert_eq!(
+            adapters,
+            vec![
+                (GpuVendor::Amd, "luid_0x00000000_0x0000d1a5".to_string(), "AMD Radeon RX 7900 XTX".to_string()),
+                (GpuVendor::Intel, "luid_0x00000001_0x00000f2c".to_string(), "Intel(R) UHD Graphics 770".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_adapter_luid() {
+        let adapters = vec![
+            (GpuVendor::Intel, "luid_0x00000000_0x00000001".to_string(), "Intel(R) UHD Graphics 770".to_string()),
+            (GpuVendor::Intel, "luid_0x00000000_0x00000002".to_string(), "Intel(R) Arc(TM) A770 Graphics".to_string()),
+        ];
+
+        // 同じベンダーが複数ある場合は名前が一致するアダプター
+        assert_eq!(
+            select_adapter_luid(&adapters, GpuVendor::Intel, "Intel(R) Arc(TM) A770 Graphics").as_deref(),
+            Some("luid_0x00000000_0x00000002")
+        );
+        // 名前が一致しない場合は最初のアダプター
+        assert_eq!(
+            select_adapter_luid(&adapters, GpuVendor::Intel, "Intel Graphics").as_deref(),
+            Some("luid_0x00000000_0x00000001")
+        );
+        assert_eq!(select_adapter_luid(&adapters, GpuVendor::Amd, "AMD Radeon"), None);
+    }
+
+    #[test]
+    fn test_parse_windows_video_controllers() {
+        let output = "PCI\\VEN_1002&DEV_744C&SUBSYS_0E3B1002|AMD Radeon RX 7900 XTX|4293918720\r\n\
+                      PCI\\VEN_8086&DEV_A780|Intel(R) UHD Graphics 770|1073741824\r\n\
+                      ROOT\\DISPLAY\\0000|Microsoft Basic Display Adapter|\r\n";
+        let controllers = parse_windows_video_controllers(output);
+
+        assert_eq!(controllers.len(), 2);
+        assert_eq!(controllers[0], (GpuVendor::Amd, "AMD Radeon RX 7900 XTX".to_string(), 4_293_918_720));
+        assert_eq!(controllers[1].0, GpuVendor::Intel);
+    }
+}
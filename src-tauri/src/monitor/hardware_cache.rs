@@ -0,0 +1,122 @@
+// ハードウェア情報キャッシュ
+//
+// 起動中に変化しないハードウェア情報を保持する。
+// CPUのベースクロックは取得APIがOSごとに異なるため、
+// 最初に計測したクロック周波数をベースとして扱う。
+
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// スロットリング中とみなす低下率のしきい値（25%）
+pub const CPU_THROTTLE_THRESHOLD: f32 = 0.25;
+
+/// ハードウェア情報のキャッシュ
+#[derive(Debug, Default, Clone)]
+pub struct HardwareInfoCache {
+    /// CPUのベースクロック周波数（MHz、未計測の場合はNone）
+    cpu_base_frequency_mhz: Option<f64>,
+}
+
+impl HardwareInfoCache {
+    /// 空のキャッシュを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CPUのベースクロック周波数（MHz）
+    pub const fn cpu_base_frequency_mhz(&self) -> Option<f64> {
+        self.cpu_base_frequency_mhz
+    }
+
+    /// CPUクロック周波数の計測値を記録し、ベースクロックを返す
+    ///
+    /// ベースクロックが未設定の場合のみ、今回の計測値をベースとして保存する
+    ///
+    /// # Arguments
+    /// * `current_mhz` - 今回計測したクロック周波数（取得できない場合はNone）
+    pub fn record_cpu_frequency(&mut self, current_mhz: Option<f64>) -> Option<f64> {
+        if self.cpu_base_frequency_mhz.is_none() {
+            self.cpu_base_frequency_mhz = current_mhz.filter(|mhz| mhz.is_finite() && *mhz > 0.0);
+        }
+        self.cpu_base_frequency_mhz
+    }
+}
+
+/// グローバルなハードウェア情報キャッシュ
+static HARDWARE_INFO_CACHE: Lazy<Mutex<HardwareInfoCache>> =
+    Lazy::new(|| Mutex::new(HardwareInfoCache::new()));
+
+/// CPUクロック周波数の計測値をグローバルキャッシュに記録し、ベースクロックを返す
+///
+/// Mutexが破損している場合はNoneを返す
+pub fn record_cpu_frequency(current_mhz: Option<f64>) -> Option<f64> {
+    HARDWARE_INFO_CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.record_cpu_frequency(current_mhz))
+}
+
+/// CPUクロックの低下率を計算（`1 - 現在 / ベース`）
+///
+/// ブーストでベースを上回っている場合は0とする
+///
+/// # Returns
+/// - `Some(0.0..=1.0)` - 低下率
+/// - `None` - 現在のクロックまたはベースクロックが不明な場合
+pub fn calculate_throttle_percent(current_mhz: Option<f64>, base_mhz: Option<f64>) -> Option<f32> {
+    let (current, base) = (current_mhz?, base_mhz?);
+    if !current.is_finite() || !base.is_finite() || base <= 0.0 {
+        return None;
+    }
+    Some((1.0 - current / base).clamp(0.0, 1.0) as f32)
+}
+
+/// 低下率からスロットリング中かを判定
+pub fn is_cpu_throttling(throttle_percent: Option<f32>) -> bool {
+    throttle_percent.is_some_and(|percent| percent > CPU_THROTTLE_THRESHOLD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_percent_calculation() {
+        let percent = calculate_throttle_percent(Some(2400.0), Some(3200.0));
+        assert!(percent.is_some_and(|p| (p - 0.25).abs() < f32::EPSILON));
+
+        // ベースと同じなら0
+        assert_eq!(calculate_throttle_percent(Some(3200.0), Some(3200.0)), Some(0.0));
+        // ブースト中は0にクランプ
+        assert_eq!(calculate_throttle_percent(Some(4500.0), Some(3200.0)), Some(0.0));
+    }
+
+    #[test]
+    fn test_throttle_percent_unknown_base_returns_none() {
+        assert_eq!(calculate_throttle_percent(Some(2400.0), None), None);
+        assert_eq!(calculate_throttle_percent(None, Some(3200.0)), None);
+        assert_eq!(calculate_throttle_percent(Some(2400.0), Some(0.0)), None);
+    }
+
+    #[test]
+    fn test_is_cpu_throttling_threshold() {
+        assert!(!is_cpu_throttling(None));
+        assert!(!is_cpu_throttling(Some(0.25)));
+        assert!(is_cpu_throttling(Some(0.26)));
+    }
+
+    #[test]
+    fn test_base_frequency_is_first_measurement() {
+        let mut cache = HardwareInfoCache::new();
+        assert_eq!(cache.cpu_base_frequency_mhz(), None);
+
+        // 取得できなかった計測値はベースにしない
+        assert_eq!(cache.record_cpu_frequency(None), None);
+        assert_eq!(cache.record_cpu_frequency(Some(0.0)), None);
+
+        assert_eq!(cache.record_cpu_frequency(Some(3600.0)), Some(3600.0));
+        // 2回目以降の計測値でベースは変わらない
+        assert_eq!(cache.record_cpu_frequency(Some(2000.0)), Some(3600.0));
+        assert_eq!(cache.cpu_base_frequency_mhz(), Some(3600.0));
+    }
+}
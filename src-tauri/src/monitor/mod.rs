@@ -2,8 +2,16 @@
 //
 // CPU、メモリ、GPU、ネットワーク、プロセスの監視機能を提供
 
+pub mod audio_device;
+pub mod conflicts;
+pub mod cpu_benchmark;
+pub mod disk;
+pub mod display;
 pub mod gpu;
+pub mod gpu_provider;
+pub mod hardware_cache;
 pub mod network;
+pub mod power;
 pub mod process;
 
 #[cfg(test)]
@@ -15,9 +23,12 @@ use once_cell::sync::Lazy;
 use crate::error::AppError;
 
 // 公開エクスポート
+pub use audio_device::AudioDeviceLatencyWarning;
+pub use display::DisplayInfo;
 pub use gpu::GpuMetrics;
-pub use network::NetworkMetrics;
-pub use process::ObsProcessMetrics;
+pub use gpu_provider::GpuVendor;
+pub use network::{NetworkAdapterType, NetworkMetrics};
+pub use process::{ObsProcessMetrics, ProcessPriority};
 
 // グローバルなSystem インスタンス（スレッドセーフ）
 // Mutex::lock() はpoisoned状態（パニック発生時）でもmap_errで適切にエラー変換される
@@ -50,6 +61,27 @@ pub fn get_cpu_usage() -> Result<f32, AppError> {
     Ok(avg.clamp(0.0, 100.0))
 }
 
+/// 現在のCPUクロック周波数を取得（全コアの平均、MHz）
+///
+/// 周波数を取得できない環境（仮想マシン等で0が返る場合）はNoneを返す
+pub fn get_cpu_frequency_mhz() -> Result<Option<f64>, AppError> {
+    let mut sys = SYSTEM.lock()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to lock system mutex: {e}")))?;
+    sys.refresh_cpu_frequency();
+
+    let frequencies: Vec<u64> = sys.cpus()
+        .iter()
+        .map(sysinfo::Cpu::frequency)
+        .filter(|&mhz| mhz > 0)
+        .collect();
+    if frequencies.is_empty() {
+        return Ok(None);
+    }
+
+    let total: u64 = frequencies.iter().sum();
+    Ok(Some(total as f64 / frequencies.len() as f64))
+}
+
 /// メモリ情報を取得（使用量, 総量）バイト単位
 pub fn get_memory_info() -> Result<(u64, u64), AppError> {
     let mut sys = SYSTEM.lock()
@@ -2,13 +2,42 @@
 //
 // sysinfo クレートを使用してネットワーク帯域を取得
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sysinfo::Networks;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 use once_cell::sync::Lazy;
 use crate::error::AppError;
 
+/// ネットワークアダプターの種類（デフォルトルートのインターフェース）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkAdapterType {
+    /// 有線LAN
+    Wired,
+    /// Wi-Fi
+    Wireless,
+    /// VPN・トンネル・コンテナ用ブリッジ等の仮想インターフェース（物理回線の種類は判定しない）
+    Virtual,
+    /// 判定できない
+    #[default]
+    Unknown,
+}
+
+impl NetworkAdapterType {
+    /// 表示用ラベルを取得
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::Wired => "有線LAN",
+            Self::Wireless => "Wi-Fi",
+            Self::Virtual => "仮想・VPN",
+            Self::Unknown => "不明",
+        }
+    }
+}
+
 /// ネットワーク使用状況のメトリクス
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -17,72 +46,172 @@ pub struct NetworkMetrics {
     pub upload_bytes_per_sec: u64,
     /// ダウンロード速度（バイト/秒）
     pub download_bytes_per_sec: u64,
+    /// デフォルトルートのアダプター種類
+    pub adapter_type: NetworkAdapterType,
+    /// 計測したインターフェース名（対象のインターフェースがない場合はNone）
+    pub interface_name: Option<String>,
+}
+
+/// ネットワークインターフェースの情報
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterfaceInfo {
+    /// インターフェース名
+    pub name: String,
+    /// 現在のアップロード速度（ビット/秒）
+    pub current_upload_bps: u64,
+    /// 現在のダウンロード速度（ビット/秒）
+    pub current_download_bps: u64,
+    /// リンクが有効か
+    pub is_up: bool,
+}
+
+/// インターフェースごとの転送速度
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InterfaceRate {
+    name: String,
+    /// アップロード速度（バイト/秒）
+    upload_bytes_per_sec: u64,
+    /// ダウンロード速度（バイト/秒）
+    download_bytes_per_sec: u64,
+    is_up: bool,
+}
+
+impl InterfaceRate {
+    fn to_info(&self) -> NetworkInterfaceInfo {
+        NetworkInterfaceInfo {
+            name: self.name.clone(),
+            current_upload_bps: self.upload_bytes_per_sec.saturating_mul(8),
+            current_download_bps: self.download_bytes_per_sec.saturating_mul(8),
+            is_up: self.is_up,
+        }
+    }
+}
+
+/// ループバックインターフェースか（配信の計測対象にしない）
+fn is_loopback(name: &str) -> bool {
+    name == "lo" || name == "lo0" || name.to_lowercase().contains("loopback")
+}
+
+/// インターフェースのリンクが有効か
+///
+/// Linuxは `/sys/class/net/<name>/operstate` を参照し、それ以外は累積転送量があれば有効とみなす
+fn is_interface_up(name: &str, total_received: u64, total_transmitted: u64) -> bool {
+    #[cfg(target_os = "linux")]
+    if let Ok(state) = std::fs::read_to_string(std::path::Path::new("/sys/class/net").join(name).join("operstate")) {
+        // ループバックや一部の仮想インターフェースは "unknown" を返す
+        return matches!(state.trim(), "up" | "unknown");
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = name;
+    total_received > 0 || total_transmitted > 0
+}
+
+/// 計測対象のインターフェースを選ぶ
+///
+/// - 優先インターフェースが指定されていて存在する場合はそれを使う
+/// - それ以外はループバックとリンクが無効なものを除き、アップロード速度が最大のものを使う
+///   （同速の場合は名前順で先のもの）
+fn select_interface<'a>(rates: &'a [InterfaceRate], preferred: Option<&str>) -> Option<&'a InterfaceRate> {
+    if let Some(rate) = preferred.and_then(|name| rates.iter().find(|rate| rate.name == name)) {
+        return Some(rate);
+    }
+    rates
+        .iter()
+        .filter(|rate| rate.is_up && !is_loopback(&rate.name))
+        .max_by(|a, b| {
+            a.upload_bytes_per_sec
+                .cmp(&b.upload_bytes_per_sec)
+                .then_with(|| b.name.cmp(&a.name))
+        })
+}
+
+/// 前回の累積転送量との差分からインターフェースごとの速度を計算
+///
+/// # Arguments
+/// * `previous` - 前回のインターフェースごとの累積（受信, 送信）バイト数
+/// * `current` - 今回の（インターフェース名, 累積受信, 累積送信, リンク有効）
+/// * `elapsed_secs` - 前回からの経過秒数
+fn compute_rates(
+    previous: &HashMap<String, (u64, u64)>,
+    current: &[(String, u64, u64, bool)],
+    elapsed_secs: f64,
+) -> Vec<InterfaceRate> {
+    let mut rates: Vec<InterfaceRate> = current
+        .iter()
+        .map(|(name, rx_total, tx_total, is_up)| {
+            // 新しく現れたインターフェースは次回から計測する
+            let (rx_diff, tx_diff) = previous.get(name).map_or((0, 0), |(last_rx, last_tx)| {
+                (rx_total.saturating_sub(*last_rx), tx_total.saturating_sub(*last_tx))
+            });
+            InterfaceRate {
+                name: name.clone(),
+                upload_bytes_per_sec: (tx_diff as f64 / elapsed_secs) as u64,
+                download_bytes_per_sec: (rx_diff as f64 / elapsed_secs) as u64,
+                is_up: *is_up,
+            }
+        })
+        .collect();
+    rates.sort_by(|a, b| a.name.cmp(&b.name));
+    rates
 }
 
 /// 前回のネットワーク統計を保持する構造体
 struct NetworkState {
     networks: Networks,
     last_update: Instant,
-    last_rx_total: u64,
-    last_tx_total: u64,
+    /// インターフェースごとの前回の累積（受信, 送信）バイト数
+    last_totals: HashMap<String, (u64, u64)>,
+    /// 前回計算した速度
+    last_rates: Vec<InterfaceRate>,
 }
 
 impl NetworkState {
     fn new() -> Self {
-        let mut networks = Networks::new_with_refreshed_list();
-        networks.refresh();
-
-        let (rx_total, tx_total) = Self::calculate_totals(&networks);
+        let networks = Networks::new_with_refreshed_list();
+        let totals = Self::current_totals(&networks);
+        let last_rates = compute_rates(&HashMap::new(), &totals, 1.0);
 
         Self {
             networks,
             last_update: Instant::now(),
-            last_rx_total: rx_total,
-            last_tx_total: tx_total,
+            last_totals: totals.into_iter().map(|(name, rx, tx, _)| (name, (rx, tx))).collect(),
+            last_rates,
         }
     }
 
-    fn calculate_totals(networks: &Networks) -> (u64, u64) {
-        let mut rx_total = 0u64;
-        let mut tx_total = 0u64;
-
-        for (_name, data) in networks {
-            rx_total = rx_total.saturating_add(data.total_received());
-            tx_total = tx_total.saturating_add(data.total_transmitted());
-        }
-
-        (rx_total, tx_total)
+    /// インターフェースごとの（名前, 累積受信, 累積送信, リンク有効）
+    fn current_totals(networks: &Networks) -> Vec<(String, u64, u64, bool)> {
+        networks
+            .iter()
+            .map(|(name, data)| {
+                let (rx, tx) = (data.total_received(), data.total_transmitted());
+                (name.clone(), rx, tx, is_interface_up(name, rx, tx))
+            })
+            .collect()
     }
 
-    /// ネットワーク速度を計算（バイト/秒）
-    fn get_speeds(&mut self) -> (u64, u64) {
-        self.networks.refresh();
-
+    /// インターフェースごとの速度を計算（名前順）
+    fn get_rates(&mut self) -> Vec<InterfaceRate> {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_update);
 
-        // 最低100ms経過していないと計算が不正確
+        // 最低100ms経過していないと計算が不正確なため、前回の値を返す
         if elapsed < Duration::from_millis(100) {
-            return (0, 0);
+            return self.last_rates.clone();
         }
 
-        let (rx_total, tx_total) = Self::calculate_totals(&self.networks);
-
-        let elapsed_secs = elapsed.as_secs_f64();
-
-        // 差分から速度を計算
-        let rx_diff = rx_total.saturating_sub(self.last_rx_total);
-        let tx_diff = tx_total.saturating_sub(self.last_tx_total);
-
-        let download_speed = (rx_diff as f64 / elapsed_secs) as u64;
-        let upload_speed = (tx_diff as f64 / elapsed_secs) as u64;
+        // 接続・切断されたインターフェースを反映する
+        self.networks.refresh_list();
+        let totals = Self::current_totals(&self.networks);
+        let rates = compute_rates(&self.last_totals, &totals, elapsed.as_secs_f64());
 
         // 状態を更新
         self.last_update = now;
-        self.last_rx_total = rx_total;
-        self.last_tx_total = tx_total;
+        self.last_totals = totals.into_iter().map(|(name, rx, tx, _)| (name, (rx, tx))).collect();
+        self.last_rates.clone_from(&rates);
 
-        (download_speed, upload_speed)
+        rates
     }
 }
 
@@ -91,32 +220,279 @@ static NETWORK_STATE: Lazy<Mutex<NetworkState>> = Lazy::new(|| {
     Mutex::new(NetworkState::new())
 });
 
+/// インターフェースごとの速度を取得
+fn current_rates() -> Result<Vec<InterfaceRate>, AppError> {
+    let mut state = NETWORK_STATE.lock()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to lock network state: {e}")))?;
+    Ok(state.get_rates())
+}
+
 /// ネットワーク使用状況を取得
 ///
 /// 前回の呼び出しからの差分を計算して速度を算出する
 /// 最初の呼び出しでは0を返す可能性がある
-pub fn get_network_metrics() -> Result<NetworkMetrics, AppError> {
-    let mut state = NETWORK_STATE.lock()
-        .map_err(|e| AppError::system_monitor(&format!("Failed to lock network state: {e}")))?;
-
-    let (download_speed, upload_speed) = state.get_speeds();
+///
+/// # Arguments
+/// * `preferred_interface` - 計測するインターフェース名。
+///   未指定または存在しない場合はアップロード速度が最大のインターフェースを使う
+pub fn get_network_metrics(preferred_interface: Option<&str>) -> Result<NetworkMetrics, AppError> {
+    let rates = current_rates()?;
+    let selected = select_interface(&rates, preferred_interface);
 
     Ok(NetworkMetrics {
-        upload_bytes_per_sec: upload_speed,
-        download_bytes_per_sec: download_speed,
+        upload_bytes_per_sec: selected.map_or(0, |rate| rate.upload_bytes_per_sec),
+        download_bytes_per_sec: selected.map_or(0, |rate| rate.download_bytes_per_sec),
+        adapter_type: cached_network_adapter_type(),
+        interface_name: selected.map(|rate| rate.name.clone()),
     })
 }
 
-/// ネットワークインターフェース名のリストを取得
-#[allow(dead_code)]
-pub fn get_network_interfaces() -> Result<Vec<String>, AppError> {
-    let state = NETWORK_STATE.lock()
-        .map_err(|e| AppError::system_monitor(&format!("Failed to lock network state: {e}")))?;
+/// アダプター種類の再判定間隔
+///
+/// 判定に外部コマンドを使うOSがあるため、メトリクス取得のたびには判定しない
+const ADAPTER_TYPE_CACHE_TTL: Duration = Duration::from_secs(30);
 
-    let names: Vec<String> = state.networks.keys().cloned()
-        .collect();
+/// 判定済みのアダプター種類と判定時刻
+static ADAPTER_TYPE_CACHE: Lazy<Mutex<Option<(Instant, NetworkAdapterType)>>> = Lazy::new(|| Mutex::new(None));
 
-    Ok(names)
+/// バックグラウンドでの再判定が実行中か
+static ADAPTER_TYPE_REFRESHING: AtomicBool = AtomicBool::new(false);
+
+/// 判定結果をキャッシュに保存
+fn store_adapter_type(adapter_type: NetworkAdapterType) {
+    *ADAPTER_TYPE_CACHE.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), adapter_type));
+}
+
+/// キャッシュが有効期限内であれば判定済みのアダプター種類を返す
+fn fresh_adapter_type() -> Option<NetworkAdapterType> {
+    ADAPTER_TYPE_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .filter(|(detected_at, _)| detected_at.elapsed() < ADAPTER_TYPE_CACHE_TTL)
+        .map(|(_, adapter_type)| adapter_type)
+}
+
+/// 直近に判定したアダプター種類（未判定の場合は判定できない状態）
+///
+/// メトリクスの取得経路から呼ばれるため、外部コマンドは実行しない。
+/// キャッシュが古い場合はブロッキング処理用のスレッドで再判定を開始し、次回以降の呼び出しに反映する
+/// （非同期ランタイムの外から呼ばれた場合は再判定しない）
+pub fn cached_network_adapter_type() -> NetworkAdapterType {
+    if let Some(adapter_type) = fresh_adapter_type() {
+        return adapter_type;
+    }
+
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        if !ADAPTER_TYPE_REFRESHING.swap(true, Ordering::AcqRel) {
+            runtime.spawn_blocking(|| {
+                store_adapter_type(detect_network_adapter_type());
+                ADAPTER_TYPE_REFRESHING.store(false, Ordering::Release);
+            });
+        }
+    }
+
+    ADAPTER_TYPE_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .map_or(NetworkAdapterType::Unknown, |(_, adapter_type)| adapter_type)
+}
+
+/// デフォルトルートのアダプター種類を取得（一定時間キャッシュする）
+///
+/// 判定が必要な場合はブロッキング処理用のスレッドで行い、結果を待つ。
+/// 分析など、判定結果を必要とする処理から使う
+pub async fn get_network_adapter_type() -> NetworkAdapterType {
+    if let Some(adapter_type) = fresh_adapter_type() {
+        return adapter_type;
+    }
+
+    let adapter_type = tokio::task::spawn_blocking(detect_network_adapter_type)
+        .await
+        .unwrap_or_default();
+    store_adapter_type(adapter_type);
+    adapter_type
+}
+
+/// VPN・トンネル・コンテナ・仮想マシン用の仮想インターフェースの名前か
+///
+/// OSがインターフェースの種類を返さない場合の判定に使う
+pub fn is_virtual_interface_name(name: &str) -> bool {
+    const VIRTUAL_PREFIXES: &[&str] = &[
+        "tun", "tap", "wg", "utun", "ipsec", "ppp", "gif", "stf", "tailscale", "zt", "docker", "br-", "bridge",
+        "veth", "virbr", "vmnet", "vboxnet", "lxc", "lxd", "cni", "flannel", "awdl", "llw",
+    ];
+    let name = name.to_lowercase();
+    VIRTUAL_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Linuxのインターフェースの種類を判定
+///
+/// # Arguments
+/// * `name` - インターフェース名
+/// * `is_wireless` - `/sys/class/net/<name>/wireless` 等が存在するか
+/// * `has_device` - `/sys/class/net/<name>/device` が存在するか（仮想インターフェースには存在しない）
+pub fn classify_linux_interface(name: &str, is_wireless: bool, has_device: bool) -> NetworkAdapterType {
+    if is_wireless {
+        NetworkAdapterType::Wireless
+    } else if !has_device || is_virtual_interface_name(name) {
+        NetworkAdapterType::Virtual
+    } else {
+        NetworkAdapterType::Wired
+    }
+}
+
+/// `/proc/net/route` からデフォルトルートのインターフェース名を取得（Linux）
+///
+/// 宛先が `00000000` の経路のうち、メトリックが最小のものを選ぶ
+pub fn parse_default_route_interface(route_table: &str) -> Option<String> {
+    route_table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (iface, destination, metric) = (fields.first()?, fields.get(1)?, fields.get(6)?);
+            (*destination == "00000000").then(|| (metric.parse::<u32>().unwrap_or(u32::MAX), (*iface).to_string()))
+        })
+        .min_by_key(|(metric, _)| *metric)
+        .map(|(_, iface)| iface)
+}
+
+/// `Get-NetAdapter` の `PhysicalMediaType` からアダプター種類を判定（Windows）
+pub fn parse_windows_media_type(media_type: &str) -> NetworkAdapterType {
+    let media_type = media_type.trim().to_lowercase();
+    if media_type.is_empty() {
+        NetworkAdapterType::Unknown
+    } else if media_type.contains("802.11") || media_type.contains("wireless") {
+        NetworkAdapterType::Wireless
+    } else if media_type.contains("802.3") {
+        NetworkAdapterType::Wired
+    } else {
+        NetworkAdapterType::Unknown
+    }
+}
+
+/// 仮想アダプターの説明（`InterfaceDescription`）に含まれる語（Windows）
+const WINDOWS_VIRTUAL_ADAPTER_KEYWORDS: &[&str] = &[
+    "vpn", "tap-", "wintun", "wireguard", "tailscale", "zerotier", "hyper-v virtual", "virtualbox", "vmware",
+    "virtual ethernet", "tunnel",
+];
+
+/// デフォルトルートのアダプターの判定スクリプトの出力からアダプター種類を判定（Windows）
+///
+/// 出力は `PhysicalMediaType|HardwareInterface|InterfaceDescription` の形式。
+/// ハードウェアではないアダプターと、説明が仮想アダプターのものは `Virtual` とする
+pub fn parse_windows_adapter(output: &str) -> NetworkAdapterType {
+    let mut fields = output.trim().splitn(3, '|');
+    let (media_type, hardware, description) = (
+        fields.next().unwrap_or_default(),
+        fields.next().unwrap_or_default().trim(),
+        fields.next().unwrap_or_default().to_lowercase(),
+    );
+    if hardware.eq_ignore_ascii_case("false")
+        || WINDOWS_VIRTUAL_ADAPTER_KEYWORDS.iter().any(|keyword| description.contains(keyword))
+    {
+        return NetworkAdapterType::Virtual;
+    }
+    parse_windows_media_type(media_type)
+}
+
+/// `route -n get default` の出力からインターフェース名を取得（macOS）
+pub fn parse_macos_route_interface(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("interface:"))
+        .map(|iface| iface.trim().to_string())
+}
+
+/// `networksetup -listallhardwareports` の出力からアダプター種類を判定（macOS）
+pub fn parse_macos_hardware_port(output: &str, iface: &str) -> NetworkAdapterType {
+    let mut port = None;
+    for line in output.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("Hardware Port:") {
+            port = Some(name.trim());
+        } else if line.strip_prefix("Device:").map(str::trim) == Some(iface) {
+            return match port {
+                Some(name) if name.contains("Wi-Fi") || name.contains("AirPort") => NetworkAdapterType::Wireless,
+                Some(_) => NetworkAdapterType::Wired,
+                None => NetworkAdapterType::Unknown,
+            };
+        }
+    }
+    NetworkAdapterType::Unknown
+}
+
+/// デフォルトルートのアダプター種類を判定
+#[cfg(target_os = "linux")]
+fn detect_network_adapter_type() -> NetworkAdapterType {
+    let Some(iface) = std::fs::read_to_string("/proc/net/route")
+        .ok()
+        .and_then(|table| parse_default_route_interface(&table))
+    else {
+        return NetworkAdapterType::Unknown;
+    };
+
+    let sys_path = std::path::Path::new("/sys/class/net").join(&iface);
+    if !sys_path.exists() {
+        return NetworkAdapterType::Unknown;
+    }
+    classify_linux_interface(
+        &iface,
+        sys_path.join("wireless").exists() || sys_path.join("phy80211").exists(),
+        sys_path.join("device").exists(),
+    )
+}
+
+/// デフォルトルートのアダプター種類を判定
+#[cfg(target_os = "windows")]
+fn detect_network_adapter_type() -> NetworkAdapterType {
+    let script = "$a = Get-NetRoute -DestinationPrefix 0.0.0.0/0 | Sort-Object RouteMetric | \
+                  Select-Object -First 1 | Get-NetAdapter; \
+                  \"$($a.PhysicalMediaType)|$($a.HardwareInterface)|$($a.InterfaceDescription)\"";
+    match std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+    {
+        Ok(output) if output.status.success() => parse_windows_adapter(&String::from_utf8_lossy(&output.stdout)),
+        _ => NetworkAdapterType::Unknown,
+    }
+}
+
+/// デフォルトルートのアダプター種類を判定
+#[cfg(target_os = "macos")]
+fn detect_network_adapter_type() -> NetworkAdapterType {
+    let run = |program: &str, args: &[&str]| {
+        std::process::Command::new(program)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let Some(iface) = run("route", &["-n", "get", "default"]).and_then(|out| parse_macos_route_interface(&out)) else {
+        return NetworkAdapterType::Unknown;
+    };
+    // VPN（utun・ipsec等）はハードウェアポートの一覧に含まれない
+    if is_virtual_interface_name(&iface) {
+        return NetworkAdapterType::Virtual;
+    }
+    run("networksetup", &["-listallhardwareports"])
+        .map_or(NetworkAdapterType::Unknown, |out| parse_macos_hardware_port(&out, &iface))
+}
+
+/// デフォルトルートのアダプター種類を判定（未対応OS）
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn detect_network_adapter_type() -> NetworkAdapterType {
+    NetworkAdapterType::Unknown
+}
+
+/// ネットワークインターフェースの一覧を取得（ループバックを除く、名前順）
+pub fn get_network_interfaces() -> Result<Vec<NetworkInterfaceInfo>, AppError> {
+    Ok(current_rates()?
+        .iter()
+        .filter(|rate| !is_loopback(&rate.name))
+        .map(InterfaceRate::to_info)
+        .collect())
 }
 
 #[cfg(test)]
@@ -127,7 +503,7 @@ mod tests {
 
     #[test]
     fn test_get_network_metrics_returns_valid_struct() {
-        let result = get_network_metrics();
+        let result = get_network_metrics(None);
         assert!(result.is_ok());
 
         let metrics = result.unwrap();
@@ -136,16 +512,86 @@ mod tests {
         assert!(metrics.download_bytes_per_sec < u64::MAX);
     }
 
+    #[test]
+    fn test_parse_default_route_interface() {
+        let table = "Iface\tDestination\tGateway\tFlags\tRefCnt\tUse\tMetric\tMask\tMTU\tWindow\tIRTT\n\
+                     wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n\
+                     eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+                     eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n";
+        // メトリックが小さい経路が優先される
+        assert_eq!(parse_default_route_interface(table), Some("eth0".to_string()));
+        assert_eq!(parse_default_route_interface("Iface\tDestination\n"), None);
+    }
+
+    #[test]
+    fn test_parse_windows_media_type() {
+        assert_eq!(parse_windows_media_type("Native 802.11\r\n"), NetworkAdapterType::Wireless);
+        assert_eq!(parse_windows_media_type("802.3"), NetworkAdapterType::Wired);
+        assert_eq!(parse_windows_media_type(""), NetworkAdapterType::Unknown);
+        assert_eq!(parse_windows_media_type("Unspecified"), NetworkAdapterType::Unknown);
+    }
+
+    #[test]
+    fn test_parse_windows_adapter() {
+        assert_eq!(
+            parse_windows_adapter("Native 802.11|True|Intel(R) Wi-Fi 6 AX201 160MHz\r\n"),
+            NetworkAdapterType::Wireless
+        );
+        assert_eq!(
+            parse_windows_adapter("802.3|True|Realtek PCIe GbE Family Controller"),
+            NetworkAdapterType::Wired
+        );
+        // VPN・Hyper-Vの仮想スイッチは802.3を返す場合がある
+        assert_eq!(parse_windows_adapter("Unspecified|False|WireGuard Tunnel"), NetworkAdapterType::Virtual);
+        assert_eq!(
+            parse_windows_adapter("802.3|True|Hyper-V Virtual Ethernet Adapter"),
+            NetworkAdapterType::Virtual
+        );
+        assert_eq!(parse_windows_adapter(""), NetworkAdapterType::Unknown);
+    }
+
+    #[test]
+    fn test_classify_linux_interface() {
+        assert_eq!(classify_linux_interface("wlp2s0", true, true), NetworkAdapterType::Wireless);
+        assert_eq!(classify_linux_interface("enp3s0", false, true), NetworkAdapterType::Wired);
+        // 仮想インターフェースは /sys/class/net/<name>/device を持たない
+        assert_eq!(classify_linux_interface("tun0", false, false), NetworkAdapterType::Virtual);
+        assert_eq!(classify_linux_interface("docker0", false, false), NetworkAdapterType::Virtual);
+        assert_eq!(classify_linux_interface("wg0", false, true), NetworkAdapterType::Virtual);
+    }
+
+    #[test]
+    fn test_is_virtual_interface_name() {
+        assert!(is_virtual_interface_name("utun3"));
+        assert!(is_virtual_interface_name("tailscale0"));
+        assert!(is_virtual_interface_name("br-1a2b3c"));
+        assert!(!is_virtual_interface_name("en0"));
+        assert!(!is_virtual_interface_name("eth0"));
+        assert!(!is_virtual_interface_name("wlan0"));
+    }
+
+    #[test]
+    fn test_parse_macos_adapter_type() {
+        let route = "   route to: default\ndestination: default\n  gateway: 192.168.1.1\n  interface: en0\n";
+        assert_eq!(parse_macos_route_interface(route), Some("en0".to_string()));
+
+        let ports = "Hardware Port: Ethernet\nDevice: en1\nEthernet Address: aa\n\n\
+                     Hardware Port: Wi-Fi\nDevice: en0\nEthernet Address: bb\n";
+        assert_eq!(parse_macos_hardware_port(ports, "en0"), NetworkAdapterType::Wireless);
+        assert_eq!(parse_macos_hardware_port(ports, "en1"), NetworkAdapterType::Wired);
+        assert_eq!(parse_macos_hardware_port(ports, "en5"), NetworkAdapterType::Unknown);
+    }
+
     #[test]
     fn test_multiple_calls_calculate_speed() {
         // 最初の呼び出し（ベースライン確立）
-        let _ = get_network_metrics();
+        let _ = get_network_metrics(None);
 
         // 少し待機
         sleep(Duration::from_millis(150));
 
         // 2回目の呼び出しで速度が計算される
-        let result = get_network_metrics();
+        let result = get_network_metrics(None);
         assert!(result.is_ok());
     }
 
@@ -153,7 +599,68 @@ mod tests {
     fn test_get_network_interfaces() {
         let result = get_network_interfaces();
         assert!(result.is_ok());
-        // 少なくとも1つのインターフェースがあるはず（lo, eth0など）
-        // ただしCI環境では0の可能性もある
+        // CI環境ではインターフェースが0の可能性もあるが、ループバックは含まない
+        assert!(result.unwrap().iter().all(|info| !is_loopback(&info.name)));
+    }
+
+    fn rate(name: &str, upload: u64, is_up: bool) -> InterfaceRate {
+        InterfaceRate {
+            name: name.to_string(),
+            upload_bytes_per_sec: upload,
+            download_bytes_per_sec: upload * 2,
+            is_up,
+        }
+    }
+
+    #[test]
+    fn test_select_preferred_interface() {
+        let rates = vec![rate("eth0", 100, true), rate("tailscale0", 5_000, true), rate("wlan0", 0, false)];
+
+        // 速度に関係なく優先インターフェースを使う（リンクが無効でも指定を尊重する）
+        assert_eq!(select_interface(&rates, Some("eth0")).map(|r| r.name.as_str()), Some("eth0"));
+        assert_eq!(select_interface(&rates, Some("wlan0")).map(|r| r.name.as_str()), Some("wlan0"));
+    }
+
+    #[test]
+    fn test_select_falls_back_to_highest_upload() {
+        let rates = vec![
+            rate("eth0", 600_000, true),
+            rate("lo", 9_000_000, true),
+            rate("tailscale0", 1_200, true),
+            rate("wlan0", 800_000, false),
+        ];
+
+        // 未指定: ループバックとリンク無効を除いた最大のアップロード
+        assert_eq!(select_interface(&rates, None).map(|r| r.name.as_str()), Some("eth0"));
+        // 存在しないインターフェースを指定した場合も同じ
+        assert_eq!(select_interface(&rates, Some("eth1")).map(|r| r.name.as_str()), Some("eth0"));
+
+        // 同速なら名前順
+        let idle = vec![rate("wlan0", 0, true), rate("eth0", 0, true)];
+        assert_eq!(select_interface(&idle, None).map(|r| r.name.as_str()), Some("eth0"));
+
+        assert_eq!(select_interface(&[rate("lo", 100, true)], None), None);
+        assert_eq!(select_interface(&[], None), None);
+    }
+
+    #[test]
+    fn test_compute_rates_per_interface() {
+        let previous = HashMap::from([
+            ("eth0".to_string(), (1_000, 2_000)),
+            ("wlan0".to_string(), (5_000, 5_000)),
+        ]);
+        let current = vec![
+            ("wlan0".to_string(), 5_000, 5_000, true),
+            ("eth0".to_string(), 3_000, 8_000, true),
+            // 新しく現れたインターフェースは0から計測する
+            ("usb0".to_string(), 9_999, 9_999, true),
+        ];
+
+        let rates = compute_rates(&previous, &current, 2.0);
+        assert_eq!(
+            rates.iter().map(|r| (r.name.as_str(), r.upload_bytes_per_sec, r.download_bytes_per_sec)).collect::<Vec<_>>(),
+            vec![("eth0", 3_000, 1_000), ("usb0", 0, 0), ("wlan0", 0, 0)]
+        );
+        assert_eq!(rates[0].to_info().current_upload_bps, 24_000);
     }
 }
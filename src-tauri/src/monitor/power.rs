@@ -0,0 +1,308 @@
+// 電源状態の監視
+//
+// ノートPCのバッテリー駆動を検出する。
+// バッテリー駆動中はGPUクロック・CPUブーストが制限されるため、
+// 推奨設定を控えめにする判断に使用する。
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 配信開始時に警告するバッテリー残量（%）
+pub const LOW_BATTERY_THRESHOLD_PERCENT: u8 = 30;
+
+/// 電源の種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    /// AC電源（バッテリーのないデスクトップを含む）
+    Ac,
+    /// バッテリー駆動
+    Battery,
+    /// 判定できない
+    #[default]
+    Unknown,
+}
+
+/// 電源状態
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    /// 電源の種類
+    pub source: PowerSource,
+    /// バッテリー残量（%、バッテリーがない場合はNone）
+    pub battery_percent: Option<u8>,
+}
+
+impl PowerStatus {
+    /// バッテリー駆動中か
+    pub fn on_battery(&self) -> bool {
+        self.source == PowerSource::Battery
+    }
+
+    /// バッテリー駆動中かつ残量が少ないか
+    pub fn is_low_battery(&self) -> bool {
+        self.on_battery()
+            && self
+                .battery_percent
+                .is_some_and(|percent| percent < LOW_BATTERY_THRESHOLD_PERCENT)
+    }
+}
+
+/// `/sys/class/power_supply/*` の1エントリ
+#[derive(Debug, Clone, Default)]
+pub struct PowerSupplyEntry {
+    /// 種類（"Battery" / "Mains" / "USB" など）
+    pub kind: String,
+    /// 給電中か（Mains/USBのみ）
+    pub online: Option<bool>,
+    /// 状態（"Charging" / "Discharging" / "Full" など、Batteryのみ）
+    pub status: Option<String>,
+    /// 残量（%、Batteryのみ）
+    pub capacity: Option<u8>,
+}
+
+/// power_supplyの一覧から電源状態を判定
+///
+/// バッテリーがない場合はAC電源とみなす
+pub fn power_status_from_supplies(entries: &[PowerSupplyEntry]) -> PowerStatus {
+    let battery = entries.iter().find(|e| e.kind.eq_ignore_ascii_case("battery"));
+    let Some(battery) = battery else {
+        return PowerStatus {
+            source: if entries.is_empty() { PowerSource::Unknown } else { PowerSource::Ac },
+            battery_percent: None,
+        };
+    };
+
+    let adapter_online = entries
+        .iter()
+        .filter(|e| !e.kind.eq_ignore_ascii_case("battery"))
+        .any(|e| e.online == Some(true));
+    let discharging = battery
+        .status
+        .as_deref()
+        .is_some_and(|status| status.trim().eq_ignore_ascii_case("discharging"));
+
+    PowerStatus {
+        source: if adapter_online || !discharging { PowerSource::Ac } else { PowerSource::Battery },
+        battery_percent: battery.capacity.map(|c| c.min(100)),
+    }
+}
+
+/// `Win32_Battery` の `BatteryStatus,EstimatedChargeRemaining` を解析
+///
+/// 出力が空の場合（バッテリーなし）はAC電源とみなす。
+/// BatteryStatus: 1=放電中, 4=残量低下, 5=残量危険, それ以外は給電中
+pub fn parse_windows_battery(output: &str) -> PowerStatus {
+    let line = output.lines().map(str::trim).find(|line| !line.is_empty());
+    let Some(line) = line else {
+        return PowerStatus {
+            source: PowerSource::Ac,
+            battery_percent: None,
+        };
+    };
+
+    let mut fields = line.split(',').map(str::trim);
+    let status = fields.next().and_then(|s| s.parse::<u16>().ok());
+    let percent = fields.next().and_then(|s| s.parse::<u8>().ok());
+
+    PowerStatus {
+        source: match status {
+            Some(1 | 4 | 5) => PowerSource::Battery,
+            Some(_) => PowerSource::Ac,
+            None => PowerSource::Unknown,
+        },
+        battery_percent: percent.map(|p| p.min(100)),
+    }
+}
+
+/// `pmset -g batt` の出力を解析（macOS）
+pub fn parse_pmset_output(output: &str) -> PowerStatus {
+    let source = if output.contains("'Battery Power'") {
+        PowerSource::Battery
+    } else if output.contains("'AC Power'") {
+        PowerSource::Ac
+    } else {
+        PowerSource::Unknown
+    };
+
+    let battery_percent = output
+        .split_whitespace()
+        .find_map(|token| token.trim_end_matches(';').strip_suffix('%'))
+        .and_then(|percent| percent.parse::<u8>().ok());
+
+    PowerStatus {
+        source,
+        battery_percent,
+    }
+}
+
+/// 現在の電源状態をOSから読み取る
+#[cfg(target_os = "linux")]
+fn read_power_status() -> PowerStatus {
+    let Ok(dir) = std::fs::read_dir("/sys/class/power_supply") else {
+        return PowerStatus::default();
+    };
+
+    let read = |path: &std::path::Path, name: &str| {
+        std::fs::read_to_string(path.join(name)).ok().map(|s| s.trim().to_string())
+    };
+    let entries: Vec<PowerSupplyEntry> = dir
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            PowerSupplyEntry {
+                kind: read(&path, "type").unwrap_or_default(),
+                online: read(&path, "online").map(|v| v == "1"),
+                status: read(&path, "status"),
+                capacity: read(&path, "capacity").and_then(|v| v.parse().ok()),
+            }
+        })
+        .collect();
+
+    power_status_from_supplies(&entries)
+}
+
+/// 現在の電源状態をOSから読み取る（powershellを実行する）
+#[cfg(target_os = "windows")]
+fn read_power_status() -> PowerStatus {
+    let script = "Get-CimInstance Win32_Battery | Select-Object -First 1 | \
+                  ForEach-Object { \"$($_.BatteryStatus),$($_.EstimatedChargeRemaining)\" }";
+    match std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+    {
+        Ok(output) if output.status.success() => parse_windows_battery(&String::from_utf8_lossy(&output.stdout)),
+        _ => PowerStatus::default(),
+    }
+}
+
+/// 現在の電源状態をOSから読み取る（pmsetを実行する）
+#[cfg(target_os = "macos")]
+fn read_power_status() -> PowerStatus {
+    match std::process::Command::new("pmset").args(["-g", "batt"]).output() {
+        Ok(output) if output.status.success() => parse_pmset_output(&String::from_utf8_lossy(&output.stdout)),
+        _ => PowerStatus::default(),
+    }
+}
+
+/// 現在の電源状態をOSから読み取る（未対応OS）
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn read_power_status() -> PowerStatus {
+    PowerStatus::default()
+}
+
+/// 電源状態の再取得間隔
+///
+/// Windows・macOSでは外部コマンド（powershell / pmset）を実行するため、呼び出しのたびには取得しない
+const POWER_STATUS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 取得済みの電源状態と取得時刻
+static POWER_STATUS_CACHE: Lazy<Mutex<Option<(Instant, PowerStatus)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 直近に取得した電源状態（未取得の場合は判定できない状態）
+///
+/// 外部コマンドを実行しないため、同期処理から参照する場合に使う
+pub fn cached_power_status() -> PowerStatus {
+    POWER_STATUS_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .map(|(_, status)| status)
+        .unwrap_or_default()
+}
+
+/// 現在の電源状態を取得（一定時間キャッシュする）
+///
+/// 外部コマンドの実行はブロッキング処理用のスレッドで行い、非同期ランタイムを止めない
+pub async fn get_power_status() -> PowerStatus {
+    let cached = *POWER_STATUS_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((read_at, status)) = cached {
+        if read_at.elapsed() < POWER_STATUS_CACHE_TTL {
+            return status;
+        }
+    }
+
+    let status = tokio::task::spawn_blocking(read_power_status)
+        .await
+        .unwrap_or_default();
+    *POWER_STATUS_CACHE.lock().unwrap_or_else(PoisonError::into_inner) = Some((Instant::now(), status));
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supply(kind: &str, online: Option<bool>, status: Option<&str>, capacity: Option<u8>) -> PowerSupplyEntry {
+        PowerSupplyEntry {
+            kind: kind.to_string(),
+            online,
+            status: status.map(ToString::to_string),
+            capacity,
+        }
+    }
+
+    #[test]
+    fn test_linux_laptop_on_battery() {
+        let status = power_status_from_supplies(&[
+            supply("Mains", Some(false), None, None),
+            supply("Battery", None, Some("Discharging"), Some(25)),
+        ]);
+        assert_eq!(status.source, PowerSource::Battery);
+        assert_eq!(status.battery_percent, Some(25));
+        assert!(status.is_low_battery());
+    }
+
+    #[test]
+    fn test_linux_laptop_on_ac() {
+        let status = power_status_from_supplies(&[
+            supply("Mains", Some(true), None, None),
+            supply("Battery", None, Some("Charging"), Some(80)),
+        ]);
+        assert_eq!(status.source, PowerSource::Ac);
+        assert!(!status.on_battery());
+    }
+
+    #[test]
+    fn test_linux_desktop_without_battery() {
+        let status = power_status_from_supplies(&[supply("Mains", Some(true), None, None)]);
+        assert_eq!(status.source, PowerSource::Ac);
+        assert_eq!(status.battery_percent, None);
+
+        assert_eq!(power_status_from_supplies(&[]).source, PowerSource::Unknown);
+    }
+
+    #[test]
+    fn test_parse_windows_battery() {
+        assert_eq!(
+            parse_windows_battery("1,45\r\n"),
+            PowerStatus { source: PowerSource::Battery, battery_percent: Some(45) }
+        );
+        assert_eq!(parse_windows_battery("2,100").source, PowerSource::Ac);
+        // バッテリーなし（デスクトップ）
+        assert_eq!(parse_windows_battery("").source, PowerSource::Ac);
+    }
+
+    #[test]
+    fn test_parse_pmset_output() {
+        let output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t28%; discharging; 2:10 remaining present: true";
+        let status = parse_pmset_output(output);
+        assert_eq!(status.source, PowerSource::Battery);
+        assert_eq!(status.battery_percent, Some(28));
+
+        assert_eq!(parse_pmset_output("Now drawing from 'AC Power'").source, PowerSource::Ac);
+    }
+
+    #[test]
+    fn test_low_battery_threshold() {
+        let at_threshold = PowerStatus {
+            source: PowerSource::Battery,
+            battery_percent: Some(LOW_BATTERY_THRESHOLD_PERCENT),
+        };
+        assert!(!at_threshold.is_low_battery());
+
+        let on_ac = PowerStatus { source: PowerSource::Ac, battery_percent: Some(10) };
+        assert!(!on_ac.is_low_battery());
+    }
+}
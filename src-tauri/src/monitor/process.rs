@@ -2,7 +2,7 @@
 //
 // OBSプロセスのリソース使用状況を監視
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
@@ -34,6 +34,76 @@ pub struct ObsProcessMetrics {
     pub total_cpu_usage: f32,
     /// 合計メモリ使用量（バイト）
     pub total_memory_bytes: u64,
+    /// メインプロセスのCPU優先度
+    ///
+    /// 取得にOSコマンドの実行を伴う場合があるため、定期取得用の
+    /// `get_obs_process_metrics` では `None`。`get_obs_process_info` でのみ設定される
+    pub priority: Option<ProcessPriority>,
+}
+
+/// プロセスのCPU優先度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessPriority {
+    /// 高（ゲームよりOBSを優先）
+    High,
+    /// 通常以上（配信中の推奨値）
+    AboveNormal,
+    /// 通常
+    #[default]
+    Normal,
+    /// 通常以下
+    BelowNormal,
+}
+
+impl ProcessPriority {
+    /// Linux/Unixのnice値に変換（小さいほど高優先度）
+    pub const fn nice_value(self) -> i32 {
+        match self {
+            Self::High => -10,
+            Self::AboveNormal => -5,
+            Self::Normal => 0,
+            Self::BelowNormal => 10,
+        }
+    }
+
+    /// nice値から優先度を判定
+    pub const fn from_nice_value(nice: i32) -> Self {
+        match nice {
+            i32::MIN..=-10 => Self::High,
+            -9..=-1 => Self::AboveNormal,
+            0..=4 => Self::Normal,
+            _ => Self::BelowNormal,
+        }
+    }
+
+    /// Windowsの優先度クラス名（`SetPriorityClass` / .NET `ProcessPriorityClass`）
+    pub const fn windows_priority_class(self) -> &'static str {
+        match self {
+            Self::High => "High",
+            Self::AboveNormal => "AboveNormal",
+            Self::Normal => "Normal",
+            Self::BelowNormal => "BelowNormal",
+        }
+    }
+
+    /// Windowsの優先度クラス名から優先度を判定
+    ///
+    /// `RealTime` は `High`、`Idle` は `BelowNormal` として扱う
+    pub fn from_windows_priority_class(class: &str) -> Option<Self> {
+        match class.trim() {
+            "High" | "RealTime" => Some(Self::High),
+            "AboveNormal" => Some(Self::AboveNormal),
+            "Normal" => Some(Self::Normal),
+            "BelowNormal" | "Idle" => Some(Self::BelowNormal),
+            _ => None,
+        }
+    }
+
+    /// 通常より高い優先度が必要か（Linuxでは `CAP_SYS_NICE` が必要）
+    pub const fn requires_elevation(self) -> bool {
+        self.nice_value() < 0
+    }
 }
 
 // プロセス監視用のSystemインスタンス
@@ -50,10 +120,27 @@ const OBS_PROCESS_NAMES: &[&str] = &[
     "obs-studio",
 ];
 
-/// プロセス名がOBSかどうかを判定
+// OBSの補助プロセス（ブラウザソース・録画の多重化）。合計使用量にのみ含める
+const OBS_HELPER_PROCESS_NAMES: &[&str] = &[
+    "obs-browser-page.exe",
+    "obs-browser-page",
+    "obs-ffmpeg-mux.exe",
+    "obs-ffmpeg-mux",
+];
+
+/// プロセス名がOBSのメインプロセスかどうかを判定
+///
+/// 部分一致では `jobsvc.exe` のような無関係なプロセスの優先度を変更してしまうため、
+/// 大文字小文字を区別せずプロセス名全体で比較する
 fn is_obs_process(name: &str) -> bool {
-    let lower_name = name.to_lowercase();
-    OBS_PROCESS_NAMES.iter().any(|pattern| lower_name.contains(pattern))
+    let lower_name = name.trim().to_lowercase();
+    OBS_PROCESS_NAMES.iter().any(|pattern| lower_name == *pattern)
+}
+
+/// プロセス名がOBSの補助プロセスかどうかを判定
+fn is_obs_helper_process(name: &str) -> bool {
+    let lower_name = name.trim().to_lowercase();
+    OBS_HELPER_PROCESS_NAMES.iter().any(|pattern| lower_name == *pattern)
 }
 
 /// 指定プロセス名のメトリクスを取得（将来使用予定）
@@ -65,7 +152,7 @@ pub fn get_process_by_name(process_name: &str) -> Result<Option<ProcessMetrics>,
     sys.refresh_processes();
 
     for (pid, process) in sys.processes() {
-        if process.name().to_lowercase().contains(&process_name.to_lowercase()) {
+        if process.name().eq_ignore_ascii_case(process_name) {
             return Ok(Some(ProcessMetrics {
                 name: process.name().to_string(),
                 pid: pid.as_u32(),
@@ -93,7 +180,10 @@ pub fn get_obs_process_metrics() -> Result<ObsProcessMetrics, AppError> {
     for (pid, process) in sys.processes() {
         let name = process.name().to_string();
 
-        if is_obs_process(&name) {
+        if is_obs_helper_process(&name) {
+            total_cpu += process.cpu_usage();
+            total_memory = total_memory.saturating_add(process.memory());
+        } else if is_obs_process(&name) {
             let cpu = process.cpu_usage();
             let memory = process.memory();
 
@@ -118,9 +208,235 @@ pub fn get_obs_process_metrics() -> Result<ObsProcessMetrics, AppError> {
         main_process,
         total_cpu_usage: total_cpu,
         total_memory_bytes: total_memory,
+        priority: None,
     })
 }
 
+/// OBSプロセスの情報を優先度付きで取得
+pub fn get_obs_process_info() -> Result<ObsProcessMetrics, AppError> {
+    let mut metrics = get_obs_process_metrics()?;
+    metrics.priority = metrics
+        .main_process
+        .as_ref()
+        .and_then(|process| get_process_priority(process.pid));
+    Ok(metrics)
+}
+
+/// OBSメインプロセスのCPU優先度を変更
+///
+/// Windowsでは `SetPriorityClass`（PowerShell経由）、
+/// Linuxでは `setpriority(2)`（renice経由）を使用する。
+/// Linuxで通常より高い優先度に設定するには `CAP_SYS_NICE` が必要。
+///
+/// # Errors
+/// - OBSが起動していない場合は `OBS_PROCESS_NOT_FOUND`
+/// - 権限不足・コマンド失敗の場合は `PROCESS_PRIORITY`
+pub fn set_obs_process_priority(priority: ProcessPriority) -> Result<(), AppError> {
+    let pid = get_obs_process_metrics()?
+        .main_process
+        .map(|process| process.pid)
+        .ok_or_else(AppError::obs_process_not_found)?;
+
+    apply_process_priority(pid, priority)?;
+
+    tracing::info!(
+        target: "process",
+        pid = pid,
+        priority = ?priority,
+        "OBSプロセスの優先度を変更しました"
+    );
+
+    Ok(())
+}
+
+/// 指定プロセスのCPU優先度を取得
+#[cfg(target_os = "linux")]
+pub fn get_process_priority(pid: u32) -> Option<ProcessPriority> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    parse_nice_from_stat(&stat).map(ProcessPriority::from_nice_value)
+}
+
+/// 指定プロセスのCPU優先度を取得
+#[cfg(target_os = "windows")]
+pub fn get_process_priority(pid: u32) -> Option<ProcessPriority> {
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", &format!("(Get-Process -Id {pid}).PriorityClass")])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    ProcessPriority::from_windows_priority_class(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 指定プロセスのCPU優先度を取得（未対応OS）
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn get_process_priority(_pid: u32) -> Option<ProcessPriority> {
+    None
+}
+
+/// /proc/<pid>/stat からnice値（19番目のフィールド）を取り出す
+///
+/// プロセス名（2番目のフィールド）に空白や括弧が含まれる場合があるため、
+/// 最後の `)` 以降を分割する
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_nice_from_stat(stat: &str) -> Option<i32> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    // rest は3番目のフィールド（state）から始まる
+    rest.split_whitespace().nth(16)?.parse().ok()
+}
+
+/// 指定プロセスのCPU優先度を変更（Windows）
+#[cfg(target_os = "windows")]
+fn apply_process_priority(pid: u32, priority: ProcessPriority) -> Result<(), AppError> {
+    let script = format!(
+        "(Get-Process -Id {pid}).PriorityClass = '{}'",
+        priority.windows_priority_class()
+    );
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()
+        .map_err(|e| AppError::process_priority(&format!("優先度変更コマンドの実行に失敗: {e}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AppError::process_priority(&format!(
+            "OBSプロセスの優先度変更に失敗しました: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// 指定プロセスのCPU優先度を変更（Linux/Unix）
+#[cfg(unix)]
+fn apply_process_priority(pid: u32, priority: ProcessPriority) -> Result<(), AppError> {
+    if priority.requires_elevation() && !has_sys_nice_capability() {
+        return Err(AppError::process_priority(
+            "優先度を上げるにはCAP_SYS_NICE権限が必要です（例: sudo setcap cap_sys_nice+ep <実行ファイル>）",
+        ));
+    }
+
+    let output = std::process::Command::new("renice")
+        .args(["-n", &priority.nice_value().to_string(), "-p", &pid.to_string()])
+        .output()
+        .map_err(|e| AppError::process_priority(&format!("reniceの実行に失敗: {e}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AppError::process_priority(&format!(
+            "OBSプロセスの優先度変更に失敗しました: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// 指定プロセスのCPU優先度を変更（未対応OS）
+#[cfg(not(any(unix, target_os = "windows")))]
+fn apply_process_priority(_pid: u32, _priority: ProcessPriority) -> Result<(), AppError> {
+    Err(AppError::process_priority("このOSでは優先度の変更に対応していません"))
+}
+
+/// 自プロセスが `CAP_SYS_NICE` を持っているか確認
+#[cfg(target_os = "linux")]
+fn has_sys_nice_capability() -> bool {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .as_deref()
+        .and_then(parse_cap_eff)
+        .is_some_and(|caps| caps & (1 << CAP_SYS_NICE) != 0)
+}
+
+/// 自プロセスが優先度を上げられるか確認（Linux以外のUnixは実行時の判定に委ねる）
+#[cfg(all(unix, not(target_os = "linux")))]
+const fn has_sys_nice_capability() -> bool {
+    true
+}
+
+/// `CAP_SYS_NICE` のケーパビリティ番号
+#[cfg(target_os = "linux")]
+const CAP_SYS_NICE: u32 = 23;
+
+/// /proc/self/status の CapEff（有効ケーパビリティ）を取り出す
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cap_eff(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|value| u64::from_str_radix(value.trim(), 16).ok())
+}
+
+/// 既知のゲーム実行ファイル名（設定の初期値）
+///
+/// プロセス名全体で照合するため、`javaw.exe` のように多くのアプリが共有するランタイムは含めない
+pub const DEFAULT_GAME_EXECUTABLES: &[&str] = &[
+    // ランチャー
+    "steam.exe",
+    "EpicGamesLauncher.exe",
+    "Battle.net.exe",
+    "RiotClientServices.exe",
+    // 人気タイトル
+    "Minecraft.exe",
+    "VALORANT-Win64-Shipping.exe",
+    "FortniteClient-Win64-Shipping.exe",
+    "r5apex.exe",
+    "cs2.exe",
+    "LeagueClient.exe",
+    "League of Legends.exe",
+    "Overwatch.exe",
+    "GenshinImpact.exe",
+    "StreetFighter6.exe",
+    "eldenring.exe",
+];
+
+/// 実行中のプロセス名一覧を取得
+pub(crate) fn running_process_names() -> Result<Vec<String>, AppError> {
+    let mut sys = PROCESS_SYSTEM.lock()
+        .map_err(|e| AppError::system_monitor(&format!("Failed to lock process system: {e}")))?;
+
+    sys.refresh_processes();
+
+    Ok(sys.processes().values().map(|p| p.name().to_string()).collect())
+}
+
+/// プロセス名一覧から既知のゲームを抽出
+///
+/// 大文字小文字を区別せず比較し、Linux/macOS向けに拡張子なしの名前にも一致させる。
+/// 同名プロセスが複数ある場合は1件にまとめ、名前順で返す。
+pub fn match_game_processes<S: AsRef<str>>(process_names: &[S], known_games: &[String]) -> Vec<String> {
+    let normalize = |name: &str| {
+        let lower = name.trim().to_lowercase();
+        lower.strip_suffix(".exe").map(ToString::to_string).unwrap_or(lower)
+    };
+    let known: Vec<String> = known_games.iter().map(|g| normalize(g)).collect();
+
+    let mut games: Vec<String> = process_names
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|name| known.contains(&normalize(name)))
+        .map(ToString::to_string)
+        .collect();
+    games.sort();
+    games.dedup();
+    games
+}
+
+/// 実行中の既知ゲームを検出（初期値のゲーム一覧を使用）
+pub fn detect_running_games() -> Result<Vec<String>, AppError> {
+    let known: Vec<String> = DEFAULT_GAME_EXECUTABLES.iter().map(ToString::to_string).collect();
+    detect_running_games_from(&known)
+}
+
+/// 指定したゲーム一覧の中から実行中のものを検出
+///
+/// # Arguments
+/// * `known_games` - 既知のゲーム実行ファイル名
+pub fn detect_running_games_from(known_games: &[String]) -> Result<Vec<String>, AppError> {
+    let process_names = running_process_names()?;
+    Ok(match_game_processes(&process_names, known_games))
+}
+
 /// 全プロセスの中からCPU使用率上位N件を取得
 #[allow(dead_code)]
 pub fn get_top_processes_by_cpu(limit: usize) -> Result<Vec<ProcessMetrics>, AppError> {
@@ -159,6 +475,12 @@ mod tests {
         assert!(is_obs_process("obs-studio"));
         assert!(!is_obs_process("chrome.exe"));
         assert!(!is_obs_process("notepad"));
+        // 名前の一部に "obs" を含むだけのプロセスは対象外
+        assert!(!is_obs_process("jobsvc.exe"));
+        // 補助プロセスはメインプロセスとして扱わない
+        assert!(!is_obs_process("obs-browser-page.exe"));
+        assert!(is_obs_helper_process("obs-browser-page.exe"));
+        assert!(!is_obs_helper_process("jobsvc.exe"));
     }
 
     #[test]
@@ -186,6 +508,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_priority_nice_value_mapping() {
+        let priorities = [
+            ProcessPriority::High,
+            ProcessPriority::AboveNormal,
+            ProcessPriority::Normal,
+            ProcessPriority::BelowNormal,
+        ];
+
+        for priority in priorities {
+            assert_eq!(ProcessPriority::from_nice_value(priority.nice_value()), priority);
+        }
+        assert_eq!(ProcessPriority::from_nice_value(19), ProcessPriority::BelowNormal);
+        assert_eq!(ProcessPriority::from_nice_value(-20), ProcessPriority::High);
+    }
+
+    #[test]
+    fn test_priority_windows_class_mapping() {
+        for priority in [
+            ProcessPriority::High,
+            ProcessPriority::AboveNormal,
+            ProcessPriority::Normal,
+            ProcessPriority::BelowNormal,
+        ] {
+            assert_eq!(
+                ProcessPriority::from_windows_priority_class(priority.windows_priority_class()),
+                Some(priority)
+            );
+        }
+        assert_eq!(ProcessPriority::from_windows_priority_class("RealTime\r\n"), Some(ProcessPriority::High));
+        assert_eq!(ProcessPriority::from_windows_priority_class("unknown"), None);
+    }
+
+    #[test]
+    fn test_priority_requires_elevation() {
+        assert!(ProcessPriority::High.requires_elevation());
+        assert!(ProcessPriority::AboveNormal.requires_elevation());
+        assert!(!ProcessPriority::Normal.requires_elevation());
+        assert!(!ProcessPriority::BelowNormal.requires_elevation());
+    }
+
+    #[test]
+    fn test_priority_serialization() {
+        assert_eq!(serde_json::to_string(&ProcessPriority::AboveNormal).unwrap(), "\"aboveNormal\"");
+        assert_eq!(ProcessPriority::default(), ProcessPriority::Normal);
+    }
+
+    #[test]
+    fn test_parse_nice_from_stat() {
+        let stat = "1234 (obs (studio)) S 1 1234 1234 0 -1 4194560 100 0 0 0 10 5 0 0 20 -5 8 0 100 0 0";
+        assert_eq!(parse_nice_from_stat(stat), Some(-5));
+        assert_eq!(parse_nice_from_stat("invalid"), None);
+    }
+
+    #[test]
+    fn test_parse_cap_eff() {
+        let status = "Name:\tobs\nCapInh:\t0000000000000000\nCapEff:\t0000000000800000\n";
+        let caps = parse_cap_eff(status).unwrap();
+        assert_ne!(caps & (1 << 23), 0, "CAP_SYS_NICEビット");
+        assert_eq!(parse_cap_eff("Name:\tobs\n"), None);
+    }
+
+    #[test]
+    fn test_set_priority_when_obs_not_running() {
+        // OBSが起動していないテスト環境ではOBS_PROCESS_NOT_FOUNDとなる
+        if get_obs_process_metrics().unwrap().main_process.is_some() {
+            return;
+        }
+
+        let err = set_obs_process_priority(ProcessPriority::AboveNormal).unwrap_err();
+        assert_eq!(err.code(), "OBS_PROCESS_NOT_FOUND");
+    }
+
+    fn known_games() -> Vec<String> {
+        DEFAULT_GAME_EXECUTABLES.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_match_game_processes_detects_known_games() {
+        let processes = ["explorer.exe", "Minecraft.exe", "obs64.exe", "cs2.exe", "chrome.exe"];
+        let games = match_game_processes(&processes, &known_games());
+
+        assert_eq!(games, vec!["Minecraft.exe".to_string(), "cs2.exe".to_string()]);
+    }
+
+    #[test]
+    fn test_match_game_processes_requires_exact_name() {
+        // 名前の一部が一致するだけのプロセスや、汎用ランタイムはゲームとみなさない
+        let processes = ["cs2helper.exe", "steamwebhelper.exe", "javaw.exe", "MinecraftLauncher.exe"];
+        assert!(match_game_processes(&processes, &known_games()).is_empty());
+    }
+
+    #[test]
+    fn test_match_game_processes_case_insensitive_and_no_extension() {
+        // Linux/Proton環境では拡張子なし・小文字のプロセス名になることがある
+        let processes = ["STEAM.EXE", "minecraft", "cs2"];
+        let games = match_game_processes(&processes, &known_games());
+
+        assert_eq!(games.len(), 3);
+    }
+
+    #[test]
+    fn test_match_game_processes_dedup_and_empty() {
+        let processes = ["cs2.exe", "cs2.exe"];
+        assert_eq!(match_game_processes(&processes, &known_games()).len(), 1);
+
+        let none: [&str; 0] = [];
+        assert!(match_game_processes(&none, &known_games()).is_empty());
+        assert!(match_game_processes(&processes, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_detect_running_games_returns_ok() {
+        assert!(detect_running_games().is_ok());
+    }
+
     #[test]
     fn test_get_process_by_name_nonexistent() {
         let result = get_process_by_name("nonexistent_process_12345");
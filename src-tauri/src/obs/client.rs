@@ -4,13 +4,18 @@
 
 use obws::client::ConnectConfig;
 use obws::Client;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
-use crate::error::AppError;
+use crate::error::{AppError, ERROR_CODE_OBS_NOT_CONNECTED};
 use super::error::ObsResult;
-use super::types::{ConnectionConfig as AppConnectionConfig, ConnectionState, ObsStatus, ReconnectConfig};
+use super::types::{
+    AudioMonitorType, ConnectionConfig as AppConnectionConfig, ConnectionState, ObsStatus, PingResult,
+    ReconnectConfig, SceneItem,
+};
 
 /// ビットレート計算用の統計情報
 #[derive(Debug, Clone)]
@@ -28,6 +33,63 @@ struct BitrateStats {
 /// 最小サンプリング間隔（秒）- ノイズ防止のため
 const MIN_BITRATE_SAMPLE_INTERVAL_SECS: f64 = 0.1;
 
+/// 配信用の出力の種類（`GetOutputList` の `outputKind`）
+const STREAM_OUTPUT_KINDS: &[&str] = &["rtmp_output", "ffmpeg_mpegts_muxer", "whip_output"];
+
+/// ヘルスチェック（ping）がこの回数連続で応答しなかった場合に `Degraded` とする
+pub const DEGRADED_AFTER_PING_FAILURES: u32 = 2;
+
+/// ヘルスチェック（ping）がこの回数連続で応答しなかった場合は接続が切れたものとみなす
+pub const DISCONNECT_AFTER_PING_FAILURES: u32 = 4;
+
+/// OBSへのリクエストのタイムアウトの既定値（`AppConfig` の `connection_timeout_secs` の初期値と同じ）
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// OBSへのリクエストにタイムアウトを設定して実行
+///
+/// OBS側でモーダルダイアログが開いている場合などは応答が返らず、
+/// obwsの呼び出しが無期限に待ち続けるため、`timeout` を過ぎたら `OBS_TIMEOUT` エラーで打ち切る
+async fn with_timeout<T, E>(timeout: Duration, request: impl Future<Output = Result<T, E>>) -> ObsResult<T>
+where
+    AppError: From<E>,
+{
+    match tokio::time::timeout(timeout, request).await {
+        Ok(result) => result.map_err(AppError::from),
+        Err(_) => Err(AppError::obs_timeout(&format!(
+            "OBSから{}秒以内に応答がありませんでした",
+            timeout.as_secs()
+        ))),
+    }
+}
+
+/// OBSとの接続が切れたら複数ステップの処理を中断するためのトークン
+///
+/// 作成時点の接続の世代を保持し、切断・再接続で世代が変わると中断扱いになる。
+/// 設定の一括適用やバックアップなど、複数のリクエストを順に送る処理のステップ間で `check` を呼ぶ。
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    generation: Arc<AtomicU64>,
+    issued_generation: u64,
+}
+
+impl CancellationToken {
+    /// 作成後に接続が切れた（または接続し直された）か
+    pub fn is_cancelled(&self) -> bool {
+        self.generation.load(Ordering::SeqCst) != self.issued_generation
+    }
+
+    /// 中断されていればエラーを返す
+    pub fn check(&self) -> ObsResult<()> {
+        if self.is_cancelled() {
+            return Err(AppError::new(
+                ERROR_CODE_OBS_NOT_CONNECTED,
+                "OBSとの接続が切れたため処理を中断しました",
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl BitrateStats {
     /// ストリームビットレートを差分計算 (kbps)
     ///
@@ -87,6 +149,14 @@ struct ObsClientInner {
     reconnect_attempts: u32,
     /// ビットレート計算用統計
     bitrate_stats: BitrateStats,
+    /// 直近のヘルスチェック（ping）の応答時間（ミリ秒）
+    last_ping_latency_ms: Option<u64>,
+    /// 直近にヘルスチェック（ping）が成功した日時（UNIX epoch秒）
+    last_ping_success_at: Option<i64>,
+    /// ヘルスチェック（ping）が連続で応答しなかった回数
+    consecutive_ping_failures: u32,
+    /// OBSへの各リクエストのタイムアウト
+    request_timeout: Duration,
 }
 
 impl ObsClientInner {
@@ -98,6 +168,10 @@ impl ObsClientInner {
             connection_state: ConnectionState::Disconnected,
             reconnect_attempts: 0,
             bitrate_stats: BitrateStats::default(),
+            last_ping_latency_ms: None,
+            last_ping_success_at: None,
+            consecutive_ping_failures: 0,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
 }
@@ -108,6 +182,10 @@ impl ObsClientInner {
 #[derive(Clone)]
 pub struct ObsClient {
     inner: Arc<RwLock<ObsClientInner>>,
+    /// 接続の世代（接続・切断のたびに進める）
+    ///
+    /// 応答待ちのリクエストがロックを保持していても確認できるよう、内部状態とは別に持つ
+    generation: Arc<AtomicU64>,
 }
 
 impl Default for ObsClient {
@@ -121,9 +199,29 @@ impl ObsClient {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(ObsClientInner::new())),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// OBSへの各リクエストのタイムアウトを設定
+    pub async fn set_request_timeout(&self, timeout: Duration) {
+        let mut inner = self.inner.write().await;
+        inner.request_timeout = timeout;
+    }
+
+    /// 現在の接続に対するキャンセルトークンを作成
+    pub fn cancellation_token(&self) -> CancellationToken {
+        CancellationToken {
+            generation: Arc::clone(&self.generation),
+            issued_generation: self.generation.load(Ordering::SeqCst),
         }
     }
 
+    /// 接続の世代を進め、発行済みのキャンセルトークンを中断扱いにする
+    fn advance_generation(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
     /// 再接続設定を更新（将来使用予定）
     #[allow(dead_code)]
     pub async fn set_reconnect_config(&self, config: ReconnectConfig) {
@@ -147,15 +245,16 @@ impl ObsClient {
             } else {
                 msg
             };
-            AppError::obs_connection(&msg)
+            AppError::validation_failed(&msg)
         })?;
 
         // 状態を接続中に更新
-        {
+        let request_timeout = {
             let mut inner = self.inner.write().await;
             inner.connection_state = ConnectionState::Connecting;
             inner.config = Some(config.clone());
-        }
+            inner.request_timeout
+        };
 
         // obws ConnectConfigを構築
         let connect_config = ConnectConfig {
@@ -164,7 +263,7 @@ impl ObsClient {
             password: config.password.clone(),
             event_subscriptions: None,
             broadcast_capacity: obws::client::DEFAULT_BROADCAST_CAPACITY,
-            connect_timeout: obws::client::DEFAULT_CONNECT_TIMEOUT,
+            connect_timeout: request_timeout,
             dangerous: None,
         };
 
@@ -178,6 +277,9 @@ impl ObsClient {
                 inner.connection_state = ConnectionState::Connected;
                 inner.reconnect_attempts = 0;
                 inner.bitrate_stats.reset(); // 新規接続時は統計をリセット
+                inner.last_ping_success_at = None;
+                inner.consecutive_ping_failures = 0;
+                self.advance_generation();
                 Ok(())
             }
             Err(e) => {
@@ -197,14 +299,76 @@ impl ObsClient {
         inner.connection_state = ConnectionState::Disconnected;
         inner.reconnect_attempts = 0;
         inner.bitrate_stats.reset(); // 統計もリセット
+        inner.last_ping_latency_ms = None;
+        inner.last_ping_success_at = None;
+        inner.consecutive_ping_failures = 0;
+        self.advance_generation();
 
         Ok(())
     }
 
-    /// 接続されているかどうかを確認
+    /// 接続のヘルスチェック（ping）を実行
+    ///
+    /// `GetVersion`リクエストの往復時間を計測する。
+    /// リクエストが失敗した場合は接続が切れたものとみなし、状態を`Disconnected`に更新する。
+    /// タイムアウトの場合は `OBS_TIMEOUT` を返して接続を維持するが、
+    /// 2回連続で `Degraded`、4回連続で `Disconnected` とする（成功すると `Connected` に戻る）。
+    pub async fn ping(&self) -> ObsResult<PingResult> {
+        let result = {
+            let inner = self.inner.read().await;
+            let client = inner.client.as_ref().ok_or_else(|| {
+                AppError::obs_not_connected()
+            })?;
+
+            let started = Instant::now();
+            let version = with_timeout(inner.request_timeout, client.general().version()).await;
+            version.map(|v| (v, started.elapsed()))
+        };
+
+        let mut inner = self.inner.write().await;
+        match result {
+            Ok((version, elapsed)) => {
+                let latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+                inner.last_ping_latency_ms = Some(latency_ms);
+                inner.last_ping_success_at = Some(chrono::Utc::now().timestamp());
+                inner.consecutive_ping_failures = 0;
+                if inner.connection_state == ConnectionState::Degraded {
+                    inner.connection_state = ConnectionState::Connected;
+                }
+                Ok(PingResult {
+                    latency_ms,
+                    obs_version: version.obs_version.to_string(),
+                    websocket_version: version.obs_web_socket_version.to_string(),
+                })
+            }
+            // 応答がないだけ（OBSでダイアログが開いている等）の場合は、連続で応答がなくなるまで接続を維持する
+            Err(e)
+                if e.code() == super::error::error_codes::OBS_TIMEOUT
+                    && inner.consecutive_ping_failures.saturating_add(1) < DISCONNECT_AFTER_PING_FAILURES =>
+            {
+                inner.consecutive_ping_failures = inner.consecutive_ping_failures.saturating_add(1);
+                if inner.consecutive_ping_failures >= DEGRADED_AFTER_PING_FAILURES {
+                    inner.connection_state = ConnectionState::Degraded;
+                }
+                Err(e)
+            }
+            Err(e) => {
+                inner.client = None;
+                inner.connection_state = ConnectionState::Disconnected;
+                inner.bitrate_stats.reset();
+                inner.last_ping_latency_ms = None;
+                inner.consecutive_ping_failures = 0;
+                self.advance_generation();
+                Err(e)
+            }
+        }
+    }
+
+    /// 接続されているかどうかを確認（応答が不安定な `Degraded` も接続中とみなす）
     pub async fn is_connected(&self) -> bool {
         let inner = self.inner.read().await;
-        inner.client.is_some() && inner.connection_state == ConnectionState::Connected
+        inner.client.is_some()
+            && matches!(inner.connection_state, ConnectionState::Connected | ConnectionState::Degraded)
     }
 
     /// 現在の接続状態を取得
@@ -221,18 +385,18 @@ impl ObsClient {
         let mut inner = self.inner.write().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
         // OBSから各種情報を取得
-        let version_info = client.general().version().await?;
-        let stream_status = client.streaming().status().await.ok();
-        let record_status = client.recording().status().await.ok();
-        let virtual_cam_status = client.virtual_cam().status().await.ok();
-        let current_scene = client.scenes().current_program_scene().await.ok();
+        let version_info = with_timeout(inner.request_timeout, client.general().version()).await?;
+        let stream_status = with_timeout(inner.request_timeout, client.streaming().status()).await.ok();
+        let record_status = with_timeout(inner.request_timeout, client.recording().status()).await.ok();
+        let virtual_cam_status = with_timeout(inner.request_timeout, client.virtual_cam().status()).await.ok();
+        let current_scene = with_timeout(inner.request_timeout, client.scenes().current_program_scene()).await.ok();
 
         // 統計情報を取得
-        let stats = client.general().stats().await.ok();
+        let stats = with_timeout(inner.request_timeout, client.general().stats()).await.ok();
 
         // ビットレートを差分計算
         let stream_bitrate = if let Some(ref stream) = stream_status {
@@ -263,6 +427,10 @@ impl ObsClient {
             fps: stats.as_ref().map(|s| s.active_fps),
             render_dropped_frames: stats.as_ref().map(|s| s.render_skipped_frames),
             output_dropped_frames: stats.as_ref().map(|s| s.output_skipped_frames),
+            last_ping_latency_ms: inner.last_ping_latency_ms,
+            last_ping_success_at: inner.last_ping_success_at,
+            // プライバシーモードの状態はサービス層（ObsService::get_status）で設定する
+            privacy_mode_active: false,
         };
 
         Ok(status)
@@ -279,22 +447,110 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let scenes = client.scenes().list().await?;
+        let scenes = with_timeout(inner.request_timeout, client.scenes().list()).await?;
         Ok(scenes.scenes.into_iter().map(|s| s.id.name).collect())
     }
 
+    /// 現在のプログラムシーン名を取得
+    pub async fn get_current_scene(&self) -> ObsResult<String> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let scene = with_timeout(inner.request_timeout, client.scenes().current_program_scene()).await?;
+        Ok(scene.id.name)
+    }
+
     /// シーンを切り替え
     pub async fn set_current_scene(&self, scene_name: &str) -> ObsResult<()> {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(inner.request_timeout, client.scenes().set_current_program_scene(scene_name)).await?;
+        Ok(())
+    }
+
+    /// シーン内のアイテム一覧を表示・ロック状態付きで取得
+    pub async fn get_scene_item_list(&self, scene_name: &str) -> ObsResult<Vec<SceneItem>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
         })?;
 
-        client.scenes().set_current_program_scene(scene_name).await?;
+        let scene = obws::requests::scenes::SceneId::Name(scene_name);
+        let items = with_timeout(inner.request_timeout, client.scene_items().list(scene)).await?;
+
+        let mut result = Vec::with_capacity(items.len());
+        for item in items {
+            let enabled = with_timeout(inner.request_timeout, client.scene_items().enabled(scene, item.id)).await?;
+            let locked = with_timeout(inner.request_timeout, client.scene_items().locked(scene, item.id)).await?;
+            let source_type = match (item.input_kind, item.is_group) {
+                (Some(kind), _) => kind,
+                (None, Some(true)) => "group".to_string(),
+                (None, _) => "scene".to_string(),
+            };
+            result.push(SceneItem {
+                id: item.id,
+                name: item.source.name,
+                source_type,
+                enabled,
+                locked,
+            });
+        }
+        Ok(result)
+    }
+
+    /// シーンアイテムの表示状態を取得
+    pub async fn get_scene_item_enabled(&self, scene_name: &str, item_id: i64) -> ObsResult<bool> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let enabled = with_timeout(
+            inner.request_timeout,
+            client
+                .scene_items()
+                .enabled(obws::requests::scenes::SceneId::Name(scene_name), item_id),
+        )
+        .await?;
+        Ok(enabled)
+    }
+
+    /// シーンアイテムの表示状態を設定
+    pub async fn set_scene_item_enabled(
+        &self,
+        scene_name: &str,
+        item_id: i64,
+        enabled: bool,
+    ) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(
+            inner.request_timeout,
+            client
+                .scene_items()
+                .set_enabled(obws::requests::scene_items::SetEnabled {
+                    scene: obws::requests::scenes::SceneId::Name(scene_name),
+                    item_id,
+                    enabled,
+                }),
+        )
+        .await?;
         Ok(())
     }
 
@@ -303,10 +559,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        client.streaming().start().await?;
+        with_timeout(inner.request_timeout, client.streaming().start()).await?;
         Ok(())
     }
 
@@ -315,10 +571,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        client.streaming().stop().await?;
+        with_timeout(inner.request_timeout, client.streaming().stop()).await?;
         Ok(())
     }
 
@@ -327,10 +583,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        client.recording().start().await?;
+        with_timeout(inner.request_timeout, client.recording().start()).await?;
         Ok(())
     }
 
@@ -342,25 +598,411 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let path = client.recording().stop().await?;
+        let path = with_timeout(inner.request_timeout, client.recording().stop()).await?;
         Ok(path)
     }
 
+    /// 仮想カメラを開始
+    pub async fn start_virtual_cam(&self) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(inner.request_timeout, client.virtual_cam().start()).await?;
+        Ok(())
+    }
+
+    /// 仮想カメラを停止
+    pub async fn stop_virtual_cam(&self) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(inner.request_timeout, client.virtual_cam().stop()).await?;
+        Ok(())
+    }
+
+    /// 仮想カメラが有効かを取得
+    pub async fn get_virtual_cam_status(&self) -> ObsResult<bool> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let active = with_timeout(inner.request_timeout, client.virtual_cam().status()).await?;
+        Ok(active)
+    }
+
     /// ビデオ設定を取得
     pub async fn get_video_settings(&self) -> ObsResult<obws::responses::config::VideoSettings> {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let settings = client.config().video_settings().await?;
+        let settings = with_timeout(inner.request_timeout, client.config().video_settings()).await?;
         Ok(settings)
     }
 
+    /// 統計情報（レンダリング時間・スキップフレーム数など）を取得
+    pub async fn get_stats(&self) -> ObsResult<obws::responses::general::Stats> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let stats = with_timeout(inner.request_timeout, client.general().stats()).await?;
+        Ok(stats)
+    }
+
+    /// 配信中かどうかを取得
+    ///
+    /// `get_status` より軽量なため、定期ポーリングで使用する
+    pub async fn is_streaming(&self) -> ObsResult<bool> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let status = with_timeout(inner.request_timeout, client.streaming().status()).await?;
+        Ok(status.active)
+    }
+
+    /// 入力（ソース）一覧を取得
+    pub async fn get_input_list(&self) -> ObsResult<Vec<obws::responses::inputs::Input>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let inputs = with_timeout(inner.request_timeout, client.inputs().list(None)).await?;
+        Ok(inputs)
+    }
+
+    /// 設定画面の「音声」で指定されたグローバル音声入力（デスクトップ音声・マイク）の名前を取得
+    ///
+    /// 未設定の項目は含まない
+    pub async fn get_special_input_names(&self) -> ObsResult<Vec<String>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let specials = with_timeout(inner.request_timeout, client.inputs().specials()).await?;
+        Ok([
+            specials.desktop1,
+            specials.desktop2,
+            specials.mic1,
+            specials.mic2,
+            specials.mic3,
+            specials.mic4,
+        ]
+        .into_iter()
+        .flatten()
+        .collect())
+    }
+
+    /// obs-websocketの永続データ（現在のプロファイルに保存される領域）を取得
+    ///
+    /// 未設定のスロットは `null`
+    pub async fn get_profile_persistent_data(&self, slot_name: &str) -> ObsResult<serde_json::Value> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        use obws::requests::config::Realm;
+        let response = with_timeout(
+            inner.request_timeout,
+            client.config().get_persistent_data(Realm::Profile, slot_name),
+        )
+        .await?;
+        Ok(response.get("slotValue").cloned().unwrap_or_default())
+    }
+
+    /// obs-websocketの永続データ（現在のプロファイルに保存される領域）を設定
+    pub async fn set_profile_persistent_data(
+        &self,
+        slot_name: &str,
+        value: &serde_json::Value,
+    ) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        use obws::requests::config::{Realm, SetPersistentData};
+        with_timeout(
+            inner.request_timeout,
+            client.config().set_persistent_data(SetPersistentData {
+                realm: Realm::Profile,
+                slot_name,
+                slot_value: value,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 入力のミュート状態を取得
+    pub async fn get_input_mute(&self, input_name: &str) -> ObsResult<bool> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let muted = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .muted(obws::requests::inputs::InputId::Name(input_name)),
+        )
+        .await?;
+        Ok(muted)
+    }
+
+    /// メディアソースの再生状態を取得
+    ///
+    /// メディアソースでない入力の場合はOBSがエラーを返す
+    pub async fn get_media_input_state(
+        &self,
+        input_name: &str,
+    ) -> ObsResult<obws::responses::media_inputs::MediaState> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let status = with_timeout(
+            inner.request_timeout,
+            client
+                .media_inputs()
+                .status(obws::requests::inputs::InputId::Name(input_name)),
+        )
+        .await?;
+        Ok(status.state)
+    }
+
+    /// 入力の音量（フェーダー値）を取得
+    pub async fn get_input_volume(
+        &self,
+        input_name: &str,
+    ) -> ObsResult<obws::responses::inputs::InputVolume> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let volume = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .volume(obws::requests::inputs::InputId::Name(input_name)),
+        )
+        .await?;
+        Ok(volume)
+    }
+
+    /// 入力の音声モニタリングの種類を取得
+    pub async fn get_audio_monitor_type(&self, input_name: &str) -> ObsResult<AudioMonitorType> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let monitor_type = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .audio_monitor_type(obws::requests::inputs::InputId::Name(input_name)),
+        )
+        .await?;
+        Ok(monitor_type.into())
+    }
+
+    /// 入力の音声モニタリングの種類を設定
+    pub async fn set_audio_monitor_type(
+        &self,
+        input_name: &str,
+        monitor_type: AudioMonitorType,
+    ) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .set_audio_monitor_type(obws::requests::inputs::InputId::Name(input_name), monitor_type.into()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 入力のミュート状態を設定
+    pub async fn set_input_mute(&self, input_name: &str, muted: bool) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .set_muted(obws::requests::inputs::InputId::Name(input_name), muted),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 入力の設定（入力種別ごとのJSON）を取得
+    pub async fn get_input_settings(&self, input_name: &str) -> ObsResult<serde_json::Value> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let settings = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .settings::<serde_json::Value>(obws::requests::inputs::InputId::Name(input_name)),
+        )
+        .await?;
+        Ok(settings.settings)
+    }
+
+    /// 入力の設定を変更（指定した項目のみ上書き）
+    pub async fn set_input_settings(
+        &self,
+        input_name: &str,
+        settings: &serde_json::Value,
+    ) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .set_settings(obws::requests::inputs::SetSettings {
+                    input: obws::requests::inputs::InputId::Name(input_name),
+                    settings,
+                    overlay: Some(true),
+                }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 入力のプロパティのボタンを押す
+    ///
+    /// ブラウザソースの `refreshnocache` を指定すると、キャッシュを無視してページを再読み込みする
+    pub async fn press_input_properties_button(&self, input_name: &str, property_name: &str) -> ObsResult<()> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .press_properties_button(obws::requests::inputs::InputId::Name(input_name), property_name),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 入力のリスト形式プロパティの選択肢（値）を取得
+    ///
+    /// 音声入力の `device_id` を指定すると、現在接続されているデバイスのID一覧が得られる
+    pub async fn get_input_property_items(
+        &self,
+        input_name: &str,
+        property_name: &str,
+    ) -> ObsResult<Vec<String>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let items = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .properties_list_property_items(
+                    obws::requests::inputs::InputId::Name(input_name),
+                    property_name,
+                ),
+        )
+        .await?;
+        Ok(items
+            .into_iter()
+            .filter_map(|item| item.value.as_str().map(ToString::to_string))
+            .collect())
+    }
+
+    /// 入力のリスト形式プロパティの選択肢を（表示名, 値）の組で取得
+    ///
+    /// 音声入力の `device_id` を指定すると、デバイスの表示名とIDの一覧が得られる
+    pub async fn get_input_property_named_items(
+        &self,
+        input_name: &str,
+        property_name: &str,
+    ) -> ObsResult<Vec<(String, String)>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let items = with_timeout(
+            inner.request_timeout,
+            client
+                .inputs()
+                .properties_list_property_items(
+                    obws::requests::inputs::InputId::Name(input_name),
+                    property_name,
+                ),
+        )
+        .await?;
+        Ok(items
+            .into_iter()
+            .filter_map(|item| item.value.as_str().map(|value| (item.name, value.to_string())))
+            .collect())
+    }
+
     /// ビデオ設定を適用
     pub async fn set_video_settings(
         &self,
@@ -369,25 +1011,66 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        client.config().set_video_settings(settings).await?;
+        with_timeout(inner.request_timeout, client.config().set_video_settings(settings)).await?;
         Ok(())
     }
 
+    /// 配信先サービスの設定を取得
+    ///
+    /// 設定の内容は配信サービスの種別（rtmp_common / rtmp_custom 等）によって異なる
+    pub async fn get_stream_service_settings(
+        &self,
+    ) -> ObsResult<obws::responses::config::StreamServiceSettings<serde_json::Value>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let settings = with_timeout(inner.request_timeout, client.config().stream_service_settings()).await?;
+        Ok(settings)
+    }
+
     /// 出力一覧を取得
     pub async fn get_output_list(&self) -> ObsResult<Vec<obws::responses::outputs::Output>> {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let outputs = client.outputs().list().await?;
+        let outputs = with_timeout(inner.request_timeout, client.outputs().list()).await?;
         Ok(outputs)
     }
 
+    /// 配信出力のステータスを取得
+    ///
+    /// 出力一覧から動作中の配信用出力（RTMP・SRT/RIST・WHIP）を探し、`GetOutputStatus` で取得する。
+    /// 配信中でない場合はNone
+    pub async fn get_stream_output_status(
+        &self,
+    ) -> ObsResult<Option<obws::responses::outputs::OutputStatus>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let outputs = with_timeout(inner.request_timeout, client.outputs().list()).await?;
+        let Some(output) = outputs
+            .iter()
+            .find(|output| output.active && STREAM_OUTPUT_KINDS.contains(&output.kind.as_str()))
+        else {
+            return Ok(None);
+        };
+
+        let status = with_timeout(inner.request_timeout, client.outputs().status(&output.name)).await?;
+        Ok(Some(status))
+    }
+
     /// 出力設定を取得
     pub async fn get_output_settings<T: serde::de::DeserializeOwned>(
         &self,
@@ -396,10 +1079,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let settings = client.outputs().settings(output_name).await?;
+        let settings = with_timeout(inner.request_timeout, client.outputs().settings(output_name)).await?;
         Ok(settings)
     }
 
@@ -408,22 +1091,46 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let profiles = client.profiles().list().await?;
+        let profiles = with_timeout(inner.request_timeout, client.profiles().list()).await?;
         Ok(profiles.profiles)
     }
 
+    /// OBSのホットキー名の一覧を取得
+    pub async fn get_hotkey_list(&self) -> ObsResult<Vec<String>> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
+        })?;
+
+        let hotkeys = with_timeout(inner.request_timeout, client.hotkeys().list()).await?;
+        Ok(hotkeys)
+    }
+
     /// 現在のプロファイル名を取得
     pub async fn get_current_profile(&self) -> ObsResult<String> {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
+        })?;
+
+        let current = with_timeout(inner.request_timeout, client.profiles().current()).await?;
+        Ok(current)
+    }
+
+    /// 現在のシーンコレクション名を取得
+    pub async fn get_current_scene_collection(&self) -> ObsResult<String> {
+        let inner = self.inner.read().await;
+
+        let client = inner.client.as_ref().ok_or_else(|| {
+            AppError::obs_not_connected()
         })?;
 
-        let current = client.profiles().current().await?;
+        let current = with_timeout(inner.request_timeout, client.scene_collections().current()).await?;
         Ok(current)
     }
 
@@ -433,10 +1140,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        client.profiles().set_current(profile_name).await?;
+        with_timeout(inner.request_timeout, client.profiles().set_current(profile_name)).await?;
         Ok(())
     }
 
@@ -449,10 +1156,10 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
-        let param = client.profiles().parameter(category, name).await?;
+        let param = with_timeout(inner.request_timeout, client.profiles().parameter(category, name)).await?;
         Ok(param.value)
     }
 
@@ -466,15 +1173,19 @@ impl ObsClient {
         let inner = self.inner.read().await;
 
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
 
         use obws::requests::profiles::SetParameter;
-        client.profiles().set_parameter(SetParameter {
-            category,
-            name,
-            value,
-        }).await?;
+        with_timeout(
+            inner.request_timeout,
+            client.profiles().set_parameter(SetParameter {
+                category,
+                name,
+                value,
+            }),
+        )
+        .await?;
         Ok(())
     }
 
@@ -561,9 +1272,9 @@ impl ObsClient {
     {
         let inner = self.inner.read().await;
         let client = inner.client.as_ref().ok_or_else(|| {
-            AppError::obs_state("OBSに接続されていません")
+            AppError::obs_not_connected()
         })?;
-        f(client).await.map_err(AppError::from)
+        with_timeout(inner.request_timeout, f(client)).await
     }
 }
 
@@ -649,6 +1360,10 @@ mod tests {
 
         let result = client.connect(invalid_config).await;
         assert!(result.is_err());
+        assert_eq!(
+            result.err().map(|e| e.code().to_string()).as_deref(),
+            Some(crate::error::ERROR_CODE_VALIDATION_FAILED)
+        );
     }
 
     #[test]
@@ -753,6 +1468,12 @@ mod tests {
 
         let result = client.set_current_scene("test").await;
         assert!(result.is_err());
+
+        let result = client.get_scene_item_list("test").await;
+        assert!(result.is_err());
+
+        let result = client.set_scene_item_enabled("test", 1, true).await;
+        assert!(result.is_err());
     }
 
     #[tokio::test]
@@ -779,6 +1500,27 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_virtual_cam_operations_when_not_connected() {
+        let client = ObsClient::new();
+
+        // 未接続時の仮想カメラ操作はエラー
+        assert!(client.start_virtual_cam().await.is_err());
+        assert!(client.stop_virtual_cam().await.is_err());
+        assert!(client.get_virtual_cam_status().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_audio_monitor_type_when_not_connected() {
+        let client = ObsClient::new();
+
+        assert!(client.get_audio_monitor_type("Mic/Aux").await.is_err());
+        assert!(client
+            .set_audio_monitor_type("Mic/Aux", AudioMonitorType::MonitorAndOutput)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_with_client_when_not_connected() {
         let client = ObsClient::new();
@@ -790,9 +1532,54 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_ping_when_not_connected() {
+        let client = ObsClient::new();
+        let result = client.ping().await;
+        assert!(result.is_err());
+        assert_eq!(client.connection_state().await, ConnectionState::Disconnected);
+    }
+
     #[tokio::test]
     async fn test_default_implementation() {
         let client = ObsClient::default();
         assert!(!client.is_connected().await);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_timeout_when_obs_never_responds() {
+        // 応答を返さないOBS（モーダルダイアログ表示中など）を模したリクエスト
+        let unresponsive = std::future::pending::<Result<(), obws::error::Error>>();
+
+        let result = with_timeout(Duration::from_secs(3), unresponsive).await;
+        let error = result.expect_err("should time out");
+        assert_eq!(error.code(), crate::obs::error::error_codes::OBS_TIMEOUT);
+        assert!(error.message().contains("3秒"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_timeout_passes_through_response() {
+        let response = async { Ok::<u32, obws::error::Error>(42) };
+        let result = with_timeout(DEFAULT_REQUEST_TIMEOUT, response).await;
+        assert_eq!(result.expect("response"), 42);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token_cancelled_on_disconnect() {
+        let client = ObsClient::new();
+        let token = client.cancellation_token();
+        assert!(!token.is_cancelled());
+        assert!(token.check().is_ok());
+
+        // クローンしたクライアントの切断でも中断扱いになる
+        client.clone().disconnect().await.expect("disconnect should succeed");
+        assert!(token.is_cancelled());
+        assert_eq!(
+            token.check().expect_err("should be cancelled").code(),
+            crate::error::ERROR_CODE_OBS_NOT_CONNECTED
+        );
+
+        // 切断後に作成したトークンは有効
+        assert!(!client.cancellation_token().is_cancelled());
+    }
 }
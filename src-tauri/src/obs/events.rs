@@ -2,10 +2,25 @@
 //
 // OBSの状態変化をフロントエンドに通知するためのイベント発行機能
 
-use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 
-use super::types::{ConnectionState, ObsStatus};
+use obws::responses::media_inputs::MediaState;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::{watch, Mutex};
+
+use super::state::get_obs_client;
+use super::types::{ConnectionState, ObsStatus, OutputStats};
+use crate::error::AppError;
+use crate::events::{emit_event, TauriEvent};
+use crate::services::alerts::{suppress_alerts_for_trigger, MetricType, SuppressionTrigger};
+use crate::services::automation::{evaluate_automation_rules, FrameDropRateTracker};
+use crate::services::debug_recorder::record_obs_event;
+use crate::services::media_autoaction::run_media_autoaction;
+use crate::services::streaming_mode::get_streaming_mode_service;
+use crate::storage::config::{config_revision, load_config, MediaAutoAction};
 
 /// OBSイベント名の定数
 pub mod event_names {
@@ -24,8 +39,23 @@ pub mod event_names {
     /// エラーイベント（将来使用予定）
     #[allow(dead_code)]
     pub const OBS_ERROR: &str = "obs:error";
+    /// 配信出力の統計更新イベント（配信中に1秒ごと）
+    pub const OBS_STATS_UPDATE: &str = "obs:stats-update";
+    /// シーン切り替え開始イベント
+    pub const OBS_SCENE_TRANSITION_STARTED: &str = "obs:scene-transition-started";
+    /// メディアソースの再生終了イベント
+    pub const OBS_MEDIA_ENDED: &str = "obs:media-ended";
 }
 
+/// 配信出力の統計を取得する間隔
+pub const OUTPUT_STATS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 自動アクションを設定したメディアソースの再生状態を取得する間隔
+pub const MEDIA_STATE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 保持するビットレートの履歴の件数（1秒ごとに5分）
+const MAX_BITRATE_HISTORY: usize = 300;
+
 /// 接続状態変化ペイロード
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -71,6 +101,24 @@ pub struct SceneChangedPayload {
     pub current_scene: String,
 }
 
+/// シーン切り替え開始ペイロード
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneTransitionStartedPayload {
+    /// 切り替え先のシーン名
+    pub to_scene: String,
+    /// アラートを抑制した秒数（抑制していない場合はnull）
+    pub alerts_suppressed_secs: Option<u64>,
+}
+
+/// メディアソースの再生終了ペイロード
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaEndedPayload {
+    /// 再生が終了したメディアソース名
+    pub source_name: String,
+}
+
 /// エラーペイロード（将来使用予定）
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize)]
@@ -84,6 +132,14 @@ pub struct ErrorPayload {
     pub recoverable: bool,
 }
 
+/// 配信出力の統計更新ペイロード
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsStatsUpdate {
+    /// 配信出力の統計
+    pub stats: OutputStats,
+}
+
 /// OBSイベント発行器
 ///
 /// Tauriのappハンドルを保持し、OBS関連のイベントをフロントエンドに発行する
@@ -110,65 +166,70 @@ impl ObsEventEmitter {
     }
 
     /// 接続状態変化を通知
-    pub fn emit_connection_changed(&self, payload: ConnectionChangedPayload) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_CONNECTION_CHANGED, payload)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_connection_changed(&self, payload: ConnectionChangedPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_CONNECTION_CHANGED, &payload);
+        emit_event(&self.app_handle, TauriEvent::ConnectionChanged(payload))
     }
 
     /// 配信状態変化を通知
-    pub fn emit_streaming_changed(&self, payload: StreamingChangedPayload) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_STREAMING_CHANGED, payload)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_streaming_changed(&self, payload: StreamingChangedPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_STREAMING_CHANGED, &payload);
+        emit_event(&self.app_handle, TauriEvent::StreamingChanged(payload))
     }
 
     /// 録画状態変化を通知
-    pub fn emit_recording_changed(&self, payload: RecordingChangedPayload) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_RECORDING_CHANGED, payload)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_recording_changed(&self, payload: RecordingChangedPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_RECORDING_CHANGED, &payload);
+        emit_event(&self.app_handle, TauriEvent::RecordingChanged(payload))
+    }
+
+    /// 配信出力の統計更新を通知
+    pub fn emit_stats_update(&self, payload: ObsStatsUpdate) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_STATS_UPDATE, &payload);
+        emit_event(&self.app_handle, TauriEvent::ObsStatsUpdate(payload))
     }
 
     /// ステータス更新を通知（将来使用予定）
     #[allow(dead_code)]
-    pub fn emit_status_update(&self, status: ObsStatus) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_STATUS_UPDATE, status)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_status_update(&self, status: ObsStatus) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_STATUS_UPDATE, &status);
+        emit_event(&self.app_handle, TauriEvent::ObsStatusUpdate(status))
     }
 
     /// シーン変更を通知（将来使用予定）
     #[allow(dead_code)]
-    pub fn emit_scene_changed(&self, payload: SceneChangedPayload) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_SCENE_CHANGED, payload)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_scene_changed(&self, payload: SceneChangedPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_SCENE_CHANGED, &payload);
+        emit_event(&self.app_handle, TauriEvent::SceneChanged(payload))
+    }
+
+    /// シーン切り替えの開始を通知
+    ///
+    /// 切り替え中はCPU・GPUの一時的な上昇が想定されるため、
+    /// 抑制ルールに従ってメトリクスのアラートを抑制してから通知する
+    pub async fn emit_scene_transition_started(&self, to_scene: String) -> Result<(), AppError> {
+        let payload = SceneTransitionStartedPayload {
+            to_scene,
+            alerts_suppressed_secs: suppress_alerts_for_trigger(SuppressionTrigger::SceneTransitionStart).await,
+        };
+        record_obs_event(event_names::OBS_SCENE_TRANSITION_STARTED, &payload);
+        emit_event(&self.app_handle, TauriEvent::SceneTransitionStarted(payload))
+    }
+
+    /// メディアソースの再生終了を通知
+    pub fn emit_media_ended(&self, payload: MediaEndedPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_MEDIA_ENDED, &payload);
+        emit_event(&self.app_handle, TauriEvent::MediaEnded(payload))
     }
 
     /// エラーを通知（将来使用予定）
     #[allow(dead_code)]
-    pub fn emit_error(&self, payload: ErrorPayload) -> Result<(), String> {
-        self.app_handle
-            .emit(event_names::OBS_ERROR, payload)
-            .map_err(|e| format!("イベント発行エラー: {e}"))
+    pub fn emit_error(&self, payload: ErrorPayload) -> Result<(), AppError> {
+        record_obs_event(event_names::OBS_ERROR, &payload);
+        emit_event(&self.app_handle, TauriEvent::ObsError(payload))
     }
 }
 
-/// 簡易的なイベント発行ヘルパー関数（将来使用予定）
-///
-/// グローバルなAppHandleを使用せずに、直接イベントを発行する場合に使用
-#[allow(dead_code)]
-pub fn emit_obs_event<T: Serialize + Clone>(
-    app_handle: &AppHandle,
-    event_name: &str,
-    payload: T,
-) -> Result<(), String> {
-    app_handle
-        .emit(event_name, payload)
-        .map_err(|e| format!("イベント発行エラー: {e}"))
-}
-
 /// 現在時刻をUnix timestampで取得（将来使用予定）
 #[allow(dead_code)]
 pub fn current_timestamp() -> u64 {
@@ -178,6 +239,312 @@ pub fn current_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
+/// 配信出力の累積値から統計を算出する
+///
+/// ビットレートは前回の取得からの送信バイト数と出力時間の差分で算出し、
+/// 初回（または配信の再開後）は配信開始からの平均とする
+#[derive(Debug, Default)]
+pub struct OutputStatsTracker {
+    /// 前回の送信バイト数と出力時間
+    last: Option<(u64, Duration)>,
+    /// 直近のビットレート（kbps、古い順）
+    bitrate_history: VecDeque<u64>,
+}
+
+impl OutputStatsTracker {
+    /// `GetOutputStatus` の累積値から統計を算出
+    ///
+    /// # Arguments
+    /// * `bytes` - 配信開始からの送信バイト数
+    /// * `duration` - 配信開始からの出力時間
+    /// * `total_frames` - 配信開始からの送出フレーム数
+    /// * `skipped_frames` - 配信開始からのスキップフレーム数
+    /// * `congestion` - 輻輳（0.0〜1.0）
+    pub fn update(
+        &mut self,
+        bytes: u64,
+        duration: Duration,
+        total_frames: u64,
+        skipped_frames: u64,
+        congestion: f32,
+    ) -> OutputStats {
+        let (delta_bytes, delta_duration) = match self.last {
+            // 累積値が減った場合は配信が再開されたとみなして平均に戻す
+            Some((last_bytes, last_duration)) if bytes >= last_bytes && duration > last_duration => {
+                (bytes - last_bytes, duration - last_duration)
+            }
+            _ => (bytes, duration),
+        };
+        self.last = Some((bytes, duration));
+
+        let current_bitrate_kbps = if delta_duration.is_zero() {
+            0.0
+        } else {
+            delta_bytes as f64 * 8.0 / 1000.0 / delta_duration.as_secs_f64()
+        };
+
+        self.bitrate_history.push_back(current_bitrate_kbps.round() as u64);
+        while self.bitrate_history.len() > MAX_BITRATE_HISTORY {
+            self.bitrate_history.pop_front();
+        }
+
+        OutputStats {
+            frames_sent: total_frames,
+            frames_dropped: skipped_frames,
+            current_bitrate_kbps,
+            congestion,
+        }
+    }
+
+    /// 直近のビットレートの履歴（kbps、古い順）
+    pub fn bitrate_history(&self) -> Vec<u64> {
+        self.bitrate_history.iter().copied().collect()
+    }
+
+    /// 配信停止時に累積値と履歴を破棄
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.bitrate_history.clear();
+    }
+}
+
+/// 配信出力の統計の算出状態
+static OUTPUT_STATS_TRACKER: Lazy<Mutex<OutputStatsTracker>> =
+    Lazy::new(|| Mutex::new(OutputStatsTracker::default()));
+
+/// 統計取得タスクのキャンセル送信チャネル
+static OUTPUT_STATS_POLLING: Lazy<Mutex<Option<watch::Sender<bool>>>> = Lazy::new(|| Mutex::new(None));
+
+/// 配信出力の統計をOBSから取得
+///
+/// 算出したビットレートは `recent_output_bitrates` で参照できるよう保持する。
+/// OBSが配信中でない場合は `VALIDATION_FAILED` エラー
+pub async fn poll_output_stats() -> Result<OutputStats, AppError> {
+    let Some(status) = get_obs_client().get_stream_output_status().await? else {
+        OUTPUT_STATS_TRACKER.lock().await.reset();
+        return Err(AppError::validation_failed("OBSが配信中ではありません"));
+    };
+
+    let stats = OUTPUT_STATS_TRACKER.lock().await.update(
+        status.bytes,
+        status.duration,
+        u64::from(status.total_frames),
+        u64::from(status.skipped_frames),
+        status.congestion,
+    );
+    Ok(stats)
+}
+
+/// 直近の配信ビットレートの実測値（kbps、古い順。未取得の場合は空）
+pub async fn recent_output_bitrates() -> Vec<u64> {
+    OUTPUT_STATS_TRACKER.lock().await.bitrate_history()
+}
+
+/// 配信出力の統計の定期取得を開始
+///
+/// 配信中モードの間、1秒ごとに統計を取得して `obs:stats-update` イベントを発行する。
+/// 既に実行中の場合は、それを停止して新しいタスクを開始する
+pub async fn start_output_stats_polling(app_handle: AppHandle) {
+    stop_output_stats_polling().await;
+
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    *OUTPUT_STATS_POLLING.lock().await = Some(cancel_tx);
+    tokio::spawn(output_stats_polling_task(ObsEventEmitter::new(app_handle), cancel_rx));
+}
+
+/// 配信出力の統計の定期取得を停止
+///
+/// 停止した配信のビットレートが分析に使われないよう、履歴も破棄する
+pub async fn stop_output_stats_polling() {
+    let cancel_tx = OUTPUT_STATS_POLLING.lock().await.take();
+    if let Some(cancel_tx) = cancel_tx {
+        let _ = cancel_tx.send(true);
+    }
+    OUTPUT_STATS_TRACKER.lock().await.reset();
+}
+
+/// バックグラウンドの統計取得タスク
+///
+/// 配信中モードかつOBSに接続中の間だけ取得し、取得できない回はスキップする。
+/// 取得間隔ごとのフレームドロップ率で自動化ルールを評価する
+async fn output_stats_polling_task(emitter: ObsEventEmitter, mut cancel_rx: watch::Receiver<bool>) {
+    let mut drop_rate_tracker = FrameDropRateTracker::new();
+
+    loop {
+        tokio::select! {
+            () = tokio::time::sleep(OUTPUT_STATS_POLL_INTERVAL) => {}
+            _ = cancel_rx.changed() => return,
+        }
+
+        if *cancel_rx.borrow() {
+            return;
+        }
+
+        if !get_streaming_mode_service().is_streaming().await || !get_obs_client().is_connected().await {
+            continue;
+        }
+
+        match poll_output_stats().await {
+            Ok(stats) => {
+                if let Err(e) = emitter.emit_stats_update(ObsStatsUpdate { stats }) {
+                    tracing::warn!(target: "obs_stats", error = %e, "Failed to emit stats_update event");
+                }
+                if let Some(rate) = drop_rate_tracker.update(stats.frames_sent, stats.frames_dropped) {
+                    evaluate_automation_rules(&emitter.app_handle, MetricType::FrameDropRate, rate).await;
+                }
+            }
+            Err(e) => tracing::debug!(target: "obs_stats", error = %e, "配信出力の統計を取得できませんでした"),
+        }
+    }
+}
+
+/// メディアソースの再生終了の検出
+///
+/// ソースごとに前回の再生状態を保持し、再生終了（`Ended`）に変わったときだけ検出する。
+/// 初回に取得した状態は基準にするだけで、既に再生が終わっているソースでは検出しない
+#[derive(Debug, Default)]
+pub struct MediaEndDetector {
+    /// ソース名ごとの前回の再生状態
+    last_states: HashMap<String, MediaState>,
+}
+
+impl MediaEndDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 再生状態を更新し、再生終了に変わった場合にtrueを返す
+    pub fn update(&mut self, source_name: &str, state: MediaState) -> bool {
+        let previous = self.last_states.insert(source_name.to_string(), state);
+        state == MediaState::Ended && previous.is_some_and(|previous| previous != MediaState::Ended)
+    }
+
+    /// 監視対象から外れたソースの状態を破棄
+    pub fn retain_sources(&mut self, source_names: &[&str]) {
+        self.last_states.retain(|name, _| source_names.contains(&name.as_str()));
+    }
+}
+
+/// メディアソースの再生状態の取得タスクのキャンセル送信チャネル
+static MEDIA_STATE_POLLING: Lazy<Mutex<Option<watch::Sender<bool>>>> = Lazy::new(|| Mutex::new(None));
+
+/// メディアソースの再生状態の定期取得を開始
+///
+/// 自動アクションを設定したメディアソースの再生状態を0.5秒ごとに取得し、再生が終了したら
+/// `obs:media-ended` イベントを発行して自動アクションを実行する。
+/// 既に実行中の場合は、それを停止して新しいタスクを開始する
+pub async fn start_media_state_polling(app_handle: AppHandle) {
+    stop_media_state_polling().await;
+
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    *MEDIA_STATE_POLLING.lock().await = Some(cancel_tx);
+    tokio::spawn(media_state_polling_task(ObsEventEmitter::new(app_handle), cancel_rx));
+}
+
+/// メディアソースの再生状態の定期取得を停止
+pub async fn stop_media_state_polling() {
+    let cancel_tx = MEDIA_STATE_POLLING.lock().await.take();
+    if let Some(cancel_tx) = cancel_tx {
+        let _ = cancel_tx.send(true);
+    }
+}
+
+/// バックグラウンドのメディアソースの再生状態の取得タスク
+///
+/// OBSに接続中の間だけ取得し、自動アクションが設定されていない場合は何も取得しない。
+/// 監視するソース名は設定ファイルが保存されたときだけ読み直す。
+/// 取得できないソース（メディアソースでない・存在しない）はスキップする
+async fn media_state_polling_task(emitter: ObsEventEmitter, mut cancel_rx: watch::Receiver<bool>) {
+    let mut detector = MediaEndDetector::new();
+    // 読み込んだ時点の設定の保存回数と、自動アクションを設定したソース名
+    let mut watched: Option<(u64, Vec<String>)> = None;
+
+    loop {
+        tokio::select! {
+            () = tokio::time::sleep(MEDIA_STATE_POLL_INTERVAL) => {}
+            _ = cancel_rx.changed() => return,
+        }
+
+        if *cancel_rx.borrow() {
+            return;
+        }
+
+        let client = get_obs_client();
+        if !client.is_connected().await {
+            continue;
+        }
+
+        let revision = config_revision();
+        if watched.as_ref().is_none_or(|(cached, _)| *cached != revision) {
+            let source_names: Vec<String> = match load_config() {
+                Ok(config) => config.media_autoactions.into_iter().map(|(name, _)| name).collect(),
+                Err(e) => {
+                    tracing::debug!(target: "obs_events", error = %e, "設定を読み込めませんでした");
+                    continue;
+                }
+            };
+            detector.retain_sources(&source_names.iter().map(String::as_str).collect::<Vec<_>>());
+            watched = Some((revision, source_names));
+        }
+        let Some((_, source_names)) = &watched else {
+            continue;
+        };
+
+        for source_name in source_names {
+            match client.get_media_input_state(source_name).await {
+                Ok(state) => {
+                    if detector.update(source_name, state) {
+                        handle_media_playback_ended(&emitter, source_name).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!(target: "obs_events", source = %source_name, error = %e, "メディアソースの再生状態を取得できませんでした");
+                }
+            }
+        }
+    }
+}
+
+/// メディアソースの再生終了を通知し、設定された自動アクションを実行
+///
+/// 自動アクションの実行後は、同じ操作をコマンドから行った場合と同様に
+/// 状態変化のイベント発行・アラートの抑制を行う
+async fn handle_media_playback_ended(emitter: &ObsEventEmitter, source_name: &str) {
+    let payload = MediaEndedPayload { source_name: source_name.to_string() };
+    if let Err(e) = emitter.emit_media_ended(payload) {
+        tracing::warn!(target: "obs_events", error = %e, "Failed to emit media_ended event");
+    }
+
+    let action = match run_media_autoaction(source_name).await {
+        Ok(Some(action)) => action,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(target: "obs_events", source = %source_name, error = %e, "メディアの自動アクションの実行に失敗");
+            return;
+        }
+    };
+    tracing::info!(target: "obs_events", source = %source_name, action = ?action, "メディアの自動アクションを実行");
+
+    let result = match action {
+        MediaAutoAction::StartStreaming => emitter.emit_streaming_changed(StreamingChangedPayload {
+            is_streaming: true,
+            started_at: Some(current_timestamp()),
+        }),
+        MediaAutoAction::SwitchScene(scene_name) => emitter.emit_scene_transition_started(scene_name).await,
+        MediaAutoAction::StartRecording => {
+            suppress_alerts_for_trigger(SuppressionTrigger::RecordingStart).await;
+            emitter.emit_recording_changed(RecordingChangedPayload {
+                is_recording: true,
+                started_at: Some(current_timestamp()),
+            })
+        }
+        MediaAutoAction::None => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::warn!(target: "obs_events", error = %e, "Failed to emit event after media autoaction");
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -191,6 +558,38 @@ mod tests {
         assert_eq!(event_names::OBS_STATUS_UPDATE, "obs:status-update");
         assert_eq!(event_names::OBS_SCENE_CHANGED, "obs:scene-changed");
         assert_eq!(event_names::OBS_ERROR, "obs:error");
+        assert_eq!(event_names::OBS_STATS_UPDATE, "obs:stats-update");
+        assert_eq!(event_names::OBS_MEDIA_ENDED, "obs:media-ended");
+    }
+
+    #[test]
+    fn test_media_end_detector() {
+        let mut detector = MediaEndDetector::new();
+
+        // 接続時に既に再生が終わっているソースでは検出しない
+        assert!(!detector.update("イントロ", MediaState::Ended));
+        assert!(!detector.update("イントロ", MediaState::Ended));
+
+        // 再生し直して終わった場合に1回だけ検出する
+        assert!(!detector.update("イントロ", MediaState::Playing));
+        assert!(detector.update("イントロ", MediaState::Ended));
+        assert!(!detector.update("イントロ", MediaState::Ended));
+
+        // 停止からの再生終了も検出する
+        assert!(!detector.update("アウトロ", MediaState::Stopped));
+        assert!(detector.update("アウトロ", MediaState::Ended));
+
+        // 監視対象から外れたソースは初回の扱いに戻る
+        detector.retain_sources(&["アウトロ"]);
+        assert!(!detector.update("イントロ", MediaState::Ended));
+    }
+
+    #[test]
+    fn test_media_ended_payload_serialization() {
+        let payload = MediaEndedPayload { source_name: "イントロ".to_string() };
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json, serde_json::json!({ "sourceName": "イントロ" }));
     }
 
     #[test]
@@ -392,6 +791,9 @@ mod tests {
             fps: Some(60.0),
             render_dropped_frames: Some(10),
             output_dropped_frames: Some(5),
+            last_ping_latency_ms: None,
+            last_ping_success_at: None,
+            privacy_mode_active: false,
         };
 
         let json = serde_json::to_string(&status).unwrap();
@@ -399,4 +801,45 @@ mod tests {
         assert!(json.contains("streaming"));
         assert!(json.contains("Test Scene"));
     }
+
+    #[test]
+    fn test_output_stats_tracker_bitrate_from_deltas() {
+        let mut tracker = OutputStatsTracker::default();
+
+        // 初回は配信開始からの平均（10秒で7,500,000バイト = 6000kbps）
+        let stats = tracker.update(7_500_000, Duration::from_secs(10), 600, 3, 0.1);
+        assert!((stats.current_bitrate_kbps - 6000.0).abs() < 0.01);
+        assert_eq!(stats.frames_sent, 600);
+        assert_eq!(stats.frames_dropped, 3);
+        assert!((stats.congestion - 0.1).abs() < f32::EPSILON);
+
+        // 以降は前回からの差分（1秒で437,500バイト = 3500kbps）
+        let stats = tracker.update(7_937_500, Duration::from_secs(11), 660, 3, 0.4);
+        assert!((stats.current_bitrate_kbps - 3500.0).abs() < 0.01);
+        assert_eq!(tracker.bitrate_history(), vec![6000, 3500]);
+    }
+
+    #[test]
+    fn test_output_stats_tracker_restart_and_reset() {
+        let mut tracker = OutputStatsTracker::default();
+        tracker.update(7_500_000, Duration::from_secs(10), 600, 0, 0.0);
+
+        // 累積値が減った（配信の再開）場合は平均に戻す
+        let stats = tracker.update(750_000, Duration::from_secs(1), 60, 0, 0.0);
+        assert!((stats.current_bitrate_kbps - 6000.0).abs() < 0.01);
+
+        tracker.reset();
+        assert!(tracker.bitrate_history().is_empty());
+        let stats = tracker.update(0, Duration::ZERO, 0, 0, 0.0);
+        assert!(stats.current_bitrate_kbps.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_output_stats_tracker_caps_history() {
+        let mut tracker = OutputStatsTracker::default();
+        for second in 1..=(MAX_BITRATE_HISTORY as u64 + 10) {
+            tracker.update(second * 750_000, Duration::from_secs(second), 0, 0, 0.0);
+        }
+        assert_eq!(tracker.bitrate_history().len(), MAX_BITRATE_HISTORY);
+    }
 }
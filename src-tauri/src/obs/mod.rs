@@ -10,20 +10,32 @@ pub mod reconnect;
 pub mod state;
 pub mod types;
 pub mod settings;
+pub mod settings_cache;
 
 // 主要な型の再エクスポート
-pub use client::ObsClient;
+pub use client::{CancellationToken, ObsClient};
 pub use events::{
+    poll_output_stats,
+    recent_output_bitrates,
+    start_media_state_polling,
+    start_output_stats_polling,
+    stop_media_state_polling,
+    stop_output_stats_polling,
     ConnectionChangedPayload,
     ObsEventEmitter,
     RecordingChangedPayload,
     StreamingChangedPayload,
 };
-pub use state::get_obs_client;
+pub use reconnect::HealthCheckCallback;
+pub use state::{get_obs_client, get_reconnect_manager};
 pub use types::{
+    AudioMonitorType,
     ConnectionConfig,
     ConnectionState,
     ObsStatus,
+    OutputStats,
+    PingResult,
+    SceneItem,
 };
 // 設定関連の型をエクスポート（公開API用）
 // 将来のAPI拡張のために定義を維持
@@ -32,7 +44,17 @@ pub use settings::{
     get_obs_settings,
     ObsSettings,
     VideoSettings,
+    VideoApplyResult,
+    VerifiedValue,
     AudioSettings,
     OutputSettings,
     EncoderType,
+    SettingsValidationWarning,
+    ObsOutputMode,
+    get_output_mode,
+    set_output_mode,
+    get_recording_output,
+    is_crash_unsafe_recording_format,
 };
+#[allow(unused_imports)]
+pub use settings_cache::{get_cached_obs_settings, invalidate_obs_settings_cache};
@@ -9,13 +9,23 @@
 //
 // 注意: このモジュールは将来的な自動再接続機能の実装用です
 // 現在は未使用ですが、設計済みのため保持しています
+//
+// ヘルスチェック:
+// - 接続中は一定間隔（既定30秒）でGetVersionによるpingを送信し、応答時間を記録
+// - 応答がない状態が続くとDegraded、さらに続くとDisconnectedに更新し、コールバックで通知
+// - 設定の一括適用などの実行中は、応答時間が偏るためpingを送らない
 
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{watch, RwLock};
 
 use super::client::ObsClient;
-use super::types::ConnectionConfig;
+use super::events::ConnectionChangedPayload;
+use super::types::{ConnectionConfig, ConnectionState};
+use crate::services::operation_lock::get_operation_lock;
+
+/// ヘルスチェックで接続状態の変化（Degraded・復帰・接続断）を検出した際のコールバック
+pub type HealthCheckCallback = Arc<dyn Fn(ConnectionChangedPayload) + Send + Sync>;
 
 /// 再接続タスクの状態（将来使用予定）
 #[allow(dead_code)]
@@ -73,6 +83,8 @@ impl ReconnectHandle {
 pub struct ReconnectManager {
     /// 現在のハンドル（タスク実行中の場合）
     current_handle: Arc<RwLock<Option<ReconnectHandle>>>,
+    /// ヘルスチェックタスクのキャンセル送信チャネル（タスク実行中の場合）
+    health_check_cancel: Arc<RwLock<Option<watch::Sender<bool>>>>,
 }
 
 impl Default for ReconnectManager {
@@ -87,6 +99,7 @@ impl ReconnectManager {
     pub fn new() -> Self {
         Self {
             current_handle: Arc::new(RwLock::new(None)),
+            health_check_cancel: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -134,6 +147,99 @@ impl ReconnectManager {
         let current = self.current_handle.read().await;
         current.clone()
     }
+
+    /// 定期ヘルスチェックを開始
+    ///
+    /// 既にヘルスチェックが実行中の場合は、それを停止して新しいタスクを開始
+    ///
+    /// # Arguments
+    /// * `client` - OBSクライアント
+    /// * `interval` - pingの送信間隔
+    /// * `on_state_changed` - pingの結果で接続状態が変化した際のコールバック
+    pub async fn start_health_check(
+        &self,
+        client: ObsClient,
+        interval: Duration,
+        on_state_changed: HealthCheckCallback,
+    ) {
+        self.stop_health_check().await;
+
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        {
+            let mut current = self.health_check_cancel.write().await;
+            *current = Some(cancel_tx);
+        }
+
+        tokio::spawn(health_check_task(client, interval, cancel_rx, on_state_changed));
+    }
+
+    /// 定期ヘルスチェックを停止
+    pub async fn stop_health_check(&self) {
+        let mut current = self.health_check_cancel.write().await;
+        if let Some(cancel_tx) = current.take() {
+            let _ = cancel_tx.send(true);
+        }
+    }
+
+    /// ヘルスチェックが実行中かどうかを確認
+    pub async fn is_health_check_running(&self) -> bool {
+        let current = self.health_check_cancel.read().await;
+        current.as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+}
+
+/// バックグラウンドヘルスチェックタスク
+///
+/// 接続中のみpingを送信し、接続状態が変化するたびに通知する。接続断を検出した時点で終了する
+async fn health_check_task(
+    client: ObsClient,
+    interval: Duration,
+    mut cancel_rx: watch::Receiver<bool>,
+    on_state_changed: HealthCheckCallback,
+) {
+    loop {
+        // キャンセル可能な待機
+        tokio::select! {
+            () = tokio::time::sleep(interval) => {}
+            _ = cancel_rx.changed() => return,
+        }
+
+        if *cancel_rx.borrow() {
+            return;
+        }
+
+        // 未接続の間はpingを送らない
+        if !client.is_connected().await {
+            continue;
+        }
+
+        // 設定の一括適用などの実行中はOBSの応答が遅れ、応答時間が偏るため送らない
+        if get_operation_lock().current().is_some() {
+            continue;
+        }
+
+        let previous_state = client.connection_state().await;
+        if let Err(e) = client.ping().await {
+            tracing::warn!(
+                target: "obs_health_check",
+                error = %e.message(),
+                "Health check ping failed"
+            );
+        }
+
+        let current_state = client.connection_state().await;
+        if current_state != previous_state {
+            on_state_changed(ConnectionChangedPayload {
+                previous_state,
+                current_state,
+                host: None,
+                port: None,
+            });
+        }
+        if current_state == ConnectionState::Disconnected {
+            return;
+        }
+    }
 }
 
 /// バックグラウンド再接続タスク
@@ -218,6 +324,19 @@ mod tests {
         assert!(handle.is_none());
     }
 
+    #[tokio::test]
+    async fn test_health_check_start_and_stop() {
+        let manager = ReconnectManager::new();
+        let callback: HealthCheckCallback = Arc::new(|_| {});
+        manager
+            .start_health_check(ObsClient::new(), Duration::from_secs(30), callback)
+            .await;
+        assert!(manager.is_health_check_running().await);
+
+        manager.stop_health_check().await;
+        assert!(!manager.is_health_check_running().await);
+    }
+
     #[tokio::test]
     async fn test_reconnect_handle_is_finished() {
         let (cancel_tx, _) = watch::channel(false);
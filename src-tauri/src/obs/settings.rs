@@ -5,8 +5,20 @@
 
 use crate::error::AppError;
 use crate::obs::get_obs_client;
+use crate::services::alerts::AlertSeverity;
 use serde::{Deserialize, Serialize};
 
+/// 配信に使用できるビットレートの下限（kbps）
+const MIN_BITRATE_KBPS: u32 = 500;
+
+/// キーフレーム間隔の上限（秒）
+///
+/// 主要な配信プラットフォームは2〜4秒を推奨しており、10秒を超えると視聴開始・シークが極端に遅くなる
+const MAX_KEYFRAME_INTERVAL_SECS: u32 = 10;
+
+/// OBSで選択できるサンプルレート（Hz）
+const SUPPORTED_SAMPLE_RATES: [u32; 2] = [44100, 48000];
+
 /// OBSの現在の設定全体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +31,124 @@ pub struct ObsSettings {
     pub output: OutputSettings,
 }
 
+impl ObsSettings {
+    /// 設定値の整合性を検証
+    ///
+    /// 出力解像度が基本解像度を超えている、ビットレートが低すぎる等の
+    /// 不整合を項目ごとの警告として返す。問題がない場合は空
+    pub fn validate(&self) -> Vec<SettingsValidationWarning> {
+        let mut warnings = Vec::new();
+        let video = &self.video;
+        let output = &self.output;
+        let audio = &self.audio;
+
+        if video.output_width > video.base_width {
+            warnings.push(SettingsValidationWarning::new(
+                "video.outputWidth",
+                video.output_width,
+                &format!("出力解像度の幅が基本解像度の幅（{}）を超えています", video.base_width),
+                AlertSeverity::Warning,
+            ));
+        }
+        if video.output_height > video.base_height {
+            warnings.push(SettingsValidationWarning::new(
+                "video.outputHeight",
+                video.output_height,
+                &format!("出力解像度の高さが基本解像度の高さ（{}）を超えています", video.base_height),
+                AlertSeverity::Warning,
+            ));
+        }
+        if video.fps_denominator == 0 {
+            warnings.push(SettingsValidationWarning::new(
+                "video.fpsDenominator",
+                video.fps_denominator,
+                "FPSの分母が0のため、フレームレートを決定できません",
+                AlertSeverity::Critical,
+            ));
+        }
+
+        if output.encoder.trim().is_empty() {
+            warnings.push(SettingsValidationWarning::new(
+                "output.encoder",
+                &output.encoder,
+                "エンコーダーが設定されていません",
+                AlertSeverity::Critical,
+            ));
+        }
+        if output.bitrate_kbps < MIN_BITRATE_KBPS {
+            // 0kbpsでは配信できないためクリティカル、それ以外は画質が極端に低下する
+            let severity = if output.bitrate_kbps == 0 {
+                AlertSeverity::Critical
+            } else {
+                AlertSeverity::Warning
+            };
+            warnings.push(SettingsValidationWarning::new(
+                "output.bitrateKbps",
+                output.bitrate_kbps,
+                &format!("ビットレートが{MIN_BITRATE_KBPS}kbps未満です"),
+                severity,
+            ));
+        }
+        if output.keyframe_interval_secs > MAX_KEYFRAME_INTERVAL_SECS {
+            warnings.push(SettingsValidationWarning::new(
+                "output.keyframeIntervalSecs",
+                output.keyframe_interval_secs,
+                &format!("キーフレーム間隔が{MAX_KEYFRAME_INTERVAL_SECS}秒を超えています"),
+                AlertSeverity::Warning,
+            ));
+        }
+
+        if !SUPPORTED_SAMPLE_RATES.contains(&audio.sample_rate) {
+            warnings.push(SettingsValidationWarning::new(
+                "audio.sampleRate",
+                audio.sample_rate,
+                "サンプルレートは44100Hzまたは48000Hzを指定してください",
+                AlertSeverity::Warning,
+            ));
+        }
+        if !matches!(audio.channels, 1 | 2) {
+            warnings.push(SettingsValidationWarning::new(
+                "audio.channels",
+                audio.channels,
+                "チャンネル数はモノラル（1）またはステレオ（2）を指定してください",
+                AlertSeverity::Warning,
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// 設定値の整合性チェックの警告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsValidationWarning {
+    /// 設定項目（例: "video.outputWidth"）
+    pub field: String,
+    /// 現在の値
+    pub value: String,
+    /// 警告メッセージ
+    pub message: String,
+    /// 重要度
+    pub severity: AlertSeverity,
+}
+
+impl SettingsValidationWarning {
+    fn new(field: &str, value: impl ToString, message: &str, severity: AlertSeverity) -> Self {
+        Self {
+            field: field.to_string(),
+            value: value.to_string(),
+            message: message.to_string(),
+            severity,
+        }
+    }
+
+    /// 適用を中止すべき警告か
+    pub fn is_critical(&self) -> bool {
+        self.severity == AlertSeverity::Critical
+    }
+}
+
 /// ビデオ設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -98,15 +228,42 @@ pub struct OutputSettings {
     pub encoder: String,
     /// ビットレート（kbps）
     pub bitrate_kbps: u32,
-    /// キーフレーム間隔（秒）
+    /// キーフレーム間隔（秒、0は自動）
     pub keyframe_interval_secs: u32,
     /// プリセット（x264/x265の場合）
     pub preset: Option<String>,
     /// レート制御モード（CBR/VBR/CQP等）
     pub rate_control: Option<String>,
+    /// 録画フォーマット（`RecFormat2`、例: mkv, mp4, fragmented_mp4）
+    #[serde(default)]
+    pub recording_format: Option<String>,
+    /// 録画の保存先フォルダ
+    #[serde(default)]
+    pub recording_path: Option<String>,
+    /// Bフレーム数（取得できない場合はNone）
+    #[serde(default)]
+    pub b_frames: Option<u32>,
+}
+
+/// クラッシュ時に録画ファイル全体が失われるフォーマット
+///
+/// MP4・MOVは録画終了時にインデックスを書き込むため、OBSが異常終了すると再生できなくなる。
+/// フラグメント化MP4/MOV・ハイブリッドMP4は対象外
+const CRASH_UNSAFE_RECORDING_FORMATS: &[&str] = &["mp4", "mov"];
+
+/// 録画フォーマット（`RecFormat2` の値）がクラッシュ時にファイル全体を失うもの（MP4・MOV）か
+pub fn is_crash_unsafe_recording_format(format: &str) -> bool {
+    CRASH_UNSAFE_RECORDING_FORMATS.contains(&format.to_lowercase().as_str())
 }
 
 impl OutputSettings {
+    /// 録画フォーマットがクラッシュ時にファイル全体を失うもの（MP4・MOV）か
+    ///
+    /// フォーマットを取得できていない場合はfalse
+    pub fn has_crash_unsafe_recording_format(&self) -> bool {
+        self.recording_format.as_deref().is_some_and(is_crash_unsafe_recording_format)
+    }
+
     /// エンコーダータイプを判定
     pub fn encoder_type(&self) -> EncoderType {
         let encoder_lower = self.encoder.to_lowercase();
@@ -153,6 +310,102 @@ pub enum EncoderType {
     Other,
 }
 
+/// OBSの出力モード（設定 > 出力 の「出力モード」）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObsOutputMode {
+    /// 基本（Bフレーム・マルチパス・Look-ahead等のエンコーダー詳細設定を変更できない）
+    #[default]
+    Simple,
+    /// 詳細
+    Advanced,
+}
+
+impl ObsOutputMode {
+    /// プロファイルパラメータ（Output/Mode）の値から変換
+    ///
+    /// 未設定・不明な値はOBSの既定と同じ基本モードとみなす
+    pub fn from_profile_value(value: Option<&str>) -> Self {
+        match value {
+            Some(mode) if mode.eq_ignore_ascii_case("Advanced") => Self::Advanced,
+            _ => Self::Simple,
+        }
+    }
+
+    /// プロファイルパラメータ（Output/Mode）に設定する値
+    pub const fn profile_value(self) -> &'static str {
+        match self {
+            Self::Simple => "Simple",
+            Self::Advanced => "Advanced",
+        }
+    }
+
+    /// 表示名
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Simple => "基本",
+            Self::Advanced => "詳細",
+        }
+    }
+}
+
+/// OBSプロファイルから現在の出力モードを取得
+pub async fn get_output_mode(client: &super::ObsClient) -> Result<ObsOutputMode, AppError> {
+    let value = client.get_profile_parameter("Output", "Mode").await?;
+    Ok(ObsOutputMode::from_profile_value(value.as_deref()))
+}
+
+/// OBSプロファイルの出力モードを切り替え
+pub async fn set_output_mode(client: &super::ObsClient, mode: ObsOutputMode) -> Result<(), AppError> {
+    client
+        .set_profile_parameter("Output", "Mode", Some(mode.profile_value()))
+        .await?;
+    Ok(())
+}
+
+/// 録画の保存先とフォーマットをプロファイルパラメータから取得
+///
+/// 出力モードによって参照するセクションが異なる（基本: `SimpleOutput`、詳細: `AdvOut`）。
+/// OBS 30より前の `RecFormat` にもフォールバックする
+///
+/// # Returns
+/// （保存先フォルダ, 録画フォーマット）
+pub async fn get_recording_output(
+    client: &super::ObsClient,
+) -> Result<(Option<String>, Option<String>), AppError> {
+    let (section, path_key) = match get_output_mode(client).await? {
+        ObsOutputMode::Simple => ("SimpleOutput", "FilePath"),
+        ObsOutputMode::Advanced => ("AdvOut", "RecFilePath"),
+    };
+    let non_empty = |value: Option<String>| value.filter(|v| !v.is_empty());
+
+    let path = non_empty(client.get_profile_parameter(section, path_key).await?);
+    let mut format = non_empty(client.get_profile_parameter(section, "RecFormat2").await?);
+    if format.is_none() {
+        format = non_empty(client.get_profile_parameter(section, "RecFormat").await?);
+    }
+    Ok((path, format))
+}
+
+/// 配信のキーフレーム間隔とBフレーム数をプロファイルパラメータから取得
+///
+/// 出力モードによって参照するパラメータが異なる（基本: `SimpleOutput/VKeyIntSec`、詳細: `AdvOut/KeyIntSec`）。
+/// Bフレーム数はどちらのモードも `bf`。キーフレーム間隔の0は「自動」
+///
+/// # Returns
+/// （キーフレーム間隔（秒）, Bフレーム数）。未設定・数値でない場合はそれぞれNone
+pub async fn get_gop_settings(client: &super::ObsClient) -> Result<(Option<u32>, Option<u32>), AppError> {
+    let (section, keyframe_key) = match get_output_mode(client).await? {
+        ObsOutputMode::Simple => ("SimpleOutput", "VKeyIntSec"),
+        ObsOutputMode::Advanced => ("AdvOut", "KeyIntSec"),
+    };
+    let parse = |value: Option<String>| value.and_then(|v| v.trim().parse::<u32>().ok());
+
+    let keyframe_interval = parse(client.get_profile_parameter(section, keyframe_key).await?);
+    let b_frames = parse(client.get_profile_parameter(section, "bf").await?);
+    Ok((keyframe_interval, b_frames))
+}
+
 /// 配信出力のエンコーダー設定を取得するための構造体
 #[derive(Debug, Clone, Deserialize)]
 struct StreamEncoderSettings {
@@ -168,6 +421,9 @@ struct StreamEncoderSettings {
     /// キーフレーム間隔
     #[serde(default, alias = "keyint_sec")]
     keyframe_interval: Option<u32>,
+    /// Bフレーム数
+    #[serde(default)]
+    bf: Option<u32>,
 }
 
 /// OBSの現在の設定を取得
@@ -178,13 +434,29 @@ pub async fn get_obs_settings() -> Result<ObsSettings, AppError> {
     let client = get_obs_client();
 
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     // obws APIを使用して実際のOBS設定を取得
     let video_settings = get_video_settings_from_obs(&client).await?;
     let audio_settings = get_audio_settings_from_obs()?;
-    let output_settings = get_output_settings_from_obs(&client).await?;
+    let mut output_settings = get_output_settings_from_obs(&client).await?;
+
+    // 録画の保存先・フォーマット（取得できない場合は省略）
+    if let Ok((path, format)) = get_recording_output(&client).await {
+        output_settings.recording_path = path;
+        output_settings.recording_format = format;
+    }
+
+    // キーフレーム間隔・Bフレーム数（プロファイルに保存されている値を優先、取得できない場合は省略）
+    if let Ok((keyframe_interval, b_frames)) = get_gop_settings(&client).await {
+        if let Some(secs) = keyframe_interval {
+            output_settings.keyframe_interval_secs = secs;
+        }
+        if b_frames.is_some() {
+            output_settings.b_frames = b_frames;
+        }
+    }
 
     Ok(ObsSettings {
         video: video_settings,
@@ -247,6 +519,9 @@ async fn get_output_settings_from_obs(client: &super::ObsClient) -> Result<Outpu
                         keyframe_interval_secs: settings.keyframe_interval.unwrap_or(2),
                         preset: settings.preset,
                         rate_control: settings.rate_control,
+                        recording_format: None,
+                        recording_path: None,
+                        b_frames: settings.bf,
                     });
                 }
             }
@@ -275,43 +550,192 @@ fn default_output_settings() -> OutputSettings {
         keyframe_interval_secs: 2,
         preset: None,
         rate_control: Some("CBR".to_string()),
+        recording_format: None,
+        recording_path: None,
+        b_frames: None,
     }
 }
 
-/// 推奨ビデオ設定をOBSに適用
+/// 推奨ビデオ設定をOBSに適用し、反映されたことを確認
+///
+/// OBSは範囲外の値を黙って丸めることがあるため、適用後にビデオ設定を読み戻して
+/// 指定した値と比較する。一致しない場合は1回だけ再適用し、
+/// それでも一致しない項目は `VideoApplyResult` の `verified` がfalseになる。
 ///
 /// # Arguments
 /// * `output_width` - 出力解像度の幅
 /// * `output_height` - 出力解像度の高さ
-/// * `fps` - フレームレート
+/// * `fps_numerator` - フレームレートの分子（例: 30000）
+/// * `fps_denominator` - フレームレートの分母（例: 1001。整数FPSは1）
 pub async fn apply_video_settings(
     output_width: u32,
     output_height: u32,
-    fps: u32,
-) -> Result<(), AppError> {
+    fps_numerator: u32,
+    fps_denominator: u32,
+) -> Result<VideoApplyResult, AppError> {
+    if fps_numerator == 0 || fps_denominator == 0 {
+        return Err(AppError::validation_failed("フレームレートの分子・分母は1以上で指定してください"));
+    }
+
     let client = get_obs_client();
 
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     // 現在のビデオ設定を取得してベース解像度を維持
     let current = client.get_video_settings().await?;
-
-    // obws の SetVideoSettings を構築
-    use obws::requests::config::SetVideoSettings;
-    let settings = SetVideoSettings {
-        fps_numerator: Some(fps),
-        fps_denominator: Some(1),
-        base_width: Some(current.base_width), // ベース解像度は維持
-        base_height: Some(current.base_height),
-        output_width: Some(output_width),
-        output_height: Some(output_height),
+    let target = VideoSettings {
+        base_width: current.base_width,
+        base_height: current.base_height,
+        output_width,
+        output_height,
+        fps_numerator,
+        fps_denominator,
     };
 
-    client.set_video_settings(settings).await?;
+    let client = &client;
+    apply_and_verify(
+        &target,
+        |target| async move {
+            // obws の SetVideoSettings を構築
+            use obws::requests::config::SetVideoSettings;
+            let settings = SetVideoSettings {
+                fps_numerator: Some(target.fps_numerator),
+                fps_denominator: Some(target.fps_denominator),
+                base_width: Some(target.base_width),
+                base_height: Some(target.base_height),
+                output_width: Some(target.output_width),
+                output_height: Some(target.output_height),
+            };
+            client.set_video_settings(settings).await
+        },
+        || get_video_settings_from_obs(client),
+    )
+    .await
+}
 
-    Ok(())
+/// ビデオ設定を書き込み、読み戻して検証する（不一致なら1回だけ再適用）
+///
+/// 書き込み・読み込みをクロージャで受け取るため、OBSに接続せずに検証処理を試験できる
+async fn apply_and_verify<Set, SetFut, Get, GetFut>(
+    target: &VideoSettings,
+    mut set: Set,
+    mut get: Get,
+) -> Result<VideoApplyResult, AppError>
+where
+    Set: FnMut(VideoSettings) -> SetFut,
+    SetFut: std::future::Future<Output = Result<(), AppError>>,
+    Get: FnMut() -> GetFut,
+    GetFut: std::future::Future<Output = Result<VideoSettings, AppError>>,
+{
+    set(target.clone()).await?;
+    let result = VideoApplyResult::compare(target, &get().await?, false);
+    if result.is_verified() {
+        return Ok(result);
+    }
+
+    tracing::warn!(
+        target: "obs_settings",
+        mismatches = ?result.mismatch_labels(),
+        "ビデオ設定が指定値と一致しないため再適用します"
+    );
+    set(target.clone()).await?;
+    let result = VideoApplyResult::compare(target, &get().await?, true);
+    if !result.is_verified() {
+        tracing::warn!(
+            target: "obs_settings",
+            mismatches = ?result.mismatch_labels(),
+            "再適用後もビデオ設定が指定値と一致しません"
+        );
+    }
+    Ok(result)
+}
+
+/// 読み戻しで検証した設定項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedValue {
+    /// 指定した値
+    pub intended: u32,
+    /// OBSから読み戻した値
+    pub actual: u32,
+    /// 指定した値がそのまま反映されたか
+    pub verified: bool,
+}
+
+impl VerifiedValue {
+    const fn new(intended: u32, actual: u32) -> Self {
+        Self {
+            intended,
+            actual,
+            verified: intended == actual,
+        }
+    }
+}
+
+/// ビデオ設定の適用結果（項目ごとの検証結果）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoApplyResult {
+    /// 出力解像度（幅）
+    pub output_width: VerifiedValue,
+    /// 出力解像度（高さ）
+    pub output_height: VerifiedValue,
+    /// フレームレート（分子）
+    pub fps_numerator: VerifiedValue,
+    /// フレームレート（分母）
+    pub fps_denominator: VerifiedValue,
+    /// 読み戻しが一致せず再適用したか
+    pub retried: bool,
+}
+
+impl VideoApplyResult {
+    /// 指定値と読み戻した値を比較
+    const fn compare(target: &VideoSettings, actual: &VideoSettings, retried: bool) -> Self {
+        Self {
+            output_width: VerifiedValue::new(target.output_width, actual.output_width),
+            output_height: VerifiedValue::new(target.output_height, actual.output_height),
+            fps_numerator: VerifiedValue::new(target.fps_numerator, actual.fps_numerator),
+            fps_denominator: VerifiedValue::new(target.fps_denominator, actual.fps_denominator),
+            retried,
+        }
+    }
+
+    /// 項目名と検証結果の一覧
+    const fn fields(&self) -> [(&'static str, VerifiedValue); 4] {
+        [
+            ("出力解像度（幅）", self.output_width),
+            ("出力解像度（高さ）", self.output_height),
+            ("FPS（分子）", self.fps_numerator),
+            ("FPS（分母）", self.fps_denominator),
+        ]
+    }
+
+    /// すべての項目が指定どおりに反映されたか
+    pub fn is_verified(&self) -> bool {
+        self.fields().iter().all(|(_, value)| value.verified)
+    }
+
+    /// 一致しなかった項目（例: "出力解像度（幅） 3840 → 1920"）
+    fn mismatch_labels(&self) -> Vec<String> {
+        self.fields()
+            .iter()
+            .filter(|(_, value)| !value.verified)
+            .map(|(label, value)| format!("{label} {} → {}", value.intended, value.actual))
+            .collect()
+    }
+
+    /// 指定どおりに反映されなかった場合の警告メッセージ（すべて一致した場合はNone）
+    pub fn warning(&self) -> Option<String> {
+        if self.is_verified() {
+            return None;
+        }
+        Some(format!(
+            "ビデオ設定の一部がOBSで変更されました（{}）",
+            self.mismatch_labels().join("、")
+        ))
+    }
 }
 
 /// 推奨設定をまとめてOBSに適用
@@ -329,17 +753,18 @@ pub async fn apply_recommended_settings_to_obs(
     let client = get_obs_client();
 
     if !client.is_connected().await {
-        return Err(AppError::obs_state("OBSに接続されていません"));
+        return Err(AppError::obs_not_connected());
     }
 
     let mut result = ApplyResult::default();
 
     // ビデオ設定を適用
     if let Some((width, height, fps)) = video {
-        match apply_video_settings(width, height, fps).await {
-            Ok(()) => {
-                result.applied.push("video".to_string());
-            }
+        match apply_video_settings(width, height, fps, 1).await {
+            Ok(video) => match video.warning() {
+                None => result.applied.push("video".to_string()),
+                Some(warning) => result.failed.push(format!("video: {warning}")),
+            },
             Err(e) => {
                 result.failed.push(format!("video: {}", e));
             }
@@ -520,6 +945,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(nvenc.encoder_type(), EncoderType::NvencH264);
         assert!(nvenc.is_hardware_encoder());
@@ -530,11 +958,35 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: Some("veryfast".to_string()),
             rate_control: Some("CBR".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(x264.encoder_type(), EncoderType::X264);
         assert!(!x264.is_hardware_encoder());
     }
 
+    #[test]
+    fn test_crash_unsafe_recording_format() {
+        let with_format = |format: Option<&str>| OutputSettings {
+            encoder: "obs_x264".to_string(),
+            bitrate_kbps: 6000,
+            keyframe_interval_secs: 2,
+            preset: None,
+            rate_control: None,
+            recording_format: format.map(str::to_string),
+            recording_path: None,
+            b_frames: None,
+        };
+
+        assert!(with_format(Some("mp4")).has_crash_unsafe_recording_format());
+        assert!(with_format(Some("MOV")).has_crash_unsafe_recording_format());
+        assert!(!with_format(Some("mkv")).has_crash_unsafe_recording_format());
+        assert!(!with_format(Some("fragmented_mp4")).has_crash_unsafe_recording_format());
+        assert!(!with_format(Some("hybrid_mp4")).has_crash_unsafe_recording_format());
+        assert!(!with_format(None).has_crash_unsafe_recording_format());
+    }
+
     #[test]
     fn test_encoder_type_nvenc_nvidia() {
         let encoder = OutputSettings {
@@ -543,6 +995,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::NvencH264);
     }
@@ -555,6 +1010,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::QuickSync);
         assert!(encoder.is_hardware_encoder());
@@ -568,6 +1026,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::AmdVce);
         assert!(encoder.is_hardware_encoder());
@@ -581,6 +1042,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: Some("medium".to_string()),
             rate_control: Some("CRF".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::X265);
         assert!(!encoder.is_hardware_encoder());
@@ -594,6 +1058,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::X265);
     }
@@ -606,6 +1073,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::Other);
         assert!(!encoder.is_hardware_encoder());
@@ -619,6 +1089,9 @@ mod tests {
             keyframe_interval_secs: 2,
             preset: None,
             rate_control: None,
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         };
         assert_eq!(encoder.encoder_type(), EncoderType::NvencH264);
     }
@@ -644,6 +1117,9 @@ mod tests {
                 keyframe_interval_secs: 2,
                 preset: Some("veryfast".to_string()),
                 rate_control: Some("CBR".to_string()),
+                recording_format: None,
+                recording_path: None,
+                b_frames: None,
             },
         };
 
@@ -662,4 +1138,285 @@ mod tests {
         let deserialized: EncoderType = serde_json::from_str(&json).expect("deserialization failed");
         assert_eq!(deserialized, EncoderType::NvencH264);
     }
+
+    #[test]
+    fn test_output_mode_from_profile_value() {
+        assert_eq!(ObsOutputMode::from_profile_value(Some("Advanced")), ObsOutputMode::Advanced);
+        assert_eq!(ObsOutputMode::from_profile_value(Some("advanced")), ObsOutputMode::Advanced);
+        assert_eq!(ObsOutputMode::from_profile_value(Some("Simple")), ObsOutputMode::Simple);
+        // 未設定の場合はOBSの既定（基本モード）
+        assert_eq!(ObsOutputMode::from_profile_value(None), ObsOutputMode::Simple);
+    }
+
+    #[test]
+    fn test_output_mode_profile_value_round_trip() {
+        for mode in [ObsOutputMode::Simple, ObsOutputMode::Advanced] {
+            assert_eq!(ObsOutputMode::from_profile_value(Some(mode.profile_value())), mode);
+        }
+        let json = serde_json::to_string(&ObsOutputMode::Advanced).expect("serialization failed");
+        assert_eq!(json, "\"advanced\"");
+    }
+
+    fn valid_obs_settings() -> ObsSettings {
+        ObsSettings {
+            video: VideoSettings {
+                base_width: 1920,
+                base_height: 1080,
+                output_width: 1280,
+                output_height: 720,
+                fps_numerator: 60,
+                fps_denominator: 1,
+            },
+            audio: AudioSettings {
+                sample_rate: 48000,
+                channels: 2,
+            },
+            output: OutputSettings {
+                encoder: "jim_nvenc".to_string(),
+                bitrate_kbps: 6000,
+                keyframe_interval_secs: 2,
+                preset: Some("p5".to_string()),
+                rate_control: Some("CBR".to_string()),
+                recording_format: None,
+                recording_path: None,
+                b_frames: None,
+            },
+        }
+    }
+
+    /// 警告が1件だけで、指定した項目・重要度であることを確認
+    fn assert_single_warning(settings: &ObsSettings, field: &str, severity: AlertSeverity) {
+        let warnings = settings.validate();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert_eq!(warnings[0].field, field);
+        assert_eq!(warnings[0].severity, severity);
+    }
+
+    #[test]
+    fn test_validate_valid_settings() {
+        assert!(valid_obs_settings().validate().is_empty());
+
+        // 境界値も有効
+        let mut settings = valid_obs_settings();
+        settings.video.output_width = 1920;
+        settings.video.output_height = 1080;
+        settings.output.bitrate_kbps = MIN_BITRATE_KBPS;
+        settings.output.keyframe_interval_secs = MAX_KEYFRAME_INTERVAL_SECS;
+        settings.audio.sample_rate = 44100;
+        settings.audio.channels = 1;
+        assert!(settings.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_output_width_exceeds_base() {
+        let mut settings = valid_obs_settings();
+        settings.video.output_width = 2560;
+        assert_single_warning(&settings, "video.outputWidth", AlertSeverity::Warning);
+        assert_eq!(settings.validate()[0].value, "2560");
+    }
+
+    #[test]
+    fn test_validate_output_height_exceeds_base() {
+        let mut settings = valid_obs_settings();
+        settings.video.output_height = 1440;
+        assert_single_warning(&settings, "video.outputHeight", AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validate_zero_fps_denominator() {
+        let mut settings = valid_obs_settings();
+        settings.video.fps_denominator = 0;
+        assert_single_warning(&settings, "video.fpsDenominator", AlertSeverity::Critical);
+        assert!(settings.validate()[0].is_critical());
+    }
+
+    #[test]
+    fn test_validate_low_bitrate() {
+        let mut settings = valid_obs_settings();
+        settings.output.bitrate_kbps = MIN_BITRATE_KBPS - 1;
+        assert_single_warning(&settings, "output.bitrateKbps", AlertSeverity::Warning);
+
+        settings.output.bitrate_kbps = 0;
+        assert_single_warning(&settings, "output.bitrateKbps", AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_validate_empty_encoder() {
+        let mut settings = valid_obs_settings();
+        settings.output.encoder = "  ".to_string();
+        assert_single_warning(&settings, "output.encoder", AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_validate_long_keyframe_interval() {
+        let mut settings = valid_obs_settings();
+        settings.output.keyframe_interval_secs = MAX_KEYFRAME_INTERVAL_SECS + 1;
+        assert_single_warning(&settings, "output.keyframeIntervalSecs", AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validate_unsupported_sample_rate() {
+        let mut settings = valid_obs_settings();
+        settings.audio.sample_rate = 96000;
+        assert_single_warning(&settings, "audio.sampleRate", AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validate_unsupported_channels() {
+        let mut settings = valid_obs_settings();
+        settings.audio.channels = 6;
+        assert_single_warning(&settings, "audio.channels", AlertSeverity::Warning);
+
+        settings.audio.channels = 0;
+        assert_single_warning(&settings, "audio.channels", AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut settings = valid_obs_settings();
+        settings.video.fps_denominator = 0;
+        settings.output.encoder = String::new();
+        settings.audio.channels = 3;
+
+        let warnings = settings.validate();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(warnings.iter().filter(|w| w.is_critical()).count(), 2);
+    }
+
+    #[test]
+    fn test_validation_warning_serialization() {
+        let mut settings = valid_obs_settings();
+        settings.output.bitrate_kbps = 0;
+
+        let json = serde_json::to_value(&settings.validate()[0]).expect("serialization failed");
+        assert_eq!(json["field"], "output.bitrateKbps");
+        assert_eq!(json["value"], "0");
+        assert_eq!(json["severity"], "critical");
+    }
+
+    /// 指定した解像度・FPSのビデオ設定（ベース解像度は4K）
+    fn video_target(output_width: u32, output_height: u32, fps_numerator: u32, fps_denominator: u32) -> VideoSettings {
+        VideoSettings {
+            base_width: 3840,
+            base_height: 2160,
+            output_width,
+            output_height,
+            fps_numerator,
+            fps_denominator,
+        }
+    }
+
+    /// 出力解像度を1920x1080、FPSを60までに丸めるOBSを再現
+    fn clamp_like_obs(mut settings: VideoSettings) -> VideoSettings {
+        settings.output_width = settings.output_width.min(1920);
+        settings.output_height = settings.output_height.min(1080);
+        if settings.fps() > 60.0 {
+            settings.fps_numerator = 60;
+            settings.fps_denominator = 1;
+        }
+        settings
+    }
+
+    /// `apply` で書き込み値を変換するモックOBSに対して検証付きの適用を実行
+    ///
+    /// 戻り値は適用結果と書き込み回数
+    async fn apply_with_mock(
+        target: &VideoSettings,
+        apply: impl Fn(VideoSettings, usize) -> VideoSettings,
+    ) -> (VideoApplyResult, usize) {
+        let stored = std::cell::RefCell::new(video_target(1280, 720, 30, 1));
+        let writes = std::cell::Cell::new(0);
+
+        let result = apply_and_verify(
+            target,
+            |settings| {
+                writes.set(writes.get() + 1);
+                *stored.borrow_mut() = apply(settings, writes.get());
+                async { Ok(()) }
+            },
+            || {
+                let settings = stored.borrow().clone();
+                async move { Ok(settings) }
+            },
+        )
+        .await
+        .expect("apply failed");
+        (result, writes.get())
+    }
+
+    #[tokio::test]
+    async fn test_apply_and_verify_accepts_exact_values() {
+        let target = video_target(1920, 1080, 30000, 1001);
+        let (result, writes) = apply_with_mock(&target, |settings, _| clamp_like_obs(settings)).await;
+
+        assert_eq!(writes, 1);
+        assert!(result.is_verified());
+        assert!(!result.retried);
+        assert_eq!(result.fps_numerator.actual, 30000);
+        assert_eq!(result.fps_denominator.actual, 1001);
+        assert!(result.warning().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_apply_and_verify_detects_clamped_values() {
+        let target = video_target(3840, 2160, 120, 1);
+        let (result, writes) = apply_with_mock(&target, |settings, _| clamp_like_obs(settings)).await;
+
+        // 1回だけ再適用し、それでも一致しない項目を記録する
+        assert_eq!(writes, 2);
+        assert!(result.retried);
+        assert!(!result.is_verified());
+        assert_eq!(
+            result.output_width,
+            VerifiedValue {
+                intended: 3840,
+                actual: 1920,
+                verified: false
+            }
+        );
+        assert!(!result.output_height.verified);
+        assert!(!result.fps_numerator.verified);
+        assert!(result.fps_denominator.verified);
+
+        let warning = result.warning().expect("mismatch must produce a warning");
+        assert!(warning.contains("出力解像度（幅） 3840 → 1920"));
+        assert!(warning.contains("FPS（分子） 120 → 60"));
+        assert!(!warning.contains("分母"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_and_verify_succeeds_on_retry() {
+        // 1回目の書き込みだけOBSに無視される
+        let target = video_target(1920, 1080, 60, 1);
+        let (result, writes) = apply_with_mock(&target, |settings, write| {
+            if write == 1 {
+                video_target(1280, 720, 30, 1)
+            } else {
+                settings
+            }
+        })
+        .await;
+
+        assert_eq!(writes, 2);
+        assert!(result.retried);
+        assert!(result.is_verified());
+        assert!(result.warning().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_apply_video_settings_rejects_zero_fps() {
+        assert!(apply_video_settings(1920, 1080, 60, 0).await.is_err());
+        assert!(apply_video_settings(1920, 1080, 0, 1).await.is_err());
+    }
+
+    #[test]
+    fn test_video_apply_result_serialization() {
+        let target = video_target(1920, 1080, 60, 1);
+        let result = VideoApplyResult::compare(&target, &clamp_like_obs(target.clone()), false);
+
+        let json = serde_json::to_value(result).expect("serialization failed");
+        assert_eq!(json["outputWidth"]["intended"], 1920);
+        assert_eq!(json["fpsDenominator"]["verified"], true);
+        assert_eq!(json["retried"], false);
+    }
 }
@@ -0,0 +1,209 @@
+// OBS設定のキャッシュ
+//
+// `get_obs_settings_command` はフロントエンドから頻繁に呼ばれ、その度に
+// obs-websocketへ複数のリクエストを送る。短時間（デフォルト5秒）は取得済みの設定を返し、
+// 設定を変更するコマンドが成功した時点で破棄する。
+
+use crate::error::AppError;
+use crate::obs::settings::{get_obs_settings, ObsSettings};
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// キャッシュの有効期間のデフォルト（秒）
+pub const DEFAULT_OBS_SETTINGS_CACHE_TTL_SECS: u64 = 5;
+
+/// OBS設定のキャッシュ
+#[derive(Debug, Clone)]
+pub struct ObsSettingsCache {
+    /// 取得済みの設定（未取得・破棄済みの場合はNone）
+    settings: Option<ObsSettings>,
+    /// 取得日時（UNIXタイムスタンプ、秒）
+    fetched_at: i64,
+    /// 有効期間（秒）
+    ttl_secs: u64,
+}
+
+impl ObsSettingsCache {
+    /// 空のキャッシュを作成
+    pub const fn new(ttl_secs: u64) -> Self {
+        Self {
+            settings: None,
+            fetched_at: 0,
+            ttl_secs,
+        }
+    }
+
+    /// 有効期間内の設定を取得
+    ///
+    /// # Arguments
+    /// * `now` - 現在日時（UNIXタイムスタンプ、秒）
+    pub fn get(&self, now: i64) -> Option<&ObsSettings> {
+        let ttl = i64::try_from(self.ttl_secs).unwrap_or(i64::MAX);
+        self.settings.as_ref().filter(|_| now.saturating_sub(self.fetched_at) < ttl)
+    }
+
+    /// 取得した設定を保存
+    pub fn store(&mut self, settings: ObsSettings, now: i64) {
+        self.settings = Some(settings);
+        self.fetched_at = now;
+    }
+
+    /// キャッシュを破棄
+    pub fn invalidate(&mut self) {
+        self.settings = None;
+    }
+}
+
+impl Default for ObsSettingsCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_OBS_SETTINGS_CACHE_TTL_SECS)
+    }
+}
+
+/// グローバルなOBS設定のキャッシュ
+static OBS_SETTINGS_CACHE: Lazy<Arc<RwLock<ObsSettingsCache>>> =
+    Lazy::new(|| Arc::new(RwLock::new(ObsSettingsCache::default())));
+
+/// キャッシュが有効なら返し、期限切れなら `fetch` で取得して保存する
+///
+/// 取得に失敗した場合はキャッシュを変更しない
+async fn get_or_fetch<F, Fut>(cache: &RwLock<ObsSettingsCache>, now: i64, fetch: F) -> Result<ObsSettings, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<ObsSettings, AppError>>,
+{
+    if let Some(settings) = cache.read().await.get(now) {
+        return Ok(settings.clone());
+    }
+
+    let settings = fetch().await?;
+    cache.write().await.store(settings.clone(), now);
+    Ok(settings)
+}
+
+/// OBS設定を取得（有効期間内はキャッシュを返す）
+pub async fn get_cached_obs_settings() -> Result<ObsSettings, AppError> {
+    get_or_fetch(&OBS_SETTINGS_CACHE, chrono::Utc::now().timestamp(), get_obs_settings).await
+}
+
+/// OBS設定のキャッシュを破棄
+///
+/// OBSの設定を変更した後に呼び出す
+pub async fn invalidate_obs_settings_cache() {
+    OBS_SETTINGS_CACHE.write().await.invalidate();
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures::{low_spec_obs_settings, standard_obs_settings};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 呼び出し回数を数える取得処理
+    async fn counted_fetch(calls: &AtomicUsize, settings: ObsSettings) -> Result<ObsSettings, AppError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(settings)
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_within_ttl() {
+        let cache = RwLock::new(ObsSettingsCache::default());
+        let calls = AtomicUsize::new(0);
+
+        let first = get_or_fetch(&cache, 100, || counted_fetch(&calls, standard_obs_settings())).await.unwrap();
+        let second = get_or_fetch(&cache, 104, || counted_fetch(&calls, low_spec_obs_settings())).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.output.bitrate_kbps, second.output.bitrate_kbps);
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_after_ttl() {
+        let cache = RwLock::new(ObsSettingsCache::default());
+        let calls = AtomicUsize::new(0);
+
+        get_or_fetch(&cache, 100, || counted_fetch(&calls, standard_obs_settings())).await.unwrap();
+        let refreshed = get_or_fetch(&cache, 105, || counted_fetch(&calls, low_spec_obs_settings())).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(refreshed.output.bitrate_kbps, low_spec_obs_settings().output.bitrate_kbps);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_on_settings_change() {
+        let cache = RwLock::new(ObsSettingsCache::default());
+        let calls = AtomicUsize::new(0);
+
+        get_or_fetch(&cache, 100, || counted_fetch(&calls, standard_obs_settings())).await.unwrap();
+        cache.write().await.invalidate();
+        assert!(cache.read().await.get(100).is_none());
+
+        let refreshed = get_or_fetch(&cache, 101, || counted_fetch(&calls, low_spec_obs_settings())).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(refreshed.output.bitrate_kbps, low_spec_obs_settings().output.bitrate_kbps);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_error_keeps_cache_empty() {
+        let cache = RwLock::new(ObsSettingsCache::default());
+        let result = get_or_fetch(&cache, 100, || async { Err(AppError::obs_not_connected()) }).await;
+
+        assert!(result.is_err());
+        assert!(cache.read().await.get(100).is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_reads() {
+        let cache = Arc::new(RwLock::new(ObsSettingsCache::default()));
+        cache.write().await.store(standard_obs_settings(), 100);
+
+        let readers: Vec<_> = (0..16)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                tokio::spawn(async move {
+                    get_or_fetch(&cache, 101, || async { Err(AppError::obs_not_connected()) })
+                        .await
+                        .map(|settings| settings.output.bitrate_kbps)
+                })
+            })
+            .collect();
+
+        for reader in readers {
+            // 全て有効期間内のため、取得処理（エラー）は呼ばれない
+            assert_eq!(reader.await.unwrap().unwrap(), standard_obs_settings().output.bitrate_kbps);
+        }
+    }
+
+    /// キャッシュヒットとミスの速度比較（ミスはOBSへの往復を模した2msの待機）
+    ///
+    /// 実行: cargo test --release settings_cache::tests::bench_cache_hit_vs_miss -- --ignored --nocapture
+    #[tokio::test]
+    #[ignore]
+    async fn bench_cache_hit_vs_miss() {
+        const ITERATIONS: u32 = 100;
+        let slow_fetch = || async {
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            Ok(standard_obs_settings())
+        };
+
+        let cache = RwLock::new(ObsSettingsCache::default());
+        let start = std::time::Instant::now();
+        for i in 0..ITERATIONS {
+            // 毎回有効期間外の時刻を渡してミスさせる
+            get_or_fetch(&cache, i64::from(i) * 10, slow_fetch).await.unwrap();
+        }
+        let miss = start.elapsed() / ITERATIONS;
+
+        let start = std::time::Instant::now();
+        for _ in 0..ITERATIONS {
+            get_or_fetch(&cache, i64::from(ITERATIONS) * 10, slow_fetch).await.unwrap();
+        }
+        let hit = start.elapsed() / ITERATIONS;
+
+        println!("get_or_fetch x{ITERATIONS}: hit={hit:?} miss={miss:?}");
+        assert!(hit < miss);
+    }
+}
@@ -9,6 +9,7 @@
 use once_cell::sync::OnceCell;
 
 use super::client::ObsClient;
+use super::reconnect::ReconnectManager;
 
 /// `グローバルなObsClientインスタンス`
 ///
@@ -39,6 +40,16 @@ pub fn get_obs_client() -> ObsClient {
     OBS_CLIENT.get_or_init(ObsClient::new).clone()
 }
 
+/// `グローバルなReconnectManagerインスタンス`
+///
+/// ヘルスチェックなどのバックグラウンドタスクをアプリ全体で一元管理する
+static RECONNECT_MANAGER: OnceCell<ReconnectManager> = OnceCell::new();
+
+/// `ReconnectManagerへのアクセスを提供するヘルパー関数`
+pub fn get_reconnect_manager() -> &'static ReconnectManager {
+    RECONNECT_MANAGER.get_or_init(ReconnectManager::new)
+}
+
 /// ObsClientをリセット（主にテスト用）
 ///
 /// 注意: `OnceCell` は再初期化できないため、このメソッドは
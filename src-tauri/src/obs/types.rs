@@ -154,6 +154,12 @@ pub struct ObsStatus {
     pub render_dropped_frames: Option<u32>,
     /// 出力ドロップフレーム数
     pub output_dropped_frames: Option<u32>,
+    /// 直近のヘルスチェック（ping）の応答時間（ミリ秒）
+    pub last_ping_latency_ms: Option<u64>,
+    /// 直近にヘルスチェック（ping）が成功した日時（UNIX epoch秒）
+    pub last_ping_success_at: Option<i64>,
+    /// プライバシーモード（音声モニタリングの一括無効化）中か
+    pub privacy_mode_active: bool,
 }
 
 impl ObsStatus {
@@ -175,6 +181,32 @@ impl ObsStatus {
     }
 }
 
+/// 接続のヘルスチェック（ping）結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+    /// 往復時間（ミリ秒）
+    pub latency_ms: u64,
+    /// OBSのバージョン
+    pub obs_version: String,
+    /// `WebSocketサーバーのバージョン`
+    pub websocket_version: String,
+}
+
+/// 配信出力の統計（OBSの `GetOutputStatus` から算出）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStats {
+    /// 配信開始からの送出フレーム数
+    pub frames_sent: u64,
+    /// 配信開始からのドロップ（スキップ）フレーム数
+    pub frames_dropped: u64,
+    /// 現在のビットレート（kbps、前回の取得からの送信量で算出）
+    pub current_bitrate_kbps: f64,
+    /// 輻輳（0.0〜1.0、回線が詰まっているほど大きい）
+    pub congestion: f32,
+}
+
 /// 接続状態の変化を表す型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -187,6 +219,8 @@ pub enum ConnectionState {
     Connecting,
     /// 接続済み
     Connected,
+    /// 応答が不安定（接続は維持しているが、ヘルスチェックが連続で失敗している）
+    Degraded,
     /// 再接続中（将来使用予定）
     #[allow(dead_code)]
     Reconnecting,
@@ -195,6 +229,67 @@ pub enum ConnectionState {
 }
 
 
+/// 音声モニタリングの種類
+///
+/// 配信者がOBS経由でソースの音を聞くかどうか（詳細オーディオプロパティの「音声モニタリング」）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AudioMonitorType {
+    /// モニターオフ
+    #[default]
+    None,
+    /// モニターのみ（出力はミュート）
+    MonitorOnly,
+    /// モニターと出力
+    MonitorAndOutput,
+}
+
+impl AudioMonitorType {
+    /// 配信者がモニタリングで音を聞けるか
+    pub const fn is_monitoring(self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+impl From<obws::common::MonitorType> for AudioMonitorType {
+    fn from(value: obws::common::MonitorType) -> Self {
+        match value {
+            obws::common::MonitorType::MonitorOnly => Self::MonitorOnly,
+            obws::common::MonitorType::MonitorAndOutput => Self::MonitorAndOutput,
+            // None、および将来追加される種類
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<AudioMonitorType> for obws::common::MonitorType {
+    fn from(value: AudioMonitorType) -> Self {
+        match value {
+            AudioMonitorType::None => Self::None,
+            AudioMonitorType::MonitorOnly => Self::MonitorOnly,
+            AudioMonitorType::MonitorAndOutput => Self::MonitorAndOutput,
+        }
+    }
+}
+
+/// シーンアイテム情報
+///
+/// シーン内に配置されたソース1つ分。`id` はシーン内で一意な `sceneItemId`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneItem {
+    /// シーンアイテムID
+    pub id: i64,
+    /// ソース名
+    pub name: String,
+    /// ソースの種類（入力の場合は `inputKind`、それ以外は "scene" / "group"）
+    pub source_type: String,
+    /// 表示されているか
+    pub enabled: bool,
+    /// ロックされているか
+    pub locked: bool,
+}
+
 /// シーン情報（将来使用予定）
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize)]
@@ -350,4 +445,18 @@ mod tests {
         assert!(!status.streaming);
         assert!(!status.recording);
     }
+
+    #[test]
+    fn test_audio_monitor_type_conversion() {
+        for monitor_type in [
+            AudioMonitorType::None,
+            AudioMonitorType::MonitorOnly,
+            AudioMonitorType::MonitorAndOutput,
+        ] {
+            let obws_type: obws::common::MonitorType = monitor_type.into();
+            assert_eq!(AudioMonitorType::from(obws_type), monitor_type);
+        }
+        assert!(!AudioMonitorType::None.is_monitoring());
+        assert!(AudioMonitorType::MonitorAndOutput.is_monitoring());
+    }
 }
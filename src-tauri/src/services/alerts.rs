@@ -4,12 +4,30 @@
 // Tauriイベントシステムを使用してフロントエンドに通知
 
 use crate::error::AppError;
+use crate::events::{emit_event, TauriEvent};
+use crate::services::automation::evaluate_automation_rules;
+use crate::services::data_usage::QUOTA_ALERT_LEVELS;
 use crate::storage::config::AlertConfig;
+use crate::storage::metrics_history::{get_metrics_history_store, MetricsHistoryStore};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tauri::{AppHandle, Runtime};
+use tokio::sync::{mpsc, RwLock};
+
+/// アラート通知イベント名
+///
+/// フロントエンドはこのイベントを受信してアプリ内のアラートとして表示する
+pub const ALERT_NOTIFICATION_EVENT: &str = "alert:notification";
+
+/// 通知のデフォルトクールダウン（秒）
+///
+/// 閾値付近で値が上下するメトリクスが通知を連発しないようにする
+pub const DEFAULT_NOTIFICATION_COOLDOWN_SECS: u64 = 300;
+
+/// 通知アクション「提案を表示」で遷移する画面
+pub const NOTIFICATION_ROUTE_PROBLEMS: &str = "problems";
 
 /// アラート重要度
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -39,6 +57,55 @@ pub enum MetricType {
     FrameDropRate,
     /// ネットワーク帯域
     NetworkBandwidth,
+    /// 配信中にマイクがミュート・無音になっている継続秒数
+    MicrophoneSilence,
+    /// 月間のアップロード量の上限までの残り（GB）
+    DataQuota,
+}
+
+/// シーン切り替えの開始時にアラートを抑制する秒数の初期値
+pub const DEFAULT_SCENE_TRANSITION_SUPPRESSION_SECS: u64 = 5;
+
+/// 録画の開始時にアラートを抑制する秒数の初期値
+pub const DEFAULT_RECORDING_START_SUPPRESSION_SECS: u64 = 10;
+
+/// 手動でアラートを抑制できる秒数の上限（1時間）
+pub const MAX_MANUAL_SUPPRESSION_SECS: u64 = 3_600;
+
+/// アラートを抑制するきっかけ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuppressionTrigger {
+    /// シーン切り替えの開始
+    SceneTransitionStart,
+    /// 録画の開始
+    RecordingStart,
+    /// ユーザーによる手動の抑制
+    ManualOverride,
+}
+
+/// 短時間の負荷の急上昇が想定される操作の間、アラートを抑制するルール
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertSuppressionRule {
+    /// 抑制するきっかけ
+    pub trigger: SuppressionTrigger,
+    /// 抑制する秒数
+    pub duration_secs: u64,
+}
+
+/// アラート抑制ルールの初期値（シーン切り替え・録画開始）
+pub fn default_suppression_rules() -> Vec<AlertSuppressionRule> {
+    vec![
+        AlertSuppressionRule {
+            trigger: SuppressionTrigger::SceneTransitionStart,
+            duration_secs: DEFAULT_SCENE_TRANSITION_SUPPRESSION_SECS,
+        },
+        AlertSuppressionRule {
+            trigger: SuppressionTrigger::RecordingStart,
+            duration_secs: DEFAULT_RECORDING_START_SUPPRESSION_SECS,
+        },
+    ]
 }
 
 /// アラートルール（将来の動的アラート機能で使用予定）
@@ -77,6 +144,132 @@ pub struct Alert {
     pub active: bool,
 }
 
+/// アラート通知のアクション
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationAction {
+    /// アクションID
+    pub id: String,
+    /// ボタンに表示するラベル
+    pub label: String,
+    /// クリック時に遷移する画面
+    pub route: String,
+}
+
+/// アラート通知ペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertNotification {
+    /// 元になったアラートのID
+    pub alert_id: String,
+    /// 通知タイトル
+    pub title: String,
+    /// 通知本文
+    pub body: String,
+    /// 通知音を鳴らすか（`AlertConfig.play_sound`）
+    pub play_sound: bool,
+    /// 通知アクション（アクション対応プラットフォームでのみ表示）
+    pub actions: Vec<NotificationAction>,
+}
+
+/// 通知のレート制限
+///
+/// メトリクスごとに最後の通知時刻を記録し、クールダウン中の通知を抑制する
+#[derive(Debug, Clone)]
+pub struct NotificationRateLimiter {
+    /// クールダウン期間
+    cooldown: Duration,
+    /// メトリクスごとの最終通知時刻
+    last_sent: HashMap<MetricType, Instant>,
+}
+
+impl NotificationRateLimiter {
+    /// 新しいレート制限を作成
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// 通知してよいかを判定し、許可した場合は通知時刻を記録
+    ///
+    /// # Arguments
+    /// * `metric` - メトリクス種別
+    /// * `now` - 現在時刻
+    pub fn try_acquire(&mut self, metric: MetricType, now: Instant) -> bool {
+        if let Some(last) = self.last_sent.get(&metric) {
+            if now.saturating_duration_since(*last) < self.cooldown {
+                return false;
+            }
+        }
+        self.last_sent.insert(metric, now);
+        true
+    }
+
+    /// 記録をリセット
+    pub fn reset(&mut self) {
+        self.last_sent.clear();
+    }
+}
+
+impl Default for NotificationRateLimiter {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_NOTIFICATION_COOLDOWN_SECS))
+    }
+}
+
+/// アラート履歴への書き込みイベント
+#[derive(Debug, Clone)]
+pub enum AlertHistoryEvent {
+    /// アラートが発生した
+    Raised(Alert),
+    /// アラートが解消した
+    Resolved {
+        /// アラートID
+        alert_id: String,
+        /// 解消時刻（UNIX epoch秒）
+        cleared_at: i64,
+    },
+    /// すべてのアラートがクリアされた
+    ClearedAll {
+        /// クリア時刻（UNIX epoch秒）
+        cleared_at: i64,
+    },
+}
+
+/// アラート履歴の書き込みタスクを開始
+///
+/// SQLiteへの書き込みはチャネル経由でバックグラウンドタスクが行うため、
+/// メトリクス更新のホットパスをブロックしない
+pub fn spawn_history_writer(store: Arc<MetricsHistoryStore>) -> mpsc::UnboundedSender<AlertHistoryEvent> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<AlertHistoryEvent>();
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = store.initialize().await {
+            tracing::warn!(target: "alerts", error = %e, "アラート履歴データベースの初期化に失敗");
+        }
+
+        while let Some(event) = rx.recv().await {
+            let result = match event {
+                AlertHistoryEvent::Raised(alert) => store.record_alert(&alert).await.map(|_| ()),
+                AlertHistoryEvent::Resolved { alert_id, cleared_at } => {
+                    store.clear_alert(&alert_id, cleared_at).await
+                }
+                AlertHistoryEvent::ClearedAll { cleared_at } => {
+                    store.clear_all_open_alerts(cleared_at).await
+                }
+            };
+
+            if let Err(e) = result {
+                tracing::warn!(target: "alerts", error = %e, "アラート履歴の保存に失敗");
+            }
+        }
+    });
+
+    tx
+}
+
 /// メトリクスの状態追跡（将来の動的アラート機能で使用予定）
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -92,12 +285,26 @@ struct MetricState {
 /// アラートエンジン（将来の動的アラート機能で使用予定）
 #[allow(dead_code)]
 pub struct AlertEngine {
+    /// アラートが有効か（`AlertConfig.enabled`）
+    enabled: bool,
     /// アラートルール
     rules: Vec<AlertRule>,
     /// メトリクス状態（キーはMetricType + AlertSeverityの組み合わせ）
     states: Arc<RwLock<HashMap<(MetricType, AlertSeverity), MetricState>>>,
     /// アクティブなアラート
     active_alerts: Arc<RwLock<HashMap<String, Alert>>>,
+    /// アラート通知を表示するか
+    show_notification: bool,
+    /// 通知音を鳴らすか
+    play_sound: bool,
+    /// 通知のレート制限
+    notification_limiter: Arc<RwLock<NotificationRateLimiter>>,
+    /// アラート履歴の書き込み先（未設定の場合は永続化しない）
+    history_tx: Option<mpsc::UnboundedSender<AlertHistoryEvent>>,
+    /// アラートの抑制ルール
+    suppression_rules: Vec<AlertSuppressionRule>,
+    /// アラートを抑制する期限（抑制していない場合はNone）
+    suppressed_until: Mutex<Option<Instant>>,
 }
 
 #[allow(dead_code)]
@@ -157,17 +364,60 @@ impl AlertEngine {
                 duration_secs: config.alert_duration_secs,
                 severity: AlertSeverity::Critical,
             });
+
+            // マイク無音クリティカルルール
+            // 値自体が継続秒数のため、継続時間チェックは不要
+            rules.push(AlertRule {
+                metric: MetricType::MicrophoneSilence,
+                threshold: config.mic_silence_alert_secs.max(1) as f64,
+                duration_secs: 0,
+                severity: AlertSeverity::Critical,
+            });
         }
 
         Self {
+            enabled: config.enabled,
             rules,
             states: Arc::new(RwLock::new(HashMap::new())),
             active_alerts: Arc::new(RwLock::new(HashMap::new())),
+            show_notification: config.show_notification,
+            play_sound: config.play_sound,
+            notification_limiter: Arc::new(RwLock::new(NotificationRateLimiter::default())),
+            history_tx: None,
+            suppression_rules: config.suppression_rules.clone(),
+            suppressed_until: Mutex::new(None),
+        }
+    }
+
+    /// アラート履歴の永続化を有効化
+    ///
+    /// 発生・解消したアラートを非同期でストアに書き込む
+    pub fn with_history_store(mut self, store: Arc<MetricsHistoryStore>) -> Self {
+        self.history_tx = Some(spawn_history_writer(store));
+        self
+    }
+
+    /// アラート履歴イベントを送信
+    fn record_history(&self, event: AlertHistoryEvent) {
+        if let Some(tx) = &self.history_tx {
+            if tx.send(event).is_err() {
+                tracing::warn!(target: "alerts", "アラート履歴の書き込みタスクが停止しています");
+            }
         }
     }
 
+    /// 通知のクールダウン期間を変更
+    pub fn with_notification_cooldown(mut self, cooldown: Duration) -> Self {
+        self.notification_limiter = Arc::new(RwLock::new(NotificationRateLimiter::new(cooldown)));
+        self
+    }
+
     /// メトリクスを更新してアラートをチェック
     ///
+    /// 抑制中は評価せず、発火前の閾値超過の継続時間もリセットする
+    /// （抑制の解除直後に、抑制中の急上昇を含めた継続時間で発火しないようにする）。
+    /// 発火済みのアラートは抑制中もアクティブのまま維持する
+    ///
     /// # Arguments
     /// * `metric` - メトリクス種別
     /// * `value` - 現在の値
@@ -177,6 +427,16 @@ impl AlertEngine {
     pub async fn update_metric(&self, metric: MetricType, value: f64) -> Vec<Alert> {
         let mut new_alerts = Vec::new();
 
+        if self.is_suppressed() {
+            let mut states = self.states.write().await;
+            for ((state_metric, _), state) in states.iter_mut() {
+                if *state_metric == metric && !state.alert_triggered {
+                    state.started_at = None;
+                }
+            }
+            return new_alerts;
+        }
+
         for rule in &self.rules {
             if rule.metric != metric {
                 continue;
@@ -190,6 +450,86 @@ impl AlertEngine {
         new_alerts
     }
 
+    /// 指定した秒数の間、メトリクスのアラートを抑制
+    ///
+    /// 既に抑制中の場合は、期限が遅い方を残す
+    pub fn suppress_alerts_for(&self, secs: u64) {
+        self.suppress_alerts_until(Instant::now() + Duration::from_secs(secs));
+    }
+
+    /// 指定した時刻までメトリクスのアラートを抑制
+    fn suppress_alerts_until(&self, until: Instant) {
+        let mut suppressed_until = self.suppressed_until.lock().unwrap_or_else(PoisonError::into_inner);
+        if suppressed_until.is_none_or(|current| current < until) {
+            *suppressed_until = Some(until);
+        }
+    }
+
+    /// 抑制ルールに従ってアラートを抑制
+    ///
+    /// # Returns
+    /// 抑制した秒数（きっかけに対応するルールがない場合はNone）
+    pub fn suppress_for_trigger(&self, trigger: SuppressionTrigger) -> Option<u64> {
+        let duration_secs = self
+            .suppression_rules
+            .iter()
+            .find(|rule| rule.trigger == trigger)?
+            .duration_secs;
+        self.suppress_alerts_for(duration_secs);
+        Some(duration_secs)
+    }
+
+    /// メトリクスのアラートを抑制中か
+    pub fn is_suppressed(&self) -> bool {
+        self.is_suppressed_at(Instant::now())
+    }
+
+    /// 指定した時刻にメトリクスのアラートを抑制中か
+    fn is_suppressed_at(&self, now: Instant) -> bool {
+        self.suppressed_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some_and(|until| now < until)
+    }
+
+    /// アップロード量の上限までの残りを更新してアラートをチェック
+    ///
+    /// 残りが閾値（`QUOTA_ALERT_LEVELS`）を下回った時点で発火し、継続時間は問わない。
+    /// 上限が未設定（None）になった場合・リセットで残りが回復した場合は解消する
+    ///
+    /// # Arguments
+    /// * `remaining_gb` - 今月の上限までの残り（GB）
+    ///
+    /// # Returns
+    /// 新しく発火したアラートのリスト
+    pub async fn update_data_quota(&self, remaining_gb: Option<f64>) -> Vec<Alert> {
+        let mut new_alerts = Vec::new();
+        if !self.enabled {
+            return new_alerts;
+        }
+
+        for (severity, threshold) in QUOTA_ALERT_LEVELS {
+            let alert_id = format!("{:?}_{severity:?}", MetricType::DataQuota);
+            let active = self.active_alerts.read().await.contains_key(&alert_id);
+
+            match remaining_gb.filter(|remaining| *remaining < threshold) {
+                Some(remaining) if !active => {
+                    let rule = AlertRule {
+                        metric: MetricType::DataQuota,
+                        threshold,
+                        duration_secs: 0,
+                        severity,
+                    };
+                    new_alerts.push(self.create_alert(&rule, remaining).await);
+                }
+                None if active => self.resolve_alert(MetricType::DataQuota, severity).await,
+                _ => {}
+            }
+        }
+
+        new_alerts
+    }
+
     /// ルールをチェックしてアラートを生成
     async fn check_rule(&self, rule: &AlertRule, value: f64) -> Option<Alert> {
         let mut states = self.states.write().await;
@@ -257,6 +597,8 @@ impl AlertEngine {
         let mut active = self.active_alerts.write().await;
         active.insert(alert_id, alert.clone());
 
+        self.record_history(AlertHistoryEvent::Raised(alert.clone()));
+
         alert
     }
 
@@ -269,7 +611,12 @@ impl AlertEngine {
             alert.active = false;
         }
 
-        active.remove(&alert_id);
+        if active.remove(&alert_id).is_some() {
+            self.record_history(AlertHistoryEvent::Resolved {
+                alert_id,
+                cleared_at: chrono::Utc::now().timestamp(),
+            });
+        }
     }
 
     /// アラートメッセージを生成
@@ -313,7 +660,50 @@ impl AlertEngine {
                     "[{severity_text}] ネットワーク帯域が不足しています（{value:.1} Mbps）"
                 )
             }
+            MetricType::MicrophoneSilence => {
+                format!(
+                    "[{severity_text}] 配信中にマイクがミュートまたは無音の状態が{value:.0}秒続いています"
+                )
+            }
+            MetricType::DataQuota => {
+                format!(
+                    "[{severity_text}] 今月のアップロード量の上限まで残り{value:.1}GBです（{threshold:.0}GB未満）"
+                )
+            }
+        }
+    }
+
+    /// 発火したアラートからアラート通知を生成
+    ///
+    /// `show_notification` が有効な場合、Criticalアラートのみを対象とし、
+    /// クールダウン中のメトリクスは除外する
+    ///
+    /// # Arguments
+    /// * `alerts` - `update_metric` で新たに発火したアラート
+    pub async fn collect_notifications(&self, alerts: &[Alert]) -> Vec<AlertNotification> {
+        if !self.show_notification {
+            return Vec::new();
         }
+
+        let mut limiter = self.notification_limiter.write().await;
+        let now = Instant::now();
+
+        alerts
+            .iter()
+            .filter(|alert| alert.severity == AlertSeverity::Critical)
+            .filter(|alert| limiter.try_acquire(alert.metric, now))
+            .map(|alert| AlertNotification {
+                alert_id: alert.id.clone(),
+                title: notification_title(alert.metric).to_string(),
+                body: alert.message.clone(),
+                play_sound: self.play_sound,
+                actions: vec![NotificationAction {
+                    id: "show-suggestions".to_string(),
+                    label: "提案を表示".to_string(),
+                    route: NOTIFICATION_ROUTE_PROBLEMS.to_string(),
+                }],
+            })
+            .collect()
     }
 
     /// アクティブなアラート一覧を取得
@@ -330,18 +720,77 @@ impl AlertEngine {
         let mut states = self.states.write().await;
         states.clear();
 
+        let mut limiter = self.notification_limiter.write().await;
+        limiter.reset();
+
+        self.record_history(AlertHistoryEvent::ClearedAll {
+            cleared_at: chrono::Utc::now().timestamp(),
+        });
+
         Ok(())
     }
 }
 
+/// 通知タイトルを取得
+const fn notification_title(metric: MetricType) -> &'static str {
+    match metric {
+        MetricType::CpuUsage => "CPU使用率が危険な水準です",
+        MetricType::GpuUsage => "GPU使用率が危険な水準です",
+        MetricType::MemoryUsage => "メモリ使用率が危険な水準です",
+        MetricType::FrameDropRate => "フレームドロップが多発しています",
+        MetricType::NetworkBandwidth => "ネットワーク帯域が不足しています",
+        MetricType::MicrophoneSilence => "マイクの音声が配信に乗っていません",
+        MetricType::DataQuota => "アップロード量の上限が近づいています",
+    }
+}
+
+/// バッテリー残量が少ない状態で配信を開始した場合の通知
+///
+/// メトリクスのアラートとは別に、配信開始時に一度だけ発行する
+pub fn low_battery_notification(battery_percent: Option<u8>) -> AlertNotification {
+    let remaining = battery_percent.map_or_else(String::new, |percent| format!("（残量{percent}%）"));
+    AlertNotification {
+        alert_id: "low-battery".to_string(),
+        title: "バッテリー残量が少ない状態で配信を開始しました".to_string(),
+        body: format!(
+            "バッテリー駆動中{remaining}です。配信中に電源が切れたり、省電力でフレームドロップが増えたりするおそれがあるため、AC電源に接続してください"
+        ),
+        play_sound: false,
+        actions: vec![NotificationAction {
+            id: "show-suggestions".to_string(),
+            label: "提案を表示".to_string(),
+            route: NOTIFICATION_ROUTE_PROBLEMS.to_string(),
+        }],
+    }
+}
+
+/// アラート通知をフロントエンドに発行
+///
+/// 通知の表示はフロントエンド側でアプリ内のアラートとして行う
+pub fn emit_alert_notifications<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    notifications: &[AlertNotification],
+) -> Result<(), AppError> {
+    for notification in notifications {
+        emit_event(app_handle, TauriEvent::AlertNotification(notification.clone()))?;
+    }
+    Ok(())
+}
+
 /// グローバルアラートエンジンインスタンス
 static ALERT_ENGINE: once_cell::sync::Lazy<Arc<RwLock<Option<AlertEngine>>>> =
     once_cell::sync::Lazy::new(|| Arc::new(RwLock::new(None)));
 
-/// アラートエンジンを初期化（将来の動的アラート機能で使用予定）
-#[allow(dead_code)]
+/// アラートエンジンを初期化
 pub async fn initialize_alert_engine(config: &AlertConfig) {
-    let engine = AlertEngine::new(config);
+    let mut engine = AlertEngine::new(config);
+
+    // 履歴ストアが利用可能な場合はアラートを永続化
+    match get_metrics_history_store() {
+        Ok(store) => engine = engine.with_history_store(store),
+        Err(e) => tracing::warn!(target: "alerts", error = %e, "アラート履歴を永続化できません"),
+    }
+
     let mut global = ALERT_ENGINE.write().await;
     *global = Some(engine);
 }
@@ -356,9 +805,86 @@ pub async fn get_alert_engine() -> Option<Arc<RwLock<Option<AlertEngine>>>> {
     }
 }
 
+/// メトリクスを更新し、必要に応じてアラート通知を発行
+///
+/// 自動化ルールの評価もあわせて行う
+///
+/// # Returns
+/// 新しく発火したアラートのリスト
+pub async fn update_metric_and_notify<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    metric: MetricType,
+    value: f64,
+) -> Result<Vec<Alert>, AppError> {
+    evaluate_automation_rules(app_handle, metric, value).await;
+
+    let global = ALERT_ENGINE.read().await;
+    let Some(engine) = global.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    let alerts = engine.update_metric(metric, value).await;
+    let notifications = engine.collect_notifications(&alerts).await;
+    emit_alert_notifications(app_handle, &notifications)?;
+
+    Ok(alerts)
+}
+
+/// アップロード量の上限までの残りを更新し、必要に応じてアラート通知を発行
+///
+/// # Returns
+/// 新しく発火したアラートのリスト
+pub async fn update_data_quota_and_notify<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    remaining_gb: Option<f64>,
+) -> Result<Vec<Alert>, AppError> {
+    let global = ALERT_ENGINE.read().await;
+    let Some(engine) = global.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    let alerts = engine.update_data_quota(remaining_gb).await;
+    let notifications = engine.collect_notifications(&alerts).await;
+    emit_alert_notifications(app_handle, &notifications)?;
+
+    Ok(alerts)
+}
+
+/// 抑制ルールに従ってグローバルアラートエンジンのアラートを抑制
+///
+/// # Returns
+/// 抑制した秒数（エンジンが未初期化・対応するルールがない場合はNone）
+pub async fn suppress_alerts_for_trigger(trigger: SuppressionTrigger) -> Option<u64> {
+    let global = ALERT_ENGINE.read().await;
+    let duration_secs = global.as_ref()?.suppress_for_trigger(trigger)?;
+    tracing::debug!(target: "alerts", ?trigger, duration_secs, "アラートを抑制します");
+    Some(duration_secs)
+}
+
+/// グローバルアラートエンジンのアラートを指定した秒数の間抑制
+///
+/// # Returns
+/// エンジンが初期化済みで抑制した場合はtrue
+pub async fn suppress_alerts(duration_secs: u64) -> bool {
+    let global = ALERT_ENGINE.read().await;
+    let Some(engine) = global.as_ref() else {
+        return false;
+    };
+    engine.suppress_alerts_for(duration_secs);
+    tracing::info!(
+        target: "alerts",
+        trigger = ?SuppressionTrigger::ManualOverride,
+        duration_secs,
+        "アラートを抑制します"
+    );
+    true
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use crate::storage::metrics_history::AlertHistoryEntry;
 
     fn create_test_config() -> AlertConfig {
         AlertConfig {
@@ -372,6 +898,9 @@ mod tests {
             alert_duration_secs: 1, // テスト用に1秒に短縮
             play_sound: false,
             show_notification: false,
+            mic_silence_alert_secs: 30,
+            mic_silence_floor_db: -60.0,
+            suppression_rules: default_suppression_rules(),
         }
     }
 
@@ -380,7 +909,7 @@ mod tests {
         let config = create_test_config();
         let engine = AlertEngine::new(&config);
 
-        assert_eq!(engine.rules.len(), 6); // CPU x2, GPU x2, FrameDrop x2
+        assert_eq!(engine.rules.len(), 7); // CPU x2, GPU x2, FrameDrop x2, Mic x1
     }
 
     #[tokio::test]
@@ -601,4 +1130,273 @@ mod tests {
             "Critical閾値200.0は超えない"
         );
     }
+
+    #[tokio::test]
+    async fn test_critical_alert_creates_notification() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        config.show_notification = true;
+        config.play_sound = true;
+        let engine = AlertEngine::new(&config);
+
+        let alerts = engine.update_metric(MetricType::CpuUsage, 96.0).await;
+        let notifications = engine.collect_notifications(&alerts).await;
+
+        // WarningとCriticalが発火するが、通知はCriticalのみ
+        assert_eq!(notifications.len(), 1);
+        assert!(notifications[0].title.contains("CPU"));
+        assert!(notifications[0].play_sound);
+        assert_eq!(notifications[0].actions[0].route, NOTIFICATION_ROUTE_PROBLEMS);
+    }
+
+    #[test]
+    fn test_low_battery_notification() {
+        let notification = low_battery_notification(Some(18));
+        assert_eq!(notification.alert_id, "low-battery");
+        assert!(notification.body.contains("残量18%"));
+        assert_eq!(notification.actions[0].route, NOTIFICATION_ROUTE_PROBLEMS);
+
+        assert!(!low_battery_notification(None).body.contains("残量"));
+    }
+
+    #[tokio::test]
+    async fn test_notification_disabled() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        config.show_notification = false;
+        let engine = AlertEngine::new(&config);
+
+        let alerts = engine.update_metric(MetricType::CpuUsage, 96.0).await;
+        let notifications = engine.collect_notifications(&alerts).await;
+        assert!(notifications.is_empty(), "通知が無効な場合は生成されない");
+    }
+
+    #[tokio::test]
+    async fn test_notification_rate_limited_while_flapping() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        config.show_notification = true;
+        let engine = AlertEngine::new(&config);
+
+        // 1回目の発火は通知される
+        let alerts = engine.update_metric(MetricType::CpuUsage, 96.0).await;
+        assert_eq!(engine.collect_notifications(&alerts).await.len(), 1);
+
+        // 閾値を下回って再度発火（フラッピング）
+        engine.update_metric(MetricType::CpuUsage, 50.0).await;
+        let alerts = engine.update_metric(MetricType::CpuUsage, 96.0).await;
+        assert!(!alerts.is_empty(), "アラート自体は再発火する");
+        assert!(
+            engine.collect_notifications(&alerts).await.is_empty(),
+            "クールダウン中は通知しない"
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_allows_after_cooldown() {
+        let mut limiter = NotificationRateLimiter::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(MetricType::GpuUsage, start));
+        assert!(!limiter.try_acquire(MetricType::GpuUsage, start + Duration::from_secs(30)));
+        // 別メトリクスは独立してカウント
+        assert!(limiter.try_acquire(MetricType::CpuUsage, start + Duration::from_secs(30)));
+        assert!(limiter.try_acquire(MetricType::GpuUsage, start + Duration::from_secs(61)));
+    }
+
+    /// テスト用の一時データベースで履歴ストアを作成
+    async fn create_history_store() -> Arc<MetricsHistoryStore> {
+        let path = std::env::temp_dir().join(format!("obs_optimizer_alerts_{}.db", uuid::Uuid::new_v4()));
+        let store = Arc::new(MetricsHistoryStore::new(path));
+        store.initialize().await.unwrap();
+        store
+    }
+
+    /// 書き込みタスクの反映を待って履歴を取得
+    async fn wait_for_history(
+        store: &MetricsHistoryStore,
+        condition: impl Fn(&[AlertHistoryEntry]) -> bool,
+    ) -> Vec<AlertHistoryEntry> {
+        for _ in 0..100 {
+            let entries = store.get_alert_history(0, i64::MAX).await.unwrap();
+            if condition(&entries) {
+                return entries;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        store.get_alert_history(0, i64::MAX).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_alert_history_lifecycle_raised_acknowledged_cleared() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let store = create_history_store().await;
+        let session_id = store.start_session().await.unwrap();
+        let engine = AlertEngine::new(&config).with_history_store(store.clone());
+
+        // 発生: Warning閾値のみ超過
+        engine.update_metric(MetricType::CpuUsage, 92.0).await;
+        let entries = wait_for_history(&store, |e| e.len() == 1).await;
+        assert_eq!(entries.len(), 1, "発生したアラートが記録される");
+        assert_eq!(entries[0].severity, AlertSeverity::Warning);
+        assert_eq!(entries[0].session_id.as_deref(), Some(session_id.as_str()));
+        assert!(!entries[0].acknowledged);
+        assert!(entries[0].cleared_at.is_none());
+
+        // 確認
+        assert!(store.acknowledge_alert(&entries[0].id).await.unwrap());
+
+        // 解消
+        engine.update_metric(MetricType::CpuUsage, 50.0).await;
+        let entries = wait_for_history(&store, |e| e.first().is_some_and(|a| a.cleared_at.is_some())).await;
+        assert!(entries[0].acknowledged, "確認状態は解消後も保持される");
+        assert!(entries[0].acknowledged_at.is_some());
+        assert!(entries[0].cleared_at.is_some(), "解消時刻が記録される");
+    }
+
+    #[tokio::test]
+    async fn test_clear_all_alerts_clears_history() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let store = create_history_store().await;
+        let engine = AlertEngine::new(&config).with_history_store(store.clone());
+
+        engine.update_metric(MetricType::CpuUsage, 96.0).await;
+        engine.update_metric(MetricType::GpuUsage, 92.0).await;
+        wait_for_history(&store, |e| e.len() == 3).await;
+
+        engine.clear_all_alerts().await.unwrap();
+        let entries = wait_for_history(&store, |e| e.iter().all(|a| a.cleared_at.is_some())).await;
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|a| a.cleared_at.is_some()), "すべて解消済みになる");
+    }
+
+    #[tokio::test]
+    async fn test_engine_without_history_store() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let engine = AlertEngine::new(&config);
+
+        // 履歴ストア未設定でも通常どおり動作する
+        let alerts = engine.update_metric(MetricType::CpuUsage, 92.0).await;
+        assert!(!alerts.is_empty());
+        engine.update_metric(MetricType::CpuUsage, 50.0).await;
+        assert!(engine.get_active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_microphone_silence_alert() {
+        let config = create_test_config();
+        let engine = AlertEngine::new(&config);
+
+        // 閾値（30秒）未満では発火しない
+        assert!(engine.update_metric(MetricType::MicrophoneSilence, 29.0).await.is_empty());
+
+        let alerts = engine.update_metric(MetricType::MicrophoneSilence, 30.0).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert!(alerts[0].message.contains("マイク"));
+
+        // 音声が戻ると解消
+        engine.update_metric(MetricType::MicrophoneSilence, 0.0).await;
+        assert!(engine.get_active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_data_quota_alert() {
+        let config = create_test_config();
+        let engine = AlertEngine::new(&config);
+
+        // 残りが十分あれば発火しない
+        assert!(engine.update_data_quota(Some(20.0)).await.is_empty());
+
+        let alerts = engine.update_data_quota(Some(4.0)).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Warning);
+
+        // 発火済みの警告は重複しない
+        let alerts = engine.update_data_quota(Some(0.5)).await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert_eq!(engine.get_active_alerts().await.len(), 2);
+
+        // 上限の設定を外すと解消
+        engine.update_data_quota(None).await;
+        assert!(engine.get_active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_suppression_on_scene_transition() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let engine = AlertEngine::new(&config);
+        assert!(!engine.is_suppressed());
+
+        assert_eq!(
+            engine.suppress_for_trigger(SuppressionTrigger::SceneTransitionStart),
+            Some(DEFAULT_SCENE_TRANSITION_SUPPRESSION_SECS)
+        );
+        assert!(engine.is_suppressed());
+
+        // 抑制中は閾値を超えても発火しない
+        assert!(engine.update_metric(MetricType::CpuUsage, 99.0).await.is_empty());
+        assert!(engine.get_active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_suppression_expires() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let engine = AlertEngine::new(&config);
+
+        engine.suppress_for_trigger(SuppressionTrigger::RecordingStart);
+        let now = Instant::now();
+        assert!(engine.is_suppressed_at(now + Duration::from_secs(DEFAULT_RECORDING_START_SUPPRESSION_SECS - 1)));
+        assert!(!engine.is_suppressed_at(now + Duration::from_secs(DEFAULT_RECORDING_START_SUPPRESSION_SECS + 1)));
+
+        // 短い抑制で長い抑制を縮めない
+        engine.suppress_alerts_for(0);
+        assert!(engine.is_suppressed());
+
+        // 期限を過ぎると再び評価する
+        let engine = AlertEngine::new(&config);
+        engine.suppress_alerts_for(0);
+        assert!(!engine.is_suppressed());
+        assert_eq!(engine.update_metric(MetricType::CpuUsage, 99.0).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_manual_suppression() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let engine = AlertEngine::new(&config);
+
+        // 手動の抑制は秒数を指定するため、ルールからは抑制しない
+        assert_eq!(engine.suppress_for_trigger(SuppressionTrigger::ManualOverride), None);
+        assert!(!engine.is_suppressed());
+
+        engine.suppress_alerts_for(60);
+        assert!(engine.is_suppressed());
+        assert!(engine.update_metric(MetricType::GpuUsage, 99.0).await.is_empty());
+
+        // 抑制ルールを空にした場合はシーン切り替えでも抑制しない
+        config.suppression_rules.clear();
+        let engine = AlertEngine::new(&config);
+        assert_eq!(engine.suppress_for_trigger(SuppressionTrigger::SceneTransitionStart), None);
+        assert!(!engine.is_suppressed());
+    }
+
+    #[tokio::test]
+    async fn test_active_alert_kept_while_suppressed() {
+        let mut config = create_test_config();
+        config.alert_duration_secs = 0;
+        let engine = AlertEngine::new(&config);
+
+        engine.update_metric(MetricType::CpuUsage, 92.0).await;
+        engine.suppress_alerts_for(60);
+        // 抑制中は解消もしない
+        engine.update_metric(MetricType::CpuUsage, 50.0).await;
+        assert_eq!(engine.get_active_alerts().await.len(), 1);
+    }
 }
@@ -0,0 +1,205 @@
+// キャンバスと出力のアスペクト比の調整
+//
+// ウルトラワイドモニター（21:9）や16:10のノートPCでは、キャンバスをモニターに合わせると
+// 16:9の配信プラットフォームの出力と比率が合わない。そのまま縮小すると映像が歪むため、
+// 黒帯を入れて全体を収める（レターボックス）か、中央を切り取る（クロップ）か、
+// ウルトラワイドに対応したプラットフォームではそのままの比率で出力するかを算出する。
+
+use serde::{Deserialize, Serialize};
+
+/// 配信プラットフォームの標準のアスペクト比（16:9）
+pub const STANDARD_ASPECT_RATIO: f64 = 16.0 / 9.0;
+
+/// 16:9とみなす許容誤差（1366x768等の端数を含む解像度のため）
+const ASPECT_TOLERANCE: f64 = 0.02;
+
+/// ウルトラワイドとみなす最小のアスペクト比（2560x1080・3440x1440は約2.37〜2.39）
+pub const ULTRAWIDE_MIN_ASPECT_RATIO: f64 = 2.2;
+
+/// 16:9以外のキャンバスの扱い方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AspectHandling {
+    /// キャンバスの比率のまま出力する（ウルトラワイドに対応したプラットフォーム）
+    Native,
+    /// 黒帯を入れて、キャンバス全体を出力に収める
+    Letterbox,
+    /// キャンバスの中央を出力の比率で切り取る
+    Crop,
+}
+
+/// アスペクト比の調整の推奨
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AspectAdvice {
+    /// 推奨する扱い方
+    pub handling: AspectHandling,
+    /// キャンバス解像度（幅）
+    pub canvas_width: u32,
+    /// キャンバス解像度（高さ）
+    pub canvas_height: u32,
+    /// 出力解像度（幅）
+    pub output_width: u32,
+    /// 出力解像度（高さ）
+    pub output_height: u32,
+    /// レターボックスの場合に出力内で映像が占める幅（残りは黒帯）
+    pub letterbox_width: u32,
+    /// レターボックスの場合に出力内で映像が占める高さ（残りは黒帯）
+    pub letterbox_height: u32,
+    /// クロップの場合にキャンバスから切り取る幅
+    pub crop_width: u32,
+    /// クロップの場合にキャンバスから切り取る高さ
+    pub crop_height: u32,
+}
+
+/// アスペクト比（幅 / 高さ）。高さが0の場合は16:9とみなす
+pub fn aspect_ratio(width: u32, height: u32) -> f64 {
+    if height == 0 {
+        return STANDARD_ASPECT_RATIO;
+    }
+    f64::from(width) / f64::from(height)
+}
+
+/// 16:9（許容誤差内）か
+pub fn is_standard_aspect(width: u32, height: u32) -> bool {
+    (aspect_ratio(width, height) - STANDARD_ASPECT_RATIO).abs() <= ASPECT_TOLERANCE
+}
+
+/// ウルトラワイド（21:9以上）か
+pub fn is_ultrawide(width: u32, height: u32) -> bool {
+    aspect_ratio(width, height) >= ULTRAWIDE_MIN_ASPECT_RATIO
+}
+
+/// エンコーダーが扱えるよう偶数に丸める
+fn round_even(value: f64) -> u32 {
+    ((value / 2.0).round() as u32) * 2
+}
+
+/// 高さを保ったまま、キャンバスの比率に合わせた出力の幅
+///
+/// 例: 3440x1440のキャンバスを1080pで出力する場合は2580
+pub fn native_output_width(canvas_width: u32, canvas_height: u32, output_height: u32) -> u32 {
+    round_even(f64::from(output_height) * aspect_ratio(canvas_width, canvas_height))
+}
+
+/// キャンバスと出力の解像度から、アスペクト比の調整を算出
+///
+/// 比率が一致する場合（16:9のキャンバスを16:9で出力する場合など）はNone
+///
+/// # Arguments
+/// * `canvas` - キャンバス解像度（幅, 高さ）
+/// * `output` - 出力解像度（幅, 高さ）
+/// * `handling` - 比率が一致しない場合の扱い方
+pub fn compute_aspect_advice(canvas: (u32, u32), output: (u32, u32), handling: AspectHandling) -> Option<AspectAdvice> {
+    let (canvas_width, canvas_height) = canvas;
+    let (output_width, output_height) = output;
+    if canvas_height == 0 || output_height == 0 {
+        return None;
+    }
+
+    let canvas_aspect = aspect_ratio(canvas_width, canvas_height);
+    let output_aspect = aspect_ratio(output_width, output_height);
+    if (canvas_aspect - output_aspect).abs() <= ASPECT_TOLERANCE {
+        return None;
+    }
+
+    // キャンバスの方が横長なら上下、縦長なら左右に黒帯・切り取りが入る
+    let wider = canvas_aspect > output_aspect;
+    let (letterbox_width, letterbox_height) = if wider {
+        (output_width, round_even(f64::from(output_width) / canvas_aspect))
+    } else {
+        (round_even(f64::from(output_height) * canvas_aspect), output_height)
+    };
+    let (crop_width, crop_height) = if wider {
+        (round_even(f64::from(canvas_height) * output_aspect), canvas_height)
+    } else {
+        (canvas_width, round_even(f64::from(canvas_width) / output_aspect))
+    };
+
+    Some(AspectAdvice {
+        handling,
+        canvas_width,
+        canvas_height,
+        output_width,
+        output_height,
+        letterbox_width,
+        letterbox_height,
+        crop_width,
+        crop_height,
+    })
+}
+
+impl AspectAdvice {
+    /// 推奨の説明（分析結果の推奨事項に表示する）
+    pub fn description(&self) -> String {
+        let canvas = format!("{}x{}", self.canvas_width, self.canvas_height);
+        let output = format!("{}x{}", self.output_width, self.output_height);
+        match self.handling {
+            AspectHandling::Native => format!(
+                "キャンバス（{canvas}）の比率のまま{output}で出力します。配信先がウルトラワイドに対応しているため、黒帯や切り取りは不要です"
+            ),
+            AspectHandling::Letterbox => format!(
+                "キャンバス（{canvas}）は16:9ではないため、出力（{output}）に黒帯を入れて全体を{}x{}で表示します。画面いっぱいに表示したい場合は中央の{}x{}を切り取ってください",
+                self.letterbox_width, self.letterbox_height, self.crop_width, self.crop_height
+            ),
+            AspectHandling::Crop => format!(
+                "キャンバス（{canvas}）は16:9ではないため、中央の{}x{}を切り取って出力（{output}）いっぱいに表示します。全体を映したい場合は黒帯を入れて{}x{}で表示してください",
+                self.crop_width, self.crop_height, self.letterbox_width, self.letterbox_height
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_and_ultrawide_detection() {
+        assert!(is_standard_aspect(1920, 1080));
+        assert!(is_standard_aspect(2560, 1440));
+        assert!(is_standard_aspect(1366, 768));
+        assert!(!is_standard_aspect(3440, 1440));
+        assert!(!is_standard_aspect(2560, 1600));
+
+        assert!(is_ultrawide(3440, 1440));
+        assert!(is_ultrawide(2560, 1080));
+        assert!(is_ultrawide(5120, 1440));
+        assert!(!is_ultrawide(2560, 1600));
+        assert!(!is_ultrawide(1920, 1080));
+    }
+
+    #[test]
+    fn test_native_output_width() {
+        assert_eq!(native_output_width(3440, 1440, 1440), 3440);
+        assert_eq!(native_output_width(3440, 1440, 1080), 2580);
+        assert_eq!(native_output_width(2560, 1080, 1080), 2560);
+    }
+
+    #[test]
+    fn test_matching_aspect_needs_no_advice() {
+        assert!(compute_aspect_advice((2560, 1440), (1920, 1080), AspectHandling::Letterbox).is_none());
+        assert!(compute_aspect_advice((3440, 1440), (2580, 1080), AspectHandling::Native).is_none());
+    }
+
+    #[test]
+    fn test_ultrawide_canvas_to_16_9_output() {
+        let advice = compute_aspect_advice((3440, 1440), (1920, 1080), AspectHandling::Letterbox).unwrap();
+        // 上下に黒帯
+        assert_eq!((advice.letterbox_width, advice.letterbox_height), (1920, 804));
+        // 中央の16:9を切り取る
+        assert_eq!((advice.crop_width, advice.crop_height), (2560, 1440));
+        assert!(advice.description().contains("1920x804"));
+    }
+
+    #[test]
+    fn test_16_10_canvas_to_16_9_output() {
+        let advice = compute_aspect_advice((2560, 1600), (1920, 1080), AspectHandling::Crop).unwrap();
+        // 左右に黒帯
+        assert_eq!((advice.letterbox_width, advice.letterbox_height), (1728, 1080));
+        // 上下を切り取る
+        assert_eq!((advice.crop_width, advice.crop_height), (2560, 1440));
+        assert!(advice.description().contains("2560x1440"));
+    }
+}
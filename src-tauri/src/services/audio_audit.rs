@@ -0,0 +1,248 @@
+// 音声ソースの監査
+//
+// 「デスクトップ音声はミュートしたのにマイクをシーンに追加していない」など、
+// 配信を始めてから気付く音声の設定ミスを配信前に検出する。
+// 全ての音声入力を取得し、現在のシーンに含まれるか・ミュート中か・
+// モニターのみ（出力はミュート）になっていないかを確認する。
+
+use crate::error::AppError;
+use crate::obs::{AudioMonitorType, ObsClient};
+use crate::services::audio_monitor::{collect_audio_inputs, AudioInputStatus};
+use crate::services::scene_checklist::collect_scene_composition;
+use serde::{Deserialize, Serialize};
+
+/// デスクトップ音声（出力キャプチャ）とみなす入力種別
+const DESKTOP_AUDIO_INPUT_KINDS: &[&str] = &[
+    "wasapi_output_capture",
+    "wasapi_process_output_capture",
+    "coreaudio_output_capture",
+    "sck_audio_capture",
+    "pulse_output_capture",
+];
+
+/// 監査対象の音声ソースの状態
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioSourceStatus {
+    /// 入力名
+    pub name: String,
+    /// ミュート中か
+    pub muted: bool,
+    /// 音量（dB）
+    pub volume_db: f32,
+    /// 現在のシーンに含まれるか（グローバル音声入力は常にtrue）
+    pub in_current_scene: bool,
+}
+
+/// 音声ソースの監査結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioAuditResult {
+    /// マイク入力
+    pub microphone_sources: Vec<AudioSourceStatus>,
+    /// デスクトップ音声
+    pub desktop_sources: Vec<AudioSourceStatus>,
+    /// 警告メッセージ
+    pub warnings: Vec<String>,
+}
+
+/// 監査で検出した問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioAuditIssue {
+    /// 現在のシーンにミュートされていないマイクがない
+    NoActiveMicrophone,
+    /// デスクトップ音声が全てミュートされている
+    AllDesktopAudioMuted,
+    /// 現在のシーンの音声ソースが「モニターのみ」で配信に乗らない
+    MonitorOnlyInOutput {
+        /// 入力名
+        name: String,
+    },
+}
+
+impl AudioAuditIssue {
+    /// 警告メッセージ
+    pub fn message(&self) -> String {
+        match self {
+            Self::NoActiveMicrophone => {
+                "現在のシーンにミュートされていないマイクがありません。配信に声が入りません".to_string()
+            }
+            Self::AllDesktopAudioMuted => {
+                "デスクトップ音声が全てミュートされています。ゲーム・アプリの音が配信に入りません".to_string()
+            }
+            Self::MonitorOnlyInOutput { name } => format!(
+                "「{name}」が「モニターのみ（出力はミュート）」に設定されているため、シーンにあっても配信には音が入りません"
+            ),
+        }
+    }
+}
+
+/// デスクトップ音声の入力種別か
+pub fn is_desktop_audio(input_kind: &str) -> bool {
+    DESKTOP_AUDIO_INPUT_KINDS.contains(&input_kind)
+}
+
+/// 音声入力一覧と現在のシーンのソース名から問題を検出
+///
+/// # Arguments
+/// * `inputs` - 音声入力一覧
+/// * `scene_sources` - 現在のシーンに含まれるソース名（グローバル音声入力を含む）
+pub fn find_audio_issues(inputs: &[AudioInputStatus], scene_sources: &[String]) -> Vec<AudioAuditIssue> {
+    let in_scene = |input: &AudioInputStatus| scene_sources.contains(&input.input_name);
+    let mut issues = Vec::new();
+
+    let has_active_microphone = inputs
+        .iter()
+        .any(|input| input.likely_microphone && in_scene(input) && !input.muted);
+    if !has_active_microphone {
+        issues.push(AudioAuditIssue::NoActiveMicrophone);
+    }
+
+    let mut desktop = inputs.iter().filter(|input| is_desktop_audio(&input.input_kind)).peekable();
+    if desktop.peek().is_some() && desktop.all(|input| input.muted) {
+        issues.push(AudioAuditIssue::AllDesktopAudioMuted);
+    }
+
+    issues.extend(
+        inputs
+            .iter()
+            .filter(|input| in_scene(input) && input.monitor_type == AudioMonitorType::MonitorOnly)
+            .map(|input| AudioAuditIssue::MonitorOnlyInOutput {
+                name: input.input_name.clone(),
+            }),
+    );
+
+    issues
+}
+
+/// 音声入力一覧と現在のシーンのソース名から監査結果を構築
+///
+/// # Arguments
+/// * `inputs` - 音声入力一覧
+/// * `scene_sources` - 現在のシーンに含まれるソース名（グローバル音声入力を含む）
+pub fn build_audio_audit(inputs: &[AudioInputStatus], scene_sources: &[String]) -> AudioAuditResult {
+    let status = |input: &AudioInputStatus| AudioSourceStatus {
+        name: input.input_name.clone(),
+        muted: input.muted,
+        volume_db: input.volume_db as f32,
+        in_current_scene: scene_sources.contains(&input.input_name),
+    };
+
+    AudioAuditResult {
+        microphone_sources: inputs.iter().filter(|input| input.likely_microphone).map(status).collect(),
+        desktop_sources: inputs
+            .iter()
+            .filter(|input| is_desktop_audio(&input.input_kind))
+            .map(status)
+            .collect(),
+        warnings: find_audio_issues(inputs, scene_sources)
+            .iter()
+            .map(AudioAuditIssue::message)
+            .collect(),
+    }
+}
+
+/// OBSから音声入力と現在のシーンの構成を取得
+///
+/// # Returns
+/// 音声入力一覧と、現在のシーンに含まれるソース名
+pub async fn collect_audio_audit_inputs(client: &ObsClient) -> Result<(Vec<AudioInputStatus>, Vec<String>), AppError> {
+    let inputs = collect_audio_inputs(client).await?;
+    let composition = collect_scene_composition(client).await?;
+    let scene_sources = composition.sources.into_iter().map(|source| source.name).collect();
+    Ok((inputs, scene_sources))
+}
+
+/// OBSの音声ソースを監査
+pub async fn audit_audio_sources(client: &ObsClient) -> Result<AudioAuditResult, AppError> {
+    let (inputs, scene_sources) = collect_audio_audit_inputs(client).await?;
+    Ok(build_audio_audit(&inputs, &scene_sources))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::audio_monitor::is_likely_microphone;
+
+    fn input(name: &str, kind: &str, muted: bool, monitor_type: AudioMonitorType) -> AudioInputStatus {
+        AudioInputStatus {
+            input_name: name.to_string(),
+            input_kind: kind.to_string(),
+            muted,
+            volume_db: -6.0,
+            volume_mul: 0.5,
+            likely_microphone: is_likely_microphone(kind, name),
+            monitor_type,
+        }
+    }
+
+    fn mic(muted: bool) -> AudioInputStatus {
+        input("Mic/Aux", "wasapi_input_capture", muted, AudioMonitorType::None)
+    }
+
+    fn desktop(name: &str, muted: bool) -> AudioInputStatus {
+        input(name, "wasapi_output_capture", muted, AudioMonitorType::None)
+    }
+
+    fn scene(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_no_issues_for_typical_setup() {
+        let inputs = vec![mic(false), desktop("デスクトップ音声", false)];
+        let result = build_audio_audit(&inputs, &scene(&["Mic/Aux", "デスクトップ音声"]));
+
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.microphone_sources.len(), 1);
+        assert!(result.microphone_sources[0].in_current_scene);
+        assert_eq!(result.desktop_sources.len(), 1);
+        assert!((result.desktop_sources[0].volume_db - -6.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_no_active_microphone_in_current_scene() {
+        // マイクがシーンにない
+        let inputs = vec![mic(false), desktop("デスクトップ音声", false)];
+        let issues = find_audio_issues(&inputs, &scene(&["デスクトップ音声"]));
+        assert_eq!(issues, vec![AudioAuditIssue::NoActiveMicrophone]);
+
+        // シーンにあるがミュート中
+        let issues = find_audio_issues(&[mic(true)], &scene(&["Mic/Aux"]));
+        assert_eq!(issues, vec![AudioAuditIssue::NoActiveMicrophone]);
+
+        // マイクの入力自体がない
+        let issues = find_audio_issues(&[], &[]);
+        assert_eq!(issues, vec![AudioAuditIssue::NoActiveMicrophone]);
+    }
+
+    #[test]
+    fn test_all_desktop_audio_muted() {
+        let inputs = vec![mic(false), desktop("デスクトップ音声", true), desktop("ゲーム音", true)];
+        let issues = find_audio_issues(&inputs, &scene(&["Mic/Aux"]));
+        assert_eq!(issues, vec![AudioAuditIssue::AllDesktopAudioMuted]);
+
+        // 1つでも有効なら対象外
+        let inputs = vec![mic(false), desktop("デスクトップ音声", true), desktop("ゲーム音", false)];
+        assert!(find_audio_issues(&inputs, &scene(&["Mic/Aux"])).is_empty());
+
+        // デスクトップ音声がない場合は対象外
+        assert!(find_audio_issues(&[mic(false)], &scene(&["Mic/Aux"])).is_empty());
+    }
+
+    #[test]
+    fn test_monitor_only_source_in_output() {
+        let bgm = input("BGM", "ffmpeg_source", false, AudioMonitorType::MonitorOnly);
+        let inputs = vec![mic(false), bgm];
+
+        let issues = find_audio_issues(&inputs, &scene(&["Mic/Aux", "BGM"]));
+        assert_eq!(
+            issues,
+            vec![AudioAuditIssue::MonitorOnlyInOutput { name: "BGM".to_string() }]
+        );
+        assert!(issues[0].message().contains("BGM"));
+
+        // シーンにないソースは対象外
+        assert!(find_audio_issues(&inputs, &scene(&["Mic/Aux"])).is_empty());
+    }
+}
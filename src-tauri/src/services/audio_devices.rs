@@ -0,0 +1,280 @@
+// 音声デバイス設定サービス
+//
+// デスクトップ音声・マイク入力のデバイスとミュート状態をOBSから取得し、
+// プロファイルとして保存・適用する。
+// 配信内容（ゲーム・ポッドキャスト等）ごとにモニタリングデバイスや
+// 使用するマイクを切り替える用途を想定している。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use crate::storage::profiles::AudioInputSettings;
+
+/// デバイスを選択する音声入力の種別（デスクトップ音声・マイク）
+const AUDIO_DEVICE_INPUT_KINDS: &[&str] = &[
+    "wasapi_output_capture",
+    "wasapi_input_capture",
+    "coreaudio_output_capture",
+    "coreaudio_input_capture",
+    "pulse_output_capture",
+    "pulse_input_capture",
+    "alsa_input_capture",
+];
+
+/// デバイスIDを保持する入力設定のキー
+const DEVICE_ID_SETTING: &str = "device_id";
+
+/// OSの既定デバイスを表すデバイスID（接続状態に関わらず常に選択可能）
+const DEFAULT_DEVICE_ID: &str = "default";
+
+/// デバイスを選択する音声入力か
+pub fn is_audio_device_input(input_kind: &str) -> bool {
+    AUDIO_DEVICE_INPUT_KINDS.contains(&input_kind)
+}
+
+/// 保存された音声入力の設定を適用できるか確認
+///
+/// 入力が削除されている場合や、デバイスが接続されていない場合は
+/// スキップ理由（警告メッセージ）を返す
+///
+/// # Arguments
+/// * `entry` - 保存された音声入力の設定
+/// * `input_exists` - OBSに同名の入力が存在するか
+/// * `available_devices` - 現在選択可能なデバイスIDの一覧
+pub fn check_audio_input(
+    entry: &AudioInputSettings,
+    input_exists: bool,
+    available_devices: &[String],
+) -> Result<(), String> {
+    if !input_exists {
+        return Err(format!(
+            "音声入力「{}」が見つからないためスキップしました",
+            entry.input_name
+        ));
+    }
+
+    match entry.device_id.as_deref() {
+        Some(device_id)
+            if device_id != DEFAULT_DEVICE_ID
+                && !available_devices.iter().any(|d| d == device_id) =>
+        {
+            Err(format!(
+                "音声入力「{}」のデバイス（{device_id}）が見つからないためスキップしました",
+                entry.input_name
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// OBSからデスクトップ音声・マイク入力のデバイスとミュート状態を取得
+pub async fn capture_audio_inputs(client: &ObsClient) -> Result<Vec<AudioInputSettings>, AppError> {
+    let inputs = client.get_input_list().await?;
+    let mut captured = Vec::new();
+
+    for input in inputs.into_iter().filter(|input| is_audio_device_input(&input.kind)) {
+        let name = input.id.name;
+        let device_id = client
+            .get_input_settings(&name)
+            .await
+            .ok()
+            .and_then(|settings| settings[DEVICE_ID_SETTING].as_str().map(ToString::to_string));
+        // ミュート状態を取得できない場合はミュートなしとみなす
+        let muted = client.get_input_mute(&name).await.unwrap_or(false);
+
+        captured.push(AudioInputSettings {
+            input_name: name,
+            input_kind: input.kind,
+            device_id,
+            muted,
+        });
+    }
+
+    Ok(captured)
+}
+
+/// マイク入力（デバイスを選択する入力のうち、出力キャプチャ以外）か
+fn is_microphone_device_input(input_kind: &str) -> bool {
+    is_audio_device_input(input_kind) && input_kind.ends_with("_input_capture")
+}
+
+/// デバイスIDに対応するデバイスの表示名を取得
+///
+/// OSの既定デバイスを選択している場合は表示名から実際のデバイスを判断できないためNoneを返す
+///
+/// # Arguments
+/// * `device_id` - 入力設定のデバイスID
+/// * `items` - デバイス選択肢の（表示名, デバイスID）の一覧
+pub fn resolve_device_name(device_id: &str, items: &[(String, String)]) -> Option<String> {
+    if device_id == DEFAULT_DEVICE_ID {
+        return None;
+    }
+    items
+        .iter()
+        .find(|(_, value)| value == device_id)
+        .map(|(name, _)| name.clone())
+}
+
+/// OBSのマイク入力で使用しているデバイスの表示名を取得
+///
+/// 既定デバイスを選択している入力や、表示名を取得できない入力は除外する
+pub async fn collect_microphone_device_names(client: &ObsClient) -> Result<Vec<String>, AppError> {
+    let inputs = client.get_input_list().await?;
+    let mut names = Vec::new();
+
+    for input in inputs.into_iter().filter(|input| is_microphone_device_input(&input.kind)) {
+        let name = input.id.name;
+        let Some(device_id) = client
+            .get_input_settings(&name)
+            .await
+            .ok()
+            .and_then(|settings| settings[DEVICE_ID_SETTING].as_str().map(ToString::to_string))
+        else {
+            continue;
+        };
+        let items = client
+            .get_input_property_named_items(&name, DEVICE_ID_SETTING)
+            .await
+            .unwrap_or_default();
+
+        if let Some(device_name) = resolve_device_name(&device_id, &items) {
+            if !names.contains(&device_name) {
+                names.push(device_name);
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+/// 保存された音声入力のデバイスとミュート状態をOBSに適用
+///
+/// 入力・デバイスが見つからない項目や適用に失敗した項目はスキップし、
+/// 項目ごとの警告メッセージを返す。OBSとの接続が切れた場合は残りの項目を適用せずにエラーを返す
+pub async fn apply_audio_inputs(
+    client: &ObsClient,
+    entries: &[AudioInputSettings],
+) -> Result<Vec<String>, AppError> {
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let existing: Vec<String> = client
+        .get_input_list()
+        .await?
+        .into_iter()
+        .map(|input| input.id.name)
+        .collect();
+    let mut warnings = Vec::new();
+    let cancellation = client.cancellation_token();
+
+    for entry in entries {
+        cancellation.check()?;
+        let input_exists = existing.contains(&entry.input_name);
+        let available_devices = if input_exists && entry.device_id.is_some() {
+            client
+                .get_input_property_items(&entry.input_name, DEVICE_ID_SETTING)
+                .await
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        if let Err(warning) = check_audio_input(entry, input_exists, &available_devices) {
+            tracing::warn!(target: "optimization", input = %entry.input_name, "{warning}");
+            warnings.push(warning);
+            continue;
+        }
+
+        if let Some(device_id) = &entry.device_id {
+            let settings = serde_json::json!({ DEVICE_ID_SETTING: device_id });
+            if let Err(e) = client.set_input_settings(&entry.input_name, &settings).await {
+                warnings.push(format!(
+                    "音声入力「{}」のデバイス設定に失敗しました: {}",
+                    entry.input_name,
+                    e.message()
+                ));
+                continue;
+            }
+        }
+
+        if let Err(e) = client.set_input_mute(&entry.input_name, entry.muted).await {
+            warnings.push(format!(
+                "音声入力「{}」のミュート設定に失敗しました: {}",
+                entry.input_name,
+                e.message()
+            ));
+        }
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn entry(device_id: Option<&str>) -> AudioInputSettings {
+        AudioInputSettings {
+            input_name: "マイク".to_string(),
+            input_kind: "wasapi_input_capture".to_string(),
+            device_id: device_id.map(ToString::to_string),
+            muted: false,
+        }
+    }
+
+    #[test]
+    fn test_is_audio_device_input() {
+        assert!(is_audio_device_input("wasapi_output_capture"));
+        assert!(is_audio_device_input("pulse_input_capture"));
+        assert!(!is_audio_device_input("browser_source"));
+        assert!(!is_audio_device_input("ffmpeg_source"));
+    }
+
+    #[test]
+    fn test_resolve_device_name() {
+        let items = vec![
+            ("既定".to_string(), "default".to_string()),
+            ("ヘッドセット (WH-1000XM4 Hands-Free AG Audio)".to_string(), "{bt-mic}".to_string()),
+        ];
+        assert_eq!(
+            resolve_device_name("{bt-mic}", &items).as_deref(),
+            Some("ヘッドセット (WH-1000XM4 Hands-Free AG Audio)")
+        );
+        assert_eq!(resolve_device_name("default", &items), None);
+        assert_eq!(resolve_device_name("{unplugged}", &items), None);
+    }
+
+    #[test]
+    fn test_is_microphone_device_input() {
+        assert!(is_microphone_device_input("wasapi_input_capture"));
+        assert!(is_microphone_device_input("alsa_input_capture"));
+        assert!(!is_microphone_device_input("wasapi_output_capture"));
+    }
+
+    #[test]
+    fn test_check_audio_input_available_device() {
+        let devices = vec!["{mic-1}".to_string(), "{mic-2}".to_string()];
+        assert!(check_audio_input(&entry(Some("{mic-2}")), true, &devices).is_ok());
+    }
+
+    #[test]
+    fn test_check_audio_input_default_device_always_available() {
+        assert!(check_audio_input(&entry(Some("default")), true, &[]).is_ok());
+        assert!(check_audio_input(&entry(None), true, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_check_audio_input_missing_device() {
+        let devices = vec!["{mic-1}".to_string()];
+        let warning = check_audio_input(&entry(Some("{usb-mic}")), true, &devices).unwrap_err();
+        assert!(warning.contains("マイク"));
+        assert!(warning.contains("{usb-mic}"));
+    }
+
+    #[test]
+    fn test_check_audio_input_missing_input() {
+        let warning = check_audio_input(&entry(Some("default")), false, &[]).unwrap_err();
+        assert!(warning.contains("見つからない"));
+    }
+}
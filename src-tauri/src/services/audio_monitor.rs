@@ -0,0 +1,404 @@
+// 音声ソース監視サービス
+//
+// OBSの音声入力のミュート状態と音量を定期的に取得し、
+// 配信中にマイクがミュート・フェーダーを下げたまま放置される事故を検知する。
+// OBS WebSocketの実際の入力レベル（InputVolumeMeters）はイベント購読でしか取得できず、
+// イベント購読にはobwsの `events` フィーチャー（依存関係の変更）が必要なため、
+// ここではミュート状態とフェーダー値（GetInputVolume）で判定する。
+// フェーダーを上げたまま入力レベルが無音のマイク（OS側のミュート・デバイスの故障）は検出できない。
+
+use crate::error::AppError;
+use crate::obs::{get_obs_client, AudioMonitorType, ObsClient};
+use crate::services::alerts::{update_metric_and_notify, MetricType};
+use crate::storage::config::{config_revision, load_config, AppConfig};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+/// ポーリング間隔
+pub const AUDIO_MONITOR_INTERVAL: Duration = Duration::from_secs(2);
+
+/// マイク入力とみなす入力種別
+const MICROPHONE_INPUT_KINDS: &[&str] = &[
+    "wasapi_input_capture",
+    "coreaudio_input_capture",
+    "pulse_input_capture",
+    "alsa_input_capture",
+];
+
+/// 音声入力の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioInputStatus {
+    /// 入力名
+    pub input_name: String,
+    /// 入力種別（例: wasapi_input_capture）
+    pub input_kind: String,
+    /// ミュート中か
+    pub muted: bool,
+    /// 音量（dB）
+    pub volume_db: f64,
+    /// 音量（乗数）
+    pub volume_mul: f64,
+    /// マイク入力と推定されるか
+    pub likely_microphone: bool,
+    /// 音声モニタリングの種類
+    #[serde(default)]
+    pub monitor_type: AudioMonitorType,
+}
+
+impl AudioInputStatus {
+    /// ミュート中、またはフェーダー値が下限未満か
+    ///
+    /// 実際の入力レベルではなくフェーダー値で判定するため、
+    /// フェーダーを上げたまま入力が無音の場合はfalseになる
+    ///
+    /// # Arguments
+    /// * `floor_db` - 無音とみなす音量の下限（dB）
+    pub fn is_silent(&self, floor_db: f64) -> bool {
+        self.muted || !self.volume_db.is_finite() || self.volume_db < floor_db
+    }
+}
+
+/// 音声ソース全体の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStatus {
+    /// 音声入力一覧
+    pub inputs: Vec<AudioInputStatus>,
+    /// 監視対象のマイク入力名（見つからない場合はNone）
+    pub microphone: Option<String>,
+    /// 監視対象のマイクがミュート・無音か
+    pub microphone_silent: bool,
+}
+
+/// 入力種別と名前からマイク入力かを推定
+pub fn is_likely_microphone(input_kind: &str, input_name: &str) -> bool {
+    if MICROPHONE_INPUT_KINDS.contains(&input_kind) {
+        return true;
+    }
+    let name = input_name.to_lowercase();
+    name.contains("mic") || name.contains("マイク")
+}
+
+/// 監視対象のマイク入力を選択
+///
+/// 設定で指定された入力名を優先し、見つからない場合は
+/// マイクと推定される最初の入力にフォールバックする
+///
+/// # Arguments
+/// * `inputs` - 音声入力一覧
+/// * `configured` - 設定で指定されたマイク入力名
+pub fn select_microphone<'a>(
+    inputs: &'a [AudioInputStatus],
+    configured: Option<&str>,
+) -> Option<&'a AudioInputStatus> {
+    configured
+        .and_then(|name| inputs.iter().find(|input| input.input_name == name))
+        .or_else(|| inputs.iter().find(|input| input.likely_microphone))
+}
+
+/// マイク・楽器入力がすべてモニターオフか
+///
+/// 歌・演奏配信で配信者が自分の音を聞けない状態を検出する。
+/// マイク入力がない場合はfalse
+pub fn microphone_monitoring_disabled(inputs: &[AudioInputStatus]) -> bool {
+    let mut microphones = inputs.iter().filter(|input| input.likely_microphone).peekable();
+    microphones.peek().is_some() && microphones.all(|input| !input.monitor_type.is_monitoring())
+}
+
+/// 音声入力一覧と状態を構築
+///
+/// # Arguments
+/// * `inputs` - 音声入力一覧
+/// * `configured` - 設定で指定されたマイク入力名
+/// * `floor_db` - 無音とみなす音量の下限（dB）
+pub fn build_audio_status(
+    inputs: Vec<AudioInputStatus>,
+    configured: Option<&str>,
+    floor_db: f64,
+) -> AudioStatus {
+    let microphone = select_microphone(&inputs, configured);
+    let microphone_silent = microphone.is_some_and(|mic| mic.is_silent(floor_db));
+    let microphone = microphone.map(|mic| mic.input_name.clone());
+
+    AudioStatus {
+        inputs,
+        microphone,
+        microphone_silent,
+    }
+}
+
+/// OBSから音声入力の状態を取得
+///
+/// ミュート・音量を取得できない入力（映像ソースなど）は除外する
+pub async fn collect_audio_inputs(client: &ObsClient) -> Result<Vec<AudioInputStatus>, AppError> {
+    let inputs = client.get_input_list().await?;
+    let mut statuses = Vec::with_capacity(inputs.len());
+
+    for input in inputs {
+        let name = input.id.name;
+        let Ok(muted) = client.get_input_mute(&name).await else {
+            continue;
+        };
+        let Ok(volume) = client.get_input_volume(&name).await else {
+            continue;
+        };
+        // 取得できない場合はモニターオフとみなす
+        let monitor_type = client.get_audio_monitor_type(&name).await.unwrap_or_default();
+
+        statuses.push(AudioInputStatus {
+            likely_microphone: is_likely_microphone(&input.kind, &name),
+            input_name: name,
+            input_kind: input.kind,
+            muted,
+            volume_db: f64::from(volume.db),
+            volume_mul: f64::from(volume.mul),
+            monitor_type,
+        });
+    }
+
+    Ok(statuses)
+}
+
+/// マイク監視に使用する設定
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicrophoneMonitorSettings {
+    /// 監視対象のマイク入力名（未設定の場合は自動検出）
+    pub input_name: Option<String>,
+    /// 無音とみなす音量の下限（dB）
+    pub floor_db: f64,
+}
+
+impl MicrophoneMonitorSettings {
+    /// アプリケーション設定から取得
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            input_name: config.microphone_input_name.clone(),
+            floor_db: config.alerts.mic_silence_floor_db,
+        }
+    }
+}
+
+/// 設定ファイルが保存されたときだけ読み直すマイク監視設定のキャッシュ
+#[derive(Debug, Default)]
+struct MicrophoneSettingsCache {
+    /// 読み込んだ時点の設定の保存回数と設定
+    cached: Option<(u64, MicrophoneMonitorSettings)>,
+}
+
+impl MicrophoneSettingsCache {
+    /// 現在の設定を取得（保存回数が変わっていなければファイルを読まない）
+    fn get(&mut self) -> Result<&MicrophoneMonitorSettings, AppError> {
+        let revision = config_revision();
+        if self.cached.as_ref().is_none_or(|(cached, _)| *cached != revision) {
+            let settings = MicrophoneMonitorSettings::from_config(&load_config()?);
+            self.cached = Some((revision, settings));
+        }
+        self.cached
+            .as_ref()
+            .map(|(_, settings)| settings)
+            .ok_or_else(|| AppError::config_io("マイク監視の設定を読み込めませんでした"))
+    }
+}
+
+/// 指定した設定で音声ソースの状態を取得
+async fn get_audio_status_with(settings: &MicrophoneMonitorSettings) -> Result<AudioStatus, AppError> {
+    let client = get_obs_client();
+    let inputs = collect_audio_inputs(&client).await?;
+
+    Ok(build_audio_status(inputs, settings.input_name.as_deref(), settings.floor_db))
+}
+
+/// 現在の音声ソースの状態を取得
+pub async fn get_audio_status() -> Result<AudioStatus, AppError> {
+    let settings = MicrophoneMonitorSettings::from_config(&load_config()?);
+    get_audio_status_with(&settings).await
+}
+
+/// マイク無音の継続時間トラッカー
+#[derive(Debug, Default)]
+pub struct SilentMicTracker {
+    /// 無音が始まった時刻
+    silent_since: Option<Instant>,
+}
+
+impl SilentMicTracker {
+    /// 新しいトラッカーを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 今回の状態を反映し、無音の継続秒数を返す
+    ///
+    /// 配信中でない場合やマイクが見つからない場合は0にリセットする
+    ///
+    /// # Arguments
+    /// * `streaming` - 配信中か
+    /// * `microphone_silent` - マイクがミュート・無音か
+    /// * `now` - 現在時刻
+    pub fn update(&mut self, streaming: bool, microphone_silent: bool, now: Instant) -> f64 {
+        if !(streaming && microphone_silent) {
+            self.silent_since = None;
+            return 0.0;
+        }
+
+        let since = *self.silent_since.get_or_insert(now);
+        now.saturating_duration_since(since).as_secs_f64()
+    }
+}
+
+/// 1回分のポーリングを実行し、マイク無音の継続秒数を返す
+async fn poll_silence(
+    tracker: &mut SilentMicTracker,
+    settings: &mut MicrophoneSettingsCache,
+) -> Result<f64, AppError> {
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Ok(tracker.update(false, false, Instant::now()));
+    }
+
+    let streaming = client.is_streaming().await?;
+    if !streaming {
+        return Ok(tracker.update(false, false, Instant::now()));
+    }
+
+    let status = get_audio_status_with(settings.get()?).await?;
+    Ok(tracker.update(streaming, status.microphone_silent, Instant::now()))
+}
+
+/// 音声監視のバックグラウンドタスクを開始
+///
+/// 2秒ごとにマイクの状態を確認し、配信中の無音継続秒数を
+/// アラートエンジンに `MicrophoneSilence` として渡す。
+/// マイク監視の設定は起動時に読み込み、設定が保存された場合のみ読み直す
+pub fn start_audio_monitor<R: Runtime>(app_handle: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = SilentMicTracker::new();
+        let mut settings = MicrophoneSettingsCache::default();
+        let mut interval = tokio::time::interval(AUDIO_MONITOR_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let silent_secs = match poll_silence(&mut tracker, &mut settings).await {
+                Ok(secs) => secs,
+                Err(e) => {
+                    tracing::debug!(target: "audio_monitor", error = %e, "音声ソースの取得に失敗");
+                    continue;
+                }
+            };
+
+            if let Err(e) =
+                update_metric_and_notify(&app_handle, MetricType::MicrophoneSilence, silent_secs).await
+            {
+                tracing::warn!(target: "audio_monitor", error = %e, "マイク無音アラートの通知に失敗");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_input(name: &str, kind: &str, muted: bool, volume_db: f64) -> AudioInputStatus {
+        AudioInputStatus {
+            input_name: name.to_string(),
+            input_kind: kind.to_string(),
+            muted,
+            volume_db,
+            volume_mul: 1.0,
+            likely_microphone: is_likely_microphone(kind, name),
+            monitor_type: AudioMonitorType::None,
+        }
+    }
+
+    #[test]
+    fn test_is_likely_microphone() {
+        assert!(is_likely_microphone("wasapi_input_capture", "音声入力"));
+        assert!(is_likely_microphone("coreaudio_input_capture", "Input"));
+        assert!(is_likely_microphone("ffmpeg_source", "Mic/Aux"));
+        assert!(is_likely_microphone("ffmpeg_source", "マイク"));
+        assert!(!is_likely_microphone("wasapi_output_capture", "デスクトップ音声"));
+    }
+
+    #[test]
+    fn test_is_silent() {
+        assert!(create_input("Mic", "wasapi_input_capture", true, 0.0).is_silent(-60.0));
+        assert!(create_input("Mic", "wasapi_input_capture", false, -80.0).is_silent(-60.0));
+        assert!(create_input("Mic", "wasapi_input_capture", false, f64::NEG_INFINITY).is_silent(-60.0));
+        assert!(!create_input("Mic", "wasapi_input_capture", false, -10.0).is_silent(-60.0));
+    }
+
+    #[test]
+    fn test_select_microphone_prefers_configured_name() {
+        let inputs = vec![
+            create_input("Mic/Aux", "wasapi_input_capture", false, 0.0),
+            create_input("配信用マイク", "wasapi_input_capture", false, 0.0),
+        ];
+
+        let selected = select_microphone(&inputs, Some("配信用マイク"));
+        assert_eq!(selected.map(|i| i.input_name.as_str()), Some("配信用マイク"));
+
+        // 指定名が存在しない場合は自動検出にフォールバック
+        let selected = select_microphone(&inputs, Some("存在しない"));
+        assert_eq!(selected.map(|i| i.input_name.as_str()), Some("Mic/Aux"));
+
+        let selected = select_microphone(&inputs, None);
+        assert_eq!(selected.map(|i| i.input_name.as_str()), Some("Mic/Aux"));
+    }
+
+    #[test]
+    fn test_build_audio_status_without_microphone() {
+        let inputs = vec![create_input("デスクトップ音声", "wasapi_output_capture", true, 0.0)];
+        let status = build_audio_status(inputs, None, -60.0);
+
+        assert!(status.microphone.is_none());
+        assert!(!status.microphone_silent, "マイクがない場合は無音扱いしない");
+    }
+
+    #[test]
+    fn test_build_audio_status_detects_muted_microphone() {
+        let inputs = vec![
+            create_input("デスクトップ音声", "wasapi_output_capture", false, 0.0),
+            create_input("Mic/Aux", "wasapi_input_capture", true, 0.0),
+        ];
+        let status = build_audio_status(inputs, None, -60.0);
+
+        assert_eq!(status.microphone.as_deref(), Some("Mic/Aux"));
+        assert!(status.microphone_silent);
+    }
+
+    #[test]
+    fn test_silent_mic_tracker() {
+        let mut tracker = SilentMicTracker::new();
+        let start = Instant::now();
+
+        assert_eq!(tracker.update(true, true, start), 0.0);
+        let secs = tracker.update(true, true, start + Duration::from_secs(31));
+        assert!((secs - 31.0).abs() < f64::EPSILON);
+
+        // 音声が戻るとリセット
+        assert_eq!(tracker.update(true, false, start + Duration::from_secs(32)), 0.0);
+        assert_eq!(tracker.update(true, true, start + Duration::from_secs(40)), 0.0);
+
+        // 配信停止でもリセット
+        assert_eq!(tracker.update(false, true, start + Duration::from_secs(50)), 0.0);
+    }
+
+    #[test]
+    fn test_microphone_monitoring_disabled() {
+        let mut mic = create_input("Mic/Aux", "wasapi_input_capture", false, 0.0);
+        let mut desktop = create_input("デスクトップ音声", "wasapi_output_capture", false, 0.0);
+        desktop.monitor_type = AudioMonitorType::MonitorAndOutput;
+        assert!(microphone_monitoring_disabled(&[mic.clone(), desktop.clone()]));
+
+        mic.monitor_type = AudioMonitorType::MonitorOnly;
+        assert!(!microphone_monitoring_disabled(&[mic, desktop.clone()]));
+
+        // マイクがない場合は対象外
+        assert!(!microphone_monitoring_disabled(&[desktop]));
+        assert!(!microphone_monitoring_disabled(&[]));
+    }
+}
@@ -0,0 +1,184 @@
+// 音声モニタリングの一括無効化（プライバシーモード）
+//
+// 対談・ポッドキャスト配信やビデオ通話中は、モニタリング音が通話相手の声と重なって
+// エコーになるため、全ての音声入力のモニタリングを一時的にオフにしたい。
+// 無効化する前のモニタリングの種類を保存しておき、後から元に戻せるようにする。
+
+use crate::error::AppError;
+use crate::obs::{AudioMonitorType, ObsClient};
+use crate::services::audio_monitor::{collect_audio_inputs, AudioInputStatus};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// 無効化する前のモニタリングの種類（入力名 → 種類）
+static PREVIOUS_MONITOR_TYPES: Lazy<Arc<Mutex<HashMap<String, AudioMonitorType>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+/// プライバシーモード（モニタリングの一括無効化）中か
+pub fn is_privacy_mode_active() -> bool {
+    !PREVIOUS_MONITOR_TYPES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_empty()
+}
+
+/// モニタリング中の音声入力を選ぶ（入力名と現在の種類）
+fn select_monitoring_inputs(inputs: &[AudioInputStatus]) -> Vec<(String, AudioMonitorType)> {
+    inputs
+        .iter()
+        .filter(|input| input.monitor_type.is_monitoring())
+        .map(|input| (input.input_name.clone(), input.monitor_type))
+        .collect()
+}
+
+/// 無効化する前の種類を保存
+///
+/// 既に保存済みの入力は最初に保存した種類を残す（無効化を繰り返しても元の種類に戻せるように）
+fn remember_previous(
+    saved: &mut HashMap<String, AudioMonitorType>,
+    input_name: String,
+    previous: AudioMonitorType,
+) {
+    saved.entry(input_name).or_insert(previous);
+}
+
+/// 保存した種類を全て取り出す（入力名順）
+fn take_previous(saved: &mut HashMap<String, AudioMonitorType>) -> Vec<(String, AudioMonitorType)> {
+    let mut entries: Vec<_> = saved.drain().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// 全ての音声入力のモニタリングをオフにする
+///
+/// # Returns
+/// モニタリングをオフにした入力名
+pub async fn disable_all_monitoring(client: &ObsClient) -> Result<Vec<String>, AppError> {
+    let inputs = collect_audio_inputs(client).await?;
+    let mut affected = Vec::new();
+
+    for (input_name, previous) in select_monitoring_inputs(&inputs) {
+        client.set_audio_monitor_type(&input_name, AudioMonitorType::None).await?;
+        remember_previous(
+            &mut PREVIOUS_MONITOR_TYPES.lock().unwrap_or_else(PoisonError::into_inner),
+            input_name.clone(),
+            previous,
+        );
+        affected.push(input_name);
+    }
+
+    Ok(affected)
+}
+
+/// 無効化する前のモニタリングの種類に戻す
+///
+/// 削除された入力など、戻せなかった入力は保存した種類を破棄する。
+/// 途中で切断された場合は、戻せなかった入力の種類を保存したままにする
+///
+/// # Returns
+/// モニタリングを元に戻した入力名
+pub async fn restore_monitoring(client: &ObsClient) -> Result<Vec<String>, AppError> {
+    let entries = take_previous(&mut PREVIOUS_MONITOR_TYPES.lock().unwrap_or_else(PoisonError::into_inner));
+    let mut restored = Vec::with_capacity(entries.len());
+
+    for (input_name, previous) in entries {
+        match client.set_audio_monitor_type(&input_name, previous).await {
+            Ok(()) => restored.push(input_name),
+            Err(e) if !client.is_connected().await => {
+                remember_previous(
+                    &mut PREVIOUS_MONITOR_TYPES.lock().unwrap_or_else(PoisonError::into_inner),
+                    input_name,
+                    previous,
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    target: "audio_privacy",
+                    input = %input_name,
+                    error = %e,
+                    "モニタリングの種類を戻せませんでした"
+                );
+            }
+        }
+    }
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str, monitor_type: AudioMonitorType) -> AudioInputStatus {
+        AudioInputStatus {
+            input_name: name.to_string(),
+            input_kind: "wasapi_input_capture".to_string(),
+            muted: false,
+            volume_db: 0.0,
+            volume_mul: 1.0,
+            likely_microphone: false,
+            monitor_type,
+        }
+    }
+
+    /// OBSへの設定を入力一覧に反映したものとして、無効化 → 復元を行う
+    fn disable(inputs: &mut [AudioInputStatus], saved: &mut HashMap<String, AudioMonitorType>) -> Vec<String> {
+        let targets = select_monitoring_inputs(inputs);
+        for (name, previous) in &targets {
+            remember_previous(saved, name.clone(), *previous);
+            if let Some(input) = inputs.iter_mut().find(|i| &i.input_name == name) {
+                input.monitor_type = AudioMonitorType::None;
+            }
+        }
+        targets.into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn restore(inputs: &mut [AudioInputStatus], saved: &mut HashMap<String, AudioMonitorType>) -> Vec<String> {
+        let entries = take_previous(saved);
+        for (name, previous) in &entries {
+            if let Some(input) = inputs.iter_mut().find(|i| &i.input_name == name) {
+                input.monitor_type = *previous;
+            }
+        }
+        entries.into_iter().map(|(name, _)| name).collect()
+    }
+
+    #[test]
+    fn test_disable_then_restore_cycle() {
+        let mut inputs = vec![
+            input("マイク", AudioMonitorType::MonitorOnly),
+            input("デスクトップ音声", AudioMonitorType::None),
+            input("BGM", AudioMonitorType::MonitorAndOutput),
+        ];
+        let mut saved = HashMap::new();
+
+        assert_eq!(disable(&mut inputs, &mut saved), vec!["マイク", "BGM"]);
+        assert!(inputs.iter().all(|i| !i.monitor_type.is_monitoring()));
+        assert_eq!(saved.len(), 2);
+
+        // 無効化を繰り返しても、最初に保存した種類が残る
+        assert!(disable(&mut inputs, &mut saved).is_empty());
+        assert_eq!(saved.get("BGM"), Some(&AudioMonitorType::MonitorAndOutput));
+
+        assert_eq!(restore(&mut inputs, &mut saved), vec!["BGM", "マイク"]);
+        assert_eq!(inputs[0].monitor_type, AudioMonitorType::MonitorOnly);
+        assert_eq!(inputs[1].monitor_type, AudioMonitorType::None);
+        assert_eq!(inputs[2].monitor_type, AudioMonitorType::MonitorAndOutput);
+        assert!(saved.is_empty());
+    }
+
+    #[test]
+    fn test_empty_sources() {
+        let mut saved = HashMap::new();
+        assert!(disable(&mut [], &mut saved).is_empty());
+        assert!(saved.is_empty());
+        assert!(restore(&mut [], &mut saved).is_empty());
+
+        // モニタリング中の入力がない場合も何も保存しない
+        let mut inputs = vec![input("マイク", AudioMonitorType::None)];
+        assert!(disable(&mut inputs, &mut saved).is_empty());
+        assert!(saved.is_empty());
+    }
+}
@@ -0,0 +1,568 @@
+// 自動化ルールの評価・実行
+//
+// 「フレームドロップ率が30秒間5%を超えたらビットレートを1000kbps下げる」のような
+// ユーザー定義のルール（`AppConfig.automation_rules`）を、メトリクスの更新ごとに評価する。
+// 条件を継続時間だけ満たし続けたルールのアクションを実行し、実行後はルールごとの
+// クールダウンが明けるまで再実行しない。実行結果は履歴として保持する。
+
+use crate::error::AppError;
+use crate::events::{emit_event, TauriEvent};
+use crate::obs::{get_obs_client, get_output_mode, ObsClient, ObsOutputMode};
+use crate::services::alerts::{
+    emit_alert_notifications, suppress_alerts_for_trigger, AlertNotification, MetricType, SuppressionTrigger,
+};
+use crate::storage::config::{cached_config, AutomationAction, AutomationRule};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+/// ルールの実行を通知するイベント名
+pub const AUTOMATION_EXECUTED_EVENT: &str = "automation:executed";
+
+/// 保持する実行履歴の件数
+const MAX_EXECUTION_LOG: usize = 100;
+
+/// 自動化で設定するビットレートの下限（kbps）
+const MIN_AUTOMATION_BITRATE_KBPS: u32 = 500;
+
+/// 自動化で設定するビットレートの上限（kbps）
+const MAX_AUTOMATION_BITRATE_KBPS: u32 = 50_000;
+
+/// ルールごとの評価状態
+#[derive(Debug, Default)]
+struct RuleState {
+    /// 条件を満たし始めた時刻（満たしていない場合はNone）
+    condition_since: Option<Instant>,
+    /// 最後に実行した時刻
+    last_executed: Option<Instant>,
+}
+
+/// 発動したルール
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleFiring {
+    /// 発動したルール
+    pub rule: AutomationRule,
+    /// 発動時のメトリクスの値
+    pub value: f64,
+}
+
+/// 自動化ルールの評価器
+///
+/// 時刻を引数で受け取るため、テストでは任意のメトリクスの列を再生できる
+#[derive(Debug, Default)]
+pub struct AutomationEvaluator {
+    /// ルールIDごとの評価状態
+    states: HashMap<String, RuleState>,
+}
+
+impl AutomationEvaluator {
+    /// 新しい評価器を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// メトリクスの更新を反映し、発動したルールを返す
+    ///
+    /// 条件を `duration_secs` 秒満たし続け、前回の実行から `cooldown_secs` 秒以上経過したルールが発動する。
+    /// 発動後も条件を満たし続ける場合は、改めて継続時間とクールダウンの両方を満たしたときに再び発動する
+    ///
+    /// # Arguments
+    /// * `rules` - 自動化ルール（無効なルール・他のメトリクスのルールは評価しない）
+    /// * `metric` - 更新されたメトリクス
+    /// * `value` - メトリクスの値
+    /// * `now` - 現在時刻
+    pub fn evaluate(
+        &mut self,
+        rules: &[AutomationRule],
+        metric: MetricType,
+        value: f64,
+        now: Instant,
+    ) -> Vec<RuleFiring> {
+        // 削除されたルールの状態を破棄
+        self.states.retain(|id, _| rules.iter().any(|rule| &rule.id == id));
+
+        let mut firings = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.enabled && rule.trigger.metric == metric) {
+            let state = self.states.entry(rule.id.clone()).or_default();
+            let trigger = &rule.trigger;
+
+            if !value.is_finite() || !trigger.comparison.matches(value, trigger.threshold) {
+                state.condition_since = None;
+                continue;
+            }
+
+            let since = *state.condition_since.get_or_insert(now);
+            if now.saturating_duration_since(since) < Duration::from_secs(trigger.duration_secs) {
+                continue;
+            }
+
+            let cooling_down = state.last_executed.is_some_and(|last| {
+                now.saturating_duration_since(last) < Duration::from_secs(rule.cooldown_secs)
+            });
+            if cooling_down {
+                continue;
+            }
+
+            state.last_executed = Some(now);
+            state.condition_since = Some(now);
+            firings.push(RuleFiring {
+                rule: rule.clone(),
+                value,
+            });
+        }
+
+        firings
+    }
+}
+
+/// 配信出力の累積フレーム数から、取得間隔ごとのフレームドロップ率を算出する
+#[derive(Debug, Default)]
+pub struct FrameDropRateTracker {
+    /// 前回の送出フレーム数とドロップフレーム数
+    last: Option<(u64, u64)>,
+}
+
+impl FrameDropRateTracker {
+    /// 新しいトラッカーを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 累積値を反映し、前回からのフレームドロップ率（%）を返す
+    ///
+    /// 初回・配信の再開直後（累積値が減った場合）・フレームが送出されていない場合はNone
+    ///
+    /// # Arguments
+    /// * `frames_sent` - 配信開始からの送出フレーム数
+    /// * `frames_dropped` - 配信開始からのドロップフレーム数
+    pub fn update(&mut self, frames_sent: u64, frames_dropped: u64) -> Option<f64> {
+        let last = self.last.replace((frames_sent, frames_dropped));
+        let (last_sent, last_dropped) = last?;
+        if frames_sent < last_sent || frames_dropped < last_dropped {
+            return None;
+        }
+
+        let sent = frames_sent - last_sent;
+        if sent == 0 {
+            return None;
+        }
+        Some((frames_dropped - last_dropped) as f64 / sent as f64 * 100.0)
+    }
+}
+
+/// アクションの実行結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationActionResult {
+    /// 実行したアクション
+    pub action: AutomationAction,
+    /// 成功したか（出力の再起動まで反映されない場合はfalse）
+    pub success: bool,
+    /// 設定は保存したが、配信中の出力には再起動するまで反映されないか
+    pub pending_restart: bool,
+    /// 結果の説明（失敗時はエラーメッセージ）
+    pub message: String,
+}
+
+/// ルールの実行履歴
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationExecution {
+    /// ルールID
+    pub rule_id: String,
+    /// ルール名
+    pub rule_name: String,
+    /// 発動したメトリクス
+    pub metric: MetricType,
+    /// 発動時のメトリクスの値
+    pub value: f64,
+    /// 実行日時（UNIX epoch秒）
+    pub executed_at: i64,
+    /// アクションごとの実行結果（記載順）
+    pub results: Vec<AutomationActionResult>,
+}
+
+/// グローバルな評価器
+static AUTOMATION_EVALUATOR: Lazy<Mutex<AutomationEvaluator>> =
+    Lazy::new(|| Mutex::new(AutomationEvaluator::new()));
+
+/// 実行履歴（古い順）
+static EXECUTION_LOG: Lazy<Mutex<VecDeque<AutomationExecution>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// 実行履歴を記録（上限を超えた古い履歴は破棄）
+fn record_execution(execution: AutomationExecution) {
+    let mut log = EXECUTION_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    log.push_back(execution);
+    while log.len() > MAX_EXECUTION_LOG {
+        log.pop_front();
+    }
+}
+
+/// 実行履歴を取得（新しい順）
+pub fn get_execution_log() -> Vec<AutomationExecution> {
+    let log = EXECUTION_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    log.iter().rev().cloned().collect()
+}
+
+/// 変更量を反映したビットレート（下限・上限の範囲に収める）
+fn adjusted_bitrate(current_kbps: u32, delta_kbps: i32) -> u32 {
+    let adjusted = i64::from(current_kbps) + i64::from(delta_kbps);
+    let clamped = adjusted.clamp(
+        i64::from(MIN_AUTOMATION_BITRATE_KBPS),
+        i64::from(MAX_AUTOMATION_BITRATE_KBPS),
+    );
+    u32::try_from(clamped).unwrap_or(MIN_AUTOMATION_BITRATE_KBPS)
+}
+
+/// プロファイルの配信ビットレートを変更量だけ上げ下げする
+///
+/// プロファイルパラメータは次回の出力開始時に読み込まれるため、配信中のエンコーダーには
+/// 配信（出力）を再起動するまで反映されない
+///
+/// # Returns
+/// 変更前と変更後のビットレート（kbps）
+async fn adjust_stream_bitrate(client: &ObsClient, delta_kbps: i32) -> Result<(u32, u32), AppError> {
+    let section = match get_output_mode(client).await? {
+        ObsOutputMode::Simple => "SimpleOutput",
+        ObsOutputMode::Advanced => "AdvOut",
+    };
+
+    let current = client
+        .get_profile_parameter(section, "VBitrate")
+        .await?
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .ok_or_else(|| AppError::validation_failed("現在のビットレートを取得できません"))?;
+    let adjusted = adjusted_bitrate(current, delta_kbps);
+
+    if adjusted != current {
+        client
+            .set_profile_parameter(section, "VBitrate", Some(&adjusted.to_string()))
+            .await?;
+    }
+    Ok((current, adjusted))
+}
+
+/// アクションの実行結果
+struct ActionOutcome {
+    /// 結果の説明
+    message: String,
+    /// 出力を再起動するまで反映されないか
+    pending_restart: bool,
+}
+
+impl ActionOutcome {
+    /// すぐに反映された結果
+    fn applied(message: impl Into<String>) -> Self {
+        Self { message: message.into(), pending_restart: false }
+    }
+}
+
+/// アクションを1つ実行
+async fn execute_action<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    rule: &AutomationRule,
+    action: &AutomationAction,
+    play_sound: bool,
+) -> Result<ActionOutcome, AppError> {
+    match action {
+        AutomationAction::AdjustBitrate { delta_kbps } => {
+            let (from, to) = adjust_stream_bitrate(&get_obs_client(), *delta_kbps).await?;
+            if from == to {
+                return Ok(ActionOutcome::applied(format!("ビットレートは既に{to}kbpsです")));
+            }
+            Ok(ActionOutcome {
+                message: format!(
+                    "ビットレートを{from}kbpsから{to}kbpsに変更してプロファイルに保存しました（配信を再開するまで反映されません）"
+                ),
+                pending_restart: true,
+            })
+        }
+        AutomationAction::Notify { message } => {
+            let notification = AlertNotification {
+                alert_id: format!("automation_{}", rule.id),
+                title: rule.name.clone(),
+                body: message.clone(),
+                play_sound,
+                actions: Vec::new(),
+            };
+            emit_alert_notifications(app_handle, &[notification])?;
+            Ok(ActionOutcome::applied("通知を表示しました"))
+        }
+        AutomationAction::SwitchScene { scene_name } => {
+            get_obs_client().set_current_scene(scene_name).await?;
+            // 切り替え中の一時的な負荷でアラートが発火しないようにする
+            suppress_alerts_for_trigger(SuppressionTrigger::SceneTransitionStart).await;
+            Ok(ActionOutcome::applied(format!("シーンを「{scene_name}」に切り替えました")))
+        }
+        AutomationAction::None => Ok(ActionOutcome::applied("実行するアクションはありません")),
+    }
+}
+
+/// 発動したルールのアクションを順に実行
+///
+/// 失敗したアクションがあっても残りのアクションは実行する
+async fn execute_rule<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    firing: &RuleFiring,
+    play_sound: bool,
+) -> AutomationExecution {
+    let rule = &firing.rule;
+    let mut results = Vec::with_capacity(rule.actions.len());
+
+    for action in &rule.actions {
+        let result = execute_action(app_handle, rule, action, play_sound).await;
+        if let Err(e) = &result {
+            tracing::warn!(
+                target: "automation",
+                rule = %rule.name,
+                action = ?action,
+                error = %e,
+                "自動化アクションの実行に失敗"
+            );
+        }
+        results.push(match result {
+            Ok(outcome) => AutomationActionResult {
+                action: action.clone(),
+                success: !outcome.pending_restart,
+                pending_restart: outcome.pending_restart,
+                message: outcome.message,
+            },
+            Err(e) => AutomationActionResult {
+                action: action.clone(),
+                success: false,
+                pending_restart: false,
+                message: e.message().to_string(),
+            },
+        });
+    }
+
+    AutomationExecution {
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        metric: rule.trigger.metric,
+        value: firing.value,
+        executed_at: chrono::Utc::now().timestamp(),
+        results,
+    }
+}
+
+/// メトリクスの更新を自動化ルールで評価し、発動したルールを実行する
+///
+/// 実行したルールは履歴に記録し、`automation:executed` イベントで通知する。
+/// メトリクスごとに頻繁に呼ばれるため、ルールは設定ファイルが保存されたときだけ読み直す
+///
+/// # Arguments
+/// * `metric` - 更新されたメトリクス
+/// * `value` - メトリクスの値
+pub async fn evaluate_automation_rules<R: Runtime>(app_handle: &AppHandle<R>, metric: MetricType, value: f64) {
+    let config = match cached_config() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::debug!(target: "automation", error = %e, "設定を読み込めないため自動化ルールを評価しません");
+            return;
+        }
+    };
+
+    let firings = AUTOMATION_EVALUATOR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .evaluate(&config.automation_rules, metric, value, Instant::now());
+
+    for firing in firings {
+        tracing::info!(
+            target: "automation",
+            rule = %firing.rule.name,
+            metric = ?metric,
+            value = firing.value,
+            "自動化ルールを実行"
+        );
+        let execution = execute_rule(app_handle, &firing, config.alerts.play_sound).await;
+        if let Err(e) = emit_event(app_handle, TauriEvent::AutomationExecuted(execution.clone())) {
+            tracing::warn!(target: "automation", error = %e, "自動化ルールの実行イベントの発行に失敗");
+        }
+        record_execution(execution);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::config::{AutomationTrigger, ThresholdComparison};
+
+    fn rule(
+        id: &str,
+        metric: MetricType,
+        threshold: f64,
+        duration_secs: u64,
+        cooldown_secs: u64,
+    ) -> AutomationRule {
+        AutomationRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            trigger: AutomationTrigger {
+                metric,
+                comparison: ThresholdComparison::Above,
+                threshold,
+                duration_secs,
+            },
+            actions: vec![AutomationAction::AdjustBitrate { delta_kbps: -1000 }],
+            cooldown_secs,
+        }
+    }
+
+    /// 1秒ごとのメトリクスの列を再生し、発動した時刻（開始からの秒数）とルールIDを返す
+    fn replay(
+        evaluator: &mut AutomationEvaluator,
+        rules: &[AutomationRule],
+        metric: MetricType,
+        values: &[f64],
+    ) -> Vec<(u64, String)> {
+        let start = Instant::now();
+        let mut fired = Vec::new();
+        for (second, value) in (0u64..).zip(values) {
+            let now = start + Duration::from_secs(second);
+            for firing in evaluator.evaluate(rules, metric, *value, now) {
+                fired.push((second, firing.rule.id));
+            }
+        }
+        fired
+    }
+
+    #[test]
+    fn test_fires_after_condition_held_for_duration() {
+        let rules = [rule("drop", MetricType::FrameDropRate, 5.0, 30, 120)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        // 0〜9秒は正常、10秒目から閾値超過が続く
+        let mut values = vec![1.0; 10];
+        values.extend(vec![8.0; 40]);
+
+        let fired = replay(&mut evaluator, &rules, MetricType::FrameDropRate, &values);
+        assert_eq!(fired, vec![(40, "drop".to_string())]);
+    }
+
+    #[test]
+    fn test_interrupted_condition_resets_duration() {
+        let rules = [rule("cpu", MetricType::CpuUsage, 95.0, 5, 60)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        // 4秒超過 → 1秒回復 → 5秒超過
+        let values = [96.0, 97.0, 98.0, 99.0, 80.0, 96.0, 96.0, 96.0, 96.0, 96.0, 96.0];
+        let fired = replay(&mut evaluator, &rules, MetricType::CpuUsage, &values);
+        assert_eq!(fired, vec![(10, "cpu".to_string())]);
+    }
+
+    #[test]
+    fn test_cooldown_prevents_repeated_execution() {
+        let rules = [rule("drop", MetricType::FrameDropRate, 5.0, 0, 30)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        // 条件を満たし続けても、クールダウン（30秒）ごとにしか実行しない
+        let fired = replay(&mut evaluator, &rules, MetricType::FrameDropRate, &[10.0; 70]);
+        let seconds: Vec<u64> = fired.into_iter().map(|(second, _)| second).collect();
+        assert_eq!(seconds, vec![0, 30, 60]);
+    }
+
+    #[test]
+    fn test_refire_requires_duration_again_after_cooldown() {
+        let rules = [rule("drop", MetricType::FrameDropRate, 5.0, 20, 10)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        // クールダウンが短くても、再実行には改めて継続時間が必要
+        let fired = replay(&mut evaluator, &rules, MetricType::FrameDropRate, &[10.0; 50]);
+        let seconds: Vec<u64> = fired.into_iter().map(|(second, _)| second).collect();
+        assert_eq!(seconds, vec![20, 40]);
+    }
+
+    #[test]
+    fn test_cooldown_applies_across_separate_episodes() {
+        let rules = [rule("cpu", MetricType::CpuUsage, 95.0, 0, 60)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        // 一度回復してすぐに再び超過しても、クールダウン中は実行しない
+        let mut values = vec![99.0, 50.0, 99.0];
+        values.extend(vec![50.0; 60]);
+        values.push(99.0);
+        let fired = replay(&mut evaluator, &rules, MetricType::CpuUsage, &values);
+        let seconds: Vec<u64> = fired.into_iter().map(|(second, _)| second).collect();
+        assert_eq!(seconds, vec![0, 63]);
+    }
+
+    #[test]
+    fn test_below_comparison() {
+        let mut low_bandwidth = rule("bandwidth", MetricType::NetworkBandwidth, 3.0, 2, 60);
+        low_bandwidth.trigger.comparison = ThresholdComparison::Below;
+        let mut evaluator = AutomationEvaluator::new();
+
+        let fired = replay(
+            &mut evaluator,
+            &[low_bandwidth],
+            MetricType::NetworkBandwidth,
+            &[10.0, 3.0, 2.5, 2.0, 1.0],
+        );
+        assert_eq!(fired, vec![(4, "bandwidth".to_string())]);
+    }
+
+    #[test]
+    fn test_ignores_disabled_rules_other_metrics_and_invalid_values() {
+        let mut disabled = rule("disabled", MetricType::CpuUsage, 90.0, 0, 60);
+        disabled.enabled = false;
+        let gpu = rule("gpu", MetricType::GpuUsage, 90.0, 0, 60);
+        let rules = [disabled, gpu, rule("cpu", MetricType::CpuUsage, 90.0, 0, 60)];
+        let mut evaluator = AutomationEvaluator::new();
+
+        let fired = replay(&mut evaluator, &rules, MetricType::CpuUsage, &[f64::NAN, 95.0]);
+        assert_eq!(fired, vec![(1, "cpu".to_string())]);
+    }
+
+    #[test]
+    fn test_multiple_rules_are_independent() {
+        let rules = [
+            rule("warn", MetricType::CpuUsage, 90.0, 2, 60),
+            rule("critical", MetricType::CpuUsage, 95.0, 4, 60),
+        ];
+        let mut evaluator = AutomationEvaluator::new();
+
+        let values = [91.0, 92.0, 96.0, 96.0, 96.0, 96.0, 96.0];
+        let fired = replay(&mut evaluator, &rules, MetricType::CpuUsage, &values);
+        assert_eq!(fired, vec![(2, "warn".to_string()), (6, "critical".to_string())]);
+    }
+
+    #[test]
+    fn test_removed_rule_state_is_discarded() {
+        let rules = [rule("cpu", MetricType::CpuUsage, 90.0, 0, 600)];
+        let mut evaluator = AutomationEvaluator::new();
+        let now = Instant::now();
+
+        assert_eq!(evaluator.evaluate(&rules, MetricType::CpuUsage, 95.0, now).len(), 1);
+        assert!(evaluator.evaluate(&[], MetricType::CpuUsage, 95.0, now).is_empty());
+
+        // 同じIDで作り直したルールはクールダウンを引き継がない
+        assert_eq!(evaluator.evaluate(&rules, MetricType::CpuUsage, 95.0, now).len(), 1);
+    }
+
+    #[test]
+    fn test_frame_drop_rate_tracker() {
+        let mut tracker = FrameDropRateTracker::new();
+        assert_eq!(tracker.update(1000, 0), None);
+        assert_eq!(tracker.update(1060, 6), Some(10.0));
+        assert_eq!(tracker.update(1120, 6), Some(0.0));
+        // 送出フレームが増えていない
+        assert_eq!(tracker.update(1120, 6), None);
+        // 配信の再開で累積値が減った
+        assert_eq!(tracker.update(60, 0), None);
+        assert_eq!(tracker.update(120, 3), Some(5.0));
+    }
+
+    #[test]
+    fn test_adjusted_bitrate_is_clamped() {
+        assert_eq!(adjusted_bitrate(6000, -1000), 5000);
+        assert_eq!(adjusted_bitrate(6000, 2000), 8000);
+        assert_eq!(adjusted_bitrate(1000, -1000), MIN_AUTOMATION_BITRATE_KBPS);
+        assert_eq!(adjusted_bitrate(49_000, 5000), MAX_AUTOMATION_BITRATE_KBPS);
+    }
+}
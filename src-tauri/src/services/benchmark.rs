@@ -0,0 +1,519 @@
+// エンコーダーベンチマーク
+//
+// 短時間のローカル録画中にCPU/GPU/エンコーダー使用率とレンダリング時間を計測し、
+// 実機での余力（ヘッドルーム）とプリセット調整の判定を算出する。
+// ティア表によるヒューリスティックな推奨を実測で補正するために使用する。
+
+use crate::error::AppError;
+use crate::obs::ObsOutputMode;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::{watch, RwLock};
+
+/// デフォルトの計測時間（秒）
+pub const DEFAULT_BENCHMARK_DURATION_SECS: u64 = 30;
+/// 計測時間の下限（秒）
+pub const MIN_BENCHMARK_DURATION_SECS: u64 = 10;
+/// 計測時間の上限（秒）
+pub const MAX_BENCHMARK_DURATION_SECS: u64 = 120;
+
+/// 判定しきい値: 余力がこれ未満なら不合格（%）
+const FAIL_HEADROOM_PERCENT: f32 = 10.0;
+/// 判定しきい値: 余力がこれ未満ならぎりぎり（%）
+const MARGINAL_HEADROOM_PERCENT: f32 = 25.0;
+/// 判定しきい値: スキップフレーム率がこれを超えたら不合格
+const FAIL_SKIPPED_RATIO: f64 = 0.01;
+/// 判定しきい値: スキップフレーム率がこれを超えたらぎりぎり
+const MARGINAL_SKIPPED_RATIO: f64 = 0.001;
+/// 判定しきい値: レンダリング時間がフレーム予算のこの割合を超えたらぎりぎり
+const MARGINAL_RENDER_BUDGET_RATIO: f64 = 0.7;
+
+/// エンコーダー単体のベンチマークの計測時間の下限（秒）
+pub const MIN_ENCODER_BENCHMARK_DURATION_SECS: u8 = 5;
+/// エンコーダー単体のベンチマークの計測時間の上限（秒）
+pub const MAX_ENCODER_BENCHMARK_DURATION_SECS: u8 = 60;
+/// 目標FPSに対してこの割合以上のフレームを出力できれば、持続可能とみなす
+pub const SUSTAINED_FPS_RATIO: f64 = 0.98;
+
+/// 直近のベンチマーク結果（推奨エンジンでのプリセット補正に使用）
+static LAST_BENCHMARK_RESULT: Lazy<RwLock<Option<BenchmarkResult>>> =
+    Lazy::new(|| RwLock::new(None));
+
+/// ベンチマーク中の1回分の計測値
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkSample {
+    /// CPU使用率（%）
+    pub cpu_usage: f32,
+    /// GPU使用率（%）
+    pub gpu_usage: Option<f32>,
+    /// ハードウェアエンコーダー使用率（%）
+    pub encoder_usage: Option<f32>,
+    /// 平均フレームレンダリング時間（ミリ秒）
+    pub average_frame_render_time_ms: f64,
+}
+
+/// OBSのフレームカウンター（累積値）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameCounters {
+    /// レンダリングでスキップされたフレーム数
+    pub render_skipped: u32,
+    /// レンダリングされた総フレーム数
+    pub render_total: u32,
+    /// エンコードでスキップされたフレーム数
+    pub output_skipped: u32,
+    /// 出力された総フレーム数
+    pub output_total: u32,
+}
+
+impl FrameCounters {
+    /// 開始時点からの差分を算出
+    pub const fn since(&self, start: &Self) -> Self {
+        Self {
+            render_skipped: self.render_skipped.saturating_sub(start.render_skipped),
+            render_total: self.render_total.saturating_sub(start.render_total),
+            output_skipped: self.output_skipped.saturating_sub(start.output_skipped),
+            output_total: self.output_total.saturating_sub(start.output_total),
+        }
+    }
+
+    /// スキップフレーム率（レンダリング・エンコードのうち悪い方）
+    pub fn skipped_ratio(&self) -> f64 {
+        let ratio = |skipped: u32, total: u32| {
+            if total == 0 {
+                0.0
+            } else {
+                f64::from(skipped) / f64::from(total)
+            }
+        };
+        ratio(self.render_skipped, self.render_total)
+            .max(ratio(self.output_skipped, self.output_total))
+    }
+}
+
+/// ベンチマーク判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BenchmarkVerdict {
+    /// 十分な余力あり（プリセットを1段階高画質にできる）
+    Pass,
+    /// 余力がぎりぎり（プリセットは維持）
+    Marginal,
+    /// 余力不足（プリセットを1段階軽くする）
+    Fail,
+}
+
+impl BenchmarkVerdict {
+    /// プリセットの調整段数（正: 高画質側、負: 軽量側）
+    pub const fn preset_step(self) -> i32 {
+        match self {
+            Self::Pass => 1,
+            Self::Marginal => 0,
+            Self::Fail => -1,
+        }
+    }
+}
+
+/// ベンチマーク結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResult {
+    /// 計測したエンコーダー
+    pub encoder: String,
+    /// 計測したプリセット
+    pub preset: Option<String>,
+    /// 計測時の目標FPS
+    pub target_fps: u32,
+    /// 計測時間（秒）
+    pub duration_secs: u64,
+    /// サンプル数
+    pub sample_count: usize,
+    /// 平均CPU使用率（%）
+    pub avg_cpu_usage: f32,
+    /// 最大CPU使用率（%）
+    pub peak_cpu_usage: f32,
+    /// 平均GPU使用率（%）
+    pub avg_gpu_usage: Option<f32>,
+    /// 平均エンコーダー使用率（%）
+    pub avg_encoder_usage: Option<f32>,
+    /// 平均フレームレンダリング時間（ミリ秒）
+    pub avg_render_time_ms: f64,
+    /// スキップフレーム率（0.0-1.0）
+    pub skipped_frame_ratio: f64,
+    /// 実測の余力（%）
+    pub headroom_percent: f32,
+    /// 判定
+    pub verdict: BenchmarkVerdict,
+    /// 判定に基づく推奨プリセット（調整不要・不明な場合は`None`）
+    pub suggested_preset: Option<String>,
+    /// 計測日時（UNIX epoch秒）
+    pub measured_at: i64,
+}
+
+/// 平均値を算出（値がない場合は`None`）
+fn average(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0f32, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// 計測値からベンチマーク結果を算出
+///
+/// # Arguments
+/// * `encoder` - 計測したエンコーダーID
+/// * `preset` - 計測したプリセット
+/// * `target_fps` - 目標FPS（フレーム予算の算出に使用）
+/// * `duration_secs` - 計測時間（秒）
+/// * `samples` - 計測値
+/// * `frames` - 計測期間中のフレームカウンター差分
+pub fn evaluate_benchmark(
+    encoder: &str,
+    preset: Option<&str>,
+    target_fps: u32,
+    duration_secs: u64,
+    samples: &[BenchmarkSample],
+    frames: FrameCounters,
+) -> BenchmarkResult {
+    let avg_cpu_usage = average(samples.iter().map(|s| s.cpu_usage)).unwrap_or(0.0);
+    let peak_cpu_usage = samples.iter().map(|s| s.cpu_usage).fold(0.0f32, f32::max);
+    let avg_gpu_usage = average(samples.iter().filter_map(|s| s.gpu_usage));
+    let avg_encoder_usage = average(samples.iter().filter_map(|s| s.encoder_usage));
+    let avg_render_time_ms = if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().map(|s| s.average_frame_render_time_ms).sum::<f64>() / samples.len() as f64
+    };
+
+    // 最も逼迫しているリソースを基準に余力を算出
+    let load = avg_cpu_usage
+        .max(avg_gpu_usage.unwrap_or(0.0))
+        .max(avg_encoder_usage.unwrap_or(0.0));
+    let headroom_percent = (100.0 - load).clamp(0.0, 100.0);
+
+    let skipped_frame_ratio = frames.skipped_ratio();
+    let frame_budget_ms = 1000.0 / f64::from(target_fps.max(1));
+
+    let verdict = if headroom_percent < FAIL_HEADROOM_PERCENT
+        || skipped_frame_ratio > FAIL_SKIPPED_RATIO
+        || avg_render_time_ms > frame_budget_ms
+    {
+        BenchmarkVerdict::Fail
+    } else if headroom_percent < MARGINAL_HEADROOM_PERCENT
+        || skipped_frame_ratio > MARGINAL_SKIPPED_RATIO
+        || avg_render_time_ms > frame_budget_ms * MARGINAL_RENDER_BUDGET_RATIO
+    {
+        BenchmarkVerdict::Marginal
+    } else {
+        BenchmarkVerdict::Pass
+    };
+
+    let suggested_preset = preset.and_then(|preset| {
+        let adjusted = super::optimizer::RecommendationEngine::adjust_preset_for_benchmark(encoder, preset, verdict);
+        (adjusted != preset).then_some(adjusted)
+    });
+
+    BenchmarkResult {
+        encoder: encoder.to_string(),
+        preset: preset.map(ToString::to_string),
+        target_fps,
+        duration_secs,
+        sample_count: samples.len(),
+        avg_cpu_usage,
+        peak_cpu_usage,
+        avg_gpu_usage,
+        avg_encoder_usage,
+        avg_render_time_ms,
+        skipped_frame_ratio,
+        headroom_percent,
+        verdict,
+        suggested_preset,
+        measured_at: chrono::Utc::now().timestamp(),
+    }
+}
+
+/// 直近のベンチマーク結果を保存
+pub async fn store_benchmark_result(result: BenchmarkResult) {
+    *LAST_BENCHMARK_RESULT.write().await = Some(result);
+}
+
+/// 直近のベンチマーク結果を取得
+pub async fn last_benchmark_result() -> Option<BenchmarkResult> {
+    LAST_BENCHMARK_RESULT.read().await.clone()
+}
+
+/// 実行中のエンコーダー単体のベンチマークへの中断要求（trueで中断）
+static ENCODER_BENCHMARK_CANCEL: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+
+/// エンコーダー単体のベンチマーク結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncoderBenchmarkResult {
+    /// 実測の平均FPS（エンコードされたフレーム数 / 経過時間）
+    pub avg_fps: f32,
+    /// 取りこぼしたフレーム数（レンダリング・エンコードの遅延によるもの）
+    pub dropped_frames: u32,
+    /// 1フレームあたりの平均処理時間（ミリ秒）
+    pub avg_encode_time_ms: f32,
+    /// 1フレームあたりの処理時間の最大値（ミリ秒）
+    pub max_encode_time_ms: f32,
+    /// 目標FPSを維持できたか（平均FPSが目標の98%以上）
+    pub sustained: bool,
+}
+
+/// 計測値からエンコーダー単体のベンチマーク結果を算出
+///
+/// OBS WebSocketはエンコーダー自体の処理時間を公開していないため、
+/// 1秒ごとに取得したフレームの処理時間（`averageFrameRenderTime`）を1フレームあたりの処理時間とする
+///
+/// # Arguments
+/// * `target_fps` - 目標FPS
+/// * `elapsed_secs` - 実際の計測時間（秒）
+/// * `frames` - 計測期間中のフレームカウンター差分
+/// * `frame_times_ms` - 1秒ごとのフレームの処理時間（ミリ秒）
+pub fn interpret_encoder_benchmark(
+    target_fps: u32,
+    elapsed_secs: f64,
+    frames: FrameCounters,
+    frame_times_ms: &[f64],
+) -> EncoderBenchmarkResult {
+    let encoded_frames = frames.output_total.saturating_sub(frames.output_skipped);
+    let avg_fps = if elapsed_secs > 0.0 {
+        f64::from(encoded_frames) / elapsed_secs
+    } else {
+        0.0
+    };
+    let avg_encode_time_ms = if frame_times_ms.is_empty() {
+        0.0
+    } else {
+        (frame_times_ms.iter().sum::<f64>() / frame_times_ms.len() as f64) as f32
+    };
+    let max_encode_time_ms = frame_times_ms.iter().copied().fold(0.0f64, f64::max) as f32;
+
+    EncoderBenchmarkResult {
+        avg_fps: avg_fps as f32,
+        dropped_frames: frames.output_skipped.saturating_add(frames.render_skipped),
+        avg_encode_time_ms,
+        max_encode_time_ms,
+        sustained: target_fps > 0 && avg_fps >= f64::from(target_fps) * SUSTAINED_FPS_RATIO,
+    }
+}
+
+/// エンコーダー単体のベンチマークで一時的に変更するプロファイルパラメータ（セクション, 名前, 値）
+///
+/// 録画に配信用のエンコーダー設定を使わせ、配信用のエンコーダー・ビットレートを計測対象に差し替える。
+/// 基本モードでは録画の画質を「配信と同じ」、詳細モードでは録画エンコーダーを「配信エンコーダーを使用」にする
+///
+/// # Arguments
+/// * `output_mode` - OBSの出力モード
+/// * `encoder_id` - 計測するエンコーダーID
+/// * `bitrate_kbps` - ビットレート（kbps）
+pub fn encoder_benchmark_parameters(
+    output_mode: ObsOutputMode,
+    encoder_id: &str,
+    bitrate_kbps: u32,
+) -> Vec<(&'static str, &'static str, String)> {
+    match output_mode {
+        ObsOutputMode::Simple => vec![
+            ("SimpleOutput", "RecQuality", "Stream".to_string()),
+            ("SimpleOutput", "StreamEncoder", encoder_id.to_string()),
+            ("SimpleOutput", "VBitrate", bitrate_kbps.to_string()),
+        ],
+        ObsOutputMode::Advanced => vec![
+            ("AdvOut", "RecType", "Standard".to_string()),
+            ("AdvOut", "RecEncoder", "none".to_string()),
+            ("AdvOut", "Encoder", encoder_id.to_string()),
+            ("AdvOut", "VBitrate", bitrate_kbps.to_string()),
+        ],
+    }
+}
+
+/// エンコーダー単体のベンチマークの開始を登録し、中断要求の受信側を返す
+///
+/// 以前の中断要求はリセットされる
+pub fn begin_encoder_benchmark() -> watch::Receiver<bool> {
+    ENCODER_BENCHMARK_CANCEL.send_replace(false);
+    ENCODER_BENCHMARK_CANCEL.subscribe()
+}
+
+/// 実行中のエンコーダー単体のベンチマークを中断
+///
+/// # Returns
+/// 実行中のベンチマークがあったか
+pub fn cancel_encoder_benchmark() -> bool {
+    ENCODER_BENCHMARK_CANCEL.send_replace(true);
+    ENCODER_BENCHMARK_CANCEL.receiver_count() > 0
+}
+
+/// 指定時間待機する。待機中に中断が要求された場合は `BENCHMARK_CANCELLED` エラー
+///
+/// # Arguments
+/// * `duration` - 待機時間
+/// * `cancel` - `begin_encoder_benchmark` で取得した受信側
+pub async fn sleep_or_cancelled(duration: Duration, cancel: &mut watch::Receiver<bool>) -> Result<(), AppError> {
+    tokio::select! {
+        () = tokio::time::sleep(duration) => Ok(()),
+        _ = cancel.wait_for(|cancelled| *cancelled) => Err(AppError::benchmark_cancelled()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu: f32, gpu: Option<f32>, render_ms: f64) -> BenchmarkSample {
+        BenchmarkSample {
+            cpu_usage: cpu,
+            gpu_usage: gpu,
+            encoder_usage: None,
+            average_frame_render_time_ms: render_ms,
+        }
+    }
+
+    fn frames(skipped: u32, total: u32) -> FrameCounters {
+        FrameCounters {
+            render_skipped: 0,
+            render_total: total,
+            output_skipped: skipped,
+            output_total: total,
+        }
+    }
+
+    #[test]
+    fn test_light_load_passes_and_suggests_slower_preset() {
+        let samples = vec![sample(30.0, Some(40.0), 3.0); 30];
+        let result = evaluate_benchmark("ffmpeg_nvenc", Some("p5"), 60, 30, &samples, frames(0, 1800));
+
+        assert_eq!(result.verdict, BenchmarkVerdict::Pass);
+        assert!((result.headroom_percent - 60.0).abs() < 0.01);
+        assert_eq!(result.suggested_preset.as_deref(), Some("p6"));
+    }
+
+    #[test]
+    fn test_high_load_fails_and_suggests_faster_preset() {
+        let samples = vec![sample(95.0, Some(50.0), 5.0); 30];
+        let result = evaluate_benchmark("obs_x264", Some("veryfast"), 60, 30, &samples, frames(0, 1800));
+
+        assert_eq!(result.verdict, BenchmarkVerdict::Fail);
+        assert_eq!(result.suggested_preset.as_deref(), Some("superfast"));
+    }
+
+    #[test]
+    fn test_skipped_frames_fail_even_with_headroom() {
+        let samples = vec![sample(30.0, Some(30.0), 3.0); 30];
+        let result = evaluate_benchmark("ffmpeg_nvenc", Some("p5"), 60, 30, &samples, frames(50, 1800));
+
+        assert_eq!(result.verdict, BenchmarkVerdict::Fail);
+    }
+
+    #[test]
+    fn test_render_lag_marginal() {
+        // 60fpsのフレーム予算は約16.7ms。その70%超で「ぎりぎり」
+        let samples = vec![sample(30.0, Some(30.0), 13.0); 30];
+        let result = evaluate_benchmark("ffmpeg_nvenc", Some("p5"), 60, 30, &samples, frames(0, 1800));
+
+        assert_eq!(result.verdict, BenchmarkVerdict::Marginal);
+        assert!(result.suggested_preset.is_none(), "ぎりぎりの場合はプリセット維持");
+    }
+
+    #[test]
+    fn test_empty_samples() {
+        let result = evaluate_benchmark("obs_x264", None, 30, 30, &[], FrameCounters::default());
+
+        assert_eq!(result.sample_count, 0);
+        assert!(result.suggested_preset.is_none());
+    }
+
+    #[test]
+    fn test_frame_counters_since() {
+        let start = FrameCounters { render_skipped: 5, render_total: 100, output_skipped: 2, output_total: 100 };
+        let end = FrameCounters { render_skipped: 5, render_total: 1100, output_skipped: 12, output_total: 1100 };
+
+        let delta = end.since(&start);
+        assert_eq!(delta.render_total, 1000);
+        assert_eq!(delta.output_skipped, 10);
+        assert!((delta.skipped_ratio() - 0.01).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_store_and_load_last_result() {
+        let result = evaluate_benchmark("obs_x264", Some("veryfast"), 30, 30, &[], FrameCounters::default());
+        store_benchmark_result(result).await;
+
+        let loaded = last_benchmark_result().await.unwrap();
+        assert_eq!(loaded.encoder, "obs_x264");
+    }
+
+    fn output_frames(total: u32, output_skipped: u32, render_skipped: u32) -> FrameCounters {
+        FrameCounters {
+            render_skipped,
+            render_total: total,
+            output_skipped,
+            output_total: total,
+        }
+    }
+
+    #[test]
+    fn test_encoder_benchmark_sustained_at_target() {
+        let result = interpret_encoder_benchmark(60, 10.0, output_frames(600, 0, 0), &[4.0, 6.0, 5.0]);
+        assert!((result.avg_fps - 60.0).abs() < f32::EPSILON);
+        assert_eq!(result.dropped_frames, 0);
+        assert!((result.avg_encode_time_ms - 5.0).abs() < f32::EPSILON);
+        assert!((result.max_encode_time_ms - 6.0).abs() < f32::EPSILON);
+        assert!(result.sustained);
+    }
+
+    #[test]
+    fn test_encoder_benchmark_sustained_threshold() {
+        // 目標の98%（58.8fps）以上は持続可能
+        let result = interpret_encoder_benchmark(60, 10.0, output_frames(600, 10, 0), &[]);
+        assert!((result.avg_fps - 59.0).abs() < 0.01);
+        assert!(result.sustained);
+        // 98%未満は持続不可
+        let result = interpret_encoder_benchmark(60, 10.0, output_frames(600, 13, 0), &[]);
+        assert!((result.avg_fps - 58.7).abs() < 0.01);
+        assert!(!result.sustained);
+    }
+
+    #[test]
+    fn test_encoder_benchmark_counts_dropped_frames() {
+        let result = interpret_encoder_benchmark(30, 10.0, output_frames(300, 45, 5), &[40.0]);
+        assert_eq!(result.dropped_frames, 50);
+        assert!((result.avg_fps - 25.5).abs() < 0.01);
+        assert!(!result.sustained);
+    }
+
+    #[test]
+    fn test_encoder_benchmark_without_measurement() {
+        let result = interpret_encoder_benchmark(60, 0.0, FrameCounters::default(), &[]);
+        assert!(result.avg_fps.abs() < f32::EPSILON);
+        assert!(result.avg_encode_time_ms.abs() < f32::EPSILON);
+        assert!(!result.sustained);
+    }
+
+    #[tokio::test]
+    async fn test_encoder_benchmark_cancellation() {
+        let mut cancel = begin_encoder_benchmark();
+        let waiting = tokio::spawn(async move { sleep_or_cancelled(Duration::from_secs(60), &mut cancel).await });
+        tokio::task::yield_now().await;
+
+        assert!(cancel_encoder_benchmark());
+        let error = tokio::time::timeout(Duration::from_secs(5), waiting).await.unwrap().unwrap().unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_BENCHMARK_CANCELLED);
+
+        // 次のベンチマークは中断要求をリセットして開始する
+        let mut cancel = begin_encoder_benchmark();
+        assert!(sleep_or_cancelled(Duration::from_millis(1), &mut cancel).await.is_ok());
+    }
+
+    #[test]
+    fn test_encoder_benchmark_parameters() {
+        let simple = encoder_benchmark_parameters(ObsOutputMode::Simple, "ffmpeg_nvenc", 6000);
+        assert!(simple.iter().all(|(section, _, _)| *section == "SimpleOutput"));
+        assert!(simple.contains(&("SimpleOutput", "RecQuality", "Stream".to_string())));
+        assert!(simple.contains(&("SimpleOutput", "VBitrate", "6000".to_string())));
+
+        let advanced = encoder_benchmark_parameters(ObsOutputMode::Advanced, "ffmpeg_nvenc", 6000);
+        assert!(advanced.contains(&("AdvOut", "RecEncoder", "none".to_string())));
+        assert!(advanced.contains(&("AdvOut", "Encoder", "ffmpeg_nvenc".to_string())));
+    }
+}
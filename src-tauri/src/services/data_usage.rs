@@ -0,0 +1,354 @@
+// データ通信量（アップロード量）の集計
+//
+// モバイル回線・衛星回線など通信量に上限がある配信者向けに、
+// セッションごとのアップロード量を記録し、月ごとの使用量と上限までの残りを集計する。
+// アップロード速度（バイト/秒）をメトリクス取得のたびに積算し、一定間隔で履歴DBに書き込む。
+
+use crate::error::AppError;
+use crate::services::alerts::{update_data_quota_and_notify, AlertSeverity};
+use crate::services::exporter::month_bounds;
+use crate::storage::config::load_config;
+use crate::storage::metrics_history::{history_store, SessionSummary};
+use chrono::Datelike;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+/// 上限までの残りがこれを下回ると警告アラート（GB）
+pub const QUOTA_WARNING_REMAINING_GB: f64 = 5.0;
+
+/// 上限までの残りがこれを下回るとクリティカルアラート（GB）
+pub const QUOTA_CRITICAL_REMAINING_GB: f64 = 1.0;
+
+/// アラートの重要度と、発火する残量の閾値（GB）
+pub const QUOTA_ALERT_LEVELS: [(AlertSeverity, f64); 2] = [
+    (AlertSeverity::Warning, QUOTA_WARNING_REMAINING_GB),
+    (AlertSeverity::Critical, QUOTA_CRITICAL_REMAINING_GB),
+];
+
+/// 1GBのバイト数（通信事業者の表記に合わせて10進数）
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+
+/// 積算に使うサンプル間隔の上限
+///
+/// 画面を閉じていた等でメトリクスの取得が止まっていた間を、同じ速度で送信し続けたとはみなさない
+const MAX_SAMPLE_GAP: Duration = Duration::from_secs(10);
+
+/// 積算したアップロード量を履歴DBに書き込む間隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// セッションごとのデータ使用量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDataUsage {
+    /// セッションID
+    pub session_id: String,
+    /// タイトル
+    pub title: Option<String>,
+    /// 開始時刻（UNIX epoch秒）
+    pub start_time: i64,
+    /// 終了時刻（UNIX epoch秒）
+    pub end_time: i64,
+    /// アップロード量（GB）
+    pub uploaded_gb: f64,
+    /// 上限の集計対象か（手動リセットより前に始まったセッションはfalse）
+    pub counts_toward_quota: bool,
+}
+
+/// 月間のデータ使用量の集計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataUsageSummary {
+    /// 月間のアップロード量の合計（GB）
+    pub total_gb: f64,
+    /// セッションごとの使用量（開始時刻の古い順）
+    pub sessions: Vec<SessionDataUsage>,
+    /// 上限までの残り（GB、上限が未設定の場合はNone）
+    pub quota_remaining_gb: Option<f64>,
+    /// 上限に対する使用率（%、上限が未設定の場合はNone）
+    pub quota_percent_used: Option<f32>,
+}
+
+/// バイト数をGBに変換
+pub fn bytes_to_gb(bytes: u64) -> f64 {
+    bytes as f64 / BYTES_PER_GB
+}
+
+/// 上限までの残り（GB、上限を超えた場合は0）
+pub fn quota_remaining_gb(used_gb: f64, quota_gb: Option<f64>) -> Option<f64> {
+    quota_gb.map(|quota| (quota - used_gb).max(0.0))
+}
+
+/// 上限に対する使用率（%、上限を超えた場合は100を超える）
+pub fn quota_percent_used(used_gb: f64, quota_gb: Option<f64>) -> Option<f32> {
+    quota_gb
+        .filter(|quota| *quota > 0.0)
+        .map(|quota| (used_gb / quota * 100.0) as f32)
+}
+
+/// 上限までの残りに応じたアラートの重要度（閾値以上残っている場合はNone）
+pub fn quota_alert_severity(remaining_gb: Option<f64>) -> Option<AlertSeverity> {
+    let remaining = remaining_gb?;
+    if remaining < QUOTA_CRITICAL_REMAINING_GB {
+        Some(AlertSeverity::Critical)
+    } else if remaining < QUOTA_WARNING_REMAINING_GB {
+        Some(AlertSeverity::Warning)
+    } else {
+        None
+    }
+}
+
+/// 月のセッションからデータ使用量を集計
+///
+/// アップロード量は時刻ごとに記録していないため、月をまたぐセッションは開始した月に計上する。
+///
+/// # Arguments
+/// * `sessions` - 月に重なるセッション
+/// * `from` / `to` - 月の開始・終了時刻（`month_bounds` の戻り値）
+/// * `quota_gb` - 月間の上限（GB）
+/// * `quota_reset_at` - 上限の集計を手動でリセットした日時（これより前に始まったセッションは上限の集計から除く）
+pub fn summarize_data_usage(
+    sessions: &[SessionSummary],
+    from: i64,
+    to: i64,
+    quota_gb: Option<f64>,
+    quota_reset_at: Option<i64>,
+) -> DataUsageSummary {
+    let quota_from = quota_reset_at.map_or(from, |reset_at| reset_at.max(from));
+
+    let sessions: Vec<SessionDataUsage> = sessions
+        .iter()
+        .filter(|session| (from..to).contains(&session.start_time))
+        .map(|session| SessionDataUsage {
+            session_id: session.session_id.clone(),
+            title: session.title.clone(),
+            start_time: session.start_time,
+            end_time: session.end_time,
+            uploaded_gb: bytes_to_gb(session.bytes_uploaded),
+            counts_toward_quota: session.start_time >= quota_from,
+        })
+        .collect();
+
+    let total_gb = sessions.iter().map(|session| session.uploaded_gb).sum();
+    let quota_used_gb: f64 = sessions
+        .iter()
+        .filter(|session| session.counts_toward_quota)
+        .map(|session| session.uploaded_gb)
+        .sum();
+
+    DataUsageSummary {
+        total_gb,
+        sessions,
+        quota_remaining_gb: quota_remaining_gb(quota_used_gb, quota_gb),
+        quota_percent_used: quota_percent_used(quota_used_gb, quota_gb),
+    }
+}
+
+/// 指定した月のデータ使用量を履歴DBから集計
+///
+/// 月の区切りはローカルタイムゾーンで判定する
+pub async fn load_data_usage_summary(year: u32, month: u8) -> Result<DataUsageSummary, AppError> {
+    let offset = *chrono::Local::now().offset();
+    let (from, to) = month_bounds(year, month, offset)?;
+
+    let config = load_config()?;
+    let sessions = history_store().await?.list_sessions_overlapping(from, to).await?;
+    Ok(summarize_data_usage(&sessions, from, to, config.monthly_quota_gb, config.quota_reset_at))
+}
+
+/// 今月のデータ使用量を集計
+pub async fn load_current_month_usage() -> Result<DataUsageSummary, AppError> {
+    let now = chrono::Local::now();
+    // 月は1〜12のため変換は失敗しない
+    let month = u8::try_from(now.month()).unwrap_or(1);
+    load_data_usage_summary(now.year().unsigned_abs(), month).await
+}
+
+/// アップロード速度のサンプルを積算し、一定間隔で書き込む量を返す
+#[derive(Debug, Default)]
+pub struct UploadAccumulator {
+    /// 前回のサンプル時刻
+    last_sample: Option<Instant>,
+    /// 前回の書き込み時刻
+    last_flush: Option<Instant>,
+    /// 未書き込みのアップロード量（バイト）
+    pending_bytes: u64,
+}
+
+impl UploadAccumulator {
+    /// アップロード速度のサンプルを積算
+    ///
+    /// 初回のサンプルは基準時刻の記録のみ行う
+    ///
+    /// # Arguments
+    /// * `upload_bytes_per_sec` - アップロード速度（バイト/秒）
+    /// * `now` - サンプル時刻
+    pub fn add_sample(&mut self, upload_bytes_per_sec: u64, now: Instant) {
+        if let Some(last) = self.last_sample {
+            let elapsed = now.saturating_duration_since(last).min(MAX_SAMPLE_GAP);
+            let bytes = (upload_bytes_per_sec as f64 * elapsed.as_secs_f64()) as u64;
+            self.pending_bytes = self.pending_bytes.saturating_add(bytes);
+        }
+        self.last_sample = Some(now);
+        self.last_flush.get_or_insert(now);
+    }
+
+    /// 書き込み間隔が経過していれば未書き込みの量を取り出す
+    pub fn take_pending(&mut self, now: Instant) -> Option<u64> {
+        let last_flush = self.last_flush?;
+        if now.saturating_duration_since(last_flush) < FLUSH_INTERVAL || self.pending_bytes == 0 {
+            return None;
+        }
+        self.last_flush = Some(now);
+        Some(std::mem::take(&mut self.pending_bytes))
+    }
+}
+
+/// グローバルなアップロード量の積算
+static UPLOAD_ACCUMULATOR: Lazy<Mutex<UploadAccumulator>> =
+    Lazy::new(|| Mutex::new(UploadAccumulator::default()));
+
+/// アップロード速度のサンプルを記録
+///
+/// 書き込み間隔ごとに現在のセッションのアップロード量へ加算し、
+/// 上限が設定されている場合は今月の残りに応じてアラートを更新する（`app_handle` がない場合は通知しない）。
+/// 失敗してもメトリクスの取得は続けられるよう、ログに記録するだけにする
+pub async fn record_upload_sample<R: Runtime>(app_handle: Option<&AppHandle<R>>, upload_bytes_per_sec: u64) {
+    let pending = {
+        let mut accumulator = UPLOAD_ACCUMULATOR.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        accumulator.add_sample(upload_bytes_per_sec, now);
+        accumulator.take_pending(now)
+    };
+    let Some(bytes) = pending else {
+        return;
+    };
+
+    let result = async {
+        if history_store().await?.add_bytes_uploaded(bytes).await?.is_none() {
+            return Ok(());
+        }
+        let Some(app_handle) = app_handle else {
+            return Ok(());
+        };
+        if load_config()?.monthly_quota_gb.is_none() {
+            return Ok(());
+        }
+        let summary = load_current_month_usage().await?;
+        update_data_quota_and_notify(app_handle, summary.quota_remaining_gb).await?;
+        Ok::<(), AppError>(())
+    };
+    if let Err(e) = result.await {
+        tracing::warn!(target: "data_usage", error = %e, "アップロード量の記録に失敗");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, start_time: i64, bytes_uploaded: u64) -> SessionSummary {
+        SessionSummary {
+            session_id: id.to_string(),
+            start_time,
+            end_time: start_time + 3_600,
+            avg_cpu: 0.0,
+            avg_gpu: 0.0,
+            total_dropped_frames: 0,
+            peak_bitrate: 0,
+            quality_score: 0.0,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded,
+        }
+    }
+
+    #[test]
+    fn test_quota_percentage_calculation() {
+        assert_eq!(quota_percent_used(25.0, Some(100.0)), Some(25.0));
+        assert_eq!(quota_remaining_gb(25.0, Some(100.0)), Some(75.0));
+
+        // 上限を超えた場合、残りは0、使用率は100%を超える
+        assert_eq!(quota_remaining_gb(120.0, Some(100.0)), Some(0.0));
+        assert_eq!(quota_percent_used(120.0, Some(100.0)), Some(120.0));
+    }
+
+    #[test]
+    fn test_quota_not_configured() {
+        assert_eq!(quota_remaining_gb(25.0, None), None);
+        assert_eq!(quota_percent_used(25.0, None), None);
+        assert_eq!(quota_alert_severity(None), None);
+
+        let summary = summarize_data_usage(&[session("a", 100, 3_000_000_000)], 0, 1_000, None, None);
+        assert!((summary.total_gb - 3.0).abs() < f64::EPSILON);
+        assert_eq!(summary.quota_remaining_gb, None);
+        assert_eq!(summary.quota_percent_used, None);
+    }
+
+    #[test]
+    fn test_quota_alert_thresholds() {
+        assert_eq!(quota_alert_severity(Some(10.0)), None);
+        assert_eq!(quota_alert_severity(Some(5.0)), None);
+        assert_eq!(quota_alert_severity(Some(4.9)), Some(AlertSeverity::Warning));
+        assert_eq!(quota_alert_severity(Some(1.0)), Some(AlertSeverity::Warning));
+        assert_eq!(quota_alert_severity(Some(0.9)), Some(AlertSeverity::Critical));
+        assert_eq!(quota_alert_severity(Some(0.0)), Some(AlertSeverity::Critical));
+    }
+
+    #[test]
+    fn test_summarize_data_usage_counts_sessions_started_in_month() {
+        let sessions = [
+            // 前月に始まり今月にまたがるセッションは前月に計上
+            session("previous", -1_800, 1_000_000_000),
+            session("first", 100, 2_000_000_000),
+            session("second", 500, 3_000_000_000),
+        ];
+
+        let summary = summarize_data_usage(&sessions, 0, 1_000, Some(10.0), None);
+        let ids: Vec<&str> = summary.sessions.iter().map(|s| s.session_id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "second"]);
+        assert!((summary.total_gb - 5.0).abs() < 1e-9);
+        assert_eq!(summary.quota_remaining_gb, Some(5.0));
+        assert_eq!(summary.quota_percent_used, Some(50.0));
+    }
+
+    #[test]
+    fn test_summarize_data_usage_after_manual_reset() {
+        let sessions = [session("before", 100, 2_000_000_000), session("after", 500, 3_000_000_000)];
+
+        let summary = summarize_data_usage(&sessions, 0, 1_000, Some(10.0), Some(300));
+        // 合計には含めるが、上限の集計はリセット後のセッションのみ
+        assert!((summary.total_gb - 5.0).abs() < 1e-9);
+        assert!(!summary.sessions[0].counts_toward_quota);
+        assert!(summary.sessions[1].counts_toward_quota);
+        assert_eq!(summary.quota_remaining_gb, Some(7.0));
+
+        // 前月のリセットは今月の集計に影響しない
+        let summary = summarize_data_usage(&sessions, 0, 1_000, Some(10.0), Some(-500));
+        assert_eq!(summary.quota_remaining_gb, Some(5.0));
+    }
+
+    #[test]
+    fn test_upload_accumulator() {
+        let start = Instant::now();
+        let mut accumulator = UploadAccumulator::default();
+
+        // 初回は基準時刻のみ
+        accumulator.add_sample(1_000_000, start);
+        assert_eq!(accumulator.take_pending(start), None);
+
+        accumulator.add_sample(1_000_000, start + Duration::from_secs(2));
+        // 書き込み間隔の経過前は取り出さない
+        assert_eq!(accumulator.take_pending(start + Duration::from_secs(2)), None);
+
+        // 取得が止まっていた間は上限（10秒）までしか積算しない
+        accumulator.add_sample(1_000_000, start + Duration::from_secs(60));
+        assert_eq!(accumulator.take_pending(start + Duration::from_secs(60)), Some(12_000_000));
+        assert_eq!(accumulator.take_pending(start + Duration::from_secs(120)), None);
+    }
+}
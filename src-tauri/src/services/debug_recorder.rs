@@ -0,0 +1,603 @@
+// デバッグ記録サービス
+//
+// 不定期に発生する問題の調査用に、監視ティック・OBSイベント・コマンド実行・エラーを
+// コンパクトなJSONLとしてローテーションファイルに記録する。
+// RUST_LOG=trace の出力は量が多すぎるため、ユーザーが明示的に有効化した場合のみ記録する。
+//
+// - 監視ティックは名前ごとにレート制限する
+// - パスワード・ストリームキーなどの秘密情報は書き込み前に伏せ字にする
+
+use crate::error::AppError;
+use crate::services::exporter::DiagnosticReport;
+use crate::services::performance_stats::{get_app_performance_stats, record_performance, PerformanceKind};
+use crate::storage::archive::{write_zip, ArchiveEntry};
+use crate::storage::config::DebugRecordingConfig;
+use crate::storage::paths::app_config_dir;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const TRACE_DIR_NAME: &str = "debug-traces";
+const BUNDLE_DIR_NAME: &str = "debug-bundles";
+const TRACE_FILE_STEM: &str = "trace";
+
+/// 伏せ字
+pub const REDACTED: &str = "[REDACTED]";
+
+/// 同じ名前の監視ティックを記録する最小間隔
+pub const MONITOR_TICK_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 1ファイルあたりの最小サイズ（KB）
+const MIN_FILE_SIZE_KB: u64 = 16;
+
+/// 秘密情報とみなすキー名（小文字、区切り文字除去後に部分一致）
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "authorization",
+    "streamkey",
+    "apikey",
+    "streamid",
+];
+
+/// 記録するイベントの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugEventKind {
+    /// 監視ティック（メトリクス取得など）
+    MonitorTick,
+    /// OBSイベント
+    ObsEvent,
+    /// コマンド実行
+    Command,
+    /// エラー
+    Error,
+}
+
+/// 記録する1件のイベント（JSONLの1行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugTraceEvent {
+    /// 記録日時（UNIX epochミリ秒）
+    pub timestamp_ms: i64,
+    /// イベントの種類
+    pub kind: DebugEventKind,
+    /// イベント名（コマンド名・イベント名など）
+    pub name: String,
+    /// 所要時間（ミリ秒、コマンドのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// 付加情報（秘密情報は伏せ字済み）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Value>,
+}
+
+impl DebugTraceEvent {
+    /// 新しいイベントを作成（付加情報は伏せ字にする）
+    pub fn new(kind: DebugEventKind, name: &str, fields: Option<Value>) -> Self {
+        Self {
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            kind,
+            name: name.to_string(),
+            duration_ms: None,
+            fields: fields.map(|mut fields| {
+                redact_secrets(&mut fields);
+                fields
+            }),
+        }
+    }
+}
+
+/// デバッグ記録の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugRecordingStatus {
+    /// 記録中か
+    pub enabled: bool,
+    /// 記録先ディレクトリ
+    pub trace_dir: String,
+    /// 1ファイルあたりの最大サイズ（KB）
+    pub max_file_size_kb: u64,
+    /// 保持するファイル数
+    pub max_files: usize,
+    /// 記録済みファイルの合計サイズ（バイト）
+    pub total_bytes: u64,
+}
+
+/// キー名が秘密情報を表すか
+fn is_secret_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    normalized == "key" || SECRET_KEY_MARKERS.iter().any(|marker| normalized.contains(marker))
+}
+
+/// JSON値の秘密情報を再帰的に伏せ字にする
+///
+/// 秘密情報を表すキーの値と、文字列中の `password=...` などのパラメータを置き換える
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        Value::String(text) => *text = redact_text(text),
+        _ => {}
+    }
+}
+
+/// 文字列中の `key=value` 形式の秘密情報を伏せ字にする
+///
+/// エラーメッセージやURLのクエリ（例: `srt://host:port?streamid=xxx`）を想定
+pub fn redact_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(eq) = rest.find('=') {
+        let (head, tail) = rest.split_at(eq);
+        // `=` 直前のキー名（英数字・`_`・`-`）
+        let key_start = head
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .map_or(0, |i| i + 1);
+        result.push_str(head);
+
+        let value = &tail[1..];
+        let value_end = value
+            .find(|c: char| c.is_whitespace() || matches!(c, '&' | ',' | ';' | '"' | '\''))
+            .unwrap_or(value.len());
+
+        if is_secret_key(&head[key_start..]) && value_end > 0 {
+            result.push('=');
+            result.push_str(REDACTED);
+            rest = &value[value_end..];
+        } else {
+            result.push('=');
+            rest = value;
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// サイズ上限付きのローテーションファイル書き込み
+///
+/// `trace.jsonl` に追記し、上限を超える場合は `trace.1.jsonl`, `trace.2.jsonl`, ... へ
+/// 順に繰り下げる。保持数を超えた最も古いファイルは削除する。
+#[derive(Debug, Clone)]
+pub struct RotatingTraceWriter {
+    dir: PathBuf,
+    max_file_bytes: u64,
+    max_files: usize,
+}
+
+impl RotatingTraceWriter {
+    /// 新しい書き込み先を作成
+    ///
+    /// # Arguments
+    /// * `dir` - 記録先ディレクトリ
+    /// * `max_file_bytes` - 1ファイルあたりの最大サイズ（バイト）
+    /// * `max_files` - 保持するファイル数（現在のファイルを含む、最小1）
+    pub fn new(dir: PathBuf, max_file_bytes: u64, max_files: usize) -> Self {
+        Self {
+            dir,
+            max_file_bytes,
+            max_files: max_files.max(1),
+        }
+    }
+
+    /// 設定から作成
+    pub fn from_config(dir: PathBuf, config: &DebugRecordingConfig) -> Self {
+        Self::new(dir, config.max_file_size_kb.max(MIN_FILE_SIZE_KB) * 1024, config.max_files)
+    }
+
+    /// 記録先ディレクトリ
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 指定番号のファイルパス（0が現在のファイル）
+    fn file_path(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            self.dir.join(format!("{TRACE_FILE_STEM}.jsonl"))
+        } else {
+            self.dir.join(format!("{TRACE_FILE_STEM}.{index}.jsonl"))
+        }
+    }
+
+    /// ファイルを1つずつ繰り下げる
+    fn rotate(&self) -> Result<(), AppError> {
+        let oldest = self.file_path(self.max_files - 1);
+        if oldest.exists() {
+            std::fs::remove_file(&oldest)?;
+        }
+        for index in (0..self.max_files - 1).rev() {
+            let from = self.file_path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.file_path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 1行追記する
+    ///
+    /// 追記後にサイズ上限を超える場合は、先にローテーションする
+    pub fn append(&self, line: &str) -> Result<(), AppError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let current = self.file_path(0);
+        let current_size = std::fs::metadata(&current).map_or(0, |m| m.len());
+        let line_size = line.len() as u64 + 1;
+        if current_size > 0 && current_size + line_size > self.max_file_bytes {
+            self.rotate()?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&current)?;
+        writeln!(file, "{line}")?;
+        Ok(())
+    }
+
+    /// 存在する記録ファイル（古い順）
+    pub fn trace_files(&self) -> Vec<PathBuf> {
+        (0..self.max_files)
+            .rev()
+            .map(|index| self.file_path(index))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    /// 記録ファイルの合計サイズ（バイト）
+    pub fn total_bytes(&self) -> u64 {
+        self.trace_files()
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+}
+
+/// 名前ごとの記録間隔制限
+#[derive(Debug)]
+pub struct RateLimiter {
+    min_interval: Duration,
+    last_recorded: HashMap<String, Instant>,
+}
+
+impl RateLimiter {
+    /// 新しいレート制限を作成
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_recorded: HashMap::new(),
+        }
+    }
+
+    /// 記録してよいか判定し、許可した場合は記録時刻を更新する
+    pub fn allow(&mut self, name: &str, now: Instant) -> bool {
+        let allowed = self
+            .last_recorded
+            .get(name)
+            .is_none_or(|last| now.saturating_duration_since(*last) >= self.min_interval);
+        if allowed {
+            self.last_recorded.insert(name.to_string(), now);
+        }
+        allowed
+    }
+}
+
+/// デバッグ記録の状態
+struct DebugRecorder {
+    /// 書き込み先（無効の場合はNone）
+    writer: Option<RotatingTraceWriter>,
+    /// 監視ティックのレート制限
+    tick_limiter: RateLimiter,
+}
+
+impl DebugRecorder {
+    fn write(&self, event: &DebugTraceEvent) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        let result = serde_json::to_string(event)
+            .map_err(AppError::from)
+            .and_then(|line| writer.append(&line));
+        if let Err(e) = result {
+            tracing::debug!(target: "debug_recorder", error = %e, "デバッグ記録の書き込みに失敗");
+        }
+    }
+}
+
+/// グローバルなデバッグ記録
+static DEBUG_RECORDER: Lazy<Mutex<DebugRecorder>> = Lazy::new(|| {
+    Mutex::new(DebugRecorder {
+        writer: None,
+        tick_limiter: RateLimiter::new(MONITOR_TICK_MIN_INTERVAL),
+    })
+});
+
+/// 記録先ディレクトリを取得
+pub fn trace_dir() -> Result<PathBuf, AppError> {
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+    Ok(config_dir.join(TRACE_DIR_NAME))
+}
+
+/// 設定に従ってデバッグ記録を開始・停止
+pub fn configure_debug_recording(config: &DebugRecordingConfig) -> Result<(), AppError> {
+    let writer = if config.enabled {
+        Some(RotatingTraceWriter::from_config(trace_dir()?, config))
+    } else {
+        None
+    };
+
+    let mut recorder = DEBUG_RECORDER
+        .lock()
+        .map_err(|_| AppError::config_error("デバッグ記録の状態を取得できませんでした"))?;
+    recorder.writer = writer;
+    Ok(())
+}
+
+/// デバッグ記録中か
+pub fn is_debug_recording_enabled() -> bool {
+    DEBUG_RECORDER
+        .lock()
+        .is_ok_and(|recorder| recorder.writer.is_some())
+}
+
+/// 現在の記録状態を取得
+pub fn get_debug_recording_status(config: &DebugRecordingConfig) -> Result<DebugRecordingStatus, AppError> {
+    let writer = RotatingTraceWriter::from_config(trace_dir()?, config);
+    Ok(DebugRecordingStatus {
+        enabled: is_debug_recording_enabled(),
+        trace_dir: writer.dir().display().to_string(),
+        max_file_size_kb: config.max_file_size_kb,
+        max_files: config.max_files,
+        total_bytes: writer.total_bytes(),
+    })
+}
+
+/// イベントを記録（無効の場合は何もしない）
+pub fn record_event(event: &DebugTraceEvent) {
+    if let Ok(recorder) = DEBUG_RECORDER.lock() {
+        recorder.write(event);
+    }
+}
+
+/// 監視ティックを記録（名前ごとに `MONITOR_TICK_MIN_INTERVAL` でレート制限）
+pub fn record_monitor_tick(name: &str, fields: Value) {
+    let Ok(mut recorder) = DEBUG_RECORDER.lock() else {
+        return;
+    };
+    if recorder.writer.is_none() || !recorder.tick_limiter.allow(name, Instant::now()) {
+        return;
+    }
+    recorder.write(&DebugTraceEvent::new(DebugEventKind::MonitorTick, name, Some(fields)));
+}
+
+/// OBSイベントを記録
+pub fn record_obs_event<T: Serialize>(name: &str, payload: &T) {
+    if !is_debug_recording_enabled() {
+        return;
+    }
+    let fields = serde_json::to_value(payload).ok();
+    record_event(&DebugTraceEvent::new(DebugEventKind::ObsEvent, name, fields));
+}
+
+/// エラーを記録
+pub fn record_error(source: &str, error: &AppError) {
+    if !is_debug_recording_enabled() {
+        return;
+    }
+    let fields = serde_json::json!({ "code": error.code(), "message": error.message() });
+    record_event(&DebugTraceEvent::new(DebugEventKind::Error, source, Some(fields)));
+}
+
+/// コマンドを実行し、名前と所要時間を記録する
+///
+/// 引数・戻り値は記録しない。失敗した場合はエラーも記録する。
+/// 所要時間はデバッグ記録の有効・無効にかかわらずパフォーマンス統計にも集計する
+pub async fn traced_command<T, F>(name: &str, command: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let started = Instant::now();
+    let result = command.await;
+    record_performance(PerformanceKind::Command, name, started.elapsed(), result.is_ok());
+
+    if is_debug_recording_enabled() {
+        let mut event = DebugTraceEvent::new(
+            DebugEventKind::Command,
+            name,
+            Some(serde_json::json!({ "success": result.is_ok() })),
+        );
+        event.duration_ms = Some(started.elapsed().as_millis() as u64);
+        record_event(&event);
+
+        if let Err(e) = &result {
+            record_error(name, e);
+        }
+    }
+    result
+}
+
+/// 記録ファイル・診断レポート・パフォーマンス統計をZIPにまとめる
+///
+/// # Returns
+/// 作成したZIPファイルのパス
+pub fn export_debug_bundle(
+    config: &DebugRecordingConfig,
+    report: &DiagnosticReport,
+) -> Result<PathBuf, AppError> {
+    let writer = RotatingTraceWriter::from_config(trace_dir()?, config);
+
+    let mut entries = Vec::new();
+    for path in writer.trace_files() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        entries.push(ArchiveEntry::new(format!("traces/{name}"), std::fs::read(&path)?));
+    }
+
+    let mut report = serde_json::to_value(report)?;
+    redact_secrets(&mut report);
+    entries.push(ArchiveEntry::new(
+        "diagnostic_report.json",
+        serde_json::to_vec_pretty(&report)?,
+    ));
+    entries.push(ArchiveEntry::new(
+        "performance_stats.json",
+        serde_json::to_vec_pretty(&get_app_performance_stats())?,
+    ));
+
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+    let path = config_dir.join(BUNDLE_DIR_NAME).join(format!(
+        "debug_bundle_{}.zip",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    write_zip(&path, &entries)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn temp_trace_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("obs_optimizer_traces_{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_redact_secret_keys() {
+        let mut value = serde_json::json!({
+            "host": "localhost",
+            "password": "hunter2",
+            "settings": { "server": "rtmp://live.twitch.tv/app", "key": "live_123456" },
+            "streamKey": "abc",
+            "keyframeIntervalSecs": 2,
+            "auth": null
+        });
+        redact_secrets(&mut value);
+
+        assert_eq!(value["host"], "localhost");
+        assert_eq!(value["password"], REDACTED);
+        assert_eq!(value["settings"]["key"], REDACTED);
+        assert_eq!(value["settings"]["server"], "rtmp://live.twitch.tv/app");
+        assert_eq!(value["streamKey"], REDACTED);
+        assert_eq!(value["keyframeIntervalSecs"], 2);
+    }
+
+    #[test]
+    fn test_redact_text_parameters() {
+        assert_eq!(
+            redact_text("srt://example.com:9000?streamid=secret123&latency=200"),
+            format!("srt://example.com:9000?streamid={REDACTED}&latency=200")
+        );
+        assert_eq!(
+            redact_text("認証失敗 password=abc token=xyz"),
+            format!("認証失敗 password={REDACTED} token={REDACTED}")
+        );
+        assert_eq!(redact_text("bitrate=6000 fps=60"), "bitrate=6000 fps=60");
+        assert_eq!(redact_text("no params"), "no params");
+    }
+
+    #[test]
+    fn test_event_fields_are_redacted() {
+        let event = DebugTraceEvent::new(
+            DebugEventKind::ObsEvent,
+            "obs:connection-changed",
+            Some(serde_json::json!({ "password": "secret" })),
+        );
+        let line = serde_json::to_string(&event).unwrap();
+        assert!(!line.contains("secret\""));
+        assert!(line.contains(REDACTED));
+        assert!(!line.contains("durationMs"));
+    }
+
+    #[test]
+    fn test_rotation_and_size_cap() {
+        let dir = temp_trace_dir();
+        let writer = RotatingTraceWriter::new(dir.clone(), 100, 3);
+        let line = "x".repeat(39); // 改行込みで40バイト
+
+        for _ in 0..10 {
+            writer.append(&line).unwrap();
+        }
+
+        // 保持数を超えたファイルは削除される
+        let files = writer.trace_files();
+        assert_eq!(files.len(), 3);
+        assert!(!dir.join("trace.3.jsonl").exists());
+
+        // 各ファイルはサイズ上限以内
+        for path in &files {
+            assert!(std::fs::metadata(path).unwrap().len() <= 100);
+        }
+        assert!(writer.total_bytes() <= 300);
+
+        // 古い順に並ぶ
+        assert_eq!(files.last().unwrap(), &dir.join("trace.jsonl"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_line_is_written_to_fresh_file() {
+        let dir = temp_trace_dir();
+        let writer = RotatingTraceWriter::new(dir.clone(), 10, 2);
+
+        writer.append("short").unwrap();
+        writer.append(&"y".repeat(50)).unwrap();
+
+        assert_eq!(writer.trace_files().len(), 2);
+        let current = std::fs::read_to_string(dir.join("trace.jsonl")).unwrap();
+        assert_eq!(current.trim_end().len(), 50);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_from_config_enforces_minimum_size() {
+        let config = DebugRecordingConfig {
+            enabled: true,
+            max_file_size_kb: 0,
+            max_files: 0,
+        };
+        let writer = RotatingTraceWriter::from_config(temp_trace_dir(), &config);
+        assert_eq!(writer.max_file_bytes, MIN_FILE_SIZE_KB * 1024);
+        assert_eq!(writer.max_files, 1);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(5));
+        let start = Instant::now();
+
+        assert!(limiter.allow("system_metrics", start));
+        assert!(!limiter.allow("system_metrics", start + Duration::from_secs(4)));
+        // 名前ごとに独立
+        assert!(limiter.allow("audio", start + Duration::from_secs(4)));
+        assert!(limiter.allow("system_metrics", start + Duration::from_secs(5)));
+    }
+}
@@ -0,0 +1,247 @@
+// 検出ハードウェア情報サービス
+//
+// アプリが検出したハードウェア（GPU・CPU・メモリ）と、推奨ロジックで使用する
+// 分類（GPU世代・グレード・統合ティア・CPUティア・エンコーダー能力）をまとめて提供する。
+// 検出結果はキャッシュし、ドライバー更新やハードウェア変更時のみ再検出する。
+
+use super::gpu_detection::{
+    calculate_effective_tier, detect_gpu_generation, detect_gpu_grade, determine_cpu_tier,
+    driver_supports_av1, get_encoder_capability, CpuTier, EffectiveTier, GpuGeneration, GpuGrade,
+    MemoryTier,
+};
+use super::optimizer::HardwareInfo;
+use crate::monitor::cpu_benchmark::cpu_benchmark_error;
+use crate::monitor::display::DisplayInfo;
+use crate::monitor::gpu::GpuInfo;
+use crate::monitor::power::PowerStatus;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::future::Future;
+use tokio::sync::Mutex;
+
+/// GPUのハードウェアエンコーダー対応状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncoderCapabilityFlags {
+    /// H.264エンコード対応
+    pub h264: bool,
+    /// HEVCエンコード対応
+    pub hevc: bool,
+    /// AV1エンコード対応（GPUの対応のみ）
+    pub av1: bool,
+    /// ドライバーがAV1エンコードに対応しているか
+    pub av1_driver_supported: bool,
+    /// Bフレーム対応
+    pub b_frames: bool,
+}
+
+/// 検出したハードウェア情報と分類結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedHardware {
+    /// CPU名
+    pub cpu_name: String,
+    /// CPUコア数
+    pub cpu_cores: usize,
+    /// 総メモリ（GB）
+    pub total_memory_gb: f64,
+    /// GPU情報（検出できない場合はNone）
+    pub gpu: Option<GpuInfo>,
+    /// 電源状態
+    pub power: PowerStatus,
+    /// 接続されているディスプレイ
+    pub displays: Vec<DisplayInfo>,
+    /// x264エンコードベンチマークの結果（フレーム/秒、未計測の場合はNone）
+    pub benchmark_result: Option<u32>,
+    /// ベンチマークを計測できなかった理由（ffmpegがない場合など。計測できた場合はNone）
+    pub benchmark_error: Option<String>,
+    /// GPU世代（アーキテクチャ）
+    pub gpu_generation: GpuGeneration,
+    /// GPU性能グレード
+    pub gpu_grade: GpuGrade,
+    /// 統合ティア（世代×グレード）
+    pub effective_tier: EffectiveTier,
+    /// CPUティア
+    pub cpu_tier: CpuTier,
+    /// メモリティア
+    pub memory_tier: MemoryTier,
+    /// ハードウェアエンコーダーの対応状況（GPUなし・不明の場合はNone）
+    pub encoder_capability: Option<EncoderCapabilityFlags>,
+    /// 表示用サマリー（例: "NVIDIA GeForce RTX 4070（Ada・アッパーミドル → Tier A）"）
+    pub summary: String,
+    /// 検出日時（Unixタイムスタンプ）
+    pub detected_at: i64,
+}
+
+impl DetectedHardware {
+    /// ハードウェア情報から分類結果を算出
+    pub fn from_hardware(hardware: &HardwareInfo, detected_at: i64) -> Self {
+        let (gpu_generation, gpu_grade) = hardware.gpu.as_ref().map_or(
+            (GpuGeneration::None, GpuGrade::Unknown),
+            |gpu| (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name)),
+        );
+        let effective_tier = calculate_effective_tier(gpu_generation, gpu_grade);
+        let encoder_capability = get_encoder_capability(gpu_generation).map(|capability| {
+            EncoderCapabilityFlags {
+                h264: capability.h264,
+                hevc: capability.hevc,
+                av1: capability.av1,
+                av1_driver_supported: capability.av1
+                    && driver_supports_av1(gpu_generation, hardware.gpu_driver_version()),
+                b_frames: capability.b_frames,
+            }
+        });
+
+        let summary = hardware.gpu.as_ref().map_or_else(
+            || format!("GPU未検出（CPUエンコード → Tier {}）", effective_tier.letter()),
+            |gpu| {
+                format!(
+                    "{}（{}・{} → Tier {}）",
+                    gpu.name,
+                    gpu_generation.display_label(),
+                    gpu_grade.display_label(),
+                    effective_tier.letter()
+                )
+            },
+        );
+
+        Self {
+            cpu_name: hardware.cpu_name.clone(),
+            cpu_cores: hardware.cpu_cores,
+            total_memory_gb: hardware.total_memory_gb,
+            gpu: hardware.gpu.clone(),
+            power: hardware.power,
+            displays: hardware.displays.clone(),
+            benchmark_result: hardware.benchmark_result,
+            benchmark_error: None,
+            gpu_generation,
+            gpu_grade,
+            effective_tier,
+            cpu_tier: determine_cpu_tier(hardware.cpu_cores, hardware.benchmark_result),
+            memory_tier: MemoryTier::from_gb(hardware.total_memory_gb),
+            encoder_capability,
+            summary,
+            detected_at,
+        }
+    }
+}
+
+/// 検出結果のキャッシュ
+static DETECTED_HARDWARE_CACHE: Lazy<Mutex<Option<DetectedHardware>>> = Lazy::new(|| Mutex::new(None));
+
+/// 検出済みのハードウェア情報を取得
+///
+/// 初回呼び出し時、または `refresh` が true の場合のみ `detect` で再検出する
+///
+/// # Arguments
+/// * `refresh` - キャッシュを破棄して再検出するか
+/// * `detect` - ハードウェア情報の検出処理
+pub async fn get_or_detect_hardware<F, Fut>(refresh: bool, detect: F) -> DetectedHardware
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = HardwareInfo>,
+{
+    let mut cache = DETECTED_HARDWARE_CACHE.lock().await;
+    if !refresh {
+        if let Some(detected) = cache.as_ref() {
+            return detected.clone();
+        }
+    }
+
+    let hardware = detect().await;
+    let detected = DetectedHardware {
+        benchmark_error: cpu_benchmark_error(),
+        ..DetectedHardware::from_hardware(&hardware, chrono::Utc::now().timestamp())
+    };
+    *cache = Some(detected.clone());
+    detected
+}
+
+/// 検出結果のキャッシュを破棄（次回取得時に再検出する）
+pub async fn clear_detected_hardware_cache() {
+    *DETECTED_HARDWARE_CACHE.lock().await = None;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn hardware(gpu_name: Option<&str>, cpu_cores: usize) -> HardwareInfo {
+        HardwareInfo {
+            cpu_name: "Test CPU".to_string(),
+            cpu_cores,
+            total_memory_gb: 32.0,
+            gpu: gpu_name.map(|name| GpuInfo {
+                name: name.to_string(),
+                driver_version: None,
+            }),
+            power: PowerStatus::default(),
+            displays: Vec::new(),
+            benchmark_result: None,
+        }
+    }
+
+    #[test]
+    fn test_detected_hardware_classifies_rtx_4070() {
+        let detected = DetectedHardware::from_hardware(&hardware(Some("NVIDIA GeForce RTX 4070"), 8), 0);
+
+        assert_eq!(detected.gpu_generation, GpuGeneration::NvidiaAda);
+        assert_eq!(detected.gpu_grade, GpuGrade::UpperMid);
+        assert_eq!(detected.effective_tier, EffectiveTier::TierA);
+        assert_eq!(detected.cpu_tier, CpuTier::UpperMiddle);
+        assert_eq!(detected.memory_tier, MemoryTier::Abundant);
+        assert_eq!(detected.summary, "NVIDIA GeForce RTX 4070（Ada・アッパーミドル → Tier A）");
+
+        let capability = detected.encoder_capability.unwrap();
+        assert!(capability.h264 && capability.hevc && capability.av1);
+        // ドライバーバージョン不明の場合はAV1を制限しない
+        assert!(capability.av1_driver_supported);
+    }
+
+    #[test]
+    fn test_detected_hardware_old_driver_disables_av1() {
+        let mut hardware = hardware(Some("NVIDIA GeForce RTX 4070"), 8);
+        if let Some(gpu) = hardware.gpu.as_mut() {
+            gpu.driver_version = Some("516.94".to_string());
+        }
+        let detected = DetectedHardware::from_hardware(&hardware, 0);
+
+        let capability = detected.encoder_capability.unwrap();
+        assert!(capability.av1);
+        assert!(!capability.av1_driver_supported);
+    }
+
+    #[test]
+    fn test_detected_hardware_without_gpu() {
+        let detected = DetectedHardware::from_hardware(&hardware(None, 4), 0);
+
+        assert_eq!(detected.gpu_generation, GpuGeneration::None);
+        assert!(detected.encoder_capability.is_none());
+        assert!(detected.summary.starts_with("GPU未検出"));
+    }
+
+    #[test]
+    fn test_detected_hardware_serializes_camel_case() {
+        let detected = DetectedHardware::from_hardware(&hardware(Some("NVIDIA GeForce RTX 3060"), 6), 0);
+        let json = serde_json::to_value(&detected).unwrap();
+
+        assert_eq!(json["gpuGeneration"], "nvidiaAmpere");
+        assert_eq!(json["effectiveTier"], "tierB");
+        assert_eq!(json["encoderCapability"]["bFrames"], true);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_detect_hardware_uses_cache_until_refresh() {
+        let first = get_or_detect_hardware(true, || async { hardware(Some("NVIDIA GeForce RTX 4070"), 8) }).await;
+        assert_eq!(first.gpu_generation, GpuGeneration::NvidiaAda);
+
+        // キャッシュがある間は検出処理を呼ばない
+        let cached = get_or_detect_hardware(false, || async { hardware(None, 4) }).await;
+        assert_eq!(cached.gpu_generation, GpuGeneration::NvidiaAda);
+
+        // refreshで再検出する
+        let refreshed = get_or_detect_hardware(true, || async { hardware(None, 4) }).await;
+        assert_eq!(refreshed.gpu_generation, GpuGeneration::None);
+    }
+}
@@ -0,0 +1,119 @@
+// 2台構成（配信用PC）の検出
+//
+// ゲーム用PCの映像をキャプチャーボードまたはNDIで受け取り、既知のゲームが動作していない場合は
+// 2台構成の配信用PCである可能性が高いと判定する。
+// WebカメラもキャプチャーボードとOBSの入力種別（dshow_input等）が同じため、
+// 映像キャプチャデバイスは入力名・デバイス名に製品名が含まれるものだけを対象とする。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+
+/// NDI・SDIなど、入力種別だけで外部PCの映像と判断できる入力
+const NETWORK_VIDEO_INPUT_KINDS: &[&str] = &["ndi_source", "decklink-input"];
+
+/// 映像キャプチャデバイスの入力種別（Webカメラと共通）
+const VIDEO_CAPTURE_INPUT_KINDS: &[&str] = &["dshow_input", "av_capture_input", "v4l2_input"];
+
+/// 映像キャプチャデバイスの設定でデバイス名を保持するキー
+pub(crate) const DEVICE_SETTING_KEYS: &[&str] = &["video_device_id", "device_name", "device"];
+
+/// キャプチャーボードとみなすデバイス名・入力名のキーワード（小文字）
+const CAPTURE_CARD_KEYWORDS: &[&str] = &[
+    "elgato",
+    "avermedia",
+    "live gamer",
+    "hd60",
+    "4k60",
+    "magewell",
+    "capture card",
+    "キャプチャーボード",
+    "キャプボ",
+];
+
+/// 入力種別・入力名・デバイス名からキャプチャーボード・NDIの映像ソースかを推定
+///
+/// # Arguments
+/// * `input_kind` - OBSの入力種別
+/// * `input_name` - 入力名
+/// * `device` - 映像キャプチャデバイスのデバイス名（取得できない場合はNone）
+pub fn is_capture_source(input_kind: &str, input_name: &str, device: Option<&str>) -> bool {
+    if NETWORK_VIDEO_INPUT_KINDS.contains(&input_kind) {
+        return true;
+    }
+    if !VIDEO_CAPTURE_INPUT_KINDS.contains(&input_kind) {
+        return false;
+    }
+
+    let name = input_name.to_lowercase();
+    let device = device.unwrap_or_default().to_lowercase();
+    CAPTURE_CARD_KEYWORDS
+        .iter()
+        .any(|keyword| name.contains(keyword) || device.contains(keyword))
+}
+
+/// 2台構成の配信用PCである可能性が高いか
+///
+/// キャプチャーボード・NDIの映像ソースがあり、既知のゲームが動作していない場合にtrue
+///
+/// # Arguments
+/// * `capture_sources` - キャプチャーボード・NDIの映像ソース名
+/// * `running_games` - 実行中の既知ゲーム
+pub fn is_likely_dual_pc_streaming(capture_sources: &[String], running_games: &[String]) -> bool {
+    !capture_sources.is_empty() && running_games.is_empty()
+}
+
+/// OBSからキャプチャーボード・NDIの映像ソース名を取得
+///
+/// 入力の設定を取得できない映像キャプチャデバイスは入力名のみで判定する
+pub async fn collect_capture_sources(client: &ObsClient) -> Result<Vec<String>, AppError> {
+    let inputs = client.get_input_list().await?;
+    let mut sources = Vec::new();
+
+    for input in inputs {
+        let name = input.id.name;
+        let device = if VIDEO_CAPTURE_INPUT_KINDS.contains(&input.kind.as_str()) {
+            client.get_input_settings(&name).await.ok().and_then(|settings| {
+                DEVICE_SETTING_KEYS
+                    .iter()
+                    .find_map(|key| settings.get(*key).and_then(serde_json::Value::as_str).map(str::to_string))
+            })
+        } else {
+            None
+        };
+
+        if is_capture_source(&input.kind, &name, device.as_deref()) {
+            sources.push(name);
+        }
+    }
+
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_source_detection() {
+        assert!(is_capture_source("ndi_source", "ゲームPC", None));
+        assert!(is_capture_source("decklink-input", "SDI", None));
+        assert!(is_capture_source(
+            "dshow_input",
+            "映像キャプチャデバイス",
+            Some("Elgato HD60 X:\\\\?\\usb#vid_0fd9")
+        ));
+        assert!(is_capture_source("v4l2_input", "AVerMedia Live Gamer", None));
+
+        // Webカメラは対象外
+        assert!(!is_capture_source("dshow_input", "Webカメラ", Some("Logitech C920")));
+        assert!(!is_capture_source("game_capture", "Elgato", None));
+    }
+
+    #[test]
+    fn test_likely_dual_pc_streaming() {
+        let sources = vec!["ゲームPC".to_string()];
+        assert!(is_likely_dual_pc_streaming(&sources, &[]));
+        assert!(!is_likely_dual_pc_streaming(&sources, &["valorant.exe".to_string()]));
+        assert!(!is_likely_dual_pc_streaming(&[], &[]));
+    }
+}
@@ -0,0 +1,453 @@
+// エンコーダーのフォールバック
+//
+// 推奨エンコーダーをOBSで使えない場合（ドライバーの削除・プラグインの欠落など）に、
+// 利用可能なエンコーダーの中から次点を選ぶ。
+// OBSはプロファイルパラメータにどのエンコーダーIDでも書き込めてしまうため、書き込みの成否ではなく
+// OBSが起動時にログへ報告した利用可能なエンコーダーの一覧で判定する。
+// 一覧を取得できない場合に限り、検出したGPUから利用可能なエンコーダーを推定する。
+// フォールバックの順序は `ENCODER_FALLBACK_CHAIN` の1か所で管理する。
+
+use super::encoder_selector::{EncoderSelectionContext, EncoderSelector};
+use super::gpu_detection::{get_encoder_capability, GpuGeneration};
+use super::optimizer::RecommendedOutputSettings;
+use crate::error::{AppError, ERROR_CODE_OBS_NOT_CONNECTED};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// フォールバックの段階（圧縮効率の高い順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EncoderCodecClass {
+    /// AV1（ハードウェア）
+    Av1,
+    /// HEVC（ハードウェア）
+    Hevc,
+    /// H.264（ハードウェア）
+    H264Hardware,
+    /// x264（CPU）
+    Software,
+}
+
+/// フォールバックの1段階
+struct FallbackTier {
+    /// 段階
+    class: EncoderCodecClass,
+    /// この段階のエンコーダーID（優先順）
+    encoder_ids: &'static [&'static str],
+}
+
+/// フォールバックの順序テーブル（AV1 → HEVC → H.264ハードウェア → x264）
+///
+/// 同じ段階の中では新しい実装（jim_* / texture系）を優先する
+const ENCODER_FALLBACK_CHAIN: &[FallbackTier] = &[
+    FallbackTier {
+        class: EncoderCodecClass::Av1,
+        encoder_ids: &["jim_av1_nvenc", "av1_texture_amf", "obs_qsv11_av1"],
+    },
+    FallbackTier {
+        class: EncoderCodecClass::Hevc,
+        encoder_ids: &["jim_hevc_nvenc", "h265_texture_amf", "obs_qsv11_hevc"],
+    },
+    FallbackTier {
+        class: EncoderCodecClass::H264Hardware,
+        encoder_ids: &["jim_nvenc", "ffmpeg_nvenc", "h264_texture_amf", "amd_amf_h264", "obs_qsv11"],
+    },
+    FallbackTier {
+        class: EncoderCodecClass::Software,
+        encoder_ids: &["obs_x264"],
+    },
+];
+
+/// フォールバックの記録
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncoderFallback {
+    /// 推奨されたエンコーダーID
+    pub requested_encoder: String,
+    /// 実際に適用したエンコーダーID
+    pub applied_encoder: String,
+    /// 試行して失敗したエンコーダーID（試行順）
+    pub failed_encoders: Vec<String>,
+    /// フォールバックした理由（最初の失敗のエラーメッセージ）
+    pub reason: String,
+}
+
+/// エンコーダーIDの段階を取得（テーブルにない場合はNone）
+pub fn codec_class(encoder_id: &str) -> Option<EncoderCodecClass> {
+    ENCODER_FALLBACK_CHAIN
+        .iter()
+        .find(|tier| tier.encoder_ids.contains(&encoder_id))
+        .map(|tier| tier.class)
+}
+
+/// 検出したGPUとドライバーから利用可能なエンコーダーを推定
+///
+/// x264は常に利用可能とみなす
+pub fn probe_available_encoders(context: &EncoderSelectionContext) -> Vec<&'static str> {
+    let capability = get_encoder_capability(context.gpu_generation);
+    let hevc = capability.is_some_and(|c| c.hevc);
+    let av1 = context.av1_available();
+
+    let (h264_ids, hevc_id, av1_id): (&[&'static str], _, _) = match context.gpu_generation {
+        GpuGeneration::NvidiaBlackwell
+        | GpuGeneration::NvidiaAda
+        | GpuGeneration::NvidiaAmpere
+        | GpuGeneration::NvidiaTuring
+        | GpuGeneration::NvidiaPascal => (&["jim_nvenc", "ffmpeg_nvenc"], "jim_hevc_nvenc", "jim_av1_nvenc"),
+        GpuGeneration::AmdVcn4 | GpuGeneration::AmdVcn3 => {
+            (&["h264_texture_amf", "amd_amf_h264"], "h265_texture_amf", "av1_texture_amf")
+        }
+        GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage | GpuGeneration::IntelQuickSync => (&["obs_qsv11"], "obs_qsv11_hevc", "obs_qsv11_av1"),
+        GpuGeneration::Unknown | GpuGeneration::None => (&[], "", ""),
+    };
+
+    let mut available = Vec::new();
+    if av1 && !av1_id.is_empty() {
+        available.push(av1_id);
+    }
+    if hevc && !hevc_id.is_empty() {
+        available.push(hevc_id);
+    }
+    available.extend_from_slice(h264_ids);
+    available.push("obs_x264");
+    available
+}
+
+/// 利用可能なエンコーダーを決定
+///
+/// OBSが報告したエンコーダーの一覧がある場合はそれを使い、ない場合はGPUから推定する
+///
+/// # Arguments
+/// * `reported` - OBSが報告した利用可能な映像エンコーダーのID（取得できない場合はNone）
+/// * `context` - エンコーダー選択コンテキスト
+pub fn available_encoders<'a>(reported: Option<&'a [String]>, context: &EncoderSelectionContext) -> Vec<&'a str> {
+    match reported {
+        Some(encoders) => encoders.iter().map(String::as_str).collect(),
+        None => probe_available_encoders(context),
+    }
+}
+
+/// 次に試すエンコーダーを選択
+///
+/// 失敗したエンコーダーと同じ段階以降（圧縮効率が同等以下）から、
+/// 利用可能かつ未試行のものをテーブル順に選ぶ。
+/// テーブルにないエンコーダーが失敗した場合はH.264ハードウェアから探す
+/// （プラットフォームが対応していないコーデックに切り替えないため）
+///
+/// # Arguments
+/// * `failed` - 適用に失敗したエンコーダーID
+/// * `available` - 利用可能なエンコーダーID
+/// * `tried` - 試行済みのエンコーダーID
+pub fn next_fallback_encoder(failed: &str, available: &[&str], tried: &[String]) -> Option<&'static str> {
+    let start = codec_class(failed).unwrap_or(EncoderCodecClass::H264Hardware);
+
+    ENCODER_FALLBACK_CHAIN
+        .iter()
+        .filter(|tier| tier.class >= start)
+        .flat_map(|tier| tier.encoder_ids.iter().copied())
+        .find(|id| *id != failed && available.contains(id) && !tried.iter().any(|t| t == id))
+}
+
+/// 出力設定を適用し、エンコーダーを使えない場合はフォールバックする
+///
+/// OBSが報告したエンコーダーの一覧がある場合、一覧にないエンコーダーは書き込まずに失敗として扱う。
+/// 推奨エンコーダーで `apply` を試行し、使えない・適用に失敗した場合は利用可能なエンコーダーから
+/// 次点を選んでプリセット・レート制御を選び直してから再試行する。
+/// 候補がなくなった場合は最後のエラーを返す。
+///
+/// # Arguments
+/// * `output` - 適用する出力設定（フォールバック時は適用したエンコーダーの設定に更新される）
+/// * `context` - エンコーダー選択コンテキスト
+/// * `reported` - OBSが報告した利用可能な映像エンコーダーのID（取得できない場合はNone）
+/// * `apply` - 出力設定をOBSに適用する処理
+///
+/// # Returns
+/// フォールバックした場合はその記録、推奨エンコーダーをそのまま適用できた場合は`None`
+pub async fn apply_with_fallback<F, Fut>(
+    output: &mut RecommendedOutputSettings,
+    context: &EncoderSelectionContext,
+    reported: Option<&[String]>,
+    mut apply: F,
+) -> Result<Option<EncoderFallback>, AppError>
+where
+    F: FnMut(RecommendedOutputSettings) -> Fut,
+    Fut: Future<Output = Result<(), AppError>>,
+{
+    let requested_encoder = output.encoder.clone();
+    let available = available_encoders(reported, context);
+    let mut failed_encoders: Vec<String> = Vec::new();
+    let mut reason: Option<String> = None;
+
+    loop {
+        let attempt = if reported.is_some() && !available.contains(&output.encoder.as_str()) {
+            Err(AppError::obs_state(&format!(
+                "OBSで利用できないエンコーダーです（プラグイン・ドライバーを確認してください）: {}",
+                output.encoder
+            )))
+        } else {
+            apply(output.clone()).await
+        };
+        let error = match attempt {
+            Ok(()) => {
+                return Ok(reason.map(|reason| EncoderFallback {
+                    requested_encoder,
+                    applied_encoder: output.encoder.clone(),
+                    failed_encoders,
+                    reason,
+                }));
+            }
+            // 未接続はエンコーダーを変えても解決しない
+            Err(e) if e.code() == ERROR_CODE_OBS_NOT_CONNECTED => return Err(e),
+            Err(e) => e,
+        };
+
+        let Some(next) = next_fallback_encoder(&output.encoder, &available, &failed_encoders) else {
+            return Err(error);
+        };
+
+        tracing::warn!(
+            target: "optimization",
+            error = %error,
+            failed = %output.encoder,
+            fallback = next,
+            "エンコーダーの適用に失敗したため、フォールバックします"
+        );
+
+        failed_encoders.push(output.encoder.clone());
+        reason.get_or_insert_with(|| error.message().to_string());
+        let encoder = EncoderSelector::select_for_encoder(context, next);
+        output.apply_fallback_encoder(&encoder);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::services::gpu_detection::{CpuTier, GpuGrade};
+    use crate::storage::config::{SetupType, StreamLatency, StreamingPlatform, StreamingStyle};
+
+    fn context(generation: GpuGeneration, driver: Option<&str>) -> EncoderSelectionContext {
+        EncoderSelectionContext {
+            gpu_generation: generation,
+            gpu_grade: GpuGrade::HighEnd,
+            gpu_driver_version: driver.map(ToString::to_string),
+            cpu_tier: CpuTier::UpperMiddle,
+            platform: StreamingPlatform::YouTube,
+            style: StreamingStyle::Gaming,
+            network_speed_mbps: 50.0,
+            recording_mode: false,
+            latency: StreamLatency::Normal,
+            setup_type: SetupType::SinglePc,
+            hdr_mode: false,
+        }
+    }
+
+    #[test]
+    fn test_chain_covers_each_class_in_order() {
+        let classes: Vec<EncoderCodecClass> = ENCODER_FALLBACK_CHAIN.iter().map(|tier| tier.class).collect();
+        assert_eq!(
+            classes,
+            vec![
+                EncoderCodecClass::Av1,
+                EncoderCodecClass::Hevc,
+                EncoderCodecClass::H264Hardware,
+                EncoderCodecClass::Software,
+            ]
+        );
+        assert_eq!(codec_class("jim_av1_nvenc"), Some(EncoderCodecClass::Av1));
+        assert_eq!(codec_class("obs_x264"), Some(EncoderCodecClass::Software));
+        assert_eq!(codec_class("unknown_encoder"), None);
+    }
+
+    #[test]
+    fn test_probe_available_encoders() {
+        let ada = probe_available_encoders(&context(GpuGeneration::NvidiaAda, Some("560.94")));
+        assert_eq!(ada, vec!["jim_av1_nvenc", "jim_hevc_nvenc", "jim_nvenc", "ffmpeg_nvenc", "obs_x264"]);
+
+        // ドライバーが古い場合はAV1を除外
+        let old_driver = probe_available_encoders(&context(GpuGeneration::NvidiaAda, Some("516.94")));
+        assert!(!old_driver.contains(&"jim_av1_nvenc"));
+
+        assert_eq!(probe_available_encoders(&context(GpuGeneration::None, None)), vec!["obs_x264"]);
+    }
+
+    #[test]
+    fn test_av1_failure_falls_back_to_hevc() {
+        let available = ["jim_av1_nvenc", "jim_hevc_nvenc", "jim_nvenc", "ffmpeg_nvenc", "obs_x264"];
+        assert_eq!(next_fallback_encoder("jim_av1_nvenc", &available, &[]), Some("jim_hevc_nvenc"));
+    }
+
+    #[test]
+    fn test_fallback_skips_unavailable_and_tried_encoders() {
+        // HEVCが利用できない（プラグイン欠落）場合はH.264ハードウェアへ
+        let available = ["jim_av1_nvenc", "jim_nvenc", "ffmpeg_nvenc", "obs_x264"];
+        assert_eq!(next_fallback_encoder("jim_av1_nvenc", &available, &[]), Some("jim_nvenc"));
+
+        // 同じ段階の別実装も試す
+        let tried = vec!["jim_av1_nvenc".to_string(), "jim_nvenc".to_string()];
+        assert_eq!(next_fallback_encoder("jim_nvenc", &available, &tried), Some("ffmpeg_nvenc"));
+
+        // ハードウェアがすべて失敗したらx264
+        let tried = vec!["jim_nvenc".to_string(), "ffmpeg_nvenc".to_string()];
+        assert_eq!(next_fallback_encoder("ffmpeg_nvenc", &available, &tried), Some("obs_x264"));
+
+        // x264も失敗したら候補なし
+        assert_eq!(next_fallback_encoder("obs_x264", &available, &[]), None);
+    }
+
+    #[test]
+    fn test_fallback_never_upgrades_codec() {
+        // H.264が失敗してもAV1/HEVCには切り替えない（配信先が対応していない可能性がある）
+        let available = ["jim_av1_nvenc", "jim_hevc_nvenc", "ffmpeg_nvenc", "obs_x264"];
+        assert_eq!(next_fallback_encoder("ffmpeg_nvenc", &available, &[]), Some("obs_x264"));
+        assert_eq!(next_fallback_encoder("custom_encoder", &available, &[]), Some("ffmpeg_nvenc"));
+    }
+
+    #[test]
+    fn test_driver_removed_leaves_only_x264() {
+        // GPUドライバーが削除されGPUを検出できない場合
+        let available = probe_available_encoders(&context(GpuGeneration::Unknown, None));
+        assert_eq!(next_fallback_encoder("ffmpeg_nvenc", &available, &[]), Some("obs_x264"));
+    }
+
+    fn recording_output(encoder: &str) -> RecommendedOutputSettings {
+        RecommendedOutputSettings {
+            encoder: encoder.to_string(),
+            bitrate_kbps: 0,
+            keyframe_interval_secs: 2,
+            preset: Some("p5".to_string()),
+            rate_control: "CQP".to_string(),
+            quality_value: Some(20),
+            recording_format: Some("mkv".to_string()),
+            protocol: crate::storage::config::StreamingProtocol::default(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            output_mode: crate::obs::ObsOutputMode::default(),
+            nvenc_tuning: None,
+            hevc_profile: None,
+        }
+    }
+
+    /// `unavailable` に含まれるエンコーダーの適用を失敗させ、試行したエンコーダーを記録する
+    async fn apply_injecting_failures(
+        output: &mut RecommendedOutputSettings,
+        context: &EncoderSelectionContext,
+        unavailable: &[&str],
+    ) -> (Result<Option<EncoderFallback>, AppError>, Vec<String>) {
+        let mut attempts = Vec::new();
+        let result = apply_with_fallback(output, context, None, |candidate| {
+            attempts.push(candidate.encoder.clone());
+            let available = !unavailable.contains(&candidate.encoder.as_str());
+            async move {
+                if available {
+                    Ok(())
+                } else {
+                    Err(AppError::obs_state(&format!("encoder not found: {}", candidate.encoder)))
+                }
+            }
+        })
+        .await;
+        (result, attempts)
+    }
+
+    #[tokio::test]
+    async fn test_apply_without_failure_records_no_fallback() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let mut output = recording_output("jim_av1_nvenc");
+
+        let (result, attempts) = apply_injecting_failures(&mut output, &ctx, &[]).await;
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(attempts, vec!["jim_av1_nvenc"]);
+    }
+
+    #[tokio::test]
+    async fn test_apply_falls_back_and_records_reason() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let mut output = recording_output("jim_av1_nvenc");
+
+        // AV1・HEVCのプラグインが欠落している
+        let (result, attempts) =
+            apply_injecting_failures(&mut output, &ctx, &["jim_av1_nvenc", "jim_hevc_nvenc"]).await;
+        let fallback = result.unwrap().unwrap();
+
+        assert_eq!(attempts, vec!["jim_av1_nvenc", "jim_hevc_nvenc", "jim_nvenc"]);
+        assert_eq!(fallback.requested_encoder, "jim_av1_nvenc");
+        assert_eq!(fallback.applied_encoder, "jim_nvenc");
+        assert_eq!(fallback.failed_encoders, vec!["jim_av1_nvenc", "jim_hevc_nvenc"]);
+        assert!(fallback.reason.contains("jim_av1_nvenc"), "{}", fallback.reason);
+
+        // 適用した設定はフォールバック先に合わせて更新される
+        assert_eq!(output.encoder, "jim_nvenc");
+        assert_eq!(output.rate_control, "CQP");
+    }
+
+    #[tokio::test]
+    async fn test_apply_falls_back_to_x264_with_crf() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let mut output = recording_output("jim_nvenc");
+
+        let (result, _) = apply_injecting_failures(&mut output, &ctx, &["jim_nvenc", "ffmpeg_nvenc"]).await;
+        assert_eq!(result.unwrap().unwrap().applied_encoder, "obs_x264");
+        assert_eq!(output.rate_control, "CRF");
+        assert_eq!(output.quality_value, Some(20));
+    }
+
+    #[tokio::test]
+    async fn test_apply_returns_error_when_all_candidates_fail() {
+        let ctx = context(GpuGeneration::None, None);
+        let mut output = recording_output("obs_x264");
+
+        let (result, attempts) = apply_injecting_failures(&mut output, &ctx, &["obs_x264"]).await;
+        assert!(result.is_err());
+        assert_eq!(attempts, vec!["obs_x264"]);
+    }
+
+    #[tokio::test]
+    async fn test_apply_skips_encoders_obs_does_not_report() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let mut output = recording_output("jim_av1_nvenc");
+        // GPUからはAV1・HEVCが使えると推定されるが、OBSはH.264とx264しか報告していない
+        let reported = vec!["jim_nvenc".to_string(), "obs_x264".to_string()];
+        let mut attempts = Vec::new();
+
+        // OBSはどのエンコーダーIDの書き込みも受け付ける
+        let result = apply_with_fallback(&mut output, &ctx, Some(&reported), |candidate| {
+            attempts.push(candidate.encoder);
+            async { Ok(()) }
+        })
+        .await;
+        let fallback = result.unwrap().unwrap();
+
+        assert_eq!(attempts, vec!["jim_nvenc"]);
+        assert_eq!(fallback.applied_encoder, "jim_nvenc");
+        assert_eq!(fallback.failed_encoders, vec!["jim_av1_nvenc"]);
+        assert!(fallback.reason.contains("jim_av1_nvenc"), "{}", fallback.reason);
+    }
+
+    #[test]
+    fn test_available_encoders_prefers_reported_list() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let reported = vec!["obs_x264".to_string()];
+        assert_eq!(available_encoders(Some(&reported), &ctx), vec!["obs_x264"]);
+        assert_eq!(available_encoders(None, &ctx), probe_available_encoders(&ctx));
+    }
+
+    #[tokio::test]
+    async fn test_apply_does_not_fall_back_when_disconnected() {
+        let ctx = context(GpuGeneration::NvidiaAda, Some("560.94"));
+        let mut output = recording_output("jim_av1_nvenc");
+        let mut attempts = 0;
+
+        let result = apply_with_fallback(&mut output, &ctx, None, |_| {
+            attempts += 1;
+            async { Err(AppError::obs_not_connected()) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        assert_eq!(output.encoder, "jim_av1_nvenc");
+    }
+}
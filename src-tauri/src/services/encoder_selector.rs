@@ -5,10 +5,12 @@
 
 use super::gpu_detection::{
     CpuTier, EffectiveTier, GpuEncoderCapability, GpuGeneration, GpuGrade,
-    adjust_preset_for_effective_tier, calculate_effective_tier, get_encoder_capability,
-    should_enable_multipass,
+    adjust_preset_for_effective_tier, calculate_effective_tier, driver_supports_av1,
+    get_encoder_capability, should_enable_multipass,
 };
-use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use super::encoder_fallback::{codec_class, EncoderCodecClass};
+use crate::obs::ObsOutputMode;
+use crate::storage::config::{SetupType, StreamLatency, StreamingPlatform, StreamingStyle};
 use serde::{Deserialize, Serialize};
 
 /// 推奨エンコーダー情報
@@ -31,14 +33,126 @@ pub struct RecommendedEncoder {
     pub psycho_visual_tuning: bool,
     /// マルチパスモード（NVENC: "disabled", "quarter_res", "full_res"）
     pub multipass_mode: String,
-    /// チューニング設定（NVENC: "hq", "ll", "ull" / x264: "film", "animation"等）
+    /// x264のチューニング設定（"zerolatency", "film", "animation"等）
     pub tuning: Option<String>,
-    /// H.264プロファイル（"baseline", "main", "high"）
+    /// NVENCのチューニング設定（NVENC以外はNone）
+    #[serde(default)]
+    pub nvenc_tuning: Option<NvencTuning>,
+    /// プロファイル（H.264: "baseline", "main", "high" / HEVC・AV1: "main", "main10"）
     pub profile: String,
+    /// HEVCのプロファイル（HEVCエンコーダー以外はNone）
+    #[serde(default)]
+    pub hevc_profile: Option<HevcProfile>,
+    /// AMFプリアナリシス有効化（画質が向上する代わりに遅延が増えるため録画時のみ）
+    #[serde(default)]
+    pub pre_analysis: bool,
+    /// AMFプリアナリシスの先読みフレーム数（プリアナリシス無効時はNone）
+    #[serde(default)]
+    pub pa_look_ahead: Option<u32>,
+    /// AMFプリアナリシスのシーンチェンジ検出
+    #[serde(default)]
+    pub pa_scene_change_detect: bool,
+    /// AOM AV1（ソフトウェアエンコード）使用時の注意（AOM AV1以外はNone）
+    #[serde(default)]
+    pub aom_av1_warning: Option<String>,
     /// 選択理由
     pub reason: String,
 }
 
+impl RecommendedEncoder {
+    /// 推奨設定の適用に必要なOBSの出力モード
+    ///
+    /// Bフレーム・マルチパス・Look-ahead・プリアナリシスは基本モードでは設定できないため、
+    /// いずれかを使用する場合は詳細モードが必要
+    pub fn required_output_mode(&self) -> ObsOutputMode {
+        let uses_b_frames = self.b_frames.is_some_and(|frames| frames > 0);
+        let uses_multipass = self.multipass_mode != "disabled";
+        if uses_b_frames || uses_multipass || self.look_ahead || self.pre_analysis {
+            ObsOutputMode::Advanced
+        } else {
+            ObsOutputMode::Simple
+        }
+    }
+}
+
+/// HEVCのプロファイル
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HevcProfile {
+    /// Main（8bit）
+    #[default]
+    Main,
+    /// Main 10（10bit、HDR配信に必要）
+    Main10,
+}
+
+impl HevcProfile {
+    /// OBSのエンコーダー設定（`profile`）に書き込む値
+    pub const fn obs_value(self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Main10 => "main10",
+        }
+    }
+
+    /// HDRモードに応じたプロファイルを選択（HDRは10bitが必要なためMain 10）
+    pub const fn for_hdr(hdr_mode: bool) -> Self {
+        if hdr_mode {
+            Self::Main10
+        } else {
+            Self::Main
+        }
+    }
+}
+
+/// NVENCのチューニング
+///
+/// 低遅延ほど画質を犠牲にしてエンコード遅延を短縮する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NvencTuning {
+    /// 高画質（"hq"）
+    #[serde(rename = "hq")]
+    HighQuality,
+    /// 低遅延（"ll"）
+    #[serde(rename = "ll")]
+    LowLatency,
+    /// 超低遅延（"ull"）
+    #[serde(rename = "ull")]
+    UltraLowLatency,
+}
+
+impl NvencTuning {
+    /// OBSのエンコーダー設定（`tune`）に書き込む値
+    pub const fn obs_value(self) -> &'static str {
+        match self {
+            Self::HighQuality => "hq",
+            Self::LowLatency => "ll",
+            Self::UltraLowLatency => "ull",
+        }
+    }
+
+    /// 配信の遅延モードと統合ティアからチューニングを選択
+    ///
+    /// - TierE: エンコーダーの負荷を下げるため、遅延モードにかかわらずLowLatency
+    /// - 録画: 遅延は問題にならないためHighQuality
+    /// - 配信: 通常・低遅延はHighQuality、超低遅延はLowLatency
+    pub const fn select(latency: StreamLatency, tier: EffectiveTier, recording_mode: bool) -> Self {
+        if matches!(tier, EffectiveTier::TierE) {
+            return Self::LowLatency;
+        }
+        if recording_mode {
+            return Self::HighQuality;
+        }
+        match latency {
+            StreamLatency::Normal | StreamLatency::Low => Self::HighQuality,
+            StreamLatency::UltraLow => Self::LowLatency,
+        }
+    }
+}
+
+/// AMFプリアナリシスの先読みフレーム数
+const AMF_PA_LOOK_AHEAD_FRAMES: u32 = 16;
+
 /// エンコーダー選択コンテキスト
 #[derive(Debug, Clone)]
 pub struct EncoderSelectionContext {
@@ -46,6 +160,8 @@ pub struct EncoderSelectionContext {
     pub gpu_generation: GpuGeneration,
     /// GPU性能グレード（xx90/xx80/xx70等）
     pub gpu_grade: GpuGrade,
+    /// GPUドライバーバージョン（不明な場合はNone）
+    pub gpu_driver_version: Option<String>,
     /// CPUティア
     pub cpu_tier: CpuTier,
     /// 配信プラットフォーム
@@ -56,6 +172,14 @@ pub struct EncoderSelectionContext {
     /// ネットワーク速度（Mbps）
     #[allow(dead_code)]
     pub network_speed_mbps: f64,
+    /// 録画用の推奨かどうか（遅延より画質を優先する）
+    pub recording_mode: bool,
+    /// 配信の遅延モード
+    pub latency: StreamLatency,
+    /// 配信環境の構成（1台 / 2台構成）
+    pub setup_type: SetupType,
+    /// HDR配信を行うか（HEVCはMain 10プロファイルを使用する）
+    pub hdr_mode: bool,
 }
 
 impl EncoderSelectionContext {
@@ -63,6 +187,64 @@ impl EncoderSelectionContext {
     pub fn effective_tier(&self) -> EffectiveTier {
         calculate_effective_tier(self.gpu_generation, self.gpu_grade)
     }
+
+    /// エンコード負荷の調整に使用する統合ティア
+    ///
+    /// 配信専用PCではGPUがゲームを描画しないため、グレード（描画性能）による
+    /// 負荷調整は行わず、同世代のハイエンドとして扱う
+    pub fn encoding_tier(&self) -> EffectiveTier {
+        if self.setup_type.is_dedicated_streaming_pc() {
+            calculate_effective_tier(self.gpu_generation, GpuGrade::HighEnd)
+        } else {
+            self.effective_tier()
+        }
+    }
+
+    /// 配信専用PCのハイエンドCPUでx264を優先するか
+    ///
+    /// ゲームとCPUを奪い合わないため、x264の低速プリセットがハードウェアエンコーダーより高画質になる
+    fn prefers_dedicated_x264(&self) -> bool {
+        self.setup_type.is_dedicated_streaming_pc()
+            && matches!(self.cpu_tier, CpuTier::HighEnd)
+            && !self.recording_mode
+    }
+
+    /// GPUとドライバーの両方がAV1エンコードに対応しているか
+    ///
+    /// ドライバーが古い場合はGPUが対応していてもAV1を使用しない
+    pub fn av1_available(&self) -> bool {
+        get_encoder_capability(self.gpu_generation).is_some_and(|c| c.av1)
+            && driver_supports_av1(self.gpu_generation, self.gpu_driver_version.as_deref())
+    }
+
+    /// GPUでHEVCエンコードが可能か
+    pub fn hevc_available(&self) -> bool {
+        get_encoder_capability(self.gpu_generation).is_some_and(|c| c.hevc)
+    }
+
+    /// ハードウェアエンコーダーで同時に実行できるエンコードセッション数の目安
+    ///
+    /// マルチトラック配信（複数解像度の同時エンコード）のラダー構築に使用する。
+    /// - NVIDIA Turing以降: ドライバーの同時セッション上限（GeForceは8）
+    /// - NVIDIA Pascal: 旧世代は上限が低く、エンコーダーの処理能力も限られる
+    /// - AMD/Intel Arc: ハード上限はないが、処理能力から4本を目安とする
+    /// - 内蔵GPU: 2本まで
+    /// - GPUなし・不明: ハードウェアエンコード不可
+    pub const fn concurrent_session_budget(&self) -> u8 {
+        match self.gpu_generation {
+            GpuGeneration::NvidiaBlackwell
+            | GpuGeneration::NvidiaAda
+            | GpuGeneration::NvidiaAmpere
+            | GpuGeneration::NvidiaTuring => 8,
+            GpuGeneration::NvidiaPascal => 3,
+            GpuGeneration::AmdVcn4
+            | GpuGeneration::AmdVcn3
+            | GpuGeneration::IntelArc
+            | GpuGeneration::IntelBattlemage => 4,
+            GpuGeneration::IntelQuickSync => 2,
+            GpuGeneration::Unknown | GpuGeneration::None => 0,
+        }
+    }
 }
 
 /// エンコーダー選択エンジン
@@ -78,13 +260,24 @@ impl EncoderSelector {
     /// 推奨エンコーダー情報
     pub fn select_encoder(context: &EncoderSelectionContext) -> RecommendedEncoder {
         // プラットフォーム別の制約を確認
-        let platform_supports_av1 = matches!(context.platform, StreamingPlatform::YouTube);
+        // Bilibili/SHOWROOMはH.264必須のため、GPU世代に関わらずAV1を選択しない
+        let platform_requires_h264 = matches!(
+            context.platform,
+            StreamingPlatform::Bilibili | StreamingPlatform::Showroom
+        );
+        let platform_supports_av1 =
+            !platform_requires_h264 && matches!(context.platform, StreamingPlatform::YouTube);
         // HEVC対応プラットフォーム（将来の拡張用）
         let _platform_supports_hevc = matches!(
             context.platform,
             StreamingPlatform::YouTube | StreamingPlatform::TwitCasting
         );
 
+        // 配信専用PCのハイエンドCPUはx264を優先（AV1配信が可能な場合はAV1の圧縮効率を優先）
+        if context.prefers_dedicated_x264() && !(platform_supports_av1 && context.av1_available()) {
+            return Self::select_x264_encoder(context);
+        }
+
         // GPU世代に基づく判定
         match context.gpu_generation {
             GpuGeneration::NvidiaBlackwell
@@ -92,7 +285,7 @@ impl EncoderSelector {
             | GpuGeneration::NvidiaAmpere
             | GpuGeneration::NvidiaTuring => {
                 // YouTube かつ AV1対応GPUの場合はAV1を優先検討
-                if platform_supports_av1 && Self::gpu_supports_av1(context.gpu_generation) {
+                if platform_supports_av1 && context.av1_available() {
                     Self::select_av1_encoder(context)
                 } else {
                     Self::select_nvenc_encoder(context)
@@ -109,9 +302,9 @@ impl EncoderSelector {
             GpuGeneration::AmdVcn4 | GpuGeneration::AmdVcn3 => {
                 Self::select_amd_encoder(context)
             }
-            GpuGeneration::IntelArc => {
-                // Intel ArcもAV1対応だが、YouTubeの場合のみ
-                if platform_supports_av1 {
+            GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage => {
+                // Intel Arc（A/Bシリーズ）もAV1対応だが、YouTubeの場合のみ
+                if platform_supports_av1 && context.av1_available() {
                     Self::select_av1_encoder(context)
                 } else {
                     Self::select_intel_arc_encoder(context)
@@ -125,12 +318,183 @@ impl EncoderSelector {
         }
     }
 
-    /// GPUがAV1をサポートしているか確認
-    fn gpu_supports_av1(generation: GpuGeneration) -> bool {
-        if let Some(capability) = get_encoder_capability(generation) {
-            capability.av1
+    /// 録画用エンコーダーを選択
+    ///
+    /// 録画はプラットフォームのコーデック制約がないため、GPUが対応していれば
+    /// AV1 > HEVC > H.264 の順で圧縮効率の高いコーデックを選択する。
+    /// レート制御は品質固定（ハードウェア: CQP / x264: CRF）とする。
+    ///
+    /// # Arguments
+    /// * `context` - エンコーダー選択コンテキスト（`platform` は無視される）
+    pub fn select_recording_encoder(context: &EncoderSelectionContext) -> RecommendedEncoder {
+        // プリセット・Bフレーム等は配信用の選択結果を流用する
+        let mut base_context = context.clone();
+        base_context.platform = StreamingPlatform::Other;
+        base_context.recording_mode = true;
+        let mut encoder = Self::select_encoder(&base_context);
+
+        if encoder.encoder_id == "obs_x264" {
+            encoder.rate_control = "CRF".to_string();
+            // 録画では低遅延チューニングは不要
+            encoder.tuning = None;
+            encoder.reason = format!("{}。録画は画質固定（CRF）で保存します", encoder.reason);
+            return encoder;
+        }
+
+        let supports_av1 = context.av1_available();
+        let supports_hevc = context.hevc_available();
+
+        let codec = if supports_av1 {
+            "AV1"
+        } else if supports_hevc {
+            "HEVC"
         } else {
-            false
+            "H.264"
+        };
+
+        let encoder_id = match (context.gpu_generation, codec) {
+            (
+                GpuGeneration::NvidiaBlackwell
+                | GpuGeneration::NvidiaAda
+                | GpuGeneration::NvidiaAmpere
+                | GpuGeneration::NvidiaTuring
+                | GpuGeneration::NvidiaPascal,
+                "AV1",
+            ) => "jim_av1_nvenc",
+            (
+                GpuGeneration::NvidiaBlackwell
+                | GpuGeneration::NvidiaAda
+                | GpuGeneration::NvidiaAmpere
+                | GpuGeneration::NvidiaTuring
+                | GpuGeneration::NvidiaPascal,
+                "HEVC",
+            ) => "jim_hevc_nvenc",
+            (GpuGeneration::AmdVcn4 | GpuGeneration::AmdVcn3, "AV1") => "av1_texture_amf",
+            (GpuGeneration::AmdVcn4 | GpuGeneration::AmdVcn3, "HEVC") => "h265_texture_amf",
+            (
+                GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage | GpuGeneration::IntelQuickSync,
+                "AV1",
+            ) => "obs_qsv11_av1",
+            (
+                GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage | GpuGeneration::IntelQuickSync,
+                "HEVC",
+            ) => "obs_qsv11_hevc",
+            // H.264 の場合は配信用の選択結果をそのまま使用
+            _ => encoder.encoder_id.as_str(),
+        }
+        .to_string();
+
+        if codec != "H.264" {
+            encoder.display_name = format!("{codec} (Hardware)");
+            encoder.hevc_profile = (codec == "HEVC").then(|| HevcProfile::for_hdr(context.hdr_mode));
+            encoder.profile = encoder.hevc_profile.map_or("main", HevcProfile::obs_value).to_string();
+        }
+        encoder.encoder_id = encoder_id;
+        encoder.rate_control = "CQP".to_string();
+        encoder.reason = format!(
+            "{}を検出。録画は{}の画質固定（CQP）で、同等画質でもファイルサイズを抑えられます",
+            Self::gpu_display_name(context.gpu_generation),
+            codec
+        );
+
+        encoder
+    }
+
+    /// 指定したエンコーダーIDに合わせてプリセット・レート制御等を選び直す
+    ///
+    /// フォールバック時に使用する。同じベンダーのH.264ハードウェアの選択結果を元に、
+    /// エンコーダーID・表示名・プロファイルをコーデックに合わせて置き換える
+    ///
+    /// # Arguments
+    /// * `context` - エンコーダー選択コンテキスト
+    /// * `encoder_id` - 使用するエンコーダーID
+    pub fn select_for_encoder(context: &EncoderSelectionContext, encoder_id: &str) -> RecommendedEncoder {
+        let codec = match codec_class(encoder_id) {
+            Some(EncoderCodecClass::Av1) => Some("AV1"),
+            Some(EncoderCodecClass::Hevc) => Some("HEVC"),
+            _ => None,
+        };
+        // HEVC以外へのフォールバックでは、HDRモードでもNVENCのHEVC選択を行わない
+        let mut base_context = context.clone();
+        base_context.hdr_mode = context.hdr_mode && codec == Some("HEVC");
+        let context = &base_context;
+
+        let mut encoder = if encoder_id.contains("nvenc") {
+            Self::select_nvenc_encoder(context)
+        } else if encoder_id.contains("amf") {
+            Self::select_amd_encoder(context)
+        } else if encoder_id.starts_with("obs_qsv11") {
+            if matches!(
+                context.gpu_generation,
+                GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage
+            ) {
+                Self::select_intel_arc_encoder(context)
+            } else {
+                Self::select_quicksync_encoder(context)
+            }
+        } else {
+            Self::select_x264_encoder(context)
+        };
+
+        if let Some(codec) = codec {
+            encoder.display_name = format!("{codec} (Hardware)");
+            encoder.hevc_profile = (codec == "HEVC").then(|| HevcProfile::for_hdr(context.hdr_mode));
+            encoder.profile = encoder.hevc_profile.map_or("main", HevcProfile::obs_value).to_string();
+        }
+        encoder.encoder_id = encoder_id.to_string();
+        encoder.reason = format!("推奨エンコーダーを適用できなかったため、{}を使用します", encoder.display_name);
+
+        encoder
+    }
+
+    /// 推奨順に並べたエンコーダーの選択肢を取得
+    ///
+    /// 先頭は `select_encoder` の推奨。ハードウェアAV1エンコーダーが使えない環境で
+    /// ハイエンドCPUかつYouTube配信の場合は、高画質な代替としてAOM AV1（ソフトウェア）を追加する。
+    /// AOM AV1はエンコードが非常に重いため、主推奨にはしない
+    ///
+    /// # Arguments
+    /// * `context` - エンコーダー選択コンテキスト
+    pub fn rank_encoders(context: &EncoderSelectionContext) -> Vec<RecommendedEncoder> {
+        let mut ranked = vec![Self::select_encoder(context)];
+
+        let no_hardware_encoder =
+            matches!(context.gpu_generation, GpuGeneration::Unknown | GpuGeneration::None);
+        if no_hardware_encoder
+            && matches!(context.cpu_tier, CpuTier::HighEnd)
+            && matches!(context.platform, StreamingPlatform::YouTube)
+        {
+            ranked.push(Self::select_aom_av1_encoder());
+        }
+
+        ranked
+    }
+
+    /// AOM AV1（ソフトウェア）エンコーダーを選択
+    ///
+    /// OBS 29以降に同梱。CPUのみでAV1エンコードを行う
+    fn select_aom_av1_encoder() -> RecommendedEncoder {
+        RecommendedEncoder {
+            encoder_id: "aom_av1".to_string(),
+            display_name: "AOM AV1 (CPU)".to_string(),
+            // cpu-used相当の速度設定。リアルタイム配信には最速側が必要
+            preset: "10".to_string(),
+            rate_control: "CBR".to_string(),
+            b_frames: None,
+            look_ahead: false,
+            psycho_visual_tuning: false,
+            multipass_mode: "disabled".to_string(),
+            tuning: None,
+            nvenc_tuning: None,
+            profile: "main".to_string(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            aom_av1_warning: Some(
+                "AOM AV1はx264よりエンコード速度が大幅に遅く、ハイエンドCPUでも高解像度・高フレームレートではフレームドロップが発生する可能性があります。配信前にOBSの統計でエンコードラグを確認してください".to_string(),
+            ),
+            hevc_profile: None,
+            reason: "ハードウェアAV1エンコーダーが利用できません。ハイエンドCPUではAOM AV1（ソフトウェア）でYouTube向けに高画質な配信も選択できます".to_string(),
         }
     }
 
@@ -138,16 +502,22 @@ impl EncoderSelector {
     fn select_av1_encoder(context: &EncoderSelectionContext) -> RecommendedEncoder {
         let encoder_id = match context.gpu_generation {
             GpuGeneration::NvidiaBlackwell | GpuGeneration::NvidiaAda => "jim_av1_nvenc", // NVIDIA AV1
-            GpuGeneration::IntelArc => "obs_qsv11_av1",  // Intel Arc AV1
+            GpuGeneration::IntelArc | GpuGeneration::IntelBattlemage => "obs_qsv11_av1", // Intel Arc AV1
             _ => "ffmpeg_nvenc", // フォールバック: H.264
         };
 
         let is_av1 = matches!(
             context.gpu_generation,
-            GpuGeneration::NvidiaBlackwell | GpuGeneration::NvidiaAda | GpuGeneration::IntelArc
+            GpuGeneration::NvidiaBlackwell
+                | GpuGeneration::NvidiaAda
+                | GpuGeneration::IntelArc
+                | GpuGeneration::IntelBattlemage
         );
 
         if is_av1 {
+            let nvenc_tuning = encoder_id.contains("nvenc").then(|| {
+                NvencTuning::select(context.latency, context.encoding_tier(), context.recording_mode)
+            });
             let reason = format!(
                 "{}を検出。AV1エンコーダーはYouTubeで高画質・低ビットレートを実現します。H.264の30%程度のビットレートで同等画質を達成可能",
                 Self::gpu_display_name(context.gpu_generation)
@@ -162,8 +532,14 @@ impl EncoderSelector {
                 look_ahead: true,
                 psycho_visual_tuning: true,
                 multipass_mode: "quarter_res".to_string(),
-                tuning: Some("hq".to_string()),
+                tuning: None,
+                nvenc_tuning,
                 profile: "main".to_string(), // AV1はmainプロファイル
+                pre_analysis: false,
+                pa_look_ahead: None,
+                pa_scene_change_detect: false,
+                aom_av1_warning: None,
+                hevc_profile: None,
                 reason,
             }
         } else {
@@ -187,8 +563,8 @@ impl EncoderSelector {
         let capability = get_encoder_capability(context.gpu_generation)
             .unwrap_or(&default_capability);
 
-        // 統合ティアを算出
-        let effective_tier = context.effective_tier();
+        // 統合ティアを算出（配信専用PCではGPUのグレードによる負荷調整を行わない）
+        let effective_tier = context.encoding_tier();
 
         let b_frames = if capability.b_frames { Some(2) } else { None };
 
@@ -215,13 +591,8 @@ impl EncoderSelector {
             "disabled".to_string()
         };
 
-        // チューニング: 高品質設定を推奨
-        let tuning = match context.gpu_generation {
-            GpuGeneration::NvidiaBlackwell | GpuGeneration::NvidiaAda | GpuGeneration::NvidiaAmpere | GpuGeneration::NvidiaTuring => {
-                Some("hq".to_string())
-            }
-            _ => None,
-        };
+        // チューニング: 遅延モードと統合ティアに応じて選択
+        let nvenc_tuning = NvencTuning::select(context.latency, effective_tier, context.recording_mode);
 
         // プリセットを統合ティアに応じて調整
         let base_preset: u8 = capability.recommended_preset
@@ -249,7 +620,7 @@ impl EncoderSelector {
             tier_note
         );
 
-        RecommendedEncoder {
+        let mut encoder = RecommendedEncoder {
             encoder_id: "ffmpeg_nvenc".to_string(),
             display_name: "NVIDIA NVENC H.264".to_string(),
             preset: preset_string,
@@ -258,10 +629,31 @@ impl EncoderSelector {
             look_ahead,
             psycho_visual_tuning,
             multipass_mode,
-            tuning,
+            tuning: None,
+            nvenc_tuning: Some(nvenc_tuning),
             profile: "high".to_string(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            aom_av1_warning: None,
+            hevc_profile: None,
             reason,
+        };
+
+        // HDR配信は10bitが必要なため、H.264ではなくHEVC（Main 10）でエンコードする
+        if context.hdr_mode && capability.hevc {
+            let hevc_profile = HevcProfile::for_hdr(context.hdr_mode);
+            encoder.encoder_id = "jim_hevc_nvenc".to_string();
+            encoder.display_name = "NVIDIA NVENC HEVC".to_string();
+            encoder.profile = hevc_profile.obs_value().to_string();
+            encoder.hevc_profile = Some(hevc_profile);
+            encoder.reason = format!(
+                "{}。HDR配信のため10bitのHEVC（Main 10プロファイル）を使用します",
+                encoder.reason
+            );
         }
+
+        encoder
     }
 
     /// グレードの表示名を取得
@@ -294,10 +686,16 @@ impl EncoderSelector {
         // VCN 4.0はBフレームサポート
         let b_frames = if capability.b_frames { Some(2) } else { None };
 
-        let reason = format!(
+        // プリアナリシスは遅延が増えるため配信では無効にし、録画かつVCN 4.0のみ有効にする
+        let pre_analysis = context.recording_mode && matches!(context.gpu_generation, GpuGeneration::AmdVcn4);
+
+        let mut reason = format!(
             "{}を検出。AMFエンコーダーはCPU負荷を軽減し、8Mbps以上では高品質です",
             Self::gpu_display_name(context.gpu_generation)
         );
+        if pre_analysis {
+            reason.push_str("。録画のためプリアナリシスを有効にして画質を向上させます");
+        }
 
         RecommendedEncoder {
             encoder_id: "amd_amf_h264".to_string(),
@@ -309,7 +707,13 @@ impl EncoderSelector {
             psycho_visual_tuning: false,
             multipass_mode: "disabled".to_string(),
             tuning: None,
+            nvenc_tuning: None,
             profile: "high".to_string(),
+            pre_analysis,
+            pa_look_ahead: pre_analysis.then_some(AMF_PA_LOOK_AHEAD_FRAMES),
+            pa_scene_change_detect: pre_analysis,
+            aom_av1_warning: None,
+            hevc_profile: None,
             reason,
         }
     }
@@ -326,7 +730,13 @@ impl EncoderSelector {
             psycho_visual_tuning: false,
             multipass_mode: "disabled".to_string(),
             tuning: None,
+            nvenc_tuning: None,
             profile: "high".to_string(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            aom_av1_warning: None,
+            hevc_profile: None,
             reason: "Intel Arcを検出。QuickSyncは低ビットレートで優秀な品質を発揮します"
                 .to_string(),
         }
@@ -344,16 +754,27 @@ impl EncoderSelector {
             psycho_visual_tuning: false,
             multipass_mode: "disabled".to_string(),
             tuning: None,
+            nvenc_tuning: None,
             profile: "main".to_string(), // 内蔵GPUは互換性重視でmain
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            aom_av1_warning: None,
+            hevc_profile: None,
             reason: "Intel内蔵GPUを検出。QuickSyncでCPU負荷を軽減できます".to_string(),
         }
     }
 
     /// x264 CPU エンコーダーを選択
     fn select_x264_encoder(context: &EncoderSelectionContext) -> RecommendedEncoder {
-        let preset = Self::select_x264_preset(context.cpu_tier);
+        let preset = Self::select_x264_preset(context.cpu_tier, context.setup_type);
 
+        let dedicated = context.setup_type.is_dedicated_streaming_pc();
         let reason = match context.cpu_tier {
+            CpuTier::HighEnd if context.prefers_dedicated_x264() => {
+                format!("配信専用PCのハイエンドCPUを検出。ゲームの負荷がないため、x264 {}プリセットで高画質配信が可能です", preset)
+            }
+            CpuTier::Middle if dedicated => format!("CPUエンコード（{}プリセット）を使用", preset),
             CpuTier::Entry => {
                 "GPUエンコーダーが利用できません。CPUエンコードは負荷が高いため、ハードウェアエンコーダー対応GPUの導入を推奨します".to_string()
             }
@@ -383,17 +804,26 @@ impl EncoderSelector {
             psycho_visual_tuning: false,
             multipass_mode: "disabled".to_string(),
             tuning,
+            nvenc_tuning: None,
             profile: "high".to_string(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            aom_av1_warning: None,
+            hevc_profile: None,
             reason,
         }
     }
 
     /// x264プリセットを選択（CPUティアに基づく）
-    fn select_x264_preset(cpu_tier: CpuTier) -> String {
+    ///
+    /// 配信専用PCのハイエンドCPUはゲームと負荷を分け合わないため、1段階低速（高画質）のslowとする
+    fn select_x264_preset(cpu_tier: CpuTier, setup_type: SetupType) -> String {
         match cpu_tier {
             CpuTier::Entry => "ultrafast".to_string(),
             CpuTier::Middle => "veryfast".to_string(),
             CpuTier::UpperMiddle => "faster".to_string(),
+            CpuTier::HighEnd if setup_type.is_dedicated_streaming_pc() => "slow".to_string(),
             CpuTier::HighEnd => "fast".to_string(),
         }
     }
@@ -424,6 +854,7 @@ impl EncoderSelector {
             GpuGeneration::AmdVcn4 => "AMD RX 7000シリーズ",
             GpuGeneration::AmdVcn3 => "AMD RX 6000シリーズ",
             GpuGeneration::IntelArc => "Intel Arc GPU",
+            GpuGeneration::IntelBattlemage => "Intel Arc Bシリーズ",
             GpuGeneration::IntelQuickSync => "Intel内蔵GPU",
             GpuGeneration::Unknown => "不明なGPU",
             GpuGeneration::None => "GPU未検出",
@@ -432,6 +863,7 @@ impl EncoderSelector {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
 
@@ -442,10 +874,15 @@ mod tests {
         EncoderSelectionContext {
             gpu_generation: gpu_gen,
             gpu_grade: GpuGrade::HighEnd, // デフォルトはハイエンド
+            gpu_driver_version: None,
             cpu_tier,
             platform: StreamingPlatform::YouTube,
             style: StreamingStyle::Gaming,
             network_speed_mbps: 10.0,
+            recording_mode: false,
+            latency: StreamLatency::Normal,
+            setup_type: SetupType::SinglePc,
+            hdr_mode: false,
         }
     }
 
@@ -457,13 +894,87 @@ mod tests {
         EncoderSelectionContext {
             gpu_generation: gpu_gen,
             gpu_grade,
+            gpu_driver_version: None,
             cpu_tier,
             platform: StreamingPlatform::YouTube,
             style: StreamingStyle::Gaming,
             network_speed_mbps: 10.0,
+            recording_mode: false,
+            latency: StreamLatency::Normal,
+            setup_type: SetupType::SinglePc,
+            hdr_mode: false,
         }
     }
 
+    #[test]
+    fn test_dedicated_streaming_pc_prefers_slow_x264_on_high_end_cpu() {
+        let mut context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::HighEnd);
+        context.platform = StreamingPlatform::Twitch;
+        context.setup_type = SetupType::DualPcStreaming;
+
+        let encoder = EncoderSelector::select_encoder(&context);
+        assert_eq!(encoder.encoder_id, "obs_x264");
+        assert_eq!(encoder.preset, "slow");
+        assert!(encoder.reason.contains("配信専用PC"));
+
+        // 1台構成ではGPUエンコーダーを維持
+        context.setup_type = SetupType::SinglePc;
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "ffmpeg_nvenc");
+
+        // ゲーム用PC側は1台構成と同じ判定
+        context.setup_type = SetupType::DualPcGaming;
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "ffmpeg_nvenc");
+    }
+
+    #[test]
+    fn test_dedicated_streaming_pc_keeps_av1_and_hardware_for_lower_cpus() {
+        // YouTubeでAV1が使える場合はAV1の圧縮効率を優先
+        let mut context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::HighEnd);
+        context.setup_type = SetupType::DualPcStreaming;
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "jim_av1_nvenc");
+
+        // ハイエンド以外のCPUではGPUエンコーダーを維持
+        let mut context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::UpperMiddle);
+        context.platform = StreamingPlatform::Twitch;
+        context.setup_type = SetupType::DualPcStreaming;
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "ffmpeg_nvenc");
+
+        // 録画ではx264を優先しない
+        let mut context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::HighEnd);
+        context.setup_type = SetupType::DualPcStreaming;
+        assert_ne!(EncoderSelector::select_recording_encoder(&context).encoder_id, "obs_x264");
+    }
+
+    #[test]
+    fn test_dedicated_streaming_pc_ignores_gpu_grade_for_nvenc_load() {
+        let mut context =
+            create_test_context_with_grade(GpuGeneration::NvidiaTuring, GpuGrade::Entry, CpuTier::Middle);
+        context.platform = StreamingPlatform::Twitch;
+        let shared = EncoderSelector::select_encoder(&context);
+
+        context.setup_type = SetupType::DualPcStreaming;
+        let dedicated = EncoderSelector::select_encoder(&context);
+
+        // ゲームを描画しないため、同世代のハイエンドと同じ負荷調整になる
+        assert_eq!(context.encoding_tier(), EffectiveTier::TierB);
+        assert_eq!(context.effective_tier(), EffectiveTier::TierD);
+        assert!(dedicated.preset > shared.preset, "{} > {}", dedicated.preset, shared.preset);
+        assert_eq!(dedicated.multipass_mode, "quarter_res");
+        assert_eq!(shared.multipass_mode, "disabled");
+    }
+
+    #[test]
+    fn test_concurrent_session_budget() {
+        let budget = |generation| create_test_context(generation, CpuTier::Middle).concurrent_session_budget();
+
+        assert_eq!(budget(GpuGeneration::NvidiaAda), 8);
+        assert_eq!(budget(GpuGeneration::NvidiaTuring), 8);
+        assert_eq!(budget(GpuGeneration::NvidiaPascal), 3);
+        assert_eq!(budget(GpuGeneration::AmdVcn4), 4);
+        assert_eq!(budget(GpuGeneration::IntelQuickSync), 2);
+        assert_eq!(budget(GpuGeneration::None), 0);
+    }
+
     #[test]
     fn test_select_nvenc_ada() {
         // Ada + HighEnd(デフォルト) = TierS → AV1エンコーダが選択される
@@ -555,6 +1066,83 @@ mod tests {
         assert_eq!(encoder.b_frames, None); // VCN 3.0はBフレーム未対応
     }
 
+    #[test]
+    fn test_required_output_mode() {
+        // Bフレーム・Look-aheadを使うNVENCは詳細モードが必要
+        let nvenc = EncoderSelector::select_encoder(&create_test_context(GpuGeneration::NvidiaAda, CpuTier::Middle));
+        assert_eq!(nvenc.required_output_mode(), ObsOutputMode::Advanced);
+
+        // Bフレーム・プリアナリシスを使わないVCN 3.0の配信は基本モードで足りる
+        let amd = EncoderSelector::select_encoder(&create_test_context(GpuGeneration::AmdVcn3, CpuTier::Middle));
+        assert_eq!(amd.required_output_mode(), ObsOutputMode::Simple);
+
+        // マルチパスのみ使用する場合も詳細モードが必要
+        let mut multipass_only = amd;
+        multipass_only.multipass_mode = "quarter_res".to_string();
+        assert_eq!(multipass_only.required_output_mode(), ObsOutputMode::Advanced);
+    }
+
+    #[test]
+    fn test_amd_pre_analysis_disabled_for_all_streaming_platforms() {
+        for gpu_gen in [GpuGeneration::AmdVcn4, GpuGeneration::AmdVcn3] {
+            for platform in [
+                StreamingPlatform::YouTube,
+                StreamingPlatform::Twitch,
+                StreamingPlatform::NicoNico,
+                StreamingPlatform::TwitCasting,
+                StreamingPlatform::Bilibili,
+                StreamingPlatform::Showroom,
+                StreamingPlatform::Other,
+            ] {
+                let mut context = create_test_context(gpu_gen, CpuTier::Middle);
+                context.platform = platform;
+                let encoder = EncoderSelector::select_encoder(&context);
+
+                assert!(!encoder.pre_analysis, "{gpu_gen:?}/{platform:?}の配信でプリアナリシスが有効");
+                assert_eq!(encoder.pa_look_ahead, None);
+                assert!(!encoder.pa_scene_change_detect);
+            }
+        }
+    }
+
+    #[test]
+    fn test_amd_pre_analysis_enabled_for_recording_on_vcn4() {
+        let mut context = create_test_context(GpuGeneration::AmdVcn4, CpuTier::Middle);
+        context.recording_mode = true;
+        let encoder = EncoderSelector::select_encoder(&context);
+
+        assert!(encoder.pre_analysis);
+        assert_eq!(encoder.pa_look_ahead, Some(AMF_PA_LOOK_AHEAD_FRAMES));
+        assert!(encoder.pa_scene_change_detect);
+
+        // 録画用エンコーダー選択でも有効になる
+        let context = create_test_context(GpuGeneration::AmdVcn4, CpuTier::Middle);
+        let recording = EncoderSelector::select_recording_encoder(&context);
+        assert_eq!(recording.encoder_id, "h265_texture_amf");
+        assert!(recording.pre_analysis);
+    }
+
+    #[test]
+    fn test_amd_pre_analysis_disabled_for_recording_on_vcn3() {
+        let mut context = create_test_context(GpuGeneration::AmdVcn3, CpuTier::Middle);
+        context.recording_mode = true;
+        let encoder = EncoderSelector::select_encoder(&context);
+
+        assert!(!encoder.pre_analysis);
+        assert_eq!(encoder.pa_look_ahead, None);
+    }
+
+    #[test]
+    fn test_recommended_encoder_serializes_pre_analysis_fields() {
+        let mut context = create_test_context(GpuGeneration::AmdVcn4, CpuTier::Middle);
+        context.recording_mode = true;
+        let json = serde_json::to_value(EncoderSelector::select_encoder(&context)).unwrap();
+
+        assert_eq!(json["preAnalysis"], true);
+        assert_eq!(json["paLookAhead"], AMF_PA_LOOK_AHEAD_FRAMES);
+        assert_eq!(json["paSceneChangeDetect"], true);
+    }
+
     #[test]
     fn test_select_intel_arc() {
         // Intel Arc + HighEnd(デフォルト) = TierA → AV1エンコーダが選択される
@@ -565,6 +1153,29 @@ mod tests {
         assert_eq!(encoder.preset, "p7");
     }
 
+    #[test]
+    fn test_select_intel_battlemage_matches_intel_arc() {
+        for platform in [
+            StreamingPlatform::YouTube,
+            StreamingPlatform::Twitch,
+            StreamingPlatform::TwitCasting,
+            StreamingPlatform::NicoNico,
+        ] {
+            let mut arc = create_test_context(GpuGeneration::IntelArc, CpuTier::Middle);
+            arc.platform = platform;
+            let mut battlemage = create_test_context(GpuGeneration::IntelBattlemage, CpuTier::Middle);
+            battlemage.platform = platform;
+
+            let arc_encoder = EncoderSelector::select_encoder(&arc);
+            let battlemage_encoder = EncoderSelector::select_encoder(&battlemage);
+            assert_eq!(battlemage_encoder.encoder_id, arc_encoder.encoder_id, "{platform:?}");
+        }
+
+        let mut context = create_test_context(GpuGeneration::IntelBattlemage, CpuTier::Middle);
+        context.platform = StreamingPlatform::YouTube;
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "obs_qsv11_av1");
+    }
+
     #[test]
     fn test_select_x264_entry_cpu() {
         let context = create_test_context(GpuGeneration::None, CpuTier::Entry);
@@ -669,6 +1280,53 @@ mod tests {
         assert_eq!(encoder.encoder_id, "obs_qsv11");
     }
 
+    #[test]
+    fn test_recording_encoder_prefers_av1_then_hevc() {
+        let test_cases = vec![
+            (GpuGeneration::NvidiaAda, "jim_av1_nvenc"),
+            (GpuGeneration::NvidiaAmpere, "jim_hevc_nvenc"),
+            (GpuGeneration::AmdVcn4, "h265_texture_amf"),
+            (GpuGeneration::IntelArc, "obs_qsv11_av1"),
+            (GpuGeneration::IntelQuickSync, "obs_qsv11_hevc"),
+        ];
+
+        for (gpu_gen, expected_encoder) in test_cases {
+            let context = create_test_context(gpu_gen, CpuTier::Middle);
+            let encoder = EncoderSelector::select_recording_encoder(&context);
+            assert_eq!(encoder.encoder_id, expected_encoder, "{:?}の録画エンコーダー", gpu_gen);
+            assert_eq!(encoder.rate_control, "CQP");
+        }
+    }
+
+    #[test]
+    fn test_recording_encoder_x264_uses_crf() {
+        let context = create_test_context(GpuGeneration::None, CpuTier::HighEnd);
+        let encoder = EncoderSelector::select_recording_encoder(&context);
+
+        assert_eq!(encoder.encoder_id, "obs_x264");
+        assert_eq!(encoder.rate_control, "CRF");
+        assert!(encoder.tuning.is_none(), "録画では低遅延チューニング不要");
+    }
+
+    #[test]
+    fn test_h264_only_for_bilibili_all_av1_gpus() {
+        // BilibiliはH.264必須のため、AV1対応GPUでもAV1を選択しない
+        for gpu_gen in [
+            GpuGeneration::NvidiaBlackwell,
+            GpuGeneration::NvidiaAda,
+            GpuGeneration::IntelArc,
+            GpuGeneration::AmdVcn4,
+        ] {
+            let mut context = create_test_context(gpu_gen, CpuTier::Middle);
+            context.platform = StreamingPlatform::Bilibili;
+            let encoder = EncoderSelector::select_encoder(&context);
+
+            assert!(!encoder.encoder_id.contains("av1"),
+                "{:?} on Bilibili must not select AV1: {}", gpu_gen, encoder.encoder_id);
+            assert!(!encoder.reason.contains("AV1"));
+        }
+    }
+
     #[test]
     fn test_platform_constraints() {
         // プラットフォームごとのエンコーダー制約テスト
@@ -679,6 +1337,8 @@ mod tests {
             (StreamingPlatform::Twitch, GpuGeneration::NvidiaAda, "ffmpeg_nvenc"),
             (StreamingPlatform::NicoNico, GpuGeneration::NvidiaAda, "ffmpeg_nvenc"),
             (StreamingPlatform::TwitCasting, GpuGeneration::NvidiaAda, "ffmpeg_nvenc"),
+            (StreamingPlatform::Bilibili, GpuGeneration::NvidiaBlackwell, "ffmpeg_nvenc"),
+            (StreamingPlatform::Showroom, GpuGeneration::NvidiaAda, "ffmpeg_nvenc"),
             (StreamingPlatform::Other, GpuGeneration::NvidiaAda, "ffmpeg_nvenc"),
         ];
 
@@ -1380,7 +2040,7 @@ mod tests {
         assert!(encoder.look_ahead, "AV1 should enable look-ahead");
         assert!(encoder.psycho_visual_tuning, "AV1 should enable psycho visual tuning");
         assert_eq!(encoder.multipass_mode, "quarter_res", "AV1 should use multipass");
-        assert_eq!(encoder.tuning, Some("hq".to_string()), "AV1 should use HQ tuning");
+        assert_eq!(encoder.nvenc_tuning, Some(NvencTuning::HighQuality), "AV1 should use HQ tuning");
         assert_eq!(encoder.profile, "main", "AV1 should use main profile");
     }
 
@@ -1413,11 +2073,12 @@ mod tests {
         let mut nvenc_ctx = create_test_context(GpuGeneration::NvidiaAmpere, CpuTier::Middle);
         nvenc_ctx.platform = StreamingPlatform::Twitch;
         let nvenc = EncoderSelector::select_encoder(&nvenc_ctx);
-        assert_eq!(nvenc.tuning, Some("hq".to_string()), "NVENC should use HQ tuning");
+        assert_eq!(nvenc.nvenc_tuning, Some(NvencTuning::HighQuality), "NVENC should use HQ tuning");
+        assert_eq!(nvenc.tuning, None, "NVENC should not use x264 tuning");
 
         let av1_ctx = create_test_context(GpuGeneration::NvidiaAda, CpuTier::Middle);
         let av1 = EncoderSelector::select_encoder(&av1_ctx);
-        assert_eq!(av1.tuning, Some("hq".to_string()), "AV1 should use HQ tuning");
+        assert_eq!(av1.nvenc_tuning, Some(NvencTuning::HighQuality), "AV1 should use HQ tuning");
 
         let x264_entry = create_test_context(GpuGeneration::None, CpuTier::Entry);
         let x264_e = EncoderSelector::select_encoder(&x264_entry);
@@ -1429,6 +2090,94 @@ mod tests {
         assert_eq!(x264_h.tuning, None, "x264 HighEnd should not use tuning");
     }
 
+    #[test]
+    fn test_nvenc_tuning_select() {
+        let tiers = [
+            EffectiveTier::TierS,
+            EffectiveTier::TierA,
+            EffectiveTier::TierB,
+            EffectiveTier::TierC,
+            EffectiveTier::TierD,
+        ];
+        for tier in tiers {
+            assert_eq!(NvencTuning::select(StreamLatency::Normal, tier, false), NvencTuning::HighQuality, "{tier:?}");
+            assert_eq!(NvencTuning::select(StreamLatency::Low, tier, false), NvencTuning::HighQuality, "{tier:?}");
+            assert_eq!(NvencTuning::select(StreamLatency::UltraLow, tier, false), NvencTuning::LowLatency, "{tier:?}");
+            // 録画は遅延モードにかかわらず高画質
+            assert_eq!(NvencTuning::select(StreamLatency::UltraLow, tier, true), NvencTuning::HighQuality, "{tier:?}");
+        }
+
+        // TierEは遅延モード・録画にかかわらず低遅延（エンコーダー負荷の軽減）
+        for latency in [StreamLatency::Normal, StreamLatency::Low, StreamLatency::UltraLow] {
+            assert_eq!(NvencTuning::select(latency, EffectiveTier::TierE, false), NvencTuning::LowLatency);
+            assert_eq!(NvencTuning::select(latency, EffectiveTier::TierE, true), NvencTuning::LowLatency);
+        }
+    }
+
+    #[test]
+    fn test_nvenc_tuning_follows_latency_mode() {
+        let mut context = create_test_context(GpuGeneration::NvidiaAmpere, CpuTier::Middle);
+        context.platform = StreamingPlatform::Twitch;
+
+        for (latency, expected) in [
+            (StreamLatency::Normal, NvencTuning::HighQuality),
+            (StreamLatency::Low, NvencTuning::HighQuality),
+            (StreamLatency::UltraLow, NvencTuning::LowLatency),
+        ] {
+            context.latency = latency;
+            let encoder = EncoderSelector::select_encoder(&context);
+            assert_eq!(encoder.encoder_id, "ffmpeg_nvenc");
+            assert_eq!(encoder.nvenc_tuning, Some(expected), "{latency:?}");
+        }
+
+        // AV1（NVIDIA）も同じ選択
+        let mut av1_context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::Middle);
+        av1_context.latency = StreamLatency::UltraLow;
+        let av1 = EncoderSelector::select_encoder(&av1_context);
+        assert_eq!(av1.encoder_id, "jim_av1_nvenc");
+        assert_eq!(av1.nvenc_tuning, Some(NvencTuning::LowLatency));
+
+        // 録画では超低遅延モードでも高画質
+        let recording = EncoderSelector::select_recording_encoder(&context);
+        assert_eq!(recording.nvenc_tuning, Some(NvencTuning::HighQuality));
+    }
+
+    #[test]
+    fn test_nvenc_tuning_tier_e_uses_low_latency() {
+        let mut context = create_test_context_with_grade(GpuGeneration::NvidiaPascal, GpuGrade::Entry, CpuTier::Middle);
+        context.platform = StreamingPlatform::Twitch;
+        assert_eq!(context.effective_tier(), EffectiveTier::TierE);
+
+        let encoder = EncoderSelector::select_encoder(&context);
+        assert_eq!(encoder.encoder_id, "ffmpeg_nvenc");
+        assert_eq!(encoder.nvenc_tuning, Some(NvencTuning::LowLatency));
+    }
+
+    #[test]
+    fn test_nvenc_tuning_not_set_for_other_encoders() {
+        for gpu_gen in [GpuGeneration::AmdVcn4, GpuGeneration::IntelQuickSync, GpuGeneration::None] {
+            let encoder = EncoderSelector::select_encoder(&create_test_context(gpu_gen, CpuTier::Middle));
+            assert_eq!(encoder.nvenc_tuning, None, "{gpu_gen:?}");
+        }
+        // Intel ArcのAV1はNVENCではない
+        let arc = EncoderSelector::select_encoder(&create_test_context(GpuGeneration::IntelArc, CpuTier::Middle));
+        assert_eq!(arc.encoder_id, "obs_qsv11_av1");
+        assert_eq!(arc.nvenc_tuning, None);
+    }
+
+    #[test]
+    fn test_nvenc_tuning_serialization() {
+        for (tuning, value) in [
+            (NvencTuning::HighQuality, "hq"),
+            (NvencTuning::LowLatency, "ll"),
+            (NvencTuning::UltraLowLatency, "ull"),
+        ] {
+            assert_eq!(tuning.obs_value(), value);
+            assert_eq!(serde_json::to_value(tuning).unwrap(), serde_json::json!(value));
+            assert_eq!(serde_json::from_value::<NvencTuning>(serde_json::json!(value)).unwrap(), tuning);
+        }
+    }
+
     #[test]
     fn test_profile_settings() {
         // プロファイル設定の確認
@@ -1449,4 +2198,128 @@ mod tests {
                 "{:?} on {:?} profile mismatch", gpu_gen, platform);
         }
     }
+
+    #[test]
+    fn test_outdated_driver_avoids_av1() {
+        // AV1 NVENCは522以降のドライバーが必要
+        let mut context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::Middle);
+        context.gpu_driver_version = Some("516.94".to_string());
+
+        let encoder = EncoderSelector::select_encoder(&context);
+        assert!(!encoder.encoder_id.contains("av1"), "{}", encoder.encoder_id);
+
+        let recording = EncoderSelector::select_recording_encoder(&context);
+        assert_eq!(recording.encoder_id, "jim_hevc_nvenc");
+
+        // 要件を満たすドライバーならAV1を選択
+        context.gpu_driver_version = Some("551.86".to_string());
+        assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "jim_av1_nvenc");
+    }
+
+    #[test]
+    fn test_select_for_encoder_rederives_settings() {
+        let context = create_test_context(GpuGeneration::NvidiaAda, CpuTier::Middle);
+
+        // AV1 → HEVCへのフォールバック
+        let hevc = EncoderSelector::select_for_encoder(&context, "jim_hevc_nvenc");
+        assert_eq!(hevc.encoder_id, "jim_hevc_nvenc");
+        assert_eq!(hevc.display_name, "HEVC (Hardware)");
+        assert_eq!(hevc.profile, "main");
+
+        // NVENC H.264 → x264へのフォールバックではCPUティアに応じたプリセットを選び直す
+        let x264 = EncoderSelector::select_for_encoder(&context, "obs_x264");
+        assert_eq!(x264.encoder_id, "obs_x264");
+        assert_eq!(x264.preset, EncoderSelector::select_x264_preset(CpuTier::Middle, SetupType::SinglePc));
+        assert!(!x264.look_ahead);
+    }
+
+    #[test]
+    fn test_hdr_mode_selects_hevc_main10() {
+        let mut context = create_test_context(GpuGeneration::NvidiaAmpere, CpuTier::Middle);
+        context.platform = StreamingPlatform::Twitch;
+
+        // SDRではH.264のまま
+        let sdr = EncoderSelector::select_encoder(&context);
+        assert_eq!(sdr.encoder_id, "ffmpeg_nvenc");
+        assert_eq!(sdr.profile, "high");
+        assert_eq!(sdr.hevc_profile, None);
+
+        context.hdr_mode = true;
+        let hdr = EncoderSelector::select_encoder(&context);
+        assert_eq!(hdr.encoder_id, "jim_hevc_nvenc");
+        assert_eq!(hdr.profile, "main10");
+        assert_eq!(hdr.hevc_profile, Some(HevcProfile::Main10));
+        assert!(hdr.reason.contains("Main 10"));
+        // プリセット・チューニングはH.264の選択結果を引き継ぐ
+        assert_eq!(hdr.preset, sdr.preset);
+        assert_eq!(hdr.nvenc_tuning, sdr.nvenc_tuning);
+    }
+
+    #[test]
+    fn test_hevc_profile_follows_hdr_mode() {
+        let mut context = create_test_context(GpuGeneration::NvidiaAmpere, CpuTier::Middle);
+
+        // 録画のHEVCはSDRではMain、HDRではMain 10
+        let recording = EncoderSelector::select_recording_encoder(&context);
+        assert_eq!(recording.encoder_id, "jim_hevc_nvenc");
+        assert_eq!(recording.hevc_profile, Some(HevcProfile::Main));
+        assert_eq!(recording.profile, "main");
+
+        context.hdr_mode = true;
+        let recording = EncoderSelector::select_recording_encoder(&context);
+        assert_eq!(recording.hevc_profile, Some(HevcProfile::Main10));
+        assert_eq!(recording.profile, "main10");
+
+        // HEVCへのフォールバックはMain 10を維持し、H.264へのフォールバックでは8bitに戻す
+        let hevc = EncoderSelector::select_for_encoder(&context, "jim_hevc_nvenc");
+        assert_eq!(hevc.hevc_profile, Some(HevcProfile::Main10));
+        let h264 = EncoderSelector::select_for_encoder(&context, "ffmpeg_nvenc");
+        assert_eq!(h264.display_name, "NVIDIA NVENC H.264");
+        assert_eq!(h264.profile, "high");
+        assert_eq!(h264.hevc_profile, None);
+    }
+
+    #[test]
+    fn test_hevc_profile_serialization() {
+        assert_eq!(serde_json::to_value(HevcProfile::Main10).unwrap(), "main10");
+        assert_eq!(serde_json::to_value(HevcProfile::Main).unwrap(), "main");
+        assert_eq!(HevcProfile::for_hdr(true).obs_value(), "main10");
+        assert_eq!(HevcProfile::for_hdr(false).obs_value(), "main");
+    }
+
+    #[test]
+    fn test_rank_encoders_offers_aom_av1_for_high_end_cpu_without_gpu() {
+        for generation in [GpuGeneration::None, GpuGeneration::Unknown] {
+            let context = create_test_context(generation, CpuTier::HighEnd);
+            let ranked = EncoderSelector::rank_encoders(&context);
+
+            // 主推奨はx264のまま
+            assert_eq!(ranked[0].encoder_id, "obs_x264");
+            assert_eq!(EncoderSelector::select_encoder(&context).encoder_id, "obs_x264");
+
+            let aom = ranked.iter().find(|encoder| encoder.encoder_id == "aom_av1").unwrap();
+            assert!(aom.aom_av1_warning.is_some());
+            assert!(ranked[0].aom_av1_warning.is_none());
+        }
+    }
+
+    #[test]
+    fn test_rank_encoders_omits_aom_av1_otherwise() {
+        let has_aom = |context: &EncoderSelectionContext| {
+            EncoderSelector::rank_encoders(context)
+                .iter()
+                .any(|encoder| encoder.encoder_id == "aom_av1")
+        };
+
+        // ハイエンド以外のCPU
+        assert!(!has_aom(&create_test_context(GpuGeneration::None, CpuTier::UpperMiddle)));
+
+        // YouTube以外
+        let mut context = create_test_context(GpuGeneration::None, CpuTier::HighEnd);
+        context.platform = StreamingPlatform::Twitch;
+        assert!(!has_aom(&context));
+
+        // ハードウェアエンコーダーがある
+        assert!(!has_aom(&create_test_context(GpuGeneration::NvidiaAmpere, CpuTier::HighEnd)));
+    }
 }
@@ -1,11 +1,21 @@
 // データエクスポート機能
 //
-// セッションデータ、診断レポートをJSON/CSV形式でエクスポート
+// セッションデータ、診断レポートをJSON/CSV/Markdown形式でエクスポート
 
 use crate::error::AppError;
-use crate::services::analyzer::ProblemReport;
-use crate::storage::metrics_history::{HistoricalMetrics, SessionSummary};
+use crate::monitor::display::{cached_display_info, DisplayInfo};
+use crate::monitor::network::{cached_network_adapter_type, NetworkAdapterType};
+use crate::monitor::power::{cached_power_status, PowerSource};
+use crate::obs::ObsSettings;
+use crate::services::alerts::AlertSeverity;
+use crate::services::analyzer::{ProblemCategory, ProblemReport};
+use crate::services::optimizer::RecommendedSettings;
+use crate::storage::metrics_history::{AlertCount, HistoricalMetrics, SessionSummary};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// セッションのJSONエクスポートの形式バージョン
+pub const SESSION_EXPORT_VERSION: &str = "1.0";
 
 /// 診断レポート
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +33,36 @@ pub struct DiagnosticReport {
     pub performance: PerformanceEvaluation,
     /// 推奨事項サマリー
     pub recommendations_summary: String,
+    /// 推奨設定（OBS未接続の場合はNone）
+    #[serde(default)]
+    pub recommended_settings: Option<RecommendedSettings>,
+    /// 現在のOBS設定（OBS未接続の場合はNone）
+    #[serde(default)]
+    pub obs_settings: Option<ObsSettings>,
+}
+
+/// 診断レポートの出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// CSV
+    Csv,
+    /// Markdown（GitHubのIssue添付用）
+    Markdown,
+}
+
+impl ReportFormat {
+    /// ファイル拡張子
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+        }
+    }
 }
 
 /// セッション情報
@@ -37,6 +77,15 @@ pub struct SessionInfo {
     pub started_at: i64,
     /// 配信終了時刻
     pub ended_at: i64,
+    /// タイトル
+    #[serde(default)]
+    pub title: Option<String>,
+    /// タグ
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// メモ
+    #[serde(default)]
+    pub notes: Option<String>,
 }
 
 /// システム情報
@@ -51,6 +100,21 @@ pub struct SystemInfo {
     pub total_memory_mb: u64,
     /// GPUモデル
     pub gpu_model: Option<String>,
+    /// GPUドライバーバージョン
+    #[serde(default)]
+    pub gpu_driver_version: Option<String>,
+    /// 電源の種類
+    #[serde(default)]
+    pub power_source: PowerSource,
+    /// バッテリー残量（%）
+    #[serde(default)]
+    pub battery_percent: Option<u8>,
+    /// ネットワークアダプターの種類
+    #[serde(default)]
+    pub network_adapter: NetworkAdapterType,
+    /// 接続されているモニター（検出できない場合は空）
+    #[serde(default)]
+    pub monitors: Vec<DisplayInfo>,
 }
 
 /// パフォーマンス評価
@@ -69,6 +133,142 @@ pub struct PerformanceEvaluation {
     pub stability_score: f64,
 }
 
+/// プラットフォーム別の配信統計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformStats {
+    /// セッション数
+    pub session_count: u32,
+    /// 平均品質スコア（0-100）
+    pub avg_quality_score: f64,
+    /// 平均ビットレート（kbps）
+    pub avg_bitrate_kbps: u32,
+    /// 月内の配信時間（時間）
+    pub total_hours: f64,
+}
+
+/// 月次配信レポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyReport {
+    /// 年
+    pub year: u32,
+    /// 月（1-12）
+    pub month: u8,
+    /// 月内の総配信時間（時間）
+    pub total_hours: f64,
+    /// プラットフォーム別の統計（キーはプラットフォーム名、未記録は"unknown"）
+    pub per_platform: HashMap<String, PlatformStats>,
+    /// 全セッションの平均品質スコア
+    pub overall_avg_score: f64,
+    /// 月内の推定データ使用量（GB）
+    pub total_data_gb: f64,
+}
+
+/// 指定月の開始・終了時刻（UNIX epoch秒、終了は含まない）を取得
+///
+/// # Arguments
+/// * `offset` - 月の区切りに使うタイムゾーンのオフセット
+pub fn month_bounds(year: u32, month: u8, offset: chrono::FixedOffset) -> Result<(i64, i64), AppError> {
+    let invalid = || AppError::validation_failed(&format!("無効な年月です: {year}-{month}"));
+    let year = i32::try_from(year).map_err(|_| invalid())?;
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, u32::from(month) + 1) };
+
+    let start = chrono::NaiveDate::from_ymd_opt(year, u32::from(month), 1).ok_or_else(invalid)?;
+    let end = chrono::NaiveDate::from_ymd_opt(next_year, next_month, 1).ok_or_else(invalid)?;
+    let to_epoch = |date: chrono::NaiveDate| {
+        date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp() - i64::from(offset.local_minus_utc())
+    };
+
+    Ok((to_epoch(start), to_epoch(end)))
+}
+
+/// 1週間の秒数
+const WEEK_SECS: i64 = 7 * 86_400;
+
+/// 前週比でヘルススコアが「変化なし」とみなす幅（ポイント）
+const TREND_STABLE_MARGIN: f64 = 2.0;
+
+/// 前週からの傾向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrendDirection {
+    /// ヘルススコアが改善
+    Improving,
+    /// ほぼ変化なし
+    Stable,
+    /// ヘルススコアが悪化
+    Declining,
+    /// どちらかの週に配信がなく比較できない
+    NoData,
+}
+
+/// 前週との比較
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyTrend {
+    /// 前週のセッション数
+    pub previous_session_count: u32,
+    /// 前週の総配信時間（時間）
+    pub previous_total_hours: f64,
+    /// 前週の平均ヘルススコア
+    pub previous_avg_health_score: f64,
+    /// 総配信時間の増減（時間）
+    pub hours_change: f64,
+    /// 平均ヘルススコアの増減（比較できない場合は0）
+    pub health_score_change: f64,
+    /// 傾向
+    pub direction: TrendDirection,
+}
+
+/// 問題カテゴリーごとのアラート件数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryCount {
+    /// カテゴリー
+    pub category: ProblemCategory,
+    /// アラート件数
+    pub count: u32,
+}
+
+/// 週次配信レポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReport {
+    /// 週の開始時刻（月曜0時、UNIX epoch秒）
+    pub week_start: i64,
+    /// 週の終了時刻（翌週月曜0時、含まない）
+    pub week_end: i64,
+    /// セッション数
+    pub session_count: u32,
+    /// 週内の総配信時間（時間）
+    pub total_hours: f64,
+    /// 平均ヘルススコア（セッションの品質スコアの平均、配信がない場合は0）
+    pub avg_health_score: f64,
+    /// 品質スコアが最も低いセッション
+    pub worst_session: Option<SessionSummary>,
+    /// アラート件数の多い問題カテゴリー（件数順）
+    pub top_problem_categories: Vec<CategoryCount>,
+    /// 前週との比較
+    pub trend: WeeklyTrend,
+}
+
+/// 指定週の開始・終了時刻（UNIX epoch秒、終了は含まない）を取得
+///
+/// 週は月曜0時（`offset` のタイムゾーン）から始まる
+///
+/// # Arguments
+/// * `week_offset` - 何週前か（0 = 今週）
+/// * `now` - 現在時刻（UNIX epoch秒）
+pub fn week_bounds(week_offset: u32, now: i64, offset: chrono::FixedOffset) -> (i64, i64) {
+    let utc_offset = i64::from(offset.local_minus_utc());
+    let local_days = (now + utc_offset).div_euclid(86_400);
+    // 1970-01-01は木曜日
+    let days_from_monday = (local_days + 3).rem_euclid(7);
+    let start = (local_days - days_from_monday) * 86_400 - utc_offset - i64::from(week_offset) * WEEK_SECS;
+    (start, start + WEEK_SECS)
+}
+
 /// レポートエクスポーター
 pub struct ReportExporter;
 
@@ -92,7 +292,7 @@ impl ReportExporter {
         metrics_history: &[HistoricalMetrics],
     ) -> Result<String, AppError> {
         let export_data = serde_json::json!({
-            "version": "1.0",
+            "version": SESSION_EXPORT_VERSION,
             "exported_at": chrono::Utc::now().timestamp(),
             "session": session_summary,
             "metrics": metrics_history,
@@ -163,28 +363,211 @@ impl ReportExporter {
             generated_at: chrono::Utc::now().timestamp(),
             session: SessionInfo {
                 session_id: session_summary.session_id.clone(),
-                duration_secs: session_summary.end_time - session_summary.start_time,
+                duration_secs: session_summary.duration_secs(),
                 started_at: session_summary.start_time,
                 ended_at: session_summary.end_time,
+                title: session_summary.title.clone(),
+                tags: session_summary.tags.clone(),
+                notes: session_summary.notes.clone(),
             },
             system_info,
             problems: problems.to_vec(),
             performance,
             recommendations_summary,
+            recommended_settings: None,
+            obs_settings: None,
         };
 
         Ok(report)
     }
 
+    /// 診断レポートを指定形式の文字列に変換
+    ///
+    /// # Arguments
+    /// * `report` - 診断レポート
+    /// * `format` - 出力形式
+    pub fn export_diagnostic_report(
+        &self,
+        report: &DiagnosticReport,
+        format: ReportFormat,
+    ) -> Result<String, AppError> {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(report)
+                .map_err(|e| AppError::export_error(&format!("Failed to serialize JSON: {e}"))),
+            ReportFormat::Csv => Ok(Self::to_csv(report)),
+            ReportFormat::Markdown => Ok(Self::to_markdown(report)),
+        }
+    }
+
+    /// 診断レポートをCSV形式に変換
+    ///
+    /// `section,item,value` の3列で、問題は重要度順に1行ずつ出力する
+    pub fn to_csv(report: &DiagnosticReport) -> String {
+        let mut rows: Vec<(&str, String, String)> = vec![
+            ("report", "generated_at".to_string(), format_timestamp(report.generated_at)),
+            ("session", "session_id".to_string(), report.session.session_id.clone()),
+            ("session", "duration_secs".to_string(), report.session.duration_secs.to_string()),
+            ("system", "os".to_string(), report.system_info.os.clone()),
+            ("system", "cpu_model".to_string(), report.system_info.cpu_model.clone()),
+            ("system", "total_memory_mb".to_string(), report.system_info.total_memory_mb.to_string()),
+            ("system", "gpu_model".to_string(), report.system_info.gpu_model.clone().unwrap_or_default()),
+            (
+                "system",
+                "gpu_driver_version".to_string(),
+                report.system_info.gpu_driver_version.clone().unwrap_or_default(),
+            ),
+            ("performance", "overall_score".to_string(), format!("{:.1}", report.performance.overall_score)),
+            ("performance", "cpu_score".to_string(), format!("{:.1}", report.performance.cpu_score)),
+            ("performance", "gpu_score".to_string(), format!("{:.1}", report.performance.gpu_score)),
+            ("performance", "network_score".to_string(), format!("{:.1}", report.performance.network_score)),
+            ("performance", "stability_score".to_string(), format!("{:.1}", report.performance.stability_score)),
+        ];
+        for problem in sorted_by_severity(&report.problems) {
+            rows.push(("problem", severity_label(problem.severity).to_string(), problem.title.clone()));
+        }
+
+        let mut csv = String::from("section,item,value\n");
+        for (section, item, value) in rows {
+            csv.push_str(&format!("{},{},{}\n", section, escape_csv(&item), escape_csv(&value)));
+        }
+        csv
+    }
+
+    /// 診断レポートをGitHub Flavored Markdown形式に変換
+    ///
+    /// Issueにそのまま貼り付けられるよう、ハードウェア情報・推奨設定は表、
+    /// 問題は重要度順の箇条書き、現在のOBS設定はJSONのコードブロックで出力する
+    pub fn to_markdown(report: &DiagnosticReport) -> String {
+        let mut md = String::new();
+
+        md.push_str("# OBS配信 診断レポート\n\n");
+        md.push_str(&format!("- 生成日時: {}\n", format_timestamp(report.generated_at)));
+        md.push_str(&format!("- セッションID: `{}`\n", report.session.session_id));
+        md.push_str(&format!("- 配信時間: {}\n", format_duration(report.session.duration_secs)));
+        if let Some(title) = &report.session.title {
+            md.push_str(&format!("- タイトル: {}\n", single_line(title)));
+        }
+        if !report.session.tags.is_empty() {
+            md.push_str(&format!("- タグ: {}\n", report.session.tags.join(", ")));
+        }
+
+        let system = &report.system_info;
+        md.push_str("\n## ハードウェア情報\n\n");
+        let mut hardware_rows = vec![
+            ("OS", system.os.clone()),
+            ("CPU", system.cpu_model.clone()),
+            ("メモリ", format!("{} MB", system.total_memory_mb)),
+            ("GPU", system.gpu_model.clone().unwrap_or_else(|| "-".to_string())),
+            (
+                "GPUドライバー",
+                system.gpu_driver_version.clone().unwrap_or_else(|| "-".to_string()),
+            ),
+            ("電源", power_label(system.power_source, system.battery_percent)),
+            ("ネットワーク", system.network_adapter.display_label().to_string()),
+        ];
+        if !system.monitors.is_empty() {
+            let monitors: Vec<String> = system.monitors.iter().map(DisplayInfo::display_label).collect();
+            hardware_rows.push(("モニター", monitors.join(", ")));
+        }
+        md.push_str(&markdown_table(("項目", "値"), &hardware_rows));
+
+        let performance = &report.performance;
+        md.push_str("\n## パフォーマンス評価\n\n");
+        md.push_str(&markdown_table(
+            ("項目", "スコア"),
+            &[
+                ("総合", format!("{:.1}", performance.overall_score)),
+                ("CPU", format!("{:.1}", performance.cpu_score)),
+                ("GPU", format!("{:.1}", performance.gpu_score)),
+                ("ネットワーク", format!("{:.1}", performance.network_score)),
+                ("安定性", format!("{:.1}", performance.stability_score)),
+            ],
+        ));
+
+        md.push_str("\n## 検出された問題\n\n");
+        if report.problems.is_empty() {
+            md.push_str("問題は検出されませんでした。\n");
+        }
+        for problem in sorted_by_severity(&report.problems) {
+            md.push_str(&format!(
+                "- **[{}]** {}\n",
+                severity_label(problem.severity),
+                single_line(&problem.title)
+            ));
+            md.push_str(&format!("  - {}\n", single_line(&problem.description)));
+            for action in &problem.suggested_actions {
+                md.push_str(&format!("  - 対処: {}\n", single_line(action)));
+            }
+        }
+
+        md.push_str("\n## 推奨設定\n\n");
+        match &report.recommended_settings {
+            Some(recommended) => {
+                let video = &recommended.video;
+                let output = &recommended.output;
+                md.push_str(&markdown_table(
+                    ("項目", "推奨値"),
+                    &[
+                        ("解像度", format!("{}x{}", video.output_width, video.output_height)),
+                        ("FPS", video.fps.to_string()),
+                        ("縮小フィルタ", video.downscale_filter.display_name().to_string()),
+                        ("エンコーダー", output.encoder.clone()),
+                        ("ビットレート", format!("{} kbps", output.bitrate_kbps)),
+                        ("キーフレーム間隔", format!("{} 秒", output.keyframe_interval_secs)),
+                        ("プリセット", output.preset.clone().unwrap_or_else(|| "-".to_string())),
+                        ("レート制御", output.rate_control.clone()),
+                        (
+                            "音声",
+                            format!(
+                                "{} Hz / {} kbps",
+                                recommended.audio.sample_rate, recommended.audio.bitrate_kbps
+                            ),
+                        ),
+                    ],
+                ));
+                if !recommended.reasons.is_empty() {
+                    md.push_str("\n推奨理由:\n\n");
+                    for reason in &recommended.reasons {
+                        md.push_str(&format!("- {}\n", single_line(reason)));
+                    }
+                }
+            }
+            None => md.push_str("OBSに接続されていないため、推奨設定は含まれていません。\n"),
+        }
+
+        md.push_str("\n## 現在のOBS設定\n\n");
+        match report
+            .obs_settings
+            .as_ref()
+            .and_then(|settings| serde_json::to_string_pretty(settings).ok())
+        {
+            Some(json) => md.push_str(&format!("```json\n{json}\n```\n")),
+            None => md.push_str("OBSに接続されていないため、現在の設定は含まれていません。\n"),
+        }
+
+        md.push_str("\n## 推奨事項サマリー\n\n");
+        md.push_str(report.recommendations_summary.trim_end());
+        md.push('\n');
+
+        md
+    }
+
     /// システム情報を取得
     fn get_system_info(&self) -> SystemInfo {
         // TODO: 実際のシステム情報を取得
         // 現在はダミーデータ
+        // 外部コマンドを実行しないよう、直近に取得した電源状態を使う
+        let power = cached_power_status();
         SystemInfo {
             os: std::env::consts::OS.to_string(),
             cpu_model: "Unknown CPU".to_string(),
             total_memory_mb: 16384,
             gpu_model: Some("Unknown GPU".to_string()),
+            gpu_driver_version: crate::monitor::gpu::get_driver_version(),
+            power_source: power.source,
+            battery_percent: power.battery_percent,
+            network_adapter: cached_network_adapter_type(),
+            monitors: cached_display_info(),
         }
     }
 
@@ -273,6 +656,252 @@ impl ReportExporter {
 
         summary
     }
+
+    /// 月次配信レポートを集計
+    ///
+    /// 月をまたぐセッションは月内の時間のみを配信時間・データ使用量に計上し、
+    /// セッション数・平均値には1セッションとして含める。
+    ///
+    /// # Arguments
+    /// * `from` / `to` - 月の開始・終了時刻（`month_bounds` の戻り値）
+    /// * `sessions` - 期間に重なるセッション
+    pub fn aggregate_monthly_report(
+        &self,
+        year: u32,
+        month: u8,
+        from: i64,
+        to: i64,
+        sessions: &[SessionSummary],
+    ) -> MonthlyReport {
+        #[derive(Default)]
+        struct Accumulator {
+            count: u32,
+            score_sum: f64,
+            bitrate_sum: u64,
+            secs: i64,
+        }
+
+        let mut per_platform: HashMap<String, Accumulator> = HashMap::new();
+        let mut total_secs = 0_i64;
+        let mut total_score = 0.0;
+        let mut session_count = 0_u32;
+        let mut total_data_gb = 0.0;
+
+        for session in sessions_in_range(sessions, from, to) {
+            let secs = session.overlap_secs(from, to);
+            let bitrate = session.effective_bitrate();
+            total_secs += secs;
+            total_score += session.quality_score;
+            session_count += 1;
+            // kbps × 秒 → GB
+            total_data_gb += bitrate as f64 * secs as f64 / 8_000_000.0;
+
+            let acc = per_platform.entry(platform_key(session)).or_default();
+            acc.count += 1;
+            acc.score_sum += session.quality_score;
+            acc.bitrate_sum += bitrate;
+            acc.secs += secs;
+        }
+
+        let per_platform = per_platform
+            .into_iter()
+            .map(|(platform, acc)| {
+                let count = f64::from(acc.count.max(1));
+                let stats = PlatformStats {
+                    session_count: acc.count,
+                    avg_quality_score: acc.score_sum / count,
+                    avg_bitrate_kbps: u32::try_from(acc.bitrate_sum / u64::from(acc.count.max(1))).unwrap_or(u32::MAX),
+                    total_hours: acc.secs as f64 / 3600.0,
+                };
+                (platform, stats)
+            })
+            .collect();
+
+        MonthlyReport {
+            year,
+            month,
+            total_hours: total_secs as f64 / 3600.0,
+            per_platform,
+            overall_avg_score: if session_count > 0 { total_score / f64::from(session_count) } else { 0.0 },
+            total_data_gb,
+        }
+    }
+
+    /// 週次配信レポートを集計
+    ///
+    /// # Arguments
+    /// * `week_start` / `week_end` - 週の開始・終了時刻（`week_bounds` の戻り値）
+    /// * `sessions` - 前週の開始から今週の終了までに重なるセッション
+    /// * `alert_counts` - 週内に発生したアラートの集計
+    pub fn aggregate_weekly_report(
+        &self,
+        week_start: i64,
+        week_end: i64,
+        sessions: &[SessionSummary],
+        alert_counts: &[AlertCount],
+    ) -> WeeklyReport {
+        let (session_count, total_hours, avg_health_score) = weekly_totals(sessions, week_start, week_end);
+        let (previous_session_count, previous_total_hours, previous_avg_health_score) =
+            weekly_totals(sessions, week_start - WEEK_SECS, week_start);
+
+        let health_score_change = if session_count > 0 && previous_session_count > 0 {
+            avg_health_score - previous_avg_health_score
+        } else {
+            0.0
+        };
+        let direction = if session_count == 0 || previous_session_count == 0 {
+            TrendDirection::NoData
+        } else if health_score_change > TREND_STABLE_MARGIN {
+            TrendDirection::Improving
+        } else if health_score_change < -TREND_STABLE_MARGIN {
+            TrendDirection::Declining
+        } else {
+            TrendDirection::Stable
+        };
+
+        let worst_session = sessions_in_range(sessions, week_start, week_end)
+            .min_by(|a, b| {
+                a.quality_score
+                    .total_cmp(&b.quality_score)
+                    .then(b.total_dropped_frames.cmp(&a.total_dropped_frames))
+            })
+            .cloned();
+
+        let mut top_problem_categories: Vec<CategoryCount> = Vec::new();
+        for alert in alert_counts {
+            let category = ProblemCategory::from_metric(alert.metric);
+            match top_problem_categories.iter_mut().find(|c| c.category == category) {
+                Some(entry) => entry.count += alert.count,
+                None => top_problem_categories.push(CategoryCount {
+                    category,
+                    count: alert.count,
+                }),
+            }
+        }
+        top_problem_categories.sort_by(|a, b| b.count.cmp(&a.count));
+
+        WeeklyReport {
+            week_start,
+            week_end,
+            session_count,
+            total_hours,
+            avg_health_score,
+            worst_session,
+            top_problem_categories,
+            trend: WeeklyTrend {
+                previous_session_count,
+                previous_total_hours,
+                previous_avg_health_score,
+                hours_change: total_hours - previous_total_hours,
+                health_score_change,
+                direction,
+            },
+        }
+    }
+
+    /// 週次レポートを指定形式の文字列に変換
+    ///
+    /// # Arguments
+    /// * `report` - 週次レポート
+    /// * `format` - 出力形式
+    pub fn export_weekly_report(&self, report: &WeeklyReport, format: ReportFormat) -> Result<String, AppError> {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(report)
+                .map_err(|e| AppError::export_error(&format!("Failed to serialize JSON: {e}"))),
+            ReportFormat::Csv => Ok(Self::weekly_to_csv(report)),
+            ReportFormat::Markdown => Ok(Self::weekly_to_markdown(report)),
+        }
+    }
+
+    /// 週次レポートをCSV形式（section,item,value）に変換
+    pub fn weekly_to_csv(report: &WeeklyReport) -> String {
+        let trend = &report.trend;
+        let mut rows: Vec<(&str, String, String)> = vec![
+            ("week", "start".to_string(), format_timestamp(report.week_start)),
+            ("week", "end".to_string(), format_timestamp(report.week_end)),
+            ("summary", "session_count".to_string(), report.session_count.to_string()),
+            ("summary", "total_hours".to_string(), format!("{:.2}", report.total_hours)),
+            ("summary", "avg_health_score".to_string(), format!("{:.1}", report.avg_health_score)),
+            ("trend", "previous_session_count".to_string(), trend.previous_session_count.to_string()),
+            ("trend", "previous_total_hours".to_string(), format!("{:.2}", trend.previous_total_hours)),
+            ("trend", "hours_change".to_string(), format!("{:+.2}", trend.hours_change)),
+            ("trend", "health_score_change".to_string(), format!("{:+.1}", trend.health_score_change)),
+            ("trend", "direction".to_string(), trend_label(trend.direction).to_string()),
+        ];
+        if let Some(worst) = &report.worst_session {
+            rows.push(("worst_session", "session_id".to_string(), worst.session_id.clone()));
+            rows.push(("worst_session", "quality_score".to_string(), format!("{:.1}", worst.quality_score)));
+        }
+        for category in &report.top_problem_categories {
+            rows.push(("problem_category", category_label(category.category).to_string(), category.count.to_string()));
+        }
+
+        let mut csv = String::from("section,item,value\n");
+        for (section, item, value) in rows {
+            csv.push_str(&format!("{},{},{}\n", section, escape_csv(&item), escape_csv(&value)));
+        }
+        csv
+    }
+
+    /// 週次レポートをMarkdown形式に変換
+    pub fn weekly_to_markdown(report: &WeeklyReport) -> String {
+        let mut md = String::new();
+
+        md.push_str("# 週次配信レポート\n\n");
+        md.push_str(&format!(
+            "- 期間: {} 〜 {}\n",
+            format_timestamp(report.week_start),
+            format_timestamp(report.week_end)
+        ));
+
+        md.push_str("\n## 概要\n\n");
+        if report.session_count == 0 {
+            md.push_str("この週の配信はありません。\n");
+        } else {
+            md.push_str(&markdown_table(
+                ("項目", "値"),
+                &[
+                    ("配信回数", format!("{} 回", report.session_count)),
+                    ("総配信時間", format_hours(report.total_hours)),
+                    ("平均ヘルススコア", format!("{:.1}", report.avg_health_score)),
+                ],
+            ));
+        }
+
+        let trend = &report.trend;
+        md.push_str("\n## 前週との比較\n\n");
+        md.push_str(&markdown_table(
+            ("項目", "値"),
+            &[
+                ("前週の配信回数", format!("{} 回", trend.previous_session_count)),
+                ("前週の総配信時間", format_hours(trend.previous_total_hours)),
+                ("配信時間の増減", format!("{:+.1} 時間", trend.hours_change)),
+                ("ヘルススコアの増減", format!("{:+.1}", trend.health_score_change)),
+                ("傾向", trend_label(trend.direction).to_string()),
+            ],
+        ));
+
+        if let Some(worst) = &report.worst_session {
+            md.push_str("\n## 最も評価の低いセッション\n\n");
+            md.push_str(&format!("- セッションID: `{}`\n", worst.session_id));
+            if let Some(title) = &worst.title {
+                md.push_str(&format!("- タイトル: {}\n", single_line(title)));
+            }
+            md.push_str(&format!("- 開始日時: {}\n", format_timestamp(worst.start_time)));
+            md.push_str(&format!("- 品質スコア: {:.1}\n", worst.quality_score));
+            md.push_str(&format!("- ドロップフレーム: {}\n", worst.total_dropped_frames));
+        }
+
+        md.push_str("\n## 多発した問題\n\n");
+        if report.top_problem_categories.is_empty() {
+            md.push_str("アラートは発生しませんでした。\n");
+        }
+        for category in &report.top_problem_categories {
+            md.push_str(&format!("- {}: {} 件\n", category_label(category.category), category.count));
+        }
+
+        md
+    }
 }
 
 impl Default for ReportExporter {
@@ -281,12 +910,139 @@ impl Default for ReportExporter {
     }
 }
 
+/// 期間に含まれるセッション（期間に重なる、または期間内に開始したもの）
+fn sessions_in_range(sessions: &[SessionSummary], from: i64, to: i64) -> impl Iterator<Item = &SessionSummary> {
+    sessions
+        .iter()
+        .filter(move |session| session.overlap_secs(from, to) > 0 || (from..to).contains(&session.start_time))
+}
+
+/// 期間のセッション数・配信時間（時間）・平均品質スコアを集計
+fn weekly_totals(sessions: &[SessionSummary], from: i64, to: i64) -> (u32, f64, f64) {
+    let (count, secs, score_sum) = sessions_in_range(sessions, from, to).fold(
+        (0_u32, 0_i64, 0.0_f64),
+        |(count, secs, score_sum), session| {
+            (count + 1, secs + session.overlap_secs(from, to), score_sum + session.quality_score)
+        },
+    );
+    let avg_score = if count > 0 { score_sum / f64::from(count) } else { 0.0 };
+    (count, secs as f64 / 3600.0, avg_score)
+}
+
+/// 問題カテゴリーの表示名
+fn category_label(category: ProblemCategory) -> &'static str {
+    match category {
+        ProblemCategory::Encoding => "エンコード",
+        ProblemCategory::Network => "ネットワーク",
+        ProblemCategory::Resource => "リソース不足",
+        ProblemCategory::Settings => "設定",
+    }
+}
+
+/// 傾向の表示名
+fn trend_label(direction: TrendDirection) -> &'static str {
+    match direction {
+        TrendDirection::Improving => "改善",
+        TrendDirection::Stable => "横ばい",
+        TrendDirection::Declining => "悪化",
+        TrendDirection::NoData => "比較データなし",
+    }
+}
+
+/// 時間数を「X時間Y分」形式に変換
+fn format_hours(hours: f64) -> String {
+    format_duration((hours * 3600.0).round() as i64)
+}
+
+/// 月次レポートの集計キー（プラットフォーム未記録は"unknown"）
+fn platform_key(session: &SessionSummary) -> String {
+    session
+        .platform
+        .and_then(|platform| serde_json::to_value(platform).ok())
+        .and_then(|value| value.as_str().map(ToString::to_string))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 重要度の表示名
+fn severity_label(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Critical => "クリティカル",
+        AlertSeverity::Warning => "警告",
+        AlertSeverity::Info => "情報",
+        AlertSeverity::Tips => "ヒント",
+    }
+}
+
+/// 問題を重要度順（Critical → Tips）に並べる（同じ重要度では元の順序を維持）
+fn sorted_by_severity(problems: &[ProblemReport]) -> Vec<&ProblemReport> {
+    let rank = |severity: AlertSeverity| match severity {
+        AlertSeverity::Critical => 0,
+        AlertSeverity::Warning => 1,
+        AlertSeverity::Info => 2,
+        AlertSeverity::Tips => 3,
+    };
+    let mut sorted: Vec<&ProblemReport> = problems.iter().collect();
+    sorted.sort_by_key(|problem| rank(problem.severity));
+    sorted
+}
+
+/// 電源状態の表示名
+fn power_label(source: PowerSource, battery_percent: Option<u8>) -> String {
+    match (source, battery_percent) {
+        (PowerSource::Ac, _) => "AC電源".to_string(),
+        (PowerSource::Battery, Some(percent)) => format!("バッテリー（残量{percent}%）"),
+        (PowerSource::Battery, None) => "バッテリー".to_string(),
+        (PowerSource::Unknown, _) => "不明".to_string(),
+    }
+}
+
+/// UNIX epoch秒をUTCの日時文字列に変換
+fn format_timestamp(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map_or_else(|| timestamp.to_string(), |dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+/// 秒数を「X時間Y分」形式に変換
+fn format_duration(duration_secs: i64) -> String {
+    let secs = duration_secs.max(0);
+    format!("{}時間{}分", secs / 3600, secs % 3600 / 60)
+}
+
+/// 改行を空白に置き換えて1行にする（箇条書き・表のセルが崩れないように）
+fn single_line(text: &str) -> String {
+    text.split(['\r', '\n'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 2列の表をMarkdownで出力
+fn markdown_table(header: (&str, &str), rows: &[(&str, String)]) -> String {
+    let escape = |cell: &str| single_line(cell).replace('|', "\\|");
+    let mut table = format!("| {} | {} |\n| --- | --- |\n", header.0, header.1);
+    for (name, value) in rows {
+        table.push_str(&format!("| {} | {} |\n", escape(name), escape(value)));
+    }
+    table
+}
+
+/// CSVのフィールドをエスケープ
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
     use crate::services::alerts::{AlertSeverity, MetricType};
     use crate::services::analyzer::ProblemCategory;
+    use crate::storage::config::StreamingPlatform;
     use crate::storage::metrics_history::{SystemMetricsSnapshot, ObsStatusSnapshot};
 
     fn create_test_session_summary() -> SessionSummary {
@@ -299,6 +1055,12 @@ mod tests {
             total_dropped_frames: 50,
             peak_bitrate: 6000,
             quality_score: 75.0,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded: 0,
         }
     }
 
@@ -324,6 +1086,7 @@ mod tests {
             session_id: "test".to_string(),
             system: SystemMetricsSnapshot {
                 cpu_usage: 50.0,
+                cpu_frequency_mhz: None,
                 memory_used: 8_000_000_000,
                 memory_total: 16_000_000_000,
                 gpu_usage: Some(60.0),
@@ -348,6 +1111,7 @@ mod tests {
             session_id: "test".to_string(),
             system: SystemMetricsSnapshot {
                 cpu_usage: 50.0,
+                cpu_frequency_mhz: None,
                 memory_used: 8_000_000_000,
                 memory_total: 16_000_000_000,
                 gpu_usage: Some(60.0),
@@ -378,6 +1142,26 @@ mod tests {
         assert_eq!(report.problems.len(), 1);
     }
 
+    #[test]
+    fn test_exports_include_session_metadata() {
+        let exporter = ReportExporter::new();
+        let mut summary = create_test_session_summary();
+        summary.title = Some("wifi night".to_string());
+        summary.tags = vec!["wifi".to_string()];
+        summary.notes = Some("有線に戻すこと".to_string());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&exporter.export_session_json(&summary, &[]).unwrap()).unwrap();
+        assert_eq!(json["session"]["title"], "wifi night");
+        assert_eq!(json["session"]["tags"][0], "wifi");
+        assert_eq!(json["session"]["notes"], "有線に戻すこと");
+
+        let report = exporter.generate_diagnostic_report(&summary, &[]).unwrap();
+        assert_eq!(report.session.title.as_deref(), Some("wifi night"));
+        assert_eq!(report.session.tags, vec!["wifi".to_string()]);
+        assert_eq!(report.session.notes.as_deref(), Some("有線に戻すこと"));
+    }
+
     #[test]
     fn test_performance_evaluation() {
         let exporter = ReportExporter::new();
@@ -424,6 +1208,12 @@ mod tests {
             total_dropped_frames: 0, // ドロップフレームなし
             peak_bitrate: 6000,
             quality_score: 100.0,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded: 0,
         };
 
         let eval = exporter.calculate_performance_evaluation(&summary, &[]);
@@ -443,6 +1233,12 @@ mod tests {
             total_dropped_frames: 1000, // 多くのドロップフレーム
             peak_bitrate: 2000, // 低いビットレート
             quality_score: 20.0,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded: 0,
         };
 
         let eval = exporter.calculate_performance_evaluation(&summary, &[]);
@@ -501,6 +1297,7 @@ mod tests {
                 session_id: "test1".to_string(),
                 system: SystemMetricsSnapshot {
                     cpu_usage: 50.0,
+                    cpu_frequency_mhz: None,
                     memory_used: 8_000_000_000,
                     memory_total: 16_000_000_000,
                     gpu_usage: Some(60.0),
@@ -515,6 +1312,7 @@ mod tests {
                 session_id: "test2".to_string(),
                 system: SystemMetricsSnapshot {
                     cpu_usage: 55.0,
+                    cpu_frequency_mhz: None,
                     memory_used: 9_000_000_000,
                     memory_total: 16_000_000_000,
                     gpu_usage: None,
@@ -580,4 +1378,405 @@ mod tests {
         assert!(report.generated_at > 1_000_000);
         assert_eq!(report.session.duration_secs, 3600);
     }
+
+    /// Markdownゴールデンテスト用の固定レポート
+    fn create_golden_report() -> DiagnosticReport {
+        use crate::services::analyzer::ProblemCategory;
+        use crate::services::optimizer::{
+            DownscaleFilter, RecommendedAudioSettings, RecommendedOutputSettings,
+            RecommendedVideoSettings, ScoreBreakdown,
+        };
+        use crate::storage::config::StreamingProtocol;
+
+        let problem = |severity, title: &str, description: &str, actions: &[&str]| ProblemReport {
+            id: title.to_string(),
+            category: ProblemCategory::Resource,
+            severity,
+            title: title.to_string(),
+            description: description.to_string(),
+            suggested_actions: actions.iter().map(ToString::to_string).collect(),
+            affected_metric: MetricType::CpuUsage,
+            detected_at: 1_700_000_000,
+        };
+
+        DiagnosticReport {
+            generated_at: 1_700_000_000,
+            session: SessionInfo {
+                session_id: "golden_session".to_string(),
+                duration_secs: 5400,
+                started_at: 1_699_994_600,
+                ended_at: 1_700_000_000,
+                title: Some("週末のゲーム配信".to_string()),
+                tags: vec!["game".to_string(), "1080p".to_string()],
+                notes: None,
+            },
+            system_info: SystemInfo {
+                os: "windows".to_string(),
+                cpu_model: "AMD Ryzen 7 5800X".to_string(),
+                total_memory_mb: 32768,
+                gpu_model: Some("NVIDIA GeForce RTX 3060".to_string()),
+                gpu_driver_version: Some("546.33".to_string()),
+                power_source: PowerSource::Battery,
+                battery_percent: Some(45),
+                network_adapter: NetworkAdapterType::Wireless,
+                monitors: Vec::new(),
+            },
+            // 重要度順に並び替えられることを確認するため、順不同で並べる
+            problems: vec![
+                problem(
+                    AlertSeverity::Info,
+                    "GPUドライバーが古いバージョンです",
+                    "ドライバー 546.33 は推奨バージョンより古い可能性があります。",
+                    &["GPUドライバーを更新"],
+                ),
+                problem(
+                    AlertSeverity::Critical,
+                    "フレームドロップが多発しています",
+                    "出力フレームの2.5%がドロップしました。\n回線の帯域不足が疑われます。",
+                    &["ビットレートを4500kbpsに下げる", "有線LANに切り替える"],
+                ),
+                problem(
+                    AlertSeverity::Warning,
+                    "CPU使用率が高い状態です",
+                    "平均CPU使用率が85%を超えています。",
+                    &[],
+                ),
+            ],
+            performance: PerformanceEvaluation {
+                overall_score: 63.8,
+                cpu_score: 45.0,
+                gpu_score: 40.0,
+                network_score: 90.0,
+                stability_score: 80.0,
+            },
+            recommendations_summary: "3個の問題が検出されました（クリティカル: 1, 警告: 1）。\n\n優先対応:\n1. フレームドロップが多発しています\n".to_string(),
+            recommended_settings: Some(RecommendedSettings {
+                video: RecommendedVideoSettings {
+                    output_width: 1920,
+                    output_height: 1080,
+                    fps: 60,
+                    downscale_filter: DownscaleFilter::Bicubic,
+                    canvas_width: None,
+                    canvas_height: None,
+                    aspect: None,
+                },
+                audio: RecommendedAudioSettings {
+                    sample_rate: 48000,
+                    bitrate_kbps: 160,
+                },
+                output: RecommendedOutputSettings {
+                    encoder: "ffmpeg_nvenc".to_string(),
+                    bitrate_kbps: 6000,
+                    keyframe_interval_secs: 2,
+                    preset: Some("p5".to_string()),
+                    rate_control: "CBR".to_string(),
+                    quality_value: None,
+                    recording_format: None,
+                    protocol: StreamingProtocol::Rtmps,
+                    pre_analysis: false,
+                    pa_look_ahead: None,
+                    pa_scene_change_detect: false,
+                    output_mode: crate::obs::ObsOutputMode::Advanced,
+                    nvenc_tuning: None,
+                    hevc_profile: None,
+                },
+                reasons: vec!["NVENCが利用可能なため、GPUエンコードを推奨します".to_string()],
+                overall_score: 80,
+                score_breakdown: ScoreBreakdown::new(30, 10, 30, 10),
+            }),
+            obs_settings: Some(crate::testing::fixtures::standard_obs_settings()),
+        }
+    }
+
+    /// Markdown出力をゴールデンファイルとバイト単位で比較
+    ///
+    /// 出力を意図的に変更した場合は `UPDATE_GOLDEN=1 cargo test` で更新する
+    #[test]
+    fn test_markdown_matches_golden_file() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden/diagnostic_report.md");
+        let actual = ReportExporter::to_markdown(&create_golden_report());
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, &actual).unwrap();
+        }
+
+        let expected = std::fs::read(&path).unwrap();
+        assert_eq!(actual.as_bytes(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_markdown_without_obs_connection() {
+        let mut report = create_golden_report();
+        report.problems.clear();
+        report.recommended_settings = None;
+        report.obs_settings = None;
+
+        let markdown = ReportExporter::to_markdown(&report);
+        assert!(markdown.contains("問題は検出されませんでした。"));
+        assert!(markdown.contains("推奨設定は含まれていません"));
+        assert!(!markdown.contains("```json"));
+    }
+
+    #[test]
+    fn test_markdown_escapes_table_cells() {
+        let mut report = create_golden_report();
+        report.system_info.cpu_model = "CPU | with pipe\nand newline".to_string();
+
+        let markdown = ReportExporter::to_markdown(&report);
+        assert!(markdown.contains("| CPU | CPU \\| with pipe and newline |"));
+    }
+
+    #[test]
+    fn test_markdown_lists_monitors() {
+        let mut report = create_golden_report();
+        report.system_info.monitors = vec![
+            DisplayInfo { width: 2560, height: 1440, refresh_rate_hz: 165, is_primary: true },
+            DisplayInfo { width: 1920, height: 1080, refresh_rate_hz: 60, is_primary: false },
+        ];
+
+        let markdown = ReportExporter::to_markdown(&report);
+        assert!(markdown.contains("| モニター | 2560x1440・165Hz（メイン）, 1920x1080・60Hz |"));
+    }
+
+    #[test]
+    fn test_diagnostic_report_csv() {
+        let csv = ReportExporter::to_csv(&create_golden_report());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "section,item,value");
+        assert!(lines.contains(&"system,cpu_model,AMD Ryzen 7 5800X"));
+        // 問題は重要度順
+        let problems: Vec<&str> = lines.iter().filter(|l| l.starts_with("problem,")).copied().collect();
+        assert_eq!(problems[0], "problem,クリティカル,フレームドロップが多発しています");
+        assert_eq!(problems[2], "problem,情報,GPUドライバーが古いバージョンです");
+    }
+
+    #[test]
+    fn test_export_diagnostic_report_formats() {
+        let exporter = ReportExporter::new();
+        let report = create_golden_report();
+
+        let json = exporter.export_diagnostic_report(&report, ReportFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["session"]["sessionId"], "golden_session");
+
+        let markdown = exporter.export_diagnostic_report(&report, ReportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# OBS配信 診断レポート"));
+        assert_eq!(ReportFormat::Markdown.extension(), "md");
+    }
+
+    /// 2024年1月（UTC）
+    const JAN_2024_START: i64 = 1_704_067_200;
+    const FEB_2024_START: i64 = 1_706_745_600;
+
+    fn monthly_session(
+        id: &str,
+        start_time: i64,
+        end_time: i64,
+        platform: Option<StreamingPlatform>,
+        quality_score: f64,
+        avg_bitrate: u64,
+    ) -> SessionSummary {
+        SessionSummary {
+            session_id: id.to_string(),
+            start_time,
+            end_time,
+            quality_score,
+            platform,
+            avg_bitrate,
+            bytes_uploaded: 0,
+            ..create_test_session_summary()
+        }
+    }
+
+    fn utc() -> chrono::FixedOffset {
+        chrono::FixedOffset::east_opt(0).unwrap()
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        assert_eq!(month_bounds(2024, 1, utc()).unwrap(), (JAN_2024_START, FEB_2024_START));
+        // 12月は翌年1月1日まで
+        assert_eq!(month_bounds(2024, 12, utc()).unwrap(), (1_733_011_200, 1_735_689_600));
+        // JSTでは9時間早く始まる
+        let jst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(month_bounds(2024, 1, jst).unwrap().0, JAN_2024_START - 9 * 3600);
+
+        assert!(month_bounds(2024, 0, utc()).is_err());
+        assert!(month_bounds(2024, 13, utc()).is_err());
+    }
+
+    #[test]
+    fn test_monthly_report_per_platform() {
+        let exporter = ReportExporter::new();
+        let sessions = vec![
+            monthly_session("a", JAN_2024_START + 3600, JAN_2024_START + 3 * 3600, Some(StreamingPlatform::Twitch), 80.0, 6000),
+            monthly_session("b", JAN_2024_START + 86_400, JAN_2024_START + 86_400 + 3600, Some(StreamingPlatform::Twitch), 60.0, 4000),
+            monthly_session("c", JAN_2024_START + 172_800, JAN_2024_START + 172_800 + 3600, Some(StreamingPlatform::YouTube), 90.0, 8000),
+        ];
+
+        let report = exporter.aggregate_monthly_report(2024, 1, JAN_2024_START, FEB_2024_START, &sessions);
+
+        assert_eq!(report.year, 2024);
+        assert_eq!(report.month, 1);
+        assert!((report.total_hours - 4.0).abs() < 1e-9);
+        assert!((report.overall_avg_score - 230.0 / 3.0).abs() < 1e-9);
+
+        let twitch = &report.per_platform["twitch"];
+        assert_eq!(twitch.session_count, 2);
+        assert!((twitch.avg_quality_score - 70.0).abs() < 1e-9);
+        assert_eq!(twitch.avg_bitrate_kbps, 5000);
+        assert!((twitch.total_hours - 3.0).abs() < 1e-9);
+
+        let youtube = &report.per_platform["youTube"];
+        assert_eq!(youtube.session_count, 1);
+        assert_eq!(youtube.avg_bitrate_kbps, 8000);
+
+        // 6000kbps×2h + 4000kbps×1h + 8000kbps×1h = 13.5GB
+        assert!((report.total_data_gb - 13.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_monthly_report_sessions_spanning_month_boundaries() {
+        let exporter = ReportExporter::new();
+        let sessions = vec![
+            // 前月末から月初にまたがる（月内は1時間）
+            monthly_session("start", JAN_2024_START - 3600, JAN_2024_START + 3600, None, 50.0, 8000),
+            // 月末から翌月にまたがる（月内は30分）
+            monthly_session("end", FEB_2024_START - 1800, FEB_2024_START + 7200, None, 70.0, 0),
+            // 前月に終わったセッションは含めない
+            monthly_session("previous", JAN_2024_START - 7200, JAN_2024_START, None, 10.0, 8000),
+        ];
+
+        let report = exporter.aggregate_monthly_report(2024, 1, JAN_2024_START, FEB_2024_START, &sessions);
+
+        assert!((report.total_hours - 1.5).abs() < 1e-9);
+        let unknown = &report.per_platform["unknown"];
+        assert_eq!(unknown.session_count, 2);
+        assert!((report.overall_avg_score - 60.0).abs() < 1e-9);
+        // 平均ビットレート未記録のセッションはピーク（6000kbps）で代用
+        assert_eq!(unknown.avg_bitrate_kbps, 7000);
+        // 8000kbps×1h + 6000kbps×0.5h = 3.6GB + 1.35GB
+        assert!((report.total_data_gb - 4.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_monthly_report_empty_month() {
+        let report = ReportExporter::new().aggregate_monthly_report(2024, 1, JAN_2024_START, FEB_2024_START, &[]);
+        assert_eq!(report.total_hours, 0.0);
+        assert_eq!(report.overall_avg_score, 0.0);
+        assert_eq!(report.total_data_gb, 0.0);
+        assert!(report.per_platform.is_empty());
+    }
+
+    /// 2024-01-08（月曜）
+    const WEEK_START: i64 = JAN_2024_START + 7 * 86_400;
+    const WEEK_END: i64 = WEEK_START + 7 * 86_400;
+
+    #[test]
+    fn test_week_bounds() {
+        // 2024-01-10（水曜）12:00 UTC
+        let now = WEEK_START + 2 * 86_400 + 12 * 3600;
+        assert_eq!(week_bounds(0, now, utc()), (WEEK_START, WEEK_END));
+        assert_eq!(week_bounds(1, now, utc()), (JAN_2024_START, WEEK_START));
+        // 月曜0時ちょうどはその週に含まれる
+        assert_eq!(week_bounds(0, WEEK_START, utc()).0, WEEK_START);
+
+        // JSTの月曜0時は日曜15時（UTC）
+        let jst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(week_bounds(0, WEEK_START - 9 * 3600 + 60, jst).0, WEEK_START - 9 * 3600);
+    }
+
+    #[test]
+    fn test_weekly_report_aggregation_and_trend() {
+        let exporter = ReportExporter::new();
+        let mut worst = monthly_session("worst", WEEK_START + 86_400, WEEK_START + 86_400 + 3600, None, 50.0, 6000);
+        worst.total_dropped_frames = 500;
+        let sessions = vec![
+            // 前週
+            monthly_session("prev", JAN_2024_START + 3600, JAN_2024_START + 2 * 3600, None, 60.0, 6000),
+            // 前週から今週にまたがる（今週は1時間）
+            monthly_session("spanning", WEEK_START - 3600, WEEK_START + 3600, None, 90.0, 6000),
+            worst,
+            monthly_session("good", WEEK_START + 2 * 86_400, WEEK_START + 2 * 86_400 + 7200, None, 88.0, 6000),
+        ];
+        let alerts = vec![
+            AlertCount::new(MetricType::CpuUsage, AlertSeverity::Warning, 2),
+            AlertCount::new(MetricType::FrameDropRate, AlertSeverity::Critical, 3),
+            AlertCount::new(MetricType::GpuUsage, AlertSeverity::Critical, 2),
+        ];
+
+        let report = exporter.aggregate_weekly_report(WEEK_START, WEEK_END, &sessions, &alerts);
+
+        assert_eq!(report.session_count, 3);
+        assert!((report.total_hours - 4.0).abs() < 1e-9);
+        assert!((report.avg_health_score - 76.0).abs() < 1e-9);
+        assert_eq!(report.worst_session.as_ref().unwrap().session_id, "worst");
+
+        // CPU + GPU はリソース不足として合算
+        assert_eq!(
+            report.top_problem_categories,
+            vec![
+                CategoryCount { category: ProblemCategory::Resource, count: 4 },
+                CategoryCount { category: ProblemCategory::Encoding, count: 3 },
+            ]
+        );
+
+        let trend = &report.trend;
+        assert_eq!(trend.previous_session_count, 2);
+        assert!((trend.previous_total_hours - 2.0).abs() < 1e-9);
+        assert!((trend.health_score_change - 1.0).abs() < 1e-9);
+        assert!((trend.hours_change - 2.0).abs() < 1e-9);
+        assert_eq!(trend.direction, TrendDirection::Stable);
+    }
+
+    #[test]
+    fn test_weekly_report_without_sessions() {
+        let exporter = ReportExporter::new();
+        let previous = vec![monthly_session("prev", JAN_2024_START, JAN_2024_START + 3600, None, 80.0, 6000)];
+
+        let report = exporter.aggregate_weekly_report(WEEK_START, WEEK_END, &previous, &[]);
+
+        assert_eq!(report.session_count, 0);
+        assert_eq!(report.total_hours, 0.0);
+        assert_eq!(report.avg_health_score, 0.0);
+        assert!(report.worst_session.is_none());
+        assert!(report.top_problem_categories.is_empty());
+        assert_eq!(report.trend.direction, TrendDirection::NoData);
+        assert_eq!(report.trend.health_score_change, 0.0);
+        assert!((report.trend.hours_change + 1.0).abs() < 1e-9);
+
+        let markdown = exporter.export_weekly_report(&report, ReportFormat::Markdown).unwrap();
+        assert!(markdown.contains("この週の配信はありません。"));
+        assert!(markdown.contains("アラートは発生しませんでした。"));
+        assert!(!markdown.contains("最も評価の低いセッション"));
+    }
+
+    #[test]
+    fn test_weekly_report_exports() {
+        let exporter = ReportExporter::new();
+        let sessions = vec![
+            monthly_session("prev", JAN_2024_START, JAN_2024_START + 3600, None, 60.0, 6000),
+            monthly_session("now", WEEK_START, WEEK_START + 5400, None, 85.0, 6000),
+        ];
+        let alerts = vec![AlertCount::new(MetricType::NetworkBandwidth, AlertSeverity::Warning, 5)];
+        let report = exporter.aggregate_weekly_report(WEEK_START, WEEK_END, &sessions, &alerts);
+        assert_eq!(report.trend.direction, TrendDirection::Improving);
+
+        let markdown = exporter.export_weekly_report(&report, ReportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# 週次配信レポート"));
+        assert!(markdown.contains("- 期間: 2024-01-08 00:00:00 UTC 〜 2024-01-15 00:00:00 UTC"));
+        assert!(markdown.contains("| 総配信時間 | 1時間30分 |"));
+        assert!(markdown.contains("| 傾向 | 改善 |"));
+        assert!(markdown.contains("- ネットワーク: 5 件"));
+
+        let json = exporter.export_weekly_report(&report, ReportFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["trend"]["direction"], "improving");
+        assert_eq!(parsed["topProblemCategories"][0]["category"], "network");
+
+        let csv = exporter.export_weekly_report(&report, ReportFormat::Csv).unwrap();
+        assert!(csv.lines().any(|line| line == "summary,session_count,1"));
+    }
 }
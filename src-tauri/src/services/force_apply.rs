@@ -0,0 +1,203 @@
+// 配信中の強制適用トークン
+//
+// 配信中は設定の適用をブロックしているが、ビットレートの入力ミスなど
+// 配信を続けながら直したいケースのために、2段階の確認を経た強制適用を許可する。
+// UIは `request_force_apply` でトークンを発行し、確認後に適用コマンドへ渡す。
+// トークンは短時間で失効し、1回の適用で消費される。
+
+use crate::error::AppError;
+use crate::services::RecommendedSettings;
+use crate::obs::ObsSettings;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+
+/// 強制適用トークンの有効期間（秒）
+pub const FORCE_TOKEN_TTL_SECS: i64 = 60;
+
+/// 強制適用トークン（`request_force_apply` で発行された値）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ForceToken(pub String);
+
+/// 発行した強制適用トークンの情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceApplyGrant {
+    /// 適用コマンドの `force` に渡すトークン
+    pub token: ForceToken,
+    /// 発行日時（Unixタイムスタンプ）
+    pub issued_at: i64,
+    /// 失効日時（Unixタイムスタンプ）
+    pub expires_at: i64,
+}
+
+/// 強制適用トークンの発行・消費を管理
+///
+/// 同時に有効なトークンは1つだけで、再発行すると以前のトークンは無効になる
+#[derive(Debug, Default)]
+pub struct ForceTokenStore {
+    /// 未使用のトークン
+    issued: Mutex<Option<ForceApplyGrant>>,
+}
+
+impl ForceTokenStore {
+    /// 新しいストアを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// トークンを発行
+    ///
+    /// # Arguments
+    /// * `now` - 現在時刻（Unixタイムスタンプ）
+    pub fn issue(&self, now: i64) -> ForceApplyGrant {
+        let grant = ForceApplyGrant {
+            token: ForceToken(uuid::Uuid::new_v4().to_string()),
+            issued_at: now,
+            expires_at: now + FORCE_TOKEN_TTL_SECS,
+        };
+        *self.issued.lock().unwrap_or_else(PoisonError::into_inner) = Some(grant.clone());
+        grant
+    }
+
+    /// トークンを検証して消費
+    ///
+    /// 一致しないトークン・失効したトークンは `VALIDATION_FAILED` エラー。
+    /// 検証の成否にかかわらず、一致したトークンは再利用できない。
+    ///
+    /// # Arguments
+    /// * `token` - 適用コマンドに渡されたトークン
+    /// * `now` - 現在時刻（Unixタイムスタンプ）
+    pub fn consume(&self, token: &ForceToken, now: i64) -> Result<(), AppError> {
+        let mut issued = self.issued.lock().unwrap_or_else(PoisonError::into_inner);
+        match issued.as_ref() {
+            Some(grant) if grant.token == *token => {
+                let expired = now > grant.expires_at;
+                *issued = None;
+                if expired {
+                    Err(AppError::validation_failed(
+                        "強制適用の確認がタイムアウトしました。もう一度確認してください",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err(AppError::validation_failed("強制適用のトークンが無効です")),
+        }
+    }
+}
+
+/// グローバルな強制適用トークンストア
+static FORCE_TOKEN_STORE: Lazy<ForceTokenStore> = Lazy::new(ForceTokenStore::new);
+
+/// グローバルな強制適用トークンストアを取得
+pub fn get_force_token_store() -> &'static ForceTokenStore {
+    &FORCE_TOKEN_STORE
+}
+
+/// 配信を止めずに適用できる項目だけに推奨設定を絞り込む
+///
+/// 解像度・FPS・縮小フィルタ・エンコーダーの変更は出力の再起動が必要なため
+/// 現在の値を維持する。ビットレート・キーフレーム間隔などは変更できる。
+///
+/// # Returns
+/// 適用を見送った項目の説明
+pub fn restrict_to_live_safe(
+    recommendations: &mut RecommendedSettings,
+    current: &ObsSettings,
+) -> Vec<String> {
+    let mut skipped = Vec::new();
+    let video = &mut recommendations.video;
+
+    if video.output_width != current.video.output_width
+        || video.output_height != current.video.output_height
+        || video.fps != current.video.fps_numerator
+    {
+        skipped.push(format!(
+            "解像度・FPS（{}x{} {}fps）は配信中に変更できないため適用を見送りました",
+            video.output_width, video.output_height, video.fps
+        ));
+        video.output_width = current.video.output_width;
+        video.output_height = current.video.output_height;
+        video.fps = current.video.fps_numerator;
+    }
+
+    let output = &mut recommendations.output;
+    if output.encoder != current.output.encoder {
+        skipped.push(format!(
+            "エンコーダー（{}）は配信中に変更できないため適用を見送りました",
+            output.encoder
+        ));
+        output.encoder.clone_from(&current.output.encoder);
+    }
+
+    skipped
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::error::ERROR_CODE_VALIDATION_FAILED;
+
+    #[test]
+    fn test_force_token_single_use() {
+        let store = ForceTokenStore::new();
+        let grant = store.issue(1_000);
+        assert_eq!(grant.expires_at, 1_000 + FORCE_TOKEN_TTL_SECS);
+
+        assert!(store.consume(&grant.token, 1_010).is_ok());
+        let error = store.consume(&grant.token, 1_011).unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_VALIDATION_FAILED);
+    }
+
+    #[test]
+    fn test_force_token_expired_or_unknown() {
+        let store = ForceTokenStore::new();
+        let grant = store.issue(1_000);
+        assert!(store.consume(&ForceToken("unknown".to_string()), 1_001).is_err());
+
+        let error = store.consume(&grant.token, 1_000 + FORCE_TOKEN_TTL_SECS + 1).unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_VALIDATION_FAILED);
+        // 失効したトークンも消費済みになる
+        assert!(store.consume(&grant.token, 1_001).is_err());
+    }
+
+    #[test]
+    fn test_reissue_invalidates_previous_token() {
+        let store = ForceTokenStore::new();
+        let first = store.issue(1_000);
+        let second = store.issue(1_001);
+        assert!(store.consume(&first.token, 1_002).is_err());
+        assert!(store.consume(&second.token, 1_002).is_ok());
+    }
+
+    #[test]
+    fn test_restrict_to_live_safe_keeps_output_settings() {
+        use crate::testing::{standard_obs_settings, HardwareInfoBuilder};
+        use crate::services::RecommendationEngine;
+        use crate::storage::config::{StreamingPlatform, StreamingStyle};
+
+        let current = standard_obs_settings();
+        let mut recommendations = RecommendationEngine::calculate_recommendations(
+            &HardwareInfoBuilder::new().gpu("NVIDIA GeForce RTX 4070").build(),
+            Some(&current),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        recommendations.video.output_width = 1280;
+        recommendations.video.output_height = 720;
+        recommendations.output.encoder = "obs_x264".to_string();
+        recommendations.output.bitrate_kbps = 5_000;
+
+        let skipped = restrict_to_live_safe(&mut recommendations, &current);
+
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(recommendations.video.output_width, current.video.output_width);
+        assert_eq!(recommendations.output.encoder, current.output.encoder);
+        // ビットレートは配信中でも適用する
+        assert_eq!(recommendations.output.bitrate_kbps, 5_000);
+    }
+}
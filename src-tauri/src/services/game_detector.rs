@@ -0,0 +1,149 @@
+// ゲーム検出サービス
+//
+// 実行中のプロセスを定期的に確認し、既知のゲームが起動したら
+// フロントエンドに通知する（「ゲームモードに切り替えますか？」の提案用）
+
+use crate::error::AppError;
+use crate::events::{emit_event, TauriEvent};
+use crate::monitor::process::detect_running_games_from;
+use crate::storage::config::load_config;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+/// ゲーム検出イベント名
+pub const GAME_DETECTED_EVENT: &str = "game:detected";
+
+/// ポーリング間隔
+pub const GAME_DETECTION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// ゲーム検出イベントのペイロード
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDetected {
+    /// 検出されたプロセス名
+    pub process_name: String,
+}
+
+/// ゲーム検出器
+///
+/// 前回のポーリング結果を保持し、新しく起動したゲームのみを通知する
+#[derive(Debug, Default)]
+pub struct GameDetector {
+    /// 前回検出されたゲーム
+    running: HashSet<String>,
+}
+
+impl GameDetector {
+    /// 新しい検出器を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 今回の検出結果を反映し、新たに起動したゲームを返す
+    ///
+    /// 終了したゲームは記録から除外されるため、再起動時は再度通知される
+    pub fn update(&mut self, detected: Vec<String>) -> Vec<GameDetected> {
+        let current: HashSet<String> = detected.into_iter().collect();
+
+        let mut started: Vec<GameDetected> = current
+            .difference(&self.running)
+            .map(|name| GameDetected { process_name: name.clone() })
+            .collect();
+        started.sort_by(|a, b| a.process_name.cmp(&b.process_name));
+
+        self.running = current;
+        started
+    }
+
+    /// 1回分のポーリングを実行
+    ///
+    /// 設定のゲーム一覧でプロセスを確認し、新たに起動したゲームを返す
+    pub fn poll(&mut self) -> Result<Vec<GameDetected>, AppError> {
+        let known_games = load_config()
+            .map(|config| config.known_game_executables)
+            .unwrap_or_default();
+        let detected = detect_running_games_from(&known_games)?;
+        Ok(self.update(detected))
+    }
+}
+
+/// 実行中の既知ゲームを取得
+pub fn get_detected_games() -> Result<Vec<String>, AppError> {
+    let known_games = load_config()?.known_game_executables;
+    detect_running_games_from(&known_games)
+}
+
+/// ゲーム検出のバックグラウンドタスクを開始
+///
+/// 5秒ごとにプロセスを確認し、新たに起動したゲームごとに
+/// `game:detected` イベントを発行する
+pub fn start_game_detector<R: Runtime>(app_handle: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut detector = GameDetector::new();
+        let mut interval = tokio::time::interval(GAME_DETECTION_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let started = match detector.poll() {
+                Ok(started) => started,
+                Err(e) => {
+                    tracing::warn!(target: "game_detector", error = %e, "ゲーム検出に失敗");
+                    continue;
+                }
+            };
+
+            for game in started {
+                tracing::info!(target: "game_detector", process = %game.process_name, "ゲームの起動を検出");
+                if let Err(e) = emit_event(&app_handle, TauriEvent::GameDetected(game)) {
+                    tracing::warn!(target: "game_detector", error = %e, "ゲーム検出イベントの発行に失敗");
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_update_reports_only_new_games() {
+        let mut detector = GameDetector::new();
+
+        let started = detector.update(names(&["cs2.exe"]));
+        assert_eq!(started, vec![GameDetected { process_name: "cs2.exe".to_string() }]);
+
+        // 同じゲームが起動し続けている間は通知しない
+        assert!(detector.update(names(&["cs2.exe"])).is_empty());
+
+        // 新しいゲームのみ通知
+        let started = detector.update(names(&["cs2.exe", "Minecraft.exe"]));
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].process_name, "Minecraft.exe");
+    }
+
+    #[test]
+    fn test_update_renotifies_after_restart() {
+        let mut detector = GameDetector::new();
+
+        detector.update(names(&["cs2.exe"]));
+        assert!(detector.update(Vec::new()).is_empty());
+
+        // 終了後に再起動した場合は再度通知
+        assert_eq!(detector.update(names(&["cs2.exe"])).len(), 1);
+    }
+
+    #[test]
+    fn test_game_detected_serialization() {
+        let payload = GameDetected { process_name: "cs2.exe".to_string() };
+        let json = serde_json::to_string(&payload).unwrap_or_default();
+        assert_eq!(json, r#"{"processName":"cs2.exe"}"#);
+    }
+}
@@ -7,6 +7,7 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// GPU世代の分類
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,8 +27,10 @@ pub enum GpuGeneration {
     AmdVcn3,
     /// AMD RX 7000シリーズ（VCN 4.0）
     AmdVcn4,
-    /// Intel Arc GPU
+    /// Intel Arc GPU（Alchemist / Aシリーズ）
     IntelArc,
+    /// Intel Arc Battlemage（Xe2 / Bシリーズ）
+    IntelBattlemage,
     /// Intel QuickSync（内蔵GPU）
     IntelQuickSync,
     /// 世代不明のGPU
@@ -36,6 +39,32 @@ pub enum GpuGeneration {
     None,
 }
 
+impl GpuGeneration {
+    /// 世代（アーキテクチャ）の表示ラベルを取得
+    pub fn display_label(&self) -> &'static str {
+        match self {
+            Self::NvidiaPascal => "Pascal",
+            Self::NvidiaTuring => "Turing",
+            Self::NvidiaAmpere => "Ampere",
+            Self::NvidiaAda => "Ada",
+            Self::NvidiaBlackwell => "Blackwell",
+            Self::AmdVcn3 => "RDNA2 (VCN 3.0)",
+            Self::AmdVcn4 => "RDNA3 (VCN 4.0)",
+            Self::IntelArc => "Intel Arc",
+            Self::IntelBattlemage => "Battlemage (Xe2)",
+            Self::IntelQuickSync => "QuickSync",
+            Self::Unknown => "不明",
+            Self::None => "GPUなし",
+        }
+    }
+}
+
+impl fmt::Display for GpuGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_label())
+    }
+}
+
 /// CPUのティア分類
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -70,6 +99,25 @@ impl CpuTier {
             Self::Entry => "エントリー",
         }
     }
+
+    /// x264エンコードベンチマークの結果からティアを判定
+    ///
+    /// # Arguments
+    /// * `fps` - 320x240・100フレームのエンコード速度（フレーム/秒）
+    pub fn from_benchmark(fps: u32) -> Self {
+        match fps {
+            0..=99 => Self::Entry,
+            100..=299 => Self::Middle,
+            300..=600 => Self::UpperMiddle,
+            _ => Self::HighEnd,
+        }
+    }
+}
+
+impl fmt::Display for CpuTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_label())
+    }
 }
 
 /// メモリ容量のティア分類
@@ -138,6 +186,39 @@ pub enum GpuGrade {
     Unknown,
 }
 
+impl GpuGrade {
+    /// グレードの表示ラベルを取得
+    pub fn display_label(&self) -> &'static str {
+        match self {
+            Self::Flagship => "フラグシップ",
+            Self::HighEnd => "ハイエンド",
+            Self::UpperMid => "アッパーミドル",
+            Self::Mid => "ミドル",
+            Self::Entry => "エントリー",
+            Self::Unknown => "不明",
+        }
+    }
+
+    /// 1段階下のグレード（エントリー・不明はそのまま）
+    ///
+    /// ノートPC向けGPUは同じ型番でも電力制限により性能が下がるため、判定後に1段階下げる
+    pub const fn one_step_lower(self) -> Self {
+        match self {
+            Self::Flagship => Self::HighEnd,
+            Self::HighEnd => Self::UpperMid,
+            Self::UpperMid => Self::Mid,
+            Self::Mid | Self::Entry => Self::Entry,
+            Self::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for GpuGrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_label())
+    }
+}
+
 /// 統合ティア（世代×グレードの総合評価）
 ///
 /// 世代の新しさとグレードを組み合わせた最終的な性能ティア
@@ -155,7 +236,7 @@ pub enum EffectiveTier {
     TierC,
     /// 下位（RTX 3050, GTX 1660, RX 6600等）
     TierD,
-    /// 最低（GTX 1050, RX 6500, 内蔵GPU等）
+    /// エントリー（GTX 1050, RX 6500, 内蔵GPU等）
     TierE,
 }
 
@@ -172,6 +253,18 @@ impl EffectiveTier {
         }
     }
 
+    /// ティアの記号を取得（"S"〜"E"）
+    pub fn letter(&self) -> &'static str {
+        match self {
+            Self::TierS => "S",
+            Self::TierA => "A",
+            Self::TierB => "B",
+            Self::TierC => "C",
+            Self::TierD => "D",
+            Self::TierE => "E",
+        }
+    }
+
     /// ティアの表示ラベルを取得
     pub fn display_label(&self) -> &'static str {
         match self {
@@ -180,9 +273,39 @@ impl EffectiveTier {
             Self::TierB => "中上位",
             Self::TierC => "中位",
             Self::TierD => "下位",
-            Self::TierE => "最低",
+            Self::TierE => "エントリー",
+        }
+    }
+
+    /// ティアの説明（UIの詳細表示用）
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::TierS => "RTX 4080/4090相当。最高品質の配信が可能です",
+            Self::TierA => "RTX 4070・RTX 3080相当。1080p60の高画質配信が可能です",
+            Self::TierB => "RTX 4060・RTX 3070相当。1080p60の配信が可能です",
+            Self::TierC => "RTX 3060・RTX 2070相当。1080p配信が可能ですが、重いゲームでは設定を控えめにしてください",
+            Self::TierD => "RTX 3050・GTX 1660相当。720p60程度の配信を推奨します",
+            Self::TierE => "GTX 1050・内蔵GPU相当。720p30程度の軽い設定での配信を推奨します",
         }
     }
+
+    /// UIのバッジ表示に使用する色（CSSの16進カラーコード）
+    pub fn colour_hex(&self) -> &'static str {
+        match self {
+            Self::TierS => "#FFD700",
+            Self::TierA => "#C0C0C0",
+            Self::TierB => "#CD7F32",
+            Self::TierC => "#4A90D9",
+            Self::TierD => "#7CB342",
+            Self::TierE => "#9E9E9E",
+        }
+    }
+}
+
+impl fmt::Display for EffectiveTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.display_label(), self.letter())
+    }
 }
 
 // 後方互換性のためのエイリアス（テストで使用）
@@ -251,14 +374,10 @@ const GPU_PATTERNS: &[GpuDetectionPattern] = &[
         exclude_keywords: &[],
         generation: GpuGeneration::NvidiaTuring,
     },
-    // NVIDIA Pascal (GTX 10シリーズ)
+    // NVIDIA Pascal (GTX 10シリーズ、Ti付きを含む)
+    // GTX 16・RTX 20の型番（1660, 2080等）は先のTuringパターンで判定済み
     GpuDetectionPattern {
         keywords: &["gtx 10", "gtx10", "1080", "1070", "1060", "1050"],
-        exclude_keywords: &["ti"],  // 1050 Tiなど正確な検出のため除外
-        generation: GpuGeneration::NvidiaPascal,
-    },
-    GpuDetectionPattern {
-        keywords: &["gtx 1080 ti", "gtx 1070 ti", "gtx 1050 ti"],
         exclude_keywords: &[],
         generation: GpuGeneration::NvidiaPascal,
     },
@@ -274,6 +393,12 @@ const GPU_PATTERNS: &[GpuDetectionPattern] = &[
         exclude_keywords: &[],
         generation: GpuGeneration::AmdVcn3,
     },
+    // Intel Arc Battlemage（"arc"より先に判定する）
+    GpuDetectionPattern {
+        keywords: &["arc b", "b580", "b570", "b770"],
+        exclude_keywords: &[],
+        generation: GpuGeneration::IntelBattlemage,
+    },
     // Intel Arc
     GpuDetectionPattern {
         keywords: &["arc a", "arc"],
@@ -298,7 +423,7 @@ const GPU_CAPABILITIES: &[GpuEncoderCapability] = &[
         hevc: true,
         av1: true,
         b_frames: true,
-        quality_equivalent: "slow", // Adaと同等以上
+        quality_equivalent: "veryslow", // 第9世代NVENC（Adaより同ビットレートで高画質）
         recommended_preset: "p7",
     },
     GpuEncoderCapability {
@@ -364,6 +489,15 @@ const GPU_CAPABILITIES: &[GpuEncoderCapability] = &[
         quality_equivalent: "medium",
         recommended_preset: "balanced",
     },
+    GpuEncoderCapability {
+        generation: GpuGeneration::IntelBattlemage,
+        h264: true,
+        hevc: true,
+        av1: true,
+        b_frames: true,
+        quality_equivalent: "medium-slow",
+        recommended_preset: "balanced",
+    },
     GpuEncoderCapability {
         generation: GpuGeneration::IntelQuickSync,
         h264: true,
@@ -419,14 +553,124 @@ pub fn get_encoder_capability(generation: GpuGeneration) -> Option<&'static GpuE
         .find(|cap| cap.generation == generation)
 }
 
-/// CPUコア数からティアを判定
+/// GPU世代ごとの推奨ドライバーバージョン
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriverRequirement {
+    /// 世代
+    pub generation: GpuGeneration,
+    /// 推奨される最小ドライバーバージョン
+    pub min_recommended: &'static str,
+    /// AV1エンコードに必要な最小ドライバーバージョン（AV1非対応の世代はNone）
+    pub min_av1: Option<&'static str>,
+}
+
+/// GPU世代別の推奨ドライバーテーブル
+///
+/// OBSのNVENC実装はNVENC SDKのバージョンに依存するため、NVIDIAのみ管理する。
+/// AMD/Intelはドライバーのバージョン体系が異なり比較できないため対象外
+const DRIVER_REQUIREMENTS: &[DriverRequirement] = &[
+    DriverRequirement {
+        generation: GpuGeneration::NvidiaBlackwell,
+        min_recommended: "572.16", // RTX 50シリーズの初回リリースドライバー
+        min_av1: Some("572.16"),
+    },
+    DriverRequirement {
+        generation: GpuGeneration::NvidiaAda,
+        min_recommended: "522.25",
+        min_av1: Some("522.25"), // AV1 NVENC（NVENC SDK 12.0）
+    },
+    DriverRequirement {
+        generation: GpuGeneration::NvidiaAmpere,
+        min_recommended: "456.71", // NVENC SDK 11.0
+        min_av1: None,
+    },
+    DriverRequirement {
+        generation: GpuGeneration::NvidiaTuring,
+        min_recommended: "456.71",
+        min_av1: None,
+    },
+    DriverRequirement {
+        generation: GpuGeneration::NvidiaPascal,
+        min_recommended: "456.71",
+        min_av1: None,
+    },
+];
+
+/// GPU世代から推奨ドライバー情報を取得
+///
+/// # Returns
+/// 推奨ドライバー情報（テーブルにない世代はNone）
+pub fn get_driver_requirement(generation: GpuGeneration) -> Option<&'static DriverRequirement> {
+    DRIVER_REQUIREMENTS
+        .iter()
+        .find(|req| req.generation == generation)
+}
+
+/// ドライバーバージョンを数値の列に分解（例: "535.154.05" → [535, 154, 5]）
+fn parse_driver_version(version: &str) -> Option<Vec<u32>> {
+    version
+        .trim()
+        .split('.')
+        .map(|part| part.trim().parse().ok())
+        .collect()
+}
+
+/// ドライバーバージョンが最小バージョン以上か
+///
+/// # Returns
+/// - `Some(true/false)` - 比較できた場合
+/// - `None` - どちらかのバージョンを解析できない場合
+pub fn driver_version_at_least(version: &str, minimum: &str) -> Option<bool> {
+    let version = parse_driver_version(version)?;
+    let minimum = parse_driver_version(minimum)?;
+    Some(version >= minimum)
+}
+
+/// ドライバーが推奨バージョンより古いか
+///
+/// バージョン不明・比較不能の場合は古いとみなさない
+///
+/// # Arguments
+/// * `generation` - GPU世代
+/// * `driver_version` - 検出されたドライバーバージョン
+pub fn is_driver_outdated(generation: GpuGeneration, driver_version: Option<&str>) -> bool {
+    let (Some(requirement), Some(version)) = (get_driver_requirement(generation), driver_version) else {
+        return false;
+    };
+    driver_version_at_least(version, requirement.min_recommended) == Some(false)
+}
+
+/// ドライバーがAV1エンコードの要件を満たすか
+///
+/// AV1の要件がない世代、バージョン不明・比較不能の場合は満たすとみなす
+/// （AV1対応自体は `GpuEncoderCapability::av1` で判定する）
+///
+/// # Arguments
+/// * `generation` - GPU世代
+/// * `driver_version` - 検出されたドライバーバージョン
+pub fn driver_supports_av1(generation: GpuGeneration, driver_version: Option<&str>) -> bool {
+    let minimum = get_driver_requirement(generation).and_then(|req| req.min_av1);
+    let (Some(minimum), Some(version)) = (minimum, driver_version) else {
+        return true;
+    };
+    driver_version_at_least(version, minimum) != Some(false)
+}
+
+/// CPUティアを判定
+///
+/// ベンチマーク結果がある場合はそれを優先し、ない場合はコア数から判定する。
+/// コア数だけでは世代差を区別できない（8コアのRyzen 9 5900XとCore i7-4770が同じティアになる）
 ///
 /// # Arguments
 /// * `cpu_cores` - CPUコア数
+/// * `benchmark_result` - x264エンコードベンチマークの結果（フレーム/秒）
 ///
 /// # Returns
 /// CPUティア
-pub fn determine_cpu_tier(cpu_cores: usize) -> CpuTier {
+pub fn determine_cpu_tier(cpu_cores: usize, benchmark_result: Option<u32>) -> CpuTier {
+    if let Some(fps) = benchmark_result {
+        return CpuTier::from_benchmark(fps);
+    }
     match cpu_cores {
         0..=3 => CpuTier::Entry,
         4..=7 => CpuTier::Middle,
@@ -444,11 +688,24 @@ struct GpuGradePattern {
 }
 
 /// GPUグレード判定パターン定義テーブル
-/// 順序が重要：より具体的なパターンを先に配置
+///
+/// キーワードは `normalize_gpu_name` で正規化した名前（"3080Ti" → "3080 ti"）と照合する。
+/// 順序が重要：Ti/Super付きなど、より具体的なパターンを先に配置
 const GPU_GRADE_PATTERNS: &[GpuGradePattern] = &[
+    // === Ti/Super・派生モデルでグレードが変わるもの ===
+    // 3080 Ti・2080 Ti・1080 Tiは上位モデルと同じダイでフラグシップ相当
+    GpuGradePattern {
+        keywords: &["3080 ti", "2080 ti", "1080 ti"],
+        grade: GpuGrade::Flagship,
+    },
+    // 4070 Ti Superは4080と同じダイ、7900 GREは7800 XT相当
+    GpuGradePattern {
+        keywords: &["4070 ti super", "7900 gre"],
+        grade: GpuGrade::HighEnd,
+    },
     // === NVIDIA Flagship (xx90, Titan) ===
     GpuGradePattern {
-        keywords: &["5090", "4090", "3090", "2080 ti", "1080 ti", "titan"],
+        keywords: &["5090", "4090", "3090", "titan"],
         grade: GpuGrade::Flagship,
     },
     // === NVIDIA HighEnd (xx80) ===
@@ -471,9 +728,9 @@ const GPU_GRADE_PATTERNS: &[GpuGradePattern] = &[
         keywords: &["5050", "4050", "3050", "1650", "1050"],
         grade: GpuGrade::Entry,
     },
-    // === AMD Flagship (x900) ===
+    // === AMD Flagship (x900, x950) ===
     GpuGradePattern {
-        keywords: &["7900", "6900"],
+        keywords: &["7900", "6950", "6900"],
         grade: GpuGrade::Flagship,
     },
     // === AMD HighEnd (x800) ===
@@ -481,14 +738,14 @@ const GPU_GRADE_PATTERNS: &[GpuGradePattern] = &[
         keywords: &["7800", "6800"],
         grade: GpuGrade::HighEnd,
     },
-    // === AMD UpperMid (x700) ===
+    // === AMD UpperMid (x700, x750) ===
     GpuGradePattern {
-        keywords: &["7700", "6700"],
+        keywords: &["7700", "6750", "6700"],
         grade: GpuGrade::UpperMid,
     },
-    // === AMD Mid (x600) ===
+    // === AMD Mid (x600, x650) ===
     GpuGradePattern {
-        keywords: &["7600", "6600"],
+        keywords: &["7650", "7600", "6650", "6600"],
         grade: GpuGrade::Mid,
     },
     // === AMD Entry (x500) ===
@@ -516,30 +773,74 @@ const GPU_GRADE_PATTERNS: &[GpuGradePattern] = &[
         keywords: &["a380", "a310"],
         grade: GpuGrade::Entry,
     },
+    // === Intel Arc Battlemage HighEnd ===
+    GpuGradePattern {
+        keywords: &["b770"],
+        grade: GpuGrade::HighEnd,
+    },
+    // === Intel Arc Battlemage Mid ===
+    GpuGradePattern {
+        keywords: &["b580", "b570"],
+        grade: GpuGrade::Mid,
+    },
 ];
 
+/// ノートPC向けGPUと判定するキーワード（正規化後）
+const LAPTOP_GPU_KEYWORDS: &[&str] = &["laptop", "mobile", "notebook", "max q"];
+
+/// GPU名を判定用に正規化
+///
+/// 小文字にして英数字以外を空白に置き換え、型番と接尾辞の間に空白を入れる
+/// （例: "RTX 3080Ti" → "rtx 3080 ti"、"RX 6800M" → "rx 6800 m"、"Max-Q" → "max q"）
+fn normalize_gpu_name(gpu_name: &str) -> String {
+    let mut normalized = String::with_capacity(gpu_name.len() + 4);
+    let mut previous: Option<char> = None;
+
+    for c in gpu_name.chars().flat_map(char::to_lowercase) {
+        let c = if c.is_ascii_alphanumeric() { c } else { ' ' };
+        if c.is_ascii_alphabetic() && previous.is_some_and(|p| p.is_ascii_digit()) {
+            normalized.push(' ');
+        }
+        if !(c == ' ' && previous == Some(' ')) {
+            normalized.push(c);
+        }
+        previous = Some(c);
+    }
+
+    normalized.trim().to_string()
+}
+
+/// GPU名からノートPC向けGPUかを判定
+///
+/// NVIDIAは "Laptop GPU" / "Max-Q"、AMDは型番末尾の "M"（例: RX 6800M）で判定する
+pub fn is_laptop_gpu(gpu_name: &str) -> bool {
+    let normalized = normalize_gpu_name(gpu_name);
+    LAPTOP_GPU_KEYWORDS.iter().any(|kw| normalized.contains(kw))
+        || (normalized.contains("radeon") && normalized.split(' ').any(|word| word == "m"))
+}
+
 /// GPU名から性能グレードを判定
 ///
+/// Ti/Super付きのモデルは専用のパターンで判定し、ノートPC向けGPUは1段階下げる
+///
 /// # Arguments
 /// * `gpu_name` - GPU名称（例: "NVIDIA GeForce RTX 3060"）
 ///
 /// # Returns
 /// 判定されたGPUグレード
 pub fn detect_gpu_grade(gpu_name: &str) -> GpuGrade {
-    let gpu_name_lower = gpu_name.to_lowercase();
+    let normalized = normalize_gpu_name(gpu_name);
 
-    for pattern in GPU_GRADE_PATTERNS {
-        let has_keyword = pattern
-            .keywords
-            .iter()
-            .any(|kw| gpu_name_lower.contains(kw));
+    let grade = GPU_GRADE_PATTERNS
+        .iter()
+        .find(|pattern| pattern.keywords.iter().any(|kw| normalized.contains(kw)))
+        .map_or(GpuGrade::Unknown, |pattern| pattern.grade);
 
-        if has_keyword {
-            return pattern.grade;
-        }
+    if is_laptop_gpu(gpu_name) {
+        grade.one_step_lower()
+    } else {
+        grade
     }
-
-    GpuGrade::Unknown
 }
 
 /// 後方互換性のためのエイリアス（テストで使用）
@@ -555,7 +856,7 @@ pub fn detect_gpu_tier(gpu_name: &str) -> GpuTier {
 /// # 統合ティアマトリクス
 /// ```text
 ///              | Flagship | HighEnd | UpperMid | Mid  | Entry |
-/// Blackwell(50)|    S     |    S    |    S     |  A   |   B   |
+/// Blackwell(50)|    S     |    S    |    S     |  S   |   B   |
 /// Ada (40)     |    S     |    S    |    A     |  A   |   B   |
 /// Ampere (30)  |    A     |    A    |    B     |  B   |   C   |
 /// Turing (20)  |    B     |    B    |    C     |  C   |   D   |
@@ -569,8 +870,8 @@ pub fn calculate_effective_tier(generation: GpuGeneration, grade: GpuGrade) -> E
     // マトリクス通りの直接マッピング
     match (generation, grade) {
         // === NVIDIA Blackwell (RTX 50シリーズ) ===
-        (GpuGeneration::NvidiaBlackwell, GpuGrade::Flagship | GpuGrade::HighEnd | GpuGrade::UpperMid) => EffectiveTier::TierS,
-        (GpuGeneration::NvidiaBlackwell, GpuGrade::Mid) => EffectiveTier::TierA,
+        // 全グレードで同じ第9世代NVENCを搭載するため、xx60までTierSとする
+        (GpuGeneration::NvidiaBlackwell, GpuGrade::Flagship | GpuGrade::HighEnd | GpuGrade::UpperMid | GpuGrade::Mid) => EffectiveTier::TierS,
         (GpuGeneration::NvidiaBlackwell, GpuGrade::Entry) => EffectiveTier::TierB,
 
         // === NVIDIA Ada (RTX 40シリーズ) ===
@@ -609,6 +910,11 @@ pub fn calculate_effective_tier(generation: GpuGeneration, grade: GpuGrade) -> E
         (GpuGeneration::IntelArc, GpuGrade::Mid) => EffectiveTier::TierC,
         (GpuGeneration::IntelArc, GpuGrade::Entry | GpuGrade::Flagship) => EffectiveTier::TierD,
 
+        // === Intel Arc Battlemage (Bシリーズ) ===
+        (GpuGeneration::IntelBattlemage, GpuGrade::Flagship | GpuGrade::HighEnd) => EffectiveTier::TierA,
+        (GpuGeneration::IntelBattlemage, GpuGrade::UpperMid | GpuGrade::Mid) => EffectiveTier::TierB,
+        (GpuGeneration::IntelBattlemage, GpuGrade::Entry) => EffectiveTier::TierC,
+
         // === Intel QuickSync (内蔵GPU) ===
         (GpuGeneration::IntelQuickSync, GpuGrade::Mid | GpuGrade::UpperMid | GpuGrade::HighEnd | GpuGrade::Flagship) => EffectiveTier::TierD,
         (GpuGeneration::IntelQuickSync, GpuGrade::Entry) => EffectiveTier::TierE,
@@ -702,7 +1008,7 @@ mod tests {
         assert!(cap.hevc);
         assert!(cap.av1, "Blackwell must support AV1");
         assert!(cap.b_frames);
-        assert_eq!(cap.quality_equivalent, "slow");
+        assert_eq!(cap.quality_equivalent, "veryslow");
         assert_eq!(cap.recommended_preset, "p7");
     }
 
@@ -723,7 +1029,7 @@ mod tests {
         );
         assert_eq!(
             calculate_effective_tier(GpuGeneration::NvidiaBlackwell, GpuGrade::Mid),
-            EffectiveTier::TierA
+            EffectiveTier::TierS
         );
         assert_eq!(
             calculate_effective_tier(GpuGeneration::NvidiaBlackwell, GpuGrade::Entry),
@@ -791,6 +1097,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_intel_battlemage() {
+        assert_eq!(
+            detect_gpu_generation("Intel Arc B580"),
+            GpuGeneration::IntelBattlemage
+        );
+        assert_eq!(
+            detect_gpu_generation("Intel Arc B770"),
+            GpuGeneration::IntelBattlemage
+        );
+        // Aシリーズは従来どおりIntel Arc
+        assert_eq!(
+            detect_gpu_generation("Intel Arc A580"),
+            GpuGeneration::IntelArc
+        );
+    }
+
+    #[test]
+    fn test_intel_battlemage_grade_and_tier() {
+        assert_eq!(detect_gpu_grade("Intel Arc B580"), GpuGrade::Mid);
+        assert_eq!(
+            calculate_effective_tier(GpuGeneration::IntelBattlemage, GpuGrade::Mid),
+            EffectiveTier::TierB
+        );
+
+        assert_eq!(detect_gpu_grade("Intel Arc B770"), GpuGrade::HighEnd);
+        assert_eq!(
+            calculate_effective_tier(GpuGeneration::IntelBattlemage, GpuGrade::HighEnd),
+            EffectiveTier::TierA
+        );
+    }
+
+    #[test]
+    fn test_intel_battlemage_capability() {
+        let capability = get_encoder_capability(GpuGeneration::IntelBattlemage).unwrap();
+        assert!(capability.h264 && capability.hevc && capability.av1 && capability.b_frames);
+        assert_eq!(capability.quality_equivalent, "medium-slow");
+        assert_eq!(capability.recommended_preset, "balanced");
+    }
+
     #[test]
     fn test_detect_intel_quicksync() {
         assert_eq!(
@@ -811,6 +1157,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blackwell_detection_to_effective_tier() {
+        // RTX 5090（フラッグシップ）・RTX 5060（ミドル）はどちらもTierS
+        for (name, grade) in [
+            ("NVIDIA GeForce RTX 5090", GpuGrade::Flagship),
+            ("NVIDIA GeForce RTX 5060", GpuGrade::Mid),
+        ] {
+            let generation = detect_gpu_generation(name);
+            assert_eq!(generation, GpuGeneration::NvidiaBlackwell, "{name}");
+            assert_eq!(detect_gpu_grade(name), grade, "{name}");
+            assert_eq!(calculate_effective_tier(generation, grade), EffectiveTier::TierS, "{name}");
+        }
+    }
+
     #[test]
     fn test_get_encoder_capability_ada() {
         let cap = get_encoder_capability(GpuGeneration::NvidiaAda);
@@ -848,12 +1208,31 @@ mod tests {
 
     #[test]
     fn test_determine_cpu_tier() {
-        assert_eq!(determine_cpu_tier(2), CpuTier::Entry);
-        assert_eq!(determine_cpu_tier(4), CpuTier::Middle);
-        assert_eq!(determine_cpu_tier(6), CpuTier::Middle);
-        assert_eq!(determine_cpu_tier(8), CpuTier::UpperMiddle);
-        assert_eq!(determine_cpu_tier(12), CpuTier::HighEnd);
-        assert_eq!(determine_cpu_tier(16), CpuTier::HighEnd);
+        assert_eq!(determine_cpu_tier(2, None), CpuTier::Entry);
+        assert_eq!(determine_cpu_tier(4, None), CpuTier::Middle);
+        assert_eq!(determine_cpu_tier(6, None), CpuTier::Middle);
+        assert_eq!(determine_cpu_tier(8, None), CpuTier::UpperMiddle);
+        assert_eq!(determine_cpu_tier(12, None), CpuTier::HighEnd);
+        assert_eq!(determine_cpu_tier(16, None), CpuTier::HighEnd);
+    }
+
+    #[test]
+    fn test_cpu_tier_from_benchmark() {
+        assert_eq!(CpuTier::from_benchmark(0), CpuTier::Entry);
+        assert_eq!(CpuTier::from_benchmark(99), CpuTier::Entry);
+        assert_eq!(CpuTier::from_benchmark(100), CpuTier::Middle);
+        assert_eq!(CpuTier::from_benchmark(299), CpuTier::Middle);
+        assert_eq!(CpuTier::from_benchmark(300), CpuTier::UpperMiddle);
+        assert_eq!(CpuTier::from_benchmark(600), CpuTier::UpperMiddle);
+        assert_eq!(CpuTier::from_benchmark(601), CpuTier::HighEnd);
+    }
+
+    #[test]
+    fn test_determine_cpu_tier_prefers_benchmark() {
+        // 同じ8コアでも計測結果で世代差を区別する
+        assert_eq!(determine_cpu_tier(8, Some(80)), CpuTier::Entry);
+        assert_eq!(determine_cpu_tier(8, Some(700)), CpuTier::HighEnd);
+        assert_eq!(determine_cpu_tier(8, None), CpuTier::UpperMiddle);
     }
 
     #[test]
@@ -880,6 +1259,7 @@ mod tests {
             GpuGeneration::AmdVcn3,
             GpuGeneration::AmdVcn4,
             GpuGeneration::IntelArc,
+            GpuGeneration::IntelBattlemage,
             GpuGeneration::IntelQuickSync,
         ] {
             assert!(
@@ -960,6 +1340,73 @@ mod tests {
         assert_eq!(detect_gpu_tier("Intel Arc A310"), GpuTier::Entry);
     }
 
+    #[test]
+    fn test_normalize_gpu_name() {
+        assert_eq!(normalize_gpu_name("NVIDIA GeForce RTX 3080Ti"), "nvidia geforce rtx 3080 ti");
+        assert_eq!(normalize_gpu_name("AMD Radeon(TM) RX 6800M"), "amd radeon tm rx 6800 m");
+        assert_eq!(normalize_gpu_name("RTX 2060 with Max-Q Design"), "rtx 2060 with max q design");
+        // 英字の後の数字は分割しない
+        assert_eq!(normalize_gpu_name("Intel Arc A770"), "intel arc a770");
+    }
+
+    #[test]
+    fn test_is_laptop_gpu() {
+        assert!(is_laptop_gpu("NVIDIA GeForce RTX 4060 Laptop GPU"));
+        assert!(is_laptop_gpu("NVIDIA GeForce RTX 2080 with Max-Q Design"));
+        assert!(is_laptop_gpu("AMD Radeon RX 6800M"));
+        assert!(!is_laptop_gpu("NVIDIA GeForce RTX 4060"));
+        assert!(!is_laptop_gpu("AMD Radeon RX 7900 XTX"));
+    }
+
+    /// 実際のGPU名での世代・グレード・統合ティアの回帰テスト
+    #[test]
+    fn test_gpu_detection_regression_matrix() {
+        use GpuGeneration as G;
+        use GpuGrade as R;
+        use EffectiveTier as T;
+
+        let cases = [
+            ("NVIDIA GeForce RTX 5090", G::NvidiaBlackwell, R::Flagship, T::TierS),
+            ("NVIDIA GeForce RTX 5070 Ti", G::NvidiaBlackwell, R::UpperMid, T::TierS),
+            ("NVIDIA GeForce RTX 5060 Laptop GPU", G::NvidiaBlackwell, R::Entry, T::TierB),
+            ("NVIDIA GeForce RTX 4090", G::NvidiaAda, R::Flagship, T::TierS),
+            ("NVIDIA GeForce RTX 4090 Laptop GPU", G::NvidiaAda, R::HighEnd, T::TierS),
+            ("NVIDIA GeForce RTX 4080 SUPER", G::NvidiaAda, R::HighEnd, T::TierS),
+            ("NVIDIA GeForce RTX 4070 Ti SUPER", G::NvidiaAda, R::HighEnd, T::TierS),
+            ("NVIDIA GeForce RTX 4070 Ti", G::NvidiaAda, R::UpperMid, T::TierA),
+            ("NVIDIA GeForce RTX 4070 SUPER", G::NvidiaAda, R::UpperMid, T::TierA),
+            ("NVIDIA GeForce RTX 4060 Ti", G::NvidiaAda, R::Mid, T::TierA),
+            ("NVIDIA GeForce RTX 4060 Laptop GPU", G::NvidiaAda, R::Entry, T::TierB),
+            ("NVIDIA GeForce RTX 3090 Ti", G::NvidiaAmpere, R::Flagship, T::TierA),
+            ("NVIDIA GeForce RTX 3080 Ti", G::NvidiaAmpere, R::Flagship, T::TierA),
+            ("NVIDIA GeForce RTX 3080", G::NvidiaAmpere, R::HighEnd, T::TierA),
+            ("NVIDIA GeForce RTX 3070 Ti Laptop GPU", G::NvidiaAmpere, R::Mid, T::TierB),
+            ("NVIDIA GeForce RTX 3060", G::NvidiaAmpere, R::Mid, T::TierB),
+            ("NVIDIA GeForce RTX 3050 Ti Laptop GPU", G::NvidiaAmpere, R::Entry, T::TierC),
+            ("NVIDIA GeForce RTX 2080 Ti", G::NvidiaTuring, R::Flagship, T::TierB),
+            ("NVIDIA GeForce RTX 2070 SUPER", G::NvidiaTuring, R::UpperMid, T::TierC),
+            ("NVIDIA GeForce RTX 2060 with Max-Q Design", G::NvidiaTuring, R::Entry, T::TierD),
+            ("NVIDIA GeForce GTX 1660 SUPER", G::NvidiaTuring, R::Mid, T::TierC),
+            ("NVIDIA GeForce GTX 1650 Ti", G::NvidiaTuring, R::Entry, T::TierD),
+            ("NVIDIA GeForce GTX 1080 Ti", G::NvidiaPascal, R::Flagship, T::TierC),
+            ("NVIDIA GeForce GTX 1080Ti", G::NvidiaPascal, R::Flagship, T::TierC),
+            ("NVIDIA GeForce GTX 1070 Ti", G::NvidiaPascal, R::UpperMid, T::TierD),
+            ("NVIDIA GeForce GTX 1050 Ti", G::NvidiaPascal, R::Entry, T::TierE),
+            ("AMD Radeon RX 7900 XTX", G::AmdVcn4, R::Flagship, T::TierA),
+            ("AMD Radeon RX 7900 GRE", G::AmdVcn4, R::HighEnd, T::TierA),
+            ("AMD Radeon RX 6800M", G::AmdVcn3, R::UpperMid, T::TierC),
+            ("AMD Radeon RX 6750 XT", G::AmdVcn3, R::UpperMid, T::TierC),
+            ("Intel(R) Arc(TM) A770 Graphics", G::IntelArc, R::HighEnd, T::TierA),
+            ("Intel Arc B580", G::IntelBattlemage, R::Mid, T::TierB),
+        ];
+
+        for (name, generation, grade, tier) in cases {
+            assert_eq!(detect_gpu_generation(name), generation, "{name}");
+            assert_eq!(detect_gpu_grade(name), grade, "{name}");
+            assert_eq!(calculate_effective_tier(generation, grade), tier, "{name}");
+        }
+    }
+
     #[test]
     fn test_detect_gpu_tier_unknown() {
         assert_eq!(detect_gpu_tier("Intel UHD Graphics 770"), GpuTier::Unknown);
@@ -1188,4 +1635,85 @@ mod tests {
         assert_eq!(EffectiveTier::TierD.score(), 2);
         assert_eq!(EffectiveTier::TierE.score(), 1);
     }
+
+    #[test]
+    fn test_driver_version_comparison() {
+        assert_eq!(driver_version_at_least("551.86", "522.25"), Some(true));
+        assert_eq!(driver_version_at_least("522.25", "522.25"), Some(true));
+        assert_eq!(driver_version_at_least("516.94", "522.25"), Some(false));
+        // Linuxの3要素形式
+        assert_eq!(driver_version_at_least("535.154.05", "522.25"), Some(true));
+        assert_eq!(driver_version_at_least("unknown", "522.25"), None);
+    }
+
+    #[test]
+    fn test_is_driver_outdated() {
+        assert!(is_driver_outdated(GpuGeneration::NvidiaAda, Some("516.94")));
+        assert!(!is_driver_outdated(GpuGeneration::NvidiaAda, Some("551.86")));
+        assert!(is_driver_outdated(GpuGeneration::NvidiaAmpere, Some("452.06")));
+
+        // バージョン不明・テーブル外の世代は警告しない
+        assert!(!is_driver_outdated(GpuGeneration::NvidiaAda, None));
+        assert!(!is_driver_outdated(GpuGeneration::NvidiaAda, Some("unknown")));
+        assert!(!is_driver_outdated(GpuGeneration::AmdVcn4, Some("1.0")));
+    }
+
+    #[test]
+    fn test_driver_supports_av1() {
+        assert!(!driver_supports_av1(GpuGeneration::NvidiaAda, Some("516.94")));
+        assert!(driver_supports_av1(GpuGeneration::NvidiaAda, Some("522.25")));
+        assert!(!driver_supports_av1(GpuGeneration::NvidiaBlackwell, Some("566.36")));
+        assert!(driver_supports_av1(GpuGeneration::NvidiaAda, None));
+        assert!(driver_supports_av1(GpuGeneration::IntelArc, Some("31.0.101.5445")));
+    }
+
+    #[test]
+    fn test_effective_tier_display() {
+        assert_eq!(EffectiveTier::TierS.to_string(), "最高性能 (S)");
+        assert_eq!(EffectiveTier::TierA.to_string(), "高性能 (A)");
+        assert_eq!(EffectiveTier::TierB.to_string(), "中上位 (B)");
+        assert_eq!(EffectiveTier::TierC.to_string(), "中位 (C)");
+        assert_eq!(EffectiveTier::TierD.to_string(), "下位 (D)");
+        assert_eq!(EffectiveTier::TierE.to_string(), "エントリー (E)");
+    }
+
+    #[test]
+    fn test_effective_tier_description_and_colour() {
+        let tiers = [
+            EffectiveTier::TierS,
+            EffectiveTier::TierA,
+            EffectiveTier::TierB,
+            EffectiveTier::TierC,
+            EffectiveTier::TierD,
+            EffectiveTier::TierE,
+        ];
+        assert_eq!(EffectiveTier::TierS.description(), "RTX 4080/4090相当。最高品質の配信が可能です");
+        assert_eq!(EffectiveTier::TierS.colour_hex(), "#FFD700");
+        assert_eq!(EffectiveTier::TierA.colour_hex(), "#C0C0C0");
+
+        for tier in tiers {
+            assert!(!tier.description().is_empty());
+
+            let colour = tier.colour_hex();
+            assert_eq!(colour.len(), 7, "{colour}");
+            assert!(colour.starts_with('#'), "{colour}");
+            assert!(colour[1..].chars().all(|c| c.is_ascii_hexdigit()), "{colour}");
+        }
+    }
+
+    #[test]
+    fn test_hardware_classification_display() {
+        assert_eq!(GpuGeneration::NvidiaAda.to_string(), "Ada");
+        assert_eq!(GpuGeneration::AmdVcn4.to_string(), "RDNA3 (VCN 4.0)");
+        assert_eq!(GpuGeneration::Unknown.to_string(), "不明");
+        assert_eq!(GpuGeneration::None.to_string(), "GPUなし");
+
+        assert_eq!(CpuTier::HighEnd.to_string(), "ハイエンド");
+        assert_eq!(CpuTier::UpperMiddle.to_string(), "アッパーミドル");
+        assert_eq!(CpuTier::Entry.to_string(), "エントリー");
+
+        assert_eq!(GpuGrade::Flagship.to_string(), "フラグシップ");
+        assert_eq!(GpuGrade::UpperMid.to_string(), "アッパーミドル");
+        assert_eq!(GpuGrade::Unknown.to_string(), "不明");
+    }
 }
@@ -0,0 +1,90 @@
+// メディアソースの再生終了時の自動アクション
+//
+// 「イントロ動画の再生が終わったら配信を開始する」「アウトロが終わったら終了画面に切り替える」のような
+// メディアソースごとの自動アクション（`AppConfig.media_autoactions`）を、
+// OBSの `MediaInputPlaybackEnded` イベントを受けて実行する
+
+use crate::error::AppError;
+use crate::services::obs::obs_service;
+use crate::storage::config::{load_config, MediaAutoAction};
+
+/// メディアソースに設定された自動アクションを検索
+///
+/// `MediaAutoAction::None` は設定なしとして扱う
+pub fn find_media_autoaction<'a>(
+    autoactions: &'a [(String, MediaAutoAction)],
+    source_name: &str,
+) -> Option<&'a MediaAutoAction> {
+    autoactions
+        .iter()
+        .find(|(name, _)| name == source_name)
+        .map(|(_, action)| action)
+        .filter(|action| **action != MediaAutoAction::None)
+}
+
+/// 再生が終了したメディアソースの自動アクションを実行
+///
+/// OBSへのリクエストのみを行う。状態変化のイベント発行・アラートの抑制は呼び出し側で行うこと
+///
+/// # Arguments
+/// * `source_name` - 再生が終了したメディアソース名
+///
+/// # Returns
+/// 実行したアクション（自動アクションが設定されていない場合はNone）
+pub async fn run_media_autoaction(source_name: &str) -> Result<Option<MediaAutoAction>, AppError> {
+    let config = load_config()?;
+    let Some(action) = find_media_autoaction(&config.media_autoactions, source_name).cloned() else {
+        return Ok(None);
+    };
+
+    let service = obs_service();
+    match &action {
+        MediaAutoAction::StartStreaming => service.start_streaming().await?,
+        MediaAutoAction::SwitchScene(scene_name) => service.set_current_scene(scene_name).await?,
+        MediaAutoAction::StartRecording => service.start_recording().await?,
+        MediaAutoAction::None => {}
+    }
+    Ok(Some(action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn autoactions() -> Vec<(String, MediaAutoAction)> {
+        vec![
+            ("イントロ".to_string(), MediaAutoAction::StartStreaming),
+            ("アウトロ".to_string(), MediaAutoAction::SwitchScene("終了画面".to_string())),
+            ("カウントダウン".to_string(), MediaAutoAction::StartRecording),
+            ("BGM".to_string(), MediaAutoAction::None),
+        ]
+    }
+
+    #[test]
+    fn test_find_media_autoaction() {
+        let autoactions = autoactions();
+
+        assert_eq!(
+            find_media_autoaction(&autoactions, "イントロ"),
+            Some(&MediaAutoAction::StartStreaming)
+        );
+        assert_eq!(
+            find_media_autoaction(&autoactions, "アウトロ"),
+            Some(&MediaAutoAction::SwitchScene("終了画面".to_string()))
+        );
+        assert_eq!(
+            find_media_autoaction(&autoactions, "カウントダウン"),
+            Some(&MediaAutoAction::StartRecording)
+        );
+    }
+
+    #[test]
+    fn test_find_media_autoaction_ignores_none_and_unknown_sources() {
+        let autoactions = autoactions();
+
+        assert_eq!(find_media_autoaction(&autoactions, "BGM"), None);
+        assert_eq!(find_media_autoaction(&autoactions, "未設定の動画"), None);
+        // ソース名は完全一致で比較する
+        assert_eq!(find_media_autoaction(&autoactions, "イントロ "), None);
+    }
+}
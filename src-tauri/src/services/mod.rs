@@ -25,8 +25,47 @@ pub mod analyzer;
 pub mod exporter;
 pub mod gpu_detection;
 pub mod encoder_selector;
+pub mod encoder_fallback;
 pub mod system_capability;
 pub mod static_settings;
+pub mod benchmark;
+pub mod game_detector;
+pub mod audio_monitor;
+pub mod audio_audit;
+pub mod platform_api;
+pub mod aspect_ratio;
+pub mod request_context;
+pub mod obs_file_backup;
+pub mod record_encoder;
+pub mod startup_digest;
+pub mod debug_recorder;
+pub mod stats;
+pub mod telemetry;
+pub mod profile_diff;
+pub mod detected_hardware;
+pub mod operation_lock;
+pub mod upgrade_advisor;
+pub mod audio_devices;
+pub mod schema;
+pub mod obs_log;
+pub mod dual_pc;
+pub mod source_frame_rate;
+pub mod post_stream_advisor;
+pub mod force_apply;
+pub mod data_usage;
+pub mod recommendation_history;
+pub mod reason_formatter;
+pub mod scene_checklist;
+pub mod monitoring_offset;
+pub mod monitor_capture;
+pub mod audio_privacy;
+pub mod automation;
+pub mod performance_stats;
+pub mod shortcuts;
+pub mod streaming_overhead;
+pub mod observer_server;
+pub mod media_autoaction;
+pub mod stream_session;
 
 // 公開エクスポート
 // 将来的な拡張や外部クレートからの利用を想定した再エクスポート
@@ -35,11 +74,13 @@ pub use obs::obs_service;
 #[allow(unused_imports)]
 pub use system::system_monitor_service;
 #[allow(unused_imports)]
-pub use optimizer::{RecommendationEngine, HardwareInfo, RecommendedSettings, RecommendedOutputSettings};
+pub use optimizer::{RecommendationEngine, HardwareInfo, OutputTarget, RecommendedSettings, RecommendedOutputSettings, ScoreBreakdown, DownscaleFilter, RecordingRecommendations, RecordingContainer, RateControlMode, RecommendedMultitrack, MultitrackRendition};
 #[allow(unused_imports)]
-pub use alerts::{AlertEngine, Alert, AlertSeverity, MetricType, initialize_alert_engine, get_alert_engine};
+pub use alerts::{AlertEngine, Alert, AlertSeverity, MetricType, AlertNotification, initialize_alert_engine, get_alert_engine};
 #[allow(unused_imports)]
-pub use streaming_mode::{StreamingModeService, SettingsLockGuard, get_streaming_mode_service};
+pub use operation_lock::{OperationLock, OperationGuard, OperationKind, CurrentOperation, get_operation_lock};
+#[allow(unused_imports)]
+pub use streaming_mode::{StreamingModeService, SettingsLockGuard, ensure_no_blocking_outputs, get_streaming_mode_service};
 #[allow(unused_imports)]
 pub use analyzer::{ProblemAnalyzer, ProblemReport, ProblemCategory};
 #[allow(unused_imports)]
@@ -49,6 +90,68 @@ pub use gpu_detection::{GpuGeneration, CpuTier, MemoryTier, EffectiveTier, detec
 #[allow(unused_imports)]
 pub use encoder_selector::{RecommendedEncoder, EncoderSelectionContext, EncoderSelector};
 #[allow(unused_imports)]
+pub use encoder_fallback::{EncoderFallback, EncoderCodecClass, probe_available_encoders, next_fallback_encoder};
+#[allow(unused_imports)]
 pub use system_capability::{SystemCapability, OverallTier, BottleneckFactor};
 #[allow(unused_imports)]
 pub use static_settings::{StaticSettings, StaticSettingReason, RateControl, ColorFormat, ColorSpace, ColorRange, H264Profile};
+#[allow(unused_imports)]
+pub use startup_digest::{StartupDigest, EnvironmentChange, EnvironmentChangeKind, ChangeSignificance};
+#[allow(unused_imports)]
+pub use profile_diff::{ProfileDiff, SettingChange, ChangeType, compute_profile_diff};
+#[allow(unused_imports)]
+pub use detected_hardware::{DetectedHardware, EncoderCapabilityFlags, clear_detected_hardware_cache, get_or_detect_hardware};
+#[allow(unused_imports)]
+pub use upgrade_advisor::{UpgradeAdvice, UpgradeComponent, UpgradeImpact, advise_upgrades};
+#[allow(unused_imports)]
+pub use audio_devices::{apply_audio_inputs, capture_audio_inputs, collect_microphone_device_names};
+#[allow(unused_imports)]
+pub use obs_log::{ObsLogAnalysis, ObsLogEvent, ObsLogEventKind, analyze_obs_log_file, parse_obs_log};
+#[allow(unused_imports)]
+pub use dual_pc::{collect_capture_sources, is_capture_source, is_likely_dual_pc_streaming};
+#[allow(unused_imports)]
+pub use post_stream_advisor::{PostStreamReport, QualityTimeline, QualityEntry, build_post_stream_report, synthesize_recommendations};
+#[allow(unused_imports)]
+pub use force_apply::{ForceToken, ForceApplyGrant, ForceTokenStore, get_force_token_store, restrict_to_live_safe};
+#[allow(unused_imports)]
+pub use data_usage::{DataUsageSummary, SessionDataUsage, load_data_usage_summary, record_upload_sample};
+#[allow(unused_imports)]
+pub use recommendation_history::{RecommendationDiff, diff_recommendations, hardware_hash, record_recommendation};
+#[allow(unused_imports)]
+pub use reason_formatter::{ReasonFormatter, JapaneseReasonFormatter, EnglishReasonFormatter, formatter_for};
+#[allow(unused_imports)]
+pub use scene_checklist::{SceneChecklistRule, SceneComposition, analyze_scene_checklist, collect_scene_composition};
+#[allow(unused_imports)]
+pub use monitoring_offset::{get_monitoring_device, needs_offset_calibration, read_monitoring_offset, write_monitoring_offset};
+#[allow(unused_imports)]
+pub use monitor_capture::{MonitorCaptureAdvice, advise_monitor_capture};
+#[allow(unused_imports)]
+pub use audio_privacy::{disable_all_monitoring, is_privacy_mode_active, restore_monitoring};
+#[allow(unused_imports)]
+pub use automation::{AutomationEvaluator, AutomationExecution, evaluate_automation_rules, get_execution_log};
+#[allow(unused_imports)]
+pub use performance_stats::{AppPerformanceStats, PerformanceEntry, PerformanceKind, get_app_performance_stats, measure_command, measure_stage};
+#[allow(unused_imports)]
+pub use shortcuts::{Accelerator, ShortcutAction, ShortcutConflict, find_shortcut_conflicts, read_obs_hotkeys};
+#[allow(unused_imports)]
+pub use audio_audit::{AudioAuditIssue, AudioAuditResult, AudioSourceStatus, audit_audio_sources, find_audio_issues};
+#[allow(unused_imports)]
+pub use platform_api::{PlatformAccount, PlatformErrorKind, StreamInfo, StreamPlatformApi, TwitchApi, YouTubeApi};
+#[allow(unused_imports)]
+pub use aspect_ratio::{AspectAdvice, AspectHandling, compute_aspect_advice, is_standard_aspect, is_ultrawide};
+#[allow(unused_imports)]
+pub use request_context::{RequestContext, in_request_span};
+#[allow(unused_imports)]
+pub use obs_file_backup::{ObsFileBackup, ObsFileRestoreResult, backup_current_obs_files, check_restore_safety};
+#[allow(unused_imports)]
+pub use streaming_overhead::{OverheadDelta, StreamingOverhead, compute_streaming_overhead, latest_streaming_overhead, record_streaming_overhead};
+#[allow(unused_imports)]
+pub use observer_server::{ObserverMetrics, ObserverServerInfo, observer_status, record_observer_metrics, start_observer, stop_observer};
+#[allow(unused_imports)]
+pub use media_autoaction::{find_media_autoaction, run_media_autoaction};
+#[allow(unused_imports)]
+pub use source_frame_rate::{FrameRateMismatch, VideoSourceInfo, VideoSourceRole, collect_video_sources, detect_frame_rate_mismatch};
+#[allow(unused_imports)]
+pub use record_encoder::{simple_recording_quality, write_current_record_encoder_quality, write_record_encoder_quality};
+#[allow(unused_imports)]
+pub use stream_session::{begin_stream_session, finish_stream_session, record_session_metrics};
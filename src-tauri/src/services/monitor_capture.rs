@@ -0,0 +1,158 @@
+// マルチモニター環境の画面キャプチャ推奨
+//
+// メイン1440p・サブ1080pのような構成では、1440pのモニターをキャプチャして1080pに縮小すると
+// 非整数倍の縮小で文字がぼやける。キャンバス（基本）解像度と各モニターの解像度を比較し、
+// 出力解像度と同じモニターを直接キャプチャするか、キャンバスをキャプチャするモニターに
+// 合わせるよう推奨する。
+//
+// OBSのモニターキャプチャの設定からはどのモニターを取り込んでいるか確実に判定できないため、
+// キャンバスと解像度が一致するモニターをキャプチャしているものとみなす。
+
+use crate::monitor::display::{primary_display, DisplayInfo};
+
+/// 画面キャプチャの推奨
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorCaptureAdvice {
+    /// 出力解像度と同じ解像度の別モニターを直接キャプチャする（キャンバスもそのモニターに合わせる）
+    CaptureOtherMonitor {
+        /// 現在キャプチャしているとみなしたモニター
+        captured: DisplayInfo,
+        /// キャプチャを推奨するモニター
+        target: DisplayInfo,
+    },
+    /// キャンバスをキャプチャするモニター（メインモニター）の解像度に合わせる
+    MatchCanvas {
+        /// キャンバスを合わせるモニター
+        monitor: DisplayInfo,
+    },
+}
+
+/// キャンバスから出力解像度への縮小が整数倍（または縮小なし）か
+fn is_integer_downscale(canvas: (u32, u32), output: (u32, u32)) -> bool {
+    let (canvas_width, canvas_height) = canvas;
+    let (output_width, output_height) = output;
+    if output_width == 0 || output_height == 0 {
+        return false;
+    }
+    canvas_width % output_width == 0
+        && canvas_height % output_height == 0
+        && canvas_width / output_width == canvas_height / output_height
+}
+
+/// マルチモニター環境での画面キャプチャの推奨を判定
+///
+/// - モニターが1台以下: 推奨なし
+/// - キャンバスがいずれかのモニターと一致し、出力解像度への縮小が非整数倍で、
+///   出力解像度と同じ解像度の別モニターがある場合: そのモニターを直接キャプチャ
+/// - キャンバスがどのモニターとも一致しない場合: キャンバスをメインモニターに合わせる
+///
+/// # Arguments
+/// * `canvas` - 現在のキャンバス（基本）解像度（幅, 高さ）
+/// * `output` - 出力解像度（幅, 高さ）
+/// * `monitors` - 接続されているモニター
+pub fn advise_monitor_capture(
+    canvas: (u32, u32),
+    output: (u32, u32),
+    monitors: &[DisplayInfo],
+) -> Option<MonitorCaptureAdvice> {
+    if monitors.len() < 2 {
+        return None;
+    }
+
+    let Some(captured) = monitors.iter().find(|m| m.has_resolution(canvas.0, canvas.1)) else {
+        return primary_display(monitors)
+            .map(|monitor| MonitorCaptureAdvice::MatchCanvas { monitor: *monitor });
+    };
+
+    if is_integer_downscale(canvas, output) {
+        return None;
+    }
+
+    monitors
+        .iter()
+        .find(|m| !std::ptr::eq(*m, captured) && m.has_resolution(output.0, output.1))
+        .map(|target| MonitorCaptureAdvice::CaptureOtherMonitor {
+            captured: *captured,
+            target: *target,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(width: u32, height: u32, refresh_rate_hz: u32, is_primary: bool) -> DisplayInfo {
+        DisplayInfo { width, height, refresh_rate_hz, is_primary }
+    }
+
+    /// メイン1440p165・サブ1080p60
+    fn qhd_with_fhd_secondary() -> Vec<DisplayInfo> {
+        vec![monitor(2560, 1440, 165, true), monitor(1920, 1080, 60, false)]
+    }
+
+    #[test]
+    fn test_single_monitor_has_no_advice() {
+        let monitors = [monitor(2560, 1440, 165, true)];
+        assert_eq!(advise_monitor_capture((2560, 1440), (1920, 1080), &monitors), None);
+        assert_eq!(advise_monitor_capture((1600, 900), (1280, 720), &monitors), None);
+        assert_eq!(advise_monitor_capture((1920, 1080), (1920, 1080), &[]), None);
+    }
+
+    #[test]
+    fn test_qhd_canvas_downscaled_to_fhd_recommends_secondary() {
+        let monitors = qhd_with_fhd_secondary();
+        assert_eq!(
+            advise_monitor_capture((2560, 1440), (1920, 1080), &monitors),
+            Some(MonitorCaptureAdvice::CaptureOtherMonitor {
+                captured: monitors[0],
+                target: monitors[1],
+            })
+        );
+    }
+
+    #[test]
+    fn test_integer_downscale_has_no_advice() {
+        // 1440p → 720p は2倍の縮小
+        let monitors = qhd_with_fhd_secondary();
+        assert_eq!(advise_monitor_capture((2560, 1440), (1280, 720), &monitors), None);
+
+        // 4K → 1080p は2倍の縮小
+        let monitors = [monitor(3840, 2160, 60, true), monitor(1920, 1080, 60, false)];
+        assert_eq!(advise_monitor_capture((3840, 2160), (1920, 1080), &monitors), None);
+    }
+
+    #[test]
+    fn test_canvas_matching_secondary_has_no_advice() {
+        let monitors = qhd_with_fhd_secondary();
+        assert_eq!(advise_monitor_capture((1920, 1080), (1920, 1080), &monitors), None);
+    }
+
+    #[test]
+    fn test_no_monitor_matching_output_has_no_advice() {
+        // 同じ解像度のモニター2台では直接キャプチャできるモニターがない
+        let monitors = [monitor(2560, 1440, 144, true), monitor(2560, 1440, 60, false)];
+        assert_eq!(advise_monitor_capture((2560, 1440), (1920, 1080), &monitors), None);
+    }
+
+    #[test]
+    fn test_secondary_qhd_captured_recommends_primary_fhd() {
+        // メインが1080pでサブの1440pをキャプチャしている場合はメインを推奨
+        let monitors = [monitor(1920, 1080, 240, true), monitor(2560, 1440, 60, false)];
+        assert_eq!(
+            advise_monitor_capture((2560, 1440), (1920, 1080), &monitors),
+            Some(MonitorCaptureAdvice::CaptureOtherMonitor {
+                captured: monitors[1],
+                target: monitors[0],
+            })
+        );
+    }
+
+    #[test]
+    fn test_canvas_matching_no_monitor_recommends_primary() {
+        let monitors = [monitor(1920, 1080, 60, false), monitor(2560, 1440, 165, true)];
+        assert_eq!(
+            advise_monitor_capture((1600, 900), (1280, 720), &monitors),
+            Some(MonitorCaptureAdvice::MatchCanvas { monitor: monitors[1] })
+        );
+    }
+}
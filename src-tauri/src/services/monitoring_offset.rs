@@ -0,0 +1,91 @@
+// 音声モニタリングの遅延補正
+//
+// スピーカー・ヘッドホンでのモニタリング音はオーディオインターフェースごとに遅延が異なるため、
+// 遅延補正値を調整する必要がある。補正値はOBSのプロファイル（obs-websocketの永続データ）に保存し、
+// プロファイルごと（＝使用するオーディオインターフェースごと）に切り替わるようにする。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use crate::storage::config::AudioMonitoringConfig;
+use std::ops::RangeInclusive;
+
+/// 遅延補正値を保存する永続データのスロット名
+const MONITORING_OFFSET_SLOT: &str = "obsOptimizer.monitoringOffsetMs";
+
+/// 遅延補正値の範囲（ミリ秒、OBSの同期オフセットと同じ範囲）
+pub const MONITORING_OFFSET_RANGE_MS: RangeInclusive<i32> = -950..=20_000;
+
+/// 遅延補正の確認を促すべきか
+///
+/// モニタリングを使用していて、補正値が未調整（0ms）の場合にtrue
+pub fn needs_offset_calibration(monitoring: &AudioMonitoringConfig) -> bool {
+    monitoring.enabled && monitoring.monitoring_offset_ms == 0
+}
+
+/// ビデオ通話でエコーの原因になるか
+///
+/// 仮想カメラでビデオ通話に映像を送っている間は、遅延補正したモニタリング音が
+/// 通話相手の声と時間差で重なり、エコーとして聞こえる
+///
+/// # Arguments
+/// * `monitoring` - 音声モニタリング設定
+/// * `video_call_active` - 仮想カメラが動作中か
+pub fn causes_video_call_echo(monitoring: &AudioMonitoringConfig, video_call_active: bool) -> bool {
+    video_call_active && monitoring.enabled && monitoring.monitoring_offset_ms != 0
+}
+
+/// OBSのモニタリングデバイス名を取得（未設定の場合はNone）
+pub async fn get_monitoring_device(client: &ObsClient) -> Result<Option<String>, AppError> {
+    let name = client.get_profile_parameter("Audio", "MonitoringDeviceName").await?;
+    Ok(name.filter(|name| !name.is_empty()))
+}
+
+/// 現在のプロファイルに保存された遅延補正値を取得（未保存の場合はNone）
+pub async fn read_monitoring_offset(client: &ObsClient) -> Result<Option<i32>, AppError> {
+    let value = client.get_profile_persistent_data(MONITORING_OFFSET_SLOT).await?;
+    Ok(value.as_i64().and_then(|offset| i32::try_from(offset).ok()))
+}
+
+/// 遅延補正値を現在のプロファイルに保存
+pub async fn write_monitoring_offset(client: &ObsClient, offset_ms: i32) -> Result<(), AppError> {
+    client
+        .set_profile_persistent_data(MONITORING_OFFSET_SLOT, &serde_json::json!(offset_ms))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitoring(enabled: bool, monitoring_offset_ms: i32) -> AudioMonitoringConfig {
+        AudioMonitoringConfig {
+            enabled,
+            monitoring_offset_ms,
+        }
+    }
+
+    #[test]
+    fn test_needs_offset_calibration() {
+        assert!(needs_offset_calibration(&monitoring(true, 0)));
+        assert!(!needs_offset_calibration(&monitoring(true, 120)));
+        assert!(!needs_offset_calibration(&monitoring(true, -40)));
+        assert!(!needs_offset_calibration(&monitoring(false, 0)));
+        assert!(!needs_offset_calibration(&AudioMonitoringConfig::default()));
+    }
+
+    #[test]
+    fn test_causes_video_call_echo() {
+        assert!(causes_video_call_echo(&monitoring(true, 120), true));
+        assert!(!causes_video_call_echo(&monitoring(true, 120), false));
+        assert!(!causes_video_call_echo(&monitoring(true, 0), true));
+        assert!(!causes_video_call_echo(&monitoring(false, 120), true));
+    }
+
+    #[test]
+    fn test_offset_range() {
+        assert!(MONITORING_OFFSET_RANGE_MS.contains(&0));
+        assert!(MONITORING_OFFSET_RANGE_MS.contains(&-950));
+        assert!(!MONITORING_OFFSET_RANGE_MS.contains(&-951));
+        assert!(!MONITORING_OFFSET_RANGE_MS.contains(&20_001));
+    }
+}
@@ -10,8 +10,36 @@
 
 use crate::error::AppError;
 use crate::obs::{
-    get_obs_client, ConnectionConfig, ConnectionState, ObsClient, ObsStatus,
+    get_obs_client, ConnectionConfig, ConnectionState, ObsClient, ObsStatus, PingResult, SceneItem,
 };
+use crate::obs::client::DEFAULT_REQUEST_TIMEOUT;
+use crate::services::audio_monitor::{collect_audio_inputs, microphone_monitoring_disabled};
+use crate::storage::config::{load_config, StreamingPlatform, StreamingStyle};
+use std::time::Duration;
+
+/// 配信先URLとして受け付けるスキーム（https:// はWHIP）
+const STREAM_URL_SCHEMES: &[&str] = &["rtmp://", "rtmps://", "srt://", "rist://", "https://"];
+
+/// 配信先URLが不正な場合のメッセージ
+const INVALID_STREAM_URL_MESSAGE: &str =
+    "OBSの配信先サーバーURLが不正です（rtmp:// / rtmps:// / srt:// / rist:// / https://（WHIP）で始まる必要があります）";
+
+/// WHIPの配信サービスの種別
+const WHIP_STREAM_SERVICE_TYPE: &str = "whip_custom";
+
+/// OBSのサービス一覧から配信先を選択した場合の種別
+///
+/// サーバーは "auto" やサービス定義上の名前になるため、URL形式のチェックは行わない
+const COMMON_STREAM_SERVICE_TYPE: &str = "rtmp_common";
+
+/// ブラウザソースのURLとして受け付けるスキーム
+const BROWSER_SOURCE_URL_SCHEMES: &[&str] = &["http://", "https://", "file://"];
+
+/// ブラウザソースの入力種別
+const BROWSER_SOURCE_INPUT_KIND: &str = "browser_source";
+
+/// ブラウザソースをキャッシュを無視して再読み込みするプロパティのボタン
+const BROWSER_SOURCE_REFRESH_BUTTON: &str = "refreshnocache";
 
 /// OBSサービスのインスタンス
 ///
@@ -41,12 +69,18 @@ impl ObsService {
 
     /// OBS `WebSocketサーバーに接続`
     ///
+    /// 接続・各リクエストのタイムアウトには設定の `connection_timeout_secs` を使用する
+    ///
     /// # Arguments
     /// * `config` - 接続設定（ホスト、ポート、パスワード）
     ///
     /// # Returns
     /// 成功時はOk(()), `失敗時はAppError`
     pub async fn connect(&self, config: ConnectionConfig) -> Result<(), AppError> {
+        let timeout = load_config().map_or(DEFAULT_REQUEST_TIMEOUT, |app_config| {
+            Duration::from_secs(app_config.connection.connection_timeout_secs)
+        });
+        self.client.set_request_timeout(timeout).await;
         self.client.connect(config).await
     }
 
@@ -69,7 +103,7 @@ impl ObsService {
     /// 現在の接続状態を取得
     ///
     /// # Returns
-    /// ConnectionState（Disconnected, Connecting, Connected, Reconnecting, Error）
+    /// ConnectionState（Disconnected, Connecting, Connected, Degraded, Reconnecting, Error）
     pub async fn connection_state(&self) -> ConnectionState {
         self.client.connection_state().await
     }
@@ -96,7 +130,20 @@ impl ObsService {
         if !self.is_connected().await {
             return Ok(ObsStatus::disconnected());
         }
-        self.client.get_status().await
+        let mut status = self.client.get_status().await?;
+        status.privacy_mode_active = crate::services::audio_privacy::is_privacy_mode_active();
+        Ok(status)
+    }
+
+    /// 接続のヘルスチェック（ping）を実行
+    ///
+    /// 失敗した場合、接続状態はDisconnectedに更新される
+    ///
+    /// # Returns
+    /// 往復時間とOBS/WebSocketのバージョン
+    pub async fn ping(&self) -> Result<PingResult, AppError> {
+        self.ensure_connected().await?;
+        self.client.ping().await
     }
 
     /// シーンリストを取得
@@ -117,6 +164,41 @@ impl ObsService {
         self.client.set_current_scene(scene_name).await
     }
 
+    /// シーン内のアイテム一覧を取得
+    ///
+    /// # Arguments
+    /// * `scene_name` - 対象のシーン名
+    pub async fn list_scene_items(&self, scene_name: &str) -> Result<Vec<SceneItem>, AppError> {
+        self.ensure_connected().await?;
+        self.client.get_scene_item_list(scene_name).await
+    }
+
+    /// シーンアイテムの表示状態を取得
+    ///
+    /// # Arguments
+    /// * `scene_name` - 対象のシーン名
+    /// * `item_id` - シーンアイテムID
+    pub async fn get_scene_item_enabled(&self, scene_name: &str, item_id: i64) -> Result<bool, AppError> {
+        self.ensure_connected().await?;
+        self.client.get_scene_item_enabled(scene_name, item_id).await
+    }
+
+    /// シーンアイテムの表示状態を変更
+    ///
+    /// # Arguments
+    /// * `scene_name` - 対象のシーン名
+    /// * `item_id` - シーンアイテムID
+    /// * `enabled` - 表示する場合は `true`
+    pub async fn set_scene_item_enabled(
+        &self,
+        scene_name: &str,
+        item_id: i64,
+        enabled: bool,
+    ) -> Result<(), AppError> {
+        self.ensure_connected().await?;
+        self.client.set_scene_item_enabled(scene_name, item_id, enabled).await
+    }
+
     /// 配信を開始
     pub async fn start_streaming(&self) -> Result<(), AppError> {
         self.ensure_connected().await?;
@@ -144,12 +226,87 @@ impl ObsService {
         self.client.stop_recording().await
     }
 
+    /// 仮想カメラを開始
+    pub async fn start_virtual_cam(&self) -> Result<(), AppError> {
+        self.ensure_connected().await?;
+        self.client.start_virtual_cam().await
+    }
+
+    /// 仮想カメラを停止
+    pub async fn stop_virtual_cam(&self) -> Result<(), AppError> {
+        self.ensure_connected().await?;
+        self.client.stop_virtual_cam().await
+    }
+
+    /// 仮想カメラが有効かを取得
+    pub async fn get_virtual_cam_status(&self) -> Result<bool, AppError> {
+        self.ensure_connected().await?;
+        self.client.get_virtual_cam_status().await
+    }
+
+    /// ブラウザソースのURLを取得
+    ///
+    /// # Arguments
+    /// * `source_name` - ブラウザソースの入力名
+    pub async fn get_browser_source_url(&self, source_name: &str) -> Result<String, AppError> {
+        self.ensure_browser_source(source_name).await?;
+        let settings = self.client.get_input_settings(source_name).await?;
+        Ok(settings
+            .get("url")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// ブラウザソースのURLを変更
+    ///
+    /// # Arguments
+    /// * `source_name` - ブラウザソースの入力名
+    /// * `url` - 新しいURL（http:// / https:// / file://）
+    /// * `refresh_cache` - 変更後にキャッシュを無視して再読み込みする場合は `true`
+    pub async fn set_browser_source_url(
+        &self,
+        source_name: &str,
+        url: &str,
+        refresh_cache: bool,
+    ) -> Result<(), AppError> {
+        validate_browser_source_url(url)?;
+        self.ensure_browser_source(source_name).await?;
+
+        self.client
+            .set_input_settings(source_name, &browser_source_url_settings(url))
+            .await?;
+        if refresh_cache {
+            self.client
+                .press_input_properties_button(source_name, BROWSER_SOURCE_REFRESH_BUTTON)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 指定した入力がブラウザソースであることを確認
+    async fn ensure_browser_source(&self, source_name: &str) -> Result<(), AppError> {
+        self.ensure_connected().await?;
+        let inputs = self.client.get_input_list().await?;
+        let input = inputs
+            .iter()
+            .find(|input| input.id.name == source_name)
+            .ok_or_else(|| AppError::validation_failed(&format!("ソースが見つかりません: {source_name}")))?;
+
+        if input.kind != BROWSER_SOURCE_INPUT_KIND {
+            return Err(AppError::validation_failed(&format!(
+                "ブラウザソースではありません: {source_name}"
+            )));
+        }
+        Ok(())
+    }
+
     /// 接続チェックヘルパー
     ///
     /// 接続されていない場合はエラーを返す
     async fn ensure_connected(&self) -> Result<(), AppError> {
         if !self.is_connected().await {
-            return Err(AppError::obs_state("OBSに接続されていません"));
+            return Err(AppError::obs_not_connected());
         }
         Ok(())
     }
@@ -165,6 +322,203 @@ pub fn obs_service() -> ObsService {
     ObsService::new()
 }
 
+/// OBSの配信先設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamDestination {
+    /// 配信サービスの種別（rtmp_common / rtmp_custom 等）
+    pub service_type: String,
+    /// サービス名（rtmp_commonの場合のみ。例: "Twitch"）
+    pub service: Option<String>,
+    /// サーバーURL
+    pub server: String,
+    /// ストリームキー
+    pub key: String,
+}
+
+impl StreamDestination {
+    /// GetStreamServiceSettingsの応答から作成
+    ///
+    /// # Arguments
+    /// * `service_type` - 配信サービスの種別
+    /// * `settings` - 配信サービスの設定
+    pub fn from_settings(service_type: &str, settings: &serde_json::Value) -> Self {
+        let field = |name: &str| {
+            settings
+                .get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(|value| value.trim().to_string())
+        };
+
+        Self {
+            service_type: service_type.to_string(),
+            service: field("service").filter(|service| !service.is_empty()),
+            server: field("server").unwrap_or_default(),
+            key: field("key").unwrap_or_default(),
+        }
+    }
+
+    /// 配信先URL・サービス名からプラットフォームを判定
+    ///
+    /// 判定できない場合は `Other` を返す
+    pub fn detected_platform(&self) -> StreamingPlatform {
+        let from_server = StreamingPlatform::from_stream_url(&self.server);
+        if from_server != StreamingPlatform::Other {
+            return from_server;
+        }
+
+        let service = self.service.as_deref().unwrap_or_default().to_lowercase();
+        if service.contains("youtube") {
+            StreamingPlatform::YouTube
+        } else if service.contains("twitch") {
+            StreamingPlatform::Twitch
+        } else {
+            StreamingPlatform::Other
+        }
+    }
+}
+
+/// 配信先URLの形式が正しいか（rtmp:// / rtmps:// / srt:// / rist:// / https:// で始まりホスト名を含む）
+pub fn is_valid_stream_url(url: &str) -> bool {
+    let url = url.trim().to_lowercase();
+    STREAM_URL_SCHEMES.iter().any(|scheme| {
+        url.strip_prefix(scheme).is_some_and(|rest| {
+            !rest.is_empty() && !rest.starts_with('/') && !rest.contains(char::is_whitespace)
+        })
+    })
+}
+
+/// ブラウザソースのURLを検証
+///
+/// http:// / https:// で始まりホスト名を含むURL、または file:// で始まるローカルファイルのパスのみ受け付ける
+pub fn validate_browser_source_url(url: &str) -> Result<(), AppError> {
+    let lower = url.trim().to_lowercase();
+    let valid = BROWSER_SOURCE_URL_SCHEMES.iter().any(|scheme| {
+        lower.strip_prefix(scheme).is_some_and(|rest| {
+            let has_target = if *scheme == "file://" {
+                !rest.is_empty()
+            } else {
+                !rest.is_empty() && !rest.starts_with('/')
+            };
+            has_target && !rest.contains(char::is_whitespace)
+        })
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::validation_failed(
+            "ブラウザソースのURLが不正です（http:// / https:// / file:// で始まる必要があります）",
+        ))
+    }
+}
+
+/// ブラウザソースのURLを変更する入力設定
+///
+/// ローカルファイル指定（`is_local_file`）が有効だとURLが使われないため、あわせて無効にする
+pub fn browser_source_url_settings(url: &str) -> serde_json::Value {
+    serde_json::json!({
+        "url": url.trim(),
+        "is_local_file": false,
+    })
+}
+
+/// 配信先設定を検証
+///
+/// SRTはストリームキーをURLの `streamid` に含められるため、その場合はキーが空でもよい。
+/// RISTとWHIPは認証（RISTのパスフレーズ・WHIPのBearerトークン）が任意のため、キーが空でもよい
+pub fn check_stream_destination(destination: &StreamDestination) -> Result<(), AppError> {
+    if destination.server.is_empty() {
+        return Err(AppError::stream_destination_invalid(
+            "OBSの配信先サーバーが設定されていません",
+        ));
+    }
+
+    if destination.service_type != COMMON_STREAM_SERVICE_TYPE
+        && !is_valid_stream_url(&destination.server)
+    {
+        return Err(AppError::stream_destination_invalid(INVALID_STREAM_URL_MESSAGE));
+    }
+
+    let server = destination.server.to_lowercase();
+    let key_in_url = server.starts_with("srt://") && destination.server.contains("streamid=");
+    let key_optional = destination.service_type == WHIP_STREAM_SERVICE_TYPE
+        || server.starts_with("rist://")
+        || server.starts_with("https://");
+    if destination.key.is_empty() && !key_in_url && !key_optional {
+        return Err(AppError::stream_destination_invalid(
+            "OBSのストリームキーが設定されていません",
+        ));
+    }
+
+    Ok(())
+}
+
+/// 配信先と設定中のプラットフォームが食い違っているか
+///
+/// # Returns
+/// 食い違っている場合は配信先から判定したプラットフォーム
+pub fn platform_mismatch(
+    destination: &StreamDestination,
+    configured: StreamingPlatform,
+) -> Option<StreamingPlatform> {
+    let detected = destination.detected_platform();
+    (detected != StreamingPlatform::Other && detected != configured).then_some(detected)
+}
+
+/// OBSから配信先設定を取得
+pub async fn get_stream_destination() -> Result<StreamDestination, AppError> {
+    let client = get_obs_client();
+    if !client.is_connected().await {
+        return Err(AppError::obs_not_connected());
+    }
+
+    let settings = client.get_stream_service_settings().await?;
+    Ok(StreamDestination::from_settings(&settings.r#type, &settings.settings))
+}
+
+/// 配信先と設定中のプラットフォームが食い違っている場合のユーザー向けの警告
+pub fn platform_mismatch_warning(detected: StreamingPlatform, configured: StreamingPlatform) -> String {
+    format!(
+        "OBSの配信先は {detected:?} ですが、アプリの配信プラットフォームは {configured:?} に設定されています。推奨設定が配信先に合っていない可能性があります"
+    )
+}
+
+/// 歌・演奏配信でマイクの音声モニタリングがオフの場合のユーザー向けの警告
+const MUSIC_MONITORING_OFF_WARNING: &str =
+    "歌・演奏配信ですが、マイクの音声モニタリングがオフのため自分の音を確認できません";
+
+/// OBSの配信先（サーバーURL・ストリームキー）を検証
+///
+/// 配信先が設定中のプラットフォームと異なる場合、および歌・演奏配信で
+/// マイクの音声モニタリングがオフ（配信者が自分の音を聞けない）の場合は警告を返す
+/// （設定ミスの可能性があるが、意図的な場合もあるためエラーにはしない）
+///
+/// # Returns
+/// ユーザーに表示する警告（問題がない場合は空）
+pub async fn validate_stream_destination() -> Result<Vec<String>, AppError> {
+    let destination = get_stream_destination().await?;
+    check_stream_destination(&destination)?;
+
+    let mut warnings = Vec::new();
+    if let Ok(config) = load_config() {
+        if let Some(detected) = platform_mismatch(&destination, config.streaming_mode.platform) {
+            warnings.push(platform_mismatch_warning(detected, config.streaming_mode.platform));
+        }
+
+        if config.streaming_mode.style == StreamingStyle::Music {
+            let inputs = collect_audio_inputs(&get_obs_client()).await.unwrap_or_default();
+            if microphone_monitoring_disabled(&inputs) {
+                warnings.push(MUSIC_MONITORING_OFF_WARNING.to_string());
+            }
+        }
+    }
+
+    for warning in &warnings {
+        tracing::warn!(target: "obs", warning = %warning, "配信先の確認を促す警告");
+    }
+    Ok(warnings)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -199,4 +553,122 @@ mod tests {
         let result = service.get_scene_list().await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_browser_source_url() {
+        assert!(validate_browser_source_url("https://streamelements.com/overlay/abc/def").is_ok());
+        assert!(validate_browser_source_url("http://localhost:8080/chat").is_ok());
+        assert!(validate_browser_source_url("file:///C:/overlays/alert.html").is_ok());
+
+        let error = validate_browser_source_url("").unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED);
+        assert!(validate_browser_source_url("   ").is_err());
+        assert!(validate_browser_source_url("twitch chat").is_err());
+        assert!(validate_browser_source_url("streamelements.com/overlay").is_err());
+        assert!(validate_browser_source_url("https://").is_err());
+        assert!(validate_browser_source_url("javascript:alert(1)").is_err());
+        assert!(validate_browser_source_url("https://example.com/a b").is_err());
+    }
+
+    #[test]
+    fn test_browser_source_url_settings() {
+        let settings = browser_source_url_settings(" https://www.twitch.tv/popout/example/chat ");
+        assert_eq!(settings["url"], "https://www.twitch.tv/popout/example/chat");
+        assert_eq!(settings["is_local_file"], false);
+    }
+
+    #[tokio::test]
+    async fn test_set_browser_source_url_validates_before_connecting() {
+        let service = obs_service();
+
+        let error = service.set_browser_source_url("チャット", "not a url", true).await.unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED);
+
+        // URLが正しい場合は接続チェックまで進む
+        let error = service
+            .set_browser_source_url("チャット", "https://example.com/chat", false)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_OBS_NOT_CONNECTED);
+    }
+
+    fn destination(service_type: &str, server: &str, key: &str) -> StreamDestination {
+        StreamDestination {
+            service_type: service_type.to_string(),
+            service: None,
+            server: server.to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_stream_url() {
+        assert!(is_valid_stream_url("rtmp://live.twitch.tv/app"));
+        assert!(is_valid_stream_url("rtmps://a.rtmp.youtube.com:443/live2"));
+        assert!(is_valid_stream_url("SRT://example.com:9000?streamid=abc"));
+
+        assert!(!is_valid_stream_url(""));
+        assert!(!is_valid_stream_url("rtmp://"));
+        assert!(!is_valid_stream_url("rtmp:///app"));
+        assert!(is_valid_stream_url("rist://example.com:5000"));
+        assert!(is_valid_stream_url("https://global.whip.live-video.net/"));
+        assert!(!is_valid_stream_url("https://"));
+        assert!(!is_valid_stream_url("http://live.twitch.tv/app"));
+        assert!(!is_valid_stream_url("live.twitch.tv/app"));
+        assert!(!is_valid_stream_url("rtmp://live twitch.tv/app"));
+    }
+
+    #[test]
+    fn test_check_stream_destination() {
+        assert!(check_stream_destination(&destination("rtmp_custom", "rtmp://example.com/live", "key")).is_ok());
+
+        let error = check_stream_destination(&destination("rtmp_custom", "", "key")).unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_STREAM_DESTINATION_INVALID);
+        assert!(check_stream_destination(&destination("rtmp_custom", "example.com/live", "key")).is_err());
+        assert!(check_stream_destination(&destination("rtmp_custom", "rtmp://example.com/live", "")).is_err());
+
+        // サービス一覧から選択した場合はサーバーが "auto" でもよい
+        assert!(check_stream_destination(&destination("rtmp_common", "auto", "key")).is_ok());
+        // SRTはstreamidにキーを含められる
+        assert!(check_stream_destination(&destination("rtmp_custom", "srt://example.com:9000?streamid=abc", "")).is_ok());
+        // RIST・WHIPは認証が任意
+        assert!(check_stream_destination(&destination("rtmp_custom", "rist://example.com:5000", "")).is_ok());
+        assert!(check_stream_destination(&destination("whip_custom", "https://example.com/whip", "")).is_ok());
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let settings = serde_json::json!({
+            "service": "Twitch",
+            "server": " auto ",
+            "key": "live_123"
+        });
+        let dest = StreamDestination::from_settings("rtmp_common", &settings);
+        assert_eq!(dest.service.as_deref(), Some("Twitch"));
+        assert_eq!(dest.server, "auto");
+        assert_eq!(dest.key, "live_123");
+
+        let dest = StreamDestination::from_settings("rtmp_custom", &serde_json::json!({}));
+        assert!(dest.server.is_empty());
+        assert!(dest.key.is_empty());
+    }
+
+    #[test]
+    fn test_platform_mismatch() {
+        let twitch = destination("rtmp_custom", "rtmp://live.twitch.tv/app", "key");
+        assert_eq!(platform_mismatch(&twitch, StreamingPlatform::YouTube), Some(StreamingPlatform::Twitch));
+        assert_eq!(platform_mismatch(&twitch, StreamingPlatform::Twitch), None);
+
+        let youtube = destination("rtmp_custom", "rtmps://a.rtmp.youtube.com/live2", "key");
+        assert_eq!(platform_mismatch(&youtube, StreamingPlatform::Twitch), Some(StreamingPlatform::YouTube));
+
+        // 判定できない配信先は警告しない
+        let custom = destination("rtmp_custom", "rtmp://192.168.0.10/live", "key");
+        assert_eq!(platform_mismatch(&custom, StreamingPlatform::YouTube), None);
+
+        // サービス名からも判定する
+        let mut common = destination("rtmp_common", "auto", "key");
+        common.service = Some("Twitch".to_string());
+        assert_eq!(platform_mismatch(&common, StreamingPlatform::YouTube), Some(StreamingPlatform::Twitch));
+    }
 }
@@ -0,0 +1,570 @@
+// OBSのプロファイル・シーンコレクションのファイルのバックアップ
+//
+// `backup_current_settings` が保存するのは本アプリの `ProfileSettings`（解像度・エンコーダー等）のみで、
+// 適用の失敗と手作業の変更が重なるとOBSのプロファイル自体がおかしな状態に残ることがある。
+// 設定で有効にした場合は、適用前にOBSの設定ディレクトリから
+// - プロファイルのディレクトリ（`basic/profiles/<プロファイル>/basic.ini`・`streamEncoder.json` 等）
+// - 使用中のシーンコレクション（`basic/scenes/<コレクション>.json`）
+// をバックアップフォルダにコピーし、復元時に書き戻す。
+//
+// OBSは起動中の設定をメモリに保持し、終了時や設定変更時にファイルへ書き出すため、
+// 起動中に書き戻したファイルはOBSを再起動するまで反映されない。
+
+use crate::error::AppError;
+use crate::obs::{ObsClient, ObsStatus};
+use crate::services::obs_log::obs_config_dir;
+use crate::services::streaming_mode::ensure_no_blocking_outputs;
+use crate::storage::paths::app_data_dir;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// バックアップの保存先ディレクトリ名（アプリのデータディレクトリ以下）
+const OBS_FILE_BACKUPS_DIR_NAME: &str = "obs_file_backups";
+
+/// バックアップの内容を記録するファイル名
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// OBSの設定ディレクトリ以下のプロファイルのディレクトリ
+const OBS_PROFILES_DIR: &str = "basic/profiles";
+
+/// OBSの設定ディレクトリ以下のシーンコレクションのディレクトリ
+const OBS_SCENES_DIR: &str = "basic/scenes";
+
+/// プロファイルの設定ファイル（`[General]` の `Name` にプロファイル名が入る）
+const PROFILE_INI_FILE_NAME: &str = "basic.ini";
+
+/// コピーするファイル1つあたりの上限（画像を埋め込んだシーンコレクションでも通常は数MB）
+pub const MAX_OBS_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// バックアップしたファイルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObsFileKind {
+    /// プロファイルのディレクトリ内のファイル
+    Profile,
+    /// シーンコレクションのJSON
+    SceneCollection,
+}
+
+/// バックアップしたファイル
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsBackupFile {
+    /// ファイルの種類
+    pub kind: ObsFileKind,
+    /// ファイル名
+    pub file_name: String,
+    /// サイズ（バイト）
+    pub size_bytes: u64,
+}
+
+/// OBSのファイルのバックアップの内容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsFileBackup {
+    /// 対応する設定バックアップのID
+    pub backup_id: String,
+    /// OBSのプロファイル名
+    pub profile_name: String,
+    /// プロファイルのディレクトリ名（`basic/profiles` 以下）
+    pub profile_dir_name: String,
+    /// シーンコレクション名
+    pub scene_collection_name: String,
+    /// バックアップしたファイル
+    pub files: Vec<ObsBackupFile>,
+    /// サイズの上限を超えたためコピーしなかったファイル名
+    pub skipped_files: Vec<String>,
+    /// 作成日時（UNIX epoch秒）
+    pub created_at: i64,
+}
+
+/// OBSのファイルの復元結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsFileRestoreResult {
+    /// 書き戻したファイル名
+    pub restored_files: Vec<String>,
+    /// OBSの再起動が必要か（OBSの起動中に書き戻した場合）
+    pub restart_required: bool,
+}
+
+/// バックアップの保存先ディレクトリを取得
+pub fn obs_file_backups_dir() -> Result<PathBuf, AppError> {
+    app_data_dir()
+        .map(|dir| dir.join(OBS_FILE_BACKUPS_DIR_NAME))
+        .ok_or_else(|| AppError::config_io("データディレクトリを取得できませんでした"))
+}
+
+/// パス区切りや `..` を含まない単一のファイル名か
+///
+/// マニフェストは書き換えられる可能性があるため、書き戻し先がOBSの設定ディレクトリの外に出ないよう確認する
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && Path::new(name).file_name() == Some(std::ffi::OsStr::new(name))
+}
+
+/// `basic.ini` の `[General]` セクションの `Name` を取得
+fn read_profile_name(ini: &str) -> Option<String> {
+    let mut in_general = false;
+    for line in ini.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_general = line == "[General]";
+        } else if in_general {
+            if let Some(name) = line.strip_prefix("Name=") {
+                return Some(name.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// プロファイル名からプロファイルのディレクトリを探す
+///
+/// OBSはプロファイル名の記号を置き換えてディレクトリ名にするため、
+/// 各ディレクトリの `basic.ini` に記録された名前で照合し、見つからない場合は同名のディレクトリを使う
+///
+/// # Arguments
+/// * `obs_config_dir` - OBSの設定ディレクトリ
+/// * `profile_name` - プロファイル名
+pub fn find_profile_dir(obs_config_dir: &Path, profile_name: &str) -> Result<PathBuf, AppError> {
+    let profiles_dir = obs_config_dir.join(OBS_PROFILES_DIR);
+    let entries = std::fs::read_dir(&profiles_dir).map_err(|e| {
+        AppError::config_io(&format!(
+            "OBSのプロファイルのディレクトリを開けませんでした（{}）: {e}",
+            profiles_dir.display()
+        ))
+    })?;
+
+    for dir in entries.filter_map(Result::ok).map(|entry| entry.path()).filter(|path| path.is_dir()) {
+        let Ok(ini) = std::fs::read_to_string(dir.join(PROFILE_INI_FILE_NAME)) else {
+            continue;
+        };
+        if read_profile_name(&ini).as_deref() == Some(profile_name) {
+            return Ok(dir);
+        }
+    }
+
+    let fallback = profiles_dir.join(profile_name);
+    if is_plain_file_name(profile_name) && fallback.is_dir() {
+        return Ok(fallback);
+    }
+    Err(AppError::config_io(&format!("OBSのプロファイルが見つかりません: {profile_name}")))
+}
+
+/// シーンコレクション名からシーンコレクションのファイルを探す
+///
+/// ファイル名はコレクション名の記号を置き換えたものになるため、各ファイルの `name` で照合する。
+/// 上限を超えるファイルは読み込まない
+///
+/// # Arguments
+/// * `obs_config_dir` - OBSの設定ディレクトリ
+/// * `collection_name` - シーンコレクション名
+pub fn find_scene_collection_file(obs_config_dir: &Path, collection_name: &str) -> Result<PathBuf, AppError> {
+    #[derive(Deserialize)]
+    struct SceneCollectionHeader {
+        name: Option<String>,
+    }
+
+    let scenes_dir = obs_config_dir.join(OBS_SCENES_DIR);
+    let entries = std::fs::read_dir(&scenes_dir).map_err(|e| {
+        AppError::config_io(&format!(
+            "OBSのシーンコレクションのディレクトリを開けませんでした（{}）: {e}",
+            scenes_dir.display()
+        ))
+    })?;
+
+    for path in entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+    {
+        if !std::fs::metadata(&path).is_ok_and(|meta| meta.len() <= MAX_OBS_FILE_BYTES) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(header) = serde_json::from_str::<SceneCollectionHeader>(&content) else {
+            continue;
+        };
+        if header.name.as_deref() == Some(collection_name) {
+            return Ok(path);
+        }
+    }
+
+    let fallback = scenes_dir.join(format!("{collection_name}.json"));
+    if is_plain_file_name(collection_name) && fallback.is_file() {
+        return Ok(fallback);
+    }
+    Err(AppError::config_io(&format!("OBSのシーンコレクションが見つかりません: {collection_name}")))
+}
+
+/// サイズの上限以内のファイルをコピー
+///
+/// # Returns
+/// コピーしたサイズ（上限を超えた場合はNone）
+fn copy_with_limit(source: &Path, destination: &Path) -> Result<Option<u64>, AppError> {
+    let size = std::fs::metadata(source)?.len();
+    if size > MAX_OBS_FILE_BYTES {
+        return Ok(None);
+    }
+    std::fs::copy(source, destination)?;
+    Ok(Some(size))
+}
+
+/// バックアップのディレクトリ
+fn backup_dir(backups_dir: &Path, backup_id: &str) -> Result<PathBuf, AppError> {
+    if !is_plain_file_name(backup_id) {
+        return Err(AppError::validation_failed(&format!("不正なバックアップIDです: {backup_id}")));
+    }
+    Ok(backups_dir.join(backup_id))
+}
+
+/// OBSのプロファイルとシーンコレクションのファイルをバックアップ
+///
+/// プロファイルのディレクトリ直下のファイルとシーンコレクションのJSONを
+/// `<backups_dir>/<backup_id>/` にコピーする。上限を超えるファイルはコピーせず `skipped_files` に記録する
+///
+/// # Arguments
+/// * `obs_config_dir` - OBSの設定ディレクトリ
+/// * `backups_dir` - バックアップの保存先ディレクトリ
+/// * `backup_id` - 対応する設定バックアップのID
+/// * `profile_name` - 使用中のプロファイル名
+/// * `collection_name` - 使用中のシーンコレクション名
+pub fn backup_obs_files(
+    obs_config_dir: &Path,
+    backups_dir: &Path,
+    backup_id: &str,
+    profile_name: &str,
+    collection_name: &str,
+) -> Result<ObsFileBackup, AppError> {
+    let profile_dir = find_profile_dir(obs_config_dir, profile_name)?;
+    let scene_file = find_scene_collection_file(obs_config_dir, collection_name)?;
+
+    let destination = backup_dir(backups_dir, backup_id)?;
+    std::fs::create_dir_all(&destination)?;
+
+    let mut profile_files: Vec<PathBuf> = std::fs::read_dir(&profile_dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    profile_files.sort();
+    let sources = profile_files
+        .into_iter()
+        .map(|path| (ObsFileKind::Profile, path))
+        .chain(std::iter::once((ObsFileKind::SceneCollection, scene_file)));
+
+    let mut files = Vec::new();
+    let mut skipped_files = Vec::new();
+    for (kind, source) in sources {
+        let Some(file_name) = source.file_name().and_then(|name| name.to_str()).map(ToString::to_string) else {
+            continue;
+        };
+        // プロファイルとシーンコレクションでファイル名が重複しないよう、種類ごとに分ける
+        let kind_dir = destination.join(kind_dir_name(kind));
+        std::fs::create_dir_all(&kind_dir)?;
+        match copy_with_limit(&source, &kind_dir.join(&file_name))? {
+            Some(size_bytes) => files.push(ObsBackupFile { kind, file_name, size_bytes }),
+            None => skipped_files.push(file_name),
+        }
+    }
+
+    let backup = ObsFileBackup {
+        backup_id: backup_id.to_string(),
+        profile_name: profile_name.to_string(),
+        profile_dir_name: profile_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(profile_name)
+            .to_string(),
+        scene_collection_name: collection_name.to_string(),
+        files,
+        skipped_files,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    std::fs::write(destination.join(MANIFEST_FILE_NAME), serde_json::to_string_pretty(&backup)?)?;
+
+    Ok(backup)
+}
+
+/// 使用中のプロファイル・シーンコレクションのファイルをバックアップ
+///
+/// # Arguments
+/// * `client` - OBSクライアント（接続済み）
+/// * `backup_id` - 対応する設定バックアップのID
+pub async fn backup_current_obs_files(client: &ObsClient, backup_id: &str) -> Result<ObsFileBackup, AppError> {
+    let profile_name = client.get_current_profile().await?;
+    let collection_name = client.get_current_scene_collection().await?;
+    backup_obs_files(
+        &obs_config_dir()?,
+        &obs_file_backups_dir()?,
+        backup_id,
+        &profile_name,
+        &collection_name,
+    )
+}
+
+/// 種類ごとのバックアップ内のディレクトリ名
+const fn kind_dir_name(kind: ObsFileKind) -> &'static str {
+    match kind {
+        ObsFileKind::Profile => "profile",
+        ObsFileKind::SceneCollection => "scenes",
+    }
+}
+
+/// OBSのファイルのバックアップを取得（バックアップしていない場合はNone）
+pub fn load_obs_file_backup(backups_dir: &Path, backup_id: &str) -> Result<Option<ObsFileBackup>, AppError> {
+    let path = backup_dir(backups_dir, backup_id)?.join(MANIFEST_FILE_NAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// OBSのファイルのバックアップを削除（バックアップしていない場合は何もしない）
+pub fn delete_obs_file_backup(backups_dir: &Path, backup_id: &str) -> Result<(), AppError> {
+    let dir = backup_dir(backups_dir, backup_id)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// 復元を実行できるかを確認
+///
+/// 配信・録画・仮想カメラの出力中は書き戻さない。OBSが起動中の場合は書き戻せるが、
+/// 反映には再起動が必要になる
+///
+/// # Arguments
+/// * `obs_running` - OBSのプロセスが起動しているか
+/// * `status` - OBSの状態（未接続の場合はNone）
+///
+/// # Returns
+/// OBSの再起動が必要か
+pub fn check_restore_safety(obs_running: bool, status: Option<&ObsStatus>) -> Result<bool, AppError> {
+    if let Some(status) = status {
+        ensure_no_blocking_outputs(status)?;
+        if status.recording {
+            return Err(AppError::obs_output_active(
+                "録画中はOBSのファイルを復元できません。録画を停止してから実行してください",
+            ));
+        }
+    }
+    Ok(obs_running || status.is_some_and(|status| status.connected))
+}
+
+/// バックアップしたOBSのファイルを書き戻す
+///
+/// 書き込み途中のファイルをOBSが読まないよう、一時ファイルに書き込んでから置き換える。
+/// 呼び出し側で `check_restore_safety` による確認を行うこと
+///
+/// # Arguments
+/// * `obs_config_dir` - OBSの設定ディレクトリ
+/// * `backups_dir` - バックアップの保存先ディレクトリ
+/// * `backup_id` - 設定バックアップのID
+/// * `restart_required` - OBSの再起動が必要か（結果に含める）
+pub fn restore_obs_files(
+    obs_config_dir: &Path,
+    backups_dir: &Path,
+    backup_id: &str,
+    restart_required: bool,
+) -> Result<ObsFileRestoreResult, AppError> {
+    let backup = load_obs_file_backup(backups_dir, backup_id)?.ok_or_else(|| {
+        AppError::validation_failed(&format!("OBSのファイルをバックアップしていません: {backup_id}"))
+    })?;
+    if !is_plain_file_name(&backup.profile_dir_name) {
+        return Err(AppError::validation_failed(&format!(
+            "不正なプロファイルのディレクトリ名です: {}",
+            backup.profile_dir_name
+        )));
+    }
+    if let Some(file) = backup.files.iter().find(|file| !is_plain_file_name(&file.file_name)) {
+        return Err(AppError::validation_failed(&format!("不正なファイル名です: {}", file.file_name)));
+    }
+
+    let source_dir = backup_dir(backups_dir, backup_id)?;
+    let mut restored_files = Vec::new();
+    for file in &backup.files {
+        let target_dir = match file.kind {
+            ObsFileKind::Profile => obs_config_dir.join(OBS_PROFILES_DIR).join(&backup.profile_dir_name),
+            ObsFileKind::SceneCollection => obs_config_dir.join(OBS_SCENES_DIR),
+        };
+        std::fs::create_dir_all(&target_dir)?;
+
+        let source = source_dir.join(kind_dir_name(file.kind)).join(&file.file_name);
+        let temporary = target_dir.join(format!("{}.restore-tmp", file.file_name));
+        if copy_with_limit(&source, &temporary)?.is_none() {
+            return Err(AppError::validation_failed(&format!(
+                "バックアップのファイルが大きすぎます: {}",
+                file.file_name
+            )));
+        }
+        std::fs::rename(&temporary, target_dir.join(&file.file_name))?;
+        restored_files.push(file.file_name.clone());
+    }
+
+    Ok(ObsFileRestoreResult { restored_files, restart_required })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    /// OBSの設定ディレクトリを模した一時ディレクトリ
+    struct TestDirs {
+        root: PathBuf,
+    }
+
+    impl TestDirs {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("obs_file_backup_test_{}", Uuid::new_v4()));
+            let profile = root.join("obs-studio").join(OBS_PROFILES_DIR).join("Main_Stream");
+            std::fs::create_dir_all(&profile).unwrap();
+            std::fs::write(profile.join("basic.ini"), "\u{feff}[General]\nName=Main Stream\n\n[Video]\nFPSCommon=60\n").unwrap();
+            std::fs::write(profile.join("streamEncoder.json"), r#"{"bitrate":6000}"#).unwrap();
+
+            let other = root.join("obs-studio").join(OBS_PROFILES_DIR).join("Untitled");
+            std::fs::create_dir_all(&other).unwrap();
+            std::fs::write(other.join("basic.ini"), "[General]\nName=Untitled\n").unwrap();
+
+            let scenes = root.join("obs-studio").join(OBS_SCENES_DIR);
+            std::fs::create_dir_all(&scenes).unwrap();
+            std::fs::write(scenes.join("My_Scenes.json"), r#"{"name":"My Scenes","sources":[]}"#).unwrap();
+            std::fs::write(scenes.join("Untitled.json"), r#"{"name":"Untitled","sources":[]}"#).unwrap();
+            Self { root }
+        }
+
+        fn obs(&self) -> PathBuf {
+            self.root.join("obs-studio")
+        }
+
+        fn backups(&self) -> PathBuf {
+            self.root.join("backups")
+        }
+    }
+
+    impl Drop for TestDirs {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_find_profile_dir_by_ini_name() {
+        let dirs = TestDirs::new();
+        let dir = find_profile_dir(&dirs.obs(), "Main Stream").unwrap();
+        assert!(dir.ends_with("Main_Stream"));
+        // ディレクトリ名が一致する場合
+        assert!(find_profile_dir(&dirs.obs(), "Untitled").unwrap().ends_with("Untitled"));
+
+        let error = find_profile_dir(&dirs.obs(), "Missing").unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_CONFIG_IO);
+    }
+
+    #[test]
+    fn test_find_scene_collection_file_by_json_name() {
+        let dirs = TestDirs::new();
+        let file = find_scene_collection_file(&dirs.obs(), "My Scenes").unwrap();
+        assert!(file.ends_with("My_Scenes.json"));
+        assert!(find_scene_collection_file(&dirs.obs(), "Other").is_err());
+        // パスを含む名前で設定ディレクトリの外を参照しない
+        assert!(find_scene_collection_file(&dirs.obs(), "../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_read_profile_name() {
+        assert_eq!(read_profile_name("[General]\nName=配信用\n").as_deref(), Some("配信用"));
+        assert_eq!(read_profile_name("[Video]\nName=x\n[General]\nOther=1\n"), None);
+    }
+
+    #[test]
+    fn test_backup_and_load_obs_files() {
+        let dirs = TestDirs::new();
+        let backup = backup_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", "Main Stream", "My Scenes").unwrap();
+
+        assert_eq!(backup.profile_dir_name, "Main_Stream");
+        let names: Vec<&str> = backup.files.iter().map(|file| file.file_name.as_str()).collect();
+        assert_eq!(names, vec!["basic.ini", "streamEncoder.json", "My_Scenes.json"]);
+        assert_eq!(backup.files[2].kind, ObsFileKind::SceneCollection);
+        assert!(backup.skipped_files.is_empty());
+
+        let copied = std::fs::read_to_string(dirs.backups().join("backup-1/profile/streamEncoder.json")).unwrap();
+        assert_eq!(copied, r#"{"bitrate":6000}"#);
+        assert_eq!(load_obs_file_backup(&dirs.backups(), "backup-1").unwrap(), Some(backup));
+        assert_eq!(load_obs_file_backup(&dirs.backups(), "backup-2").unwrap(), None);
+
+        delete_obs_file_backup(&dirs.backups(), "backup-1").unwrap();
+        assert!(!dirs.backups().join("backup-1").exists());
+    }
+
+    #[test]
+    fn test_backup_skips_files_over_size_limit() {
+        let dirs = TestDirs::new();
+        let large = dirs.obs().join(OBS_PROFILES_DIR).join("Main_Stream").join("large.bin");
+        std::fs::File::create(&large).unwrap().set_len(MAX_OBS_FILE_BYTES + 1).unwrap();
+
+        let backup = backup_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", "Main Stream", "My Scenes").unwrap();
+        assert_eq!(backup.skipped_files, vec!["large.bin".to_string()]);
+        assert!(backup.files.iter().all(|file| file.file_name != "large.bin"));
+        assert!(!dirs.backups().join("backup-1/profile/large.bin").exists());
+    }
+
+    #[test]
+    fn test_restore_writes_files_back() {
+        let dirs = TestDirs::new();
+        backup_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", "Main Stream", "My Scenes").unwrap();
+
+        // 適用後に手作業で変更された状態
+        let encoder = dirs.obs().join(OBS_PROFILES_DIR).join("Main_Stream").join("streamEncoder.json");
+        std::fs::write(&encoder, r#"{"bitrate":99999}"#).unwrap();
+        std::fs::remove_file(dirs.obs().join(OBS_SCENES_DIR).join("My_Scenes.json")).unwrap();
+
+        let result = restore_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", false).unwrap();
+        assert_eq!(result.restored_files.len(), 3);
+        assert!(!result.restart_required);
+        assert_eq!(std::fs::read_to_string(&encoder).unwrap(), r#"{"bitrate":6000}"#);
+        assert!(dirs.obs().join(OBS_SCENES_DIR).join("My_Scenes.json").exists());
+        assert!(!dirs.obs().join(OBS_SCENES_DIR).join("My_Scenes.json.restore-tmp").exists());
+    }
+
+    #[test]
+    fn test_restore_rejects_tampered_manifest() {
+        let dirs = TestDirs::new();
+        let mut backup =
+            backup_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", "Main Stream", "My Scenes").unwrap();
+        backup.profile_dir_name = "../..".to_string();
+        std::fs::write(
+            dirs.backups().join("backup-1").join(MANIFEST_FILE_NAME),
+            serde_json::to_string(&backup).unwrap(),
+        )
+        .unwrap();
+
+        let error = restore_obs_files(&dirs.obs(), &dirs.backups(), "backup-1", false).unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED);
+        assert!(restore_obs_files(&dirs.obs(), &dirs.backups(), "../backup-1", false).is_err());
+        assert!(restore_obs_files(&dirs.obs(), &dirs.backups(), "missing", false).is_err());
+    }
+
+    #[test]
+    fn test_check_restore_safety() {
+        // OBSが起動していない場合はそのまま反映される
+        assert!(!check_restore_safety(false, None).unwrap());
+        // 起動中・接続中は再起動が必要
+        assert!(check_restore_safety(true, None).unwrap());
+        let idle = ObsStatus { connected: true, ..ObsStatus::default() };
+        assert!(check_restore_safety(true, Some(&idle)).unwrap());
+
+        for status in [
+            ObsStatus { connected: true, streaming: true, ..ObsStatus::default() },
+            ObsStatus { connected: true, recording: true, ..ObsStatus::default() },
+            ObsStatus { connected: true, virtual_cam_active: true, ..ObsStatus::default() },
+        ] {
+            let error = check_restore_safety(true, Some(&status)).unwrap_err();
+            assert_eq!(error.code(), crate::error::ERROR_CODE_OBS_OUTPUT_ACTIVE);
+        }
+    }
+}
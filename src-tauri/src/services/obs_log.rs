@@ -0,0 +1,641 @@
+// OBSログ解析
+//
+// アプリを起動していなかった配信でも原因を調べられるよう、OBS自身のログファイルから
+// エンコード過負荷・フレームドロップの集計・出力の停止コードを読み取り、問題レポートに変換する。
+//
+// ログの保存先:
+// - Windows: %APPDATA%/obs-studio/logs
+// - macOS: ~/Library/Application Support/obs-studio/logs
+// - Linux: ~/.config/obs-studio/logs
+//
+// ファイル名は開始日時（例: "2024-05-01 20-15-30.txt"）、各行は "HH:MM:SS.mmm: " で始まる
+
+use crate::error::AppError;
+use crate::services::alerts::{AlertSeverity, MetricType};
+use crate::services::analyzer::{ProblemCategory, ProblemReport};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// OBSの設定ディレクトリ名
+const OBS_CONFIG_DIR_NAME: &str = "obs-studio";
+/// ログディレクトリ名
+const OBS_LOGS_DIR_NAME: &str = "logs";
+/// ログファイル名の日時フォーマット
+const LOG_FILE_NAME_FORMAT: &str = "%Y-%m-%d %H-%M-%S";
+/// 各行の時刻フォーマット
+const LOG_LINE_TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
+/// 起動時に列挙される利用可能なエンコーダーの見出し
+const MARKER_AVAILABLE_ENCODERS: &str = "Available Encoders:";
+/// 利用可能なエンコーダーのうち映像エンコーダーの見出し
+const MARKER_VIDEO_ENCODERS: &str = "Video Encoders:";
+
+/// フレーム損失率の警告閾値（%）
+const FRAME_LOSS_WARNING_PERCENT: f64 = 0.5;
+/// フレーム損失率のクリティカル閾値（%）
+const FRAME_LOSS_CRITICAL_PERCENT: f64 = 2.0;
+
+/// エンコード遅延によるスキップフレームの集計行
+const MARKER_ENCODING_LAG: &str = "skipped frames due to encoding lag: ";
+/// レンダリング遅延によるラグフレームの集計行
+const MARKER_RENDERING_LAG: &str = "lagged frames due to rendering lag/stalls: ";
+/// 帯域不足によるドロップフレームの集計行
+const MARKER_NETWORK_DROPS: &str = "dropped frames due to insufficient bandwidth/connection stalls: ";
+/// エンコード過負荷の通知
+const MARKER_ENCODING_OVERLOADED: &str = "encoding overloaded";
+/// 配信サーバーへの接続失敗（末尾に停止コード）
+const MARKER_CONNECTION_FAILED: &str = "] Connection to ";
+/// 配信中の切断
+const MARKER_DISCONNECTED: &str = "] Disconnected from ";
+
+/// OBSの出力停止コード: 接続が切断された（`OBS_OUTPUT_DISCONNECTED`）
+const OBS_OUTPUT_DISCONNECTED: i32 = -5;
+
+/// ログから検出したイベントの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObsLogEventKind {
+    /// エンコード過負荷の通知
+    EncodingOverloaded,
+    /// エンコード遅延によるスキップフレームの集計
+    EncodingLag,
+    /// レンダリング遅延によるラグフレームの集計
+    RenderingLag,
+    /// 帯域不足によるドロップフレームの集計
+    NetworkDrops,
+    /// 出力の異常停止
+    OutputStopped,
+}
+
+/// ログから検出したイベント
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsLogEvent {
+    /// イベントの種類
+    pub kind: ObsLogEventKind,
+    /// 行の時刻（"HH:MM:SS"、時刻のない行はNone）
+    pub time: Option<String>,
+    /// 行の日時（ログの開始日時が分かる場合のみ、UNIX epoch秒）
+    pub timestamp: Option<i64>,
+    /// 対象フレーム数
+    pub frames: Option<u64>,
+    /// 対象フレームの割合（%）
+    pub percent: Option<f64>,
+    /// 出力停止コード（`OutputStopped` のみ）
+    pub code: Option<i32>,
+}
+
+/// OBSログの解析結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsLogAnalysis {
+    /// 解析したログファイルのパス
+    pub log_path: String,
+    /// ログの開始日時（ファイル名から取得できない場合はNone、UNIX epoch秒）
+    pub log_started_at: Option<i64>,
+    /// 検出された問題
+    pub problems: Vec<ProblemReport>,
+}
+
+/// OBSの設定ディレクトリを取得
+pub fn obs_config_dir() -> Result<PathBuf, AppError> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+    Ok(config_dir.join(OBS_CONFIG_DIR_NAME))
+}
+
+/// OBSのログディレクトリを取得
+pub fn obs_logs_dir() -> Result<PathBuf, AppError> {
+    Ok(obs_config_dir()?.join(OBS_LOGS_DIR_NAME))
+}
+
+/// ログディレクトリ内の最新のログファイルを取得
+///
+/// ファイル名が開始日時のため、名前順で最後のものを最新とする
+pub fn latest_log_file(dir: &Path) -> Result<PathBuf, AppError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        AppError::analyzer_error(&format!("OBSのログディレクトリを開けませんでした（{}）: {e}", dir.display()))
+    })?;
+
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .max_by(|a, b| a.file_name().cmp(&b.file_name()))
+        .ok_or_else(|| AppError::analyzer_error("OBSのログファイルが見つかりませんでした"))
+}
+
+/// ログファイル名から開始日時を取得（例: "2024-05-01 20-15-30.txt"）
+pub fn log_started_at(path: &Path) -> Option<NaiveDateTime> {
+    let stem = path.file_stem()?.to_str()?;
+    NaiveDateTime::parse_from_str(stem, LOG_FILE_NAME_FORMAT).ok()
+}
+
+/// ログファイルを解析して問題レポートに変換
+///
+/// # Arguments
+/// * `path` - ログファイルのパス
+pub fn analyze_obs_log_file(path: &Path) -> Result<ObsLogAnalysis, AppError> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        AppError::analyzer_error(&format!("OBSのログファイルを読み込めませんでした（{}）: {e}", path.display()))
+    })?;
+    // OBSのログは基本的にUTF-8だが、ソース名等に不正なバイトが含まれても解析を続ける
+    let content = String::from_utf8_lossy(&bytes);
+
+    let started = log_started_at(path);
+    let events = parse_obs_log(&content, started);
+
+    Ok(ObsLogAnalysis {
+        log_path: path.display().to_string(),
+        log_started_at: started.and_then(local_timestamp),
+        problems: events_to_problems(&events),
+    })
+}
+
+/// ログ本文から、OBSが起動時に報告した利用可能な映像エンコーダーのIDを抽出
+///
+/// OBSは起動時に "Available Encoders:" → "Video Encoders:" の下へ "- <ID> (<表示名>)" の形式で列挙する。
+/// プラグインが読み込めなかった・ドライバーがないエンコーダーはここに含まれない
+pub fn parse_available_video_encoders(content: &str) -> Vec<String> {
+    let mut encoders = Vec::new();
+    let mut in_available = false;
+    let mut in_video = false;
+
+    for line in content.lines() {
+        let message = split_line_time(line).1.trim();
+        if message == MARKER_AVAILABLE_ENCODERS {
+            in_available = true;
+            in_video = false;
+        } else if in_available && message == MARKER_VIDEO_ENCODERS {
+            in_video = true;
+        } else if in_video {
+            // 次の見出し（"Audio Encoders:" 等）または一覧以外の行で映像エンコーダーの一覧は終わる
+            let Some(id) = message.strip_prefix("- ").and_then(|rest| rest.split_whitespace().next()) else {
+                break;
+            };
+            encoders.push(id.to_string());
+        }
+    }
+
+    encoders
+}
+
+/// 最新のOBSログから、OBSが報告した利用可能な映像エンコーダーのIDを取得
+///
+/// OBSの起動中は最新のログが現在のセッションのログになる。
+/// ログが読めない場合はエラー、一覧が記録されていない場合は空を返す
+pub fn reported_video_encoders() -> Result<Vec<String>, AppError> {
+    let path = latest_log_file(&obs_logs_dir()?)?;
+    let bytes = std::fs::read(&path).map_err(|e| {
+        AppError::analyzer_error(&format!("OBSのログファイルを読み込めませんでした（{}）: {e}", path.display()))
+    })?;
+    Ok(parse_available_video_encoders(&String::from_utf8_lossy(&bytes)))
+}
+
+/// ログ本文からイベントを抽出
+///
+/// # Arguments
+/// * `content` - ログ本文
+/// * `started` - ログの開始日時（各行の時刻を日時に変換するために使用）
+pub fn parse_obs_log(content: &str, started: Option<NaiveDateTime>) -> Vec<ObsLogEvent> {
+    let mut events = Vec::new();
+    let mut date = started.map(|s| s.date());
+    let mut previous_time: Option<NaiveTime> = None;
+
+    for line in content.lines() {
+        let (time, message) = split_line_time(line);
+
+        // 時刻が巻き戻った場合は日付をまたいだとみなす
+        if let Some(time) = time {
+            if previous_time.is_some_and(|prev| time < prev) {
+                date = date.and_then(|d| d.checked_add_signed(Duration::days(1)));
+            }
+            previous_time = Some(time);
+        }
+
+        let Some(mut event) = parse_message(message) else {
+            continue;
+        };
+        event.time = time.map(|t| t.format("%H:%M:%S").to_string());
+        event.timestamp = time
+            .zip(date)
+            .and_then(|(time, date): (NaiveTime, NaiveDate)| local_timestamp(date.and_time(time)));
+        events.push(event);
+    }
+
+    events
+}
+
+/// 行頭の時刻を分離（時刻のない行はメッセージ全体を返す）
+fn split_line_time(line: &str) -> (Option<NaiveTime>, &str) {
+    line.split_once(": ")
+        .and_then(|(prefix, message)| {
+            NaiveTime::parse_from_str(prefix.trim(), LOG_LINE_TIME_FORMAT)
+                .ok()
+                .map(|time| (Some(time), message))
+        })
+        .unwrap_or((None, line))
+}
+
+/// 1行分のメッセージからイベントを抽出
+fn parse_message(message: &str) -> Option<ObsLogEvent> {
+    let event = |kind: ObsLogEventKind| ObsLogEvent {
+        kind,
+        time: None,
+        timestamp: None,
+        frames: None,
+        percent: None,
+        code: None,
+    };
+
+    let summaries = [
+        (MARKER_ENCODING_LAG, ObsLogEventKind::EncodingLag),
+        (MARKER_RENDERING_LAG, ObsLogEventKind::RenderingLag),
+        (MARKER_NETWORK_DROPS, ObsLogEventKind::NetworkDrops),
+    ];
+    for (marker, kind) in summaries {
+        if let Some((_, rest)) = message.split_once(marker) {
+            let (frames, percent) = parse_frame_summary(rest);
+            return Some(ObsLogEvent {
+                frames,
+                percent,
+                ..event(kind)
+            });
+        }
+    }
+
+    if message.to_lowercase().contains(MARKER_ENCODING_OVERLOADED) {
+        return Some(event(ObsLogEventKind::EncodingOverloaded));
+    }
+
+    if message.contains(MARKER_CONNECTION_FAILED) {
+        // "[rtmp stream: 'simple_stream'] Connection to rtmp://... failed: -2"
+        let code = message
+            .rsplit_once("failed: ")
+            .and_then(|(_, code)| code.trim().parse::<i32>().ok())?;
+        return Some(ObsLogEvent {
+            code: Some(code),
+            ..event(ObsLogEventKind::OutputStopped)
+        });
+    }
+
+    if message.contains(MARKER_DISCONNECTED) {
+        return Some(ObsLogEvent {
+            code: Some(OBS_OUTPUT_DISCONNECTED),
+            ..event(ObsLogEventKind::OutputStopped)
+        });
+    }
+
+    None
+}
+
+/// フレーム集計行の数値部分を解析（例: "1200 (6.7%)" / "1200/18000 (6.7%)"）
+fn parse_frame_summary(rest: &str) -> (Option<u64>, Option<f64>) {
+    let frames = rest
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .next()
+        .and_then(|count| count.parse::<u64>().ok());
+    let percent = rest
+        .split_once('(')
+        .and_then(|(_, value)| value.split_once('%'))
+        .and_then(|(value, _)| value.trim().parse::<f64>().ok());
+    (frames, percent)
+}
+
+/// ローカル日時をUNIX epoch秒に変換
+fn local_timestamp(datetime: NaiveDateTime) -> Option<i64> {
+    chrono::Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|dt| dt.timestamp())
+}
+
+/// OBSの出力停止コードの説明
+fn output_code_description(code: i32) -> &'static str {
+    match code {
+        -1 => "配信先のURLが不正です",
+        -2 => "配信サーバーに接続できませんでした",
+        -3 => "ストリームキーが不正か、配信サーバーに拒否されました",
+        -4 => "配信中にエラーが発生しました",
+        -5 => "配信サーバーとの接続が切断されました",
+        -6 => "配信サーバーが対応していない設定です",
+        -7 => "ディスクの空き容量が不足しています",
+        -8 => "エンコーダーでエラーが発生しました",
+        _ => "出力が異常終了しました",
+    }
+}
+
+/// フレーム損失率から重要度を決定（閾値未満はNone）
+fn frame_loss_severity(percent: f64) -> Option<AlertSeverity> {
+    if percent >= FRAME_LOSS_CRITICAL_PERCENT {
+        Some(AlertSeverity::Critical)
+    } else if percent >= FRAME_LOSS_WARNING_PERCENT {
+        Some(AlertSeverity::Warning)
+    } else {
+        None
+    }
+}
+
+/// イベントを問題レポートに変換
+///
+/// フレーム集計は出力ごと・映像全体で同じ内容が複数行出力されるため、
+/// 種類ごとに損失率が最も高い行のみを採用する
+pub fn events_to_problems(events: &[ObsLogEvent]) -> Vec<ProblemReport> {
+    let mut problems = Vec::new();
+
+    let summary_kinds = [
+        ObsLogEventKind::EncodingLag,
+        ObsLogEventKind::RenderingLag,
+        ObsLogEventKind::NetworkDrops,
+    ];
+    for kind in summary_kinds {
+        let worst = events
+            .iter()
+            .filter(|e| e.kind == kind)
+            .filter_map(|e| e.percent.map(|percent| (e, percent)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((event, percent)) = worst else {
+            continue;
+        };
+        let Some(severity) = frame_loss_severity(percent) else {
+            continue;
+        };
+        let frames = event.frames.unwrap_or_default();
+
+        let (category, metric, title, cause, actions) = match kind {
+            ObsLogEventKind::EncodingLag => (
+                ProblemCategory::Encoding,
+                MetricType::FrameDropRate,
+                "OBSログ: エンコード遅延によるフレームスキップ",
+                "エンコーダーの処理が追いつかず",
+                vec![
+                    "エンコーダーのプリセットを軽量なものに変更".to_string(),
+                    "出力解像度またはFPSを下げる".to_string(),
+                ],
+            ),
+            ObsLogEventKind::RenderingLag => (
+                ProblemCategory::Resource,
+                MetricType::GpuUsage,
+                "OBSログ: レンダリング遅延によるフレーム遅延",
+                "GPUの描画が間に合わず",
+                vec![
+                    "ゲームのフレームレートを制限してGPUに余裕を持たせる".to_string(),
+                    "OBSを管理者として実行し、GPUスケジューリングの優先度を上げる".to_string(),
+                ],
+            ),
+            _ => (
+                ProblemCategory::Network,
+                MetricType::NetworkBandwidth,
+                "OBSログ: 帯域不足によるフレームドロップ",
+                "回線の帯域不足や接続の停滞により",
+                vec![
+                    "配信ビットレートを下げる".to_string(),
+                    "有線LANで接続する".to_string(),
+                ],
+            ),
+        };
+
+        problems.push(log_problem(
+            event,
+            category,
+            severity,
+            title,
+            format!("{cause}{frames}フレーム（{percent:.1}%）が失われました。"),
+            actions,
+            metric,
+        ));
+    }
+
+    for event in events {
+        match event.kind {
+            ObsLogEventKind::EncodingOverloaded => problems.push(log_problem(
+                event,
+                ProblemCategory::Encoding,
+                AlertSeverity::Warning,
+                "OBSログ: エンコードの過負荷",
+                "OBSがエンコードの過負荷を検出しました。".to_string(),
+                vec![
+                    "エンコーダーのプリセットを軽量なものに変更".to_string(),
+                    "ハードウェアエンコーダー（NVENC等）に切り替える".to_string(),
+                ],
+                MetricType::FrameDropRate,
+            )),
+            ObsLogEventKind::OutputStopped => {
+                let code = event.code.unwrap_or_default();
+                problems.push(log_problem(
+                    event,
+                    ProblemCategory::Network,
+                    AlertSeverity::Critical,
+                    "OBSログ: 配信出力の異常停止",
+                    format!("{}（コード: {code}）。", output_code_description(code)),
+                    vec![
+                        "OBSの「設定 → 配信」でサーバーURLとストリームキーを確認".to_string(),
+                        "回線の状態を確認し、自動再接続を有効にする".to_string(),
+                    ],
+                    MetricType::NetworkBandwidth,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    problems
+}
+
+/// ログのイベントから問題レポートを作成（説明の先頭にログの時刻を付ける）
+fn log_problem(
+    event: &ObsLogEvent,
+    category: ProblemCategory,
+    severity: AlertSeverity,
+    title: &str,
+    description: String,
+    suggested_actions: Vec<String>,
+    affected_metric: MetricType,
+) -> ProblemReport {
+    let description = match &event.time {
+        Some(time) => format!("[{time}] {description}"),
+        None => description,
+    };
+    ProblemReport {
+        id: Uuid::new_v4().to_string(),
+        category,
+        severity,
+        title: title.to_string(),
+        description,
+        suggested_actions,
+        affected_metric,
+        detected_at: event.timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// エンコード過負荷で配信を終了したときのログ
+    const ENCODING_OVERLOAD_LOG: &str = "\
+20:15:31.120: ==== Streaming Start ===============================================
+20:42:10.004: Encoding overloaded! Consider turning down video settings or using a faster encoding preset.
+21:03:55.310: [rtmp stream: 'adv_stream'] User stopped the stream
+21:03:55.311: Output 'adv_stream': stopping
+21:03:55.311: Output 'adv_stream': Total frames output: 150000
+21:03:55.311: Output 'adv_stream': Total drawn frames: 157470 (157500 attempted)
+21:03:55.311: Output 'adv_stream': Number of lagged frames due to rendering lag/stalls: 30 (0.0%)
+21:03:55.312: Output 'adv_stream': Number of skipped frames due to encoding lag: 7500 (4.8%)
+21:03:55.312: Video stopped, number of skipped frames due to encoding lag: 7500/157500 (4.8%)
+21:03:55.400: ==== Streaming Stop ================================================
+";
+
+    /// 回線が不安定で切断されたときのログ
+    const NETWORK_DISCONNECT_LOG: &str = "\
+23:58:00.000: ==== Streaming Start ===============================================
+00:20:11.532: [rtmp stream: 'simple_stream'] Disconnected from rtmp://live.twitch.tv/app
+00:20:11.533: Output 'simple_stream': Reconnecting in 2.00 seconds..
+00:31:40.001: Output 'simple_stream': stopping
+00:31:40.001: Output 'simple_stream': Number of dropped frames due to insufficient bandwidth/connection stalls: 1520 (1.2%)
+";
+
+    /// ストリームキー不正で接続に失敗したときのログ
+    const CONNECTION_FAILED_LOG: &str = "\
+19:00:00.100: [rtmp stream: 'simple_stream'] Connecting to RTMP URL rtmp://a.rtmp.youtube.com/live2...
+19:00:01.250: [rtmp stream: 'simple_stream'] Connection to rtmp://a.rtmp.youtube.com/live2 failed: -3
+";
+
+    /// レンダリング遅延が大きいときのログ
+    const RENDERING_LAG_LOG: &str = "\
+18:30:00.000: Output 'adv_stream': Number of lagged frames due to rendering lag/stalls: 9000 (5.0%)
+18:30:00.000: Output 'adv_stream': Number of skipped frames due to encoding lag: 12 (0.0%)
+";
+
+    fn started(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, LOG_FILE_NAME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_encoding_overload_log() {
+        let events = parse_obs_log(ENCODING_OVERLOAD_LOG, None);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ObsLogEventKind::EncodingOverloaded,
+                ObsLogEventKind::RenderingLag,
+                ObsLogEventKind::EncodingLag,
+                ObsLogEventKind::EncodingLag,
+            ]
+        );
+        assert_eq!(events[0].time.as_deref(), Some("20:42:10"));
+        assert_eq!(events[2].frames, Some(7500));
+        assert_eq!(events[2].percent, Some(4.8));
+        assert_eq!(events[3].frames, Some(7500));
+
+        let problems = events_to_problems(&events);
+        // 集計行は種類ごとに1件、レンダリング遅延は閾値未満のため除外
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].category, ProblemCategory::Encoding);
+        assert_eq!(problems[0].severity, AlertSeverity::Critical);
+        assert!(problems[0].description.contains("7500フレーム（4.8%）"));
+        assert_eq!(problems[1].severity, AlertSeverity::Warning);
+        assert!(problems[1].description.starts_with("[20:42:10]"));
+    }
+
+    #[test]
+    fn test_parse_network_disconnect_log() {
+        let events = parse_obs_log(NETWORK_DISCONNECT_LOG, Some(started("2024-05-01 23-57-59")));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, ObsLogEventKind::OutputStopped);
+        assert_eq!(events[0].code, Some(OBS_OUTPUT_DISCONNECTED));
+        assert_eq!(events[1].kind, ObsLogEventKind::NetworkDrops);
+        assert_eq!(events[1].frames, Some(1520));
+
+        // 日付をまたいだ行は翌日の日時になる
+        let expected = local_timestamp(started("2024-05-02 00-20-11")).unwrap();
+        assert_eq!(events[0].timestamp, Some(expected));
+
+        let problems = events_to_problems(&events);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].category, ProblemCategory::Network);
+        assert_eq!(problems[0].severity, AlertSeverity::Warning);
+        assert_eq!(problems[1].severity, AlertSeverity::Critical);
+        assert!(problems[1].description.contains("切断"));
+        assert_eq!(problems[1].detected_at, expected);
+    }
+
+    #[test]
+    fn test_parse_connection_failed_log() {
+        let events = parse_obs_log(CONNECTION_FAILED_LOG, None);
+        assert_eq!(events.len(), 1, "接続開始の行はイベントにしない");
+        assert_eq!(events[0].code, Some(-3));
+
+        let problems = events_to_problems(&events);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, AlertSeverity::Critical);
+        assert!(problems[0].description.contains("ストリームキー"));
+        assert!(problems[0].description.contains("コード: -3"));
+    }
+
+    #[test]
+    fn test_parse_rendering_lag_log() {
+        let problems = events_to_problems(&parse_obs_log(RENDERING_LAG_LOG, None));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].category, ProblemCategory::Resource);
+        assert_eq!(problems[0].affected_metric, MetricType::GpuUsage);
+        assert_eq!(problems[0].severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_clean_log_has_no_problems() {
+        let log = "\
+20:00:00.000: ==== Streaming Start ===============================================
+21:00:00.000: [rtmp stream: 'adv_stream'] User stopped the stream
+21:00:00.001: Output 'adv_stream': Number of skipped frames due to encoding lag: 0 (0.0%)
+21:00:00.001: Output 'adv_stream': Number of dropped frames due to insufficient bandwidth/connection stalls: 0 (0.0%)
+";
+        assert!(events_to_problems(&parse_obs_log(log, None)).is_empty());
+        assert!(parse_obs_log("", None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_available_video_encoders() {
+        let log = "\
+19:00:00.100: Available Encoders:
+19:00:00.100:   Video Encoders:
+19:00:00.100: \t- jim_nvenc (NVIDIA NVENC H.264)
+19:00:00.100: \t- obs_x264 (x264)
+19:00:00.100:   Audio Encoders:
+19:00:00.100: \t- ffmpeg_aac (FFmpeg AAC)
+19:00:00.200: ==== Startup complete ===============================================
+";
+        assert_eq!(parse_available_video_encoders(log), vec!["jim_nvenc", "obs_x264"]);
+        assert!(parse_available_video_encoders(ENCODING_OVERLOAD_LOG).is_empty());
+    }
+
+    #[test]
+    fn test_log_started_at_from_file_name() {
+        let path = Path::new("/tmp/obs-studio/logs/2024-05-01 20-15-30.txt");
+        assert_eq!(log_started_at(path), Some(started("2024-05-01 20-15-30")));
+        assert_eq!(log_started_at(Path::new("crash.txt")), None);
+    }
+
+    #[test]
+    fn test_latest_log_file_and_analyze() {
+        let dir = std::env::temp_dir().join(format!("obs_log_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("2024-04-30 19-00-00.txt"), CONNECTION_FAILED_LOG).unwrap();
+        std::fs::write(dir.join("2024-05-01 20-15-30.txt"), ENCODING_OVERLOAD_LOG).unwrap();
+        std::fs::write(dir.join("notes.md"), "not a log").unwrap();
+
+        let latest = latest_log_file(&dir).unwrap();
+        assert!(latest.ends_with("2024-05-01 20-15-30.txt"));
+
+        let analysis = analyze_obs_log_file(&latest).unwrap();
+        assert_eq!(analysis.problems.len(), 2);
+        assert_eq!(analysis.log_started_at, local_timestamp(started("2024-05-01 20-15-30")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(latest_log_file(&dir).is_err());
+        assert!(analyze_obs_log_file(&latest).is_err());
+    }
+}
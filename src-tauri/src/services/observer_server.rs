@@ -0,0 +1,603 @@
+// オブザーバーモード（モデレーター・ヘルパー向けの読み取り専用HTTPサーバー）
+//
+// 配信者以外のメンバーがブラウザや外部ツールから配信状況を確認できるよう、
+// 現在のメトリクス・アラート・セッションをJSONで返す。
+//
+// - 既定では起動しない（`start_observer_server` コマンドで起動した場合のみ待ち受ける）
+// - すべてのエンドポイントに起動ごとに生成するトークンが必要
+//   （`Authorization: Bearer <token>` または `?token=<token>`）
+// - GETのみ受け付け、設定の変更やOBSの操作を行うエンドポイントは持たない
+
+use crate::error::AppError;
+use crate::obs::get_obs_client;
+use crate::services::alerts::{get_alert_engine, Alert};
+use crate::services::streaming_overhead::StreamingOverhead;
+use crate::storage::metrics_history::{get_metrics_history_store, SessionSummary};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, JoinSet};
+
+/// リクエストヘッダーの最大サイズ
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+
+/// リクエストの受信のタイムアウト
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 起動ごとに生成するアクセストークン
+static OBSERVER_TOKEN: Lazy<String> = Lazy::new(|| uuid::Uuid::new_v4().simple().to_string());
+
+/// 最新のメトリクス（監視ティックごとに更新）
+static LATEST_METRICS: Lazy<Mutex<Option<ObserverMetrics>>> = Lazy::new(|| Mutex::new(None));
+
+/// 起動中のサーバー
+static OBSERVER_SERVER: Lazy<tokio::sync::Mutex<Option<ObserverServerHandle>>> =
+    Lazy::new(|| tokio::sync::Mutex::new(None));
+
+/// `/metrics/current` で返すメトリクス
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObserverMetrics {
+    /// 計測時刻（UNIX epoch秒）
+    pub timestamp: i64,
+    /// CPU使用率（0-100%）
+    pub cpu_usage: f32,
+    /// メモリ使用率（0-100%）
+    pub memory_usage_percent: f32,
+    /// GPU使用率（0-100%、取得できない場合はNone）
+    pub gpu_usage: Option<f32>,
+    /// ハードウェアエンコーダーの使用率（0-100%、取得できない場合はNone）
+    pub encoder_usage: Option<f32>,
+    /// アップロード速度（kbps）
+    pub upload_kbps: f64,
+    /// OBSの負荷（OBSが起動していない場合はNone）
+    pub overhead: Option<StreamingOverhead>,
+}
+
+/// 起動中のサーバーの情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObserverServerInfo {
+    /// 待ち受けアドレス
+    pub address: String,
+    /// ブラウザで開くためのURL（トークンを含む `/health` のURL）
+    pub url: String,
+    /// アクセストークン（アプリの起動ごとに変わる）
+    pub token: String,
+}
+
+/// エンドポイント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObserverRoute {
+    Health,
+    CurrentMetrics,
+    Alerts,
+    CurrentSession,
+}
+
+/// 受信したリクエストのうち、ルーティングと認証に使う部分
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ObserverRequest {
+    method: String,
+    path: String,
+    /// クエリパラメーター `token` の値
+    query_token: Option<String>,
+    /// `Authorization: Bearer` ヘッダーの値
+    bearer_token: Option<String>,
+}
+
+/// 返すレスポンス
+#[derive(Debug, PartialEq, Eq)]
+struct ObserverResponse {
+    status: u16,
+    /// JSONの本文
+    body: String,
+    /// 追加のヘッダー行（`Name: value`）
+    extra_header: Option<&'static str>,
+}
+
+impl ObserverResponse {
+    fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+            extra_header: None,
+        }
+    }
+
+    fn error(status: u16, error: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": error }))
+    }
+
+    const fn with_header(mut self, header: &'static str) -> Self {
+        self.extra_header = Some(header);
+        self
+    }
+
+    /// HTTP/1.1のレスポンスに変換
+    ///
+    /// ブラウザに保存されないよう `no-store` を指定し、1リクエストごとに接続を閉じる
+    fn to_http(&self) -> String {
+        let extra = self.extra_header.map(|header| format!("{header}\r\n")).unwrap_or_default();
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n{extra}\r\n{}",
+            self.status,
+            reason_phrase(self.status),
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+const fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// このアプリの起動で使うアクセストークンを取得
+pub fn observer_token() -> &'static str {
+    OBSERVER_TOKEN.as_str()
+}
+
+/// 監視ティックのメトリクスを記録
+pub fn record_observer_metrics(metrics: ObserverMetrics) {
+    *LATEST_METRICS.lock().unwrap_or_else(PoisonError::into_inner) = Some(metrics);
+}
+
+/// 最新のメトリクスを取得（監視前の場合はNone）
+fn latest_observer_metrics() -> Option<ObserverMetrics> {
+    LATEST_METRICS.lock().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// トークンを比較（一致しない位置によって処理時間が変わらないようにする）
+fn tokens_match(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .bytes()
+            .zip(actual.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// リクエストヘッダーを解析（不正な形式の場合はNone）
+fn parse_request(head: &str) -> Option<ObserverRequest> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query_token = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(ToString::to_string);
+    let bearer_token = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .and_then(|(_, value)| value.trim().strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    Some(ObserverRequest {
+        method,
+        path: path.to_string(),
+        query_token,
+        bearer_token,
+    })
+}
+
+/// 認証・メソッド・パスを確認し、エンドポイントを決定
+///
+/// 認証を最初に確認し、トークンのないリクエストにはエンドポイントの有無も返さない
+fn route_request(request: &ObserverRequest, token: &str) -> Result<ObserverRoute, ObserverResponse> {
+    let authorized = [&request.bearer_token, &request.query_token]
+        .into_iter()
+        .flatten()
+        .any(|candidate| tokens_match(token, candidate));
+    if !authorized {
+        return Err(ObserverResponse::error(401, "unauthorized").with_header("WWW-Authenticate: Bearer"));
+    }
+    if request.method != "GET" {
+        return Err(ObserverResponse::error(405, "method not allowed").with_header("Allow: GET"));
+    }
+
+    match request.path.trim_end_matches('/') {
+        "/health" => Ok(ObserverRoute::Health),
+        "/metrics/current" => Ok(ObserverRoute::CurrentMetrics),
+        "/alerts" => Ok(ObserverRoute::Alerts),
+        "/session/current" => Ok(ObserverRoute::CurrentSession),
+        _ => Err(ObserverResponse::error(404, "not found")),
+    }
+}
+
+/// アクティブなアラートを取得（アラートエンジンの初期化前は空）
+async fn active_alerts() -> Vec<Alert> {
+    let Some(engine_arc) = get_alert_engine().await else {
+        return Vec::new();
+    };
+    let engine_option = engine_arc.read().await;
+    match engine_option.as_ref() {
+        Some(engine) => engine.get_active_alerts().await,
+        None => Vec::new(),
+    }
+}
+
+/// 配信中のセッションを取得（セッションがない・履歴DBを開けない場合はNone）
+async fn current_session() -> Option<SessionSummary> {
+    let store = get_metrics_history_store().ok()?;
+    store.initialize().await.ok()?;
+    let session_id = store.current_session_id().await?;
+    store.get_session(&session_id).await.ok().flatten()
+}
+
+/// エンドポイントの応答を生成
+async fn respond(route: ObserverRoute) -> ObserverResponse {
+    let body = match route {
+        ObserverRoute::Health => {
+            let client = get_obs_client();
+            let status = if client.is_connected().await {
+                client.get_status().await.ok()
+            } else {
+                None
+            };
+            serde_json::json!({
+                "status": "ok",
+                "timestamp": chrono::Utc::now().timestamp(),
+                "obsConnected": status.is_some(),
+                "streaming": status.as_ref().is_some_and(|s| s.streaming),
+                "recording": status.as_ref().is_some_and(|s| s.recording),
+            })
+        }
+        ObserverRoute::CurrentMetrics => serde_json::json!(latest_observer_metrics()),
+        ObserverRoute::Alerts => serde_json::json!(active_alerts().await),
+        ObserverRoute::CurrentSession => serde_json::json!(current_session().await),
+    };
+    ObserverResponse::json(200, &body)
+}
+
+/// リクエストヘッダーを空行まで読み取る
+///
+/// # Returns
+/// ヘッダー（上限を超えた場合はNone）
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            return Ok(None);
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(Some(String::from_utf8_lossy(&head).into_owned()))
+}
+
+/// 1つの接続を処理
+async fn handle_connection(mut stream: TcpStream, token: Arc<str>) {
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream)).await {
+        Ok(Ok(Some(head))) => match parse_request(&head) {
+            Some(request) => match route_request(&request, &token) {
+                Ok(route) => respond(route).await,
+                Err(response) => response,
+            },
+            None => ObserverResponse::error(400, "bad request"),
+        },
+        Ok(Ok(None)) => ObserverResponse::error(431, "request header too large"),
+        // 読み取りエラー・タイムアウトは応答せずに切断する
+        Ok(Err(_)) | Err(_) => return,
+    };
+
+    if let Err(e) = stream.write_all(response.to_http().as_bytes()).await {
+        tracing::debug!(target: "observer", error = %e, "レスポンスの送信に失敗");
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// 起動したサーバーのハンドル
+pub struct ObserverServerHandle {
+    local_addr: SocketAddr,
+    shutdown_tx: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl ObserverServerHandle {
+    /// 実際の待ち受けアドレス（ポート0を指定した場合は割り当てられたポート）
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// 待ち受けを停止し、処理中の接続を切断する
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        if let Err(e) = self.task.await {
+            tracing::debug!(target: "observer", error = %e, "サーバーのタスクが異常終了");
+        }
+    }
+}
+
+/// サーバーを起動
+///
+/// # Arguments
+/// * `addr` - 待ち受けアドレス
+/// * `token` - アクセストークン
+pub async fn spawn_observer_server(addr: SocketAddr, token: String) -> std::io::Result<ObserverServerHandle> {
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+    let token: Arc<str> = Arc::from(token);
+
+    let task = tokio::spawn(async move {
+        let mut connections = JoinSet::new();
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        connections.spawn(handle_connection(stream, Arc::clone(&token)));
+                    }
+                    Err(e) => tracing::debug!(target: "observer", error = %e, "接続の受け付けに失敗"),
+                },
+            }
+            while connections.try_join_next().is_some() {}
+        }
+        connections.shutdown().await;
+    });
+
+    Ok(ObserverServerHandle {
+        local_addr,
+        shutdown_tx,
+        task,
+    })
+}
+
+fn server_info(local_addr: SocketAddr) -> ObserverServerInfo {
+    let token = observer_token().to_string();
+    ObserverServerInfo {
+        address: local_addr.to_string(),
+        url: format!("http://{local_addr}/health?token={token}"),
+        token,
+    }
+}
+
+/// オブザーバーモードを開始
+///
+/// すでに起動している場合は停止してから指定したアドレスで起動し直す
+///
+/// # Arguments
+/// * `bind_address` - 待ち受けアドレス（`ホスト:ポート`）
+pub async fn start_observer(bind_address: &str) -> Result<ObserverServerInfo, AppError> {
+    let addr: SocketAddr = bind_address.trim().parse().map_err(|_| {
+        AppError::validation_failed(&format!("待ち受けアドレスが不正です: {bind_address}"))
+    })?;
+
+    let mut server = OBSERVER_SERVER.lock().await;
+    if let Some(running) = server.take() {
+        running.shutdown().await;
+    }
+    let handle = spawn_observer_server(addr, observer_token().to_string())
+        .await
+        .map_err(|e| AppError::observer_server(&format!("{addr} で待ち受けできません: {e}")))?;
+    let info = server_info(handle.local_addr());
+    *server = Some(handle);
+
+    if !addr.ip().is_loopback() {
+        tracing::warn!(target: "observer", address = %info.address, "オブザーバーモードをローカルホスト以外で公開しています");
+    }
+    tracing::info!(target: "observer", address = %info.address, "オブザーバーモードを開始しました");
+    Ok(info)
+}
+
+/// オブザーバーモードを停止
+///
+/// # Returns
+/// 起動していたサーバーを停止した場合はtrue
+pub async fn stop_observer() -> bool {
+    let Some(running) = OBSERVER_SERVER.lock().await.take() else {
+        return false;
+    };
+    running.shutdown().await;
+    tracing::info!(target: "observer", "オブザーバーモードを停止しました");
+    true
+}
+
+/// 起動中のサーバーの情報を取得（停止中の場合はNone）
+pub async fn observer_status() -> Option<ObserverServerInfo> {
+    OBSERVER_SERVER
+        .lock()
+        .await
+        .as_ref()
+        .map(|handle| server_info(handle.local_addr()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+    fn request(method: &str, path: &str, bearer: Option<&str>, query: Option<&str>) -> ObserverRequest {
+        ObserverRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query_token: query.map(ToString::to_string),
+            bearer_token: bearer.map(ToString::to_string),
+        }
+    }
+
+    /// サーバーにリクエストを送り、レスポンス全体を返す
+    async fn send(addr: SocketAddr, raw: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(raw.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn test_parse_request_extracts_tokens() {
+        let parsed = parse_request(&format!(
+            "GET /alerts?foo=1&token={TOKEN} HTTP/1.1\r\nHost: localhost\r\nauthorization: Bearer abc\r\n\r\n"
+        ))
+        .unwrap();
+        assert_eq!(parsed.method, "GET");
+        assert_eq!(parsed.path, "/alerts");
+        assert_eq!(parsed.query_token.as_deref(), Some(TOKEN));
+        assert_eq!(parsed.bearer_token.as_deref(), Some("abc"));
+
+        assert!(parse_request("GET /alerts\r\n\r\n").is_none());
+        assert!(parse_request("garbage").is_none());
+    }
+
+    #[test]
+    fn test_route_request_requires_token() {
+        let missing = route_request(&request("GET", "/health", None, None), TOKEN).unwrap_err();
+        assert_eq!(missing.status, 401);
+
+        let wrong = route_request(&request("GET", "/health", Some("x".repeat(32).as_str()), None), TOKEN).unwrap_err();
+        assert_eq!(wrong.status, 401);
+
+        // トークンがない場合は存在しないパスでも404を返さない
+        let unknown = route_request(&request("GET", "/nope", None, None), TOKEN).unwrap_err();
+        assert_eq!(unknown.status, 401);
+
+        assert_eq!(
+            route_request(&request("GET", "/health", Some(TOKEN), None), TOKEN).unwrap(),
+            ObserverRoute::Health
+        );
+        assert_eq!(
+            route_request(&request("GET", "/metrics/current", None, Some(TOKEN)), TOKEN).unwrap(),
+            ObserverRoute::CurrentMetrics
+        );
+    }
+
+    #[test]
+    fn test_route_request_is_read_only() {
+        for method in ["POST", "PUT", "PATCH", "DELETE"] {
+            let response = route_request(&request(method, "/alerts", Some(TOKEN), None), TOKEN).unwrap_err();
+            assert_eq!(response.status, 405);
+            assert_eq!(response.extra_header, Some("Allow: GET"));
+        }
+
+        assert_eq!(
+            route_request(&request("GET", "/session/current/", Some(TOKEN), None), TOKEN).unwrap(),
+            ObserverRoute::CurrentSession
+        );
+        let unknown = route_request(&request("GET", "/config", Some(TOKEN), None), TOKEN).unwrap_err();
+        assert_eq!(unknown.status, 404);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match(TOKEN, TOKEN));
+        assert!(!tokens_match(TOKEN, &TOKEN[..31]));
+        assert!(!tokens_match(TOKEN, "1123456789abcdef0123456789abcdef"));
+        assert!(!tokens_match(TOKEN, ""));
+    }
+
+    #[test]
+    fn test_response_format() {
+        let http = ObserverResponse::error(401, "unauthorized")
+            .with_header("WWW-Authenticate: Bearer")
+            .to_http();
+        assert!(http.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(http.contains("Cache-Control: no-store\r\n"));
+        assert!(http.contains("WWW-Authenticate: Bearer\r\n\r\n"));
+        assert!(http.ends_with(r#"{"error":"unauthorized"}"#));
+    }
+
+    #[test]
+    fn test_observer_token_is_stable_per_launch() {
+        assert_eq!(observer_token().len(), 32);
+        assert_eq!(observer_token(), observer_token());
+    }
+
+    #[tokio::test]
+    async fn test_server_serves_authorized_requests() {
+        record_observer_metrics(ObserverMetrics {
+            timestamp: 1_700_000_000,
+            cpu_usage: 42.0,
+            memory_usage_percent: 50.0,
+            gpu_usage: None,
+            encoder_usage: None,
+            upload_kbps: 6000.0,
+            overhead: None,
+        });
+        let server = spawn_observer_server("127.0.0.1:0".parse().unwrap(), TOKEN.to_string())
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+
+        let unauthorized = send(addr, "GET /metrics/current HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(unauthorized.starts_with("HTTP/1.1 401"));
+        assert!(!unauthorized.contains("cpuUsage"));
+
+        let metrics = send(
+            addr,
+            &format!("GET /metrics/current HTTP/1.1\r\nAuthorization: Bearer {TOKEN}\r\n\r\n"),
+        )
+        .await;
+        assert!(metrics.starts_with("HTTP/1.1 200 OK\r\n"));
+        let body: serde_json::Value = serde_json::from_str(metrics.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["uploadKbps"], 6000.0);
+
+        let health = send(addr, &format!("GET /health?token={TOKEN} HTTP/1.1\r\n\r\n")).await;
+        assert!(health.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(health.contains(r#""status":"ok""#));
+
+        let post = send(addr, &format!("POST /alerts?token={TOKEN} HTTP/1.1\r\nContent-Length: 0\r\n\r\n")).await;
+        assert!(post.starts_with("HTTP/1.1 405"));
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_server_rejects_oversized_headers() {
+        let server = spawn_observer_server("127.0.0.1:0".parse().unwrap(), TOKEN.to_string())
+            .await
+            .unwrap();
+        // 上限をちょうど1バイト超える（読み残しがあると切断時にリセットされるため）
+        let prefix = "GET /health HTTP/1.1\r\nX-Pad: ";
+        let padding = "a".repeat(MAX_REQUEST_HEAD_BYTES + 1 - prefix.len());
+        let response = send(server.local_addr(), &format!("{prefix}{padding}")).await;
+        assert!(response.starts_with("HTTP/1.1 431"));
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_listening() {
+        let server = spawn_observer_server("127.0.0.1:0".parse().unwrap(), TOKEN.to_string())
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        // 停止前に接続したまま何も送らないクライアントがいても停止できる
+        let idle = TcpStream::connect(addr).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), server.shutdown()).await.unwrap();
+        assert!(TcpStream::connect(addr).await.is_err());
+        drop(idle);
+    }
+
+    #[tokio::test]
+    async fn test_start_observer_rejects_invalid_address() {
+        let error = start_observer("localhost").await.unwrap_err();
+        assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED);
+    }
+}
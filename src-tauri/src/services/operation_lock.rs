@@ -0,0 +1,221 @@
+// 設定操作の排他制御サービス
+//
+// 推奨設定の適用・プロファイル適用・バックアップ・復元など、OBSの設定を書き換える
+// 操作を同時に1つだけ実行できるようにする。
+// UIのダブルクリックや複数ウィンドウからの同時実行でOBSへの書き込みが
+// 混ざらないよう、2つ目の呼び出しは待機せずに `OPERATION_IN_PROGRESS` エラーを返す。
+//
+// ロックはガードのドロップで解放されるため、Futureのキャンセルやパニック時も
+// 確実に解放される（tokioのMutexはポイズンしない）。
+
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, PoisonError};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// 排他制御の対象となる操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    /// 推奨設定の適用
+    ApplyRecommendedSettings,
+    /// カスタム推奨設定の適用
+    ApplyCustomSettings,
+    /// プリセットによる最適化の適用
+    ApplyOptimization,
+    /// プロファイルの適用
+    ApplyProfile,
+    /// バックアップからの復元
+    RestoreBackup,
+    /// 現在の設定のバックアップ
+    BackupSettings,
+}
+
+impl OperationKind {
+    /// 表示用ラベルを取得
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::ApplyRecommendedSettings => "推奨設定の適用",
+            Self::ApplyCustomSettings => "カスタム設定の適用",
+            Self::ApplyOptimization => "最適化の適用",
+            Self::ApplyProfile => "プロファイルの適用",
+            Self::RestoreBackup => "バックアップからの復元",
+            Self::BackupSettings => "設定のバックアップ",
+        }
+    }
+}
+
+/// 実行中の操作の情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentOperation {
+    /// 操作の種類
+    pub kind: OperationKind,
+    /// 開始日時（Unixタイムスタンプ）
+    pub started_at: i64,
+}
+
+/// 設定操作の排他ロック
+#[derive(Debug, Clone, Default)]
+pub struct OperationLock {
+    /// 操作の実行権（保持している間は他の操作を開始できない）
+    lock: Arc<Mutex<()>>,
+    /// 実行中の操作の情報
+    current: Arc<std::sync::Mutex<Option<CurrentOperation>>>,
+}
+
+/// 操作ロックのガード
+///
+/// ドロップ時に実行中の操作情報をクリアし、ロックを解放する
+#[derive(Debug)]
+pub struct OperationGuard {
+    /// 実行中の操作の情報への参照
+    current: Arc<std::sync::Mutex<Option<CurrentOperation>>>,
+    /// ロックガード（操作情報のクリア後に解放される）
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        *self.current.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+impl OperationLock {
+    /// 新しいOperationLockインスタンスを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 操作ロックを取得
+    ///
+    /// 待機はせず、他の操作が実行中の場合は即座に `OPERATION_IN_PROGRESS` エラーを返す。
+    ///
+    /// # Arguments
+    /// * `kind` - 開始する操作の種類
+    pub fn try_acquire(&self, kind: OperationKind) -> Result<OperationGuard, AppError> {
+        let Ok(guard) = self.lock.clone().try_lock_owned() else {
+            return Err(match self.current() {
+                Some(current) => AppError::operation_in_progress(current.kind.display_label(), current.started_at),
+                // ロック取得直後で操作情報が未設定の場合
+                None => AppError::operation_in_progress("不明な操作", chrono::Utc::now().timestamp()),
+            });
+        };
+
+        *self.current.lock().unwrap_or_else(PoisonError::into_inner) = Some(CurrentOperation {
+            kind,
+            started_at: chrono::Utc::now().timestamp(),
+        });
+        tracing::debug!(target: "operation_lock", operation = ?kind, "操作ロックを取得しました");
+
+        Ok(OperationGuard {
+            current: self.current.clone(),
+            _guard: guard,
+        })
+    }
+
+    /// 実行中の操作を取得（実行中の操作がない場合はNone）
+    pub fn current(&self) -> Option<CurrentOperation> {
+        *self.current.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 操作ロックを取得してから操作を実行
+    ///
+    /// 操作の完了（またはキャンセル）時にロックを解放する
+    ///
+    /// # Arguments
+    /// * `kind` - 実行する操作の種類
+    /// * `operation` - 実行する非同期操作
+    pub async fn run_exclusive<F, Fut, T>(&self, kind: OperationKind, operation: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, AppError>>,
+    {
+        let _guard = self.try_acquire(kind)?;
+        operation().await
+    }
+}
+
+/// グローバルOperationLockインスタンス
+static OPERATION_LOCK: Lazy<OperationLock> = Lazy::new(OperationLock::new);
+
+/// グローバルOperationLockを取得
+pub fn get_operation_lock() -> &'static OperationLock {
+    &OPERATION_LOCK
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::error::ERROR_CODE_OPERATION_IN_PROGRESS;
+    use std::time::Duration;
+
+    #[test]
+    fn test_second_acquire_fails_with_current_operation() {
+        let lock = OperationLock::new();
+        let _guard = lock.try_acquire(OperationKind::ApplyProfile).unwrap();
+
+        let current = lock.current().unwrap();
+        assert_eq!(current.kind, OperationKind::ApplyProfile);
+
+        let err = lock.try_acquire(OperationKind::RestoreBackup).unwrap_err();
+        assert_eq!(err.code(), ERROR_CODE_OPERATION_IN_PROGRESS);
+        assert!(err.message().contains("プロファイルの適用"));
+    }
+
+    #[test]
+    fn test_guard_drop_releases_lock() {
+        let lock = OperationLock::new();
+        drop(lock.try_acquire(OperationKind::BackupSettings).unwrap());
+
+        assert!(lock.current().is_none());
+        assert!(lock.try_acquire(OperationKind::ApplyRecommendedSettings).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_operation_releases_lock() {
+        let lock = OperationLock::new();
+        let pending = lock.run_exclusive(OperationKind::ApplyCustomSettings, || async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        });
+
+        // タイムアウトでFutureがドロップされてもロックは解放される
+        let result = tokio::time::timeout(Duration::from_millis(10), pending).await;
+        assert!(result.is_err());
+        assert!(lock.current().is_none());
+        assert!(lock.try_acquire(OperationKind::ApplyCustomSettings).is_ok());
+    }
+
+    fn panicking_operation() -> Result<(), AppError> {
+        panic!("操作中のパニック")
+    }
+
+    #[tokio::test]
+    async fn test_panicked_operation_releases_lock() {
+        let lock = OperationLock::new();
+        let task_lock = lock.clone();
+        let handle = tokio::spawn(async move {
+            task_lock
+                .run_exclusive(OperationKind::ApplyProfile, || async { panicking_operation() })
+                .await
+        });
+
+        assert!(handle.await.is_err());
+        assert!(lock.current().is_none());
+        assert!(lock.try_acquire(OperationKind::ApplyProfile).is_ok());
+    }
+
+    #[test]
+    fn test_current_operation_serialization() {
+        let current = CurrentOperation {
+            kind: OperationKind::RestoreBackup,
+            started_at: 1_700_000_000,
+        };
+        let json = serde_json::to_value(current).unwrap();
+        assert_eq!(json["kind"], "restoreBackup");
+        assert_eq!(json["startedAt"], 1_700_000_000);
+    }
+}
@@ -3,11 +3,18 @@
 // ハードウェア情報、現在のOBS設定、配信プラットフォーム、配信スタイル、
 // ネットワーク速度を元に最適な設定を算出する
 
-use crate::obs::ObsSettings;
-use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use crate::obs::{AudioMonitorType, EncoderType, ObsOutputMode, ObsSettings, OutputSettings as ObsOutputSettings};
+use crate::storage::config::{SetupType, StreamLatency, StreamingPlatform, StreamingProtocol, StreamingStyle};
+use crate::monitor::display::{primary_display, DisplayInfo};
 use crate::monitor::gpu::GpuInfo;
-use super::gpu_detection::{detect_gpu_generation, detect_gpu_grade, determine_cpu_tier, GpuGeneration, GpuGrade};
-use super::encoder_selector::{EncoderSelector, EncoderSelectionContext};
+use crate::monitor::power::PowerStatus;
+use super::gpu_detection::{calculate_effective_tier, detect_gpu_generation, detect_gpu_grade, determine_cpu_tier, CpuTier, EffectiveTier, GpuGeneration, GpuGrade};
+use super::encoder_selector::{EncoderSelector, EncoderSelectionContext, HevcProfile, NvencTuning, RecommendedEncoder};
+use super::encoder_fallback::{codec_class, probe_available_encoders, EncoderCodecClass};
+use super::audio_monitor::AudioInputStatus;
+use super::benchmark::{BenchmarkResult, BenchmarkVerdict};
+use super::reason_formatter::{JapaneseReasonFormatter, ReasonFormatter};
+use super::aspect_ratio::{compute_aspect_advice, is_ultrawide, native_output_width, AspectAdvice, AspectHandling};
 use serde::{Deserialize, Serialize};
 
 /// ハードウェア情報のサマリー
@@ -22,6 +29,35 @@ pub struct HardwareInfo {
     pub total_memory_gb: f64,
     /// GPU情報（利用可能な場合）
     pub gpu: Option<GpuInfo>,
+    /// 電源状態（バッテリー駆動の検出用）
+    pub power: PowerStatus,
+    /// 接続されているディスプレイ（検出できない場合は空）
+    pub displays: Vec<DisplayInfo>,
+    /// x264エンコードベンチマークの結果（フレーム/秒、未計測の場合はNone）
+    pub benchmark_result: Option<u32>,
+}
+
+impl HardwareInfo {
+    /// GPUドライバーのバージョン（GPUなし・取得できない場合はNone）
+    pub fn gpu_driver_version(&self) -> Option<&str> {
+        self.gpu.as_ref().and_then(|gpu| gpu.driver_version.as_deref())
+    }
+
+    /// メインモニター（検出できない場合はNone）
+    pub fn primary_display(&self) -> Option<&DisplayInfo> {
+        primary_display(&self.displays)
+    }
+}
+
+/// 推奨設定の出力先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputTarget {
+    /// 配信（ビットレート固定、ネットワーク速度を考慮）
+    #[default]
+    Streaming,
+    /// 録画（品質固定、ネットワーク速度は考慮しない）
+    Recording,
 }
 
 /// 推奨設定
@@ -36,8 +72,58 @@ pub struct RecommendedSettings {
     pub output: RecommendedOutputSettings,
     /// 推奨理由
     pub reasons: Vec<String>,
-    /// 全体スコア（0-100）
+    /// 全体スコア（0-100、`score_breakdown.total` と同じ値）
     pub overall_score: u8,
+    /// 全体スコアの項目別の内訳
+    #[serde(default)]
+    pub score_breakdown: ScoreBreakdown,
+}
+
+/// 現在の設定と推奨設定の一致度の項目別スコア
+///
+/// 各項目は満点が異なり（解像度30点・FPS20点・ビットレート30点・エンコーダー20点）、
+/// 合計が全体スコア（0-100）になる。録画用の推奨設定では、ビットレートの代わりに
+/// 品質固定レート制御の使用有無を `bitrate_score` で評価する
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBreakdown {
+    /// 出力解像度の一致度（0-30）
+    pub resolution_score: u8,
+    /// FPSの一致度（0-20）
+    pub fps_score: u8,
+    /// ビットレートの適切性（0-30）
+    pub bitrate_score: u8,
+    /// エンコーダーの適切性（0-20）
+    pub encoder_score: u8,
+    /// 合計（0-100）
+    pub total: u8,
+}
+
+impl ScoreBreakdown {
+    /// 解像度の満点
+    pub const RESOLUTION_MAX: u8 = 30;
+    /// FPSの満点
+    pub const FPS_MAX: u8 = 20;
+    /// ビットレートの満点
+    pub const BITRATE_MAX: u8 = 30;
+    /// エンコーダーの満点
+    pub const ENCODER_MAX: u8 = 20;
+
+    /// 項目別のスコアから合計を算出して構築
+    pub fn new(resolution_score: u8, fps_score: u8, bitrate_score: u8, encoder_score: u8) -> Self {
+        let total = (u32::from(resolution_score)
+            + u32::from(fps_score)
+            + u32::from(bitrate_score)
+            + u32::from(encoder_score))
+        .min(100) as u8;
+        Self {
+            resolution_score,
+            fps_score,
+            bitrate_score,
+            encoder_score,
+            total,
+        }
+    }
 }
 
 /// 推奨ビデオ設定
@@ -51,7 +137,57 @@ pub struct RecommendedVideoSettings {
     /// 推奨FPS
     pub fps: u32,
     /// ダウンスケールフィルター
-    pub downscale_filter: String,
+    pub downscale_filter: DownscaleFilter,
+    /// 推奨キャンバス（基本）解像度（幅）。メインモニターを検出できない場合はNone
+    #[serde(default)]
+    pub canvas_width: Option<u32>,
+    /// 推奨キャンバス（基本）解像度（高さ）。メインモニターを検出できない場合はNone
+    #[serde(default)]
+    pub canvas_height: Option<u32>,
+    /// キャンバスが出力と異なるアスペクト比の場合の調整（一致する場合はNone）
+    #[serde(default)]
+    pub aspect: Option<AspectAdvice>,
+}
+
+/// ダウンスケールフィルター（OBSの「縮小フィルタ」）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownscaleFilter {
+    /// バイリニア（最速、GPU負荷最小）
+    Bilinear,
+    /// バイキュービック（16サンプル、GPU負荷中）
+    Bicubic,
+    /// ランチョス（32サンプル、シャープ）
+    Lanczos,
+    /// エリア（高倍率の縮小で最も高品質）
+    Area,
+    /// Spline36（シャープ）
+    Spline36,
+}
+
+impl DownscaleFilter {
+    /// OBSのプロファイルパラメータ（`Video` / `ScaleType`）の値
+    ///
+    /// OBSの出力スケーリングにSpline36はないため、近い特性のLanczosとして書き込む
+    pub fn obs_scale_type(self) -> &'static str {
+        match self {
+            Self::Bilinear => "bilinear",
+            Self::Bicubic => "bicubic",
+            Self::Lanczos | Self::Spline36 => "lanczos",
+            Self::Area => "area",
+        }
+    }
+
+    /// 表示名（プロファイル保存用）
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Bilinear => "Bilinear",
+            Self::Bicubic => "Bicubic",
+            Self::Lanczos => "Lanczos",
+            Self::Area => "Area",
+            Self::Spline36 => "Spline36",
+        }
+    }
 }
 
 /// 推奨音声設定
@@ -78,6 +214,185 @@ pub struct RecommendedOutputSettings {
     pub preset: Option<String>,
     /// レート制御モード
     pub rate_control: String,
+    /// 品質値（CQP: CQレベル / CRF: CRF値）。ビットレート制御の場合は`None`
+    #[serde(default)]
+    pub quality_value: Option<u32>,
+    /// 推奨録画フォーマット（録画モード時のみ）
+    #[serde(default)]
+    pub recording_format: Option<String>,
+    /// 推奨配信プロトコル
+    #[serde(default)]
+    pub protocol: StreamingProtocol,
+    /// AMFプリアナリシス有効化（AMFエンコーダーのみ）
+    #[serde(default)]
+    pub pre_analysis: bool,
+    /// AMFプリアナリシスの先読みフレーム数
+    #[serde(default)]
+    pub pa_look_ahead: Option<u32>,
+    /// AMFプリアナリシスのシーンチェンジ検出
+    #[serde(default)]
+    pub pa_scene_change_detect: bool,
+    /// 推奨設定の適用に必要なOBSの出力モード
+    #[serde(default)]
+    pub output_mode: ObsOutputMode,
+    /// NVENCのチューニング（NVENC以外はNone）
+    #[serde(default)]
+    pub nvenc_tuning: Option<NvencTuning>,
+    /// HEVCのプロファイル（HEVC以外はNone）
+    #[serde(default)]
+    pub hevc_profile: Option<HevcProfile>,
+}
+
+impl RecommendedOutputSettings {
+    /// 品質固定のレート制御（CQP/CRF）かどうか
+    pub fn is_constant_quality(&self) -> bool {
+        matches!(self.rate_control.as_str(), "CQP" | "CRF")
+    }
+
+    /// 録画用の推奨設定かどうか（録画フォーマットは録画の推奨でのみ設定される）
+    pub fn is_recording(&self) -> bool {
+        self.recording_format.is_some()
+    }
+
+    /// フォールバック先のエンコーダーに合わせてエンコーダー・プリセット・レート制御を置き換える
+    ///
+    /// 品質固定の場合はエンコーダーに応じてCRF（x264）とCQP（ハードウェア）を切り替え、
+    /// 品質値は維持する。CBR/VBRはどのエンコーダーでも使えるためそのまま維持する
+    pub fn apply_fallback_encoder(&mut self, encoder: &RecommendedEncoder) {
+        self.encoder.clone_from(&encoder.encoder_id);
+        self.preset = Some(encoder.preset.clone());
+        self.pre_analysis = encoder.pre_analysis;
+        self.pa_look_ahead = encoder.pa_look_ahead;
+        self.pa_scene_change_detect = encoder.pa_scene_change_detect;
+        self.output_mode = encoder.required_output_mode();
+        self.nvenc_tuning = encoder.nvenc_tuning;
+        self.hevc_profile = encoder.hevc_profile;
+        if self.is_constant_quality() {
+            let rate_control = if encoder.encoder_id == "obs_x264" { "CRF" } else { "CQP" };
+            self.rate_control = rate_control.to_string();
+        }
+    }
+}
+
+/// 録画コンテナ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordingContainer {
+    /// Matroska（クラッシュ時もファイルが壊れにくい）
+    Mkv,
+    /// MP4（互換性が高いが、クラッシュ時にファイルが壊れる）
+    Mp4,
+    /// QuickTime（編集ソフト向け）
+    Mov,
+}
+
+impl RecordingContainer {
+    /// OBS設定値（`RecFormat2`）として出力
+    pub fn as_obs_value(&self) -> &'static str {
+        match self {
+            Self::Mkv => "mkv",
+            Self::Mp4 => "mp4",
+            Self::Mov => "mov",
+        }
+    }
+}
+
+/// 録画のレート制御方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RateControlMode {
+    /// 固定ビットレート（GPU負荷を一定に保つ）
+    Cbr,
+    /// 量子化値固定（ハードウェアエンコーダー）
+    Cqp,
+    /// 品質係数固定（x264）
+    Crf,
+}
+
+impl RateControlMode {
+    /// OBS設定値として出力
+    pub fn as_obs_value(&self) -> &'static str {
+        match self {
+            Self::Cbr => "CBR",
+            Self::Cqp => "CQP",
+            Self::Crf => "CRF",
+        }
+    }
+}
+
+/// 録画のエンコーダーとレート制御の決定結果
+///
+/// `calculate_recording_recommendations` と `calculate_for_recording` で共有し、
+/// 両者の推奨が食い違わないようにする
+struct RecordingEncoderPlan {
+    /// エンコーダー選択コンテキスト
+    context: EncoderSelectionContext,
+    /// 選択したエンコーダー（`rate_control` はレート制御方式に合わせて設定済み）
+    encoder: RecommendedEncoder,
+    /// レート制御方式
+    rate_control: RateControlMode,
+    /// CQP/CRF値（CBRの場合はNone）
+    quality_value: Option<u32>,
+    /// ビットレート（kbps、品質固定の場合はNone）
+    bitrate_kbps: Option<u32>,
+    /// 推奨理由
+    reasons: Vec<String>,
+}
+
+/// 録画専用の推奨設定（ハードウェアティア別）
+///
+/// 配信と異なりキーフレーム間隔やネットワーク帯域の制約を受けないため、
+/// `RecommendedSettings` とは別に出力する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingRecommendations {
+    /// 推奨エンコーダーID
+    pub encoder: String,
+    /// レート制御方式
+    pub rate_control: RateControlMode,
+    /// ビットレート（kbps）。品質固定の場合は`None`
+    pub bitrate_kbps: Option<u32>,
+    /// CQP値（x264の場合はCRF値）。ビットレート制御の場合は`None`
+    pub cqp_value: Option<u8>,
+    /// 録画コンテナ
+    pub container: RecordingContainer,
+    /// ロスレス録画を使用するか（ファイルサイズが極端に大きくなるため自動では推奨しない）
+    pub use_lossless: bool,
+    /// 推奨理由
+    pub reasons: Vec<String>,
+}
+
+/// マルチトラック配信の1レンディション（解像度・FPS・ビットレートの組）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultitrackRendition {
+    /// 出力解像度（幅）
+    pub width: u32,
+    /// 出力解像度（高さ）
+    pub height: u32,
+    /// FPS
+    pub fps: u32,
+    /// ビットレート（kbps）
+    pub bitrate_kbps: u32,
+    /// コーデック（"h264", "hevc", "av1"）
+    pub codec: String,
+}
+
+/// マルチトラック配信（Twitchの拡張ブロードキャスト）の推奨
+///
+/// 複数の解像度を同時にエンコードして送信するため、GPUの同時エンコード能力と
+/// 回線の上り帯域の両方に余裕がある場合のみ有効化を推奨する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedMultitrack {
+    /// マルチトラック配信を有効にすることを推奨するか
+    pub enabled: bool,
+    /// 推奨するレンディション（高解像度順）。有効化しない場合は空
+    pub renditions: Vec<MultitrackRendition>,
+    /// GPUの同時エンコードセッション数の目安
+    pub session_budget: u8,
+    /// 推奨理由（推奨しない場合はその理由）
+    pub reason: String,
 }
 
 /// プラットフォーム別の推奨値テーブル
@@ -92,6 +407,12 @@ struct PlatformPreset {
     recommended_fps: u32,
     /// キーフレーム間隔（秒）
     keyframe_interval: u32,
+    /// キーフレーム間隔を固定値で指定する必要があるか（異なる場合は取り込みサーバーが警告する）
+    keyframe_interval_strict: bool,
+    /// 受け付ける出力解像度の高さの上限
+    max_output_height: u32,
+    /// 16:9以外（ウルトラワイド）の出力をそのまま再生できるか
+    supports_ultrawide: bool,
 }
 
 impl PlatformPreset {
@@ -104,6 +425,9 @@ impl PlatformPreset {
                 recommended_height: 1080,
                 recommended_fps: 60,
                 keyframe_interval: 2,
+                keyframe_interval_strict: true,
+                max_output_height: 1440,
+                supports_ultrawide: true,
             },
             StreamingPlatform::Twitch => Self {
                 max_bitrate: 6000,
@@ -111,6 +435,9 @@ impl PlatformPreset {
                 recommended_height: 1080,
                 recommended_fps: 60,
                 keyframe_interval: 2,
+                keyframe_interval_strict: true,
+                max_output_height: 1080,
+                supports_ultrawide: false,
             },
             StreamingPlatform::NicoNico => Self {
                 max_bitrate: 6000,
@@ -118,6 +445,9 @@ impl PlatformPreset {
                 recommended_height: 720,
                 recommended_fps: 30,
                 keyframe_interval: 2,
+                keyframe_interval_strict: false,
+                max_output_height: 1080,
+                supports_ultrawide: false,
             },
             StreamingPlatform::TwitCasting => Self {
                 max_bitrate: 60000,
@@ -125,6 +455,29 @@ impl PlatformPreset {
                 recommended_height: 1080,
                 recommended_fps: 60,
                 keyframe_interval: 2,
+                keyframe_interval_strict: false,
+                max_output_height: 1080,
+                supports_ultrawide: false,
+            },
+            StreamingPlatform::Bilibili => Self {
+                max_bitrate: 8000,
+                recommended_width: 1920,
+                recommended_height: 1080,
+                recommended_fps: 60,
+                keyframe_interval: 2,
+                keyframe_interval_strict: false,
+                max_output_height: 1080,
+                supports_ultrawide: false,
+            },
+            StreamingPlatform::Showroom => Self {
+                max_bitrate: 2500,
+                recommended_width: 1280,
+                recommended_height: 720,
+                recommended_fps: 30,
+                keyframe_interval: 2,
+                keyframe_interval_strict: false,
+                max_output_height: 720,
+                supports_ultrawide: false,
             },
             StreamingPlatform::Other => Self {
                 max_bitrate: 6000,
@@ -132,11 +485,26 @@ impl PlatformPreset {
                 recommended_height: 1080,
                 recommended_fps: 30,
                 keyframe_interval: 2,
+                keyframe_interval_strict: false,
+                max_output_height: 1440,
+                supports_ultrawide: true,
             },
         }
     }
 }
 
+/// 解像度推奨の結果
+struct ResolutionRecommendation {
+    /// 出力解像度（幅）
+    width: u32,
+    /// 出力解像度（高さ）
+    height: u32,
+    /// キャンバス解像度（メインモニターを検出できない場合はNone）
+    canvas: Option<(u32, u32)>,
+    /// アスペクト比の調整
+    aspect: Option<AspectAdvice>,
+}
+
 /// 配信スタイル別の補正係数
 struct StyleModifier {
     /// ビットレート補正（倍率）
@@ -173,15 +541,72 @@ impl StyleModifier {
     }
 }
 
+/// x264のプリセット（軽量 → 高画質の順）
+const X264_PRESETS: &[&str] = &[
+    "ultrafast", "superfast", "veryfast", "faster", "fast", "medium", "slow", "slower",
+];
+/// NVENCのプリセット（軽量 → 高画質の順）
+const NVENC_PRESETS: &[&str] = &["p1", "p2", "p3", "p4", "p5", "p6", "p7"];
+/// QSV/AMFのプリセット（軽量 → 高画質の順）
+const QUALITY_PRESETS: &[&str] = &["speed", "balanced", "quality"];
+
+/// SRTを推奨するネットワーク速度の下限（Mbps）
+const SRT_MIN_NETWORK_SPEED_MBPS: f64 = 20.0;
+
+/// 低ティア向け録画のCBRビットレート（kbps）
+const RECORDING_CBR_BITRATE_KBPS: u32 = 20_000;
+
+/// マルチトラック配信のラダー候補（幅, 高さ, FPS, ビットレートkbps）。高解像度順
+const MULTITRACK_LADDER: &[(u32, u32, u32, u32)] = &[
+    (1920, 1080, 60, 6000),
+    (1280, 720, 60, 3000),
+    (852, 480, 30, 1200),
+    (640, 360, 30, 700),
+];
+
+/// 緊急用の安全な設定のビットレート範囲（kbps）
+const SAFE_DEFAULT_BITRATE_RANGE: (u32, u32) = (2500, 3500);
+
+/// 緊急用の安全な設定の出力解像度（幅, 高さ）とFPS
+const SAFE_DEFAULT_VIDEO: (u32, u32, u32) = (1280, 720, 30);
+
+/// 緊急用の安全な設定を算出する際に仮定するネットワーク速度（Mbps）
+///
+/// 3,500kbpsの配信に十分な余裕があり、SRTなど回線に依存する推奨が出ない値
+const SAFE_DEFAULT_NETWORK_SPEED_MBPS: f64 = 10.0;
+
+/// マルチトラック配信として成立する最小レンディション数
+const MULTITRACK_MIN_RENDITIONS: usize = 2;
+
+/// 1440p・ウルトラワイドの出力を推奨する最低ネットワーク速度（Mbps）
+const HIGH_RESOLUTION_MIN_NETWORK_SPEED_MBPS: f64 = 20.0;
+
+/// リフレッシュレートに合わせる場合の最低FPS（これを下回る場合は合わせない）
+const MIN_DISPLAY_ALIGNED_FPS: u32 = 24;
+
+/// 配信で使用できるBフレーム数の上限
+///
+/// 主要な配信プラットフォームは連続するBフレームを2以下とするよう推奨している
+const MAX_STREAM_B_FRAMES: u32 = 2;
+
+/// 配信先の要件に対するキーフレーム間隔・Bフレーム数の違反
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GopViolation {
+    /// キーフレーム間隔が配信先の要件と異なる（`current` の0は自動）
+    KeyframeInterval { current: u32, required: u32 },
+    /// Bフレーム数が上限を超えている
+    BFrames { current: u32, max: u32 },
+}
+
 /// 推奨エンジン
 pub struct RecommendationEngine;
 
 impl RecommendationEngine {
-    /// 推奨設定を算出
+    /// 推奨設定を算出（推奨理由は日本語）
     ///
     /// # Arguments
     /// * `hardware` - ハードウェア情報
-    /// * `current_settings` - 現在のOBS設定
+    /// * `current_settings` - 現在のOBS設定（OBSに接続していない場合はNone。スコアは0になる）
     /// * `platform` - 配信プラットフォーム
     /// * `style` - 配信スタイル
     /// * `network_speed_mbps` - ネットワーク速度（Mbps）
@@ -190,42 +615,95 @@ impl RecommendationEngine {
     /// 推奨設定
     pub fn calculate_recommendations(
         hardware: &HardwareInfo,
-        current_settings: &ObsSettings,
+        current_settings: Option<&ObsSettings>,
+        platform: StreamingPlatform,
+        style: StreamingStyle,
+        network_speed_mbps: f64,
+    ) -> RecommendedSettings {
+        Self::calculate_recommendations_with_formatter(
+            hardware,
+            current_settings,
+            platform,
+            style,
+            network_speed_mbps,
+            &JapaneseReasonFormatter,
+        )
+    }
+
+    /// 推奨設定を算出（推奨理由は `formatter` の言語）
+    ///
+    /// # Arguments
+    /// * `hardware` - ハードウェア情報
+    /// * `current_settings` - 現在のOBS設定（OBSに接続していない場合はNone。スコアは0になる）
+    /// * `platform` - 配信プラットフォーム
+    /// * `style` - 配信スタイル
+    /// * `network_speed_mbps` - ネットワーク速度（Mbps）
+    /// * `formatter` - 推奨理由の文言生成器
+    pub fn calculate_recommendations_with_formatter(
+        hardware: &HardwareInfo,
+        current_settings: Option<&ObsSettings>,
         platform: StreamingPlatform,
         style: StreamingStyle,
         network_speed_mbps: f64,
+        formatter: &dyn ReasonFormatter,
     ) -> RecommendedSettings {
+        // 呼び出し元のリクエストのスパン（request_id）の子スパンとして記録する
+        let _span = tracing::info_span!("calculate_recommendations", ?platform, ?style).entered();
+        tracing::debug!(target: "optimizer", network_speed_mbps, cpu_cores = hardware.cpu_cores, "推奨設定の算出を開始");
+
         let preset = PlatformPreset::from_platform(platform);
         let modifier = StyleModifier::from_style(style);
         let mut reasons = Vec::new();
 
         // エンコーダー推奨（新ロジック）
-        let recommended_encoder = Self::recommend_encoder(
+        let selected_encoder = Self::recommend_encoder(
             hardware,
             platform,
             style,
             network_speed_mbps,
+            formatter,
             &mut reasons,
         );
+        let output_mode = selected_encoder.required_output_mode();
+        let nvenc_tuning = selected_encoder.nvenc_tuning;
+        let hevc_profile = selected_encoder.hevc_profile;
+        let recommended_encoder = selected_encoder.encoder_id;
 
         // ビットレート推奨
         let recommended_bitrate = Self::recommend_bitrate(
             &preset,
             &modifier,
             network_speed_mbps,
+            formatter,
             &mut reasons,
         );
 
         // 解像度推奨
-        let (recommended_width, recommended_height) = Self::recommend_resolution(
+        let ResolutionRecommendation {
+            width: recommended_width,
+            height: recommended_height,
+            canvas,
+            aspect,
+        } = Self::recommend_resolution(
             &preset,
             hardware,
+            style,
+            current_settings.map(|current| (current.video.base_width, current.video.base_height)),
             network_speed_mbps,
+            formatter,
             &mut reasons,
         );
 
         // FPS推奨
-        let recommended_fps = Self::recommend_fps(&preset, &modifier, hardware, &mut reasons);
+        let display_refresh_hz = hardware.primary_display().map(|display| display.refresh_rate_hz);
+        let recommended_fps = Self::recommend_fps(
+            &preset,
+            &modifier,
+            hardware,
+            display_refresh_hz,
+            formatter,
+            &mut reasons,
+        );
 
         // 音声設定推奨
         let audio_bitrate = Self::recommend_audio_bitrate(platform, style);
@@ -239,16 +717,44 @@ impl RecommendationEngine {
             network_speed_mbps,
         );
 
+        // バッテリー駆動時は控えめな設定に補正
+        let (preset_string, recommended_fps) = Self::apply_battery_modifier(
+            hardware,
+            &recommended_encoder,
+            preset_string,
+            recommended_fps,
+            formatter,
+            &mut reasons,
+        );
+
         // 縮小フィルタ推奨
-        let downscale_filter = Self::recommend_downscale_filter(style).to_string();
+        let downscale_filter = Self::recommend_downscale_filter(style, Self::effective_tier(hardware));
+
+        // 配信プロトコル推奨
+        let protocol =
+            Self::recommend_protocol(platform, style, network_speed_mbps, formatter, &mut reasons);
+
+        tracing::debug!(
+            target: "optimizer",
+            encoder = %recommended_encoder,
+            bitrate_kbps = recommended_bitrate,
+            width = recommended_width,
+            height = recommended_height,
+            fps = recommended_fps,
+            ?protocol,
+            "推奨値を決定"
+        );
 
         // スコア算出
-        let score = Self::calculate_score(current_settings, &RecommendedSettings {
+        let breakdown = current_settings.map_or_else(ScoreBreakdown::default, |current| Self::calculate_score(current, &RecommendedSettings {
             video: RecommendedVideoSettings {
                 output_width: recommended_width,
                 output_height: recommended_height,
                 fps: recommended_fps,
-                downscale_filter: downscale_filter.clone(),
+                downscale_filter,
+                canvas_width: canvas.map(|(width, _)| width),
+                canvas_height: canvas.map(|(_, height)| height),
+                aspect,
             },
             audio: RecommendedAudioSettings {
                 sample_rate: 48000,
@@ -260,10 +766,21 @@ impl RecommendationEngine {
                 keyframe_interval_secs: preset.keyframe_interval,
                 preset: Some(preset_string.clone()),
                 rate_control: "CBR".to_string(),
+                quality_value: None,
+                recording_format: None,
+                protocol,
+                pre_analysis: false,
+                pa_look_ahead: None,
+                pa_scene_change_detect: false,
+                output_mode,
+                nvenc_tuning,
+                hevc_profile,
             },
             reasons: Vec::new(),
             overall_score: 0,
-        });
+            score_breakdown: ScoreBreakdown::default(),
+        }));
+        tracing::debug!(target: "optimizer", score = breakdown.total, "推奨設定の算出が完了");
 
         RecommendedSettings {
             video: RecommendedVideoSettings {
@@ -271,6 +788,9 @@ impl RecommendationEngine {
                 output_height: recommended_height,
                 fps: recommended_fps,
                 downscale_filter,
+                canvas_width: canvas.map(|(width, _)| width),
+                canvas_height: canvas.map(|(_, height)| height),
+                aspect,
             },
             audio: RecommendedAudioSettings {
                 sample_rate: 48000,
@@ -282,1602 +802,3743 @@ impl RecommendationEngine {
                 keyframe_interval_secs: preset.keyframe_interval,
                 preset: Some(preset_string),
                 rate_control: "CBR".to_string(),
+                quality_value: None,
+                recording_format: None,
+                protocol,
+                pre_analysis: false,
+                pa_look_ahead: None,
+                pa_scene_change_detect: false,
+                output_mode,
+                nvenc_tuning,
+                hevc_profile,
             },
             reasons,
-            overall_score: score,
+            overall_score: breakdown.total,
+            score_breakdown: breakdown,
         }
     }
 
-    /// エンコーダー推奨（新ロジック）
-    fn recommend_encoder(
-        hardware: &HardwareInfo,
-        platform: StreamingPlatform,
-        style: StreamingStyle,
-        network_speed_mbps: f64,
-        reasons: &mut Vec<String>,
-    ) -> String {
-        // GPU世代とグレードを判定
-        let (gpu_generation, gpu_grade) = if let Some(gpu) = &hardware.gpu {
-            (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name))
-        } else {
-            (GpuGeneration::None, GpuGrade::Unknown)
-        };
-
-        // CPUティアを判定
-        let cpu_tier = determine_cpu_tier(hardware.cpu_cores);
-
-        // エンコーダー選択コンテキストを構築
-        let context = EncoderSelectionContext {
-            gpu_generation,
-            gpu_grade,
-            cpu_tier,
+    /// 緊急用の安全な設定を算出
+    ///
+    /// OBSの設定が壊れて配信できない場合に手入力するための、確実に動作する控えめな設定。
+    /// 検出したハードウェアと配信プラットフォームのみを使い、OBSへの接続は不要。
+    /// 720p30・2,500〜3,500kbps・H.264ハードウェアエンコーダー（ない場合はx264 veryfast）・
+    /// 基本出力モードで、推奨理由にはOBSのメニューでの入力手順を並べる
+    ///
+    /// # Arguments
+    /// * `hardware` - ハードウェア情報
+    /// * `platform` - 配信プラットフォーム
+    pub fn calculate_safe_defaults(hardware: &HardwareInfo, platform: StreamingPlatform) -> RecommendedSettings {
+        let style = StreamingStyle::Other;
+        let mut recommended = Self::calculate_recommendations(
+            hardware,
+            None,
             platform,
             style,
-            network_speed_mbps,
+            SAFE_DEFAULT_NETWORK_SPEED_MBPS,
+        );
+
+        let context = Self::encoder_selection_context(hardware, platform, style, SAFE_DEFAULT_NETWORK_SPEED_MBPS);
+        let hardware_encoder = probe_available_encoders(&context)
+            .into_iter()
+            .find(|id| codec_class(id) == Some(EncoderCodecClass::H264Hardware));
+        let encoder = EncoderSelector::select_for_encoder(&context, hardware_encoder.unwrap_or("obs_x264"));
+        let preset = if hardware_encoder.is_some() { encoder.preset.clone() } else { "veryfast".to_string() };
+
+        let (min_bitrate, max_bitrate) = SAFE_DEFAULT_BITRATE_RANGE;
+        let bitrate = Self::target_bitrate_kbps(platform, style)
+            .clamp(min_bitrate, max_bitrate)
+            .min(PlatformPreset::from_platform(platform).max_bitrate);
+        let (width, height, fps) = SAFE_DEFAULT_VIDEO;
+
+        recommended.video.output_width = width;
+        recommended.video.output_height = height;
+        recommended.video.aspect = recommended.video.aspect.and_then(|aspect| {
+            compute_aspect_advice((aspect.canvas_width, aspect.canvas_height), (width, height), aspect.handling)
+        });
+        recommended.video.fps = fps;
+        recommended.video.downscale_filter = DownscaleFilter::Bicubic;
+        recommended.audio.sample_rate = 48000;
+        recommended.audio.bitrate_kbps = 160;
+        recommended.output = RecommendedOutputSettings {
+            encoder: encoder.encoder_id.clone(),
+            bitrate_kbps: bitrate,
+            keyframe_interval_secs: 2,
+            preset: Some(preset.clone()),
+            rate_control: "CBR".to_string(),
+            quality_value: None,
+            recording_format: None,
+            protocol: StreamingProtocol::default(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            output_mode: ObsOutputMode::Simple,
+            nvenc_tuning: None,
+            hevc_profile: None,
         };
 
-        // エンコーダーを選択
-        let recommended = EncoderSelector::select_encoder(&context);
-        reasons.push(recommended.reason.clone());
+        let encoder_reason = if hardware_encoder.is_some() {
+            format!("GPUのハードウェアエンコーダー（{}）でCPU負荷を抑えます", encoder.display_name)
+        } else {
+            "ハードウェアエンコーダーが見つからないため、CPU負荷の軽いx264（veryfast）を使用します".to_string()
+        };
+        let mut steps = vec![
+            "設定 → 出力 → 出力モード: 基本".to_string(),
+            format!("設定 → 出力 → 配信 → 映像ビットレート: {bitrate} Kbps"),
+            format!("設定 → 出力 → 配信 → エンコーダ: {}", encoder.display_name),
+        ];
+        if hardware_encoder.is_none() {
+            steps.push(format!("設定 → 出力 → 配信 → エンコーダプリセット: {preset}"));
+        }
+        steps.push("設定 → 出力 → 配信 → 音声ビットレート: 160".to_string());
+        if let (Some(canvas_width), Some(canvas_height)) = (recommended.video.canvas_width, recommended.video.canvas_height) {
+            steps.push(format!("設定 → 映像 → 基本（キャンバス）解像度: {canvas_width}x{canvas_height}"));
+        }
+        steps.push(format!("設定 → 映像 → 出力（スケーリング）解像度: {width}x{height}"));
+        steps.push("設定 → 映像 → 縮小フィルタ: バイキュービック".to_string());
+        steps.push(format!("設定 → 映像 → FPS共通値: {fps}"));
+        steps.push("設定 → 音声 → サンプリングレート: 48 kHz".to_string());
+        steps.push("「適用」→「OK」で保存し、配信を開始して問題がないか確認".to_string());
+
+        let mut reasons = vec![
+            "OBSに接続せずに算出した、確実に配信できる控えめな設定です。以下の手順でOBSに入力してください".to_string(),
+            encoder_reason,
+        ];
+        reasons.extend(steps.iter().enumerate().map(|(i, step)| format!("{}. {step}", i + 1)));
+        recommended.reasons = reasons;
 
-        recommended.encoder_id
+        recommended
     }
 
-    /// ビットレート推奨
-    fn recommend_bitrate(
-        preset: &PlatformPreset,
-        modifier: &StyleModifier,
-        network_speed_mbps: f64,
-        reasons: &mut Vec<String>,
-    ) -> u32 {
-        // 回線速度による分類（参考: https://castcraft.live/blog/178/）
-        // - 5Mbps未満: 回線弱い → 2,000〜3,000kbps推奨
-        // - 5〜10Mbps: 中程度 → 4,000〜6,000kbps推奨
-        // - 10Mbps以上: 十分 → 高画質設定可能
-
-        // プラットフォーム最大値に補正係数を適用
-        let ideal_bitrate = (f64::from(preset.max_bitrate) * modifier.bitrate_multiplier) as u32;
-
-        // ネットワーク速度の80%を上限とする（安全マージン）
-        let network_limit = (network_speed_mbps * 1000.0 * 0.8) as u32;
-
-        // 最低ビットレート（2000kbps）を保証
-        let min_bitrate = 2000u32;
+    /// ベンチマーク判定に応じてプリセットを1段階調整
+    ///
+    /// 合格なら高画質側へ、不合格なら軽量側へ1段階移動する。
+    /// 未知のエンコーダー・プリセットの場合は変更しない。
+    ///
+    /// # Arguments
+    /// * `encoder` - エンコーダーID
+    /// * `preset` - 現在のプリセット
+    /// * `verdict` - ベンチマーク判定
+    pub fn adjust_preset_for_benchmark(encoder: &str, preset: &str, verdict: BenchmarkVerdict) -> String {
+        Self::shift_preset(encoder, preset, verdict.preset_step())
+    }
 
-        // 回線が弱い場合の調整
-        let recommended = if network_speed_mbps < 3.0 {
-            // 超低速回線: 2,000〜2,500kbps
-            let limited = 2500.min(network_limit).max(min_bitrate);
-            reasons.push(format!(
-                "回線速度が非常に遅い（{:.1}Mbps）ため、ビットレートを{}kbpsに制限。720p30fps推奨",
-                network_speed_mbps, limited
-            ));
-            limited
-        } else if network_speed_mbps < 5.0 {
-            // 低速回線: 2,500〜3,500kbps
-            let limited = 3500.min(network_limit).max(min_bitrate);
-            reasons.push(format!(
-                "回線速度が低め（{:.1}Mbps）のため、ビットレートを{}kbpsに調整",
-                network_speed_mbps, limited
-            ));
-            limited
-        } else if network_speed_mbps < 10.0 {
-            // 中速回線: プラットフォーム推奨値の80%程度
-            let limited = (ideal_bitrate as f64 * 0.8) as u32;
-            let limited = limited.min(network_limit).min(preset.max_bitrate);
-            if limited < ideal_bitrate {
-                reasons.push(format!(
-                    "回線速度（{:.1}Mbps）に合わせてビットレートを{}kbpsに最適化",
-                    network_speed_mbps, limited
-                ));
-            }
-            limited
+    /// プリセットを指定段階だけ移動（正: 高画質側 / 負: 軽量側）
+    ///
+    /// 未知のエンコーダー・プリセットの場合は変更しない
+    fn shift_preset(encoder: &str, preset: &str, step: i32) -> String {
+        let encoder_lower = encoder.to_lowercase();
+        let ladder = if encoder_lower.contains("x264") {
+            X264_PRESETS
+        } else if encoder_lower.contains("nvenc") {
+            NVENC_PRESETS
+        } else if encoder_lower.contains("qsv") || encoder_lower.contains("amf") || encoder_lower.contains("amd") {
+            QUALITY_PRESETS
         } else {
-            // 高速回線: 理想値を使用可能
-            let limited = ideal_bitrate.min(network_limit).min(preset.max_bitrate);
-            if network_speed_mbps >= 20.0 && limited >= 9000 {
-                reasons.push("高速回線を検出。9,000kbps以上で滑らかな高画質配信が可能です".to_string());
-            }
-            limited
+            return preset.to_string();
         };
 
-        // 最低ビットレートを保証
-        recommended.max(min_bitrate)
-    }
-
-    /// 解像度推奨
-    fn recommend_resolution(
-        preset: &PlatformPreset,
-        hardware: &HardwareInfo,
-        network_speed_mbps: f64,
-        reasons: &mut Vec<String>,
-    ) -> (u32, u32) {
-        // 低スペックまたは低速回線の場合は720pにダウンスケール
-        if hardware.cpu_cores < 4 || network_speed_mbps < 5.0 {
-            reasons.push("ハードウェア性能またはネットワーク速度の制限により、720p解像度を推奨します".to_string());
-            return (1280, 720);
-        }
+        let Some(index) = ladder.iter().position(|p| p.eq_ignore_ascii_case(preset)) else {
+            return preset.to_string();
+        };
 
-        (preset.recommended_width, preset.recommended_height)
+        let adjusted = (index as i32 + step).clamp(0, ladder.len() as i32 - 1);
+        ladder[adjusted as usize].to_string()
     }
 
-    /// FPS推奨
-    fn recommend_fps(
-        preset: &PlatformPreset,
-        modifier: &StyleModifier,
+    /// バッテリー駆動時の補正
+    ///
+    /// バッテリー駆動中はCPUブースト・GPUクロックが制限されるため、
+    /// プリセットを1段階軽量側へ移動し、Entry/MiddleティアのCPUではFPSを30に制限する。
+    ///
+    /// # Returns
+    /// (補正後のプリセット, 補正後のFPS)
+    fn apply_battery_modifier(
         hardware: &HardwareInfo,
+        encoder: &str,
+        preset: String,
+        fps: u32,
+        formatter: &dyn ReasonFormatter,
         reasons: &mut Vec<String>,
-    ) -> u32 {
-        let ideal_fps = (f64::from(preset.recommended_fps) * modifier.fps_multiplier) as u32;
+    ) -> (String, u32) {
+        if !hardware.power.on_battery() {
+            return (preset, fps);
+        }
 
-        // 低スペックの場合は30FPSに制限
-        if hardware.cpu_cores < 4 && ideal_fps > 30 {
-            reasons.push("CPU性能の制限により、30FPSを推奨します".to_string());
-            return 30;
+        let adjusted_preset = Self::shift_preset(encoder, &preset, -1);
+        let low_tier_cpu = matches!(
+            determine_cpu_tier(hardware.cpu_cores, hardware.benchmark_result),
+            CpuTier::Entry | CpuTier::Middle
+        );
+        let adjusted_fps = if low_tier_cpu { fps.min(30) } else { fps };
+
+        if adjusted_preset != preset || adjusted_fps != fps {
+            reasons.push(formatter.format_battery_reason(
+                hardware.power.battery_percent,
+                (adjusted_preset != preset)
+                    .then_some((preset.as_str(), adjusted_preset.as_str())),
+                (adjusted_fps != fps).then_some((fps, adjusted_fps)),
+            ));
         }
 
-        ideal_fps
+        (adjusted_preset, adjusted_fps)
     }
 
-    /// 音声ビットレート推奨
-    fn recommend_audio_bitrate(platform: StreamingPlatform, style: StreamingStyle) -> u32 {
-        // スタイルによる基本ビットレート
-        let base_bitrate = match style {
-            StreamingStyle::Music => 320,      // 歌・演奏は高音質
-            StreamingStyle::Gaming => 160,     // ゲームは標準
-            StreamingStyle::Talk => 128,       // 雑談は控えめ
-            StreamingStyle::Art => 160,        // お絵描きは標準
-            StreamingStyle::Other => 160,      // その他は標準
+    /// ベンチマーク結果を推奨設定に反映
+    ///
+    /// 計測時と同じエンコーダーが推奨されている場合のみ、プリセットを調整する
+    pub fn apply_benchmark(recommended: &mut RecommendedSettings, benchmark: &BenchmarkResult) {
+        if recommended.output.encoder != benchmark.encoder {
+            return;
+        }
+        let Some(current) = recommended.output.preset.clone() else {
+            return;
         };
 
-        // プラットフォームによる調整
-        match platform {
-            StreamingPlatform::YouTube => base_bitrate,
-            StreamingPlatform::Twitch => base_bitrate.min(160), // Twitchは160kbps上限推奨
-            StreamingPlatform::NicoNico => base_bitrate.min(128), // ニコニコは128kbps推奨
-            StreamingPlatform::TwitCasting => base_bitrate, // ツイキャスは上限なし
-            StreamingPlatform::Other => base_bitrate.min(160),
+        let adjusted = Self::adjust_preset_for_benchmark(&benchmark.encoder, &current, benchmark.verdict);
+        if adjusted != current {
+            recommended.reasons.push(format!(
+                "実機ベンチマーク（余力{:.0}%）の結果により、プリセットを{}から{}に調整",
+                benchmark.headroom_percent, current, adjusted
+            ));
+            recommended.output.preset = Some(adjusted);
         }
     }
 
-    /// 縮小フィルタ推奨
+    /// 配信の遅延モードをNVENCのチューニングに反映
     ///
-    /// 配信スタイルに応じて最適なダウンスケールフィルタを選択
-    /// - ゲーム/Esports: Bicubic (16サンプル、GPU負荷中)
-    /// - トーク/IRL: Lanczos (32サンプル、カメラ映像向け)
-    fn recommend_downscale_filter(style: StreamingStyle) -> &'static str {
-        match style {
-            StreamingStyle::Gaming => "Bicubic",
-            StreamingStyle::Talk => "Lanczos",
-            StreamingStyle::Music => "Lanczos",  // カメラ重視
-            StreamingStyle::Art => "Bicubic",    // 画面キャプチャ重視
-            StreamingStyle::Other => "Bicubic",  // デフォルトはゲーム向け
+    /// 推奨計算は通常の遅延を前提とするため、設定中の遅延モードに合わせて選び直す。
+    /// NVENC以外のエンコーダー、および録画の推奨では何もしない
+    pub fn apply_stream_latency(recommended: &mut RecommendedSettings, hardware: &HardwareInfo, latency: StreamLatency) {
+        if recommended.output.nvenc_tuning.is_none() || recommended.output.is_recording() {
+            return;
+        }
+        let context = Self::encoder_selection_context(hardware, StreamingPlatform::Other, StreamingStyle::Other, 0.0);
+        let tuning = NvencTuning::select(latency, context.effective_tier(), false);
+        if recommended.output.nvenc_tuning != Some(tuning) {
+            recommended.reasons.push(format!(
+                "配信の遅延モードに合わせて、NVENCのチューニングを「{}」に設定",
+                tuning.obs_value()
+            ));
+            recommended.output.nvenc_tuning = Some(tuning);
         }
     }
 
-    /// プリセット推奨（新ロジック対応）
-    fn recommend_preset(
-        _encoder: &str,
-        hardware: &HardwareInfo,
-        platform: StreamingPlatform,
+    /// 配信環境の構成を推奨設定に反映
+    ///
+    /// 推奨計算は1台構成を前提とするため、配信専用PCではゲームとの負荷の奪い合いを考慮せずに
+    /// エンコーダーを選び直す（GPUのグレードによる負荷調整をせず、ハイエンドCPUではx264の低速プリセット）。
+    /// ゲーム用PCでは配信のエンコードを行わないため、その旨を推奨理由に追加する。
+    /// 録画の推奨では何もしない
+    ///
+    /// # Arguments
+    /// * `context` - 遅延モードと配信環境の構成を設定したエンコーダー選択コンテキスト
+    pub fn apply_setup_type(recommended: &mut RecommendedSettings, context: &EncoderSelectionContext) {
+        if recommended.output.is_recording() {
+            return;
+        }
+
+        match context.setup_type {
+            SetupType::SinglePc => {}
+            SetupType::DualPcGaming => recommended.reasons.push(
+                "2台構成のゲーム用PCです。配信のエンコードは配信用PCで行うため、エンコーダー設定は配信用PCのOBSに適用してください"
+                    .to_string(),
+            ),
+            SetupType::DualPcStreaming => {
+                let encoder = EncoderSelector::select_encoder(context);
+
+                // 1台構成を前提としたエンコーダーの推奨理由を置き換える
+                let mut shared_context = context.clone();
+                shared_context.setup_type = SetupType::SinglePc;
+                let shared_reason = EncoderSelector::select_encoder(&shared_context).reason;
+                match recommended.reasons.iter().position(|reason| *reason == shared_reason) {
+                    Some(index) => recommended.reasons[index].clone_from(&encoder.reason),
+                    None => recommended.reasons.push(encoder.reason.clone()),
+                }
+
+                recommended.output.apply_fallback_encoder(&encoder);
+            }
+        }
+    }
+
+    /// HDR配信の設定を推奨設定に反映
+    ///
+    /// HDRは10bitのエンコードが必要なため、HEVC（Main 10プロファイル）のエンコーダーを選び直す。
+    /// HDRモードが無効な場合、ゲーム用PC（2台構成）、および録画の推奨では何もしない
+    ///
+    /// # Arguments
+    /// * `context` - HDRモードと配信環境の構成を設定したエンコーダー選択コンテキスト
+    pub fn apply_hdr_mode(recommended: &mut RecommendedSettings, context: &EncoderSelectionContext) {
+        if !context.hdr_mode
+            || context.setup_type == SetupType::DualPcGaming
+            || recommended.output.is_recording()
+        {
+            return;
+        }
+
+        let encoder = EncoderSelector::select_encoder(context);
+        if encoder.hevc_profile != Some(HevcProfile::Main10) {
+            recommended.reasons.push(
+                "HDR配信にはNVENCのHEVCエンコーダーが必要です。現在の環境ではSDRで配信します".to_string(),
+            );
+            return;
+        }
+
+        if recommended.output.encoder != encoder.encoder_id {
+            recommended.reasons.push(encoder.reason.clone());
+        }
+        recommended.output.apply_fallback_encoder(&encoder);
+        recommended.reasons.push(
+            "HDR配信にはOBS 30以降とHDR対応のキャプチャーボードが必要です。OBSの詳細設定で色フォーマットをP010、色空間をRec. 2100（PQ）に設定してください"
+                .to_string(),
+        );
+    }
+
+    /// 配信スタイルに応じた音声モニタリングの推奨
+    ///
+    /// 歌・演奏配信では配信者がヘッドホンでミックスを確認できるよう「モニターと出力」を推奨する
+    pub fn recommended_audio_monitor_type(style: StreamingStyle) -> Option<AudioMonitorType> {
+        (style == StreamingStyle::Music).then_some(AudioMonitorType::MonitorAndOutput)
+    }
+
+    /// 音声モニタリングの変更を推奨する入力と推奨値
+    ///
+    /// 対象はマイク・楽器入力のみ（デスクトップ音声をモニターするとエコーになるため）
+    pub fn audio_monitor_changes(
         style: StreamingStyle,
-        network_speed_mbps: f64,
-    ) -> String {
+        inputs: &[AudioInputStatus],
+    ) -> Vec<(&AudioInputStatus, AudioMonitorType)> {
+        let Some(recommended) = Self::recommended_audio_monitor_type(style) else {
+            return Vec::new();
+        };
+
+        inputs
+            .iter()
+            .filter(|input| input.likely_microphone && input.monitor_type != recommended)
+            .map(|input| (input, recommended))
+            .collect()
+    }
+
+    /// 現在のキーフレーム間隔・Bフレーム数を配信先の要件と比較
+    ///
+    /// キーフレーム間隔の0は「自動」。x264等は自動の場合シーンチェンジでキーフレームを挿入するため
+    /// 間隔が一定にならず、常に違反とする。NVENCの自動は一定間隔でキーフレームを挿入するため、
+    /// キーフレーム間隔を固定値で指定する必要がない配信先（Twitch・YouTube以外）では許容する
+    pub fn gop_violations(platform: StreamingPlatform, output: &ObsOutputSettings) -> Vec<GopViolation> {
+        let preset = PlatformPreset::from_platform(platform);
+        let mut violations = Vec::new();
+
+        let keyframe_ok = match output.keyframe_interval_secs {
+            0 => !preset.keyframe_interval_strict && output.encoder_type() == EncoderType::NvencH264,
+            secs => secs == preset.keyframe_interval,
+        };
+        if !keyframe_ok {
+            violations.push(GopViolation::KeyframeInterval {
+                current: output.keyframe_interval_secs,
+                required: preset.keyframe_interval,
+            });
+        }
+
+        if let Some(b_frames) = output.b_frames.filter(|frames| *frames > MAX_STREAM_B_FRAMES) {
+            violations.push(GopViolation::BFrames { current: b_frames, max: MAX_STREAM_B_FRAMES });
+        }
+
+        violations
+    }
+
+    /// 録画のエンコーダーとレート制御を決定
+    ///
+    /// エンコーダーは `EncoderSelector::select_recording_encoder`（AV1 > HEVC > H.264）で選び、
+    /// レート制御はGPUの実効ティアで決める。
+    /// - ティアS/A: CQP 20
+    /// - ティアB/C: CQP 23
+    /// - ティアD/E: CBR 20,000kbps（品質固定ではGPU負荷が読めないため）
+    /// - x264: CPUティアに応じたCRF
+    fn plan_recording_encoder(hardware: &HardwareInfo, style: StreamingStyle) -> RecordingEncoderPlan {
         // GPU世代とグレードを判定
         let (gpu_generation, gpu_grade) = if let Some(gpu) = &hardware.gpu {
             (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name))
         } else {
             (GpuGeneration::None, GpuGrade::Unknown)
         };
+        let cpu_tier = determine_cpu_tier(hardware.cpu_cores, hardware.benchmark_result);
 
-        // CPUティアを判定
-        let cpu_tier = determine_cpu_tier(hardware.cpu_cores);
-
-        // エンコーダー選択コンテキストを構築
+        // 録画はプラットフォーム・ネットワークの制約を受けない
         let context = EncoderSelectionContext {
             gpu_generation,
             gpu_grade,
+            gpu_driver_version: hardware.gpu_driver_version().map(str::to_string),
             cpu_tier,
-            platform,
+            platform: StreamingPlatform::Other,
             style,
-            network_speed_mbps,
+            network_speed_mbps: 0.0,
+            recording_mode: true,
+            latency: StreamLatency::Normal,
+            setup_type: SetupType::SinglePc,
+            hdr_mode: false,
         };
-
-        // エンコーダーを選択してプリセットを取得
-        let recommended = EncoderSelector::select_encoder(&context);
-        recommended.preset
+        let mut encoder = EncoderSelector::select_recording_encoder(&context);
+        let tier = context.effective_tier();
+        let mut reasons = vec![encoder.reason.clone()];
+
+        let (rate_control, quality_value, bitrate_kbps) = if encoder.encoder_id == "obs_x264" {
+            let crf = Self::recommend_recording_crf(cpu_tier);
+            reasons.push(format!("CRF={crf}で画質を固定します（値が小さいほど高画質・大容量）"));
+            (RateControlMode::Crf, Some(crf), None)
+        } else if let Some(cqp) = Self::recommend_recording_cqp(tier) {
+            reasons.push(format!("CQ={cqp}で画質を固定します（値が小さいほど高画質・大容量）"));
+            (RateControlMode::Cqp, Some(cqp), None)
+        } else {
+            reasons.push(format!(
+                "GPUの実効ティアが{}のため、GPU負荷を一定に保つCBR {RECORDING_CBR_BITRATE_KBPS}kbpsで録画します",
+                tier.display_label()
+            ));
+            (RateControlMode::Cbr, None, Some(RECORDING_CBR_BITRATE_KBPS))
+        };
+        encoder.rate_control = rate_control.as_obs_value().to_string();
+
+        RecordingEncoderPlan {
+            context,
+            encoder,
+            rate_control,
+            quality_value,
+            bitrate_kbps,
+            reasons,
+        }
     }
 
-    /// 現在の設定と推奨設定を比較してスコアを算出
-    fn calculate_score(current: &ObsSettings, recommended: &RecommendedSettings) -> u8 {
-        let mut score = 100u32;
+    /// 録画用の推奨設定を算出
+    ///
+    /// 配信と異なりネットワーク速度によるビットレート制限は行わない。
+    /// エンコーダーとレート制御は `plan_recording_encoder` で決める（品質固定: NVENC等はCQP / x264はCRF、
+    /// 低ティアのGPUはCBR）。
+    /// 録画フォーマットはクラッシュ時にもファイルが壊れにくいmkvとし、
+    /// 自動再多重化（remux）でMP4を生成することを推奨する。
+    ///
+    /// # Arguments
+    /// * `hardware` - ハードウェア情報
+    /// * `current_settings` - 現在のOBS設定
+    /// * `style` - 配信スタイル（コンテンツ種別）
+    ///
+    /// # Returns
+    /// 推奨設定（品質固定の場合 `output.bitrate_kbps` は0）
+    pub fn calculate_recording_recommendations(
+        hardware: &HardwareInfo,
+        current_settings: &ObsSettings,
+        style: StreamingStyle,
+    ) -> RecommendedSettings {
+        let modifier = StyleModifier::from_style(style);
+        let RecordingEncoderPlan {
+            context,
+            encoder,
+            rate_control,
+            quality_value,
+            bitrate_kbps,
+            mut reasons,
+        } = Self::plan_recording_encoder(hardware, style);
+
+        // 解像度は現在のキャンバス解像度を維持（録画はダウンスケール不要）
+        let (output_width, output_height) = if hardware.cpu_cores < 4 && rate_control == RateControlMode::Crf {
+            reasons.push("CPU性能の制限により、720p解像度を推奨します".to_string());
+            (1280, 720)
+        } else {
+            (current_settings.video.base_width, current_settings.video.base_height)
+        };
 
-        // 解像度の一致度（0-30点）
-        let resolution_match = if current.video.output_width == recommended.video.output_width
-            && current.video.output_height == recommended.video.output_height
-        {
+        // FPSはスタイル補正のみ適用（基準60fps）
+        let fps = if hardware.cpu_cores < 4 {
+            reasons.push("CPU性能の制限により、30FPSを推奨します".to_string());
             30
         } else {
-            0
+            (60.0 * modifier.fps_multiplier) as u32
         };
 
-        // FPSの一致度（0-20点）
-        let current_fps = current.video.fps() as u32;
-        let fps_match = if current_fps == recommended.video.fps {
-            20
-        } else if (current_fps as i32 - recommended.video.fps as i32).abs() <= 10 {
-            10
-        } else {
-            0
+        // 録画は後編集を想定し、音声ビットレートを高めに設定
+        let audio_bitrate = match style {
+            StreamingStyle::Music => 320,
+            _ => 192,
         };
 
-        // ビットレートの適切性（0-30点）
-        let bitrate_diff = (current.output.bitrate_kbps as i32
-            - recommended.output.bitrate_kbps as i32)
-            .abs();
-        let bitrate_score = if bitrate_diff < 500 {
-            30
-        } else if bitrate_diff < 2000 {
-            15
-        } else {
-            0
+        reasons.push(
+            "録画フォーマットはmkvを推奨。クラッシュ時もファイルが破損しにくく、「自動でmp4に再多重化」を有効にすると編集ソフトでも扱えます"
+                .to_string(),
+        );
+
+        let output_mode = encoder.required_output_mode();
+        let mut recommended = RecommendedSettings {
+            video: RecommendedVideoSettings {
+                output_width,
+                output_height,
+                fps,
+                downscale_filter: Self::recommend_downscale_filter(style, context.effective_tier()),
+                // 録画は現在のキャンバス解像度を維持する
+                canvas_width: None,
+                canvas_height: None,
+                aspect: None,
+            },
+            audio: RecommendedAudioSettings {
+                sample_rate: 48000,
+                bitrate_kbps: audio_bitrate,
+            },
+            output: RecommendedOutputSettings {
+                encoder: encoder.encoder_id,
+                bitrate_kbps: bitrate_kbps.unwrap_or(0),
+                keyframe_interval_secs: 2,
+                preset: Some(encoder.preset),
+                rate_control: encoder.rate_control,
+                quality_value,
+                recording_format: Some(RecordingContainer::Mkv.as_obs_value().to_string()),
+                protocol: StreamingProtocol::default(),
+                pre_analysis: encoder.pre_analysis,
+                pa_look_ahead: encoder.pa_look_ahead,
+                pa_scene_change_detect: encoder.pa_scene_change_detect,
+                output_mode,
+                nvenc_tuning: encoder.nvenc_tuning,
+                hevc_profile: encoder.hevc_profile,
+            },
+            reasons,
+            overall_score: 0,
+            score_breakdown: ScoreBreakdown::default(),
+        };
+        let breakdown = Self::calculate_recording_score(current_settings, &recommended);
+        recommended.overall_score = breakdown.total;
+        recommended.score_breakdown = breakdown;
+
+        recommended
+    }
+
+    /// ハードウェアティア別の録画専用推奨設定を算出
+    ///
+    /// OBSの現在の設定を参照せず、`calculate_recording_recommendations` と同じ
+    /// エンコーダー・レート制御の決定（`plan_recording_encoder`）からエンコーダー設定のみを返す。
+    /// コンテナはクラッシュ時に壊れにくいMKVとする
+    pub fn calculate_for_recording(hardware: &HardwareInfo) -> RecordingRecommendations {
+        let plan = Self::plan_recording_encoder(hardware, StreamingStyle::Other);
+        let mut reasons = vec![format!("GPUの実効ティア: {}", plan.context.effective_tier().display_label())];
+        reasons.extend(plan.reasons);
+        reasons.push("クラッシュ時にもファイルが壊れにくいMKVで録画し、必要に応じてMP4へ再多重化します".to_string());
+
+        RecordingRecommendations {
+            encoder: plan.encoder.encoder_id,
+            rate_control: plan.rate_control,
+            bitrate_kbps: plan.bitrate_kbps,
+            cqp_value: plan.quality_value.and_then(|value| u8::try_from(value).ok()),
+            container: RecordingContainer::Mkv,
+            use_lossless: false,
+            reasons,
+        }
+    }
+
+    /// マルチトラック配信の推奨を算出
+    ///
+    /// 対応プラットフォーム（Twitch）でのみ推奨を返し、それ以外は`None`。
+    /// - TierS: 最大4本、TierA: 最大3本（GPUの同時セッション数を上限とする）
+    /// - TierB以下: 追加のエンコード負荷で本配信の品質が落ちるため推奨しない
+    /// - 合計ビットレートが回線速度の80%を超える場合は低解像度側から削る
+    ///
+    /// 最上位のレンディションはAV1 > HEVC > H.264の順で対応コーデックを使用し、
+    /// 下位のレンディションは視聴端末の互換性のためH.264とする
+    pub fn recommend_multitrack(
+        hardware: &HardwareInfo,
+        platform: StreamingPlatform,
+        network_speed_mbps: f64,
+    ) -> Option<RecommendedMultitrack> {
+        if platform != StreamingPlatform::Twitch {
+            return None;
+        }
+
+        let context = Self::encoder_selection_context(hardware, platform, StreamingStyle::Other, network_speed_mbps);
+        let tier = context.effective_tier();
+        let session_budget = context.concurrent_session_budget();
+        let not_recommended = |reason: String| RecommendedMultitrack {
+            enabled: false,
+            renditions: Vec::new(),
+            session_budget,
+            reason,
+        };
+
+        let tier_limit = match tier {
+            EffectiveTier::TierS => 4,
+            EffectiveTier::TierA => 3,
+            _ => {
+                return Some(not_recommended(format!(
+                    "GPUの実効ティアが{}のため、複数解像度の同時エンコードは本配信の画質低下やフレームドロップにつながります",
+                    tier.display_label()
+                )));
+            }
         };
 
-        // エンコーダーの適切性（0-20点）
-        let encoder_score = if current.output.is_hardware_encoder() {
-            20
+        let count = tier_limit.min(usize::from(session_budget));
+        if count < MULTITRACK_MIN_RENDITIONS {
+            return Some(not_recommended(format!(
+                "GPUの同時エンコード数（{session_budget}本）が不足しているため、マルチトラック配信は推奨しません"
+            )));
+        }
+
+        let top_codec = if context.av1_available() {
+            "av1"
+        } else if context.hevc_available() {
+            "hevc"
         } else {
-            10
+            "h264"
         };
+        let mut renditions: Vec<MultitrackRendition> = MULTITRACK_LADDER
+            .iter()
+            .take(count)
+            .enumerate()
+            .map(|(index, &(width, height, fps, bitrate_kbps))| MultitrackRendition {
+                width,
+                height,
+                fps,
+                bitrate_kbps,
+                codec: if index == 0 { top_codec } else { "h264" }.to_string(),
+            })
+            .collect();
+
+        // 回線速度が分かる場合は合計ビットレートを80%以内に収める
+        if network_speed_mbps > 0.0 {
+            let network_limit = (network_speed_mbps * 1000.0 * 0.8) as u32;
+            let total = |renditions: &[MultitrackRendition]| {
+                renditions.iter().map(|r| r.bitrate_kbps).sum::<u32>()
+            };
+            while renditions.len() > MULTITRACK_MIN_RENDITIONS && total(&renditions) > network_limit {
+                renditions.pop();
+            }
+            if total(&renditions) > network_limit {
+                return Some(not_recommended(format!(
+                    "複数解像度の合計ビットレート（{}kbps）が回線速度に対して大きすぎるため、マルチトラック配信は推奨しません",
+                    total(&renditions)
+                )));
+            }
+        }
 
-        score = score.min(resolution_match + fps_match + bitrate_score + encoder_score);
-        score.min(100) as u8
+        let ladder = renditions
+            .iter()
+            .map(|r| format!("{}p{}", r.height, r.fps))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        Some(RecommendedMultitrack {
+            enabled: true,
+            reason: format!(
+                "GPUの実効ティアが{}のため、拡張ブロードキャスト（{ladder}）で視聴者の回線に合わせた画質を配信できます",
+                tier.display_label()
+            ),
+            renditions,
+            session_budget,
+        })
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::obs::{VideoSettings, AudioSettings, OutputSettings};
 
-    fn create_test_hardware() -> HardwareInfo {
-        HardwareInfo {
-            cpu_name: "Test CPU".to_string(),
-            cpu_cores: 8,
-            total_memory_gb: 16.0,
-            gpu: None,
+    /// x264で録画する場合のCRF値（CPUティアが高いほど高画質）
+    const fn recommend_recording_crf(cpu_tier: CpuTier) -> u32 {
+        match cpu_tier {
+            CpuTier::HighEnd => 18,
+            CpuTier::UpperMiddle => 20,
+            CpuTier::Middle => 22,
+            CpuTier::Entry => 23,
         }
     }
 
-    fn create_test_settings() -> ObsSettings {
-        ObsSettings {
-            video: VideoSettings {
-                base_width: 1920,
-                base_height: 1080,
-                output_width: 1920,
-                output_height: 1080,
-                fps_numerator: 60,
-                fps_denominator: 1,
-            },
-            audio: AudioSettings {
-                sample_rate: 48000,
-                channels: 2,
-            },
-            output: OutputSettings {
-                encoder: "obs_x264".to_string(),
-                bitrate_kbps: 6000,
-                keyframe_interval_secs: 2,
-                preset: Some("veryfast".to_string()),
-                rate_control: Some("CBR".to_string()),
-            },
+    /// ハードウェアエンコーダーで録画する場合のCQP値（ティアD/EはCBRのためNone）
+    const fn recommend_recording_cqp(effective_tier: EffectiveTier) -> Option<u32> {
+        match effective_tier {
+            EffectiveTier::TierS | EffectiveTier::TierA => Some(20),
+            EffectiveTier::TierB | EffectiveTier::TierC => Some(23),
+            EffectiveTier::TierD | EffectiveTier::TierE => None,
         }
     }
 
-    #[test]
-    fn test_platform_preset_youtube() {
-        let preset = PlatformPreset::from_platform(StreamingPlatform::YouTube);
-        assert_eq!(preset.max_bitrate, 9000);
-        assert_eq!(preset.recommended_width, 1920);
-        assert_eq!(preset.recommended_height, 1080);
+    /// 録画設定のスコアを算出
+    ///
+    /// 配信用スコアのビットレート項目の代わりに、品質固定レート制御の使用有無を評価する
+    fn calculate_recording_score(current: &ObsSettings, recommended: &RecommendedSettings) -> ScoreBreakdown {
+        // 品質固定レート制御の使用有無（0-30点）
+        let rate_control_score = match current.output.rate_control.as_deref() {
+            Some("CQP" | "CRF" | "ICQ" | "CQVBR") => ScoreBreakdown::BITRATE_MAX,
+            Some("VBR") => ScoreBreakdown::BITRATE_MAX / 2,
+            _ => 0,
+        };
+
+        ScoreBreakdown::new(
+            Self::resolution_score(current, recommended),
+            Self::fps_score(current, recommended),
+            rate_control_score,
+            Self::encoder_score(current),
+        )
     }
 
-    #[test]
-    fn test_style_modifier_gaming() {
-        let modifier = StyleModifier::from_style(StreamingStyle::Gaming);
-        assert_eq!(modifier.bitrate_multiplier, 1.2);
-        assert_eq!(modifier.fps_multiplier, 1.0);
+    /// 回線の制約がない場合の目標ビットレート（kbps）
+    ///
+    /// プラットフォームの最大ビットレートに配信スタイルの補正係数を適用し、
+    /// プラットフォームの上限で丸めた値
+    pub fn target_bitrate_kbps(platform: StreamingPlatform, style: StreamingStyle) -> u32 {
+        let preset = PlatformPreset::from_platform(platform);
+        let modifier = StyleModifier::from_style(style);
+        ((f64::from(preset.max_bitrate) * modifier.bitrate_multiplier) as u32).min(preset.max_bitrate)
     }
 
-    #[test]
-    fn test_recommendation_engine() {
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
+    /// ハードウェア情報からエンコーダー選択コンテキストを構築
+    pub fn encoder_selection_context(
+        hardware: &HardwareInfo,
+        platform: StreamingPlatform,
+        style: StreamingStyle,
+        network_speed_mbps: f64,
+    ) -> EncoderSelectionContext {
+        // GPU世代とグレードを判定
+        let (gpu_generation, gpu_grade) = if let Some(gpu) = &hardware.gpu {
+            (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name))
+        } else {
+            (GpuGeneration::None, GpuGrade::Unknown)
+        };
 
-        let recommended = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            10.0,
-        );
+        EncoderSelectionContext {
+            gpu_generation,
+            gpu_grade,
+            gpu_driver_version: hardware.gpu_driver_version().map(str::to_string),
+            cpu_tier: determine_cpu_tier(hardware.cpu_cores, hardware.benchmark_result),
+            platform,
+            style,
+            network_speed_mbps,
+            recording_mode: false,
+            latency: StreamLatency::Normal,
+            setup_type: SetupType::SinglePc,
+            hdr_mode: false,
+        }
+    }
 
-        assert_eq!(recommended.video.output_width, 1920);
-        assert_eq!(recommended.video.output_height, 1080);
-        assert!(recommended.output.bitrate_kbps > 0);
-        assert!(!recommended.reasons.is_empty());
+    /// エンコーダー推奨（新ロジック）
+    fn recommend_encoder(
+        hardware: &HardwareInfo,
+        platform: StreamingPlatform,
+        style: StreamingStyle,
+        network_speed_mbps: f64,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> RecommendedEncoder {
+        let context = Self::encoder_selection_context(hardware, platform, style, network_speed_mbps);
+
+        // エンコーダーを選択
+        let recommended = EncoderSelector::select_encoder(&context);
+        let device_name =
+            hardware.gpu.as_ref().map_or(hardware.cpu_name.as_str(), |gpu| gpu.name.as_str());
+        let tier = format!("Tier {}", context.effective_tier().letter());
+        reasons.push(formatter.format_encoder_reason(&recommended, device_name, &tier));
+
+        recommended
     }
 
-    // === 追加のエッジケーステスト ===
+    /// ビットレート推奨
+    fn recommend_bitrate(
+        preset: &PlatformPreset,
+        modifier: &StyleModifier,
+        network_speed_mbps: f64,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> u32 {
+        // 回線速度による分類（参考: https://castcraft.live/blog/178/）
+        // - 5Mbps未満: 回線弱い → 2,000〜3,000kbps推奨
+        // - 5〜10Mbps: 中程度 → 4,000〜6,000kbps推奨
+        // - 10Mbps以上: 十分 → 高画質設定可能
 
-    #[test]
-    fn test_low_network_speed_limits_bitrate() {
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
+        // プラットフォーム最大値に補正係数を適用
+        let ideal_bitrate = (f64::from(preset.max_bitrate) * modifier.bitrate_multiplier) as u32;
 
-        // 極端に低いネットワーク速度（1Mbps）
-        let recommended = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            1.0,
+        // ネットワーク速度の80%を上限とする（安全マージン）
+        let network_limit = (network_speed_mbps * 1000.0 * 0.8) as u32;
+
+        // 最低ビットレート（2000kbps）を保証
+        let min_bitrate = 2000u32;
+
+        // 回線が弱い場合の調整
+        let recommended = if network_speed_mbps < 3.0 {
+            // 超低速回線: 2,000〜2,500kbps
+            let limited = 2500.min(network_limit).min(preset.max_bitrate).max(min_bitrate);
+            reasons
+                .push(formatter.format_bitrate_very_slow_reason(network_speed_mbps, limited));
+            limited
+        } else if network_speed_mbps < 5.0 {
+            // 低速回線: 2,500〜3,500kbps
+            let limited = 3500.min(network_limit).min(preset.max_bitrate).max(min_bitrate);
+            reasons.push(formatter.format_bitrate_slow_reason(network_speed_mbps, limited));
+            limited
+        } else if network_speed_mbps < 10.0 {
+            // 中速回線: プラットフォーム推奨値の80%程度
+            let limited = (ideal_bitrate as f64 * 0.8) as u32;
+            let limited = limited.min(network_limit).min(preset.max_bitrate);
+            if limited < ideal_bitrate {
+                reasons
+                    .push(formatter.format_bitrate_optimized_reason(network_speed_mbps, limited));
+            }
+            limited
+        } else {
+            // 高速回線: 理想値を使用可能
+            let limited = ideal_bitrate.min(network_limit).min(preset.max_bitrate);
+            if network_speed_mbps >= 20.0 && limited >= 9000 {
+                reasons.push(formatter.format_bitrate_high_speed_reason());
+            }
+            limited
+        };
+
+        // 最低ビットレートを保証
+        recommended.max(min_bitrate)
+    }
+
+    /// 解像度推奨
+    ///
+    /// 出力解像度と、メインモニターに合わせたキャンバス解像度、アスペクト比の調整を返す。
+    /// Tier A以上のGPUと十分な回線があり、プラットフォームが対応している場合は
+    /// 1440p・ウルトラワイドの出力も推奨する
+    ///
+    /// # Arguments
+    /// * `current_canvas` - 現在のキャンバス解像度（メインモニターを検出できない場合に使用）
+    fn recommend_resolution(
+        preset: &PlatformPreset,
+        hardware: &HardwareInfo,
+        style: StreamingStyle,
+        current_canvas: Option<(u32, u32)>,
+        network_speed_mbps: f64,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> ResolutionRecommendation {
+        let canvas = Self::recommend_canvas(hardware, formatter, reasons);
+        let source_canvas = canvas.or(current_canvas);
+
+        // 低スペックまたは低速回線の場合は720pにダウンスケール
+        let low_spec = hardware.cpu_cores < 4 || network_speed_mbps < 5.0;
+        if low_spec {
+            reasons.push(formatter.format_resolution_limited_reason());
+        }
+        let high_end = !low_spec
+            && Self::effective_tier(hardware) <= EffectiveTier::TierA
+            && network_speed_mbps >= HIGH_RESOLUTION_MIN_NETWORK_SPEED_MBPS;
+
+        let (mut width, height) = if low_spec {
+            (1280, 720)
+        } else if high_end
+            && preset.max_output_height >= 1440
+            && source_canvas.is_some_and(|(_, canvas_height)| canvas_height >= 1440)
+        {
+            reasons.push(formatter.format_resolution_high_reason(2560, 1440));
+            (2560, 1440)
+        } else {
+            (preset.recommended_width, preset.recommended_height)
+        };
+
+        // キャンバスが16:9でない場合のアスペクト比の調整
+        let aspect = source_canvas.and_then(|(canvas_width, canvas_height)| {
+            let handling = if high_end && preset.supports_ultrawide && is_ultrawide(canvas_width, canvas_height) {
+                width = native_output_width(canvas_width, canvas_height, height);
+                AspectHandling::Native
+            } else if style == StreamingStyle::Gaming {
+                // ゲームは中央に情報が集まるため、画面いっぱいに表示する
+                AspectHandling::Crop
+            } else {
+                AspectHandling::Letterbox
+            };
+            let advice = if handling == AspectHandling::Native {
+                // ネイティブ比率で出力する場合も、推奨事項として明示する
+                AspectAdvice {
+                    handling,
+                    canvas_width,
+                    canvas_height,
+                    output_width: width,
+                    output_height: height,
+                    letterbox_width: width,
+                    letterbox_height: height,
+                    crop_width: canvas_width,
+                    crop_height: canvas_height,
+                }
+            } else {
+                compute_aspect_advice((canvas_width, canvas_height), (width, height), handling)?
+            };
+            reasons.push(formatter.format_aspect_reason(&advice));
+            Some(advice)
+        });
+
+        ResolutionRecommendation {
+            width,
+            height,
+            canvas,
+            aspect,
+        }
+    }
+
+    /// キャンバス（基本）解像度推奨
+    ///
+    /// メインモニターの解像度に合わせる。4Kモニターはキャンバスからの縮小負荷が大きいため、
+    /// Tier A以上なら2560x1440、それ以外は1920x1080とする
+    fn recommend_canvas(
+        hardware: &HardwareInfo,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> Option<(u32, u32)> {
+        let display = hardware.primary_display()?;
+        if !display.is_4k_or_higher() {
+            return Some((display.width, display.height));
+        }
+
+        if Self::effective_tier(hardware) <= EffectiveTier::TierA {
+            reasons.push(formatter.format_canvas_reason(2560, 1440, false));
+            Some((2560, 1440))
+        } else {
+            reasons.push(formatter.format_canvas_reason(1920, 1080, true));
+            Some((1920, 1080))
+        }
+    }
+
+    /// FPS推奨
+    ///
+    /// # Arguments
+    /// * `display_refresh_hz` - プライマリモニターのリフレッシュレート（取得できない場合はNone）
+    fn recommend_fps(
+        preset: &PlatformPreset,
+        modifier: &StyleModifier,
+        hardware: &HardwareInfo,
+        display_refresh_hz: Option<u32>,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> u32 {
+        let ideal_fps = (f64::from(preset.recommended_fps) * modifier.fps_multiplier) as u32;
+
+        // 低スペックの場合は30FPSに制限
+        if hardware.cpu_cores < 4 && ideal_fps > 30 {
+            reasons.push(formatter.format_fps_limited_reason());
+            return 30;
+        }
+
+        // モニターのリフレッシュレートで割り切れるFPSに合わせる
+        if let Some(display_hz) = display_refresh_hz.filter(|&hz| hz > 0) {
+            let fps = Self::recommended_fps_for_display(display_hz, ideal_fps);
+            if fps != ideal_fps {
+                reasons.push(formatter.format_fps_display_reason(display_hz, fps));
+            }
+            return fps;
+        }
+
+        ideal_fps
+    }
+
+    /// モニターのリフレッシュレートで割り切れるFPSのうち、上限以下で最も高いものを返す
+    ///
+    /// リフレッシュレートを整数で割った値（display/1, display/2, display/3, ...）を候補とし、
+    /// フレームの表示間隔が揃わないことによるカクつき（ジャダー）を防ぐ。
+    /// 59.94Hz等のNTSC系のレート（59, 119, 239）は切り上げて扱う。
+    /// 候補が `MIN_DISPLAY_ALIGNED_FPS` を下回る場合は上限をそのまま返す
+    ///
+    /// # Arguments
+    /// * `display_fps` - モニターのリフレッシュレート（Hz）
+    /// * `platform_fps` - 配信プラットフォーム・スタイルから決まるFPSの上限
+    pub fn recommended_fps_for_display(display_fps: u32, platform_fps: u32) -> u32 {
+        let display_fps = if (display_fps + 1) % 30 == 0 { display_fps + 1 } else { display_fps };
+        if display_fps == 0 || platform_fps == 0 {
+            return platform_fps;
+        }
+
+        (1..=display_fps)
+            .filter(|divisor| display_fps % divisor == 0)
+            .map(|divisor| display_fps / divisor)
+            .find(|&fps| fps <= platform_fps)
+            .filter(|&fps| fps >= MIN_DISPLAY_ALIGNED_FPS)
+            .unwrap_or(platform_fps)
+    }
+
+    /// 音声ビットレート推奨
+    fn recommend_audio_bitrate(platform: StreamingPlatform, style: StreamingStyle) -> u32 {
+        // スタイルによる基本ビットレート
+        let base_bitrate = match style {
+            StreamingStyle::Music => 320,      // 歌・演奏は高音質
+            StreamingStyle::Gaming => 160,     // ゲームは標準
+            StreamingStyle::Talk => 128,       // 雑談は控えめ
+            StreamingStyle::Art => 160,        // お絵描きは標準
+            StreamingStyle::Other => 160,      // その他は標準
+        };
+
+        // プラットフォームによる調整
+        match platform {
+            StreamingPlatform::YouTube => base_bitrate,
+            StreamingPlatform::Twitch => base_bitrate.min(160), // Twitchは160kbps上限推奨
+            StreamingPlatform::NicoNico => base_bitrate.min(128), // ニコニコは128kbps推奨
+            StreamingPlatform::TwitCasting => base_bitrate, // ツイキャスは上限なし
+            StreamingPlatform::Bilibili => base_bitrate.min(192), // Bilibiliは192kbps推奨
+            StreamingPlatform::Showroom => base_bitrate.min(128), // SHOWROOMは128kbps推奨
+            StreamingPlatform::Other => base_bitrate.min(160),
+        }
+    }
+
+    /// 配信プロトコル推奨
+    ///
+    /// カスタムサーバー（`Other`）へのゲーム配信で十分な帯域がある場合のみ、
+    /// 超低遅延のSRTを推奨する。主要プラットフォームはRTMPSを維持する。
+    fn recommend_protocol(
+        platform: StreamingPlatform,
+        style: StreamingStyle,
+        network_speed_mbps: f64,
+        formatter: &dyn ReasonFormatter,
+        reasons: &mut Vec<String>,
+    ) -> StreamingProtocol {
+        if platform == StreamingPlatform::Other
+            && style == StreamingStyle::Gaming
+            && network_speed_mbps >= SRT_MIN_NETWORK_SPEED_MBPS
+        {
+            reasons.push(formatter.format_srt_reason());
+            return StreamingProtocol::Srt;
+        }
+
+        StreamingProtocol::Rtmps
+    }
+
+    /// ハードウェアの統合ティアを算出
+    fn effective_tier(hardware: &HardwareInfo) -> EffectiveTier {
+        let (gpu_generation, gpu_grade) = if let Some(gpu) = &hardware.gpu {
+            (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name))
+        } else {
+            (GpuGeneration::None, GpuGrade::Unknown)
+        };
+        calculate_effective_tier(gpu_generation, gpu_grade)
+    }
+
+    /// 縮小フィルタ推奨
+    ///
+    /// 統合ティアと配信スタイルに応じて最適なダウンスケールフィルタを選択
+    /// - TierD/E: Bilinear（GPU負荷を最小化）
+    /// - TierS/Aのゲーム: Area（高倍率の縮小でBicubicより高品質）
+    /// - ゲーム/Esports: Bicubic (16サンプル、GPU負荷中)
+    /// - トーク/IRL: Lanczos (32サンプル、カメラ映像向け)
+    fn recommend_downscale_filter(style: StreamingStyle, effective_tier: EffectiveTier) -> DownscaleFilter {
+        match (effective_tier, style) {
+            (EffectiveTier::TierD | EffectiveTier::TierE, _) => DownscaleFilter::Bilinear,
+            (EffectiveTier::TierS | EffectiveTier::TierA, StreamingStyle::Gaming) => DownscaleFilter::Area,
+            (_, StreamingStyle::Gaming) => DownscaleFilter::Bicubic,
+            (_, StreamingStyle::Talk) => DownscaleFilter::Lanczos,
+            (_, StreamingStyle::Music) => DownscaleFilter::Lanczos, // カメラ重視
+            (_, StreamingStyle::Art) => DownscaleFilter::Bicubic,   // 画面キャプチャ重視
+            (_, StreamingStyle::Other) => DownscaleFilter::Bicubic, // デフォルトはゲーム向け
+        }
+    }
+
+    /// プリセット推奨（新ロジック対応）
+    fn recommend_preset(
+        _encoder: &str,
+        hardware: &HardwareInfo,
+        platform: StreamingPlatform,
+        style: StreamingStyle,
+        network_speed_mbps: f64,
+    ) -> String {
+        let context = Self::encoder_selection_context(hardware, platform, style, network_speed_mbps);
+
+        // エンコーダーを選択してプリセットを取得
+        let recommended = EncoderSelector::select_encoder(&context);
+        recommended.preset
+    }
+
+    /// 出力解像度の一致度（0-30点）
+    ///
+    /// 画素数の比とアスペクト比の比の積に比例させる。
+    /// 1440pを推奨する場合に1080pを0点にするなど、近い設定を極端に低く評価しない
+    fn resolution_score(current: &ObsSettings, recommended: &RecommendedSettings) -> u8 {
+        let (current_width, current_height) = (current.video.output_width, current.video.output_height);
+        let (recommended_width, recommended_height) =
+            (recommended.video.output_width, recommended.video.output_height);
+        if current_width == recommended_width && current_height == recommended_height {
+            return ScoreBreakdown::RESOLUTION_MAX;
+        }
+
+        let ratio = |a: f64, b: f64| if a <= 0.0 || b <= 0.0 { 0.0 } else { a.min(b) / a.max(b) };
+        let pixel_ratio = ratio(
+            f64::from(current_width) * f64::from(current_height),
+            f64::from(recommended_width) * f64::from(recommended_height),
+        );
+        let aspect_ratio = ratio(
+            f64::from(current_width) / f64::from(current_height.max(1)),
+            f64::from(recommended_width) / f64::from(recommended_height.max(1)),
+        );
+        (f64::from(ScoreBreakdown::RESOLUTION_MAX) * pixel_ratio * aspect_ratio).round() as u8
+    }
+
+    /// FPSの一致度（0-20点）
+    fn fps_score(current: &ObsSettings, recommended: &RecommendedSettings) -> u8 {
+        let current_fps = current.video.fps() as u32;
+        if current_fps == recommended.video.fps {
+            ScoreBreakdown::FPS_MAX
+        } else if (current_fps as i32 - recommended.video.fps as i32).abs() <= 10 {
+            ScoreBreakdown::FPS_MAX / 2
+        } else {
+            0
+        }
+    }
+
+    /// エンコーダーの適切性（0-20点）
+    fn encoder_score(current: &ObsSettings) -> u8 {
+        if current.output.is_hardware_encoder() {
+            ScoreBreakdown::ENCODER_MAX
+        } else {
+            ScoreBreakdown::ENCODER_MAX / 2
+        }
+    }
+
+    /// 現在の設定と推奨設定を比較して項目別のスコアを算出
+    fn calculate_score(current: &ObsSettings, recommended: &RecommendedSettings) -> ScoreBreakdown {
+        // ビットレートの適切性（0-30点）
+        let bitrate_diff = (current.output.bitrate_kbps as i32
+            - recommended.output.bitrate_kbps as i32)
+            .abs();
+        let bitrate_score = if bitrate_diff < 500 {
+            ScoreBreakdown::BITRATE_MAX
+        } else if bitrate_diff < 2000 {
+            ScoreBreakdown::BITRATE_MAX / 2
+        } else {
+            0
+        };
+
+        ScoreBreakdown::new(
+            Self::resolution_score(current, recommended),
+            Self::fps_score(current, recommended),
+            bitrate_score,
+            Self::encoder_score(current),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obs::{VideoSettings, AudioSettings, OutputSettings};
+
+    fn create_test_hardware() -> HardwareInfo {
+        HardwareInfo {
+            cpu_name: "Test CPU".to_string(),
+            cpu_cores: 8,
+            total_memory_gb: 16.0,
+            gpu: None,
+            power: PowerStatus::default(),
+            displays: Vec::new(),
+            benchmark_result: None,
+        }
+    }
+
+    fn create_test_settings() -> ObsSettings {
+        ObsSettings {
+            video: VideoSettings {
+                base_width: 1920,
+                base_height: 1080,
+                output_width: 1920,
+                output_height: 1080,
+                fps_numerator: 60,
+                fps_denominator: 1,
+            },
+            audio: AudioSettings {
+                sample_rate: 48000,
+                channels: 2,
+            },
+            output: OutputSettings {
+                encoder: "obs_x264".to_string(),
+                bitrate_kbps: 6000,
+                keyframe_interval_secs: 2,
+                preset: Some("veryfast".to_string()),
+                rate_control: Some("CBR".to_string()),
+                recording_format: None,
+                recording_path: None,
+                b_frames: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_platform_preset_youtube() {
+        let preset = PlatformPreset::from_platform(StreamingPlatform::YouTube);
+        assert_eq!(preset.max_bitrate, 9000);
+        assert_eq!(preset.recommended_width, 1920);
+        assert_eq!(preset.recommended_height, 1080);
+    }
+
+    #[test]
+    fn test_style_modifier_gaming() {
+        let modifier = StyleModifier::from_style(StreamingStyle::Gaming);
+        assert_eq!(modifier.bitrate_multiplier, 1.2);
+        assert_eq!(modifier.fps_multiplier, 1.0);
+    }
+
+    #[test]
+    fn test_recommendation_engine() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        assert_eq!(recommended.video.output_width, 1920);
+        assert_eq!(recommended.video.output_height, 1080);
+        assert!(recommended.output.bitrate_kbps > 0);
+        assert!(!recommended.reasons.is_empty());
+    }
+
+    // === 追加のエッジケーステスト ===
+
+    #[test]
+    fn test_low_network_speed_limits_bitrate() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 極端に低いネットワーク速度（1Mbps）
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            1.0,
+        );
+
+        // 最低ビットレート2000kbpsが保証される
+        // network_limit = 1.0 * 1000 * 0.8 = 800だが、min_bitrate=2000で底上げ
+        assert_eq!(recommended.output.bitrate_kbps, 2000,
+            "最低ビットレートが適用される: {}",
+            recommended.output.bitrate_kbps);
+        assert!(
+            recommended.reasons.iter().any(|r| r.contains("回線速度")),
+            "回線速度による制限の理由が含まれる"
+        );
+    }
+
+    #[test]
+    fn test_very_high_network_speed() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 非常に高速なネットワーク（100Mbps）
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        // プラットフォームの最大値を超えない
+        assert!(recommended.output.bitrate_kbps <= 12000, // YouTube Gaming最大値程度
+            "ネットワーク速度が高くてもプラットフォーム最大値は超えない");
+    }
+
+    #[test]
+    fn test_zero_network_speed() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 異常値：ネットワーク速度0
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            0.0,
+        );
+
+        // クラッシュせずに最小限のビットレートを推奨
+        assert!(recommended.output.bitrate_kbps >= 0, "0でもクラッシュしない");
+    }
+
+    #[test]
+    fn test_low_spec_hardware_downscales() {
+        let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 2; // 低性能CPU
+        hardware.gpu = None;
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // 低スペックなので720pにダウンスケール
+        assert_eq!(recommended.video.output_width, 1280, "低スペックでは720p推奨");
+        assert_eq!(recommended.video.output_height, 720);
+        assert_eq!(recommended.video.fps, 30, "低スペックでは30fps推奨");
+    }
+
+    #[test]
+    fn test_nvidia_gpu_encoder_recommendation() {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 3080".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc", "NVIDIA GPUではNVENC推奨");
+        assert!(
+            recommended.reasons.iter().any(|r| r.contains("NVIDIA")),
+            "NVIDIA検出の理由が含まれる"
+        );
+    }
+
+    #[test]
+    fn test_amd_gpu_encoder_recommendation() {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "AMD Radeon RX 6800".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        assert_eq!(recommended.output.encoder, "amd_amf_h264", "AMD GPUではVCE推奨");
+    }
+
+    #[test]
+    fn test_intel_gpu_encoder_recommendation() {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "Intel UHD Graphics 770".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        assert_eq!(recommended.output.encoder, "obs_qsv11", "Intel GPUではQuickSync推奨");
+    }
+
+    #[test]
+    fn test_all_platforms() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 各プラットフォームで推奨設定が生成できること
+        for platform in [
+            StreamingPlatform::YouTube,
+            StreamingPlatform::Twitch,
+            StreamingPlatform::NicoNico,
+            StreamingPlatform::TwitCasting,
+            StreamingPlatform::Bilibili,
+            StreamingPlatform::Showroom,
+            StreamingPlatform::Other,
+        ] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                platform,
+                StreamingStyle::Gaming,
+                10.0,
+            );
+
+            assert!(recommended.output.bitrate_kbps > 0, "{:?}でビットレート設定", platform);
+            assert!(recommended.score_breakdown.total <= 100, "スコアは100以下");
+            assert_eq!(recommended.overall_score, recommended.score_breakdown.total);
+        }
+    }
+
+    #[test]
+    fn test_all_streaming_styles() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 各配信スタイルで推奨設定が生成できること
+        for style in [
+            StreamingStyle::Talk,
+            StreamingStyle::Gaming,
+            StreamingStyle::Music,
+            StreamingStyle::Art,
+            StreamingStyle::Other,
+        ] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                StreamingPlatform::YouTube,
+                style,
+                10.0,
+            );
+
+            assert!(recommended.video.fps > 0, "{:?}でFPS設定", style);
+            assert!(recommended.output.bitrate_kbps > 0, "{:?}でビットレート設定", style);
+        }
+    }
+
+    #[test]
+    fn test_talk_style_lower_requirements() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let talk = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Talk,
+            10.0,
+        );
+
+        let gaming = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // トークはゲームより低FPS・低ビットレート
+        assert!(talk.video.fps <= gaming.video.fps, "トークはゲームよりFPS低い");
+        assert!(talk.output.bitrate_kbps <= gaming.output.bitrate_kbps,
+            "トークはゲームよりビットレート低い");
+    }
+
+    #[test]
+    fn test_niconico_limitations() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::NicoNico,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // ニコニコは制限が厳しい
+        assert!(recommended.output.bitrate_kbps <= 6000, "ニコニコは6Mbps上限");
+        assert_eq!(recommended.video.output_width, 1280, "ニコニコは720p推奨");
+        assert_eq!(recommended.video.output_height, 720);
+    }
+
+    #[test]
+    fn test_score_calculation_perfect_match() {
+        let hardware = create_test_hardware();
+        let mut current = create_test_settings();
+
+        // まず推奨設定を取得
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // 現在の設定を推奨設定に合わせる
+        current.video.output_width = recommended.video.output_width;
+        current.video.output_height = recommended.video.output_height;
+        current.video.fps_numerator = recommended.video.fps;
+        current.video.fps_denominator = 1;
+        current.output.bitrate_kbps = recommended.output.bitrate_kbps;
+        current.output.encoder = "ffmpeg_nvenc".to_string(); // ハードウェアエンコーダー
+
+        let perfect = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // 完全一致ならスコアが高いはず（80以上）
+        assert!(perfect.score_breakdown.total >= 80,
+            "完全一致に近い設定ではスコアが高い: {:?}", perfect.score_breakdown);
+    }
+
+    #[test]
+    fn test_score_calculation_poor_match() {
+        let hardware = create_test_hardware();
+        let mut current = create_test_settings();
+
+        // 推奨とかけ離れた設定
+        current.video.output_width = 640;
+        current.video.output_height = 480;
+        current.video.fps_numerator = 15;
+        current.output.bitrate_kbps = 500;
+        current.output.encoder = "obs_x264".to_string();
+
+        let poor = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        // 大きく異なる設定ではスコアが低い
+        assert!(poor.score_breakdown.total < 50,
+            "推奨と大きく異なる設定ではスコアが低い: {:?}", poor.score_breakdown);
+    }
+
+    #[test]
+    fn test_extreme_cpu_cores() {
+        let mut hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // 1コア
+        hardware.cpu_cores = 1;
+        let one_core = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+        assert!(one_core.output.preset.as_ref().unwrap().contains("fast"),
+            "1コアでは軽量プリセット");
+
+        // 32コア
+        hardware.cpu_cores = 32;
+        let many_cores = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+        assert!(many_cores.output.preset.is_some(), "32コアでもプリセット設定");
+    }
+
+    #[test]
+    fn test_audio_bitrate_recommendations() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        // ゲームスタイル - 160kbps
+        let youtube_gaming = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+        assert_eq!(youtube_gaming.audio.bitrate_kbps, 160, "YouTubeゲーム音声ビットレート");
+
+        // 音楽スタイル - 320kbps
+        let youtube_music = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Music,
+            10.0,
+        );
+        assert_eq!(youtube_music.audio.bitrate_kbps, 320, "YouTube音楽音声ビットレート");
+
+        // トークスタイル - 128kbps
+        let youtube_talk = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Talk,
+            10.0,
+        );
+        assert_eq!(youtube_talk.audio.bitrate_kbps, 128, "YouTubeトーク音声ビットレート");
+
+        // ニコニコは128kbps上限
+        let niconico_music = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::NicoNico,
+            StreamingStyle::Music,
+            10.0,
+        );
+        assert_eq!(niconico_music.audio.bitrate_kbps, 128, "ニコニコ音声ビットレート上限");
+    }
+
+    // === プラットフォーム制約の詳細テスト ===
+
+    #[test]
+    fn test_platform_bitrate_constraints_youtube() {
+        // YouTube: 最大9000kbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            100.0, // 高速回線
+        );
+
+        assert!(recommended.output.bitrate_kbps <= 9000,
+            "YouTubeは9000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_platform_bitrate_constraints_twitch() {
+        // Twitch: 最大6000kbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        assert!(recommended.output.bitrate_kbps <= 6000,
+            "Twitchは6000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_platform_bitrate_constraints_niconico() {
+        // ニコニコ: 最大6000kbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::NicoNico,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        assert!(recommended.output.bitrate_kbps <= 6000,
+            "ニコニコは6000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_platform_bitrate_constraints_twitcasting() {
+        // ツイキャス: 最大60000kbps（実質制限なし）
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::TwitCasting,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        // 回線速度80%制限で 100 * 1000 * 0.8 = 80000だが、
+        // プラットフォーム制限60000が適用される
+        assert!(recommended.output.bitrate_kbps <= 60000,
+            "ツイキャスは60000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_platform_bitrate_constraints_bilibili() {
+        // Bilibili: 最大8000kbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::Bilibili,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        assert!(recommended.output.bitrate_kbps <= 8000,
+            "Bilibiliは8000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+        assert_eq!(recommended.video.output_width, 1920);
+        assert_eq!(recommended.video.fps, 60);
+    }
+
+    #[test]
+    fn test_platform_bitrate_constraints_showroom() {
+        // SHOWROOM: 最大2500kbps、720p30fps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        for network_speed in [3.5, 8.0, 100.0] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                StreamingPlatform::Showroom,
+                StreamingStyle::Gaming,
+                network_speed,
+            );
+
+            assert!(recommended.output.bitrate_kbps <= 2500,
+                "SHOWROOMは2500kbps上限: {}kbps ({}Mbps)", recommended.output.bitrate_kbps, network_speed);
+            assert_eq!(recommended.video.output_height, 720);
+            assert!(recommended.video.fps <= 30);
+        }
+    }
+
+    #[test]
+    fn test_audio_bitrate_bilibili_showroom() {
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let bilibili = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::Bilibili,
+            StreamingStyle::Music,
+            20.0,
+        );
+        assert_eq!(bilibili.audio.bitrate_kbps, 192, "Bilibiliは192kbps");
+
+        let showroom = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::Showroom,
+            StreamingStyle::Music,
+            20.0,
+        );
+        assert_eq!(showroom.audio.bitrate_kbps, 128, "SHOWROOMは128kbps");
+    }
+
+    #[test]
+    fn test_bilibili_never_av1_with_av1_capable_gpu() {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 4080".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        for platform in [StreamingPlatform::Bilibili, StreamingPlatform::Showroom] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                platform,
+                StreamingStyle::Gaming,
+                20.0,
+            );
+
+            assert_eq!(recommended.output.encoder, "ffmpeg_nvenc",
+                "{:?}ではH.264のNVENCを使用", platform);
+        }
+    }
+
+    // === ネットワーク制約の詳細テスト ===
+
+    #[test]
+    fn test_network_constraint_super_low_speed() {
+        // 超低速回線: 2Mbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            2.0,
+        );
+
+        // 2.0 * 1000 * 0.8 = 1600kbps だが、min_bitrate=2000で底上げ
+        // 超低速回線では2500kbps上限
+        assert!(recommended.output.bitrate_kbps <= 2500,
+            "2Mbps回線では2500kbps以下: {}kbps", recommended.output.bitrate_kbps);
+        assert!(recommended.output.bitrate_kbps >= 2000,
+            "最低ビットレート2000kbps保証: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_network_constraint_low_speed() {
+        // 低速回線: 4Mbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            4.0,
+        );
+
+        // 4.0 * 1000 * 0.8 = 3200kbps、低速回線では3500kbps上限
+        assert!(recommended.output.bitrate_kbps <= 3500,
+            "4Mbps回線では3500kbps以下: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_network_constraint_medium_speed() {
+        // 中速回線: 7Mbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            7.0,
+        );
+
+        // 7.0 * 1000 * 0.8 = 5600kbps
+        // プラットフォーム最大9000kbps * 1.2(Gaming) = 10800kbps * 0.8 = 8640kbps
+        // → 5600kbps制限が適用される
+        assert!(recommended.output.bitrate_kbps <= 5600,
+            "7Mbps回線では5600kbps以下: {}kbps", recommended.output.bitrate_kbps);
+    }
+
+    #[test]
+    fn test_network_constraint_high_speed() {
+        // 高速回線: 20Mbps
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            20.0,
         );
 
-        // 最低ビットレート2000kbpsが保証される
-        // network_limit = 1.0 * 1000 * 0.8 = 800だが、min_bitrate=2000で底上げ
-        assert_eq!(recommended.output.bitrate_kbps, 2000,
-            "最低ビットレートが適用される: {}",
-            recommended.output.bitrate_kbps);
+        // 20.0 * 1000 * 0.8 = 16000kbps
+        // プラットフォーム最大9000kbps * 1.2(Gaming) = 10800kbps
+        // → 9000kbps上限が適用される
+        assert!(recommended.output.bitrate_kbps <= 9000,
+            "YouTubeプラットフォーム上限9000kbps: {}kbps", recommended.output.bitrate_kbps);
         assert!(
-            recommended.reasons.iter().any(|r| r.contains("回線速度")),
-            "回線速度による制限の理由が含まれる"
+            recommended.reasons.iter().any(|r| r.contains("高速回線") || r.contains("9,000kbps")),
+            "高速回線の検出メッセージが含まれる"
         );
     }
 
     #[test]
-    fn test_very_high_network_speed() {
+    fn test_network_vs_platform_limit_youtube() {
+        // ネットワーク制限 vs プラットフォーム制限（YouTube）
         let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        // 非常に高速なネットワーク（100Mbps）
-        let recommended = RecommendationEngine::calculate_recommendations(
+        // ケース1: ネットワークが制限要因（5Mbps）
+        let network_limited = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            100.0,
+            5.0,
         );
+        assert!(network_limited.output.bitrate_kbps <= 4000,
+            "5Mbps回線では4000kbps以下");
 
-        // プラットフォームの最大値を超えない
-        assert!(recommended.output.bitrate_kbps <= 12000, // YouTube Gaming最大値程度
-            "ネットワーク速度が高くてもプラットフォーム最大値は超えない");
+        // ケース2: プラットフォームが制限要因（50Mbps）
+        let platform_limited = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            50.0,
+        );
+        assert!(platform_limited.output.bitrate_kbps <= 9000,
+            "YouTube上限9000kbps");
     }
 
     #[test]
-    fn test_zero_network_speed() {
+    fn test_network_vs_platform_limit_twitch() {
+        // ネットワーク制限 vs プラットフォーム制限（Twitch）
         let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        // 異常値：ネットワーク速度0
-        let recommended = RecommendationEngine::calculate_recommendations(
+        // ケース1: ネットワークが制限要因（3Mbps）
+        let network_limited = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&current),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            0.0,
+            3.0,
         );
+        assert!(network_limited.output.bitrate_kbps <= 2500,
+            "3Mbps回線では2500kbps以下");
 
-        // クラッシュせずに最小限のビットレートを推奨
-        assert!(recommended.output.bitrate_kbps >= 0, "0でもクラッシュしない");
+        // ケース2: プラットフォームが制限要因（20Mbps）
+        let platform_limited = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        assert!(platform_limited.output.bitrate_kbps <= 6000,
+            "Twitch上限6000kbps");
     }
 
+    // === ハードウェアティア影響テスト ===
+
     #[test]
-    fn test_low_spec_hardware_downscales() {
+    fn test_hardware_tier_low_cpu_cores() {
+        // 低コアCPU（2コア）
         let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 2; // 低性能CPU
-        hardware.gpu = None;
+        hardware.cpu_cores = 2;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 低スペックなので720pにダウンスケール
-        assert_eq!(recommended.video.output_width, 1280, "低スペックでは720p推奨");
+        // 4コア未満は720p推奨
+        assert_eq!(recommended.video.output_width, 1280, "2コアでは720p");
         assert_eq!(recommended.video.output_height, 720);
-        assert_eq!(recommended.video.fps, 30, "低スペックでは30fps推奨");
+        // Gaming (fps_multiplier=1.0) でも低コアでは30fps制限
+        assert_eq!(recommended.video.fps, 30, "2コアでは30fps");
     }
 
     #[test]
-    fn test_nvidia_gpu_encoder_recommendation() {
+    fn test_hardware_tier_mid_cpu_cores() {
+        // ミドルCPU（4コア）
         let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce RTX 3080".to_string(),
-        });
+        hardware.cpu_cores = 4;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc", "NVIDIA GPUではNVENC推奨");
-        assert!(
-            recommended.reasons.iter().any(|r| r.contains("NVIDIA")),
-            "NVIDIA検出の理由が含まれる"
-        );
+        // 4コア以上は1080p可能
+        assert_eq!(recommended.video.output_width, 1920, "4コアでは1080p");
+        assert_eq!(recommended.video.output_height, 1080);
     }
 
     #[test]
-    fn test_amd_gpu_encoder_recommendation() {
+    fn test_hardware_tier_high_cpu_cores() {
+        // ハイエンドCPU（16コア）
         let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "AMD Radeon RX 6800".to_string(),
-        });
+        hardware.cpu_cores = 16;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        assert_eq!(recommended.output.encoder, "amd_amf_h264", "AMD GPUではVCE推奨");
+        // 高コアCPUでも解像度は変わらない（プラットフォーム設定依存）
+        assert_eq!(recommended.video.output_width, 1920);
+        assert_eq!(recommended.video.output_height, 1080);
+        // プリセットが高品質になる
+        assert!(recommended.output.preset.is_some());
     }
 
     #[test]
-    fn test_intel_gpu_encoder_recommendation() {
+    fn test_hardware_tier_very_low_memory() {
+        // 超低メモリ（4GB）
         let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "Intel UHD Graphics 770".to_string(),
-        });
+        hardware.total_memory_gb = 4.0;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        assert_eq!(recommended.output.encoder, "obs_qsv11", "Intel GPUではQuickSync推奨");
+        // メモリ容量は解像度判定に直接影響しない（CPU依存）
+        // ただし、将来的な拡張の余地を確認
+        assert!(recommended.score_breakdown.total <= 100);
     }
 
     #[test]
-    fn test_all_platforms() {
-        let hardware = create_test_hardware();
+    fn test_hardware_tier_high_memory() {
+        // 高メモリ（32GB）
+        let mut hardware = create_test_hardware();
+        hardware.total_memory_gb = 32.0;
         let current = create_test_settings();
 
-        // 各プラットフォームで推奨設定が生成できること
-        for platform in [
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingPlatform::Twitch,
-            StreamingPlatform::NicoNico,
-            StreamingPlatform::TwitCasting,
-            StreamingPlatform::Other,
-        ] {
-            let recommended = RecommendationEngine::calculate_recommendations(
-                &hardware,
-                &current,
-                platform,
-                StreamingStyle::Gaming,
-                10.0,
-            );
-
-            assert!(recommended.output.bitrate_kbps > 0, "{:?}でビットレート設定", platform);
-            assert!(recommended.overall_score <= 100, "スコアは100以下");
-        }
-    }
-
-    #[test]
-    fn test_all_streaming_styles() {
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
-
-        // 各配信スタイルで推奨設定が生成できること
-        for style in [
-            StreamingStyle::Talk,
             StreamingStyle::Gaming,
-            StreamingStyle::Music,
-            StreamingStyle::Art,
-            StreamingStyle::Other,
-        ] {
-            let recommended = RecommendationEngine::calculate_recommendations(
-                &hardware,
-                &current,
-                StreamingPlatform::YouTube,
-                style,
-                10.0,
-            );
+            10.0,
+        );
 
-            assert!(recommended.video.fps > 0, "{:?}でFPS設定", style);
-            assert!(recommended.output.bitrate_kbps > 0, "{:?}でビットレート設定", style);
-        }
+        // 高メモリでも解像度は変わらない
+        assert_eq!(recommended.video.output_width, 1920);
+        assert_eq!(recommended.video.output_height, 1080);
     }
 
     #[test]
-    fn test_talk_style_lower_requirements() {
-        let hardware = create_test_hardware();
+    fn test_hardware_tier_no_gpu_low_cpu() {
+        // GPU無し＆低性能CPU（2コア）
+        let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 2;
+        hardware.gpu = None;
         let current = create_test_settings();
 
-        let talk = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Talk,
-            10.0,
-        );
-
-        let gaming = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // トークはゲームより低FPS・低ビットレート
-        assert!(talk.video.fps <= gaming.video.fps, "トークはゲームよりFPS低い");
-        assert!(talk.output.bitrate_kbps <= gaming.output.bitrate_kbps,
-            "トークはゲームよりビットレート低い");
+        // x264エンコーダー
+        assert_eq!(recommended.output.encoder, "obs_x264");
+        // 低性能なので720p30fps
+        assert_eq!(recommended.video.output_width, 1280);
+        assert_eq!(recommended.video.output_height, 720);
+        assert_eq!(recommended.video.fps, 30);
     }
 
+    // === GPU世代検出テスト ===
+
     #[test]
-    fn test_niconico_limitations() {
-        let hardware = create_test_hardware();
+    fn test_gpu_generation_nvidia_ada() {
+        // NVIDIA Ada（RTX 40シリーズ）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 4090".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::NicoNico,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // ニコニコは制限が厳しい
-        assert!(recommended.output.bitrate_kbps <= 6000, "ニコニコは6Mbps上限");
-        assert_eq!(recommended.video.output_width, 1280, "ニコニコは720p推奨");
-        assert_eq!(recommended.video.output_height, 720);
+        // AV1対応（YouTube）
+        assert_eq!(recommended.output.encoder, "jim_av1_nvenc",
+            "RTX 40シリーズはYouTubeでAV1推奨");
     }
 
     #[test]
-    fn test_score_calculation_perfect_match() {
-        let hardware = create_test_hardware();
-        let mut current = create_test_settings();
+    fn test_gpu_generation_nvidia_ada_twitch() {
+        // NVIDIA Ada（RTX 40シリーズ）on Twitch
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 4070".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
 
-        // まず推奨設定を取得
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&current),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 現在の設定を推奨設定に合わせる
-        current.video.output_width = recommended.video.output_width;
-        current.video.output_height = recommended.video.output_height;
-        current.video.fps_numerator = recommended.video.fps;
-        current.video.fps_denominator = 1;
-        current.output.bitrate_kbps = recommended.output.bitrate_kbps;
-        current.output.encoder = "ffmpeg_nvenc".to_string(); // ハードウェアエンコーダー
+        // TwitchではH.264
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc",
+            "TwitchではH.264使用");
+    }
 
-        let perfect = RecommendationEngine::calculate_recommendations(
+    #[test]
+    fn test_gpu_generation_nvidia_blackwell() {
+        // NVIDIA Blackwell（RTX 50シリーズ）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 5090".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 完全一致ならスコアが高いはず（80以上）
-        assert!(perfect.overall_score >= 80,
-            "完全一致に近い設定ではスコアが高い: {}", perfect.overall_score);
+        // 最新世代もAV1対応
+        assert_eq!(recommended.output.encoder, "jim_av1_nvenc",
+            "RTX 50シリーズはAV1推奨");
     }
 
     #[test]
-    fn test_score_calculation_poor_match() {
-        let hardware = create_test_hardware();
-        let mut current = create_test_settings();
-
-        // 推奨とかけ離れた設定
-        current.video.output_width = 640;
-        current.video.output_height = 480;
-        current.video.fps_numerator = 15;
-        current.output.bitrate_kbps = 500;
-        current.output.encoder = "obs_x264".to_string();
+    fn test_gpu_generation_nvidia_ampere() {
+        // NVIDIA Ampere（RTX 30シリーズ）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 3070".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
 
-        let poor = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 大きく異なる設定ではスコアが低い
-        assert!(poor.overall_score < 50,
-            "推奨と大きく異なる設定ではスコアが低い: {}", poor.overall_score);
+        // AmpereはAV1非対応
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc",
+            "RTX 30シリーズはH.264使用");
     }
 
     #[test]
-    fn test_extreme_cpu_cores() {
+    fn test_gpu_generation_nvidia_turing() {
+        // NVIDIA Turing（RTX 20/GTX 16シリーズ）
         let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce GTX 1660 Ti".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
-        // 1コア
-        hardware.cpu_cores = 1;
-        let one_core = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
-        assert!(one_core.output.preset.as_ref().unwrap().contains("fast"),
-            "1コアでは軽量プリセット");
 
-        // 32コア
-        hardware.cpu_cores = 32;
-        let many_cores = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            10.0,
-        );
-        assert!(many_cores.output.preset.is_some(), "32コアでもプリセット設定");
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc");
     }
 
     #[test]
-    fn test_audio_bitrate_recommendations() {
-        let hardware = create_test_hardware();
+    fn test_gpu_generation_nvidia_pascal() {
+        // NVIDIA Pascal（GTX 10シリーズ）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce GTX 1060".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
-        // ゲームスタイル - 160kbps
-        let youtube_gaming = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
-        assert_eq!(youtube_gaming.audio.bitrate_kbps, 160, "YouTubeゲーム音声ビットレート");
 
-        // 音楽スタイル - 320kbps
-        let youtube_music = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Music,
-            10.0,
-        );
-        assert_eq!(youtube_music.audio.bitrate_kbps, 320, "YouTube音楽音声ビットレート");
+        // Pascalは品質が低いが、CPUがハイエンドでないのでNVENC
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc");
+    }
 
-        // トークスタイル - 128kbps
-        let youtube_talk = RecommendationEngine::calculate_recommendations(
+    #[test]
+    fn test_gpu_generation_amd_vcn4() {
+        // AMD VCN4（RX 7000シリーズ）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "AMD Radeon RX 7900 XTX".to_string(),
+            driver_version: None,
+        });
+        let current = create_test_settings();
+
+        let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingStyle::Talk,
+            StreamingStyle::Gaming,
             10.0,
         );
-        assert_eq!(youtube_talk.audio.bitrate_kbps, 128, "YouTubeトーク音声ビットレート");
 
-        // ニコニコは128kbps上限
-        let niconico_music = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::NicoNico,
-            StreamingStyle::Music,
-            10.0,
-        );
-        assert_eq!(niconico_music.audio.bitrate_kbps, 128, "ニコニコ音声ビットレート上限");
+        assert_eq!(recommended.output.encoder, "amd_amf_h264");
     }
 
-    // === プラットフォーム制約の詳細テスト ===
-
     #[test]
-    fn test_platform_bitrate_constraints_youtube() {
-        // YouTube: 最大9000kbps
-        let hardware = create_test_hardware();
+    fn test_gpu_generation_intel_arc() {
+        // Intel Arc
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "Intel Arc A770".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            100.0, // 高速回線
+            10.0,
         );
 
-        assert!(recommended.output.bitrate_kbps <= 9000,
-            "YouTubeは9000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+        // Intel ArcはAV1対応
+        assert_eq!(recommended.output.encoder, "obs_qsv11_av1");
     }
 
     #[test]
-    fn test_platform_bitrate_constraints_twitch() {
-        // Twitch: 最大6000kbps
-        let hardware = create_test_hardware();
+    fn test_gpu_generation_intel_quicksync() {
+        // Intel QuickSync（内蔵GPU）
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "Intel UHD Graphics 770".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::Twitch,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            100.0,
+            10.0,
         );
 
-        assert!(recommended.output.bitrate_kbps <= 6000,
-            "Twitchは6000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+        assert_eq!(recommended.output.encoder, "obs_qsv11");
     }
 
+    // === エッジケーステスト ===
+
     #[test]
-    fn test_platform_bitrate_constraints_niconico() {
-        // ニコニコ: 最大6000kbps
+    fn test_edge_case_negative_network_speed() {
+        // 異常値: 負のネットワーク速度
         let hardware = create_test_hardware();
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::NicoNico,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            100.0,
+            -1.0,
         );
 
-        assert!(recommended.output.bitrate_kbps <= 6000,
-            "ニコニコは6000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+        // クラッシュせず最小ビットレート推奨
+        assert!(recommended.output.bitrate_kbps >= 2000,
+            "負のネットワーク速度でも最低ビットレート保証");
     }
 
     #[test]
-    fn test_platform_bitrate_constraints_twitcasting() {
-        // ツイキャス: 最大60000kbps（実質制限なし）
-        let hardware = create_test_hardware();
+    fn test_edge_case_zero_cpu_cores() {
+        // 異常値: 0コア
+        let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 0;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::TwitCasting,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            100.0,
+            10.0,
         );
 
-        // 回線速度80%制限で 100 * 1000 * 0.8 = 80000だが、
-        // プラットフォーム制限60000が適用される
-        assert!(recommended.output.bitrate_kbps <= 60000,
-            "ツイキャスは60000kbps上限: {}kbps", recommended.output.bitrate_kbps);
+        // クラッシュせずに推奨設定を生成
+        assert!(recommended.score_breakdown.total <= 100);
+        // 0コアはEntryティア扱い
+        assert_eq!(recommended.video.output_width, 1280, "0コアは720p推奨");
     }
 
-    // === ネットワーク制約の詳細テスト ===
-
     #[test]
-    fn test_network_constraint_super_low_speed() {
-        // 超低速回線: 2Mbps
-        let hardware = create_test_hardware();
+    fn test_edge_case_extremely_high_cpu_cores() {
+        // 極端な値: 128コア
+        let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 128;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            2.0,
+            10.0,
         );
 
-        // 2.0 * 1000 * 0.8 = 1600kbps だが、min_bitrate=2000で底上げ
-        // 超低速回線では2500kbps上限
-        assert!(recommended.output.bitrate_kbps <= 2500,
-            "2Mbps回線では2500kbps以下: {}kbps", recommended.output.bitrate_kbps);
-        assert!(recommended.output.bitrate_kbps >= 2000,
-            "最低ビットレート2000kbps保証: {}kbps", recommended.output.bitrate_kbps);
+        // 正常に処理される
+        assert_eq!(recommended.video.output_width, 1920);
+        assert_eq!(recommended.video.output_height, 1080);
     }
 
     #[test]
-    fn test_network_constraint_low_speed() {
-        // 低速回線: 4Mbps
-        let hardware = create_test_hardware();
+    fn test_edge_case_zero_memory() {
+        // 異常値: 0GBメモリ
+        let mut hardware = create_test_hardware();
+        hardware.total_memory_gb = 0.0;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            4.0,
+            10.0,
         );
 
-        // 4.0 * 1000 * 0.8 = 3200kbps、低速回線では3500kbps上限
-        assert!(recommended.output.bitrate_kbps <= 3500,
-            "4Mbps回線では3500kbps以下: {}kbps", recommended.output.bitrate_kbps);
+        // クラッシュせず推奨設定を生成
+        assert!(recommended.score_breakdown.total <= 100);
     }
 
     #[test]
-    fn test_network_constraint_medium_speed() {
-        // 中速回線: 7Mbps
-        let hardware = create_test_hardware();
+    fn test_edge_case_unknown_gpu() {
+        // 不明なGPU名
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "Unknown Exotic GPU 9000".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            7.0,
+            10.0,
         );
 
-        // 7.0 * 1000 * 0.8 = 5600kbps
-        // プラットフォーム最大9000kbps * 1.2(Gaming) = 10800kbps * 0.8 = 8640kbps
-        // → 5600kbps制限が適用される
-        assert!(recommended.output.bitrate_kbps <= 5600,
-            "7Mbps回線では5600kbps以下: {}kbps", recommended.output.bitrate_kbps);
+        // 不明GPUはCPUエンコーダーにフォールバック
+        assert_eq!(recommended.output.encoder, "obs_x264");
     }
 
     #[test]
-    fn test_network_constraint_high_speed() {
-        // 高速回線: 20Mbps
-        let hardware = create_test_hardware();
+    fn test_edge_case_combined_low_specs() {
+        // 複合エッジケース: 低CPU、低メモリ、低回線
+        let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 2;
+        hardware.total_memory_gb = 4.0;
+        hardware.gpu = None;
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            20.0,
+            2.0, // 低速回線
         );
 
-        // 20.0 * 1000 * 0.8 = 16000kbps
-        // プラットフォーム最大9000kbps * 1.2(Gaming) = 10800kbps
-        // → 9000kbps上限が適用される
-        assert!(recommended.output.bitrate_kbps <= 9000,
-            "YouTubeプラットフォーム上限9000kbps: {}kbps", recommended.output.bitrate_kbps);
-        assert!(
-            recommended.reasons.iter().any(|r| r.contains("高速回線") || r.contains("9,000kbps")),
-            "高速回線の検出メッセージが含まれる"
-        );
+        // 全て低スペックでも推奨設定を生成
+        assert_eq!(recommended.video.output_width, 1280, "低スペックは720p");
+        assert_eq!(recommended.video.output_height, 720);
+        assert_eq!(recommended.video.fps, 30, "低スペックは30fps");
+        assert!(recommended.output.bitrate_kbps <= 2500, "低速回線制限");
+        assert!(recommended.output.bitrate_kbps >= 2000, "最低ビットレート保証");
+        assert!(recommended.reasons.len() > 0, "理由が含まれる");
     }
 
+    // === 配信スタイルによる違いテスト ===
+
     #[test]
-    fn test_network_vs_platform_limit_youtube() {
-        // ネットワーク制限 vs プラットフォーム制限（YouTube）
+    fn test_style_talk_vs_gaming_bitrate() {
+        // トーク vs ゲーム: ビットレート比較
         let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        // ケース1: ネットワークが制限要因（5Mbps）
-        let network_limited = RecommendationEngine::calculate_recommendations(
+        let talk = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            5.0,
+            StreamingStyle::Talk,
+            10.0,
         );
-        assert!(network_limited.output.bitrate_kbps <= 4000,
-            "5Mbps回線では4000kbps以下");
 
-        // ケース2: プラットフォームが制限要因（50Mbps）
-        let platform_limited = RecommendationEngine::calculate_recommendations(
+        let gaming = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            50.0,
+            10.0,
         );
-        assert!(platform_limited.output.bitrate_kbps <= 9000,
-            "YouTube上限9000kbps");
+
+        // トークはゲームより低ビットレート（0.8 vs 1.2倍率）
+        assert!(talk.output.bitrate_kbps < gaming.output.bitrate_kbps,
+            "トーク{}kbps < ゲーム{}kbps",
+            talk.output.bitrate_kbps, gaming.output.bitrate_kbps);
     }
 
     #[test]
-    fn test_network_vs_platform_limit_twitch() {
-        // ネットワーク制限 vs プラットフォーム制限（Twitch）
+    fn test_style_talk_vs_gaming_fps() {
+        // トーク vs ゲーム: FPS比較
         let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        // ケース1: ネットワークが制限要因（3Mbps）
-        let network_limited = RecommendationEngine::calculate_recommendations(
+        let talk = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::Twitch,
-            StreamingStyle::Gaming,
-            3.0,
+            Some(&current),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Talk,
+            10.0,
         );
-        assert!(network_limited.output.bitrate_kbps <= 2500,
-            "3Mbps回線では2500kbps以下");
 
-        // ケース2: プラットフォームが制限要因（20Mbps）
-        let platform_limited = RecommendationEngine::calculate_recommendations(
+        let gaming = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::Twitch,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            20.0,
+            10.0,
         );
-        assert!(platform_limited.output.bitrate_kbps <= 6000,
-            "Twitch上限6000kbps");
-    }
 
-    // === ハードウェアティア影響テスト ===
+        // トークは30fps、ゲームは60fps
+        assert_eq!(talk.video.fps, 30, "トークは30fps");
+        assert_eq!(gaming.video.fps, 60, "ゲームは60fps");
+    }
 
     #[test]
-    fn test_hardware_tier_low_cpu_cores() {
-        // 低コアCPU（2コア）
-        let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 2;
+    fn test_style_music_high_audio_bitrate() {
+        // 音楽配信: 高音質音声
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let music = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
+            StreamingStyle::Music,
             10.0,
         );
 
-        // 4コア未満は720p推奨
-        assert_eq!(recommended.video.output_width, 1280, "2コアでは720p");
-        assert_eq!(recommended.video.output_height, 720);
-        // Gaming (fps_multiplier=1.0) でも低コアでは30fps制限
-        assert_eq!(recommended.video.fps, 30, "2コアでは30fps");
+        // 音楽は320kbps
+        assert_eq!(music.audio.bitrate_kbps, 320, "音楽は320kbps高音質");
     }
 
     #[test]
-    fn test_hardware_tier_mid_cpu_cores() {
-        // ミドルCPU（4コア）
+    fn test_style_art_downscale_filter() {
+        // お絵描き: ダウンスケールフィルター（スタイルの影響を見るため中位GPUで比較）
         let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 4;
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 3060".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let art = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
+            StreamingStyle::Art,
             10.0,
         );
 
-        // 4コア以上は1080p可能
-        assert_eq!(recommended.video.output_width, 1920, "4コアでは1080p");
-        assert_eq!(recommended.video.output_height, 1080);
-    }
-
-    #[test]
-    fn test_hardware_tier_high_cpu_cores() {
-        // ハイエンドCPU（16コア）
-        let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 16;
-        let current = create_test_settings();
-
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let gaming = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 高コアCPUでも解像度は変わらない（プラットフォーム設定依存）
-        assert_eq!(recommended.video.output_width, 1920);
-        assert_eq!(recommended.video.output_height, 1080);
-        // プリセットが高品質になる
-        assert!(recommended.output.preset.is_some());
+        // 両方ともBicubic（画面キャプチャ向け）
+        assert_eq!(art.video.downscale_filter, DownscaleFilter::Bicubic);
+        assert_eq!(gaming.video.downscale_filter, DownscaleFilter::Bicubic);
     }
 
     #[test]
-    fn test_hardware_tier_very_low_memory() {
-        // 超低メモリ（4GB）
+    fn test_style_talk_downscale_filter() {
+        // トーク: Lanczosフィルター（カメラ向け）
         let mut hardware = create_test_hardware();
-        hardware.total_memory_gb = 4.0;
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 3060".to_string(),
+            driver_version: None,
+        });
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let talk = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
+            StreamingStyle::Talk,
             10.0,
         );
 
-        // メモリ容量は解像度判定に直接影響しない（CPU依存）
-        // ただし、将来的な拡張の余地を確認
-        assert!(recommended.overall_score <= 100);
+        assert_eq!(talk.video.downscale_filter, DownscaleFilter::Lanczos,
+            "トークはLanczos（カメラ向け）");
     }
 
     #[test]
-    fn test_hardware_tier_high_memory() {
-        // 高メモリ（32GB）
-        let mut hardware = create_test_hardware();
-        hardware.total_memory_gb = 32.0;
+    fn test_downscale_filter_tier_style_matrix() {
+        use DownscaleFilter::{Area, Bicubic, Bilinear, Lanczos};
+        use EffectiveTier::*;
+        use StreamingStyle::{Art, Gaming, Music, Other, Talk};
+
+        // ティアごとの（配信スタイル, 推奨縮小フィルタ）
+        let expected = [
+            (TierS, [(Gaming, Area), (Talk, Lanczos), (Music, Lanczos), (Art, Bicubic), (Other, Bicubic)]),
+            (TierA, [(Gaming, Area), (Talk, Lanczos), (Music, Lanczos), (Art, Bicubic), (Other, Bicubic)]),
+            (TierB, [(Gaming, Bicubic), (Talk, Lanczos), (Music, Lanczos), (Art, Bicubic), (Other, Bicubic)]),
+            (TierC, [(Gaming, Bicubic), (Talk, Lanczos), (Music, Lanczos), (Art, Bicubic), (Other, Bicubic)]),
+            (TierD, [(Gaming, Bilinear), (Talk, Bilinear), (Music, Bilinear), (Art, Bilinear), (Other, Bilinear)]),
+            (TierE, [(Gaming, Bilinear), (Talk, Bilinear), (Music, Bilinear), (Art, Bilinear), (Other, Bilinear)]),
+        ];
+
+        for (tier, cases) in expected {
+            for (style, filter) in cases {
+                assert_eq!(
+                    RecommendationEngine::recommend_downscale_filter(style, tier),
+                    filter,
+                    "{tier:?} / {style:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_entry_hardware_always_uses_bilinear() {
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        for gpu_name in [None, Some("NVIDIA GeForce GTX 1050"), Some("Intel UHD Graphics 630")] {
+            let mut hardware = create_test_hardware();
+            hardware.gpu = gpu_name.map(|name| GpuInfo {
+                name: name.to_string(),
+                driver_version: None,
+            });
+
+            for style in [StreamingStyle::Gaming, StreamingStyle::Talk, StreamingStyle::Art] {
+                let streaming = RecommendationEngine::calculate_recommendations(
+                    &hardware,
+                    Some(&current),
+                    StreamingPlatform::YouTube,
+                    style,
+                    10.0,
+                );
+                assert_eq!(streaming.video.downscale_filter, DownscaleFilter::Bilinear, "{gpu_name:?}");
+
+                let recording = RecommendationEngine::calculate_recording_recommendations(&hardware, &current, style);
+                assert_eq!(recording.video.downscale_filter, DownscaleFilter::Bilinear, "{gpu_name:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_high_tier_gaming_uses_area() {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 4090".to_string(),
+            driver_version: None,
+        });
+
+        let gaming = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
             10.0,
         );
+        assert_eq!(gaming.video.downscale_filter, DownscaleFilter::Area);
+    }
 
-        // 高メモリでも解像度は変わらない
-        assert_eq!(recommended.video.output_width, 1920);
-        assert_eq!(recommended.video.output_height, 1080);
+    #[test]
+    fn test_downscale_filter_serialization() {
+        assert_eq!(serde_json::to_value(DownscaleFilter::Bilinear).ok(), Some(serde_json::json!("bilinear")));
+        assert_eq!(serde_json::to_value(DownscaleFilter::Spline36).ok(), Some(serde_json::json!("spline36")));
+        assert_eq!(DownscaleFilter::Area.obs_scale_type(), "area");
+        assert_eq!(DownscaleFilter::Spline36.obs_scale_type(), "lanczos");
     }
 
+    // === スコア算出の詳細テスト ===
+
     #[test]
-    fn test_hardware_tier_no_gpu_low_cpu() {
-        // GPU無し＆低性能CPU（2コア）
-        let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 2;
-        hardware.gpu = None;
-        let current = create_test_settings();
+    fn test_score_resolution_mismatch() {
+        // 解像度不一致でスコア減少
+        let hardware = create_test_hardware();
+        let mut current = create_test_settings();
+        current.video.output_width = 1280;
+        current.video.output_height = 720;
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // x264エンコーダー
-        assert_eq!(recommended.output.encoder, "obs_x264");
-        // 低性能なので720p30fps
-        assert_eq!(recommended.video.output_width, 1280);
-        assert_eq!(recommended.video.output_height, 720);
-        assert_eq!(recommended.video.fps, 30);
+        // 推奨は1920x1080だが現在は1280x720なのでスコア低下
+        assert!(recommended.score_breakdown.resolution_score < ScoreBreakdown::RESOLUTION_MAX,
+            "解像度不一致でスコア低下: {:?}", recommended.score_breakdown);
+        assert!(recommended.score_breakdown.total < 80,
+            "解像度不一致でスコア低下: {:?}", recommended.score_breakdown);
     }
 
-    // === GPU世代検出テスト ===
-
     #[test]
-    fn test_gpu_generation_nvidia_ada() {
-        // NVIDIA Ada（RTX 40シリーズ）
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce RTX 4090".to_string(),
-        });
-        let current = create_test_settings();
+    fn test_score_fps_mismatch() {
+        // FPS不一致でスコア減少
+        let hardware = create_test_hardware();
+        let mut current = create_test_settings();
+        current.video.fps_numerator = 30;
+        current.video.fps_denominator = 1;
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // AV1対応（YouTube）
-        assert_eq!(recommended.output.encoder, "jim_av1_nvenc",
-            "RTX 40シリーズはYouTubeでAV1推奨");
+        // 推奨は60fpsだが現在は30fpsなのでスコア低下
+        assert!(recommended.score_breakdown.fps_score < ScoreBreakdown::FPS_MAX,
+            "FPS不一致でスコア低下: {:?}", recommended.score_breakdown);
+        assert!(recommended.score_breakdown.total < 90,
+            "FPS不一致でスコア低下: {:?}", recommended.score_breakdown);
     }
 
     #[test]
-    fn test_gpu_generation_nvidia_ada_twitch() {
-        // NVIDIA Ada（RTX 40シリーズ）on Twitch
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce RTX 4070".to_string(),
-        });
+    fn test_score_bitrate_close_match() {
+        // ビットレートがほぼ一致（500kbps以内）
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::Twitch,
+            Some(&current),
+            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // TwitchではH.264
-        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc",
-            "TwitchではH.264使用");
-    }
-
-    #[test]
-    fn test_gpu_generation_nvidia_blackwell() {
-        // NVIDIA Blackwell（RTX 50シリーズ）
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce RTX 5090".to_string(),
-        });
-        let current = create_test_settings();
+        // 現在のビットレートを推奨値に近づける
+        let mut adjusted_current = current.clone();
+        adjusted_current.output.bitrate_kbps = recommended.output.bitrate_kbps + 300;
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let score_check = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&adjusted_current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // 最新世代もAV1対応
-        assert_eq!(recommended.output.encoder, "jim_av1_nvenc",
-            "RTX 50シリーズはAV1推奨");
+        // 500kbps以内なら高スコア（ビットレート分30点満点）
+        assert_eq!(score_check.score_breakdown.bitrate_score, ScoreBreakdown::BITRATE_MAX);
+        assert!(score_check.score_breakdown.total >= 50,
+            "ビットレート近似でスコア高め: {:?}", score_check.score_breakdown);
+    }
+
+    #[test]
+    fn test_reasons_not_empty() {
+        // すべてのパターンで理由が含まれることを確認
+        let test_cases = vec![
+            (StreamingPlatform::YouTube, StreamingStyle::Gaming, 10.0),
+            (StreamingPlatform::Twitch, StreamingStyle::Talk, 5.0),
+            (StreamingPlatform::NicoNico, StreamingStyle::Music, 3.0),
+            (StreamingPlatform::TwitCasting, StreamingStyle::Art, 20.0),
+        ];
+
+        for (platform, style, network_speed) in test_cases {
+            let hardware = create_test_hardware();
+            let current = create_test_settings();
+
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                platform,
+                style,
+                network_speed,
+            );
+
+            assert!(!recommended.reasons.is_empty(),
+                "{:?} {:?} で理由が空", platform, style);
+        }
     }
 
     #[test]
-    fn test_gpu_generation_nvidia_ampere() {
-        // NVIDIA Ampere（RTX 30シリーズ）
+    fn test_recording_recommendations_nvidia_cqp() {
         let mut hardware = create_test_hardware();
         hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce RTX 3070".to_string(),
+            name: "NVIDIA GeForce RTX 3080".to_string(),
+            driver_version: None,
         });
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recording_recommendations(
             &hardware,
             &current,
-            StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            10.0,
         );
 
-        // AmpereはAV1非対応
-        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc",
-            "RTX 30シリーズはH.264使用");
+        assert_eq!(recommended.output.encoder, "jim_hevc_nvenc", "AmpereはHEVCで録画");
+        assert_eq!(recommended.output.rate_control, "CQP");
+        assert!(recommended.output.is_constant_quality());
+        assert!(recommended.output.quality_value.is_some());
+        assert_eq!(recommended.output.bitrate_kbps, 0, "品質固定のためビットレートは使用しない");
+        assert_eq!(recommended.output.recording_format.as_deref(), Some("mkv"));
     }
 
     #[test]
-    fn test_gpu_generation_nvidia_turing() {
-        // NVIDIA Turing（RTX 20/GTX 16シリーズ）
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce GTX 1660 Ti".to_string(),
-        });
+    fn test_recording_recommendations_x264_crf() {
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let recommended = RecommendationEngine::calculate_recording_recommendations(
             &hardware,
             &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            10.0,
+            StreamingStyle::Talk,
         );
 
-        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc");
+        assert_eq!(recommended.output.encoder, "obs_x264");
+        assert_eq!(recommended.output.rate_control, "CRF");
+        assert_eq!(recommended.output.quality_value, Some(20), "8コアはCRF 20");
     }
 
     #[test]
-    fn test_gpu_generation_nvidia_pascal() {
-        // NVIDIA Pascal（GTX 10シリーズ）
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "NVIDIA GeForce GTX 1060".to_string(),
-        });
+    fn test_recording_quality_by_tier() {
+        assert_eq!(RecommendationEngine::recommend_recording_cqp(EffectiveTier::TierS), Some(20));
+        assert_eq!(RecommendationEngine::recommend_recording_cqp(EffectiveTier::TierC), Some(23));
+        assert_eq!(RecommendationEngine::recommend_recording_cqp(EffectiveTier::TierE), None, "ティアD/EはCBR");
+        assert_eq!(RecommendationEngine::recommend_recording_crf(CpuTier::HighEnd), 18);
+        assert_eq!(RecommendationEngine::recommend_recording_crf(CpuTier::Entry), 23);
+    }
+
+    #[test]
+    fn test_streaming_recommendations_not_constant_quality() {
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
 
-        // Pascalは品質が低いが、CPUがハイエンドでないのでNVENC
-        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc");
+        assert!(!recommended.output.is_constant_quality());
+        assert!(recommended.output.quality_value.is_none());
+        assert!(recommended.output.recording_format.is_none());
     }
 
     #[test]
-    fn test_gpu_generation_amd_vcn4() {
-        // AMD VCN4（RX 7000シリーズ）
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "AMD Radeon RX 7900 XTX".to_string(),
-        });
+    fn test_protocol_srt_for_custom_server_gaming() {
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&current),
+            StreamingPlatform::Other,
             StreamingStyle::Gaming,
-            10.0,
+            30.0,
         );
+        assert_eq!(recommended.output.protocol, StreamingProtocol::Srt);
+        assert!(recommended.reasons.iter().any(|r| r.contains("SRT")));
 
-        assert_eq!(recommended.output.encoder, "amd_amf_h264");
+        // 帯域不足・ゲーム以外はRTMPSを維持
+        for (style, speed) in [(StreamingStyle::Gaming, 19.9), (StreamingStyle::Talk, 30.0)] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                StreamingPlatform::Other,
+                style,
+                speed,
+            );
+            assert_eq!(recommended.output.protocol, StreamingProtocol::Rtmps);
+        }
     }
 
     #[test]
-    fn test_gpu_generation_intel_arc() {
-        // Intel Arc
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "Intel Arc A770".to_string(),
-        });
+    fn test_protocol_rtmps_for_major_platforms() {
+        let hardware = create_test_hardware();
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        for platform in [StreamingPlatform::YouTube, StreamingPlatform::Twitch, StreamingPlatform::NicoNico] {
+            let recommended = RecommendationEngine::calculate_recommendations(
+                &hardware,
+                Some(&current),
+                platform,
+                StreamingStyle::Gaming,
+                100.0,
+            );
+            assert_eq!(recommended.output.protocol, StreamingProtocol::Rtmps, "{platform:?}");
+        }
+    }
+
+    #[test]
+    fn test_adjust_preset_for_benchmark() {
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("ffmpeg_nvenc", "p5", BenchmarkVerdict::Pass), "p6");
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("ffmpeg_nvenc", "p5", BenchmarkVerdict::Fail), "p4");
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("obs_x264", "veryfast", BenchmarkVerdict::Marginal), "veryfast");
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("obs_qsv11", "balanced", BenchmarkVerdict::Pass), "quality");
+    }
+
+    #[test]
+    fn test_adjust_preset_for_benchmark_bounds_and_unknown() {
+        // 端のプリセットはそれ以上移動しない
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("ffmpeg_nvenc", "p7", BenchmarkVerdict::Pass), "p7");
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("obs_x264", "ultrafast", BenchmarkVerdict::Fail), "ultrafast");
+        // 未知のエンコーダー・プリセットは変更しない
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("unknown_encoder", "p5", BenchmarkVerdict::Fail), "p5");
+        assert_eq!(RecommendationEngine::adjust_preset_for_benchmark("ffmpeg_nvenc", "custom", BenchmarkVerdict::Fail), "custom");
+    }
+
+    #[test]
+    fn test_apply_benchmark_only_for_same_encoder() {
+        use crate::services::benchmark::{evaluate_benchmark, BenchmarkSample, FrameCounters};
+
+        let hardware = create_test_hardware();
+        let current = create_test_settings();
+        let mut recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
+        let original_preset = recommended.output.preset.clone().unwrap();
+
+        let overloaded = vec![
+            BenchmarkSample {
+                cpu_usage: 98.0,
+                gpu_usage: None,
+                encoder_usage: None,
+                average_frame_render_time_ms: 5.0,
+            };
+            10
+        ];
+
+        // 別エンコーダーの結果は反映しない
+        let other = evaluate_benchmark("ffmpeg_nvenc", Some("p5"), 60, 30, &overloaded, FrameCounters::default());
+        RecommendationEngine::apply_benchmark(&mut recommended, &other);
+        assert_eq!(recommended.output.preset.as_deref(), Some(original_preset.as_str()));
+
+        let same = evaluate_benchmark(
+            &recommended.output.encoder.clone(),
+            Some(&original_preset),
+            60,
+            30,
+            &overloaded,
+            FrameCounters::default(),
+        );
+        RecommendationEngine::apply_benchmark(&mut recommended, &same);
+        assert_ne!(recommended.output.preset.as_deref(), Some(original_preset.as_str()));
+    }
 
-        // Intel ArcはAV1対応
-        assert_eq!(recommended.output.encoder, "obs_qsv11_av1");
+    fn battery_power(percent: u8) -> PowerStatus {
+        PowerStatus {
+            source: crate::monitor::power::PowerSource::Battery,
+            battery_percent: Some(percent),
+        }
     }
 
     #[test]
-    fn test_gpu_generation_intel_quicksync() {
-        // Intel QuickSync（内蔵GPU）
+    fn test_battery_lowers_preset_and_caps_fps_for_middle_tier() {
         let mut hardware = create_test_hardware();
+        hardware.cpu_cores = 6;
         hardware.gpu = Some(GpuInfo {
-            name: "Intel UHD Graphics 770".to_string(),
+            name: "NVIDIA GeForce RTX 3060".to_string(),
+            driver_version: None,
         });
         let current = create_test_settings();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let on_ac = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
 
-        assert_eq!(recommended.output.encoder, "obs_qsv11");
-    }
-
-    // === エッジケーステスト ===
-
-    #[test]
-    fn test_edge_case_negative_network_speed() {
-        // 異常値: 負のネットワーク速度
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
-
-        let recommended = RecommendationEngine::calculate_recommendations(
+        hardware.power = battery_power(55);
+        let on_battery = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&current),
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            -1.0,
+            20.0,
         );
 
-        // クラッシュせず最小ビットレート推奨
-        assert!(recommended.output.bitrate_kbps >= 2000,
-            "負のネットワーク速度でも最低ビットレート保証");
+        let expected_preset = RecommendationEngine::shift_preset(
+            &on_ac.output.encoder,
+            on_ac.output.preset.as_deref().unwrap(),
+            -1,
+        );
+        assert_eq!(on_battery.output.preset.as_deref(), Some(expected_preset.as_str()));
+        assert!(on_battery.video.fps <= 30);
+        assert!(on_battery.reasons.iter().any(|r| r.contains("バッテリー駆動中") && r.contains("55%")));
+        assert!(!on_ac.reasons.iter().any(|r| r.contains("バッテリー駆動中")));
     }
 
     #[test]
-    fn test_edge_case_zero_cpu_cores() {
-        // 異常値: 0コア
+    fn test_battery_keeps_fps_for_high_end_cpu() {
         let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 0;
-        let current = create_test_settings();
+        hardware.cpu_cores = 16;
+        hardware.power = battery_power(80);
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let mut reasons = Vec::new();
+        let (preset, fps) = RecommendationEngine::apply_battery_modifier(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            10.0,
+            "obs_x264",
+            "faster".to_string(),
+            60,
+            &JapaneseReasonFormatter,
+            &mut reasons,
         );
+        assert_eq!(preset, "veryfast");
+        assert_eq!(fps, 60);
+        assert_eq!(reasons.len(), 1);
+    }
 
-        // クラッシュせずに推奨設定を生成
-        assert!(recommended.overall_score <= 100);
-        // 0コアはEntryティア扱い
-        assert_eq!(recommended.video.output_width, 1280, "0コアは720p推奨");
+    #[test]
+    fn test_battery_modifier_noop_on_ac() {
+        let hardware = create_test_hardware();
+        let mut reasons = Vec::new();
+        let (preset, fps) = RecommendationEngine::apply_battery_modifier(
+            &hardware,
+            "ffmpeg_nvenc",
+            "p5".to_string(),
+            60,
+            &JapaneseReasonFormatter,
+            &mut reasons,
+        );
+        assert_eq!(preset, "p5");
+        assert_eq!(fps, 60);
+        assert!(reasons.is_empty());
     }
 
     #[test]
-    fn test_edge_case_extremely_high_cpu_cores() {
-        // 極端な値: 128コア
-        let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 128;
-        let current = create_test_settings();
+    fn test_english_formatter_changes_only_reason_language() {
+        use crate::services::reason_formatter::EnglishReasonFormatter;
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let hardware = create_test_hardware();
+        let japanese = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            None,
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            10.0,
+            4.0,
+        );
+        let english = RecommendationEngine::calculate_recommendations_with_formatter(
+            &hardware,
+            None,
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            4.0,
+            &EnglishReasonFormatter,
         );
 
-        // 正常に処理される
-        assert_eq!(recommended.video.output_width, 1920);
-        assert_eq!(recommended.video.output_height, 1080);
+        // 推奨値は言語に関係なく同じ
+        assert_eq!(english.output.encoder, japanese.output.encoder);
+        assert_eq!(english.output.bitrate_kbps, japanese.output.bitrate_kbps);
+        assert_eq!(english.video.fps, japanese.video.fps);
+        assert_eq!(english.reasons.len(), japanese.reasons.len());
+
+        assert!(japanese.reasons.iter().any(|r| r.contains("回線速度が低め")));
+        assert!(english.reasons.iter().any(|r| r.contains("4.0 Mbps")));
+        assert!(english.reasons[0].starts_with("Detected"));
+    }
+
+    fn audio_input(name: &str, kind: &str, monitor_type: AudioMonitorType) -> AudioInputStatus {
+        AudioInputStatus {
+            input_name: name.to_string(),
+            input_kind: kind.to_string(),
+            muted: false,
+            volume_db: 0.0,
+            volume_mul: 1.0,
+            likely_microphone: crate::services::audio_monitor::is_likely_microphone(kind, name),
+            monitor_type,
+        }
     }
 
     #[test]
-    fn test_edge_case_zero_memory() {
-        // 異常値: 0GBメモリ
-        let mut hardware = create_test_hardware();
-        hardware.total_memory_gb = 0.0;
-        let current = create_test_settings();
+    fn test_recommended_audio_monitor_type_by_style() {
+        assert_eq!(
+            RecommendationEngine::recommended_audio_monitor_type(StreamingStyle::Music),
+            Some(AudioMonitorType::MonitorAndOutput)
+        );
+        for style in [StreamingStyle::Talk, StreamingStyle::Gaming, StreamingStyle::Art, StreamingStyle::Other] {
+            assert_eq!(RecommendationEngine::recommended_audio_monitor_type(style), None);
+        }
+    }
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+    #[test]
+    fn test_audio_monitor_changes_for_music_targets_microphones_only() {
+        let inputs = vec![
+            audio_input("Mic/Aux", "wasapi_input_capture", AudioMonitorType::None),
+            audio_input("ギター", "wasapi_input_capture", AudioMonitorType::MonitorOnly),
+            audio_input("ボーカル", "wasapi_input_capture", AudioMonitorType::MonitorAndOutput),
+            audio_input("デスクトップ音声", "wasapi_output_capture", AudioMonitorType::None),
+        ];
+
+        let changes = RecommendationEngine::audio_monitor_changes(StreamingStyle::Music, &inputs);
+        let names: Vec<&str> = changes.iter().map(|(input, _)| input.input_name.as_str()).collect();
+        assert_eq!(names, vec!["Mic/Aux", "ギター"]);
+        assert!(changes.iter().all(|(_, recommended)| *recommended == AudioMonitorType::MonitorAndOutput));
+
+        // 歌・演奏以外では推奨しない
+        assert!(RecommendationEngine::audio_monitor_changes(StreamingStyle::Gaming, &inputs).is_empty());
+    }
+
+    fn gop_output(encoder: &str, keyframe_interval_secs: u32, b_frames: Option<u32>) -> OutputSettings {
+        OutputSettings {
+            encoder: encoder.to_string(),
+            keyframe_interval_secs,
+            b_frames,
+            ..create_test_settings().output
+        }
+    }
+
+    #[test]
+    fn test_gop_violations_keyframe_interval() {
+        for platform in [StreamingPlatform::Twitch, StreamingPlatform::YouTube, StreamingPlatform::NicoNico] {
+            assert!(RecommendationEngine::gop_violations(platform, &gop_output("obs_x264", 2, None)).is_empty());
+            assert_eq!(
+                RecommendationEngine::gop_violations(platform, &gop_output("obs_x264", 10, None)),
+                vec![GopViolation::KeyframeInterval { current: 10, required: 2 }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_gop_violations_auto_keyframe_interval() {
+        let auto = |encoder: &str, platform| {
+            RecommendationEngine::gop_violations(platform, &gop_output(encoder, 0, None))
+        };
+        let violation = vec![GopViolation::KeyframeInterval { current: 0, required: 2 }];
+
+        // Twitch・YouTubeは固定値が必要なため、NVENCでも自動は違反
+        assert_eq!(auto("ffmpeg_nvenc", StreamingPlatform::Twitch), violation);
+        assert_eq!(auto("jim_nvenc", StreamingPlatform::YouTube), violation);
+        // それ以外の配信先ではNVENCの自動を許容する
+        assert!(auto("ffmpeg_nvenc", StreamingPlatform::NicoNico).is_empty());
+        assert!(auto("jim_nvenc", StreamingPlatform::Other).is_empty());
+        // x264・QSV・AMFの自動は間隔が一定にならないため常に違反
+        assert_eq!(auto("obs_x264", StreamingPlatform::NicoNico), violation);
+        assert_eq!(auto("obs_qsv11", StreamingPlatform::Other), violation);
+        assert_eq!(auto("amd_amf_h264", StreamingPlatform::Bilibili), violation);
+    }
+
+    #[test]
+    fn test_gop_violations_b_frames() {
+        let platform = StreamingPlatform::Twitch;
+        for b_frames in [None, Some(0), Some(2)] {
+            assert!(RecommendationEngine::gop_violations(platform, &gop_output("ffmpeg_nvenc", 2, b_frames)).is_empty());
+        }
+        assert_eq!(
+            RecommendationEngine::gop_violations(platform, &gop_output("ffmpeg_nvenc", 10, Some(4))),
+            vec![
+                GopViolation::KeyframeInterval { current: 10, required: 2 },
+                GopViolation::BFrames { current: 4, max: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_fallback_encoder_switches_quality_mode() {
+        let context = RecommendationEngine::encoder_selection_context(
+            &create_test_hardware(),
+            StreamingPlatform::Other,
+            StreamingStyle::Gaming,
+            0.0,
+        );
+        let mut output = RecommendedOutputSettings {
+            encoder: "jim_av1_nvenc".to_string(),
+            bitrate_kbps: 0,
+            keyframe_interval_secs: 2,
+            preset: Some("p5".to_string()),
+            rate_control: "CQP".to_string(),
+            quality_value: Some(20),
+            recording_format: Some("mkv".to_string()),
+            protocol: StreamingProtocol::default(),
+            pre_analysis: false,
+            pa_look_ahead: None,
+            pa_scene_change_detect: false,
+            output_mode: ObsOutputMode::Simple,
+            nvenc_tuning: Some(NvencTuning::HighQuality),
+            hevc_profile: None,
+        };
+
+        // x264へのフォールバックではCQPをCRFに切り替え、品質値は維持
+        let x264 = EncoderSelector::select_for_encoder(&context, "obs_x264");
+        output.apply_fallback_encoder(&x264);
+        assert_eq!(output.encoder, "obs_x264");
+        assert_eq!(output.preset.as_deref(), Some(x264.preset.as_str()));
+        assert_eq!(output.rate_control, "CRF");
+        assert_eq!(output.quality_value, Some(20));
+        // Bフレームを使用するため詳細モードが必要
+        assert_eq!(output.output_mode, ObsOutputMode::Advanced);
+        // x264にはNVENCのチューニングを適用しない
+        assert_eq!(output.nvenc_tuning, None);
+
+        // ビットレート制御はそのまま維持
+        output.rate_control = "VBR".to_string();
+        let hevc = EncoderSelector::select_for_encoder(&context, "jim_hevc_nvenc");
+        output.apply_fallback_encoder(&hevc);
+        assert_eq!(output.encoder, "jim_hevc_nvenc");
+        assert_eq!(output.rate_control, "VBR");
+    }
+
+    #[test]
+    fn test_apply_stream_latency_updates_nvenc_tuning() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 3080"));
+        let mut recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
+        assert_eq!(recommended.output.nvenc_tuning, Some(NvencTuning::HighQuality));
 
-        // クラッシュせず推奨設定を生成
-        assert!(recommended.overall_score <= 100);
+        RecommendationEngine::apply_stream_latency(&mut recommended, &hardware, StreamLatency::Low);
+        assert_eq!(recommended.output.nvenc_tuning, Some(NvencTuning::HighQuality));
+
+        RecommendationEngine::apply_stream_latency(&mut recommended, &hardware, StreamLatency::UltraLow);
+        assert_eq!(recommended.output.nvenc_tuning, Some(NvencTuning::LowLatency));
+
+        // NVENC以外では何もしない
+        let mut x264 = RecommendationEngine::calculate_recommendations(
+            &create_test_hardware(),
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        RecommendationEngine::apply_stream_latency(&mut x264, &create_test_hardware(), StreamLatency::UltraLow);
+        assert_eq!(x264.output.nvenc_tuning, None);
     }
 
     #[test]
-    fn test_edge_case_unknown_gpu() {
-        // 不明なGPU名
-        let mut hardware = create_test_hardware();
-        hardware.gpu = Some(GpuInfo {
-            name: "Unknown Exotic GPU 9000".to_string(),
-        });
-        let current = create_test_settings();
+    fn test_apply_setup_type_dedicated_streaming_pc() {
+        let mut hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4070"));
+        hardware.cpu_cores = 16;
+        let mut recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        assert_eq!(recommended.output.encoder, "ffmpeg_nvenc");
+        let reason_count = recommended.reasons.len();
 
-        let recommended = RecommendationEngine::calculate_recommendations(
+        let mut context = RecommendationEngine::encoder_selection_context(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
+        context.setup_type = SetupType::DualPcStreaming;
+        RecommendationEngine::apply_setup_type(&mut recommended, &context);
 
-        // 不明GPUはCPUエンコーダーにフォールバック
         assert_eq!(recommended.output.encoder, "obs_x264");
+        assert_eq!(recommended.output.preset.as_deref(), Some("slow"));
+        assert_eq!(recommended.output.nvenc_tuning, None);
+        assert_eq!(recommended.output.rate_control, "CBR");
+        // NVENCの推奨理由を置き換える
+        assert_eq!(recommended.reasons.len(), reason_count);
+        assert!(recommended.reasons.iter().any(|r| r.contains("配信専用PC")));
+        assert!(!recommended.reasons.iter().any(|r| r.contains("NVENC")));
     }
 
     #[test]
-    fn test_edge_case_combined_low_specs() {
-        // 複合エッジケース: 低CPU、低メモリ、低回線
-        let mut hardware = create_test_hardware();
-        hardware.cpu_cores = 2;
-        hardware.total_memory_gb = 4.0;
-        hardware.gpu = None;
-        let current = create_test_settings();
-
-        let recommended = RecommendationEngine::calculate_recommendations(
+    fn test_apply_setup_type_single_and_gaming_pc() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4070"));
+        let original = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        let mut context = RecommendationEngine::encoder_selection_context(
+            &hardware,
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            2.0, // 低速回線
+            20.0,
         );
 
-        // 全て低スペックでも推奨設定を生成
-        assert_eq!(recommended.video.output_width, 1280, "低スペックは720p");
-        assert_eq!(recommended.video.output_height, 720);
-        assert_eq!(recommended.video.fps, 30, "低スペックは30fps");
-        assert!(recommended.output.bitrate_kbps <= 2500, "低速回線制限");
-        assert!(recommended.output.bitrate_kbps >= 2000, "最低ビットレート保証");
-        assert!(recommended.reasons.len() > 0, "理由が含まれる");
+        let mut single = original.clone();
+        RecommendationEngine::apply_setup_type(&mut single, &context);
+        assert_eq!(single.reasons, original.reasons);
+        assert_eq!(single.output.encoder, original.output.encoder);
+
+        context.setup_type = SetupType::DualPcGaming;
+        let mut gaming = original.clone();
+        RecommendationEngine::apply_setup_type(&mut gaming, &context);
+        assert_eq!(gaming.output.encoder, original.output.encoder);
+        assert!(gaming.reasons.last().is_some_and(|r| r.contains("ゲーム用PC")));
+
+        // 録画（品質固定）の推奨では何もしない
+        context.setup_type = SetupType::DualPcStreaming;
+        let mut recording =
+            RecommendationEngine::calculate_recording_recommendations(&hardware, &create_test_settings(), StreamingStyle::Gaming);
+        let encoder = recording.output.encoder.clone();
+        RecommendationEngine::apply_setup_type(&mut recording, &context);
+        assert_eq!(recording.output.encoder, encoder);
     }
 
-    // === 配信スタイルによる違いテスト ===
-
     #[test]
-    fn test_style_talk_vs_gaming_bitrate() {
-        // トーク vs ゲーム: ビットレート比較
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
-
-        let talk = RecommendationEngine::calculate_recommendations(
+    fn test_apply_hdr_mode_selects_hevc_main10() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4070"));
+        let original = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&create_test_settings()),
             StreamingPlatform::YouTube,
-            StreamingStyle::Talk,
-            10.0,
+            StreamingStyle::Gaming,
+            20.0,
         );
-
-        let gaming = RecommendationEngine::calculate_recommendations(
+        let mut context = RecommendationEngine::encoder_selection_context(
             &hardware,
-            &current,
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
 
-        // トークはゲームより低ビットレート（0.8 vs 1.2倍率）
-        assert!(talk.output.bitrate_kbps < gaming.output.bitrate_kbps,
-            "トーク{}kbps < ゲーム{}kbps",
-            talk.output.bitrate_kbps, gaming.output.bitrate_kbps);
+        // HDRモードが無効な場合は何もしない
+        let mut sdr = original.clone();
+        RecommendationEngine::apply_hdr_mode(&mut sdr, &context);
+        assert_eq!(sdr.output.encoder, original.output.encoder);
+        assert_eq!(sdr.output.hevc_profile, None);
+
+        context.hdr_mode = true;
+        let mut hdr = original.clone();
+        RecommendationEngine::apply_hdr_mode(&mut hdr, &context);
+        assert_eq!(hdr.output.encoder, "jim_hevc_nvenc");
+        assert_eq!(hdr.output.hevc_profile, Some(HevcProfile::Main10));
+        assert_eq!(hdr.output.bitrate_kbps, original.output.bitrate_kbps);
+        assert!(hdr.reasons.iter().any(|r| r.contains("OBS 30") && r.contains("キャプチャーボード")));
     }
 
     #[test]
-    fn test_style_talk_vs_gaming_fps() {
-        // トーク vs ゲーム: FPS比較
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
-
-        let talk = RecommendationEngine::calculate_recommendations(
+    fn test_apply_hdr_mode_without_hevc_keeps_sdr() {
+        let hardware = hardware_with_gpu(None);
+        let mut recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
+            Some(&create_test_settings()),
             StreamingPlatform::YouTube,
-            StreamingStyle::Talk,
-            10.0,
+            StreamingStyle::Gaming,
+            20.0,
         );
-
-        let gaming = RecommendationEngine::calculate_recommendations(
+        let encoder = recommended.output.encoder.clone();
+        let mut context = RecommendationEngine::encoder_selection_context(
             &hardware,
-            &current,
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
+        context.hdr_mode = true;
+        RecommendationEngine::apply_hdr_mode(&mut recommended, &context);
 
-        // トークは30fps、ゲームは60fps
-        assert_eq!(talk.video.fps, 30, "トークは30fps");
-        assert_eq!(gaming.video.fps, 60, "ゲームは60fps");
+        assert_eq!(recommended.output.encoder, encoder);
+        assert_eq!(recommended.output.hevc_profile, None);
+        assert!(recommended.reasons.last().is_some_and(|r| r.contains("SDR")));
+    }
+
+    fn hardware_with_gpu(gpu_name: Option<&str>) -> HardwareInfo {
+        let mut hardware = create_test_hardware();
+        hardware.gpu = gpu_name.map(|name| GpuInfo {
+            name: name.to_string(),
+            driver_version: None,
+        });
+        hardware
+    }
+
+    fn recording_tier(hardware: &HardwareInfo) -> EffectiveTier {
+        RecommendationEngine::encoder_selection_context(hardware, StreamingPlatform::Other, StreamingStyle::Other, 0.0)
+            .effective_tier()
     }
 
     #[test]
-    fn test_style_music_high_audio_bitrate() {
-        // 音楽配信: 高音質音声
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
+    fn test_recording_tier_s_and_a_use_best_codec_cqp20() {
+        for (gpu, tier) in [
+            ("NVIDIA GeForce RTX 4090", EffectiveTier::TierS),
+            ("NVIDIA GeForce RTX 4070", EffectiveTier::TierA),
+        ] {
+            let hardware = hardware_with_gpu(Some(gpu));
+            assert_eq!(recording_tier(&hardware), tier, "{gpu}");
+
+            // 録画用エンコーダーの選択（AV1 > HEVC > H.264）に従う
+            let recommended = RecommendationEngine::calculate_for_recording(&hardware);
+            assert_eq!(recommended.encoder, "jim_av1_nvenc", "{gpu}");
+            assert_eq!(recommended.rate_control, RateControlMode::Cqp);
+            assert_eq!(recommended.cqp_value, Some(20));
+            assert_eq!(recommended.bitrate_kbps, None);
+            assert_eq!(recommended.container, RecordingContainer::Mkv);
+            assert!(!recommended.use_lossless);
+        }
+    }
 
-        let music = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Music,
-            10.0,
-        );
+    #[test]
+    fn test_recording_tier_b_and_c_use_cqp23() {
+        for (gpu, tier) in [
+            ("NVIDIA GeForce RTX 3060", EffectiveTier::TierB),
+            ("NVIDIA GeForce RTX 3050", EffectiveTier::TierC),
+        ] {
+            let hardware = hardware_with_gpu(Some(gpu));
+            assert_eq!(recording_tier(&hardware), tier, "{gpu}");
+
+            let recommended = RecommendationEngine::calculate_for_recording(&hardware);
+            assert_eq!(recommended.encoder, "jim_hevc_nvenc", "{gpu}");
+            assert_eq!(recommended.rate_control, RateControlMode::Cqp);
+            assert_eq!(recommended.cqp_value, Some(23));
+            assert_eq!(recommended.bitrate_kbps, None);
+        }
+    }
 
-        // 音楽は320kbps
-        assert_eq!(music.audio.bitrate_kbps, 320, "音楽は320kbps高音質");
+    #[test]
+    fn test_recording_tier_d_and_e_use_cbr() {
+        for (gpu, tier) in [
+            ("NVIDIA GeForce GTX 1060", EffectiveTier::TierD),
+            ("NVIDIA GeForce GTX 1050 Ti", EffectiveTier::TierE),
+        ] {
+            let hardware = hardware_with_gpu(Some(gpu));
+            assert_eq!(recording_tier(&hardware), tier, "{gpu}");
+
+            // GTX 10シリーズはHEVCに対応しないため、H.264のNVENC
+            let recommended = RecommendationEngine::calculate_for_recording(&hardware);
+            assert_eq!(recommended.encoder, "ffmpeg_nvenc", "{gpu}");
+            assert_eq!(recommended.rate_control, RateControlMode::Cbr);
+            assert_eq!(recommended.bitrate_kbps, Some(20_000));
+            assert_eq!(recommended.cqp_value, None);
+        }
+
+        // GPUなしはx264でCPUティアに応じたCRF
+        let recommended = RecommendationEngine::calculate_for_recording(&hardware_with_gpu(None));
+        assert_eq!(recommended.encoder, "obs_x264");
+        assert_eq!(recommended.rate_control, RateControlMode::Crf);
+        assert_eq!(recommended.cqp_value, Some(20));
+        assert_eq!(recommended.bitrate_kbps, None);
     }
 
     #[test]
-    fn test_style_art_downscale_filter() {
-        // お絵描き: ダウンスケールフィルター
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
+    fn test_recording_recommenders_agree() {
+        for gpu in [
+            Some("NVIDIA GeForce RTX 4090"),
+            Some("NVIDIA GeForce RTX 3060"),
+            Some("NVIDIA GeForce GTX 1050 Ti"),
+            None,
+        ] {
+            let hardware = hardware_with_gpu(gpu);
+            let tiered = RecommendationEngine::calculate_for_recording(&hardware);
+            let full = RecommendationEngine::calculate_recording_recommendations(
+                &hardware,
+                &create_test_settings(),
+                StreamingStyle::Other,
+            );
 
-        let art = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Art,
-            10.0,
-        );
+            assert_eq!(full.output.encoder, tiered.encoder, "{gpu:?}");
+            assert_eq!(full.output.rate_control, tiered.rate_control.as_obs_value(), "{gpu:?}");
+            assert_eq!(full.output.quality_value, tiered.cqp_value.map(u32::from), "{gpu:?}");
+            assert_eq!(full.output.bitrate_kbps, tiered.bitrate_kbps.unwrap_or(0), "{gpu:?}");
+            assert!(full.output.is_recording());
+        }
+    }
 
-        let gaming = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Gaming,
-            10.0,
-        );
+    #[test]
+    fn test_multitrack_tier_s_uses_four_renditions() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4090"));
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 50.0).unwrap();
+
+        assert!(multitrack.enabled);
+        assert_eq!(multitrack.session_budget, 8);
+        let ladder: Vec<_> = multitrack.renditions.iter().map(|r| (r.height, r.fps)).collect();
+        assert_eq!(ladder, vec![(1080, 60), (720, 60), (480, 30), (360, 30)]);
+        // 最上位はAV1、下位はH.264
+        assert_eq!(multitrack.renditions[0].codec, "av1");
+        assert!(multitrack.renditions[1..].iter().all(|r| r.codec == "h264"));
+
+        // ドライバーがAV1非対応の場合、最上位はHEVC
+        let mut hardware = hardware;
+        if let Some(gpu) = hardware.gpu.as_mut() {
+            gpu.driver_version = Some("516.94".to_string());
+        }
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 50.0).unwrap();
+        assert_eq!(multitrack.renditions[0].codec, "hevc");
+    }
 
-        // 両方ともBicubic（画面キャプチャ向け）
-        assert_eq!(art.video.downscale_filter, "Bicubic");
-        assert_eq!(gaming.video.downscale_filter, "Bicubic");
+    #[test]
+    fn test_multitrack_tier_a_uses_three_renditions() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4070"));
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 50.0).unwrap();
+
+        assert!(multitrack.enabled);
+        let ladder: Vec<_> = multitrack.renditions.iter().map(|r| (r.height, r.fps)).collect();
+        assert_eq!(ladder, vec![(1080, 60), (720, 60), (480, 30)]);
+        assert!(multitrack.reason.contains("1080p60 + 720p60 + 480p30"));
     }
 
     #[test]
-    fn test_style_talk_downscale_filter() {
-        // トーク: Lanczosフィルター（カメラ向け）
-        let hardware = create_test_hardware();
-        let current = create_test_settings();
+    fn test_multitrack_not_recommended_below_tier_a() {
+        for gpu in [
+            "NVIDIA GeForce RTX 3060",
+            "NVIDIA GeForce RTX 3050",
+            "NVIDIA GeForce GTX 1060",
+            "NVIDIA GeForce GTX 1050 Ti",
+        ] {
+            let hardware = hardware_with_gpu(Some(gpu));
+            let multitrack =
+                RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 50.0).unwrap();
 
-        let talk = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
-            StreamingPlatform::YouTube,
-            StreamingStyle::Talk,
-            10.0,
-        );
+            assert!(!multitrack.enabled, "{gpu}");
+            assert!(multitrack.renditions.is_empty(), "{gpu}");
+            assert!(multitrack.reason.contains("ティア"), "{gpu}");
+        }
 
-        assert_eq!(talk.video.downscale_filter, "Lanczos",
-            "トークはLanczos（カメラ向け）");
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware_with_gpu(None), StreamingPlatform::Twitch, 50.0)
+                .unwrap();
+        assert!(!multitrack.enabled);
+        assert_eq!(multitrack.session_budget, 0);
     }
 
-    // === スコア算出の詳細テスト ===
+    #[test]
+    fn test_multitrack_trims_ladder_to_network_speed() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4090"));
+
+        // 12Mbps * 0.8 = 9600kbps → 1080p60(6000) + 720p60(3000) のみ
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 12.0).unwrap();
+        assert!(multitrack.enabled);
+        assert_eq!(multitrack.renditions.len(), 2);
+
+        // 2本でも収まらない場合は推奨しない
+        let multitrack =
+            RecommendationEngine::recommend_multitrack(&hardware, StreamingPlatform::Twitch, 5.0).unwrap();
+        assert!(!multitrack.enabled);
+        assert!(multitrack.reason.contains("回線速度"));
+    }
 
     #[test]
-    fn test_score_resolution_mismatch() {
-        // 解像度不一致でスコア減少
-        let hardware = create_test_hardware();
-        let mut current = create_test_settings();
-        current.video.output_width = 1280;
-        current.video.output_height = 720;
+    fn test_multitrack_only_for_supported_platforms() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4090"));
+        for platform in [StreamingPlatform::YouTube, StreamingPlatform::NicoNico, StreamingPlatform::Other] {
+            assert!(RecommendationEngine::recommend_multitrack(&hardware, platform, 50.0).is_none());
+        }
+    }
+
+    fn hardware_with_display(gpu_name: Option<&str>, width: u32, height: u32) -> HardwareInfo {
+        let mut hardware = hardware_with_gpu(gpu_name);
+        hardware.displays = vec![
+            DisplayInfo { width: 1920, height: 1080, refresh_rate_hz: 60, is_primary: false },
+            DisplayInfo { width, height, refresh_rate_hz: 165, is_primary: true },
+        ];
+        hardware
+    }
+
+    #[test]
+    fn test_recommended_fps_for_display() {
+        let cases = [
+            // (リフレッシュレート, 上限, 期待値)
+            (144, 60, 48),
+            (144, 30, 24),
+            (144, 120, 72),
+            (120, 60, 60),
+            (120, 30, 30),
+            (240, 60, 60),
+            (240, 30, 30),
+            (240, 120, 120),
+            (60, 60, 60),
+            (60, 30, 30),
+            (165, 60, 55),
+            // 59.94Hzは60Hzとして扱う
+            (59, 60, 60),
+            // 割り切れる候補が最低FPSを下回る場合は上限のまま
+            (165, 30, 30),
+            (0, 60, 60),
+        ];
+        for (display_hz, platform_fps, expected) in cases {
+            assert_eq!(
+                RecommendationEngine::recommended_fps_for_display(display_hz, platform_fps),
+                expected,
+                "{display_hz}Hz / 上限{platform_fps}fps"
+            );
+        }
+    }
 
+    #[test]
+    fn test_fps_recommendation_uses_display_refresh_rate() {
+        let mut hardware = hardware_with_display(Some("NVIDIA GeForce RTX 3060"), 1920, 1080);
+        hardware.displays[1].refresh_rate_hz = 144;
         let recommended = RecommendationEngine::calculate_recommendations(
             &hardware,
-            &current,
-            StreamingPlatform::YouTube,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
+        assert_eq!(recommended.video.fps, 48);
+        assert!(recommended.reasons.iter().any(|reason| reason.contains("144Hz")));
 
-        // 推奨は1920x1080だが現在は1280x720なのでスコア低下
-        assert!(recommended.overall_score < 80,
-            "解像度不一致でスコア低下: {}", recommended.overall_score);
+        // リフレッシュレートが取得できない場合は従来どおり
+        hardware.displays[1].refresh_rate_hz = 0;
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &hardware,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            20.0,
+        );
+        assert_eq!(recommended.video.fps, 60);
+    }
+
+    fn recommend_for(hardware: &HardwareInfo, platform: StreamingPlatform, style: StreamingStyle, network_speed_mbps: f64) -> RecommendedSettings {
+        RecommendationEngine::calculate_recommendations(
+            hardware,
+            Some(&create_test_settings()),
+            platform,
+            style,
+            network_speed_mbps,
+        )
     }
 
     #[test]
-    fn test_score_fps_mismatch() {
-        // FPS不一致でスコア減少
-        let hardware = create_test_hardware();
+    fn test_1440p_output_for_supported_platform_and_high_tier() {
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 4080"), 2560, 1440);
+        let youtube = recommend_for(&hardware, StreamingPlatform::YouTube, StreamingStyle::Gaming, 30.0);
+        assert_eq!((youtube.video.output_width, youtube.video.output_height), (2560, 1440));
+        assert!(youtube.video.aspect.is_none());
+
+        // Twitchは1080pまで
+        let twitch = recommend_for(&hardware, StreamingPlatform::Twitch, StreamingStyle::Gaming, 30.0);
+        assert_eq!((twitch.video.output_width, twitch.video.output_height), (1920, 1080));
+
+        // 回線速度が足りない場合・GPUのティアが低い場合は1080p
+        let slow = recommend_for(&hardware, StreamingPlatform::YouTube, StreamingStyle::Gaming, 10.0);
+        assert_eq!((slow.video.output_width, slow.video.output_height), (1920, 1080));
+        let mid_tier = hardware_with_display(Some("NVIDIA GeForce RTX 3060"), 2560, 1440);
+        let mid = recommend_for(&mid_tier, StreamingPlatform::YouTube, StreamingStyle::Gaming, 30.0);
+        assert_eq!((mid.video.output_width, mid.video.output_height), (1920, 1080));
+    }
+
+    #[test]
+    fn test_ultrawide_output_kept_native_on_supported_platform() {
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 4080"), 3440, 1440);
+        let recommended = recommend_for(&hardware, StreamingPlatform::YouTube, StreamingStyle::Gaming, 30.0);
+
+        assert_eq!((recommended.video.output_width, recommended.video.output_height), (3440, 1440));
+        let aspect = recommended.video.aspect.unwrap();
+        assert_eq!(aspect.handling, AspectHandling::Native);
+        assert_eq!((aspect.canvas_width, aspect.canvas_height), (3440, 1440));
+    }
+
+    #[test]
+    fn test_ultrawide_canvas_on_16_9_platform() {
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 4080"), 3440, 1440);
+
+        let gaming = recommend_for(&hardware, StreamingPlatform::Twitch, StreamingStyle::Gaming, 30.0);
+        assert_eq!((gaming.video.output_width, gaming.video.output_height), (1920, 1080));
+        let aspect = gaming.video.aspect.unwrap();
+        assert_eq!(aspect.handling, AspectHandling::Crop);
+        assert_eq!((aspect.crop_width, aspect.crop_height), (2560, 1440));
+        assert_eq!((aspect.letterbox_width, aspect.letterbox_height), (1920, 804));
+        assert!(gaming.reasons.iter().any(|reason| reason.contains("2560x1440")));
+
+        let talk = recommend_for(&hardware, StreamingPlatform::Twitch, StreamingStyle::Talk, 30.0);
+        assert_eq!(talk.video.aspect.unwrap().handling, AspectHandling::Letterbox);
+    }
+
+    #[test]
+    fn test_resolution_score_is_proportional() {
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 4080"), 2560, 1440);
+        let recommended = recommend_for(&hardware, StreamingPlatform::YouTube, StreamingStyle::Gaming, 30.0);
+        let score_for = |width: u32, height: u32| {
+            let mut current = create_test_settings();
+            current.video.output_width = width;
+            current.video.output_height = height;
+            RecommendationEngine::resolution_score(&current, &recommended)
+        };
+
+        assert_eq!(score_for(2560, 1440), 30);
+        // 1080pは1440pの56%の画素数
+        assert_eq!(score_for(1920, 1080), 17);
+        assert!(score_for(1280, 720) < score_for(1920, 1080));
+        // アスペクト比が異なる場合はさらに減点
+        assert!(score_for(1920, 1440) < score_for(2560, 1440) && score_for(640, 480) < score_for(1280, 720));
+        assert!(score_for(1280, 720) > 0);
+    }
+
+    /// すべての項目が推奨と異なる設定
+    fn mismatched_settings(recommended: &RecommendedSettings) -> ObsSettings {
         let mut current = create_test_settings();
-        current.video.fps_numerator = 30;
+        current.video.output_width = 640;
+        current.video.output_height = 480;
+        current.video.fps_numerator = 15;
         current.video.fps_denominator = 1;
+        current.output.bitrate_kbps = recommended.output.bitrate_kbps + 5000;
+        current.output.encoder = "obs_x264".to_string();
+        current
+    }
 
+    #[test]
+    fn test_score_breakdown_each_dimension_reaches_max() {
         let recommended = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
+            &create_test_hardware(),
+            None,
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
+        let base = mismatched_settings(&recommended);
+        let base_score = RecommendationEngine::calculate_score(&base, &recommended);
+        assert!(base_score.resolution_score < ScoreBreakdown::RESOLUTION_MAX);
+        assert_eq!(base_score.fps_score, 0);
+        assert_eq!(base_score.bitrate_score, 0);
+        assert!(base_score.encoder_score < ScoreBreakdown::ENCODER_MAX);
+
+        let mut resolution = base.clone();
+        resolution.video.output_width = recommended.video.output_width;
+        resolution.video.output_height = recommended.video.output_height;
+        let score = RecommendationEngine::calculate_score(&resolution, &recommended);
+        assert_eq!(score.resolution_score, ScoreBreakdown::RESOLUTION_MAX);
+        assert_eq!(
+            (score.fps_score, score.bitrate_score, score.encoder_score),
+            (base_score.fps_score, base_score.bitrate_score, base_score.encoder_score)
+        );
 
-        // 推奨は60fpsだが現在は30fpsなのでスコア低下
-        assert!(recommended.overall_score < 90,
-            "FPS不一致でスコア低下: {}", recommended.overall_score);
+        let mut fps = base.clone();
+        fps.video.fps_numerator = recommended.video.fps;
+        let score = RecommendationEngine::calculate_score(&fps, &recommended);
+        assert_eq!(score.fps_score, ScoreBreakdown::FPS_MAX);
+        assert_eq!(
+            (score.resolution_score, score.bitrate_score, score.encoder_score),
+            (base_score.resolution_score, base_score.bitrate_score, base_score.encoder_score)
+        );
+
+        let mut bitrate = base.clone();
+        bitrate.output.bitrate_kbps = recommended.output.bitrate_kbps;
+        let score = RecommendationEngine::calculate_score(&bitrate, &recommended);
+        assert_eq!(score.bitrate_score, ScoreBreakdown::BITRATE_MAX);
+        assert_eq!(
+            (score.resolution_score, score.fps_score, score.encoder_score),
+            (base_score.resolution_score, base_score.fps_score, base_score.encoder_score)
+        );
+
+        let mut encoder = base;
+        encoder.output.encoder = "ffmpeg_nvenc".to_string();
+        let score = RecommendationEngine::calculate_score(&encoder, &recommended);
+        assert_eq!(score.encoder_score, ScoreBreakdown::ENCODER_MAX);
+        assert_eq!(
+            (score.resolution_score, score.fps_score, score.bitrate_score),
+            (base_score.resolution_score, base_score.fps_score, base_score.bitrate_score)
+        );
     }
 
     #[test]
-    fn test_score_bitrate_close_match() {
-        // ビットレートがほぼ一致（500kbps以内）
-        let hardware = create_test_hardware();
+    fn test_score_breakdown_total_is_sum_of_dimensions() {
+        let full = ScoreBreakdown::new(
+            ScoreBreakdown::RESOLUTION_MAX,
+            ScoreBreakdown::FPS_MAX,
+            ScoreBreakdown::BITRATE_MAX,
+            ScoreBreakdown::ENCODER_MAX,
+        );
+        assert_eq!(full.total, 100);
+        assert_eq!(ScoreBreakdown::new(17, 10, 15, 10).total, 52);
+
         let current = create_test_settings();
+        let recommended = RecommendationEngine::calculate_recommendations(
+            &create_test_hardware(),
+            Some(&current),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Talk,
+            10.0,
+        );
+        let breakdown = recommended.score_breakdown;
+        assert_eq!(
+            breakdown.total,
+            breakdown.resolution_score + breakdown.fps_score + breakdown.bitrate_score + breakdown.encoder_score
+        );
+        assert_eq!(recommended.overall_score, breakdown.total);
+    }
 
+    #[test]
+    fn test_recording_score_rewards_quality_rate_control() {
         let recommended = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &current,
+            &create_test_hardware(),
+            None,
             StreamingPlatform::YouTube,
             StreamingStyle::Gaming,
             10.0,
         );
+        let mut current = create_test_settings();
+        current.output.rate_control = Some("CQP".to_string());
+        let cqp = RecommendationEngine::calculate_recording_score(&current, &recommended);
+        assert_eq!(cqp.bitrate_score, ScoreBreakdown::BITRATE_MAX);
+
+        current.output.rate_control = Some("CBR".to_string());
+        let cbr = RecommendationEngine::calculate_recording_score(&current, &recommended);
+        assert_eq!(cbr.bitrate_score, 0);
+        assert_eq!(cqp.total - cbr.total, ScoreBreakdown::BITRATE_MAX);
+    }
 
-        // 現在のビットレートを推奨値に近づける
-        let mut adjusted_current = current.clone();
-        adjusted_current.output.bitrate_kbps = recommended.output.bitrate_kbps + 300;
-
-        let score_check = RecommendationEngine::calculate_recommendations(
-            &hardware,
-            &adjusted_current,
-            StreamingPlatform::YouTube,
+    fn recommend_canvas_for(hardware: &HardwareInfo) -> (Option<u32>, Option<u32>) {
+        let settings = RecommendationEngine::calculate_recommendations(
+            hardware,
+            Some(&create_test_settings()),
+            StreamingPlatform::Twitch,
             StreamingStyle::Gaming,
-            10.0,
+            20.0,
         );
+        (settings.video.canvas_width, settings.video.canvas_height)
+    }
 
-        // 500kbps以内なら高スコア（ビットレート分30点満点）
-        assert!(score_check.overall_score >= 50,
-            "ビットレート近似でスコア高め: {}", score_check.overall_score);
+    #[test]
+    fn test_canvas_matches_primary_display() {
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 3060"), 2560, 1440);
+        assert_eq!(recommend_canvas_for(&hardware), (Some(2560), Some(1440)));
     }
 
     #[test]
-    fn test_reasons_not_empty() {
-        // すべてのパターンで理由が含まれることを確認
-        let test_cases = vec![
-            (StreamingPlatform::YouTube, StreamingStyle::Gaming, 10.0),
-            (StreamingPlatform::Twitch, StreamingStyle::Talk, 5.0),
-            (StreamingPlatform::NicoNico, StreamingStyle::Music, 3.0),
-            (StreamingPlatform::TwitCasting, StreamingStyle::Art, 20.0),
-        ];
+    fn test_canvas_for_4k_display_depends_on_tier() {
+        // Tier A以上は2560x1440
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 4080"), 3840, 2160);
+        assert_eq!(recommend_canvas_for(&hardware), (Some(2560), Some(1440)));
+
+        // Tier B以下は1920x1080
+        let hardware = hardware_with_display(Some("NVIDIA GeForce RTX 3060"), 3840, 2160);
+        assert_eq!(recommend_canvas_for(&hardware), (Some(1920), Some(1080)));
+    }
 
-        for (platform, style, network_speed) in test_cases {
-            let hardware = create_test_hardware();
-            let current = create_test_settings();
+    #[test]
+    fn test_canvas_not_recommended_without_display() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 4080"));
+        assert_eq!(recommend_canvas_for(&hardware), (None, None));
+    }
 
-            let recommended = RecommendationEngine::calculate_recommendations(
-                &hardware,
-                &current,
-                platform,
-                style,
-                network_speed,
-            );
+    #[test]
+    fn test_recording_container_obs_value() {
+        assert_eq!(RecordingContainer::Mkv.as_obs_value(), "mkv");
+        assert_eq!(RecordingContainer::Mp4.as_obs_value(), "mp4");
+        assert_eq!(RecordingContainer::Mov.as_obs_value(), "mov");
+    }
 
-            assert!(!recommended.reasons.is_empty(),
-                "{:?} {:?} で理由が空", platform, style);
-        }
+    #[test]
+    fn test_safe_defaults_with_hardware_encoder() {
+        let hardware = hardware_with_gpu(Some("NVIDIA GeForce RTX 3080"));
+        let safe = RecommendationEngine::calculate_safe_defaults(&hardware, StreamingPlatform::YouTube);
+
+        assert_eq!((safe.video.output_width, safe.video.output_height, safe.video.fps), (1280, 720, 30));
+        assert!((2500..=3500).contains(&safe.output.bitrate_kbps));
+        assert_eq!(codec_class(&safe.output.encoder), Some(EncoderCodecClass::H264Hardware));
+        assert_eq!(safe.output.rate_control, "CBR");
+        assert_eq!(safe.output.output_mode, ObsOutputMode::Simple);
+        // 現在の設定と比較しないためスコアは0
+        assert_eq!(safe.overall_score, 0);
+        assert_eq!(safe.score_breakdown, ScoreBreakdown::default());
+        assert!(safe.reasons.iter().any(|r| r.starts_with("1. 設定 → 出力 → 出力モード")));
+        assert!(safe.reasons.iter().any(|r| r.contains("映像ビットレート: ") && r.contains("Kbps")));
+    }
+
+    #[test]
+    fn test_safe_defaults_without_gpu_uses_x264_veryfast() {
+        let safe = RecommendationEngine::calculate_safe_defaults(&hardware_with_gpu(None), StreamingPlatform::Twitch);
+
+        assert_eq!(safe.output.encoder, "obs_x264");
+        assert_eq!(safe.output.preset.as_deref(), Some("veryfast"));
+        assert!(safe.reasons.iter().any(|r| r == "4. 設定 → 出力 → 配信 → エンコーダプリセット: veryfast"));
+    }
+
+    #[test]
+    fn test_safe_defaults_respect_platform_bitrate_limit() {
+        let safe = RecommendationEngine::calculate_safe_defaults(&hardware_with_gpu(None), StreamingPlatform::Showroom);
+        assert_eq!(safe.output.bitrate_kbps, 2500);
     }
 }
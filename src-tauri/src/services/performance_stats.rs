@@ -0,0 +1,287 @@
+// アプリ自身のパフォーマンス計測
+//
+// 最適化ツール自体が配信の負荷にならないよう、コマンドと監視処理の各段階の
+// 所要時間・成功/失敗回数をメモリ上に集計する（ファイルには書き込まない）。
+// 「最適化ツールのせいでドロップする」という報告の切り分けに使用する。
+
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 名前ごとに保持する直近の所要時間の件数
+const MAX_RECENT_DURATIONS: usize = 100;
+
+/// 監視ティック全体を表す段階名
+pub const MONITOR_TICK_STAGE: &str = "monitor_tick";
+
+/// 計測対象の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PerformanceKind {
+    /// Tauriコマンド
+    Command,
+    /// 監視処理の段階（CPU・GPU・ネットワークの取得など）
+    MonitoringStage,
+}
+
+/// 1つの計測対象の集計値
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceEntry {
+    /// 計測対象の種類
+    pub kind: PerformanceKind,
+    /// コマンド名・段階名
+    pub name: String,
+    /// 実行回数（起動から）
+    pub call_count: u64,
+    /// 失敗回数（起動から）
+    pub error_count: u64,
+    /// 直近の平均所要時間（ミリ秒）
+    pub avg_ms: f64,
+    /// 直近の95パーセンタイル所要時間（ミリ秒）
+    pub p95_ms: f64,
+    /// 最大所要時間（ミリ秒、起動から）
+    pub max_ms: f64,
+    /// 最後の所要時間（ミリ秒）
+    pub last_ms: f64,
+}
+
+/// アプリのパフォーマンス統計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPerformanceStats {
+    /// 計測対象ごとの集計値（平均所要時間の長い順）
+    pub entries: Vec<PerformanceEntry>,
+    /// 監視ティックが更新間隔を超えた回数（起動から）
+    pub slow_tick_count: u64,
+}
+
+/// 1つの計測対象の集計状態
+#[derive(Debug, Default)]
+struct RollingStats {
+    call_count: u64,
+    error_count: u64,
+    max: Duration,
+    /// 直近の所要時間（古い順）
+    recent: VecDeque<Duration>,
+}
+
+impl RollingStats {
+    fn record(&mut self, duration: Duration, success: bool) {
+        self.call_count += 1;
+        if !success {
+            self.error_count += 1;
+        }
+        self.max = self.max.max(duration);
+        self.recent.push_back(duration);
+        while self.recent.len() > MAX_RECENT_DURATIONS {
+            self.recent.pop_front();
+        }
+    }
+
+    fn to_entry(&self, kind: PerformanceKind, name: &str) -> PerformanceEntry {
+        let to_ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+
+        let mut sorted: Vec<Duration> = self.recent.iter().copied().collect();
+        sorted.sort_unstable();
+        let avg_ms = if sorted.is_empty() {
+            0.0
+        } else {
+            sorted.iter().map(|d| to_ms(*d)).sum::<f64>() / sorted.len() as f64
+        };
+        let p95_ms = sorted
+            .get((sorted.len() * 95 / 100).min(sorted.len().saturating_sub(1)))
+            .map_or(0.0, |d| to_ms(*d));
+
+        PerformanceEntry {
+            kind,
+            name: name.to_string(),
+            call_count: self.call_count,
+            error_count: self.error_count,
+            avg_ms,
+            p95_ms,
+            max_ms: to_ms(self.max),
+            last_ms: self.recent.back().map_or(0.0, |d| to_ms(*d)),
+        }
+    }
+}
+
+/// パフォーマンス統計の集計
+#[derive(Debug, Default)]
+pub struct PerformanceRecorder {
+    stats: HashMap<(PerformanceKind, String), RollingStats>,
+    slow_tick_count: u64,
+}
+
+impl PerformanceRecorder {
+    /// 新しい集計を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 1回分の所要時間を記録
+    pub fn record(&mut self, kind: PerformanceKind, name: &str, duration: Duration, success: bool) {
+        self.stats
+            .entry((kind, name.to_string()))
+            .or_default()
+            .record(duration, success);
+    }
+
+    /// 監視ティック全体の所要時間を記録
+    ///
+    /// # Returns
+    /// 更新間隔を超えた場合はtrue
+    pub fn record_tick(&mut self, duration: Duration, interval: Duration) -> bool {
+        self.record(PerformanceKind::MonitoringStage, MONITOR_TICK_STAGE, duration, true);
+        let slow = duration > interval;
+        if slow {
+            self.slow_tick_count += 1;
+        }
+        slow
+    }
+
+    /// 現在の統計
+    pub fn snapshot(&self) -> AppPerformanceStats {
+        let mut entries: Vec<PerformanceEntry> = self
+            .stats
+            .iter()
+            .map(|((kind, name), stats)| stats.to_entry(*kind, name))
+            .collect();
+        entries.sort_by(|a, b| b.avg_ms.total_cmp(&a.avg_ms).then_with(|| a.name.cmp(&b.name)));
+
+        AppPerformanceStats {
+            entries,
+            slow_tick_count: self.slow_tick_count,
+        }
+    }
+}
+
+/// グローバルなパフォーマンス統計
+static PERFORMANCE_RECORDER: Lazy<Mutex<PerformanceRecorder>> =
+    Lazy::new(|| Mutex::new(PerformanceRecorder::new()));
+
+/// 1回分の所要時間を記録
+pub fn record_performance(kind: PerformanceKind, name: &str, duration: Duration, success: bool) {
+    PERFORMANCE_RECORDER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .record(kind, name, duration, success);
+}
+
+/// 監視処理の段階を実行し、所要時間と成否を記録する
+///
+/// # Arguments
+/// * `name` - 段階名（例: "gpu_metrics"）
+/// * `stage` - 実行する処理
+pub fn measure_stage<T>(name: &str, stage: impl FnOnce() -> Result<T, AppError>) -> Result<T, AppError> {
+    let started = Instant::now();
+    let result = stage();
+    record_performance(PerformanceKind::MonitoringStage, name, started.elapsed(), result.is_ok());
+    result
+}
+
+/// コマンドを実行し、所要時間と成否を記録する
+///
+/// # Arguments
+/// * `name` - コマンド名
+/// * `command` - 実行するコマンドの処理
+pub async fn measure_command<T, F>(name: &str, command: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let started = Instant::now();
+    let result = command.await;
+    record_performance(PerformanceKind::Command, name, started.elapsed(), result.is_ok());
+    result
+}
+
+/// 監視ティック全体の所要時間を記録
+///
+/// 更新間隔を超えた場合は、監視処理が配信の負荷になっている可能性があるため警告を出力する
+///
+/// # Arguments
+/// * `duration` - ティックの所要時間
+/// * `interval` - 設定された更新間隔
+pub fn record_monitor_tick_duration(duration: Duration, interval: Duration) {
+    let slow = PERFORMANCE_RECORDER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .record_tick(duration, interval);
+    if slow {
+        tracing::warn!(
+            target: "performance",
+            duration_ms = duration.as_millis() as u64,
+            interval_ms = interval.as_millis() as u64,
+            "監視処理が更新間隔を超えました"
+        );
+    }
+}
+
+/// 現在のパフォーマンス統計を取得
+pub fn get_app_performance_stats() -> AppPerformanceStats {
+    PERFORMANCE_RECORDER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_record_counts_and_timings() {
+        let mut recorder = PerformanceRecorder::new();
+        for value in 1..=20 {
+            recorder.record(PerformanceKind::Command, "analyze_problems", ms(value), value != 20);
+        }
+        recorder.record(PerformanceKind::MonitoringStage, "cpu_usage", ms(1), true);
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats.entries.len(), 2);
+        let entry = &stats.entries[0];
+        assert_eq!(entry.name, "analyze_problems");
+        assert_eq!(entry.kind, PerformanceKind::Command);
+        assert_eq!(entry.call_count, 20);
+        assert_eq!(entry.error_count, 1);
+        assert!((entry.avg_ms - 10.5).abs() < 0.01);
+        assert!((entry.p95_ms - 20.0).abs() < 0.01);
+        assert!((entry.max_ms - 20.0).abs() < 0.01);
+        assert!((entry.last_ms - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_rolling_window_keeps_recent_durations() {
+        let mut recorder = PerformanceRecorder::new();
+        recorder.record(PerformanceKind::Command, "slow", ms(1000), true);
+        for _ in 0..MAX_RECENT_DURATIONS {
+            recorder.record(PerformanceKind::Command, "slow", ms(10), true);
+        }
+
+        let entry = &recorder.snapshot().entries[0];
+        // 平均は直近の件数から、最大と回数は起動からの値
+        assert!((entry.avg_ms - 10.0).abs() < 0.01);
+        assert!((entry.max_ms - 1000.0).abs() < 0.01);
+        assert_eq!(entry.call_count, MAX_RECENT_DURATIONS as u64 + 1);
+    }
+
+    #[test]
+    fn test_slow_tick_count() {
+        let mut recorder = PerformanceRecorder::new();
+        assert!(!recorder.record_tick(ms(200), ms(1000)));
+        assert!(recorder.record_tick(ms(1500), ms(1000)));
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats.slow_tick_count, 1);
+        assert_eq!(stats.entries[0].name, MONITOR_TICK_STAGE);
+        assert_eq!(stats.entries[0].call_count, 2);
+    }
+}
@@ -0,0 +1,703 @@
+// 配信プラットフォームのAPI連携
+//
+// 配信開始前の確認画面から配信タイトル・カテゴリーを更新できるようにする。
+// プラットフォームごとに `StreamPlatformApi` を実装する（現在はTwitchのみ、YouTubeは未対応）。
+// APIトークンはユーザーが発行したものをキーリングに保存し、
+// 設定ファイル・ログ・エクスポートには出力しない。
+//
+// HTTPリクエストはOS標準の `curl` コマンドで送信する（Windows 10以降・macOS・主要なLinuxに同梱）。
+// トークンはコマンドライン引数に含めず、標準入力から渡す設定（`--config -`）でのみ指定する。
+
+use crate::error::{AppError, ERROR_CODE_PLATFORM_API, ERROR_CODE_PLATFORM_AUTH, ERROR_CODE_PLATFORM_NOT_SUPPORTED};
+use crate::storage::config::StreamingPlatform;
+use crate::storage::credentials::{delete_platform_token, get_platform_token, save_platform_token};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// APIリクエストのタイムアウト
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTPリクエストに使用するコマンド
+const CURL_PROGRAM: &str = "curl";
+
+/// Twitchのトークン検証エンドポイント
+const TWITCH_VALIDATE_URL: &str = "https://id.twitch.tv/oauth2/validate";
+/// Twitchのカテゴリー（ゲーム）検索エンドポイント
+const TWITCH_GAMES_URL: &str = "https://api.twitch.tv/helix/games";
+/// Twitchのチャンネル情報エンドポイント
+const TWITCH_CHANNELS_URL: &str = "https://api.twitch.tv/helix/channels";
+
+/// 配信タイトル・カテゴリーの変更に必要なTwitchのスコープ
+pub const TWITCH_BROADCAST_SCOPE: &str = "channel:manage:broadcast";
+/// Twitchの配信タイトルの最大文字数
+pub const TWITCH_MAX_TITLE_CHARS: usize = 140;
+
+/// API連携のエラーの種類（`details.kind` としてフロントエンドに返す）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlatformErrorKind {
+    /// プラットフォームが未対応
+    NotSupported,
+    /// トークンが保存されていない
+    TokenMissing,
+    /// トークンが無効・期限切れ
+    TokenInvalid,
+    /// トークンに必要な権限がない
+    MissingScope,
+    /// カテゴリーが見つからない
+    CategoryNotFound,
+    /// リクエストが拒否された（タイトルが長すぎる等）
+    InvalidRequest,
+    /// 呼び出し回数の上限に達した
+    RateLimited,
+    /// 接続できない・タイムアウト
+    Network,
+    /// プラットフォーム側のエラー
+    Server,
+}
+
+impl PlatformErrorKind {
+    /// エラーコード
+    pub const fn error_code(self) -> &'static str {
+        match self {
+            Self::NotSupported => ERROR_CODE_PLATFORM_NOT_SUPPORTED,
+            Self::TokenMissing | Self::TokenInvalid | Self::MissingScope => ERROR_CODE_PLATFORM_AUTH,
+            Self::CategoryNotFound | Self::InvalidRequest | Self::RateLimited | Self::Network | Self::Server => {
+                ERROR_CODE_PLATFORM_API
+            }
+        }
+    }
+}
+
+/// API連携のエラーを作成
+///
+/// `details` は `{ "platform": StreamingPlatform, "kind": PlatformErrorKind }` の形式
+pub fn platform_error(platform: StreamingPlatform, kind: PlatformErrorKind, message: &str) -> AppError {
+    AppError::new(kind.error_code(), message).with_details(serde_json::json!({
+        "platform": platform,
+        "kind": kind,
+    }))
+}
+
+/// 未対応のプラットフォームのエラー
+fn not_supported(platform: StreamingPlatform) -> AppError {
+    platform_error(
+        platform,
+        PlatformErrorKind::NotSupported,
+        "このプラットフォームの配信タイトル・カテゴリーの更新には対応していません",
+    )
+}
+
+/// APIトークン
+///
+/// ログに出力されないよう、Debug出力では値を表示しない
+#[derive(Clone)]
+pub struct ApiToken(String);
+
+impl ApiToken {
+    /// 入力されたトークンを正規化して作成
+    ///
+    /// 前後の空白と、トークン生成ツールが付ける `oauth:` 接頭辞を取り除く
+    pub fn new(raw: &str) -> Result<Self, AppError> {
+        let trimmed = raw.trim();
+        let token = trimmed.strip_prefix("oauth:").unwrap_or(trimmed).trim();
+        if token.is_empty() || token.chars().any(char::is_whitespace) {
+            return Err(AppError::validation_failed("トークンの形式が不正です"));
+        }
+        Ok(Self(token.to_string()))
+    }
+
+    /// トークンの値（HTTPヘッダーにのみ使用する）
+    fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiToken(***)")
+    }
+}
+
+/// 更新する配信情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamInfo {
+    /// 配信タイトル
+    pub title: String,
+    /// カテゴリー名（Noneの場合は変更しない）
+    pub category: Option<String>,
+}
+
+impl StreamInfo {
+    /// タイトル・カテゴリーの前後の空白を取り除き、空のタイトルを拒否する
+    pub fn normalized(&self) -> Result<Self, AppError> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err(AppError::validation_failed("配信タイトルを入力してください"));
+        }
+        Ok(Self {
+            title: title.to_string(),
+            category: self
+                .category
+                .as_deref()
+                .map(str::trim)
+                .filter(|category| !category.is_empty())
+                .map(ToString::to_string),
+        })
+    }
+}
+
+/// トークンの確認結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformAccount {
+    /// プラットフォーム
+    pub platform: StreamingPlatform,
+    /// ログイン名
+    pub login: String,
+    /// 配信タイトル・カテゴリーを変更する権限があるか
+    pub can_update_stream_info: bool,
+    /// トークンの有効期限までの秒数（期限がない場合はNone）
+    pub expires_in_secs: Option<u64>,
+}
+
+/// 配信プラットフォームのAPI
+pub trait StreamPlatformApi {
+    /// 保存されたトークンを確認
+    fn verify_token(&self) -> impl Future<Output = Result<PlatformAccount, AppError>> + Send;
+
+    /// 配信タイトル・カテゴリーを更新
+    fn set_stream_info(&self, info: &StreamInfo) -> impl Future<Output = Result<(), AppError>> + Send;
+}
+
+/// TwitchのエラーレスポンスのHTTPステータスとメッセージからエラーの種類を判定
+fn twitch_error_kind(status: u16, message: &str) -> PlatformErrorKind {
+    match status {
+        401 | 403 if message.to_lowercase().contains("scope") => PlatformErrorKind::MissingScope,
+        401 => PlatformErrorKind::TokenInvalid,
+        403 => PlatformErrorKind::MissingScope,
+        429 => PlatformErrorKind::RateLimited,
+        400..=499 => PlatformErrorKind::InvalidRequest,
+        _ => PlatformErrorKind::Server,
+    }
+}
+
+/// Twitchのエラーを作成
+fn twitch_error(kind: PlatformErrorKind, detail: &str) -> AppError {
+    let message = match kind {
+        PlatformErrorKind::NotSupported => "Twitchはこの操作に対応していません".to_string(),
+        PlatformErrorKind::TokenMissing => "Twitchのトークンが保存されていません".to_string(),
+        PlatformErrorKind::TokenInvalid => {
+            "Twitchのトークンが無効か期限切れです。トークンを発行し直してください".to_string()
+        }
+        PlatformErrorKind::MissingScope => {
+            format!("Twitchのトークンに配信情報を変更する権限（{TWITCH_BROADCAST_SCOPE}）がありません")
+        }
+        PlatformErrorKind::CategoryNotFound => format!("Twitchのカテゴリー「{detail}」が見つかりません"),
+        PlatformErrorKind::InvalidRequest => format!("Twitchが変更を受け付けませんでした: {detail}"),
+        PlatformErrorKind::RateLimited => {
+            "TwitchのAPIの呼び出し回数の上限に達しました。しばらく待ってから再試行してください".to_string()
+        }
+        PlatformErrorKind::Network => format!("Twitchに接続できませんでした: {detail}"),
+        PlatformErrorKind::Server => format!("Twitchのサーバーでエラーが発生しました（{detail}）"),
+    };
+    platform_error(StreamingPlatform::Twitch, kind, &message)
+}
+
+/// TwitchのAPIのレスポンスを検査（2xx以外はエラー）
+fn check_twitch_response(status: u16, body: &str) -> Result<(), AppError> {
+    #[derive(Deserialize)]
+    struct ErrorBody {
+        #[serde(default)]
+        message: String,
+    }
+
+    if (200..300).contains(&status) {
+        return Ok(());
+    }
+
+    let message = serde_json::from_str::<ErrorBody>(body)
+        .map(|error| error.message)
+        .unwrap_or_default();
+    let kind = twitch_error_kind(status, &message);
+    let detail = if message.is_empty() { format!("HTTP {status}") } else { message };
+    Err(twitch_error(kind, &detail))
+}
+
+/// Twitchのトークン検証の結果
+#[derive(Debug, Clone, Deserialize)]
+struct TwitchValidation {
+    client_id: String,
+    login: String,
+    user_id: String,
+    #[serde(default)]
+    scopes: Vec<String>,
+    #[serde(default)]
+    expires_in: u64,
+}
+
+impl TwitchValidation {
+    /// 配信タイトル・カテゴリーを変更する権限があるか
+    fn can_update_stream_info(&self) -> bool {
+        self.scopes.iter().any(|scope| scope == TWITCH_BROADCAST_SCOPE)
+    }
+
+    fn to_account(&self) -> PlatformAccount {
+        PlatformAccount {
+            platform: StreamingPlatform::Twitch,
+            login: self.login.clone(),
+            can_update_stream_info: self.can_update_stream_info(),
+            // 0は期限のないトークン
+            expires_in_secs: (self.expires_in > 0).then_some(self.expires_in),
+        }
+    }
+}
+
+/// Twitchのチャンネル情報の更新内容
+fn twitch_channel_update_body(title: &str, game_id: Option<&str>) -> serde_json::Value {
+    let mut body = serde_json::json!({ "title": title });
+    if let Some(game_id) = game_id {
+        body["game_id"] = serde_json::json!(game_id);
+    }
+    body
+}
+
+/// HTTPリクエスト
+#[derive(Debug)]
+struct HttpRequest {
+    /// メソッド（"GET" / "PATCH"）
+    method: &'static str,
+    /// URL（クエリ文字列を含む）
+    url: String,
+    /// ヘッダー（"名前: 値"）
+    headers: Vec<String>,
+    /// JSONの本文
+    json_body: Option<String>,
+}
+
+impl HttpRequest {
+    fn new(method: &'static str, url: &'static str) -> Self {
+        Self { method, url: url.to_string(), headers: Vec::new(), json_body: None }
+    }
+
+    /// クエリパラメータを追加（値はURLエンコードする）
+    fn query(mut self, name: &str, value: &str) -> Self {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        self.url = format!("{}{separator}{name}={}", self.url, percent_encode(value));
+        self
+    }
+
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push(format!("{name}: {value}"));
+        self
+    }
+
+    fn json(mut self, body: &serde_json::Value) -> Self {
+        self.json_body = Some(body.to_string());
+        self.header("Content-Type", "application/json")
+    }
+
+    /// curlの設定ファイルの形式に変換（トークンを含むため標準入力でのみ渡す）
+    fn to_curl_config(&self) -> String {
+        let mut lines = vec![
+            format!("url = {}", curl_config_string(&self.url)),
+            format!("request = {}", curl_config_string(self.method)),
+        ];
+        for header in &self.headers {
+            lines.push(format!("header = {}", curl_config_string(header)));
+        }
+        if let Some(body) = &self.json_body {
+            lines.push(format!("data-binary = {}", curl_config_string(body)));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// クエリパラメータの値をURLエンコード（RFC 3986の非予約文字以外をエンコードする）
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => char::from(byte).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// curlの設定ファイルの文字列値（ダブルクォートで囲み、`\` と `"` をエスケープする）
+fn curl_config_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("\"{escaped}\"")
+}
+
+/// curlの出力（本文の後に `--write-out` のHTTPステータスが続く）を本文とステータスに分ける
+fn parse_curl_output(stdout: &str) -> Option<(u16, String)> {
+    let (body, status) = stdout.rsplit_once('\n')?;
+    let status = status.trim().parse::<u16>().ok().filter(|status| *status > 0)?;
+    Some((status, body.to_string()))
+}
+
+/// リクエストを送信し、HTTPステータスと本文を返す
+async fn send_twitch_request(request: HttpRequest) -> Result<(u16, String), AppError> {
+    let mut child = tokio::process::Command::new(CURL_PROGRAM)
+        .args([
+            "--silent",
+            "--show-error",
+            "--max-time",
+            &REQUEST_TIMEOUT.as_secs().to_string(),
+            "--write-out",
+            "\n%{http_code}",
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            let detail = if e.kind() == std::io::ErrorKind::NotFound {
+                "curl not found on PATH".to_string()
+            } else {
+                e.to_string()
+            };
+            twitch_error(PlatformErrorKind::Network, &detail)
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(request.to_curl_config().as_bytes())
+            .await
+            .map_err(|e| twitch_error(PlatformErrorKind::Network, &e.to_string()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| twitch_error(PlatformErrorKind::Network, &e.to_string()))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match parse_curl_output(&stdout) {
+        Some(response) if output.status.success() => Ok(response),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = stderr.trim().trim_start_matches("curl: ");
+            Err(twitch_error(PlatformErrorKind::Network, detail))
+        }
+    }
+}
+
+/// TwitchのAPI
+#[derive(Debug, Clone)]
+pub struct TwitchApi {
+    token: ApiToken,
+}
+
+impl TwitchApi {
+    /// キーリングのトークン識別子
+    pub const TOKEN_KEY: &'static str = "twitch";
+
+    /// トークンを指定して作成
+    pub const fn new(token: ApiToken) -> Self {
+        Self { token }
+    }
+
+    /// キーリングに保存されたトークンで作成
+    pub fn from_keyring() -> Result<Self, AppError> {
+        let token = get_platform_token(Self::TOKEN_KEY)?
+            .ok_or_else(|| twitch_error(PlatformErrorKind::TokenMissing, ""))?;
+        Ok(Self::new(ApiToken::new(&token)?))
+    }
+
+    /// トークンを検証し、ユーザーID・クライアントID・スコープを取得
+    async fn validate(&self) -> Result<TwitchValidation, AppError> {
+        let request = HttpRequest::new("GET", TWITCH_VALIDATE_URL)
+            .header("Authorization", &format!("OAuth {}", self.token.expose()));
+        let (status, body) = send_twitch_request(request).await?;
+        check_twitch_response(status, &body)?;
+        serde_json::from_str(&body).map_err(|e| twitch_error(PlatformErrorKind::Server, &e.to_string()))
+    }
+
+    /// Helix APIのリクエストを作成
+    fn helix(&self, method: &'static str, url: &'static str, client_id: &str) -> HttpRequest {
+        HttpRequest::new(method, url)
+            .header("Authorization", &format!("Bearer {}", self.token.expose()))
+            .header("Client-Id", client_id)
+    }
+
+    /// カテゴリー名からIDを検索（大文字小文字を区別しない完全一致）
+    async fn find_category_id(&self, client_id: &str, name: &str) -> Result<String, AppError> {
+        #[derive(Deserialize)]
+        struct Game {
+            id: String,
+        }
+        #[derive(Deserialize)]
+        struct Games {
+            #[serde(default)]
+            data: Vec<Game>,
+        }
+
+        let request = self.helix("GET", TWITCH_GAMES_URL, client_id).query("name", name);
+        let (status, body) = send_twitch_request(request).await?;
+        check_twitch_response(status, &body)?;
+
+        let games: Games =
+            serde_json::from_str(&body).map_err(|e| twitch_error(PlatformErrorKind::Server, &e.to_string()))?;
+        games
+            .data
+            .into_iter()
+            .next()
+            .map(|game| game.id)
+            .ok_or_else(|| twitch_error(PlatformErrorKind::CategoryNotFound, name))
+    }
+}
+
+impl StreamPlatformApi for TwitchApi {
+    async fn verify_token(&self) -> Result<PlatformAccount, AppError> {
+        Ok(self.validate().await?.to_account())
+    }
+
+    async fn set_stream_info(&self, info: &StreamInfo) -> Result<(), AppError> {
+        let info = info.normalized()?;
+        if info.title.chars().count() > TWITCH_MAX_TITLE_CHARS {
+            return Err(AppError::validation_failed(&format!(
+                "Twitchの配信タイトルは{TWITCH_MAX_TITLE_CHARS}文字以内で入力してください"
+            )));
+        }
+
+        let validation = self.validate().await?;
+        if !validation.can_update_stream_info() {
+            return Err(twitch_error(PlatformErrorKind::MissingScope, ""));
+        }
+
+        let game_id = match info.category.as_deref() {
+            Some(category) => Some(self.find_category_id(&validation.client_id, category).await?),
+            None => None,
+        };
+
+        let request = self
+            .helix("PATCH", TWITCH_CHANNELS_URL, &validation.client_id)
+            .query("broadcaster_id", &validation.user_id)
+            .json(&twitch_channel_update_body(&info.title, game_id.as_deref()));
+        let (status, body) = send_twitch_request(request).await?;
+        check_twitch_response(status, &body)
+    }
+}
+
+/// YouTubeのAPI（未対応）
+///
+/// YouTube Data APIはOAuthのクライアント登録が必要なため、現在は未対応
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YouTubeApi;
+
+impl StreamPlatformApi for YouTubeApi {
+    async fn verify_token(&self) -> Result<PlatformAccount, AppError> {
+        Err(not_supported(StreamingPlatform::YouTube))
+    }
+
+    async fn set_stream_info(&self, _info: &StreamInfo) -> Result<(), AppError> {
+        Err(not_supported(StreamingPlatform::YouTube))
+    }
+}
+
+/// トークンを保存するプラットフォームの識別子（未対応のプラットフォームはNone）
+const fn token_key(platform: StreamingPlatform) -> Option<&'static str> {
+    match platform {
+        StreamingPlatform::Twitch => Some(TwitchApi::TOKEN_KEY),
+        _ => None,
+    }
+}
+
+/// APIトークンをキーリングに保存
+///
+/// # Arguments
+/// * `platform` - 配信プラットフォーム
+/// * `raw_token` - ユーザーが入力したトークン
+pub fn save_token(platform: StreamingPlatform, raw_token: &str) -> Result<(), AppError> {
+    let key = token_key(platform).ok_or_else(|| not_supported(platform))?;
+    let token = ApiToken::new(raw_token)?;
+    save_platform_token(key, token.expose())?;
+    tracing::info!(target: "platform_api", ?platform, "APIトークンを保存しました");
+    Ok(())
+}
+
+/// APIトークンをキーリングから削除
+pub fn delete_token(platform: StreamingPlatform) -> Result<(), AppError> {
+    let key = token_key(platform).ok_or_else(|| not_supported(platform))?;
+    delete_platform_token(key)?;
+    tracing::info!(target: "platform_api", ?platform, "APIトークンを削除しました");
+    Ok(())
+}
+
+/// 保存されたトークンを確認
+pub async fn verify_token(platform: StreamingPlatform) -> Result<PlatformAccount, AppError> {
+    match platform {
+        StreamingPlatform::Twitch => TwitchApi::from_keyring()?.verify_token().await,
+        StreamingPlatform::YouTube => YouTubeApi.verify_token().await,
+        _ => Err(not_supported(platform)),
+    }
+}
+
+/// 配信タイトル・カテゴリーを更新
+pub async fn update_stream_info(platform: StreamingPlatform, info: &StreamInfo) -> Result<(), AppError> {
+    let result = match platform {
+        StreamingPlatform::Twitch => TwitchApi::from_keyring()?.set_stream_info(info).await,
+        StreamingPlatform::YouTube => YouTubeApi.set_stream_info(info).await,
+        _ => Err(not_supported(platform)),
+    };
+    match &result {
+        Ok(()) => tracing::info!(target: "platform_api", ?platform, "配信タイトル・カテゴリーを更新しました"),
+        Err(e) => tracing::warn!(target: "platform_api", ?platform, code = e.code(), "配信情報の更新に失敗"),
+    }
+    result
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn kind_of(error: &AppError) -> PlatformErrorKind {
+        let details = serde_json::to_value(error).unwrap()["details"]["kind"].clone();
+        serde_json::from_value(details).unwrap()
+    }
+
+    #[test]
+    fn test_api_token_normalization_and_redaction() {
+        let token = ApiToken::new("  oauth:abc123xyz \n").unwrap();
+        assert_eq!(token.expose(), "abc123xyz");
+        assert_eq!(format!("{token:?}"), "ApiToken(***)");
+        assert!(!format!("{:?}", TwitchApi::new(token)).contains("abc123xyz"));
+
+        assert!(ApiToken::new("").is_err());
+        assert!(ApiToken::new("oauth:").is_err());
+        assert!(ApiToken::new("abc 123").is_err());
+    }
+
+    #[test]
+    fn test_stream_info_normalized() {
+        let info = StreamInfo {
+            title: "  ランクマッチ配信 ".to_string(),
+            category: Some("  ".to_string()),
+        };
+        let normalized = info.normalized().unwrap();
+        assert_eq!(normalized.title, "ランクマッチ配信");
+        assert_eq!(normalized.category, None);
+
+        let empty = StreamInfo { title: " ".to_string(), category: None };
+        assert!(empty.normalized().is_err());
+    }
+
+    #[test]
+    fn test_twitch_error_kind() {
+        assert_eq!(twitch_error_kind(401, "Invalid OAuth token"), PlatformErrorKind::TokenInvalid);
+        assert_eq!(
+            twitch_error_kind(401, "Missing scope: channel:manage:broadcast"),
+            PlatformErrorKind::MissingScope
+        );
+        assert_eq!(twitch_error_kind(403, ""), PlatformErrorKind::MissingScope);
+        assert_eq!(twitch_error_kind(429, ""), PlatformErrorKind::RateLimited);
+        assert_eq!(twitch_error_kind(400, "title too long"), PlatformErrorKind::InvalidRequest);
+        assert_eq!(twitch_error_kind(503, ""), PlatformErrorKind::Server);
+    }
+
+    #[test]
+    fn test_check_twitch_response() {
+        assert!(check_twitch_response(204, "").is_ok());
+
+        let error = check_twitch_response(
+            401,
+            r#"{"error":"Unauthorized","status":401,"message":"Missing scope: channel:manage:broadcast"}"#,
+        )
+        .unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_PLATFORM_AUTH);
+        assert_eq!(kind_of(&error), PlatformErrorKind::MissingScope);
+
+        let error = check_twitch_response(502, "<html>Bad Gateway</html>").unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_PLATFORM_API);
+        assert_eq!(kind_of(&error), PlatformErrorKind::Server);
+        assert!(error.message().contains("HTTP 502"));
+    }
+
+    #[test]
+    fn test_twitch_validation_to_account() {
+        let validation: TwitchValidation = serde_json::from_str(
+            r#"{"client_id":"cid","login":"streamer","scopes":["channel:manage:broadcast","chat:read"],"user_id":"123","expires_in":5520838}"#,
+        )
+        .unwrap();
+        let account = validation.to_account();
+        assert_eq!(account.login, "streamer");
+        assert!(account.can_update_stream_info);
+        assert_eq!(account.expires_in_secs, Some(5_520_838));
+
+        let read_only: TwitchValidation = serde_json::from_str(
+            r#"{"client_id":"cid","login":"streamer","scopes":[],"user_id":"123","expires_in":0}"#,
+        )
+        .unwrap();
+        assert!(!read_only.to_account().can_update_stream_info);
+        assert_eq!(read_only.to_account().expires_in_secs, None);
+    }
+
+    #[test]
+    fn test_twitch_channel_update_body() {
+        let body = twitch_channel_update_body("配信タイトル", Some("509658"));
+        assert_eq!(body["title"], "配信タイトル");
+        assert_eq!(body["game_id"], "509658");
+
+        let body = twitch_channel_update_body("配信タイトル", None);
+        assert!(body.get("game_id").is_none());
+    }
+
+    #[test]
+    fn test_curl_config_keeps_token_out_of_arguments() {
+        let request = HttpRequest::new("PATCH", TWITCH_CHANNELS_URL)
+            .query("broadcaster_id", "123")
+            .header("Authorization", "Bearer abc123xyz")
+            .json(&twitch_channel_update_body("タイトル \"引用\"", None));
+        let config = request.to_curl_config();
+
+        assert!(config.contains(r#"url = "https://api.twitch.tv/helix/channels?broadcaster_id=123""#));
+        assert!(config.contains(r#"request = "PATCH""#));
+        assert!(config.contains(r#"header = "Authorization: Bearer abc123xyz""#));
+        assert!(config.contains(r#"header = "Content-Type: application/json""#));
+        assert!(config.contains(r#"data-binary = "{\"title\":\"タイトル \\\"引用\\\"\"}""#));
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("Just Chatting"), "Just%20Chatting");
+        assert_eq!(percent_encode("a&b=c"), "a%26b%3Dc");
+        assert_eq!(percent_encode("雑談"), "%E9%9B%91%E8%AB%87");
+        assert_eq!(percent_encode("Valorant-2_v1.0~"), "Valorant-2_v1.0~");
+    }
+
+    #[test]
+    fn test_parse_curl_output() {
+        assert_eq!(parse_curl_output("{\"data\":[]}\n200"), Some((200, "{\"data\":[]}".to_string())));
+        assert_eq!(parse_curl_output("\n204"), Some((204, String::new())));
+        // 接続できない場合のステータスは000
+        assert_eq!(parse_curl_output("\n000"), None);
+        assert_eq!(parse_curl_output(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_platforms() {
+        let info = StreamInfo { title: "test".to_string(), category: None };
+
+        let error = YouTubeApi.set_stream_info(&info).await.unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_PLATFORM_NOT_SUPPORTED);
+        assert_eq!(kind_of(&error), PlatformErrorKind::NotSupported);
+
+        let error = update_stream_info(StreamingPlatform::NicoNico, &info).await.unwrap_err();
+        assert_eq!(error.code(), ERROR_CODE_PLATFORM_NOT_SUPPORTED);
+        assert!(save_token(StreamingPlatform::YouTube, "token").is_err());
+    }
+}
@@ -0,0 +1,400 @@
+// 配信後レポート
+//
+// 配信終了後にセッションのメトリクス・検出された問題を振り返り、
+// 品質の推移・最も品質が低かった時点・次回の配信に向けた改善提案をまとめる
+
+use crate::services::alerts::AlertSeverity;
+use crate::services::analyzer::{ProblemCategory, ProblemReport};
+use crate::storage::metrics_history::{HistoricalMetrics, SessionSummary};
+use serde::{Deserialize, Serialize};
+
+/// 配信後レポート生成完了イベント名
+pub const POST_STREAM_REPORT_READY_EVENT: &str = "post-stream-report:ready";
+
+/// レポートに含める主な問題の件数
+const TOP_PROBLEM_COUNT: usize = 3;
+
+/// 品質スコアの減点を始めるCPU使用率（%）
+const CPU_PENALTY_THRESHOLD: f32 = 85.0;
+/// 品質スコアの減点を始めるGPU使用率（%）
+const GPU_PENALTY_THRESHOLD: f32 = 90.0;
+/// ドロップフレーム1件あたりの減点
+const DROPPED_FRAME_PENALTY: f64 = 2.0;
+/// ドロップフレームによる減点の上限
+const MAX_DROPPED_FRAME_PENALTY: f64 = 60.0;
+
+/// 配信後レポート生成完了イベントのペイロード
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostStreamReportReady {
+    /// セッションID
+    pub session_id: String,
+}
+
+/// ある時点の配信品質
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityEntry {
+    /// タイムスタンプ（UNIX epoch秒）
+    pub timestamp: i64,
+    /// 品質スコア（0-100）
+    pub score: u8,
+    /// 前回の計測からのドロップフレーム数（レンダリング + 出力）
+    pub dropped_frames: u64,
+    /// CPU使用率（%）
+    pub cpu_usage: f32,
+    /// GPU使用率（%）
+    pub gpu_usage: Option<f32>,
+}
+
+/// セッション中の品質の推移（古い順）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityTimeline {
+    /// 各時点の品質
+    pub entries: Vec<QualityEntry>,
+}
+
+impl QualityTimeline {
+    /// 履歴メトリクスから品質の推移を算出
+    ///
+    /// ドロップフレーム数は累計値のため、直前の計測との差分を各時点のドロップ数とする
+    pub fn from_metrics(metrics: &[HistoricalMetrics]) -> Self {
+        let mut previous_dropped: Option<u64> = None;
+        let entries = metrics
+            .iter()
+            .map(|metric| {
+                let total_dropped = metric.obs.render_dropped_frames.unwrap_or(0)
+                    + metric.obs.output_dropped_frames.unwrap_or(0);
+                let dropped_frames = previous_dropped.map_or(0, |prev| total_dropped.saturating_sub(prev));
+                previous_dropped = Some(total_dropped);
+
+                QualityEntry {
+                    timestamp: metric.timestamp,
+                    score: sample_score(metric.system.cpu_usage, metric.system.gpu_usage, dropped_frames),
+                    dropped_frames,
+                    cpu_usage: metric.system.cpu_usage,
+                    gpu_usage: metric.system.gpu_usage,
+                }
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// 平均品質スコア（記録がない場合はNone）
+    pub fn average_score(&self) -> Option<u8> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let sum: u64 = self.entries.iter().map(|entry| u64::from(entry.score)).sum();
+        Some((sum as f64 / self.entries.len() as f64).round() as u8)
+    }
+
+    /// 品質スコアが最も低かった時点（同点の場合は早い方）
+    pub fn worst_moment(&self) -> Option<&QualityEntry> {
+        self.entries
+            .iter()
+            .reduce(|worst, entry| if entry.score < worst.score { entry } else { worst })
+    }
+}
+
+/// 配信後レポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostStreamReport {
+    /// セッションID
+    pub session_id: String,
+    /// 配信時間（「X時間Y分」形式）
+    pub session_duration_formatted: String,
+    /// 平均品質スコア（0-100）
+    pub average_quality_score: u8,
+    /// 品質が最も低かった時点（メトリクスが記録されていない場合はNone）
+    pub worst_moment: Option<QualityEntry>,
+    /// 検出された問題の総数
+    pub total_problems: usize,
+    /// 重要度の高い問題（最大3件）
+    pub top_problems: Vec<ProblemReport>,
+    /// 次回の配信に向けた改善提案
+    pub recommendations_for_next_stream: Vec<String>,
+}
+
+/// 配信後レポートを作成
+///
+/// 品質の推移が記録されていない場合はセッションサマリーの品質スコアを平均値とする
+///
+/// # Arguments
+/// * `summary` - セッションサマリー
+/// * `timeline` - セッション中の品質の推移
+/// * `problems` - セッション中に検出された問題
+pub fn build_post_stream_report(
+    summary: &SessionSummary,
+    timeline: &QualityTimeline,
+    problems: &[ProblemReport],
+) -> PostStreamReport {
+    let average_quality_score = timeline
+        .average_score()
+        .unwrap_or_else(|| summary.quality_score.clamp(0.0, 100.0).round() as u8);
+    let top_problems: Vec<ProblemReport> = sorted_by_severity(problems)
+        .into_iter()
+        .take(TOP_PROBLEM_COUNT)
+        .cloned()
+        .collect();
+
+    PostStreamReport {
+        session_id: summary.session_id.clone(),
+        session_duration_formatted: format_duration(summary.duration_secs()),
+        average_quality_score,
+        worst_moment: timeline.worst_moment().cloned(),
+        total_problems: problems.len(),
+        top_problems,
+        recommendations_for_next_stream: synthesize_recommendations(problems),
+    }
+}
+
+/// 検出された問題から次回の配信に向けた改善提案を作成
+///
+/// 重要度の高い問題のカテゴリーから順に、カテゴリーごとの方針と
+/// そのカテゴリーで最も重要な問題の対処方法を1件ずつ提案する（重複は除く）
+pub fn synthesize_recommendations(problems: &[ProblemReport]) -> Vec<String> {
+    let mut categories: Vec<ProblemCategory> = Vec::new();
+    let mut recommendations: Vec<String> = Vec::new();
+
+    for problem in sorted_by_severity(problems) {
+        if categories.contains(&problem.category) {
+            continue;
+        }
+        categories.push(problem.category);
+
+        let candidates = std::iter::once(category_recommendation(problem.category).to_string())
+            .chain(problem.suggested_actions.first().cloned());
+        for recommendation in candidates {
+            if !recommendations.contains(&recommendation) {
+                recommendations.push(recommendation);
+            }
+        }
+    }
+
+    recommendations
+}
+
+/// カテゴリーごとの次回の配信に向けた方針
+const fn category_recommendation(category: ProblemCategory) -> &'static str {
+    match category {
+        ProblemCategory::Encoding => {
+            "次回はエンコーダーのプリセットを1段階軽くするか、出力解像度を下げて配信してください"
+        }
+        ProblemCategory::Network => {
+            "次回の配信前に回線速度を測定し、ビットレートを上り帯域の8割以下に設定してください"
+        }
+        ProblemCategory::Resource => {
+            "次回は配信前に不要なアプリケーションを終了し、CPU・GPU・メモリに余裕を持たせてください"
+        }
+        ProblemCategory::Settings => "次回の配信前に設定分析を実行し、推奨設定との差分を確認してください",
+    }
+}
+
+/// 1時点の品質スコアを算出
+///
+/// CPU・GPUが閾値を超えた分とドロップフレーム数に応じて100から減点する
+fn sample_score(cpu_usage: f32, gpu_usage: Option<f32>, dropped_frames: u64) -> u8 {
+    let cpu_penalty = f64::from((cpu_usage - CPU_PENALTY_THRESHOLD).max(0.0)) * 2.0;
+    let gpu_penalty = f64::from((gpu_usage.unwrap_or(0.0) - GPU_PENALTY_THRESHOLD).max(0.0)) * 2.0;
+    let drop_penalty = (dropped_frames as f64 * DROPPED_FRAME_PENALTY).min(MAX_DROPPED_FRAME_PENALTY);
+
+    (100.0 - cpu_penalty - gpu_penalty - drop_penalty).clamp(0.0, 100.0).round() as u8
+}
+
+/// 問題を重要度順（Critical → Tips）に並べる（同じ重要度では元の順序を維持）
+fn sorted_by_severity(problems: &[ProblemReport]) -> Vec<&ProblemReport> {
+    let rank = |severity: AlertSeverity| match severity {
+        AlertSeverity::Critical => 0,
+        AlertSeverity::Warning => 1,
+        AlertSeverity::Info => 2,
+        AlertSeverity::Tips => 3,
+    };
+    let mut sorted: Vec<&ProblemReport> = problems.iter().collect();
+    sorted.sort_by_key(|problem| rank(problem.severity));
+    sorted
+}
+
+/// 秒数を「X時間Y分」形式に変換
+fn format_duration(duration_secs: i64) -> String {
+    let secs = duration_secs.max(0);
+    format!("{}時間{}分", secs / 3600, secs % 3600 / 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::alerts::MetricType;
+    use crate::storage::metrics_history::{ObsStatusSnapshot, SystemMetricsSnapshot};
+
+    fn problem(category: ProblemCategory, severity: AlertSeverity, action: &str) -> ProblemReport {
+        ProblemReport {
+            id: format!("{category:?}-{severity:?}"),
+            category,
+            severity,
+            title: "テスト".to_string(),
+            description: String::new(),
+            suggested_actions: vec![action.to_string()],
+            affected_metric: MetricType::CpuUsage,
+            detected_at: 0,
+        }
+    }
+
+    fn metrics(timestamp: i64, cpu_usage: f32, dropped: u64) -> HistoricalMetrics {
+        HistoricalMetrics {
+            timestamp,
+            session_id: "session".to_string(),
+            system: SystemMetricsSnapshot {
+                cpu_usage,
+                cpu_frequency_mhz: None,
+                memory_used: 4_000_000_000,
+                memory_total: 16_000_000_000,
+                gpu_usage: Some(50.0),
+                gpu_memory_used: None,
+                network_upload: 0,
+                network_download: 0,
+            },
+            obs: ObsStatusSnapshot {
+                streaming: true,
+                recording: false,
+                fps: Some(60.0),
+                render_dropped_frames: Some(0),
+                output_dropped_frames: Some(dropped),
+                stream_bitrate: Some(6000),
+                output_stats: None,
+            },
+        }
+    }
+
+    fn summary() -> SessionSummary {
+        SessionSummary {
+            session_id: "session".to_string(),
+            start_time: 0,
+            end_time: 5_400,
+            avg_cpu: 40.0,
+            avg_gpu: 50.0,
+            total_dropped_frames: 0,
+            peak_bitrate: 6000,
+            quality_score: 72.4,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 6000,
+            bytes_uploaded: 0,
+        }
+    }
+
+    #[test]
+    fn test_encoding_problem_recommendation() {
+        let recommendations = synthesize_recommendations(&[problem(
+            ProblemCategory::Encoding,
+            AlertSeverity::Critical,
+            "配信解像度を下げる",
+        )]);
+        assert_eq!(
+            recommendations,
+            vec![
+                category_recommendation(ProblemCategory::Encoding).to_string(),
+                "配信解像度を下げる".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_network_problem_recommendation() {
+        let recommendations = synthesize_recommendations(&[problem(
+            ProblemCategory::Network,
+            AlertSeverity::Warning,
+            "有線LAN接続に変更",
+        )]);
+        assert!(recommendations[0].contains("ビットレート"));
+        assert!(recommendations.contains(&"有線LAN接続に変更".to_string()));
+    }
+
+    #[test]
+    fn test_resource_problem_recommendation() {
+        let recommendations = synthesize_recommendations(&[problem(
+            ProblemCategory::Resource,
+            AlertSeverity::Critical,
+            "他のアプリケーションを終了してCPUリソースを確保",
+        )]);
+        assert_eq!(recommendations.len(), 2);
+        assert!(recommendations[0].contains("不要なアプリケーション"));
+    }
+
+    #[test]
+    fn test_settings_problem_recommendation() {
+        let recommendations = synthesize_recommendations(&[problem(
+            ProblemCategory::Settings,
+            AlertSeverity::Info,
+            "OBSのプロセス優先度を「通常以上」に設定",
+        )]);
+        assert!(recommendations[0].contains("設定分析"));
+        assert_eq!(recommendations.len(), 2);
+    }
+
+    #[test]
+    fn test_recommendations_ordered_by_severity_and_deduplicated() {
+        let problems = vec![
+            problem(ProblemCategory::Settings, AlertSeverity::Tips, "設定を確認"),
+            problem(ProblemCategory::Network, AlertSeverity::Critical, "ビットレートを下げる"),
+            problem(ProblemCategory::Network, AlertSeverity::Warning, "有線LAN接続に変更"),
+            problem(ProblemCategory::Encoding, AlertSeverity::Warning, "ビットレートを下げる"),
+        ];
+        let recommendations = synthesize_recommendations(&problems);
+
+        assert_eq!(recommendations[0], category_recommendation(ProblemCategory::Network));
+        assert_eq!(recommendations[1], "ビットレートを下げる");
+        assert_eq!(recommendations[2], category_recommendation(ProblemCategory::Encoding));
+        // 同じカテゴリーの2件目・重複する対処方法は含めない
+        assert!(!recommendations.contains(&"有線LAN接続に変更".to_string()));
+        assert_eq!(recommendations.iter().filter(|r| *r == "ビットレートを下げる").count(), 1);
+        assert_eq!(recommendations.last().map(String::as_str), Some("設定を確認"));
+    }
+
+    #[test]
+    fn test_no_problems_no_recommendations() {
+        assert!(synthesize_recommendations(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_quality_timeline_uses_dropped_frame_deltas() {
+        let timeline = QualityTimeline::from_metrics(&[
+            metrics(0, 40.0, 100),
+            metrics(1, 40.0, 110),
+            metrics(2, 95.0, 110),
+        ]);
+
+        assert_eq!(timeline.entries[0].dropped_frames, 0);
+        assert_eq!(timeline.entries[1].dropped_frames, 10);
+        assert_eq!(timeline.entries[0].score, 100);
+        assert_eq!(timeline.entries[1].score, 80);
+        assert_eq!(timeline.entries[2].score, 80);
+        // 同点の場合は早い方
+        assert_eq!(timeline.worst_moment().map(|entry| entry.timestamp), Some(1));
+        assert_eq!(timeline.average_score(), Some(87));
+    }
+
+    #[test]
+    fn test_build_report_keeps_top_three_by_severity() {
+        let problems = vec![
+            problem(ProblemCategory::Settings, AlertSeverity::Tips, "a"),
+            problem(ProblemCategory::Network, AlertSeverity::Warning, "b"),
+            problem(ProblemCategory::Encoding, AlertSeverity::Critical, "c"),
+            problem(ProblemCategory::Resource, AlertSeverity::Info, "d"),
+        ];
+        let report = build_post_stream_report(&summary(), &QualityTimeline::default(), &problems);
+
+        assert_eq!(report.session_duration_formatted, "1時間30分");
+        assert_eq!(report.total_problems, 4);
+        let severities: Vec<AlertSeverity> = report.top_problems.iter().map(|p| p.severity).collect();
+        assert_eq!(severities, vec![AlertSeverity::Critical, AlertSeverity::Warning, AlertSeverity::Info]);
+        // 品質の推移がない場合はサマリーのスコアを使う
+        assert_eq!(report.average_quality_score, 72);
+        assert!(report.worst_moment.is_none());
+    }
+}
@@ -0,0 +1,271 @@
+// プロファイル適用の差分計算サービス
+//
+// プロファイルを適用した場合に現在のOBS設定から何が変わるかを算出する。
+// 適用前のプレビュー（ドライラン）用で、OBSの設定は一切変更しない。
+
+use crate::obs::ObsSettings;
+use crate::storage::ProfileSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 設定値の変化の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeType {
+    /// 数値が増える
+    Increase,
+    /// 数値が減る
+    Decrease,
+    /// 数値以外の値が変わる
+    Change,
+    /// 変化なし
+    Unchanged,
+}
+
+/// 1項目分の設定変更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    /// 設定項目名（例: "output.bitrateKbps"）
+    pub setting_name: String,
+    /// 現在の値
+    pub current_value: Value,
+    /// 適用後の値
+    pub new_value: Value,
+    /// 変化の種類
+    pub change_type: ChangeType,
+}
+
+/// プロファイル適用時の差分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDiff {
+    /// 項目ごとの変更（変化なしの項目も含む）
+    pub changes: Vec<SettingChange>,
+}
+
+impl ProfileDiff {
+    /// 実際に変化する項目があるか
+    pub fn has_changes(&self) -> bool {
+        self.changes.iter().any(|c| c.change_type != ChangeType::Unchanged)
+    }
+}
+
+/// 数値項目の変更を作成
+pub(crate) fn numeric_change(setting_name: &str, current: Value, new: Value) -> SettingChange {
+    let as_number = |value: &Value| value.as_f64().unwrap_or_default();
+    let (current_number, new_number) = (as_number(&current), as_number(&new));
+    let change_type = if (new_number - current_number).abs() < f64::EPSILON {
+        ChangeType::Unchanged
+    } else if new_number > current_number {
+        ChangeType::Increase
+    } else {
+        ChangeType::Decrease
+    };
+    SettingChange {
+        setting_name: setting_name.to_string(),
+        current_value: current,
+        new_value: new,
+        change_type,
+    }
+}
+
+/// 文字列項目の変更を作成
+pub(crate) fn text_change(setting_name: &str, current: Option<&str>, new: Option<&str>) -> SettingChange {
+    SettingChange {
+        setting_name: setting_name.to_string(),
+        current_value: json!(current),
+        new_value: json!(new),
+        change_type: if current == new {
+            ChangeType::Unchanged
+        } else {
+            ChangeType::Change
+        },
+    }
+}
+
+/// プロファイルを適用した場合の差分を計算
+///
+/// `apply_profile` が書き込む項目（出力解像度・FPS・出力設定）のみを比較する。
+/// プロファイルにプリセットがない場合、適用時もプリセットは変更されないため変化なしとする。
+///
+/// # Arguments
+/// * `current` - 現在のOBS設定
+/// * `settings` - 適用するプロファイルの設定内容
+pub fn compute_profile_diff(current: &ObsSettings, settings: &ProfileSettings) -> ProfileDiff {
+    let current_preset = current.output.preset.as_deref();
+    let new_preset = settings.output.preset.as_deref().or(current_preset);
+
+    let changes = vec![
+        numeric_change(
+            "video.outputWidth",
+            json!(current.video.output_width),
+            json!(settings.video.output_width),
+        ),
+        numeric_change(
+            "video.outputHeight",
+            json!(current.video.output_height),
+            json!(settings.video.output_height),
+        ),
+        numeric_change("video.fps", json!(current.video.fps()), json!(settings.video.fps)),
+        text_change(
+            "output.encoder",
+            Some(current.output.encoder.as_str()),
+            Some(settings.output.encoder.as_str()),
+        ),
+        numeric_change(
+            "output.bitrateKbps",
+            json!(current.output.bitrate_kbps),
+            json!(settings.output.bitrate_kbps),
+        ),
+        numeric_change(
+            "output.keyframeIntervalSecs",
+            json!(current.output.keyframe_interval_secs),
+            json!(settings.output.keyframe_interval_secs),
+        ),
+        text_change("output.preset", current_preset, new_preset),
+        text_change(
+            "output.rateControl",
+            current.output.rate_control.as_deref(),
+            Some(settings.output.rate_control.as_str()),
+        ),
+    ];
+
+    ProfileDiff { changes }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::obs::{AudioSettings, OutputSettings, VideoSettings};
+    use crate::storage::profiles;
+
+    fn current_settings() -> ObsSettings {
+        ObsSettings {
+            video: VideoSettings {
+                base_width: 1920,
+                base_height: 1080,
+                output_width: 1920,
+                output_height: 1080,
+                fps_numerator: 30,
+                fps_denominator: 1,
+            },
+            audio: AudioSettings {
+                sample_rate: 48000,
+                channels: 2,
+            },
+            output: OutputSettings {
+                encoder: "obs_x264".to_string(),
+                bitrate_kbps: 6000,
+                keyframe_interval_secs: 2,
+                preset: Some("veryfast".to_string()),
+                rate_control: Some("CBR".to_string()),
+                recording_format: None,
+                recording_path: None,
+                b_frames: None,
+            },
+        }
+    }
+
+    fn profile_settings() -> ProfileSettings {
+        ProfileSettings {
+            video: profiles::VideoSettings {
+                output_width: 1280,
+                output_height: 720,
+                fps: 60,
+                downscale_filter: "Lanczos".to_string(),
+            },
+            audio: profiles::AudioSettings {
+                sample_rate: 48000,
+                bitrate_kbps: 160,
+                inputs: Vec::new(),
+            },
+            output: profiles::OutputSettings {
+                encoder: "jim_nvenc".to_string(),
+                bitrate_kbps: 8000,
+                keyframe_interval_secs: 2,
+                preset: None,
+                rate_control: "CBR".to_string(),
+            },
+        }
+    }
+
+    fn change_for<'a>(diff: &'a ProfileDiff, name: &str) -> &'a SettingChange {
+        diff.changes.iter().find(|c| c.setting_name == name).unwrap()
+    }
+
+    #[test]
+    fn test_profile_diff_categorises_each_setting() {
+        let diff = compute_profile_diff(&current_settings(), &profile_settings());
+
+        let expected = [
+            ("video.outputWidth", ChangeType::Decrease),
+            ("video.outputHeight", ChangeType::Decrease),
+            ("video.fps", ChangeType::Increase),
+            ("output.encoder", ChangeType::Change),
+            ("output.bitrateKbps", ChangeType::Increase),
+            ("output.keyframeIntervalSecs", ChangeType::Unchanged),
+            ("output.preset", ChangeType::Unchanged),
+            ("output.rateControl", ChangeType::Unchanged),
+        ];
+        assert_eq!(diff.changes.len(), expected.len());
+        for (name, change_type) in expected {
+            assert_eq!(change_for(&diff, name).change_type, change_type, "{name}");
+        }
+        assert!(diff.has_changes());
+    }
+
+    #[test]
+    fn test_profile_diff_reports_values() {
+        let diff = compute_profile_diff(&current_settings(), &profile_settings());
+
+        let bitrate = change_for(&diff, "output.bitrateKbps");
+        assert_eq!(bitrate.current_value, json!(6000));
+        assert_eq!(bitrate.new_value, json!(8000));
+
+        let encoder = change_for(&diff, "output.encoder");
+        assert_eq!(encoder.current_value, json!("obs_x264"));
+        assert_eq!(encoder.new_value, json!("jim_nvenc"));
+
+        // プリセット未指定のプロファイルは現在の値を維持する
+        let preset = change_for(&diff, "output.preset");
+        assert_eq!(preset.new_value, json!("veryfast"));
+    }
+
+    #[test]
+    fn test_profile_diff_preset_and_rate_control_changes() {
+        let mut profile = profile_settings();
+        profile.output.preset = Some("p5".to_string());
+        profile.output.rate_control = "VBR".to_string();
+        let mut current = current_settings();
+        current.output.rate_control = None;
+
+        let diff = compute_profile_diff(&current, &profile);
+        assert_eq!(change_for(&diff, "output.preset").change_type, ChangeType::Change);
+        let rate_control = change_for(&diff, "output.rateControl");
+        assert_eq!(rate_control.change_type, ChangeType::Change);
+        assert_eq!(rate_control.current_value, Value::Null);
+    }
+
+    #[test]
+    fn test_profile_diff_identical_settings_has_no_changes() {
+        let current = current_settings();
+        let mut profile = profile_settings();
+        profile.video.output_width = 1920;
+        profile.video.output_height = 1080;
+        profile.video.fps = 30;
+        profile.output.encoder = "obs_x264".to_string();
+        profile.output.bitrate_kbps = 6000;
+
+        let diff = compute_profile_diff(&current, &profile);
+        assert!(diff.changes.iter().all(|c| c.change_type == ChangeType::Unchanged));
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_change_type_serialization() {
+        assert_eq!(serde_json::to_string(&ChangeType::Increase).unwrap(), "\"increase\"");
+        assert_eq!(serde_json::to_string(&ChangeType::Unchanged).unwrap(), "\"unchanged\"");
+    }
+}
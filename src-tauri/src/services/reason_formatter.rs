@@ -0,0 +1,366 @@
+// 推奨理由の文言生成
+//
+// 推奨エンジンが算出した判断（回線速度によるビットレート制限、4Kモニターのキャンバスなど）を
+// ユーザーの表示言語で説明する。判断そのものは推奨エンジン側で行い、
+// このモジュールは数値を埋め込んだ文言の組み立てのみを担当する
+
+use super::aspect_ratio::{AspectAdvice, AspectHandling};
+use super::encoder_selector::RecommendedEncoder;
+use crate::storage::config::Language;
+
+/// 推奨理由の文言を組み立てる
+pub trait ReasonFormatter: Send + Sync {
+    /// 文言の言語
+    fn language(&self) -> Language;
+
+    /// エンコーダーの推奨理由
+    ///
+    /// # Arguments
+    /// * `encoder` - 選択されたエンコーダー
+    /// * `gpu_name` - GPU名（GPUがない場合はCPU名）
+    /// * `tier` - ハードウェアの統合ティア（例: "Tier A"）
+    fn format_encoder_reason(&self, encoder: &RecommendedEncoder, gpu_name: &str, tier: &str) -> String;
+
+    /// 超低速回線（3Mbps未満）でビットレートを制限した理由
+    fn format_bitrate_very_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String;
+
+    /// 低速回線（5Mbps未満）でビットレートを調整した理由
+    fn format_bitrate_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String;
+
+    /// 中速回線（10Mbps未満）に合わせてビットレートを下げた理由
+    fn format_bitrate_optimized_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String;
+
+    /// 高速回線で高ビットレートを推奨する理由
+    fn format_bitrate_high_speed_reason(&self) -> String;
+
+    /// 出力解像度を720pに下げた理由
+    fn format_resolution_limited_reason(&self) -> String;
+
+    /// 1440p等の高解像度の出力を推奨する理由
+    fn format_resolution_high_reason(&self, width: u32, height: u32) -> String;
+
+    /// キャンバスが16:9でない場合のアスペクト比の扱いの理由
+    fn format_aspect_reason(&self, advice: &AspectAdvice) -> String;
+
+    /// 4Kモニターでキャンバス解像度を下げた理由
+    ///
+    /// # Arguments
+    /// * `width` / `height` - 推奨するキャンバス解像度
+    /// * `reduce_gpu_load` - GPU負荷を抑えるために1080pまで下げたか
+    fn format_canvas_reason(&self, width: u32, height: u32, reduce_gpu_load: bool) -> String;
+
+    /// FPSを30に制限した理由
+    fn format_fps_limited_reason(&self) -> String;
+
+    /// モニターのリフレッシュレートに合わせてFPSを調整した理由
+    ///
+    /// # Arguments
+    /// * `display_hz` - モニターのリフレッシュレート（Hz）
+    /// * `fps` - 推奨するFPS
+    fn format_fps_display_reason(&self, display_hz: u32, fps: u32) -> String;
+
+    /// バッテリー駆動中のため設定を控えめにした理由
+    ///
+    /// # Arguments
+    /// * `battery_percent` - バッテリー残量（%）
+    /// * `preset_change` - プリセットの変更（変更前, 変更後）
+    /// * `fps_change` - FPSの変更（変更前, 変更後）
+    fn format_battery_reason(
+        &self,
+        battery_percent: Option<u8>,
+        preset_change: Option<(&str, &str)>,
+        fps_change: Option<(u32, u32)>,
+    ) -> String;
+
+    /// SRTを推奨する理由
+    fn format_srt_reason(&self) -> String;
+}
+
+/// 言語に対応する文言生成器を取得
+pub fn formatter_for(language: Language) -> Box<dyn ReasonFormatter> {
+    match language {
+        Language::Japanese => Box::new(JapaneseReasonFormatter),
+        Language::English => Box::new(EnglishReasonFormatter),
+    }
+}
+
+/// 日本語の推奨理由
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JapaneseReasonFormatter;
+
+impl ReasonFormatter for JapaneseReasonFormatter {
+    fn language(&self) -> Language {
+        Language::Japanese
+    }
+
+    fn format_encoder_reason(&self, encoder: &RecommendedEncoder, _gpu_name: &str, _tier: &str) -> String {
+        // エンコーダー選択ロジックがGPU世代・配信条件に応じた詳しい理由を日本語で組み立て済み
+        encoder.reason.clone()
+    }
+
+    fn format_bitrate_very_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!(
+            "回線速度が非常に遅い（{network_speed_mbps:.1}Mbps）ため、ビットレートを{bitrate_kbps}kbpsに制限。720p30fps推奨"
+        )
+    }
+
+    fn format_bitrate_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!("回線速度が低め（{network_speed_mbps:.1}Mbps）のため、ビットレートを{bitrate_kbps}kbpsに調整")
+    }
+
+    fn format_bitrate_optimized_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!("回線速度（{network_speed_mbps:.1}Mbps）に合わせてビットレートを{bitrate_kbps}kbpsに最適化")
+    }
+
+    fn format_bitrate_high_speed_reason(&self) -> String {
+        "高速回線を検出。9,000kbps以上で滑らかな高画質配信が可能です".to_string()
+    }
+
+    fn format_resolution_limited_reason(&self) -> String {
+        "ハードウェア性能またはネットワーク速度の制限により、720p解像度を推奨します".to_string()
+    }
+
+    fn format_resolution_high_reason(&self, width: u32, height: u32) -> String {
+        format!("GPU性能と回線速度に余裕があり、配信先も対応しているため、{width}x{height}での配信を推奨します")
+    }
+
+    fn format_aspect_reason(&self, advice: &AspectAdvice) -> String {
+        advice.description()
+    }
+
+    fn format_canvas_reason(&self, width: u32, height: u32, reduce_gpu_load: bool) -> String {
+        if reduce_gpu_load {
+            format!("4Kモニターを検出。GPU負荷を抑えるため、キャンバスは{width}x{height}を推奨します")
+        } else {
+            format!("4Kモニターを検出。キャンバスを{width}x{height}にすると、縮小負荷を抑えつつ高精細な映像を配信できます")
+        }
+    }
+
+    fn format_fps_limited_reason(&self) -> String {
+        "CPU性能の制限により、30FPSを推奨します".to_string()
+    }
+
+    fn format_fps_display_reason(&self, display_hz: u32, fps: u32) -> String {
+        format!("モニターのリフレッシュレート（{display_hz}Hz）で割り切れる{fps}FPSにすると、カクつき（ジャダー）を防げます")
+    }
+
+    fn format_battery_reason(
+        &self,
+        battery_percent: Option<u8>,
+        preset_change: Option<(&str, &str)>,
+        fps_change: Option<(u32, u32)>,
+    ) -> String {
+        let battery = battery_percent.map_or_else(String::new, |percent| format!("（残量{percent}%）"));
+        let mut changes = Vec::new();
+        if let Some((from, to)) = preset_change {
+            changes.push(format!("プリセットを{from}から{to}に"));
+        }
+        if let Some((from, to)) = fps_change {
+            changes.push(format!("FPSを{from}から{to}に"));
+        }
+        format!(
+            "バッテリー駆動中{}のため性能が制限されます。{}下げました。安定した配信にはAC電源への接続を推奨します",
+            battery,
+            changes.join("、")
+        )
+    }
+
+    fn format_srt_reason(&self) -> String {
+        "カスタムサーバーへのゲーム配信のため、超低遅延のSRTを推奨します（サーバー側のSRT対応とOBS 29以降が必要）"
+            .to_string()
+    }
+}
+
+/// 英語の推奨理由
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnglishReasonFormatter;
+
+impl ReasonFormatter for EnglishReasonFormatter {
+    fn language(&self) -> Language {
+        Language::English
+    }
+
+    fn format_encoder_reason(&self, encoder: &RecommendedEncoder, gpu_name: &str, tier: &str) -> String {
+        format!(
+            "Detected {gpu_name} ({tier}). {} with the {} preset is recommended",
+            encoder.display_name, encoder.preset
+        )
+    }
+
+    fn format_bitrate_very_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!(
+            "Your connection is very slow ({network_speed_mbps:.1} Mbps), so the bitrate is limited to {bitrate_kbps} kbps. 720p at 30 fps is recommended"
+        )
+    }
+
+    fn format_bitrate_slow_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!("Your connection is slow ({network_speed_mbps:.1} Mbps), so the bitrate is adjusted to {bitrate_kbps} kbps")
+    }
+
+    fn format_bitrate_optimized_reason(&self, network_speed_mbps: f64, bitrate_kbps: u32) -> String {
+        format!("The bitrate is optimized to {bitrate_kbps} kbps for your connection speed ({network_speed_mbps:.1} Mbps)")
+    }
+
+    fn format_bitrate_high_speed_reason(&self) -> String {
+        "Fast connection detected. Smooth, high-quality streaming at 9,000 kbps or more is possible".to_string()
+    }
+
+    fn format_resolution_limited_reason(&self) -> String {
+        "720p output is recommended due to hardware performance or network speed limits".to_string()
+    }
+
+    fn format_resolution_high_reason(&self, width: u32, height: u32) -> String {
+        format!("{width}x{height} output is recommended because the GPU and network have headroom and the platform supports it")
+    }
+
+    fn format_aspect_reason(&self, advice: &AspectAdvice) -> String {
+        let canvas = format!("{}x{}", advice.canvas_width, advice.canvas_height);
+        let output = format!("{}x{}", advice.output_width, advice.output_height);
+        match advice.handling {
+            AspectHandling::Native => format!(
+                "The {canvas} canvas is streamed as {output} without letterboxing or cropping because the platform supports ultrawide video"
+            ),
+            AspectHandling::Letterbox => format!(
+                "The {canvas} canvas is not 16:9, so it is letterboxed to {}x{} inside the {output} output. Crop the center {}x{} to fill the frame instead",
+                advice.letterbox_width, advice.letterbox_height, advice.crop_width, advice.crop_height
+            ),
+            AspectHandling::Crop => format!(
+                "The {canvas} canvas is not 16:9, so the center {}x{} is cropped to fill the {output} output. Letterbox it to {}x{} to show the whole canvas instead",
+                advice.crop_width, advice.crop_height, advice.letterbox_width, advice.letterbox_height
+            ),
+        }
+    }
+
+    fn format_canvas_reason(&self, width: u32, height: u32, reduce_gpu_load: bool) -> String {
+        if reduce_gpu_load {
+            format!("4K monitor detected. A {width}x{height} canvas is recommended to reduce GPU load")
+        } else {
+            format!("4K monitor detected. A {width}x{height} canvas keeps the image sharp while reducing the downscaling load")
+        }
+    }
+
+    fn format_fps_limited_reason(&self) -> String {
+        "30 fps is recommended due to CPU performance limits".to_string()
+    }
+
+    fn format_fps_display_reason(&self, display_hz: u32, fps: u32) -> String {
+        format!("{fps} fps divides the {display_hz} Hz monitor refresh rate evenly, which prevents judder")
+    }
+
+    fn format_battery_reason(
+        &self,
+        battery_percent: Option<u8>,
+        preset_change: Option<(&str, &str)>,
+        fps_change: Option<(u32, u32)>,
+    ) -> String {
+        let battery = battery_percent.map_or_else(String::new, |percent| format!(" ({percent}% remaining)"));
+        let mut changes = Vec::new();
+        if let Some((from, to)) = preset_change {
+            changes.push(format!("the preset from {from} to {to}"));
+        }
+        if let Some((from, to)) = fps_change {
+            changes.push(format!("FPS from {from} to {to}"));
+        }
+        format!(
+            "Running on battery{} limits performance, so {} {} lowered. Connect to AC power for a stable stream",
+            battery,
+            changes.join(" and "),
+            if changes.len() > 1 { "were" } else { "was" }
+        )
+    }
+
+    fn format_srt_reason(&self) -> String {
+        "SRT is recommended for ultra-low latency when streaming games to a custom server (requires SRT support on the server and OBS 29 or later)"
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::encoder_selector::EncoderSelector;
+    use crate::services::optimizer::RecommendationEngine;
+    use crate::storage::config::{StreamingPlatform, StreamingStyle};
+    use crate::testing::fixtures::mid_range_hardware;
+
+    fn nvenc_encoder() -> RecommendedEncoder {
+        EncoderSelector::select_encoder(&RecommendationEngine::encoder_selection_context(
+            &mid_range_hardware(),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            20.0,
+        ))
+    }
+
+    #[test]
+    fn test_formatter_for_language() {
+        assert_eq!(formatter_for(Language::Japanese).language(), Language::Japanese);
+        assert_eq!(formatter_for(Language::English).language(), Language::English);
+    }
+
+    #[test]
+    fn test_japanese_formatter() {
+        let formatter = JapaneseReasonFormatter;
+        let encoder = nvenc_encoder();
+
+        // エンコーダー選択ロジックの理由をそのまま使う
+        assert_eq!(
+            formatter.format_encoder_reason(&encoder, "NVIDIA GeForce RTX 4070", "Tier A"),
+            encoder.reason
+        );
+        assert_eq!(
+            formatter.format_bitrate_very_slow_reason(2.0, 2000),
+            "回線速度が非常に遅い（2.0Mbps）ため、ビットレートを2000kbpsに制限。720p30fps推奨"
+        );
+        assert_eq!(
+            formatter.format_bitrate_optimized_reason(8.0, 4800),
+            "回線速度（8.0Mbps）に合わせてビットレートを4800kbpsに最適化"
+        );
+        assert!(formatter.format_canvas_reason(1920, 1080, true).contains("GPU負荷を抑える"));
+        assert!(formatter.format_canvas_reason(2560, 1440, false).contains("2560x1440"));
+        assert_eq!(
+            formatter.format_battery_reason(Some(55), Some(("p5", "p4")), Some((60, 30))),
+            "バッテリー駆動中（残量55%）のため性能が制限されます。プリセットをp5からp4に、FPSを60から30に下げました。安定した配信にはAC電源への接続を推奨します"
+        );
+        assert!(formatter.format_srt_reason().contains("SRT"));
+    }
+
+    #[test]
+    fn test_english_formatter() {
+        let formatter = EnglishReasonFormatter;
+        let encoder = nvenc_encoder();
+
+        let reason = formatter.format_encoder_reason(&encoder, "NVIDIA GeForce RTX 4070", "Tier A");
+        assert!(reason.contains("NVIDIA GeForce RTX 4070 (Tier A)"));
+        assert!(reason.contains(&encoder.display_name));
+        assert_eq!(
+            formatter.format_bitrate_slow_reason(4.0, 3200),
+            "Your connection is slow (4.0 Mbps), so the bitrate is adjusted to 3200 kbps"
+        );
+        assert!(formatter.format_canvas_reason(1920, 1080, true).contains("reduce GPU load"));
+        assert_eq!(
+            formatter.format_battery_reason(None, None, Some((60, 30))),
+            "Running on battery limits performance, so FPS from 60 to 30 was lowered. Connect to AC power for a stable stream"
+        );
+        assert!(formatter
+            .format_battery_reason(Some(20), Some(("p5", "p4")), Some((60, 30)))
+            .contains("(20% remaining)"));
+
+        // 日本語の文字が混ざらない
+        let reasons = [
+            reason,
+            formatter.format_bitrate_very_slow_reason(2.0, 2000),
+            formatter.format_bitrate_optimized_reason(8.0, 4800),
+            formatter.format_bitrate_high_speed_reason(),
+            formatter.format_resolution_limited_reason(),
+            formatter.format_canvas_reason(2560, 1440, false),
+            formatter.format_fps_limited_reason(),
+            formatter.format_fps_display_reason(144, 48),
+            formatter.format_resolution_high_reason(2560, 1440),
+            formatter.format_srt_reason(),
+        ];
+        for reason in reasons.iter().skip(1) {
+            assert!(reason.is_ascii(), "英語の理由に日本語が含まれる: {reason}");
+        }
+    }
+}
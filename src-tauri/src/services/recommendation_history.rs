@@ -0,0 +1,377 @@
+// 推奨設定の履歴と差分の説明
+//
+// 推奨設定の計算結果を入力条件とともに履歴に記録し、2回の計算結果の違いを
+// 「どの入力条件が変わり、その結果どの設定が変わったか」の形で説明する。
+// 例: 「回線速度 10→4Mbps → ビットレート 6000→3500kbps」
+
+use crate::error::AppError;
+use crate::services::optimizer::{HardwareInfo, OutputTarget, RecommendedSettings};
+use crate::services::profile_diff::{numeric_change, text_change, ChangeType, SettingChange};
+use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use crate::storage::recommendation_history::{
+    append_recommendation_history, RecommendationHistoryEntry, RecommendationInput,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 入力条件の項目ごとに、影響する推奨設定の項目
+///
+/// 入力条件の変化と推奨設定の変化を結びつけて説明するために使用する
+const INPUT_EFFECTS: &[(&str, &[&str])] = &[
+    ("input.networkSpeedMbps", &["output.bitrateKbps", "video.resolution", "video.fps"]),
+    (
+        "input.hardwareHash",
+        &["output.encoder", "output.preset", "video.resolution", "video.fps", "output.bitrateKbps"],
+    ),
+    (
+        "input.platform",
+        &["output.bitrateKbps", "output.keyframeIntervalSecs", "video.resolution", "video.fps", "output.encoder"],
+    ),
+    ("input.style", &["video.fps", "output.bitrateKbps", "output.preset", "audio.bitrateKbps"]),
+    ("input.target", &["output.rateControl", "output.bitrateKbps", "output.encoder", "output.preset"]),
+];
+
+/// 2回分の推奨設定の差分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationDiff {
+    /// 比較元の履歴ID
+    pub from_id: String,
+    /// 比較先の履歴ID
+    pub to_id: String,
+    /// 変化した入力条件
+    pub input_changes: Vec<SettingChange>,
+    /// 変化した推奨設定
+    pub setting_changes: Vec<SettingChange>,
+    /// 変化の説明（例: "回線速度 10→4Mbps → ビットレート 6000→3500kbps"）
+    pub explanations: Vec<String>,
+}
+
+/// ハードウェア構成のハッシュ（FNV-1a、16進数16桁）
+///
+/// CPU・GPU・メモリ容量（GB単位）・メインモニターが同じなら同じ値になる。
+/// バージョンをまたいで同じ値になるよう、標準ライブラリのハッシュ関数は使わない
+pub fn hardware_hash(hardware: &HardwareInfo) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let display = hardware.primary_display().map_or_else(String::new, |display| {
+        format!("{}x{}@{}", display.width, display.height, display.refresh_rate_hz)
+    });
+    let key = format!(
+        "{}|{}|{}|{}|{display}",
+        hardware.cpu_name,
+        hardware.cpu_cores,
+        hardware.gpu.as_ref().map_or("", |gpu| gpu.name.as_str()),
+        hardware.total_memory_gb.round() as u64,
+    );
+
+    let hash = key.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// 推奨設定の計算結果を履歴に記録
+///
+/// 記録に失敗しても推奨設定の計算自体は成功させるため、ログに記録するだけにする
+pub fn record_recommendation(
+    hardware: &HardwareInfo,
+    platform: StreamingPlatform,
+    style: StreamingStyle,
+    network_speed_mbps: f64,
+    target: OutputTarget,
+    settings: &RecommendedSettings,
+) {
+    let entry = RecommendationHistoryEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        computed_at: chrono::Utc::now().timestamp(),
+        input: RecommendationInput {
+            hardware_hash: hardware_hash(hardware),
+            platform,
+            style,
+            network_speed_mbps,
+            target,
+        },
+        settings: settings.clone(),
+    };
+    if let Err(e) = append_recommendation_history(entry) {
+        tracing::warn!(target: "recommendation_history", error = %e, "推奨設定の履歴の保存に失敗");
+    }
+}
+
+/// 履歴から指定したIDの記録を取得
+pub fn find_entry<'a>(
+    entries: &'a [RecommendationHistoryEntry],
+    id: &str,
+) -> Result<&'a RecommendationHistoryEntry, AppError> {
+    entries
+        .iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| AppError::validation_failed(&format!("推奨設定の履歴が見つかりません: {id}")))
+}
+
+/// 入力条件の差分（変化した項目のみ）
+fn input_changes(from: &RecommendationInput, to: &RecommendationInput) -> Vec<SettingChange> {
+    vec![
+        numeric_change(
+            "input.networkSpeedMbps",
+            json!(from.network_speed_mbps),
+            json!(to.network_speed_mbps),
+        ),
+        text_change("input.hardwareHash", Some(from.hardware_hash.as_str()), Some(to.hardware_hash.as_str())),
+        text_change(
+            "input.platform",
+            Some(format!("{:?}", from.platform).as_str()),
+            Some(format!("{:?}", to.platform).as_str()),
+        ),
+        text_change(
+            "input.style",
+            Some(format!("{:?}", from.style).as_str()),
+            Some(format!("{:?}", to.style).as_str()),
+        ),
+        text_change(
+            "input.target",
+            Some(format!("{:?}", from.target).as_str()),
+            Some(format!("{:?}", to.target).as_str()),
+        ),
+    ]
+    .into_iter()
+    .filter(|change| change.change_type != ChangeType::Unchanged)
+    .collect()
+}
+
+/// 推奨設定の差分（変化した項目のみ）
+fn setting_changes(from: &RecommendedSettings, to: &RecommendedSettings) -> Vec<SettingChange> {
+    let resolution = |settings: &RecommendedSettings| {
+        format!("{}x{}", settings.video.output_width, settings.video.output_height)
+    };
+
+    vec![
+        numeric_change("output.bitrateKbps", json!(from.output.bitrate_kbps), json!(to.output.bitrate_kbps)),
+        text_change("video.resolution", Some(resolution(from).as_str()), Some(resolution(to).as_str())),
+        numeric_change("video.fps", json!(from.video.fps), json!(to.video.fps)),
+        text_change("output.encoder", Some(from.output.encoder.as_str()), Some(to.output.encoder.as_str())),
+        text_change("output.preset", from.output.preset.as_deref(), to.output.preset.as_deref()),
+        text_change(
+            "output.rateControl",
+            Some(from.output.rate_control.as_str()),
+            Some(to.output.rate_control.as_str()),
+        ),
+        numeric_change(
+            "output.keyframeIntervalSecs",
+            json!(from.output.keyframe_interval_secs),
+            json!(to.output.keyframe_interval_secs),
+        ),
+        numeric_change("audio.bitrateKbps", json!(from.audio.bitrate_kbps), json!(to.audio.bitrate_kbps)),
+    ]
+    .into_iter()
+    .filter(|change| change.change_type != ChangeType::Unchanged)
+    .collect()
+}
+
+/// 項目の表示名と単位
+fn field_label(setting_name: &str) -> (&'static str, &'static str) {
+    match setting_name {
+        "input.networkSpeedMbps" => ("回線速度", "Mbps"),
+        "input.hardwareHash" => ("ハードウェア構成", ""),
+        "input.platform" => ("配信プラットフォーム", ""),
+        "input.style" => ("配信スタイル", ""),
+        "input.target" => ("出力先", ""),
+        "output.bitrateKbps" => ("ビットレート", "kbps"),
+        "video.resolution" => ("解像度", ""),
+        "video.fps" => ("FPS", ""),
+        "output.encoder" => ("エンコーダー", ""),
+        "output.preset" => ("プリセット", ""),
+        "output.rateControl" => ("レート制御", ""),
+        "output.keyframeIntervalSecs" => ("キーフレーム間隔", "秒"),
+        "audio.bitrateKbps" => ("音声ビットレート", "kbps"),
+        _ => ("その他", ""),
+    }
+}
+
+/// 説明用に値を文字列化
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => "なし".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.as_f64().map_or_else(|| number.to_string(), |n| format!("{n}")),
+        other => other.to_string(),
+    }
+}
+
+/// 1項目分の変化の説明（例: "ビットレート 6000→3500kbps"）
+///
+/// ハードウェア構成はハッシュ値のため、値を出さず「変更」とだけ表示する
+fn describe_change(change: &SettingChange) -> String {
+    let (label, unit) = field_label(&change.setting_name);
+    if change.setting_name == "input.hardwareHash" {
+        return format!("{label}の変更");
+    }
+    format!(
+        "{label} {}→{}{unit}",
+        format_value(&change.current_value),
+        format_value(&change.new_value)
+    )
+}
+
+/// 変化の説明を組み立てる
+///
+/// 変化した入力条件ごとに、影響しうる推奨設定の変化を結びつける。
+/// どの入力条件にも結びつかない変化は、推奨ロジックの更新などによるものとしてまとめる
+fn explain(inputs: &[SettingChange], settings: &[SettingChange]) -> Vec<String> {
+    let mut explained = vec![false; settings.len()];
+    let mut explanations = Vec::new();
+
+    for input in inputs {
+        let effects = INPUT_EFFECTS
+            .iter()
+            .find(|(name, _)| *name == input.setting_name)
+            .map_or(&[][..], |(_, effects)| *effects);
+        let mut related = Vec::new();
+        for (index, change) in settings.iter().enumerate() {
+            if !explained[index] && effects.contains(&change.setting_name.as_str()) {
+                explained[index] = true;
+                related.push(describe_change(change));
+            }
+        }
+
+        if related.is_empty() {
+            explanations.push(format!("{}（推奨設定への影響なし）", describe_change(input)));
+        } else {
+            explanations.push(format!("{} → {}", describe_change(input), related.join("、")));
+        }
+    }
+
+    let unexplained: Vec<String> = settings
+        .iter()
+        .zip(&explained)
+        .filter(|(_, explained)| !**explained)
+        .map(|(change, _)| describe_change(change))
+        .collect();
+    if !unexplained.is_empty() {
+        let prefix = if inputs.is_empty() { "入力条件は同じ" } else { "その他" };
+        explanations.push(format!("{prefix}（推奨ロジックの更新など） → {}", unexplained.join("、")));
+    }
+
+    explanations
+}
+
+/// 2回分の推奨設定の差分と、変化の説明を算出
+pub fn diff_recommendations(
+    from: &RecommendationHistoryEntry,
+    to: &RecommendationHistoryEntry,
+) -> RecommendationDiff {
+    let input_changes = input_changes(&from.input, &to.input);
+    let setting_changes = setting_changes(&from.settings, &to.settings);
+    let explanations = explain(&input_changes, &setting_changes);
+
+    RecommendationDiff {
+        from_id: from.id.clone(),
+        to_id: to.id.clone(),
+        input_changes,
+        setting_changes,
+        explanations,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::services::optimizer::RecommendationEngine;
+    use crate::testing::fixtures::{high_end_hardware, mid_range_hardware};
+
+    fn entry(id: &str, hardware: &HardwareInfo, network_speed_mbps: f64) -> RecommendationHistoryEntry {
+        RecommendationHistoryEntry {
+            id: id.to_string(),
+            computed_at: 1_700_000_000,
+            input: RecommendationInput {
+                hardware_hash: hardware_hash(hardware),
+                platform: StreamingPlatform::YouTube,
+                style: StreamingStyle::Gaming,
+                network_speed_mbps,
+                target: OutputTarget::Streaming,
+            },
+            settings: RecommendationEngine::calculate_recommendations(
+                hardware,
+                None,
+                StreamingPlatform::YouTube,
+                StreamingStyle::Gaming,
+                network_speed_mbps,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_hardware_hash_is_stable() {
+        let hardware = mid_range_hardware();
+        assert_eq!(hardware_hash(&hardware), hardware_hash(&mid_range_hardware()));
+        assert_eq!(hardware_hash(&hardware).len(), 16);
+        assert_ne!(hardware_hash(&hardware), hardware_hash(&high_end_hardware()));
+
+        // メモリ容量の端数は無視する
+        let mut rounded = mid_range_hardware();
+        rounded.total_memory_gb += 0.1;
+        assert_eq!(hardware_hash(&hardware), hardware_hash(&rounded));
+    }
+
+    #[test]
+    fn test_diff_explains_network_speed_change() {
+        let hardware = mid_range_hardware();
+        let from = entry("a", &hardware, 10.0);
+        let to = entry("b", &hardware, 4.0);
+        assert!(to.settings.output.bitrate_kbps < from.settings.output.bitrate_kbps);
+
+        let diff = diff_recommendations(&from, &to);
+        assert_eq!(diff.input_changes.len(), 1);
+        assert_eq!(diff.input_changes[0].setting_name, "input.networkSpeedMbps");
+        assert_eq!(diff.input_changes[0].change_type, ChangeType::Decrease);
+
+        let expected = format!(
+            "回線速度 10→4Mbps → ビットレート {}→{}kbps",
+            from.settings.output.bitrate_kbps, to.settings.output.bitrate_kbps
+        );
+        assert!(diff.explanations[0].starts_with(&expected), "{:?}", diff.explanations);
+    }
+
+    #[test]
+    fn test_diff_identical_runs_has_no_changes() {
+        let hardware = mid_range_hardware();
+        let diff = diff_recommendations(&entry("a", &hardware, 10.0), &entry("b", &hardware, 10.0));
+        assert!(diff.input_changes.is_empty());
+        assert!(diff.setting_changes.is_empty());
+        assert!(diff.explanations.is_empty());
+    }
+
+    #[test]
+    fn test_diff_falls_back_to_field_comparison() {
+        let hardware = mid_range_hardware();
+        let from = entry("a", &hardware, 10.0);
+        let mut to = entry("b", &hardware, 10.0);
+        to.settings.output.preset = Some("p7".to_string());
+
+        let diff = diff_recommendations(&from, &to);
+        assert!(diff.input_changes.is_empty());
+        assert_eq!(diff.setting_changes.len(), 1);
+        assert!(diff.explanations[0].starts_with("入力条件は同じ"));
+        assert!(diff.explanations[0].contains("プリセット"));
+    }
+
+    #[test]
+    fn test_hardware_change_hides_hash() {
+        let from = entry("a", &mid_range_hardware(), 10.0);
+        let to = entry("b", &high_end_hardware(), 10.0);
+
+        let diff = diff_recommendations(&from, &to);
+        assert!(diff.explanations[0].starts_with("ハードウェア構成の変更"));
+        assert!(!diff.explanations[0].contains(&from.input.hardware_hash));
+    }
+
+    #[test]
+    fn test_find_entry_unknown_id() {
+        let entries = vec![entry("a", &mid_range_hardware(), 10.0)];
+        assert_eq!(find_entry(&entries, "a").unwrap().id, "a");
+        assert!(find_entry(&entries, "missing").is_err());
+    }
+}
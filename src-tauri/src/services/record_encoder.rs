@@ -0,0 +1,202 @@
+// 録画エンコーダーの品質値・ビットレートの適用
+//
+// 詳細出力モードの録画エンコーダーのレート制御・品質値（CQP/CRF）・ビットレート（CBR）は
+// プロファイルパラメータではなく、プロファイルのディレクトリの `recordEncoder.json` に保存される。
+// 基本出力モードでは品質値を直接指定できないため、`SimpleOutput/RecQuality` の近いプリセットを選ぶ。
+//
+// OBSは起動中のエンコーダー設定をメモリに保持するため、`recordEncoder.json` に書き込んだ値は
+// 出力の再設定（設定画面を閉じる・プロファイルの再読み込み・OBSの再起動）後に反映される。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use crate::services::obs_file_backup::find_profile_dir;
+use crate::services::obs_log::obs_config_dir;
+use std::path::Path;
+
+/// 録画エンコーダーの設定ファイル名
+pub const RECORD_ENCODER_FILE_NAME: &str = "recordEncoder.json";
+
+/// 基本出力モードの「高品質、ファイルサイズ中」（CRF 23相当）と「無劣化に近い高品質」（CRF 16相当）の境界
+const SIMPLE_HQ_MAX_QUALITY: u32 = 19;
+
+/// 基本出力モードで配信と同じエンコーダーを共有する録画品質
+///
+/// 基本出力モードでは録画のビットレートを指定できないため、CBRの推奨ではエンコードを共有して負荷を抑える
+pub const SIMPLE_SHARED_RECORDING_QUALITY: &str = "Stream";
+
+/// 基本出力モードで品質値に最も近い録画品質（`SimpleOutput/RecQuality`）を選ぶ
+///
+/// # Arguments
+/// * `quality` - 推奨の品質値（CQレベル・CRF値）
+pub const fn simple_recording_quality(quality: u32) -> &'static str {
+    if quality <= SIMPLE_HQ_MAX_QUALITY {
+        "HQ"
+    } else {
+        "Small"
+    }
+}
+
+/// エンコーダーごとの品質値の設定キー
+///
+/// x264はCRF、QSVはIフレーム・Pフレーム・Bフレームごとの量子化値、それ以外（NVENC・AMF）はCQレベル
+fn quality_setting_keys(encoder_id: &str) -> &'static [&'static str] {
+    if encoder_id == "obs_x264" {
+        &["crf"]
+    } else if encoder_id.contains("qsv") {
+        &["qpi", "qpp", "qpb"]
+    } else {
+        &["cqp"]
+    }
+}
+
+/// 既存のエンコーダー設定にレート制御と品質値を書き込む
+///
+/// 他の設定（プリセット・プロファイル等）は保持する。オブジェクトでない場合は空の設定から作り直す
+pub fn merge_quality_settings(
+    existing: serde_json::Value,
+    encoder_id: &str,
+    rate_control: &str,
+    quality: u32,
+) -> serde_json::Value {
+    let mut settings = match existing {
+        serde_json::Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+    settings.insert("rate_control".to_string(), serde_json::json!(rate_control));
+    for key in quality_setting_keys(encoder_id) {
+        settings.insert((*key).to_string(), serde_json::json!(quality));
+    }
+    serde_json::Value::Object(settings)
+}
+
+/// 既存のエンコーダー設定にCBRとビットレートを書き込む
+///
+/// 他の設定（プリセット・プロファイル等）は保持する。オブジェクトでない場合は空の設定から作り直す
+pub fn merge_bitrate_settings(existing: serde_json::Value, bitrate_kbps: u32) -> serde_json::Value {
+    let mut settings = match existing {
+        serde_json::Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+    settings.insert("rate_control".to_string(), serde_json::json!("CBR"));
+    settings.insert("bitrate".to_string(), serde_json::json!(bitrate_kbps));
+    serde_json::Value::Object(settings)
+}
+
+/// プロファイルのディレクトリの `recordEncoder.json` を読み込み、更新して書き戻す
+///
+/// 既存のファイルが読めない・壊れている場合は新しく作成する
+fn update_record_encoder_settings(
+    profile_dir: &Path,
+    update: impl FnOnce(serde_json::Value) -> serde_json::Value,
+) -> Result<(), AppError> {
+    let path = profile_dir.join(RECORD_ENCODER_FILE_NAME);
+    let existing = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or(serde_json::Value::Null);
+    let settings = update(existing);
+
+    let temporary = path.with_extension("json.tmp");
+    std::fs::write(&temporary, serde_json::to_string_pretty(&settings)?)?;
+    std::fs::rename(&temporary, &path)?;
+    Ok(())
+}
+
+/// プロファイルのディレクトリの `recordEncoder.json` にレート制御と品質値を書き込む
+///
+/// 既存のファイルが読めない・壊れている場合は新しく作成する
+///
+/// # Arguments
+/// * `profile_dir` - OBSのプロファイルのディレクトリ
+/// * `encoder_id` - 録画エンコーダーのID
+/// * `rate_control` - レート制御（"CQP" / "CRF"）
+/// * `quality` - 品質値
+pub fn write_record_encoder_quality(
+    profile_dir: &Path,
+    encoder_id: &str,
+    rate_control: &str,
+    quality: u32,
+) -> Result<(), AppError> {
+    update_record_encoder_settings(profile_dir, |existing| {
+        merge_quality_settings(existing, encoder_id, rate_control, quality)
+    })
+}
+
+/// プロファイルのディレクトリの `recordEncoder.json` にCBRとビットレートを書き込む
+///
+/// # Arguments
+/// * `profile_dir` - OBSのプロファイルのディレクトリ
+/// * `bitrate_kbps` - ビットレート（kbps）
+pub fn write_record_encoder_bitrate(profile_dir: &Path, bitrate_kbps: u32) -> Result<(), AppError> {
+    update_record_encoder_settings(profile_dir, |existing| merge_bitrate_settings(existing, bitrate_kbps))
+}
+
+/// 使用中のプロファイルの `recordEncoder.json` にレート制御と品質値を書き込む
+pub async fn write_current_record_encoder_quality(
+    client: &ObsClient,
+    encoder_id: &str,
+    rate_control: &str,
+    quality: u32,
+) -> Result<(), AppError> {
+    let profile_name = client.get_current_profile().await?;
+    let profile_dir = find_profile_dir(&obs_config_dir()?, &profile_name)?;
+    write_record_encoder_quality(&profile_dir, encoder_id, rate_control, quality)
+}
+
+/// 使用中のプロファイルの `recordEncoder.json` にCBRとビットレートを書き込む
+pub async fn write_current_record_encoder_bitrate(client: &ObsClient, bitrate_kbps: u32) -> Result<(), AppError> {
+    let profile_name = client.get_current_profile().await?;
+    let profile_dir = find_profile_dir(&obs_config_dir()?, &profile_name)?;
+    write_record_encoder_bitrate(&profile_dir, bitrate_kbps)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_simple_recording_quality() {
+        assert_eq!(simple_recording_quality(16), "HQ");
+        assert_eq!(simple_recording_quality(19), "HQ");
+        assert_eq!(simple_recording_quality(20), "Small");
+        assert_eq!(simple_recording_quality(23), "Small");
+    }
+
+    #[test]
+    fn test_merge_quality_settings_keeps_other_settings() {
+        let existing = serde_json::json!({ "preset2": "p5", "rate_control": "CBR", "bitrate": 6000 });
+        let merged = merge_quality_settings(existing, "jim_nvenc", "CQP", 18);
+        assert_eq!(merged["rate_control"], "CQP");
+        assert_eq!(merged["cqp"], 18);
+        assert_eq!(merged["preset2"], "p5");
+
+        let merged = merge_quality_settings(serde_json::Value::Null, "obs_x264", "CRF", 20);
+        assert_eq!(merged, serde_json::json!({ "rate_control": "CRF", "crf": 20 }));
+
+        let merged = merge_quality_settings(serde_json::Value::Null, "obs_qsv11_v2", "CQP", 22);
+        assert_eq!(merged["qpi"], 22);
+        assert_eq!(merged["qpb"], 22);
+    }
+
+    #[test]
+    fn test_write_record_encoder_quality() {
+        let dir = std::env::temp_dir().join(format!("record_encoder_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(RECORD_ENCODER_FILE_NAME), r#"{"preset":"veryfast"}"#).unwrap();
+
+        write_record_encoder_quality(&dir, "obs_x264", "CRF", 18).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(RECORD_ENCODER_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!({ "preset": "veryfast", "rate_control": "CRF", "crf": 18 }));
+
+        write_record_encoder_bitrate(&dir, 20_000).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(RECORD_ENCODER_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!({ "preset": "veryfast", "rate_control": "CBR", "crf": 18, "bitrate": 20000 }));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+// リクエストの相関ID
+//
+// 推奨設定の算出・分析・適用のログを1回のリクエスト単位で追跡できるよう、
+// コマンドの入口でリクエストIDを採番し、`tracing` のスパンのフィールドとして付与する。
+// 内部の処理（`calculate_recommendations` 等）は子スパンを開くだけで、
+// 親スパンのリクエストIDがそのログにも出力される。
+// リクエストIDは結果にも含め、フロントエンドが不具合報告に添付できるようにする。
+
+use crate::error::AppError;
+use once_cell::sync::Lazy;
+use std::future::Future;
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// アプリの起動ごとのセッションID
+static APP_SESSION_ID: Lazy<Uuid> = Lazy::new(Uuid::new_v4);
+
+/// リクエストの相関情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestContext {
+    /// リクエストID（コマンドの呼び出しごとに採番）
+    pub request_id: Uuid,
+    /// セッションID（アプリの起動ごと）
+    pub session_id: Option<Uuid>,
+}
+
+impl RequestContext {
+    /// 新しいリクエストIDを採番
+    pub fn new() -> Self {
+        Self {
+            request_id: Uuid::new_v4(),
+            session_id: Some(*APP_SESSION_ID),
+        }
+    }
+}
+
+impl Default for RequestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// リクエストのスパンを作成
+///
+/// スパン名はコンパイル時に決まる必要があるため、マクロで提供する
+///
+/// # Example
+/// ```ignore
+/// let ctx = RequestContext::new();
+/// let span = request_span!("analyze_settings", ctx);
+/// ```
+#[macro_export]
+macro_rules! request_span {
+    ($name:literal, $ctx:expr) => {
+        tracing::info_span!(
+            $name,
+            request_id = %$ctx.request_id,
+            session_id = $ctx.session_id.map(tracing::field::display)
+        )
+    };
+}
+
+/// リクエストのスパン内で処理を実行し、開始・終了をログに記録する
+///
+/// # Arguments
+/// * `span` - `request_span!` で作成したスパン
+/// * `future` - 実行する処理
+pub async fn in_request_span<T, F>(span: tracing::Span, future: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    async move {
+        tracing::info!(target: "request", "開始");
+        let result = future.await;
+        match &result {
+            Ok(_) => tracing::info!(target: "request", "完了"),
+            Err(e) => tracing::warn!(target: "request", code = e.code(), "失敗"),
+        }
+        result
+    }
+    .instrument(span)
+    .await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::services::analyzer::ProblemAnalyzer;
+    use crate::services::optimizer::RecommendationEngine;
+    use crate::storage::config::{StreamingPlatform, StreamingStyle};
+    use crate::testing::fixtures::{healthy_system_metrics, high_end_hardware, standard_obs_settings};
+    use std::sync::{Arc, Mutex};
+
+    /// fmtサブスクライバーの出力を蓄積する
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_request_context_ids() {
+        let first = RequestContext::new();
+        let second = RequestContext::new();
+        assert_ne!(first.request_id, second.request_id);
+        // セッションIDは起動中は共通
+        assert_eq!(first.session_id, second.session_id);
+        assert!(first.session_id.is_some());
+    }
+
+    #[test]
+    fn test_request_id_appears_in_child_span_logs() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .with_max_level(tracing::Level::TRACE)
+            .finish();
+        let ctx = RequestContext::new();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _entered = request_span!("analyze_settings", ctx).entered();
+            RecommendationEngine::calculate_recommendations(
+                &high_end_hardware(),
+                Some(&standard_obs_settings()),
+                StreamingPlatform::Twitch,
+                StreamingStyle::Gaming,
+                20.0,
+            );
+            ProblemAnalyzer::new().analyze_comprehensive(&[healthy_system_metrics()], &[6000], 6000, "obs_x264");
+        });
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines.iter().any(|line| line.contains("calculate_recommendations")), "{output}");
+        assert!(lines.iter().any(|line| line.contains("analyze_comprehensive")), "{output}");
+
+        let request_id = ctx.request_id.to_string();
+        for line in lines {
+            assert!(line.contains(&request_id), "リクエストIDがないログ: {line}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_request_span_logs_entry_and_exit() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+        let ctx = RequestContext::new();
+
+        let result = in_request_span(request_span!("apply_recommended_settings", ctx), async {
+            Err::<(), _>(AppError::obs_not_connected())
+        })
+        .await;
+        assert!(result.is_err());
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("開始") && output.contains("失敗"), "{output}");
+        assert!(output.lines().all(|line| line.contains(&ctx.request_id.to_string())));
+    }
+}
@@ -0,0 +1,339 @@
+// 配信スタイルごとのシーン構成チェック
+//
+// ゲーム配信なのにゲーム・画面キャプチャがない、雑談配信なのにマイクがないなど、
+// 配信スタイルに対して必要なソースが現在のシーンにない場合は設定ミスの可能性が高い。
+// スタイルごとに必要なソースの種類をルール表として持ち、足りないものを問題として報告する。
+// ルールはユーザーごとに非表示にでき、非表示にしたルールIDは設定ファイルに保存する。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use crate::services::alerts::{AlertSeverity, MetricType};
+use crate::services::analyzer::{ProblemCategory, ProblemReport};
+use crate::services::audio_monitor::is_likely_microphone;
+use crate::services::dual_pc::is_capture_source;
+use crate::storage::config::StreamingStyle;
+
+/// ゲーム・画面を映す入力種別（Windows・macOS・Linux）
+const SCREEN_CAPTURE_INPUT_KINDS: &[&str] = &[
+    "game_capture",
+    "monitor_capture",
+    "window_capture",
+    "display_capture",
+    "screen_capture",
+    "xshm_input",
+    "xcomposite_input",
+    "pipewire-desktop-capture-source",
+    "pipewire-window-capture-source",
+    "pipewire-screen-capture-source",
+];
+
+/// カメラ・映像キャプチャデバイスの入力種別
+const CAMERA_INPUT_KINDS: &[&str] = &[
+    "dshow_input",
+    "av_capture_input",
+    "av_capture_input_v2",
+    "macos-avcapture",
+    "v4l2_input",
+];
+
+/// ネストしたシーン・グループを展開する深さの上限（循環参照対策）
+const MAX_NESTED_SCENE_DEPTH: usize = 4;
+
+/// ルールが求めるソースの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequiredSource {
+    /// ゲーム・画面キャプチャ（キャプチャーボード・NDIを含む）
+    ScreenCapture,
+    /// カメラ（VTuberのアバター表示用のウィンドウキャプチャを含む）
+    Camera,
+    /// マイク
+    Microphone,
+}
+
+impl RequiredSource {
+    /// 入力種別・入力名がこの種類のソースに該当するか
+    pub fn is_satisfied_by(self, source: &ChecklistSource) -> bool {
+        let kind = source.kind.as_str();
+        match self {
+            Self::ScreenCapture => {
+                SCREEN_CAPTURE_INPUT_KINDS.contains(&kind) || is_capture_source(kind, &source.name, None)
+            }
+            Self::Camera => {
+                CAMERA_INPUT_KINDS.contains(&kind)
+                    || kind == "window_capture"
+                    || kind == "spout_capture"
+                    || kind == "ndi_source"
+            }
+            Self::Microphone => is_likely_microphone(kind, &source.name),
+        }
+    }
+}
+
+/// シーン構成チェックのルール
+#[derive(Debug, Clone, Copy)]
+pub struct SceneChecklistRule {
+    /// ルールID（非表示設定に使用）
+    pub id: &'static str,
+    /// 対象の配信スタイル
+    pub style: StreamingStyle,
+    /// 必要なソースの種類
+    pub required: RequiredSource,
+    /// 重要度
+    pub severity: AlertSeverity,
+    /// 問題のタイトル
+    pub title: &'static str,
+    /// 問題の説明（シーン名の前に付く）
+    pub description: &'static str,
+    /// 推奨される対処方法
+    pub suggested_action: &'static str,
+    /// 影響を受けるメトリクス
+    pub affected_metric: MetricType,
+}
+
+/// 配信スタイルごとのシーン構成チェックのルール表
+pub const SCENE_CHECKLIST_RULES: &[SceneChecklistRule] = &[
+    SceneChecklistRule {
+        id: "gaming-screen-capture",
+        style: StreamingStyle::Gaming,
+        required: RequiredSource::ScreenCapture,
+        severity: AlertSeverity::Warning,
+        title: "ゲーム配信ですが、ゲーム・画面キャプチャのソースがありません",
+        description: "配信スタイルが「ゲーム実況」ですが、ゲームキャプチャ・画面キャプチャ・キャプチャーボードのソースが見つかりません",
+        suggested_action: "OBSのソースに「ゲームキャプチャ」または「画面キャプチャ」を追加する",
+        affected_metric: MetricType::FrameDropRate,
+    },
+    SceneChecklistRule {
+        id: "talk-camera",
+        style: StreamingStyle::Talk,
+        required: RequiredSource::Camera,
+        severity: AlertSeverity::Info,
+        title: "雑談配信ですが、カメラのソースがありません",
+        description: "配信スタイルが「雑談・トーク」ですが、カメラ（映像キャプチャデバイス）・アバター表示用のソースが見つかりません",
+        suggested_action: "OBSのソースに「映像キャプチャデバイス」を追加する（顔出ししない場合はこのチェックを非表示にしてください）",
+        affected_metric: MetricType::FrameDropRate,
+    },
+    SceneChecklistRule {
+        id: "talk-microphone",
+        style: StreamingStyle::Talk,
+        required: RequiredSource::Microphone,
+        severity: AlertSeverity::Warning,
+        title: "雑談配信ですが、マイクのソースがありません",
+        description: "配信スタイルが「雑談・トーク」ですが、マイク（音声入力キャプチャ）が見つかりません",
+        suggested_action: "OBSの設定「音声」でマイク音声を指定するか、ソースに「音声入力キャプチャ」を追加する",
+        affected_metric: MetricType::MicrophoneSilence,
+    },
+    SceneChecklistRule {
+        id: "music-microphone",
+        style: StreamingStyle::Music,
+        required: RequiredSource::Microphone,
+        severity: AlertSeverity::Warning,
+        title: "歌・演奏配信ですが、マイクのソースがありません",
+        description: "配信スタイルが「歌・演奏」ですが、マイク・楽器の音声入力が見つかりません",
+        suggested_action: "OBSの設定「音声」でマイク音声を指定するか、ソースに「音声入力キャプチャ」を追加する",
+        affected_metric: MetricType::MicrophoneSilence,
+    },
+    SceneChecklistRule {
+        id: "art-screen-capture",
+        style: StreamingStyle::Art,
+        required: RequiredSource::ScreenCapture,
+        severity: AlertSeverity::Warning,
+        title: "お絵描き配信ですが、画面キャプチャのソースがありません",
+        description: "配信スタイルが「お絵描き・制作」ですが、作業画面を映す画面キャプチャ・ウィンドウキャプチャが見つかりません",
+        suggested_action: "OBSのソースに「画面キャプチャ」または「ウィンドウキャプチャ」を追加する",
+        affected_metric: MetricType::FrameDropRate,
+    },
+];
+
+/// IDからルールを検索
+pub fn find_checklist_rule(id: &str) -> Option<&'static SceneChecklistRule> {
+    SCENE_CHECKLIST_RULES.iter().find(|rule| rule.id == id)
+}
+
+/// チェック対象のソース
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistSource {
+    /// ソース名
+    pub name: String,
+    /// 入力種別
+    pub kind: String,
+}
+
+impl ChecklistSource {
+    /// ソース名と入力種別から作成
+    pub fn new(name: &str, kind: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: kind.to_string(),
+        }
+    }
+}
+
+/// 現在のシーンの構成
+#[derive(Debug, Clone, Default)]
+pub struct SceneComposition {
+    /// シーン名
+    pub scene_name: String,
+    /// 表示中のソース（ネストしたシーン・グループ内のソースと、グローバル音声入力を含む）
+    pub sources: Vec<ChecklistSource>,
+}
+
+/// 配信スタイルに対して足りないソースを問題として報告
+///
+/// 問題のIDにはルールIDを使用する（非表示にする際に指定する）
+///
+/// # Arguments
+/// * `style` - 配信スタイル
+/// * `composition` - 現在のシーンの構成
+/// * `dismissed` - 非表示にしたルールID
+pub fn analyze_scene_checklist(
+    style: StreamingStyle,
+    composition: &SceneComposition,
+    dismissed: &[String],
+) -> Vec<ProblemReport> {
+    SCENE_CHECKLIST_RULES
+        .iter()
+        .filter(|rule| rule.style == style)
+        .filter(|rule| !dismissed.iter().any(|id| id == rule.id))
+        .filter(|rule| {
+            !composition
+                .sources
+                .iter()
+                .any(|source| rule.required.is_satisfied_by(source))
+        })
+        .map(|rule| ProblemReport {
+            id: rule.id.to_string(),
+            category: ProblemCategory::Settings,
+            severity: rule.severity,
+            title: rule.title.to_string(),
+            description: format!("{}（シーン「{}」）", rule.description, composition.scene_name),
+            suggested_actions: vec![
+                rule.suggested_action.to_string(),
+                "意図した構成の場合は、このチェックを非表示にできます".to_string(),
+            ],
+            affected_metric: rule.affected_metric,
+            detected_at: chrono::Utc::now().timestamp(),
+        })
+        .collect()
+}
+
+/// OBSから現在のシーンの構成を取得
+///
+/// 非表示のソースは含めない。ネストしたシーン・グループは展開し、
+/// 設定画面の「音声」で指定されたグローバル音声入力はシーンに関係なく含める
+pub async fn collect_scene_composition(client: &ObsClient) -> Result<SceneComposition, AppError> {
+    let scene_name = client.get_current_scene().await?;
+    let mut sources = Vec::new();
+    let mut pending = vec![(scene_name.clone(), 0)];
+    let mut visited = vec![scene_name.clone()];
+
+    while let Some((name, depth)) = pending.pop() {
+        // ネストしたシーン・グループの取得に失敗した場合はそのシーンのみ省略する
+        let items = match client.get_scene_item_list(&name).await {
+            Ok(items) => items,
+            Err(e) if depth == 0 => return Err(e),
+            Err(_) => continue,
+        };
+        for item in items.into_iter().filter(|item| item.enabled) {
+            if matches!(item.source_type.as_str(), "scene" | "group") {
+                if depth < MAX_NESTED_SCENE_DEPTH && !visited.contains(&item.name) {
+                    visited.push(item.name.clone());
+                    pending.push((item.name, depth + 1));
+                }
+            } else {
+                sources.push(ChecklistSource::new(&item.name, &item.source_type));
+            }
+        }
+    }
+
+    let special_inputs = client.get_special_input_names().await.unwrap_or_default();
+    if !special_inputs.is_empty() {
+        let inputs = client.get_input_list().await?;
+        sources.extend(
+            inputs
+                .into_iter()
+                .filter(|input| special_inputs.contains(&input.id.name))
+                .map(|input| ChecklistSource::new(&input.id.name, &input.kind)),
+        );
+    }
+
+    Ok(SceneComposition { scene_name, sources })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn composition(sources: &[(&str, &str)]) -> SceneComposition {
+        SceneComposition {
+            scene_name: "メイン".to_string(),
+            sources: sources
+                .iter()
+                .map(|(name, kind)| ChecklistSource::new(name, kind))
+                .collect(),
+        }
+    }
+
+    fn rule_ids(problems: &[ProblemReport]) -> Vec<&str> {
+        problems.iter().map(|p| p.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_gaming_without_capture_is_reported() {
+        let scene = composition(&[("Webカメラ", "dshow_input"), ("マイク", "wasapi_input_capture")]);
+        let problems = analyze_scene_checklist(StreamingStyle::Gaming, &scene, &[]);
+
+        assert_eq!(rule_ids(&problems), vec!["gaming-screen-capture"]);
+        assert_eq!(problems[0].category, ProblemCategory::Settings);
+        assert_eq!(problems[0].severity, AlertSeverity::Warning);
+        assert!(problems[0].description.contains("メイン"));
+    }
+
+    #[test]
+    fn test_gaming_with_game_or_card_capture_passes() {
+        for scene in [
+            composition(&[("ゲーム", "game_capture")]),
+            composition(&[("画面", "monitor_capture")]),
+            composition(&[("画面", "pipewire-screen-capture-source")]),
+            composition(&[("Elgato HD60 S+", "dshow_input")]),
+            composition(&[("ゲームPC", "ndi_source")]),
+        ] {
+            assert!(analyze_scene_checklist(StreamingStyle::Gaming, &scene, &[]).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_talk_requires_camera_and_microphone() {
+        let empty = composition(&[("背景", "image_source")]);
+        let problems = analyze_scene_checklist(StreamingStyle::Talk, &empty, &[]);
+        assert_eq!(rule_ids(&problems), vec!["talk-camera", "talk-microphone"]);
+
+        let camera_only = composition(&[("Webカメラ", "dshow_input")]);
+        let problems = analyze_scene_checklist(StreamingStyle::Talk, &camera_only, &[]);
+        assert_eq!(rule_ids(&problems), vec!["talk-microphone"]);
+
+        let complete = composition(&[("アバター", "window_capture"), ("Mic/Aux", "wasapi_input_capture")]);
+        assert!(analyze_scene_checklist(StreamingStyle::Talk, &complete, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_dismissed_rules_are_suppressed() {
+        let empty = composition(&[]);
+        let dismissed = vec!["talk-camera".to_string()];
+        let problems = analyze_scene_checklist(StreamingStyle::Talk, &empty, &dismissed);
+        assert_eq!(rule_ids(&problems), vec!["talk-microphone"]);
+    }
+
+    #[test]
+    fn test_other_style_has_no_rules() {
+        assert!(analyze_scene_checklist(StreamingStyle::Other, &composition(&[]), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_rule_ids_are_unique_and_findable() {
+        for (index, rule) in SCENE_CHECKLIST_RULES.iter().enumerate() {
+            assert_eq!(find_checklist_rule(rule.id).map(|r| r.id), Some(rule.id));
+            assert!(SCENE_CHECKLIST_RULES[index + 1..].iter().all(|other| other.id != rule.id));
+        }
+        assert!(find_checklist_rule("unknown-rule").is_none());
+    }
+}
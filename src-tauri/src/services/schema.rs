@@ -0,0 +1,788 @@
+// エクスポート形式のJSON Schema
+//
+// 外部ツール（OBSオーバーレイ・スプレッドシート等）が読み込むエクスポートデータの
+// 形式をJSON Schema（draft 2020-12）として定義する。
+// スキーマは手書きだが、列挙値は実際の列挙型をシリアライズして生成するため、
+// 名前の変更はスキーマにも反映される。フィールドの追加・削除はテストで検出する。
+
+use crate::error::AppError;
+use crate::monitor::network::NetworkAdapterType;
+use crate::monitor::power::PowerSource;
+use crate::monitor::ProcessPriority;
+use crate::services::alerts::{AlertSeverity, MetricType, SuppressionTrigger};
+use crate::services::analyzer::ProblemCategory;
+use crate::services::exporter::SESSION_EXPORT_VERSION;
+use crate::storage::config::{
+    Language, SetupType, StreamLatency, StreamingPlatform, StreamingProtocol, StreamingStyle,
+    ThresholdComparison,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// スキーマのバージョン（互換性のない変更を行った場合に上げる）
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 設定分析結果のスキーマのバージョン
+///
+/// v2で `qualityScore` を整数から項目別の内訳に変更した。既存の外部ツール向けにv1も書き出す
+pub const ANALYSIS_RESULT_SCHEMA_VERSION: u32 = 2;
+
+/// 準拠するJSON Schemaのドラフト
+const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// 文字列
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+/// 整数
+fn integer() -> Value {
+    json!({ "type": "integer" })
+}
+
+/// 数値（整数・小数）
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+/// 真偽値
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+/// 任意の値
+fn any() -> Value {
+    json!({})
+}
+
+/// 内容を検証しないオブジェクト（推奨設定など構造の大きいネスト型に使用）
+fn open_object() -> Value {
+    json!({ "type": "object" })
+}
+
+/// 配列
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// 列挙型のシリアライズ結果を値とする文字列
+fn variants<T: Serialize>(values: &[T]) -> Value {
+    let values: Vec<Value> = values
+        .iter()
+        .filter_map(|value| serde_json::to_value(value).ok())
+        .collect();
+    json!({ "type": "string", "enum": values })
+}
+
+/// 文字列定数の列挙
+fn string_enum(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+/// nullを許容（`Option<T>` のフィールド用）
+fn nullable(mut schema: Value) -> Value {
+    if let Some(object) = schema.as_object_mut() {
+        if let Some(Value::String(kind)) = object.get("type").cloned() {
+            object.insert("type".to_string(), json!([kind, "null"]));
+        }
+        if let Some(Value::Array(values)) = object.get_mut("enum") {
+            values.push(Value::Null);
+        }
+    }
+    schema
+}
+
+/// プロパティを列挙したオブジェクトのスキーマ
+///
+/// 定義していないプロパティは許可しない（フィールドの追加を検出するため）
+#[derive(Default)]
+struct ObjectSchema {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+}
+
+impl ObjectSchema {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// 常に出力されるプロパティ
+    fn required(mut self, name: &str, schema: Value) -> Self {
+        self.properties.insert(name.to_string(), schema);
+        self.required.push(name.to_string());
+        self
+    }
+
+    /// 省略されることがあるプロパティ（`skip_serializing_if` 付きのフィールド）
+    fn optional(mut self, name: &str, schema: Value) -> Self {
+        self.properties.insert(name.to_string(), schema);
+        self
+    }
+
+    fn build(self) -> Value {
+        json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+            "additionalProperties": false,
+        })
+    }
+}
+
+/// トップレベルのスキーマにメタ情報を付与
+fn document(name: &str, version: u32, title: &str, mut schema: Value) -> Value {
+    if let Some(object) = schema.as_object_mut() {
+        object.insert("$schema".to_string(), json!(SCHEMA_DRAFT));
+        object.insert("$id".to_string(), json!(schema_file_name(name, version)));
+        object.insert("title".to_string(), json!(title));
+    }
+    schema
+}
+
+/// スキーマファイル名（例: `diagnostic-report.v1.schema.json`）
+pub fn schema_file_name(name: &str, version: u32) -> String {
+    format!("{name}.v{version}.schema.json")
+}
+
+fn power_source() -> Value {
+    variants(&[PowerSource::Ac, PowerSource::Battery, PowerSource::Unknown])
+}
+
+fn streaming_platform() -> Value {
+    variants(&[
+        StreamingPlatform::YouTube,
+        StreamingPlatform::Twitch,
+        StreamingPlatform::NicoNico,
+        StreamingPlatform::TwitCasting,
+        StreamingPlatform::Bilibili,
+        StreamingPlatform::Showroom,
+        StreamingPlatform::Other,
+    ])
+}
+
+fn metric_type() -> Value {
+    variants(&[
+        MetricType::CpuUsage,
+        MetricType::GpuUsage,
+        MetricType::MemoryUsage,
+        MetricType::FrameDropRate,
+        MetricType::NetworkBandwidth,
+        MetricType::MicrophoneSilence,
+        MetricType::DataQuota,
+    ])
+}
+
+fn display_info() -> Value {
+    ObjectSchema::new()
+        .required("width", integer())
+        .required("height", integer())
+        .required("refreshRateHz", integer())
+        .required("isPrimary", boolean())
+        .build()
+}
+
+/// 検出された問題（`ProblemReport`）
+fn problem_report() -> Value {
+    ObjectSchema::new()
+        .required("id", string())
+        .required(
+            "category",
+            variants(&[
+                ProblemCategory::Encoding,
+                ProblemCategory::Network,
+                ProblemCategory::Resource,
+                ProblemCategory::Settings,
+            ]),
+        )
+        .required(
+            "severity",
+            variants(&[
+                AlertSeverity::Critical,
+                AlertSeverity::Warning,
+                AlertSeverity::Info,
+                AlertSeverity::Tips,
+            ]),
+        )
+        .required("title", string())
+        .required("description", string())
+        .required("suggestedActions", array(string()))
+        .required("affectedMetric", metric_type())
+        .required("detectedAt", integer())
+        .build()
+}
+
+/// セッションサマリー（`SessionSummary`）
+fn session_summary() -> Value {
+    ObjectSchema::new()
+        .required("sessionId", string())
+        .required("startTime", integer())
+        .required("endTime", integer())
+        .required("avgCpu", number())
+        .required("avgGpu", number())
+        .required("totalDroppedFrames", integer())
+        .required("peakBitrate", integer())
+        .required("qualityScore", number())
+        .required("title", nullable(string()))
+        .required("tags", array(string()))
+        .required("notes", nullable(string()))
+        .required("platform", nullable(streaming_platform()))
+        .required("avgBitrate", integer())
+        .required("bytesUploaded", integer())
+        .build()
+}
+
+/// 配信出力の統計（`OutputStats`）
+fn output_stats() -> Value {
+    ObjectSchema::new()
+        .required("framesSent", integer())
+        .required("framesDropped", integer())
+        .required("currentBitrateKbps", number())
+        .required("congestion", number())
+        .build()
+}
+
+/// 履歴メトリクス（`HistoricalMetrics`）
+fn historical_metrics() -> Value {
+    let system = ObjectSchema::new()
+        .required("cpuUsage", number())
+        .required("cpuFrequencyMhz", nullable(number()))
+        .required("memoryUsed", integer())
+        .required("memoryTotal", integer())
+        .required("gpuUsage", nullable(number()))
+        .required("gpuMemoryUsed", nullable(integer()))
+        .required("networkUpload", integer())
+        .required("networkDownload", integer())
+        .build();
+    let obs = ObjectSchema::new()
+        .required("streaming", boolean())
+        .required("recording", boolean())
+        .required("fps", nullable(number()))
+        .required("renderDroppedFrames", nullable(integer()))
+        .required("outputDroppedFrames", nullable(integer()))
+        .required("streamBitrate", nullable(integer()))
+        .optional("outputStats", output_stats())
+        .build();
+
+    ObjectSchema::new()
+        .required("timestamp", integer())
+        .required("sessionId", string())
+        .required("system", system)
+        .required("obs", obs)
+        .build()
+}
+
+/// 診断レポート（`DiagnosticReport`）のスキーマ
+pub fn diagnostic_report_schema() -> Value {
+    let session = ObjectSchema::new()
+        .required("sessionId", string())
+        .required("durationSecs", integer())
+        .required("startedAt", integer())
+        .required("endedAt", integer())
+        .required("title", nullable(string()))
+        .required("tags", array(string()))
+        .required("notes", nullable(string()))
+        .build();
+    let system_info = ObjectSchema::new()
+        .required("os", string())
+        .required("cpuModel", string())
+        .required("totalMemoryMb", integer())
+        .required("gpuModel", nullable(string()))
+        .required("gpuDriverVersion", nullable(string()))
+        .required("powerSource", power_source())
+        .required("batteryPercent", nullable(integer()))
+        .required(
+            "networkAdapter",
+            variants(&[
+                NetworkAdapterType::Wired,
+                NetworkAdapterType::Wireless,
+                NetworkAdapterType::Virtual,
+                NetworkAdapterType::Unknown,
+            ]),
+        )
+        .required("monitors", array(display_info()))
+        .build();
+    let performance = ObjectSchema::new()
+        .required("overallScore", number())
+        .required("cpuScore", number())
+        .required("gpuScore", number())
+        .required("networkScore", number())
+        .required("stabilityScore", number())
+        .build();
+
+    let schema = ObjectSchema::new()
+        .required("generatedAt", integer())
+        .required("session", session)
+        .required("systemInfo", system_info)
+        .required("problems", array(problem_report()))
+        .required("performance", performance)
+        .required("recommendationsSummary", string())
+        .required("recommendedSettings", nullable(open_object()))
+        .required("obsSettings", nullable(open_object()))
+        .build();
+    document("diagnostic-report", SCHEMA_VERSION, "診断レポート", schema)
+}
+
+/// セッションのJSONエクスポート（`export_session_json` の出力）のスキーマ
+pub fn session_export_schema() -> Value {
+    let schema = ObjectSchema::new()
+        .required("version", string_enum(&[SESSION_EXPORT_VERSION]))
+        .required("exported_at", integer())
+        .required("session", session_summary())
+        .required("metrics", array(historical_metrics()))
+        .build();
+    document("session-export", SCHEMA_VERSION, "セッションエクスポート", schema)
+}
+
+/// 履歴メトリクス1件（`HistoricalMetrics`）のスキーマ
+pub fn historical_metrics_schema() -> Value {
+    document("historical-metrics", SCHEMA_VERSION, "履歴メトリクス", historical_metrics())
+}
+
+/// 設定分析結果（`AnalysisResult`）の本体
+///
+/// # Arguments
+/// * `quality_score` - `qualityScore` のスキーマ（バージョンによって異なる）
+fn analysis_result(quality_score: Value) -> Value {
+    let recommendation = ObjectSchema::new()
+        .required("key", string())
+        .required("displayName", string())
+        .required("currentValue", any())
+        .required("recommendedValue", any())
+        .required("reason", string())
+        .required("priority", string_enum(&["critical", "recommended", "optional"]))
+        .build();
+    let system_info = ObjectSchema::new()
+        .required("cpuModel", string())
+        .required("gpuModel", nullable(string()))
+        .required("gpuDriverVersion", nullable(string()))
+        .required("totalMemoryMb", integer())
+        .required("availableMemoryMb", integer())
+        .required("powerSource", power_source())
+        .required("batteryPercent", nullable(integer()))
+        .required("monitors", array(display_info()))
+        .build();
+    let key_recommendation = ObjectSchema::new()
+        .required("label", string())
+        .required("value", string())
+        .required("reasonSimple", string())
+        .build();
+    let summary = ObjectSchema::new()
+        .required("headline", string())
+        .required("recommendedPreset", string_enum(&["low", "medium", "high", "ultra"]))
+        .required("keyRecommendations", array(key_recommendation))
+        .build();
+
+    ObjectSchema::new()
+        .required("qualityScore", quality_score)
+        .required("issueCount", integer())
+        .required("recommendations", array(recommendation))
+        .required("systemInfo", system_info)
+        .required("analyzedAt", integer())
+        .required("summary", summary)
+        .optional("systemCapability", open_object())
+        .optional("staticSettings", open_object())
+        .optional("multitrack", open_object())
+        .build()
+}
+
+/// 設定分析結果（`AnalysisResult`）のスキーマ
+pub fn analysis_result_schema() -> Value {
+    let quality_score = ObjectSchema::new()
+        .required("resolutionScore", integer())
+        .required("fpsScore", integer())
+        .required("bitrateScore", integer())
+        .required("encoderScore", integer())
+        .required("total", integer())
+        .build();
+    document(
+        "analysis-result",
+        ANALYSIS_RESULT_SCHEMA_VERSION,
+        "設定分析結果",
+        analysis_result(quality_score),
+    )
+}
+
+/// 設定分析結果のv1のスキーマ（`qualityScore` が0-100の整数）
+pub fn analysis_result_v1_schema() -> Value {
+    document("analysis-result", 1, "設定分析結果", analysis_result(integer()))
+}
+
+/// アプリケーション設定（`AppConfig`）のスキーマ
+///
+/// フロントエンドが `patch_app_config` に渡す前の検証に使用する。
+/// 値の範囲は検証しないため、範囲外の値は `patch_app_config` の `VALIDATION_FAILED` で検出する
+pub fn app_config_schema() -> Value {
+    let connection = ObjectSchema::new()
+        .required("lastHost", string())
+        .required("lastPort", integer())
+        .required("savePassword", boolean())
+        .required("autoConnectOnStartup", boolean())
+        .required("connectionTimeoutSecs", integer())
+        .optional("savedPassword", nullable(string()))
+        .build();
+    let monitoring = ObjectSchema::new()
+        .required("updateIntervalMs", integer())
+        .required("collectSystemMetrics", boolean())
+        .required("collectGpuMetrics", boolean())
+        .required("collectProcessMetrics", boolean())
+        .required("saveMetricsHistory", boolean())
+        .required("healthCheckIntervalSecs", integer())
+        .optional("preferredNetworkInterface", nullable(string()))
+        .build();
+    let alerts = ObjectSchema::new()
+        .required("enabled", boolean())
+        .required("cpuWarningThreshold", number())
+        .required("cpuCriticalThreshold", number())
+        .required("gpuWarningThreshold", number())
+        .required("gpuCriticalThreshold", number())
+        .required("frameDropWarningThreshold", number())
+        .required("frameDropCriticalThreshold", number())
+        .required("alertDurationSecs", integer())
+        .required("playSound", boolean())
+        .required("showNotification", boolean())
+        .required("micSilenceAlertSecs", integer())
+        .required("micSilenceFloorDb", number())
+        .required(
+            "suppressionRules",
+            array(
+                ObjectSchema::new()
+                    .required(
+                        "trigger",
+                        variants(&[
+                            SuppressionTrigger::SceneTransitionStart,
+                            SuppressionTrigger::RecordingStart,
+                            SuppressionTrigger::ManualOverride,
+                        ]),
+                    )
+                    .required("durationSecs", integer())
+                    .build(),
+            ),
+        )
+        .build();
+    let display = ObjectSchema::new()
+        .required("darkMode", boolean())
+        .required("graphHistoryDurationSecs", integer())
+        .required("compactMode", boolean())
+        .required("alwaysOnTop", boolean())
+        .required("language", variants(&[Language::Japanese, Language::English]))
+        .required(
+            "shortcuts",
+            ObjectSchema::new()
+                .optional("panicMode", nullable(string()))
+                .optional("applyRecommended", nullable(string()))
+                .build(),
+        )
+        .build();
+    let streaming_mode = ObjectSchema::new()
+        .required("platform", streaming_platform())
+        .required(
+            "style",
+            variants(&[
+                StreamingStyle::Talk,
+                StreamingStyle::Gaming,
+                StreamingStyle::Music,
+                StreamingStyle::Art,
+                StreamingStyle::Other,
+            ]),
+        )
+        .required("networkSpeedMbps", number())
+        .required("qualityPriority", boolean())
+        .required(
+            "protocol",
+            variants(&[
+                StreamingProtocol::Rtmp,
+                StreamingProtocol::Rtmps,
+                StreamingProtocol::Srt,
+                StreamingProtocol::Rist,
+            ]),
+        )
+        .required(
+            "latency",
+            variants(&[StreamLatency::Normal, StreamLatency::Low, StreamLatency::UltraLow]),
+        )
+        .required(
+            "setupType",
+            variants(&[SetupType::SinglePc, SetupType::DualPcStreaming, SetupType::DualPcGaming]),
+        )
+        .required("hdrMode", boolean())
+        .build();
+    let debug_recording = ObjectSchema::new()
+        .required("enabled", boolean())
+        .required("maxFileSizeKb", integer())
+        .required("maxFiles", integer())
+        .build();
+    let telemetry = ObjectSchema::new()
+        .required("enabled", boolean())
+        .required("endpoint", string())
+        .build();
+    let observer = ObjectSchema::new().required("bindAddress", string()).build();
+    let audio_monitoring = ObjectSchema::new()
+        .required("enabled", boolean())
+        .required("monitoringOffsetMs", integer())
+        .build();
+    let required_scene_item = ObjectSchema::new()
+        .required("sceneName", string())
+        .required("sourceName", string())
+        .build();
+    let automation_trigger = ObjectSchema::new()
+        .required("metric", metric_type())
+        .required(
+            "comparison",
+            variants(&[ThresholdComparison::Above, ThresholdComparison::Below]),
+        )
+        .required("threshold", number())
+        .required("durationSecs", integer())
+        .build();
+    let automation_rule = ObjectSchema::new()
+        .required("id", string())
+        .required("name", string())
+        .required("enabled", boolean())
+        .required("trigger", automation_trigger)
+        .required("actions", array(open_object()))
+        .required("cooldownSecs", integer())
+        .build();
+
+    let schema = ObjectSchema::new()
+        .required("version", string())
+        .required("connection", connection)
+        .required("monitoring", monitoring)
+        .required("alerts", alerts)
+        .required("display", display)
+        .required("streamingMode", streaming_mode)
+        .required(
+            "obsProcessPriority",
+            variants(&[
+                ProcessPriority::High,
+                ProcessPriority::AboveNormal,
+                ProcessPriority::Normal,
+                ProcessPriority::BelowNormal,
+            ]),
+        )
+        .required("knownGameExecutables", array(string()))
+        .required("microphoneInputName", nullable(string()))
+        .required("debugRecording", debug_recording)
+        .required("telemetry", telemetry)
+        .required("requiredSceneItems", array(required_scene_item))
+        .required("monthlyQuotaGb", nullable(number()))
+        .required("quotaResetAt", nullable(integer()))
+        .required("dismissedChecklistRules", array(string()))
+        .required("audioMonitoring", audio_monitoring)
+        .required("automationRules", array(automation_rule))
+        .required("backupObsFiles", boolean())
+        .required("observer", observer)
+        .required("mediaAutoactions", array(array(any())))
+        .build();
+    document("app-config", SCHEMA_VERSION, "アプリケーション設定", schema)
+}
+
+/// エクスポート形式ごとのスキーマ（名前, バージョン, スキーマ）
+pub fn exported_schemas() -> Vec<(&'static str, u32, Value)> {
+    vec![
+        ("diagnostic-report", SCHEMA_VERSION, diagnostic_report_schema()),
+        ("session-export", SCHEMA_VERSION, session_export_schema()),
+        ("historical-metrics", SCHEMA_VERSION, historical_metrics_schema()),
+        ("analysis-result", 1, analysis_result_v1_schema()),
+        ("analysis-result", ANALYSIS_RESULT_SCHEMA_VERSION, analysis_result_schema()),
+    ]
+}
+
+/// すべてのスキーマを `<name>.v<version>.schema.json` としてディレクトリに書き出す
+///
+/// # Returns
+/// 書き出したファイルのパス
+pub fn write_schemas(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| AppError::export_error(&format!("Failed to create schema directory: {e}")))?;
+
+    exported_schemas()
+        .into_iter()
+        .map(|(name, version, schema)| {
+            let path = dir.join(schema_file_name(name, version));
+            let content = serde_json::to_string_pretty(&schema)
+                .map_err(|e| AppError::export_error(&format!("Failed to serialize schema: {e}")))?;
+            std::fs::write(&path, content)
+                .map_err(|e| AppError::export_error(&format!("Failed to write schema: {e}")))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// JSONがスキーマに適合するか検証
+///
+/// このモジュールが生成するスキーマで使用するキーワード
+/// （`type` / `enum` / `properties` / `required` / `additionalProperties` / `items`）のみ対応する。
+///
+/// # Returns
+/// 不適合箇所（JSON Pointer形式のパスと理由）の一覧。適合する場合は空
+pub fn validate(schema: &Value, instance: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, instance, "", &mut errors);
+    errors
+}
+
+fn validate_at(schema: &Value, instance: &Value, path: &str, errors: &mut Vec<String>) {
+    let location = if path.is_empty() { "/" } else { path };
+
+    if let Some(expected) = schema.get("type") {
+        let matches = match expected {
+            Value::String(kind) => type_matches(kind, instance),
+            Value::Array(kinds) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|kind| type_matches(kind, instance)),
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!("{location}: expected type {expected}, got {instance}"));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(instance) {
+            errors.push(format!("{location}: {instance} is not one of {}", Value::Array(allowed.clone())));
+        }
+    }
+
+    if let Value::Object(object) = instance {
+        let properties = schema.get("properties").and_then(Value::as_object);
+
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    errors.push(format!("{location}: missing required property \"{name}\""));
+                }
+            }
+        }
+
+        for (name, value) in object {
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(property) => validate_at(property, value, &format!("{path}/{name}"), errors),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    errors.push(format!("{location}: unexpected property \"{name}\""));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (instance, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{path}/{index}"), errors);
+        }
+    }
+}
+
+/// JSON Schemaの型名と値が一致するか
+fn type_matches(kind: &str, value: &Value) -> bool {
+    match kind {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullable_adds_null_to_type_and_enum() {
+        let schema = nullable(string_enum(&["a", "b"]));
+
+        assert_eq!(schema["type"], json!(["string", "null"]));
+        assert_eq!(schema["enum"], json!(["a", "b", null]));
+        assert!(validate(&schema, &Value::Null).is_empty());
+        assert!(!validate(&schema, &json!("c")).is_empty());
+    }
+
+    #[test]
+    fn test_variants_use_serialized_names() {
+        assert_eq!(streaming_platform()["enum"][5], "showRoom");
+        assert_eq!(power_source()["enum"], json!(["ac", "battery", "unknown"]));
+    }
+
+    #[test]
+    fn test_validate_reports_missing_and_unexpected_properties() {
+        let schema = ObjectSchema::new()
+            .required("id", string())
+            .optional("note", string())
+            .build();
+
+        assert!(validate(&schema, &json!({ "id": "a" })).is_empty());
+        assert!(validate(&schema, &json!({ "id": "a", "note": "b" })).is_empty());
+
+        let errors = validate(&schema, &json!({ "extra": 1 }));
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.contains("missing required property \"id\"")));
+        assert!(errors.iter().any(|e| e.contains("unexpected property \"extra\"")));
+    }
+
+    #[test]
+    fn test_validate_reports_nested_path() {
+        let schema = ObjectSchema::new()
+            .required("items", array(ObjectSchema::new().required("count", integer()).build()))
+            .build();
+
+        let errors = validate(&schema, &json!({ "items": [{ "count": 1 }, { "count": 1.5 }] }));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/items/1/count"));
+    }
+
+    #[test]
+    fn test_exported_schemas_have_versioned_ids() {
+        for (name, version, schema) in exported_schemas() {
+            assert_eq!(schema["$schema"], SCHEMA_DRAFT);
+            assert_eq!(schema["$id"], format!("{name}.v{version}.schema.json"));
+            assert_eq!(schema["type"], "object");
+        }
+    }
+
+    #[test]
+    fn test_analysis_result_schema_versions() {
+        let v1 = analysis_result_v1_schema();
+        let v2 = analysis_result_schema();
+        assert_eq!(v1["$id"], "analysis-result.v1.schema.json");
+        assert_eq!(v2["$id"], "analysis-result.v2.schema.json");
+        assert_eq!(v1["properties"]["qualityScore"], integer());
+        assert_eq!(v2["properties"]["qualityScore"]["type"], "object");
+
+        // qualityScore以外のプロパティは共通
+        let mut v1_properties = v1["properties"].as_object().unwrap().clone();
+        let mut v2_properties = v2["properties"].as_object().unwrap().clone();
+        v1_properties.remove("qualityScore");
+        v2_properties.remove("qualityScore");
+        assert_eq!(v1_properties, v2_properties);
+    }
+
+    #[test]
+    fn test_app_config_schema_matches_default_config() {
+        use crate::storage::config::AppConfig;
+
+        let config = serde_json::to_value(AppConfig::default()).unwrap();
+        let errors = validate(&app_config_schema(), &config);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_write_schemas() {
+        let dir = std::env::temp_dir().join(format!("obs-optimizer-schemas-{}", std::process::id()));
+
+        let paths = write_schemas(&dir).unwrap();
+        assert_eq!(paths.len(), exported_schemas().len());
+        for path in &paths {
+            let content = std::fs::read_to_string(path).unwrap();
+            let schema: Value = serde_json::from_str(&content).unwrap();
+            assert!(schema.get("properties").is_some());
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
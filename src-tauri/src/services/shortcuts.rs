@@ -0,0 +1,416 @@
+// グローバルショートカットの検証と競合検出
+//
+// アプリのグローバルショートカットがOBSや他のアプリのホットキーと重なると、
+// どちらか（または両方）が黙って動作しなくなる。
+// 割り当て前にアクセラレーター文字列（例: "Ctrl+Shift+F9"）を検証し、
+// OBSのプロファイルに保存されたホットキーと既知のアプリのショートカットに照合する。
+//
+// OBSのホットキーのうち、プロファイル（basic.ini の [Hotkeys]）に保存される
+// フロントエンドのホットキー（配信開始・録画開始など）のみ照合できる。
+// ソースごとのホットキーはシーンコレクションに保存され、obs-websocketから取得できない。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// プロファイルに保存されるOBSのホットキー名の接頭辞
+const OBS_FRONTEND_HOTKEY_PREFIX: &str = "OBSBasic.";
+
+/// OBSのホットキー設定のプロファイルのセクション名
+const OBS_HOTKEY_SECTION: &str = "Hotkeys";
+
+/// OBSのキー名の接頭辞（例: `OBS_KEY_F9`）
+const OBS_KEY_PREFIX: &str = "OBS_KEY_";
+
+/// ショートカットを割り当てる操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutAction {
+    /// パニックモード（緊急時に配信の負荷を下げる）
+    PanicMode,
+    /// 推奨設定の適用
+    ApplyRecommended,
+}
+
+impl ShortcutAction {
+    /// 全ての操作
+    pub const ALL: [Self; 2] = [Self::PanicMode, Self::ApplyRecommended];
+
+    /// 設定ファイルでのフィールド名
+    pub const fn field_name(self) -> &'static str {
+        match self {
+            Self::PanicMode => "panicMode",
+            Self::ApplyRecommended => "applyRecommended",
+        }
+    }
+}
+
+/// 名前付きキーの表（比較用の小文字表記, 正規の表記）
+const NAMED_KEYS: &[(&str, &str)] = &[
+    ("space", "Space"),
+    ("tab", "Tab"),
+    ("enter", "Enter"),
+    ("return", "Enter"),
+    ("escape", "Escape"),
+    ("esc", "Escape"),
+    ("backspace", "Backspace"),
+    ("delete", "Delete"),
+    ("del", "Delete"),
+    ("insert", "Insert"),
+    ("home", "Home"),
+    ("end", "End"),
+    ("pageup", "PageUp"),
+    ("pagedown", "PageDown"),
+    ("up", "Up"),
+    ("down", "Down"),
+    ("left", "Left"),
+    ("right", "Right"),
+    ("pause", "Pause"),
+    ("printscreen", "PrintScreen"),
+    ("print", "PrintScreen"),
+];
+
+/// キー名を正規の表記に変換（未対応のキーはNone）
+fn canonical_key(name: &str) -> Option<String> {
+    let lower = name.trim().to_ascii_lowercase();
+
+    if lower.len() == 1 && lower.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(lower.to_ascii_uppercase());
+    }
+    if let Some(number) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=24).contains(&number).then(|| format!("F{number}"));
+    }
+    if let Some(number) = lower.strip_prefix("num").and_then(|n| n.parse::<u8>().ok()) {
+        return (number <= 9).then(|| format!("Num{number}"));
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, canonical)| (*canonical).to_string())
+}
+
+/// キーの組み合わせ（アクセラレーター）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    /// Ctrl（macOSではCommandとして扱う）
+    pub ctrl: bool,
+    /// Alt（macOSのOption）
+    pub alt: bool,
+    /// Shift
+    pub shift: bool,
+    /// Windowsキー / Super
+    pub super_key: bool,
+    /// 修飾キー以外のキー（正規の表記）
+    pub key: String,
+}
+
+impl Accelerator {
+    /// アクセラレーター文字列を解析
+    ///
+    /// 修飾キーとキーを`+`で区切る（大文字小文字は区別しない）。
+    /// 修飾キーなしで割り当てられるのはファンクションキーのみ（文字入力を奪わないため）
+    ///
+    /// # Arguments
+    /// * `value` - アクセラレーター文字列（例: "Ctrl+Shift+F9", "CommandOrControl+Alt+P"）
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        let invalid = |reason: &str| AppError::validation_failed(&format!("ショートカット「{value}」が不正です: {reason}"));
+
+        let tokens: Vec<&str> = value.split('+').map(str::trim).collect();
+        if tokens.iter().any(|token| token.is_empty()) {
+            return Err(invalid("空のキーが含まれています"));
+        }
+        let Some((key, modifiers)) = tokens.split_last() else {
+            return Err(invalid("キーを指定してください"));
+        };
+
+        let mut accelerator = Self {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            super_key: false,
+            key: String::new(),
+        };
+        for modifier in modifiers {
+            let flag = match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "cmdorctrl" | "commandorcontrol" => &mut accelerator.ctrl,
+                "alt" | "option" => &mut accelerator.alt,
+                "shift" => &mut accelerator.shift,
+                "super" | "meta" | "win" | "cmd" | "command" => &mut accelerator.super_key,
+                _ => return Err(invalid(&format!("「{modifier}」は修飾キーではありません"))),
+            };
+            if *flag {
+                return Err(invalid(&format!("修飾キー「{modifier}」が重複しています")));
+            }
+            *flag = true;
+        }
+
+        accelerator.key = canonical_key(key).ok_or_else(|| invalid(&format!("「{key}」は使用できないキーです")))?;
+        if !accelerator.has_modifier() && !accelerator.is_function_key() {
+            return Err(invalid("ファンクションキー以外は修飾キーと組み合わせてください"));
+        }
+        Ok(accelerator)
+    }
+
+    /// 修飾キーを含むか
+    pub const fn has_modifier(&self) -> bool {
+        self.ctrl || self.alt || self.shift || self.super_key
+    }
+
+    /// ファンクションキー（F1〜F24）か
+    fn is_function_key(&self) -> bool {
+        self.key.len() > 1 && self.key.starts_with('F') && self.key[1..].chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+impl fmt::Display for Accelerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (enabled, name) in [
+            (self.ctrl, "Ctrl"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+            (self.super_key, "Super"),
+        ] {
+            if enabled {
+                write!(f, "{name}+")?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// OBSのホットキー設定の1つの割り当て
+#[derive(Debug, Deserialize)]
+struct ObsKeyBinding {
+    #[serde(default)]
+    control: bool,
+    #[serde(default)]
+    alt: bool,
+    #[serde(default)]
+    shift: bool,
+    #[serde(default)]
+    command: bool,
+    #[serde(default)]
+    key: String,
+}
+
+/// OBSのホットキー設定（プロファイルに保存されるJSON）
+#[derive(Debug, Deserialize)]
+struct ObsHotkeySetting {
+    #[serde(default)]
+    bindings: Vec<ObsKeyBinding>,
+}
+
+/// OBSのホットキー設定を解析
+///
+/// マウスボタンなど照合できないキーの割り当ては無視する
+///
+/// # Arguments
+/// * `value` - プロファイルの値（例: `{"bindings":[{"control":true,"key":"OBS_KEY_F9"}]}`）
+pub fn parse_obs_hotkey_bindings(value: &str) -> Vec<Accelerator> {
+    let Ok(setting) = serde_json::from_str::<ObsHotkeySetting>(value) else {
+        return Vec::new();
+    };
+
+    setting
+        .bindings
+        .into_iter()
+        .filter_map(|binding| {
+            let key = canonical_key(binding.key.strip_prefix(OBS_KEY_PREFIX)?)?;
+            Some(Accelerator {
+                ctrl: binding.control,
+                alt: binding.alt,
+                shift: binding.shift,
+                super_key: binding.command,
+                key,
+            })
+        })
+        .collect()
+}
+
+/// OBSに割り当てられたホットキー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObsHotkeyBinding {
+    /// OBSのホットキー名（例: "OBSBasic.StartStreaming"）
+    pub name: String,
+    /// 割り当てられたキー
+    pub accelerator: Accelerator,
+}
+
+/// 既知のアプリのショートカット
+#[derive(Debug, Clone, Copy)]
+pub struct KnownShortcut {
+    /// アクセラレーター文字列
+    pub accelerator: &'static str,
+    /// 使用しているアプリと操作
+    pub owner: &'static str,
+}
+
+/// 既定で割り当てられている既知のアプリ・OSのショートカット
+pub const KNOWN_APPLICATION_SHORTCUTS: &[KnownShortcut] = &[
+    KnownShortcut { accelerator: "Alt+Z", owner: "NVIDIAオーバーレイ（オーバーレイを開く）" },
+    KnownShortcut { accelerator: "Alt+F9", owner: "NVIDIAオーバーレイ（録画の開始・停止）" },
+    KnownShortcut { accelerator: "Alt+F10", owner: "NVIDIAオーバーレイ（インスタントリプレイの保存）" },
+    KnownShortcut { accelerator: "Super+G", owner: "Xbox Game Bar（Game Barを開く）" },
+    KnownShortcut { accelerator: "Super+Alt+R", owner: "Xbox Game Bar（録画の開始・停止）" },
+    KnownShortcut { accelerator: "Super+Alt+G", owner: "Xbox Game Bar（直前の録画）" },
+    KnownShortcut { accelerator: "Ctrl+Shift+M", owner: "Discord（マイクのミュート）" },
+    KnownShortcut { accelerator: "Ctrl+Shift+D", owner: "Discord（スピーカーのミュート）" },
+    KnownShortcut { accelerator: "Alt+F4", owner: "Windows（ウィンドウを閉じる）" },
+    KnownShortcut { accelerator: "Alt+Tab", owner: "Windows（ウィンドウの切り替え）" },
+    KnownShortcut { accelerator: "Ctrl+Alt+Delete", owner: "Windows（セキュリティオプション）" },
+    KnownShortcut { accelerator: "Super+L", owner: "Windows（画面のロック）" },
+];
+
+/// 競合の相手
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutConflictSource {
+    /// OBSのホットキー
+    Obs,
+    /// 他のアプリ・OSのショートカット
+    Application,
+}
+
+/// 検出されたショートカットの競合
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutConflict {
+    /// 競合しているアプリの操作
+    pub action: ShortcutAction,
+    /// 割り当てられたショートカット（正規の表記）
+    pub accelerator: String,
+    /// 競合の相手
+    pub source: ShortcutConflictSource,
+    /// 競合しているホットキー名・アプリ名
+    pub conflicting_with: String,
+}
+
+/// アプリのショートカットとOBS・既知のアプリのショートカットを照合
+///
+/// # Arguments
+/// * `bindings` - アプリの操作と割り当てられたショートカット
+/// * `obs_hotkeys` - OBSに割り当てられたホットキー
+pub fn find_shortcut_conflicts(
+    bindings: &[(ShortcutAction, Accelerator)],
+    obs_hotkeys: &[ObsHotkeyBinding],
+) -> Vec<ShortcutConflict> {
+    let known: Vec<(Accelerator, &str)> = KNOWN_APPLICATION_SHORTCUTS
+        .iter()
+        .filter_map(|shortcut| Some((Accelerator::parse(shortcut.accelerator).ok()?, shortcut.owner)))
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for (action, accelerator) in bindings {
+        let conflict = |source, conflicting_with: &str| ShortcutConflict {
+            action: *action,
+            accelerator: accelerator.to_string(),
+            source,
+            conflicting_with: conflicting_with.to_string(),
+        };
+        conflicts.extend(
+            obs_hotkeys
+                .iter()
+                .filter(|hotkey| hotkey.accelerator == *accelerator)
+                .map(|hotkey| conflict(ShortcutConflictSource::Obs, &hotkey.name)),
+        );
+        conflicts.extend(
+            known
+                .iter()
+                .filter(|(known, _)| known == accelerator)
+                .map(|(_, owner)| conflict(ShortcutConflictSource::Application, owner)),
+        );
+    }
+    conflicts
+}
+
+/// OBSの現在のプロファイルに割り当てられたホットキーを取得
+///
+/// # Arguments
+/// * `client` - OBSクライアント（接続済み）
+pub async fn read_obs_hotkeys(client: &ObsClient) -> Result<Vec<ObsHotkeyBinding>, AppError> {
+    let mut hotkeys = Vec::new();
+    for name in client.get_hotkey_list().await? {
+        if !name.starts_with(OBS_FRONTEND_HOTKEY_PREFIX) {
+            continue;
+        }
+        let Some(value) = client.get_profile_parameter(OBS_HOTKEY_SECTION, &name).await? else {
+            continue;
+        };
+        hotkeys.extend(
+            parse_obs_hotkey_bindings(&value)
+                .into_iter()
+                .map(|accelerator| ObsHotkeyBinding { name: name.clone(), accelerator }),
+        );
+    }
+    Ok(hotkeys)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accelerator() {
+        let accelerator = Accelerator::parse("ctrl + shift + f9").unwrap();
+        assert!(accelerator.ctrl && accelerator.shift && !accelerator.alt && !accelerator.super_key);
+        assert_eq!(accelerator.key, "F9");
+        assert_eq!(accelerator.to_string(), "Ctrl+Shift+F9");
+
+        assert_eq!(Accelerator::parse("CommandOrControl+Alt+p").unwrap().to_string(), "Ctrl+Alt+P");
+        assert_eq!(Accelerator::parse("Win+PageUp").unwrap().to_string(), "Super+PageUp");
+        assert_eq!(Accelerator::parse("Shift+Alt+esc").unwrap().to_string(), "Alt+Shift+Escape");
+        assert_eq!(Accelerator::parse("F12").unwrap().to_string(), "F12");
+        assert_eq!(Accelerator::parse("Ctrl+Num5").unwrap().key, "Num5");
+    }
+
+    #[test]
+    fn test_parse_invalid_accelerator() {
+        for value in ["", "Ctrl+", "Ctrl++P", "Ctrl+Ctrl+P", "Hyper+P", "Ctrl+F25", "Ctrl+Shift", "P", "F", "Ctrl+Mouse4"] {
+            let error = Accelerator::parse(value).unwrap_err();
+            assert_eq!(error.code(), crate::error::ERROR_CODE_VALIDATION_FAILED, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_obs_hotkey_bindings() {
+        let value = r#"{"bindings":[{"control":true,"shift":true,"key":"OBS_KEY_F9"},{"alt":true,"key":"OBS_KEY_RETURN"},{"key":"OBS_KEY_MOUSE4"}]}"#;
+        let bindings = parse_obs_hotkey_bindings(value);
+        let names: Vec<String> = bindings.iter().map(ToString::to_string).collect();
+        assert_eq!(names, vec!["Ctrl+Shift+F9", "Alt+Enter"]);
+
+        assert!(parse_obs_hotkey_bindings("").is_empty());
+        assert!(parse_obs_hotkey_bindings(r#"{"bindings":[]}"#).is_empty());
+    }
+
+    #[test]
+    fn test_find_shortcut_conflicts() {
+        let obs_hotkeys = vec![ObsHotkeyBinding {
+            name: "OBSBasic.StartStreaming".to_string(),
+            accelerator: Accelerator::parse("Ctrl+Shift+F9").unwrap(),
+        }];
+        let bindings = vec![
+            (ShortcutAction::PanicMode, Accelerator::parse("shift+ctrl+F9").unwrap()),
+            (ShortcutAction::ApplyRecommended, Accelerator::parse("Alt+Z").unwrap()),
+        ];
+
+        let conflicts = find_shortcut_conflicts(&bindings, &obs_hotkeys);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].action, ShortcutAction::PanicMode);
+        assert_eq!(conflicts[0].source, ShortcutConflictSource::Obs);
+        assert_eq!(conflicts[0].conflicting_with, "OBSBasic.StartStreaming");
+        assert_eq!(conflicts[0].accelerator, "Ctrl+Shift+F9");
+        assert_eq!(conflicts[1].source, ShortcutConflictSource::Application);
+
+        let free = vec![(ShortcutAction::PanicMode, Accelerator::parse("Ctrl+Alt+F9").unwrap())];
+        assert!(find_shortcut_conflicts(&free, &obs_hotkeys).is_empty());
+    }
+
+    #[test]
+    fn test_known_application_shortcuts_are_valid() {
+        for shortcut in KNOWN_APPLICATION_SHORTCUTS {
+            assert!(Accelerator::parse(shortcut.accelerator).is_ok(), "{}", shortcut.accelerator);
+        }
+    }
+}
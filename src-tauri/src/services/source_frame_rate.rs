@@ -0,0 +1,369 @@
+// 映像ソースと出力のフレームレート不一致の検出
+//
+// 雑談・歌配信では画面の大部分をWebカメラが占めるため、30fpsのカメラを60fpsで出力しても
+// 同じフレームを2回エンコードするだけで見た目は変わらない。
+// 入力の設定からカメラのFPS・解像度を読み取り、主要な映像ソースが出力の半分のFPSで
+// 動作している場合に30fps出力を提案する。
+// ゲーム配信ではゲームキャプチャが主要な映像ソースのため対象外とする。
+
+use crate::error::AppError;
+use crate::obs::ObsClient;
+use crate::services::dual_pc::{is_capture_source, DEVICE_SETTING_KEYS};
+use crate::storage::config::StreamingStyle;
+
+/// Webカメラの入力種別（キャプチャーボードと共通）
+const CAMERA_INPUT_KINDS: &[&str] = &["dshow_input", "av_capture_input", "v4l2_input"];
+
+/// ゲームキャプチャの入力種別
+const GAME_CAPTURE_INPUT_KINDS: &[&str] = &["game_capture"];
+
+/// 画面・ウィンドウキャプチャの入力種別
+const SCREEN_CAPTURE_INPUT_KINDS: &[&str] = &[
+    "monitor_capture",
+    "window_capture",
+    "screen_capture",
+    "display_capture",
+    "xshm_input",
+    "xcomposite_input",
+    "pipewire-desktop-capture-source",
+    "pipewire-window-capture-source",
+];
+
+/// 出力FPSが映像ソースの「2倍」とみなす比率の範囲（59.94fps/29.97fpsなどの端数を許容）
+const HALF_RATE_RATIO_RANGE: std::ops::RangeInclusive<f64> = 1.9..=2.1;
+
+/// 映像ソースの役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoSourceRole {
+    /// Webカメラ
+    Camera,
+    /// ゲームキャプチャ（キャプチャーボード・NDIの映像を含む）
+    GameCapture,
+    /// 画面・ウィンドウキャプチャ
+    ScreenCapture,
+}
+
+/// 映像ソースの情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoSourceInfo {
+    /// 入力名
+    pub name: String,
+    /// 役割
+    pub role: VideoSourceRole,
+    /// 設定されたFPS（デバイスの既定値・出力に合わせる設定の場合は`None`）
+    pub fps: Option<f64>,
+    /// 設定された解像度（デバイスの既定値の場合は`None`）
+    pub resolution: Option<(u32, u32)>,
+}
+
+/// 主要な映像ソースと出力のフレームレート不一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameRateMismatch {
+    /// 主要な映像ソースの入力名
+    pub source_name: String,
+    /// 映像ソースのFPS
+    pub source_fps: f64,
+    /// 推奨する出力FPS
+    pub recommended_fps: u32,
+    /// 映像ソースの解像度が出力解像度より低い場合の解像度
+    pub lower_resolution: Option<(u32, u32)>,
+}
+
+/// 入力種別・入力名・デバイス名から映像ソースの役割を判定
+///
+/// 映像キャプチャデバイスのうちキャプチャーボードと推定されるものはゲームキャプチャとして扱う
+pub fn classify_video_source(input_kind: &str, input_name: &str, device: Option<&str>) -> Option<VideoSourceRole> {
+    if is_capture_source(input_kind, input_name, device) || GAME_CAPTURE_INPUT_KINDS.contains(&input_kind) {
+        Some(VideoSourceRole::GameCapture)
+    } else if CAMERA_INPUT_KINDS.contains(&input_kind) {
+        Some(VideoSourceRole::Camera)
+    } else if SCREEN_CAPTURE_INPUT_KINDS.contains(&input_kind) {
+        Some(VideoSourceRole::ScreenCapture)
+    } else {
+        None
+    }
+}
+
+/// 配信スタイルから画面の大部分を占める映像ソースの役割を推定
+///
+/// その他のスタイルは構成が定まらないため推定しない
+pub const fn dominant_role(style: StreamingStyle) -> Option<VideoSourceRole> {
+    match style {
+        StreamingStyle::Talk | StreamingStyle::Music => Some(VideoSourceRole::Camera),
+        StreamingStyle::Gaming => Some(VideoSourceRole::GameCapture),
+        StreamingStyle::Art => Some(VideoSourceRole::ScreenCapture),
+        StreamingStyle::Other => None,
+    }
+}
+
+/// 主要な映像ソースを推定
+///
+/// 配信スタイルに対応する役割の最初のソースを主要な映像ソースとする。
+/// 該当する役割のソースがない場合は推定しない
+pub fn infer_dominant_source(style: StreamingStyle, sources: &[VideoSourceInfo]) -> Option<&VideoSourceInfo> {
+    let role = dominant_role(style)?;
+    sources.iter().find(|source| source.role == role)
+}
+
+/// 主要な映像ソースが出力の半分のFPSで動作しているかを検出
+///
+/// 主要な映像ソースがWebカメラの場合のみ判定する
+///
+/// # Arguments
+/// * `style` - 配信スタイル
+/// * `output_fps` - 現在の出力FPS
+/// * `output_resolution` - 現在の出力解像度
+/// * `sources` - 映像ソース
+pub fn detect_frame_rate_mismatch(
+    style: StreamingStyle,
+    output_fps: f64,
+    output_resolution: (u32, u32),
+    sources: &[VideoSourceInfo],
+) -> Option<FrameRateMismatch> {
+    let source = infer_dominant_source(style, sources)?;
+    if source.role != VideoSourceRole::Camera {
+        return None;
+    }
+
+    let source_fps = source.fps.filter(|fps| *fps > 0.0)?;
+    if !HALF_RATE_RATIO_RANGE.contains(&(output_fps / source_fps)) {
+        return None;
+    }
+
+    let lower_resolution = source
+        .resolution
+        .filter(|(_, height)| *height < output_resolution.1);
+
+    Some(FrameRateMismatch {
+        source_name: source.name.clone(),
+        source_fps,
+        recommended_fps: (output_fps / 2.0).round() as u32,
+        lower_resolution,
+    })
+}
+
+/// `"1920x1080"`形式の解像度を解析
+fn parse_resolution_text(text: &str) -> Option<(u32, u32)> {
+    let (width, height) = text.split_once('x')?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+/// 映像キャプチャデバイスの設定から解像度とFPSを読み取る
+///
+/// 入力種別ごとに設定の形式が異なる。
+/// デバイスの既定値・出力に合わせる設定の場合は値を返さない
+pub fn parse_camera_settings(input_kind: &str, settings: &serde_json::Value) -> (Option<(u32, u32)>, Option<f64>) {
+    match input_kind {
+        // Windows: res_type=1（カスタム）の場合のみ解像度・FPSが有効。frame_intervalは100ns単位
+        "dshow_input" => {
+            if settings.get("res_type").and_then(serde_json::Value::as_i64) != Some(1) {
+                return (None, None);
+            }
+            let resolution = settings
+                .get("resolution")
+                .and_then(serde_json::Value::as_str)
+                .and_then(parse_resolution_text);
+            let fps = settings
+                .get("frame_interval")
+                .and_then(serde_json::Value::as_i64)
+                .filter(|interval| *interval > 0)
+                .map(|interval| 10_000_000.0 / interval as f64);
+            (resolution, fps)
+        }
+        // macOS: use_preset=falseの場合のみ解像度・FPSが有効。解像度はJSON文字列
+        "av_capture_input" => {
+            if settings.get("use_preset").and_then(serde_json::Value::as_bool) != Some(false) {
+                return (None, None);
+            }
+            let resolution = settings
+                .get("resolution")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok())
+                .and_then(|value| {
+                    let width = u32::try_from(value.get("width")?.as_u64()?).ok()?;
+                    let height = u32::try_from(value.get("height")?.as_u64()?).ok()?;
+                    Some((width, height))
+                });
+            let fps = settings.get("frame_rate").and_then(|rate| {
+                let numerator = rate.get("numerator")?.as_f64()?;
+                let denominator = rate.get("denominator")?.as_f64()?;
+                (numerator > 0.0 && denominator > 0.0).then_some(numerator / denominator)
+            });
+            (resolution, fps)
+        }
+        // Linux: 上位16ビット・下位16ビットに値を詰めた整数。-1は変更しない設定
+        "v4l2_input" => {
+            let unpack = |key: &str| {
+                let packed = settings.get(key)?.as_i64().filter(|value| *value > 0)?;
+                Some(((packed >> 16) as u32, (packed & 0xffff) as u32))
+            };
+            let resolution = unpack("resolution");
+            // フレーム間隔（秒）を numerator/denominator で保持する
+            let fps = unpack("framerate")
+                .filter(|(numerator, denominator)| *numerator > 0 && *denominator > 0)
+                .map(|(numerator, denominator)| f64::from(denominator) / f64::from(numerator));
+            (resolution, fps)
+        }
+        _ => (None, None),
+    }
+}
+
+/// OBSから映像ソースを取得
+///
+/// 入力の設定を取得できない映像キャプチャデバイスはFPS・解像度を不明として扱う
+pub async fn collect_video_sources(client: &ObsClient) -> Result<Vec<VideoSourceInfo>, AppError> {
+    let inputs = client.get_input_list().await?;
+    let mut sources = Vec::new();
+
+    for input in inputs {
+        let name = input.id.name;
+        let settings = if CAMERA_INPUT_KINDS.contains(&input.kind.as_str()) {
+            client.get_input_settings(&name).await.ok()
+        } else {
+            None
+        };
+        let device = settings.as_ref().and_then(|settings| {
+            DEVICE_SETTING_KEYS
+                .iter()
+                .find_map(|key| settings.get(*key).and_then(serde_json::Value::as_str))
+        });
+
+        let Some(role) = classify_video_source(&input.kind, &name, device) else {
+            continue;
+        };
+        let (resolution, fps) = settings
+            .as_ref()
+            .map_or((None, None), |settings| parse_camera_settings(&input.kind, settings));
+
+        sources.push(VideoSourceInfo { name, role, fps, resolution });
+    }
+
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(fps: Option<f64>, resolution: Option<(u32, u32)>) -> VideoSourceInfo {
+        VideoSourceInfo {
+            name: "Webカメラ".to_string(),
+            role: VideoSourceRole::Camera,
+            fps,
+            resolution,
+        }
+    }
+
+    fn game_capture() -> VideoSourceInfo {
+        VideoSourceInfo {
+            name: "ゲームキャプチャ".to_string(),
+            role: VideoSourceRole::GameCapture,
+            fps: None,
+            resolution: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_video_source() {
+        assert_eq!(
+            classify_video_source("dshow_input", "Webカメラ", Some("Logitech C920")),
+            Some(VideoSourceRole::Camera)
+        );
+        assert_eq!(
+            classify_video_source("dshow_input", "映像キャプチャデバイス", Some("Elgato HD60 X")),
+            Some(VideoSourceRole::GameCapture)
+        );
+        assert_eq!(classify_video_source("game_capture", "ゲーム", None), Some(VideoSourceRole::GameCapture));
+        assert_eq!(classify_video_source("monitor_capture", "画面", None), Some(VideoSourceRole::ScreenCapture));
+        assert_eq!(classify_video_source("image_source", "背景", None), None);
+    }
+
+    #[test]
+    fn test_infer_dominant_source() {
+        let sources = vec![game_capture(), camera(Some(30.0), None)];
+
+        assert_eq!(infer_dominant_source(StreamingStyle::Talk, &sources).map(|s| s.role), Some(VideoSourceRole::Camera));
+        assert_eq!(
+            infer_dominant_source(StreamingStyle::Gaming, &sources).map(|s| s.role),
+            Some(VideoSourceRole::GameCapture)
+        );
+        assert_eq!(infer_dominant_source(StreamingStyle::Other, &sources), None);
+        // 該当する役割のソースがない
+        assert_eq!(infer_dominant_source(StreamingStyle::Art, &sources), None);
+    }
+
+    #[test]
+    fn test_detects_half_rate_camera_for_talk() {
+        let sources = vec![camera(Some(30.0), Some((1280, 720)))];
+
+        let mismatch = detect_frame_rate_mismatch(StreamingStyle::Talk, 60.0, (1920, 1080), &sources)
+            .expect("30fpsのカメラを60fpsで出力している");
+        assert_eq!(mismatch.recommended_fps, 30);
+        assert_eq!(mismatch.lower_resolution, Some((1280, 720)));
+
+        // 59.94fps出力と29.97fpsのカメラ
+        let sources = vec![camera(Some(29.97), Some((1920, 1080)))];
+        let mismatch = detect_frame_rate_mismatch(StreamingStyle::Music, 59.94, (1920, 1080), &sources)
+            .expect("端数のあるFPSも検出する");
+        assert_eq!(mismatch.recommended_fps, 30);
+        assert_eq!(mismatch.lower_resolution, None);
+    }
+
+    #[test]
+    fn test_no_mismatch_when_rates_match_or_unknown() {
+        let matched = vec![camera(Some(30.0), None)];
+        assert_eq!(detect_frame_rate_mismatch(StreamingStyle::Talk, 30.0, (1920, 1080), &matched), None);
+
+        let unknown = vec![camera(None, None)];
+        assert_eq!(detect_frame_rate_mismatch(StreamingStyle::Talk, 60.0, (1920, 1080), &unknown), None);
+
+        // 半分以外の比率は対象外
+        let slow = vec![camera(Some(15.0), None)];
+        assert_eq!(detect_frame_rate_mismatch(StreamingStyle::Talk, 60.0, (1920, 1080), &slow), None);
+    }
+
+    #[test]
+    fn test_gaming_style_never_fires() {
+        let sources = vec![camera(Some(30.0), None), game_capture()];
+        assert_eq!(detect_frame_rate_mismatch(StreamingStyle::Gaming, 60.0, (1920, 1080), &sources), None);
+
+        // ゲームキャプチャがなくてもゲーム配信ではカメラを主要ソースとみなさない
+        let camera_only = vec![camera(Some(30.0), None)];
+        assert_eq!(detect_frame_rate_mismatch(StreamingStyle::Gaming, 60.0, (1920, 1080), &camera_only), None);
+    }
+
+    #[test]
+    fn test_parse_camera_settings() {
+        let dshow = serde_json::json!({
+            "res_type": 1,
+            "resolution": "1280x720",
+            "frame_interval": 333_333,
+        });
+        let (resolution, fps) = parse_camera_settings("dshow_input", &dshow);
+        assert_eq!(resolution, Some((1280, 720)));
+        assert!(fps.is_some_and(|fps| (fps - 30.0).abs() < 0.01));
+
+        // デバイスの既定値
+        let dshow_default = serde_json::json!({ "res_type": 0, "frame_interval": 333_333 });
+        assert_eq!(parse_camera_settings("dshow_input", &dshow_default), (None, None));
+
+        let av_capture = serde_json::json!({
+            "use_preset": false,
+            "resolution": "{\"width\":1920,\"height\":1080}",
+            "frame_rate": { "numerator": 30000, "denominator": 1001 },
+        });
+        let (resolution, fps) = parse_camera_settings("av_capture_input", &av_capture);
+        assert_eq!(resolution, Some((1920, 1080)));
+        assert!(fps.is_some_and(|fps| (fps - 29.97).abs() < 0.01));
+
+        let v4l2 = serde_json::json!({
+            "resolution": (1280 << 16) | 720,
+            "framerate": (1 << 16) | 30,
+        });
+        let (resolution, fps) = parse_camera_settings("v4l2_input", &v4l2);
+        assert_eq!(resolution, Some((1280, 720)));
+        assert_eq!(fps, Some(30.0));
+
+        assert_eq!(parse_camera_settings("v4l2_input", &serde_json::json!({ "framerate": -1 })), (None, None));
+    }
+}
@@ -0,0 +1,413 @@
+// 起動時ダイジェストサービス
+//
+// 前回終了時に保存した実行環境スナップショットと現在の環境を比較し、
+// 「OBSが更新された」「GPUドライバーが変わった」などの変化を検出する。
+// 変化があれば設定の再分析を促すためにUIへ表示する。
+
+use crate::monitor::get_cpu_name;
+use crate::monitor::gpu::get_gpu_info;
+use crate::obs::{get_obs_client, get_obs_settings};
+use crate::storage::environment::{
+    load_environment_snapshot, save_environment_snapshot, EnvironmentSnapshot, MonitorInfo,
+    SettingsFingerprint,
+};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+/// 変化の重要度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSignificance {
+    /// 再分析を強く推奨（OBS・ハードウェアの変更）
+    High,
+    /// 確認を推奨（ドライバー・設定の変更）
+    Medium,
+    /// 参考情報（モニター構成の変更）
+    Low,
+}
+
+/// 変化の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnvironmentChangeKind {
+    /// OBSのバージョンが変わった
+    ObsVersion,
+    /// GPUドライバーのバージョンが変わった
+    GpuDriver,
+    /// CPU・GPUが変わった
+    Hardware,
+    /// OBSの設定が変わった
+    SettingsDrift,
+    /// モニターが追加された
+    MonitorAdded,
+    /// モニターが取り外された
+    MonitorRemoved,
+}
+
+/// 検出された変化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentChange {
+    /// 変化の種類
+    pub kind: EnvironmentChangeKind,
+    /// 重要度
+    pub significance: ChangeSignificance,
+    /// 表示用メッセージ（例: "OBSが30.1.2から30.2.0に更新されました"）
+    pub message: String,
+    /// 前回の値
+    pub previous: Option<String>,
+    /// 現在の値
+    pub current: Option<String>,
+    /// 設定の再分析を推奨するか
+    pub suggest_reanalysis: bool,
+}
+
+impl EnvironmentChange {
+    fn new(
+        kind: EnvironmentChangeKind,
+        significance: ChangeSignificance,
+        message: String,
+        previous: Option<String>,
+        current: Option<String>,
+    ) -> Self {
+        Self {
+            kind,
+            significance,
+            message,
+            previous,
+            current,
+            suggest_reanalysis: significance != ChangeSignificance::Low,
+        }
+    }
+}
+
+/// 起動時ダイジェスト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupDigest {
+    /// 前回スナップショットの取得日時（初回起動時はNone）
+    pub previous_captured_at: Option<i64>,
+    /// 検出された変化（重要度順）
+    pub changes: Vec<EnvironmentChange>,
+}
+
+/// 両方の値が取得できており、かつ異なる場合のみ `(前回, 現在)` を返す
+fn changed<'a, T: PartialEq + ?Sized>(
+    previous: Option<&'a T>,
+    current: Option<&'a T>,
+) -> Option<(&'a T, &'a T)> {
+    match (previous, current) {
+        (Some(previous), Some(current)) if previous != current => Some((previous, current)),
+        _ => None,
+    }
+}
+
+/// 設定サマリーの差分を表示用の文字列にする
+fn describe_settings_drift(previous: &SettingsFingerprint, current: &SettingsFingerprint) -> Vec<String> {
+    let mut diffs = Vec::new();
+    if previous.resolution != current.resolution {
+        diffs.push(format!("解像度 {} → {}", previous.resolution, current.resolution));
+    }
+    if (previous.fps - current.fps).abs() > f64::EPSILON {
+        diffs.push(format!("FPS {} → {}", previous.fps, current.fps));
+    }
+    if previous.encoder != current.encoder {
+        diffs.push(format!("エンコーダー {} → {}", previous.encoder, current.encoder));
+    }
+    if previous.bitrate_kbps != current.bitrate_kbps {
+        diffs.push(format!("ビットレート {}kbps → {}kbps", previous.bitrate_kbps, current.bitrate_kbps));
+    }
+    diffs
+}
+
+/// 2つのスナップショットを比較し、変化を重要度順に返す
+///
+/// どちらかで取得できなかった項目は比較しない
+pub fn diff_environment(previous: &EnvironmentSnapshot, current: &EnvironmentSnapshot) -> Vec<EnvironmentChange> {
+    let mut changes = Vec::new();
+
+    if let Some((before, after)) = changed(previous.obs_version.as_deref(), current.obs_version.as_deref()) {
+        changes.push(EnvironmentChange::new(
+            EnvironmentChangeKind::ObsVersion,
+            ChangeSignificance::High,
+            format!("OBSが{before}から{after}に更新されました。設定を再分析することをおすすめします"),
+            Some(before.to_string()),
+            Some(after.to_string()),
+        ));
+    }
+
+    if previous.cpu_name != current.cpu_name {
+        changes.push(EnvironmentChange::new(
+            EnvironmentChangeKind::Hardware,
+            ChangeSignificance::High,
+            format!("CPUが{}から{}に変わりました", previous.cpu_name, current.cpu_name),
+            Some(previous.cpu_name.clone()),
+            Some(current.cpu_name.clone()),
+        ));
+    }
+
+    let gpu_changed = changed(previous.gpu_name.as_deref(), current.gpu_name.as_deref());
+    if let Some((before, after)) = gpu_changed {
+        changes.push(EnvironmentChange::new(
+            EnvironmentChangeKind::Hardware,
+            ChangeSignificance::High,
+            format!("GPUが{before}から{after}に変わりました"),
+            Some(before.to_string()),
+            Some(after.to_string()),
+        ));
+    }
+
+    // GPU自体が変わった場合はドライバーの変化を個別に報告しない
+    let driver_changed = changed(previous.gpu_driver_version.as_deref(), current.gpu_driver_version.as_deref());
+    if let (None, Some((before, after))) = (gpu_changed, driver_changed) {
+        changes.push(EnvironmentChange::new(
+            EnvironmentChangeKind::GpuDriver,
+            ChangeSignificance::Medium,
+            format!("GPUドライバーが{before}から{after}に更新されました"),
+            Some(before.to_string()),
+            Some(after.to_string()),
+        ));
+    }
+
+    if let Some((before, after)) = changed(previous.settings.as_ref(), current.settings.as_ref()) {
+        let diffs = describe_settings_drift(before, after);
+        if !diffs.is_empty() {
+            changes.push(EnvironmentChange::new(
+                EnvironmentChangeKind::SettingsDrift,
+                ChangeSignificance::Medium,
+                format!("前回終了時からOBSの設定が変更されています（{}）", diffs.join("、")),
+                None,
+                None,
+            ));
+        }
+    }
+
+    // モニター情報が取得できない場合は比較しない
+    if !previous.monitors.is_empty() && !current.monitors.is_empty() {
+        let identities = |monitors: &[MonitorInfo]| monitors.iter().map(MonitorInfo::identity).collect::<Vec<_>>();
+        let previous_ids = identities(&previous.monitors);
+        let current_ids = identities(&current.monitors);
+
+        for monitor in current.monitors.iter().filter(|m| !previous_ids.contains(&m.identity())) {
+            changes.push(EnvironmentChange::new(
+                EnvironmentChangeKind::MonitorAdded,
+                ChangeSignificance::Low,
+                format!("新しいモニターが接続されました: {}", monitor.label()),
+                None,
+                Some(monitor.label()),
+            ));
+        }
+        for monitor in previous.monitors.iter().filter(|m| !current_ids.contains(&m.identity())) {
+            changes.push(EnvironmentChange::new(
+                EnvironmentChangeKind::MonitorRemoved,
+                ChangeSignificance::Low,
+                format!("モニターが取り外されました: {}", monitor.label()),
+                Some(monitor.label()),
+                None,
+            ));
+        }
+    }
+
+    changes
+}
+
+/// 接続中のモニター一覧を取得
+fn collect_monitors<R: Runtime>(app: &AppHandle<R>) -> Vec<MonitorInfo> {
+    match app.available_monitors() {
+        Ok(monitors) => monitors
+            .iter()
+            .map(|monitor| MonitorInfo {
+                name: monitor.name().cloned(),
+                width: monitor.size().width,
+                height: monitor.size().height,
+                scale_factor: monitor.scale_factor(),
+            })
+            .collect(),
+        Err(e) => {
+            tracing::debug!(target: "startup_digest", error = %e, "モニター一覧の取得に失敗");
+            Vec::new()
+        }
+    }
+}
+
+/// 現在の実行環境のスナップショットを取得
+///
+/// OBSに未接続の場合、OBSバージョンと設定はNoneになる
+pub async fn capture_environment<R: Runtime>(app: &AppHandle<R>) -> EnvironmentSnapshot {
+    let gpu = get_gpu_info().await;
+
+    let client = get_obs_client();
+    let (obs_version, settings) = if client.is_connected().await {
+        let obs_version = client.get_status().await.ok().and_then(|status| status.obs_version);
+        let settings = get_obs_settings().await.ok().map(|settings| SettingsFingerprint {
+            resolution: settings.video.resolution_string(),
+            fps: settings.video.fps(),
+            encoder: settings.output.encoder,
+            bitrate_kbps: settings.output.bitrate_kbps,
+        });
+        (obs_version, settings)
+    } else {
+        (None, None)
+    };
+
+    EnvironmentSnapshot {
+        captured_at: chrono::Utc::now().timestamp(),
+        obs_version,
+        cpu_name: get_cpu_name().unwrap_or_else(|_| "Unknown CPU".to_string()),
+        gpu_name: gpu.as_ref().map(|gpu| gpu.name.clone()),
+        gpu_driver_version: gpu.and_then(|gpu| gpu.driver_version),
+        settings,
+        monitors: collect_monitors(app),
+    }
+}
+
+/// 起動時ダイジェストを取得
+///
+/// 前回終了時のスナップショットと現在の環境を比較する。
+/// 初回起動時は変化なしとして返す。
+pub async fn get_startup_digest<R: Runtime>(app: &AppHandle<R>) -> StartupDigest {
+    let previous = match load_environment_snapshot() {
+        Ok(previous) => previous,
+        Err(e) => {
+            tracing::warn!(target: "startup_digest", error = %e, "前回の環境スナップショットの読み込みに失敗");
+            None
+        }
+    };
+
+    let Some(previous) = previous else {
+        return StartupDigest {
+            previous_captured_at: None,
+            changes: Vec::new(),
+        };
+    };
+
+    let current = capture_environment(app).await;
+    StartupDigest {
+        previous_captured_at: Some(previous.captured_at),
+        changes: diff_environment(&previous, &current),
+    }
+}
+
+/// 現在の実行環境をスナップショットとして保存
+///
+/// アプリ終了時に呼び出す。取得できなかった項目は前回の値を引き継ぐ。
+pub async fn save_current_environment<R: Runtime>(app: &AppHandle<R>) {
+    let mut snapshot = capture_environment(app).await;
+    if let Ok(Some(previous)) = load_environment_snapshot() {
+        snapshot.fill_missing_from(&previous);
+    }
+
+    if let Err(e) = save_environment_snapshot(&snapshot) {
+        tracing::warn!(target: "startup_digest", error = %e, "環境スナップショットの保存に失敗");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::environment::test_fixtures::create_snapshot;
+
+    fn kinds(changes: &[EnvironmentChange]) -> Vec<EnvironmentChangeKind> {
+        changes.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let snapshot = create_snapshot();
+        assert!(diff_environment(&snapshot, &snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_obs_version_changed() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.obs_version = Some("30.2.0".to_string());
+
+        let changes = diff_environment(&previous, &current);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::ObsVersion]);
+        assert_eq!(changes[0].significance, ChangeSignificance::High);
+        assert!(changes[0].suggest_reanalysis);
+        assert!(changes[0].message.contains("30.2.0"));
+        assert_eq!(changes[0].previous.as_deref(), Some("30.1.2"));
+    }
+
+    #[test]
+    fn test_unknown_values_are_not_compared() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.obs_version = None;
+        current.settings = None;
+        current.gpu_driver_version = None;
+        current.monitors.clear();
+
+        assert!(diff_environment(&previous, &current).is_empty());
+    }
+
+    #[test]
+    fn test_gpu_driver_changed() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.gpu_driver_version = Some("560.70".to_string());
+
+        let changes = diff_environment(&previous, &current);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::GpuDriver]);
+        assert_eq!(changes[0].significance, ChangeSignificance::Medium);
+    }
+
+    #[test]
+    fn test_gpu_replaced_reports_hardware_only() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.gpu_name = Some("NVIDIA GeForce RTX 5080".to_string());
+        current.gpu_driver_version = Some("572.16".to_string());
+
+        let changes = diff_environment(&previous, &current);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::Hardware]);
+        assert_eq!(changes[0].significance, ChangeSignificance::High);
+    }
+
+    #[test]
+    fn test_cpu_changed() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.cpu_name = "Intel Core i9-14900K".to_string();
+
+        assert_eq!(kinds(&diff_environment(&previous, &current)), vec![EnvironmentChangeKind::Hardware]);
+    }
+
+    #[test]
+    fn test_settings_drift() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        if let Some(settings) = current.settings.as_mut() {
+            settings.bitrate_kbps = 8000;
+            settings.resolution = "1280x720".to_string();
+        }
+
+        let changes = diff_environment(&previous, &current);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::SettingsDrift]);
+        assert!(changes[0].message.contains("8000kbps"));
+        assert!(changes[0].message.contains("1280x720"));
+    }
+
+    #[test]
+    fn test_monitor_added_and_removed() {
+        let previous = create_snapshot();
+        let mut current = create_snapshot();
+        current.monitors.push(MonitorInfo {
+            name: Some("ASUS VG279Q".to_string()),
+            width: 1920,
+            height: 1080,
+            scale_factor: 1.0,
+        });
+
+        let changes = diff_environment(&previous, &current);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::MonitorAdded]);
+        assert_eq!(changes[0].significance, ChangeSignificance::Low);
+        assert!(!changes[0].suggest_reanalysis);
+
+        let changes = diff_environment(&current, &previous);
+        assert_eq!(kinds(&changes), vec![EnvironmentChangeKind::MonitorRemoved]);
+        assert_eq!(changes[0].previous.as_deref(), Some("ASUS VG279Q (1920x1080)"));
+    }
+}
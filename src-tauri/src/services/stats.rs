@@ -0,0 +1,167 @@
+// 統計計算
+//
+// メトリクス履歴（最大3600サンプル）の平均・分散・変動係数を計算する。
+// `std::simd` は安定版Rustでは利用できないため、レーン数分の独立したアキュムレータで
+// 集計し、コンパイラの自動ベクトル化（AVX2: 8レーン、SSE4.1: 4レーン）に任せる。
+// いずれのターゲット機能も有効でない場合は単純なスカラーループで計算する。
+
+/// 同時に集計するレーン数（AVX2）
+#[cfg(target_feature = "avx2")]
+const LANES: usize = 8;
+
+/// 同時に集計するレーン数（SSE4.1）
+#[cfg(all(target_feature = "sse4.1", not(target_feature = "avx2")))]
+const LANES: usize = 4;
+
+/// レーン単位で合計を計算
+#[cfg(any(target_feature = "avx2", target_feature = "sse4.1"))]
+fn lane_sum(data: &[f32], map: impl Fn(f32) -> f32) -> f32 {
+    let mut acc = [0.0_f32; LANES];
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        for (lane, value) in acc.iter_mut().zip(chunk) {
+            *lane += map(*value);
+        }
+    }
+
+    acc.iter().sum::<f32>() + remainder.iter().map(|v| map(*v)).sum::<f32>()
+}
+
+/// 合計を計算（スカラーフォールバック）
+#[cfg(not(any(target_feature = "avx2", target_feature = "sse4.1")))]
+fn lane_sum(data: &[f32], map: impl Fn(f32) -> f32) -> f32 {
+    data.iter().map(|v| map(*v)).sum()
+}
+
+/// 平均値を計算（空の場合は0）
+pub fn mean_f32(data: &[f32]) -> f32 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    lane_sum(data, |v| v) / data.len() as f32
+}
+
+/// 母分散を計算（空の場合は0）
+pub fn variance_f32(data: &[f32]) -> f32 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let mean = mean_f32(data);
+    lane_sum(data, |v| {
+        let diff = v - mean;
+        diff * diff
+    }) / data.len() as f32
+}
+
+/// 標準偏差を計算（空の場合は0）
+pub fn std_dev_f32(data: &[f32]) -> f32 {
+    variance_f32(data).sqrt()
+}
+
+/// 変動係数（標準偏差 / 平均）を計算
+///
+/// 空の場合や平均が0の場合は0を返す
+pub fn coeff_variation_f32(data: &[f32]) -> f32 {
+    let mean = mean_f32(data);
+    if mean == 0.0 {
+        return 0.0;
+    }
+    std_dev_f32(data) / mean
+}
+
+/// 比較用のスカラー実装
+#[cfg(test)]
+mod scalar {
+    pub fn mean_f32(data: &[f32]) -> f32 {
+        if data.is_empty() {
+            return 0.0;
+        }
+        data.iter().sum::<f32>() / data.len() as f32
+    }
+
+    pub fn variance_f32(data: &[f32]) -> f32 {
+        if data.is_empty() {
+            return 0.0;
+        }
+        let mean = mean_f32(data);
+        data.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / data.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1時間分（1秒間隔）のサンプル数
+    const ONE_HOUR_SAMPLES: usize = 3600;
+
+    /// CPU使用率のような 0〜100 の擬似データ
+    fn sample_data(len: usize) -> Vec<f32> {
+        (0..len).map(|i| 50.0 + 40.0 * ((i as f32) * 0.37).sin()).collect()
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        let tolerance = expected.abs().max(1.0) * 1e-4;
+        assert!((actual - expected).abs() <= tolerance, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(mean_f32(&[]), 0.0);
+        assert_eq!(variance_f32(&[]), 0.0);
+        assert_eq!(coeff_variation_f32(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_known_values() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_close(mean_f32(&data), 5.0);
+        assert_close(variance_f32(&data), 4.0);
+        assert_close(std_dev_f32(&data), 2.0);
+        assert_close(coeff_variation_f32(&data), 0.4);
+    }
+
+    #[test]
+    fn test_zero_mean_coeff_variation() {
+        assert_eq!(coeff_variation_f32(&[0.0, 0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_matches_scalar_for_various_lengths() {
+        // レーン数で割り切れない長さ・レーン数未満の長さも含める
+        for len in [1, 3, 7, 8, 9, 31, 100, ONE_HOUR_SAMPLES] {
+            let data = sample_data(len);
+            assert_close(mean_f32(&data), scalar::mean_f32(&data));
+            assert_close(variance_f32(&data), scalar::variance_f32(&data));
+        }
+    }
+
+    /// SIMD版とスカラー版の速度比較
+    ///
+    /// 実行: cargo test --release stats::tests::bench_mean_variance -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_mean_variance() {
+        const ITERATIONS: u32 = 10_000;
+        let data = sample_data(ONE_HOUR_SAMPLES);
+
+        let start = std::time::Instant::now();
+        let mut checksum = 0.0;
+        for _ in 0..ITERATIONS {
+            checksum += variance_f32(std::hint::black_box(&data));
+        }
+        let simd = start.elapsed();
+
+        let start = std::time::Instant::now();
+        for _ in 0..ITERATIONS {
+            checksum -= scalar::variance_f32(std::hint::black_box(&data));
+        }
+        let scalar = start.elapsed();
+
+        println!(
+            "variance x{ITERATIONS} ({ONE_HOUR_SAMPLES}要素): simd={simd:?} scalar={scalar:?} (checksum={checksum})"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// 配信セッションの記録
+//
+// 配信中モードの切り替え（OBSの配信開始・停止）に合わせて履歴DBのセッションを開始・終了し、
+// 監視ティックごとのメトリクスを一定間隔で現在のセッションに保存する。
+// セッションの終了時には、配信先プラットフォームと平均ビットレートを記録する。
+// 月間レポート・配信後レポート・データ通信量・OBSの負荷の記録はこのセッションを参照する。
+
+use crate::error::AppError;
+use crate::obs::recent_output_bitrates;
+use crate::services::obs::obs_service;
+use crate::storage::config::{cached_config, load_config};
+use crate::storage::metrics_history::{
+    get_metrics_history_store, history_store, ObsStatusSnapshot, SystemMetricsSnapshot,
+};
+use once_cell::sync::Lazy;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// メトリクスを履歴DBに保存する間隔
+const METRICS_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 保存間隔を判定する
+#[derive(Debug, Default)]
+pub struct SaveThrottle {
+    /// 前回の保存時刻
+    last_saved: Option<Instant>,
+}
+
+impl SaveThrottle {
+    /// 保存間隔が経過していれば保存時刻を更新してtrueを返す
+    pub fn should_save(&mut self, now: Instant) -> bool {
+        if self
+            .last_saved
+            .is_some_and(|last| now.saturating_duration_since(last) < METRICS_SAVE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_saved = Some(now);
+        true
+    }
+
+    /// セッションの開始時に次のサンプルをすぐ保存できるようにする
+    pub fn reset(&mut self) {
+        self.last_saved = None;
+    }
+}
+
+/// グローバルな保存間隔の判定
+static SAVE_THROTTLE: Lazy<Mutex<SaveThrottle>> = Lazy::new(|| Mutex::new(SaveThrottle::default()));
+
+/// 配信セッションを開始
+///
+/// 既にセッション中の場合は何もしない（配信中モードは繰り返し有効化される場合がある）
+///
+/// # Returns
+/// 開始したセッションのID（既にセッション中の場合はNone）
+pub async fn begin_stream_session() -> Result<Option<String>, AppError> {
+    let store = history_store().await?;
+    if store.current_session_id().await.is_some() {
+        return Ok(None);
+    }
+    let session_id = store.start_session().await?;
+    SAVE_THROTTLE.lock().unwrap_or_else(PoisonError::into_inner).reset();
+    tracing::info!(target: "stream_session", session_id = %session_id, "配信セッションを開始しました");
+    Ok(Some(session_id))
+}
+
+/// 配信セッションを終了
+///
+/// # Returns
+/// 終了したセッションのID（セッション外の場合はNone）
+pub async fn finish_stream_session() -> Result<Option<String>, AppError> {
+    let store = history_store().await?;
+    let Some(session_id) = store.current_session_id().await else {
+        return Ok(None);
+    };
+    store.end_session().await?;
+    tracing::info!(target: "stream_session", session_id = %session_id, "配信セッションを終了しました");
+
+    // メトリクスにビットレートが記録されていない場合は出力統計の実測値を使う
+    let avg_bitrate = match store.average_stream_bitrate(&session_id).await? {
+        Some(bitrate) => bitrate,
+        None => mean_bitrate(&recent_output_bitrates().await).unwrap_or(0),
+    };
+    let platform = load_config()?.streaming_mode.platform;
+    store.update_session_stream_info(&session_id, platform, avg_bitrate).await?;
+    Ok(Some(session_id))
+}
+
+/// ビットレートの平均（サンプルがない場合はNone）
+fn mean_bitrate(samples: &[u64]) -> Option<u64> {
+    if samples.is_empty() {
+        return None;
+    }
+    Some(samples.iter().sum::<u64>() / samples.len() as u64)
+}
+
+/// OBSの状態をスナップショットに変換（取得できない場合は配信中のみを記録）
+async fn current_obs_snapshot() -> ObsStatusSnapshot {
+    match obs_service().get_status().await {
+        Ok(status) => ObsStatusSnapshot::from_obs_status(
+            status.streaming,
+            status.recording,
+            status.fps.map(|fps| fps as f32),
+            status.render_dropped_frames.map(u64::from),
+            status.output_dropped_frames.map(u64::from),
+            status.stream_bitrate.map(u64::from),
+        ),
+        Err(e) => {
+            tracing::debug!(target: "stream_session", error = %e, "OBSの状態を取得できませんでした");
+            ObsStatusSnapshot { streaming: true, ..ObsStatusSnapshot::empty() }
+        }
+    }
+}
+
+/// 監視ティックのメトリクスを現在のセッションに保存
+///
+/// セッション外・履歴の保存が無効な場合・保存間隔が経過していない場合は何もしない。
+/// セッション外の判定はメモリ上のセッションIDだけで行い、履歴DB・設定ファイルにはアクセスしない。
+/// 失敗してもメトリクスの取得は続けられるよう、ログに記録するだけにする
+pub async fn record_session_metrics(system: SystemMetricsSnapshot) {
+    let result = async {
+        // セッションはhistory_store()で初期化したストアでのみ開始されるため、初期化せずに判定できる
+        let store = get_metrics_history_store()?;
+        if store.current_session_id().await.is_none() || !cached_config()?.monitoring.save_metrics_history {
+            return Ok(());
+        }
+        let save = SAVE_THROTTLE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .should_save(Instant::now());
+        if !save {
+            return Ok(());
+        }
+        store.save_metrics(system, current_obs_snapshot().await).await
+    };
+    if let Err(e) = result.await {
+        tracing::warn!(target: "stream_session", error = %e, "セッションのメトリクスの保存に失敗");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_throttle() {
+        let mut throttle = SaveThrottle::default();
+        let start = Instant::now();
+
+        assert!(throttle.should_save(start), "初回はすぐに保存する");
+        assert!(!throttle.should_save(start + Duration::from_secs(1)));
+        assert!(throttle.should_save(start + METRICS_SAVE_INTERVAL));
+
+        throttle.reset();
+        assert!(throttle.should_save(start + METRICS_SAVE_INTERVAL + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_mean_bitrate() {
+        assert_eq!(mean_bitrate(&[]), None);
+        assert_eq!(mean_bitrate(&[5_800, 6_200, 6_000]), Some(6_000));
+    }
+}
@@ -2,12 +2,14 @@
 //
 // 配信中かどうかのフラグを管理し、OBS配信状態と連動する
 // 配信中は通知やアラートの抑制などに使用
+// 仮想カメラも映像設定の変更をブロックするため、同様に有効状態を管理する
 //
 // TOCTOU対策:
 // 設定適用時は acquire_settings_lock() でロックを取得することで、
 // ロック保持中は配信状態の変更をブロックし、一貫した操作を保証する。
 
 use crate::error::AppError;
+use crate::obs::ObsStatus;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
@@ -20,6 +22,8 @@ const SETTINGS_LOCK_TIMEOUT_SECS: u64 = 30;
 pub struct StreamingModeService {
     /// 配信中フラグ（スレッドセーフ）
     is_streaming: Arc<RwLock<bool>>,
+    /// 仮想カメラ有効フラグ
+    virtual_cam_active: Arc<RwLock<bool>>,
     /// 設定変更ロック（TOCTOU対策）
     /// このロックを保持している間は配信状態の変更がブロックされる
     settings_lock: Arc<Mutex<()>>,
@@ -34,6 +38,8 @@ pub struct SettingsLockGuard {
     _guard: OwnedMutexGuard<()>,
     /// 配信中フラグへの参照
     is_streaming: Arc<RwLock<bool>>,
+    /// 仮想カメラ有効フラグへの参照
+    virtual_cam_active: Arc<RwLock<bool>>,
 }
 
 impl SettingsLockGuard {
@@ -45,24 +51,54 @@ impl SettingsLockGuard {
         *is_streaming
     }
 
-    /// 配信中でないことを確認
+    /// ロック保持中に仮想カメラの有効状態をチェック
+    pub async fn is_virtual_cam_active(&self) -> bool {
+        let active = self.virtual_cam_active.read().await;
+        *active
+    }
+
+    /// 配信中・仮想カメラ有効中でないことを確認
     ///
-    /// 配信中の場合はエラーを返す
+    /// どちらかが有効な場合は、原因に応じたメッセージのエラーを返す
     pub async fn ensure_not_streaming(&self) -> Result<(), AppError> {
         if self.is_streaming().await {
-            return Err(AppError::obs_state(
-                "配信中のため設定を変更できません。配信を停止してから再度お試しください。",
-            ));
+            return Err(AppError::obs_output_active(STREAMING_BLOCK_MESSAGE));
+        }
+        if self.is_virtual_cam_active().await {
+            return Err(AppError::obs_output_active(VIRTUAL_CAM_BLOCK_MESSAGE));
         }
         Ok(())
     }
 }
 
+/// 配信中に設定変更を拒否する際のメッセージ
+const STREAMING_BLOCK_MESSAGE: &str =
+    "配信中のため設定を変更できません。配信を停止してから再度お試しください。";
+
+/// 仮想カメラ有効中に設定変更を拒否する際のメッセージ
+const VIRTUAL_CAM_BLOCK_MESSAGE: &str =
+    "仮想カメラが動作中のため映像設定を変更できません。仮想カメラを停止してから再度お試しください。";
+
+/// OBSの現在の出力状態から、設定変更をブロックする出力がないことを確認
+///
+/// フラグはアプリ経由の操作でしか更新されないため、OBS側で直接開始された
+/// 配信・仮想カメラも検出できるよう、適用直前に実際のステータスで確認する
+pub fn ensure_no_blocking_outputs(status: &ObsStatus) -> Result<(), AppError> {
+    if status.streaming {
+        return Err(AppError::obs_output_active(STREAMING_BLOCK_MESSAGE));
+    }
+    if status.virtual_cam_active {
+        return Err(AppError::obs_output_active(VIRTUAL_CAM_BLOCK_MESSAGE));
+    }
+    Ok(())
+}
+
 impl StreamingModeService {
     /// 新しいStreamingModeServiceインスタンスを作成
     pub fn new() -> Self {
         Self {
             is_streaming: Arc::new(RwLock::new(false)),
+            virtual_cam_active: Arc::new(RwLock::new(false)),
             settings_lock: Arc::new(Mutex::new(())),
         }
     }
@@ -90,6 +126,22 @@ impl StreamingModeService {
         *is_streaming
     }
 
+    /// 仮想カメラの有効状態を設定（ロック待機あり）
+    ///
+    /// # Arguments
+    /// * `active` - 仮想カメラが有効な場合はtrue
+    pub async fn set_virtual_cam_active(&self, active: bool) {
+        let _lock = self.settings_lock.lock().await;
+        let mut virtual_cam_active = self.virtual_cam_active.write().await;
+        *virtual_cam_active = active;
+    }
+
+    /// 仮想カメラの有効状態を取得
+    pub async fn is_virtual_cam_active(&self) -> bool {
+        let active = self.virtual_cam_active.read().await;
+        *active
+    }
+
     /// 設定変更ロックを取得（タイムアウト付き）
     ///
     /// このロックを保持している間は、配信状態の変更がブロックされる。
@@ -128,6 +180,7 @@ impl StreamingModeService {
                 Ok(SettingsLockGuard {
                     _guard: guard,
                     is_streaming: self.is_streaming.clone(),
+                    virtual_cam_active: self.virtual_cam_active.clone(),
                 })
             },
             Err(_) => {
@@ -144,7 +197,7 @@ impl StreamingModeService {
 
     /// 配信中でない場合にのみ操作を実行
     ///
-    /// 内部的にロックを取得し、配信中でないことを確認してから操作を実行する。
+    /// 内部的にロックを取得し、配信中・仮想カメラ有効中でないことを確認してから操作を実行する。
     /// 操作完了まで配信状態の変更はブロックされる。
     ///
     /// # Arguments
@@ -156,7 +209,7 @@ impl StreamingModeService {
     /// # Example
     /// ```ignore
     /// let result = service.execute_if_not_streaming(|| async {
-    ///     apply_video_settings(1920, 1080, 60).await
+    ///     apply_video_settings(1920, 1080, 60, 1).await
     /// }).await?;
     /// ```
     pub async fn execute_if_not_streaming<F, Fut, T>(&self, operation: F) -> Result<T, AppError>
@@ -178,6 +231,31 @@ impl StreamingModeService {
 
         result
     }
+
+    /// 配信中かどうかにかかわらず操作を実行（強制適用用）
+    ///
+    /// 設定変更ロックは取得するため、操作中に配信状態が変わることはない。
+    /// 操作には実行開始時点で配信中・仮想カメラ有効中だったかが渡される。
+    /// 呼び出し側で強制適用トークンを検証してから使用すること。
+    pub async fn execute_forced<F, Fut, T>(&self, operation: F) -> Result<T, AppError>
+    where
+        F: FnOnce(bool) -> Fut,
+        Fut: std::future::Future<Output = Result<T, AppError>>,
+    {
+        let guard = self.acquire_settings_lock().await?;
+        let output_active = guard.ensure_not_streaming().await.is_err();
+
+        tracing::warn!(output_active, "配信中チェックを省略して設定操作を強制実行します");
+        let result = operation(output_active).await;
+
+        if result.is_ok() {
+            tracing::info!("強制実行した設定操作が正常に完了しました");
+        } else {
+            tracing::warn!("強制実行した設定操作がエラーで終了しました");
+        }
+
+        result
+    }
 }
 
 impl Default for StreamingModeService {
@@ -374,7 +452,7 @@ mod tests {
         assert!(result.is_err());
 
         let err = result.unwrap_err();
-        assert_eq!(err.code(), "OBS_STATE");
+        assert_eq!(err.code(), "OBS_OUTPUT_ACTIVE");
         assert!(err.message().contains("配信中"));
     }
 
@@ -439,7 +517,21 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.code(), "OBS_STATE");
+        assert_eq!(err.code(), "OBS_OUTPUT_ACTIVE");
+    }
+
+    /// execute_forced が配信中でも操作を実行し、配信状態を渡すことをテスト
+    #[tokio::test]
+    async fn test_execute_forced_runs_while_streaming() {
+        let service = StreamingModeService::new();
+        service.set_streaming_mode(true).await;
+
+        let result = service.execute_forced(|output_active| async move { Ok(output_active) }).await;
+        assert!(result.unwrap_or(false));
+
+        service.set_streaming_mode(false).await;
+        let result = service.execute_forced(|output_active| async move { Ok(output_active) }).await;
+        assert!(!result.unwrap_or(true));
     }
 
     /// execute_if_not_streaming が配信中でない場合に操作を実行することをテスト
@@ -615,4 +707,44 @@ mod tests {
         // タイムアウトになるはず（デッドロック防止）
         assert!(result.is_err());
     }
+
+    // =====================================================================
+    // 仮想カメラ
+    // =====================================================================
+
+    #[tokio::test]
+    async fn test_ensure_not_streaming_when_virtual_cam_active() {
+        let service = StreamingModeService::new();
+        service.set_virtual_cam_active(true).await;
+        assert!(service.is_virtual_cam_active().await);
+
+        let guard = service.acquire_settings_lock().await.unwrap();
+        let err = guard.ensure_not_streaming().await.unwrap_err();
+        assert_eq!(err.code(), "OBS_OUTPUT_ACTIVE");
+        assert!(err.message().contains("仮想カメラ"));
+        drop(guard);
+
+        service.set_virtual_cam_active(false).await;
+        let guard = service.acquire_settings_lock().await.unwrap();
+        assert!(guard.ensure_not_streaming().await.is_ok());
+    }
+
+    #[test]
+    fn test_ensure_no_blocking_outputs() {
+        let idle = ObsStatus { connected: true, ..Default::default() };
+        assert!(ensure_no_blocking_outputs(&idle).is_ok());
+
+        // 録画のみは設定変更をブロックしない
+        let recording = ObsStatus { recording: true, ..idle.clone() };
+        assert!(ensure_no_blocking_outputs(&recording).is_ok());
+
+        let virtual_cam = ObsStatus { virtual_cam_active: true, ..idle.clone() };
+        let err = ensure_no_blocking_outputs(&virtual_cam).unwrap_err();
+        assert!(err.message().contains("仮想カメラ"));
+
+        // 両方有効な場合は配信を優先して案内する
+        let both = ObsStatus { streaming: true, virtual_cam_active: true, ..idle };
+        let err = ensure_no_blocking_outputs(&both).unwrap_err();
+        assert!(err.message().contains("配信中"));
+    }
 }
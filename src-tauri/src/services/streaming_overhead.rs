@@ -0,0 +1,555 @@
+// OBSの負荷（配信のコスト）の集計
+//
+// OBSプロセスのCPU・メモリ使用量とGPU・エンコーダー使用率を1つの指標にまとめ、
+// 監視ティックごとに算出する。配信セッション中は一定間隔で履歴DBに記録する。
+// 推奨設定の適用直前の値を基準値として保存し、適用後に負荷が落ち着いた時点の値と比較して
+// 「この適用でOBSのCPU使用率が18%から9%に減少しました」のように効果を示す。
+
+use crate::error::AppError;
+use crate::monitor::{GpuMetrics, ObsProcessMetrics};
+use crate::storage::metrics_history::history_store;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// 適用前の基準値に使う直近のサンプルの期間
+const BASELINE_WINDOW: Duration = Duration::from_secs(30);
+
+/// 適用後、計測を始めるまでの待ち時間
+///
+/// 出力の再初期化・エンコーダーの切り替え直後の一時的な負荷を含めない
+const SETTLE_DELAY: Duration = Duration::from_secs(15);
+
+/// 適用後の値を平均する期間
+const AFTER_WINDOW: Duration = Duration::from_secs(30);
+
+/// セッション中の履歴DBへの書き込み間隔
+const HISTORY_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// `get_overhead_deltas` で件数を省略した場合の取得件数
+pub const DEFAULT_OVERHEAD_DELTA_LIMIT: u32 = 10;
+
+/// `get_overhead_deltas` で取得できる件数の上限
+pub const MAX_OVERHEAD_DELTA_LIMIT: u32 = 100;
+
+/// 変化とみなす使用率の差の下限（ポイント）
+const MIN_CHANGE_PERCENT: f32 = 1.0;
+
+/// OBSの負荷
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingOverhead {
+    /// OBSプロセスのCPU使用率（0-100%、全コアに対する割合）
+    pub cpu_percent: f32,
+    /// GPU使用率（0-100%、取得できない場合はNone）
+    ///
+    /// プロセス単位のGPU使用率は取得できないため、OBS起動中のGPU全体の使用率で代用する
+    pub gpu_percent: Option<f32>,
+    /// ハードウェアエンコーダーの使用率（0-100%、取得できない場合はNone）
+    pub encoder_percent: Option<f32>,
+    /// OBSプロセスのメモリ使用量（MB）
+    pub memory_mb: f64,
+}
+
+/// セッション履歴に記録したOBSの負荷
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingOverheadSample {
+    /// 計測時刻（UNIX epoch秒）
+    pub timestamp: i64,
+    /// OBSの負荷
+    #[serde(flatten)]
+    pub overhead: StreamingOverhead,
+}
+
+/// 推奨設定の適用前後のOBSの負荷（保存用）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverheadBaseline {
+    /// 基準値のID
+    pub baseline_id: String,
+    /// 適用日時（UNIX epoch秒）
+    pub applied_at: i64,
+    /// 適用前の負荷（直近のサンプルの平均）
+    pub before: StreamingOverhead,
+    /// 適用後の負荷（計測が終わっていない場合はNone）
+    pub after: Option<StreamingOverhead>,
+}
+
+/// 推奨設定の適用前後のOBSの負荷の変化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverheadDelta {
+    /// 基準値のID
+    pub baseline_id: String,
+    /// 適用日時（UNIX epoch秒）
+    pub applied_at: i64,
+    /// 適用前の負荷
+    pub before: StreamingOverhead,
+    /// 適用後の負荷（計測中の場合はNone）
+    pub after: Option<StreamingOverhead>,
+    /// CPU使用率の変化（ポイント、負の値は減少）
+    pub cpu_change: Option<f32>,
+    /// GPU使用率の変化（ポイント）
+    pub gpu_change: Option<f32>,
+    /// エンコーダー使用率の変化（ポイント）
+    pub encoder_change: Option<f32>,
+    /// メモリ使用量の変化（MB）
+    pub memory_change_mb: Option<f64>,
+    /// 変化の説明（計測中の場合はNone）
+    pub summary: Option<String>,
+}
+
+/// OBSプロセスのメトリクスとGPUメトリクスからOBSの負荷を算出
+///
+/// # Arguments
+/// * `process` - OBSプロセスのメトリクス
+/// * `gpu` - GPUメトリクス
+/// * `core_count` - CPUの論理コア数（プロセスのCPU使用率の正規化に使う）
+///
+/// # Returns
+/// OBSの負荷（OBSが起動していない場合はNone）
+pub fn compute_streaming_overhead(
+    process: &ObsProcessMetrics,
+    gpu: Option<&GpuMetrics>,
+    core_count: usize,
+) -> Option<StreamingOverhead> {
+    process.main_process.as_ref()?;
+
+    Some(StreamingOverhead {
+        cpu_percent: (process.total_cpu_usage / core_count.max(1) as f32).clamp(0.0, 100.0),
+        gpu_percent: gpu.map(|gpu| gpu.usage_percent),
+        encoder_percent: gpu.and_then(|gpu| gpu.encoder_usage),
+        memory_mb: process.total_memory_bytes as f64 / (1024.0 * 1024.0),
+    })
+}
+
+/// OBSの負荷の平均（サンプルがない場合はNone）
+///
+/// GPU・エンコーダー使用率は値を取得できたサンプルだけで平均する
+pub fn average_overhead(samples: &[StreamingOverhead]) -> Option<StreamingOverhead> {
+    if samples.is_empty() {
+        return None;
+    }
+    let count = samples.len() as f64;
+    let mean_of = |values: Vec<f32>| -> Option<f32> {
+        (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+    };
+
+    Some(StreamingOverhead {
+        cpu_percent: samples.iter().map(|s| s.cpu_percent).sum::<f32>() / count as f32,
+        gpu_percent: mean_of(samples.iter().filter_map(|s| s.gpu_percent).collect()),
+        encoder_percent: mean_of(samples.iter().filter_map(|s| s.encoder_percent).collect()),
+        memory_mb: samples.iter().map(|s| s.memory_mb).sum::<f64>() / count,
+    })
+}
+
+/// 適用前後の負荷を比較
+pub fn compute_overhead_delta(baseline: &OverheadBaseline) -> OverheadDelta {
+    let before = &baseline.before;
+    let after = baseline.after.as_ref();
+    let change = |before: Option<f32>, after: Option<f32>| Some(after? - before?);
+
+    OverheadDelta {
+        baseline_id: baseline.baseline_id.clone(),
+        applied_at: baseline.applied_at,
+        before: before.clone(),
+        after: after.cloned(),
+        cpu_change: after.map(|after| after.cpu_percent - before.cpu_percent),
+        gpu_change: after.and_then(|after| change(before.gpu_percent, after.gpu_percent)),
+        encoder_change: after.and_then(|after| change(before.encoder_percent, after.encoder_percent)),
+        memory_change_mb: after.map(|after| after.memory_mb - before.memory_mb),
+        summary: after.map(|after| describe_overhead_change(before, after)),
+    }
+}
+
+/// 適用前後の負荷の変化を説明する文を作成
+///
+/// 差が `MIN_CHANGE_PERCENT` 未満の項目は変化なしとして省く
+pub fn describe_overhead_change(before: &StreamingOverhead, after: &StreamingOverhead) -> String {
+    let changes: Vec<String> = [
+        ("CPU使用率", Some(before.cpu_percent), Some(after.cpu_percent)),
+        ("GPU使用率", before.gpu_percent, after.gpu_percent),
+        ("エンコーダー使用率", before.encoder_percent, after.encoder_percent),
+    ]
+    .into_iter()
+    .filter_map(|(label, before, after)| {
+        let (before, after) = (before?, after?);
+        if (after - before).abs() < MIN_CHANGE_PERCENT {
+            return None;
+        }
+        let direction = if after < before { "減少" } else { "増加" };
+        Some(format!("{label}が{before:.0}%から{after:.0}%に{direction}"))
+    })
+    .collect();
+
+    if changes.is_empty() {
+        "適用前後でOBSの負荷はほとんど変わりませんでした".to_string()
+    } else {
+        format!("この適用でOBSの{}しました", changes.join("し、"))
+    }
+}
+
+/// 適用後の負荷の計測
+#[derive(Debug)]
+struct PendingMeasurement {
+    /// 基準値のID
+    baseline_id: String,
+    /// 適用時刻
+    started: Instant,
+    /// 計測期間内のサンプル
+    samples: Vec<StreamingOverhead>,
+}
+
+/// 監視ティックごとのOBSの負荷を保持し、基準値と適用後の値を集計する
+#[derive(Debug, Default)]
+pub struct OverheadTracker {
+    /// 最新のOBSの負荷
+    latest: Option<StreamingOverhead>,
+    /// 基準値に使う直近のサンプル
+    recent: VecDeque<(Instant, StreamingOverhead)>,
+    /// 計測中の適用後の負荷
+    pending: Option<PendingMeasurement>,
+    /// 前回の履歴DBへの書き込み時刻
+    last_flush: Option<Instant>,
+}
+
+impl OverheadTracker {
+    /// 最新のOBSの負荷
+    pub fn latest(&self) -> Option<&StreamingOverhead> {
+        self.latest.as_ref()
+    }
+
+    /// 監視ティックのサンプルを追加
+    ///
+    /// # Arguments
+    /// * `overhead` - OBSの負荷（OBSが起動していない場合はNone）
+    /// * `now` - サンプル時刻
+    ///
+    /// # Returns
+    /// 適用後の計測が終わった場合は基準値のIDと適用後の負荷
+    pub fn add_sample(
+        &mut self,
+        overhead: Option<StreamingOverhead>,
+        now: Instant,
+    ) -> Option<(String, StreamingOverhead)> {
+        self.latest.clone_from(&overhead);
+        while self
+            .recent
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) > BASELINE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+
+        if let Some(overhead) = &overhead {
+            self.recent.push_back((now, overhead.clone()));
+        }
+
+        let pending = self.pending.as_mut()?;
+        let elapsed = now.saturating_duration_since(pending.started);
+        if let Some(overhead) = overhead {
+            if elapsed >= SETTLE_DELAY && elapsed < SETTLE_DELAY + AFTER_WINDOW {
+                pending.samples.push(overhead);
+            }
+        }
+        if elapsed < SETTLE_DELAY + AFTER_WINDOW {
+            return None;
+        }
+
+        // 計測期間中にOBSが起動していなかった場合は適用後の値を記録しない
+        let pending = self.pending.take()?;
+        average_overhead(&pending.samples).map(|after| (pending.baseline_id, after))
+    }
+
+    /// 直近のサンプルの平均（適用前の基準値）
+    pub fn baseline(&self) -> Option<StreamingOverhead> {
+        let samples: Vec<StreamingOverhead> = self.recent.iter().map(|(_, overhead)| overhead.clone()).collect();
+        average_overhead(&samples)
+    }
+
+    /// 適用後の負荷の計測を開始
+    ///
+    /// 計測中の適用があった場合は、その適用後の値は記録しない
+    pub fn start_measurement(&mut self, baseline_id: String, now: Instant) {
+        // 適用前の負荷を適用後の値に混ぜない
+        self.recent.clear();
+        self.pending = Some(PendingMeasurement {
+            baseline_id,
+            started: now,
+            samples: Vec::new(),
+        });
+    }
+
+    /// 履歴DBへの書き込み間隔が経過したか
+    pub fn take_flush(&mut self, now: Instant) -> bool {
+        if self
+            .last_flush
+            .is_some_and(|last| now.saturating_duration_since(last) < HISTORY_FLUSH_INTERVAL)
+        {
+            return false;
+        }
+        self.last_flush = Some(now);
+        true
+    }
+}
+
+/// グローバルなOBSの負荷の集計
+static OVERHEAD_TRACKER: Lazy<Mutex<OverheadTracker>> = Lazy::new(|| Mutex::new(OverheadTracker::default()));
+
+/// 最新のOBSの負荷を取得（監視前・OBSが起動していない場合はNone）
+pub fn latest_streaming_overhead() -> Option<StreamingOverhead> {
+    OVERHEAD_TRACKER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .latest()
+        .cloned()
+}
+
+/// 監視ティックのOBSの負荷を記録
+///
+/// セッション中は書き込み間隔ごとに履歴DBへ保存し、適用後の計測が終わった場合は基準値に記録する。
+/// 失敗してもメトリクスの取得は続けられるよう、ログに記録するだけにする
+pub async fn record_streaming_overhead(overhead: Option<StreamingOverhead>) {
+    let (finished, flush) = {
+        let mut tracker = OVERHEAD_TRACKER.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        let finished = tracker.add_sample(overhead.clone(), now);
+        let flush = overhead.is_some() && tracker.take_flush(now);
+        (finished, flush)
+    };
+    if finished.is_none() && !flush {
+        return;
+    }
+
+    let result = async {
+        let store = history_store().await?;
+        if let Some((baseline_id, after)) = finished {
+            store.set_overhead_after(&baseline_id, after).await?;
+            tracing::info!(target: "streaming_overhead", baseline_id = %baseline_id, "適用後のOBSの負荷を記録しました");
+        }
+        if let (true, Some(overhead)) = (flush, overhead) {
+            if let Some(session_id) = store.current_session_id().await {
+                store
+                    .insert_overhead_sample(session_id, chrono::Utc::now().timestamp(), overhead)
+                    .await?;
+            }
+        }
+        Ok::<(), AppError>(())
+    };
+    if let Err(e) = result.await {
+        tracing::warn!(target: "streaming_overhead", error = %e, "OBSの負荷の記録に失敗");
+    }
+}
+
+/// 推奨設定の適用前の基準値を取得（直近のサンプルがない場合はNone）
+///
+/// 適用に成功した場合だけ `save_overhead_baseline` で保存する
+pub fn capture_overhead_baseline() -> Option<StreamingOverhead> {
+    OVERHEAD_TRACKER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .baseline()
+}
+
+/// 推奨設定の適用前の基準値を保存し、適用後の負荷の計測を開始
+///
+/// 保存に失敗しても適用結果には影響させず、ログに記録するだけにする
+///
+/// # Returns
+/// 基準値のID（保存できなかった場合はNone）
+pub async fn save_overhead_baseline(before: StreamingOverhead) -> Option<String> {
+    let baseline = OverheadBaseline {
+        baseline_id: uuid::Uuid::new_v4().to_string(),
+        applied_at: chrono::Utc::now().timestamp(),
+        before,
+        after: None,
+    };
+    let baseline_id = baseline.baseline_id.clone();
+
+    let result = async { history_store().await?.insert_overhead_baseline(baseline).await };
+    if let Err(e) = result.await {
+        tracing::warn!(target: "streaming_overhead", error = %e, "適用前のOBSの負荷の保存に失敗");
+        return None;
+    }
+
+    OVERHEAD_TRACKER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .start_measurement(baseline_id.clone(), Instant::now());
+    Some(baseline_id)
+}
+
+/// 推奨設定の適用前後の負荷の変化を取得（適用日時の新しい順）
+///
+/// # Arguments
+/// * `limit` - 取得する件数の上限
+pub async fn load_overhead_deltas(limit: u32) -> Result<Vec<OverheadDelta>, AppError> {
+    let baselines = history_store().await?.list_overhead_baselines(limit).await?;
+    Ok(baselines.iter().map(compute_overhead_delta).collect())
+}
+
+/// セッションのOBSの負荷の履歴を取得
+pub async fn load_session_overhead(session_id: &str) -> Result<Vec<StreamingOverheadSample>, AppError> {
+    history_store().await?.get_session_overhead(session_id).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::monitor::process::ProcessMetrics;
+
+    fn overhead(cpu_percent: f32, gpu_percent: Option<f32>) -> StreamingOverhead {
+        StreamingOverhead {
+            cpu_percent,
+            gpu_percent,
+            encoder_percent: None,
+            memory_mb: 500.0,
+        }
+    }
+
+    fn process_metrics(running: bool, total_cpu_usage: f32) -> ObsProcessMetrics {
+        ObsProcessMetrics {
+            main_process: running.then(|| ProcessMetrics {
+                name: "obs64".to_string(),
+                pid: 1,
+                cpu_usage: total_cpu_usage,
+                memory_bytes: 512 * 1024 * 1024,
+                is_running: true,
+            }),
+            total_cpu_usage,
+            total_memory_bytes: 512 * 1024 * 1024,
+            priority: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_streaming_overhead_normalizes_cpu_by_cores() {
+        let gpu = GpuMetrics {
+            name: "GPU".to_string(),
+            index: 0,
+            usage_percent: 40.0,
+            memory_used_bytes: 0,
+            memory_total_bytes: 0,
+            temperature: None,
+            encoder_usage: Some(25.0),
+        };
+        let result = compute_streaming_overhead(&process_metrics(true, 144.0), Some(&gpu), 8).unwrap();
+        assert!((result.cpu_percent - 18.0).abs() < f32::EPSILON);
+        assert_eq!(result.gpu_percent, Some(40.0));
+        assert_eq!(result.encoder_percent, Some(25.0));
+        assert!((result.memory_mb - 512.0).abs() < f64::EPSILON);
+
+        // OBSが起動していない場合は算出しない
+        assert_eq!(compute_streaming_overhead(&process_metrics(false, 0.0), Some(&gpu), 8), None);
+        // コア数が取得できない場合も100%を超えない
+        let result = compute_streaming_overhead(&process_metrics(true, 250.0), None, 0).unwrap();
+        assert!((result.cpu_percent - 100.0).abs() < f32::EPSILON);
+        assert_eq!(result.gpu_percent, None);
+    }
+
+    #[test]
+    fn test_average_overhead_skips_missing_gpu_values() {
+        assert_eq!(average_overhead(&[]), None);
+        let average = average_overhead(&[overhead(10.0, Some(30.0)), overhead(20.0, None)]).unwrap();
+        assert!((average.cpu_percent - 15.0).abs() < f32::EPSILON);
+        assert_eq!(average.gpu_percent, Some(30.0));
+        assert_eq!(average.encoder_percent, None);
+    }
+
+    #[test]
+    fn test_describe_overhead_change() {
+        assert_eq!(
+            describe_overhead_change(&overhead(18.0, Some(40.0)), &overhead(9.0, Some(40.4))),
+            "この適用でOBSのCPU使用率が18%から9%に減少しました"
+        );
+        assert_eq!(
+            describe_overhead_change(&overhead(18.0, Some(40.0)), &overhead(9.0, Some(55.0))),
+            "この適用でOBSのCPU使用率が18%から9%に減少し、GPU使用率が40%から55%に増加しました"
+        );
+        assert_eq!(
+            describe_overhead_change(&overhead(18.0, None), &overhead(18.5, Some(50.0))),
+            "適用前後でOBSの負荷はほとんど変わりませんでした"
+        );
+    }
+
+    #[test]
+    fn test_compute_overhead_delta() {
+        let mut baseline = OverheadBaseline {
+            baseline_id: "b1".to_string(),
+            applied_at: 1_000,
+            before: overhead(18.0, Some(40.0)),
+            after: None,
+        };
+        let pending = compute_overhead_delta(&baseline);
+        assert_eq!(pending.cpu_change, None);
+        assert_eq!(pending.summary, None);
+
+        baseline.after = Some(overhead(9.0, None));
+        let delta = compute_overhead_delta(&baseline);
+        assert_eq!(delta.cpu_change, Some(-9.0));
+        // 片方しか取得できていない項目は比較しない
+        assert_eq!(delta.gpu_change, None);
+        assert_eq!(delta.memory_change_mb, Some(0.0));
+        assert_eq!(delta.summary.as_deref(), Some("この適用でOBSのCPU使用率が18%から9%に減少しました"));
+    }
+
+    #[test]
+    fn test_tracker_baseline_uses_recent_window() {
+        let mut tracker = OverheadTracker::default();
+        let start = Instant::now();
+        assert_eq!(tracker.baseline(), None);
+
+        tracker.add_sample(Some(overhead(30.0, None)), start);
+        tracker.add_sample(Some(overhead(20.0, None)), start + Duration::from_secs(20));
+        tracker.add_sample(Some(overhead(10.0, None)), start + Duration::from_secs(40));
+        // 30秒より前のサンプルは基準値に含めない
+        assert!((tracker.baseline().unwrap().cpu_percent - 15.0).abs() < f32::EPSILON);
+
+        tracker.add_sample(None, start + Duration::from_secs(41));
+        assert_eq!(tracker.latest(), None);
+    }
+
+    #[test]
+    fn test_tracker_measures_after_settle_delay() {
+        let mut tracker = OverheadTracker::default();
+        let start = Instant::now();
+        tracker.add_sample(Some(overhead(18.0, None)), start);
+        tracker.start_measurement("b1".to_string(), start);
+
+        // 適用直後の一時的な負荷は含めない
+        assert_eq!(tracker.add_sample(Some(overhead(50.0, None)), start + Duration::from_secs(5)), None);
+        assert_eq!(tracker.add_sample(Some(overhead(8.0, None)), start + Duration::from_secs(20)), None);
+        assert_eq!(tracker.add_sample(Some(overhead(10.0, None)), start + Duration::from_secs(40)), None);
+
+        let (baseline_id, after) = tracker
+            .add_sample(Some(overhead(9.0, None)), start + SETTLE_DELAY + AFTER_WINDOW)
+            .unwrap();
+        assert_eq!(baseline_id, "b1");
+        assert!((after.cpu_percent - 9.0).abs() < f32::EPSILON);
+
+        // 計測は1回で終わる
+        assert_eq!(tracker.add_sample(Some(overhead(9.0, None)), start + Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn test_tracker_drops_measurement_without_samples() {
+        let mut tracker = OverheadTracker::default();
+        let start = Instant::now();
+        tracker.start_measurement("b1".to_string(), start);
+        assert_eq!(tracker.add_sample(None, start + SETTLE_DELAY + AFTER_WINDOW), None);
+        assert_eq!(tracker.add_sample(Some(overhead(9.0, None)), start + Duration::from_secs(90)), None);
+    }
+
+    #[test]
+    fn test_tracker_flush_interval() {
+        let mut tracker = OverheadTracker::default();
+        let start = Instant::now();
+        assert!(tracker.take_flush(start));
+        assert!(!tracker.take_flush(start + Duration::from_secs(5)));
+        assert!(tracker.take_flush(start + HISTORY_FLUSH_INTERVAL));
+    }
+}
@@ -9,7 +9,10 @@
 // - 将来的なキャッシング、レート制限のフックポイントを提供
 
 use crate::error::AppError;
-use crate::monitor::{self, GpuMetrics, NetworkMetrics, ObsProcessMetrics};
+use crate::monitor::conflicts::SoftwareConflict;
+use crate::monitor::network::NetworkInterfaceInfo;
+use crate::monitor::{self, GpuMetrics, NetworkMetrics, ObsProcessMetrics, ProcessPriority};
+use crate::storage::config::cached_config;
 
 /// システム監視サービスのインスタンス
 ///
@@ -41,6 +44,26 @@ impl SystemMonitorService {
         monitor::get_cpu_usage()
     }
 
+    /// 現在のCPUクロック周波数を取得
+    ///
+    /// 初回の計測値はハードウェア情報キャッシュにベースクロックとして記録される
+    ///
+    /// # Returns
+    /// 全コア平均のクロック周波数（MHz、取得できない場合はNone）
+    pub fn get_cpu_frequency_mhz(&self) -> Result<Option<f64>, AppError> {
+        let current = monitor::get_cpu_frequency_mhz()?;
+        monitor::hardware_cache::record_cpu_frequency(current);
+        Ok(current)
+    }
+
+    /// CPUのベースクロック周波数を取得
+    ///
+    /// # Returns
+    /// 最初に計測したクロック周波数（MHz、未計測の場合はNone）
+    pub fn get_cpu_base_frequency_mhz(&self) -> Option<f64> {
+        monitor::hardware_cache::record_cpu_frequency(None)
+    }
+
     /// メモリ情報を取得
     ///
     /// # Returns
@@ -91,10 +114,26 @@ impl SystemMonitorService {
 
     /// ネットワークメトリクスを取得
     ///
+    /// 設定の優先インターフェース（`monitoring.preferredNetworkInterface`）を計測する。
+    /// 未設定の場合はアップロード速度が最大のインターフェースを計測する。
+    /// 優先インターフェースは設定ファイルが保存されたときだけ読み直す
+    ///
     /// # Returns
-    /// ネットワーク情報（受信/送信バイト数、パケット数など）
+    /// ネットワーク情報（送受信速度、計測したインターフェース）
     pub fn get_network_metrics(&self) -> Result<NetworkMetrics, AppError> {
-        monitor::network::get_network_metrics()
+        let config = cached_config().ok();
+        let preferred = config
+            .as_ref()
+            .and_then(|config| config.monitoring.preferred_network_interface.as_deref());
+        monitor::network::get_network_metrics(preferred)
+    }
+
+    /// ネットワークインターフェースの一覧を取得
+    ///
+    /// # Returns
+    /// インターフェース名と現在の送受信速度（ループバックを除く、名前順）
+    pub fn get_network_interfaces(&self) -> Result<Vec<NetworkInterfaceInfo>, AppError> {
+        monitor::network::get_network_interfaces()
     }
 
     /// OBSプロセスのメトリクスを取得
@@ -105,6 +144,27 @@ impl SystemMonitorService {
         monitor::process::get_obs_process_metrics()
     }
 
+    /// OBSプロセスの情報を優先度付きで取得
+    ///
+    /// # Returns
+    /// OBSプロセスの情報（CPU使用率、メモリ使用量、CPU優先度）
+    pub fn get_obs_process_info(&self) -> Result<ObsProcessMetrics, AppError> {
+        monitor::process::get_obs_process_info()
+    }
+
+    /// 実行中の競合ソフトウェア（オーバーレイ・フックを行う常駐ソフト）を検出
+    pub fn get_software_conflicts(&self) -> Result<Vec<SoftwareConflict>, AppError> {
+        monitor::conflicts::get_software_conflicts()
+    }
+
+    /// OBSプロセスのCPU優先度を変更
+    ///
+    /// # Arguments
+    /// * `priority` - 設定する優先度
+    pub fn set_obs_process_priority(&self, priority: ProcessPriority) -> Result<(), AppError> {
+        monitor::process::set_obs_process_priority(priority)
+    }
+
     /// 包括的なシステムメトリクスを取得（将来使用予定）
     ///
     /// CPU、メモリ、GPU、ネットワークの全情報を一度に取得する
@@ -0,0 +1,397 @@
+// 匿名ハードウェア統計の送信サービス
+//
+// ティア判定テーブルの改善のため、ユーザーが明示的に有効化した場合のみ
+// GPU世代・グレード、CPUティア、配信プラットフォーム、選択中のエンコーダー・プリセットを送信する。
+// 名前・ID・IPアドレスなど個人や機器を特定できる情報は含めない。
+//
+// - 送信は週1回まで（最終送信日時をローカルに記録）
+// - 失敗してもアプリの動作には影響させない（ログのみ）
+// - 送信先はHTTP（`http://`）のみ対応
+
+use super::gpu_detection::{detect_gpu_generation, detect_gpu_grade, determine_cpu_tier, CpuTier, GpuGeneration, GpuGrade};
+use crate::monitor::get_cpu_core_count;
+use crate::monitor::gpu::get_gpu_info;
+use crate::obs::{get_obs_client, get_obs_settings, OutputSettings};
+use crate::storage::config::{load_config, StreamingPlatform};
+use crate::storage::telemetry::{load_telemetry_record, save_telemetry_record, TelemetryRecord};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// 送信データの形式バージョン
+const PAYLOAD_SCHEMA_VERSION: u32 = 1;
+/// 送信間隔（1週間）
+const SEND_INTERVAL_SECS: i64 = 7 * 24 * 60 * 60;
+/// 送信要否の確認間隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 起動直後の処理と重ならないよう、初回確認を遅らせる
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+/// 1回の送信のタイムアウト
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+/// 送信の試行回数
+const SEND_ATTEMPTS: u32 = 3;
+/// 再試行の待機時間（試行ごとに倍増）
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// 送信データ
+///
+/// ここに含まれる項目のみが送信される
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryPayload {
+    /// 送信データの形式バージョン
+    pub schema_version: u32,
+    /// GPU世代
+    pub gpu_generation: GpuGeneration,
+    /// GPUグレード
+    pub gpu_grade: GpuGrade,
+    /// CPUティア
+    pub cpu_tier: CpuTier,
+    /// 配信プラットフォーム
+    pub platform: StreamingPlatform,
+    /// 選択中のエンコーダーID（OBS未接続の場合はNone）
+    pub encoder: Option<String>,
+    /// 選択中のプリセット（OBS未接続の場合はNone）
+    pub preset: Option<String>,
+}
+
+/// 送信内容のプレビュー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryPreview {
+    /// 送信が有効か
+    pub enabled: bool,
+    /// 送信先URL
+    pub endpoint: String,
+    /// 最後に送信した日時（Unixタイムスタンプ）
+    pub last_sent_at: Option<i64>,
+    /// 次回送信可能になる日時（Unixタイムスタンプ、未送信の場合はNone）
+    pub next_send_at: Option<i64>,
+    /// 送信されるデータ
+    pub payload: TelemetryPayload,
+}
+
+/// 送信データを構築
+///
+/// GPU名・CPU名は分類にのみ使用し、送信データには含めない
+///
+/// # Arguments
+/// * `gpu_name` - GPU名（検出できない場合はNone）
+/// * `cpu_cores` - CPUの論理コア数
+/// * `platform` - 配信プラットフォーム
+/// * `output` - OBSの出力設定（OBS未接続の場合はNone）
+pub fn build_telemetry_payload(
+    gpu_name: Option<&str>,
+    cpu_cores: usize,
+    platform: StreamingPlatform,
+    output: Option<&OutputSettings>,
+) -> TelemetryPayload {
+    let (gpu_generation, gpu_grade) = gpu_name.map_or((GpuGeneration::None, GpuGrade::Unknown), |name| {
+        (detect_gpu_generation(name), detect_gpu_grade(name))
+    });
+
+    TelemetryPayload {
+        schema_version: PAYLOAD_SCHEMA_VERSION,
+        gpu_generation,
+        gpu_grade,
+        cpu_tier: determine_cpu_tier(cpu_cores, None),
+        platform,
+        encoder: output.map(|o| o.encoder.clone()),
+        preset: output.and_then(|o| o.preset.clone()),
+    }
+}
+
+/// 送信時期かどうか（前回送信から1週間以上経過、または未送信）
+pub fn is_send_due(last_sent_at: Option<i64>, now: i64) -> bool {
+    last_sent_at.is_none_or(|last| now - last >= SEND_INTERVAL_SECS)
+}
+
+/// 現在の環境から送信データを収集
+pub async fn collect_telemetry_payload() -> TelemetryPayload {
+    let config = load_config().unwrap_or_default();
+    let gpu = get_gpu_info().await;
+    let cpu_cores = get_cpu_core_count().unwrap_or(4);
+    let settings = if get_obs_client().is_connected().await {
+        get_obs_settings().await.ok()
+    } else {
+        None
+    };
+
+    build_telemetry_payload(
+        gpu.as_ref().map(|g| g.name.as_str()),
+        cpu_cores,
+        config.streaming_mode.platform,
+        settings.as_ref().map(|s| &s.output),
+    )
+}
+
+/// 送信内容のプレビューを取得
+pub async fn preview_telemetry() -> TelemetryPreview {
+    let config = load_config().unwrap_or_default();
+    let record = load_telemetry_record().unwrap_or_default();
+
+    TelemetryPreview {
+        enabled: config.telemetry.enabled,
+        endpoint: config.telemetry.endpoint,
+        last_sent_at: record.last_sent_at,
+        next_send_at: record.last_sent_at.map(|last| last + SEND_INTERVAL_SECS),
+        payload: collect_telemetry_payload().await,
+    }
+}
+
+/// 送信先URLを `(ホスト, ポート, パス)` に分解
+fn parse_http_endpoint(endpoint: &str) -> Result<(String, u16, String), String> {
+    let rest = endpoint
+        .trim()
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("HTTPのURLのみ対応しています: {endpoint}"))?;
+    let (authority, path) = rest
+        .split_once('/')
+        .map_or((rest, "/".to_string()), |(authority, path)| (authority, format!("/{path}")));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse::<u16>().map_err(|_| format!("無効なポート番号: {port}"))?),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("ホスト名がありません: {endpoint}"));
+    }
+
+    Ok((host.to_string(), port, path))
+}
+
+/// JSONをPOSTし、2xxの応答を確認
+async fn post_json(endpoint: &str, body: &str) -> Result<(), String> {
+    let (host, port, path) = parse_http_endpoint(endpoint)?;
+    let request = format!(
+        "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let exchange = async {
+        let mut stream = TcpStream::connect((host.as_str(), port)).await?;
+        stream.write_all(request.as_bytes()).await?;
+
+        // ステータス行のみ読み取る
+        let mut response = Vec::new();
+        let mut buf = [0u8; 256];
+        while !response.windows(2).any(|w| w == b"\r\n") && response.len() < 1024 {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            response.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, std::io::Error>(String::from_utf8_lossy(&response).into_owned())
+    };
+
+    let response = tokio::time::timeout(SEND_TIMEOUT, exchange)
+        .await
+        .map_err(|_| "送信がタイムアウトしました".to_string())?
+        .map_err(|e| format!("送信に失敗: {e}"))?;
+
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| "不正な応答".to_string())?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("送信先がステータス{status}を返しました"))
+    }
+}
+
+/// 再試行付きで送信
+///
+/// # Arguments
+/// * `attempts` - 最大試行回数
+/// * `retry_delay` - 1回目の再試行までの待機時間（以降は試行ごとに倍増）
+async fn send_with_retries(endpoint: &str, body: &str, attempts: u32, retry_delay: Duration) -> Result<(), String> {
+    let mut last_error = String::new();
+    for attempt in 1..=attempts {
+        match post_json(endpoint, body).await {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = e,
+        }
+        if attempt < attempts {
+            tokio::time::sleep(retry_delay * 2u32.pow(attempt - 1)).await;
+        }
+    }
+    Err(last_error)
+}
+
+/// 送信時期であれば統計を送信
+///
+/// 無効・送信先未設定・前回送信から1週間未満の場合は何もしない。
+/// 失敗はログに記録するのみで、呼び出し元には伝えない
+pub async fn send_telemetry_if_due() {
+    let Ok(config) = load_config() else {
+        return;
+    };
+    let endpoint = config.telemetry.endpoint.trim();
+    if !config.telemetry.enabled || endpoint.is_empty() {
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let record = load_telemetry_record().unwrap_or_default();
+    if !is_send_due(record.last_sent_at, now) {
+        return;
+    }
+
+    let payload = collect_telemetry_payload().await;
+    let Ok(body) = serde_json::to_string(&payload) else {
+        return;
+    };
+
+    match send_with_retries(endpoint, &body, SEND_ATTEMPTS, RETRY_DELAY).await {
+        Ok(()) => {
+            if let Err(e) = save_telemetry_record(&TelemetryRecord { last_sent_at: Some(now) }) {
+                tracing::debug!(target: "telemetry", error = %e, "送信記録の保存に失敗");
+            }
+            tracing::info!(target: "telemetry", "匿名ハードウェア統計を送信しました");
+        }
+        Err(e) => {
+            tracing::debug!(target: "telemetry", error = %e, "匿名ハードウェア統計の送信に失敗");
+        }
+    }
+}
+
+/// 定期送信を開始
+///
+/// 起動から1分後とその後6時間ごとに送信時期を確認する。
+/// 設定は毎回読み込むため、起動後に有効化した場合も反映される
+pub fn start_telemetry_sender() {
+    tauri::async_runtime::spawn(async {
+        let mut interval =
+            tokio::time::interval_at(tokio::time::Instant::now() + STARTUP_DELAY, CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            send_telemetry_if_due().await;
+        }
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn output() -> OutputSettings {
+        OutputSettings {
+            encoder: "jim_nvenc".to_string(),
+            bitrate_kbps: 6000,
+            keyframe_interval_secs: 2,
+            preset: Some("p5".to_string()),
+            rate_control: Some("CBR".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
+        }
+    }
+
+    /// 指定したステータスを順に返すHTTPサーバーを起動し、受信したリクエストを返す
+    async fn start_server(statuses: Vec<u16>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v1/hardware", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap();
+                requests.push(String::from_utf8_lossy(&buf[..n]).into_owned());
+                let response = format!("HTTP/1.1 {status} OK\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (endpoint, handle)
+    }
+
+    #[test]
+    fn test_payload_contains_no_identifying_fields() {
+        let payload = build_telemetry_payload(
+            Some("NVIDIA GeForce RTX 4070"),
+            16,
+            StreamingPlatform::Twitch,
+            Some(&output()),
+        );
+        assert_eq!(payload.gpu_generation, GpuGeneration::NvidiaAda);
+        assert_eq!(payload.encoder.as_deref(), Some("jim_nvenc"));
+        assert_eq!(payload.preset.as_deref(), Some("p5"));
+
+        let json = serde_json::to_value(&payload).unwrap();
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec!["cpuTier", "encoder", "gpuGeneration", "gpuGrade", "platform", "preset", "schemaVersion"]
+        );
+        // GPU名は分類にのみ使用する
+        assert!(!json.to_string().contains("RTX 4070"));
+    }
+
+    #[test]
+    fn test_payload_without_gpu_or_obs() {
+        let payload = build_telemetry_payload(None, 4, StreamingPlatform::YouTube, None);
+        assert_eq!(payload.gpu_generation, GpuGeneration::None);
+        assert_eq!(payload.gpu_grade, GpuGrade::Unknown);
+        assert!(payload.encoder.is_none());
+        assert!(payload.preset.is_none());
+    }
+
+    #[test]
+    fn test_is_send_due_weekly() {
+        let now = 1_700_000_000;
+        assert!(is_send_due(None, now));
+        assert!(!is_send_due(Some(now - 60), now));
+        assert!(!is_send_due(Some(now - SEND_INTERVAL_SECS + 1), now));
+        assert!(is_send_due(Some(now - SEND_INTERVAL_SECS), now));
+    }
+
+    #[test]
+    fn test_parse_http_endpoint() {
+        assert_eq!(
+            parse_http_endpoint("http://stats.example.com/v1/hardware").unwrap(),
+            ("stats.example.com".to_string(), 80, "/v1/hardware".to_string())
+        );
+        assert_eq!(
+            parse_http_endpoint("http://127.0.0.1:8080").unwrap(),
+            ("127.0.0.1".to_string(), 8080, "/".to_string())
+        );
+        assert!(parse_http_endpoint("https://stats.example.com").is_err());
+        assert!(parse_http_endpoint("http://host:port/").is_err());
+        assert!(parse_http_endpoint("http:///path").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_post_json_sends_payload() {
+        let (endpoint, server) = start_server(vec![204]).await;
+
+        post_json(&endpoint, r#"{"schemaVersion":1}"#).await.unwrap();
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /v1/hardware HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with(r#"{"schemaVersion":1}"#));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retries_recovers_from_server_error() {
+        let (endpoint, server) = start_server(vec![500, 503, 200]).await;
+
+        send_with_retries(&endpoint, "{}", 3, Duration::ZERO).await.unwrap();
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retries_gives_up() {
+        let (endpoint, server) = start_server(vec![500, 500]).await;
+
+        let result = send_with_retries(&endpoint, "{}", 2, Duration::ZERO).await;
+        assert!(result.unwrap_err().contains("500"));
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,383 @@
+// アップグレード提案サービス
+//
+// 検出したハードウェアと回線速度、目標の配信条件（プラットフォーム・スタイル）から、
+// 推奨設定を制限しているボトルネックを特定し、アップグレードによる改善効果を
+// 優先度順に提示する。推奨ロジックと同じ判定（統合ティア・プリセット調整・
+// 目標ビットレート）を使用するため、提案内容は推奨設定の制限理由と一致する。
+
+use super::gpu_detection::{
+    adjust_preset_for_effective_tier, calculate_effective_tier, detect_gpu_generation, detect_gpu_grade,
+    determine_cpu_tier, get_encoder_capability, CpuTier, GpuGeneration, GpuGrade, MemoryTier,
+};
+use super::optimizer::{HardwareInfo, RecommendationEngine};
+use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use serde::{Deserialize, Serialize};
+
+/// ネットワーク速度のうち配信に使用できる割合（推奨ビットレートの安全マージンと同じ）
+const NETWORK_USABLE_RATIO: f64 = 0.8;
+
+/// 回線が主なボトルネックと判断する上り速度（Mbps）
+const SLOW_UPLOAD_MBPS: f64 = 5.0;
+
+/// NVENCの最高画質プリセット番号（P7）
+const MAX_PRESET: u8 = 7;
+
+/// アップグレード対象のコンポーネント
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpgradeComponent {
+    /// GPU
+    Gpu,
+    /// CPU
+    Cpu,
+    /// メモリ
+    Memory,
+    /// ネットワーク（上り回線）
+    Network,
+}
+
+impl UpgradeComponent {
+    /// 表示用ラベルを取得
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::Gpu => "GPU",
+            Self::Cpu => "CPU",
+            Self::Memory => "メモリ",
+            Self::Network => "ネットワーク",
+        }
+    }
+}
+
+/// アップグレードによる改善効果の大きさ（優先度順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UpgradeImpact {
+    /// 大きい（推奨設定を大きく制限している）
+    High,
+    /// 中程度
+    Medium,
+    /// 小さい（余裕が少ない程度）
+    Low,
+}
+
+impl UpgradeImpact {
+    /// 表示用ラベルを取得
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::High => "大",
+            Self::Medium => "中",
+            Self::Low => "小",
+        }
+    }
+}
+
+/// アップグレード提案
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeAdvice {
+    /// 対象コンポーネント
+    pub component: UpgradeComponent,
+    /// 現在の構成（例: "NVIDIA GeForce GTX 1060（Pascal・Tier D）"）
+    pub current: String,
+    /// 現在の構成による制限
+    pub limitation: String,
+    /// アップグレードで期待できる改善
+    pub expected_gain: String,
+    /// 改善効果の大きさ
+    pub impact: UpgradeImpact,
+}
+
+/// ハードウェアと回線速度からアップグレード提案を作成
+///
+/// 改善効果の大きい順に並べて返す。ボトルネックがない場合は空
+///
+/// # Arguments
+/// * `hardware` - ハードウェア情報
+/// * `platform` - 目標の配信プラットフォーム
+/// * `style` - 目標の配信スタイル
+/// * `network_speed_mbps` - 上り回線速度（Mbps）
+pub fn advise_upgrades(
+    hardware: &HardwareInfo,
+    platform: StreamingPlatform,
+    style: StreamingStyle,
+    network_speed_mbps: f64,
+) -> Vec<UpgradeAdvice> {
+    let (generation, grade) = hardware.gpu.as_ref().map_or(
+        (GpuGeneration::None, GpuGrade::Unknown),
+        |gpu| (detect_gpu_generation(&gpu.name), detect_gpu_grade(&gpu.name)),
+    );
+    let has_hardware_encoder = get_encoder_capability(generation).is_some();
+
+    let mut advice: Vec<UpgradeAdvice> = [
+        gpu_advice(hardware, generation, grade),
+        cpu_advice(hardware, has_hardware_encoder),
+        memory_advice(hardware),
+        network_advice(platform, style, network_speed_mbps),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    // 同じ効果の場合はGPU → CPU → メモリ → ネットワークの順を維持する
+    advice.sort_by_key(|entry| entry.impact);
+    advice
+}
+
+/// GPUのアップグレード提案
+fn gpu_advice(hardware: &HardwareInfo, generation: GpuGeneration, grade: GpuGrade) -> Option<UpgradeAdvice> {
+    let tier = calculate_effective_tier(generation, grade);
+    let penalty = MAX_PRESET - adjust_preset_for_effective_tier(MAX_PRESET, tier);
+    let current = hardware.gpu.as_ref().map_or_else(
+        || "GPUなし".to_string(),
+        |gpu| format!("{}（{}・Tier {}）", gpu.name, generation.display_label(), tier.letter()),
+    );
+
+    let (limitation, expected_gain, impact) = match generation {
+        GpuGeneration::None | GpuGeneration::Unknown => (
+            "ハードウェアエンコーダーが使えないため、CPUでエンコードする必要があります".to_string(),
+            "NVENC対応GPU（RTX 20シリーズ以降）等を追加すると、CPU負荷を大幅に減らしてゲームと配信を両立できます".to_string(),
+            UpgradeImpact::High,
+        ),
+        GpuGeneration::NvidiaPascal => (
+            format!("旧世代のNVENCのため画質が低く、プリセットが{penalty}段階下がります"),
+            format!("Turing以降（RTX 20シリーズ以降）のGPUでNVENCの画質が向上し、プリセットの{penalty}段階の引き下げが解消されます"),
+            UpgradeImpact::High,
+        ),
+        GpuGeneration::IntelQuickSync => (
+            format!("内蔵GPUのQuickSyncは画質と処理能力に限りがあり、プリセットが{penalty}段階下がります"),
+            "専用GPU（RTX 40シリーズ・Intel Arc等）でエンコード品質と負荷の余裕が大きく改善します".to_string(),
+            UpgradeImpact::High,
+        ),
+        _ if penalty == 0 => return None,
+        _ => (
+            format!("GPUの性能に余裕がなく、プリセットが{penalty}段階下がります"),
+            "Tier A以上のGPU（RTX 4070以上等）で最高画質のプリセットを使用できます".to_string(),
+            if penalty >= 2 { UpgradeImpact::Medium } else { UpgradeImpact::Low },
+        ),
+    };
+
+    Some(UpgradeAdvice {
+        component: UpgradeComponent::Gpu,
+        current,
+        limitation,
+        expected_gain,
+        impact,
+    })
+}
+
+/// CPUのアップグレード提案
+///
+/// ハードウェアエンコーダーがある場合、CPUはゲームとOBSの合成処理のみを担うため
+/// エントリークラスのみ提案する
+fn cpu_advice(hardware: &HardwareInfo, has_hardware_encoder: bool) -> Option<UpgradeAdvice> {
+    let tier = determine_cpu_tier(hardware.cpu_cores, hardware.benchmark_result);
+    let (limitation, impact) = match (has_hardware_encoder, tier) {
+        (false, CpuTier::Entry | CpuTier::Middle) => (
+            "CPUエンコード（x264）にはコア数が不足しており、軽量なプリセットと低い解像度に制限されます",
+            UpgradeImpact::High,
+        ),
+        (false, CpuTier::UpperMiddle) => (
+            "CPUエンコード（x264）でゲームと配信を両立するには余裕が少なく、プリセットが制限されます",
+            UpgradeImpact::Medium,
+        ),
+        (true, CpuTier::Entry) => (
+            "コア数が少なく、ゲームとOBSの合成処理を同時に行うとフレーム落ちしやすくなります",
+            UpgradeImpact::Medium,
+        ),
+        _ => return None,
+    };
+
+    Some(UpgradeAdvice {
+        component: UpgradeComponent::Cpu,
+        current: format!("{}（{}コア・{}）", hardware.cpu_name, hardware.cpu_cores, tier.display_label()),
+        limitation: limitation.to_string(),
+        expected_gain: "8コア以上のCPUで、配信中もゲームのフレームレートを維持しやすくなります".to_string(),
+        impact,
+    })
+}
+
+/// メモリのアップグレード提案
+fn memory_advice(hardware: &HardwareInfo) -> Option<UpgradeAdvice> {
+    let tier = MemoryTier::from_gb(hardware.total_memory_gb);
+    let (limitation, impact) = match tier {
+        MemoryTier::Entry => ("メモリが8GB未満のため、720pでの配信が推奨されます", UpgradeImpact::Medium),
+        MemoryTier::Standard => (
+            "ゲームと配信を同時に行うと1080p60では余裕が少なくなります",
+            UpgradeImpact::Low,
+        ),
+        MemoryTier::Adequate | MemoryTier::Abundant => return None,
+    };
+
+    Some(UpgradeAdvice {
+        component: UpgradeComponent::Memory,
+        current: format!("{:.0}GB（{}）", hardware.total_memory_gb, tier.display_label()),
+        limitation: limitation.to_string(),
+        expected_gain: "16GB以上に増設すると、1080p60の配信とゲームを余裕を持って両立できます".to_string(),
+        impact,
+    })
+}
+
+/// ネットワークのアップグレード提案
+///
+/// 推奨ビットレートと同じく回線速度の80%を上限とし、目標ビットレートに届かない場合に提案する
+fn network_advice(
+    platform: StreamingPlatform,
+    style: StreamingStyle,
+    network_speed_mbps: f64,
+) -> Option<UpgradeAdvice> {
+    let target_kbps = RecommendationEngine::target_bitrate_kbps(platform, style);
+    let usable_kbps = (network_speed_mbps * 1000.0 * NETWORK_USABLE_RATIO) as u32;
+    if usable_kbps >= target_kbps {
+        return None;
+    }
+
+    let required_mbps = f64::from(target_kbps) / 1000.0 / NETWORK_USABLE_RATIO;
+    let (limitation, impact) = if network_speed_mbps < SLOW_UPLOAD_MBPS {
+        (
+            format!(
+                "回線が主なボトルネックです。ハードウェアに関わらず、ビットレートは{usable_kbps}kbps程度に制限されます"
+            ),
+            UpgradeImpact::High,
+        )
+    } else {
+        (
+            format!("目標の{target_kbps}kbpsに対して、ビットレートが{usable_kbps}kbps程度に制限されます"),
+            UpgradeImpact::Medium,
+        )
+    };
+
+    Some(UpgradeAdvice {
+        component: UpgradeComponent::Network,
+        current: format!("上り{network_speed_mbps:.1}Mbps"),
+        limitation,
+        expected_gain: format!("上り{required_mbps:.1}Mbps以上の回線で、目標の{target_kbps}kbpsで配信できます"),
+        impact,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::monitor::gpu::GpuInfo;
+    use crate::monitor::power::PowerStatus;
+
+    fn hardware(gpu_name: Option<&str>, cpu_cores: usize, total_memory_gb: f64) -> HardwareInfo {
+        HardwareInfo {
+            cpu_name: "Test CPU".to_string(),
+            cpu_cores,
+            total_memory_gb,
+            gpu: gpu_name.map(|name| GpuInfo {
+                name: name.to_string(),
+                driver_version: None,
+            }),
+            power: PowerStatus::default(),
+            displays: Vec::new(),
+            benchmark_result: None,
+        }
+    }
+
+    fn components(advice: &[UpgradeAdvice]) -> Vec<UpgradeComponent> {
+        advice.iter().map(|entry| entry.component).collect()
+    }
+
+    #[test]
+    fn test_good_gpu_bad_network_suggests_network_only() {
+        let advice = advise_upgrades(
+            &hardware(Some("NVIDIA GeForce RTX 4080"), 16, 32.0),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            4.0,
+        );
+
+        assert_eq!(components(&advice), vec![UpgradeComponent::Network]);
+        assert_eq!(advice[0].impact, UpgradeImpact::High);
+        assert_eq!(advice[0].current, "上り4.0Mbps");
+        assert!(advice[0].limitation.contains("主なボトルネック"));
+        assert!(advice[0].limitation.contains("3200kbps"));
+        assert!(advice[0].expected_gain.contains("6000kbps"));
+    }
+
+    #[test]
+    fn test_bad_gpu_good_network_suggests_gpu_first() {
+        let advice = advise_upgrades(
+            &hardware(Some("NVIDIA GeForce GTX 1060"), 8, 16.0),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Gaming,
+            50.0,
+        );
+
+        assert_eq!(components(&advice), vec![UpgradeComponent::Gpu]);
+        let gpu = &advice[0];
+        assert_eq!(gpu.impact, UpgradeImpact::High);
+        assert!(gpu.current.contains("Pascal"));
+        assert!(gpu.current.contains("Tier D"));
+        assert!(gpu.limitation.contains("2段階"));
+        assert!(gpu.expected_gain.contains("Turing"));
+    }
+
+    #[test]
+    fn test_balanced_low_end_suggests_all_components_by_impact() {
+        let advice = advise_upgrades(
+            &hardware(None, 4, 6.0),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            3.0,
+        );
+
+        assert_eq!(
+            components(&advice),
+            vec![
+                UpgradeComponent::Gpu,
+                UpgradeComponent::Cpu,
+                UpgradeComponent::Network,
+                UpgradeComponent::Memory,
+            ]
+        );
+        assert!(advice.windows(2).all(|pair| pair[0].impact <= pair[1].impact));
+        assert_eq!(advice[0].current, "GPUなし");
+    }
+
+    #[test]
+    fn test_mid_tier_gpu_with_moderate_network_is_medium_or_low() {
+        // RTX 3060（Ampere・Tier B）はプリセット1段階下げ、10Mbpsは目標に届かない
+        let advice = advise_upgrades(
+            &hardware(Some("NVIDIA GeForce RTX 3060"), 8, 16.0),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            10.0,
+        );
+
+        assert_eq!(components(&advice), vec![UpgradeComponent::Network, UpgradeComponent::Gpu]);
+        assert_eq!(advice[0].impact, UpgradeImpact::Medium);
+        assert_eq!(advice[1].impact, UpgradeImpact::Low);
+        assert!(advice[1].limitation.contains("1段階"));
+    }
+
+    #[test]
+    fn test_high_end_setup_has_no_advice() {
+        let advice = advise_upgrades(
+            &hardware(Some("NVIDIA GeForce RTX 4090"), 16, 64.0),
+            StreamingPlatform::YouTube,
+            StreamingStyle::Gaming,
+            100.0,
+        );
+
+        assert!(advice.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_advice_serialization() {
+        let advice = advise_upgrades(
+            &hardware(None, 16, 32.0),
+            StreamingPlatform::Twitch,
+            StreamingStyle::Talk,
+            50.0,
+        );
+        let json = serde_json::to_value(&advice[0]).unwrap();
+
+        assert_eq!(json["component"], "gpu");
+        assert_eq!(json["impact"], "high");
+        assert!(json["expectedGain"].is_string());
+    }
+}
@@ -0,0 +1,182 @@
+// ZIPアーカイブ書き出し
+//
+// デバッグバンドルなど、複数ファイルを1つにまとめてユーザーに送ってもらう用途で使用する。
+// 外部クレートに依存しないよう、無圧縮（STORED）形式のみをサポートする。
+
+use crate::error::AppError;
+use std::path::Path;
+
+/// ローカルファイルヘッダーのシグネチャ
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+/// セントラルディレクトリヘッダーのシグネチャ
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0201_4b50;
+/// セントラルディレクトリ終端レコードのシグネチャ
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// 展開に必要なバージョン（2.0）
+const VERSION_NEEDED: u16 = 20;
+/// 汎用フラグ（ファイル名がUTF-8）
+const FLAG_UTF8: u16 = 1 << 11;
+/// DOS形式の日付（1980-01-01）
+const DOS_DATE_EPOCH: u16 = (1 << 5) | 1;
+
+/// アーカイブに含めるエントリ
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// アーカイブ内のファイル名
+    pub name: String,
+    /// ファイル内容
+    pub data: Vec<u8>,
+}
+
+impl ArchiveEntry {
+    /// 新しいエントリを作成
+    pub fn new(name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            data,
+        }
+    }
+}
+
+/// CRC-32（IEEE 802.3）を計算
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// エントリをZIP形式のバイト列に変換
+///
+/// 4GBを超えるエントリ・65535件を超えるエントリ数はサポートしない（ZIP64非対応）
+pub fn build_zip(entries: &[ArchiveEntry]) -> Result<Vec<u8>, AppError> {
+    let too_large = || AppError::export_error("アーカイブのサイズが上限を超えています");
+    let entry_count = u16::try_from(entries.len()).map_err(|_| too_large())?;
+
+    let mut output = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for entry in entries {
+        let name = entry.name.as_bytes();
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large())?;
+        let size = u32::try_from(entry.data.len()).map_err(|_| too_large())?;
+        let offset = u32::try_from(output.len()).map_err(|_| too_large())?;
+        let crc = crc32(&entry.data);
+
+        // ローカルファイルヘッダー
+        output.extend_from_slice(&LOCAL_FILE_HEADER_SIGNATURE.to_le_bytes());
+        output.extend_from_slice(&VERSION_NEEDED.to_le_bytes());
+        output.extend_from_slice(&FLAG_UTF8.to_le_bytes());
+        output.extend_from_slice(&0u16.to_le_bytes()); // 圧縮方式: STORED
+        output.extend_from_slice(&0u16.to_le_bytes()); // 最終更新時刻
+        output.extend_from_slice(&DOS_DATE_EPOCH.to_le_bytes());
+        output.extend_from_slice(&crc.to_le_bytes());
+        output.extend_from_slice(&size.to_le_bytes());
+        output.extend_from_slice(&size.to_le_bytes());
+        output.extend_from_slice(&name_len.to_le_bytes());
+        output.extend_from_slice(&0u16.to_le_bytes()); // 拡張フィールド長
+        output.extend_from_slice(name);
+        output.extend_from_slice(&entry.data);
+
+        // セントラルディレクトリヘッダー
+        central_directory.extend_from_slice(&CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&VERSION_NEEDED.to_le_bytes()); // 作成バージョン
+        central_directory.extend_from_slice(&VERSION_NEEDED.to_le_bytes());
+        central_directory.extend_from_slice(&FLAG_UTF8.to_le_bytes());
+        central_directory.extend_from_slice(&0u16.to_le_bytes());
+        central_directory.extend_from_slice(&0u16.to_le_bytes());
+        central_directory.extend_from_slice(&DOS_DATE_EPOCH.to_le_bytes());
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&name_len.to_le_bytes());
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // 拡張フィールド長
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // コメント長
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // ディスク番号
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // 内部属性
+        central_directory.extend_from_slice(&0u32.to_le_bytes()); // 外部属性
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name);
+    }
+
+    let directory_offset = u32::try_from(output.len()).map_err(|_| too_large())?;
+    let directory_size = u32::try_from(central_directory.len()).map_err(|_| too_large())?;
+    output.extend_from_slice(&central_directory);
+
+    // セントラルディレクトリ終端レコード
+    output.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+    output.extend_from_slice(&0u16.to_le_bytes()); // ディスク番号
+    output.extend_from_slice(&0u16.to_le_bytes()); // セントラルディレクトリ開始ディスク
+    output.extend_from_slice(&entry_count.to_le_bytes());
+    output.extend_from_slice(&entry_count.to_le_bytes());
+    output.extend_from_slice(&directory_size.to_le_bytes());
+    output.extend_from_slice(&directory_offset.to_le_bytes());
+    output.extend_from_slice(&0u16.to_le_bytes()); // コメント長
+
+    Ok(output)
+}
+
+/// エントリをZIPファイルとして書き出す
+pub fn write_zip(path: &Path, entries: &[ArchiveEntry]) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, build_zip(entries)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+    }
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_build_zip_layout() {
+        let entries = vec![
+            ArchiveEntry::new("a.txt", b"hello".to_vec()),
+            ArchiveEntry::new("dir/b.json", b"{}".to_vec()),
+        ];
+        let zip = build_zip(&entries).unwrap();
+
+        // 先頭はローカルファイルヘッダー
+        assert_eq!(read_u32(&zip, 0), LOCAL_FILE_HEADER_SIGNATURE);
+        assert_eq!(read_u32(&zip, 14), crc32(b"hello"));
+        assert_eq!(read_u32(&zip, 18), 5);
+        assert_eq!(&zip[30..35], b"a.txt");
+        assert_eq!(&zip[35..40], b"hello");
+
+        // 末尾22バイトがセントラルディレクトリ終端レコード
+        let eocd = zip.len() - 22;
+        assert_eq!(read_u32(&zip, eocd), END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        assert_eq!(read_u16(&zip, eocd + 10), 2);
+
+        let directory_offset = read_u32(&zip, eocd + 16) as usize;
+        assert_eq!(read_u32(&zip, directory_offset), CENTRAL_DIRECTORY_SIGNATURE);
+    }
+
+    #[test]
+    fn test_empty_archive() {
+        let zip = build_zip(&[]).unwrap();
+        assert_eq!(zip.len(), 22);
+        assert_eq!(read_u32(&zip, 0), END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+    }
+}
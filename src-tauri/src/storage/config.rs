@@ -4,12 +4,55 @@
 // デフォルト値を提供し、存在しない場合は自動作成
 
 use crate::error::AppError;
+use crate::monitor::process::DEFAULT_GAME_EXECUTABLES;
+use crate::monitor::ProcessPriority;
+use crate::services::alerts::{default_suppression_rules, AlertSuppressionRule, MetricType};
+use crate::services::shortcuts::{Accelerator, ShortcutAction};
+use crate::storage::paths::app_config_dir;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 
-const APP_NAME: &str = "obs-optimizer";
 const CONFIG_FILE_NAME: &str = "config.json";
 
+/// 設定ファイルの保存回数
+///
+/// 設定の一部を保持するバックグラウンドタスクが、毎回ファイルを読み直さずに変更を検知するために使用する
+static CONFIG_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// 設定ファイルの保存回数を取得（保存するたびに増える）
+pub fn config_revision() -> u64 {
+    CONFIG_REVISION.load(Ordering::Acquire)
+}
+
+/// 設定の保存先が変わったことを通知し、保存回数で設定を保持しているキャッシュに読み直させる
+#[cfg(any(test, feature = "testing"))]
+pub(crate) fn notify_config_location_changed() {
+    CONFIG_REVISION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// 読み込んだ時点の保存回数と設定
+static CACHED_CONFIG: Lazy<Mutex<Option<(u64, Arc<AppConfig>)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 設定を取得（前回の読み込みから保存されていなければファイルを読まない）
+///
+/// 監視ティックごとなど、頻繁に設定を参照する処理で使用する
+pub fn cached_config() -> Result<Arc<AppConfig>, AppError> {
+    let revision = config_revision();
+    let mut cache = CACHED_CONFIG.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((cached, config)) = cache.as_ref() {
+        if *cached == revision {
+            return Ok(Arc::clone(config));
+        }
+    }
+
+    let config = Arc::new(load_config()?);
+    *cache = Some((revision, Arc::clone(&config)));
+    Ok(config)
+}
+
 /// アプリケーション設定全体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +69,55 @@ pub struct AppConfig {
     pub display: DisplayConfig,
     /// 配信モード設定
     pub streaming_mode: StreamingModeConfig,
+    /// OBSプロセスのCPU優先度
+    #[serde(default)]
+    pub obs_process_priority: ProcessPriority,
+    /// ゲーム検出に使用する実行ファイル名の一覧
+    #[serde(default = "default_known_game_executables")]
+    pub known_game_executables: Vec<String>,
+    /// 監視対象のマイク入力名（未設定の場合は自動検出）
+    #[serde(default)]
+    pub microphone_input_name: Option<String>,
+    /// デバッグ記録設定
+    #[serde(default)]
+    pub debug_recording: DebugRecordingConfig,
+    /// 匿名ハードウェア統計の送信設定（オプトイン）
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// 配信開始時に表示されている必要があるシーンアイテム
+    #[serde(default)]
+    pub required_scene_items: Vec<RequiredSceneItem>,
+    /// 月間のアップロード量の上限（GB、モバイル回線等で上限がある場合のみ設定）
+    #[serde(default)]
+    pub monthly_quota_gb: Option<f64>,
+    /// 上限の集計を手動でリセットした日時（UNIX epoch秒）
+    ///
+    /// 請求サイクルが月の途中で切り替わる場合に、これより前に始まったセッションを集計から除く
+    #[serde(default)]
+    pub quota_reset_at: Option<i64>,
+    /// 非表示にしたシーン構成チェックのルールID
+    #[serde(default)]
+    pub dismissed_checklist_rules: Vec<String>,
+    /// 音声モニタリング設定
+    #[serde(default)]
+    pub audio_monitoring: AudioMonitoringConfig,
+    /// 自動化ルール（「Xが起きたらYを行う」）
+    #[serde(default)]
+    pub automation_rules: Vec<AutomationRule>,
+    /// 推奨設定の適用前に、OBSのプロファイル・シーンコレクションのファイルもバックアップするか
+    #[serde(default)]
+    pub backup_obs_files: bool,
+    /// オブザーバーモード（モデレーター向けの読み取り専用HTTPサーバー）の設定
+    #[serde(default)]
+    pub observer: ObserverConfig,
+    /// メディアソースの再生終了時の自動アクション（メディアソース名, アクション）
+    #[serde(default)]
+    pub media_autoactions: Vec<(String, MediaAutoAction)>,
+}
+
+/// ゲーム実行ファイル名一覧の初期値
+fn default_known_game_executables() -> Vec<String> {
+    DEFAULT_GAME_EXECUTABLES.iter().map(ToString::to_string).collect()
 }
 
 /// OBS接続設定
@@ -40,7 +132,7 @@ pub struct ConnectionConfig {
     pub save_password: bool,
     /// 起動時に自動接続するか
     pub auto_connect_on_startup: bool,
-    /// 接続タイムアウト（秒）
+    /// 接続・OBSへの各リクエストのタイムアウト（秒）
     pub connection_timeout_secs: u64,
     /// 【移行用】旧プレーンテキストパスワード
     /// 読み込み時に検出された場合、キーリングに移行して削除
@@ -92,12 +184,23 @@ pub struct MonitoringConfig {
     pub update_interval_ms: u64,
     /// システムメトリクスを収集するか
     pub collect_system_metrics: bool,
-    /// GPUメトリクスを収集するか（NVIDIA専用）
+    /// GPUメトリクスを収集するか（NVIDIA・AMD・Intel）
     pub collect_gpu_metrics: bool,
     /// OBSプロセスメトリクスを収集するか
     pub collect_process_metrics: bool,
     /// メトリクス履歴を保存するか
     pub save_metrics_history: bool,
+    /// OBS接続のヘルスチェック（ping）間隔（秒）
+    #[serde(default = "default_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    /// 帯域を計測するネットワークインターフェース名（未設定の場合はアップロード速度が最大のもの）
+    #[serde(default)]
+    pub preferred_network_interface: Option<String>,
+}
+
+/// ヘルスチェック間隔の初期値
+const fn default_health_check_interval_secs() -> u64 {
+    30
 }
 
 impl Default for MonitoringConfig {
@@ -108,6 +211,8 @@ impl Default for MonitoringConfig {
             collect_gpu_metrics: true,
             collect_process_metrics: true,
             save_metrics_history: true,
+            health_check_interval_secs: default_health_check_interval_secs(),
+            preferred_network_interface: None,
         }
     }
 }
@@ -136,6 +241,25 @@ pub struct AlertConfig {
     pub play_sound: bool,
     /// デスクトップ通知を表示するか
     pub show_notification: bool,
+    /// 配信中にマイクがミュート・無音のままの場合にアラートを出すまでの秒数
+    #[serde(default = "default_mic_silence_alert_secs")]
+    pub mic_silence_alert_secs: u64,
+    /// マイクを無音とみなす音量の下限（dB）
+    #[serde(default = "default_mic_silence_floor_db")]
+    pub mic_silence_floor_db: f64,
+    /// シーン切り替え・録画開始など、負荷の急上昇が想定される操作の間アラートを抑制するルール
+    #[serde(default = "default_suppression_rules")]
+    pub suppression_rules: Vec<AlertSuppressionRule>,
+}
+
+/// マイク無音アラートまでの秒数の初期値
+const fn default_mic_silence_alert_secs() -> u64 {
+    30
+}
+
+/// マイク無音判定の下限音量の初期値
+const fn default_mic_silence_floor_db() -> f64 {
+    -60.0
 }
 
 impl Default for AlertConfig {
@@ -151,10 +275,261 @@ impl Default for AlertConfig {
             alert_duration_secs: 5,
             play_sound: true,
             show_notification: true,
+            mic_silence_alert_secs: default_mic_silence_alert_secs(),
+            mic_silence_floor_db: default_mic_silence_floor_db(),
+            suppression_rules: default_suppression_rules(),
+        }
+    }
+}
+
+/// デバッグ記録設定
+///
+/// 不定期に発生する問題の調査用に、監視・イベント・コマンドの記録をJSONLで保存する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugRecordingConfig {
+    /// デバッグ記録を有効にするか
+    pub enabled: bool,
+    /// 1ファイルあたりの最大サイズ（KB）
+    pub max_file_size_kb: u64,
+    /// 保持するファイル数（現在のファイルを含む）
+    pub max_files: usize,
+}
+
+impl Default for DebugRecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_file_size_kb: 1024, // 1MB
+            max_files: 5,
         }
     }
 }
 
+/// 匿名ハードウェア統計の送信設定
+///
+/// ティア判定テーブルの改善のため、GPU世代・CPUティア・選択したエンコーダー等を
+/// 週1回まで送信する。明示的に有効化した場合のみ送信する
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryConfig {
+    /// 送信を有効にするか（デフォルト: 無効）
+    pub enabled: bool,
+    /// 送信先URL（未設定の場合は送信しない）
+    #[serde(default)]
+    pub endpoint: String,
+}
+
+/// オブザーバーモードの待ち受けアドレスの初期値（ローカルホストのみ）
+pub const DEFAULT_OBSERVER_BIND_ADDRESS: &str = "127.0.0.1:8787";
+
+/// オブザーバーモードの設定
+///
+/// モデレーター・ヘルパーが配信状況を確認するための読み取り専用HTTPサーバー。
+/// コマンドで起動した場合のみ待ち受け、起動時に自動で開始することはない
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObserverConfig {
+    /// 待ち受けアドレス（`ホスト:ポート`）
+    pub bind_address: String,
+}
+
+impl Default for ObserverConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: DEFAULT_OBSERVER_BIND_ADDRESS.to_string(),
+        }
+    }
+}
+
+/// 音声モニタリング設定
+///
+/// スピーカー・ヘッドホンでモニタリングする場合、オーディオインターフェースごとに
+/// 遅延補正値を調整する必要がある
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioMonitoringConfig {
+    /// 音声モニタリングを使用しているか
+    pub enabled: bool,
+    /// モニタリングの遅延補正（ミリ秒）
+    #[serde(default)]
+    pub monitoring_offset_ms: i32,
+}
+
+/// 配信開始時に表示されている必要があるシーンアイテム
+///
+/// 配信前チェックで、指定したシーン内のソースが非表示になっていれば警告する
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredSceneItem {
+    /// シーン名
+    pub scene_name: String,
+    /// ソース名
+    pub source_name: String,
+}
+
+/// 自動化ルールのクールダウンの下限（秒）
+///
+/// ビットレート変更やシーン切り替えが短時間に繰り返されないようにする
+pub const MIN_AUTOMATION_COOLDOWN_SECS: u64 = 10;
+
+/// 自動化ルールで変更できるビットレートの1回あたりの上限（kbps）
+pub const MAX_AUTOMATION_BITRATE_STEP_KBPS: i32 = 10_000;
+
+/// 自動化ルール
+///
+/// メトリクスが閾値を一定時間超えた（または下回った）ときにアクションを実行する。
+/// アクションは配信を止めずに行える操作（ビットレート変更・通知・シーン切り替え）に限る
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    /// ルールID
+    pub id: String,
+    /// ルール名
+    pub name: String,
+    /// 有効か
+    pub enabled: bool,
+    /// 発動条件
+    pub trigger: AutomationTrigger,
+    /// 実行するアクション（記載順に実行）
+    pub actions: Vec<AutomationAction>,
+    /// 実行後、同じルールを再び実行するまでの間隔（秒）
+    #[serde(default = "default_automation_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+/// 自動化ルールのクールダウンの初期値
+const fn default_automation_cooldown_secs() -> u64 {
+    120
+}
+
+impl AutomationRule {
+    /// ルールを検証
+    ///
+    /// # Returns
+    /// 不正な項目ごとのメッセージ（`<フィールド名>: <理由>`）の一覧
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut check = |valid: bool, field: &str, message: &str| {
+            if !valid {
+                errors.push(format!("{field}: {message}"));
+            }
+        };
+
+        check(!self.name.trim().is_empty(), "name", "ルール名を指定してください");
+        check(self.trigger.threshold.is_finite(), "trigger.threshold", "数値を指定してください");
+        check(self.trigger.duration_secs <= 3600, "trigger.durationSecs", "3600秒以下を指定してください");
+        check(!self.actions.is_empty(), "actions", "アクションを1つ以上指定してください");
+        check(
+            self.cooldown_secs >= MIN_AUTOMATION_COOLDOWN_SECS,
+            "cooldownSecs",
+            &format!("{MIN_AUTOMATION_COOLDOWN_SECS}秒以上を指定してください"),
+        );
+
+        for (index, action) in self.actions.iter().enumerate() {
+            let field = format!("actions[{index}]");
+            match action {
+                AutomationAction::AdjustBitrate { delta_kbps } => check(
+                    *delta_kbps != 0
+                        && (-MAX_AUTOMATION_BITRATE_STEP_KBPS..=MAX_AUTOMATION_BITRATE_STEP_KBPS).contains(delta_kbps),
+                    &field,
+                    &format!("ビットレートの変更量は±1〜{MAX_AUTOMATION_BITRATE_STEP_KBPS}kbpsで指定してください"),
+                ),
+                AutomationAction::Notify { message } => {
+                    check(!message.trim().is_empty(), &field, "通知メッセージを指定してください");
+                }
+                AutomationAction::SwitchScene { scene_name } => {
+                    check(!scene_name.trim().is_empty(), &field, "シーン名を指定してください");
+                }
+                AutomationAction::None => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// 自動化ルールの発動条件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationTrigger {
+    /// 監視するメトリクス
+    pub metric: MetricType,
+    /// 閾値との比較方法
+    #[serde(default)]
+    pub comparison: ThresholdComparison,
+    /// 閾値
+    pub threshold: f64,
+    /// 条件を満たし続ける必要がある時間（秒）
+    pub duration_secs: u64,
+}
+
+/// 閾値との比較方法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThresholdComparison {
+    /// 閾値以上
+    #[default]
+    Above,
+    /// 閾値未満
+    Below,
+}
+
+impl ThresholdComparison {
+    /// 値が条件を満たすか
+    pub fn matches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::Above => value >= threshold,
+            Self::Below => value < threshold,
+        }
+    }
+}
+
+/// 自動化ルールのアクション（配信中に実行しても出力の再起動が不要な操作のみ）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AutomationAction {
+    /// 配信ビットレートを変更量だけ上げ下げする
+    #[serde(rename_all = "camelCase")]
+    AdjustBitrate {
+        /// 変更量（kbps、負の値で下げる）
+        delta_kbps: i32,
+    },
+    /// デスクトップ通知を表示する
+    Notify {
+        /// 通知本文
+        message: String,
+    },
+    /// シーンを切り替える
+    #[serde(rename_all = "camelCase")]
+    SwitchScene {
+        /// 切り替え先のシーン名
+        scene_name: String,
+    },
+    /// 何もしない（実行履歴の記録のみ）
+    None,
+}
+
+/// メディアソースの再生終了時の自動アクション
+///
+/// イントロ動画の再生が終わったら配信を開始する、のような用途に使う
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "sceneName", rename_all = "camelCase")]
+pub enum MediaAutoAction {
+    /// 配信を開始する
+    StartStreaming,
+    /// シーンを切り替える（切り替え先のシーン名）
+    SwitchScene(String),
+    /// 録画を開始する
+    StartRecording,
+    /// 何もしない
+    None,
+}
+
 /// 表示設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -167,6 +542,12 @@ pub struct DisplayConfig {
     pub compact_mode: bool,
     /// 常に最前面に表示
     pub always_on_top: bool,
+    /// 表示言語（推奨理由などの文言）
+    #[serde(default)]
+    pub language: Language,
+    /// グローバルショートカットの割り当て
+    #[serde(default)]
+    pub shortcuts: ShortcutBindings,
 }
 
 impl Default for DisplayConfig {
@@ -176,10 +557,96 @@ impl Default for DisplayConfig {
             graph_history_duration_secs: 60, // 1分
             compact_mode: false,
             always_on_top: false,
+            language: Language::default(),
+            shortcuts: ShortcutBindings::default(),
+        }
+    }
+}
+
+/// グローバルショートカットの割り当て（未割り当ての操作はNone）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBindings {
+    /// パニックモード
+    #[serde(default)]
+    pub panic_mode: Option<String>,
+    /// 推奨設定の適用
+    #[serde(default)]
+    pub apply_recommended: Option<String>,
+}
+
+impl ShortcutBindings {
+    /// 操作に割り当てられたショートカット
+    pub fn binding(&self, action: ShortcutAction) -> Option<&str> {
+        match action {
+            ShortcutAction::PanicMode => self.panic_mode.as_deref(),
+            ShortcutAction::ApplyRecommended => self.apply_recommended.as_deref(),
+        }
+    }
+
+    /// 操作にショートカットを割り当てる（Noneで解除）
+    pub fn set_binding(&mut self, action: ShortcutAction, accelerator: Option<String>) {
+        let slot = match action {
+            ShortcutAction::PanicMode => &mut self.panic_mode,
+            ShortcutAction::ApplyRecommended => &mut self.apply_recommended,
+        };
+        *slot = accelerator;
+    }
+
+    /// 割り当て済みのショートカットを解析して返す（不正な文字列は除く）
+    pub fn parsed(&self) -> Vec<(ShortcutAction, Accelerator)> {
+        ShortcutAction::ALL
+            .into_iter()
+            .filter_map(|action| Some((action, Accelerator::parse(self.binding(action)?).ok()?)))
+            .collect()
+    }
+
+    /// 割り当てを検証
+    ///
+    /// # Returns
+    /// 不正な項目ごとのメッセージ（`<フィールド名>: <理由>`）の一覧
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut assigned: Vec<(ShortcutAction, Accelerator)> = Vec::new();
+
+        for action in ShortcutAction::ALL {
+            let Some(value) = self.binding(action) else {
+                continue;
+            };
+            match Accelerator::parse(value) {
+                Ok(accelerator) => {
+                    if let Some((other, _)) = assigned.iter().find(|(_, other)| *other == accelerator) {
+                        errors.push(format!(
+                            "{}: {}と同じショートカットは割り当てられません",
+                            action.field_name(),
+                            other.field_name()
+                        ));
+                    }
+                    assigned.push((action, accelerator));
+                }
+                Err(e) => errors.push(format!("{}: {}", action.field_name(), e.message())),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 }
 
+/// 表示言語
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Language {
+    /// 日本語
+    #[default]
+    Japanese,
+    /// 英語
+    English,
+}
+
 /// 配信モード設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -192,6 +659,18 @@ pub struct StreamingModeConfig {
     pub network_speed_mbps: f64,
     /// 画質優先モード
     pub quality_priority: bool,
+    /// 配信プロトコル
+    #[serde(default)]
+    pub protocol: StreamingProtocol,
+    /// 配信の遅延モード
+    #[serde(default)]
+    pub latency: StreamLatency,
+    /// 配信環境の構成（1台 / 2台構成）
+    #[serde(default)]
+    pub setup_type: SetupType,
+    /// HDR配信を行うか（HEVC Main 10でエンコードする）
+    #[serde(default)]
+    pub hdr_mode: bool,
 }
 
 impl Default for StreamingModeConfig {
@@ -201,6 +680,10 @@ impl Default for StreamingModeConfig {
             style: StreamingStyle::Gaming,
             network_speed_mbps: 10.0,
             quality_priority: false,
+            protocol: StreamingProtocol::default(),
+            latency: StreamLatency::default(),
+            setup_type: SetupType::default(),
+            hdr_mode: false,
         }
     }
 }
@@ -217,10 +700,121 @@ pub enum StreamingPlatform {
     NicoNico,
     /// ツイキャス
     TwitCasting,
+    /// Bilibili（bilibili.com）
+    Bilibili,
+    /// SHOWROOM
+    #[serde(rename = "showRoom")]
+    Showroom,
     /// その他
     Other,
 }
 
+impl StreamingPlatform {
+    /// 配信先URL（RTMPサーバーURL等）からプラットフォームを判定
+    ///
+    /// 判定できない場合は `Other` を返す
+    ///
+    /// # Arguments
+    /// * `url` - 配信先URL（例: "rtmp://live.bilibili.com/live-bvc/"）
+    pub fn from_stream_url(url: &str) -> Self {
+        let url_lower = url.to_lowercase();
+
+        if url_lower.contains("youtube.com") {
+            Self::YouTube
+        } else if url_lower.contains("twitch.tv") {
+            Self::Twitch
+        } else if url_lower.contains("nicovideo.jp") || url_lower.contains("nicolive") {
+            Self::NicoNico
+        } else if url_lower.contains("twitcasting.tv") {
+            Self::TwitCasting
+        } else if url_lower.contains("live.bilibili.com") || url_lower.contains("bilivideo.com") {
+            Self::Bilibili
+        } else if url_lower.contains("showroom-live.com") {
+            Self::Showroom
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// 配信プロトコル
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamingProtocol {
+    /// RTMP（暗号化なし）
+    Rtmp,
+    /// RTMPS（TLS暗号化、主要プラットフォームの標準）
+    #[default]
+    Rtmps,
+    /// SRT（低遅延・パケットロス耐性、OBS 29以降）
+    Srt,
+    /// RIST（低遅延・パケットロス耐性、OBS 29以降）
+    Rist,
+}
+
+impl StreamingProtocol {
+    /// SRT/RISTに対応するOBSのメジャーバージョン
+    pub const LOW_LATENCY_MIN_OBS_MAJOR: u32 = 29;
+
+    /// OBSのバージョン文字列から対応プロトコルを判定
+    ///
+    /// バージョンを解析できない場合はRTMP/RTMPSのみとする
+    ///
+    /// # Arguments
+    /// * `obs_version` - OBSのバージョン（例: "30.2.0"）
+    pub fn supported_by_obs(obs_version: &str) -> Vec<Self> {
+        let major = obs_version
+            .split('.')
+            .next()
+            .and_then(|major| major.trim().parse::<u32>().ok());
+
+        let mut protocols = vec![Self::Rtmp, Self::Rtmps];
+        if major.is_some_and(|major| major >= Self::LOW_LATENCY_MIN_OBS_MAJOR) {
+            protocols.push(Self::Srt);
+            protocols.push(Self::Rist);
+        }
+        protocols
+    }
+}
+
+/// 配信の遅延モード（YouTubeの「通常の遅延 / 低遅延 / 超低遅延」に相当）
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamLatency {
+    /// 通常の遅延（画質優先）
+    #[default]
+    Normal,
+    /// 低遅延
+    Low,
+    /// 超低遅延（視聴者とのやり取りを優先）
+    UltraLow,
+}
+
+/// 配信環境の構成
+///
+/// 2台構成ではゲーム用PCの映像をキャプチャーボードまたはNDIで配信用PCに送り、
+/// 配信用PCのOBSでエンコードする
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SetupType {
+    /// 1台のPCでゲームと配信を行う
+    #[default]
+    SinglePc,
+    /// 2台構成の配信用PC（ゲームは別のPCで動作）
+    DualPcStreaming,
+    /// 2台構成のゲーム用PC（エンコードは配信用PCで行う）
+    DualPcGaming,
+}
+
+impl SetupType {
+    /// ゲームが動作しない配信専用のPCか
+    ///
+    /// 配信専用PCではGPU・CPUをゲームと奪い合わないため、エンコードに性能を割り当てられる
+    pub const fn is_dedicated_streaming_pc(self) -> bool {
+        matches!(self, Self::DualPcStreaming)
+    }
+}
+
 /// 配信スタイル
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -246,6 +840,177 @@ impl Default for AppConfig {
             alerts: AlertConfig::default(),
             display: DisplayConfig::default(),
             streaming_mode: StreamingModeConfig::default(),
+            obs_process_priority: ProcessPriority::default(),
+            known_game_executables: default_known_game_executables(),
+            microphone_input_name: None,
+            debug_recording: DebugRecordingConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            required_scene_items: Vec::new(),
+            monthly_quota_gb: None,
+            quota_reset_at: None,
+            dismissed_checklist_rules: Vec::new(),
+            audio_monitoring: AudioMonitoringConfig::default(),
+            automation_rules: Vec::new(),
+            backup_obs_files: false,
+            observer: ObserverConfig::default(),
+            media_autoactions: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// 設定値を検証
+    ///
+    /// # Returns
+    /// 不正な項目ごとのメッセージ（`<フィールドのパス>: <理由>`）の一覧
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut check = |valid: bool, field: &str, message: &str| {
+            if !valid {
+                errors.push(format!("{field}: {message}"));
+            }
+        };
+
+        check(!self.connection.last_host.trim().is_empty(), "connection.lastHost", "ホスト名を指定してください");
+        check(self.connection.last_port != 0, "connection.lastPort", "1〜65535の範囲で指定してください");
+        check(self.connection.connection_timeout_secs > 0, "connection.connectionTimeoutSecs", "1秒以上を指定してください");
+        check(self.monitoring.update_interval_ms >= 100, "monitoring.updateIntervalMs", "100ミリ秒以上を指定してください");
+        check(self.monitoring.health_check_interval_secs > 0, "monitoring.healthCheckIntervalSecs", "1秒以上を指定してください");
+        check(
+            self.observer.bind_address.parse::<std::net::SocketAddr>().is_ok(),
+            "observer.bindAddress",
+            "`127.0.0.1:8787` の形式で指定してください",
+        );
+
+        let alerts = &self.alerts;
+        for (field, value) in [
+            ("alerts.cpuWarningThreshold", alerts.cpu_warning_threshold),
+            ("alerts.cpuCriticalThreshold", alerts.cpu_critical_threshold),
+            ("alerts.gpuWarningThreshold", alerts.gpu_warning_threshold),
+            ("alerts.gpuCriticalThreshold", alerts.gpu_critical_threshold),
+            ("alerts.frameDropWarningThreshold", alerts.frame_drop_warning_threshold),
+            ("alerts.frameDropCriticalThreshold", alerts.frame_drop_critical_threshold),
+        ] {
+            check((0.0..=100.0).contains(&value), field, "0〜100の範囲で指定してください");
+        }
+        check(
+            alerts.cpu_warning_threshold <= alerts.cpu_critical_threshold,
+            "alerts.cpuWarningThreshold",
+            "クリティカル閾値以下を指定してください",
+        );
+        check(
+            alerts.gpu_warning_threshold <= alerts.gpu_critical_threshold,
+            "alerts.gpuWarningThreshold",
+            "クリティカル閾値以下を指定してください",
+        );
+        check(
+            alerts.frame_drop_warning_threshold <= alerts.frame_drop_critical_threshold,
+            "alerts.frameDropWarningThreshold",
+            "クリティカル閾値以下を指定してください",
+        );
+
+        check(self.display.graph_history_duration_secs > 0, "display.graphHistoryDurationSecs", "1秒以上を指定してください");
+        check(
+            self.streaming_mode.network_speed_mbps.is_finite() && self.streaming_mode.network_speed_mbps > 0.0,
+            "streamingMode.networkSpeedMbps",
+            "0より大きい値を指定してください",
+        );
+        check(self.debug_recording.max_file_size_kb > 0, "debugRecording.maxFileSizeKb", "1KB以上を指定してください");
+        check(self.debug_recording.max_files > 0, "debugRecording.maxFiles", "1以上を指定してください");
+        check(
+            self.monthly_quota_gb.is_none_or(|quota| quota.is_finite() && quota > 0.0),
+            "monthlyQuotaGb",
+            "0より大きい値を指定してください",
+        );
+        for (index, (source_name, action)) in self.media_autoactions.iter().enumerate() {
+            let field = format!("mediaAutoactions[{index}]");
+            check(!source_name.trim().is_empty(), &field, "メディアソース名を指定してください");
+            if let MediaAutoAction::SwitchScene(scene_name) = action {
+                check(!scene_name.trim().is_empty(), &field, "切り替え先のシーン名を指定してください");
+            }
+        }
+
+        if let Err(shortcut_errors) = self.display.shortcuts.validate() {
+            errors.extend(
+                shortcut_errors
+                    .into_iter()
+                    .map(|error| format!("display.shortcuts.{error}")),
+            );
+        }
+
+        for (index, rule) in self.automation_rules.iter().enumerate() {
+            if let Err(rule_errors) = rule.validate() {
+                errors.extend(
+                    rule_errors
+                        .into_iter()
+                        .map(|error| format!("automationRules[{index}].{error}")),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// JSON Merge Patch（RFC 7396）を適用した設定を返す
+    ///
+    /// 指定したフィールドだけを上書きし、ネストしたオブジェクトは再帰的にマージする。
+    /// nullを指定したフィールドは初期値に戻す。マージ後の設定は `validate` で検証する。
+    ///
+    /// # Arguments
+    /// * `patch` - 変更するフィールドだけを含むJSONオブジェクト
+    ///
+    /// # Returns
+    /// マージ後の設定。パッチがオブジェクトでない場合・型が合わない場合・検証エラーの場合はメッセージの一覧
+    pub fn merge(&self, patch: serde_json::Value) -> Result<Self, Vec<String>> {
+        if !patch.is_object() {
+            return Err(vec!["設定の変更はJSONオブジェクトで指定してください".to_string()]);
+        }
+
+        let to_value = |config: &Self| serde_json::to_value(config).map_err(|e| vec![e.to_string()]);
+        let mut merged = to_value(self)?;
+        apply_merge_patch(&mut merged, &patch, &to_value(&Self::default())?);
+
+        let config: Self = serde_json::from_value(merged)
+            .map_err(|e| vec![format!("設定の形式が不正です: {e}")])?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// JSON Merge Patch（RFC 7396）を適用
+///
+/// RFC 7396ではnullはフィールドの削除だが、設定は必須フィールドを持つため
+/// `defaults` の同じ位置の値（初期値）に置き換える
+fn apply_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value, defaults: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let Some(target) = target.as_object_mut() else {
+        return;
+    };
+
+    for (key, value) in patch {
+        let default = defaults.get(key).unwrap_or(&serde_json::Value::Null);
+        if value.is_null() {
+            if default.is_null() {
+                target.remove(key);
+            } else {
+                target.insert(key.clone(), default.clone());
+            }
+        } else {
+            apply_merge_patch(
+                target.entry(key.clone()).or_insert(serde_json::Value::Null),
+                value,
+                default,
+            );
         }
     }
 }
@@ -256,27 +1021,25 @@ impl Default for AppConfig {
 /// Linux: ~/.config/obs-optimizer/config.json
 /// macOS: ~/Library/Application Support/obs-optimizer/config.json
 fn get_config_path() -> Result<PathBuf, AppError> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| AppError::new("CONFIG_ERROR", "設定ディレクトリを取得できませんでした"))?;
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
 
-    let app_config_dir = config_dir.join(APP_NAME);
-    let config_path = app_config_dir.join(CONFIG_FILE_NAME);
+    let config_path = config_dir.join(CONFIG_FILE_NAME);
 
     Ok(config_path)
 }
 
 /// 設定ディレクトリを作成
 fn ensure_config_dir() -> Result<PathBuf, AppError> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| AppError::new("CONFIG_ERROR", "設定ディレクトリを取得できませんでした"))?;
-
-    let app_config_dir = config_dir.join(APP_NAME);
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
 
-    if !app_config_dir.exists() {
-        std::fs::create_dir_all(&app_config_dir)?;
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| AppError::config_io(&format!("設定ディレクトリの作成に失敗: {e}")))?;
     }
 
-    Ok(app_config_dir)
+    Ok(config_dir)
 }
 
 /// 設定ファイルを読み込む
@@ -291,7 +1054,8 @@ pub fn load_config() -> Result<AppConfig, AppError> {
         return Ok(AppConfig::default());
     }
 
-    let content = std::fs::read_to_string(&config_path)?;
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| AppError::config_io(&format!("設定ファイルの読み込みに失敗: {e}")))?;
     let mut config: AppConfig = serde_json::from_str(&content)?;
 
     // プレーンテキストパスワードの移行処理
@@ -344,7 +1108,9 @@ pub fn save_config(config: &AppConfig) -> Result<(), AppError> {
     let config_path = get_config_path()?;
 
     let content = serde_json::to_string_pretty(config)?;
-    std::fs::write(&config_path, content)?;
+    std::fs::write(&config_path, content)
+        .map_err(|e| AppError::config_io(&format!("設定ファイルの書き込みに失敗: {e}")))?;
+    CONFIG_REVISION.fetch_add(1, Ordering::AcqRel);
 
     Ok(())
 }
@@ -364,6 +1130,187 @@ mod tests {
         assert_eq!(config.alerts.cpu_warning_threshold, 90.0);
     }
 
+    #[test]
+    fn test_legacy_config_gets_new_field_defaults() {
+        // 優先度・ゲーム一覧が存在しない旧形式の設定ファイル
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("obsProcessPriority");
+        object.remove("knownGameExecutables");
+
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.obs_process_priority, ProcessPriority::Normal);
+        assert!(config.known_game_executables.iter().any(|g| g == "steam.exe"));
+    }
+
+    #[test]
+    fn test_telemetry_disabled_by_default() {
+        assert!(!AppConfig::default().telemetry.enabled);
+
+        // 送信設定が存在しない旧形式の設定ファイルでも無効
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("telemetry");
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert!(!config.telemetry.enabled);
+        assert!(config.telemetry.endpoint.is_empty());
+    }
+
+    #[test]
+    fn test_observer_defaults_to_localhost() {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("observer");
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.observer.bind_address, DEFAULT_OBSERVER_BIND_ADDRESS);
+
+        let mut invalid = AppConfig::default();
+        invalid.observer.bind_address = "localhost".to_string();
+        let errors = invalid.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("observer.bindAddress")));
+    }
+
+    #[test]
+    fn test_legacy_config_gets_microphone_defaults() {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("microphoneInputName");
+        let alerts = object.get_mut("alerts").unwrap().as_object_mut().unwrap();
+        alerts.remove("micSilenceAlertSecs");
+        alerts.remove("micSilenceFloorDb");
+
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert!(config.microphone_input_name.is_none(), "未設定時は自動検出");
+        assert_eq!(config.alerts.mic_silence_alert_secs, 30);
+        assert_eq!(config.alerts.mic_silence_floor_db, -60.0);
+    }
+
+    #[test]
+    fn test_legacy_config_gets_health_check_default() {
+        let mut value = serde_json::to_value(AppConfig::default()).unwrap();
+        let monitoring = value
+            .get_mut("monitoring")
+            .unwrap()
+            .as_object_mut()
+            .unwrap();
+        monitoring.remove("healthCheckIntervalSecs");
+
+        let config: AppConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.monitoring.health_check_interval_secs, 30);
+    }
+
+    #[test]
+    fn test_merge_partial_update() {
+        let config = AppConfig::default();
+        let merged = config
+            .merge(serde_json::json!({ "connection": { "lastPort": 4456 } }))
+            .unwrap();
+
+        assert_eq!(merged.connection.last_port, 4456);
+        // 指定していないフィールドは維持される
+        assert_eq!(merged.connection.last_host, "localhost");
+        assert_eq!(merged.alerts.cpu_warning_threshold, 90.0);
+    }
+
+    #[test]
+    fn test_merge_null_resets_to_default() {
+        let mut config = AppConfig::default();
+        config.alerts.cpu_warning_threshold = 70.0;
+        config.display.dark_mode = false;
+        config.microphone_input_name = Some("マイク".to_string());
+
+        let merged = config
+            .merge(serde_json::json!({
+                "alerts": { "cpuWarningThreshold": null },
+                "display": null,
+                "microphoneInputName": null,
+            }))
+            .unwrap();
+
+        assert_eq!(merged.alerts.cpu_warning_threshold, 90.0);
+        assert!(merged.display.dark_mode);
+        assert!(merged.microphone_input_name.is_none());
+    }
+
+    #[test]
+    fn test_merge_deep_nested_struct() {
+        let mut config = AppConfig::default();
+        config.streaming_mode.platform = StreamingPlatform::Twitch;
+
+        let merged = config
+            .merge(serde_json::json!({
+                "streamingMode": { "setupType": "dualPcStreaming", "networkSpeedMbps": 50.0 },
+                "requiredSceneItems": [{ "sceneName": "ゲーム", "sourceName": "Webカメラ" }],
+            }))
+            .unwrap();
+
+        assert_eq!(merged.streaming_mode.setup_type, SetupType::DualPcStreaming);
+        assert_eq!(merged.streaming_mode.network_speed_mbps, 50.0);
+        assert_eq!(merged.streaming_mode.platform, StreamingPlatform::Twitch);
+        // 配列はマージせず置き換える
+        assert_eq!(merged.required_scene_items.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_concurrent_updates_keep_unrelated_fields() {
+        // 2つの画面が同じ設定を読み込んだ後、それぞれ別の項目を保存する
+        let stored = AppConfig::default();
+        let patch_alerts = serde_json::json!({ "alerts": { "playSound": false } });
+        let patch_display = serde_json::json!({ "display": { "compactMode": true } });
+
+        let stored = stored.merge(patch_alerts).unwrap();
+        let stored = stored.merge(patch_display).unwrap();
+
+        assert!(!stored.alerts.play_sound);
+        assert!(stored.display.compact_mode);
+    }
+
+    #[test]
+    fn test_merge_rejects_invalid_values() {
+        let config = AppConfig::default();
+
+        let errors = config
+            .merge(serde_json::json!({ "alerts": { "cpuWarningThreshold": 150.0 } }))
+            .unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("alerts.cpuWarningThreshold")));
+
+        assert!(config.merge(serde_json::json!({ "connection": { "lastPort": "abc" } })).is_err());
+        assert!(config.merge(serde_json::json!([1, 2])).is_err());
+
+        let errors = config.merge(serde_json::json!({ "monthlyQuotaGb": 0.0 })).unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("monthlyQuotaGb")));
+        assert!(config.merge(serde_json::json!({ "monthlyQuotaGb": 50.0 })).is_ok());
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_shortcut_bindings() {
+        let mut config = AppConfig::default();
+        config.display.shortcuts.set_binding(ShortcutAction::PanicMode, Some("Ctrl+Shift+F9".to_string()));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.display.shortcuts.parsed().len(), 1);
+
+        config.display.shortcuts.set_binding(ShortcutAction::ApplyRecommended, Some("shift+ctrl+f9".to_string()));
+        let errors = config.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("display.shortcuts.applyRecommended")), "{errors:?}");
+
+        config.display.shortcuts.set_binding(ShortcutAction::ApplyRecommended, Some("Ctrl+".to_string()));
+        let errors = config.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("display.shortcuts.applyRecommended")), "{errors:?}");
+
+        // 既存の設定ファイル（shortcutsなし）も読み込める
+        let display: DisplayConfig = serde_json::from_value(serde_json::json!({
+            "darkMode": true,
+            "graphHistoryDurationSecs": 60,
+            "compactMode": false,
+            "alwaysOnTop": false
+        }))
+        .unwrap();
+        assert_eq!(display.shortcuts, ShortcutBindings::default());
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = AppConfig::default();
@@ -401,6 +1348,8 @@ mod tests {
             StreamingPlatform::Twitch,
             StreamingPlatform::NicoNico,
             StreamingPlatform::TwitCasting,
+            StreamingPlatform::Bilibili,
+            StreamingPlatform::Showroom,
             StreamingPlatform::Other,
         ] {
             let json = serde_json::to_string(&platform).unwrap();
@@ -409,6 +1358,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bilibili_showroom_serialization() {
+        assert_eq!(serde_json::to_string(&StreamingPlatform::Bilibili).unwrap(), r#""bilibili""#);
+        assert_eq!(serde_json::to_string(&StreamingPlatform::Showroom).unwrap(), r#""showRoom""#);
+
+        let platform: StreamingPlatform = serde_json::from_str(r#""showRoom""#).unwrap();
+        assert_eq!(platform, StreamingPlatform::Showroom);
+    }
+
+    #[test]
+    fn test_platform_from_stream_url() {
+        let cases = [
+            ("rtmp://a.rtmp.youtube.com/live2", StreamingPlatform::YouTube),
+            ("rtmp://live-tyo.twitch.tv/app", StreamingPlatform::Twitch),
+            ("rtmp://live.bilibili.com/live-bvc/", StreamingPlatform::Bilibili),
+            ("rtmp://live-push.bilivideo.com/live-bvc/", StreamingPlatform::Bilibili),
+            ("rtmp://rtmp.showroom-live.com/liveedge", StreamingPlatform::Showroom),
+            ("rtmp://example.com/live", StreamingPlatform::Other),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(StreamingPlatform::from_stream_url(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_streaming_protocol_serialization() {
+        let cases = [
+            (StreamingProtocol::Rtmp, r#""rtmp""#),
+            (StreamingProtocol::Rtmps, r#""rtmps""#),
+            (StreamingProtocol::Srt, r#""srt""#),
+            (StreamingProtocol::Rist, r#""rist""#),
+        ];
+
+        for (protocol, expected) in cases {
+            let json = serde_json::to_string(&protocol).unwrap();
+            assert_eq!(json, expected);
+            let deserialized: StreamingProtocol = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, protocol);
+        }
+    }
+
+    #[test]
+    fn test_supported_protocols_by_obs_version() {
+        let low_latency = [StreamingProtocol::Srt, StreamingProtocol::Rist];
+
+        let v30 = StreamingProtocol::supported_by_obs("30.2.0");
+        assert!(low_latency.iter().all(|p| v30.contains(p)));
+        assert!(StreamingProtocol::supported_by_obs("29.0.0").contains(&StreamingProtocol::Srt));
+
+        let v28 = StreamingProtocol::supported_by_obs("28.1.2");
+        assert_eq!(v28, vec![StreamingProtocol::Rtmp, StreamingProtocol::Rtmps]);
+
+        // 解析できない場合はRTMP系のみ
+        assert_eq!(StreamingProtocol::supported_by_obs("unknown").len(), 2);
+    }
+
+    #[test]
+    fn test_legacy_streaming_mode_defaults_to_rtmps() {
+        let json = r#"{"platform":"twitch","style":"gaming","networkSpeedMbps":10.0,"qualityPriority":false}"#;
+        let config: StreamingModeConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.protocol, StreamingProtocol::Rtmps);
+    }
+
+    #[test]
+    fn test_setup_type_defaults_to_single_pc() {
+        let json = r#"{"platform":"twitch","style":"gaming","networkSpeedMbps":10.0,"qualityPriority":false}"#;
+        let config: StreamingModeConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.setup_type, SetupType::SinglePc);
+
+        let json = serde_json::to_string(&SetupType::DualPcStreaming).unwrap();
+        assert_eq!(json, r#""dualPcStreaming""#);
+        assert!(SetupType::DualPcStreaming.is_dedicated_streaming_pc());
+        assert!(!SetupType::DualPcGaming.is_dedicated_streaming_pc());
+    }
+
     #[test]
     fn test_all_streaming_styles_serialization() {
         // すべてのスタイルがシリアライズ可能
@@ -461,6 +1486,7 @@ mod tests {
         assert_eq!(config.display.graph_history_duration_secs, 60);
         assert!(!config.display.compact_mode);
         assert!(!config.display.always_on_top);
+        assert_eq!(config.display.language, Language::Japanese);
 
         // StreamingModeConfig デフォルト値
         assert_eq!(config.streaming_mode.platform, StreamingPlatform::YouTube);
@@ -544,6 +1570,8 @@ mod tests {
         let config = config.unwrap();
         assert_eq!(config.connection.last_host, "192.168.1.1");
         assert!(config.connection.auto_connect_on_startup);
+        // 言語設定の追加前の設定ファイルは日本語
+        assert_eq!(config.display.language, Language::Japanese);
     }
 
     #[test]
@@ -864,4 +1892,101 @@ mod tests {
             "Noneのsaved_passwordはシリアライズされない"
         );
     }
+
+    fn automation_rule(actions: Vec<AutomationAction>) -> AutomationRule {
+        AutomationRule {
+            id: "rule-1".to_string(),
+            name: "ドロップ時にビットレートを下げる".to_string(),
+            enabled: true,
+            trigger: AutomationTrigger {
+                metric: MetricType::FrameDropRate,
+                comparison: ThresholdComparison::Above,
+                threshold: 5.0,
+                duration_secs: 30,
+            },
+            actions,
+            cooldown_secs: 120,
+        }
+    }
+
+    #[test]
+    fn test_automation_rule_json_format() {
+        let json = r#"{
+            "id": "rule-1",
+            "name": "BRB",
+            "enabled": true,
+            "trigger": { "metric": "cpuUsage", "threshold": 95, "durationSecs": 60 },
+            "actions": [
+                { "type": "notify", "message": "CPU使用率が高い状態です" },
+                { "type": "switchScene", "sceneName": "BRB" },
+                { "type": "adjustBitrate", "deltaKbps": -1000 },
+                { "type": "none" }
+            ]
+        }"#;
+
+        let rule: AutomationRule = serde_json::from_str(json).unwrap();
+        assert_eq!(rule.trigger.comparison, ThresholdComparison::Above);
+        assert_eq!(rule.cooldown_secs, default_automation_cooldown_secs());
+        assert_eq!(rule.actions[1], AutomationAction::SwitchScene { scene_name: "BRB".to_string() });
+        assert_eq!(rule.actions[2], AutomationAction::AdjustBitrate { delta_kbps: -1000 });
+        assert!(rule.validate().is_ok());
+    }
+
+    #[test]
+    fn test_automation_rule_validation() {
+        assert!(automation_rule(vec![AutomationAction::AdjustBitrate { delta_kbps: -1000 }]).validate().is_ok());
+
+        let errors = automation_rule(vec![]).validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("actions:")));
+
+        let mut rule = automation_rule(vec![
+            AutomationAction::AdjustBitrate { delta_kbps: 0 },
+            AutomationAction::SwitchScene { scene_name: " ".to_string() },
+        ]);
+        rule.cooldown_secs = MIN_AUTOMATION_COOLDOWN_SECS - 1;
+        rule.trigger.threshold = f64::NAN;
+        let errors = rule.validate().unwrap_err();
+        assert_eq!(errors.len(), 4);
+
+        // アプリ設定の検証にもルールの検証結果が含まれる
+        let mut config = AppConfig::default();
+        config.automation_rules.push(rule);
+        let errors = config.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.starts_with("automationRules[0].cooldownSecs")));
+    }
+
+    #[test]
+    fn test_media_autoaction_serialization() {
+        let mut config = AppConfig::default();
+        config.media_autoactions = vec![
+            ("イントロ".to_string(), MediaAutoAction::StartStreaming),
+            ("アウトロ".to_string(), MediaAutoAction::SwitchScene("終了画面".to_string())),
+        ];
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["mediaAutoactions"][0][0], "イントロ");
+        assert_eq!(json["mediaAutoactions"][0][1], serde_json::json!({ "type": "startStreaming" }));
+        assert_eq!(
+            json["mediaAutoactions"][1][1],
+            serde_json::json!({ "type": "switchScene", "sceneName": "終了画面" })
+        );
+
+        let restored: AppConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.media_autoactions, config.media_autoactions);
+    }
+
+    #[test]
+    fn test_validate_media_autoactions() {
+        let mut config = AppConfig::default();
+        config.media_autoactions = vec![
+            (" ".to_string(), MediaAutoAction::StartRecording),
+            ("アウトロ".to_string(), MediaAutoAction::SwitchScene(String::new())),
+            ("イントロ".to_string(), MediaAutoAction::StartStreaming),
+        ];
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("mediaAutoactions[0]:"));
+        assert!(errors[1].starts_with("mediaAutoactions[1]:"));
+    }
 }
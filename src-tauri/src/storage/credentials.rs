@@ -21,6 +21,59 @@ fn keyring_error(msg: &str) -> AppError {
     AppError::new(ERROR_CODE_KEYRING, msg)
 }
 
+/// keyringクレートのエラーをAppErrorに変換
+///
+/// OSのキーリング自体が利用できない場合は `KEYRING_UNAVAILABLE` を返し、
+/// フロントエンドが設定ファイル保存へのフォールバックを案内できるようにする
+fn map_keyring_error(context: &str, err: &keyring::Error) -> AppError {
+    match err {
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_) => {
+            AppError::keyring_unavailable(&format!("{context}: {err}"))
+        }
+        _ => keyring_error(&format!("{context}: {err}")),
+    }
+}
+
+/// キーリングに秘密情報を保存（既存の値は上書き）
+///
+/// # Arguments
+/// * `username` - キーリングのユーザー名（保存する情報ごとに異なる値を使用）
+/// * `secret` - 保存する値
+/// * `label` - エラーメッセージに使用する名前（例: "パスワード"）
+fn save_secret(username: &str, secret: &str, label: &str) -> Result<(), AppError> {
+    let entry = keyring::Entry::new(SERVICE_NAME, username)
+        .map_err(|e| map_keyring_error("キーリングエントリの作成に失敗", &e))?;
+
+    entry
+        .set_password(secret)
+        .map_err(|e| map_keyring_error(&format!("{label}の保存に失敗"), &e))?;
+
+    Ok(())
+}
+
+/// キーリングから秘密情報を取得（保存されていない場合はNone）
+fn get_secret(username: &str, label: &str) -> Result<Option<String>, AppError> {
+    let entry = keyring::Entry::new(SERVICE_NAME, username)
+        .map_err(|e| map_keyring_error("キーリングエントリの作成に失敗", &e))?;
+
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(map_keyring_error(&format!("{label}の取得に失敗"), &e)),
+    }
+}
+
+/// キーリングから秘密情報を削除（保存されていない場合もエラーにしない）
+fn delete_secret(username: &str, label: &str) -> Result<(), AppError> {
+    let entry = keyring::Entry::new(SERVICE_NAME, username)
+        .map_err(|e| map_keyring_error("キーリングエントリの作成に失敗", &e))?;
+
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(map_keyring_error(&format!("{label}の削除に失敗"), &e)),
+    }
+}
+
 /// OBS WebSocketパスワードを安全に保存
 ///
 /// OSのキーリング（Windows Credential Manager等）に保存する。
@@ -32,14 +85,7 @@ fn keyring_error(msg: &str) -> AppError {
 /// # Returns
 /// 成功時はOk(()), 失敗時はAppError
 pub fn save_obs_password(password: &str) -> Result<(), AppError> {
-    let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
-        .map_err(|e| keyring_error(&format!("キーリングエントリの作成に失敗: {e}")))?;
-
-    entry
-        .set_password(password)
-        .map_err(|e| keyring_error(&format!("パスワードの保存に失敗: {e}")))?;
-
-    Ok(())
+    save_secret(USERNAME, password, "パスワード")
 }
 
 /// OBS WebSocketパスワードを取得
@@ -50,14 +96,7 @@ pub fn save_obs_password(password: &str) -> Result<(), AppError> {
 /// # Returns
 /// 保存されたパスワード（存在する場合）、またはNone
 pub fn get_obs_password() -> Result<Option<String>, AppError> {
-    let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
-        .map_err(|e| keyring_error(&format!("キーリングエントリの作成に失敗: {e}")))?;
-
-    match entry.get_password() {
-        Ok(password) => Ok(Some(password)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(e) => Err(keyring_error(&format!("パスワードの取得に失敗: {e}"))),
-    }
+    get_secret(USERNAME, "パスワード")
 }
 
 /// OBS WebSocketパスワードを削除
@@ -68,15 +107,33 @@ pub fn get_obs_password() -> Result<Option<String>, AppError> {
 /// # Returns
 /// 成功時はOk(()), 失敗時はAppError
 pub fn delete_obs_password() -> Result<(), AppError> {
-    let entry = keyring::Entry::new(SERVICE_NAME, USERNAME)
-        .map_err(|e| keyring_error(&format!("キーリングエントリの作成に失敗: {e}")))?;
+    delete_secret(USERNAME, "パスワード")
+}
 
-    match entry.delete_credential() {
-        Ok(()) => Ok(()),
-        // パスワードが存在しない場合はエラーにしない
-        Err(keyring::Error::NoEntry) => Ok(()),
-        Err(e) => Err(keyring_error(&format!("パスワードの削除に失敗: {e}"))),
-    }
+/// 配信プラットフォームのAPIトークンのキーリング上のユーザー名
+fn platform_token_username(platform: &str) -> String {
+    format!("platform_token_{platform}")
+}
+
+/// 配信プラットフォームのAPIトークンを保存
+///
+/// トークンはキーリングにのみ保存し、設定ファイル・ログ・エクスポートには含めない
+///
+/// # Arguments
+/// * `platform` - プラットフォームの識別子（例: "twitch"）
+/// * `token` - 保存するトークン
+pub fn save_platform_token(platform: &str, token: &str) -> Result<(), AppError> {
+    save_secret(&platform_token_username(platform), token, "トークン")
+}
+
+/// 配信プラットフォームのAPIトークンを取得（保存されていない場合はNone）
+pub fn get_platform_token(platform: &str) -> Result<Option<String>, AppError> {
+    get_secret(&platform_token_username(platform), "トークン")
+}
+
+/// 配信プラットフォームのAPIトークンを削除
+pub fn delete_platform_token(platform: &str) -> Result<(), AppError> {
+    delete_secret(&platform_token_username(platform), "トークン")
 }
 
 /// プレーンテキストからキーリングへの移行を試行
@@ -429,4 +486,16 @@ mod tests {
         assert_eq!(error.code(), ERROR_CODE_KEYRING);
         assert_eq!(error.message(), "テストエラー");
     }
+
+    #[test]
+    fn test_map_keyring_error_unavailable() {
+        let err = keyring::Error::NoStorageAccess(Box::new(std::io::Error::other("locked")));
+        let error = map_keyring_error("パスワードの取得に失敗", &err);
+        assert_eq!(error.code(), crate::error::ERROR_CODE_KEYRING_UNAVAILABLE);
+        assert!(error.message().contains("パスワードの取得に失敗"));
+
+        let err = keyring::Error::NoEntry;
+        let error = map_keyring_error("パスワードの取得に失敗", &err);
+        assert_eq!(error.code(), ERROR_CODE_KEYRING);
+    }
 }
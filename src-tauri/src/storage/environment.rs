@@ -0,0 +1,220 @@
+// 実行環境スナップショットストレージ
+//
+// アプリ終了時のハードウェア・OBS設定をJSONファイルとして保存し、
+// 次回起動時に「前回から何が変わったか」を比較するために使用する
+
+use crate::error::AppError;
+use crate::storage::paths::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const ENVIRONMENT_FILE_NAME: &str = "environment.json";
+
+/// OBS設定の比較用サマリー
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsFingerprint {
+    /// 出力解像度（例: "1920x1080"）
+    pub resolution: String,
+    /// フレームレート
+    pub fps: f64,
+    /// エンコーダー名
+    pub encoder: String,
+    /// ビットレート（kbps）
+    pub bitrate_kbps: u32,
+}
+
+/// 接続中のモニター情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    /// モニター名（取得できない場合はNone）
+    pub name: Option<String>,
+    /// 幅（物理ピクセル）
+    pub width: u32,
+    /// 高さ（物理ピクセル）
+    pub height: u32,
+    /// 表示倍率
+    pub scale_factor: f64,
+}
+
+impl MonitorInfo {
+    /// 比較用の識別子（名前がない場合は解像度）
+    pub fn identity(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}x{}", self.width, self.height))
+    }
+
+    /// 表示用ラベル（例: "DELL U2720Q (3840x2160)"）
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{name} ({}x{})", self.width, self.height),
+            None => format!("{}x{}", self.width, self.height),
+        }
+    }
+}
+
+/// 実行環境のスナップショット
+///
+/// 取得できなかった項目はNoneとし、比較対象から除外する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentSnapshot {
+    /// 取得日時（UNIX epoch秒）
+    pub captured_at: i64,
+    /// OBSのバージョン（未接続の場合はNone）
+    #[serde(default)]
+    pub obs_version: Option<String>,
+    /// CPU名
+    pub cpu_name: String,
+    /// GPU名
+    #[serde(default)]
+    pub gpu_name: Option<String>,
+    /// GPUドライバーバージョン
+    #[serde(default)]
+    pub gpu_driver_version: Option<String>,
+    /// OBS設定のサマリー（未接続の場合はNone）
+    #[serde(default)]
+    pub settings: Option<SettingsFingerprint>,
+    /// 接続中のモニター（取得できない場合は空）
+    #[serde(default)]
+    pub monitors: Vec<MonitorInfo>,
+}
+
+impl EnvironmentSnapshot {
+    /// 取得できなかった項目を前回のスナップショットで補完
+    ///
+    /// 終了時にOBSが未接続だった場合でも、前回のOBSバージョン・設定を引き継ぐ
+    pub fn fill_missing_from(&mut self, previous: &Self) {
+        if self.obs_version.is_none() {
+            self.obs_version.clone_from(&previous.obs_version);
+        }
+        if self.gpu_name.is_none() {
+            self.gpu_name.clone_from(&previous.gpu_name);
+        }
+        if self.gpu_driver_version.is_none() {
+            self.gpu_driver_version.clone_from(&previous.gpu_driver_version);
+        }
+        if self.settings.is_none() {
+            self.settings.clone_from(&previous.settings);
+        }
+        if self.monitors.is_empty() {
+            self.monitors.clone_from(&previous.monitors);
+        }
+    }
+}
+
+/// スナップショットファイルのパスを取得
+fn get_environment_path() -> Result<PathBuf, AppError> {
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+
+    Ok(config_dir.join(ENVIRONMENT_FILE_NAME))
+}
+
+/// 前回保存したスナップショットを読み込む
+///
+/// # Returns
+/// - `Ok(Some(snapshot))` - 保存済みの場合
+/// - `Ok(None)` - 初回起動などでファイルが存在しない場合
+pub fn load_environment_snapshot() -> Result<Option<EnvironmentSnapshot>, AppError> {
+    let path = get_environment_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// スナップショットを保存
+pub fn save_environment_snapshot(snapshot: &EnvironmentSnapshot) -> Result<(), AppError> {
+    let path = get_environment_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(snapshot)?;
+    std::fs::write(&path, content)?;
+    Ok(())
+}
+
+/// テスト用の環境スナップショット（複数モジュールのテストで共有する）
+#[cfg(test)]
+pub mod test_fixtures {
+    use super::{EnvironmentSnapshot, MonitorInfo, SettingsFingerprint};
+
+    /// OBS 30.1.2・RTX 4070・4Kモニター1台の環境
+    pub fn create_snapshot() -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            captured_at: 1_700_000_000,
+            obs_version: Some("30.1.2".to_string()),
+            cpu_name: "AMD Ryzen 7 7800X3D".to_string(),
+            gpu_name: Some("NVIDIA GeForce RTX 4070".to_string()),
+            gpu_driver_version: Some("551.86".to_string()),
+            settings: Some(SettingsFingerprint {
+                resolution: "1920x1080".to_string(),
+                fps: 60.0,
+                encoder: "jim_nvenc".to_string(),
+                bitrate_kbps: 6000,
+            }),
+            monitors: vec![MonitorInfo {
+                name: Some("DELL U2720Q".to_string()),
+                width: 3840,
+                height: 2160,
+                scale_factor: 1.5,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::test_fixtures::create_snapshot;
+    use super::*;
+
+    #[test]
+    fn test_snapshot_serialization_roundtrip() {
+        let snapshot = create_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("\"obsVersion\":\"30.1.2\""));
+
+        let restored: EnvironmentSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, snapshot);
+    }
+
+    #[test]
+    fn test_fill_missing_from_previous() {
+        let previous = create_snapshot();
+        let mut current = EnvironmentSnapshot {
+            captured_at: 1_700_100_000,
+            obs_version: None,
+            cpu_name: previous.cpu_name.clone(),
+            gpu_name: previous.gpu_name.clone(),
+            gpu_driver_version: Some("560.70".to_string()),
+            settings: None,
+            monitors: Vec::new(),
+        };
+
+        current.fill_missing_from(&previous);
+        assert_eq!(current.obs_version.as_deref(), Some("30.1.2"));
+        assert_eq!(current.settings, previous.settings);
+        assert_eq!(current.monitors, previous.monitors);
+        // 取得できた項目は上書きしない
+        assert_eq!(current.gpu_driver_version.as_deref(), Some("560.70"));
+    }
+
+    #[test]
+    fn test_monitor_identity_falls_back_to_resolution() {
+        let monitor = MonitorInfo {
+            name: None,
+            width: 2560,
+            height: 1440,
+            scale_factor: 1.0,
+        };
+        assert_eq!(monitor.identity(), "2560x1440");
+        assert_eq!(monitor.label(), "2560x1440");
+    }
+}
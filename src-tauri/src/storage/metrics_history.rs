@@ -5,10 +5,153 @@
 
 use crate::error::AppError;
 use crate::monitor::{GpuMetrics, NetworkMetrics};
+use crate::obs::OutputStats;
+use crate::services::alerts::{Alert, AlertSeverity, MetricType};
+use crate::services::streaming_overhead::{OverheadBaseline, StreamingOverhead, StreamingOverheadSample};
+use crate::storage::config::StreamingPlatform;
+use crate::storage::paths::app_data_dir;
+use crate::storage::profiles::normalize_tags;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError};
+use tokio::sync::{Mutex, OnceCell};
+
+/// アラート履歴テーブルのスキーマ
+const ALERT_HISTORY_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS alert_history (
+        id TEXT PRIMARY KEY,
+        alert_id TEXT NOT NULL,
+        session_id TEXT,
+        metric TEXT NOT NULL,
+        severity TEXT NOT NULL,
+        message TEXT NOT NULL,
+        current_value REAL NOT NULL,
+        threshold REAL NOT NULL,
+        raised_at INTEGER NOT NULL,
+        acknowledged INTEGER NOT NULL DEFAULT 0,
+        acknowledged_at INTEGER,
+        cleared_at INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS idx_alert_history_raised_at ON alert_history (raised_at);
+    CREATE INDEX IF NOT EXISTS idx_alert_history_session ON alert_history (session_id);
+    CREATE INDEX IF NOT EXISTS idx_alert_history_raised_metric ON alert_history (raised_at, metric, severity);
+";
+
+/// セッションテーブルのスキーマ（メタデータ列は `migrate_sessions_table` で追加）
+const SESSIONS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sessions (
+        session_id TEXT PRIMARY KEY,
+        start_time INTEGER NOT NULL,
+        end_time INTEGER NOT NULL,
+        avg_cpu REAL NOT NULL DEFAULT 0,
+        avg_gpu REAL NOT NULL DEFAULT 0,
+        total_dropped_frames INTEGER NOT NULL DEFAULT 0,
+        peak_bitrate INTEGER NOT NULL DEFAULT 0,
+        quality_score REAL NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS idx_sessions_start_time ON sessions (start_time);
+    CREATE INDEX IF NOT EXISTS idx_sessions_end_time ON sessions (end_time);
+";
+
+/// セッションのメタデータ列（列名と定義）
+///
+/// 既存のデータベースには `ALTER TABLE` で追加する
+const SESSION_METADATA_COLUMNS: &[(&str, &str)] = &[
+    ("title", "TEXT"),
+    ("tags", "TEXT NOT NULL DEFAULT '[]'"),
+    ("notes", "TEXT"),
+    ("platform", "TEXT"),
+    ("avg_bitrate", "INTEGER NOT NULL DEFAULT 0"),
+    ("bytes_uploaded", "INTEGER NOT NULL DEFAULT 0"),
+];
+
+/// メトリクス（生データ）テーブルのスキーマ
+const METRICS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS metrics (
+        session_id TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        cpu_usage REAL NOT NULL,
+        cpu_frequency_mhz REAL,
+        memory_used INTEGER NOT NULL,
+        memory_total INTEGER NOT NULL,
+        gpu_usage REAL,
+        gpu_memory_used INTEGER,
+        network_upload INTEGER NOT NULL,
+        network_download INTEGER NOT NULL,
+        streaming INTEGER NOT NULL,
+        recording INTEGER NOT NULL,
+        fps REAL,
+        render_dropped_frames INTEGER,
+        output_dropped_frames INTEGER,
+        stream_bitrate INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS idx_metrics_session_time ON metrics (session_id, timestamp);
+";
+
+/// OBSの負荷の履歴と、推奨設定の適用前後の基準値テーブルのスキーマ
+const STREAMING_OVERHEAD_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS streaming_overhead (
+        session_id TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        cpu_percent REAL NOT NULL,
+        gpu_percent REAL,
+        encoder_percent REAL,
+        memory_mb REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_streaming_overhead_session_time ON streaming_overhead (session_id, timestamp);
+    CREATE TABLE IF NOT EXISTS overhead_baselines (
+        baseline_id TEXT PRIMARY KEY,
+        applied_at INTEGER NOT NULL,
+        before_cpu_percent REAL NOT NULL,
+        before_gpu_percent REAL,
+        before_encoder_percent REAL,
+        before_memory_mb REAL NOT NULL,
+        after_cpu_percent REAL,
+        after_gpu_percent REAL,
+        after_encoder_percent REAL,
+        after_memory_mb REAL
+    );
+    CREATE INDEX IF NOT EXISTS idx_overhead_baselines_applied_at ON overhead_baselines (applied_at);
+";
+
+/// メトリクス行の取得列
+const METRICS_COLUMNS: &str = "session_id, timestamp, cpu_usage, cpu_frequency_mhz, memory_used,
+    memory_total, gpu_usage, gpu_memory_used, network_upload, network_download, streaming, recording,
+    fps, render_dropped_frames, output_dropped_frames, stream_bitrate";
+
+/// ロールアップで平均・最小・最大を集計する列
+///
+/// NULL可の列があるため、列ごとにサンプル数を数える
+const ROLLUP_SERIES: [&str; 9] = [
+    "cpu_usage",
+    "cpu_frequency_mhz",
+    "memory_used",
+    "gpu_usage",
+    "gpu_memory_used",
+    "network_upload",
+    "network_download",
+    "fps",
+    "stream_bitrate",
+];
+
+/// ロールアップで最大値のみを保持する列（累積値・フラグ）
+const ROLLUP_MAX_COLUMNS: [&str; 5] = [
+    "memory_total",
+    "streaming",
+    "recording",
+    "render_dropped_frames",
+    "output_dropped_frames",
+];
+
+/// `get_metrics_range` で解像度を省略した場合の点数の上限
+pub const DEFAULT_METRICS_MAX_POINTS: u32 = 1_000;
+
+/// セッション行の取得列
+const SESSION_COLUMNS: &str = "session_id, start_time, end_time, avg_cpu, avg_gpu,
+    total_dropped_frames, peak_bitrate, quality_score, title, tags, notes, platform, avg_bitrate,
+    bytes_uploaded";
 
 /// 履歴メトリクス（保存用）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +173,9 @@ pub struct HistoricalMetrics {
 pub struct SystemMetricsSnapshot {
     /// CPU使用率（%）
     pub cpu_usage: f32,
+    /// CPUクロック周波数（MHz）
+    #[serde(default)]
+    pub cpu_frequency_mhz: Option<f64>,
     /// メモリ使用量（バイト）
     pub memory_used: u64,
     /// メモリ総容量（バイト）
@@ -60,6 +206,9 @@ pub struct ObsStatusSnapshot {
     pub output_dropped_frames: Option<u64>,
     /// 配信ビットレート（kbps）
     pub stream_bitrate: Option<u64>,
+    /// 配信出力の統計（配信中にOBSから取得した場合のみ。DBには保存しない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_stats: Option<OutputStats>,
 }
 
 /// セッションサマリー（統計情報）
@@ -82,18 +231,296 @@ pub struct SessionSummary {
     pub peak_bitrate: u64,
     /// 品質スコア（0-100）
     pub quality_score: f64,
+    /// タイトル（例: 「新しいNVENCプリセットを試した」）
+    #[serde(default)]
+    pub title: Option<String>,
+    /// タグ
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// メモ
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// 配信プラットフォーム（記録されていない場合はNone）
+    #[serde(default)]
+    pub platform: Option<StreamingPlatform>,
+    /// 平均ビットレート（kbps、記録されていない場合は0）
+    #[serde(default)]
+    pub avg_bitrate: u64,
+    /// セッション中のアップロード量（バイト、記録されていない場合は0）
+    #[serde(default)]
+    pub bytes_uploaded: u64,
+}
+
+impl SessionSummary {
+    /// セッションの長さ（秒）
+    pub const fn duration_secs(&self) -> i64 {
+        self.end_time - self.start_time
+    }
+
+    /// データ量の算出に使うビットレート（kbps）
+    ///
+    /// 平均ビットレートが記録されていない古いセッションはピークビットレートで代用する
+    pub const fn effective_bitrate(&self) -> u64 {
+        if self.avg_bitrate > 0 {
+            self.avg_bitrate
+        } else {
+            self.peak_bitrate
+        }
+    }
+
+    /// 指定期間 `[from, to)` と重なる秒数
+    pub fn overlap_secs(&self, from: i64, to: i64) -> i64 {
+        (self.end_time.min(to) - self.start_time.max(from)).max(0)
+    }
+}
+
+/// セッション一覧の絞り込み条件
+///
+/// 指定された条件をすべて満たすセッションのみを返す
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionFilter {
+    /// タグ（完全一致）
+    pub tag: Option<String>,
+    /// 開始時刻の下限（UNIX epoch秒）
+    pub from: Option<i64>,
+    /// 開始時刻の上限（UNIX epoch秒）
+    pub to: Option<i64>,
+    /// 最小の長さ（秒）
+    pub min_duration_secs: Option<i64>,
+}
+
+impl SessionFilter {
+    /// 条件に一致するか
+    pub fn matches(&self, session: &SessionSummary) -> bool {
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| session.tags.iter().any(|t| t == tag))
+            && self.from.is_none_or(|from| session.start_time >= from)
+            && self.to.is_none_or(|to| session.start_time <= to)
+            && self
+                .min_duration_secs
+                .is_none_or(|min| session.duration_secs() >= min)
+    }
+}
+
+/// アラート履歴エントリ
+///
+/// 発生したアラート1件ごとに記録され、確認・解消の状態を保持する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertHistoryEntry {
+    /// 履歴ID（発生ごとに一意）
+    pub id: String,
+    /// 元になったアラートのID（`Alert.id`）
+    pub alert_id: String,
+    /// 発生時のセッションID（セッション外の場合はNone）
+    pub session_id: Option<String>,
+    /// メトリクス種別
+    pub metric: MetricType,
+    /// 重要度
+    pub severity: AlertSeverity,
+    /// メッセージ
+    pub message: String,
+    /// 発生時の値
+    pub current_value: f64,
+    /// 閾値
+    pub threshold: f64,
+    /// 発生時刻（UNIX epoch秒）
+    pub raised_at: i64,
+    /// ユーザーが確認済みか
+    pub acknowledged: bool,
+    /// 確認時刻（UNIX epoch秒）
+    pub acknowledged_at: Option<i64>,
+    /// 解消時刻（UNIX epoch秒、継続中の場合はNone）
+    pub cleared_at: Option<i64>,
+}
+
+/// セッション内のアラート集計（メトリクス・重要度ごと）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertCount {
+    /// メトリクス種別
+    pub metric: MetricType,
+    /// 重要度
+    pub severity: AlertSeverity,
+    /// 発生件数
+    pub count: u32,
+    /// セッションレポート用の表示文（例: 「クリティカルなCPUアラート 3件」）
+    pub summary: String,
+}
+
+impl AlertCount {
+    /// 集計結果を作成
+    pub fn new(metric: MetricType, severity: AlertSeverity, count: u32) -> Self {
+        let severity_text = match severity {
+            AlertSeverity::Critical => "クリティカルな",
+            AlertSeverity::Warning => "警告レベルの",
+            AlertSeverity::Info => "情報レベルの",
+            AlertSeverity::Tips => "ヒントレベルの",
+        };
+        let metric_text = match metric {
+            MetricType::CpuUsage => "CPU",
+            MetricType::GpuUsage => "GPU",
+            MetricType::MemoryUsage => "メモリ",
+            MetricType::FrameDropRate => "フレームドロップ",
+            MetricType::NetworkBandwidth => "ネットワーク",
+            MetricType::MicrophoneSilence => "マイク",
+            MetricType::DataQuota => "データ通信量",
+        };
+        Self {
+            metric,
+            severity,
+            count,
+            summary: format!("{severity_text}{metric_text}アラート {count}件"),
+        }
+    }
+}
+
+/// メトリクスの時間解像度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MetricsResolution {
+    /// 生データ（保存したサンプルそのまま）
+    Raw,
+    /// 1分ごとの集計
+    Minute,
+    /// 1時間ごとの集計
+    Hour,
+}
+
+impl MetricsResolution {
+    /// 細かい順の全解像度
+    pub const ALL: [Self; 3] = [Self::Raw, Self::Minute, Self::Hour];
+
+    /// 集計単位（秒）。生データの場合はNone
+    pub const fn bucket_secs(self) -> Option<i64> {
+        match self {
+            Self::Raw => None,
+            Self::Minute => Some(60),
+            Self::Hour => Some(3_600),
+        }
+    }
+
+    /// データを保持するテーブル名
+    const fn table(self) -> &'static str {
+        match self {
+            Self::Raw => "metrics",
+            Self::Minute => "metrics_rollup_minute",
+            Self::Hour => "metrics_rollup_hour",
+        }
+    }
+
+    /// タイムスタンプが属する集計区間の開始時刻
+    pub fn bucket_start(self, timestamp: i64) -> i64 {
+        self.bucket_secs()
+            .map_or(timestamp, |secs| timestamp.div_euclid(secs) * secs)
+    }
+}
+
+/// 1系列の集計値
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricAggregate {
+    /// 平均
+    pub avg: f64,
+    /// 最小
+    pub min: f64,
+    /// 最大
+    pub max: f64,
+}
+
+/// 集計区間ごとのメトリクス（分・時間単位のロールアップ）
+///
+/// 値が記録されていない系列（GPU非搭載・OBS未接続等）はNone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsRollup {
+    /// セッションID
+    pub session_id: String,
+    /// 集計区間の開始時刻（UNIX epoch秒）
+    pub bucket_start: i64,
+    /// 区間内のサンプル数
+    pub sample_count: u64,
+    /// CPU使用率（%）
+    pub cpu_usage: Option<MetricAggregate>,
+    /// CPUクロック周波数（MHz）
+    pub cpu_frequency_mhz: Option<MetricAggregate>,
+    /// メモリ使用量（バイト）
+    pub memory_used: Option<MetricAggregate>,
+    /// GPU使用率（%）
+    pub gpu_usage: Option<MetricAggregate>,
+    /// GPUメモリ使用量（バイト）
+    pub gpu_memory_used: Option<MetricAggregate>,
+    /// アップロード速度（バイト/秒）
+    pub network_upload: Option<MetricAggregate>,
+    /// ダウンロード速度（バイト/秒）
+    pub network_download: Option<MetricAggregate>,
+    /// FPS
+    pub fps: Option<MetricAggregate>,
+    /// 配信ビットレート（kbps）
+    pub stream_bitrate: Option<MetricAggregate>,
+    /// メモリ総容量（バイト、区間内の最大）
+    pub memory_total: u64,
+    /// 区間内に配信していた時間があるか
+    pub streaming: bool,
+    /// 区間内に録画していた時間があるか
+    pub recording: bool,
+    /// レンダリングドロップフレーム（累積値の区間内の最大）
+    pub render_dropped_frames: Option<u64>,
+    /// 出力ドロップフレーム（累積値の区間内の最大）
+    pub output_dropped_frames: Option<u64>,
+}
+
+impl MetricsRollup {
+    /// グラフ表示用に平均値の履歴メトリクスへ変換
+    ///
+    /// タイムスタンプは集計区間の開始時刻、ドロップフレームは区間内の最大（累積値）とする
+    pub fn to_historical(&self) -> HistoricalMetrics {
+        let avg = |aggregate: Option<MetricAggregate>| aggregate.map(|a| a.avg);
+        HistoricalMetrics {
+            timestamp: self.bucket_start,
+            session_id: self.session_id.clone(),
+            system: SystemMetricsSnapshot {
+                cpu_usage: avg(self.cpu_usage).unwrap_or(0.0) as f32,
+                cpu_frequency_mhz: avg(self.cpu_frequency_mhz),
+                memory_used: avg(self.memory_used).unwrap_or(0.0) as u64,
+                memory_total: self.memory_total,
+                gpu_usage: avg(self.gpu_usage).map(|v| v as f32),
+                gpu_memory_used: avg(self.gpu_memory_used).map(|v| v as u64),
+                network_upload: avg(self.network_upload).unwrap_or(0.0) as u64,
+                network_download: avg(self.network_download).unwrap_or(0.0) as u64,
+            },
+            obs: ObsStatusSnapshot {
+                streaming: self.streaming,
+                recording: self.recording,
+                fps: avg(self.fps).map(|v| v as f32),
+                render_dropped_frames: self.render_dropped_frames,
+                output_dropped_frames: self.output_dropped_frames,
+                stream_bitrate: avg(self.stream_bitrate).map(|v| v as u64),
+                output_stats: None,
+            },
+        }
+    }
 }
 
-/// メトリクス履歴ストア（将来のSQLite永続化で使用予定）
-#[allow(dead_code)]
+/// メトリクス履歴ストア
+///
+/// セッション・メトリクス（生データとロールアップ）・アラート履歴・OBSの負荷をSQLiteに保存する。
+/// 接続はストアごとに1つだけ開き、テーブルの作成・移行は接続を開いたときに1回だけ行う
 pub struct MetricsHistoryStore {
     /// データベースファイルパス
     db_path: PathBuf,
+    /// データベース接続（初回の操作で開き、以降は使い回す）
+    ///
+    /// SQLiteの操作は `spawn_blocking` 上で行うため、同期のMutexで保護する
+    connection: Arc<std::sync::Mutex<Option<Connection>>>,
+    /// 初期化（ディレクトリ・テーブルの作成）が済んだか
+    initialized: OnceCell<()>,
     /// 現在のセッションID
     current_session_id: Arc<Mutex<Option<String>>>,
 }
 
-#[allow(dead_code)]
 impl MetricsHistoryStore {
     /// 新しいストアを作成
     ///
@@ -102,43 +529,260 @@ impl MetricsHistoryStore {
     pub fn new(db_path: PathBuf) -> Self {
         Self {
             db_path,
+            connection: Arc::new(std::sync::Mutex::new(None)),
+            initialized: OnceCell::new(),
             current_session_id: Arc::new(Mutex::new(None)),
         }
     }
 
     /// データベースを初期化
     ///
-    /// テーブルが存在しない場合は作成する
+    /// ディレクトリを作成して接続を開き、テーブルの作成・移行を行う。
+    /// 2回目以降の呼び出しではデータベースにアクセスしない（失敗した場合は次の呼び出しで再試行する）
     pub async fn initialize(&self) -> Result<(), AppError> {
-        // 現時点ではファイルシステムベースの実装
-        // 将来的にSQLite統合時に実装を追加
-
-        // データベースディレクトリを作成
-        if let Some(parent) = self.db_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| AppError::database_error(&format!("Failed to create database directory: {e}")))?;
-        }
-
+        self.initialized
+            .get_or_try_init(|| async {
+                if let Some(parent) = self.db_path.parent() {
+                    tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                        AppError::database_error(&format!("Failed to create database directory: {e}"))
+                    })?;
+                }
+                self.with_connection(|_| Ok(())).await
+            })
+            .await?;
         Ok(())
     }
 
+    /// 現在のセッションIDを取得
+    pub async fn current_session_id(&self) -> Option<String> {
+        self.current_session_id.lock().await.clone()
+    }
+
     /// 新しいセッションを開始
     ///
     /// # Returns
     /// セッションID
     pub async fn start_session(&self) -> Result<String, AppError> {
-        let session_id = format!("session_{}", chrono::Utc::now().timestamp());
+        let now = chrono::Utc::now().timestamp();
+        let session_id = format!("session_{now}");
+
+        let id = session_id.clone();
+        self.with_connection(move |conn| {
+            conn.execute(
+                "INSERT OR IGNORE INTO sessions (session_id, start_time, end_time) VALUES (?1, ?2, ?2)",
+                params![id, now],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to insert session: {e}")))?;
+            Ok(())
+        })
+        .await?;
+
         let mut current = self.current_session_id.lock().await;
         *current = Some(session_id.clone());
         Ok(session_id)
     }
 
     /// 現在のセッションを終了
+    ///
+    /// セッション中に保存したメトリクスから平均CPU・GPU使用率、ドロップフレーム数、
+    /// ピークビットレートを集計して記録する（メトリクスがない項目は変更しない）
     pub async fn end_session(&self) -> Result<(), AppError> {
         let mut current = self.current_session_id.lock().await;
-        *current = None;
-        Ok(())
+        let Some(session_id) = current.take() else {
+            return Ok(());
+        };
+        drop(current);
+
+        let now = chrono::Utc::now().timestamp();
+        self.with_connection(move |conn| {
+            // OBSのドロップフレーム数は累積値のため、セッション中の増加分を集計する
+            conn.execute(
+                "UPDATE sessions SET
+                    end_time = ?2,
+                    avg_cpu = COALESCE((SELECT AVG(cpu_usage) FROM metrics WHERE session_id = ?1), avg_cpu),
+                    avg_gpu = COALESCE((SELECT AVG(gpu_usage) FROM metrics WHERE session_id = ?1), avg_gpu),
+                    total_dropped_frames = COALESCE((
+                        SELECT (MAX(COALESCE(render_dropped_frames, 0)) - MIN(COALESCE(render_dropped_frames, 0)))
+                             + (MAX(COALESCE(output_dropped_frames, 0)) - MIN(COALESCE(output_dropped_frames, 0)))
+                        FROM metrics WHERE session_id = ?1
+                    ), total_dropped_frames),
+                    peak_bitrate = COALESCE((SELECT MAX(stream_bitrate) FROM metrics WHERE session_id = ?1), peak_bitrate)
+                 WHERE session_id = ?1",
+                params![session_id, now],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to end session: {e}")))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// セッションを取得
+    ///
+    /// # Returns
+    /// セッションが存在しない場合はNone
+    pub async fn get_session(&self, session_id: &str) -> Result<Option<SessionSummary>, AppError> {
+        let session_id = session_id.to_string();
+
+        self.with_connection(move |conn| {
+            conn.query_row(
+                &format!("SELECT {SESSION_COLUMNS} FROM sessions WHERE session_id = ?1"),
+                params![session_id],
+                read_session,
+            )
+            .optional()
+            .map_err(|e| AppError::database_error(&format!("Failed to read session: {e}")))
+        })
+        .await
+    }
+
+    /// 条件に一致するセッション一覧を取得（開始時刻の新しい順）
+    pub async fn list_sessions(&self, filter: &SessionFilter) -> Result<Vec<SessionSummary>, AppError> {
+        let filter = filter.clone();
+
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT {SESSION_COLUMNS} FROM sessions ORDER BY start_time DESC"
+                ))
+                .map_err(|e| AppError::database_error(&format!("Failed to query sessions: {e}")))?;
+
+            let sessions = stmt
+                .query_map([], read_session)
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read sessions: {e}")))?;
+            Ok(sessions.into_iter().filter(|s| filter.matches(s)).collect())
+        })
+        .await
+    }
+
+    /// セッションのメタデータ（タイトル・タグ・メモ）を更新
+    ///
+    /// 空白のみのタイトル・メモは未設定として扱い、タグは正規化して保存する
+    ///
+    /// # Returns
+    /// 更新後のセッション（存在しない場合はNone）
+    pub async fn update_session_metadata(
+        &self,
+        session_id: &str,
+        title: Option<String>,
+        tags: Vec<String>,
+        notes: Option<String>,
+    ) -> Result<Option<SessionSummary>, AppError> {
+        let id = session_id.to_string();
+        let title = non_blank(title);
+        let notes = non_blank(notes);
+        let tags = serde_json::to_string(&normalize_tags(tags))
+            .map_err(|e| AppError::database_error(&format!("Failed to serialize tags: {e}")))?;
+
+        let updated = self
+            .with_connection(move |conn| {
+                conn.execute(
+                    "UPDATE sessions SET title = ?2, tags = ?3, notes = ?4 WHERE session_id = ?1",
+                    params![id, title, tags, notes],
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to update session: {e}")))
+            })
+            .await?;
+
+        if updated == 0 {
+            return Ok(None);
+        }
+        self.get_session(session_id).await
+    }
+
+    /// セッションの配信情報（プラットフォーム・平均ビットレート）を記録
+    ///
+    /// # Returns
+    /// セッションが存在した場合はtrue
+    pub async fn update_session_stream_info(
+        &self,
+        session_id: &str,
+        platform: StreamingPlatform,
+        avg_bitrate: u64,
+    ) -> Result<bool, AppError> {
+        let id = session_id.to_string();
+        let platform = enum_to_text(&platform)?;
+
+        self.with_connection(move |conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE sessions SET platform = ?2, avg_bitrate = ?3 WHERE session_id = ?1",
+                    params![id, platform, avg_bitrate],
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to update session: {e}")))?;
+            Ok(updated > 0)
+        })
+        .await
+    }
+
+    /// セッション中に記録した配信ビットレートの平均（kbps）
+    ///
+    /// # Returns
+    /// ビットレートが記録されていない場合はNone
+    pub async fn average_stream_bitrate(&self, session_id: &str) -> Result<Option<u64>, AppError> {
+        let id = session_id.to_string();
+
+        self.with_connection(move |conn| {
+            let average: Option<f64> = conn
+                .query_row(
+                    "SELECT AVG(stream_bitrate) FROM metrics WHERE session_id = ?1 AND stream_bitrate IS NOT NULL",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to read bitrate: {e}")))?;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let average = average.map(|value| value.round() as u64);
+            Ok(average)
+        })
+        .await
+    }
+
+    /// 現在のセッションのアップロード量に加算
+    ///
+    /// # Returns
+    /// 加算したセッションのID（セッション外の場合はNone）
+    pub async fn add_bytes_uploaded(&self, bytes: u64) -> Result<Option<String>, AppError> {
+        let Some(session_id) = self.current_session_id().await else {
+            return Ok(None);
+        };
+
+        let id = session_id.clone();
+        self.with_connection(move |conn| {
+            conn.execute(
+                "UPDATE sessions SET bytes_uploaded = bytes_uploaded + ?2 WHERE session_id = ?1",
+                params![id, bytes],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to update session: {e}")))?;
+            Ok(())
+        })
+        .await?;
+        Ok(Some(session_id))
+    }
+
+    /// 指定期間 `[from, to)` と重なるセッションを取得（開始時刻の古い順）
+    ///
+    /// 期間の境界をまたぐセッションも含む
+    pub async fn list_sessions_overlapping(
+        &self,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<SessionSummary>, AppError> {
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT {SESSION_COLUMNS} FROM sessions
+                     WHERE start_time < ?2 AND end_time >= ?1
+                     ORDER BY start_time"
+                ))
+                .map_err(|e| AppError::database_error(&format!("Failed to query sessions: {e}")))?;
+
+            let sessions = stmt
+                .query_map(params![from, to], read_session)
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read sessions: {e}")))?;
+            Ok(sessions)
+        })
+        .await
     }
 
     /// メトリクスを保存
@@ -163,9 +807,6 @@ impl MetricsHistoryStore {
             obs,
         };
 
-        // TODO: SQLite実装後、ここでデータベースに保存
-        // 現在はメモリ内のみで保持（Phase 2b初期実装）
-
         // デバッグログ
         tracing::debug!(
             target: "metrics",
@@ -175,26 +816,167 @@ impl MetricsHistoryStore {
             "Saved metrics"
         );
 
-        // metricsは将来使用予定
-        let _ = metrics;
+        self.insert_metrics(metrics).await
+    }
 
-        Ok(())
+    /// 履歴メトリクスを保存し、分・時間単位のロールアップを更新
+    ///
+    /// 生データの挿入とロールアップの更新は同じトランザクションで行う
+    pub async fn insert_metrics(&self, metrics: HistoricalMetrics) -> Result<(), AppError> {
+        self.with_connection(move |conn| {
+            let tx = conn
+                .unchecked_transaction()
+                .map_err(|e| AppError::database_error(&format!("Failed to begin transaction: {e}")))?;
+            insert_raw_metrics(&tx, &metrics)?;
+            for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+                upsert_rollup(&tx, resolution, &metrics)?;
+            }
+            tx.commit()
+                .map_err(|e| AppError::database_error(&format!("Failed to save metrics: {e}")))
+        })
+        .await
     }
 
-    /// 指定期間のメトリクスを取得
+    /// ロールアップを生データから作り直す
+    ///
+    /// 増分更新が中断された場合等の修復用。生データは変更しない
+    pub async fn rebuild_metric_rollups(&self) -> Result<(), AppError> {
+        self.with_connection(|conn| {
+            let tx = conn
+                .unchecked_transaction()
+                .map_err(|e| AppError::database_error(&format!("Failed to begin transaction: {e}")))?;
+            for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+                rebuild_rollup(&tx, resolution)?;
+            }
+            tx.commit()
+                .map_err(|e| AppError::database_error(&format!("Failed to rebuild rollups: {e}")))
+        })
+        .await
+    }
+
+    /// 期間内の点数が上限に収まる最も細かい解像度を選択
+    ///
+    /// どの解像度でも上限を超える場合は時間単位とする
     ///
     /// # Arguments
+    /// * `session_id` - セッションID
     /// * `from` - 開始時刻（UNIX epoch秒）
     /// * `to` - 終了時刻（UNIX epoch秒）
-    #[allow(clippy::unused_async)]
+    /// * `max_points` - 点数の上限
+    pub async fn select_metrics_resolution(
+        &self,
+        session_id: &str,
+        from: i64,
+        to: i64,
+        max_points: u32,
+    ) -> Result<MetricsResolution, AppError> {
+        let session_id = session_id.to_string();
+
+        self.with_connection(move |conn| {
+            for resolution in MetricsResolution::ALL {
+                let (column, from) = match resolution {
+                    MetricsResolution::Raw => ("timestamp", from),
+                    _ => ("bucket_start", resolution.bucket_start(from)),
+                };
+                let points: u32 = conn
+                    .query_row(
+                        &format!(
+                            "SELECT COUNT(*) FROM {} WHERE session_id = ?1 AND {column} BETWEEN ?2 AND ?3",
+                            resolution.table()
+                        ),
+                        params![session_id, from, to],
+                        |row| row.get(0),
+                    )
+                    .map_err(|e| AppError::database_error(&format!("Failed to count metrics: {e}")))?;
+                if points <= max_points {
+                    return Ok(resolution);
+                }
+            }
+            Ok(MetricsResolution::Hour)
+        })
+        .await
+    }
+
+    /// 指定期間のメトリクスを取得（古い順）
+    ///
+    /// 分・時間単位の場合は集計区間ごとの平均値を返す（`MetricsRollup::to_historical`）
+    ///
+    /// # Arguments
+    /// * `session_id` - セッションID
+    /// * `from` - 開始時刻（UNIX epoch秒）
+    /// * `to` - 終了時刻（UNIX epoch秒）
+    /// * `resolution` - 時間解像度
     pub async fn get_metrics_range(
         &self,
-        _from: i64,
-        _to: i64,
+        session_id: &str,
+        from: i64,
+        to: i64,
+        resolution: MetricsResolution,
     ) -> Result<Vec<HistoricalMetrics>, AppError> {
-        // TODO: SQLite実装後、データベースから取得
-        // 現在は空のベクタを返す
-        Ok(Vec::new())
+        if resolution != MetricsResolution::Raw {
+            let rollups = self.get_metric_rollups(session_id, from, to, resolution).await?;
+            return Ok(rollups.iter().map(MetricsRollup::to_historical).collect());
+        }
+
+        let session_id = session_id.to_string();
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT {METRICS_COLUMNS} FROM metrics
+                     WHERE session_id = ?1 AND timestamp BETWEEN ?2 AND ?3
+                     ORDER BY timestamp"
+                ))
+                .map_err(|e| AppError::database_error(&format!("Failed to query metrics: {e}")))?;
+
+            let metrics = stmt
+                .query_map(params![session_id, from, to], read_metrics)
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read metrics: {e}")))?;
+            Ok(metrics)
+        })
+        .await
+    }
+
+    /// 指定期間と重なる集計区間のロールアップを取得（古い順）
+    ///
+    /// 生データ（`Raw`）を指定した場合は空を返す
+    ///
+    /// # Arguments
+    /// * `session_id` - セッションID
+    /// * `from` - 開始時刻（UNIX epoch秒）
+    /// * `to` - 終了時刻（UNIX epoch秒）
+    /// * `resolution` - 時間解像度
+    pub async fn get_metric_rollups(
+        &self,
+        session_id: &str,
+        from: i64,
+        to: i64,
+        resolution: MetricsResolution,
+    ) -> Result<Vec<MetricsRollup>, AppError> {
+        if resolution == MetricsResolution::Raw {
+            return Ok(Vec::new());
+        }
+
+        let session_id = session_id.to_string();
+        let from = resolution.bucket_start(from);
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT session_id, bucket_start, {} FROM {}
+                     WHERE session_id = ?1 AND bucket_start BETWEEN ?2 AND ?3
+                     ORDER BY bucket_start",
+                    rollup_value_columns().join(", "),
+                    resolution.table()
+                ))
+                .map_err(|e| AppError::database_error(&format!("Failed to query rollups: {e}")))?;
+
+            let rollups = stmt
+                .query_map(params![session_id, from, to], read_rollup)
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read rollups: {e}")))?;
+            Ok(rollups)
+        })
+        .await
     }
 
     /// セッションサマリーを取得
@@ -214,6 +996,12 @@ impl MetricsHistoryStore {
             total_dropped_frames: 0,
             peak_bitrate: 6000,
             quality_score: 85.0,
+            title: None,
+            tags: Vec::new(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded: 0,
         })
     }
 
@@ -223,82 +1011,892 @@ impl MetricsHistoryStore {
         // TODO: SQLite実装後、データベースから取得
         Ok(Vec::new())
     }
-}
 
-/// SystemMetricsSnapshotを作成するヘルパー
-impl SystemMetricsSnapshot {
-    /// システムメトリクスから作成
-    pub fn from_metrics(
-        cpu_usage: f32,
-        memory_used: u64,
-        memory_total: u64,
-        gpu: Option<&GpuMetrics>,
-        network: &NetworkMetrics,
-    ) -> Self {
-        Self {
-            cpu_usage,
-            memory_used,
-            memory_total,
-            gpu_usage: gpu.map(|g| g.usage_percent),
-            gpu_memory_used: gpu.map(|g| g.memory_used_bytes),
-            network_upload: network.upload_bytes_per_sec,
-            network_download: network.download_bytes_per_sec,
-        }
-    }
-}
+    /// 発生したアラートを記録
+    ///
+    /// 現在のセッションに紐付けて保存する
+    ///
+    /// # Returns
+    /// 履歴ID
+    pub async fn record_alert(&self, alert: &Alert) -> Result<String, AppError> {
+        let entry_id = uuid::Uuid::new_v4().to_string();
+        let session_id = self.current_session_id().await;
+        let alert = alert.clone();
+        let id = entry_id.clone();
 
-/// ObsStatusSnapshotを作成するヘルパー（将来のOBS状態追跡で使用予定）
-#[allow(dead_code)]
-impl ObsStatusSnapshot {
-    /// 空のスナップショットを作成
-    pub fn empty() -> Self {
-        Self {
-            streaming: false,
-            recording: false,
-            fps: None,
-            render_dropped_frames: None,
-            output_dropped_frames: None,
-            stream_bitrate: None,
-        }
-    }
+        self.with_connection(move |conn| {
+            conn.execute(
+                "INSERT INTO alert_history
+                    (id, alert_id, session_id, metric, severity, message, current_value, threshold, raised_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    alert.id,
+                    session_id,
+                    enum_to_text(&alert.metric)?,
+                    enum_to_text(&alert.severity)?,
+                    alert.message,
+                    alert.current_value,
+                    alert.threshold,
+                    i64::try_from(alert.timestamp).unwrap_or(i64::MAX),
+                ],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to record alert: {e}")))?;
+            Ok(())
+        })
+        .await?;
 
-    /// OBS接続時のスナップショットを作成
-    pub fn from_obs_status(
-        streaming: bool,
-        recording: bool,
-        fps: Option<f32>,
-        render_dropped: Option<u64>,
-        output_dropped: Option<u64>,
-        bitrate: Option<u64>,
-    ) -> Self {
-        Self {
-            streaming,
-            recording,
-            fps,
-            render_dropped_frames: render_dropped,
-            output_dropped_frames: output_dropped,
-            stream_bitrate: bitrate,
-        }
+        Ok(entry_id)
     }
-}
 
-#[cfg(test)]
-#[allow(clippy::unwrap_used)]
-mod tests {
-    use super::*;
+    /// アラートを確認済みにする
+    ///
+    /// 履歴IDに一致するエントリ、またはアラートIDに一致する未解消のエントリを対象とする
+    ///
+    /// # Returns
+    /// 対象が存在した場合はtrue
+    pub async fn acknowledge_alert(&self, id: &str) -> Result<bool, AppError> {
+        let id = id.to_string();
+        let now = chrono::Utc::now().timestamp();
 
-    #[tokio::test]
-    async fn test_store_creation() {
-        let store = MetricsHistoryStore::new(PathBuf::from("/tmp/test_metrics.db"));
-        assert!(store.initialize().await.is_ok());
+        self.with_connection(move |conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE alert_history
+                     SET acknowledged = 1, acknowledged_at = COALESCE(acknowledged_at, ?2)
+                     WHERE id = ?1 OR (alert_id = ?1 AND cleared_at IS NULL)",
+                    params![id, now],
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to acknowledge alert: {e}")))?;
+            Ok(updated > 0)
+        })
+        .await
     }
 
-    #[tokio::test]
-    async fn test_session_management() {
-        let store = MetricsHistoryStore::new(PathBuf::from("/tmp/test_metrics.db"));
-        store.initialize().await.unwrap();
+    /// アラートの解消を記録
+    ///
+    /// # Arguments
+    /// * `alert_id` - 元になったアラートのID（`Alert.id`）
+    /// * `cleared_at` - 解消時刻（UNIX epoch秒）
+    pub async fn clear_alert(&self, alert_id: &str, cleared_at: i64) -> Result<(), AppError> {
+        let alert_id = alert_id.to_string();
 
-        let session_id = store.start_session().await.unwrap();
+        self.with_connection(move |conn| {
+            conn.execute(
+                "UPDATE alert_history SET cleared_at = ?2 WHERE alert_id = ?1 AND cleared_at IS NULL",
+                params![alert_id, cleared_at],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to clear alert: {e}")))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// 未解消のアラートをすべて解消済みにする
+    ///
+    /// # Arguments
+    /// * `cleared_at` - 解消時刻（UNIX epoch秒）
+    pub async fn clear_all_open_alerts(&self, cleared_at: i64) -> Result<(), AppError> {
+        self.with_connection(move |conn| {
+            conn.execute(
+                "UPDATE alert_history SET cleared_at = ?1 WHERE cleared_at IS NULL",
+                params![cleared_at],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to clear alerts: {e}")))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// 指定期間に発生したアラート履歴を取得（新しい順）
+    ///
+    /// # Arguments
+    /// * `from` - 開始時刻（UNIX epoch秒）
+    /// * `to` - 終了時刻（UNIX epoch秒）
+    pub async fn get_alert_history(&self, from: i64, to: i64) -> Result<Vec<AlertHistoryEntry>, AppError> {
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, alert_id, session_id, metric, severity, message, current_value,
+                            threshold, raised_at, acknowledged, acknowledged_at, cleared_at
+                     FROM alert_history
+                     WHERE raised_at BETWEEN ?1 AND ?2
+                     ORDER BY raised_at DESC",
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to query alert history: {e}")))?;
+
+            let entries = stmt
+                .query_map(params![from, to], read_alert_entry)
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read alert history: {e}")))?;
+            Ok(entries)
+        })
+        .await
+    }
+
+    /// セッション内で発生したアラートをメトリクス・重要度ごとに集計
+    ///
+    /// # Arguments
+    /// * `session_id` - セッションID
+    pub async fn count_session_alerts(&self, session_id: &str) -> Result<Vec<AlertCount>, AppError> {
+        let session_id = session_id.to_string();
+
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT metric, severity, COUNT(*) FROM alert_history
+                     WHERE session_id = ?1
+                     GROUP BY metric, severity
+                     ORDER BY COUNT(*) DESC, metric, severity",
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to count session alerts: {e}")))?;
+
+            let counts = stmt
+                .query_map(params![session_id], |row| {
+                    Ok(AlertCount::new(read_enum(row, 0)?, read_enum(row, 1)?, row.get(2)?))
+                })
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read alert counts: {e}")))?;
+            Ok(counts)
+        })
+        .await
+    }
+
+    /// 期間内に発生したアラートをメトリクス・重要度ごとに集計
+    ///
+    /// `(raised_at, metric, severity)` のインデックスのみで集計できるため、
+    /// 履歴が大きくてもテーブル本体を読まない
+    ///
+    /// # Arguments
+    /// * `from` - 開始時刻（UNIX epoch秒、含む）
+    /// * `to` - 終了時刻（UNIX epoch秒、含まない）
+    pub async fn count_alerts_in_range(&self, from: i64, to: i64) -> Result<Vec<AlertCount>, AppError> {
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT metric, severity, COUNT(*) FROM alert_history
+                     WHERE raised_at >= ?1 AND raised_at < ?2
+                     GROUP BY metric, severity
+                     ORDER BY COUNT(*) DESC, metric, severity",
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to count alerts: {e}")))?;
+
+            let counts = stmt
+                .query_map(params![from, to], |row| {
+                    Ok(AlertCount::new(read_enum(row, 0)?, read_enum(row, 1)?, row.get(2)?))
+                })
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read alert counts: {e}")))?;
+            Ok(counts)
+        })
+        .await
+    }
+
+    /// OBSの負荷のサンプルを保存
+    ///
+    /// # Arguments
+    /// * `session_id` - セッションID
+    /// * `timestamp` - 計測時刻（UNIX epoch秒）
+    /// * `overhead` - OBSの負荷
+    pub async fn insert_overhead_sample(
+        &self,
+        session_id: String,
+        timestamp: i64,
+        overhead: StreamingOverhead,
+    ) -> Result<(), AppError> {
+        self.with_connection(move |conn| {
+            conn.execute(
+                "INSERT INTO streaming_overhead
+                 (session_id, timestamp, cpu_percent, gpu_percent, encoder_percent, memory_mb)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    session_id,
+                    timestamp,
+                    overhead.cpu_percent,
+                    overhead.gpu_percent,
+                    overhead.encoder_percent,
+                    overhead.memory_mb,
+                ],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to insert streaming overhead: {e}")))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// セッションのOBSの負荷の履歴を取得（計測時刻の古い順）
+    pub async fn get_session_overhead(&self, session_id: &str) -> Result<Vec<StreamingOverheadSample>, AppError> {
+        let session_id = session_id.to_string();
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT timestamp, cpu_percent, gpu_percent, encoder_percent, memory_mb
+                     FROM streaming_overhead WHERE session_id = ?1 ORDER BY timestamp",
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to query streaming overhead: {e}")))?;
+
+            let samples = stmt
+                .query_map(params![session_id], |row| {
+                    Ok(StreamingOverheadSample {
+                        timestamp: row.get(0)?,
+                        overhead: read_overhead(row, 1)?,
+                    })
+                })
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read streaming overhead: {e}")))?;
+            Ok(samples)
+        })
+        .await
+    }
+
+    /// 推奨設定の適用前の基準値を保存
+    pub async fn insert_overhead_baseline(&self, baseline: OverheadBaseline) -> Result<(), AppError> {
+        self.with_connection(move |conn| {
+            let before = &baseline.before;
+            conn.execute(
+                "INSERT INTO overhead_baselines
+                 (baseline_id, applied_at, before_cpu_percent, before_gpu_percent, before_encoder_percent, before_memory_mb)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    baseline.baseline_id,
+                    baseline.applied_at,
+                    before.cpu_percent,
+                    before.gpu_percent,
+                    before.encoder_percent,
+                    before.memory_mb,
+                ],
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to insert overhead baseline: {e}")))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// 基準値に適用後の値を記録
+    ///
+    /// # Returns
+    /// 基準値が存在した場合はtrue
+    pub async fn set_overhead_after(&self, baseline_id: &str, after: StreamingOverhead) -> Result<bool, AppError> {
+        let baseline_id = baseline_id.to_string();
+        self.with_connection(move |conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE overhead_baselines SET after_cpu_percent = ?2, after_gpu_percent = ?3,
+                     after_encoder_percent = ?4, after_memory_mb = ?5 WHERE baseline_id = ?1",
+                    params![
+                        baseline_id,
+                        after.cpu_percent,
+                        after.gpu_percent,
+                        after.encoder_percent,
+                        after.memory_mb,
+                    ],
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to update overhead baseline: {e}")))?;
+            Ok(updated > 0)
+        })
+        .await
+    }
+
+    /// 基準値を取得（適用日時の新しい順）
+    ///
+    /// # Arguments
+    /// * `limit` - 取得する件数の上限
+    pub async fn list_overhead_baselines(&self, limit: u32) -> Result<Vec<OverheadBaseline>, AppError> {
+        self.with_connection(move |conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT baseline_id, applied_at, before_cpu_percent, before_gpu_percent,
+                     before_encoder_percent, before_memory_mb, after_cpu_percent, after_gpu_percent,
+                     after_encoder_percent, after_memory_mb
+                     FROM overhead_baselines ORDER BY applied_at DESC, rowid DESC LIMIT ?1",
+                )
+                .map_err(|e| AppError::database_error(&format!("Failed to query overhead baselines: {e}")))?;
+
+            let baselines = stmt
+                .query_map(params![limit], |row| {
+                    // 適用後の値はCPU使用率の有無で記録済みかを判定する
+                    let after = match row.get::<_, Option<f32>>(6)? {
+                        Some(_) => Some(read_overhead(row, 6)?),
+                        None => None,
+                    };
+                    Ok(OverheadBaseline {
+                        baseline_id: row.get(0)?,
+                        applied_at: row.get(1)?,
+                        before: read_overhead(row, 2)?,
+                        after,
+                    })
+                })
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+                .map_err(|e| AppError::database_error(&format!("Failed to read overhead baselines: {e}")))?;
+            Ok(baselines)
+        })
+        .await
+    }
+
+    /// ブロッキングスレッドでデータベース接続を使って処理を実行
+    ///
+    /// SQLiteの操作は同期I/Oのため、非同期ランタイムをブロックしないよう
+    /// `spawn_blocking` 上で実行する。接続がまだない場合だけ開いてテーブルを作成する
+    async fn with_connection<T, F>(&self, f: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, AppError> + Send + 'static,
+    {
+        let db_path = self.db_path.clone();
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || {
+            let mut guard = connection.lock().unwrap_or_else(PoisonError::into_inner);
+            let conn = match guard.take() {
+                Some(conn) => conn,
+                None => open_connection(&db_path)?,
+            };
+            f(guard.insert(conn))
+        })
+        .await
+        .map_err(|e| AppError::database_error(&format!("Database task failed: {e}")))?
+    }
+}
+
+/// データベース接続を開き、テーブルの作成・移行を行う
+fn open_connection(db_path: &Path) -> Result<Connection, AppError> {
+    let conn = Connection::open(db_path)
+        .map_err(|e| AppError::database_error(&format!("Failed to open database: {e}")))?;
+    conn.execute_batch(ALERT_HISTORY_SCHEMA)
+        .map_err(|e| AppError::database_error(&format!("Failed to create alert_history table: {e}")))?;
+    conn.execute_batch(SESSIONS_SCHEMA)
+        .map_err(|e| AppError::database_error(&format!("Failed to create sessions table: {e}")))?;
+    migrate_sessions_table(&conn)?;
+    conn.execute_batch(METRICS_SCHEMA)
+        .map_err(|e| AppError::database_error(&format!("Failed to create metrics table: {e}")))?;
+    migrate_metric_rollups(&conn)?;
+    conn.execute_batch(STREAMING_OVERHEAD_SCHEMA)
+        .map_err(|e| AppError::database_error(&format!("Failed to create streaming_overhead tables: {e}")))?;
+    Ok(conn)
+}
+
+/// ロールアップテーブルを作成
+///
+/// ロールアップ導入前に保存された生データがある場合は、作成時に集計し直す
+fn migrate_metric_rollups(conn: &Connection) -> Result<(), AppError> {
+    for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+        let table = resolution.table();
+        let exists: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+                params![table],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::database_error(&format!("Failed to inspect {table}: {e}")))?;
+        if exists {
+            continue;
+        }
+
+        conn.execute_batch(&rollup_schema(resolution))
+            .map_err(|e| AppError::database_error(&format!("Failed to create {table} table: {e}")))?;
+        rebuild_rollup(conn, resolution)?;
+        tracing::info!(target: "metrics_history", table, "ロールアップテーブルを作成しました");
+    }
+
+    Ok(())
+}
+
+/// ロールアップテーブルのスキーマ
+fn rollup_schema(resolution: MetricsResolution) -> String {
+    let series = ROLLUP_SERIES.iter().map(|name| {
+        format!(
+            "{name}_count INTEGER NOT NULL DEFAULT 0, {name}_sum REAL NOT NULL DEFAULT 0, \
+             {name}_min REAL, {name}_max REAL"
+        )
+    });
+    let maxima = ROLLUP_MAX_COLUMNS.iter().map(|name| format!("{name} INTEGER"));
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            session_id TEXT NOT NULL,
+            bucket_start INTEGER NOT NULL,
+            sample_count INTEGER NOT NULL,
+            {},
+            PRIMARY KEY (session_id, bucket_start)
+        );",
+        resolution.table(),
+        series.chain(maxima).collect::<Vec<_>>().join(",\n            ")
+    )
+}
+
+/// ロールアップテーブルの値の列（`sample_count` 以降、`read_rollup` の読み取り順）
+fn rollup_value_columns() -> Vec<String> {
+    let series = ROLLUP_SERIES.iter().flat_map(|name| {
+        [
+            format!("{name}_count"),
+            format!("{name}_sum"),
+            format!("{name}_min"),
+            format!("{name}_max"),
+        ]
+    });
+    std::iter::once("sample_count".to_string())
+        .chain(series)
+        .chain(ROLLUP_MAX_COLUMNS.iter().map(ToString::to_string))
+        .collect()
+}
+
+/// 生データを保存
+fn insert_raw_metrics(conn: &Connection, metrics: &HistoricalMetrics) -> Result<(), AppError> {
+    let HistoricalMetrics { timestamp, session_id, system, obs } = metrics;
+    conn.execute(
+        &format!(
+            "INSERT INTO metrics ({METRICS_COLUMNS})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+        ),
+        params![
+            session_id,
+            timestamp,
+            system.cpu_usage,
+            system.cpu_frequency_mhz,
+            system.memory_used,
+            system.memory_total,
+            system.gpu_usage,
+            system.gpu_memory_used,
+            system.network_upload,
+            system.network_download,
+            obs.streaming,
+            obs.recording,
+            obs.fps,
+            obs.render_dropped_frames,
+            obs.output_dropped_frames,
+            obs.stream_bitrate,
+        ],
+    )
+    .map_err(|e| AppError::database_error(&format!("Failed to insert metrics: {e}")))?;
+    Ok(())
+}
+
+/// 1サンプル分をロールアップに加算
+///
+/// 合計・サンプル数を加算し、最小・最大はNULLを無視して更新する
+fn upsert_rollup(
+    conn: &Connection,
+    resolution: MetricsResolution,
+    metrics: &HistoricalMetrics,
+) -> Result<(), AppError> {
+    use rusqlite::types::Value;
+
+    let mut values = vec![
+        Value::Text(metrics.session_id.clone()),
+        Value::Integer(resolution.bucket_start(metrics.timestamp)),
+        Value::Integer(1),
+    ];
+    for value in rollup_series_values(metrics) {
+        let nullable = value.map_or(Value::Null, Value::Real);
+        values.push(Value::Integer(i64::from(value.is_some())));
+        values.push(Value::Real(value.unwrap_or(0.0)));
+        values.push(nullable.clone());
+        values.push(nullable);
+    }
+    values.extend(
+        rollup_max_values(metrics)
+            .into_iter()
+            .map(|value| value.map_or(Value::Null, Value::Integer)),
+    );
+
+    let columns = rollup_value_columns();
+    let placeholders = (1..=values.len()).map(|i| format!("?{i}")).collect::<Vec<_>>();
+    let keep_min = |name: &str| format!("{name} = MIN(COALESCE({name}, excluded.{name}), COALESCE(excluded.{name}, {name}))");
+    let keep_max = |name: &str| format!("{name} = MAX(COALESCE({name}, excluded.{name}), COALESCE(excluded.{name}, {name}))");
+    let updates = std::iter::once("sample_count = sample_count + 1".to_string())
+        .chain(ROLLUP_SERIES.iter().flat_map(|name| {
+            [
+                format!("{name}_count = {name}_count + excluded.{name}_count"),
+                format!("{name}_sum = {name}_sum + excluded.{name}_sum"),
+                keep_min(&format!("{name}_min")),
+                keep_max(&format!("{name}_max")),
+            ]
+        }))
+        .chain(ROLLUP_MAX_COLUMNS.iter().map(|name| keep_max(name)))
+        .collect::<Vec<_>>();
+
+    conn.execute(
+        &format!(
+            "INSERT INTO {} (session_id, bucket_start, {}) VALUES ({})
+             ON CONFLICT (session_id, bucket_start) DO UPDATE SET {}",
+            resolution.table(),
+            columns.join(", "),
+            placeholders.join(", "),
+            updates.join(", ")
+        ),
+        rusqlite::params_from_iter(values),
+    )
+    .map_err(|e| AppError::database_error(&format!("Failed to update {}: {e}", resolution.table())))?;
+    Ok(())
+}
+
+/// ロールアップを生データから集計し直す
+fn rebuild_rollup(conn: &Connection, resolution: MetricsResolution) -> Result<(), AppError> {
+    let table = resolution.table();
+    let secs = resolution.bucket_secs().unwrap_or(1);
+    let aggregates = std::iter::once("COUNT(*)".to_string())
+        .chain(ROLLUP_SERIES.iter().flat_map(|name| {
+            [
+                format!("COUNT({name})"),
+                format!("TOTAL({name})"),
+                format!("MIN({name})"),
+                format!("MAX({name})"),
+            ]
+        }))
+        .chain(ROLLUP_MAX_COLUMNS.iter().map(|name| format!("MAX({name})")))
+        .collect::<Vec<_>>();
+
+    // 区間の開始時刻は `MetricsResolution::bucket_start` と同じく負の値も切り捨てる
+    conn.execute_batch(&format!(
+        "DELETE FROM {table};
+         INSERT INTO {table} (session_id, bucket_start, {})
+         SELECT session_id, timestamp - ((timestamp % {secs}) + {secs}) % {secs} AS bucket, {}
+         FROM metrics GROUP BY session_id, bucket;",
+        rollup_value_columns().join(", "),
+        aggregates.join(", ")
+    ))
+    .map_err(|e| AppError::database_error(&format!("Failed to rebuild {table}: {e}")))
+}
+
+/// ロールアップで集計する値（`ROLLUP_SERIES` の順）
+fn rollup_series_values(metrics: &HistoricalMetrics) -> [Option<f64>; 9] {
+    let HistoricalMetrics { system, obs, .. } = metrics;
+    [
+        Some(f64::from(system.cpu_usage)),
+        system.cpu_frequency_mhz,
+        Some(system.memory_used as f64),
+        system.gpu_usage.map(f64::from),
+        system.gpu_memory_used.map(|v| v as f64),
+        Some(system.network_upload as f64),
+        Some(system.network_download as f64),
+        obs.fps.map(f64::from),
+        obs.stream_bitrate.map(|v| v as f64),
+    ]
+}
+
+/// ロールアップで最大値を保持する値（`ROLLUP_MAX_COLUMNS` の順）
+fn rollup_max_values(metrics: &HistoricalMetrics) -> [Option<i64>; 5] {
+    let HistoricalMetrics { system, obs, .. } = metrics;
+    [
+        Some(system.memory_total as i64),
+        Some(i64::from(obs.streaming)),
+        Some(i64::from(obs.recording)),
+        obs.render_dropped_frames.map(|v| v as i64),
+        obs.output_dropped_frames.map(|v| v as i64),
+    ]
+}
+
+/// 行から履歴メトリクス（生データ）を読み取る
+fn read_metrics(row: &rusqlite::Row<'_>) -> rusqlite::Result<HistoricalMetrics> {
+    Ok(HistoricalMetrics {
+        session_id: row.get(0)?,
+        timestamp: row.get(1)?,
+        system: SystemMetricsSnapshot {
+            cpu_usage: row.get(2)?,
+            cpu_frequency_mhz: row.get(3)?,
+            memory_used: row.get(4)?,
+            memory_total: row.get(5)?,
+            gpu_usage: row.get(6)?,
+            gpu_memory_used: row.get(7)?,
+            network_upload: row.get(8)?,
+            network_download: row.get(9)?,
+        },
+        obs: ObsStatusSnapshot {
+            streaming: row.get(10)?,
+            recording: row.get(11)?,
+            fps: row.get(12)?,
+            render_dropped_frames: row.get(13)?,
+            output_dropped_frames: row.get(14)?,
+            stream_bitrate: row.get(15)?,
+            output_stats: None,
+        },
+    })
+}
+
+/// 行のOBSの負荷（CPU・GPU・エンコーダー・メモリの4列）を読み取る
+fn read_overhead(row: &rusqlite::Row<'_>, start: usize) -> rusqlite::Result<StreamingOverhead> {
+    Ok(StreamingOverhead {
+        cpu_percent: row.get(start)?,
+        gpu_percent: row.get(start + 1)?,
+        encoder_percent: row.get(start + 2)?,
+        memory_mb: row.get(start + 3)?,
+    })
+}
+
+/// 行からロールアップを読み取る
+fn read_rollup(row: &rusqlite::Row<'_>) -> rusqlite::Result<MetricsRollup> {
+    // 系列ごとに (サンプル数, 合計, 最小, 最大) の4列が並ぶ
+    let series = |index: usize| -> rusqlite::Result<Option<MetricAggregate>> {
+        let column = 3 + index * 4;
+        let count: i64 = row.get(column)?;
+        if count == 0 {
+            return Ok(None);
+        }
+        let sum: f64 = row.get(column + 1)?;
+        Ok(Some(MetricAggregate {
+            avg: sum / count as f64,
+            min: row.get(column + 2)?,
+            max: row.get(column + 3)?,
+        }))
+    };
+    let maxima = 3 + ROLLUP_SERIES.len() * 4;
+
+    Ok(MetricsRollup {
+        session_id: row.get(0)?,
+        bucket_start: row.get(1)?,
+        sample_count: row.get(2)?,
+        cpu_usage: series(0)?,
+        cpu_frequency_mhz: series(1)?,
+        memory_used: series(2)?,
+        gpu_usage: series(3)?,
+        gpu_memory_used: series(4)?,
+        network_upload: series(5)?,
+        network_download: series(6)?,
+        fps: series(7)?,
+        stream_bitrate: series(8)?,
+        memory_total: row.get::<_, Option<u64>>(maxima)?.unwrap_or(0),
+        streaming: row.get::<_, Option<bool>>(maxima + 1)?.unwrap_or(false),
+        recording: row.get::<_, Option<bool>>(maxima + 2)?.unwrap_or(false),
+        render_dropped_frames: row.get(maxima + 3)?,
+        output_dropped_frames: row.get(maxima + 4)?,
+    })
+}
+
+/// セッションテーブルに不足しているメタデータ列を追加
+///
+/// メタデータ導入前に作成されたデータベースの既存行は、
+/// タイトル・メモがNULL、タグが空配列になる
+fn migrate_sessions_table(conn: &Connection) -> Result<(), AppError> {
+    let columns: Vec<String> = conn
+        .prepare("PRAGMA table_info(sessions)")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(1))
+                .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        })
+        .map_err(|e| AppError::database_error(&format!("Failed to inspect sessions table: {e}")))?;
+
+    for (name, definition) in SESSION_METADATA_COLUMNS {
+        if columns.iter().any(|column| column == name) {
+            continue;
+        }
+        conn.execute_batch(&format!("ALTER TABLE sessions ADD COLUMN {name} {definition}"))
+            .map_err(|e| AppError::database_error(&format!("Failed to add sessions.{name}: {e}")))?;
+        tracing::info!(target: "metrics_history", column = name, "セッションテーブルに列を追加しました");
+    }
+
+    Ok(())
+}
+
+/// 行からセッションを読み取る
+fn read_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSummary> {
+    let tags: String = row.get(9)?;
+    let tags = serde_json::from_str(&tags).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(9, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    Ok(SessionSummary {
+        session_id: row.get(0)?,
+        start_time: row.get(1)?,
+        end_time: row.get(2)?,
+        avg_cpu: row.get(3)?,
+        avg_gpu: row.get(4)?,
+        total_dropped_frames: row.get(5)?,
+        peak_bitrate: row.get(6)?,
+        quality_score: row.get(7)?,
+        title: row.get(8)?,
+        tags,
+        notes: row.get(10)?,
+        // 未知のプラットフォーム値は未記録として扱う
+        platform: row
+            .get::<_, Option<String>>(11)?
+            .and_then(|text| enum_from_text(&text).ok()),
+        avg_bitrate: row.get(12)?,
+        bytes_uploaded: row.get(13)?,
+    })
+}
+
+/// 空白のみの文字列をNoneにする
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// 行からアラート履歴エントリを読み取る
+fn read_alert_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<AlertHistoryEntry> {
+    Ok(AlertHistoryEntry {
+        id: row.get(0)?,
+        alert_id: row.get(1)?,
+        session_id: row.get(2)?,
+        metric: read_enum(row, 3)?,
+        severity: read_enum(row, 4)?,
+        message: row.get(5)?,
+        current_value: row.get(6)?,
+        threshold: row.get(7)?,
+        raised_at: row.get(8)?,
+        acknowledged: row.get(9)?,
+        acknowledged_at: row.get(10)?,
+        cleared_at: row.get(11)?,
+    })
+}
+
+/// 列の文字列を列挙型として読み取る
+fn read_enum<T: DeserializeOwned>(row: &rusqlite::Row<'_>, idx: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(idx)?;
+    enum_from_text(&text).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// 列挙型をシリアライズ表現（camelCase）の文字列に変換
+fn enum_to_text<T: Serialize>(value: &T) -> Result<String, AppError> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => Ok(text),
+        Ok(other) => Err(AppError::database_error(&format!("Unexpected enum representation: {other}"))),
+        Err(e) => Err(AppError::database_error(&format!("Failed to serialize enum: {e}"))),
+    }
+}
+
+/// シリアライズ表現の文字列から列挙型に変換
+fn enum_from_text<T: DeserializeOwned>(text: &str) -> Result<T, AppError> {
+    serde_json::from_value(serde_json::Value::String(text.to_string()))
+        .map_err(|e| AppError::database_error(&format!("Unknown enum value '{text}': {e}")))
+}
+
+/// アラート履歴データベースのデフォルトパス
+pub fn default_history_db_path() -> Result<PathBuf, AppError> {
+    let data_dir = app_data_dir()
+        .ok_or_else(|| AppError::database_error("データディレクトリが見つかりません"))?;
+    Ok(data_dir.join("history.db"))
+}
+
+/// グローバル履歴ストア
+static METRICS_HISTORY_STORE: once_cell::sync::Lazy<Option<Arc<MetricsHistoryStore>>> =
+    once_cell::sync::Lazy::new(|| {
+        default_history_db_path()
+            .map(|path| Arc::new(MetricsHistoryStore::new(path)))
+            .ok()
+    });
+
+/// グローバル履歴ストアを取得
+pub fn get_metrics_history_store() -> Result<Arc<MetricsHistoryStore>, AppError> {
+    METRICS_HISTORY_STORE
+        .clone()
+        .ok_or_else(|| AppError::database_error("履歴データベースのパスを決定できません"))
+}
+
+/// 初期化済みの履歴ストアを取得
+///
+/// テーブルの作成・移行を済ませたグローバル履歴ストアを返す。
+/// 初期化は初回の呼び出しで1回だけ行うため、監視ティックごとに呼び出してもデータベースにはアクセスしない
+pub async fn history_store() -> Result<Arc<MetricsHistoryStore>, AppError> {
+    let store = get_metrics_history_store()?;
+    store.initialize().await?;
+    Ok(store)
+}
+
+/// SystemMetricsSnapshotを作成するヘルパー
+impl SystemMetricsSnapshot {
+    /// システムメトリクスから作成
+    pub fn from_metrics(
+        cpu_usage: f32,
+        cpu_frequency_mhz: Option<f64>,
+        memory_used: u64,
+        memory_total: u64,
+        gpu: Option<&GpuMetrics>,
+        network: &NetworkMetrics,
+    ) -> Self {
+        Self {
+            cpu_usage,
+            cpu_frequency_mhz,
+            memory_used,
+            memory_total,
+            gpu_usage: gpu.map(|g| g.usage_percent),
+            gpu_memory_used: gpu.map(|g| g.memory_used_bytes),
+            network_upload: network.upload_bytes_per_sec,
+            network_download: network.download_bytes_per_sec,
+        }
+    }
+}
+
+/// ObsStatusSnapshotを作成するヘルパー
+impl ObsStatusSnapshot {
+    /// 空のスナップショットを作成
+    pub fn empty() -> Self {
+        Self {
+            streaming: false,
+            recording: false,
+            fps: None,
+            render_dropped_frames: None,
+            output_dropped_frames: None,
+            stream_bitrate: None,
+            output_stats: None,
+        }
+    }
+
+    /// OBS接続時のスナップショットを作成
+    pub fn from_obs_status(
+        streaming: bool,
+        recording: bool,
+        fps: Option<f32>,
+        render_dropped: Option<u64>,
+        output_dropped: Option<u64>,
+        bitrate: Option<u64>,
+    ) -> Self {
+        Self {
+            streaming,
+            recording,
+            fps,
+            render_dropped_frames: render_dropped,
+            output_dropped_frames: output_dropped,
+            stream_bitrate: bitrate,
+            output_stats: None,
+        }
+    }
+
+    /// 配信出力の統計を設定（出力ドロップフレームと配信ビットレートも実測値で上書き）
+    #[must_use]
+    pub fn with_output_stats(mut self, stats: OutputStats) -> Self {
+        self.output_dropped_frames = Some(stats.frames_dropped);
+        self.stream_bitrate = Some(stats.current_bitrate_kbps.round() as u64);
+        self.output_stats = Some(stats);
+        self
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_creation() {
+        let store = MetricsHistoryStore::new(PathBuf::from("/tmp/test_metrics.db"));
+        assert!(store.initialize().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_opens_connection_once() {
+        let db_path = temp_db_path();
+        let store = MetricsHistoryStore::new(db_path.clone());
+        store.initialize().await.unwrap();
+        assert!(store.connection.lock().unwrap().is_some());
+
+        // 初期化済みの場合はデータベースファイルを開き直さない
+        std::fs::remove_file(&db_path).unwrap();
+        store.initialize().await.unwrap();
+        assert!(!db_path.exists());
+
+        // 同じ接続で操作を続けられる
+        let session_id = store.start_session().await.unwrap();
+        assert!(store.get_session(&session_id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_session_management() {
+        let store = MetricsHistoryStore::new(PathBuf::from("/tmp/test_metrics.db"));
+        store.initialize().await.unwrap();
+
+        let session_id = store.start_session().await.unwrap();
         assert!(session_id.starts_with("session_"));
 
         store.end_session().await.unwrap();
@@ -312,6 +1910,7 @@ mod tests {
 
         let system = SystemMetricsSnapshot {
             cpu_usage: 50.0,
+            cpu_frequency_mhz: None,
             memory_used: 8_000_000_000,
             memory_total: 16_000_000_000,
             gpu_usage: Some(60.0),
@@ -324,4 +1923,737 @@ mod tests {
 
         assert!(store.save_metrics(system, obs).await.is_ok());
     }
+
+    fn temp_db_path() -> PathBuf {
+        std::env::temp_dir().join(format!("obs_optimizer_history_{}.db", uuid::Uuid::new_v4()))
+    }
+
+    fn create_test_alert(metric: MetricType, severity: AlertSeverity, timestamp: u64) -> Alert {
+        Alert {
+            id: format!("{metric:?}_{severity:?}"),
+            metric,
+            current_value: 96.0,
+            threshold: 95.0,
+            severity,
+            message: "テストアラート".to_string(),
+            timestamp,
+            active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alert_lifecycle_raised_acknowledged_cleared() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        // 発生
+        let alert = create_test_alert(MetricType::CpuUsage, AlertSeverity::Critical, 1_000);
+        let entry_id = store.record_alert(&alert).await.unwrap();
+        let entries = store.get_alert_history(0, 2_000).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, entry_id);
+        assert_eq!(entries[0].metric, MetricType::CpuUsage);
+        assert_eq!(entries[0].severity, AlertSeverity::Critical);
+        assert_eq!(entries[0].raised_at, 1_000);
+        assert!(!entries[0].acknowledged);
+        assert!(entries[0].cleared_at.is_none());
+
+        // 確認
+        assert!(store.acknowledge_alert(&entry_id).await.unwrap());
+        let entries = store.get_alert_history(0, 2_000).await.unwrap();
+        assert!(entries[0].acknowledged);
+        assert!(entries[0].acknowledged_at.is_some());
+        assert!(entries[0].cleared_at.is_none(), "確認しただけでは解消されない");
+
+        // 解消
+        store.clear_alert(&alert.id, 1_500).await.unwrap();
+        let entries = store.get_alert_history(0, 2_000).await.unwrap();
+        assert!(entries[0].acknowledged);
+        assert_eq!(entries[0].cleared_at, Some(1_500));
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_by_alert_id_targets_open_entries_only() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        // 同じアラートが2回発生し、1回目は解消済み
+        let first = create_test_alert(MetricType::GpuUsage, AlertSeverity::Warning, 100);
+        store.record_alert(&first).await.unwrap();
+        store.clear_alert(&first.id, 150).await.unwrap();
+        let second = create_test_alert(MetricType::GpuUsage, AlertSeverity::Warning, 200);
+        store.record_alert(&second).await.unwrap();
+
+        assert!(store.acknowledge_alert(&second.id).await.unwrap());
+
+        let entries = store.get_alert_history(0, 1_000).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        // 新しい順
+        assert!(entries[0].acknowledged, "未解消の発生のみ確認済みになる");
+        assert!(!entries[1].acknowledged);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_unknown_alert() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        assert!(!store.acknowledge_alert("unknown").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_alert_history_range_filter() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        for timestamp in [100, 200, 300] {
+            let alert = create_test_alert(MetricType::CpuUsage, AlertSeverity::Warning, timestamp);
+            store.record_alert(&alert).await.unwrap();
+        }
+
+        let entries = store.get_alert_history(150, 300).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].raised_at, 300);
+        assert_eq!(entries[1].raised_at, 200);
+    }
+
+    #[tokio::test]
+    async fn test_count_alerts_in_range() {
+        let db_path = temp_db_path();
+        let store = MetricsHistoryStore::new(db_path.clone());
+        store.initialize().await.unwrap();
+
+        for (metric, timestamp) in [
+            (MetricType::CpuUsage, 100),
+            (MetricType::CpuUsage, 150),
+            (MetricType::FrameDropRate, 199),
+            // 終了時刻ちょうどは含まない
+            (MetricType::GpuUsage, 200),
+        ] {
+            store.record_alert(&create_test_alert(metric, AlertSeverity::Warning, timestamp)).await.unwrap();
+        }
+
+        let counts = store.count_alerts_in_range(100, 200).await.unwrap();
+        assert_eq!(
+            counts.iter().map(|c| (c.metric, c.count)).collect::<Vec<_>>(),
+            vec![(MetricType::CpuUsage, 2), (MetricType::FrameDropRate, 1)]
+        );
+
+        // 集計はインデックスのみで行われる
+        let conn = Connection::open(&db_path).unwrap();
+        let plan: String = conn
+            .query_row(
+                "EXPLAIN QUERY PLAN SELECT metric, severity, COUNT(*) FROM alert_history
+                 WHERE raised_at >= 100 AND raised_at < 200 GROUP BY metric, severity",
+                [],
+                |row| row.get(3),
+            )
+            .unwrap();
+        assert!(plan.contains("COVERING INDEX idx_alert_history_raised_metric"), "{plan}");
+    }
+
+    #[tokio::test]
+    async fn test_clear_all_open_alerts() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        let cpu = create_test_alert(MetricType::CpuUsage, AlertSeverity::Critical, 100);
+        let gpu = create_test_alert(MetricType::GpuUsage, AlertSeverity::Warning, 110);
+        store.record_alert(&cpu).await.unwrap();
+        store.record_alert(&gpu).await.unwrap();
+        store.clear_alert(&cpu.id, 120).await.unwrap();
+
+        store.clear_all_open_alerts(200).await.unwrap();
+
+        let entries = store.get_alert_history(0, 1_000).await.unwrap();
+        let cleared: Vec<_> = entries.iter().map(|e| (e.alert_id.as_str(), e.cleared_at)).collect();
+        assert!(cleared.contains(&(cpu.id.as_str(), Some(120))), "解消済みの時刻は上書きしない");
+        assert!(cleared.contains(&(gpu.id.as_str(), Some(200))));
+    }
+
+    #[tokio::test]
+    async fn test_count_session_alerts() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        // セッション外のアラートは集計対象外
+        store
+            .record_alert(&create_test_alert(MetricType::CpuUsage, AlertSeverity::Critical, 50))
+            .await
+            .unwrap();
+
+        let session_id = store.start_session().await.unwrap();
+        for timestamp in [100, 200, 300] {
+            let alert = create_test_alert(MetricType::CpuUsage, AlertSeverity::Critical, timestamp);
+            store.record_alert(&alert).await.unwrap();
+        }
+        store
+            .record_alert(&create_test_alert(MetricType::GpuUsage, AlertSeverity::Warning, 400))
+            .await
+            .unwrap();
+        store.end_session().await.unwrap();
+
+        let counts = store.count_session_alerts(&session_id).await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0], AlertCount::new(MetricType::CpuUsage, AlertSeverity::Critical, 3));
+        assert_eq!(counts[0].summary, "クリティカルなCPUアラート 3件");
+        assert_eq!(counts[1].count, 1);
+    }
+
+    fn create_test_session(session_id: &str, start_time: i64, end_time: i64, tags: &[&str]) -> SessionSummary {
+        SessionSummary {
+            session_id: session_id.to_string(),
+            start_time,
+            end_time,
+            avg_cpu: 0.0,
+            avg_gpu: 0.0,
+            total_dropped_frames: 0,
+            peak_bitrate: 0,
+            quality_score: 0.0,
+            title: None,
+            tags: tags.iter().map(ToString::to_string).collect(),
+            notes: None,
+            platform: None,
+            avg_bitrate: 0,
+            bytes_uploaded: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrates_sessions_without_metadata_columns() {
+        let db_path = temp_db_path();
+
+        // メタデータ列導入前のスキーマで既存行を作成
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE sessions (
+                    session_id TEXT PRIMARY KEY,
+                    start_time INTEGER NOT NULL,
+                    end_time INTEGER NOT NULL,
+                    avg_cpu REAL NOT NULL DEFAULT 0,
+                    avg_gpu REAL NOT NULL DEFAULT 0,
+                    total_dropped_frames INTEGER NOT NULL DEFAULT 0,
+                    peak_bitrate INTEGER NOT NULL DEFAULT 0,
+                    quality_score REAL NOT NULL DEFAULT 0
+                );
+                INSERT INTO sessions VALUES ('session_old', 1000, 4600, 45.5, 62.3, 15, 6200, 85.5);",
+            )
+            .unwrap();
+        }
+
+        let store = MetricsHistoryStore::new(db_path);
+        store.initialize().await.unwrap();
+
+        let session = store.get_session("session_old").await.unwrap().unwrap();
+        assert_eq!(session.duration_secs(), 3600);
+        assert_eq!(session.total_dropped_frames, 15);
+        assert!(session.title.is_none());
+        assert!(session.tags.is_empty());
+        assert!(session.notes.is_none());
+
+        // 移行後はメタデータを更新できる
+        let updated = store
+            .update_session_metadata("session_old", Some("wifi night".to_string()), vec!["wifi".to_string()], None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.title.as_deref(), Some("wifi night"));
+        assert_eq!(updated.tags, vec!["wifi".to_string()]);
+
+        // 2回目の初期化でも列は重複追加されない
+        assert!(store.initialize().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_end_session_summarizes_metrics() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+        let session_id = store.start_session().await.unwrap();
+
+        for (cpu_usage, gpu_usage, render_dropped, output_dropped, bitrate) in
+            [(40.0, 50.0, 10, 100, 5_800), (60.0, 70.0, 12, 105, 6_200)]
+        {
+            let system = SystemMetricsSnapshot {
+                cpu_usage,
+                cpu_frequency_mhz: None,
+                memory_used: 8_000_000_000,
+                memory_total: 16_000_000_000,
+                gpu_usage: Some(gpu_usage),
+                gpu_memory_used: None,
+                network_upload: 750_000,
+                network_download: 0,
+            };
+            let obs = ObsStatusSnapshot::from_obs_status(
+                true,
+                false,
+                Some(60.0),
+                Some(render_dropped),
+                Some(output_dropped),
+                Some(bitrate),
+            );
+            store.save_metrics(system, obs).await.unwrap();
+        }
+        store.end_session().await.unwrap();
+
+        let session = store.get_session(&session_id).await.unwrap().unwrap();
+        assert!((session.avg_cpu - 50.0).abs() < f64::EPSILON);
+        assert!((session.avg_gpu - 60.0).abs() < f64::EPSILON);
+        // 累積値の増加分（レンダリング2 + 出力5）
+        assert_eq!(session.total_dropped_frames, 7);
+        assert_eq!(session.peak_bitrate, 6_200);
+        assert_eq!(store.average_stream_bitrate(&session_id).await.unwrap(), Some(6_000));
+        assert!(store.current_session_id().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_session_metadata() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+        let session_id = store.start_session().await.unwrap();
+        store.end_session().await.unwrap();
+
+        let updated = store
+            .update_session_metadata(
+                &session_id,
+                Some("  新しいNVENCプリセットを試した ".to_string()),
+                vec!["NVENC".to_string(), " NVENC ".to_string(), String::new()],
+                Some("   ".to_string()),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.title.as_deref(), Some("新しいNVENCプリセットを試した"));
+        assert_eq!(updated.tags, vec!["NVENC".to_string()]);
+        assert!(updated.notes.is_none(), "空白のみのメモは未設定");
+
+        let missing = store
+            .update_session_metadata("unknown", None, Vec::new(), None)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn test_session_filter() {
+        let short_wifi = create_test_session("a", 1_000, 1_600, &["wifi"]);
+        let long_nvenc = create_test_session("b", 5_000, 12_200, &["NVENC"]);
+
+        assert!(SessionFilter::default().matches(&short_wifi));
+
+        let by_tag = SessionFilter { tag: Some("wifi".to_string()), ..SessionFilter::default() };
+        assert!(by_tag.matches(&short_wifi));
+        assert!(!by_tag.matches(&long_nvenc));
+
+        let by_range = SessionFilter { from: Some(2_000), to: Some(6_000), ..SessionFilter::default() };
+        assert!(!by_range.matches(&short_wifi));
+        assert!(by_range.matches(&long_nvenc));
+
+        let by_duration = SessionFilter { min_duration_secs: Some(3_600), ..SessionFilter::default() };
+        assert!(!by_duration.matches(&short_wifi));
+        assert!(by_duration.matches(&long_nvenc));
+    }
+
+    #[tokio::test]
+    async fn test_list_sessions_applies_filter() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+        let session_id = store.start_session().await.unwrap();
+        store.end_session().await.unwrap();
+        store
+            .update_session_metadata(&session_id, None, vec!["大会".to_string()], None)
+            .await
+            .unwrap();
+
+        let all = store.list_sessions(&SessionFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 1);
+
+        let tagged = SessionFilter { tag: Some("大会".to_string()), ..SessionFilter::default() };
+        assert_eq!(store.list_sessions(&tagged).await.unwrap().len(), 1);
+
+        let other = SessionFilter { tag: Some("雑談".to_string()), ..SessionFilter::default() };
+        assert!(store.list_sessions(&other).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_bytes_uploaded_to_current_session() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        // セッション外では記録しない
+        assert_eq!(store.add_bytes_uploaded(1_000).await.unwrap(), None);
+
+        let session_id = store.start_session().await.unwrap();
+        assert_eq!(store.add_bytes_uploaded(1_500_000).await.unwrap().as_deref(), Some(session_id.as_str()));
+        store.add_bytes_uploaded(500_000).await.unwrap();
+
+        let session = store.get_session(&session_id).await.unwrap().unwrap();
+        assert_eq!(session.bytes_uploaded, 2_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_list_sessions_overlapping_and_stream_info() {
+        let db_path = temp_db_path();
+        let store = MetricsHistoryStore::new(db_path.clone());
+        store.initialize().await.unwrap();
+        // セッションテーブルを作成
+        store.list_sessions(&SessionFilter::default()).await.unwrap();
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "INSERT INTO sessions (session_id, start_time, end_time) VALUES
+                    ('before', 100, 900),
+                    ('spanning', 900, 1100),
+                    ('inside', 1200, 1500),
+                    ('after', 2000, 2100);",
+            )
+            .unwrap();
+        }
+
+        // 期間の開始をまたぐセッションは含め、終了時刻ちょうどに始まるセッションは含めない
+        let sessions = store.list_sessions_overlapping(1000, 2000).await.unwrap();
+        let ids: Vec<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+        assert_eq!(ids, vec!["spanning", "inside"]);
+        assert!(sessions.iter().all(|s| s.platform.is_none() && s.avg_bitrate == 0));
+
+        assert!(store
+            .update_session_stream_info("inside", StreamingPlatform::Twitch, 6000)
+            .await
+            .unwrap());
+        let inside = store.get_session("inside").await.unwrap().unwrap();
+        assert_eq!(inside.platform, Some(StreamingPlatform::Twitch));
+        assert_eq!(inside.avg_bitrate, 6000);
+
+        assert!(!store
+            .update_session_stream_info("missing", StreamingPlatform::Twitch, 6000)
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    fn test_session_overlap_and_effective_bitrate() {
+        let mut session = create_test_session("a", 900, 1_100, &[]);
+        assert_eq!(session.overlap_secs(1_000, 2_000), 100);
+        assert_eq!(session.overlap_secs(2_000, 3_000), 0);
+
+        // 平均ビットレート未記録の場合はピークで代用
+        session.peak_bitrate = 8000;
+        assert_eq!(session.effective_bitrate(), 8000);
+        session.avg_bitrate = 6000;
+        assert_eq!(session.effective_bitrate(), 6000);
+    }
+
+    /// テスト用の決定的な疑似乱数（線形合同法）
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, max: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (self.0 >> 33) % max
+        }
+    }
+
+    /// 不規則な間隔・欠損値を含むサンプルを生成
+    fn random_samples(seed: u64, count: usize, session_id: &str) -> Vec<HistoricalMetrics> {
+        let mut rng = Lcg(seed);
+        let mut timestamp = 1_700_000_000 + rng.below(3_600) as i64;
+        let mut dropped = 0;
+
+        (0..count)
+            .map(|_| {
+                // 区間の境界をまたぐよう、最大3分の間隔を空ける
+                timestamp += 1 + rng.below(180) as i64;
+                dropped += rng.below(3);
+                let has_gpu = rng.below(4) != 0;
+                let obs_connected = rng.below(3) != 0;
+                HistoricalMetrics {
+                    timestamp,
+                    session_id: session_id.to_string(),
+                    system: SystemMetricsSnapshot {
+                        cpu_usage: rng.below(10_000) as f32 / 100.0,
+                        cpu_frequency_mhz: Some(3_000.0 + rng.below(2_000) as f64),
+                        memory_used: 4_000_000_000 + rng.below(8_000_000_000),
+                        memory_total: 16_000_000_000,
+                        gpu_usage: has_gpu.then(|| rng.below(1_000) as f32 / 10.0),
+                        gpu_memory_used: has_gpu.then(|| rng.below(8_000_000_000)),
+                        network_upload: rng.below(2_000_000),
+                        network_download: rng.below(500_000),
+                    },
+                    obs: ObsStatusSnapshot {
+                        streaming: obs_connected && rng.below(2) == 0,
+                        recording: false,
+                        fps: obs_connected.then(|| 55.0 + rng.below(60) as f32 / 10.0),
+                        render_dropped_frames: obs_connected.then_some(0),
+                        output_dropped_frames: obs_connected.then_some(dropped),
+                        stream_bitrate: obs_connected.then(|| 5_000 + rng.below(2_000)),
+                        output_stats: None,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// ロールアップの系列（`ROLLUP_SERIES` の順）
+    fn rollup_series(rollup: &MetricsRollup) -> [Option<MetricAggregate>; 9] {
+        [
+            rollup.cpu_usage,
+            rollup.cpu_frequency_mhz,
+            rollup.memory_used,
+            rollup.gpu_usage,
+            rollup.gpu_memory_used,
+            rollup.network_upload,
+            rollup.network_download,
+            rollup.fps,
+            rollup.stream_bitrate,
+        ]
+    }
+
+    /// ロールアップが生データから直接計算した平均・最小・最大と一致することを確認
+    fn assert_rollups_match_raw(
+        rollups: &[MetricsRollup],
+        samples: &[HistoricalMetrics],
+        resolution: MetricsResolution,
+    ) {
+        let mut buckets: std::collections::BTreeMap<i64, Vec<&HistoricalMetrics>> =
+            std::collections::BTreeMap::new();
+        for sample in samples {
+            buckets
+                .entry(resolution.bucket_start(sample.timestamp))
+                .or_default()
+                .push(sample);
+        }
+
+        assert_eq!(
+            rollups.iter().map(|r| r.bucket_start).collect::<Vec<_>>(),
+            buckets.keys().copied().collect::<Vec<_>>(),
+            "{resolution:?}: 集計区間が一致しない"
+        );
+
+        for (rollup, bucket) in rollups.iter().zip(buckets.values()) {
+            assert_eq!(rollup.sample_count, bucket.len() as u64);
+
+            for (index, aggregate) in rollup_series(rollup).into_iter().enumerate() {
+                let values: Vec<f64> = bucket
+                    .iter()
+                    .filter_map(|sample| rollup_series_values(sample)[index])
+                    .collect();
+                let context = format!("{resolution:?} {} {}", rollup.bucket_start, ROLLUP_SERIES[index]);
+                let Some(aggregate) = aggregate else {
+                    assert!(values.is_empty(), "{context}: 値があるのに集計されていない");
+                    continue;
+                };
+
+                let avg = values.iter().sum::<f64>() / values.len() as f64;
+                assert!((aggregate.avg - avg).abs() <= avg.abs().max(1.0) * 1e-9, "{context}: 平均");
+                assert_eq!(aggregate.min, values.iter().copied().fold(f64::INFINITY, f64::min), "{context}: 最小");
+                assert_eq!(aggregate.max, values.iter().copied().fold(f64::NEG_INFINITY, f64::max), "{context}: 最大");
+                assert!(aggregate.min <= aggregate.avg && aggregate.avg <= aggregate.max, "{context}");
+            }
+
+            let streaming = bucket.iter().any(|s| s.obs.streaming);
+            let output_dropped = bucket.iter().filter_map(|s| s.obs.output_dropped_frames).max();
+            assert_eq!(rollup.streaming, streaming);
+            assert_eq!(rollup.output_dropped_frames, output_dropped);
+            assert_eq!(rollup.memory_total, 16_000_000_000);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rollups_match_raw_data() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        for seed in [1, 7, 42, 2024] {
+            let session_id = format!("session_{seed}");
+            let samples = random_samples(seed, 120, &session_id);
+            for sample in &samples {
+                store.insert_metrics(sample.clone()).await.unwrap();
+            }
+            let (from, to) = (samples[0].timestamp, samples[samples.len() - 1].timestamp);
+
+            for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+                let rollups = store.get_metric_rollups(&session_id, from, to, resolution).await.unwrap();
+                assert_rollups_match_raw(&rollups, &samples, resolution);
+            }
+        }
+
+        // 生データから作り直しても増分更新と同じ結果になる
+        store.rebuild_metric_rollups().await.unwrap();
+        let samples = random_samples(42, 120, "session_42");
+        let (from, to) = (samples[0].timestamp, samples[samples.len() - 1].timestamp);
+        for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+            let rollups = store.get_metric_rollups("session_42", from, to, resolution).await.unwrap();
+            assert_rollups_match_raw(&rollups, &samples, resolution);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_range_raw_and_rollup() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+        let samples = random_samples(3, 20, "session_range");
+        for sample in &samples {
+            store.insert_metrics(sample.clone()).await.unwrap();
+        }
+        let (from, to) = (samples[0].timestamp, samples[samples.len() - 1].timestamp);
+
+        // 生データは保存した値をそのまま返す
+        let raw = store
+            .get_metrics_range("session_range", from, to, MetricsResolution::Raw)
+            .await
+            .unwrap();
+        assert_eq!(raw.len(), samples.len());
+        assert_eq!(
+            serde_json::to_value(&raw).unwrap(),
+            serde_json::to_value(&samples).unwrap()
+        );
+
+        // 分単位は区間の開始時刻・平均値で返す
+        let minute = store
+            .get_metrics_range("session_range", from, to, MetricsResolution::Minute)
+            .await
+            .unwrap();
+        let rollups = store
+            .get_metric_rollups("session_range", from, to, MetricsResolution::Minute)
+            .await
+            .unwrap();
+        assert_eq!(minute.len(), rollups.len());
+        assert!(minute.iter().all(|m| m.timestamp % 60 == 0));
+        assert_eq!(
+            f64::from(minute[0].system.cpu_usage),
+            f64::from(rollups[0].cpu_usage.unwrap().avg as f32)
+        );
+
+        // 他のセッション・期間外のデータは含まない
+        assert!(store
+            .get_metrics_range("other", from, to, MetricsResolution::Raw)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            store
+                .get_metrics_range("session_range", from + 1, to, MetricsResolution::Raw)
+                .await
+                .unwrap()
+                .len(),
+            samples.len() - 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_metrics_resolution_by_point_budget() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        // 30秒間隔で3時間分（生データ360点・分単位180点・時間単位3点）
+        let start = 1_700_006_400;
+        for i in 0..360 {
+            let mut sample = random_samples(i, 1, "session_long").remove(0);
+            sample.timestamp = start + i as i64 * 30;
+            store.insert_metrics(sample).await.unwrap();
+        }
+        let end = start + 359 * 30;
+
+        let select = |max_points| store.select_metrics_resolution("session_long", start, end, max_points);
+        assert_eq!(select(1_000).await.unwrap(), MetricsResolution::Raw);
+        assert_eq!(select(200).await.unwrap(), MetricsResolution::Minute);
+        assert_eq!(select(10).await.unwrap(), MetricsResolution::Hour);
+        // どの解像度でも収まらない場合は時間単位
+        assert_eq!(select(1).await.unwrap(), MetricsResolution::Hour);
+    }
+
+    #[tokio::test]
+    async fn test_migration_backfills_rollups_from_raw_metrics() {
+        let db_path = temp_db_path();
+        let samples = random_samples(11, 40, "session_old");
+
+        // ロールアップ導入前のスキーマで生データのみ保存
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(METRICS_SCHEMA).unwrap();
+            for sample in &samples {
+                insert_raw_metrics(&conn, sample).unwrap();
+            }
+        }
+
+        let store = MetricsHistoryStore::new(db_path);
+        store.initialize().await.unwrap();
+        let (from, to) = (samples[0].timestamp, samples[samples.len() - 1].timestamp);
+        for resolution in [MetricsResolution::Minute, MetricsResolution::Hour] {
+            let rollups = store.get_metric_rollups("session_old", from, to, resolution).await.unwrap();
+            assert_rollups_match_raw(&rollups, &samples, resolution);
+        }
+
+        // 移行後に保存したサンプルは増分で集計される
+        let mut extra = random_samples(12, 1, "session_old").remove(0);
+        extra.timestamp = to;
+        store.insert_metrics(extra.clone()).await.unwrap();
+        let all: Vec<_> = samples.iter().cloned().chain(std::iter::once(extra)).collect();
+        let rollups = store
+            .get_metric_rollups("session_old", from, to, MetricsResolution::Minute)
+            .await
+            .unwrap();
+        assert_rollups_match_raw(&rollups, &all, MetricsResolution::Minute);
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        assert_eq!(MetricsResolution::Raw.bucket_start(1_700_000_059), 1_700_000_059);
+        assert_eq!(MetricsResolution::Minute.bucket_start(1_700_000_059), 1_700_000_040);
+        assert_eq!(MetricsResolution::Hour.bucket_start(1_700_000_059), 1_699_999_200);
+        assert_eq!(MetricsResolution::Minute.bucket_start(-1), -60);
+    }
+
+    #[tokio::test]
+    async fn test_overhead_samples_and_baselines_roundtrip() {
+        let store = MetricsHistoryStore::new(temp_db_path());
+        store.initialize().await.unwrap();
+
+        let before = StreamingOverhead {
+            cpu_percent: 18.0,
+            gpu_percent: Some(40.0),
+            encoder_percent: None,
+            memory_mb: 512.0,
+        };
+        let after = StreamingOverhead {
+            cpu_percent: 9.0,
+            gpu_percent: Some(35.0),
+            encoder_percent: Some(20.0),
+            memory_mb: 480.0,
+        };
+
+        store.insert_overhead_sample("s1".to_string(), 200, after.clone()).await.unwrap();
+        store.insert_overhead_sample("s1".to_string(), 100, before.clone()).await.unwrap();
+        store.insert_overhead_sample("s2".to_string(), 150, before.clone()).await.unwrap();
+        let samples = store.get_session_overhead("s1").await.unwrap();
+        assert_eq!(samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![100, 200]);
+        assert_eq!(samples[0].overhead, before);
+
+        for (id, applied_at) in [("b1", 1_000), ("b2", 2_000)] {
+            store
+                .insert_overhead_baseline(OverheadBaseline {
+                    baseline_id: id.to_string(),
+                    applied_at,
+                    before: before.clone(),
+                    after: None,
+                })
+                .await
+                .unwrap();
+        }
+        assert!(store.set_overhead_after("b1", after.clone()).await.unwrap());
+        assert!(!store.set_overhead_after("missing", after.clone()).await.unwrap());
+
+        // 適用日時の新しい順。適用後の値が未記録のものはNone
+        let baselines = store.list_overhead_baselines(10).await.unwrap();
+        assert_eq!(baselines.len(), 2);
+        assert_eq!(baselines[0].baseline_id, "b2");
+        assert_eq!(baselines[0].after, None);
+        assert_eq!(baselines[1].before, before);
+        assert_eq!(baselines[1].after, Some(after));
+
+        assert_eq!(store.list_overhead_baselines(1).await.unwrap().len(), 1);
+    }
 }
@@ -10,6 +10,11 @@ pub mod config;
 pub mod credentials;
 pub mod profiles;
 pub mod metrics_history;
+pub mod environment;
+pub mod archive;
+pub mod telemetry;
+pub mod recommendation_history;
+pub mod paths;
 
 // 将来的な拡張や外部クレートからの利用を想定した再エクスポート
 #[allow(unused_imports)]
@@ -17,15 +22,31 @@ pub use config::{AppConfig, load_config, save_config};
 #[allow(unused_imports)]
 pub use credentials::{
     save_obs_password, get_obs_password, delete_obs_password,
+    save_platform_token, get_platform_token, delete_platform_token,
     migrate_from_plaintext, ERROR_CODE_KEYRING,
 };
 #[allow(unused_imports)]
 pub use profiles::{
-    SettingsProfile, ProfileSettings, ProfileSummary,
+    SettingsProfile, ProfileSettings, ProfileSummary, ProfileFilter, BackupTrigger,
     get_profiles, get_profile, save_profile, delete_profile,
+    get_profiles_filtered, update_profile_metadata, mark_profile_applied,
 };
 #[allow(unused_imports)]
 pub use metrics_history::{
-    MetricsHistoryStore, HistoricalMetrics, SessionSummary,
+    MetricsHistoryStore, HistoricalMetrics, SessionSummary, SessionFilter,
     SystemMetricsSnapshot, ObsStatusSnapshot,
+    AlertHistoryEntry, AlertCount,
+    MetricsResolution, MetricsRollup, MetricAggregate,
+};
+#[allow(unused_imports)]
+pub use environment::{
+    EnvironmentSnapshot, SettingsFingerprint, MonitorInfo,
+    load_environment_snapshot, save_environment_snapshot,
+};
+#[allow(unused_imports)]
+pub use telemetry::{TelemetryRecord, load_telemetry_record, save_telemetry_record};
+#[allow(unused_imports)]
+pub use recommendation_history::{
+    RecommendationHistoryEntry, RecommendationInput,
+    load_recommendation_history, append_recommendation_history,
 };
@@ -0,0 +1,53 @@
+// アプリケーションの保存先ディレクトリ
+//
+// 設定ファイル・プロファイル・各種履歴の保存先を一元管理する。
+// 統合テストでは保存先を一時ディレクトリに差し替え、ユーザーのデータに触れずに
+// コマンドを実行できるようにする。
+
+use std::path::PathBuf;
+use std::sync::{PoisonError, RwLock};
+
+/// アプリケーション名（保存先ディレクトリ名）
+const APP_NAME: &str = "obs-optimizer";
+
+/// 保存先のルートディレクトリの差し替え（テスト用）
+static STORAGE_ROOT_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// 差し替えられたルートディレクトリ以下のサブディレクトリ
+fn overridden_dir(name: &str) -> Option<PathBuf> {
+    STORAGE_ROOT_OVERRIDE
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .map(|root| root.join(name))
+}
+
+/// アプリケーションの設定ディレクトリを取得
+///
+/// Windows: %APPDATA%/obs-optimizer
+/// Linux: ~/.config/obs-optimizer
+/// macOS: ~/Library/Application Support/obs-optimizer
+///
+/// OSの設定ディレクトリを取得できない場合はNone
+pub fn app_config_dir() -> Option<PathBuf> {
+    overridden_dir("config").or_else(|| dirs::config_dir().map(|dir| dir.join(APP_NAME)))
+}
+
+/// アプリケーションのデータディレクトリを取得（履歴データベース等）
+///
+/// OSのデータディレクトリを取得できない場合はNone
+pub fn app_data_dir() -> Option<PathBuf> {
+    overridden_dir("data").or_else(|| dirs::data_dir().map(|dir| dir.join(APP_NAME)))
+}
+
+/// 保存先のルートディレクトリを差し替える（Noneで既定の保存先に戻す）
+///
+/// 設定は `root/config`、データは `root/data` に保存される。
+/// 履歴データベースは初回アクセス時のパスで開かれるため、コマンドを呼ぶ前に設定すること
+#[cfg(any(test, feature = "testing"))]
+pub fn set_storage_root(root: Option<PathBuf>) {
+    *STORAGE_ROOT_OVERRIDE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = root;
+    crate::storage::config::notify_config_location_changed();
+}
@@ -5,12 +5,27 @@
 
 use crate::error::AppError;
 use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use crate::storage::paths::app_config_dir;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-const APP_NAME: &str = "obs-optimizer";
 const PROFILES_DIR: &str = "profiles";
 
+/// 旧形式のバックアップ名の接頭辞（`is_backup` 導入前のバックアップ判定用）
+const LEGACY_BACKUP_NAME_PREFIX: &str = "バックアップ";
+
+/// バックアップの作成契機
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupTrigger {
+    /// ユーザーが手動で作成
+    Manual,
+    /// 推奨設定・最適化の適用前
+    PreOptimization,
+    /// プロファイルの適用前
+    PreProfileApply,
+}
+
 /// 設定プロファイル
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +46,57 @@ pub struct SettingsProfile {
     pub created_at: i64,
     /// 更新日時（Unixタイムスタンプ）
     pub updated_at: i64,
+    /// タグ
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// お気に入り
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// 最終適用日時（Unixタイムスタンプ、未適用の場合はNone）
+    #[serde(default)]
+    pub last_applied_at: Option<i64>,
+    /// 設定変更前に自動作成されたバックアップか
+    #[serde(default)]
+    pub is_backup: bool,
+    /// バックアップの作成契機（バックアップ以外、または旧形式の場合はNone）
+    #[serde(default)]
+    pub backup_trigger: Option<BackupTrigger>,
+}
+
+impl SettingsProfile {
+    /// メタデータを更新
+    ///
+    /// タグは前後の空白を除去し、空文字と重複を取り除く
+    pub fn update_metadata(&mut self, description: String, tags: Vec<String>, is_favorite: bool, now: i64) {
+        self.description = description;
+        self.tags = normalize_tags(tags);
+        self.is_favorite = is_favorite;
+        self.updated_at = now;
+    }
+
+    /// 適用日時を記録
+    pub fn mark_applied(&mut self, now: i64) {
+        self.last_applied_at = Some(now);
+    }
+
+    /// バックアッププロファイルか
+    ///
+    /// `is_backup` 導入前のバックアップは名前の接頭辞で判定する
+    pub fn is_backup_profile(&self) -> bool {
+        self.is_backup || self.name.starts_with(LEGACY_BACKUP_NAME_PREFIX)
+    }
+}
+
+/// タグを正規化（空白除去・空文字除外・重複除外、順序は維持）
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
 }
 
 /// プロファイル設定内容
@@ -67,6 +133,23 @@ pub struct AudioSettings {
     pub sample_rate: u32,
     /// ビットレート（kbps）
     pub bitrate_kbps: u32,
+    /// 音声入力（デスクトップ音声・マイク）ごとのデバイスとミュート状態
+    #[serde(default)]
+    pub inputs: Vec<AudioInputSettings>,
+}
+
+/// 音声入力（デスクトップ音声・マイク）の設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioInputSettings {
+    /// 入力名
+    pub input_name: String,
+    /// 入力種別（例: wasapi_input_capture）
+    pub input_kind: String,
+    /// デバイスID（入力設定の `device_id`、未設定の場合はNone）
+    pub device_id: Option<String>,
+    /// ミュートするか
+    pub muted: bool,
 }
 
 /// 出力設定
@@ -103,6 +186,14 @@ pub struct ProfileSummary {
     pub created_at: i64,
     /// 更新日時
     pub updated_at: i64,
+    /// タグ
+    pub tags: Vec<String>,
+    /// お気に入り
+    pub is_favorite: bool,
+    /// 最終適用日時
+    pub last_applied_at: Option<i64>,
+    /// バックアップか
+    pub is_backup: bool,
 }
 
 impl From<&SettingsProfile> for ProfileSummary {
@@ -115,16 +206,51 @@ impl From<&SettingsProfile> for ProfileSummary {
             style: profile.style,
             created_at: profile.created_at,
             updated_at: profile.updated_at,
+            tags: profile.tags.clone(),
+            is_favorite: profile.is_favorite,
+            last_applied_at: profile.last_applied_at,
+            is_backup: profile.is_backup_profile(),
+        }
+    }
+}
+
+/// プロファイル一覧の絞り込み条件
+///
+/// 指定された条件をすべて満たすプロファイルのみを返す
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileFilter {
+    /// 配信プラットフォーム
+    pub platform: Option<StreamingPlatform>,
+    /// 配信スタイル
+    pub style: Option<StreamingStyle>,
+    /// お気に入りのみ / お気に入り以外のみ
+    pub is_favorite: Option<bool>,
+}
+
+impl ProfileFilter {
+    /// お気に入りのみを対象とする条件
+    pub fn favorites() -> Self {
+        Self {
+            is_favorite: Some(true),
+            ..Self::default()
         }
     }
+
+    /// 条件に一致するか
+    pub fn matches(&self, summary: &ProfileSummary) -> bool {
+        self.platform.is_none_or(|platform| summary.platform == platform)
+            && self.style.is_none_or(|style| summary.style == style)
+            && self.is_favorite.is_none_or(|favorite| summary.is_favorite == favorite)
+    }
 }
 
 /// プロファイルディレクトリのパスを取得
 fn get_profiles_dir() -> Result<PathBuf, AppError> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| AppError::config_error("設定ディレクトリを取得できませんでした"))?;
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
 
-    let profiles_dir = config_dir.join(APP_NAME).join(PROFILES_DIR);
+    let profiles_dir = config_dir.join(PROFILES_DIR);
 
     if !profiles_dir.exists() {
         std::fs::create_dir_all(&profiles_dir)?;
@@ -182,6 +308,34 @@ pub fn get_profiles() -> Result<Vec<ProfileSummary>, AppError> {
     Ok(summaries)
 }
 
+/// 条件に一致するプロファイル一覧を取得
+pub fn get_profiles_filtered(filter: &ProfileFilter) -> Result<Vec<ProfileSummary>, AppError> {
+    Ok(get_profiles()?
+        .into_iter()
+        .filter(|summary| filter.matches(summary))
+        .collect())
+}
+
+/// プロファイルのメタデータを更新
+pub fn update_profile_metadata(
+    profile_id: &str,
+    description: String,
+    tags: Vec<String>,
+    is_favorite: bool,
+) -> Result<SettingsProfile, AppError> {
+    let mut profile = get_profile(profile_id)?;
+    profile.update_metadata(description, tags, is_favorite, chrono::Utc::now().timestamp());
+    save_profile(&profile)?;
+    Ok(profile)
+}
+
+/// プロファイルの適用日時を記録
+pub fn mark_profile_applied(profile_id: &str) -> Result<(), AppError> {
+    let mut profile = get_profile(profile_id)?;
+    profile.mark_applied(chrono::Utc::now().timestamp());
+    save_profile(&profile)
+}
+
 /// プロファイルを取得
 pub fn get_profile(profile_id: &str) -> Result<SettingsProfile, AppError> {
     let path = get_profile_path(profile_id)?;
@@ -245,6 +399,7 @@ mod tests {
                 audio: AudioSettings {
                     sample_rate: 48000,
                     bitrate_kbps: 160,
+                    inputs: Vec::new(),
                 },
                 output: OutputSettings {
                     encoder: "ffmpeg_nvenc".to_string(),
@@ -256,6 +411,11 @@ mod tests {
             },
             created_at: 1_703_332_800, // 2023-12-23 12:00:00 UTC
             updated_at: 1_703_332_800,
+            tags: Vec::new(),
+            is_favorite: false,
+            last_applied_at: None,
+            is_backup: false,
+            backup_trigger: None,
         }
     }
 
@@ -281,4 +441,140 @@ mod tests {
         assert_eq!(summary.platform, profile.platform);
         assert_eq!(summary.style, profile.style);
     }
+
+    #[test]
+    fn test_metadata_persists_through_serialization() {
+        let mut profile = create_test_profile();
+        profile.update_metadata(
+            "大会用".to_string(),
+            vec!["FPS".to_string(), "大会".to_string()],
+            true,
+            1_703_400_000,
+        );
+        profile.mark_applied(1_703_500_000);
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(json.contains(r#""isFavorite":true"#));
+        assert!(json.contains(r#""lastAppliedAt":1703500000"#));
+
+        let deserialized: SettingsProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.description, "大会用");
+        assert_eq!(deserialized.tags, vec!["FPS".to_string(), "大会".to_string()]);
+        assert!(deserialized.is_favorite);
+        assert_eq!(deserialized.last_applied_at, Some(1_703_500_000));
+        assert_eq!(deserialized.updated_at, 1_703_400_000);
+    }
+
+    #[test]
+    fn test_legacy_profile_without_metadata() {
+        let mut value = serde_json::to_value(create_test_profile()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("tags");
+        object.remove("isFavorite");
+        object.remove("lastAppliedAt");
+        object.remove("isBackup");
+        object.remove("backupTrigger");
+
+        let profile: SettingsProfile = serde_json::from_value(value).unwrap();
+        assert!(profile.tags.is_empty());
+        assert!(!profile.is_favorite);
+        assert!(profile.last_applied_at.is_none());
+        assert!(!profile.is_backup);
+        assert!(profile.backup_trigger.is_none());
+    }
+
+    #[test]
+    fn test_legacy_profile_without_audio_inputs() {
+        let mut value = serde_json::to_value(create_test_profile()).unwrap();
+        value["settings"]["audio"].as_object_mut().unwrap().remove("inputs");
+
+        let profile: SettingsProfile = serde_json::from_value(value).unwrap();
+        assert!(profile.settings.audio.inputs.is_empty());
+        assert_eq!(profile.settings.audio.sample_rate, 48000);
+    }
+
+    #[test]
+    fn test_backup_profile_detection() {
+        let mut profile = create_test_profile();
+        assert!(!profile.is_backup_profile());
+
+        profile.is_backup = true;
+        profile.backup_trigger = Some(BackupTrigger::PreProfileApply);
+        assert!(profile.is_backup_profile());
+        assert!(ProfileSummary::from(&profile).is_backup);
+
+        let json = serde_json::to_value(&profile).unwrap();
+        assert_eq!(json["backupTrigger"], "preProfileApply");
+
+        // 旧形式のバックアップは名前で判定
+        let mut legacy = create_test_profile();
+        legacy.name = "バックアップ 2024-01-01 12:00:00".to_string();
+        assert!(legacy.is_backup_profile());
+    }
+
+    #[test]
+    fn test_mark_applied_updates_timestamp_only() {
+        let mut profile = create_test_profile();
+        profile.mark_applied(1_703_500_000);
+        assert_eq!(profile.last_applied_at, Some(1_703_500_000));
+        // 適用は内容の更新ではないため更新日時は変わらない
+        assert_eq!(profile.updated_at, 1_703_332_800);
+
+        profile.mark_applied(1_703_600_000);
+        assert_eq!(profile.last_applied_at, Some(1_703_600_000));
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![
+            " FPS ".to_string(),
+            String::new(),
+            "FPS".to_string(),
+            "雑談".to_string(),
+            "   ".to_string(),
+        ];
+        assert_eq!(normalize_tags(tags), vec!["FPS".to_string(), "雑談".to_string()]);
+    }
+
+    #[test]
+    fn test_profile_filter() {
+        let youtube_gaming = ProfileSummary::from(&create_test_profile());
+
+        let mut favorite_profile = create_test_profile();
+        favorite_profile.platform = StreamingPlatform::Twitch;
+        favorite_profile.style = StreamingStyle::Talk;
+        favorite_profile.is_favorite = true;
+        let twitch_talk_favorite = ProfileSummary::from(&favorite_profile);
+
+        // 条件なしはすべて一致
+        let filter = ProfileFilter::default();
+        assert!(filter.matches(&youtube_gaming));
+        assert!(filter.matches(&twitch_talk_favorite));
+
+        let filter = ProfileFilter {
+            platform: Some(StreamingPlatform::YouTube),
+            ..ProfileFilter::default()
+        };
+        assert!(filter.matches(&youtube_gaming));
+        assert!(!filter.matches(&twitch_talk_favorite));
+
+        let filter = ProfileFilter {
+            style: Some(StreamingStyle::Talk),
+            ..ProfileFilter::default()
+        };
+        assert!(!filter.matches(&youtube_gaming));
+        assert!(filter.matches(&twitch_talk_favorite));
+
+        let filter = ProfileFilter::favorites();
+        assert!(!filter.matches(&youtube_gaming));
+        assert!(filter.matches(&twitch_talk_favorite));
+
+        // 複数条件はAND
+        let filter = ProfileFilter {
+            platform: Some(StreamingPlatform::Twitch),
+            style: Some(StreamingStyle::Gaming),
+            is_favorite: None,
+        };
+        assert!(!filter.matches(&twitch_talk_favorite));
+    }
 }
@@ -0,0 +1,162 @@
+// 推奨設定の履歴ストレージ
+//
+// 推奨設定を計算するたびに、結果と入力条件（ハードウェア・プラットフォーム・回線速度など）を
+// JSONファイルに保存し、推奨内容が時間とともにどう変わったかを確認できるようにする
+
+use crate::error::AppError;
+use crate::services::optimizer::{OutputTarget, RecommendedSettings};
+use crate::storage::config::{StreamingPlatform, StreamingStyle};
+use crate::storage::paths::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const RECOMMENDATION_HISTORY_FILE_NAME: &str = "recommendation_history.json";
+
+/// 保存する履歴の上限（古いものから削除）
+pub const MAX_RECOMMENDATION_HISTORY: usize = 50;
+
+/// 推奨設定の計算に使った入力条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationInput {
+    /// ハードウェア構成のハッシュ（CPU・GPU・メモリ・ディスプレイが同じなら同じ値）
+    pub hardware_hash: String,
+    /// 配信プラットフォーム
+    pub platform: StreamingPlatform,
+    /// 配信スタイル
+    pub style: StreamingStyle,
+    /// 回線速度（Mbps）
+    pub network_speed_mbps: f64,
+    /// 出力先
+    #[serde(default)]
+    pub target: OutputTarget,
+}
+
+/// 推奨設定の履歴1件分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationHistoryEntry {
+    /// 履歴ID
+    pub id: String,
+    /// 計算日時（UNIX epoch秒）
+    pub computed_at: i64,
+    /// 入力条件
+    pub input: RecommendationInput,
+    /// 計算結果
+    pub settings: RecommendedSettings,
+}
+
+/// 履歴ファイルのパスを取得
+fn get_recommendation_history_path() -> Result<PathBuf, AppError> {
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+
+    Ok(config_dir.join(RECOMMENDATION_HISTORY_FILE_NAME))
+}
+
+/// 履歴ファイルを読み込む（ファイルがない場合は空）
+fn load_from(path: &Path) -> Result<Vec<RecommendationHistoryEntry>, AppError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// 履歴ファイルに保存
+fn save_to(path: &Path, entries: &[RecommendationHistoryEntry]) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(entries)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// 履歴に追加し、上限を超えた古いものを削除
+fn push_capped(entries: &mut Vec<RecommendationHistoryEntry>, entry: RecommendationHistoryEntry) {
+    entries.push(entry);
+    if entries.len() > MAX_RECOMMENDATION_HISTORY {
+        let excess = entries.len() - MAX_RECOMMENDATION_HISTORY;
+        entries.drain(..excess);
+    }
+}
+
+/// 推奨設定の履歴を読み込む（計算日時の古い順）
+pub fn load_recommendation_history() -> Result<Vec<RecommendationHistoryEntry>, AppError> {
+    load_from(&get_recommendation_history_path()?)
+}
+
+/// 推奨設定の履歴に追加
+///
+/// 上限（50件）を超えた場合は古いものから削除する
+pub fn append_recommendation_history(entry: RecommendationHistoryEntry) -> Result<(), AppError> {
+    let path = get_recommendation_history_path()?;
+    let mut entries = load_from(&path)?;
+    push_capped(&mut entries, entry);
+    save_to(&path, &entries)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::services::optimizer::RecommendationEngine;
+    use crate::testing::fixtures::mid_range_hardware;
+
+    fn entry(id: usize) -> RecommendationHistoryEntry {
+        RecommendationHistoryEntry {
+            id: format!("entry-{id}"),
+            computed_at: 1_700_000_000 + id as i64,
+            input: RecommendationInput {
+                hardware_hash: "0123456789abcdef".to_string(),
+                platform: StreamingPlatform::YouTube,
+                style: StreamingStyle::Gaming,
+                network_speed_mbps: 10.0,
+                target: OutputTarget::Streaming,
+            },
+            settings: RecommendationEngine::calculate_recommendations(
+                &mid_range_hardware(),
+                None,
+                StreamingPlatform::YouTube,
+                StreamingStyle::Gaming,
+                10.0,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_push_capped_drops_oldest() {
+        let mut entries = Vec::new();
+        for id in 0..(MAX_RECOMMENDATION_HISTORY + 5) {
+            push_capped(&mut entries, entry(id));
+        }
+
+        assert_eq!(entries.len(), MAX_RECOMMENDATION_HISTORY);
+        assert_eq!(entries[0].id, "entry-5");
+        assert_eq!(entries.last().unwrap().id, format!("entry-{}", MAX_RECOMMENDATION_HISTORY + 4));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("obs-optimizer-test-{}", uuid::Uuid::new_v4()))
+            .join(RECOMMENDATION_HISTORY_FILE_NAME);
+        assert!(load_from(&path).unwrap().is_empty());
+
+        save_to(&path, &[entry(1), entry(2)]).unwrap();
+        let loaded = load_from(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].id, "entry-2");
+        assert_eq!(loaded[1].input, entry(2).input);
+        assert_eq!(loaded[1].settings.output.bitrate_kbps, entry(2).settings.output.bitrate_kbps);
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"hardwareHash\""));
+        assert!(json.contains("\"networkSpeedMbps\""));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+// 匿名ハードウェア統計の送信記録ストレージ
+//
+// 最後に送信した日時をJSONファイルとして保存し、
+// 送信を週1回までに制限するために使用する
+
+use crate::error::AppError;
+use crate::storage::paths::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const TELEMETRY_FILE_NAME: &str = "telemetry.json";
+
+/// 送信記録
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryRecord {
+    /// 最後に送信に成功した日時（Unixタイムスタンプ）
+    pub last_sent_at: Option<i64>,
+}
+
+/// 送信記録ファイルのパスを取得
+fn get_telemetry_path() -> Result<PathBuf, AppError> {
+    let config_dir = app_config_dir()
+        .ok_or_else(|| AppError::config_io("設定ディレクトリを取得できませんでした"))?;
+
+    Ok(config_dir.join(TELEMETRY_FILE_NAME))
+}
+
+/// 送信記録を読み込む（未送信の場合はデフォルト値）
+pub fn load_telemetry_record() -> Result<TelemetryRecord, AppError> {
+    let path = get_telemetry_path()?;
+    if !path.exists() {
+        return Ok(TelemetryRecord::default());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// 送信記録を保存
+pub fn save_telemetry_record(record: &TelemetryRecord) -> Result<(), AppError> {
+    let path = get_telemetry_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let content = serde_json::to_string_pretty(record)?;
+    std::fs::write(&path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telemetry_record_serialization() {
+        let record = TelemetryRecord { last_sent_at: Some(1_700_000_000) };
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["lastSentAt"], 1_700_000_000);
+
+        let restored: TelemetryRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, record);
+        assert_eq!(serde_json::from_str::<TelemetryRecord>("{}").unwrap(), TelemetryRecord::default());
+    }
+}
@@ -7,8 +7,12 @@ use crate::obs::settings::{
     AudioSettings, ObsSettings, OutputSettings, VideoSettings,
 };
 use crate::obs::types::{ConnectionConfig, ObsStatus};
+use crate::monitor::display::DisplayInfo;
+use crate::monitor::power::{PowerSource, PowerStatus};
 use crate::services::optimizer::HardwareInfo;
-use crate::storage::metrics_history::SystemMetricsSnapshot;
+use crate::storage::metrics_history::{
+    HistoricalMetrics, ObsStatusSnapshot, SystemMetricsSnapshot,
+};
 
 // =============================================================================
 // SystemMetricsSnapshot ビルダー
@@ -86,6 +90,7 @@ impl SystemMetricsBuilder {
     pub fn build(self) -> SystemMetricsSnapshot {
         SystemMetricsSnapshot {
             cpu_usage: self.cpu_usage,
+            cpu_frequency_mhz: None,
             memory_used: self.memory_used,
             memory_total: self.memory_total,
             gpu_usage: self.gpu_usage,
@@ -96,6 +101,180 @@ impl SystemMetricsBuilder {
     }
 }
 
+// =============================================================================
+// MetricsSnapshotBuilder（分析テスト向け）
+// =============================================================================
+
+/// 分析テスト向けのメトリクススナップショットビルダー
+///
+/// CPU/GPU/メモリ使用率をパーセントで指定でき、時系列データの生成もサポートする。
+/// デフォルトはメモリ総量16GB、GPUメモリ4GB、上り1MB/s・下り500KB/s。
+///
+/// # 使用例
+///
+/// 単一スナップショット:
+/// ```ignore
+/// let snapshot = MetricsSnapshotBuilder::new()
+///     .cpu(85.0)
+///     .gpu(70.0)
+///     .memory_percent(60.0)
+///     .timestamp(Utc::now().timestamp())
+///     .build();
+/// ```
+///
+/// N番目でCPUがスパイクする時系列:
+/// ```ignore
+/// let spike_at = 5;
+/// let metrics = MetricsSnapshotBuilder::series(
+///     10,
+///     |i| if i == spike_at { 98.0 } else { 40.0 },
+///     |_| 50.0,
+///     |_| 60.0,
+/// );
+/// ```
+///
+/// GPU使用率が徐々に上昇する時系列:
+/// ```ignore
+/// let gpu = MetricsSnapshotBuilder::linear_trend(50.0, 95.0, 10);
+/// let metrics = MetricsSnapshotBuilder::series(10, |_| 40.0, |i| gpu[i], |_| 60.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricsSnapshotBuilder {
+    cpu_usage: f32,
+    gpu_usage: Option<f32>,
+    gpu_memory_used: Option<u64>,
+    memory_total: u64,
+    memory_percent: f32,
+    network_upload: u64,
+    network_download: u64,
+    timestamp: i64,
+}
+
+impl Default for MetricsSnapshotBuilder {
+    fn default() -> Self {
+        Self {
+            cpu_usage: 50.0,
+            gpu_usage: Some(50.0),
+            gpu_memory_used: Some(4_000_000_000),
+            memory_total: 16_000_000_000,
+            memory_percent: 50.0,
+            network_upload: 1_000_000,
+            network_download: 500_000,
+            timestamp: 0,
+        }
+    }
+}
+
+impl MetricsSnapshotBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// CPU使用率（%）を設定
+    pub fn cpu(mut self, usage: f32) -> Self {
+        self.cpu_usage = usage;
+        self
+    }
+
+    /// GPU使用率（%）を設定
+    pub fn gpu(mut self, usage: f32) -> Self {
+        self.gpu_usage = Some(usage);
+        self
+    }
+
+    /// GPU情報なし（GPU非搭載・取得失敗）として設定
+    pub fn no_gpu(mut self) -> Self {
+        self.gpu_usage = None;
+        self.gpu_memory_used = None;
+        self
+    }
+
+    /// メモリ使用率（%）を設定
+    pub fn memory_percent(mut self, percent: f32) -> Self {
+        self.memory_percent = percent;
+        self
+    }
+
+    /// ネットワーク速度（バイト/秒）を設定
+    pub fn network(mut self, upload: u64, download: u64) -> Self {
+        self.network_upload = upload;
+        self.network_download = download;
+        self
+    }
+
+    /// タイムスタンプ（UNIX epoch秒）を設定
+    ///
+    /// `SystemMetricsSnapshot` 自体はタイムスタンプを持たないため、
+    /// `build_historical` で `HistoricalMetrics` を生成する際に使用される
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn build(self) -> SystemMetricsSnapshot {
+        let memory_used = (self.memory_total as f32 * self.memory_percent / 100.0) as u64;
+
+        SystemMetricsSnapshot {
+            cpu_usage: self.cpu_usage,
+            cpu_frequency_mhz: None,
+            memory_used,
+            memory_total: self.memory_total,
+            gpu_usage: self.gpu_usage,
+            gpu_memory_used: self.gpu_memory_used,
+            network_upload: self.network_upload,
+            network_download: self.network_download,
+        }
+    }
+
+    /// タイムスタンプ付きの履歴メトリクスを生成
+    pub fn build_historical(self, session_id: &str) -> HistoricalMetrics {
+        let timestamp = self.timestamp;
+        HistoricalMetrics {
+            timestamp,
+            session_id: session_id.to_string(),
+            system: self.build(),
+            obs: ObsStatusSnapshot::empty(),
+        }
+    }
+
+    /// 各フィールドをインデックスの関数として時系列を生成
+    ///
+    /// # Arguments
+    /// * `n` - 生成するスナップショット数
+    /// * `cpu_fn` - インデックスからCPU使用率（%）を返す関数
+    /// * `gpu_fn` - インデックスからGPU使用率（%）を返す関数
+    /// * `memory_fn` - インデックスからメモリ使用率（%）を返す関数
+    pub fn series(
+        n: usize,
+        cpu_fn: impl Fn(usize) -> f32,
+        gpu_fn: impl Fn(usize) -> f32,
+        memory_fn: impl Fn(usize) -> f32,
+    ) -> Vec<SystemMetricsSnapshot> {
+        (0..n)
+            .map(|i| {
+                Self::new()
+                    .cpu(cpu_fn(i))
+                    .gpu(gpu_fn(i))
+                    .memory_percent(memory_fn(i))
+                    .build()
+            })
+            .collect()
+    }
+
+    /// `start` から `end` まで単調に変化する値の列を生成
+    ///
+    /// 最初の値が `start`、最後の値が `end` になる（`n == 1` の場合は `start` のみ）
+    pub fn linear_trend(start: f32, end: f32, n: usize) -> Vec<f32> {
+        // n - 1 で割ることで、最初の値が start、最後の値が end になる
+        let step = if n > 1 {
+            (end - start) / (n - 1) as f32
+        } else {
+            0.0
+        };
+        (0..n).map(|i| start + step * i as f32).collect()
+    }
+}
+
 // =============================================================================
 // HardwareInfo ビルダー
 // =============================================================================
@@ -107,6 +286,9 @@ pub struct HardwareInfoBuilder {
     cpu_cores: usize,
     total_memory_gb: f64,
     gpu_name: Option<String>,
+    gpu_driver_version: Option<String>,
+    power: PowerStatus,
+    displays: Vec<DisplayInfo>,
 }
 
 impl Default for HardwareInfoBuilder {
@@ -116,6 +298,9 @@ impl Default for HardwareInfoBuilder {
             cpu_cores: 8,
             total_memory_gb: 16.0,
             gpu_name: Some("NVIDIA GeForce RTX 3060".to_string()),
+            gpu_driver_version: None,
+            power: PowerStatus::default(),
+            displays: Vec::new(),
         }
     }
 }
@@ -166,16 +351,53 @@ impl HardwareInfoBuilder {
         self
     }
 
+    pub fn driver_version(mut self, version: &str) -> Self {
+        self.gpu_driver_version = Some(version.to_string());
+        self
+    }
+
+    pub fn on_battery(mut self, percent: u8) -> Self {
+        self.power = PowerStatus {
+            source: PowerSource::Battery,
+            battery_percent: Some(percent),
+        };
+        self
+    }
+
+    pub fn primary_display(mut self, width: u32, height: u32, refresh_rate_hz: u32) -> Self {
+        self.displays = vec![DisplayInfo {
+            width,
+            height,
+            refresh_rate_hz,
+            is_primary: true,
+        }];
+        self
+    }
+
+    pub fn secondary_display(mut self, width: u32, height: u32, refresh_rate_hz: u32) -> Self {
+        self.displays.push(DisplayInfo {
+            width,
+            height,
+            refresh_rate_hz,
+            is_primary: false,
+        });
+        self
+    }
+
     pub fn build(self) -> HardwareInfo {
         use crate::monitor::gpu::GpuInfo;
 
-        let gpu = self.gpu_name.map(|name| GpuInfo { name });
+        let driver_version = self.gpu_driver_version;
+        let gpu = self.gpu_name.map(|name| GpuInfo { name, driver_version });
 
         HardwareInfo {
             cpu_name: self.cpu_name,
             cpu_cores: self.cpu_cores,
             total_memory_gb: self.total_memory_gb,
             gpu,
+            power: self.power,
+            displays: self.displays,
+            benchmark_result: None,
         }
     }
 }
@@ -319,6 +541,9 @@ impl ObsSettingsBuilder {
                 keyframe_interval_secs: self.keyframe_interval_secs,
                 preset: self.preset,
                 rate_control: self.rate_control,
+                recording_format: None,
+                recording_path: None,
+                b_frames: None,
             },
         }
     }
@@ -345,6 +570,8 @@ pub struct ObsStatusBuilder {
     fps: Option<f64>,
     render_dropped_frames: Option<u32>,
     output_dropped_frames: Option<u32>,
+    last_ping_latency_ms: Option<u64>,
+    last_ping_success_at: Option<i64>,
 }
 
 impl ObsStatusBuilder {
@@ -409,6 +636,16 @@ impl ObsStatusBuilder {
         self
     }
 
+    pub fn ping_latency(mut self, ms: u64) -> Self {
+        self.last_ping_latency_ms = Some(ms);
+        self
+    }
+
+    pub fn ping_success_at(mut self, timestamp: i64) -> Self {
+        self.last_ping_success_at = Some(timestamp);
+        self
+    }
+
     pub fn build(self) -> ObsStatus {
         ObsStatus {
             connected: self.connected,
@@ -425,6 +662,9 @@ impl ObsStatusBuilder {
             fps: self.fps,
             render_dropped_frames: self.render_dropped_frames,
             output_dropped_frames: self.output_dropped_frames,
+            last_ping_latency_ms: self.last_ping_latency_ms,
+            last_ping_success_at: self.last_ping_success_at,
+            privacy_mode_active: false,
         }
     }
 }
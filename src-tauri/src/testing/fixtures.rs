@@ -7,6 +7,7 @@ use crate::obs::settings::{
     AudioSettings, ObsSettings, OutputSettings, VideoSettings,
 };
 use crate::obs::types::ObsStatus;
+use crate::monitor::power::PowerStatus;
 use crate::services::optimizer::HardwareInfo;
 pub use crate::storage::metrics_history::SystemMetricsSnapshot;
 
@@ -18,6 +19,7 @@ pub use crate::storage::metrics_history::SystemMetricsSnapshot;
 pub fn healthy_system_metrics() -> SystemMetricsSnapshot {
     SystemMetricsSnapshot {
         cpu_usage: 35.0,
+        cpu_frequency_mhz: None,
         memory_used: 8_000_000_000,      // 8GB
         memory_total: 32_000_000_000,     // 32GB
         gpu_usage: Some(40.0),
@@ -31,6 +33,7 @@ pub fn healthy_system_metrics() -> SystemMetricsSnapshot {
 pub fn high_load_system_metrics() -> SystemMetricsSnapshot {
     SystemMetricsSnapshot {
         cpu_usage: 85.0,
+        cpu_frequency_mhz: None,
         memory_used: 28_000_000_000,      // 28GB
         memory_total: 32_000_000_000,     // 32GB
         gpu_usage: Some(92.0),
@@ -44,6 +47,7 @@ pub fn high_load_system_metrics() -> SystemMetricsSnapshot {
 pub fn critical_system_metrics() -> SystemMetricsSnapshot {
     SystemMetricsSnapshot {
         cpu_usage: 98.0,
+        cpu_frequency_mhz: None,
         memory_used: 31_500_000_000,      // 31.5GB
         memory_total: 32_000_000_000,     // 32GB
         gpu_usage: Some(99.0),
@@ -57,6 +61,7 @@ pub fn critical_system_metrics() -> SystemMetricsSnapshot {
 pub fn no_gpu_system_metrics() -> SystemMetricsSnapshot {
     SystemMetricsSnapshot {
         cpu_usage: 50.0,
+        cpu_frequency_mhz: None,
         memory_used: 8_000_000_000,
         memory_total: 16_000_000_000,
         gpu_usage: None,
@@ -80,7 +85,11 @@ pub fn high_end_hardware() -> HardwareInfo {
         total_memory_gb: 64.0,
         gpu: Some(GpuInfo {
             name: "NVIDIA GeForce RTX 4090".to_string(),
+            driver_version: None,
         }),
+        power: PowerStatus::default(),
+        displays: Vec::new(),
+        benchmark_result: None,
     }
 }
 
@@ -94,7 +103,11 @@ pub fn mid_range_hardware() -> HardwareInfo {
         total_memory_gb: 32.0,
         gpu: Some(GpuInfo {
             name: "NVIDIA GeForce RTX 3060".to_string(),
+            driver_version: None,
         }),
+        power: PowerStatus::default(),
+        displays: Vec::new(),
+        benchmark_result: None,
     }
 }
 
@@ -105,6 +118,9 @@ pub fn low_end_hardware() -> HardwareInfo {
         cpu_cores: 4,
         total_memory_gb: 8.0,
         gpu: None,
+        power: PowerStatus::default(),
+        displays: Vec::new(),
+        benchmark_result: None,
     }
 }
 
@@ -133,6 +149,9 @@ pub fn standard_obs_settings() -> ObsSettings {
             keyframe_interval_secs: 2,
             preset: Some("p5".to_string()),
             rate_control: Some("CBR".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         },
     }
 }
@@ -158,6 +177,9 @@ pub fn low_spec_obs_settings() -> ObsSettings {
             keyframe_interval_secs: 2,
             preset: Some("veryfast".to_string()),
             rate_control: Some("CBR".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         },
     }
 }
@@ -183,6 +205,9 @@ pub fn high_end_obs_settings() -> ObsSettings {
             keyframe_interval_secs: 2,
             preset: Some("p6".to_string()),
             rate_control: Some("CBR".to_string()),
+            recording_format: None,
+            recording_path: None,
+            b_frames: None,
         },
     }
 }
@@ -208,6 +233,8 @@ pub fn streaming_obs_status() -> ObsStatus {
         fps: Some(60.0),
         render_dropped_frames: Some(5),
         output_dropped_frames: Some(2),
+        last_ping_latency_ms: None,
+        last_ping_success_at: None,
     }
 }
 
@@ -228,6 +255,8 @@ pub fn recording_obs_status() -> ObsStatus {
         fps: Some(60.0),
         render_dropped_frames: Some(0),
         output_dropped_frames: Some(0),
+        last_ping_latency_ms: None,
+        last_ping_success_at: None,
     }
 }
 
@@ -248,6 +277,8 @@ pub fn idle_obs_status() -> ObsStatus {
         fps: Some(60.0),
         render_dropped_frames: None,
         output_dropped_frames: None,
+        last_ping_latency_ms: None,
+        last_ping_success_at: None,
     }
 }
 
@@ -281,6 +312,7 @@ pub fn generate_metrics_history(count: usize, base: SystemMetricsSnapshot) -> Ve
             let variance = (i as f32 % 10.0) - 5.0;
             SystemMetricsSnapshot {
                 cpu_usage: (base.cpu_usage + variance).clamp(0.0, 100.0),
+                cpu_frequency_mhz: None,
                 gpu_usage: base.gpu_usage.map(|g| (g + variance).clamp(0.0, 100.0)),
                 ..base.clone()
             }
@@ -16,3 +16,90 @@ pub mod assertions;
 pub use fixtures::*;
 pub use builders::*;
 pub use assertions::*;
+
+/// 統合テスト（tests/）からOBS関連のAPIにアクセスするための再エクスポート
+///
+/// `commands`/`services` はクレート外に公開されていないため、
+/// モックOBSサーバーを使ったエンドツーエンドテストに必要な入口のみを公開する。
+pub mod obs_api {
+    pub use crate::commands::{
+        get_live_output_stats, get_obs_settings_command, get_scene_item_enabled, get_scene_list,
+        list_scene_items, set_scene_item_enabled,
+    };
+    pub use crate::obs::{
+        get_obs_client, recent_output_bitrates, ConnectionConfig, ConnectionState, OutputStats,
+        SceneItem,
+    };
+    pub use crate::services::obs::obs_service;
+}
+
+/// 統合テスト（tests/）から設定バックアップ関連のAPIにアクセスするための再エクスポート
+pub mod backup_api {
+    pub use crate::commands::{
+        apply_custom_settings, apply_custom_settings_with_result, apply_profile, backup_current_settings,
+        get_backups, get_current_operation, restore_backup,
+    };
+    pub use crate::error::ERROR_CODE_OPERATION_IN_PROGRESS;
+    pub use crate::storage::config::{StreamingPlatform, StreamingStyle};
+    pub use crate::storage::paths::set_storage_root;
+    pub use crate::storage::BackupTrigger;
+}
+
+/// 統合テスト（tests/）から設定プロファイル関連のAPIにアクセスするための再エクスポート
+pub mod profile_api {
+    pub use crate::commands::{
+        apply_profile, get_backups, get_profile, save_current_settings_as_profile, save_profile,
+    };
+    pub use crate::storage::config::{StreamingPlatform, StreamingStyle};
+    pub use crate::storage::delete_profile;
+    pub use crate::storage::profiles::AudioInputSettings;
+}
+
+/// 統合テスト（tests/）からスナップショットテストの対象にアクセスするための再エクスポート
+pub mod snapshot_api {
+    pub use crate::commands::{build_analysis_result, AnalysisInput};
+    pub use crate::monitor::gpu::GpuInfo;
+    pub use crate::monitor::power::{PowerSource, PowerStatus};
+    pub use crate::monitor::ProcessPriority;
+    pub use crate::obs::{AudioMonitorType, ObsSettings};
+    pub use crate::services::audio_monitor::AudioInputStatus;
+    pub use crate::services::exporter::ReportExporter;
+    pub use crate::storage::config::{
+        AudioMonitoringConfig, SetupType, StreamingPlatform, StreamingProtocol, StreamingStyle,
+    };
+}
+
+/// 統合テスト（tests/）からエクスポート形式のスキーマにアクセスするための再エクスポート
+pub mod schema_api {
+    pub use crate::services::schema::{
+        analysis_result_schema, analysis_result_v1_schema, app_config_schema,
+        diagnostic_report_schema, historical_metrics_schema, session_export_schema, validate,
+    };
+}
+
+/// 統合テスト（tests/）からコマンドをエンドツーエンドで実行するための再エクスポート
+///
+/// `set_storage_root` で保存先を一時ディレクトリに差し替え、モックOBSサーバーに接続した状態で
+/// コマンドの関数を直接（IPCを介さずに）呼び出す。
+pub mod command_api {
+    pub use crate::commands::{
+        analyze_settings, apply_recommended_settings, backup_current_settings, get_backups,
+        restore_backup,
+    };
+    pub use crate::obs::ConnectionConfig;
+    pub use crate::services::obs::obs_service;
+    pub use crate::storage::config::{load_config, save_config, AppConfig, StreamingPlatform};
+    pub use crate::storage::paths::set_storage_root;
+    pub use crate::storage::BackupTrigger;
+}
+
+/// メディアソースの自動アクションの統合テスト用の再エクスポート
+pub mod media_autoaction_api {
+    pub use crate::commands::set_media_autoaction;
+    pub use crate::error::ERROR_CODE_VALIDATION_FAILED;
+    pub use crate::obs::ConnectionConfig;
+    pub use crate::services::media_autoaction::run_media_autoaction;
+    pub use crate::services::obs::obs_service;
+    pub use crate::storage::config::{load_config, MediaAutoAction};
+    pub use crate::storage::paths::set_storage_root;
+}
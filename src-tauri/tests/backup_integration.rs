@@ -0,0 +1,224 @@
+// 設定バックアップ統合テスト
+//
+// モックOBSサーバーに接続し、バックアップ → 設定適用 → 復元の一連の流れを
+// コマンド層まで通してテストする。
+// バックアップの保存先は `set_storage_root` で一時ディレクトリに差し替え、
+// ユーザーのプロファイルディレクトリには書き込まない。
+//
+// 実行: cargo test --features testing --test backup_integration
+
+mod common;
+
+use common::{unique_test_path, MockObsServer};
+use obs_optimizer_app_lib::testing::backup_api::{
+    apply_custom_settings, apply_custom_settings_with_result, apply_profile, backup_current_settings,
+    get_backups, get_current_operation, restore_backup, set_storage_root, BackupTrigger,
+    StreamingPlatform, StreamingStyle, ERROR_CODE_OPERATION_IN_PROGRESS,
+};
+use obs_optimizer_app_lib::testing::obs_api::{obs_service, ConnectionConfig};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// OBSクライアントと保存先はグローバルなため、テストを直列化する
+static OBS_TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// モックOBSサーバーと一時ディレクトリの保存先を用意したテスト環境
+struct BackupHarness {
+    server: MockObsServer,
+    storage_root: PathBuf,
+}
+
+impl BackupHarness {
+    /// 保存先を差し替えてモックサーバーに接続
+    async fn start() -> Self {
+        let storage_root = unique_test_path("backup_integration");
+        std::fs::create_dir_all(&storage_root).unwrap();
+        set_storage_root(Some(storage_root.clone()));
+
+        let server = connect_mock_server().await;
+        Self { server, storage_root }
+    }
+
+    /// 切断して保存先を元に戻す
+    async fn shutdown(self) {
+        // テスト内で切断済みの場合があるため、切断の失敗は無視する
+        let _ = obs_service().disconnect().await;
+        set_storage_root(None);
+        let _ = std::fs::remove_dir_all(&self.storage_root);
+    }
+}
+
+/// 設定の読み書きに応答するモックサーバーを起動して接続
+async fn connect_mock_server() -> MockObsServer {
+    let server = MockObsServer::start().await.unwrap();
+    server.video_settings(initial_video_settings());
+    server.any_request(|request_type| match request_type {
+        "GetOutputList" => json!({ "outputs": [] }),
+        "GetProfileParameter" => json!({
+            "parameterValue": "Advanced",
+            "defaultParameterValue": null
+        }),
+        // Set系のリクエストは応答データなし
+        _ => Value::Null,
+    });
+
+    let config = ConnectionConfig {
+        host: "127.0.0.1".to_string(),
+        port: server.port(),
+        password: None,
+    };
+    obs_service().connect(config).await.unwrap();
+    server
+}
+
+/// 適用前のビデオ設定（1280x720@60、ベース解像度1920x1080）
+fn initial_video_settings() -> Value {
+    json!({
+        "fpsNumerator": 60,
+        "fpsDenominator": 1,
+        "baseWidth": 1920,
+        "baseHeight": 1080,
+        "outputWidth": 1280,
+        "outputHeight": 720
+    })
+}
+
+/// 受信したリクエストのうち指定種別の件数
+fn count_requests(server: &MockObsServer, request_type: &str) -> usize {
+    server
+        .received_requests()
+        .iter()
+        .filter(|r| r.as_str() == request_type)
+        .count()
+}
+
+#[tokio::test]
+async fn test_backup_apply_restore_cycle() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let harness = BackupHarness::start().await;
+
+    // 1. 手動バックアップ
+    let manual_id = backup_current_settings().await.unwrap();
+
+    // 2. カスタム推奨設定の適用（適用前に自動バックアップされる）
+    apply_custom_settings(StreamingPlatform::Twitch, StreamingStyle::Gaming, 10.0, None, None)
+        .await
+        .unwrap();
+
+    let backups = get_backups().await.unwrap();
+    assert_eq!(backups.len(), 2, "手動と適用前の2件が作成される");
+
+    let manual = backups.iter().find(|b| b.id == manual_id).unwrap();
+    assert_eq!(manual.trigger, BackupTrigger::Manual);
+
+    let pre_optimization = backups
+        .iter()
+        .find(|b| b.trigger == BackupTrigger::PreOptimization)
+        .unwrap();
+    assert_eq!(pre_optimization.settings.video.output_width, 1280);
+    assert_eq!(pre_optimization.settings.video.output_height, 720);
+    assert_eq!(pre_optimization.settings.video.fps, 60);
+
+    // 3. バックアップから復元（ビデオ設定が再適用される）
+    let set_video_before = count_requests(&harness.server, "SetVideoSettings");
+    restore_backup(pre_optimization.id.clone()).await.unwrap();
+    assert_eq!(count_requests(&harness.server, "SetVideoSettings"), set_video_before + 1);
+
+    // 復元ではバックアップは作成されない
+    assert_eq!(get_backups().await.unwrap().len(), 2);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_apply_reports_video_settings_clamped_by_obs() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let harness = BackupHarness::start().await;
+    // 出力解像度を640x360までに丸めるOBSを再現（推奨解像度はこれより必ず大きい）
+    harness.server.clamp_video_settings(|video| {
+        video["outputWidth"] = json!(video["outputWidth"].as_u64().unwrap().min(640));
+        video["outputHeight"] = json!(video["outputHeight"].as_u64().unwrap().min(360));
+    });
+
+    let result =
+        apply_custom_settings_with_result(StreamingPlatform::Twitch, StreamingStyle::Gaming, 10.0, None, None)
+            .await
+            .unwrap();
+
+    // 読み戻しで不一致を検出し、1回だけ再適用する
+    assert_eq!(count_requests(&harness.server, "SetVideoSettings"), 2);
+    let verification = result.video_verification.unwrap();
+    assert!(verification.retried);
+    assert!(!verification.output_width.verified);
+    assert_eq!(verification.output_width.actual, 640);
+    assert!(verification.fps_numerator.verified);
+
+    // 不一致は成功扱いにせず警告として返す
+    assert_eq!(result.failed_count, result.errors.len());
+    assert!(result.errors.iter().any(|e| e.contains("出力解像度（幅）")));
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_apply_profile_creates_pre_apply_backup() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let harness = BackupHarness::start().await;
+
+    let manual_id = backup_current_settings().await.unwrap();
+    apply_profile(manual_id.clone()).await.unwrap();
+
+    let backups = get_backups().await.unwrap();
+    assert_eq!(backups.len(), 2);
+    assert!(backups
+        .iter()
+        .any(|b| b.id != manual_id && b.trigger == BackupTrigger::PreProfileApply));
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_restore_unknown_backup_fails() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let harness = BackupHarness::start().await;
+
+    let result = restore_backup("00000000-0000-0000-0000-000000000000".to_string()).await;
+    assert!(result.is_err());
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_concurrent_applies_are_serialized() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let harness = BackupHarness::start().await;
+    // 1つ目の適用が完了する前に2つ目を開始できるよう、OBSの応答を遅らせる
+    harness.server.delay_response("GetVideoSettings", Duration::from_millis(200));
+
+    let apply = || apply_custom_settings(StreamingPlatform::Twitch, StreamingStyle::Gaming, 10.0, None, None);
+    let observe = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        get_current_operation().await.unwrap()
+    };
+    let (first, second, running) = tokio::join!(apply(), apply(), observe);
+
+    // 一方のみが実行され、もう一方は実行中エラーになる
+    let results = [first, second];
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    let rejected = results.iter().find_map(|r| r.as_ref().err()).unwrap();
+    assert_eq!(rejected.code(), ERROR_CODE_OPERATION_IN_PROGRESS);
+
+    // 実行中の操作が取得でき、完了後はクリアされる
+    assert!(running.is_some());
+    assert!(get_current_operation().await.unwrap().is_none());
+
+    // OBSへの書き込みは1回分のみ
+    assert_eq!(count_requests(&harness.server, "SetVideoSettings"), 1);
+
+    // ロック解放後は再び適用できる
+    apply().await.unwrap();
+
+    harness.shutdown().await;
+}
@@ -0,0 +1,187 @@
+// コマンド統合テスト
+//
+// 保存先を一時ディレクトリに差し替え、モックOBSサーバーに接続した状態で
+// コマンドの関数を直接呼び出し、接続 → 分析 → 推奨設定の適用 → バックアップ・復元の
+// 一連の流れを検証する。保存先のファイルとOBSへのリクエスト順序の両方を確認する。
+//
+// 実行: cargo test --features testing --test command_integration
+
+mod common;
+
+use common::{unique_test_path, MockObsServer};
+use obs_optimizer_app_lib::testing::command_api::{
+    analyze_settings, apply_recommended_settings, backup_current_settings, get_backups,
+    load_config, obs_service, restore_backup, save_config, set_storage_root, AppConfig,
+    BackupTrigger, ConnectionConfig, StreamingPlatform,
+};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// OBSクライアントと保存先はグローバルなため、テストを直列化する
+static COMMAND_TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// モックOBSサーバーと一時ディレクトリの保存先を用意したテスト環境
+struct CommandHarness {
+    server: MockObsServer,
+    storage_root: PathBuf,
+}
+
+impl CommandHarness {
+    /// 保存先を差し替えてモックサーバーに接続
+    async fn start() -> Self {
+        let storage_root = unique_test_path("command_integration");
+        std::fs::create_dir_all(&storage_root).unwrap();
+        set_storage_root(Some(storage_root.clone()));
+
+        let server = MockObsServer::start().await.unwrap();
+        server.video_settings(json!({
+            "fpsNumerator": 60,
+            "fpsDenominator": 1,
+            "baseWidth": 1920,
+            "baseHeight": 1080,
+            "outputWidth": 1280,
+            "outputHeight": 720
+        }));
+        server.any_request(mock_response);
+
+        let config = ConnectionConfig {
+            host: "127.0.0.1".to_string(),
+            port: server.port(),
+            password: None,
+        };
+        obs_service().connect(config).await.unwrap();
+
+        Self { server, storage_root }
+    }
+
+    /// アプリケーションの設定ディレクトリ
+    fn config_dir(&self) -> PathBuf {
+        self.storage_root.join("config")
+    }
+
+    /// 保存されたプロファイル（バックアップを含む）のファイル数
+    fn profile_file_count(&self) -> usize {
+        std::fs::read_dir(self.config_dir().join("profiles"))
+            .map(|entries| entries.count())
+            .unwrap_or(0)
+    }
+
+    /// これまでに受信したリクエスト種別の一覧
+    fn requests(&self) -> Vec<String> {
+        self.server.received_requests()
+    }
+
+    /// 切断して保存先を元に戻す
+    async fn shutdown(self) {
+        // テスト内で切断済みの場合があるため、切断の失敗は無視する
+        let _ = obs_service().disconnect().await;
+        set_storage_root(None);
+        let _ = std::fs::remove_dir_all(&self.storage_root);
+    }
+}
+
+/// 配信先が設定済みのOBSを再現する応答
+fn mock_response(request_type: &str) -> Value {
+    match request_type {
+        "GetOutputList" => json!({ "outputs": [] }),
+        "GetProfileParameter" => json!({
+            "parameterValue": "Advanced",
+            "defaultParameterValue": null
+        }),
+        "GetStreamServiceSettings" => json!({
+            "streamServiceType": "rtmp_custom",
+            "streamServiceSettings": {
+                "server": "rtmp://live.twitch.tv/app",
+                "key": "live_test_key"
+            }
+        }),
+        // Set系のリクエストは応答データなし
+        _ => Value::Null,
+    }
+}
+
+/// 指定したリクエスト種別が最初に現れる位置
+fn first_index(requests: &[String], request_type: &str) -> Option<usize> {
+    requests.iter().position(|r| r == request_type)
+}
+
+/// 指定したリクエスト種別の件数
+fn count(requests: &[String], request_type: &str) -> usize {
+    requests.iter().filter(|r| r.as_str() == request_type).count()
+}
+
+/// 保存先のファイルを読み込んでJSONとして返す
+fn read_json(path: &Path) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_analyze_apply_backup_restore_flow() {
+    let _guard = COMMAND_TEST_LOCK.lock().await;
+    let harness = CommandHarness::start().await;
+
+    // 設定ファイルは一時ディレクトリに保存される
+    let mut config = AppConfig::default();
+    config.streaming_mode.platform = StreamingPlatform::Twitch;
+    save_config(&config).unwrap();
+    assert!(harness.config_dir().join("config.json").exists());
+    assert_eq!(load_config().unwrap().streaming_mode.platform, StreamingPlatform::Twitch);
+
+    // 1. 分析（OBSへの書き込みは行わない）
+    let analysis = analyze_settings(None).await.unwrap();
+    assert!(analysis.quality_score.total <= 100);
+    assert!(!harness.requests().iter().any(|r| r.starts_with("Set")));
+
+    // 2. 推奨設定の適用（適用前に自動バックアップが作成される）
+    let before_apply = harness.requests().len();
+    apply_recommended_settings(None).await.unwrap();
+    let apply_requests = harness.requests()[before_apply..].to_vec();
+
+    // 配信先の検証 → 現在の設定の読み込み（バックアップ） → 書き込みの順に呼ばれる
+    let destination = first_index(&apply_requests, "GetStreamServiceSettings").unwrap();
+    let read_video = first_index(&apply_requests, "GetVideoSettings").unwrap();
+    let write_video = first_index(&apply_requests, "SetVideoSettings").unwrap();
+    assert!(destination < write_video);
+    assert!(read_video < write_video);
+    assert_eq!(count(&apply_requests, "SetVideoSettings"), 1);
+
+    let backups = get_backups().await.unwrap();
+    assert_eq!(backups.len(), 1);
+    let backup = &backups[0];
+    assert_eq!(backup.trigger, BackupTrigger::PreOptimization);
+    assert_eq!(harness.profile_file_count(), 1);
+
+    // バックアップファイルには適用前の設定が保存される
+    let saved = read_json(&harness.config_dir().join("profiles").join(format!("{}.json", backup.id)));
+    assert_eq!(saved["settings"]["video"]["outputWidth"], 1280);
+    assert_eq!(saved["settings"]["video"]["outputHeight"], 720);
+
+    // 3. 手動バックアップ
+    let manual_id = backup_current_settings().await.unwrap();
+    assert_eq!(harness.profile_file_count(), 2);
+
+    // 4. 復元（ビデオ設定が再適用され、新しいバックアップは作成されない）
+    let before_restore = harness.requests().len();
+    restore_backup(manual_id).await.unwrap();
+    let restore_requests = harness.requests()[before_restore..].to_vec();
+    assert_eq!(count(&restore_requests, "SetVideoSettings"), 1);
+    assert_eq!(harness.profile_file_count(), 2);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_apply_without_connection_leaves_storage_untouched() {
+    let _guard = COMMAND_TEST_LOCK.lock().await;
+    let harness = CommandHarness::start().await;
+    obs_service().disconnect().await.unwrap();
+
+    // 未接続では適用されず、バックアップも作成されない
+    assert!(apply_recommended_settings(None).await.is_err());
+    assert!(get_backups().await.unwrap().is_empty());
+    assert_eq!(harness.profile_file_count(), 0);
+    assert!(!harness.requests().iter().any(|r| r.starts_with("Set")));
+
+    harness.shutdown().await;
+}
@@ -4,7 +4,15 @@
 // 注意: ここでは外部クレートに依存しない基本的な型のみを定義する。
 // obs_optimizer_app_libの型を使用する場合は、各テストファイルでインポートすること。
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
 
 // =============================================================================
 // テスト用パス
@@ -148,3 +156,438 @@ pub async fn wait_ms(ms: u64) {
 pub fn wait_ms_sync(ms: u64) {
     std::thread::sleep(std::time::Duration::from_millis(ms));
 }
+
+// =============================================================================
+// モックOBS WebSocketサーバー
+// =============================================================================
+
+/// ワイルドカードリクエストハンドラー
+type RequestHandler = Box<dyn Fn(&str) -> Value + Send + Sync>;
+
+/// 書き込まれたビデオ設定を変換するハンドラー（OBSによる値の丸めの再現用）
+type VideoClamp = Box<dyn Fn(&mut Value) + Send + Sync>;
+
+/// モックOBSサーバーの共有状態
+#[derive(Default)]
+struct MockObsState {
+    /// リクエスト種別ごとの応答データ
+    responses: HashMap<String, Value>,
+    /// 未登録リクエスト用のハンドラー
+    wildcard: Option<RequestHandler>,
+    /// 受信したリクエスト種別の履歴
+    received: Vec<String>,
+    /// リクエスト種別ごとの応答遅延
+    delays: HashMap<String, Duration>,
+    /// 保持しているビデオ設定（`video_settings` で有効化）
+    video: Option<Value>,
+    /// `SetVideoSettings` の書き込み値に適用する変換
+    video_clamp: Option<VideoClamp>,
+}
+
+/// OBS WebSocket（v5プロトコル）のモックサーバー
+///
+/// ランダムなポートで待ち受け、単一のWebSocket接続を受け付ける。
+/// `Hello`/`Identify` ハンドシェイクに応答した後、`expect` で登録された
+/// リクエスト種別には登録済みの `responseData` を返す。
+/// 未登録のリクエストは `any_request` のハンドラーで処理し、
+/// ハンドラーもなければ `UnknownRequestType`（204）エラーを返す。
+///
+/// `Drop` 時にバックグラウンドタスクを停止する。
+///
+/// # Example
+/// ```ignore
+/// let server = MockObsServer::start().await?;
+/// server.expect("GetSceneList", json!({ "scenes": [] }));
+/// let config = ConnectionConfig { host: "127.0.0.1".into(), port: server.port(), password: None };
+/// ```
+pub struct MockObsServer {
+    port: u16,
+    state: Arc<Mutex<MockObsState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockObsServer {
+    /// サーバーを起動（127.0.0.1のランダムポート）
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let state = Arc::new(Mutex::new(MockObsState::default()));
+
+        let task_state = Arc::clone(&state);
+        let task = tokio::spawn(async move {
+            if let Ok((stream, _)) = listener.accept().await {
+                // 接続が切れた・プロトコルエラーの場合はそのまま終了
+                let _ = serve_connection(stream, task_state).await;
+            }
+        });
+
+        let server = Self { port, state, task };
+        // obwsは接続時にGetVersionでバージョンを検証するため、既定の応答を登録
+        server.expect("GetVersion", default_version_response());
+        Ok(server)
+    }
+
+    /// 待ち受けポートを取得
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// リクエスト種別に対する応答データ（`responseData`）を登録
+    pub fn expect(&self, request_type: &str, response_json: Value) -> &Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.responses.insert(request_type.to_string(), response_json);
+        }
+        self
+    }
+
+    /// 未登録のリクエストを処理するハンドラーを登録
+    ///
+    /// ハンドラーはリクエスト種別を受け取り、`responseData` を返す
+    pub fn any_request(&self, handler: impl Fn(&str) -> Value + Send + Sync + 'static) -> &Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.wildcard = Some(Box::new(handler));
+        }
+        self
+    }
+
+    /// リクエスト種別に対する応答を指定時間だけ遅らせる（低速なOBSの再現用）
+    pub fn delay_response(&self, request_type: &str, delay: Duration) -> &Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.delays.insert(request_type.to_string(), delay);
+        }
+        self
+    }
+
+    /// ビデオ設定を保持し、`SetVideoSettings` の内容を `GetVideoSettings` で返す
+    ///
+    /// `expect` / `any_request` より優先される
+    pub fn video_settings(&self, initial: Value) -> &Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.video = Some(initial);
+        }
+        self
+    }
+
+    /// `SetVideoSettings` で書き込まれたビデオ設定を保持する前に変換する
+    ///
+    /// 範囲外の値を黙って丸めるOBSの再現に使用する。`video_settings` と併用すること
+    pub fn clamp_video_settings(&self, clamp: impl Fn(&mut Value) + Send + Sync + 'static) -> &Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.video_clamp = Some(Box::new(clamp));
+        }
+        self
+    }
+
+    /// これまでに受信したリクエスト種別の一覧を取得
+    pub fn received_requests(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .map(|state| state.received.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MockObsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// GetVersionの既定応答
+fn default_version_response() -> Value {
+    json!({
+        "obsVersion": "30.2.0",
+        "obsWebSocketVersion": "5.5.0",
+        "rpcVersion": 1,
+        "availableRequests": [],
+        "supportedImageFormats": ["png", "jpg"],
+        "platform": "windows",
+        "platformDescription": "Windows 11 (mock)"
+    })
+}
+
+/// 1接続分のWebSocketセッションを処理
+async fn serve_connection(
+    mut stream: TcpStream,
+    state: Arc<Mutex<MockObsState>>,
+) -> std::io::Result<()> {
+    accept_handshake(&mut stream).await?;
+
+    // Hello（OpCode 0）: 認証なし
+    let hello = json!({ "op": 0, "d": { "obsWebSocketVersion": "5.5.0", "rpcVersion": 1 } });
+    write_text_frame(&mut stream, &hello.to_string()).await?;
+
+    while let Some(text) = read_text_frame(&mut stream).await? {
+        let Ok(message) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+
+        let reply = match message["op"].as_u64() {
+            // Identify → Identified
+            Some(1) => json!({ "op": 2, "d": { "negotiatedRpcVersion": 1 } }),
+            // Request → RequestResponse
+            Some(6) => {
+                let delay = message["d"]["requestType"].as_str().and_then(|request_type| {
+                    state
+                        .lock()
+                        .ok()
+                        .and_then(|state| state.delays.get(request_type).copied())
+                });
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+                build_request_response(&message["d"], &state)
+            }
+            _ => continue,
+        };
+        write_text_frame(&mut stream, &reply.to_string()).await?;
+    }
+
+    Ok(())
+}
+
+/// 保持しているビデオ設定の読み書きを処理（ビデオ設定を保持していない場合はNone）
+fn handle_video_settings(state: &mut MockObsState, request_type: &str, request_data: &Value) -> Option<Value> {
+    let MockObsState { video, video_clamp, .. } = state;
+    let video = video.as_mut()?;
+
+    match request_type {
+        "GetVideoSettings" => Some(video.clone()),
+        "SetVideoSettings" => {
+            let mut written = video.clone();
+            if let (Some(written), Some(fields)) = (written.as_object_mut(), request_data.as_object()) {
+                for (key, value) in fields.iter().filter(|(_, value)| !value.is_null()) {
+                    written.insert(key.clone(), value.clone());
+                }
+            }
+            if let Some(clamp) = video_clamp {
+                clamp(&mut written);
+            }
+            *video = written;
+            Some(Value::Null)
+        }
+        _ => None,
+    }
+}
+
+/// リクエストに対するRequestResponse（OpCode 7）を構築
+fn build_request_response(request: &Value, state: &Arc<Mutex<MockObsState>>) -> Value {
+    let request_type = request["requestType"].as_str().unwrap_or_default().to_string();
+    let request_id = request["requestId"].clone();
+
+    let response_data = state.lock().ok().and_then(|mut state| {
+        state.received.push(request_type.clone());
+        if let Some(data) = handle_video_settings(&mut state, &request_type, &request["requestData"]) {
+            return Some(data);
+        }
+        state
+            .responses
+            .get(&request_type)
+            .cloned()
+            .or_else(|| state.wildcard.as_ref().map(|handler| handler(&request_type)))
+    });
+
+    match response_data {
+        Some(data) => json!({
+            "op": 7,
+            "d": {
+                "requestType": request_type,
+                "requestId": request_id,
+                "requestStatus": { "result": true, "code": 100 },
+                "responseData": data
+            }
+        }),
+        None => json!({
+            "op": 7,
+            "d": {
+                "requestType": request_type,
+                "requestId": request_id,
+                "requestStatus": {
+                    "result": false,
+                    "code": 204,
+                    "comment": "Mock server has no response for this request"
+                }
+            }
+        }),
+    }
+}
+
+/// HTTPアップグレード（RFC 6455）ハンドシェイクに応答
+async fn accept_handshake(stream: &mut TcpStream) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let header = |name: &str| {
+        request.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim().eq_ignore_ascii_case(name).then(|| value.trim().to_string())
+        })
+    };
+
+    let key = header("Sec-WebSocket-Key")
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "missing Sec-WebSocket-Key"))?;
+    let accept = base64_encode(&sha1(format!("{key}258EAFA5-E914-47DA-95CA-C5AB0DC85B11").as_bytes()));
+
+    let mut response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n"
+    );
+    // サブプロトコルが要求された場合はそのまま返す
+    if let Some(protocol) = header("Sec-WebSocket-Protocol") {
+        let first = protocol.split(',').next().unwrap_or_default().trim().to_string();
+        response.push_str(&format!("Sec-WebSocket-Protocol: {first}\r\n"));
+    }
+    response.push_str("\r\n");
+
+    stream.write_all(response.as_bytes()).await
+}
+
+/// テキストフレームを1つ読み取る（接続終了時は`None`）
+///
+/// クライアントからのフレームはマスクされている前提。フラグメント化は扱わない。
+async fn read_text_frame(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    loop {
+        let mut head = [0u8; 2];
+        if stream.read_exact(&mut head).await.is_err() {
+            return Ok(None);
+        }
+
+        let opcode = head[0] & 0x0f;
+        let masked = head[1] & 0x80 != 0;
+        let len = match head[1] & 0x7f {
+            126 => {
+                let mut ext = [0u8; 2];
+                stream.read_exact(&mut ext).await?;
+                u64::from(u16::from_be_bytes(ext))
+            }
+            127 => {
+                let mut ext = [0u8; 8];
+                stream.read_exact(&mut ext).await?;
+                u64::from_be_bytes(ext)
+            }
+            n => u64::from(n),
+        };
+
+        let mut mask = [0u8; 4];
+        if masked {
+            stream.read_exact(&mut mask).await?;
+        }
+
+        let mut payload = vec![0u8; len as usize];
+        stream.read_exact(&mut payload).await?;
+        if masked {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+        }
+
+        match opcode {
+            0x1 => return Ok(Some(String::from_utf8_lossy(&payload).into_owned())),
+            0x8 => return Ok(None),
+            // Ping → Pong
+            0x9 => write_frame(stream, 0xA, &payload).await?,
+            _ => {}
+        }
+    }
+}
+
+/// テキストフレームを書き込む（サーバー→クライアントはマスクなし）
+async fn write_text_frame(stream: &mut TcpStream, text: &str) -> std::io::Result<()> {
+    write_frame(stream, 0x1, text.as_bytes()).await
+}
+
+/// フレームを書き込む
+async fn write_frame(stream: &mut TcpStream, opcode: u8, payload: &[u8]) -> std::io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= usize::from(u16::MAX) => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame).await
+}
+
+/// SHA-1ダイジェスト（ハンドシェイクのSec-WebSocket-Accept算出用）
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476, 0xC3D2_E1F0];
+
+    let mut message = data.to_vec();
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, add) in h.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (i, value) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+/// Base64エンコード（標準アルファベット、パディングあり）
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        out.push(ALPHABET[(n >> 18) as usize & 0x3f] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 0x3f] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 0x3f] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 0x3f] as char } else { '=' });
+    }
+    out
+}
@@ -5,6 +5,8 @@
 
 pub mod fixtures;
 pub mod assertions;
+pub mod snapshot;
 
 pub use fixtures::*;
 pub use assertions::*;
+pub use snapshot::*;
@@ -0,0 +1,90 @@
+// スナップショットテスト用ヘルパー
+//
+// 出力をJSONとして `tests/snapshots/<name>.snap.json` に保存し、以降の実行で比較する。
+//
+// - スナップショットが存在しない場合・出力が変わった場合は `<name>.snap.json.new` に
+//   新しい出力を書き出して失敗する
+// - 新しいスナップショットの記録・意図した出力の変更は `UPDATE_SNAPSHOTS=1 cargo test` で行い、
+//   記録した `.snap.json` をコミットする
+
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// マスクした値の置換文字列
+pub const REDACTED: &str = "[redacted]";
+
+/// スナップショットの保存ディレクトリ
+fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+/// 実行ごとに変わる値をマスク
+///
+/// `paths` はドット区切りのキーで指定する。配列の全要素は `[]` で指定する。
+/// 例: `"generatedAt"`, `"problems[].id"`
+pub fn redact(value: &mut Value, paths: &[&str]) {
+    for path in paths {
+        let segments: Vec<&str> = path.split('.').collect();
+        redact_path(value, &segments);
+    }
+}
+
+fn redact_path(value: &mut Value, segments: &[&str]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    };
+
+    let (key, each) = segment
+        .strip_suffix("[]")
+        .map_or((*segment, false), |key| (key, true));
+    let Some(child) = value.get_mut(key) else {
+        return;
+    };
+
+    if each {
+        if let Some(items) = child.as_array_mut() {
+            for item in items {
+                redact_path(item, rest);
+            }
+        }
+    } else {
+        redact_path(child, rest);
+    }
+}
+
+/// 値をJSONスナップショットと比較
+///
+/// # Arguments
+/// * `name` - スナップショット名（ファイル名になる）
+/// * `value` - 比較する値（マスク済みであること）
+pub fn assert_json_snapshot<T: Serialize>(name: &str, value: &T) {
+    let actual = serde_json::to_string_pretty(value).expect("snapshot serialization failed") + "\n";
+    let dir = snapshot_dir();
+    let path = dir.join(format!("{name}.snap.json"));
+    let pending = dir.join(format!("{name}.snap.json.new"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(&dir).expect("failed to create snapshot directory");
+        std::fs::write(&path, &actual).expect("failed to write snapshot");
+        let _ = std::fs::remove_file(&pending);
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        std::fs::create_dir_all(&dir).expect("failed to create snapshot directory");
+        std::fs::write(&pending, &actual).expect("failed to write pending snapshot");
+        panic!(
+            "スナップショット {name} が記録されていません: {}\n記録する場合は UPDATE_SNAPSHOTS=1 で実行し、スナップショットをコミットしてください。",
+            path.display()
+        );
+    };
+    if actual != expected {
+        std::fs::write(&pending, &actual).expect("failed to write pending snapshot");
+        panic!(
+            "スナップショット {name} が一致しません。\n新しい出力: {}\n意図した変更の場合は UPDATE_SNAPSHOTS=1 で更新してください。",
+            pending.display()
+        );
+    }
+}
@@ -0,0 +1,367 @@
+// OBS WebSocketエンドツーエンド統合テスト
+//
+// モックOBSサーバー（common::MockObsServer）に実際にWebSocket接続し、
+// 接続・シーン一覧取得・設定取得をコマンド層まで通してテストする。
+//
+// 実行: cargo test --features testing --test integration_obs
+
+mod common;
+
+use common::MockObsServer;
+use std::time::Duration;
+
+use obs_optimizer_app_lib::testing::obs_api::{
+    get_obs_client, get_obs_settings_command, get_scene_item_enabled, get_scene_list,
+    list_scene_items, obs_service, set_scene_item_enabled, ConnectionConfig, ConnectionState,
+};
+use once_cell::sync::Lazy;
+use serde_json::json;
+
+/// OBSクライアントはグローバルシングルトンのため、テストを直列化する
+static OBS_TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// モックサーバーへの接続設定を作成
+fn mock_config(server: &MockObsServer) -> ConnectionConfig {
+    ConnectionConfig {
+        host: "127.0.0.1".to_string(),
+        port: server.port(),
+        password: None,
+    }
+}
+
+/// 1920x1080 60fps のビデオ設定応答
+fn video_settings_response() -> serde_json::Value {
+    json!({
+        "fpsNumerator": 60,
+        "fpsDenominator": 1,
+        "baseWidth": 1920,
+        "baseHeight": 1080,
+        "outputWidth": 1280,
+        "outputHeight": 720
+    })
+}
+
+// =============================================================================
+// 接続テスト
+// =============================================================================
+
+#[tokio::test]
+async fn test_connect_obs_with_mock_server() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+
+    let result = obs_service().connect(mock_config(&server)).await;
+    assert!(result.is_ok(), "モックサーバーへの接続に失敗: {:?}", result.err());
+    assert!(get_obs_client().is_connected().await);
+
+    obs_service().disconnect().await.unwrap();
+    assert!(!get_obs_client().is_connected().await);
+}
+
+#[tokio::test]
+async fn test_connect_fails_after_server_dropped() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    let config = mock_config(&server);
+
+    // Dropでバックグラウンドタスクが停止し、ポートが閉じられる
+    drop(server);
+    common::wait_ms(50).await;
+
+    let result = obs_service().connect(config).await;
+    assert!(result.is_err());
+    assert!(!get_obs_client().is_connected().await);
+}
+
+// =============================================================================
+// ヘルスチェック（ping）テスト
+// =============================================================================
+
+#[tokio::test]
+async fn test_ping_measures_slow_response_latency() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    // 接続時のGetVersionには影響させず、ping時のみ遅延させる
+    server.delay_response("GetVersion", Duration::from_millis(200));
+
+    let result = get_obs_client().ping().await.unwrap();
+    assert!(result.latency_ms >= 200, "遅延が計測されていない: {}ms", result.latency_ms);
+    assert!(result.latency_ms < 5_000);
+    assert_eq!(result.obs_version, "30.2.0");
+    assert_eq!(result.websocket_version, "5.5.0");
+
+    let status = get_obs_client().get_status().await.unwrap();
+    assert_eq!(status.last_ping_latency_ms, Some(result.latency_ms));
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_ping_fast_response_latency() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let result = get_obs_client().ping().await.unwrap();
+    assert!(result.latency_ms < 200);
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_ping_failure_marks_disconnected() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    // サーバーを停止して接続を切る
+    drop(server);
+    common::wait_ms(50).await;
+
+    let result = tokio::time::timeout(Duration::from_secs(5), get_obs_client().ping())
+        .await
+        .expect("pingがタイムアウトした");
+    assert!(result.is_err());
+    assert_eq!(get_obs_client().connection_state().await, ConnectionState::Disconnected);
+    assert!(!get_obs_client().is_connected().await);
+}
+
+#[tokio::test]
+async fn test_ping_timeouts_degrade_then_recover() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let first = get_obs_client().ping().await.unwrap();
+    let status = get_obs_client().get_status().await.unwrap();
+    assert_eq!(status.last_ping_latency_ms, Some(first.latency_ms));
+    let last_success = status.last_ping_success_at.expect("成功日時が記録されていない");
+
+    // タイムアウトより遅い応答で、ハーフオープンなWebSocketを再現
+    get_obs_client().set_request_timeout(Duration::from_millis(100)).await;
+    server.delay_response("GetVersion", Duration::from_millis(150));
+
+    // 1回目の無応答では接続状態を変えない
+    assert!(get_obs_client().ping().await.is_err());
+    assert_eq!(get_obs_client().connection_state().await, ConnectionState::Connected);
+
+    // 2回連続でDegraded（接続は維持する）
+    assert!(get_obs_client().ping().await.is_err());
+    assert_eq!(get_obs_client().connection_state().await, ConnectionState::Degraded);
+    assert!(get_obs_client().is_connected().await);
+
+    // 応答が戻れば成功時刻を更新してConnectedに戻る（遅れた応答が届くまで待つ）
+    server.delay_response("GetVersion", Duration::ZERO);
+    common::wait_ms(400).await;
+    get_obs_client().ping().await.unwrap();
+    assert_eq!(get_obs_client().connection_state().await, ConnectionState::Connected);
+    let status = get_obs_client().get_status().await.unwrap();
+    assert!(status.last_ping_success_at.unwrap() >= last_success);
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_repeated_ping_timeouts_mark_disconnected() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    get_obs_client().set_request_timeout(Duration::from_millis(50)).await;
+    server.delay_response("GetVersion", Duration::from_millis(100));
+
+    for _ in 0..4 {
+        assert!(get_obs_client().ping().await.is_err());
+    }
+    assert_eq!(get_obs_client().connection_state().await, ConnectionState::Disconnected);
+    assert!(!get_obs_client().is_connected().await);
+}
+
+// =============================================================================
+// シーン一覧テスト
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_scene_list_end_to_end() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect(
+        "GetSceneList",
+        json!({
+            "currentProgramSceneName": "ゲーム",
+            "currentProgramSceneUuid": "8f1c2d3e-4a5b-4c6d-8e7f-0a1b2c3d4e5f",
+            "currentPreviewSceneName": null,
+            "currentPreviewSceneUuid": null,
+            "scenes": [
+                {
+                    "sceneName": "雑談",
+                    "sceneUuid": "1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d",
+                    "sceneIndex": 0
+                },
+                {
+                    "sceneName": "ゲーム",
+                    "sceneUuid": "8f1c2d3e-4a5b-4c6d-8e7f-0a1b2c3d4e5f",
+                    "sceneIndex": 1
+                }
+            ]
+        }),
+    );
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let scenes = get_scene_list().await.unwrap();
+    assert_eq!(scenes.len(), 2);
+    assert!(scenes.contains(&"雑談".to_string()));
+    assert!(scenes.contains(&"ゲーム".to_string()));
+    assert!(server.received_requests().contains(&"GetSceneList".to_string()));
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_scene_list_not_connected() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    obs_service().disconnect().await.unwrap();
+
+    let result = get_scene_list().await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().code(), "OBS_NOT_CONNECTED");
+}
+
+// =============================================================================
+// シーンアイテムテスト
+// =============================================================================
+
+#[tokio::test]
+async fn test_scene_item_enabled_toggle() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect("SetSceneItemEnabled", json!(null));
+    server.expect("GetSceneItemEnabled", json!({ "sceneItemEnabled": false }));
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    set_scene_item_enabled("ゲーム".to_string(), 3, false).await.unwrap();
+    let enabled = get_scene_item_enabled("ゲーム".to_string(), 3).await.unwrap();
+    assert!(!enabled);
+
+    let requests = server.received_requests();
+    assert!(requests.contains(&"SetSceneItemEnabled".to_string()));
+    assert!(requests.contains(&"GetSceneItemEnabled".to_string()));
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_list_scene_items_end_to_end() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect(
+        "GetSceneItemList",
+        json!({
+            "sceneItems": [
+                {
+                    "sceneItemId": 1,
+                    "sceneItemIndex": 0,
+                    "sourceName": "Webカメラ",
+                    "sourceUuid": "2b3c4d5e-6f7a-4b8c-9d0e-1f2a3b4c5d6e",
+                    "sourceType": "OBS_SOURCE_TYPE_INPUT",
+                    "inputKind": "dshow_input",
+                    "isGroup": null
+                }
+            ]
+        }),
+    );
+    server.expect("GetSceneItemEnabled", json!({ "sceneItemEnabled": true }));
+    server.expect("GetSceneItemLocked", json!({ "sceneItemLocked": false }));
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let items = list_scene_items("ゲーム".to_string()).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, 1);
+    assert_eq!(items[0].name, "Webカメラ");
+    assert_eq!(items[0].source_type, "dshow_input");
+    assert!(items[0].enabled);
+    assert!(!items[0].locked);
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_list_scene_items_empty_scene() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect("GetSceneItemList", json!({ "sceneItems": [] }));
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let items = list_scene_items("空のシーン".to_string()).await.unwrap();
+    assert!(items.is_empty());
+    // アイテムがなければ表示状態の問い合わせは行わない
+    assert!(!server.received_requests().contains(&"GetSceneItemEnabled".to_string()));
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_scene_item_commands_not_connected() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    obs_service().disconnect().await.unwrap();
+
+    let result = set_scene_item_enabled("ゲーム".to_string(), 1, true).await;
+    assert_eq!(result.unwrap_err().code(), "OBS_NOT_CONNECTED");
+    let result = list_scene_items("ゲーム".to_string()).await;
+    assert_eq!(result.unwrap_err().code(), "OBS_NOT_CONNECTED");
+}
+
+// =============================================================================
+// OBS設定取得テスト
+// =============================================================================
+
+#[tokio::test]
+async fn test_get_obs_settings_end_to_end() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect("GetVideoSettings", video_settings_response());
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let settings = get_obs_settings_command().await.unwrap();
+    assert_eq!(settings.video.base_width, 1920);
+    assert_eq!(settings.video.base_height, 1080);
+    assert_eq!(settings.video.output_width, 1280);
+    assert_eq!(settings.video.output_height, 720);
+    assert_eq!(settings.video.fps_numerator, 60);
+
+    // GetOutputListは未登録のためエラー応答 → デフォルトの出力設定にフォールバック
+    assert_eq!(settings.output.encoder, "unknown");
+    assert_eq!(settings.output.bitrate_kbps, 6000);
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_obs_settings_with_wildcard_handler() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.any_request(|request_type| match request_type {
+        "GetVideoSettings" => video_settings_response(),
+        "GetOutputList" => json!({ "outputs": [] }),
+        _ => json!({}),
+    });
+
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let settings = get_obs_settings_command().await.unwrap();
+    assert_eq!(settings.video.base_width, 1920);
+    assert_eq!(settings.output.encoder, "unknown", "出力なしの場合はデフォルト値");
+
+    let received = server.received_requests();
+    assert!(received.contains(&"GetVideoSettings".to_string()));
+    assert!(received.contains(&"GetOutputList".to_string()));
+
+    obs_service().disconnect().await.unwrap();
+}
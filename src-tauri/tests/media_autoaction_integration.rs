@@ -0,0 +1,171 @@
+// メディアソースの自動アクション統合テスト
+//
+// 保存先を一時ディレクトリに差し替えて自動アクションを設定し、モックOBSサーバーに接続した状態で
+// メディアソースの再生終了時の処理を実行して、アクションごとにOBSへ送るリクエストを検証する。
+//
+// 実行: cargo test --features testing --test media_autoaction_integration
+
+mod common;
+
+use common::{unique_test_path, MockObsServer};
+use obs_optimizer_app_lib::testing::media_autoaction_api::{
+    load_config, obs_service, run_media_autoaction, set_media_autoaction, set_storage_root, ConnectionConfig,
+    MediaAutoAction, ERROR_CODE_VALIDATION_FAILED,
+};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// OBSクライアントと保存先はグローバルなため、テストを直列化する
+static MEDIA_TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// モックOBSサーバーと一時ディレクトリの保存先を用意したテスト環境
+struct MediaHarness {
+    server: MockObsServer,
+    storage_root: PathBuf,
+}
+
+impl MediaHarness {
+    /// 保存先を差し替え、自動アクションを設定してモックサーバーに接続
+    async fn start() -> Self {
+        let storage_root = unique_test_path("media_autoaction_integration");
+        std::fs::create_dir_all(&storage_root).unwrap();
+        set_storage_root(Some(storage_root.clone()));
+
+        set_media_autoaction("イントロ".to_string(), MediaAutoAction::StartStreaming)
+            .await
+            .unwrap();
+        set_media_autoaction(
+            "アウトロ".to_string(),
+            MediaAutoAction::SwitchScene("終了画面".to_string()),
+        )
+        .await
+        .unwrap();
+        set_media_autoaction("カウントダウン".to_string(), MediaAutoAction::StartRecording)
+            .await
+            .unwrap();
+
+        // Start系・Set系のリクエストは応答データなし
+        let server = MockObsServer::start().await.unwrap();
+        server.any_request(|_| Value::Null);
+
+        let config = ConnectionConfig {
+            host: "127.0.0.1".to_string(),
+            port: server.port(),
+            password: None,
+        };
+        obs_service().connect(config).await.unwrap();
+
+        Self { server, storage_root }
+    }
+
+    /// GetVersion（接続時の確認）以外に受信したリクエスト種別の一覧
+    fn requests(&self) -> Vec<String> {
+        self.server
+            .received_requests()
+            .into_iter()
+            .filter(|r| r != "GetVersion")
+            .collect()
+    }
+
+    /// 切断して保存先を元に戻す
+    async fn shutdown(self) {
+        let _ = obs_service().disconnect().await;
+        set_storage_root(None);
+        let _ = std::fs::remove_dir_all(&self.storage_root);
+    }
+}
+
+#[tokio::test]
+async fn test_start_streaming_on_playback_ended() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+
+    let action = run_media_autoaction("イントロ").await.unwrap();
+    assert_eq!(action, Some(MediaAutoAction::StartStreaming));
+    assert_eq!(harness.requests(), vec!["StartStream"]);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_switch_scene_on_playback_ended() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+
+    let action = run_media_autoaction("アウトロ").await.unwrap();
+    assert_eq!(action, Some(MediaAutoAction::SwitchScene("終了画面".to_string())));
+    assert_eq!(harness.requests(), vec!["SetCurrentProgramScene"]);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_start_recording_on_playback_ended() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+
+    let action = run_media_autoaction("カウントダウン").await.unwrap();
+    assert_eq!(action, Some(MediaAutoAction::StartRecording));
+    assert_eq!(harness.requests(), vec!["StartRecord"]);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_no_action_for_unconfigured_or_cleared_source() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+
+    // 自動アクションを設定していないメディアソース
+    assert_eq!(run_media_autoaction("BGM").await.unwrap(), None);
+
+    // Noneを設定すると自動アクションが削除される
+    set_media_autoaction("イントロ".to_string(), MediaAutoAction::None)
+        .await
+        .unwrap();
+    assert_eq!(run_media_autoaction("イントロ").await.unwrap(), None);
+    assert_eq!(load_config().unwrap().media_autoactions.len(), 2);
+
+    assert!(harness.requests().is_empty());
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_set_media_autoaction_replaces_and_validates() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+
+    // 同じメディアソースの設定は置き換える
+    set_media_autoaction("イントロ".to_string(), MediaAutoAction::StartRecording)
+        .await
+        .unwrap();
+    let autoactions = load_config().unwrap().media_autoactions;
+    assert_eq!(autoactions.len(), 3);
+    assert!(autoactions.contains(&("イントロ".to_string(), MediaAutoAction::StartRecording)));
+
+    let error = set_media_autoaction(" ".to_string(), MediaAutoAction::StartStreaming)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ERROR_CODE_VALIDATION_FAILED);
+    let error = set_media_autoaction("アウトロ".to_string(), MediaAutoAction::SwitchScene(String::new()))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), ERROR_CODE_VALIDATION_FAILED);
+
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_action_fails_without_connection() {
+    let _guard = MEDIA_TEST_LOCK.lock().await;
+    let harness = MediaHarness::start().await;
+    obs_service().disconnect().await.unwrap();
+
+    assert!(run_media_autoaction("イントロ").await.is_err());
+    // 設定のないメディアソースは接続状態に関わらず何もしない
+    assert_eq!(run_media_autoaction("BGM").await.unwrap(), None);
+
+    harness.shutdown().await;
+}
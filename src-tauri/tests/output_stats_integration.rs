@@ -0,0 +1,150 @@
+// 配信出力の統計取得の統合テスト
+//
+// モックOBSサーバー（common::MockObsServer）の `GetOutputList` / `GetOutputStatus` 応答から、
+// 送出・ドロップフレーム数、実測ビットレート、輻輳が算出されることをコマンド層まで通してテストする。
+//
+// 実行: cargo test --features testing --test output_stats_integration
+
+mod common;
+
+use common::MockObsServer;
+
+use obs_optimizer_app_lib::testing::obs_api::{
+    get_live_output_stats, obs_service, recent_output_bitrates, ConnectionConfig,
+};
+use obs_optimizer_app_lib::ObsStatusSnapshot;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+/// OBSクライアントと統計の算出状態はグローバルシングルトンのため、テストを直列化する
+static OBS_TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// モックサーバーへの接続設定を作成
+fn mock_config(server: &MockObsServer) -> ConnectionConfig {
+    ConnectionConfig {
+        host: "127.0.0.1".to_string(),
+        port: server.port(),
+        password: None,
+    }
+}
+
+/// 出力一覧の応答（配信出力の動作状態を指定）
+fn output_list_response(streaming: bool) -> Value {
+    let flags = json!({
+        "OBS_OUTPUT_AUDIO": true,
+        "OBS_OUTPUT_VIDEO": true,
+        "OBS_OUTPUT_ENCODED": true,
+        "OBS_OUTPUT_MULTI_TRACK": true,
+        "OBS_OUTPUT_SERVICE": true
+    });
+    json!({
+        "outputs": [
+            {
+                "outputName": "virtualcam_output",
+                "outputKind": "virtualcam_output",
+                "outputWidth": 1920,
+                "outputHeight": 1080,
+                "outputActive": true,
+                "outputFlags": flags
+            },
+            {
+                "outputName": "adv_stream",
+                "outputKind": "rtmp_output",
+                "outputWidth": 1920,
+                "outputHeight": 1080,
+                "outputActive": streaming,
+                "outputFlags": flags
+            }
+        ]
+    })
+}
+
+/// 出力ステータスの応答（配信開始からの累積値）
+fn output_status_response(bytes: u64, duration_ms: u64, total_frames: u32, skipped_frames: u32, congestion: f32) -> Value {
+    json!({
+        "outputActive": true,
+        "outputReconnecting": false,
+        "outputTimecode": "00:00:10.000",
+        "outputDuration": duration_ms,
+        "outputCongestion": congestion,
+        "outputBytes": bytes,
+        "outputSkippedFrames": skipped_frames,
+        "outputTotalFrames": total_frames
+    })
+}
+
+#[tokio::test]
+async fn test_live_output_stats_from_mock_server() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect("GetOutputList", output_list_response(true));
+    server.expect("GetOutputStatus", output_status_response(7_500_000, 10_000, 600, 3, 0.1));
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    // 初回は配信開始からの平均（10秒で7,500,000バイト = 6000kbps）
+    let stats = get_live_output_stats().await.unwrap();
+    assert_eq!(stats.frames_sent, 600);
+    assert_eq!(stats.frames_dropped, 3);
+    assert!((stats.current_bitrate_kbps - 6000.0).abs() < 0.01);
+    assert!((stats.congestion - 0.1).abs() < 0.001);
+
+    // 以降は前回からの差分（1秒で437,500バイト = 3500kbps）
+    server.expect("GetOutputStatus", output_status_response(7_937_500, 11_000, 660, 45, 0.6));
+    let stats = get_live_output_stats().await.unwrap();
+    assert!((stats.current_bitrate_kbps - 3500.0).abs() < 0.01);
+    assert_eq!(stats.frames_dropped, 45);
+
+    // 分析には目標ビットレートではなく実測値が使われる
+    assert_eq!(recent_output_bitrates().await, vec![6000, 3500]);
+
+    // 配信出力のステータスは動作中の配信用出力に対して問い合わせる
+    let requests = server.received_requests();
+    assert_eq!(requests.iter().filter(|r| *r == "GetOutputStatus").count(), 2);
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_live_output_stats_fails_when_not_streaming() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+    let server = MockObsServer::start().await.unwrap();
+    server.expect("GetOutputList", output_list_response(false));
+    obs_service().connect(mock_config(&server)).await.unwrap();
+
+    let result = get_live_output_stats().await;
+    let error = result.expect_err("配信中でない場合はエラーになるべき");
+    assert_eq!(error.code(), "VALIDATION_FAILED");
+
+    // 配信中でない場合は過去のビットレートを破棄する
+    assert!(recent_output_bitrates().await.is_empty());
+    assert!(!server.received_requests().iter().any(|r| r == "GetOutputStatus"));
+
+    obs_service().disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_live_output_stats_requires_connection() {
+    let _guard = OBS_TEST_LOCK.lock().await;
+
+    let error = get_live_output_stats().await.expect_err("未接続の場合はエラーになるべき");
+    assert_eq!(error.code(), "OBS_NOT_CONNECTED");
+}
+
+#[test]
+fn test_snapshot_without_output_stats_deserializes() {
+    // 統計導入前に保存されたスナップショットも読み込める
+    let snapshot: ObsStatusSnapshot = serde_json::from_value(json!({
+        "streaming": true,
+        "recording": false,
+        "fps": 60.0,
+        "renderDroppedFrames": 0,
+        "outputDroppedFrames": 12,
+        "streamBitrate": 6000
+    }))
+    .unwrap();
+    assert!(snapshot.output_stats.is_none());
+
+    // 統計がない場合はフィールド自体を出力しない
+    let json = serde_json::to_value(&snapshot).unwrap();
+    assert!(json.get("outputStats").is_none());
+}
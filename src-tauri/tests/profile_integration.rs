@@ -0,0 +1,168 @@
+// 設定プロファイル統合テスト
+//
+// モックOBSサーバーに接続し、現在の設定のプロファイル保存 → プロファイル適用の
+// 往復をコマンド層まで通してテストする。
+// プロファイルはユーザーのプロファイルディレクトリに保存されるため、
+// 各テストの最後に作成したプロファイル・バックアップを削除する。
+//
+// 実行: cargo test --features testing --test profile_integration
+
+mod common;
+
+use common::MockObsServer;
+use obs_optimizer_app_lib::testing::obs_api::{obs_service, ConnectionConfig};
+use obs_optimizer_app_lib::testing::profile_api::{
+    apply_profile, delete_profile, get_backups, get_profile, save_current_settings_as_profile,
+    save_profile, AudioInputSettings, StreamingPlatform, StreamingStyle,
+};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// 接続中のマイクのデバイスID
+const MIC_DEVICE_ID: &str = "{0.0.1.00000000}.{mic-1}";
+
+/// 音声入力の読み書きに応答するモックサーバーを起動して接続
+///
+/// モックはリクエスト種別のみで応答を決めるため、
+/// すべての音声入力が同じデバイス・ミュート状態を返す
+async fn connect_mock_server() -> MockObsServer {
+    let server = MockObsServer::start().await.unwrap();
+    server.any_request(|request_type| match request_type {
+        "GetVideoSettings" => json!({
+            "fpsNumerator": 60,
+            "fpsDenominator": 1,
+            "baseWidth": 1920,
+            "baseHeight": 1080,
+            "outputWidth": 1280,
+            "outputHeight": 720
+        }),
+        "GetOutputList" => json!({ "outputs": [] }),
+        "GetProfileParameter" => json!({
+            "parameterValue": "Advanced",
+            "defaultParameterValue": null
+        }),
+        "GetInputList" => json!({
+            "inputs": [
+                {
+                    "inputName": "デスクトップ音声",
+                    "inputUuid": "6f2a1c3e-0d4b-4e8a-9c7f-1a2b3c4d5e6f",
+                    "inputKind": "wasapi_output_capture",
+                    "unversionedInputKind": "wasapi_output_capture"
+                },
+                {
+                    "inputName": "マイク",
+                    "inputUuid": "7a3b2d4f-1e5c-4f9b-8d6e-2b3c4d5e6f70",
+                    "inputKind": "wasapi_input_capture",
+                    "unversionedInputKind": "wasapi_input_capture"
+                },
+                {
+                    "inputName": "ゲーム画面",
+                    "inputUuid": "8b4c3e5a-2f6d-4a0c-9e7f-3c4d5e6f7081",
+                    "inputKind": "game_capture",
+                    "unversionedInputKind": "game_capture"
+                }
+            ]
+        }),
+        "GetInputSettings" => json!({
+            "inputKind": "wasapi_input_capture",
+            "inputSettings": { "device_id": MIC_DEVICE_ID }
+        }),
+        "GetInputMute" => json!({ "inputMuted": true }),
+        "GetInputPropertiesListPropertyItems" => json!({
+            "propertyItems": [
+                { "itemName": "既定", "itemEnabled": true, "itemValue": "default" },
+                { "itemName": "USBマイク", "itemEnabled": true, "itemValue": MIC_DEVICE_ID }
+            ]
+        }),
+        // Set系のリクエストは応答データなし
+        _ => Value::Null,
+    });
+
+    let config = ConnectionConfig {
+        host: "127.0.0.1".to_string(),
+        port: server.port(),
+        password: None,
+    };
+    obs_service().connect(config).await.unwrap();
+    server
+}
+
+/// 受信したリクエストのうち指定種別の件数
+fn count_requests(server: &MockObsServer, request_type: &str) -> usize {
+    server
+        .received_requests()
+        .iter()
+        .filter(|r| r.as_str() == request_type)
+        .count()
+}
+
+/// 現在のバックアップIDの一覧
+async fn backup_ids() -> HashSet<String> {
+    get_backups().await.unwrap().into_iter().map(|b| b.id).collect()
+}
+
+#[tokio::test]
+async fn test_audio_inputs_round_trip_through_profile() {
+    let backups_before = backup_ids().await;
+    let server = connect_mock_server().await;
+
+    // 1. 現在の設定をプロファイルとして保存（音声入力のみ取得される）
+    let profile_id = save_current_settings_as_profile(
+        "ポッドキャスト".to_string(),
+        "音声入力の往復テスト".to_string(),
+        StreamingPlatform::YouTube,
+        StreamingStyle::Talk,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let mut profile = get_profile(profile_id.clone()).await.unwrap();
+    let inputs = &profile.settings.audio.inputs;
+    assert_eq!(inputs.len(), 2, "映像ソースは保存されない");
+    assert_eq!(inputs[0].input_name, "デスクトップ音声");
+    assert_eq!(inputs[1].input_name, "マイク");
+    assert!(inputs
+        .iter()
+        .all(|input| input.device_id.as_deref() == Some(MIC_DEVICE_ID) && input.muted));
+
+    // 2. そのまま適用すると、すべての音声入力にデバイスとミュート状態が設定される
+    let result = apply_profile(profile_id.clone()).await.unwrap();
+    assert!(result.warnings.is_empty());
+    assert_eq!(count_requests(&server, "SetInputSettings"), 2);
+    assert_eq!(count_requests(&server, "SetInputMute"), 2);
+
+    // 3. 取り外したデバイス・削除した入力はスキップされ、項目ごとに警告が返る
+    profile.settings.audio.inputs[0].device_id = Some("{0.0.0.00000000}.{removed}".to_string());
+    profile.settings.audio.inputs.push(AudioInputSettings {
+        input_name: "旧マイク".to_string(),
+        input_kind: "wasapi_input_capture".to_string(),
+        device_id: Some("default".to_string()),
+        muted: false,
+    });
+    save_profile(profile).await.unwrap();
+
+    let result = apply_profile(profile_id.clone()).await.unwrap();
+    assert_eq!(result.warnings.len(), 2);
+    assert!(result.warnings[0].contains("デスクトップ音声"));
+    assert!(result.warnings[1].contains("旧マイク"));
+    assert_eq!(count_requests(&server, "SetInputSettings"), 3);
+    assert_eq!(count_requests(&server, "SetInputMute"), 3);
+
+    // 適用前の自動バックアップにも音声入力が含まれる
+    let created: Vec<_> = get_backups()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|b| !backups_before.contains(&b.id))
+        .collect();
+    assert_eq!(created.len(), 2);
+    assert!(created.iter().all(|b| b.settings.audio.inputs.len() == 2));
+
+    for backup in &created {
+        let _ = delete_profile(&backup.id);
+    }
+    let _ = delete_profile(&profile_id);
+    obs_service().disconnect().await.unwrap();
+}
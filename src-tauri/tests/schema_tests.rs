@@ -0,0 +1,141 @@
+// エクスポート形式のスキーマ検証テスト
+//
+// 実際にエクスポートしたドキュメントを、手書きのJSON Schemaで検証する。
+// 構造体のフィールドを追加・削除・改名した場合にスキーマの更新漏れを検出する。
+//
+// 実行: cargo test --features testing --test schema_tests
+
+use obs_optimizer_app_lib::testing::fixtures::{
+    critical_system_metrics, generate_metrics_history, healthy_system_metrics, high_end_hardware,
+    standard_obs_settings, unstable_bitrate_history,
+};
+use obs_optimizer_app_lib::testing::schema_api::{
+    analysis_result_schema, diagnostic_report_schema, historical_metrics_schema,
+    session_export_schema, validate,
+};
+use obs_optimizer_app_lib::testing::snapshot_api::{
+    build_analysis_result, AnalysisInput, AudioMonitoringConfig, ReportExporter, SetupType,
+    StreamingPlatform, StreamingProtocol, StreamingStyle,
+};
+use obs_optimizer_app_lib::{HistoricalMetrics, ObsStatusSnapshot, ProblemAnalyzer, SessionSummary};
+use serde_json::Value;
+
+fn sample_session() -> SessionSummary {
+    SessionSummary {
+        session_id: "schema_session".to_string(),
+        start_time: 1_700_000_000,
+        end_time: 1_700_003_600,
+        avg_cpu: 45.5,
+        avg_gpu: 60.0,
+        total_dropped_frames: 12,
+        peak_bitrate: 6500,
+        quality_score: 88.0,
+        title: Some("スキーマ検証用セッション".to_string()),
+        tags: vec!["game".to_string()],
+        notes: None,
+        platform: Some(StreamingPlatform::YouTube),
+        avg_bitrate: 6000,
+        bytes_uploaded: 0,
+    }
+}
+
+fn sample_metrics() -> Vec<HistoricalMetrics> {
+    (0..3)
+        .map(|i| HistoricalMetrics {
+            timestamp: 1_700_000_000 + i,
+            session_id: "schema_session".to_string(),
+            system: healthy_system_metrics(),
+            obs: ObsStatusSnapshot {
+                streaming: true,
+                recording: false,
+                fps: Some(59.94),
+                render_dropped_frames: Some(0),
+                output_dropped_frames: Some(4),
+                stream_bitrate: Some(6000),
+                output_stats: None,
+            },
+        })
+        .collect()
+}
+
+/// スキーマ違反があれば一覧を表示して失敗する
+fn assert_valid(schema: &Value, document: &Value) {
+    let errors = validate(schema, document);
+    assert!(errors.is_empty(), "スキーマに適合しません:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn test_diagnostic_report_matches_schema() {
+    let analyzer = ProblemAnalyzer::new();
+    let metrics = generate_metrics_history(10, critical_system_metrics());
+    let problems =
+        analyzer.analyze_comprehensive(&metrics, &unstable_bitrate_history(), 6000, "jim_nvenc");
+    let report = ReportExporter::new()
+        .generate_diagnostic_report(&sample_session(), &problems)
+        .unwrap();
+
+    assert!(!report.problems.is_empty());
+    assert_valid(&diagnostic_report_schema(), &serde_json::to_value(&report).unwrap());
+}
+
+#[test]
+fn test_session_export_matches_schema() {
+    let exported = ReportExporter::new()
+        .export_session_json(&sample_session(), &sample_metrics())
+        .unwrap();
+    let document: Value = serde_json::from_str(&exported).unwrap();
+
+    assert_valid(&session_export_schema(), &document);
+}
+
+#[test]
+fn test_historical_metrics_match_schema() {
+    let schema = historical_metrics_schema();
+    for metrics in sample_metrics() {
+        assert_valid(&schema, &serde_json::to_value(&metrics).unwrap());
+    }
+}
+
+#[test]
+fn test_analysis_result_matches_schema() {
+    let hardware_info = high_end_hardware();
+    let memory_total_bytes = (hardware_info.total_memory_gb * 1_073_741_824.0) as u64;
+    let input = AnalysisInput {
+        obs_settings: standard_obs_settings(),
+        hardware_info,
+        platform: StreamingPlatform::Twitch,
+        style: StreamingStyle::Gaming,
+        network_speed_mbps: 50.0,
+        current_protocol: StreamingProtocol::default(),
+        current_output_mode: None,
+        audio_inputs: Vec::new(),
+        microphone_devices: Vec::new(),
+        audio_monitoring: AudioMonitoringConfig::default(),
+        monitoring_device: None,
+        setup_type: SetupType::SinglePc,
+        dual_pc_detected: false,
+        video_sources: Vec::new(),
+        memory_used_bytes: memory_total_bytes / 2,
+        memory_total_bytes,
+        analyzed_at: 1_700_000_000,
+    };
+    let result = build_analysis_result(&input);
+
+    assert_valid(&analysis_result_schema(), &serde_json::to_value(&result).unwrap());
+}
+
+#[test]
+fn test_schema_detects_removed_field() {
+    let mut document = serde_json::to_value(&sample_session()).unwrap();
+    document.as_object_mut().unwrap().remove("avgBitrate");
+    let envelope = serde_json::json!({
+        "version": "1.0",
+        "exported_at": 1_700_000_000,
+        "session": document,
+        "metrics": [],
+    });
+
+    let errors = validate(&session_export_schema(), &envelope);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("avgBitrate"));
+}
@@ -0,0 +1,160 @@
+// 分析結果のスナップショットテスト
+//
+// 推奨ロジックを変更すると多数のフィールドが連動して変わるため、
+// analyze_settings の分析結果と診断レポートを丸ごとスナップショットと比較する。
+// スナップショットは tests/snapshots/ に保存される。
+//
+// 実行: cargo test --features testing --test snapshot_tests
+// 更新: UPDATE_SNAPSHOTS=1 cargo test --features testing --test snapshot_tests
+
+mod common;
+
+use common::{assert_json_snapshot, redact};
+use obs_optimizer_app_lib::testing::fixtures::{
+    critical_system_metrics, generate_metrics_history, high_end_obs_settings, low_end_hardware,
+    low_spec_obs_settings, standard_obs_settings, unstable_bitrate_history,
+};
+use obs_optimizer_app_lib::testing::snapshot_api::{
+    build_analysis_result, AnalysisInput, AudioInputStatus, AudioMonitorType, AudioMonitoringConfig, GpuInfo,
+    ObsSettings, PowerSource, PowerStatus, ProcessPriority, ReportExporter, SetupType,
+    StreamingPlatform, StreamingProtocol, StreamingStyle,
+};
+use obs_optimizer_app_lib::{HardwareInfo, ProblemAnalyzer, SessionSummary};
+
+/// スナップショットで固定する分析日時
+const ANALYZED_AT: i64 = 1_700_000_000;
+
+/// 分析入力を作成（メモリ使用量は総メモリの半分とする）
+fn analysis_input(
+    hardware_info: HardwareInfo,
+    obs_settings: ObsSettings,
+    platform: StreamingPlatform,
+    style: StreamingStyle,
+    network_speed_mbps: f64,
+) -> AnalysisInput {
+    let memory_total_bytes = (hardware_info.total_memory_gb * 1_073_741_824.0) as u64;
+    AnalysisInput {
+        obs_settings,
+        hardware_info,
+        platform,
+        style,
+        network_speed_mbps,
+        current_protocol: StreamingProtocol::default(),
+        current_output_mode: None,
+        audio_inputs: Vec::new(),
+        microphone_devices: Vec::new(),
+        audio_monitoring: AudioMonitoringConfig::default(),
+        monitoring_device: None,
+        setup_type: SetupType::SinglePc,
+        dual_pc_detected: false,
+        video_sources: Vec::new(),
+        memory_used_bytes: memory_total_bytes / 2,
+        memory_total_bytes,
+        analyzed_at: ANALYZED_AT,
+    }
+}
+
+#[test]
+fn test_analysis_result_gaming_nvidia_rtx4080() {
+    let hardware = HardwareInfo {
+        cpu_name: "Intel Core i7-13700K".to_string(),
+        cpu_cores: 16,
+        total_memory_gb: 32.0,
+        gpu: Some(GpuInfo {
+            name: "NVIDIA GeForce RTX 4080".to_string(),
+            driver_version: Some("552.22".to_string()),
+        }),
+        power: PowerStatus::default(),
+        displays: Vec::new(),
+        benchmark_result: None,
+    };
+    let input = analysis_input(
+        hardware,
+        high_end_obs_settings(),
+        StreamingPlatform::Twitch,
+        StreamingStyle::Gaming,
+        50.0,
+    );
+
+    assert_json_snapshot("analysis_result_gaming_nvidia_rtx4080", &build_analysis_result(&input));
+}
+
+#[test]
+fn test_analysis_result_talk_no_gpu() {
+    let input = analysis_input(
+        low_end_hardware(),
+        standard_obs_settings(),
+        StreamingPlatform::YouTube,
+        StreamingStyle::Talk,
+        10.0,
+    );
+
+    assert_json_snapshot("analysis_result_talk_no_gpu", &build_analysis_result(&input));
+}
+
+#[test]
+fn test_analysis_result_music_low_spec() {
+    let mut input = analysis_input(
+        low_end_hardware(),
+        low_spec_obs_settings(),
+        StreamingPlatform::NicoNico,
+        StreamingStyle::Music,
+        5.0,
+    );
+    input.audio_inputs = vec![AudioInputStatus {
+        input_name: "マイク".to_string(),
+        input_kind: "wasapi_input_capture".to_string(),
+        muted: false,
+        volume_db: -6.0,
+        volume_mul: 0.5,
+        likely_microphone: true,
+        monitor_type: AudioMonitorType::None,
+    }];
+
+    assert_json_snapshot("analysis_result_music_low_spec", &build_analysis_result(&input));
+}
+
+#[test]
+fn test_diagnostic_report_all_problems_detected() {
+    let analyzer = ProblemAnalyzer::new();
+    let metrics = generate_metrics_history(10, critical_system_metrics());
+
+    let mut problems =
+        analyzer.analyze_comprehensive(&metrics, &unstable_bitrate_history(), 6000, "jim_nvenc");
+    problems.extend(analyzer.analyze_gpu_driver("NVIDIA GeForce RTX 4080", Some("456.71")));
+    problems.extend(analyzer.analyze_power_source(&PowerStatus {
+        source: PowerSource::Battery,
+        battery_percent: Some(15),
+    }));
+    problems.extend(analyzer.analyze_process_priority(Some(ProcessPriority::Normal), true));
+
+    let session = SessionSummary {
+        session_id: "snapshot_session".to_string(),
+        start_time: ANALYZED_AT - 3600,
+        end_time: ANALYZED_AT,
+        avg_cpu: 92.5,
+        avg_gpu: 97.0,
+        total_dropped_frames: 1200,
+        peak_bitrate: 6500,
+        quality_score: 35.0,
+        title: Some("スナップショット用セッション".to_string()),
+        tags: vec!["game".to_string()],
+        notes: None,
+        platform: Some(StreamingPlatform::Twitch),
+        avg_bitrate: 4900,
+        bytes_uploaded: 0,
+    };
+    let report = ReportExporter::new()
+        .generate_diagnostic_report(&session, &problems)
+        .unwrap();
+
+    // 生成日時・問題ID・実行環境のシステム情報は実行ごとに変わる
+    let mut json = serde_json::to_value(&report).unwrap();
+    redact(
+        &mut json,
+        &["generatedAt", "systemInfo", "problems[].id", "problems[].detectedAt"],
+    );
+
+    assert!(!report.problems.is_empty());
+    assert_json_snapshot("diagnostic_report_all_problems_detected", &json);
+}
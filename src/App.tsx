@@ -14,6 +14,7 @@ import { OneClickApply } from './features/optimization/OneClickApply';
 import { ProfileList } from './features/profiles/ProfileList';
 import { SessionHistory } from './features/history/SessionHistory';
 import { ExportPanel } from './features/export/ExportPanel';
+import { ObserverModePanel } from './features/streaming/ObserverModePanel';
 import { useObsStore } from './stores/obsStore';
 import { useConfigStore } from './stores/configStore';
 import { useOnboardingStore } from './stores/onboardingStore';
@@ -47,7 +48,7 @@ function App() {
   const { startPolling, subscribeToEvents } = useObsStore();
   const { config, loadConfig } = useConfigStore();
   const { completed: onboardingCompleted } = useOnboardingStore();
-  const { getActiveAlerts } = useAlertStore();
+  const { getActiveAlerts, subscribeToNotifications } = useAlertStore();
   const { problems } = useAnalysisStore();
   const [showOnboarding, setShowOnboarding] = useState(false);
   const [isLoading, setIsLoading] = useState(true);
@@ -101,7 +102,12 @@ function App() {
       // 非同期処理のレースコンディション対策
       const setupSubscription = async () => {
         try {
-          const unsub = await subscribeToEvents();
+          const unsubObs = await subscribeToEvents();
+          const unsubAlerts = await subscribeToNotifications();
+          const unsub = () => {
+            unsubObs();
+            unsubAlerts();
+          };
           if (isMounted) {
             unsubscribe = unsub;
           } else {
@@ -326,12 +332,13 @@ function HistoryTab() {
 }
 
 /**
- * エクスポートタブ - データのエクスポート機能
+ * エクスポートタブ - データのエクスポートとオブザーバーモード
  */
 function ExportTab() {
   return (
     <div className="space-y-6">
       <ExportPanel />
+      <ObserverModePanel />
     </div>
   );
 }
@@ -140,7 +140,7 @@ export function DiagnosticReport() {
           {/* スコアとシステム情報 */}
           <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
             {/* 品質スコア */}
-            <QualityScore score={result.qualityScore} />
+            <QualityScore score={result.qualityScore.total} />
 
             {/* システム情報 */}
             <div className="bg-gray-50 rounded-lg p-6 border border-gray-200">
@@ -1,12 +1,13 @@
 import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { DiagnosticReport } from '../../types/commands';
+import type { DiagnosticReport, DiagnosticReportResponse } from '../../types/commands';
 
 /**
  * 診断レポート生成
  * - 診断レポート生成ボタン
  * - レポートプレビュー
  * - PDF保存（ブラウザ印刷機能利用）
+ * - Markdown保存（GitHubのIssue添付用）
  */
 export function ReportGenerator() {
   const [report, setReport] = useState<DiagnosticReport | null>(null);
@@ -18,8 +19,8 @@ export function ReportGenerator() {
     setError(null);
 
     try {
-      const diagnosticReport = await invoke<DiagnosticReport>('generate_diagnostic_report');
-      setReport(diagnosticReport);
+      const result = await invoke<DiagnosticReport>('generate_diagnostic_report');
+      setReport(result);
     } catch (err) {
       const message = err instanceof Error ? err.message : 'レポート生成に失敗しました';
       setError(message);
@@ -33,6 +34,32 @@ export function ReportGenerator() {
     window.print();
   };
 
+  const handleSaveMarkdown = async () => {
+    setError(null);
+
+    try {
+      const { data, filename } = await invoke<DiagnosticReportResponse>(
+        'export_diagnostic_report',
+        { format: 'markdown' }
+      );
+
+      // ブラウザのダウンロード機能を使用
+      const blob = new Blob([data], { type: 'text/markdown' });
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement('a');
+      a.href = url;
+      a.download = filename;
+      document.body.appendChild(a);
+      a.click();
+      document.body.removeChild(a);
+      URL.revokeObjectURL(url);
+    } catch (err) {
+      const message = err instanceof Error ? err.message : 'Markdownの保存に失敗しました';
+      setError(message);
+      console.error('Markdown export failed:', err);
+    }
+  };
+
   const formatDuration = (durationSecs: number): string => {
     const hours = Math.floor(durationSecs / 3600);
     const minutes = Math.floor((durationSecs % 3600) / 60);
@@ -63,6 +90,17 @@ export function ReportGenerator() {
               PDF保存
             </button>
           )}
+          {report && (
+            <button
+              onClick={() => {
+                void handleSaveMarkdown();
+              }}
+              className="px-4 py-2 bg-gray-700 text-white rounded-lg hover:bg-gray-800 transition-colors"
+              aria-label="Markdownとして保存"
+            >
+              Markdown保存
+            </button>
+          )}
         </div>
       </div>
 
@@ -109,12 +109,24 @@ function SessionHeader({ session, label }: SessionHeaderProps) {
     <div className="bg-white border border-gray-200 rounded-lg p-4">
       <h2 className="text-sm font-semibold text-gray-600 mb-2">{label}</h2>
       <p className="text-lg font-bold text-gray-900 mb-2">
-        {session.sessionId.substring(0, 8)}
+        {session.title ?? session.sessionId.substring(0, 8)}
       </p>
       <div className="text-sm text-gray-600 space-y-1">
         <p>開始: {startTime}</p>
         <p>終了: {endTime}</p>
       </div>
+      {session.tags && session.tags.length > 0 && (
+        <div className="flex flex-wrap gap-1 mt-2">
+          {session.tags.map((tag) => (
+            <span key={tag} className="text-xs bg-gray-100 text-gray-700 rounded px-2 py-0.5">
+              {tag}
+            </span>
+          ))}
+        </div>
+      )}
+      {session.notes && (
+        <p className="text-sm text-gray-700 mt-2 whitespace-pre-wrap">{session.notes}</p>
+      )}
     </div>
   );
 }
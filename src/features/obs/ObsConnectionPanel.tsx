@@ -73,7 +73,8 @@ export function ObsConnectionPanel() {
     return translateError(error);
   }, [error]);
 
-  const isConnected = connectionState === 'connected';
+  // 応答が不安定（degraded）な間も接続は維持している
+  const isConnected = connectionState === 'connected' || connectionState === 'degraded';
   const isConnecting = connectionState === 'connecting' || connectionState === 'reconnecting';
   // 接続ボタンの有効状態（ホストが空でなく、ポートが有効な場合）
   const canConnect = host.trim() !== '' && portValidation.valid;
@@ -116,6 +117,7 @@ export function ObsConnectionPanel() {
         return 'bg-green-500';
       case 'connecting':
       case 'reconnecting':
+      case 'degraded':
         return 'bg-yellow-500';
       case 'error':
         return 'bg-red-500';
@@ -133,6 +135,8 @@ export function ObsConnectionPanel() {
         return '接続中...';
       case 'reconnecting':
         return '再接続中...';
+      case 'degraded':
+        return '応答が不安定';
       case 'error':
         return 'エラー';
       default:
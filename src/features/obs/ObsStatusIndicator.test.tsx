@@ -48,6 +48,9 @@ describe('ObsStatusIndicator', () => {
       fps: 60.0,
       renderDroppedFrames: 0,
       outputDroppedFrames: 0,
+      lastPingLatencyMs: null,
+      lastPingSuccessAt: null,
+      privacyModeActive: false,
     };
 
     it('接続済みメッセージとバージョン情報を表示する', () => {
@@ -5,16 +5,18 @@ import { useObsStore } from '../../stores/obsStore';
 import { Welcome } from './steps/Welcome';
 import { ObsSetupGuide } from './steps/ObsSetupGuide';
 import { ConnectionTest } from './steps/ConnectionTest';
+import { EncoderBenchmark } from './steps/EncoderBenchmark';
 import { Complete } from './steps/Complete';
 
 /**
  * オンボーディングウィザード
  *
- * 4ステップのシンプルなセットアップフロー:
+ * 5ステップのシンプルなセットアップフロー:
  * 1. ウェルカム画面
  * 2. OBS WebSocket設定ガイド
  * 3. 接続テスト
- * 4. 完了画面
+ * 4. エンコード性能の計測（任意）
+ * 5. 完了画面
  */
 export function OnboardingWizard() {
   const { currentStep, nextStep, prevStep, setStep, completeOnboarding } =
@@ -47,6 +49,8 @@ export function OnboardingWizard() {
       case 3:
         return <ConnectionTest />;
       case 4:
+        return <EncoderBenchmark />;
+      case 5:
         return <Complete />;
       default:
         return <Welcome />;
@@ -66,6 +70,9 @@ export function OnboardingWizard() {
         // OBS接続テストは接続成功が必須
         return connectionState === 'connected';
       case 4:
+        // エンコード性能の計測は任意（計測中に進んだ場合は中断される）
+        return true;
+      case 5:
         // 完了画面
         return true;
       default:
@@ -218,6 +225,8 @@ function getStepTitle(step: number): string {
     case 3:
       return '接続テスト';
     case 4:
+      return 'エンコード性能の計測';
+    case 5:
       return 'セットアップ完了';
     default:
       return '';
@@ -139,7 +139,7 @@ export function Analysis() {
                   あなたのPCに最適な設定を見つけました
                 </p>
                 <p className="text-sm text-blue-800 dark:text-blue-200">
-                  品質スコア: <span className="font-semibold">{result.qualityScore}/100</span>
+                  品質スコア: <span className="font-semibold">{result.qualityScore.total}/100</span>
                   {result.issueCount > 0 && (
                     <> · {result.issueCount}件の改善案があります</>
                   )}
@@ -1,5 +1,5 @@
 /**
- * オンボーディング Step 4: 完了画面
+ * オンボーディング Step 5: 完了画面
  */
 export function Complete() {
   return (
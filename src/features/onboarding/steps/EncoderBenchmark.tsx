@@ -0,0 +1,170 @@
+import { useEffect, useRef, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import {
+  APP_ERROR_CODES,
+  type EncoderBenchmarkResult,
+  type RecommendedSettings,
+} from '../../../types/commands';
+
+/** 計測時間（秒） */
+const BENCHMARK_DURATION_SECS = 10;
+
+/**
+ * オンボーディング Step 4: エンコード性能の計測（任意）
+ *
+ * 推奨設定（エンコーダー・解像度・FPS・ビットレート）で短時間のローカル録画を行い、
+ * 配信前に目標FPSを維持できるかを確認する。
+ * ステップを離れた場合やウィザードを閉じた場合は計測を中断する。
+ */
+export function EncoderBenchmark() {
+  const [running, setRunning] = useState(false);
+  const [target, setTarget] = useState<RecommendedSettings | null>(null);
+  const [result, setResult] = useState<EncoderBenchmarkResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const runningRef = useRef(false);
+
+  // 計測中にステップを離れた場合は中断する（設定はバックエンドで元に戻される）
+  useEffect(() => {
+    return () => {
+      if (runningRef.current) {
+        void invoke<boolean>('cancel_encoder_benchmark');
+      }
+    };
+  }, []);
+
+  const handleStart = async () => {
+    setRunning(true);
+    runningRef.current = true;
+    setError(null);
+    setResult(null);
+
+    try {
+      const recommendations = await invoke<RecommendedSettings>('calculate_recommendations');
+      setTarget(recommendations);
+      const benchmark = await invoke<EncoderBenchmarkResult>('benchmark_encoder', {
+        encoderId: recommendations.output.encoder,
+        width: recommendations.video.outputWidth,
+        height: recommendations.video.outputHeight,
+        fps: recommendations.video.fps,
+        bitrateKbps: recommendations.output.bitrateKbps,
+        durationSecs: BENCHMARK_DURATION_SECS,
+      });
+      setResult(benchmark);
+    } catch (e) {
+      if (!isCancelled(e)) {
+        setError(extractMessage(e));
+      }
+    } finally {
+      runningRef.current = false;
+      setRunning(false);
+    }
+  };
+
+  const handleCancel = () => {
+    void invoke<boolean>('cancel_encoder_benchmark');
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="text-center mb-4">
+        <h2 className="text-xl font-bold text-gray-900 dark:text-gray-100 mb-1">
+          エンコード性能の計測
+        </h2>
+        <p className="text-gray-600 dark:text-gray-400 text-sm">
+          推奨設定で{BENCHMARK_DURATION_SECS}秒間テスト録画を行い、配信前にフレームレートを維持できるか確認します
+        </p>
+        <p className="text-gray-500 dark:text-gray-400 text-xs mt-1">
+          このステップは任意です。計測せずに次へ進むこともできます
+        </p>
+      </div>
+
+      {target && (
+        <div className="bg-gray-50 dark:bg-gray-700/50 border border-gray-200 dark:border-gray-600 rounded-lg p-3 text-sm text-gray-700 dark:text-gray-300">
+          計測条件: {target.output.encoder} / {target.video.outputWidth}x{target.video.outputHeight} /{' '}
+          {target.video.fps}fps / {target.output.bitrateKbps}kbps
+        </div>
+      )}
+
+      {running ? (
+        <div className="flex flex-col items-center gap-3 py-4">
+          <div className="w-10 h-10 border-4 border-blue-200 border-t-blue-600 rounded-full animate-spin" />
+          <p className="text-sm text-gray-600 dark:text-gray-300">計測中...（録画ファイルは計測後に削除されます）</p>
+          <button
+            onClick={handleCancel}
+            className="px-4 py-2 text-sm text-gray-700 dark:text-gray-200 bg-white dark:bg-gray-700 border border-gray-300 dark:border-gray-600 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-600 transition-all"
+          >
+            中断
+          </button>
+        </div>
+      ) : (
+        <div className="flex justify-center">
+          <button
+            onClick={() => void handleStart()}
+            className="px-6 py-3 bg-blue-600 text-white rounded-lg hover:bg-blue-700 transition-all font-medium shadow"
+          >
+            {result ? 'もう一度計測' : '計測を開始'}
+          </button>
+        </div>
+      )}
+
+      {result && (
+        <div
+          className={`border-2 rounded-lg p-4 ${
+            result.sustained
+              ? 'border-green-300 bg-green-50 dark:border-green-700 dark:bg-green-900/20'
+              : 'border-yellow-300 bg-yellow-50 dark:border-yellow-700 dark:bg-yellow-900/20'
+          }`}
+        >
+          <p
+            className={`font-semibold mb-2 ${
+              result.sustained ? 'text-green-800 dark:text-green-200' : 'text-yellow-800 dark:text-yellow-200'
+            }`}
+          >
+            {result.sustained
+              ? '推奨設定のフレームレートを維持できます'
+              : 'フレームレートを維持できませんでした。解像度・FPSを下げるか、軽いエンコーダーを検討してください'}
+          </p>
+          <dl className="grid grid-cols-2 gap-2 text-sm text-gray-700 dark:text-gray-300">
+            <dt>平均FPS</dt>
+            <dd>{result.avgFps.toFixed(1)}</dd>
+            <dt>取りこぼしたフレーム</dt>
+            <dd>{result.droppedFrames}</dd>
+            <dt>フレーム処理時間（平均 / 最大）</dt>
+            <dd>
+              {result.avgEncodeTimeMs.toFixed(1)}ms / {result.maxEncodeTimeMs.toFixed(1)}ms
+            </dd>
+          </dl>
+        </div>
+      )}
+
+      {error && (
+        <div className="bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-700 rounded-lg p-3 text-sm text-red-800 dark:text-red-200">
+          {error}
+        </div>
+      )}
+    </div>
+  );
+}
+
+// ========================================
+// ヘルパー関数
+// ========================================
+
+function isCancelled(e: unknown): boolean {
+  return (
+    typeof e === 'object' &&
+    e !== null &&
+    'code' in e &&
+    (e as { code: unknown }).code === APP_ERROR_CODES.BENCHMARK_CANCELLED
+  );
+}
+
+function extractMessage(e: unknown): string {
+  if (e instanceof Error) {
+    return e.message;
+  }
+  if (typeof e === 'object' && e !== null && 'message' in e) {
+    return String((e as { message: unknown }).message);
+  }
+  return String(e);
+}
@@ -1,6 +1,7 @@
 import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import type { OptimizationResult } from '../../types/commands';
+import { OverheadDeltaNotice } from './OverheadDeltaNotice';
 
 interface OneClickApplyProps {
   /** 適用完了時のコールバック */
@@ -28,7 +29,7 @@ export function OneClickApply({ onApplied, onError }: OneClickApplyProps) {
       await invoke('backup_current_settings');
 
       // 推奨設定を適用
-      const applyResult = await invoke<OptimizationResult>('apply_recommended_settings');
+      const applyResult = await invoke<OptimizationResult>('apply_recommended_settings_with_result');
       setResult(applyResult);
 
       if (applyResult.failedCount === 0) {
@@ -129,6 +130,8 @@ export function OneClickApply({ onApplied, onError }: OneClickApplyProps) {
             {result.failedCount > 0 && ` / 失敗: ${result.failedCount}件`}
           </p>
 
+          {result.overheadBaselineId && <OverheadDeltaNotice baselineId={result.overheadBaselineId} />}
+
           {result.errors.length > 0 && (
             <ul className="mt-2 text-sm list-disc list-inside">
               {result.errors.map((error, index) => (
@@ -136,6 +139,14 @@ export function OneClickApply({ onApplied, onError }: OneClickApplyProps) {
               ))}
             </ul>
           )}
+
+          {result.warnings.length > 0 && (
+            <ul className="mt-2 text-sm list-disc list-inside" aria-label="確認が必要な項目">
+              {result.warnings.map((warning, index) => (
+                <li key={index}>⚠ {warning}</li>
+              ))}
+            </ul>
+          )}
         </div>
       )}
     </div>
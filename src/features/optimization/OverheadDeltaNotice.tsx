@@ -0,0 +1,74 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { OverheadDelta } from '../../types/commands';
+
+/** 適用後の負荷を確認する間隔（ミリ秒） */
+const POLL_INTERVAL_MS = 10_000;
+
+/** 確認を打ち切るまでの回数（適用後の計測は約45秒で終わる） */
+const MAX_POLLS = 12;
+
+interface OverheadDeltaNoticeProps {
+  /** 適用結果の基準値ID（OptimizationResult.overheadBaselineId） */
+  baselineId: string;
+}
+
+/**
+ * 推奨設定の適用前後のOBSの負荷の変化を表示するコンポーネント
+ *
+ * 適用後の負荷はバックエンドで落ち着くのを待ってから計測されるため、
+ * 計測が終わるまで一定間隔で確認する
+ */
+export function OverheadDeltaNotice({ baselineId }: OverheadDeltaNoticeProps) {
+  const [delta, setDelta] = useState<OverheadDelta | null>(null);
+  const [gaveUp, setGaveUp] = useState(false);
+
+  useEffect(() => {
+    let polls = 0;
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    let cancelled = false;
+
+    const poll = async () => {
+      polls += 1;
+      try {
+        const deltas = await invoke<OverheadDelta[]>('get_overhead_deltas', { limit: 5 });
+        const found = deltas.find((d) => d.baselineId === baselineId);
+        if (cancelled) {
+          return;
+        }
+        if (found?.summary) {
+          setDelta(found);
+          return;
+        }
+      } catch {
+        // 表示は補助的なものなので、取得に失敗しても再試行する
+      }
+      if (cancelled) {
+        return;
+      }
+      if (polls >= MAX_POLLS) {
+        setGaveUp(true);
+        return;
+      }
+      timer = setTimeout(() => void poll(), POLL_INTERVAL_MS);
+    };
+
+    timer = setTimeout(() => void poll(), POLL_INTERVAL_MS);
+    return () => {
+      cancelled = true;
+      if (timer) {
+        clearTimeout(timer);
+      }
+    };
+  }, [baselineId]);
+
+  if (gaveUp) {
+    return null;
+  }
+
+  return (
+    <p className="mt-2 text-sm" role="status" aria-live="polite">
+      {delta?.summary ?? 'OBSの負荷の変化を計測中...'}
+    </p>
+  );
+}
@@ -26,6 +26,8 @@ function convertPlatform(platform: string | null): StreamingPlatform | undefined
     twitch: 'twitch',
     niconico: 'nicoNico',
     twitcasting: 'twitCasting',
+    bilibili: 'bilibili',
+    showroom: 'showRoom',
     other: 'other',
   };
   return map[platform] ?? 'other';
@@ -181,7 +183,8 @@ export function RecommendedSettingsPanel({ refreshTrigger }: RecommendedSettings
     );
   }
 
-  const { systemInfo, summary, qualityScore } = analysisResult;
+  const { systemInfo, summary } = analysisResult;
+  const qualityScore = analysisResult.qualityScore.total;
   const { headline, recommendedPreset, keyRecommendations } = summary;
 
   // 推奨適用後のスコアを算出（qualityScoreベース + 20%改善を想定）
@@ -348,6 +351,9 @@ function HardwareInfoSection({ systemInfo, isExpanded, onToggle }: HardwareInfoS
             value={systemInfo.gpuModel || '取得できませんでした'}
             valueClassName={!systemInfo.gpuModel ? 'text-gray-500 dark:text-gray-500' : ''}
           />
+          {systemInfo.gpuDriverVersion && (
+            <InfoRow label="GPUドライバー" value={systemInfo.gpuDriverVersion} />
+          )}
           <InfoRow
             label="メモリ"
             value={`${formatMemory(systemInfo.totalMemoryMb)} (利用可能: ${formatMemory(
@@ -1,4 +1,5 @@
 export { OneClickApply } from './OneClickApply';
+export { OverheadDeltaNotice } from './OverheadDeltaNotice';
 export { BackupRestore } from './BackupRestore';
 export { ComponentTierCard } from './ComponentTierCard';
 export { SystemEvaluationSummary } from './SystemEvaluationSummary';
@@ -81,6 +81,8 @@ export function ProfileList({ onApplied, onDeleted, onError }: ProfileListProps)
       twitch: 'Twitch',
       nicoNico: 'ニコニコ生放送',
       twitCasting: 'ツイキャス',
+      bilibili: 'Bilibili',
+      showRoom: 'SHOWROOM',
       other: 'その他',
     };
     return names[platform];
@@ -0,0 +1,91 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { ObserverServerInfo } from '../../types/commands';
+
+/**
+ * オブザーバーモードパネル
+ *
+ * モデレーター・ヘルパー向けの読み取り専用HTTPサーバーを起動・停止し、
+ * 共有するURLとアクセストークンを表示する
+ */
+export function ObserverModePanel() {
+  const [info, setInfo] = useState<ObserverServerInfo | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<ObserverServerInfo | null>('get_observer_server_status')
+      .then(setInfo)
+      .catch(() => setInfo(null));
+  }, []);
+
+  const handleToggle = async () => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      if (info) {
+        await invoke<boolean>('stop_observer_server');
+        setInfo(null);
+      } else {
+        setInfo(await invoke<ObserverServerInfo>('start_observer_server', {}));
+      }
+    } catch (e) {
+      setError(extractMessage(e));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="bg-white dark:bg-gray-800 rounded-lg p-4 border border-gray-200 dark:border-gray-700">
+      <div className="flex items-center justify-between">
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-gray-900 dark:text-white mb-1">
+            オブザーバーモード
+          </h3>
+          <p className="text-sm text-gray-600 dark:text-gray-300">
+            モデレーターが配信状況（メトリクス・アラート・セッション）を読み取り専用で確認できます
+          </p>
+        </div>
+        <button
+          onClick={() => void handleToggle()}
+          disabled={isLoading}
+          className="px-4 py-2 text-sm font-medium rounded-lg border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-50 dark:hover:bg-gray-700 disabled:opacity-50 disabled:cursor-not-allowed transition-all"
+        >
+          {info ? '停止' : '開始'}
+        </button>
+      </div>
+
+      {info && (
+        <dl className="mt-4 grid grid-cols-[auto_1fr] gap-x-4 gap-y-2 text-sm text-gray-700 dark:text-gray-300">
+          <dt>URL</dt>
+          <dd className="font-mono break-all select-all">{info.url}</dd>
+          <dt>トークン</dt>
+          <dd className="font-mono break-all select-all">{info.token}</dd>
+        </dl>
+      )}
+
+      {info && (
+        <p className="mt-3 text-xs text-gray-500 dark:text-gray-400">
+          トークンはアプリを再起動すると変わります。信頼できる相手にのみ共有してください
+        </p>
+      )}
+
+      {error && (
+        <div className="mt-3 bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-700 rounded-lg p-3 text-sm text-red-800 dark:text-red-200">
+          {error}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function extractMessage(e: unknown): string {
+  if (e instanceof Error) {
+    return e.message;
+  }
+  if (typeof e === 'object' && e !== null && 'message' in e) {
+    return String((e as { message: unknown }).message);
+  }
+  return String(e);
+}
@@ -1 +1,2 @@
 export { StreamingModeToggle } from './StreamingModeToggle';
+export { ObserverModePanel } from './ObserverModePanel';
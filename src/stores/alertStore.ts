@@ -1,5 +1,7 @@
 import { create } from 'zustand';
-import type { AlertSeverity } from '../types/commands';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { ALERT_EVENTS } from '../types/commands';
+import type { AlertNotification, AlertSeverity } from '../types/commands';
 
 // ========================================
 // アラートの型定義（フロントエンド用拡張）
@@ -34,6 +36,8 @@ interface AlertState {
   setStreamingMode: (enabled: boolean) => void;
   getActiveAlerts: () => UIAlert[];
   getAlertsByPriority: () => UIAlert[];
+  /** バックエンドのアラート通知（Criticalのみ）を購読 */
+  subscribeToNotifications: () => Promise<UnlistenFn>;
 }
 
 // ========================================
@@ -106,4 +110,14 @@ export const useAlertStore = create<AlertState>((set, get) => ({
       (a, b) => getSeverityPriority(a.severity) - getSeverityPriority(b.severity)
     );
   },
+
+  subscribeToNotifications: async () => {
+    return listen<AlertNotification>(ALERT_EVENTS.NOTIFICATION, (event) => {
+      get().addAlert({
+        severity: 'critical',
+        title: event.payload.title,
+        message: event.payload.body,
+      });
+    });
+  },
 }));
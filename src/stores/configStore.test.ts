@@ -139,7 +139,7 @@ describe('configStore', () => {
       expect(state.loading).toBe(false);
       expect(state.error).toBeNull();
 
-      expect(mockInvoke).toHaveBeenCalledWith('save_app_config', { config: mockConfig });
+      expect(mockInvoke).toHaveBeenCalledWith('patch_app_config', { patch: mockConfig });
     });
 
     it('保存中はローディング状態になる', async () => {
@@ -222,8 +222,8 @@ describe('configStore', () => {
       expect(state.config?.streamStyle).toBe('game');
       expect(state.config?.platform).toBe('youtube');
 
-      expect(mockInvoke).toHaveBeenCalledWith('save_app_config', {
-        config: {
+      expect(mockInvoke).toHaveBeenCalledWith('patch_app_config', {
+        patch: {
           ...mockConfig,
           saveConnection: false,
           autoConnect: false,
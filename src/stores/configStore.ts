@@ -62,7 +62,7 @@ export const useConfigStore = create<ConfigState>((set, get) => ({
   saveConfig: async (config: SimpleAppConfig) => {
     set({ loading: true, error: null });
     try {
-      await invoke('save_app_config', { config });
+      await invoke('patch_app_config', { patch: config });
       set({ config, loading: false });
     } catch (e) {
       set({
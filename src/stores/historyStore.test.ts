@@ -55,7 +55,13 @@ const mockSystemMetrics: SystemMetrics = {
   network: {
     uploadBytesPerSec: 5000000,
     downloadBytesPerSec: 10000000,
+    adapterType: 'wired',
+    interfaceName: 'eth0',
   },
+  cpuFrequencyMhz: 3600,
+  cpuBaseFrequencyMhz: 3600,
+  cpuThrottlePercent: 0,
+  cpuIsThrottling: false,
 };
 
 const mockObsStatusSnapshot: ObsStatusSnapshot = {
@@ -65,7 +65,7 @@ describe('metricsStore', () => {
       expect(state.error).toBeNull();
       expect(state.lastUpdate).toBeGreaterThan(0);
 
-      expect(mockInvoke).toHaveBeenCalledWith('get_system_metrics');
+      expect(mockInvoke).toHaveBeenCalledWith('poll_system_metrics');
     });
 
     it('履歴にデータポイントを追加する', async () => {
@@ -189,7 +189,7 @@ describe('metricsStore', () => {
       // 1秒後: システムメトリクスのみ
       vi.advanceTimersByTime(1000);
       const calls1 = mockInvoke.mock.calls.filter(
-        (call) => call[0] === 'get_system_metrics'
+        (call) => call[0] === 'poll_system_metrics'
       );
       expect(calls1.length).toBeGreaterThan(0);
 
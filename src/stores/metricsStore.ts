@@ -93,7 +93,7 @@ export const useMetricsStore = create<MetricsState>((set, get) => ({
   fetchMetrics: async () => {
     set({ loading: true, error: null });
     try {
-      const metrics = await invoke<SystemMetrics>('get_system_metrics');
+      const metrics = await invoke<SystemMetrics>('poll_system_metrics');
 
       // 履歴を更新
       const { history } = get();
@@ -15,7 +15,7 @@ export interface UserPreferences {
 // ========================================
 
 interface OnboardingState {
-  /** 現在のステップ（1-4） */
+  /** 現在のステップ（1-5） */
   currentStep: number;
   /** オンボーディング完了フラグ */
   completed: boolean;
@@ -39,7 +39,7 @@ interface OnboardingState {
 // ========================================
 
 /** 総ステップ数 */
-const TOTAL_STEPS = 4;
+const TOTAL_STEPS = 5;
 
 /** 必須ステップ（スキップ不可） */
 const REQUIRED_STEPS = [3]; // Step 3: OBS接続テスト
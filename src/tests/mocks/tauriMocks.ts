@@ -30,6 +30,9 @@ export const mockObsStatus: ObsStatus = {
   fps: 60.0,
   renderDroppedFrames: 0,
   outputDroppedFrames: 0,
+  lastPingLatencyMs: null,
+  lastPingSuccessAt: null,
+  privacyModeActive: false,
 };
 
 // モックデータ: システムメトリクス
@@ -56,7 +59,13 @@ export const mockSystemMetrics: SystemMetrics = {
   network: {
     uploadBytesPerSec: 5000000,
     downloadBytesPerSec: 10000000,
+    adapterType: 'wired',
+    interfaceName: 'eth0',
   },
+  cpuFrequencyMhz: 3600,
+  cpuBaseFrequencyMhz: 3600,
+  cpuThrottlePercent: 0,
+  cpuIsThrottling: false,
 };
 
 // モックデータ: OBSプロセスメトリクス
@@ -70,6 +79,7 @@ export const mockObsProcessMetrics: ObsProcessMetrics = {
   },
   totalCpuUsage: 10.5,
   totalMemoryBytes: 500000000,
+  priority: null,
 };
 
 // モックデータ: シーンリスト
@@ -84,6 +94,7 @@ export function setupInvokeMock(invoke: InvokeMockType) {
       case 'get_obs_status':
         return Promise.resolve(mockObsStatus);
       case 'get_system_metrics':
+      case 'poll_system_metrics':
         return Promise.resolve(mockSystemMetrics);
       case 'get_process_metrics':
         return Promise.resolve(mockObsProcessMetrics);
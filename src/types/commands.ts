@@ -1,6 +1,37 @@
 // Tauriコマンドの型定義 - バックエンドと同期必須
 // contracts/api.md に準拠
 
+// ========================================
+// エラー
+// ========================================
+
+/** 構造化エラーコード（src-tauri/src/error.rs の AppError.code） */
+export const APP_ERROR_CODES = {
+  OBS_NOT_CONNECTED: 'OBS_NOT_CONNECTED',
+  OBS_OUTPUT_ACTIVE: 'OBS_OUTPUT_ACTIVE',
+  OBS_TIMEOUT: 'OBS_TIMEOUT',
+  CONFIG_IO: 'CONFIG_IO',
+  KEYRING_UNAVAILABLE: 'KEYRING_UNAVAILABLE',
+  VALIDATION_FAILED: 'VALIDATION_FAILED',
+  STREAM_DESTINATION_INVALID: 'STREAM_DESTINATION_INVALID',
+  OPERATION_IN_PROGRESS: 'OPERATION_IN_PROGRESS',
+  BENCHMARK_CANCELLED: 'BENCHMARK_CANCELLED',
+  OBSERVER_SERVER: 'OBSERVER_SERVER',
+} as const;
+
+/** VALIDATION_FAILED エラーの details（キーは引数名、値は表示用メッセージ） */
+export interface ValidationErrorDetails {
+  fields: Record<string, string>;
+}
+
+/** OPERATION_IN_PROGRESS エラーの details */
+export interface OperationInProgressErrorDetails {
+  /** 実行中の操作の表示名 */
+  operation: string;
+  /** 実行中の操作の開始日時（Unixタイムスタンプ） */
+  startedAt: number;
+}
+
 // ========================================
 // OBS関連の型
 // ========================================
@@ -37,12 +68,43 @@ export interface ObsStatus {
   fps: number | null;
   renderDroppedFrames: number | null;
   outputDroppedFrames: number | null;
+  /** 直近のヘルスチェック（ping）の応答時間（ミリ秒） */
+  lastPingLatencyMs: number | null;
+  /** 直近にヘルスチェック（ping）が成功した日時（UNIX epoch秒） */
+  lastPingSuccessAt: number | null;
+  /** 音声モニタリングを一括で無効にしている（プライバシーモード）か */
+  privacyModeActive: boolean;
+}
+
+/** 接続のヘルスチェック（ping）結果 */
+export interface PingResult {
+  /** 往復時間（ミリ秒） */
+  latencyMs: number;
+  /** OBSのバージョン */
+  obsVersion: string;
+  /** WebSocketサーバーのバージョン */
+  websocketVersion: string;
+}
+
+/** シーンアイテム情報 */
+export interface SceneItem {
+  /** シーンアイテムID（シーン内で一意） */
+  id: number;
+  /** ソース名 */
+  name: string;
+  /** ソースの種類（入力の場合は inputKind、それ以外は 'scene' / 'group'） */
+  sourceType: string;
+  /** 表示されているか */
+  enabled: boolean;
+  /** ロックされているか */
+  locked: boolean;
 }
 
 export type ConnectionState =
   | 'disconnected'
   | 'connecting'
   | 'connected'
+  | 'degraded'
   | 'reconnecting'
   | 'error';
 
@@ -69,12 +131,43 @@ export interface SceneChangedPayload {
   currentScene: string;
 }
 
+/** シーン切り替え開始ペイロード（obs:scene-transition-started） */
+export interface SceneTransitionStartedPayload {
+  /** 切り替え先のシーン名 */
+  toScene: string;
+  /** アラートを抑制した秒数（抑制していない場合はnull） */
+  alertsSuppressedSecs: number | null;
+}
+
+/** メディアソースの再生終了ペイロード（obs:media-ended） */
+export interface MediaEndedPayload {
+  /** 再生が終了したメディアソース名 */
+  sourceName: string;
+}
+
 export interface ObsErrorPayload {
   code: string;
   message: string;
   recoverable: boolean;
 }
 
+/** 配信出力の統計（OBSの GetOutputStatus から算出） */
+export interface OutputStats {
+  /** 配信開始からの送出フレーム数 */
+  framesSent: number;
+  /** 配信開始からのドロップ（スキップ）フレーム数 */
+  framesDropped: number;
+  /** 現在のビットレート（kbps、前回の取得からの送信量で算出） */
+  currentBitrateKbps: number;
+  /** 輻輳（0.0〜1.0） */
+  congestion: number;
+}
+
+/** 配信出力の統計更新ペイロード（配信中モードの間、1秒ごと） */
+export interface ObsStatsUpdate {
+  stats: OutputStats;
+}
+
 // OBSイベント名
 export const OBS_EVENTS = {
   CONNECTION_CHANGED: 'obs:connection-changed',
@@ -82,7 +175,15 @@ export const OBS_EVENTS = {
   RECORDING_CHANGED: 'obs:recording-changed',
   STATUS_UPDATE: 'obs:status-update',
   SCENE_CHANGED: 'obs:scene-changed',
+  SCENE_TRANSITION_STARTED: 'obs:scene-transition-started',
+  MEDIA_ENDED: 'obs:media-ended',
   ERROR: 'obs:error',
+  STATS_UPDATE: 'obs:stats-update',
+} as const;
+
+// アラートイベント名
+export const ALERT_EVENTS = {
+  NOTIFICATION: 'alert:notification',
 } as const;
 
 // ========================================
@@ -127,12 +228,50 @@ export interface GpuMetrics {
   encoderUsage: number;
 }
 
+/** ネットワークアダプターの種類（デフォルトルートのインターフェース） */
+export type NetworkAdapterType = 'wired' | 'wireless' | 'virtual' | 'unknown';
+
 /** ネットワーク使用状況のメトリクス */
 export interface NetworkMetrics {
   /** アップロード速度（バイト/秒） */
   uploadBytesPerSec: number;
   /** ダウンロード速度（バイト/秒） */
   downloadBytesPerSec: number;
+  /** 接続に使用しているアダプターの種類 */
+  adapterType: NetworkAdapterType;
+  /** 計測したインターフェース名（対象のインターフェースがない場合はnull） */
+  interfaceName: string | null;
+}
+
+/** ネットワークインターフェースの情報 */
+export interface NetworkInterfaceInfo {
+  /** インターフェース名 */
+  name: string;
+  /** 現在のアップロード速度（ビット/秒） */
+  currentUploadBps: number;
+  /** 現在のダウンロード速度（ビット/秒） */
+  currentDownloadBps: number;
+  /** リンクが有効か */
+  isUp: boolean;
+}
+
+/** 競合ソフトウェアの重要度 */
+export type ConflictSeverity = 'info' | 'tips';
+
+/** 実行中の競合ソフトウェア（オーバーレイ・フックを行う常駐ソフト） */
+export interface SoftwareConflict {
+  /** ルールID */
+  id: string;
+  /** ソフトウェア名 */
+  name: string;
+  /** 検出されたプロセス名 */
+  processName: string;
+  /** 重要度 */
+  severity: ConflictSeverity;
+  /** OBSへの影響 */
+  explanation: string;
+  /** 対処方法 */
+  mitigation: string;
 }
 
 /** システム全体のメトリクス（契約準拠） */
@@ -145,6 +284,14 @@ export interface SystemMetrics {
   gpu: GpuMetrics | null;
   /** ネットワーク情報 */
   network: NetworkMetrics;
+  /** 現在のCPUクロック周波数（MHz、取得できない場合はnull） */
+  cpuFrequencyMhz: number | null;
+  /** CPUのベースクロック周波数（MHz、最初の計測値） */
+  cpuBaseFrequencyMhz: number | null;
+  /** ベースクロックからの低下率（0.0-1.0） */
+  cpuThrottlePercent: number | null;
+  /** 低下率が25%を超えているか（サーマルスロットリングの可能性） */
+  cpuIsThrottling: boolean;
 }
 
 // ========================================
@@ -173,6 +320,51 @@ export interface ObsProcessMetrics {
   totalCpuUsage: number;
   /** 合計メモリ使用量（バイト） */
   totalMemoryBytes: number;
+  /** メインプロセスのCPU優先度（get_obs_process_info でのみ設定） */
+  priority: ProcessPriority | null;
+}
+
+/** プロセスのCPU優先度 */
+export type ProcessPriority = 'high' | 'aboveNormal' | 'normal' | 'belowNormal';
+
+/** OBSの負荷（get_streaming_overhead） */
+export interface StreamingOverhead {
+  /** OBSプロセスのCPU使用率（0-100%、全コアに対する割合） */
+  cpuPercent: number;
+  /** GPU使用率（0-100%、OBS起動中のGPU全体の使用率） */
+  gpuPercent: number | null;
+  /** ハードウェアエンコーダーの使用率（0-100%） */
+  encoderPercent: number | null;
+  /** OBSプロセスのメモリ使用量（MB） */
+  memoryMb: number;
+}
+
+/** セッション履歴に記録したOBSの負荷（get_session_overhead） */
+export interface StreamingOverheadSample extends StreamingOverhead {
+  /** 計測時刻（UNIX epoch秒） */
+  timestamp: number;
+}
+
+/** 推奨設定の適用前後のOBSの負荷の変化（get_overhead_deltas） */
+export interface OverheadDelta {
+  /** 基準値のID（OptimizationResult.overheadBaselineId） */
+  baselineId: string;
+  /** 適用日時（UNIX epoch秒） */
+  appliedAt: number;
+  /** 適用前の負荷 */
+  before: StreamingOverhead;
+  /** 適用後の負荷（計測中の場合はnull） */
+  after: StreamingOverhead | null;
+  /** CPU使用率の変化（ポイント、負の値は減少） */
+  cpuChange: number | null;
+  /** GPU使用率の変化（ポイント） */
+  gpuChange: number | null;
+  /** エンコーダー使用率の変化（ポイント） */
+  encoderChange: number | null;
+  /** メモリ使用量の変化（MB） */
+  memoryChangeMb: number | null;
+  /** 変化の説明（例: 「この適用でOBSのCPU使用率が18%から9%に減少しました」。計測中の場合はnull） */
+  summary: string | null;
 }
 
 // ========================================
@@ -209,7 +401,7 @@ export interface ConnectionConfig {
   savePassword: boolean;
   /** 起動時に自動接続するか */
   autoConnectOnStartup: boolean;
-  /** 接続タイムアウト（秒） */
+  /** 接続・OBSへの各リクエストのタイムアウト（秒） */
   connectionTimeoutSecs: number;
 }
 
@@ -225,6 +417,10 @@ export interface MonitoringConfig {
   collectProcessMetrics: boolean;
   /** メトリクス履歴を保存するか */
   saveMetricsHistory: boolean;
+  /** OBS接続のヘルスチェック（ping）間隔（秒） */
+  healthCheckIntervalSecs?: number;
+  /** 帯域を計測するネットワークインターフェース名（未設定の場合はアップロード速度が最大のもの） */
+  preferredNetworkInterface?: string | null;
 }
 
 /** アラート設定 */
@@ -247,10 +443,30 @@ export interface AlertConfig {
   alertDurationSecs: number;
   /** アラート音を鳴らすか */
   playSound: boolean;
-  /** デスクトップ通知を表示するか */
+  /** アラート通知を表示するか */
   showNotification: boolean;
+  /** 配信中のマイク無音アラートを発行するまでの継続時間（秒） */
+  micSilenceAlertSecs: number;
+  /** マイクを無音とみなす音量の下限（dB） */
+  micSilenceFloorDb: number;
+  /** シーン切り替え・録画開始など、負荷の急上昇が想定される操作の間アラートを抑制するルール */
+  suppressionRules?: AlertSuppressionRule[];
+}
+
+/** アラートを抑制するきっかけ */
+export type SuppressionTrigger = 'sceneTransitionStart' | 'recordingStart' | 'manualOverride';
+
+/** アラートの抑制ルール */
+export interface AlertSuppressionRule {
+  /** 抑制するきっかけ */
+  trigger: SuppressionTrigger;
+  /** 抑制する秒数 */
+  durationSecs: number;
 }
 
+/** 表示言語（推奨理由の文言に使用） */
+export type Language = 'japanese' | 'english';
+
 /** 表示設定 */
 export interface DisplayConfig {
   /** ダークモードを使用するか */
@@ -261,6 +477,64 @@ export interface DisplayConfig {
   compactMode: boolean;
   /** 常に最前面に表示 */
   alwaysOnTop: boolean;
+  /** 表示言語（未設定の場合は日本語） */
+  language?: Language;
+  /** グローバルショートカットの割り当て（未設定の場合は未割り当て） */
+  shortcuts?: ShortcutBindings;
+}
+
+/** ショートカットを割り当てる操作 */
+export type ShortcutAction = 'panicMode' | 'applyRecommended';
+
+/** グローバルショートカットの割り当て（例: "Ctrl+Shift+F9"、未割り当てはnull） */
+export interface ShortcutBindings {
+  /** パニックモード */
+  panicMode?: string | null;
+  /** 推奨設定の適用 */
+  applyRecommended?: string | null;
+}
+
+/** ショートカットの競合の相手 */
+export type ShortcutConflictSource = 'obs' | 'application';
+
+/** 検出されたショートカットの競合 */
+export interface ShortcutConflict {
+  /** 競合しているアプリの操作 */
+  action: ShortcutAction;
+  /** 割り当てられたショートカット（正規の表記） */
+  accelerator: string;
+  /** 競合の相手 */
+  source: ShortcutConflictSource;
+  /** 競合しているホットキー名（例: "OBSBasic.StartStreaming"）・アプリ名 */
+  conflictingWith: string;
+}
+
+/**
+ * 配信プラットフォームのAPI連携のエラーの種類
+ *
+ * エラー（`PLATFORM_NOT_SUPPORTED` / `PLATFORM_AUTH` / `PLATFORM_API`）の `details.kind` に設定される
+ */
+export type PlatformErrorKind =
+  | 'notSupported'
+  | 'tokenMissing'
+  | 'tokenInvalid'
+  | 'missingScope'
+  | 'categoryNotFound'
+  | 'invalidRequest'
+  | 'rateLimited'
+  | 'network'
+  | 'server';
+
+/** APIトークンの確認結果 */
+export interface PlatformAccount {
+  /** 配信プラットフォーム */
+  platform: StreamingPlatform;
+  /** ログイン名 */
+  login: string;
+  /** 配信タイトル・カテゴリーを変更する権限があるか */
+  canUpdateStreamInfo: boolean;
+  /** トークンの有効期限までの秒数（期限がない場合はnull） */
+  expiresInSecs: number | null;
 }
 
 /** 配信モード設定 */
@@ -273,6 +547,14 @@ export interface StreamingModeConfig {
   networkSpeedMbps: number;
   /** 画質優先モード */
   qualityPriority: boolean;
+  /** 配信プロトコル */
+  protocol: StreamingProtocol;
+  /** 配信の遅延モード（省略時は normal） */
+  latency?: StreamLatency;
+  /** 配信環境の構成（省略時は singlePc） */
+  setupType?: SetupType;
+  /** HDR配信を行うか（省略時は false） */
+  hdrMode?: boolean;
 }
 
 /** アプリケーション設定（Rust AppConfigに対応） */
@@ -289,6 +571,287 @@ export interface AppConfig {
   display: DisplayConfig;
   /** 配信モード設定 */
   streamingMode: StreamingModeConfig;
+  /** OBSプロセスのCPU優先度 */
+  obsProcessPriority: ProcessPriority;
+  /** ゲーム検出に使用する実行ファイル名の一覧 */
+  knownGameExecutables: string[];
+  /** 監視対象のマイク入力名（未設定の場合は自動検出） */
+  microphoneInputName?: string | null;
+  /** デバッグ記録設定 */
+  debugRecording?: DebugRecordingConfig;
+  /** 匿名ハードウェア統計の送信設定（オプトイン） */
+  telemetry?: TelemetryConfig;
+  /** 配信開始時に表示されている必要があるシーンアイテム */
+  requiredSceneItems?: RequiredSceneItem[];
+  /** 月間のアップロード量の上限（GB、未設定の場合は上限なし） */
+  monthlyQuotaGb?: number | null;
+  /** 上限の集計を手動でリセットした日時（UNIX epoch秒） */
+  quotaResetAt?: number | null;
+  /** 非表示にしたシーン構成チェックのルールID */
+  dismissedChecklistRules?: string[];
+  /** 音声モニタリング設定 */
+  audioMonitoring?: AudioMonitoringConfig;
+  /** 自動化ルール（「Xが起きたらYを行う」） */
+  automationRules?: AutomationRule[];
+  /** 推奨設定の適用前に、OBSのプロファイル・シーンコレクションのファイルもバックアップするか */
+  backupObsFiles?: boolean;
+  /** オブザーバーモード（モデレーター向けの読み取り専用HTTPサーバー）の設定 */
+  observer?: ObserverConfig;
+  /** メディアソースの再生終了時の自動アクション（[メディアソース名, アクション]） */
+  mediaAutoactions?: [string, MediaAutoAction][];
+}
+
+/**
+ * patch_app_config に渡す設定の部分更新（JSON Merge Patch）
+ *
+ * 指定したフィールドだけが変更され、nullを指定したフィールドは初期値に戻る
+ */
+export type AppConfigPatch = {
+  [K in keyof AppConfig]?: AppConfig[K] extends unknown[]
+    ? AppConfig[K] | null
+    : AppConfig[K] extends object
+      ? { [P in keyof AppConfig[K]]?: AppConfig[K][P] | null } | null
+      : AppConfig[K] | null;
+};
+
+/** 配信開始時に表示されている必要があるシーンアイテム */
+export interface RequiredSceneItem {
+  /** シーン名 */
+  sceneName: string;
+  /** ソース名 */
+  sourceName: string;
+}
+
+/** 匿名ハードウェア統計の送信設定 */
+export interface TelemetryConfig {
+  /** 送信を有効にするか（デフォルト: 無効） */
+  enabled: boolean;
+  /** 送信先URL（http://のみ対応、未設定の場合は送信しない） */
+  endpoint: string;
+}
+
+/** オブザーバーモードの設定 */
+export interface ObserverConfig {
+  /** 待ち受けアドレス（ホスト:ポート、デフォルト: 127.0.0.1:8787） */
+  bindAddress: string;
+}
+
+/** 音声モニタリング設定 */
+export interface AudioMonitoringConfig {
+  /** 音声モニタリングを使用しているか */
+  enabled: boolean;
+  /** モニタリングの遅延補正（ミリ秒） */
+  monitoringOffsetMs: number;
+}
+
+/** 閾値との比較方法 */
+export type ThresholdComparison = 'above' | 'below';
+
+/** 自動化ルールの発動条件 */
+export interface AutomationTrigger {
+  /** 監視するメトリクス */
+  metric: MetricType;
+  /** 閾値との比較方法（above: 閾値以上、below: 閾値未満） */
+  comparison: ThresholdComparison;
+  /** 閾値 */
+  threshold: number;
+  /** 条件を満たし続ける必要がある時間（秒、0〜3600） */
+  durationSecs: number;
+}
+
+/** 自動化ルールのアクション（配信中に出力を再起動せずに行える操作のみ） */
+export type AutomationAction =
+  | { type: 'adjustBitrate'; deltaKbps: number }
+  | { type: 'notify'; message: string }
+  | { type: 'switchScene'; sceneName: string }
+  | { type: 'none' };
+
+/** メディアソースの再生終了時の自動アクション（none を指定すると設定を削除） */
+export type MediaAutoAction =
+  | { type: 'startStreaming' }
+  | { type: 'switchScene'; sceneName: string }
+  | { type: 'startRecording' }
+  | { type: 'none' };
+
+/** 自動化ルール */
+export interface AutomationRule {
+  /** ルールID（create_automation_rule で採番） */
+  id: string;
+  /** ルール名 */
+  name: string;
+  /** 有効か */
+  enabled: boolean;
+  /** 発動条件 */
+  trigger: AutomationTrigger;
+  /** 実行するアクション（記載順に実行） */
+  actions: AutomationAction[];
+  /** 実行後、再び実行するまでの間隔（秒、10以上） */
+  cooldownSecs: number;
+}
+
+/** アクションの実行結果 */
+export interface AutomationActionResult {
+  action: AutomationAction;
+  /** 成功したか（出力の再起動まで反映されない場合はfalse） */
+  success: boolean;
+  /** 設定は保存したが、配信中の出力には再起動するまで反映されないか */
+  pendingRestart: boolean;
+  /** 結果の説明（失敗時はエラーメッセージ） */
+  message: string;
+}
+
+/** 自動化ルールの実行履歴 */
+export interface AutomationExecution {
+  ruleId: string;
+  ruleName: string;
+  metric: MetricType;
+  value: number;
+  /** 実行日時（UNIX epoch秒） */
+  executedAt: number;
+  results: AutomationActionResult[];
+}
+
+/** 送信される匿名ハードウェア統計 */
+export interface TelemetryPayload {
+  schemaVersion: number;
+  gpuGeneration: GpuGeneration;
+  gpuGrade: GpuGrade;
+  cpuTier: CpuTier;
+  platform: StreamingPlatform;
+  /** 選択中のエンコーダーID（OBS未接続の場合はnull） */
+  encoder: string | null;
+  /** 選択中のプリセット（OBS未接続の場合はnull） */
+  preset: string | null;
+}
+
+/** 匿名ハードウェア統計のプレビュー */
+export interface TelemetryPreview {
+  enabled: boolean;
+  endpoint: string;
+  /** 最後に送信した日時（Unixタイムスタンプ） */
+  lastSentAt: number | null;
+  /** 次回送信可能になる日時（Unixタイムスタンプ） */
+  nextSendAt: number | null;
+  /** 送信されるデータ */
+  payload: TelemetryPayload;
+}
+
+/** 起動中のオブザーバーモードのサーバーの情報 */
+export interface ObserverServerInfo {
+  /** 実際の待ち受けアドレス */
+  address: string;
+  /** トークンを含む /health のURL */
+  url: string;
+  /** アクセストークン（アプリの起動ごとに変わる） */
+  token: string;
+}
+
+/** デバッグ記録設定 */
+export interface DebugRecordingConfig {
+  enabled: boolean;
+  /** 1ファイルあたりの最大サイズ（KB） */
+  maxFileSizeKb: number;
+  /** 保持するファイル数（現在のファイルを含む） */
+  maxFiles: number;
+}
+
+/** デバッグ記録の状態 */
+export interface DebugRecordingStatus {
+  enabled: boolean;
+  /** 記録先ディレクトリ */
+  traceDir: string;
+  maxFileSizeKb: number;
+  maxFiles: number;
+  /** 記録済みファイルの合計サイズ（バイト） */
+  totalBytes: number;
+}
+
+/** パフォーマンス計測対象の種類 */
+export type PerformanceKind = 'command' | 'monitoringStage';
+
+/** コマンド・監視処理の段階ごとの所要時間 */
+export interface PerformanceEntry {
+  kind: PerformanceKind;
+  /** コマンド名・段階名 */
+  name: string;
+  /** 実行回数（起動から） */
+  callCount: number;
+  /** 失敗回数（起動から） */
+  errorCount: number;
+  /** 直近100回の平均所要時間（ミリ秒） */
+  avgMs: number;
+  /** 直近100回の95パーセンタイル所要時間（ミリ秒） */
+  p95Ms: number;
+  /** 最大所要時間（ミリ秒、起動から） */
+  maxMs: number;
+  /** 最後の所要時間（ミリ秒） */
+  lastMs: number;
+}
+
+/** アプリ自身のパフォーマンス統計 */
+export interface AppPerformanceStats {
+  /** 平均所要時間の長い順 */
+  entries: PerformanceEntry[];
+  /** 監視ティックが更新間隔を超えた回数（起動から） */
+  slowTickCount: number;
+}
+
+/** 音声入力の状態 */
+export interface AudioInputStatus {
+  /** 入力名 */
+  inputName: string;
+  /** 入力種別（例: wasapi_input_capture） */
+  inputKind: string;
+  /** ミュート中か */
+  muted: boolean;
+  /** 音量（dB） */
+  volumeDb: number;
+  /** 音量（乗数） */
+  volumeMul: number;
+  /** マイク入力と推定されるか */
+  likelyMicrophone: boolean;
+  /** 音声モニタリングの種類 */
+  monitorType: AudioMonitorType;
+}
+
+/** 音声モニタリングの種類 */
+export type AudioMonitorType = 'none' | 'monitorOnly' | 'monitorAndOutput';
+
+/** 音声ソース全体の状態 */
+export interface AudioStatus {
+  /** 音声入力一覧 */
+  inputs: AudioInputStatus[];
+  /** 監視対象のマイク入力名（見つからない場合はnull） */
+  microphone: string | null;
+  /** 監視対象のマイクがミュート・無音か */
+  microphoneSilent: boolean;
+}
+
+/** 監査対象の音声ソースの状態 */
+export interface AudioSourceStatus {
+  /** 入力名 */
+  name: string;
+  /** ミュート中か */
+  muted: boolean;
+  /** 音量（dB） */
+  volumeDb: number;
+  /** 現在のシーンに含まれるか（グローバル音声入力は常にtrue） */
+  inCurrentScene: boolean;
+}
+
+/** 音声ソースの監査結果 */
+export interface AudioAuditResult {
+  /** マイク入力 */
+  microphoneSources: AudioSourceStatus[];
+  /** デスクトップ音声 */
+  desktopSources: AudioSourceStatus[];
+  /** 警告メッセージ */
+  warnings: string[];
+}
+
+/** ゲーム検出イベント（game:detected）のペイロード */
+export interface GameDetectedPayload {
+  processName: string;
 }
 
 /** フロントエンド用簡易設定（オンボーディング等で使用） */
@@ -327,16 +890,35 @@ export interface ObsSetting {
   priority: 'critical' | 'recommended' | 'optional';
 }
 
+/** 電源の種類 */
+export type PowerSource = 'ac' | 'battery' | 'unknown';
+
+/** 電源状態 */
+export interface PowerStatus {
+  source: PowerSource;
+  batteryPercent: number | null;
+}
+
 /** システム環境情報 */
 export interface SystemInfo {
   /** CPUモデル名 */
   cpuModel: string;
   /** GPUモデル名 */
   gpuModel: string | null;
+  /** GPUドライバーバージョン */
+  gpuDriverVersion: string | null;
   /** 総メモリ容量（MB） */
   totalMemoryMb: number;
   /** 利用可能メモリ（MB） */
   availableMemoryMb: number;
+  /** 電源の種類 */
+  powerSource: PowerSource;
+  /** バッテリー残量（%） */
+  batteryPercent: number | null;
+  /** ネットワークアダプターの種類 */
+  networkAdapter: NetworkAdapterType;
+  /** 接続されているモニター（検出できない場合は空配列） */
+  monitors: DisplayInfo[];
 }
 
 /** 設定分析リクエスト */
@@ -351,8 +933,8 @@ export interface AnalyzeSettingsRequest {
 
 /** 診断結果 */
 export interface AnalysisResult {
-  /** 全体の品質スコア（0-100） */
-  qualityScore: number;
+  /** 品質スコアの項目別の内訳（合計は qualityScore.total、0-100） */
+  qualityScore: ScoreBreakdown;
   /** 検出された問題の数 */
   issueCount: number;
   /** 推奨される設定変更リスト */
@@ -367,6 +949,32 @@ export interface AnalysisResult {
   systemCapability?: SystemCapability;
   /** スペック非依存の静的設定 */
   staticSettings?: StaticSettings;
+  /** マルチトラック配信の推奨（対応プラットフォームのみ） */
+  multitrack?: RecommendedMultitrack;
+  /** リクエストID（ログとの照合用。不具合報告に添付する） */
+  requestId: string | null;
+}
+
+/** マルチトラック配信の1レンディション */
+export interface MultitrackRendition {
+  width: number;
+  height: number;
+  fps: number;
+  bitrateKbps: number;
+  /** コーデック */
+  codec: 'h264' | 'hevc' | 'av1';
+}
+
+/** マルチトラック配信（Twitchの拡張ブロードキャスト）の推奨 */
+export interface RecommendedMultitrack {
+  /** 有効化を推奨するか */
+  enabled: boolean;
+  /** 推奨レンディション（高解像度順）。推奨しない場合は空 */
+  renditions: MultitrackRendition[];
+  /** GPUの同時エンコードセッション数の目安 */
+  sessionBudget: number;
+  /** 推奨理由（推奨しない場合はその理由） */
+  reason: string;
 }
 
 /** 分析サマリー（初心者向け） */
@@ -397,6 +1005,68 @@ export interface OptimizationResult {
   failedCount: number;
   /** エラーメッセージ（失敗時） */
   errors: string[];
+  /** 推奨エンコーダーを適用できずフォールバックした場合の記録 */
+  encoderFallback?: EncoderFallback | null;
+  /** 強制適用トークンを使用して適用したか */
+  forced: boolean;
+  /** 配信中の強制適用で適用を見送った項目の説明 */
+  skipped: string[];
+  /** 配信中の強制適用でプロファイルに保存したが、配信の停止・再開まで反映されない項目の説明（appliedCountに含まない） */
+  pendingRestart: string[];
+  /** リクエストID（apply_recommended_settings_with_resultのみ。ログとの照合用） */
+  requestId: string | null;
+  /** 適用前のOBSの負荷の基準値ID（get_overhead_deltas で適用前後の変化を確認できる） */
+  overheadBaselineId: string | null;
+  /** ビデオ設定の読み戻しによる項目ごとの検証結果（ビデオ設定を適用しなかった場合はnull） */
+  videoVerification: VideoApplyResult | null;
+  /** 確認を促す警告（配信先とプラットフォーム設定の食い違い等。適用自体は行われている） */
+  warnings: string[];
+}
+
+/** 読み戻しで検証した設定項目 */
+export interface VerifiedValue {
+  /** 指定した値 */
+  intended: number;
+  /** OBSから読み戻した値 */
+  actual: number;
+  /** 指定した値がそのまま反映されたか */
+  verified: boolean;
+}
+
+/** ビデオ設定の適用結果（項目ごとの検証結果） */
+export interface VideoApplyResult {
+  /** 出力解像度（幅） */
+  outputWidth: VerifiedValue;
+  /** 出力解像度（高さ） */
+  outputHeight: VerifiedValue;
+  /** フレームレート（分子） */
+  fpsNumerator: VerifiedValue;
+  /** フレームレート（分母） */
+  fpsDenominator: VerifiedValue;
+  /** 読み戻しが一致せず再適用したか */
+  retried: boolean;
+}
+
+/** 配信中の強制適用トークン（request_force_apply） */
+export interface ForceApplyGrant {
+  /** apply_recommended_settings / apply_custom_settings（_with_result を含む）の force に渡すトークン */
+  token: string;
+  /** 発行日時（Unixタイムスタンプ） */
+  issuedAt: number;
+  /** 失効日時（Unixタイムスタンプ） */
+  expiresAt: number;
+}
+
+/** エンコーダーのフォールバック記録 */
+export interface EncoderFallback {
+  /** 推奨されたエンコーダーID */
+  requestedEncoder: string;
+  /** 実際に適用したエンコーダーID */
+  appliedEncoder: string;
+  /** 試行して失敗したエンコーダーID（試行順） */
+  failedEncoders: string[];
+  /** フォールバックした理由（最初の失敗のエラーメッセージ） */
+  reason: string;
 }
 
 // ========================================
@@ -406,18 +1076,37 @@ export interface OptimizationResult {
 export interface Commands {
   // システムメトリクス
   get_system_metrics: () => Promise<SystemMetrics>;
+  poll_system_metrics: () => Promise<SystemMetrics>;
   get_process_metrics: () => Promise<ObsProcessMetrics>;
+  get_obs_process_info: () => Promise<ObsProcessMetrics>;
+  get_streaming_overhead: () => Promise<StreamingOverhead | null>;
+  set_obs_priority: (priority: ProcessPriority) => Promise<void>;
+  get_detected_games: () => Promise<string[]>;
+  get_detected_hardware: (params?: { refresh?: boolean }) => Promise<DetectedHardware>;
+  get_network_interfaces: () => Promise<NetworkInterfaceInfo[]>;
+  set_preferred_network_interface: (params: { name: string | null }) => Promise<void>;
+  get_software_conflicts: () => Promise<SoftwareConflict[]>;
   get_legacy_system_metrics: () => Promise<LegacySystemMetrics>;
 
   // OBS接続
   connect_obs: (params: ObsConnectionParams) => Promise<void>;
   disconnect_obs: () => Promise<void>;
   get_obs_status: () => Promise<ObsStatus>;
+  get_live_output_stats: () => Promise<OutputStats>;
+  ping_obs_connection: () => Promise<PingResult>;
   get_saved_connection: () => Promise<SavedConnectionInfo>;
 
   // OBSシーン操作
   get_scene_list: () => Promise<string[]>;
   set_current_scene: (sceneName: string) => Promise<void>;
+  list_scene_items: (sceneName: string) => Promise<SceneItem[]>;
+  get_scene_item_enabled: (sceneName: string, itemId: number) => Promise<boolean>;
+  set_scene_item_enabled: (sceneName: string, itemId: number, enabled: boolean) => Promise<void>;
+
+  // OBSブラウザソース
+  get_browser_source_url: (sourceName: string) => Promise<string>;
+  /** URLは http:// / https:// / file:// のみ。refreshCache=true でキャッシュを無視して再読み込み */
+  update_browser_source_url: (sourceName: string, url: string, refreshCache?: boolean) => Promise<void>;
 
   // OBS配信・録画
   start_streaming: () => Promise<void>;
@@ -425,9 +1114,33 @@ export interface Commands {
   start_recording: () => Promise<void>;
   stop_recording: () => Promise<string>;
 
+  // OBS仮想カメラ
+  start_virtual_cam: () => Promise<void>;
+  stop_virtual_cam: () => Promise<void>;
+  get_virtual_cam_status: () => Promise<boolean>;
+
   // 設定管理
   get_config: () => Promise<AppConfig>;
   save_app_config: (config: AppConfig) => Promise<void>;
+  patch_app_config: (patch: AppConfigPatch) => Promise<AppConfig>;
+  get_config_schema: () => Promise<Record<string, unknown>>;
+  set_language: (params: { language: Language }) => Promise<AppConfig>;
+  get_shortcut_conflicts: () => Promise<ShortcutConflict[]>;
+  set_shortcut_binding: (params: {
+    action: ShortcutAction;
+    accelerator: string | null;
+    force: boolean;
+  }) => Promise<ShortcutBindings>;
+
+  // 配信プラットフォームのAPI連携
+  save_platform_token: (params: { platform: StreamingPlatform; token: string }) => Promise<void>;
+  delete_platform_token: (params: { platform: StreamingPlatform }) => Promise<void>;
+  test_platform_token: (params: { platform: StreamingPlatform }) => Promise<PlatformAccount>;
+  update_stream_info: (params: {
+    platform: StreamingPlatform;
+    title: string;
+    category: string | null;
+  }) => Promise<void>;
 
   // 診断・最適化
   analyze_settings: (request?: AnalyzeSettingsRequest) => Promise<AnalysisResult>;
@@ -438,6 +1151,18 @@ export interface Commands {
 
   // Phase 1b: OBS設定取得
   get_obs_settings_command: () => Promise<ObsSettings>;
+  invalidate_obs_settings_cache: () => Promise<void>;
+  get_recording_output_info: () => Promise<RecordingOutputInfo>;
+  validate_obs_settings: () => Promise<SettingsValidationWarning[]>;
+  set_obs_output_mode: (params: { mode: ObsOutputMode }) => Promise<void>;
+  set_hdr_mode: (params: { enabled: boolean }) => Promise<void>;
+  get_supported_protocols: () => Promise<StreamingProtocol[]>;
+  get_audio_status: () => Promise<AudioStatus>;
+  audit_audio_sources: () => Promise<AudioAuditResult>;
+  set_audio_monitor_type: (params: { sourceName: string; monitorType: AudioMonitorType }) => Promise<void>;
+  set_audio_monitoring_offset: (params: { offsetMs: number }) => Promise<void>;
+  set_all_audio_monitoring: (params: { enabled: boolean }) => Promise<string[]>;
+  restore_audio_monitoring_state: () => Promise<string[]>;
 
   // Phase 1b: 推奨設定算出
   calculate_recommendations: () => Promise<RecommendedSettings>;
@@ -445,35 +1170,101 @@ export interface Commands {
     platform: StreamingPlatform;
     style: StreamingStyle;
     networkSpeedMbps: number;
+    target?: OutputTarget;
   }) => Promise<RecommendedSettings>;
+  calculate_recording_recommendations: (params: {
+    style?: StreamingStyle;
+  }) => Promise<RecommendedSettings>;
+  /** OBS未接続でも使える緊急用の安全な設定（reasonsにOBSでの入力手順を含む） */
+  generate_safe_defaults: (params: { platform: StreamingPlatform }) => Promise<RecommendedSettings>;
+  calculate_tiered_recording_recommendations: () => Promise<RecordingRecommendations>;
+  get_upgrade_advice: (params?: {
+    platform?: StreamingPlatform;
+    style?: StreamingStyle;
+    networkSpeedMbps?: number;
+  }) => Promise<UpgradeAdvice[]>;
+  /** 推奨順のエンコーダー選択肢（先頭が推奨） */
+  get_encoder_options: () => Promise<RecommendedEncoder[]>;
+  get_recommendation_history: (params?: { limit?: number }) => Promise<RecommendationHistoryEntry[]>;
+  diff_recommendation_history: (params: { fromId: string; toId: string }) => Promise<RecommendationDiff>;
+
+  // ベンチマーク
+  run_encoder_benchmark: (params: { durationSecs?: number }) => Promise<BenchmarkResult>;
+  get_last_benchmark_result: () => Promise<BenchmarkResult | null>;
+  benchmark_encoder: (params: {
+    encoderId: string;
+    width: number;
+    height: number;
+    fps: number;
+    bitrateKbps: number;
+    durationSecs: number;
+  }) => Promise<EncoderBenchmarkResult>;
+  cancel_encoder_benchmark: () => Promise<boolean>;
 
   // Phase 1b: アラート管理
   get_active_alerts: () => Promise<Alert[]>;
   clear_all_alerts: () => Promise<void>;
+  manually_suppress_alerts: (params: { durationSecs: number }) => Promise<void>;
+  acknowledge_alert: (params: { id: string }) => Promise<void>;
+  get_alert_history: (params: { from: number; to: number }) => Promise<AlertHistoryEntry[]>;
+  get_session_alert_counts: (params: { sessionId: string }) => Promise<AlertCount[]>;
+
+  // 自動化ルール
+  get_automation_rules: () => Promise<AutomationRule[]>;
+  create_automation_rule: (params: { rule: AutomationRule }) => Promise<AutomationRule>;
+  update_automation_rule: (params: { rule: AutomationRule }) => Promise<AutomationRule>;
+  delete_automation_rule: (params: { id: string }) => Promise<void>;
+  get_automation_log: () => Promise<AutomationExecution[]>;
+  set_media_autoaction: (params: { sourceName: string; action: MediaAutoAction }) => Promise<void>;
 
   // Phase 2a: プロファイル管理
-  get_profiles: () => Promise<ProfileSummary[]>;
+  get_profiles: (params?: { filter?: ProfileFilter }) => Promise<ProfileSummary[]>;
+  get_favorite_profiles: () => Promise<ProfileSummary[]>;
+  update_profile_metadata: (params: {
+    id: string;
+    description: string;
+    tags: string[];
+    isFavorite: boolean;
+  }) => Promise<void>;
   get_profile: (profileId: string) => Promise<SettingsProfile>;
   save_profile: (profile: SettingsProfile) => Promise<void>;
   delete_profile: (profileId: string) => Promise<void>;
-  apply_profile: (profileId: string) => Promise<void>;
+  apply_profile: (profileId: string) => Promise<ProfileApplyResult>;
+  apply_profile_dry_run: (profileId: string) => Promise<ProfileDiff>;
   save_current_settings_as_profile: (params: {
     name: string;
     description: string;
     platform: StreamingPlatform;
     style: StreamingStyle;
+    tags?: string[];
+    isFavorite?: boolean;
   }) => Promise<string>;
 
   // Phase 2a: ワンクリック適用・バックアップ
-  apply_recommended_settings: () => Promise<void>;
+  apply_recommended_settings: (params?: { force?: string }) => Promise<void>;
+  /** 推奨設定を一括適用し、適用結果を返す */
+  apply_recommended_settings_with_result: (params?: { force?: string }) => Promise<OptimizationResult>;
   apply_custom_settings: (params: {
     platform: StreamingPlatform;
     style: StreamingStyle;
     networkSpeedMbps: number;
+    target?: OutputTarget;
+    force?: string;
   }) => Promise<void>;
+  /** カスタム推奨設定を適用し、適用結果を返す */
+  apply_custom_settings_with_result: (params: {
+    platform: StreamingPlatform;
+    style: StreamingStyle;
+    networkSpeedMbps: number;
+    target?: OutputTarget;
+    force?: string;
+  }) => Promise<OptimizationResult>;
   backup_current_settings: () => Promise<string>;
   restore_backup: (backupId: string) => Promise<void>;
+  get_obs_file_backup: (backupId: string) => Promise<ObsFileBackup | null>;
+  restore_obs_files: (backupId: string) => Promise<ObsFileRestoreResult>;
   get_backups: () => Promise<BackupInfo[]>;
+  get_current_operation: () => Promise<CurrentOperation | null>;
 
   // Phase 2a: 配信中モード
   set_streaming_mode: (enabled: boolean) => Promise<void>;
@@ -482,26 +1273,73 @@ export interface Commands {
   // Phase 2b: 問題分析
   analyze_problems: (params: AnalyzeProblemsRequest) => Promise<AnalyzeProblemsResponse>;
   get_problem_history: (limit: number) => Promise<ProblemReport[]>;
+  get_headroom: () => Promise<HeadroomEstimate>;
+  set_checklist_rule_dismissed: (params: { ruleId: string; dismissed: boolean }) => Promise<AppConfig>;
+  analyze_obs_log: (params?: { path?: string }) => Promise<ObsLogAnalysis>;
 
   // Phase 2b: セッション履歴
-  get_sessions: () => Promise<SessionSummary[]>;
+  get_sessions: (params?: { filter?: SessionFilter }) => Promise<SessionSummary[]>;
+  update_session_metadata: (params: {
+    sessionId: string;
+    title?: string | null;
+    tags: string[];
+    notes?: string | null;
+  }) => Promise<SessionSummary>;
   get_metrics_range: (params: {
     sessionId: string;
     from: number;
     to: number;
+    /** 時間解像度（省略時は点数が maxPoints に収まる最も細かい解像度） */
+    resolution?: MetricsResolution;
+    /** 解像度を省略した場合の点数の上限（省略時は1000） */
+    maxPoints?: number;
   }) => Promise<HistoricalMetrics[]>;
+  generate_post_stream_report: (params: { sessionId: string }) => Promise<PostStreamReport>;
+  get_data_usage_summary: (params: { year: number; month: number }) => Promise<DataUsageSummary>;
+  reset_monthly_quota: () => Promise<DataUsageSummary>;
+  get_session_overhead: (params: { sessionId: string }) => Promise<StreamingOverheadSample[]>;
+  get_overhead_deltas: (params: { limit?: number }) => Promise<OverheadDelta[]>;
 
   // Phase 2b: エクスポート
   export_session_json: (request: ExportSessionRequest) => Promise<ExportJsonResponse>;
   export_session_csv: (request: ExportSessionRequest) => Promise<ExportCsvResponse>;
   generate_diagnostic_report: () => Promise<DiagnosticReport>;
+  /** 診断レポートを指定形式（JSON / CSV / Markdown）に変換して返す */
+  export_diagnostic_report: (params: { format: ReportFormat }) => Promise<DiagnosticReportResponse>;
+  get_monthly_streaming_report: (params: { year: number; month: number }) => Promise<MonthlyReport>;
+  generate_weekly_report: (params: { weekOffset: number; format: ReportFormat }) => Promise<WeeklyReportResponse>;
+  /** エクスポート形式のJSON Schemaを書き出し、ファイルパスを返す（開発者向け） */
+  export_schemas: (params: { dir: string }) => Promise<string[]>;
+
+  // 起動時ダイジェスト
+  get_startup_digest: () => Promise<StartupDigest>;
+
+  // デバッグ記録
+  set_debug_recording: (params: {
+    enabled: boolean;
+    maxFileSizeKb?: number;
+    maxFiles?: number;
+  }) => Promise<DebugRecordingStatus>;
+  get_debug_recording_status: () => Promise<DebugRecordingStatus>;
+  export_debug_bundle: () => Promise<string>;
+  get_app_performance_stats: () => Promise<AppPerformanceStats>;
+
+  // 匿名ハードウェア統計
+  preview_telemetry_payload: () => Promise<TelemetryPreview>;
+
+  // オブザーバーモード（読み取り専用HTTPサーバー）
+  start_observer_server: (params: { bindAddress?: string }) => Promise<ObserverServerInfo>;
+  stop_observer_server: () => Promise<boolean>;
+  get_observer_server_status: () => Promise<ObserverServerInfo | null>;
 }
 
 // ========================================
 // Phase 1b追加型定義
 // ========================================
 
-export type StreamingPlatform = 'youTube' | 'twitch' | 'nicoNico' | 'twitCasting' | 'other';
+export type StreamingPlatform = 'youTube' | 'twitch' | 'nicoNico' | 'twitCasting' | 'bilibili' | 'showRoom' | 'other';
+/** 配信プロトコル（SRT/RISTはOBS 29以降） */
+export type StreamingProtocol = 'rtmp' | 'rtmps' | 'srt' | 'rist';
 export type StreamingStyle = 'talk' | 'gaming' | 'music' | 'art' | 'other';
 
 // ========================================
@@ -588,9 +1426,11 @@ export type GpuGeneration =
   | 'nvidiaTuring'
   | 'nvidiaAmpere'
   | 'nvidiaAda'
+  | 'nvidiaBlackwell'
   | 'amdVcn3'
   | 'amdVcn4'
   | 'intelArc'
+  | 'intelBattlemage'
   | 'intelQuickSync'
   | 'unknown'
   | 'none';
@@ -598,6 +1438,56 @@ export type GpuGeneration =
 // CPUティアの分類
 export type CpuTier = 'entry' | 'middle' | 'upperMiddle' | 'highEnd';
 
+// GPUグレードの分類
+export type GpuGrade = 'flagship' | 'highEnd' | 'upperMid' | 'mid' | 'entry' | 'unknown';
+
+/** ハードウェアエンコーダーの対応状況 */
+export interface EncoderCapabilityFlags {
+  h264: boolean;
+  hevc: boolean;
+  /** AV1エンコード対応（GPUの対応のみ） */
+  av1: boolean;
+  /** ドライバーがAV1エンコードに対応しているか */
+  av1DriverSupported: boolean;
+  bFrames: boolean;
+}
+
+/** ディスプレイ情報 */
+export interface DisplayInfo {
+  width: number;
+  height: number;
+  /** リフレッシュレート（Hz、取得できない場合は0） */
+  refreshRateHz: number;
+  /** メインモニターか */
+  isPrimary: boolean;
+}
+
+/** 検出したハードウェア情報と分類結果 */
+export interface DetectedHardware {
+  cpuName: string;
+  cpuCores: number;
+  totalMemoryGb: number;
+  gpu: { name: string; driverVersion: string | null } | null;
+  power: PowerStatus;
+  /** 接続されているディスプレイ（検出できない場合は空） */
+  displays: DisplayInfo[];
+  /** x264エンコードベンチマークの結果（フレーム/秒、未計測の場合はnull） */
+  benchmarkResult: number | null;
+  /** ベンチマークを計測できなかった理由（ffmpegがない場合など。計測できた場合はnull） */
+  benchmarkError: string | null;
+  gpuGeneration: GpuGeneration;
+  gpuGrade: GpuGrade;
+  effectiveTier: EffectiveTier;
+  cpuTier: CpuTier;
+  memoryTier: MemoryTier;
+  /** GPUなし・不明の場合はnull */
+  encoderCapability: EncoderCapabilityFlags | null;
+  /** 表示用サマリー（例: "NVIDIA GeForce RTX 4070（Ada・アッパーミドル → Tier A）"） */
+  summary: string;
+  /** 検出日時（Unixタイムスタンプ） */
+  detectedAt: number;
+}
+
 // GPU世代ごとのエンコーダー能力
 export interface GpuEncoderCapability {
   generation: GpuGeneration;
@@ -620,10 +1510,22 @@ export interface RecommendedEncoder {
   psychoVisualTuning: boolean;
   /** マルチパスモード（NVENC: "disabled", "quarter_res", "full_res"） */
   multipassMode: string;
-  /** チューニング設定（NVENC: "hq", "ll", "ull" / x264: "film", "animation"等） */
+  /** x264のチューニング設定（"zerolatency", "film", "animation"等） */
   tuning: string | null;
-  /** H.264プロファイル（"baseline", "main", "high"） */
+  /** NVENCのチューニング設定（NVENC以外はnull） */
+  nvencTuning: NvencTuning | null;
+  /** プロファイル（H.264: "baseline", "main", "high" / HEVC・AV1: "main", "main10"） */
   profile: string;
+  /** HEVCのプロファイル（HEVCエンコーダー以外はnull） */
+  hevcProfile: HevcProfile | null;
+  /** AMFプリアナリシス有効化（録画時のみ） */
+  preAnalysis: boolean;
+  /** AMFプリアナリシスの先読みフレーム数（プリアナリシス無効時はnull） */
+  paLookAhead: number | null;
+  /** AMFプリアナリシスのシーンチェンジ検出 */
+  paSceneChangeDetect: boolean;
+  /** AOM AV1（ソフトウェアエンコード）使用時の注意（AOM AV1以外はnull） */
+  aomAv1Warning: string | null;
   reason: string;
 }
 
@@ -658,26 +1560,130 @@ export interface AudioSettings {
 export interface OutputSettings {
   encoder: string;
   bitrateKbps: number;
+  /** キーフレーム間隔（秒、0は自動） */
   keyframeIntervalSecs: number;
   preset: string | null;
   rateControl: string | null;
+  /** 録画フォーマット（RecFormat2、例: mkv, mp4, fragmented_mp4） */
+  recordingFormat?: string | null;
+  /** 録画の保存先フォルダ */
+  recordingPath?: string | null;
+  /** Bフレーム数（取得できない場合はnull） */
+  bFrames?: number | null;
+}
+
+/** 録画の保存先・フォーマット・空き容量 */
+export interface RecordingOutputInfo {
+  /** 保存先フォルダ（取得できない場合はnull） */
+  path: string | null;
+  /** 録画フォーマット（取得できない場合はnull） */
+  format: string | null;
+  /** 保存先ボリュームの空き容量（バイト、取得できない場合はnull） */
+  freeSpaceBytes: number | null;
+  /** 録画終了後に自動でMP4に再多重化（リマックス）するか（取得できない場合はnull） */
+  autoRemux: boolean | null;
+  /** クラッシュ時にファイル全体を失うフォーマット（MP4・MOV）か */
+  crashUnsafe: boolean;
 }
 
 export type EncoderType = 'nvencH264' | 'quickSync' | 'amdVce' | 'x264' | 'x265' | 'other';
 
+/** OBSの出力モード（基本/詳細） */
+export type ObsOutputMode = 'simple' | 'advanced';
+
+/** NVENCのチューニング（高画質 / 低遅延 / 超低遅延） */
+export type NvencTuning = 'hq' | 'll' | 'ull';
+
+/** HEVCのプロファイル（8bit / 10bit） */
+export type HevcProfile = 'main' | 'main10';
+
+/** 配信の遅延モード */
+export type StreamLatency = 'normal' | 'low' | 'ultraLow';
+
+/** 配信環境の構成（1台構成 / 2台構成の配信用PC / 2台構成のゲーム用PC） */
+export type SetupType = 'singlePc' | 'dualPcStreaming' | 'dualPcGaming';
+
+/** OBS設定の整合性チェックの警告 */
+export interface SettingsValidationWarning {
+  /** 設定項目（例: "video.outputWidth"） */
+  field: string;
+  /** 現在の値 */
+  value: string;
+  /** 警告メッセージ */
+  message: string;
+  /** 重要度（criticalの場合、推奨設定の適用は中止される） */
+  severity: AlertSeverity;
+}
+
 export interface RecommendedSettings {
   video: RecommendedVideoSettings;
   audio: RecommendedAudioSettings;
   output: RecommendedOutputSettings;
   reasons: string[];
+  /** 全体スコア（0-100、scoreBreakdown.total と同じ値） */
   overallScore: number;
+  /** 全体スコアの項目別の内訳 */
+  scoreBreakdown: ScoreBreakdown;
+}
+
+/**
+ * 現在の設定と推奨設定の一致度の項目別スコア
+ *
+ * 各項目の満点は SCORE_BREAKDOWN_MAX を参照
+ */
+export interface ScoreBreakdown {
+  /** 出力解像度の一致度（0-30） */
+  resolutionScore: number;
+  /** FPSの一致度（0-20） */
+  fpsScore: number;
+  /** ビットレートの適切性（0-30、録画用は品質固定レート制御の使用有無） */
+  bitrateScore: number;
+  /** エンコーダーの適切性（0-20） */
+  encoderScore: number;
+  /** 合計（0-100） */
+  total: number;
+}
+
+/** ScoreBreakdown の各項目の満点（レーダーチャート等での正規化用） */
+export const SCORE_BREAKDOWN_MAX = {
+  resolutionScore: 30,
+  fpsScore: 20,
+  bitrateScore: 30,
+  encoderScore: 20,
+} as const;
+
+/** 縮小フィルタ */
+export type DownscaleFilter = 'bilinear' | 'bicubic' | 'lanczos' | 'area' | 'spline36';
+
+/** 16:9以外のキャンバスの扱い方 */
+export type AspectHandling = 'native' | 'letterbox' | 'crop';
+
+/** アスペクト比の調整の推奨 */
+export interface AspectAdvice {
+  /** 推奨する扱い方（native: 比率のまま出力、letterbox: 黒帯、crop: 中央を切り取り） */
+  handling: AspectHandling;
+  canvasWidth: number;
+  canvasHeight: number;
+  outputWidth: number;
+  outputHeight: number;
+  /** レターボックスの場合に出力内で映像が占める領域 */
+  letterboxWidth: number;
+  letterboxHeight: number;
+  /** クロップの場合にキャンバスから切り取る領域 */
+  cropWidth: number;
+  cropHeight: number;
 }
 
 export interface RecommendedVideoSettings {
   outputWidth: number;
   outputHeight: number;
   fps: number;
-  downscaleFilter: string;
+  downscaleFilter: DownscaleFilter;
+  /** 推奨キャンバス（基本）解像度。メインモニターを検出できない場合はnull */
+  canvasWidth: number | null;
+  canvasHeight: number | null;
+  /** キャンバスが出力と異なるアスペクト比の場合の調整（一致する場合はnull） */
+  aspect: AspectAdvice | null;
 }
 
 export interface RecommendedAudioSettings {
@@ -691,10 +1697,155 @@ export interface RecommendedOutputSettings {
   keyframeIntervalSecs: number;
   preset: string | null;
   rateControl: string;
+  /** 品質値（CQP: CQレベル / CRF: CRF値）。ビットレート制御の場合はnull */
+  qualityValue: number | null;
+  /** 推奨録画フォーマット（録画モード時のみ） */
+  recordingFormat: string | null;
+  /** 推奨配信プロトコル */
+  protocol: StreamingProtocol;
+  /** AMFプリアナリシス有効化（AMFエンコーダーのみ） */
+  preAnalysis: boolean;
+  /** AMFプリアナリシスの先読みフレーム数 */
+  paLookAhead: number | null;
+  /** AMFプリアナリシスのシーンチェンジ検出 */
+  paSceneChangeDetect: boolean;
+  /** 推奨設定の適用に必要なOBSの出力モード */
+  outputMode: ObsOutputMode;
+  /** NVENCのチューニング（NVENC以外はnull） */
+  nvencTuning: NvencTuning | null;
+  /** HEVCのプロファイル（HEVC以外はnull） */
+  hevcProfile: HevcProfile | null;
+}
+
+/** 推奨設定の出力先 */
+export type OutputTarget = 'streaming' | 'recording';
+
+/** レート制御方式 */
+export type RateControl = 'cbr' | 'cqp' | 'vbr';
+
+/** 録画のレート制御方式 */
+export type RateControlMode = 'cbr' | 'cqp' | 'crf';
+
+/** 録画コンテナ */
+export type RecordingContainer = 'mkv' | 'mp4' | 'mov';
+
+/** 録画専用の推奨設定（ハードウェアティア別） */
+export interface RecordingRecommendations {
+  /** 推奨エンコーダーID */
+  encoder: string;
+  /** レート制御方式 */
+  rateControl: RateControlMode;
+  /** ビットレート（kbps）。品質固定の場合はnull */
+  bitrateKbps: number | null;
+  /** CQP値（x264の場合はCRF値）。ビットレート制御の場合はnull */
+  cqpValue: number | null;
+  /** 録画コンテナ */
+  container: RecordingContainer;
+  /** ロスレス録画を使用するか */
+  useLossless: boolean;
+  /** 推奨理由 */
+  reasons: string[];
+}
+
+/** アップグレード対象のコンポーネント */
+export type UpgradeComponent = 'gpu' | 'cpu' | 'memory' | 'network';
+
+/** アップグレードによる改善効果の大きさ */
+export type UpgradeImpact = 'high' | 'medium' | 'low';
+
+/** アップグレード提案 */
+export interface UpgradeAdvice {
+  /** 対象コンポーネント */
+  component: UpgradeComponent;
+  /** 現在の構成 */
+  current: string;
+  /** 現在の構成による制限 */
+  limitation: string;
+  /** アップグレードで期待できる改善 */
+  expectedGain: string;
+  /** 改善効果の大きさ */
+  impact: UpgradeImpact;
+}
+
+/** 推奨設定の計算に使った入力条件 */
+export interface RecommendationInput {
+  /** ハードウェア構成のハッシュ（同じ構成なら同じ値） */
+  hardwareHash: string;
+  platform: StreamingPlatform;
+  style: StreamingStyle;
+  networkSpeedMbps: number;
+  target: OutputTarget;
+}
+
+/** 推奨設定の履歴1件分 */
+export interface RecommendationHistoryEntry {
+  id: string;
+  /** 計算日時（UNIX epoch秒） */
+  computedAt: number;
+  input: RecommendationInput;
+  settings: RecommendedSettings;
+}
+
+/** 推奨設定の履歴2件の差分 */
+export interface RecommendationDiff {
+  fromId: string;
+  toId: string;
+  /** 変化した入力条件（settingNameは "input.networkSpeedMbps" など） */
+  inputChanges: SettingChange[];
+  /** 変化した推奨設定（settingNameは "output.bitrateKbps" など） */
+  settingChanges: SettingChange[];
+  /** 変化の説明（例: "回線速度 10→4Mbps → ビットレート 6000→3500kbps"） */
+  explanations: string[];
+}
+
+/** ベンチマーク判定 */
+export type BenchmarkVerdict = 'pass' | 'marginal' | 'fail';
+
+/** エンコーダーベンチマーク結果 */
+export interface BenchmarkResult {
+  encoder: string;
+  preset: string | null;
+  targetFps: number;
+  durationSecs: number;
+  sampleCount: number;
+  avgCpuUsage: number;
+  peakCpuUsage: number;
+  avgGpuUsage: number | null;
+  avgEncoderUsage: number | null;
+  avgRenderTimeMs: number;
+  /** スキップフレーム率（0.0-1.0） */
+  skippedFrameRatio: number;
+  /** 実測の余力（%） */
+  headroomPercent: number;
+  verdict: BenchmarkVerdict;
+  /** 判定に基づく推奨プリセット（調整不要の場合はnull） */
+  suggestedPreset: string | null;
+  measuredAt: number;
+}
+
+/** エンコーダー単体のベンチマーク結果 */
+export interface EncoderBenchmarkResult {
+  /** 実測の平均FPS */
+  avgFps: number;
+  /** 取りこぼしたフレーム数 */
+  droppedFrames: number;
+  /** 1フレームあたりの平均処理時間（ミリ秒） */
+  avgEncodeTimeMs: number;
+  /** 1フレームあたりの処理時間の最大値（ミリ秒） */
+  maxEncodeTimeMs: number;
+  /** 目標FPSを維持できたか（平均FPSが目標の98%以上） */
+  sustained: boolean;
 }
 
 export type AlertSeverity = 'critical' | 'warning' | 'info' | 'tips';
-export type MetricType = 'cpuUsage' | 'gpuUsage' | 'memoryUsage' | 'frameDropRate' | 'networkBandwidth';
+export type MetricType =
+  | 'cpuUsage'
+  | 'gpuUsage'
+  | 'memoryUsage'
+  | 'frameDropRate'
+  | 'networkBandwidth'
+  | 'microphoneSilence'
+  | 'dataQuota';
 
 export interface Alert {
   id: string;
@@ -707,6 +1858,49 @@ export interface Alert {
   active: boolean;
 }
 
+/** アラート通知のアクション */
+export interface NotificationAction {
+  id: string;
+  label: string;
+  /** クリック時に遷移する画面 */
+  route: string;
+}
+
+/** アラート通知（`alert:notification` イベントのペイロード） */
+export interface AlertNotification {
+  alertId: string;
+  title: string;
+  body: string;
+  playSound: boolean;
+  actions: NotificationAction[];
+}
+
+/** アラート履歴エントリ（発生ごとに1件） */
+export interface AlertHistoryEntry {
+  id: string;
+  alertId: string;
+  sessionId: string | null;
+  metric: MetricType;
+  severity: AlertSeverity;
+  message: string;
+  currentValue: number;
+  threshold: number;
+  raisedAt: number;
+  acknowledged: boolean;
+  acknowledgedAt: number | null;
+  /** 解消時刻（継続中の場合はnull） */
+  clearedAt: number | null;
+}
+
+/** セッション内のアラート集計 */
+export interface AlertCount {
+  metric: MetricType;
+  severity: AlertSeverity;
+  count: number;
+  /** 表示文（例: 「クリティカルなCPUアラート 3件」） */
+  summary: string;
+}
+
 // ========================================
 // Phase 2a追加型定義
 // ========================================
@@ -730,6 +1924,26 @@ export interface ProfileVideoSettings {
 export interface ProfileAudioSettings {
   sampleRate: number;
   bitrateKbps: number;
+  /** デスクトップ音声・マイク入力ごとの設定（省略時は空） */
+  inputs?: ProfileAudioInputSettings[];
+}
+
+/** プロファイル用音声入力設定（デスクトップ音声・マイク） */
+export interface ProfileAudioInputSettings {
+  /** 入力名 */
+  inputName: string;
+  /** 入力種別（例: "wasapi_input_capture"） */
+  inputKind: string;
+  /** デバイスID（入力設定の device_id、未設定の場合はnull） */
+  deviceId: string | null;
+  /** ミュートするか */
+  muted: boolean;
+}
+
+/** プロファイル適用結果 */
+export interface ProfileApplyResult {
+  /** スキップした項目ごとの警告（削除された音声入力・接続されていないデバイス等） */
+  warnings: string[];
 }
 
 /** プロファイル用出力設定 */
@@ -741,6 +1955,27 @@ export interface ProfileOutputSettings {
   rateControl: string;
 }
 
+/** 設定値の変化の種類 */
+export type ChangeType = 'increase' | 'decrease' | 'change' | 'unchanged';
+
+/** 1項目分の設定変更 */
+export interface SettingChange {
+  /** 設定項目名（例: "output.bitrateKbps"） */
+  settingName: string;
+  /** 現在の値 */
+  currentValue: string | number | null;
+  /** 適用後の値 */
+  newValue: string | number | null;
+  /** 変化の種類 */
+  changeType: ChangeType;
+}
+
+/** プロファイル適用時の差分（ドライラン結果） */
+export interface ProfileDiff {
+  /** 項目ごとの変更（変化なしの項目も含む） */
+  changes: SettingChange[];
+}
+
 /** 設定プロファイル */
 export interface SettingsProfile {
   id: string;
@@ -751,6 +1986,16 @@ export interface SettingsProfile {
   settings: ProfileSettings;
   createdAt: number;
   updatedAt: number;
+  /** タグ（省略時は空） */
+  tags?: string[];
+  /** お気に入り（省略時はfalse） */
+  isFavorite?: boolean;
+  /** 最終適用日時（未適用の場合はnull） */
+  lastAppliedAt?: number | null;
+  /** 設定変更前に自動作成されたバックアップか（省略時はfalse） */
+  isBackup?: boolean;
+  /** バックアップの作成契機（バックアップ以外はnull） */
+  backupTrigger?: BackupTrigger | null;
 }
 
 /** プロファイル概要（一覧表示用） */
@@ -762,14 +2007,82 @@ export interface ProfileSummary {
   style: StreamingStyle;
   createdAt: number;
   updatedAt: number;
+  tags: string[];
+  isFavorite: boolean;
+  lastAppliedAt: number | null;
+  isBackup: boolean;
+}
+
+/** プロファイル一覧の絞り込み条件（指定した条件をすべて満たすもの） */
+export interface ProfileFilter {
+  platform?: StreamingPlatform;
+  style?: StreamingStyle;
+  isFavorite?: boolean;
 }
 
 /** バックアップ情報 */
+/** バックアップの作成契機 */
+export type BackupTrigger = 'manual' | 'preOptimization' | 'preProfileApply';
+
 export interface BackupInfo {
   id: string;
   createdAt: number;
   description: string;
   settings: ProfileSettings;
+  /** 作成契機 */
+  trigger: BackupTrigger;
+}
+
+/** バックアップしたOBSのファイルの種類 */
+export type ObsFileKind = 'profile' | 'sceneCollection';
+
+/** バックアップしたOBSのファイル */
+export interface ObsBackupFile {
+  kind: ObsFileKind;
+  fileName: string;
+  /** サイズ（バイト） */
+  sizeBytes: number;
+}
+
+/** OBSのプロファイル・シーンコレクションのファイルのバックアップ */
+export interface ObsFileBackup {
+  /** 対応する設定バックアップのID */
+  backupId: string;
+  /** OBSのプロファイル名 */
+  profileName: string;
+  /** プロファイルのディレクトリ名 */
+  profileDirName: string;
+  /** シーンコレクション名 */
+  sceneCollectionName: string;
+  files: ObsBackupFile[];
+  /** サイズの上限（16MB）を超えたためコピーしなかったファイル名 */
+  skippedFiles: string[];
+  /** 作成日時（UNIX epoch秒） */
+  createdAt: number;
+}
+
+/** OBSのファイルの復元結果 */
+export interface ObsFileRestoreResult {
+  /** 書き戻したファイル名 */
+  restoredFiles: string[];
+  /** OBSの再起動が必要か（OBSの起動中に書き戻した場合） */
+  restartRequired: boolean;
+}
+
+/** 排他制御の対象となる設定操作の種類 */
+export type OperationKind =
+  | 'applyRecommendedSettings'
+  | 'applyCustomSettings'
+  | 'applyOptimization'
+  | 'applyProfile'
+  | 'restoreBackup'
+  | 'backupSettings';
+
+/** 実行中の設定操作 */
+export interface CurrentOperation {
+  kind: OperationKind;
+  /** 開始日時（Unixタイムスタンプ） */
+  startedAt: number;
 }
 
 // ========================================
@@ -802,6 +2115,89 @@ export interface SessionSummary {
   totalDroppedFrames: number;
   peakBitrate: number;
   qualityScore: number;
+  /** タイトル（未設定の場合はnull） */
+  title?: string | null;
+  /** タグ（省略時は空） */
+  tags?: string[];
+  /** メモ（未設定の場合はnull） */
+  notes?: string | null;
+  /** 配信プラットフォーム（未記録の場合はnull） */
+  platform?: StreamingPlatform | null;
+  /** 平均ビットレート（kbps、未記録の場合は0） */
+  avgBitrate?: number;
+  /** セッション中のアップロード量（バイト、未記録の場合は0） */
+  bytesUploaded?: number;
+}
+
+/** セッションごとのデータ使用量 */
+export interface SessionDataUsage {
+  sessionId: string;
+  title: string | null;
+  /** UNIX epoch秒 */
+  startTime: number;
+  /** UNIX epoch秒 */
+  endTime: number;
+  /** アップロード量（GB） */
+  uploadedGb: number;
+  /** 上限の集計対象か（手動リセットより前に始まったセッションはfalse） */
+  countsTowardQuota: boolean;
+}
+
+/** 月間のデータ使用量 */
+export interface DataUsageSummary {
+  /** 月間のアップロード量の合計（GB） */
+  totalGb: number;
+  /** セッションごとの使用量（開始時刻の古い順） */
+  sessions: SessionDataUsage[];
+  /** 上限までの残り（GB、上限が未設定の場合はnull） */
+  quotaRemainingGb: number | null;
+  /** 上限に対する使用率（%、上限が未設定の場合はnull） */
+  quotaPercentUsed: number | null;
+}
+
+/** ある時点の配信品質 */
+export interface QualityEntry {
+  /** UNIX epoch秒 */
+  timestamp: number;
+  /** 品質スコア（0-100） */
+  score: number;
+  /** 前回の計測からのドロップフレーム数 */
+  droppedFrames: number;
+  cpuUsage: number;
+  gpuUsage: number | null;
+}
+
+/** 配信後レポート */
+export interface PostStreamReport {
+  sessionId: string;
+  /** 配信時間（「X時間Y分」形式） */
+  sessionDurationFormatted: string;
+  /** 平均品質スコア（0-100） */
+  averageQualityScore: number;
+  /** 品質が最も低かった時点（メトリクス未記録の場合はnull） */
+  worstMoment: QualityEntry | null;
+  totalProblems: number;
+  /** 重要度の高い問題（最大3件） */
+  topProblems: ProblemReport[];
+  /** 次回の配信に向けた改善提案 */
+  recommendationsForNextStream: string[];
+}
+
+/** 配信後レポート生成完了イベント（post-stream-report:ready）のペイロード */
+export interface PostStreamReportReadyPayload {
+  sessionId: string;
+}
+
+/** セッション一覧の絞り込み条件（指定した条件をすべて満たすもの） */
+export interface SessionFilter {
+  /** タグ（完全一致） */
+  tag?: string;
+  /** 開始時刻の下限（UNIX epoch秒） */
+  from?: number;
+  /** 開始時刻の上限（UNIX epoch秒） */
+  to?: number;
+  /** 最小の長さ（秒） */
+  minDurationSecs?: number;
 }
 
 export interface ObsStatusSnapshot {
@@ -811,6 +2207,8 @@ export interface ObsStatusSnapshot {
   renderDroppedFrames: number | null;
   outputDroppedFrames: number | null;
   streamBitrate: number | null;
+  /** 配信出力の統計（配信中にOBSから取得した場合のみ） */
+  outputStats?: OutputStats;
 }
 
 export interface HistoricalMetrics {
@@ -820,6 +2218,9 @@ export interface HistoricalMetrics {
   obs: ObsStatusSnapshot;
 }
 
+/** メトリクスの時間解像度（生データ / 1分ごと / 1時間ごとの集計） */
+export type MetricsResolution = 'raw' | 'minute' | 'hour';
+
 // ========================================
 // Phase 2b: エクスポート関連の型
 // ========================================
@@ -832,6 +2233,42 @@ export interface AnalyzeProblemsRequest {
 export interface AnalyzeProblemsResponse {
   problems: ProblemReport[];
   overallScore: number;
+  /** 直近のサンプルから推定したヘッドルーム */
+  headroom?: HeadroomEstimate;
+}
+
+/** 1リソース分のヘッドルーム */
+export interface ResourceHeadroom {
+  /** 使用率の上限 */
+  threshold: number;
+  /** 直近の使用率の95パーセンタイル */
+  p95Usage: number;
+  /** 余力（上限 − 95パーセンタイル、負の値は上限超過） */
+  headroom: number;
+}
+
+/** システム全体のヘッドルーム推定（サンプル不足・取得できないリソースは null） */
+export interface HeadroomEstimate {
+  /** 推定に使用したサンプル数 */
+  sampleCount: number;
+  /** CPU使用率（%） */
+  cpu: ResourceHeadroom | null;
+  /** GPU使用率（%） */
+  gpu: ResourceHeadroom | null;
+  /** エンコーダー使用率（%） */
+  encoder: ResourceHeadroom | null;
+  /** アップロード速度（kbps） */
+  uploadKbps: ResourceHeadroom | null;
+}
+
+/** OBSログの解析結果 */
+export interface ObsLogAnalysis {
+  /** 解析したログファイルのパス */
+  logPath: string;
+  /** ログの開始日時（UNIX epoch秒、ファイル名から取得できない場合は null） */
+  logStartedAt: number | null;
+  /** 検出された問題 */
+  problems: ProblemReport[];
 }
 
 export interface ExportSessionRequest {
@@ -853,6 +2290,9 @@ export interface SessionInfo {
   durationSecs: number;
   startedAt: number;
   endedAt: number;
+  title?: string | null;
+  tags?: string[];
+  notes?: string | null;
 }
 
 /** 診断レポート用のシステム情報（OSを含む） */
@@ -861,6 +2301,10 @@ export interface DiagnosticSystemInfo {
   cpuModel: string;
   totalMemoryMb: number;
   gpuModel: string | null;
+  gpuDriverVersion?: string | null;
+  powerSource?: PowerSource;
+  batteryPercent?: number | null;
+  monitors?: DisplayInfo[];
 }
 
 export interface PerformanceEvaluation {
@@ -878,4 +2322,123 @@ export interface DiagnosticReport {
   problems: ProblemReport[];
   performance: PerformanceEvaluation;
   recommendationsSummary: string;
+  /** 推奨設定（OBS未接続の場合はnull） */
+  recommendedSettings?: RecommendedSettings | null;
+  /** 現在のOBS設定（OBS未接続の場合はnull） */
+  obsSettings?: ObsSettings | null;
+}
+
+/** 診断レポートの出力形式 */
+export type ReportFormat = 'json' | 'csv' | 'markdown';
+
+/** 診断レポートのレスポンス */
+export interface DiagnosticReportResponse {
+  report: DiagnosticReport;
+  /** 指定形式に変換したデータ */
+  data: string;
+  filename: string;
+}
+
+/** プラットフォーム別の配信統計 */
+export interface PlatformStats {
+  sessionCount: number;
+  /** 平均品質スコア（0-100） */
+  avgQualityScore: number;
+  /** 平均ビットレート（kbps） */
+  avgBitrateKbps: number;
+  /** 月内の配信時間（時間） */
+  totalHours: number;
+}
+
+/** 月次配信レポート */
+export interface MonthlyReport {
+  year: number;
+  /** 月（1-12） */
+  month: number;
+  /** 月内の総配信時間（時間） */
+  totalHours: number;
+  /** プラットフォーム別の統計（キーはStreamingPlatform、未記録は"unknown"） */
+  perPlatform: Record<string, PlatformStats>;
+  overallAvgScore: number;
+  /** 月内の推定データ使用量（GB） */
+  totalDataGb: number;
+}
+
+/** 前週からの傾向 */
+export type TrendDirection = 'improving' | 'stable' | 'declining' | 'noData';
+
+/** 前週との比較 */
+export interface WeeklyTrend {
+  previousSessionCount: number;
+  previousTotalHours: number;
+  previousAvgHealthScore: number;
+  /** 総配信時間の増減（時間） */
+  hoursChange: number;
+  /** 平均ヘルススコアの増減（比較できない場合は0） */
+  healthScoreChange: number;
+  direction: TrendDirection;
+}
+
+/** 問題カテゴリーごとのアラート件数 */
+export interface CategoryCount {
+  category: ProblemCategory;
+  count: number;
+}
+
+/** 週次配信レポート */
+export interface WeeklyReport {
+  /** 週の開始時刻（月曜0時、UNIX epoch秒） */
+  weekStart: number;
+  /** 週の終了時刻（翌週月曜0時、含まない） */
+  weekEnd: number;
+  sessionCount: number;
+  totalHours: number;
+  /** 平均ヘルススコア（配信がない場合は0） */
+  avgHealthScore: number;
+  /** 品質スコアが最も低いセッション */
+  worstSession: SessionSummary | null;
+  /** アラート件数の多い問題カテゴリー（件数順） */
+  topProblemCategories: CategoryCount[];
+  trend: WeeklyTrend;
+}
+
+/** 週次レポートのレスポンス */
+export interface WeeklyReportResponse {
+  report: WeeklyReport;
+  /** 指定形式に変換したデータ */
+  data: string;
+  filename: string;
+}
+
+// ========================================
+// 起動時ダイジェスト
+// ========================================
+
+/** 環境変化の重要度 */
+export type ChangeSignificance = 'high' | 'medium' | 'low';
+
+/** 環境変化の種類 */
+export type EnvironmentChangeKind =
+  | 'obsVersion'
+  | 'gpuDriver'
+  | 'hardware'
+  | 'settingsDrift'
+  | 'monitorAdded'
+  | 'monitorRemoved';
+
+/** 前回セッションから検出された環境変化 */
+export interface EnvironmentChange {
+  kind: EnvironmentChangeKind;
+  significance: ChangeSignificance;
+  message: string;
+  previous: string | null;
+  current: string | null;
+  /** 設定の再分析を推奨するか */
+  suggestReanalysis: boolean;
+}
+
+export interface StartupDigest {
+  /** 前回スナップショットの取得日時（UNIX秒、初回起動時はnull） */
+  previousCapturedAt: number | null;
+  changes: EnvironmentChange[];
 }
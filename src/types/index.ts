@@ -3,4 +3,6 @@ export * from './commands';
 export interface AppError {
   code: string;
   message: string;
+  /** 追加情報（検証エラーのフィールド名など） */
+  details?: unknown;
 }